@@ -64,9 +64,13 @@ AGPL-3.0 - Copyright (C) 2024-2025 Chernov Denys
 
 from ._core import (
     Token,
+    Grid,
+    StateArray,
     IntuitionEngine,
     IntuitionConfig,
     SignalSystem,
+    ExperienceStream,
+    Learner,
     __version__,
     __author__,
     __license__,
@@ -74,9 +78,13 @@ from ._core import (
 
 __all__ = [
     "Token",
+    "Grid",
+    "StateArray",
     "IntuitionEngine",
     "IntuitionConfig",
     "SignalSystem",
+    "ExperienceStream",
+    "Learner",
     "__version__",
     "__author__",
     "__license__",
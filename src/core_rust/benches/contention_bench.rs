@@ -0,0 +1,219 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Lock Contention Benchmarks v1.0
+//!
+//! Reader throughput while one writer mutates continuously:
+//! - contention_grid: KNN queries, `RwLock<Grid>` vs `ShardedGrid`
+//! - contention_graph: spreading activation, `RwLock<Graph>` vs `ShardedGraph`
+//!
+//! Each iteration runs 1/2/4/8 reader threads for `QUERIES_PER_READER`
+//! queries each; the reported time is the wall time of the slowest reader.
+//! The writer re-inserts existing tokens and toggles edges, so the data
+//! size stays constant however long the benchmark runs.
+
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Barrier;
+use std::time::{Duration, Instant};
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use parking_lot::RwLock;
+
+use _core::{CoordinateSpace, Graph, Grid, ShardedGraph, ShardedGrid, Token};
+
+const TOKENS: u32 = 10_000;
+const QUERIES_PER_READER: u32 = 200;
+const READERS: [usize; 4] = [1, 2, 4, 8];
+
+fn token(id: u32) -> Token {
+    let mut token = Token::new(id);
+    token.set_coordinates(
+        CoordinateSpace::L8Abstract,
+        (id as f32 * 0.001) % 10.0,
+        (id as f32 * 0.002) % 10.0,
+        (id as f32 * 0.003) % 10.0,
+    );
+    token
+}
+
+/// Run `readers` threads of `query` next to a writer looping on `write`
+///
+/// `write` receives a counter in `0..TOKENS`.
+///
+/// Returns the elapsed time of `iters` rounds, measured per round from the
+/// barrier to the last reader finishing.
+fn run_contended<Q, W>(iters: u64, readers: usize, query: Q, write: W) -> Duration
+where
+    Q: Fn(u32) + Sync,
+    W: Fn(u32) + Sync,
+{
+    let mut total = Duration::ZERO;
+    let counter = AtomicU32::new(0);
+
+    for _ in 0..iters {
+        let done = AtomicBool::new(false);
+        let barrier = Barrier::new(readers + 1);
+
+        total += std::thread::scope(|scope| {
+            scope.spawn(|| {
+                while !done.load(Ordering::Relaxed) {
+                    write(counter.fetch_add(1, Ordering::Relaxed) % TOKENS);
+                }
+            });
+
+            let handles: Vec<_> = (0..readers)
+                .map(|reader| {
+                    let (barrier, query) = (&barrier, &query);
+                    scope.spawn(move || {
+                        barrier.wait();
+                        for i in 0..QUERIES_PER_READER {
+                            query((i * 97 + reader as u32 * 13) % TOKENS);
+                        }
+                    })
+                })
+                .collect();
+
+            barrier.wait();
+            let start = Instant::now();
+            for handle in handles {
+                handle.join().unwrap();
+            }
+            let elapsed = start.elapsed();
+            done.store(true, Ordering::Relaxed);
+            elapsed
+        });
+    }
+
+    total
+}
+
+fn bench_grid(c: &mut Criterion) {
+    let mut group = c.benchmark_group("contention_grid");
+    group.sample_size(10);
+
+    let locked = RwLock::new(Grid::new());
+    let sharded = ShardedGrid::new();
+    for id in 0..TOKENS {
+        locked.write().add(token(id)).ok();
+        sharded.add(token(id)).ok();
+    }
+
+    for readers in READERS {
+        group.bench_with_input(BenchmarkId::new("rwlock", readers), &readers, |b, &readers| {
+            b.iter_custom(|iters| {
+                run_contended(
+                    iters,
+                    readers,
+                    |id| {
+                        black_box(locked.read().find_neighbors(id, CoordinateSpace::L8Abstract, 1.0, 10));
+                    },
+                    |id| {
+                        let mut grid = locked.write();
+                        grid.remove(id);
+                        grid.add(token(id)).ok();
+                    },
+                )
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("sharded", readers), &readers, |b, &readers| {
+            b.iter_custom(|iters| {
+                run_contended(
+                    iters,
+                    readers,
+                    |id| {
+                        black_box(sharded.find_neighbors(id, CoordinateSpace::L8Abstract, 1.0, 10));
+                    },
+                    |id| {
+                        sharded.remove(id);
+                        sharded.add(token(id)).ok();
+                    },
+                )
+            })
+        });
+    }
+
+    group.finish();
+}
+
+/// Extra edge toggled by the graph writer
+fn churn_edge(from: u32) -> (u32, u64) {
+    let to = (from + 7) % TOKENS;
+    (to, Graph::compute_edge_id(from, to, 0))
+}
+
+fn bench_graph(c: &mut Criterion) {
+    let mut group = c.benchmark_group("contention_graph");
+    group.sample_size(10);
+
+    let mut graph = Graph::new();
+    for i in 0..TOKENS {
+        graph.add_node(i);
+    }
+    for i in 0..TOKENS {
+        for offset in 1..=5 {
+            let to = (i + offset) % TOKENS;
+            graph.add_edge(Graph::compute_edge_id(i, to, 0), i, to, 0, 0.5, false).ok();
+        }
+    }
+    let sharded = ShardedGraph::from_graph(&graph);
+    // Graph::spreading_activation writes activation state, so readers need the write lock
+    let locked = RwLock::new(graph);
+
+    for readers in READERS {
+        group.bench_with_input(BenchmarkId::new("rwlock", readers), &readers, |b, &readers| {
+            b.iter_custom(|iters| {
+                run_contended(
+                    iters,
+                    readers,
+                    |id| {
+                        black_box(locked.write().spreading_activation(id, 1.0, None));
+                    },
+                    |id| {
+                        let (to, edge_id) = churn_edge(id);
+                        let mut graph = locked.write();
+                        if !graph.remove_edge(edge_id) {
+                            graph.add_edge(edge_id, id, to, 0, 0.5, false).ok();
+                        }
+                    },
+                )
+            })
+        });
+
+        group.bench_with_input(BenchmarkId::new("sharded", readers), &readers, |b, &readers| {
+            b.iter_custom(|iters| {
+                run_contended(
+                    iters,
+                    readers,
+                    |id| {
+                        black_box(sharded.spreading_activation(id, 1.0, None));
+                    },
+                    |id| {
+                        let (to, edge_id) = churn_edge(id);
+                        if !sharded.remove_edge(edge_id) {
+                            sharded.add_edge(edge_id, id, to, 0, 0.5, false).ok();
+                        }
+                    },
+                )
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_grid, bench_graph);
+criterion_main!(benches);
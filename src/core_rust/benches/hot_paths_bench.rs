@@ -0,0 +1,329 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Hot Path Benchmarks v1.0
+//!
+//! Regression suite for the per-signal hot paths:
+//! - hot_grid_knn: Grid neighbor search at 1k/10k/50k tokens
+//! - hot_spreading_activation: 1k/10k/50k nodes (avg degree 5)
+//! - hot_connection_v3: 64-byte to_bytes / from_bytes
+//! - hot_normalizer: normalize_text throughput (bytes/s)
+//! - hot_appraise_event: all 4 appraisers on one event
+//!
+//! After a measured run the mean and median of every benchmark are written
+//! to `target/criterion/hot_paths.json` (or `$NEUROGRAPH_BENCH_OUT`). To
+//! catch regressions, keep a previous file and pass it back:
+//!
+//! ```text
+//! cargo bench --bench hot_paths_bench
+//! cp target/criterion/hot_paths.json baseline.json
+//! # ... change code ...
+//! NEUROGRAPH_BENCH_BASELINE=baseline.json cargo bench --bench hot_paths_bench
+//! ```
+//!
+//! Benchmarks whose mean grew by more than `$NEUROGRAPH_BENCH_THRESHOLD`
+//! percent (default 10) are listed and the run exits with status 1.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use criterion::{black_box, criterion_group, BenchmarkId, Criterion, Throughput};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+use _core::gateway::normalizer::Normalizer;
+use _core::{
+    appraise_event, AppraiserConfig, BootstrapConfig, BootstrapLibrary, ConnectionV3,
+    CoordinateSpace, ExperienceEvent, GatewayConfig, Graph, Grid, Token,
+};
+
+/// Benchmark groups covered by the baseline file
+const GROUPS: &[&str] = &[
+    "hot_grid_knn",
+    "hot_spreading_activation",
+    "hot_connection_v3",
+    "hot_normalizer",
+    "hot_appraise_event",
+];
+
+const SIZES: [u32; 3] = [1_000, 10_000, 50_000];
+
+/// Grid KNN (k = 10) around the middle token
+fn bench_grid_knn(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hot_grid_knn");
+
+    for size in SIZES {
+        let mut grid = Grid::new();
+        for i in 0..size {
+            let mut token = Token::new(i);
+            token.set_coordinates(
+                CoordinateSpace::L8Abstract,
+                (i as f32 * 0.001) % 10.0,
+                (i as f32 * 0.002) % 10.0,
+                (i as f32 * 0.003) % 10.0,
+            );
+            grid.add(token).ok();
+        }
+
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
+            b.iter(|| grid.find_neighbors(black_box(size / 2), CoordinateSpace::L8Abstract, black_box(1.0), black_box(10)))
+        });
+    }
+
+    group.finish();
+}
+
+/// Spreading activation from node 0 with the default SignalConfig
+fn bench_spreading_activation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hot_spreading_activation");
+    group.sample_size(20);
+
+    for size in SIZES {
+        let mut graph = Graph::new();
+        for i in 0..size {
+            graph.add_node(i);
+        }
+        for i in 0..size {
+            for offset in 1..=5 {
+                let to = (i + offset) % size;
+                let edge_id = Graph::compute_edge_id(i, to, 0);
+                graph.add_edge(edge_id, i, to, 0, 0.5 + offset as f32 * 0.1, false).ok();
+            }
+        }
+
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, _| {
+            b.iter(|| graph.spreading_activation(black_box(0), black_box(1.0), None))
+        });
+    }
+
+    group.finish();
+}
+
+/// ConnectionV3 64-byte wire format
+fn bench_connection_v3(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hot_connection_v3");
+    group.throughput(Throughput::Bytes(64));
+
+    let connection = ConnectionV3::new(1, 2);
+    let bytes = connection.to_bytes();
+
+    group.bench_function("to_bytes", |b| b.iter(|| black_box(&connection).to_bytes()));
+    group.bench_function("from_bytes", |b| b.iter(|| ConnectionV3::from_bytes(black_box(&bytes))));
+
+    group.finish();
+}
+
+/// Text → state vector through the Gateway normalizer
+fn bench_normalizer(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hot_normalizer");
+
+    let words: Vec<String> = (0..1_000).map(|i| format!("word{}", i)).collect();
+    let concepts: Vec<_> = words
+        .iter()
+        .enumerate()
+        .map(|(i, word)| {
+            serde_json::json!({
+                "word": word,
+                "id": i + 1,
+                "coords": [(i % 17) as f32 * 0.05, (i % 13) as f32 * 0.07, (i % 7) as f32 * 0.1],
+            })
+        })
+        .collect();
+    let mut library = BootstrapLibrary::new(BootstrapConfig::default());
+    library
+        .load_bootstrap_map_str(&serde_json::Value::Array(concepts).to_string())
+        .expect("bootstrap map");
+    let normalizer = Normalizer::new(Arc::new(RwLock::new(library)), GatewayConfig::default());
+
+    for len in [4usize, 16, 64] {
+        // Every fourth word is unknown to exercise the fallback path
+        let text = (0..len)
+            .map(|i| if i % 4 == 3 { format!("unknown{}", i) } else { words[i * 37 % words.len()].clone() })
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        group.throughput(Throughput::Bytes(text.len() as u64));
+        group.bench_with_input(BenchmarkId::new("words", len), &text, |b, text| {
+            b.iter(|| normalizer.normalize_text(black_box(text)))
+        });
+    }
+
+    group.finish();
+}
+
+/// Homeostasis + curiosity + efficiency + goal rewards for one event
+fn bench_appraise_event(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hot_appraise_event");
+    let config = AppraiserConfig::default();
+
+    let mut in_range = ExperienceEvent::default();
+    in_range.state = [0.0, 0.2, 0.0, 0.0, 0.5, 0.6, 0.3, 0.8];
+    let mut out_of_range = ExperienceEvent::default();
+    out_of_range.state = [0.0, 0.9, 0.7, 0.4, 0.95, 0.1, 0.9, 0.2];
+
+    group.bench_function("in_range", |b| b.iter(|| appraise_event(black_box(&in_range), &config)));
+    group.bench_function("out_of_range", |b| b.iter(|| appraise_event(black_box(&out_of_range), &config)));
+
+    group.finish();
+}
+
+criterion_group!(
+    hot_paths,
+    bench_grid_knn,
+    bench_spreading_activation,
+    bench_connection_v3,
+    bench_normalizer,
+    bench_appraise_event,
+);
+
+// ========================================
+// Baseline JSON
+// ========================================
+
+/// Timing of one benchmark in the baseline file (nanoseconds)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BaselineEntry {
+    mean_ns: f64,
+    median_ns: f64,
+}
+
+#[derive(Deserialize)]
+struct BenchmarkInfo {
+    full_id: String,
+}
+
+#[derive(Deserialize)]
+struct Estimate {
+    point_estimate: f64,
+}
+
+#[derive(Deserialize)]
+struct Estimates {
+    mean: Estimate,
+    median: Estimate,
+}
+
+fn criterion_dir() -> PathBuf {
+    let target = std::env::var("CARGO_TARGET_DIR").unwrap_or_else(|_| "target".to_string());
+    Path::new(&target).join("criterion")
+}
+
+/// Latest estimates of every benchmark in `GROUPS`, keyed by full id
+fn collect_results(dir: &Path) -> BTreeMap<String, BaselineEntry> {
+    fn walk(dir: &Path, results: &mut BTreeMap<String, BaselineEntry>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+            if path.file_name().is_some_and(|name| name == "new") {
+                let read = |file: &str| std::fs::read_to_string(path.join(file)).ok();
+                let info = read("benchmark.json").and_then(|s| serde_json::from_str::<BenchmarkInfo>(&s).ok());
+                let estimates = read("estimates.json").and_then(|s| serde_json::from_str::<Estimates>(&s).ok());
+                if let (Some(info), Some(estimates)) = (info, estimates) {
+                    results.insert(
+                        info.full_id,
+                        BaselineEntry {
+                            mean_ns: estimates.mean.point_estimate,
+                            median_ns: estimates.median.point_estimate,
+                        },
+                    );
+                }
+            } else {
+                walk(&path, results);
+            }
+        }
+    }
+
+    let mut results = BTreeMap::new();
+    for group in GROUPS {
+        walk(&dir.join(group), &mut results);
+    }
+    results
+}
+
+/// Benchmarks slower than the baseline by more than `threshold` percent
+fn regressions(
+    baseline: &BTreeMap<String, BaselineEntry>,
+    current: &BTreeMap<String, BaselineEntry>,
+    threshold: f64,
+) -> Vec<(String, f64)> {
+    current
+        .iter()
+        .filter_map(|(id, entry)| {
+            let base = baseline.get(id)?;
+            let change = (entry.mean_ns / base.mean_ns - 1.0) * 100.0;
+            (change > threshold).then(|| (id.clone(), change))
+        })
+        .collect()
+}
+
+fn write_baseline() -> Result<bool, Box<dyn std::error::Error>> {
+    let results = collect_results(&criterion_dir());
+    if results.is_empty() {
+        return Ok(true);
+    }
+
+    let out = std::env::var("NEUROGRAPH_BENCH_OUT")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| criterion_dir().join("hot_paths.json"));
+    std::fs::write(&out, serde_json::to_string_pretty(&results)?)?;
+    println!("Baseline written to {}", out.display());
+
+    let Ok(path) = std::env::var("NEUROGRAPH_BENCH_BASELINE") else {
+        return Ok(true);
+    };
+    let baseline: BTreeMap<String, BaselineEntry> = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+    let threshold = std::env::var("NEUROGRAPH_BENCH_THRESHOLD")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(10.0);
+
+    let slower = regressions(&baseline, &results, threshold);
+    if slower.is_empty() {
+        println!("No regressions over {:.0}% against {}", threshold, path);
+        return Ok(true);
+    }
+    println!("Regressions over {:.0}% against {}:", threshold, path);
+    for (id, change) in &slower {
+        println!("  {:<48} +{:.1}%", id, change);
+    }
+    Ok(false)
+}
+
+fn main() {
+    hot_paths();
+    Criterion::default().configure_from_args().final_summary();
+
+    // `cargo test --benches` and `--list` only smoke-run the benchmarks
+    let args: Vec<String> = std::env::args().collect();
+    if args.iter().any(|a| a == "--test" || a == "--list") {
+        return;
+    }
+
+    match write_baseline() {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(1),
+        Err(e) => {
+            eprintln!("Failed to write benchmark baseline: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
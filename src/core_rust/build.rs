@@ -0,0 +1,31 @@
+// Build script
+//
+// With `--features c-api`, regenerates include/neurograph_ffi.h from src/capi.rs.
+
+fn main() {
+    #[cfg(feature = "c-api")]
+    generate_c_header();
+}
+
+#[cfg(feature = "c-api")]
+fn generate_c_header() {
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
+    println!("cargo:rerun-if-changed=src/capi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir))
+        .expect("Failed to read cbindgen.toml");
+
+    // Only the C ABI module is parsed: avoids `cargo metadata` over the whole
+    // dependency tree, and a header failure must not break the library build
+    match cbindgen::Builder::new()
+        .with_src(format!("{}/src/capi.rs", crate_dir))
+        .with_config(config)
+        .generate()
+    {
+        Ok(bindings) => {
+            bindings.write_to_file(format!("{}/include/neurograph_ffi.h", crate_dir));
+        }
+        Err(e) => println!("cargo:warning=cbindgen failed, header not updated: {}", e),
+    }
+}
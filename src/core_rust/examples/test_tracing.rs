@@ -5,7 +5,7 @@
 //
 // Usage:
 //   1. Start Jaeger: docker run -d -p16686:16686 -p14268:14268 jaegertracing/all-in-one:1.51
-//   2. Run: cargo run --example test_tracing
+//   2. Run: cargo run --example test_tracing --features otel
 //   3. Open Jaeger UI: http://localhost:16686
 
 use neurograph_core::tracing_otel;
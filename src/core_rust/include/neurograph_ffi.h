@@ -0,0 +1,310 @@
+/* NeuroGraph C ABI - generated by cbindgen from src/capi.rs, do not edit */
+
+#ifndef NEUROGRAPH_FFI_H
+#define NEUROGRAPH_FFI_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * C ABI version; bumped on any breaking change to the exported surface
+ */
+#define NG_ABI_VERSION 1
+
+/**
+ * Result code of fallible calls
+ */
+typedef enum NgStatus {
+  NG_STATUS_OK = 0,
+  /**
+   * A required pointer argument was NULL
+   */
+  NG_STATUS_NULL_POINTER = 1,
+  /**
+   * Argument out of range or not valid UTF-8/JSON
+   */
+  NG_STATUS_INVALID_ARGUMENT = 2,
+  /**
+   * Requested item does not exist (or queue is empty)
+   */
+  NG_STATUS_NOT_FOUND = 3,
+  /**
+   * Result not available yet
+   */
+  NG_STATUS_PENDING = 4,
+  /**
+   * Operation failed; see `ng_last_error()`
+   */
+  NG_STATUS_ERROR = 5,
+  /**
+   * A panic was caught at the boundary
+   */
+  NG_STATUS_PANIC = 6,
+} NgStatus;
+
+/**
+ * Opaque Gateway handle
+ *
+ * Owns its own single-threaded runtime and the processed-signal queue;
+ * the embedder drains that queue (acting as the ActionController) and
+ * reports results back with `ng_gateway_complete`.
+ */
+typedef struct NgGateway NgGateway;
+
+/**
+ * Opaque graph handle
+ */
+typedef struct NgGraph NgGraph;
+
+/**
+ * Opaque token handle
+ */
+typedef struct NgToken NgToken;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * ABI version of the loaded library (compare against `NG_ABI_VERSION`)
+ */
+uint32_t ng_abi_version(void);
+
+/**
+ * Last error message on the calling thread, or NULL
+ *
+ * The pointer stays valid until the next failing call on the same thread.
+ */
+const char *ng_last_error(void);
+
+/**
+ * Release a string returned by the library
+ *
+ * # Safety
+ * `s` must be NULL or a string returned by this library, freed at most once.
+ */
+void ng_string_free(char *s);
+
+/**
+ * Create a token with zeroed coordinates
+ */
+struct NgToken *ng_token_new(uint32_t id);
+
+/**
+ * Create a token from an 8D state (one value per coordinate space)
+ *
+ * # Safety
+ * `state` must point to 8 readable floats.
+ */
+struct NgToken *ng_token_from_state(uint32_t id, const float *state);
+
+/**
+ * Release a token
+ *
+ * # Safety
+ * `token` must be NULL or a handle from `ng_token_*`, freed at most once.
+ */
+void ng_token_free(struct NgToken *token);
+
+/**
+ * Token ID (0 for NULL)
+ *
+ * # Safety
+ * `token` must be NULL or a live token handle.
+ */
+uint32_t ng_token_id(const struct NgToken *token);
+
+/**
+ * Copy the 8D state into `out`
+ *
+ * # Safety
+ * `token` must be a live handle; `out` must point to 8 writable floats.
+ */
+enum NgStatus ng_token_state(const struct NgToken *token, float *out);
+
+/**
+ * Set `[x, y, z]` in one coordinate space (0-7)
+ *
+ * # Safety
+ * `token` must be a live handle.
+ */
+enum NgStatus ng_token_set_coordinates(struct NgToken *token,
+                                       uint8_t space,
+                                       float x,
+                                       float y,
+                                       float z);
+
+/**
+ * Copy `[x, y, z]` of one coordinate space (0-7) into `out`
+ *
+ * # Safety
+ * `token` must be a live handle; `out` must point to 3 writable floats.
+ */
+enum NgStatus ng_token_get_coordinates(const struct NgToken *token, uint8_t space, float *out);
+
+/**
+ * Serialize the token to its 64-byte binary form
+ *
+ * # Safety
+ * `token` must be a live handle; `out` must point to 64 writable bytes.
+ */
+enum NgStatus ng_token_to_bytes(const struct NgToken *token, uint8_t *out);
+
+/**
+ * Create an empty graph
+ */
+struct NgGraph *ng_graph_new(void);
+
+/**
+ * Release a graph
+ *
+ * # Safety
+ * `graph` must be NULL or a handle from `ng_graph_new`, freed at most once.
+ */
+void ng_graph_free(struct NgGraph *graph);
+
+/**
+ * Add a node; returns `NG_STATUS_OK` also when it already exists
+ *
+ * # Safety
+ * `graph` must be a live handle.
+ */
+enum NgStatus ng_graph_add_node(struct NgGraph *graph, uint32_t node_id);
+
+/**
+ * Add an edge between existing nodes, writing its ID to `out_edge_id` (may be NULL)
+ *
+ * # Safety
+ * `graph` must be a live handle; `out_edge_id` must be NULL or writable.
+ */
+enum NgStatus ng_graph_add_edge(struct NgGraph *graph,
+                                uint32_t from_id,
+                                uint32_t to_id,
+                                uint8_t edge_type,
+                                float weight,
+                                bool bidirectional,
+                                uint64_t *out_edge_id);
+
+/**
+ * Number of nodes (0 for NULL)
+ *
+ * # Safety
+ * `graph` must be NULL or a live handle.
+ */
+size_t ng_graph_node_count(const struct NgGraph *graph);
+
+/**
+ * Number of edges (0 for NULL)
+ *
+ * # Safety
+ * `graph` must be NULL or a live handle.
+ */
+size_t ng_graph_edge_count(const struct NgGraph *graph);
+
+/**
+ * Copy up to `capacity` neighbor IDs (both directions) into `out`
+ *
+ * Writes the total neighbor count to `out_total`, so callers can retry
+ * with a larger buffer when `*out_total > capacity`.
+ *
+ * # Safety
+ * `graph` must be a live handle; `out` must hold `capacity` u32 values
+ * (may be NULL when `capacity == 0`); `out_total` must be writable.
+ */
+enum NgStatus ng_graph_neighbors(const struct NgGraph *graph,
+                                 uint32_t node_id,
+                                 uint32_t *out,
+                                 size_t capacity,
+                                 size_t *out_total);
+
+/**
+ * Create a Gateway, optionally seeded with a bootstrap map (JSON text, may be NULL)
+ *
+ * Returns NULL on failure; see `ng_last_error()`.
+ *
+ * # Safety
+ * `map_json` must be NULL or a valid NUL-terminated string.
+ */
+struct NgGateway *ng_gateway_new(const char *map_json);
+
+/**
+ * Release a Gateway
+ *
+ * # Safety
+ * `gateway` must be NULL or a handle from `ng_gateway_new`, freed at most once.
+ */
+void ng_gateway_free(struct NgGateway *gateway);
+
+/**
+ * Inject a text signal, writing its signal ID to `out_signal_id` (may be NULL)
+ *
+ * # Safety
+ * `gateway` must be a live handle; `text` a valid NUL-terminated string.
+ */
+enum NgStatus ng_gateway_inject_text(struct NgGateway *gateway,
+                                     const char *text,
+                                     uint64_t *out_signal_id);
+
+/**
+ * Inject a direct 8D state signal
+ *
+ * # Safety
+ * `gateway` must be a live handle; `state` must point to 8 readable floats.
+ */
+enum NgStatus ng_gateway_inject_state(struct NgGateway *gateway,
+                                      const float *state,
+                                      uint64_t *out_signal_id);
+
+/**
+ * Pop the next processed signal as JSON
+ *
+ * Returns `NG_STATUS_NOT_FOUND` when the queue is empty.
+ *
+ * # Safety
+ * `gateway` must be a live handle; `out_json` must be writable.
+ */
+enum NgStatus ng_gateway_next_signal(struct NgGateway *gateway, char **out_json);
+
+/**
+ * Report the result of a processed signal
+ *
+ * `output_json` (may be NULL) becomes the result payload; when `success`
+ * is false it is used as the error message instead.
+ *
+ * # Safety
+ * `gateway` must be a live handle; `output_json` NULL or a valid string.
+ */
+enum NgStatus ng_gateway_complete(struct NgGateway *gateway,
+                                  uint64_t signal_id,
+                                  bool success,
+                                  const char *output_json);
+
+/**
+ * Fetch the result of an injected signal as JSON
+ *
+ * Returns `NG_STATUS_PENDING` until it is completed and `NG_STATUS_NOT_FOUND`
+ * for unknown or already fetched IDs.
+ *
+ * # Safety
+ * `gateway` must be a live handle; `out_json` must be writable.
+ */
+enum NgStatus ng_gateway_poll_result(struct NgGateway *gateway,
+                                     uint64_t signal_id,
+                                     char **out_json);
+
+/**
+ * Gateway statistics as JSON
+ *
+ * # Safety
+ * `gateway` must be a live handle; `out_json` must be writable.
+ */
+enum NgStatus ng_gateway_stats(const struct NgGateway *gateway, char **out_json);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* NEUROGRAPH_FFI_H */
@@ -13,6 +13,8 @@ BENCHMARKS=(
     "graph_bench"
     "experience_stream_bench"
     "intuition_bench"
+    "hot_paths_bench"
+    "contention_bench"
 )
 
 for bench in "${BENCHMARKS[@]}"; do
@@ -28,5 +30,8 @@ done
 echo "✅ All benchmarks completed!"
 echo "Results saved in target/criterion/"
 echo ""
+echo "Hot path baseline: target/criterion/hot_paths.json"
+echo "  compare later runs with NEUROGRAPH_BENCH_BASELINE=<saved copy>"
+echo ""
 echo "To view HTML reports:"
 echo "  open target/criterion/report/index.html"
\ No newline at end of file
@@ -23,16 +23,19 @@
 //! The arbitrator intelligently chooses between fast reflexive responses and
 //! slower analytical reasoning based on confidence thresholds and Guardian validation.
 
-use crate::action_executor::{ActionExecutor, ActionResult, ActionError};
+use crate::action_executor::{ActionExecutor, ActionResult, ActionError, CancellationToken};
+use crate::action_types::{ActionIntent, CandidatePathway, DecisionCandidate, DecisionTrace};
 use crate::adna::{ADNAReader, Intent, ActionPolicy};
 use crate::experience_stream::{ExperienceWriter, ExperienceEvent};
 use crate::module_id::ModuleId;
 use crate::module_registry::REGISTRY;
+use crate::profiling::{PipelineStage, PROFILER};
 use parking_lot::RwLock;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
-use tracing::error;
+use tracing::{error, Instrument};
 
 /// Configuration for ActionController
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -45,6 +48,22 @@ pub struct ActionControllerConfig {
 
     /// Timeout for action execution in milliseconds
     pub timeout_ms: u64,
+
+    /// Per-executor timeout overrides in milliseconds (executor_id → timeout)
+    #[serde(default)]
+    pub executor_timeouts_ms: HashMap<String, u64>,
+
+    /// Executor for low-confidence signals, e.g. "llm_executor" (None = disabled)
+    #[serde(default)]
+    pub fallback_executor: Option<String>,
+
+    /// Gateway interpretation confidence below which `fallback_executor` is used
+    #[serde(default = "default_fallback_confidence_threshold")]
+    pub fallback_confidence_threshold: f32,
+}
+
+fn default_fallback_confidence_threshold() -> f32 {
+    0.3
 }
 
 impl Default for ActionControllerConfig {
@@ -53,6 +72,9 @@ impl Default for ActionControllerConfig {
             exploration_rate: 0.1,  // 10% exploration
             log_all_actions: true,
             timeout_ms: 30000,      // 30 seconds
+            executor_timeouts_ms: HashMap::new(),
+            fallback_executor: None,
+            fallback_confidence_threshold: default_fallback_confidence_threshold(),
         }
     }
 }
@@ -65,6 +87,15 @@ impl ActionControllerConfig {
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
     }
 
+    /// Timeout for a specific executor (falls back to `timeout_ms`)
+    pub fn timeout_for(&self, executor_id: &str) -> std::time::Duration {
+        let ms = self.executor_timeouts_ms
+            .get(executor_id)
+            .copied()
+            .unwrap_or(self.timeout_ms);
+        std::time::Duration::from_millis(ms)
+    }
+
     /// Load configuration from JSON file, or use default if file doesn't exist
     pub fn from_file_or_default(path: &str) -> Self {
         Self::from_file(path).unwrap_or_else(|_| {
@@ -96,6 +127,14 @@ pub struct ArbiterConfig {
 
     /// Shadow mode: run ADNA in parallel for comparison (training)
     pub shadow_mode: bool,
+
+    /// Number of recent decision traces kept for explainability (0 = disabled)
+    #[serde(default = "default_trace_capacity")]
+    pub trace_capacity: usize,
+}
+
+fn default_trace_capacity() -> usize {
+    256
 }
 
 impl Default for ArbiterConfig {
@@ -106,7 +145,21 @@ impl Default for ArbiterConfig {
             max_action_depth: 3,
             enable_metrics: true,
             shadow_mode: false,
+            trace_capacity: default_trace_capacity(),
+        }
+    }
+}
+
+impl ArbiterConfig {
+    /// Validate configuration
+    pub fn validate(&self) -> Result<(), String> {
+        if self.adna_timeout_ms == 0 {
+            return Err("adna_timeout_ms must be > 0".to_string());
+        }
+        if self.max_action_depth == 0 {
+            return Err("max_action_depth must be > 0".to_string());
         }
+        Ok(())
     }
 }
 
@@ -227,6 +280,74 @@ impl ArbiterStats {
     }
 }
 
+// ============================================================================
+// Decision Trace Log (explainability)
+// ============================================================================
+
+/// Ring buffer of recent Arbiter decisions
+///
+/// Shared via Arc so the REST API can read traces without holding the controller.
+pub struct DecisionTraceLog {
+    capacity: usize,
+    traces: RwLock<VecDeque<DecisionTrace>>,
+    total_recorded: AtomicU64,
+}
+
+impl DecisionTraceLog {
+    /// Create log keeping at most `capacity` traces (0 disables recording)
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            traces: RwLock::new(VecDeque::with_capacity(capacity)),
+            total_recorded: AtomicU64::new(0),
+        }
+    }
+
+    /// Append a trace, evicting the oldest one when full
+    pub fn push(&self, trace: DecisionTrace) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut traces = self.traces.write();
+        if traces.len() >= self.capacity {
+            traces.pop_front();
+        }
+        traces.push_back(trace);
+        self.total_recorded.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Most recent traces, newest first
+    pub fn recent(&self, limit: usize) -> Vec<DecisionTrace> {
+        self.traces.read().iter().rev().take(limit).cloned().collect()
+    }
+
+    /// Find trace by action ID
+    pub fn get(&self, action_id: u64) -> Option<DecisionTrace> {
+        self.traces.read().iter().rev().find(|t| t.action_id == action_id).cloned()
+    }
+
+    /// Number of traces currently held
+    pub fn len(&self) -> usize {
+        self.traces.read().len()
+    }
+
+    /// Check if log is empty
+    pub fn is_empty(&self) -> bool {
+        self.traces.read().is_empty()
+    }
+
+    /// Total traces recorded since creation (including evicted)
+    pub fn total_recorded(&self) -> u64 {
+        self.total_recorded.load(Ordering::Relaxed)
+    }
+
+    /// Drop all traces
+    pub fn clear(&self) {
+        self.traces.write().clear();
+    }
+}
+
 /// Central action dispatcher with dual-path arbitration (v2.0)
 ///
 /// ActionController v2.0 coordinates between:
@@ -248,6 +369,7 @@ pub struct ActionController {
     guardian: Option<Arc<crate::Guardian>>,
     arbiter_config: ArbiterConfig,
     arbiter_stats: Arc<RwLock<ArbiterStats>>,
+    decision_log: Arc<DecisionTraceLog>,
     action_id_counter: std::sync::atomic::AtomicU64,
 
     // v0.38.0 component (Curiosity-driven exploration)
@@ -255,6 +377,18 @@ pub struct ActionController {
 
     // v0.39.1 component (Gateway integration)
     gateway: Option<Arc<crate::gateway::Gateway>>,
+
+    // Epsilon-greedy exploration draws
+    rng: Arc<crate::rng::SeededRng>,
+
+    // Receives curiosity surprise as learning-rate modulation
+    learner: Option<Arc<crate::learner::Learner>>,
+
+    // Only the NoOp executor runs while safe mode is engaged
+    safe_mode: Arc<crate::safe_mode::SafeMode>,
+
+    // Counts executor calls; curiosity-driven actions stop when exceeded
+    budget: Option<Arc<crate::resource_budget::ResourceBudget>>,
 }
 
 impl ActionController {
@@ -274,11 +408,16 @@ impl ActionController {
             config,
             intuition: Some(intuition),
             guardian: Some(guardian),
+            decision_log: Arc::new(DecisionTraceLog::new(arbiter_config.trace_capacity)),
             arbiter_config,
             arbiter_stats: Arc::new(RwLock::new(ArbiterStats::new())),
             action_id_counter: std::sync::atomic::AtomicU64::new(1),
             curiosity: None, // Optional, can be added later
             gateway: None,   // Optional, can be added later (v0.39.1)
+            rng: Arc::new(crate::rng::SeededRng::from_entropy()),
+            learner: None,
+            safe_mode: Arc::default(),
+            budget: None,
         }
     }
 
@@ -299,11 +438,16 @@ impl ActionController {
             config,
             intuition: Some(intuition),
             guardian: Some(guardian),
+            decision_log: Arc::new(DecisionTraceLog::new(arbiter_config.trace_capacity)),
             arbiter_config,
             arbiter_stats: Arc::new(RwLock::new(ArbiterStats::new())),
             action_id_counter: std::sync::atomic::AtomicU64::new(1),
             curiosity: Some(curiosity),
             gateway: None,   // Optional, can be added later (v0.39.1)
+            rng: Arc::new(crate::rng::SeededRng::from_entropy()),
+            learner: None,
+            safe_mode: Arc::default(),
+            budget: None,
         }
     }
 
@@ -322,6 +466,40 @@ impl ActionController {
         self.gateway = Some(gateway);
     }
 
+    /// Set seeded RNG for exploration draws (reproducible runs)
+    pub fn set_rng(&mut self, rng: Arc<crate::rng::SeededRng>) {
+        self.rng = rng;
+    }
+
+    /// Set learner whose rate is modulated by outcome surprise (`update_curiosity`)
+    pub fn set_learner(&mut self, learner: Arc<crate::learner::Learner>) {
+        self.learner = Some(learner);
+    }
+
+    /// Run only the NoOp executor while this switch is engaged
+    pub fn set_safe_mode(&mut self, safe_mode: Arc<crate::safe_mode::SafeMode>) {
+        self.safe_mode = safe_mode;
+    }
+
+    /// Safe mode switch the executors follow
+    pub fn safe_mode(&self) -> &Arc<crate::safe_mode::SafeMode> {
+        &self.safe_mode
+    }
+
+    /// Count executor calls against a resource budget
+    pub fn set_budget(&mut self, budget: Arc<crate::resource_budget::ResourceBudget>) {
+        self.budget = Some(budget);
+    }
+
+    pub fn budget(&self) -> Option<&Arc<crate::resource_budget::ResourceBudget>> {
+        self.budget.as_ref()
+    }
+
+    /// Whether the resource budget is used up (curiosity-driven actions pause)
+    pub fn budget_exceeded(&self) -> bool {
+        self.budget.as_ref().is_some_and(|budget| budget.is_exceeded())
+    }
+
     /// Get gateway
     pub fn gateway(&self) -> Option<&Arc<crate::gateway::Gateway>> {
         self.gateway.as_ref()
@@ -332,6 +510,16 @@ impl ActionController {
         self.arbiter_stats.read().clone()
     }
 
+    /// Get shared decision trace log (for REST /decisions)
+    pub fn decision_log(&self) -> Arc<DecisionTraceLog> {
+        Arc::clone(&self.decision_log)
+    }
+
+    /// Get most recent decision traces, newest first
+    pub fn recent_decisions(&self, limit: usize) -> Vec<DecisionTrace> {
+        self.decision_log.recent(limit)
+    }
+
     /// Generate unique action ID
     fn next_action_id(&self) -> u64 {
         self.action_id_counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst)
@@ -352,6 +540,17 @@ impl ActionController {
         Ok(())
     }
 
+    /// Register a sandboxed FileExecutor sharing this controller's Guardian and ExperienceStream
+    pub fn register_file_executor(
+        &self,
+        config: crate::executors::FileExecutorConfig,
+    ) -> Result<(), ActionError> {
+        let guardian = self.guardian.clone().unwrap_or_else(|| Arc::new(crate::Guardian::new()));
+        let executor = crate::executors::FileExecutor::new(config, guardian, Arc::clone(&self.experience_writer))
+            .map_err(|e| ActionError::ExecutionFailed(format!("Failed to create sandbox: {}", e)))?;
+        self.register_executor(Arc::new(executor))
+    }
+
     /// Get list of registered executor IDs
     pub fn list_executors(&self) -> Vec<String> {
         let executors = self.executors.read();
@@ -367,6 +566,19 @@ impl ActionController {
     /// 4. Executes action with timeout
     /// 5. Logs action_finished event with result
     pub async fn execute_intent(&self, intent: Intent) -> Result<ActionResult, ActionError> {
+        self.execute_intent_with_cancel(intent, CancellationToken::new()).await
+    }
+
+    /// Execute an intent with an externally owned cancellation token
+    ///
+    /// The token is handed to the executor for cooperative cancellation.
+    /// On timeout the token is cancelled as well, so executors that spawned
+    /// background work can wind it down.
+    pub async fn execute_intent_with_cancel(
+        &self,
+        intent: Intent,
+        cancel: CancellationToken,
+    ) -> Result<ActionResult, ActionError> {
         // Проверяем, включен ли модуль
         if !REGISTRY.is_enabled(ModuleId::ActionController) {
             // Модуль выключен — возвращаем ошибку
@@ -374,17 +586,57 @@ impl ActionController {
         }
 
         let start = Instant::now();
+        let decide_timer = PROFILER.start(PipelineStage::Decide);
+
+        let arbitrate = tracing::info_span!("action.arbitrate", intent_type = %intent.intent_type);
 
         // 1. Get policy from ADNA
         let policy = self.adna_reader
             .get_action_policy(&intent.state)
+            .instrument(arbitrate.clone())
             .await
             .map_err(|e| ActionError::ADNAError(e.to_string()))?;
 
         // 2. Select executor based on policy
-        let executor_id = self.select_executor(&policy)?;
+        let executor_id = arbitrate.in_scope(|| self.select_executor(&policy))?;
+        drop(decide_timer);
+
+        let span = tracing::info_span!("action.execute", executor = %executor_id);
+        self.run_executor(intent, executor_id, cancel, start).instrument(span).await
+    }
+
+    /// Execute an intent on a specific executor, bypassing ADNA policy selection
+    ///
+    /// Used for fallback routing (e.g. low-confidence signals → LLM).
+    pub async fn execute_intent_on(
+        &self,
+        intent: Intent,
+        executor_id: &str,
+        cancel: CancellationToken,
+    ) -> Result<ActionResult, ActionError> {
+        if !REGISTRY.is_enabled(ModuleId::ActionController) {
+            return Err(ActionError::ExecutorNotFound("ActionController module is disabled".to_string()));
+        }
 
-        // 3. Get executor
+        let span = tracing::info_span!("action.execute", executor = %executor_id);
+        self.run_executor(intent, executor_id.to_string(), cancel, Instant::now()).instrument(span).await
+    }
+
+    /// Steps 3-7 of execution: validate, log, run with timeout/cancel, log
+    async fn run_executor(
+        &self,
+        intent: Intent,
+        executor_id: String,
+        cancel: CancellationToken,
+        start: Instant,
+    ) -> Result<ActionResult, ActionError> {
+        // 3. Get executor (safe mode leaves only NoOp)
+        if !self.safe_mode.allows_executor(&executor_id) {
+            return Err(ActionError::ExecutionFailed(format!(
+                "Executor '{}' is disabled in safe mode",
+                executor_id
+            )));
+        }
         let executor = {
             let executors = self.executors.read();
             executors.get(&executor_id)
@@ -397,25 +649,33 @@ impl ActionController {
             return Err(ActionError::InvalidParameters(e));
         }
 
+        if let Some(budget) = &self.budget {
+            budget.record_call(&executor_id);
+        }
+
         // 5. Log action_started
         if self.config.log_all_actions {
             self.log_action_started(&intent, &executor_id);
         }
 
-        // 6. Execute action with timeout
-        let result = match tokio::time::timeout(
-            tokio::time::Duration::from_millis(self.config.timeout_ms),
-            executor.execute(intent.context.clone())
-        )
-        .await
-        {
-            Ok(action_result) => action_result,
-            Err(_) => {
-                return Err(ActionError::Timeout(
-                    tokio::time::Duration::from_millis(self.config.timeout_ms)
-                ));
+        // 6. Execute action with timeout (per-executor override)
+        let timeout = self.config.timeout_for(&executor_id);
+        let execute_timer = PROFILER.start(PipelineStage::Execute);
+        let result = tokio::select! {
+            outcome = tokio::time::timeout(timeout, executor.execute(intent.context.clone(), cancel.clone())) => {
+                match outcome {
+                    Ok(action_result) => action_result,
+                    Err(_) => {
+                        cancel.cancel();
+                        return Err(ActionError::Timeout(timeout));
+                    }
+                }
+            }
+            _ = cancel.cancelled() => {
+                return Err(ActionError::Cancelled(executor_id));
             }
         };
+        drop(execute_timer);
 
         // 7. Log action_finished
         if self.config.log_all_actions {
@@ -423,8 +683,14 @@ impl ActionController {
         }
 
         let total_duration = start.elapsed().as_millis() as u64;
-        println!("[ActionController] Executed intent '{}' with executor '{}' in {}ms",
-                 intent.intent_type, executor_id, total_duration);
+        match crate::correlation::current() {
+            Some(correlation_id) => println!(
+                "[ActionController] Executed intent '{}' with executor '{}' in {}ms (correlation {})",
+                intent.intent_type, executor_id, total_duration, correlation_id
+            ),
+            None => println!("[ActionController] Executed intent '{}' with executor '{}' in {}ms",
+                             intent.intent_type, executor_id, total_duration),
+        }
 
         Ok(result)
     }
@@ -484,8 +750,13 @@ impl ActionController {
     /// 2. Executes the intent via execute_intent()
     /// 3. Calls Gateway.complete_request() with the result
     ///
-    /// This closes the Gateway → ActionController loop.
+    /// This closes the Gateway → ActionController loop. Everything runs in the
+    /// signal's correlation scope (see `correlation`).
     pub async fn process_signal(&self, signal: crate::gateway::signals::ProcessedSignal) {
+        crate::correlation::scope(signal.correlation_id, self.process_signal_scoped(signal)).await
+    }
+
+    async fn process_signal_scoped(&self, signal: crate::gateway::signals::ProcessedSignal) {
         let signal_id = signal.signal_id;
 
         // Convert ProcessedSignal state [f32; 8] to Intent state [i16; 8]
@@ -509,11 +780,30 @@ impl ActionController {
                 "source": format!("{:?}", signal.source),
                 "metadata": signal.metadata,
                 "interpretation_confidence": signal.interpretation_confidence,
+                "state": signal.state,
             }),
         };
 
-        // Execute the intent
-        let result = self.execute_intent(intent).await.unwrap_or_else(|e| {
+        // Use the Gateway's token so Gateway::cancel(signal_id) can abort us
+        let cancel = self.gateway
+            .as_ref()
+            .and_then(|g| g.cancellation_token(signal_id))
+            .unwrap_or_default();
+
+        let queue_us = crate::action_types::current_timestamp_ms()
+            .saturating_sub(signal.processed_at)
+            * 1000;
+        let execution_start = Instant::now();
+
+        // Execute the intent (low-confidence signals go to the fallback executor)
+        let fallback = self.fallback_executor_for(signal.interpretation_confidence);
+        let outcome = match &fallback {
+            Some(executor_id) => self.execute_intent_on(intent, executor_id, cancel).await,
+            None => self.execute_intent_with_cancel(intent, cancel).await,
+        };
+        let execution_us = execution_start.elapsed().as_micros() as u64;
+
+        let mut result = outcome.unwrap_or_else(|e| {
             // If execution failed, create error result
             ActionResult {
                 success: false,
@@ -523,12 +813,85 @@ impl ActionController {
             }
         });
 
+        // Attach provenance so callers can explain the answer
+        if let Some(output) = result.output.as_object_mut() {
+            if !output.contains_key("provenance") {
+                let source = Self::signal_decision_source(&signal, fallback.as_deref(), execution_us);
+                output.insert(
+                    "provenance".to_string(),
+                    serde_json::json!({
+                        "matched_tokens": signal.metadata.matched_tokens.iter()
+                            .map(|(word, token_id, confidence)| serde_json::json!({
+                                "word": word,
+                                "token_id": token_id,
+                                "confidence": confidence,
+                            }))
+                            .collect::<Vec<_>>(),
+                        "unknown_words": signal.metadata.unknown_words,
+                        "decision_source": source,
+                        "correlation_id": signal.correlation_id,
+                        "timings": {
+                            "normalization_us": signal.metadata.processing_time_ns / 1000,
+                            "queue_us": queue_us,
+                            "execution_us": execution_us,
+                        },
+                    }),
+                );
+            }
+        }
+
         // Complete the Gateway request if gateway is set
         if let Some(gateway) = &self.gateway {
             gateway.complete_request(signal_id, result);
         }
     }
 
+    /// Which pathway answered a Gateway signal
+    ///
+    /// Curiosity triggers are exploration, low-confidence signals routed to the
+    /// fallback executor count as failsafe, everything else went through ADNA policy.
+    fn signal_decision_source(
+        signal: &crate::gateway::signals::ProcessedSignal,
+        fallback: Option<&str>,
+        execution_us: u64,
+    ) -> crate::action_types::DecisionSource {
+        use crate::action_types::DecisionSource;
+        use crate::gateway::signals::SignalType;
+
+        if let Some(executor_id) = fallback {
+            return DecisionSource::Failsafe {
+                reason: format!(
+                    "interpretation confidence {:.2} below threshold, routed to {}",
+                    signal.interpretation_confidence, executor_id
+                ),
+            };
+        }
+
+        match signal.signal_type {
+            SignalType::CuriosityTrigger => DecisionSource::Curiosity {
+                curiosity_score: 1.0 - signal.interpretation_confidence,
+                exploration_reason: "curiosity_trigger".to_string(),
+            },
+            _ => DecisionSource::Reasoning {
+                policy_version: 1, // policy_version (placeholder)
+                reasoning_time_ms: execution_us / 1000,
+            },
+        }
+    }
+
+    /// Fallback executor to use for a signal with given confidence, if configured and registered
+    fn fallback_executor_for(&self, confidence: f32) -> Option<String> {
+        let executor_id = self.config.fallback_executor.as_ref()?;
+        if confidence >= self.config.fallback_confidence_threshold {
+            return None;
+        }
+
+        self.executors
+            .read()
+            .contains_key(executor_id)
+            .then(|| executor_id.clone())
+    }
+
     /// Select executor based on policy using epsilon-greedy strategy
     fn select_executor(&self, policy: &ActionPolicy) -> Result<String, ActionError> {
         let executors = self.executors.read();
@@ -537,40 +900,45 @@ impl ActionController {
             return Err(ActionError::ExecutorNotFound("No executors registered".to_string()));
         }
 
+        // Sorted so that a seeded run picks the same executors
+        let mut ids: Vec<_> = executors.keys().cloned().collect();
+        ids.sort();
+
         // Epsilon-greedy: explore or exploit
-        let should_explore = rand::random::<f64>() < self.config.exploration_rate;
+        let should_explore = self.rng.next_f64() < self.config.exploration_rate;
 
         if should_explore {
             // EXPLORE: Pick random executor
-            let ids: Vec<_> = executors.keys().cloned().collect();
-            let idx = rand::random::<usize>() % ids.len();
-            Ok(ids[idx].clone())
+            Ok(ids[self.rng.index(ids.len())].clone())
         } else {
             // EXPLOIT: Pick executor based on policy weights
             // Map action_type to executor_id (simplified: use action_type as index)
             if let Some(action_type) = policy.select_action() {
                 // For simplicity: map action types to executor IDs
                 // action_type 1 → first executor, 2 → second, etc.
-                let ids: Vec<_> = executors.keys().cloned().collect();
-                if ids.is_empty() {
-                    return Err(ActionError::ExecutorNotFound("No executors available".to_string()));
-                }
-
                 let idx = (action_type as usize - 1) % ids.len();
                 Ok(ids[idx].clone())
             } else {
                 // No policy weights, pick first executor
-                let ids: Vec<_> = executors.keys().cloned().collect();
                 Ok(ids[0].clone())
             }
         }
     }
 
+    /// Source trust the Gateway attached to the intent's signal, if any
+    fn intent_trust(intent: &Intent) -> Option<f32> {
+        intent.context["metadata"]["source_trust"].as_f64().map(|t| t as f32)
+    }
+
     /// Log action_started event
     fn log_action_started(&self, intent: &Intent, executor_id: &str) {
         let mut event = ExperienceEvent::default();
+        event.event_id = uuid::Uuid::new_v4().as_u128(); // keys the correlation record
         event.event_type = 1000; // action_started
         event.state = intent.state.map(|v| v as f32 / 32767.0); // Convert i16 to f32
+        if let Some(trust) = Self::intent_trust(intent) {
+            event.set_trust(trust);
+        }
 
         // Store intent_type and executor_id in event metadata (simplified)
         let _ = self.experience_writer.write_event(event);
@@ -579,11 +947,15 @@ impl ActionController {
     /// Log action_finished event
     fn log_action_finished(&self, intent: &Intent, executor_id: &str, result: &ActionResult) {
         let mut event = ExperienceEvent::default();
+        event.event_id = uuid::Uuid::new_v4().as_u128();
         event.event_type = 1001; // action_finished
         event.state = intent.state.map(|v| v as f32 / 32767.0);
 
         // Encode success in L8 (Coherence): 1.0 if success, -1.0 if failure
         event.state[7] = if result.success { 1.0 } else { -1.0 };
+        if let Some(trust) = Self::intent_trust(intent) {
+            event.set_trust(trust);
+        }
 
         let _ = self.experience_writer.write_event(event);
     }
@@ -606,7 +978,22 @@ impl ActionController {
     /// # Returns
     /// ActionIntent with decision metadata (source, confidence, timing)
     pub fn act(&self, state: [f32; 8]) -> crate::action_types::ActionIntent {
-        use crate::action_types::ActionIntent;
+        let mut candidates = Vec::new();
+        let (intent, reason) = PROFILER.time(PipelineStage::Decide, || self.decide(state, &mut candidates));
+        self.record_decision(state, candidates, &intent, reason);
+        intent
+    }
+
+    /// Dual-path decision that also collects every candidate it considered
+    ///
+    /// Returns the chosen intent and a human-readable reason for the choice.
+    fn decide(
+        &self,
+        state: [f32; 8],
+        candidates: &mut Vec<DecisionCandidate>,
+    ) -> (ActionIntent, String) {
+        let _span = tracing::info_span!("action.arbitrate").entered();
+        let threshold_f32 = self.arbiter_config.reflex_confidence_threshold as f32 / 255.0;
 
         // Try Fast Path first (if available)
         if let Some(ref intuition_arc) = self.intuition {
@@ -631,11 +1018,20 @@ impl ActionController {
                     if confidence_u8 >= self.arbiter_config.reflex_confidence_threshold {
                         // Guardian validation (optional)
                         if let Some(ref guardian) = self.guardian {
-                            if let Err(_) = guardian.validate_reflex(&connection) {
+                            if let Err(e) = guardian.validate_reflex(&connection) {
                                 // Guardian rejected: fallback to Slow Path
                                 self.arbiter_stats.write().record_guardian_rejection();
                                 drop(intuition);
-                                return self.act_slow_path(state);
+                                candidates.push(DecisionCandidate {
+                                    pathway: CandidatePathway::Reflex,
+                                    score: confidence_f32,
+                                    action_type: None,
+                                    chosen: false,
+                                    verdict: format!("rejected by Guardian: {}", e),
+                                });
+                                let intent = self.act_slow_path(state);
+                                candidates.push(Self::slow_path_candidate(&intent));
+                                return (intent, "reflex rejected by Guardian, fell back to reasoning".to_string());
                             }
                         }
 
@@ -648,7 +1044,15 @@ impl ActionController {
                         // Record stats
                         self.arbiter_stats.write().record_reflex(confidence_f32, lookup_time_ns);
 
-                        return ActionIntent::from_reflex(
+                        candidates.push(DecisionCandidate {
+                            pathway: CandidatePathway::Reflex,
+                            score: confidence_f32,
+                            action_type: Some(action_type),
+                            chosen: true,
+                            verdict: format!("similarity {:.2}, confidence {:.2} >= {:.2}", similarity, confidence_f32, threshold_f32),
+                        });
+
+                        let intent = ActionIntent::from_reflex(
                             action_id,
                             action_type,
                             target_state,
@@ -657,13 +1061,70 @@ impl ActionController {
                             similarity,
                             confidence_f32,
                         );
+                        return (intent, "reflex confidence above threshold, reasoning skipped".to_string());
                     }
+
+                    candidates.push(DecisionCandidate {
+                        pathway: CandidatePathway::Reflex,
+                        score: confidence_f32,
+                        action_type: None,
+                        chosen: false,
+                        verdict: format!("confidence {:.2} below threshold {:.2}", confidence_f32, threshold_f32),
+                    });
                 }
             }
         }
 
         // Fast Path unavailable or failed → Slow Path
-        self.act_slow_path(state)
+        let intent = self.act_slow_path(state);
+        candidates.push(Self::slow_path_candidate(&intent));
+
+        let reason = if intent.source.is_failsafe() {
+            "ADNA reasoning failed, failsafe engaged"
+        } else if candidates.iter().any(|c| c.pathway == CandidatePathway::Reflex) {
+            "reflex below confidence threshold, used reasoning"
+        } else {
+            "no reflex matched, used reasoning"
+        };
+        (intent, reason.to_string())
+    }
+
+    /// Build trace candidate for a Slow Path (or failsafe) intent
+    fn slow_path_candidate(intent: &ActionIntent) -> DecisionCandidate {
+        match &intent.source {
+            crate::action_types::DecisionSource::Failsafe { reason } => DecisionCandidate {
+                pathway: CandidatePathway::Failsafe,
+                score: 0.0,
+                action_type: Some(intent.action_type),
+                chosen: true,
+                verdict: reason.clone(),
+            },
+            _ => DecisionCandidate {
+                pathway: CandidatePathway::Reasoning,
+                score: intent.confidence,
+                action_type: Some(intent.action_type),
+                chosen: true,
+                verdict: format!("ADNA policy confidence {:.2}", intent.confidence),
+            },
+        }
+    }
+
+    /// Append a decision trace to the ring buffer
+    fn record_decision(
+        &self,
+        state: [f32; 8],
+        candidates: Vec<DecisionCandidate>,
+        intent: &ActionIntent,
+        reason: String,
+    ) {
+        self.decision_log.push(DecisionTrace {
+            action_id: intent.action_id,
+            state,
+            candidates,
+            chosen: intent.source.clone(),
+            reason,
+            timestamp: intent.timestamp,
+        });
     }
 
     /// Act with shadow mode: run both Fast and Slow paths in parallel (NEW v0.34.0)
@@ -706,11 +1167,28 @@ impl ActionController {
                     self.arbiter_stats.write().record_shadow_disagreement();
                 }
 
+                let mut shadow_candidate = Self::slow_path_candidate(&slow_result);
+                shadow_candidate.chosen = false;
+                shadow_candidate.verdict = format!("shadow evaluation, params distance {:.2}", params_distance);
+                let candidates = vec![
+                    DecisionCandidate {
+                        pathway: CandidatePathway::Reflex,
+                        score: fast_intent.confidence,
+                        action_type: Some(fast_intent.action_type),
+                        chosen: true,
+                        verdict: "reflex accepted".to_string(),
+                    },
+                    shadow_candidate,
+                ];
+                self.record_decision(state, candidates, &fast_intent, "shadow mode: reflex is primary".to_string());
+
                 // Return Fast Path as primary, Slow as shadow
                 (fast_intent, Some(slow_result))
             }
             None => {
                 // Fast Path failed - use Slow Path as primary (no shadow)
+                let candidates = vec![Self::slow_path_candidate(&slow_result)];
+                self.record_decision(state, candidates, &slow_result, "shadow mode: no reflex, used reasoning".to_string());
                 (slow_result, None)
             }
         }
@@ -966,22 +1444,63 @@ impl ActionController {
         };
 
         let curiosity_score = curiosity.calculate_curiosity(&context);
+        let mut candidates = Vec::new();
 
-        // If curiosity triggers exploration
-        if curiosity_score.triggers_exploration {
-            return self.explore_curious_target(state, &curiosity_score);
+        if curiosity_score.triggers_exploration && self.budget_exceeded() {
+            // Over budget: exploration waits for the next budget window
+            if let Some(budget) = &self.budget {
+                budget.record_throttled();
+            }
+            candidates.push(DecisionCandidate {
+                pathway: CandidatePathway::Curiosity,
+                score: curiosity_score.overall,
+                action_type: None,
+                chosen: false,
+                verdict: "exploration throttled by resource budget".to_string(),
+            });
+        } else if curiosity_score.triggers_exploration {
+            if let Some(intent) = self.explore_curious_target(&curiosity_score) {
+                candidates.push(DecisionCandidate {
+                    pathway: CandidatePathway::Curiosity,
+                    score: curiosity_score.overall,
+                    action_type: Some(intent.action_type),
+                    chosen: true,
+                    verdict: format!("curiosity {:.2} triggered exploration", curiosity_score.overall),
+                });
+                self.record_decision(state, candidates, &intent, "curiosity pre-empted reflex and reasoning".to_string());
+                return intent;
+            }
+
+            candidates.push(DecisionCandidate {
+                pathway: CandidatePathway::Curiosity,
+                score: curiosity_score.overall,
+                action_type: None,
+                chosen: false,
+                verdict: "exploration triggered but no target available".to_string(),
+            });
+        } else {
+            candidates.push(DecisionCandidate {
+                pathway: CandidatePathway::Curiosity,
+                score: curiosity_score.overall,
+                action_type: None,
+                chosen: false,
+                verdict: "below exploration threshold".to_string(),
+            });
         }
 
         // Standard act (Fast/Slow path)
-        self.act(state)
+        let (intent, reason) = self.decide(state, &mut candidates);
+        self.record_decision(state, candidates, &intent, reason);
+        intent
     }
 
     /// Explore a curious target (high uncertainty/surprise/novelty)
+    ///
+    /// Returns None when no exploration target is available.
     fn explore_curious_target(
         &self,
-        current_state: [f32; 8],
         curiosity_score: &crate::curiosity::CuriosityScore,
-    ) -> crate::action_types::ActionIntent {
+    ) -> Option<crate::action_types::ActionIntent> {
         use crate::action_types::{ActionIntent, ActionType, DecisionSource};
 
         let curiosity = self.curiosity.as_ref().unwrap();
@@ -1002,7 +1521,7 @@ impl ActionController {
             let action_id = self.next_action_id();
 
             // Create exploration action
-            return ActionIntent {
+            return Some(ActionIntent {
                 action_id,
                 action_type: ActionType::Explore,
                 params: target_state,
@@ -1016,16 +1535,18 @@ impl ActionController {
                     .duration_since(std::time::UNIX_EPOCH)
                     .unwrap()
                     .as_millis() as u64,
-            };
+                correlation_id: crate::correlation::current(),
+            });
         }
 
-        // No exploration target available → fallback to standard
-        self.act(current_state)
+        // No exploration target available → caller falls back to standard
+        None
     }
 
     /// Update curiosity with actual outcome (for surprise calculation)
     ///
-    /// Call this after executing an action to feed the result back to curiosity
+    /// Call this after executing an action to feed the result back to curiosity.
+    /// The resulting surprise also modulates the learner's rate (`set_learner`).
     pub fn update_curiosity(&self, predicted_state: [f32; 8], actual_state: [f32; 8]) {
         if let Some(ref curiosity) = self.curiosity {
             // Convert to f64
@@ -1058,7 +1579,10 @@ impl ActionController {
                 prediction_accuracy: Some(accuracy),
             };
 
-            curiosity.calculate_curiosity(&context);
+            let score = curiosity.calculate_curiosity(&context);
+            if let Some(learner) = &self.learner {
+                learner.modulate_surprise(score.surprise);
+            }
         }
     }
 
@@ -1095,6 +1619,7 @@ impl ActionController {
                     .duration_since(std::time::UNIX_EPOCH)
                     .unwrap()
                     .as_millis() as u64,
+                correlation_id: crate::correlation::current(),
             });
         }
 
@@ -1603,4 +2128,190 @@ mod tests {
         assert!(conf1 > conf2, "Certain policy should have higher confidence than uncertain");
     }
 
+    #[test]
+    fn test_decision_trace_records_candidates() {
+        use crate::{IntuitionEngine, IntuitionConfig, Guardian};
+        use crate::connection_v3::{ConnectionV3, ConnectionMutability};
+        use tokio::sync::mpsc;
+        use crate::adna::Proposal;
+
+        let adna_reader = Arc::new(InMemoryADNAReader::with_defaults());
+        let experience_stream = Arc::new(ExperienceStream::new(1000, 10));
+
+        let (proposal_tx, _proposal_rx) = mpsc::channel::<Proposal>(100);
+        let mut intuition = IntuitionEngine::new(
+            IntuitionConfig::default(),
+            Arc::clone(&experience_stream),
+            Arc::clone(&adna_reader) as Arc<dyn crate::adna::ADNAReader>,
+            proposal_tx,
+        );
+
+        // Confident reflex that Guardian rejects: considered but not chosen
+        let source = [0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8];
+        let source_token = crate::Token::from_state_f32(1, &source);
+        let mut connection = ConnectionV3::new(1, 2);
+        connection.confidence = 250;
+        connection.mutability = ConnectionMutability::Hypothesis as u8;
+        intuition.consolidate_reflex(&source_token, connection);
+
+        let mut arbiter_config = ArbiterConfig::default();
+        arbiter_config.trace_capacity = 2;
+
+        let controller = ActionController::new(
+            adna_reader as Arc<dyn ADNAReader>,
+            experience_stream as Arc<dyn ExperienceWriter>,
+            Arc::new(RwLock::new(intuition)),
+            Arc::new(Guardian::new()),
+            ActionControllerConfig::default(),
+            arbiter_config,
+        );
+
+        let intent = controller.act(source);
+        let traces = controller.recent_decisions(10);
+        assert_eq!(traces.len(), 1);
+
+        let trace = &traces[0];
+        assert_eq!(trace.action_id, intent.action_id);
+        assert_eq!(trace.candidates.len(), 2);
+        assert_eq!(trace.candidates[0].pathway, CandidatePathway::Reflex);
+        assert!(!trace.candidates[0].chosen);
+        assert_eq!(trace.candidates[1].pathway, CandidatePathway::Reasoning);
+        assert!(trace.candidates[1].chosen);
+        assert!(trace.candidates[0].verdict.contains("Guardian"));
+        assert!(trace.reason.contains("rejected by Guardian"));
+
+        // Ring buffer keeps only the newest traces
+        controller.act(source);
+        controller.act(source);
+        assert_eq!(controller.decision_log().len(), 2);
+        assert_eq!(controller.decision_log().total_recorded(), 3);
+    }
+
+    #[test]
+    fn test_per_executor_timeout() {
+        let mut config = ActionControllerConfig::default();
+        config.executor_timeouts_ms.insert("slow".to_string(), 500);
+
+        assert_eq!(config.timeout_for("slow"), std::time::Duration::from_millis(500));
+        assert_eq!(config.timeout_for("noop"), std::time::Duration::from_millis(config.timeout_ms));
+
+        // Old config files without overrides still load
+        let json = r#"{"exploration_rate": 0.1, "log_all_actions": true, "timeout_ms": 100}"#;
+        let parsed: ActionControllerConfig = serde_json::from_str(json).unwrap();
+        assert!(parsed.executor_timeouts_ms.is_empty());
+        assert!(parsed.fallback_executor.is_none());
+    }
+
+    #[test]
+    fn test_fallback_executor_routing() {
+        use crate::{IntuitionEngine, IntuitionConfig, Guardian};
+        use crate::adna::Proposal;
+        use tokio::sync::mpsc;
+
+        let adna_reader = Arc::new(InMemoryADNAReader::with_defaults());
+        let experience_stream = Arc::new(ExperienceStream::new(1000, 10));
+        let (proposal_tx, _proposal_rx) = mpsc::channel::<Proposal>(100);
+        let intuition = IntuitionEngine::new(
+            IntuitionConfig::default(),
+            Arc::clone(&experience_stream),
+            Arc::clone(&adna_reader) as Arc<dyn crate::adna::ADNAReader>,
+            proposal_tx,
+        );
+
+        let mut config = ActionControllerConfig::default();
+        config.fallback_executor = Some("noop".to_string());
+
+        let controller = ActionController::new(
+            adna_reader as Arc<dyn ADNAReader>,
+            experience_stream as Arc<dyn ExperienceWriter>,
+            Arc::new(RwLock::new(intuition)),
+            Arc::new(Guardian::new()),
+            config,
+            ArbiterConfig::default(),
+        );
+
+        // Not registered yet → no fallback
+        assert_eq!(controller.fallback_executor_for(0.1), None);
+
+        controller.register_executor(Arc::new(crate::executors::NoOpExecutor::new())).unwrap();
+        assert_eq!(controller.fallback_executor_for(0.1), Some("noop".to_string()));
+        assert_eq!(controller.fallback_executor_for(0.9), None);
+    }
+
+    #[tokio::test]
+    async fn test_safe_mode_allows_only_noop() {
+        use crate::{IntuitionEngine, IntuitionConfig, Guardian};
+        use crate::adna::Proposal;
+        use crate::safe_mode::SafeMode;
+        use tokio::sync::mpsc;
+
+        let adna_reader = Arc::new(InMemoryADNAReader::with_defaults());
+        let experience_stream = Arc::new(ExperienceStream::new(1000, 10));
+        let (proposal_tx, _proposal_rx) = mpsc::channel::<Proposal>(100);
+        let intuition = IntuitionEngine::new(
+            IntuitionConfig::default(),
+            Arc::clone(&experience_stream),
+            Arc::clone(&adna_reader) as Arc<dyn crate::adna::ADNAReader>,
+            proposal_tx,
+        );
+
+        let mut controller = ActionController::new(
+            adna_reader as Arc<dyn ADNAReader>,
+            experience_stream as Arc<dyn ExperienceWriter>,
+            Arc::new(RwLock::new(intuition)),
+            Arc::new(Guardian::new()),
+            ActionControllerConfig::default(),
+            ArbiterConfig::default(),
+        );
+        let safe_mode = Arc::new(SafeMode::new());
+        controller.set_safe_mode(safe_mode.clone());
+        controller.register_executor(Arc::new(crate::executors::NoOpExecutor::new())).unwrap();
+        controller.register_executor(Arc::new(crate::executors::MessageSenderExecutor::new())).unwrap();
+
+        let intent = || Intent::new("test", serde_json::json!({"message": "hi"}), [0; 8]);
+        assert!(controller.execute_intent_on(intent(), "message_sender", CancellationToken::new()).await.is_ok());
+
+        safe_mode.engage(Some("test"));
+        let err = controller.execute_intent_on(intent(), "message_sender", CancellationToken::new()).await.unwrap_err();
+        assert!(err.to_string().contains("safe mode"));
+        assert!(controller.execute_intent_on(intent(), "noop", CancellationToken::new()).await.is_ok());
+
+        safe_mode.release();
+        assert!(controller.execute_intent_on(intent(), "message_sender", CancellationToken::new()).await.is_ok());
+    }
+
+    #[test]
+    fn test_outcome_surprise_modulates_learner() {
+        use crate::{IntuitionEngine, IntuitionConfig, Guardian};
+        use crate::adna::Proposal;
+        use crate::curiosity::{CuriosityConfig, CuriosityDrive};
+        use crate::learner::Learner;
+        use tokio::sync::mpsc;
+
+        let adna_reader = Arc::new(InMemoryADNAReader::with_defaults());
+        let experience_stream = Arc::new(ExperienceStream::new(1000, 10));
+        let (proposal_tx, _proposal_rx) = mpsc::channel::<Proposal>(100);
+        let intuition = IntuitionEngine::new(
+            IntuitionConfig::default(),
+            Arc::clone(&experience_stream),
+            Arc::clone(&adna_reader) as Arc<dyn crate::adna::ADNAReader>,
+            proposal_tx,
+        );
+
+        let mut controller = ActionController::with_curiosity(
+            adna_reader as Arc<dyn ADNAReader>,
+            experience_stream as Arc<dyn ExperienceWriter>,
+            Arc::new(RwLock::new(intuition)),
+            Arc::new(Guardian::new()),
+            Arc::new(CuriosityDrive::new(CuriosityConfig::default())),
+            ActionControllerConfig::default(),
+            ArbiterConfig::default(),
+        );
+        let learner = Arc::new(Learner::default());
+        controller.set_learner(learner.clone());
+
+        // Outcome far from the prediction → surprise → boosted learning rate
+        controller.update_curiosity([0.0; 8], [1.0; 8]);
+        assert!(learner.adaptive_factor() > 1.0);
+    }
 }
\ No newline at end of file
@@ -18,7 +18,10 @@
 
 use async_trait::async_trait;
 use serde_json::Value;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::Notify;
 
 /// Result of an action execution
 #[derive(Debug, Clone)]
@@ -73,6 +76,10 @@ pub enum ActionError {
     #[error("Timeout after {0:?}")]
     Timeout(Duration),
 
+    /// Action was cancelled via its CancellationToken
+    #[error("Cancelled: {0}")]
+    Cancelled(String),
+
     #[error("ADNA reader error: {0}")]
     ADNAError(String),
 
@@ -81,6 +88,51 @@ pub enum ActionError {
     PanicRecovered(String),
 }
 
+/// Cooperative cancellation token passed to executors
+///
+/// Cloning is cheap: all clones share the same flag. Long-running executors
+/// should check `is_cancelled()` between steps or `select!` on `cancelled()`.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    inner: Arc<CancellationInner>,
+}
+
+#[derive(Debug, Default)]
+struct CancellationInner {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl CancellationToken {
+    /// Create a new, non-cancelled token
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation and wake all waiters
+    pub fn cancel(&self) {
+        if !self.inner.cancelled.swap(true, Ordering::SeqCst) {
+            self.inner.notify.notify_waiters();
+        }
+    }
+
+    /// Check whether cancellation was requested
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Wait until cancellation is requested
+    pub async fn cancelled(&self) {
+        loop {
+            let notified = self.inner.notify.notified();
+            if self.is_cancelled() {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
 /// Common trait for all action executors
 ///
 /// Each executor implements a specific capability (moving tokens, sending messages, etc.)
@@ -97,10 +149,11 @@ pub trait ActionExecutor: Send + Sync {
     ///
     /// # Arguments
     /// * `params` - JSON parameters for the action
+    /// * `cancel` - Cooperative cancellation token (timeout or Gateway cancel)
     ///
     /// # Returns
     /// * `ActionResult` with success status, output data, and duration
-    async fn execute(&self, params: Value) -> ActionResult;
+    async fn execute(&self, params: Value, cancel: CancellationToken) -> ActionResult;
 
     /// Validate parameters before execution (optional)
     ///
@@ -142,4 +195,17 @@ mod tests {
         let err = ActionError::InvalidParameters("missing field".to_string());
         assert_eq!(err.to_string(), "Invalid parameters: missing field");
     }
+
+    #[tokio::test]
+    async fn test_cancellation_token() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!clone.is_cancelled());
+
+        let waiter = tokio::spawn(async move { clone.cancelled().await });
+        token.cancel();
+
+        waiter.await.unwrap();
+        assert!(token.is_cancelled());
+    }
 }
@@ -20,7 +20,9 @@
 //! - ActionIntent: High-level description of desired action
 //! - DecisionSource: Tracks whether decision came from Reflex (Fast) or Reasoning (Slow)
 //! - ActionType: Enumeration of all possible action types in the system
+//! - DecisionTrace: Explainability record of a single arbitration
 
+use crate::correlation::CorrelationId;
 use serde::{Deserialize, Serialize};
 
 /// Types of actions available in NeuroGraph OS
@@ -132,6 +134,16 @@ impl DecisionSource {
         matches!(self, DecisionSource::Curiosity { .. })
     }
 
+    /// Short pathway name ("reflex", "reasoning", "failsafe", "curiosity")
+    pub fn name(&self) -> &'static str {
+        match self {
+            DecisionSource::Reflex { .. } => "reflex",
+            DecisionSource::Reasoning { .. } => "reasoning",
+            DecisionSource::Failsafe { .. } => "failsafe",
+            DecisionSource::Curiosity { .. } => "curiosity",
+        }
+    }
+
     /// Get execution time in nanoseconds (for metrics)
     pub fn execution_time_ns(&self) -> u64 {
         match self {
@@ -166,6 +178,10 @@ pub struct ActionIntent {
 
     /// Unix timestamp (milliseconds)
     pub timestamp: u64,
+
+    /// Interaction the decision belongs to (see `correlation`)
+    #[serde(default)]
+    pub correlation_id: Option<CorrelationId>,
 }
 
 impl ActionIntent {
@@ -191,6 +207,7 @@ impl ActionIntent {
             confidence,
             estimated_reward: 0.0, // Will be filled by appraisers
             timestamp: current_timestamp_ms(),
+            correlation_id: crate::correlation::current(),
         }
     }
 
@@ -214,6 +231,7 @@ impl ActionIntent {
             confidence,
             estimated_reward: 0.0,
             timestamp: current_timestamp_ms(),
+            correlation_id: crate::correlation::current(),
         }
     }
 
@@ -227,10 +245,62 @@ impl ActionIntent {
             confidence: 0.0,
             estimated_reward: 0.0,
             timestamp: current_timestamp_ms(),
+            correlation_id: crate::correlation::current(),
         }
     }
 }
 
+/// Pathway that proposed a candidate during arbitration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CandidatePathway {
+    Reflex,
+    Reasoning,
+    Curiosity,
+    Failsafe,
+}
+
+/// One candidate considered by the Arbiter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionCandidate {
+    /// Which pathway produced this candidate
+    pub pathway: CandidatePathway,
+
+    /// Pathway score (reflex confidence, policy confidence, curiosity score)
+    pub score: f32,
+
+    /// Proposed action (None if the pathway produced no action)
+    pub action_type: Option<ActionType>,
+
+    /// Whether this candidate was chosen
+    pub chosen: bool,
+
+    /// Why the candidate was accepted or rejected
+    pub verdict: String,
+}
+
+/// Explainability record for a single Arbiter decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionTrace {
+    /// Action ID of the chosen intent (0 for failsafe)
+    pub action_id: u64,
+
+    /// Input state that was arbitrated
+    pub state: [f32; 8],
+
+    /// All candidates in evaluation order
+    pub candidates: Vec<DecisionCandidate>,
+
+    /// Source of the chosen intent
+    pub chosen: DecisionSource,
+
+    /// Why the chosen candidate won over the others
+    pub reason: String,
+
+    /// Unix timestamp (milliseconds)
+    pub timestamp: u64,
+}
+
 /// Get current Unix timestamp in milliseconds
 pub fn current_timestamp_ms() -> u64 {
     std::time::SystemTime::now()
@@ -324,6 +394,28 @@ mod tests {
         assert_eq!(intent.confidence, 0.6);
     }
 
+    #[test]
+    fn test_decision_trace_serialization() {
+        let trace = DecisionTrace {
+            action_id: 7,
+            state: [0.0; 8],
+            candidates: vec![DecisionCandidate {
+                pathway: CandidatePathway::Reflex,
+                score: 0.5,
+                action_type: None,
+                chosen: false,
+                verdict: "below threshold".to_string(),
+            }],
+            chosen: DecisionSource::Failsafe { reason: "test".to_string() },
+            reason: "no candidate accepted".to_string(),
+            timestamp: current_timestamp_ms(),
+        };
+
+        let json = serde_json::to_value(&trace).unwrap();
+        assert_eq!(json["candidates"][0]["pathway"], "reflex");
+        assert_eq!(json["action_id"], 7);
+    }
+
     #[test]
     fn test_action_intent_failsafe() {
         let intent = ActionIntent::failsafe("ADNA timeout".to_string());
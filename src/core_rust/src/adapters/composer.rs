@@ -0,0 +1,454 @@
+//! Natural-language response composer
+//!
+//! Turns structured ActionResult output (activated node sets, paths,
+//! confidence values) into short readable sentences. Node IDs are replaced
+//! by labels from a `LabelSource` (e.g. the bootstrap concept map) or from
+//! `label`/`word` fields already present in the output.
+//!
+//! Sentences come from `ComposerTemplates`, so wording can be changed (or
+//! localized) without touching the composition logic.
+
+use crate::action_executor::ActionResult;
+use crate::bootstrap::BootstrapLibrary;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Resolves node IDs to human-readable labels
+pub trait LabelSource: Send + Sync {
+    /// Label for a node, if known
+    fn label(&self, node_id: u32) -> Option<String>;
+}
+
+/// No labels; nodes are shown as `#id`
+pub struct NoLabels;
+
+impl LabelSource for NoLabels {
+    fn label(&self, _node_id: u32) -> Option<String> {
+        None
+    }
+}
+
+impl LabelSource for HashMap<u32, String> {
+    fn label(&self, node_id: u32) -> Option<String> {
+        self.get(&node_id).cloned()
+    }
+}
+
+impl LabelSource for BootstrapLibrary {
+    fn label(&self, node_id: u32) -> Option<String> {
+        self.concepts_iter()
+            .find(|(_, c)| c.id == node_id)
+            .map(|(word, _)| word.clone())
+    }
+}
+
+impl<T: LabelSource> LabelSource for parking_lot::RwLock<T> {
+    fn label(&self, node_id: u32) -> Option<String> {
+        self.read().label(node_id)
+    }
+}
+
+impl<T: LabelSource + ?Sized> LabelSource for std::sync::Arc<T> {
+    fn label(&self, node_id: u32) -> Option<String> {
+        (**self).label(node_id)
+    }
+}
+
+/// Sentence templates; `{name}` placeholders are filled by the composer
+#[derive(Debug, Clone)]
+pub struct ComposerTemplates {
+    /// Activation with a known source: `{source}`, `{items}`
+    pub activation: String,
+    /// Activation without a source: `{items}`
+    pub activation_no_source: String,
+    /// Activation that reached nothing: `{source}`
+    pub activation_empty: String,
+    /// Path through the graph: `{path}`
+    pub path: String,
+    /// Confidence note: `{percent}`, `{qualifier}`
+    pub confidence: String,
+    /// Failed action: `{error}`
+    pub error: String,
+    /// Word joining the last two list items
+    pub and: String,
+    /// Qualifiers for high / medium / low confidence
+    pub qualifiers: [String; 3],
+}
+
+impl Default for ComposerTemplates {
+    fn default() -> Self {
+        Self {
+            activation: "{source} brings to mind {items}.".to_string(),
+            activation_no_source: "Related: {items}.".to_string(),
+            activation_empty: "Nothing related to {source} came up.".to_string(),
+            path: "The strongest link runs {path}.".to_string(),
+            confidence: "I'm {qualifier} ({percent}% confidence).".to_string(),
+            error: "I couldn't do that: {error}.".to_string(),
+            and: "and".to_string(),
+            qualifiers: ["confident".to_string(), "fairly sure".to_string(), "unsure".to_string()],
+        }
+    }
+}
+
+/// Composer configuration
+#[derive(Debug, Clone)]
+pub struct ComposerConfig {
+    /// Maximum items listed in one sentence
+    pub max_items: usize,
+    /// Activated nodes below this energy are not mentioned
+    pub min_energy: f32,
+    /// Append scores, e.g. "dog (0.85)"
+    pub show_scores: bool,
+    /// Confidence at or above this is "high"
+    pub high_confidence: f32,
+    /// Confidence at or above this is "medium"
+    pub medium_confidence: f32,
+    /// Sentence templates
+    pub templates: ComposerTemplates,
+}
+
+impl Default for ComposerConfig {
+    fn default() -> Self {
+        Self {
+            max_items: 5,
+            min_energy: 0.05,
+            show_scores: false,
+            high_confidence: 0.8,
+            medium_confidence: 0.5,
+            templates: ComposerTemplates::default(),
+        }
+    }
+}
+
+impl ComposerConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_items == 0 {
+            return Err("max_items must be > 0".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.medium_confidence)
+            || !(0.0..=1.0).contains(&self.high_confidence)
+            || self.medium_confidence > self.high_confidence
+        {
+            return Err("confidence thresholds must satisfy 0 <= medium <= high <= 1".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Builds readable sentences from ActionResults
+#[derive(Debug, Clone, Default)]
+pub struct ResponseComposer {
+    config: ComposerConfig,
+}
+
+impl ResponseComposer {
+    pub fn new(config: ComposerConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &ComposerConfig {
+        &self.config
+    }
+
+    /// Compose a response using only labels embedded in the output
+    pub fn compose(&self, result: &ActionResult) -> Option<String> {
+        self.compose_with(result, &NoLabels)
+    }
+
+    /// Compose a response, resolving node labels through `labels`
+    ///
+    /// Returns `None` when the output has nothing the composer understands;
+    /// callers should then fall back to the raw data.
+    pub fn compose_with(&self, result: &ActionResult, labels: &dyn LabelSource) -> Option<String> {
+        let templates = &self.config.templates;
+
+        if !result.success {
+            let error = result.error.as_deref().unwrap_or("unknown error");
+            return Some(fill(&templates.error, &[("error", error.trim_end_matches('.'))]));
+        }
+
+        let output = &result.output;
+        if let Some(text) = output.get("response").and_then(Value::as_str) {
+            return Some(text.to_string());
+        }
+
+        let embedded = embedded_labels(output);
+        let resolve = |node_id: u32| -> String {
+            embedded
+                .get(&node_id)
+                .cloned()
+                .or_else(|| labels.label(node_id))
+                .unwrap_or_else(|| format!("#{}", node_id))
+        };
+
+        let mut sentences = Vec::new();
+
+        if let Some(nodes) = output.get("activated_nodes").and_then(Value::as_array) {
+            let source = output.get("source_id").and_then(as_node_id);
+            sentences.push(self.activation_sentence(nodes, source, &resolve));
+        } else if let Some(results) = output.get("results").and_then(Value::as_array) {
+            if let Some(sentence) = self.results_sentence(results) {
+                sentences.push(sentence);
+            }
+        }
+
+        let path = output
+            .get("strongest_path")
+            .or_else(|| output.get("path"))
+            .and_then(Value::as_array)
+            .map(|p| p.iter().filter_map(as_node_id).collect::<Vec<_>>())
+            .unwrap_or_default();
+        if path.len() >= 2 {
+            let path = path.into_iter().map(&resolve).collect::<Vec<_>>().join(" → ");
+            sentences.push(fill(&templates.path, &[("path", &path)]));
+        }
+
+        if let Some(confidence) = output.get("confidence").and_then(Value::as_f64) {
+            sentences.push(self.confidence_sentence(confidence as f32));
+        }
+
+        if sentences.is_empty() {
+            None
+        } else {
+            Some(sentences.join(" "))
+        }
+    }
+
+    fn activation_sentence(
+        &self,
+        nodes: &[Value],
+        source: Option<u32>,
+        resolve: &dyn Fn(u32) -> String,
+    ) -> String {
+        let templates = &self.config.templates;
+
+        let mut activated: Vec<(u32, f32)> = nodes
+            .iter()
+            .filter_map(|n| {
+                let id = n.get("node_id").and_then(as_node_id)?;
+                let energy = n.get("energy").and_then(Value::as_f64).unwrap_or(0.0) as f32;
+                Some((id, energy))
+            })
+            .filter(|&(id, energy)| Some(id) != source && energy >= self.config.min_energy)
+            .collect();
+        activated.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        activated.truncate(self.config.max_items);
+
+        let source_label = source.map(|id| format!("\"{}\"", resolve(id)));
+        if activated.is_empty() {
+            let source = source_label.unwrap_or_else(|| "this".to_string());
+            return fill(&templates.activation_empty, &[("source", &source)]);
+        }
+
+        let items: Vec<String> = activated
+            .iter()
+            .map(|&(id, energy)| self.item(&resolve(id), energy))
+            .collect();
+        let items = join_list(&items, &templates.and);
+
+        match source_label {
+            Some(source) => capitalize(&fill(&templates.activation, &[("source", &source), ("items", &items)])),
+            None => fill(&templates.activation_no_source, &[("items", &items)]),
+        }
+    }
+
+    /// `results: [[word, score], ...]` or `[{word|label, score}, ...]`
+    fn results_sentence(&self, results: &[Value]) -> Option<String> {
+        let items: Vec<String> = results
+            .iter()
+            .filter_map(|r| match r {
+                Value::Array(pair) => Some((pair.first()?.as_str()?, pair.get(1).and_then(Value::as_f64))),
+                Value::Object(obj) => Some((
+                    obj.get("word").or_else(|| obj.get("label"))?.as_str()?,
+                    obj.get("score").and_then(Value::as_f64),
+                )),
+                _ => None,
+            })
+            .take(self.config.max_items)
+            .map(|(label, score)| self.item(label, score.unwrap_or(0.0) as f32))
+            .collect();
+
+        if items.is_empty() {
+            return None;
+        }
+        let items = join_list(&items, &self.config.templates.and);
+        Some(fill(&self.config.templates.activation_no_source, &[("items", &items)]))
+    }
+
+    fn confidence_sentence(&self, confidence: f32) -> String {
+        let confidence = confidence.clamp(0.0, 1.0);
+        let qualifiers = &self.config.templates.qualifiers;
+        let qualifier = if confidence >= self.config.high_confidence {
+            &qualifiers[0]
+        } else if confidence >= self.config.medium_confidence {
+            &qualifiers[1]
+        } else {
+            &qualifiers[2]
+        };
+        let percent = format!("{:.0}", confidence * 100.0);
+        fill(&self.config.templates.confidence, &[("qualifier", qualifier), ("percent", &percent)])
+    }
+
+    fn item(&self, label: &str, score: f32) -> String {
+        if self.config.show_scores {
+            format!("{} ({:.2})", label, score)
+        } else {
+            label.to_string()
+        }
+    }
+}
+
+/// Node ID from a JSON number
+fn as_node_id(value: &Value) -> Option<u32> {
+    value.as_u64().and_then(|v| u32::try_from(v).ok())
+}
+
+/// Labels carried in the output itself: `labels: {"id": "word"}` and
+/// `label`/`word` fields on activated nodes
+fn embedded_labels(output: &Value) -> HashMap<u32, String> {
+    let mut labels = HashMap::new();
+
+    if let Some(map) = output.get("labels").and_then(Value::as_object) {
+        for (id, label) in map {
+            if let (Ok(id), Some(label)) = (id.parse::<u32>(), label.as_str()) {
+                labels.insert(id, label.to_string());
+            }
+        }
+    }
+
+    if let Some(nodes) = output.get("activated_nodes").and_then(Value::as_array) {
+        for node in nodes {
+            let id = node.get("node_id").and_then(as_node_id);
+            let label = node.get("label").or_else(|| node.get("word")).and_then(Value::as_str);
+            if let (Some(id), Some(label)) = (id, label) {
+                labels.insert(id, label.to_string());
+            }
+        }
+    }
+
+    labels
+}
+
+/// Replace `{name}` placeholders
+fn fill(template: &str, values: &[(&str, &str)]) -> String {
+    values
+        .iter()
+        .fold(template.to_string(), |text, (name, value)| text.replace(&format!("{{{}}}", name), value))
+}
+
+/// "a", "a and b", "a, b and c"
+fn join_list(items: &[String], and: &str) -> String {
+    match items {
+        [] => String::new(),
+        [one] => one.clone(),
+        [rest @ .., last] => format!("{} {} {}", rest.join(", "), and, last),
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn activation_output() -> Value {
+        json!({
+            "source_id": 1,
+            "strongest_path": [1, 2, 4],
+            "activated_nodes": [
+                {"node_id": 1, "energy": 1.0, "depth": 0},
+                {"node_id": 2, "energy": 0.8, "depth": 1},
+                {"node_id": 3, "energy": 0.5, "depth": 1},
+                {"node_id": 4, "energy": 0.3, "depth": 2},
+                {"node_id": 5, "energy": 0.01, "depth": 3},
+            ],
+        })
+    }
+
+    fn labels() -> HashMap<u32, String> {
+        [(1, "cat"), (2, "animal"), (3, "dog"), (4, "pet")]
+            .into_iter()
+            .map(|(id, w)| (id, w.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_compose_activation_with_labels() {
+        let composer = ResponseComposer::default();
+        let result = ActionResult::success(activation_output(), 1);
+        let text = composer.compose_with(&result, &labels()).unwrap();
+
+        assert_eq!(
+            text,
+            "\"cat\" brings to mind animal, dog and pet. The strongest link runs cat → animal → pet."
+        );
+    }
+
+    #[test]
+    fn test_compose_without_labels_and_scores() {
+        let composer = ResponseComposer::new(ComposerConfig {
+            max_items: 2,
+            show_scores: true,
+            ..Default::default()
+        });
+        let result = ActionResult::success(activation_output(), 1);
+        let text = composer.compose(&result).unwrap();
+
+        assert!(text.starts_with("\"#1\" brings to mind #2 (0.80) and #3 (0.50)."));
+    }
+
+    #[test]
+    fn test_compose_embedded_labels_and_confidence() {
+        let composer = ResponseComposer::default();
+        let result = ActionResult::success(
+            json!({
+                "activated_nodes": [{"node_id": 9, "energy": 0.6, "label": "tree"}],
+                "confidence": 0.65,
+            }),
+            1,
+        );
+        assert_eq!(
+            composer.compose(&result).unwrap(),
+            "Related: tree. I'm fairly sure (65% confidence)."
+        );
+    }
+
+    #[test]
+    fn test_compose_results_error_and_passthrough() {
+        let composer = ResponseComposer::default();
+
+        let result = ActionResult::success(json!({"results": [["dog", 0.9], ["fox", 0.4]]}), 1);
+        assert_eq!(composer.compose(&result).unwrap(), "Related: dog and fox.");
+
+        let result = ActionResult::success(json!({"response": "hello"}), 1);
+        assert_eq!(composer.compose(&result).unwrap(), "hello");
+
+        let result = ActionResult::failure("timeout.".to_string(), 1);
+        assert_eq!(composer.compose(&result).unwrap(), "I couldn't do that: timeout.");
+
+        let result = ActionResult::success(json!({"state": [0.0, 0.0]}), 1);
+        assert!(composer.compose(&result).is_none());
+    }
+
+    #[test]
+    fn test_custom_templates() {
+        let mut config = ComposerConfig::default();
+        config.templates.activation = "{source} → {items}".to_string();
+        config.templates.and = "и".to_string();
+        assert!(config.validate().is_ok());
+
+        let composer = ResponseComposer::new(config);
+        let result = ActionResult::success(
+            json!({"source_id": 1, "activated_nodes": [{"node_id": 2, "energy": 0.9}, {"node_id": 3, "energy": 0.8}]}),
+            1,
+        );
+        assert_eq!(composer.compose_with(&result, &labels()).unwrap(), "\"cat\" → animal и dog");
+    }
+}
@@ -1,3 +1,4 @@
+use super::composer::{LabelSource, NoLabels, ResponseComposer};
 use super::{FormattedOutput, OutputAdapter, OutputContext, OutputError};
 use crate::action_executor::ActionResult;
 use crate::gateway::Gateway;
@@ -16,6 +17,9 @@ pub struct ConsoleConfig {
 
     /// Use colored output (ANSI codes)
     pub colorize: bool,
+
+    /// Print raw JSON output even when a readable response was composed
+    pub show_raw_output: bool,
 }
 
 impl Default for ConsoleConfig {
@@ -24,6 +28,7 @@ impl Default for ConsoleConfig {
             show_confidence: true,
             max_results: 10,
             colorize: true,
+            show_raw_output: false,
         }
     }
 }
@@ -31,11 +36,29 @@ impl Default for ConsoleConfig {
 /// Console output adapter
 pub struct ConsoleOutputAdapter {
     config: ConsoleConfig,
+    composer: ResponseComposer,
+    labels: Arc<dyn LabelSource>,
 }
 
 impl ConsoleOutputAdapter {
     pub fn new(config: ConsoleConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            composer: ResponseComposer::default(),
+            labels: Arc::new(NoLabels),
+        }
+    }
+
+    /// Use a custom response composer
+    pub fn with_composer(mut self, composer: ResponseComposer) -> Self {
+        self.composer = composer;
+        self
+    }
+
+    /// Resolve node labels (e.g. from the bootstrap library)
+    pub fn with_labels(mut self, labels: Arc<dyn LabelSource>) -> Self {
+        self.labels = labels;
+        self
     }
 }
 
@@ -78,8 +101,13 @@ impl OutputAdapter for ConsoleOutputAdapter {
             output.push_str("   ⚠️  Partial success\n");
         }
 
-        // Show output data if available
-        if !result.output.is_null() {
+        // Readable response, raw data as fallback
+        let composed = self.composer.compose_with(result, self.labels.as_ref());
+        if let Some(text) = &composed {
+            output.push_str(&format!("   💬 {}\n", text));
+        }
+
+        if !result.output.is_null() && (composed.is_none() || self.config.show_raw_output) {
             output.push_str(&format!(
                 "   Output: {}\n",
                 serde_json::to_string_pretty(&result.output)
@@ -175,6 +203,31 @@ mod tests {
         assert!(text.contains("test query"));
     }
 
+    #[tokio::test]
+    async fn test_console_output_composed() {
+        let labels: std::collections::HashMap<u32, String> =
+            [(1, "cat".to_string()), (2, "dog".to_string())].into_iter().collect();
+        let adapter = ConsoleOutputAdapter::new(ConsoleConfig::default()).with_labels(Arc::new(labels));
+
+        let result = ActionResult::success(
+            serde_json::json!({
+                "source_id": 1,
+                "activated_nodes": [{"node_id": 2, "energy": 0.7, "depth": 1}],
+            }),
+            1,
+        );
+        let context = OutputContext::new(
+            1,
+            Some("cat".to_string()),
+            crate::SignalType::SemanticQuery,
+            SignalSource::Console,
+        );
+
+        let text = adapter.format_output(&result, &context).await.unwrap().text.unwrap();
+        assert!(text.contains("\"cat\" brings to mind dog."));
+        assert!(!text.contains("activated_nodes"));
+    }
+
     #[tokio::test]
     async fn test_console_input_adapter() {
         let bootstrap = Arc::new(RwLock::new(BootstrapLibrary::new(
@@ -1,10 +1,28 @@
+pub mod composer;
 pub mod console;
+pub mod output_pipeline;
+pub mod pipe;
+#[cfg(feature = "mqtt")]
+pub mod mqtt;
+#[cfg(feature = "audio")]
+pub mod speech;
+#[cfg(feature = "tts")]
+pub mod tts;
+#[cfg(feature = "onnx")]
+pub mod vision;
 
 use crate::action_executor::ActionResult;
+use crate::correlation::CorrelationId;
 pub use crate::{SignalSource, SignalType};
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::fmt;
 
+pub use output_pipeline::{
+    DispatchReport, OutputFilter, OutputPipeline, OutputRoute, OutputTransform, Redact, Template,
+    Truncate,
+};
+
 /// Error during output operations
 #[derive(Debug)]
 pub enum OutputError {
@@ -39,6 +57,9 @@ pub struct OutputContext {
 
     /// Source of the signal
     pub source: SignalSource,
+
+    /// Interaction the output answers (see `correlation`)
+    pub correlation_id: Option<CorrelationId>,
 }
 
 impl OutputContext {
@@ -53,12 +74,18 @@ impl OutputContext {
             original_input,
             signal_type,
             source,
+            correlation_id: crate::correlation::current(),
         }
     }
+
+    pub fn with_correlation_id(mut self, correlation_id: Option<CorrelationId>) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
 }
 
 /// Formatted output ready for display
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FormattedOutput {
     /// Text representation (for console/logs)
     pub text: Option<String>,
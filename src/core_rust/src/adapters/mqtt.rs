@@ -0,0 +1,339 @@
+//! MQTT input/output adapters (feature `mqtt`)
+//!
+//! MqttInputAdapter subscribes to topics and injects payloads into Gateway:
+//! - `[f32; 8]` array or `{"state": [...], "label": "..."}` → InputSignal::DirectState
+//! - `{"text": "..."}` or plain UTF-8 → InputSignal::Text
+//!
+//! MqttOutputAdapter publishes ActionResults as JSON to a single topic.
+
+use super::{FormattedOutput, OutputAdapter, OutputContext, OutputError};
+use crate::action_executor::ActionResult;
+use crate::gateway::Gateway;
+use crate::{InputSignal, SignalSource};
+use rumqttc::{AsyncClient, Event, EventLoop, MqttOptions, Packet, QoS};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Configuration for MQTT adapters
+#[derive(Debug, Clone)]
+pub struct MqttConfig {
+    /// Broker host
+    pub host: String,
+
+    /// Broker port
+    pub port: u16,
+
+    /// Client ID (must be unique per broker)
+    pub client_id: String,
+
+    /// Optional credentials (username, password)
+    pub credentials: Option<(String, String)>,
+
+    /// Topics to subscribe to (wildcards allowed)
+    pub input_topics: Vec<String>,
+
+    /// Topic for publishing action results
+    pub output_topic: String,
+
+    /// Quality of service level (0, 1 or 2)
+    pub qos: u8,
+
+    /// Keep-alive interval in seconds
+    pub keep_alive_secs: u64,
+
+    /// Client request channel capacity
+    pub channel_capacity: usize,
+}
+
+impl Default for MqttConfig {
+    fn default() -> Self {
+        Self {
+            host: "localhost".to_string(),
+            port: 1883,
+            client_id: "neurograph".to_string(),
+            credentials: None,
+            input_topics: vec!["neurograph/in/#".to_string()],
+            output_topic: "neurograph/out".to_string(),
+            qos: 1,
+            keep_alive_secs: 30,
+            channel_capacity: 100,
+        }
+    }
+}
+
+impl MqttConfig {
+    /// Validate configuration
+    pub fn validate(&self) -> Result<(), String> {
+        if self.host.is_empty() {
+            return Err("host must not be empty".to_string());
+        }
+        if self.client_id.is_empty() {
+            return Err("client_id must not be empty".to_string());
+        }
+        if self.output_topic.is_empty() || self.output_topic.contains(['#', '+']) {
+            return Err("output_topic must be a non-empty topic without wildcards".to_string());
+        }
+        if self.qos > 2 {
+            return Err("qos must be 0, 1 or 2".to_string());
+        }
+        if self.keep_alive_secs < 5 {
+            return Err("keep_alive_secs must be >= 5".to_string());
+        }
+        Ok(())
+    }
+
+    fn qos_level(&self) -> QoS {
+        match self.qos {
+            0 => QoS::AtMostOnce,
+            1 => QoS::AtLeastOnce,
+            _ => QoS::ExactlyOnce,
+        }
+    }
+
+    fn options(&self) -> MqttOptions {
+        let mut options = MqttOptions::new(&self.client_id, &self.host, self.port);
+        options.set_keep_alive(Duration::from_secs(self.keep_alive_secs));
+        if let Some((user, password)) = &self.credentials {
+            options.set_credentials(user, password);
+        }
+        options
+    }
+}
+
+/// Convert an MQTT payload into an InputSignal
+pub fn payload_to_signal(topic: &str, payload: &[u8]) -> Result<InputSignal, String> {
+    let text = std::str::from_utf8(payload)
+        .map_err(|_| format!("Non UTF-8 payload on topic '{}'", topic))?
+        .trim();
+
+    if text.is_empty() {
+        return Err(format!("Empty payload on topic '{}'", topic));
+    }
+
+    let metadata = Some(json!({ "topic": topic }));
+
+    match serde_json::from_str::<Value>(text) {
+        Ok(Value::Array(values)) => Ok(InputSignal::DirectState {
+            state: parse_state(&values)?,
+            label: Some(topic.to_string()),
+        }),
+        Ok(Value::Object(obj)) => {
+            if let Some(Value::Array(values)) = obj.get("state") {
+                let label = obj
+                    .get("label")
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string())
+                    .or_else(|| Some(topic.to_string()));
+                Ok(InputSignal::DirectState {
+                    state: parse_state(values)?,
+                    label,
+                })
+            } else if let Some(Value::String(content)) = obj.get("text") {
+                Ok(InputSignal::Text {
+                    content: content.clone(),
+                    source: SignalSource::Mqtt,
+                    metadata,
+                })
+            } else {
+                Err(format!("JSON payload on topic '{}' has neither 'state' nor 'text'", topic))
+            }
+        }
+        // Plain strings (and JSON scalars) are treated as text
+        _ => Ok(InputSignal::Text {
+            content: text.to_string(),
+            source: SignalSource::Mqtt,
+            metadata,
+        }),
+    }
+}
+
+fn parse_state(values: &[Value]) -> Result<[f32; 8], String> {
+    if values.len() != 8 {
+        return Err(format!("State must have 8 dimensions, got {}", values.len()));
+    }
+
+    let mut state = [0.0f32; 8];
+    for (i, v) in values.iter().enumerate() {
+        state[i] = v
+            .as_f64()
+            .ok_or_else(|| format!("State dimension {} is not a number", i))? as f32;
+    }
+    Ok(state)
+}
+
+/// MQTT input adapter
+pub struct MqttInputAdapter {
+    gateway: Arc<Gateway>,
+    config: MqttConfig,
+}
+
+impl MqttInputAdapter {
+    pub fn new(gateway: Arc<Gateway>, config: MqttConfig) -> Self {
+        Self { gateway, config }
+    }
+
+    /// Convert payload and inject into Gateway
+    pub async fn handle_message(&self, topic: &str, payload: &[u8]) -> Result<u64, String> {
+        let signal = payload_to_signal(topic, payload)?;
+
+        let (receipt, _receiver) = self
+            .gateway
+            .inject(signal)
+            .await
+            .map_err(|e| format!("Gateway error: {}", e))?;
+
+        Ok(receipt.signal_id)
+    }
+
+    /// Connect, subscribe to input topics and process messages until the connection fails
+    ///
+    /// Malformed payloads are logged and skipped.
+    pub async fn run(&self) -> Result<(), String> {
+        self.config.validate()?;
+
+        let (client, mut eventloop) = AsyncClient::new(self.config.options(), self.config.channel_capacity);
+        for topic in &self.config.input_topics {
+            client
+                .subscribe(topic, self.config.qos_level())
+                .await
+                .map_err(|e| format!("Subscribe to '{}' failed: {}", topic, e))?;
+        }
+
+        loop {
+            match eventloop.poll().await {
+                Ok(Event::Incoming(Packet::Publish(publish))) => {
+                    if let Err(e) = self.handle_message(&publish.topic, &publish.payload).await {
+                        eprintln!("[MQTT] {}", e);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => return Err(format!("MQTT connection error: {}", e)),
+            }
+        }
+    }
+}
+
+/// MQTT output adapter
+///
+/// The returned EventLoop from [`MqttOutputAdapter::connect`] must be polled
+/// (e.g. in a spawned task) for publishes to reach the broker.
+pub struct MqttOutputAdapter {
+    client: AsyncClient,
+    config: MqttConfig,
+}
+
+impl MqttOutputAdapter {
+    /// Create adapter over an existing client
+    pub fn new(client: AsyncClient, config: MqttConfig) -> Self {
+        Self { client, config }
+    }
+
+    /// Create client and adapter from config
+    pub fn connect(config: MqttConfig) -> Result<(Self, EventLoop), String> {
+        config.validate()?;
+        let (client, eventloop) = AsyncClient::new(config.options(), config.channel_capacity);
+        Ok((Self::new(client, config), eventloop))
+    }
+}
+
+#[async_trait::async_trait]
+impl OutputAdapter for MqttOutputAdapter {
+    fn name(&self) -> &str {
+        "mqtt"
+    }
+
+    async fn format_output(
+        &self,
+        result: &ActionResult,
+        context: &OutputContext,
+    ) -> Result<FormattedOutput, OutputError> {
+        Ok(FormattedOutput::data(json!({
+            "signal_id": context.signal_id,
+            "input": context.original_input,
+            "success": result.success,
+            "output": result.output,
+            "error": result.error,
+            "duration_ms": result.duration_ms,
+        })))
+    }
+
+    async fn send(&self, output: FormattedOutput) -> Result<(), OutputError> {
+        let payload = match (output.data, output.text) {
+            (Some(data), _) => serde_json::to_vec(&data)
+                .map_err(|e| OutputError::FormatError(e.to_string()))?,
+            (None, Some(text)) => text.into_bytes(),
+            (None, None) => return Ok(()),
+        };
+
+        self.client
+            .publish(&self.config.output_topic, self.config.qos_level(), false, payload)
+            .await
+            .map_err(|e| OutputError::SendFailed(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mqtt_config_validate() {
+        assert!(MqttConfig::default().validate().is_ok());
+
+        let mut config = MqttConfig::default();
+        config.output_topic = "out/#".to_string();
+        assert!(config.validate().is_err());
+
+        let mut config = MqttConfig::default();
+        config.qos = 3;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_payload_to_direct_state() {
+        let signal = payload_to_signal("sensors/imu", b"[0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8]").unwrap();
+        match signal {
+            InputSignal::DirectState { state, label } => {
+                assert_eq!(state[7], 0.8);
+                assert_eq!(label.as_deref(), Some("sensors/imu"));
+            }
+            _ => panic!("Expected DirectState"),
+        }
+
+        let signal = payload_to_signal("x", br#"{"state": [0,0,0,0,0,0,0,1], "label": "bump"}"#).unwrap();
+        assert!(matches!(signal, InputSignal::DirectState { label: Some(ref l), .. } if l == "bump"));
+
+        assert!(payload_to_signal("x", b"[1, 2, 3]").is_err());
+    }
+
+    #[test]
+    fn test_payload_to_text() {
+        let signal = payload_to_signal("chat", b"hello robot").unwrap();
+        assert!(matches!(signal, InputSignal::Text { ref content, source: SignalSource::Mqtt, .. } if content == "hello robot"));
+
+        let signal = payload_to_signal("chat", br#"{"text": "turn left"}"#).unwrap();
+        assert!(matches!(signal, InputSignal::Text { ref content, .. } if content == "turn left"));
+
+        assert!(payload_to_signal("chat", b"").is_err());
+        assert!(payload_to_signal("chat", &[0xff, 0xfe]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mqtt_output_format() {
+        let (adapter, _eventloop) = MqttOutputAdapter::connect(MqttConfig::default()).unwrap();
+
+        let result = ActionResult::success(json!({"answer": 42}), 3);
+        let context = OutputContext::new(
+            7,
+            Some("question".to_string()),
+            crate::SignalType::SemanticQuery,
+            SignalSource::Mqtt,
+        );
+
+        let formatted = adapter.format_output(&result, &context).await.unwrap();
+        let data = formatted.data.unwrap();
+        assert_eq!(data["signal_id"], 7);
+        assert_eq!(data["output"]["answer"], 42);
+    }
+}
@@ -0,0 +1,388 @@
+//! Output pipeline: one result, several adapters
+//!
+//! An [`OutputPipeline`] holds any number of [`OutputRoute`]s. Each route
+//! pairs an [`OutputAdapter`] with an [`OutputFilter`] (which signal
+//! sources and types it wants) and a chain of [`OutputTransform`] stages
+//! applied to the formatted output before it is sent:
+//!
+//! ```text
+//! result ─┬─ console   (all)            → send
+//!         ├─ websocket (RestApi, WS)    → Truncate(500) → send
+//!         └─ webhook   (ActionRequest)  → Redact → Template → send
+//! ```
+//!
+//! A failing adapter does not stop the others; [`OutputPipeline::dispatch`]
+//! reports the outcome per route.
+
+use super::{FormattedOutput, OutputAdapter, OutputContext, OutputError};
+use crate::action_executor::ActionResult;
+use crate::{SignalSource, SignalType};
+use parking_lot::RwLock;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// Which results a route receives; empty lists match everything
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OutputFilter {
+    pub sources: Vec<SignalSource>,
+    pub signal_types: Vec<SignalType>,
+}
+
+impl OutputFilter {
+    /// Everything
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    pub fn with_source(mut self, source: SignalSource) -> Self {
+        self.sources.push(source);
+        self
+    }
+
+    pub fn with_signal_type(mut self, signal_type: SignalType) -> Self {
+        self.signal_types.push(signal_type);
+        self
+    }
+
+    pub fn matches(&self, context: &OutputContext) -> bool {
+        (self.sources.is_empty() || self.sources.contains(&context.source))
+            && (self.signal_types.is_empty() || self.signal_types.contains(&context.signal_type))
+    }
+}
+
+/// A transformation stage applied to formatted output
+pub trait OutputTransform: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn apply(&self, output: FormattedOutput, context: &OutputContext) -> FormattedOutput;
+}
+
+/// Replace sensitive text and the values of sensitive data fields
+///
+/// Terms are matched literally (case-sensitive) in the text and in every
+/// string of the data; fields named in `keys` are replaced wholesale.
+#[derive(Debug, Clone)]
+pub struct Redact {
+    pub terms: Vec<String>,
+    pub keys: Vec<String>,
+    pub replacement: String,
+}
+
+impl Redact {
+    pub fn new() -> Self {
+        Self {
+            terms: Vec::new(),
+            keys: Vec::new(),
+            replacement: "[redacted]".to_string(),
+        }
+    }
+
+    pub fn term(mut self, term: &str) -> Self {
+        self.terms.push(term.to_string());
+        self
+    }
+
+    pub fn key(mut self, key: &str) -> Self {
+        self.keys.push(key.to_string());
+        self
+    }
+
+    fn redact_text(&self, text: &str) -> String {
+        self.terms
+            .iter()
+            .filter(|term| !term.is_empty())
+            .fold(text.to_string(), |text, term| text.replace(term.as_str(), &self.replacement))
+    }
+
+    fn redact_value(&self, value: &mut Value) {
+        match value {
+            Value::String(s) => *s = self.redact_text(s),
+            Value::Array(items) => items.iter_mut().for_each(|item| self.redact_value(item)),
+            Value::Object(fields) => {
+                for (key, field) in fields.iter_mut() {
+                    if self.keys.contains(key) {
+                        *field = Value::String(self.replacement.clone());
+                    } else {
+                        self.redact_value(field);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Default for Redact {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OutputTransform for Redact {
+    fn name(&self) -> &str {
+        "redact"
+    }
+
+    fn apply(&self, mut output: FormattedOutput, _context: &OutputContext) -> FormattedOutput {
+        output.text = output.text.map(|text| self.redact_text(&text));
+        if let Some(data) = output.data.as_mut() {
+            self.redact_value(data);
+        }
+        output
+    }
+}
+
+/// Cut the text to at most `max_chars` characters (ellipsis included)
+#[derive(Debug, Clone)]
+pub struct Truncate {
+    pub max_chars: usize,
+}
+
+impl Truncate {
+    pub fn new(max_chars: usize) -> Self {
+        Self { max_chars }
+    }
+}
+
+impl OutputTransform for Truncate {
+    fn name(&self) -> &str {
+        "truncate"
+    }
+
+    fn apply(&self, mut output: FormattedOutput, _context: &OutputContext) -> FormattedOutput {
+        if let Some(text) = output.text.as_mut() {
+            if text.chars().count() > self.max_chars {
+                let kept: String = text.chars().take(self.max_chars.saturating_sub(1)).collect();
+                *text = format!("{}…", kept);
+            }
+        }
+        output
+    }
+}
+
+/// Render the text through a template
+///
+/// Placeholders: `{text}`, `{signal_id}`, `{input}`, `{source}`,
+/// `{signal_type}`. Outputs without text get `{text}` = "".
+#[derive(Debug, Clone)]
+pub struct Template {
+    pub template: String,
+}
+
+impl Template {
+    pub fn new(template: &str) -> Self {
+        Self { template: template.to_string() }
+    }
+}
+
+impl OutputTransform for Template {
+    fn name(&self) -> &str {
+        "template"
+    }
+
+    fn apply(&self, mut output: FormattedOutput, context: &OutputContext) -> FormattedOutput {
+        let text = self
+            .template
+            .replace("{signal_id}", &context.signal_id.to_string())
+            .replace("{input}", context.original_input.as_deref().unwrap_or(""))
+            .replace("{source}", &format!("{:?}", context.source))
+            .replace("{signal_type}", &format!("{:?}", context.signal_type))
+            // Last, so placeholders inside the text itself stay literal
+            .replace("{text}", output.text.as_deref().unwrap_or(""));
+        output.text = Some(text);
+        output
+    }
+}
+
+/// An adapter with its filter and transformation stages
+pub struct OutputRoute {
+    adapter: Arc<dyn OutputAdapter>,
+    filter: OutputFilter,
+    transforms: Vec<Arc<dyn OutputTransform>>,
+}
+
+impl OutputRoute {
+    pub fn new(adapter: Arc<dyn OutputAdapter>) -> Self {
+        Self {
+            adapter,
+            filter: OutputFilter::all(),
+            transforms: Vec::new(),
+        }
+    }
+
+    pub fn with_filter(mut self, filter: OutputFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Append a stage; stages run in the order they were added
+    pub fn with_transform<T: OutputTransform + 'static>(mut self, transform: T) -> Self {
+        self.transforms.push(Arc::new(transform));
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        self.adapter.name()
+    }
+
+    /// Format, transform and send one result
+    async fn deliver(&self, result: &ActionResult, context: &OutputContext) -> Result<(), OutputError> {
+        let formatted = self.adapter.format_output(result, context).await?;
+        let output = self
+            .transforms
+            .iter()
+            .fold(formatted, |output, transform| transform.apply(output, context));
+        self.adapter.send(output).await
+    }
+}
+
+/// Outcome of one dispatch
+#[derive(Debug, Default)]
+pub struct DispatchReport {
+    /// Adapters that sent the result
+    pub delivered: Vec<String>,
+    /// Adapters whose filter did not match
+    pub skipped: Vec<String>,
+    /// Adapters that failed, with the error
+    pub failed: Vec<(String, OutputError)>,
+}
+
+/// Fan-out of results to several output adapters
+#[derive(Default)]
+pub struct OutputPipeline {
+    routes: RwLock<Vec<Arc<OutputRoute>>>,
+}
+
+impl OutputPipeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a route; an existing route with the same adapter name is replaced
+    pub fn register(&self, route: OutputRoute) {
+        let mut routes = self.routes.write();
+        routes.retain(|r| r.name() != route.name());
+        routes.push(Arc::new(route));
+    }
+
+    /// Remove the route of an adapter; false if there was none
+    pub fn remove(&self, name: &str) -> bool {
+        let mut routes = self.routes.write();
+        let before = routes.len();
+        routes.retain(|r| r.name() != name);
+        routes.len() != before
+    }
+
+    /// Registered adapter names, in registration order
+    pub fn adapters(&self) -> Vec<String> {
+        self.routes.read().iter().map(|r| r.name().to_string()).collect()
+    }
+
+    /// Send a result through every matching route
+    pub async fn dispatch(&self, result: &ActionResult, context: &OutputContext) -> DispatchReport {
+        let routes = self.routes.read().clone();
+        let mut report = DispatchReport::default();
+        for route in routes {
+            let name = route.name().to_string();
+            if !route.filter.matches(context) {
+                report.skipped.push(name);
+                continue;
+            }
+            match route.deliver(result, context).await {
+                Ok(()) => report.delivered.push(name),
+                Err(e) => {
+                    tracing::warn!(adapter = %name, error = %e, "Output adapter failed");
+                    report.failed.push((name, e));
+                }
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use serde_json::json;
+
+    /// Records what it is asked to send
+    struct Recorder {
+        name: &'static str,
+        sent: Mutex<Vec<FormattedOutput>>,
+        fail: bool,
+    }
+
+    impl Recorder {
+        fn new(name: &'static str) -> Arc<Self> {
+            Arc::new(Self { name, sent: Mutex::new(Vec::new()), fail: false })
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl OutputAdapter for Recorder {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn format_output(
+            &self,
+            result: &ActionResult,
+            _context: &OutputContext,
+        ) -> Result<FormattedOutput, OutputError> {
+            Ok(FormattedOutput::both(result.output["answer"].as_str().unwrap_or("").to_string(), result.output.clone()))
+        }
+
+        async fn send(&self, output: FormattedOutput) -> Result<(), OutputError> {
+            if self.fail {
+                return Err(OutputError::SendFailed("offline".to_string()));
+            }
+            self.sent.lock().push(output);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_filters_and_transforms() {
+        let console = Recorder::new("console");
+        let websocket = Recorder::new("websocket");
+        let webhook = Recorder::new("webhook");
+        let broken = Arc::new(Recorder { name: "broken", sent: Mutex::new(Vec::new()), fail: true });
+
+        let pipeline = OutputPipeline::new();
+        pipeline.register(OutputRoute::new(console.clone()));
+        pipeline.register(
+            OutputRoute::new(websocket.clone())
+                .with_filter(OutputFilter::all().with_source(SignalSource::WebSocket))
+                .with_transform(Truncate::new(5)),
+        );
+        pipeline.register(
+            OutputRoute::new(webhook.clone())
+                .with_filter(OutputFilter::all().with_signal_type(SignalType::SemanticQuery))
+                .with_transform(Redact::new().term("Alice").key("email"))
+                .with_transform(Template::new("#{signal_id} {source}: {text}")),
+        );
+        pipeline.register(OutputRoute::new(broken));
+        assert_eq!(pipeline.adapters(), vec!["console", "websocket", "webhook", "broken"]);
+
+        let result = ActionResult::success(json!({"answer": "Alice likes cats", "email": "a@b.c"}), 1);
+        let context = OutputContext::new(7, None, SignalType::SemanticQuery, SignalSource::RestApi);
+        let report = pipeline.dispatch(&result, &context).await;
+        assert_eq!(report.delivered, vec!["console", "webhook"]);
+        assert_eq!(report.skipped, vec!["websocket"]);
+        assert_eq!(report.failed.len(), 1);
+
+        assert_eq!(console.sent.lock()[0].text.as_deref(), Some("Alice likes cats"));
+        let hook = webhook.sent.lock()[0].clone();
+        assert_eq!(hook.text.as_deref(), Some("#7 RestApi: [redacted] likes cats"));
+        assert_eq!(hook.data.unwrap(), json!({"answer": "[redacted] likes cats", "email": "[redacted]"}));
+
+        let context = OutputContext::new(8, None, SignalType::ActionRequest, SignalSource::WebSocket);
+        pipeline.dispatch(&result, &context).await;
+        assert_eq!(websocket.sent.lock()[0].text.as_deref(), Some("Alic…"));
+        assert_eq!(webhook.sent.lock().len(), 1);
+
+        assert!(pipeline.remove("broken"));
+        assert!(!pipeline.remove("broken"));
+    }
+}
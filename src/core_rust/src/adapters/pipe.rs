@@ -0,0 +1,280 @@
+//! Pipe mode: newline-delimited JSON over stdin/stdout
+//!
+//! Each input line is a serialized InputSignal, e.g.
+//! `{"Text":{"content":"hello","source":"Console","metadata":null}}`.
+//! Each output line is a serialized FormattedOutput. Useful for shell
+//! pipelines and integration tests without the REST server.
+
+use super::{FormattedOutput, OutputAdapter, OutputContext, OutputError};
+use crate::action_executor::ActionResult;
+use crate::gateway::Gateway;
+use crate::{InputSignal, SignalType};
+use serde_json::json;
+use std::io::{self, BufRead, Write};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Configuration for pipe mode
+#[derive(Debug, Clone)]
+pub struct PipeConfig {
+    /// How long to wait for each result (milliseconds)
+    pub response_timeout_ms: u64,
+
+    /// Wait for the ActionResult; if false only the receipt is written
+    pub wait_for_result: bool,
+}
+
+impl Default for PipeConfig {
+    fn default() -> Self {
+        Self {
+            response_timeout_ms: 5000,
+            wait_for_result: true,
+        }
+    }
+}
+
+/// Summary of a pipe session
+#[derive(Debug, Clone, Default)]
+pub struct PipeStats {
+    /// Non-empty lines read
+    pub lines_read: u64,
+
+    /// Lines that produced a result
+    pub processed: u64,
+
+    /// Lines that failed to parse, inject or complete
+    pub errors: u64,
+}
+
+/// JSON-lines output adapter
+///
+/// Writes one serialized FormattedOutput per line to stdout.
+pub struct PipeOutputAdapter;
+
+impl PipeOutputAdapter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Serialize output as a single JSON line
+    pub fn to_line(output: &FormattedOutput) -> Result<String, OutputError> {
+        serde_json::to_string(output).map_err(|e| OutputError::FormatError(e.to_string()))
+    }
+}
+
+impl Default for PipeOutputAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait::async_trait]
+impl OutputAdapter for PipeOutputAdapter {
+    fn name(&self) -> &str {
+        "pipe"
+    }
+
+    async fn format_output(
+        &self,
+        result: &ActionResult,
+        context: &OutputContext,
+    ) -> Result<FormattedOutput, OutputError> {
+        Ok(FormattedOutput::data(json!({
+            "signal_id": context.signal_id,
+            "success": result.success,
+            "output": result.output,
+            "error": result.error,
+            "duration_ms": result.duration_ms,
+        })))
+    }
+
+    async fn send(&self, output: FormattedOutput) -> Result<(), OutputError> {
+        let line = Self::to_line(&output)?;
+        let mut stdout = io::stdout().lock();
+        writeln!(stdout, "{}", line)
+            .and_then(|_| stdout.flush())
+            .map_err(|e| OutputError::IoError(e.to_string()))
+    }
+}
+
+/// JSON-lines input adapter
+pub struct PipeInputAdapter {
+    gateway: Arc<Gateway>,
+    output: PipeOutputAdapter,
+    config: PipeConfig,
+}
+
+impl PipeInputAdapter {
+    pub fn new(gateway: Arc<Gateway>, config: PipeConfig) -> Self {
+        Self {
+            gateway,
+            output: PipeOutputAdapter::new(),
+            config,
+        }
+    }
+
+    /// Process a single JSON line into a FormattedOutput
+    ///
+    /// Errors are reported in-band as `{"error": "..."}` so a bad line
+    /// never stops the pipeline.
+    pub async fn process_line(&self, line: &str) -> Result<FormattedOutput, FormattedOutput> {
+        let signal: InputSignal = serde_json::from_str(line)
+            .map_err(|e| error_output(None, format!("Invalid InputSignal JSON: {}", e)))?;
+
+        let original_input = match &signal {
+            InputSignal::Text { content, .. } => Some(content.clone()),
+            _ => None,
+        };
+        let signal_type = signal_type_of(&signal);
+        let source = match &signal {
+            InputSignal::Text { source, .. } => *source,
+            _ => crate::SignalSource::Unknown,
+        };
+
+        let (receipt, receiver) = self
+            .gateway
+            .inject(signal)
+            .await
+            .map_err(|e| error_output(None, format!("Gateway error: {}", e)))?;
+
+        if !self.config.wait_for_result {
+            return Ok(FormattedOutput::data(json!({
+                "signal_id": receipt.signal_id,
+                "queue_position": receipt.queue_position,
+                "correlation_id": receipt.correlation_id,
+            })));
+        }
+
+        let timeout = Duration::from_millis(self.config.response_timeout_ms);
+        let result = match tokio::time::timeout(timeout, receiver).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => return Err(error_output(Some(receipt.signal_id), "Result channel closed".to_string())),
+            Err(_) => return Err(error_output(Some(receipt.signal_id), "Timed out waiting for result".to_string())),
+        };
+
+        let context = OutputContext::new(receipt.signal_id, original_input, signal_type, source)
+            .with_correlation_id(receipt.correlation_id);
+        self.output
+            .format_output(&result, &context)
+            .await
+            .map_err(|e| error_output(Some(receipt.signal_id), e.to_string()))
+    }
+
+    /// Read signals from `reader` until EOF, writing one output line per input line
+    pub async fn run<R: BufRead, W: Write>(&self, reader: R, mut writer: W) -> io::Result<PipeStats> {
+        let mut stats = PipeStats::default();
+
+        for line in reader.lines() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            stats.lines_read += 1;
+
+            let output = match self.process_line(line).await {
+                Ok(output) => {
+                    stats.processed += 1;
+                    output
+                }
+                Err(output) => {
+                    stats.errors += 1;
+                    output
+                }
+            };
+
+            let json = PipeOutputAdapter::to_line(&output)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+            writeln!(writer, "{}", json)?;
+            writer.flush()?;
+        }
+
+        Ok(stats)
+    }
+
+    /// Run pipe mode on process stdin/stdout
+    pub async fn run_stdio(&self) -> io::Result<PipeStats> {
+        let stdin = io::stdin();
+        self.run(stdin.lock(), io::stdout()).await
+    }
+}
+
+fn error_output(signal_id: Option<u64>, message: String) -> FormattedOutput {
+    FormattedOutput::data(json!({
+        "signal_id": signal_id,
+        "success": false,
+        "error": message,
+    }))
+}
+
+fn signal_type_of(signal: &InputSignal) -> SignalType {
+    match signal {
+        InputSignal::Text { .. } => SignalType::SemanticQuery,
+        InputSignal::DirectState { .. } | InputSignal::DirectToken { .. } => SignalType::ActionRequest,
+        InputSignal::Command { .. } | InputSignal::SystemTick { .. } => SignalType::SystemSignal,
+        InputSignal::Feedback { .. } => SignalType::FeedbackSignal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootstrap::{BootstrapConfig, BootstrapLibrary};
+    use crate::GatewayConfig;
+    use parking_lot::RwLock;
+    use tokio::sync::mpsc;
+
+    fn gateway() -> (Arc<Gateway>, mpsc::Receiver<crate::ProcessedSignal>) {
+        let bootstrap = Arc::new(RwLock::new(BootstrapLibrary::new(BootstrapConfig::default())));
+        let (tx, rx) = mpsc::channel(100);
+        (Arc::new(Gateway::new(tx, bootstrap, GatewayConfig::default())), rx)
+    }
+
+    #[tokio::test]
+    async fn test_pipe_round_trip() {
+        let (gateway, mut rx) = gateway();
+
+        // Minimal consumer: echo the signal ID back as the result
+        let consumer = Arc::clone(&gateway);
+        tokio::spawn(async move {
+            while let Some(signal) = rx.recv().await {
+                let result = ActionResult::success(json!({"echo": signal.signal_id}), 0);
+                consumer.complete_request(signal.signal_id, result);
+            }
+        });
+
+        let adapter = PipeInputAdapter::new(gateway, PipeConfig::default());
+        let input = "{\"DirectState\":{\"state\":[0,0,0,0,0,0,0,1],\"label\":null}}\n\nnot json\n";
+        let mut out = Vec::new();
+
+        let stats = adapter.run(input.as_bytes(), &mut out).await.unwrap();
+        assert_eq!(stats.lines_read, 2);
+        assert_eq!(stats.processed, 1);
+        assert_eq!(stats.errors, 1);
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["data"]["success"], true);
+        assert!(lines[1]["data"]["error"].as_str().unwrap().contains("Invalid InputSignal"));
+    }
+
+    #[tokio::test]
+    async fn test_pipe_receipt_only() {
+        let (gateway, _rx) = gateway();
+        let config = PipeConfig {
+            wait_for_result: false,
+            ..Default::default()
+        };
+        let adapter = PipeInputAdapter::new(gateway, config);
+
+        let output = adapter
+            .process_line(r#"{"DirectState":{"state":[0,0,0,0,0,0,0,0],"label":"x"}}"#)
+            .await
+            .unwrap();
+        assert!(output.data.unwrap()["signal_id"].is_u64());
+    }
+}
@@ -0,0 +1,596 @@
+//! Speech input adapter (feature `audio`)
+//!
+//! SpeechInputAdapter listens to the microphone and injects what is said
+//! into the Gateway as `InputSignal::Text` with `SignalSource::Voice`:
+//!
+//! ```text
+//! recorder (arecord, raw s16le mono) → frames → VAD → utterance → STT → Gateway
+//! ```
+//!
+//! - Audio is read from a recorder program's stdout, so any capture tool
+//!   that can write raw 16-bit PCM works (arecord, sox `rec`, ffmpeg)
+//! - [`VoiceActivityDetector`] cuts the stream into utterances by frame
+//!   energy, with a hangover so short pauses do not split a sentence
+//! - Transcription is pluggable via [`SpeechToText`]; [`CommandStt`] runs a
+//!   local engine (whisper.cpp, vosk) on a WAV file, [`HttpStt`] posts the
+//!   WAV to a service
+//!
+//! Listening is started and stopped at runtime; [`register_terminal_commands`]
+//! exposes `voice start|stop|status` in the desktop Terminal.
+
+use crate::gateway::Gateway;
+use crate::terminal::{CommandError, CommandOutput, CommandSpec, Interpreter};
+use crate::{InputSignal, SignalSource};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::Read;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Voice activity detection parameters
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VadConfig {
+    /// Analysis frame length
+    pub frame_ms: u32,
+
+    /// RMS level (0..1) above which a frame counts as speech
+    pub energy_threshold: f32,
+
+    /// Shorter bursts (clicks, coughs) are dropped
+    pub min_speech_ms: u32,
+
+    /// Silence that ends an utterance
+    pub max_silence_ms: u32,
+
+    /// Utterances are cut at this length
+    pub max_utterance_ms: u32,
+}
+
+impl Default for VadConfig {
+    fn default() -> Self {
+        Self {
+            frame_ms: 30,
+            energy_threshold: 0.02,
+            min_speech_ms: 250,
+            max_silence_ms: 700,
+            max_utterance_ms: 15_000,
+        }
+    }
+}
+
+impl VadConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(10..=100).contains(&self.frame_ms) {
+            return Err("frame_ms must be in 10..=100".to_string());
+        }
+        if !(0.0..1.0).contains(&self.energy_threshold) {
+            return Err("energy_threshold must be in [0, 1)".to_string());
+        }
+        if self.max_utterance_ms <= self.min_speech_ms {
+            return Err("max_utterance_ms must exceed min_speech_ms".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Configuration for speech input
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpeechConfig {
+    /// Capture program writing raw signed 16-bit little-endian mono PCM to stdout
+    pub recorder: Vec<String>,
+
+    /// Sample rate the recorder is set to
+    pub sample_rate: u32,
+
+    pub vad: VadConfig,
+}
+
+impl Default for SpeechConfig {
+    fn default() -> Self {
+        Self {
+            recorder: ["arecord", "-q", "-t", "raw", "-f", "S16_LE", "-c", "1", "-r", "16000"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            sample_rate: 16_000,
+            vad: VadConfig::default(),
+        }
+    }
+}
+
+impl SpeechConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.recorder.first().is_none_or(|program| program.is_empty()) {
+            return Err("recorder program must not be empty".to_string());
+        }
+        if !(8_000..=48_000).contains(&self.sample_rate) {
+            return Err("sample_rate must be in 8000..=48000".to_string());
+        }
+        self.vad.validate()
+    }
+
+    fn frame_len(&self) -> usize {
+        (self.sample_rate * self.vad.frame_ms / 1000) as usize
+    }
+}
+
+/// A stretch of speech cut out by the VAD
+#[derive(Debug, Clone)]
+pub struct Utterance {
+    pub samples: Vec<f32>,
+    pub sample_rate: u32,
+}
+
+impl Utterance {
+    pub fn duration_ms(&self) -> u64 {
+        self.samples.len() as u64 * 1000 / self.sample_rate as u64
+    }
+}
+
+/// Energy-based voice activity detector
+pub struct VoiceActivityDetector {
+    config: VadConfig,
+    sample_rate: u32,
+    frame_len: usize,
+    /// Samples not yet forming a whole frame
+    pending: Vec<f32>,
+    /// Current utterance, including trailing silence
+    speech: Vec<f32>,
+    speech_frames: u32,
+    silent_frames: u32,
+}
+
+impl VoiceActivityDetector {
+    pub fn new(config: VadConfig, sample_rate: u32) -> Self {
+        let frame_len = (sample_rate * config.frame_ms / 1000) as usize;
+        Self {
+            config,
+            sample_rate,
+            frame_len,
+            pending: Vec::new(),
+            speech: Vec::new(),
+            speech_frames: 0,
+            silent_frames: 0,
+        }
+    }
+
+    /// Feed samples; returns utterances completed by them
+    pub fn push(&mut self, samples: &[f32]) -> Vec<Utterance> {
+        self.pending.extend_from_slice(samples);
+        let mut utterances = Vec::new();
+        let frames = self.pending.len() / self.frame_len;
+        let rest = self.pending.split_off(frames * self.frame_len);
+        let pending = std::mem::replace(&mut self.pending, rest);
+
+        for frame in pending.chunks(self.frame_len) {
+            let rms = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
+            let voiced = rms >= self.config.energy_threshold;
+
+            if voiced {
+                self.speech_frames += 1;
+                self.silent_frames = 0;
+            } else if self.speech.is_empty() {
+                continue;
+            } else {
+                self.silent_frames += 1;
+            }
+            self.speech.extend_from_slice(frame);
+
+            let speech_ms = self.speech_frames * self.config.frame_ms;
+            let silence_ms = self.silent_frames * self.config.frame_ms;
+            let total_ms = (self.speech.len() / self.frame_len) as u32 * self.config.frame_ms;
+            if silence_ms >= self.config.max_silence_ms || total_ms >= self.config.max_utterance_ms {
+                if let Some(utterance) = self.finish(speech_ms) {
+                    utterances.push(utterance);
+                }
+            }
+        }
+        utterances
+    }
+
+    /// End the current utterance (e.g. when capture stops)
+    pub fn flush(&mut self) -> Option<Utterance> {
+        self.pending.clear();
+        self.finish(self.speech_frames * self.config.frame_ms)
+    }
+
+    fn finish(&mut self, speech_ms: u32) -> Option<Utterance> {
+        let trailing = self.silent_frames as usize * self.frame_len;
+        let mut samples = std::mem::take(&mut self.speech);
+        samples.truncate(samples.len() - trailing.min(samples.len()));
+        self.speech_frames = 0;
+        self.silent_frames = 0;
+        (speech_ms >= self.config.min_speech_ms).then_some(Utterance {
+            samples,
+            sample_rate: self.sample_rate,
+        })
+    }
+}
+
+/// Encode mono samples as a 16-bit PCM WAV file
+pub fn encode_wav(samples: &[f32], sample_rate: u32) -> Vec<u8> {
+    let data_len = samples.len() as u32 * 2;
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes()); // fmt chunk size
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(sample_rate * 2).to_le_bytes()); // byte rate
+    wav.extend_from_slice(&2u16.to_le_bytes()); // block align
+    wav.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        wav.extend_from_slice(&((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16).to_le_bytes());
+    }
+    wav
+}
+
+/// Speech-to-text backend
+#[async_trait::async_trait]
+pub trait SpeechToText: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Transcribe one utterance; an empty string means nothing was recognized
+    async fn transcribe(&self, utterance: &Utterance) -> Result<String, String>;
+}
+
+/// Local STT program; `{wav}` in args is replaced by a WAV file path, the
+/// transcript is read from stdout
+#[derive(Debug, Clone)]
+pub struct CommandStt {
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+impl CommandStt {
+    pub fn new(program: &str, args: &[&str]) -> Self {
+        Self {
+            program: program.to_string(),
+            args: args.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl SpeechToText for CommandStt {
+    fn name(&self) -> &str {
+        &self.program
+    }
+
+    async fn transcribe(&self, utterance: &Utterance) -> Result<String, String> {
+        static NEXT_FILE: AtomicU64 = AtomicU64::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "neurograph-utterance-{}-{}.wav",
+            std::process::id(),
+            NEXT_FILE.fetch_add(1, Ordering::Relaxed)
+        ));
+        let wav = encode_wav(&utterance.samples, utterance.sample_rate);
+        let program = self.program.clone();
+        let args: Vec<String> = self
+            .args
+            .iter()
+            .map(|arg| arg.replace("{wav}", &path.to_string_lossy()))
+            .collect();
+
+        tokio::task::spawn_blocking(move || {
+            std::fs::write(&path, wav).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+            let output = Command::new(&program).args(&args).stderr(Stdio::null()).output();
+            let _ = std::fs::remove_file(&path);
+            let output = output.map_err(|e| format!("{}: {}", program, e))?;
+            if !output.status.success() {
+                return Err(format!("{} exited with {}", program, output.status));
+            }
+            Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+        })
+        .await
+        .map_err(|e| e.to_string())?
+    }
+}
+
+/// STT service taking `audio/wav` and answering `{"text": "..."}` or plain text
+#[derive(Debug, Clone)]
+pub struct HttpStt {
+    url: String,
+    client: reqwest::Client,
+    timeout: Duration,
+}
+
+impl HttpStt {
+    pub fn new(url: &str, timeout: Duration) -> Self {
+        Self {
+            url: url.to_string(),
+            client: reqwest::Client::new(),
+            timeout,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl SpeechToText for HttpStt {
+    fn name(&self) -> &str {
+        "http"
+    }
+
+    async fn transcribe(&self, utterance: &Utterance) -> Result<String, String> {
+        let body = self
+            .client
+            .post(&self.url)
+            .timeout(self.timeout)
+            .header("Content-Type", "audio/wav")
+            .body(encode_wav(&utterance.samples, utterance.sample_rate))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| e.to_string())?
+            .text()
+            .await
+            .map_err(|e| e.to_string())?;
+
+        Ok(match serde_json::from_str::<Value>(&body) {
+            Ok(Value::Object(obj)) => obj.get("text").and_then(Value::as_str).unwrap_or("").trim().to_string(),
+            _ => body.trim().to_string(),
+        })
+    }
+}
+
+/// Speech input adapter
+pub struct SpeechInputAdapter {
+    gateway: Arc<Gateway>,
+    config: SpeechConfig,
+    stt: Arc<dyn SpeechToText>,
+    listening: Arc<AtomicBool>,
+    recorder: Arc<Mutex<Option<Child>>>,
+    utterances: AtomicU64,
+}
+
+impl SpeechInputAdapter {
+    pub fn new(gateway: Arc<Gateway>, config: SpeechConfig, stt: Arc<dyn SpeechToText>) -> Result<Self, String> {
+        config.validate()?;
+        Ok(Self {
+            gateway,
+            config,
+            stt,
+            listening: Arc::new(AtomicBool::new(false)),
+            recorder: Arc::new(Mutex::new(None)),
+            utterances: AtomicU64::new(0),
+        })
+    }
+
+    pub fn is_listening(&self) -> bool {
+        self.listening.load(Ordering::Relaxed)
+    }
+
+    /// Transcribe an utterance and inject it; `None` if nothing was recognized
+    pub async fn handle_utterance(&self, utterance: &Utterance) -> Result<Option<u64>, String> {
+        let text = self.stt.transcribe(utterance).await?;
+        if text.is_empty() {
+            return Ok(None);
+        }
+        self.utterances.fetch_add(1, Ordering::Relaxed);
+
+        let signal = InputSignal::Text {
+            content: text,
+            source: SignalSource::Voice,
+            metadata: Some(json!({ "stt": self.stt.name(), "duration_ms": utterance.duration_ms() })),
+        };
+        let (receipt, _receiver) = self
+            .gateway
+            .inject(signal)
+            .await
+            .map_err(|e| format!("Gateway error: {}", e))?;
+        Ok(Some(receipt.signal_id))
+    }
+
+    /// Start the recorder and process speech in the background
+    pub fn start(self: &Arc<Self>) -> Result<(), String> {
+        if self.listening.swap(true, Ordering::SeqCst) {
+            return Err("Already listening".to_string());
+        }
+
+        let mut child = match Command::new(&self.config.recorder[0])
+            .args(&self.config.recorder[1..])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(e) => {
+                self.listening.store(false, Ordering::SeqCst);
+                return Err(format!("{}: {}", self.config.recorder[0], e));
+            }
+        };
+        let mut stdout = child.stdout.take().expect("stdout is piped");
+        *self.recorder.lock() = Some(child);
+
+        // Capture on a blocking thread, one frame per message
+        let (tx, mut rx) = mpsc::channel::<Vec<f32>>(64);
+        let frame_bytes = self.config.frame_len() * 2;
+        std::thread::spawn(move || {
+            let mut buf = vec![0u8; frame_bytes];
+            while stdout.read_exact(&mut buf).is_ok() {
+                let frame = buf
+                    .chunks_exact(2)
+                    .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / i16::MAX as f32)
+                    .collect();
+                if tx.blocking_send(frame).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let adapter = self.clone();
+        tokio::spawn(async move {
+            let mut vad = VoiceActivityDetector::new(adapter.config.vad.clone(), adapter.config.sample_rate);
+            while let Some(frame) = rx.recv().await {
+                for utterance in vad.push(&frame) {
+                    if let Err(e) = adapter.handle_utterance(&utterance).await {
+                        eprintln!("[Speech] {}", e);
+                    }
+                }
+            }
+            if let Some(utterance) = vad.flush() {
+                if let Err(e) = adapter.handle_utterance(&utterance).await {
+                    eprintln!("[Speech] {}", e);
+                }
+            }
+            adapter.stop();
+        });
+        Ok(())
+    }
+
+    /// Stop the recorder; the utterance in progress is still transcribed
+    pub fn stop(&self) -> bool {
+        let was_listening = self.listening.swap(false, Ordering::SeqCst);
+        if let Some(mut child) = self.recorder.lock().take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+        was_listening
+    }
+
+    pub fn status(&self) -> Value {
+        json!({
+            "listening": self.is_listening(),
+            "stt": self.stt.name(),
+            "sample_rate": self.config.sample_rate,
+            "utterances": self.utterances.load(Ordering::Relaxed),
+        })
+    }
+}
+
+impl Drop for SpeechInputAdapter {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Add `voice start`, `voice stop` and `voice status` to a Terminal
+pub fn register_terminal_commands(
+    interpreter: &mut Interpreter,
+    adapter: Arc<SpeechInputAdapter>,
+) -> Result<(), CommandError> {
+    let speech = adapter.clone();
+    interpreter.register(CommandSpec::new("voice start", "Start listening to the microphone"), move |_, _| {
+        let speech = speech.clone();
+        Box::pin(async move {
+            speech.start().map_err(CommandError::Failed)?;
+            Ok(CommandOutput::text("Listening").with_data(&speech.status()))
+        })
+    })?;
+
+    let speech = adapter.clone();
+    interpreter.register(CommandSpec::new("voice stop", "Stop listening"), move |_, _| {
+        let speech = speech.clone();
+        Box::pin(async move {
+            let text = if speech.stop() { "Stopped listening" } else { "Not listening" };
+            Ok(CommandOutput::text(text).with_data(&speech.status()))
+        })
+    })?;
+
+    interpreter.register(CommandSpec::new("voice status", "Microphone and transcription state"), move |_, _| {
+        let speech = adapter.clone();
+        Box::pin(async move {
+            let status = speech.status();
+            let text = format!(
+                "{} (stt: {}, {} utterances)",
+                if speech.is_listening() { "Listening" } else { "Not listening" },
+                speech.stt.name(),
+                status["utterances"]
+            );
+            Ok(CommandOutput::text(text).with_data(&status))
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootstrap::{BootstrapConfig, BootstrapLibrary};
+    use crate::gateway::config::GatewayConfig;
+    use crate::terminal::TerminalContext;
+
+    const RATE: u32 = 16_000;
+
+    fn tone(ms: u32, amplitude: f32) -> Vec<f32> {
+        (0..RATE * ms / 1000)
+            .map(|i| amplitude * (i as f32 * 440.0 * std::f32::consts::TAU / RATE as f32).sin())
+            .collect()
+    }
+
+    /// Returns a fixed transcript
+    struct FixedStt(&'static str);
+
+    #[async_trait::async_trait]
+    impl SpeechToText for FixedStt {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+
+        async fn transcribe(&self, _utterance: &Utterance) -> Result<String, String> {
+            Ok(self.0.to_string())
+        }
+    }
+
+    #[test]
+    fn test_vad_segments_speech() {
+        let mut vad = VoiceActivityDetector::new(VadConfig::default(), RATE);
+        let mut audio = tone(300, 0.0);
+        audio.extend(tone(600, 0.3));
+        audio.extend(tone(900, 0.0));
+        // A click is too short to count
+        audio.extend(tone(60, 0.3));
+        audio.extend(tone(900, 0.0));
+
+        let utterances = vad.push(&audio);
+        assert_eq!(utterances.len(), 1);
+        assert!((utterances[0].duration_ms() as i64 - 600).abs() <= 30);
+        assert!(vad.flush().is_none());
+
+        // Capture stopping mid-sentence still yields the utterance
+        vad.push(&tone(400, 0.3));
+        assert!(vad.flush().is_some());
+    }
+
+    #[test]
+    fn test_encode_wav() {
+        let wav = encode_wav(&[0.0, 1.0, -1.0], RATE);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(wav.len(), 44 + 6);
+        assert_eq!(u32::from_le_bytes(wav[24..28].try_into().unwrap()), RATE);
+        assert_eq!(i16::from_le_bytes([wav[46], wav[47]]), i16::MAX);
+    }
+
+    #[tokio::test]
+    async fn test_transcript_injected_as_voice() {
+        let bootstrap = Arc::new(parking_lot::RwLock::new(BootstrapLibrary::new(BootstrapConfig::default())));
+        let (tx, mut rx) = mpsc::channel(10);
+        let gateway = Arc::new(Gateway::new(tx, bootstrap, GatewayConfig::default()));
+        let config = SpeechConfig {
+            recorder: vec!["/nonexistent/recorder".to_string()],
+            ..Default::default()
+        };
+        let adapter = Arc::new(SpeechInputAdapter::new(gateway, config, Arc::new(FixedStt("hello"))).unwrap());
+
+        let utterance = Utterance { samples: tone(500, 0.3), sample_rate: RATE };
+        assert!(adapter.handle_utterance(&utterance).await.unwrap().is_some());
+        assert_eq!(rx.recv().await.unwrap().source, SignalSource::Voice);
+
+        // A recorder that cannot start leaves the adapter idle
+        let mut interpreter = Interpreter::new(TerminalContext::new());
+        register_terminal_commands(&mut interpreter, adapter.clone()).unwrap();
+        assert!(interpreter.execute("voice start").await.is_err());
+        assert!(!adapter.is_listening());
+        let status = interpreter.execute("voice status").await.unwrap();
+        assert!(status.text.starts_with("Not listening"));
+    }
+}
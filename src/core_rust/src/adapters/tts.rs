@@ -0,0 +1,357 @@
+//! Speech synthesis output adapter (feature `tts`)
+//!
+//! TtsOutputAdapter speaks the composed response of each ActionResult:
+//! - `TtsEngine::Command` runs a local engine (espeak-ng, say, piper, ...)
+//!   with `{voice}`, `{rate}` and `{text}` substituted into its arguments
+//! - `TtsEngine::Http` posts `{"text", "voice", "rate"}` to a TTS service and
+//!   pipes the returned audio into a player command (or leaves playback to
+//!   the service when no player is set)
+//!
+//! Speech can be muted at runtime with `/mute` and `/unmute`: the Gateway
+//! owns the switch, adapters attached via [`TtsOutputAdapter::with_mute_switch`]
+//! follow it.
+
+use super::composer::{LabelSource, NoLabels, ResponseComposer};
+use super::{FormattedOutput, OutputAdapter, OutputContext, OutputError};
+use crate::action_executor::ActionResult;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Speech engine backend
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TtsEngine {
+    /// Local program; `{voice}`, `{rate}` and `{text}` are replaced in args
+    Command { program: String, args: Vec<String> },
+
+    /// HTTP service returning audio; `player` reads it from stdin
+    Http {
+        url: String,
+        player: Vec<String>,
+        timeout_secs: u64,
+    },
+}
+
+impl Default for TtsEngine {
+    fn default() -> Self {
+        TtsEngine::Command {
+            program: "espeak-ng".to_string(),
+            args: ["-v", "{voice}", "-s", "{rate}", "{text}"].iter().map(|s| s.to_string()).collect(),
+        }
+    }
+}
+
+/// Configuration for speech output
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TtsConfig {
+    pub engine: TtsEngine,
+
+    /// Voice name as understood by the engine
+    pub voice: String,
+
+    /// Speaking rate in words per minute
+    pub rate: u32,
+
+    /// Longer responses are cut at a word boundary
+    pub max_chars: usize,
+
+    /// Also speak error messages
+    pub speak_errors: bool,
+}
+
+impl Default for TtsConfig {
+    fn default() -> Self {
+        Self {
+            engine: TtsEngine::default(),
+            voice: "en".to_string(),
+            rate: 175,
+            max_chars: 400,
+            speak_errors: false,
+        }
+    }
+}
+
+impl TtsConfig {
+    /// Validate configuration
+    pub fn validate(&self) -> Result<(), String> {
+        match &self.engine {
+            TtsEngine::Command { program, .. } if program.is_empty() => {
+                return Err("engine program must not be empty".to_string());
+            }
+            TtsEngine::Http { url, timeout_secs, .. } => {
+                if !url.starts_with("http://") && !url.starts_with("https://") {
+                    return Err("engine url must be http(s)".to_string());
+                }
+                if *timeout_secs == 0 {
+                    return Err("timeout_secs must be > 0".to_string());
+                }
+            }
+            _ => {}
+        }
+        if self.voice.is_empty() {
+            return Err("voice must not be empty".to_string());
+        }
+        if !(80..=450).contains(&self.rate) {
+            return Err("rate must be in 80..=450 words per minute".to_string());
+        }
+        if self.max_chars == 0 {
+            return Err("max_chars must be > 0".to_string());
+        }
+        Ok(())
+    }
+
+    /// Engine arguments for one utterance
+    fn render_args(&self, args: &[String], text: &str) -> Vec<String> {
+        args.iter()
+            .map(|arg| {
+                arg.replace("{voice}", &self.voice)
+                    .replace("{rate}", &self.rate.to_string())
+                    .replace("{text}", text)
+            })
+            .collect()
+    }
+}
+
+/// Cut text to `max_chars` characters, preferring a word boundary
+fn clip(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let cut: String = text.chars().take(max_chars).collect();
+    match cut.rfind(char::is_whitespace) {
+        Some(end) if end > 0 => cut[..end].to_string(),
+        _ => cut,
+    }
+}
+
+/// Speech output adapter
+pub struct TtsOutputAdapter {
+    config: RwLock<TtsConfig>,
+    muted: Arc<AtomicBool>,
+    composer: ResponseComposer,
+    labels: Arc<dyn LabelSource>,
+    client: reqwest::Client,
+}
+
+impl TtsOutputAdapter {
+    pub fn new(config: TtsConfig) -> Result<Self, String> {
+        config.validate()?;
+        Ok(Self {
+            config: RwLock::new(config),
+            muted: Arc::new(AtomicBool::new(false)),
+            composer: ResponseComposer::default(),
+            labels: Arc::new(NoLabels),
+            client: reqwest::Client::new(),
+        })
+    }
+
+    /// Follow a shared mute switch (see `Gateway::mute_switch`)
+    pub fn with_mute_switch(mut self, switch: Arc<AtomicBool>) -> Self {
+        self.muted = switch;
+        self
+    }
+
+    /// Resolve node labels (e.g. from the bootstrap library)
+    pub fn with_labels(mut self, labels: Arc<dyn LabelSource>) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    pub fn config(&self) -> TtsConfig {
+        self.config.read().clone()
+    }
+
+    /// Replace the configuration (voice, rate, engine) at runtime
+    pub fn update_config(&self, config: TtsConfig) -> Result<(), String> {
+        config.validate()?;
+        *self.config.write() = config;
+        Ok(())
+    }
+
+    pub fn set_voice(&self, voice: &str) -> Result<(), String> {
+        let config = TtsConfig { voice: voice.to_string(), ..self.config() };
+        self.update_config(config)
+    }
+
+    pub fn set_rate(&self, rate: u32) -> Result<(), String> {
+        let config = TtsConfig { rate, ..self.config() };
+        self.update_config(config)
+    }
+
+    pub fn set_muted(&self, muted: bool) {
+        self.muted.store(muted, Ordering::Relaxed);
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.muted.load(Ordering::Relaxed)
+    }
+
+    /// Run a local engine to completion
+    async fn speak_command(&self, program: String, args: Vec<String>) -> Result<(), OutputError> {
+        let status = tokio::task::spawn_blocking(move || {
+            Command::new(&program)
+                .args(&args)
+                .stdout(Stdio::null())
+                .status()
+                .map_err(|e| OutputError::IoError(format!("{}: {}", program, e)))
+        })
+        .await
+        .map_err(|e| OutputError::SendFailed(e.to_string()))??;
+
+        if status.success() {
+            Ok(())
+        } else {
+            Err(OutputError::SendFailed(format!("TTS engine exited with {}", status)))
+        }
+    }
+
+    /// Synthesize via HTTP and hand the audio to the player
+    async fn speak_http(
+        &self,
+        url: &str,
+        player: Vec<String>,
+        timeout_secs: u64,
+        text: &str,
+    ) -> Result<(), OutputError> {
+        let (voice, rate) = {
+            let config = self.config.read();
+            (config.voice.clone(), config.rate)
+        };
+        let response = self
+            .client
+            .post(url)
+            .timeout(Duration::from_secs(timeout_secs))
+            .json(&json!({"text": text, "voice": voice, "rate": rate}))
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| OutputError::SendFailed(e.to_string()))?;
+        let audio = response.bytes().await.map_err(|e| OutputError::SendFailed(e.to_string()))?;
+
+        let Some((program, args)) = player.split_first() else {
+            return Ok(());
+        };
+        let (program, args) = (program.clone(), args.to_vec());
+        tokio::task::spawn_blocking(move || {
+            let mut child = Command::new(&program)
+                .args(&args)
+                .stdin(Stdio::piped())
+                .stdout(Stdio::null())
+                .spawn()
+                .map_err(|e| OutputError::IoError(format!("{}: {}", program, e)))?;
+            if let Some(mut stdin) = child.stdin.take() {
+                stdin.write_all(&audio).map_err(|e| OutputError::IoError(e.to_string()))?;
+            }
+            let status = child.wait().map_err(|e| OutputError::IoError(e.to_string()))?;
+            if status.success() {
+                Ok(())
+            } else {
+                Err(OutputError::SendFailed(format!("Audio player exited with {}", status)))
+            }
+        })
+        .await
+        .map_err(|e| OutputError::SendFailed(e.to_string()))?
+    }
+}
+
+#[async_trait::async_trait]
+impl OutputAdapter for TtsOutputAdapter {
+    fn name(&self) -> &str {
+        "tts"
+    }
+
+    async fn format_output(
+        &self,
+        result: &ActionResult,
+        _context: &OutputContext,
+    ) -> Result<FormattedOutput, OutputError> {
+        let config = self.config.read();
+        let text = match &result.error {
+            Some(error) if config.speak_errors => Some(format!("Error: {}", error)),
+            Some(_) => None,
+            None => self.composer.compose_with(result, self.labels.as_ref()),
+        };
+
+        Ok(match text {
+            Some(text) => FormattedOutput::text(clip(text.trim(), config.max_chars)),
+            None => FormattedOutput { text: None, data: None },
+        })
+    }
+
+    async fn send(&self, output: FormattedOutput) -> Result<(), OutputError> {
+        let Some(text) = output.text.filter(|t| !t.trim().is_empty()) else {
+            return Ok(());
+        };
+        if self.is_muted() {
+            return Ok(());
+        }
+
+        let config = self.config();
+        match config.engine {
+            TtsEngine::Command { ref program, ref args } => {
+                let args = config.render_args(args, &text);
+                self.speak_command(program.clone(), args).await
+            }
+            TtsEngine::Http { ref url, ref player, timeout_secs } => {
+                self.speak_http(url, player.clone(), timeout_secs, &text).await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SignalSource, SignalType};
+
+    #[test]
+    fn test_tts_config_validate() {
+        assert!(TtsConfig::default().validate().is_ok());
+        assert!(TtsConfig { rate: 20, ..Default::default() }.validate().is_err());
+        assert!(TtsConfig { voice: String::new(), ..Default::default() }.validate().is_err());
+
+        let http = TtsEngine::Http { url: "ftp://x".to_string(), player: Vec::new(), timeout_secs: 5 };
+        assert!(TtsConfig { engine: http, ..Default::default() }.validate().is_err());
+
+        let config = TtsConfig { voice: "de".to_string(), rate: 140, ..Default::default() };
+        let TtsEngine::Command { args, .. } = &config.engine else { unreachable!() };
+        assert_eq!(config.render_args(args, "hallo welt"), vec!["-v", "de", "-s", "140", "hallo welt"]);
+    }
+
+    #[test]
+    fn test_clip_at_word_boundary() {
+        assert_eq!(clip("short", 10), "short");
+        assert_eq!(clip("the quick brown fox", 12), "the quick");
+        assert_eq!(clip("abcdefgh", 4), "abcd");
+    }
+
+    #[tokio::test]
+    async fn test_muted_adapter_stays_silent() {
+        // A program that cannot run: any attempt to speak fails
+        let engine = TtsEngine::Command { program: "/nonexistent/tts".to_string(), args: Vec::new() };
+        let switch = Arc::new(AtomicBool::new(true));
+        let adapter = TtsOutputAdapter::new(TtsConfig { engine, ..Default::default() })
+            .unwrap()
+            .with_mute_switch(switch.clone());
+
+        assert!(adapter.send(FormattedOutput::text("hello".to_string())).await.is_ok());
+        switch.store(false, Ordering::Relaxed);
+        assert!(adapter.send(FormattedOutput::text("hello".to_string())).await.is_err());
+
+        // Errors are not spoken unless asked for
+        let failed = ActionResult::failure("boom".to_string(), 1);
+        let context = OutputContext::new(1, None, SignalType::SemanticQuery, SignalSource::Console);
+        assert!(adapter.format_output(&failed, &context).await.unwrap().text.is_none());
+
+        adapter.set_rate(200).unwrap();
+        assert!(adapter.set_rate(1000).is_err());
+        assert_eq!(adapter.config().rate, 200);
+    }
+}
@@ -0,0 +1,328 @@
+//! Sensor encoder input adapter (feature `onnx`)
+//!
+//! Runs a small ONNX encoder on image or audio frames and injects the result
+//! into Gateway as InputSignal::DirectState, so non-text modalities reach the
+//! cognitive loop in the same 8D space as text.
+//!
+//! The model takes a single float tensor shaped `config.input_shape`
+//! (e.g. `[1, 3, 32, 32]` for RGB, `[1, 16000]` for audio) and returns any
+//! float tensor. Outputs with 8 values are used as-is; longer outputs are
+//! average-pooled into 8 bins. Values are squashed with tanh into [-1, 1].
+
+use crate::gateway::Gateway;
+use crate::InputSignal;
+use ort::session::Session;
+use ort::value::Tensor;
+use parking_lot::Mutex;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Kind of frames fed to the encoder
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SensorModality {
+    /// RGB8 images, converted to planar CHW floats in [0, 1]
+    Image,
+    /// Mono PCM samples in [-1, 1]
+    Audio,
+}
+
+impl SensorModality {
+    fn label(&self) -> &'static str {
+        match self {
+            SensorModality::Image => "vision",
+            SensorModality::Audio => "audio",
+        }
+    }
+}
+
+/// Configuration for the sensor encoder
+#[derive(Debug, Clone)]
+pub struct VisionConfig {
+    /// Path to the ONNX encoder
+    pub model_path: PathBuf,
+
+    /// Frame modality
+    pub modality: SensorModality,
+
+    /// Model input shape; images use `[1, 3, H, W]`, audio `[1, N]`
+    pub input_shape: Vec<usize>,
+
+    /// Label prefix for emitted DirectState signals
+    pub label: Option<String>,
+}
+
+impl Default for VisionConfig {
+    fn default() -> Self {
+        Self {
+            model_path: PathBuf::from("models/encoder.onnx"),
+            modality: SensorModality::Image,
+            input_shape: vec![1, 3, 32, 32],
+            label: None,
+        }
+    }
+}
+
+impl VisionConfig {
+    /// Validate configuration
+    pub fn validate(&self) -> Result<(), String> {
+        if self.input_shape.is_empty() || self.input_shape.contains(&0) {
+            return Err("input_shape must be non-empty with no zero dimensions".to_string());
+        }
+        match self.modality {
+            SensorModality::Image if self.input_shape.len() != 4 || self.input_shape[1] != 3 => {
+                Err("image input_shape must be [1, 3, H, W]".to_string())
+            }
+            SensorModality::Audio if self.input_shape.len() != 2 => {
+                Err("audio input_shape must be [1, N]".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn input_len(&self) -> usize {
+        self.input_shape.iter().product()
+    }
+}
+
+// ============================================================================
+// Preprocessing / postprocessing
+// ============================================================================
+
+/// Resize RGB8 image (nearest neighbour) into planar CHW floats in [0, 1]
+pub fn preprocess_image(
+    rgb: &[u8],
+    width: usize,
+    height: usize,
+    target_w: usize,
+    target_h: usize,
+) -> Result<Vec<f32>, String> {
+    if width == 0 || height == 0 || rgb.len() != width * height * 3 {
+        return Err(format!(
+            "Expected {}x{} RGB frame ({} bytes), got {} bytes",
+            width,
+            height,
+            width * height * 3,
+            rgb.len()
+        ));
+    }
+
+    let plane = target_w * target_h;
+    let mut out = vec![0.0f32; plane * 3];
+    for y in 0..target_h {
+        let src_y = y * height / target_h;
+        for x in 0..target_w {
+            let src_x = x * width / target_w;
+            let src = (src_y * width + src_x) * 3;
+            for c in 0..3 {
+                out[c * plane + y * target_w + x] = rgb[src + c] as f32 / 255.0;
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Pad with silence or truncate audio samples to `len`
+pub fn preprocess_audio(samples: &[f32], len: usize) -> Vec<f32> {
+    let mut out: Vec<f32> = samples.iter().take(len).map(|s| s.clamp(-1.0, 1.0)).collect();
+    out.resize(len, 0.0);
+    out
+}
+
+/// Reduce encoder output to an 8D state in [-1, 1]
+pub fn output_to_state(output: &[f32]) -> Result<[f32; 8], String> {
+    if output.len() < 8 {
+        return Err(format!("Encoder output has {} values, need at least 8", output.len()));
+    }
+
+    let mut state = [0.0f32; 8];
+    for (i, value) in state.iter_mut().enumerate() {
+        let start = i * output.len() / 8;
+        let end = (i + 1) * output.len() / 8;
+        let bin = &output[start..end];
+        *value = (bin.iter().sum::<f32>() / bin.len() as f32).tanh();
+    }
+    Ok(state)
+}
+
+// ============================================================================
+// Encoder
+// ============================================================================
+
+/// ONNX sensor encoder
+pub struct SensorEncoder {
+    session: Mutex<Session>,
+    config: VisionConfig,
+}
+
+impl SensorEncoder {
+    /// Load the encoder model
+    ///
+    /// Requires the ONNX Runtime shared library (`ORT_DYLIB_PATH`).
+    pub fn new(config: VisionConfig) -> Result<Self, String> {
+        config.validate()?;
+        let session = Session::builder()
+            .and_then(|b| b.commit_from_file(&config.model_path))
+            .map_err(|e| format!("Failed to load ONNX model: {}", e))?;
+
+        Ok(Self {
+            session: Mutex::new(session),
+            config,
+        })
+    }
+
+    pub fn config(&self) -> &VisionConfig {
+        &self.config
+    }
+
+    /// Run the model on a preprocessed input tensor
+    pub fn encode(&self, input: Vec<f32>) -> Result<[f32; 8], String> {
+        if input.len() != self.config.input_len() {
+            return Err(format!(
+                "Input has {} values, model expects {}",
+                input.len(),
+                self.config.input_len()
+            ));
+        }
+
+        let tensor = Tensor::from_array((self.config.input_shape.clone(), input))
+            .map_err(|e| format!("Invalid input tensor: {}", e))?;
+
+        let mut session = self.session.lock();
+        let outputs = session
+            .run(ort::inputs![tensor])
+            .map_err(|e| format!("ONNX inference failed: {}", e))?;
+        let (_shape, data) = outputs[0]
+            .try_extract_tensor::<f32>()
+            .map_err(|e| format!("Unexpected encoder output: {}", e))?;
+
+        output_to_state(data)
+    }
+
+    /// Encode an RGB8 image frame of any size
+    pub fn encode_image(&self, rgb: &[u8], width: usize, height: usize) -> Result<[f32; 8], String> {
+        if self.config.modality != SensorModality::Image {
+            return Err("Encoder is not configured for images".to_string());
+        }
+        let (h, w) = (self.config.input_shape[2], self.config.input_shape[3]);
+        self.encode(preprocess_image(rgb, width, height, w, h)?)
+    }
+
+    /// Encode a mono audio frame
+    pub fn encode_audio(&self, samples: &[f32]) -> Result<[f32; 8], String> {
+        if self.config.modality != SensorModality::Audio {
+            return Err("Encoder is not configured for audio".to_string());
+        }
+        self.encode(preprocess_audio(samples, self.config.input_shape[1]))
+    }
+}
+
+// ============================================================================
+// Input Adapter
+// ============================================================================
+
+/// Sensor input adapter: frames → SensorEncoder → Gateway DirectState
+pub struct VisionInputAdapter {
+    gateway: Arc<Gateway>,
+    encoder: Arc<SensorEncoder>,
+}
+
+impl VisionInputAdapter {
+    pub fn new(gateway: Arc<Gateway>, encoder: Arc<SensorEncoder>) -> Self {
+        Self { gateway, encoder }
+    }
+
+    /// Encode an RGB8 image and inject it
+    ///
+    /// # Returns
+    /// Signal ID
+    pub async fn submit_image(&self, rgb: &[u8], width: usize, height: usize) -> Result<u64, String> {
+        let state = self.encoder.encode_image(rgb, width, height)?;
+        self.inject(state).await
+    }
+
+    /// Encode an audio frame and inject it
+    ///
+    /// # Returns
+    /// Signal ID
+    pub async fn submit_audio(&self, samples: &[f32]) -> Result<u64, String> {
+        let state = self.encoder.encode_audio(samples)?;
+        self.inject(state).await
+    }
+
+    async fn inject(&self, state: [f32; 8]) -> Result<u64, String> {
+        let config = self.encoder.config();
+        let label = config
+            .label
+            .clone()
+            .unwrap_or_else(|| config.modality.label().to_string());
+
+        let (receipt, _receiver) = self
+            .gateway
+            .inject(InputSignal::DirectState {
+                state,
+                label: Some(label),
+            })
+            .await
+            .map_err(|e| format!("Gateway error: {}", e))?;
+
+        Ok(receipt.signal_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vision_config_validate() {
+        assert!(VisionConfig::default().validate().is_ok());
+
+        let config = VisionConfig {
+            modality: SensorModality::Audio,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = VisionConfig {
+            modality: SensorModality::Audio,
+            input_shape: vec![1, 16000],
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_preprocess_image() {
+        // 2x1 image: red, blue → 1x1 picks the first pixel
+        let rgb = [255, 0, 0, 0, 0, 255];
+        let out = preprocess_image(&rgb, 2, 1, 1, 1).unwrap();
+        assert_eq!(out, vec![1.0, 0.0, 0.0]);
+
+        // Upscale keeps planar layout: R plane, G plane, B plane
+        let out = preprocess_image(&rgb, 2, 1, 2, 2).unwrap();
+        assert_eq!(&out[0..4], &[1.0, 0.0, 1.0, 0.0]);
+        assert_eq!(&out[8..12], &[0.0, 1.0, 0.0, 1.0]);
+
+        assert!(preprocess_image(&rgb, 3, 1, 1, 1).is_err());
+    }
+
+    #[test]
+    fn test_preprocess_audio() {
+        assert_eq!(preprocess_audio(&[0.5, 2.0], 3), vec![0.5, 1.0, 0.0]);
+        assert_eq!(preprocess_audio(&[0.1, 0.2, 0.3], 2), vec![0.1, 0.2]);
+    }
+
+    #[test]
+    fn test_output_to_state() {
+        let state = output_to_state(&[0.0; 8]).unwrap();
+        assert_eq!(state, [0.0; 8]);
+
+        // 16 values pool pairwise
+        let output: Vec<f32> = (0..16).map(|i| if i < 2 { 100.0 } else { 0.0 }).collect();
+        let state = output_to_state(&output).unwrap();
+        assert!((state[0] - 1.0).abs() < 1e-4);
+        assert_eq!(state[1], 0.0);
+
+        assert!(output_to_state(&[1.0; 4]).is_err());
+    }
+}
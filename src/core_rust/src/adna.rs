@@ -32,6 +32,7 @@
 //! - **Asynchronous learning**: Policy updates happen in dedicated learning phases
 //! - **Appraiser configuration**: Parameters for all 4 reward appraisers (v3.1+)
 
+use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Magic number for ADNA structure validation: 'ADNA' in ASCII
@@ -151,8 +152,50 @@ pub struct EvolutionMetrics {
     /// Update frequency (updates per hour)
     pub update_frequency: u32,          // 4 bytes (total: 40)
 
+    /// Shadow A/B evaluations of candidate policies
+    pub shadow_evaluations: u32,        // 4 bytes (total: 44)
+
+    /// Candidates promoted after shadow evaluation
+    pub shadow_promotions: u32,         // 4 bytes (total: 48)
+
+    /// Reward lift (candidate - live) of the last shadow evaluation
+    pub shadow_lift: f32,               // 4 bytes (total: 52)
+
     /// Reserved for future use
-    pub _reserved: [u8; 24],            // 24 bytes (total: 64)
+    pub _reserved: [u8; 12],            // 12 bytes (total: 64)
+}
+
+impl Default for EvolutionMetrics {
+    fn default() -> Self {
+        Self {
+            generation: 0,
+            fitness_score: 0.0,
+            confidence: 0.5,
+            exploration_rate: 0.9,
+            learning_rate: 0.01,
+            trajectory_count: 0,
+            success_rate: 0.0,
+            last_update: 0,
+            update_frequency: 0,
+            shadow_evaluations: 0,
+            shadow_promotions: 0,
+            shadow_lift: 0.0,
+            _reserved: [0; 12],
+        }
+    }
+}
+
+impl EvolutionMetrics {
+    /// Record a shadow A/B evaluation of a candidate policy
+    pub fn record_shadow_evaluation(&mut self, lift: f32, matched_events: u32, promoted: bool) {
+        self.shadow_evaluations += 1;
+        if promoted {
+            self.shadow_promotions += 1;
+        }
+        self.shadow_lift = lift;
+        self.trajectory_count = self.trajectory_count.saturating_add(matched_events);
+        self.success_rate = self.shadow_promotions as f32 / self.shadow_evaluations as f32;
+    }
 }
 
 // ============================================================================
@@ -239,7 +282,10 @@ impl ADNA {
                 success_rate: 0.0,
                 last_update: now,
                 update_frequency: 0,
-                _reserved: [0; 24],
+                shadow_evaluations: 0,
+                shadow_promotions: 0,
+                shadow_lift: 0.0,
+                _reserved: [0; 12],
             },
             policy_ptr: PolicyPointer {
                 policy_size: 0,
@@ -259,6 +305,16 @@ impl ADNA {
         }
     }
 
+    /// Serialize to bytes (256 bytes)
+    pub fn to_bytes(&self) -> [u8; 256] {
+        unsafe { std::mem::transmute(*self) }
+    }
+
+    /// Deserialize from bytes (256 bytes); see `validate_bytes`
+    pub fn from_bytes(bytes: &[u8; 256]) -> Self {
+        unsafe { std::mem::transmute(*bytes) }
+    }
+
     /// Validate ADNA structure
     pub fn is_valid(&self) -> bool {
         self.header.magic == ADNA_MAGIC
@@ -302,6 +358,46 @@ impl Default for ADNA {
     }
 }
 
+/// Decode and validate a serialized ADNA
+///
+/// Rejects input that is not exactly 256 bytes, a wrong magic or major
+/// version, an unknown policy type and non-finite metrics or mapping values.
+pub fn validate_bytes(bytes: &[u8]) -> Result<ADNA, String> {
+    let bytes: &[u8; 256] = bytes
+        .try_into()
+        .map_err(|_| format!("ADNA requires 256 bytes, got {}", bytes.len()))?;
+    let adna = ADNA::from_bytes(bytes);
+    if adna.header.magic != ADNA_MAGIC {
+        return Err(format!("Invalid magic number: 0x{:08X}", adna.header.magic));
+    }
+    if adna.header.version_major != ADNA_VERSION_MAJOR {
+        return Err(format!("Unsupported major version: {}", adna.header.version_major));
+    }
+    if adna.header.policy_type > PolicyType::Programmatic as u16 {
+        return Err(format!("Unknown policy type {}", adna.header.policy_type));
+    }
+
+    let evolution = adna.evolution;
+    let metrics = [
+        evolution.fitness_score,
+        evolution.confidence,
+        evolution.exploration_rate,
+        evolution.learning_rate,
+        evolution.success_rate,
+        evolution.shadow_lift,
+    ];
+    let mapping = adna.state_mapping;
+    if metrics
+        .iter()
+        .chain(&mapping.state_normalization)
+        .chain(&mapping.action_bounds)
+        .any(|v| !v.is_finite())
+    {
+        return Err("Evolution metrics and state mapping must be finite".to_string());
+    }
+    Ok(adna)
+}
+
 // ============================================================================
 // Appraiser Configuration (v3.1+)
 // ============================================================================
@@ -309,7 +405,7 @@ impl Default for ADNA {
 /// Parameters for HomeostasisAppraiser
 ///
 /// Controls penalties for deviations from target ranges in L1-L8 coordinates.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct HomeostasisParams {
     /// Overall weight/importance of homeostasis rewards
     pub weight: f32,
@@ -342,7 +438,7 @@ impl Default for HomeostasisParams {
 /// Parameters for CuriosityAppraiser
 ///
 /// Controls rewards for novelty and exploration.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct CuriosityParams {
     /// Overall weight/importance of curiosity rewards
     pub weight: f32,
@@ -371,7 +467,7 @@ impl Default for CuriosityParams {
 /// Parameters for EfficiencyAppraiser
 ///
 /// Controls penalties for resource usage.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct EfficiencyParams {
     /// Overall weight/importance of efficiency penalties
     pub weight: f32,
@@ -400,7 +496,7 @@ impl Default for EfficiencyParams {
 /// Parameters for GoalDirectedAppraiser
 ///
 /// Controls retroactive reward distribution for goal achievement.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct GoalDirectedParams {
     /// Overall weight/importance of goal-directed rewards
     pub weight: f32,
@@ -425,7 +521,7 @@ impl Default for GoalDirectedParams {
 /// Complete appraiser configuration
 ///
 /// This structure holds all parameters for the 4 reward appraisers.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct AppraiserConfig {
     pub homeostasis: HomeostasisParams,
     pub curiosity: CuriosityParams,
@@ -556,6 +652,30 @@ impl InMemoryADNAReader {
         let mut lock = self.config.write().await;
         lock.goal_directed = params;
     }
+
+    /// Serialize appraiser config and action policies to JSON (used by checkpoint)
+    pub async fn export_state(&self) -> serde_json::Result<Vec<u8>> {
+        let config = self.config.read().await;
+        let policies = self.policies.read().await;
+        serde_json::to_vec(&serde_json::json!({
+            "config": &*config,
+            "policies": &*policies,
+        }))
+    }
+
+    /// Replace appraiser config and action policies from `export_state` output
+    pub async fn import_state(&self, data: &[u8]) -> serde_json::Result<()> {
+        #[derive(Deserialize)]
+        struct AdnaState {
+            config: AppraiserConfig,
+            policies: HashMap<String, ActionPolicy>,
+        }
+
+        let state: AdnaState = serde_json::from_slice(data)?;
+        *self.config.write().await = state.config;
+        *self.policies.write().await = state.policies;
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -823,6 +943,9 @@ pub struct Proposal {
 
     /// Timestamp when proposal was created
     pub created_at: SystemTime,
+
+    /// Interaction that led to the proposal, if created inside one
+    pub correlation_id: Option<crate::correlation::CorrelationId>,
 }
 
 impl Proposal {
@@ -841,6 +964,7 @@ impl Proposal {
             expected_impact,
             confidence,
             created_at: SystemTime::now(),
+            correlation_id: crate::correlation::current(),
         }
     }
 }
@@ -873,7 +997,7 @@ impl Intent {
 /// Action selection policy from ADNA
 ///
 /// Maps actions to weights/probabilities for a given state.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ActionPolicy {
     /// Map of action_type (event_type) → weight/probability
     pub action_weights: HashMap<u16, f64>,
@@ -912,7 +1036,12 @@ impl ActionPolicy {
     pub fn select_action(&self) -> Option<u16> {
         self.action_weights
             .iter()
-            .max_by(|(_, w1), (_, w2)| w1.partial_cmp(w2).unwrap_or(std::cmp::Ordering::Equal))
+            // Ties go to the lowest action id, independent of map order
+            .max_by(|(a1, w1), (a2, w2)| {
+                w1.partial_cmp(w2)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| a2.cmp(a1))
+            })
             .map(|(action, _)| *action)
     }
 }
@@ -0,0 +1,479 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Anomaly Detection v1.0 - EWMA monitoring of key metrics
+//!
+//! [`AnomalyMonitor`] samples a set of [`Watch`]es (reward mean, edge
+//! creation rate, queue depth, panic count, ...) every `interval_ms` and
+//! keeps an exponentially weighted mean and variance per metric. A sample
+//! further than `z_threshold` standard deviations from the mean is an
+//! [`Anomaly`]:
+//!
+//! ```text
+//! ewma  = ewma + α·(x − ewma)
+//! var   = (1 − α)·(var + α·(x − ewma)²)
+//! z     = |x − ewma| / √var          anomaly if z > threshold
+//! ```
+//!
+//! Counters are watched by their per-second rate ([`WatchMode::Rate`]), so
+//! a burst of panics or edge creations stands out against the usual pace.
+//! The first `warmup_samples` only train the estimate.
+//!
+//! Every anomaly is logged, kept in a bounded list and emitted as a
+//! Guardian `AnomalyDetected` event; with `pause_exploration` the
+//! autonomous exploration of the attached CuriosityDrive is switched off
+//! until an operator turns it back on.
+
+use crate::curiosity::CuriosityDrive;
+use crate::experience_stream::ExperienceStream;
+use crate::gateway::Gateway;
+use crate::guardian::{Event as GuardianEvent, EventType as GuardianEventType, Guardian};
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::task::JoinHandle;
+
+/// Anomalies kept for inspection
+const RECENT_CAPACITY: usize = 100;
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Detector configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AnomalyConfig {
+    /// EWMA smoothing factor (0, 1]; higher adapts faster
+    pub alpha: f64,
+
+    /// Standard deviations from the mean that count as anomalous
+    pub z_threshold: f64,
+
+    /// Deviations smaller than this are never anomalous (flat metrics have
+    /// near-zero variance)
+    pub min_deviation: f64,
+
+    /// Samples that only train the estimate
+    pub warmup_samples: u32,
+
+    /// Sampling interval in milliseconds
+    pub interval_ms: u64,
+
+    /// Switch off autonomous exploration on an anomaly
+    pub pause_exploration: bool,
+}
+
+impl Default for AnomalyConfig {
+    fn default() -> Self {
+        Self {
+            alpha: 0.1,
+            z_threshold: 4.0,
+            min_deviation: 1e-3,
+            warmup_samples: 12,
+            interval_ms: 5_000,
+            pause_exploration: false,
+        }
+    }
+}
+
+impl AnomalyConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.alpha > 0.0 && self.alpha <= 1.0) {
+            return Err("alpha must be in (0, 1]".to_string());
+        }
+        if self.z_threshold <= 0.0 {
+            return Err("z_threshold must be > 0".to_string());
+        }
+        if self.min_deviation < 0.0 {
+            return Err("min_deviation must be >= 0".to_string());
+        }
+        if self.interval_ms == 0 {
+            return Err("interval_ms must be > 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Watches
+// ============================================================================
+
+/// How raw readings are turned into samples
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WatchMode {
+    /// The reading itself (gauges, means)
+    Level,
+    /// Per-second change between readings (counters)
+    Rate,
+}
+
+type Probe = Box<dyn Fn() -> Option<f64> + Send + Sync>;
+
+/// A metric to watch
+pub struct Watch {
+    name: String,
+    mode: WatchMode,
+    probe: Probe,
+}
+
+impl Watch {
+    /// Watch any reading; `None` skips the sample
+    pub fn new<F>(name: &str, mode: WatchMode, probe: F) -> Self
+    where
+        F: Fn() -> Option<f64> + Send + Sync + 'static,
+    {
+        Self {
+            name: name.to_string(),
+            mode,
+            probe: Box::new(probe),
+        }
+    }
+
+    /// Mean total reward of the last `window` experience events
+    pub fn reward_mean(stream: Arc<ExperienceStream>, window: usize) -> Self {
+        Self::new("reward_mean", WatchMode::Level, move || {
+            let end = stream.total_written();
+            let events = stream.query_range(end.saturating_sub(window as u64), end);
+            (!events.is_empty())
+                .then(|| events.iter().map(|e| e.total_reward() as f64).sum::<f64>() / events.len() as f64)
+        })
+    }
+
+    /// Connections created per second
+    pub fn edge_creation_rate() -> Self {
+        Self::prometheus("edge_creation_rate", "neurograph_connections_created_total", WatchMode::Rate)
+    }
+
+    /// Processed signals waiting in the Gateway queue
+    pub fn queue_depth(gateway: Arc<Gateway>) -> Self {
+        Self::new("queue_depth", WatchMode::Level, move || Some(gateway.queue_depth() as f64))
+    }
+
+    /// Recovered panics per second, all subsystems
+    pub fn panic_rate() -> Self {
+        Self::new("panic_rate", WatchMode::Rate, || {
+            Some(
+                registry_total("neurograph_panics_recovered_total").unwrap_or(0.0)
+                    + registry_total("neurograph_subsystem_panics_total").unwrap_or(0.0),
+            )
+        })
+    }
+
+    /// A counter or gauge of the Prometheus default registry, summed over labels
+    pub fn prometheus(name: &str, metric: &str, mode: WatchMode) -> Self {
+        let metric = metric.to_string();
+        Self::new(name, mode, move || registry_total(&metric))
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Sum of a counter or gauge over all label sets
+fn registry_total(name: &str) -> Option<f64> {
+    use prometheus::proto::MetricType;
+    let family = prometheus::gather().into_iter().find(|f| f.get_name() == name)?;
+    Some(
+        family
+            .get_metric()
+            .iter()
+            .map(|m| match family.get_field_type() {
+                MetricType::COUNTER => m.get_counter().get_value(),
+                MetricType::GAUGE => m.get_gauge().get_value(),
+                _ => m.get_untyped().get_value(),
+            })
+            .sum(),
+    )
+}
+
+// ============================================================================
+// Detector
+// ============================================================================
+
+/// Exponentially weighted mean and variance of one metric
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Ewma {
+    pub mean: f64,
+    pub variance: f64,
+    pub samples: u32,
+}
+
+impl Ewma {
+    /// Deviation of `x` in standard deviations, then fold `x` into the estimate
+    pub fn update(&mut self, x: f64, alpha: f64) -> f64 {
+        let deviation = x - self.mean;
+        let z = if self.samples == 0 {
+            0.0
+        } else if self.variance > 0.0 {
+            deviation.abs() / self.variance.sqrt()
+        } else if deviation == 0.0 {
+            0.0
+        } else {
+            f64::INFINITY
+        };
+
+        if self.samples == 0 {
+            self.mean = x;
+        } else {
+            self.mean += alpha * deviation;
+            self.variance = (1.0 - alpha) * (self.variance + alpha * deviation * deviation);
+        }
+        self.samples = self.samples.saturating_add(1);
+        z
+    }
+}
+
+/// A sample that diverged from its metric's recent behavior
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Anomaly {
+    pub metric: String,
+    pub value: f64,
+    /// EWMA before this sample
+    pub expected: f64,
+    pub z_score: f64,
+    /// Unix ms
+    pub timestamp: u64,
+}
+
+#[derive(Default)]
+struct MetricState {
+    ewma: Ewma,
+    /// Last raw reading for rates (Unix ms, value)
+    last: Option<(u64, f64)>,
+}
+
+/// Watches metrics and reports abrupt divergence
+pub struct AnomalyMonitor {
+    config: AnomalyConfig,
+    watches: Vec<Watch>,
+    states: Mutex<HashMap<String, MetricState>>,
+    recent: Mutex<VecDeque<Anomaly>>,
+    guardian: Option<Arc<RwLock<Guardian>>>,
+    curiosity: Option<Arc<CuriosityDrive>>,
+}
+
+impl AnomalyMonitor {
+    pub fn new(config: AnomalyConfig) -> Result<Self, String> {
+        config.validate()?;
+        Ok(Self {
+            config,
+            watches: Vec::new(),
+            states: Mutex::new(HashMap::new()),
+            recent: Mutex::new(VecDeque::new()),
+            guardian: None,
+            curiosity: None,
+        })
+    }
+
+    pub fn with_watch(mut self, watch: Watch) -> Self {
+        self.watches.push(watch);
+        self
+    }
+
+    /// Emit `AnomalyDetected` events to a Guardian
+    pub fn with_guardian(mut self, guardian: Arc<RwLock<Guardian>>) -> Self {
+        self.guardian = Some(guardian);
+        self
+    }
+
+    /// Exploration to switch off when `pause_exploration` is set
+    pub fn with_curiosity(mut self, curiosity: Arc<CuriosityDrive>) -> Self {
+        self.curiosity = Some(curiosity);
+        self
+    }
+
+    pub fn config(&self) -> &AnomalyConfig {
+        &self.config
+    }
+
+    /// Read every watch once
+    pub fn check(&self) -> Vec<Anomaly> {
+        let t = now_ms();
+        self.watches
+            .iter()
+            .filter_map(|watch| {
+                let reading = (watch.probe)()?;
+                self.observe(&watch.name, watch.mode, t, reading)
+            })
+            .collect()
+    }
+
+    /// Feed one reading of a metric taken at `t` (Unix ms)
+    pub fn observe(&self, metric: &str, mode: WatchMode, t: u64, reading: f64) -> Option<Anomaly> {
+        if !reading.is_finite() {
+            return None;
+        }
+        let anomaly = {
+            let mut states = self.states.lock();
+            let state = states.entry(metric.to_string()).or_default();
+            let value = match mode {
+                WatchMode::Level => reading,
+                WatchMode::Rate => {
+                    let last = state.last.replace((t, reading));
+                    match last {
+                        // Counter resets (restarts) restart the rate
+                        Some((last_t, last_value)) if t > last_t && reading >= last_value => {
+                            (reading - last_value) * 1000.0 / (t - last_t) as f64
+                        }
+                        _ => return None,
+                    }
+                }
+            };
+
+            let expected = state.ewma.mean;
+            let trained = state.ewma.samples >= self.config.warmup_samples;
+            let z_score = state.ewma.update(value, self.config.alpha);
+            let diverged = z_score > self.config.z_threshold && (value - expected).abs() > self.config.min_deviation;
+            (trained && diverged).then(|| Anomaly {
+                metric: metric.to_string(),
+                value,
+                expected,
+                z_score,
+                timestamp: t,
+            })?
+        };
+
+        self.report(&anomaly);
+        Some(anomaly)
+    }
+
+    /// Current estimate per metric
+    pub fn estimates(&self) -> HashMap<String, Ewma> {
+        self.states.lock().iter().map(|(name, state)| (name.clone(), state.ewma.clone())).collect()
+    }
+
+    /// Latest anomalies, oldest first
+    pub fn recent(&self) -> Vec<Anomaly> {
+        self.recent.lock().iter().cloned().collect()
+    }
+
+    /// Check every `interval_ms` until the runtime shuts down
+    pub fn spawn(self: Arc<Self>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(Duration::from_millis(self.config.interval_ms));
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                self.check();
+            }
+        })
+    }
+
+    fn report(&self, anomaly: &Anomaly) {
+        tracing::warn!(
+            metric = %anomaly.metric,
+            value = anomaly.value,
+            expected = anomaly.expected,
+            z_score = anomaly.z_score,
+            "Metric diverged from its recent behavior"
+        );
+
+        {
+            let mut recent = self.recent.lock();
+            if recent.len() == RECENT_CAPACITY {
+                recent.pop_front();
+            }
+            recent.push_back(anomaly.clone());
+        }
+
+        let mut paused = false;
+        if self.config.pause_exploration {
+            if let Some(curiosity) = &self.curiosity {
+                paused = curiosity.is_autonomous_enabled();
+                curiosity.set_autonomous(false);
+            }
+        }
+        if paused {
+            tracing::warn!(metric = %anomaly.metric, "Autonomous exploration paused");
+        }
+
+        if let Some(guardian) = &self.guardian {
+            let mut data = serde_json::to_value(anomaly).unwrap_or_default();
+            data["exploration_paused"] = serde_json::Value::Bool(paused);
+            guardian
+                .write()
+                .emit_event(GuardianEvent::new(GuardianEventType::AnomalyDetected).with_data(data.to_string()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curiosity::CuriosityConfig;
+
+    #[test]
+    fn test_level_spike_is_anomalous() {
+        let guardian = Arc::new(RwLock::new(Guardian::new()));
+        let curiosity = Arc::new(CuriosityDrive::new(CuriosityConfig::default()));
+        curiosity.set_autonomous(true);
+        let config = AnomalyConfig { pause_exploration: true, ..Default::default() };
+        let monitor = AnomalyMonitor::new(config)
+            .unwrap()
+            .with_guardian(guardian.clone())
+            .with_curiosity(curiosity.clone());
+
+        // Noisy but steady reward around 0.5
+        for i in 0..40 {
+            let reward = 0.5 + if i % 2 == 0 { 0.02 } else { -0.02 };
+            assert!(monitor.observe("reward_mean", WatchMode::Level, i * 1000, reward).is_none());
+        }
+        assert!(curiosity.is_autonomous_enabled());
+
+        let anomaly = monitor.observe("reward_mean", WatchMode::Level, 40_000, -0.8).unwrap();
+        assert!(anomaly.z_score > 4.0);
+        assert!((anomaly.expected - 0.5).abs() < 0.05);
+        assert_eq!(monitor.recent().len(), 1);
+        assert!(!curiosity.is_autonomous_enabled());
+
+        let events = guardian.read().pending_events().clone();
+        assert_eq!(events.iter().filter(|e| e.event_type == GuardianEventType::AnomalyDetected).count(), 1);
+    }
+
+    #[test]
+    fn test_rate_burst_and_warmup() {
+        let monitor = AnomalyMonitor::new(AnomalyConfig::default()).unwrap();
+
+        // A spike during warmup only trains the estimate
+        assert!(monitor.observe("edges", WatchMode::Rate, 0, 0.0).is_none());
+        assert!(monitor.observe("edges", WatchMode::Rate, 1000, 500.0).is_none());
+
+        // Counter growing by 10/s, with a little jitter
+        let mut total = 500.0;
+        for i in 2..60u64 {
+            total += if i % 3 == 0 { 11.0 } else { 10.0 };
+            assert!(monitor.observe("edges", WatchMode::Rate, i * 1000, total).is_none(), "{}", i);
+        }
+        let burst = monitor.observe("edges", WatchMode::Rate, 60_000, total + 400.0).unwrap();
+        assert!((burst.value - 400.0).abs() < 1e-9);
+
+        // A counter reset is skipped, not reported
+        assert!(monitor.observe("edges", WatchMode::Rate, 61_000, 0.0).is_none());
+        assert!(monitor.estimates()["edges"].samples > 50);
+    }
+}
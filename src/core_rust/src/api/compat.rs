@@ -0,0 +1,343 @@
+// NeuroGraph OS - OpenAI-compatible API
+//
+// Minimal /v1/chat/completions and /v1/models so existing chat frontends
+// can talk to NeuroGraph. The last user message is injected into Gateway
+// as a text signal; the ActionResult is returned as an assistant message
+// (or streamed as chat deltas over SSE when `stream: true`).
+
+use super::handlers::ApiError;
+use super::state::ApiState;
+use crate::action_executor::ActionResult;
+use crate::adapters::composer::{LabelSource, NoLabels, ResponseComposer};
+use crate::{InputSignal, SignalSource};
+use axum::{
+    extract::{Json, State},
+    http::HeaderMap,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::convert::Infallible;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Model name reported to clients
+pub const MODEL_ID: &str = "neurograph";
+
+// ============================================================================
+// Models
+// ============================================================================
+
+/// Chat message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatMessage {
+    /// "system", "user" or "assistant"
+    pub role: String,
+
+    /// Message text
+    pub content: String,
+}
+
+/// POST /v1/chat/completions request
+///
+/// Unknown OpenAI fields (temperature, top_p, ...) are accepted and ignored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatCompletionRequest {
+    /// Requested model (informational)
+    #[serde(default)]
+    pub model: Option<String>,
+
+    /// Conversation so far
+    pub messages: Vec<ChatMessage>,
+
+    /// Stream response as server-sent events
+    #[serde(default)]
+    pub stream: bool,
+}
+
+/// Non-streaming completion choice
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatChoice {
+    pub index: u32,
+    pub message: ChatMessage,
+    pub finish_reason: String,
+}
+
+/// Non-streaming completion response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatCompletionResponse {
+    pub id: String,
+    pub object: String,
+    pub created: u64,
+    pub model: String,
+    pub choices: Vec<ChatChoice>,
+}
+
+/// Streaming delta
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChatDelta {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+}
+
+/// Streaming chunk choice
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatChunkChoice {
+    pub index: u32,
+    pub delta: ChatDelta,
+    pub finish_reason: Option<String>,
+}
+
+/// Streaming chunk (`chat.completion.chunk`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatCompletionChunk {
+    pub id: String,
+    pub object: String,
+    pub created: u64,
+    pub model: String,
+    pub choices: Vec<ChatChunkChoice>,
+}
+
+impl ChatCompletionChunk {
+    fn new(id: &str, created: u64, delta: ChatDelta, finish_reason: Option<String>) -> Self {
+        Self {
+            id: id.to_string(),
+            object: "chat.completion.chunk".to_string(),
+            created,
+            model: MODEL_ID.to_string(),
+            choices: vec![ChatChunkChoice {
+                index: 0,
+                delta,
+                finish_reason,
+            }],
+        }
+    }
+}
+
+// ============================================================================
+// Handlers
+// ============================================================================
+
+/// Extract API key from `Authorization: Bearer` (OpenAI clients) or `X-API-Key`
+fn extract_compat_api_key(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get("Authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .or_else(|| headers.get("X-API-Key").and_then(|v| v.to_str().ok()))
+        .map(|s| s.trim().to_string())
+}
+
+/// Text shown to the user for an ActionResult
+///
+/// Prefers the `response` field, then a composed sentence; falls back to the JSON output.
+pub fn result_to_text(result: &ActionResult) -> String {
+    result_to_text_with_labels(result, &NoLabels)
+}
+
+/// `result_to_text` with node labels resolved through `labels`
+pub fn result_to_text_with_labels(result: &ActionResult, labels: &dyn LabelSource) -> String {
+    if !result.success {
+        return format!(
+            "Error: {}",
+            result.error.as_deref().unwrap_or("Unknown error")
+        );
+    }
+
+    ResponseComposer::default()
+        .compose_with(result, labels)
+        .unwrap_or_else(|| result.output.to_string())
+}
+
+/// Split answer into word-sized deltas (keeps trailing whitespace on each piece)
+fn split_deltas(text: &str) -> Vec<String> {
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    for ch in text.chars() {
+        current.push(ch);
+        if ch.is_whitespace() {
+            pieces.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        pieces.push(current);
+    }
+    pieces
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// POST /v1/chat/completions
+pub async fn handle_chat_completions(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Json(req): Json<ChatCompletionRequest>,
+) -> Result<Response, ApiError> {
+    // Validate API key
+    let api_key = extract_compat_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let prompt = req
+        .messages
+        .iter()
+        .rev()
+        .find(|m| m.role == "user")
+        .map(|m| m.content.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .ok_or_else(|| ApiError::BadRequest("No user message".to_string()))?;
+
+    let signal = InputSignal::Text {
+        content: prompt,
+        source: SignalSource::RestApi,
+        metadata: Some(json!({ "compat": "openai", "model": req.model })),
+    };
+
+    let (receipt, receiver) = state
+        .gateway
+        .inject(signal)
+        .await
+        .map_err(super::handlers::gateway_error)?;
+
+    let timeout = std::time::Duration::from_millis(state.config.request_timeout_ms);
+    let result = tokio::time::timeout(timeout, receiver)
+        .await
+        .map_err(|_| ApiError::Timeout)?
+        .map_err(|_| ApiError::InternalError("Response channel closed".to_string()))?;
+
+    let id = format!("chatcmpl-{}", receipt.signal_id);
+    let created = unix_now();
+    let text = match &state.bootstrap {
+        Some(bootstrap) => result_to_text_with_labels(&result, bootstrap.as_ref()),
+        None => result_to_text(&result),
+    };
+
+    if !req.stream {
+        let response = ChatCompletionResponse {
+            id,
+            object: "chat.completion".to_string(),
+            created,
+            model: MODEL_ID.to_string(),
+            choices: vec![ChatChoice {
+                index: 0,
+                message: ChatMessage {
+                    role: "assistant".to_string(),
+                    content: text,
+                },
+                finish_reason: "stop".to_string(),
+            }],
+        };
+        return Ok(Json(response).into_response());
+    }
+
+    // Streaming: role chunk, content deltas, stop chunk, [DONE]
+    let mut chunks = vec![ChatCompletionChunk::new(
+        &id,
+        created,
+        ChatDelta {
+            role: Some("assistant".to_string()),
+            content: None,
+        },
+        None,
+    )];
+    chunks.extend(split_deltas(&text).into_iter().map(|piece| {
+        ChatCompletionChunk::new(
+            &id,
+            created,
+            ChatDelta {
+                role: None,
+                content: Some(piece),
+            },
+            None,
+        )
+    }));
+    chunks.push(ChatCompletionChunk::new(&id, created, ChatDelta::default(), Some("stop".to_string())));
+
+    let mut events: Vec<Result<Event, Infallible>> = chunks
+        .iter()
+        .map(|chunk| Ok(Event::default().data(serde_json::to_string(chunk).unwrap_or_default())))
+        .collect();
+    events.push(Ok(Event::default().data("[DONE]")));
+
+    Ok(Sse::new(futures::stream::iter(events))
+        .keep_alive(KeepAlive::default())
+        .into_response())
+}
+
+/// GET /v1/models
+pub async fn handle_models() -> impl IntoResponse {
+    Json(json!({
+        "object": "list",
+        "data": [{
+            "id": MODEL_ID,
+            "object": "model",
+            "created": 0,
+            "owned_by": "neurograph",
+        }],
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_deltas() {
+        let pieces = split_deltas("hello big world");
+        assert_eq!(pieces, vec!["hello ", "big ", "world"]);
+        assert_eq!(pieces.concat(), "hello big world");
+        assert!(split_deltas("").is_empty());
+    }
+
+    #[test]
+    fn test_result_to_text() {
+        let result = ActionResult::success(json!({"response": "hi there"}), 1);
+        assert_eq!(result_to_text(&result), "hi there");
+
+        let result = ActionResult::success(json!({"state": [0.0]}), 1);
+        assert!(result_to_text(&result).contains("state"));
+
+        let result = ActionResult::failure("boom".to_string(), 1);
+        assert_eq!(result_to_text(&result), "Error: boom");
+
+        let result = ActionResult::success(
+            json!({"activated_nodes": [{"node_id": 2, "energy": 0.5, "word": "dog"}]}),
+            1,
+        );
+        assert_eq!(result_to_text(&result), "Related: dog.");
+    }
+
+    #[test]
+    fn test_request_ignores_unknown_fields() {
+        let req: ChatCompletionRequest = serde_json::from_value(json!({
+            "model": "gpt-4",
+            "temperature": 0.2,
+            "messages": [{"role": "user", "content": "hello"}],
+        }))
+        .unwrap();
+        assert!(!req.stream);
+        assert_eq!(req.messages[0].content, "hello");
+    }
+
+    #[test]
+    fn test_extract_bearer_key() {
+        let mut headers = HeaderMap::new();
+        headers.insert("Authorization", "Bearer secret".parse().unwrap());
+        assert_eq!(extract_compat_api_key(&headers).as_deref(), Some("secret"));
+
+        let mut headers = HeaderMap::new();
+        headers.insert("X-API-Key", "other".parse().unwrap());
+        assert_eq!(extract_compat_api_key(&headers).as_deref(), Some("other"));
+    }
+}
@@ -5,12 +5,19 @@
 use super::models::*;
 use super::state::ApiState;
 use axum::{
-    extract::{Json, State},
+    extract::{Json, Path, Query, Request, State},
     http::{HeaderMap, StatusCode},
-    response::{IntoResponse, Response},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
 };
-use crate::{InputSignal, SignalSource};
+use crate::{InputSignal, SignalSource, SystemCommand};
 use crate::feedback::{DetailedFeedbackType, FeedbackSignal};
+use crate::i18n::{tr, Localize};
+use crate::instance::{InstanceError, InstanceId, InstanceRegistry};
+use std::sync::Arc;
+use tower::Service;
 use std::time::SystemTime;
 use std::collections::HashMap;
 
@@ -21,7 +28,12 @@ use std::collections::HashMap;
 /// API error type
 pub enum ApiError {
     Unauthorized,
+    Forbidden,
+    ReadOnlyReplica,
+    SafeMode,
     BadRequest(String),
+    NotFound(String),
+    TooManyRequests(String),
     Timeout,
     InternalError(String),
 }
@@ -33,10 +45,30 @@ impl IntoResponse for ApiError {
                 StatusCode::UNAUTHORIZED,
                 ErrorResponse::new("unauthorized", "Invalid or missing API key"),
             ),
+            ApiError::Forbidden => (
+                StatusCode::FORBIDDEN,
+                ErrorResponse::new("forbidden", "Admin scope required"),
+            ),
+            ApiError::ReadOnlyReplica => (
+                StatusCode::FORBIDDEN,
+                ErrorResponse::new("read_only_replica", "Read-only replica; send writes to the writer instance"),
+            ),
+            ApiError::SafeMode => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                ErrorResponse::new("safe_mode", "Safe mode is engaged; writes are frozen until it is lifted"),
+            ),
             ApiError::BadRequest(msg) => (
                 StatusCode::BAD_REQUEST,
                 ErrorResponse::new("bad_request", msg),
             ),
+            ApiError::NotFound(msg) => (
+                StatusCode::NOT_FOUND,
+                ErrorResponse::new("not_found", msg),
+            ),
+            ApiError::TooManyRequests(msg) => (
+                StatusCode::TOO_MANY_REQUESTS,
+                ErrorResponse::new("too_many_requests", msg),
+            ),
             ApiError::Timeout => (
                 StatusCode::REQUEST_TIMEOUT,
                 ErrorResponse::new("timeout", "Request timed out"),
@@ -51,11 +83,19 @@ impl IntoResponse for ApiError {
     }
 }
 
-/// Extract API key from headers
+/// Extract API key (or session token) from headers
+///
+/// `X-API-Key: <key>` or `Authorization: Bearer <token>`
 fn extract_api_key(headers: &HeaderMap) -> Option<String> {
     headers
         .get("X-API-Key")
         .and_then(|v| v.to_str().ok())
+        .or_else(|| {
+            headers
+                .get(axum::http::header::AUTHORIZATION)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.strip_prefix("Bearer "))
+        })
         .map(|s| s.to_string())
 }
 
@@ -63,6 +103,14 @@ fn extract_api_key(headers: &HeaderMap) -> Option<String> {
 // Query Handler
 // ============================================================================
 
+/// Gateway rejection, in the configured locale
+pub(crate) fn gateway_error(e: crate::gateway::GatewayError) -> ApiError {
+    match e {
+        crate::gateway::GatewayError::ReadOnly(_) => ApiError::ReadOnlyReplica,
+        e => ApiError::InternalError(format!("{}: {}", tr("gateway.error"), e.localized())),
+    }
+}
+
 /// POST /api/v1/query
 ///
 /// Process a text query through the system
@@ -96,7 +144,8 @@ pub async fn handle_query(
         .gateway
         .inject(signal)
         .await
-        .map_err(|e| ApiError::InternalError(format!("Gateway error: {}", e)))?;
+        .map_err(gateway_error)?;
+    let gateway_us = start.elapsed().as_micros() as u64;
 
     // Wait for result with timeout
     let timeout_duration = std::time::Duration::from_millis(
@@ -117,6 +166,9 @@ pub async fn handle_query(
         ));
     }
 
+    let bootstrap = state.bootstrap.clone();
+    let chat = state.chat.clone();
+
     // Parse output JSON to extract signal data
     let state: [f32; 8] = result.output
         .get("state")
@@ -134,25 +186,41 @@ pub async fn handle_query(
         .and_then(|v| v.as_f64())
         .map(|c| c as f32);
 
-    let response_text = result.output
-        .get("response")
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
+    let response_text = {
+        let composer = crate::adapters::composer::ResponseComposer::default();
+        match &bootstrap {
+            Some(bootstrap) => composer.compose_with(&result, bootstrap.as_ref()),
+            None => composer.compose(&result),
+        }
+    };
+
+    let mut provenance: QueryProvenance = result.output
+        .get("provenance")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default();
+    provenance.timings.gateway_us = gateway_us;
+    provenance.timings.total_us = processing_time;
+    if let Some(bootstrap) = &bootstrap {
+        provenance.traversed_edges = traversed_edges(&result.output, &bootstrap.read());
+    }
 
     let matched_tokens = result.output
         .get("matched_tokens")
         .and_then(|v| v.as_u64())
-        .unwrap_or(0) as usize;
+        .map(|n| n as usize)
+        .unwrap_or(provenance.matched_tokens.len());
 
     let unknown_words = result.output
         .get("unknown_words")
         .and_then(|v| v.as_u64())
-        .unwrap_or(0) as usize;
+        .map(|n| n as usize)
+        .unwrap_or(provenance.unknown_words.len());
 
     let decision_source = result.output
         .get("decision_source")
         .and_then(|v| v.as_str())
-        .map(|s| s.to_string());
+        .map(|s| s.to_string())
+        .or_else(|| provenance.decision_source.as_ref().map(|s| s.name().to_string()));
 
     // Build response
     let response = QueryResponse {
@@ -167,11 +235,54 @@ pub async fn handle_query(
             decision_source,
             confidence,
         },
+        provenance: Some(Box::new(provenance)),
     };
 
+    if let Some(chat) = &chat {
+        record_exchange(chat, &req.context, &req.query, &response).await;
+    }
+
     Ok(Json(response))
 }
 
+/// Edges between consecutive nodes of the answer path
+fn traversed_edges(
+    output: &serde_json::Value,
+    library: &crate::bootstrap::BootstrapLibrary,
+) -> Vec<TraversedEdge> {
+    let path: Vec<u32> = output
+        .get("strongest_path")
+        .or_else(|| output.get("path"))
+        .and_then(|v| v.as_array())
+        .map(|p| p.iter().filter_map(|id| id.as_u64()).map(|id| id as u32).collect())
+        .unwrap_or_default();
+    if path.len() < 2 {
+        return Vec::new();
+    }
+
+    let graph = library.graph();
+    let words: HashMap<u32, &String> = library.concepts_iter().map(|(w, c)| (c.id, w)).collect();
+    path.windows(2)
+        .filter_map(|pair| {
+            let (from_id, to_id) = (pair[0], pair[1]);
+            let edge = graph
+                .get_neighbors(from_id, crate::graph::Direction::Both)
+                .into_iter()
+                .filter(|(neighbor_id, _)| *neighbor_id == to_id)
+                .filter_map(|(_, edge_id)| graph.get_edge(edge_id))
+                .max_by(|a, b| a.weight.partial_cmp(&b.weight).unwrap_or(std::cmp::Ordering::Equal))?;
+            Some(TraversedEdge {
+                from_id,
+                to_id,
+                from_word: words.get(&from_id).map(|w| (*w).clone()),
+                to_word: words.get(&to_id).map(|w| (*w).clone()),
+                edge_type: crate::graph::export::edge_type_name(edge.edge_type),
+                weight: edge.weight,
+            })
+        })
+        .collect()
+}
+
 // ============================================================================
 // Feedback Handler
 // ============================================================================
@@ -212,7 +323,10 @@ pub async fn handle_feedback(
         .feedback_processor
         .process(feedback_signal)
         .await
-        .map_err(|e| ApiError::InternalError(format!("Feedback error: {}", e)))?;
+        .map_err(|e| match e {
+            crate::feedback::FeedbackError::LearningPaused => ApiError::BadRequest(e.localized()),
+            _ => ApiError::InternalError(format!("{}: {}", tr("feedback.error"), e.localized())),
+        })?;
 
     let response = FeedbackResponse {
         success: result.success,
@@ -273,142 +387,1804 @@ pub async fn handle_status(
 }
 
 // ============================================================================
-// Statistics Handler
+// Decision Trace Handler
 // ============================================================================
 
-/// GET /api/v1/stats
+/// Default number of traces returned by /decisions
+const DEFAULT_DECISIONS_LIMIT: usize = 50;
+
+/// GET /api/v1/decisions?limit=N
 ///
-/// Get detailed statistics
-pub async fn handle_stats(
+/// Get recent Arbiter decision traces (explainability)
+pub async fn handle_decisions(
     State(state): State<ApiState>,
     headers: HeaderMap,
-) -> Result<Json<StatsResponse>, ApiError> {
+    Query(query): Query<DecisionsQuery>,
+) -> Result<Json<DecisionsResponse>, ApiError> {
     // Validate API key
     let api_key = extract_api_key(&headers);
     if !state.validate_api_key(api_key.as_deref()) {
         return Err(ApiError::Unauthorized);
     }
 
-    // Get gateway stats
-    let gateway_stats = state.gateway.stats();
+    let log = state.decision_log.as_ref().ok_or_else(|| {
+        ApiError::InternalError("Decision tracing is not enabled".to_string())
+    })?;
 
-    let gateway_stats_response = GatewayStats {
-        total_signals: gateway_stats.total_signals,
-        text_signals: gateway_stats.text_signals,
-        tick_signals: gateway_stats.tick_signals,
-        command_signals: gateway_stats.command_signals,
-        feedback_signals: gateway_stats.feedback_signals,
-        unknown_words: gateway_stats.unknown_words,
-        queue_overflows: gateway_stats.queue_overflows,
-        timeouts: gateway_stats.timeouts,
-        errors: gateway_stats.errors,
-        avg_processing_time_us: gateway_stats.avg_processing_time_us(),
-        success_rate: gateway_stats.success_rate() as f32,
-    };
+    let limit = query.limit.unwrap_or(DEFAULT_DECISIONS_LIMIT);
 
-    // Get curiosity stats if available
-    let curiosity_stats_response = state.curiosity.as_ref().map(|c| {
-        let stats = c.stats();
-        CuriosityStats {
-            uncertainty: UncertaintyStats {
-                total_cells: stats.uncertainty.total_cells,
-                total_visits: stats.uncertainty.total_visits,
-                avg_confidence: stats.uncertainty.avg_confidence,
-                avg_visits: stats.uncertainty.avg_visits,
-            },
-            surprise: SurpriseStats {
-                current_surprise: stats.surprise.current_surprise,
-                avg_surprise: stats.surprise.avg_surprise,
-                max_recent_surprise: stats.surprise.max_recent_surprise,
-                history_size: stats.surprise.history_size,
-                total_events: stats.surprise.total_events,
-            },
-            novelty: NoveltyStats {
-                unique_states: stats.novelty.unique_states,
-                total_observations: stats.novelty.total_observations,
-                total_unique_seen: stats.novelty.total_unique_seen,
-            },
-            exploration: ExplorationStats {
-                queue_size: stats.exploration.queue_size,
-                total_added: stats.exploration.total_added,
-                total_explored: stats.exploration.total_explored,
-            },
-            autonomous_enabled: stats.autonomous_enabled,
-        }
+    Ok(Json(DecisionsResponse {
+        decisions: log.recent(limit),
+        total_recorded: log.total_recorded(),
+    }))
+}
+
+// ============================================================================
+// Log Handlers
+// ============================================================================
+
+/// Default number of entries returned by /logs
+const DEFAULT_LOGS_LIMIT: usize = 200;
+
+/// Default backlog sent before live entries by /logs/stream
+const DEFAULT_LOG_STREAM_BACKLOG: usize = 100;
+
+/// GET /api/v1/logs
+///
+/// Recent core log entries (admin scope)
+pub async fn handle_logs(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Query(query): Query<LogsQuery>,
+) -> Result<Json<LogsResponse>, ApiError> {
+    require_admin(&state, &headers)?;
+
+    let limit = query.limit.unwrap_or(DEFAULT_LOGS_LIMIT);
+
+    Ok(Json(LogsResponse {
+        entries: crate::log_stream::LOG_HUB.recent(&query.filter(), limit),
+    }))
+}
+
+/// GET /api/v1/logs/stream
+///
+/// Server-sent events: matching backlog, then live entries (admin scope).
+/// Each event is named `log`, carries the entry as JSON and uses its `seq` as ID.
+pub async fn handle_log_stream(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Query(query): Query<LogsQuery>,
+) -> Result<Sse<impl futures::Stream<Item = Result<Event, axum::Error>>>, ApiError> {
+    require_admin(&state, &headers)?;
+
+    let backlog = query.limit.unwrap_or(DEFAULT_LOG_STREAM_BACKLOG);
+    let subscription = crate::log_stream::LOG_HUB.subscribe(query.filter(), backlog);
+
+    let stream = futures::stream::unfold(subscription, |mut subscription| async move {
+        let entry = subscription.next().await?;
+        let event = Event::default()
+            .event("log")
+            .id(entry.seq.to_string())
+            .json_data(&entry);
+        Some((event, subscription))
     });
 
-    let response = StatsResponse {
-        gateway: gateway_stats_response,
-        curiosity: curiosity_stats_response,
-    };
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
 
-    Ok(Json(response))
+// ============================================================================
+// Ingestion Handlers
+// ============================================================================
+
+/// Largest document accepted by /ingest
+const MAX_INGEST_BYTES: usize = 16 * 1024 * 1024;
+
+fn ingestor(state: &ApiState) -> Result<Arc<crate::ingestion::Ingestor>, ApiError> {
+    state
+        .ingestor
+        .clone()
+        .ok_or_else(|| ApiError::InternalError("Document ingestion is not enabled".to_string()))
+}
+
+/// POST /api/v1/ingest
+///
+/// Ingest one .txt, .md or .csv document (e.g. a file dropped on the UI).
+/// Progress is published on /ingest/events while this request runs.
+pub async fn handle_ingest(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Json(req): Json<IngestRequest>,
+) -> Result<Json<IngestResponse>, ApiError> {
+    require_admin(&state, &headers)?;
+    reject_in_safe_mode(&state)?;
+
+    let ingestor = ingestor(&state)?;
+    if req.content.len() > MAX_INGEST_BYTES {
+        return Err(ApiError::BadRequest(format!(
+            "Document too large: {} bytes (max {})",
+            req.content.len(),
+            MAX_INGEST_BYTES
+        )));
+    }
+    let format = crate::ingestion::DocumentFormat::from_name(&req.filename).ok_or_else(|| {
+        ApiError::BadRequest(crate::ingestion::IngestionError::UnsupportedFormat(req.filename.clone()).to_string())
+    })?;
+
+    // Ingestion is CPU-bound and takes graph write locks
+    let document = req.filename.clone();
+    let report = tokio::task::spawn_blocking(move || ingestor.ingest_document(&req.filename, &req.content))
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Ingestion task failed: {}", e)))?
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    Ok(Json(IngestResponse { document, format, report }))
+}
+
+/// GET /api/v1/ingest/events
+///
+/// Server-sent events: `ingestion` events (started, progress, finished)
+/// for every document ingested while connected
+pub async fn handle_ingest_events(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Sse<impl futures::Stream<Item = Result<Event, axum::Error>>>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let receiver = ingestor(&state)?.subscribe();
+    let stream = futures::stream::unfold(receiver, |mut receiver| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(event) => {
+                    let event = Event::default().event("ingestion").json_data(&event);
+                    return Some((event, receiver));
+                }
+                // Progress is advisory: skip what a slow client missed
+                Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(tokio::sync::broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
 }
 
 // ============================================================================
-// Health Check Handler
+// Sync Handlers
 // ============================================================================
 
-/// GET /api/v1/health
+fn sync_node(state: &ApiState) -> Result<Arc<crate::sync::SyncNode>, ApiError> {
+    state
+        .sync
+        .clone()
+        .ok_or_else(|| ApiError::InternalError("Knowledge sync is not enabled".to_string()))
+}
+
+/// GET /api/v1/sync/delta?since=1700000000
 ///
-/// Health check endpoint
-pub async fn handle_health(
+/// Concepts and connections changed since `since`, for a peer to apply
+pub async fn handle_sync_export(
     State(state): State<ApiState>,
-) -> Result<Json<HealthResponse>, ApiError> {
-    let mut checks = HashMap::new();
+    headers: HeaderMap,
+    Query(query): Query<SyncDeltaQuery>,
+) -> Result<Json<crate::sync::GraphDelta>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
 
-    // Check gateway
-    checks.insert("gateway".to_string(), true);
+    Ok(Json(sync_node(&state)?.export_delta(query.since)))
+}
 
-    // Check curiosity if available
-    if let Some(_curiosity) = &state.curiosity {
-        checks.insert("curiosity".to_string(), true);
+/// POST /api/v1/sync/delta
+///
+/// Merge a peer's delta (admin scope: it rewrites learned connections)
+pub async fn handle_sync_apply(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Json(delta): Json<crate::sync::GraphDelta>,
+) -> Result<Json<crate::sync::SyncReport>, ApiError> {
+    require_admin(&state, &headers)?;
+    reject_in_safe_mode(&state)?;
+
+    let node = sync_node(&state)?;
+    let report = tokio::task::spawn_blocking(move || node.apply_delta(&delta))
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Sync task failed: {}", e)))?
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+
+    Ok(Json(report))
+}
+
+// ============================================================================
+// Replication Handlers
+// ============================================================================
+
+/// GET /api/v1/replication/changes?after=42&epoch=...
+///
+/// Writer's graph changes after `after` (a snapshot for fresh or lagging replicas)
+pub async fn handle_replication_changes(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Query(query): Query<ReplicationChangesQuery>,
+) -> Result<Json<crate::replica::ChangeBatch>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
     }
 
-    let all_healthy = checks.values().all(|&v| v);
+    let feed = state
+        .change_feed
+        .clone()
+        .ok_or_else(|| ApiError::InternalError("Change feed is not enabled".to_string()))?;
+    let batch = tokio::task::spawn_blocking(move || {
+        feed.changes_since(query.epoch.as_deref(), query.after, query.limit)
+    })
+    .await
+    .map_err(|e| ApiError::InternalError(format!("Replication task failed: {}", e)))?;
 
-    let response = HealthResponse {
-        status: if all_healthy {
-            "healthy".to_string()
-        } else {
-            "unhealthy".to_string()
-        },
-        checks,
+    Ok(Json(batch))
+}
+
+/// GET /api/v1/replication/status
+///
+/// Replication role of this instance and, for replicas, how far behind they are
+pub async fn handle_replication_status(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<ReplicationStatusResponse>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let role = match (&state.replica, &state.change_feed) {
+        (Some(_), _) => "replica",
+        (None, Some(_)) => "writer",
+        (None, None) => "standalone",
     };
 
-    Ok(Json(response))
+    Ok(Json(ReplicationStatusResponse {
+        role: role.to_string(),
+        epoch: state.change_feed.as_ref().map(|feed| feed.epoch().to_string()),
+        head: state.change_feed.as_ref().map(|feed| feed.head()),
+        replica: state.replica.as_ref().map(|replica| replica.status()),
+    }))
 }
 
 // ============================================================================
-// Metrics Handler (v0.42.0)
+// I18n Handler
 // ============================================================================
 
-/// Prometheus metrics endpoint
+/// GET /api/v1/i18n?locale=ru
 ///
-/// Returns metrics in Prometheus exposition format for scraping.
-/// No authentication required for metrics endpoint (standard practice).
+/// Message catalog for UI labels
+pub async fn handle_i18n(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Query(query): Query<I18nQuery>,
+) -> Result<Json<I18nResponse>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let locale = match query.locale {
+        Some(locale) => locale.parse().map_err(ApiError::BadRequest)?,
+        None => crate::i18n::locale(),
+    };
+    Ok(Json(I18nResponse {
+        locale,
+        available: crate::i18n::Locale::ALL.to_vec(),
+        messages: crate::i18n::messages(locale)
+            .into_iter()
+            .map(|(key, message)| (key.to_string(), message.to_string()))
+            .collect(),
+    }))
+}
+
+// ============================================================================
+// Module Lifecycle Handlers
+// ============================================================================
+
+fn module_action_error(e: crate::module_registry::ModuleActionError) -> ApiError {
+    use crate::module_registry::ModuleActionError;
+    match e {
+        ModuleActionError::NotFound(_) => ApiError::NotFound(e.to_string()),
+        ModuleActionError::InvalidAction(_) | ModuleActionError::Failed { .. } => {
+            ApiError::BadRequest(e.to_string())
+        }
+    }
+}
+
+/// GET /api/v1/modules
 ///
-/// # Example
+/// Core modules with their enable flags plus managed subsystems with
+/// lifecycle state and health (admin scope)
+pub async fn handle_modules(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<ModulesResponse>, ApiError> {
+    require_admin(&state, &headers)?;
+
+    let registry = &crate::module_registry::REGISTRY;
+    Ok(Json(ModulesResponse {
+        modules: registry.get_all_modules(),
+        managed: registry.managed_modules(),
+    }))
+}
+
+/// POST /api/v1/modules/:name/:action
 ///
-/// ```bash
-/// curl http://localhost:8080/metrics
-/// ```
-pub async fn handle_metrics() -> Result<impl IntoResponse, ApiError> {
-    match crate::metrics::export_metrics() {
-        Ok(metrics_text) => {
-            // Return with proper content type for Prometheus
-            Ok((
-                StatusCode::OK,
-                [(
-                    axum::http::header::CONTENT_TYPE,
-                    "text/plain; version=0.0.4; charset=utf-8",
-                )],
-                metrics_text,
-            ))
-        }
-        Err(e) => Err(ApiError::InternalError(format!(
-            "Failed to export metrics: {}",
-            e
-        ))),
+/// Start, stop or restart a managed subsystem (admin scope)
+pub async fn handle_module_action(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Path((name, action)): Path<(String, String)>,
+) -> Result<Json<crate::module_registry::ManagedModuleInfo>, ApiError> {
+    require_admin(&state, &headers)?;
+
+    let action = action
+        .parse::<crate::module_registry::ModuleAction>()
+        .map_err(module_action_error)?;
+    crate::module_registry::REGISTRY
+        .module_action(&name, action)
+        .await
+        .map(Json)
+        .map_err(module_action_error)
+}
+
+// ============================================================================
+// Graph Visualization Handler
+// ============================================================================
+
+/// Default neighborhood radius for /graph/subgraph.dot
+const DEFAULT_SUBGRAPH_RADIUS: usize = 1;
+
+/// Maximum neighborhood radius (keeps output renderable)
+const MAX_SUBGRAPH_RADIUS: usize = 3;
+
+/// Center node from a concept word or a node ID
+fn subgraph_center(
+    library: &crate::bootstrap::BootstrapLibrary,
+    word: Option<&str>,
+    node_id: Option<u32>,
+) -> Result<u32, ApiError> {
+    match (word, node_id) {
+        (Some(word), _) => library
+            .get_concept(&word.to_lowercase())
+            .map(|c| c.id)
+            .ok_or_else(|| ApiError::BadRequest(format!("Unknown concept '{}'", word))),
+        (None, Some(id)) if library.graph().contains_node(id) => Ok(id),
+        (None, Some(id)) => Err(ApiError::BadRequest(format!("Unknown node {}", id))),
+        (None, None) => Err(ApiError::BadRequest("Provide 'word' or 'node_id'".to_string())),
+    }
+}
+
+/// GET /api/v1/graph/subgraph.dot?word=cat&radius=2
+///
+/// Graphviz DOT of the neighborhood around a concept
+pub async fn handle_subgraph_dot(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Query(query): Query<SubgraphDotQuery>,
+) -> Result<Response, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
     }
+
+    let bootstrap = state.bootstrap.as_ref().ok_or_else(|| {
+        ApiError::InternalError("Graph visualization is not enabled".to_string())
+    })?;
+    let library = bootstrap.read();
+    let center = subgraph_center(&library, query.word.as_deref(), query.node_id)?;
+
+    let radius = query.radius.unwrap_or(DEFAULT_SUBGRAPH_RADIUS).min(MAX_SUBGRAPH_RADIUS);
+    let subgraph = library.graph().extract_neighborhood(center, radius);
+
+    let labels = library
+        .concepts_iter()
+        .filter(|(_, c)| subgraph.contains_node(c.id))
+        .map(|(word, c)| (c.id, word.clone()))
+        .collect();
+    let options = crate::graph::export::DotOptions {
+        labels,
+        highlight: Some(center),
+        edge_labels: query.edge_labels,
+        ..Default::default()
+    };
+    let dot = subgraph.to_dot(library.graph(), &options);
+
+    Ok(([(axum::http::header::CONTENT_TYPE, "text/vnd.graphviz; charset=utf-8")], dot).into_response())
+}
+
+/// GET /api/v1/graph/subgraph?word=cat&depth=2&layout=projected
+///
+/// Neighborhood around a concept with node positions and typed, colored
+/// edges, for interactive graph views
+pub async fn handle_subgraph(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Query(query): Query<SubgraphQuery>,
+) -> Result<Json<crate::graph::export::SubgraphView>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let bootstrap = state.bootstrap.as_ref().ok_or_else(|| {
+        ApiError::InternalError("Graph visualization is not enabled".to_string())
+    })?;
+    let library = bootstrap.read();
+    let center = subgraph_center(&library, query.word.as_deref(), query.node_id)?;
+
+    let depth = query.depth.unwrap_or(DEFAULT_SUBGRAPH_RADIUS).min(MAX_SUBGRAPH_RADIUS);
+    let layout = query.layout.unwrap_or(crate::graph::export::ViewLayout::Force);
+    let view = library
+        .subgraph_view(center, depth, layout)
+        .ok_or_else(|| ApiError::BadRequest(format!("Unknown node {}", center)))?;
+    Ok(Json(view))
+}
+
+const DEFAULT_NEIGHBORS_LIMIT: usize = 20;
+
+/// GET /api/v1/graph/neighbors?word=cat&limit=10
+///
+/// Direct neighbors of a concept, strongest edge first
+pub async fn handle_neighbors(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Query(query): Query<NeighborsQuery>,
+) -> Result<Json<NeighborsResponse>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let bootstrap = state.bootstrap.as_ref().ok_or_else(|| {
+        ApiError::InternalError("Graph queries are not enabled".to_string())
+    })?;
+    let library = bootstrap.read();
+    let graph = library.graph();
+
+    let word = query.word.to_lowercase();
+    let node_id = library
+        .get_concept(&word)
+        .map(|c| c.id)
+        .ok_or_else(|| ApiError::BadRequest(format!("Unknown concept '{}'", query.word)))?;
+
+    let words: HashMap<u32, &String> = library.concepts_iter().map(|(w, c)| (c.id, w)).collect();
+    let mut neighbors: Vec<Neighbor> = graph
+        .get_neighbors(node_id, crate::graph::Direction::Both)
+        .into_iter()
+        .filter_map(|(neighbor_id, edge_id)| {
+            let edge = graph.get_edge(edge_id)?;
+            Some(Neighbor {
+                node_id: neighbor_id,
+                word: words.get(&neighbor_id).map(|w| (*w).clone()),
+                weight: edge.weight,
+                edge_type: crate::graph::export::edge_type_name(edge.edge_type),
+            })
+        })
+        .collect();
+    neighbors.sort_by(|a, b| b.weight.partial_cmp(&a.weight).unwrap_or(std::cmp::Ordering::Equal));
+    neighbors.truncate(query.limit.unwrap_or(DEFAULT_NEIGHBORS_LIMIT));
+
+    Ok(Json(NeighborsResponse { word, node_id, neighbors }))
+}
+
+/// POST /api/v1/query/ngql
+///
+/// Run an NGQL query, e.g. `{"query": "ANALOGY king:queen::man:?"}`
+pub async fn handle_ngql(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Json(request): Json<NgqlRequest>,
+) -> Result<Json<crate::ngql::NgqlResult>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let bootstrap = state.bootstrap.as_ref().ok_or_else(|| {
+        ApiError::InternalError("Graph queries are not enabled".to_string())
+    })?;
+    let result = crate::ngql::query(&bootstrap.read(), &request.query).map_err(|e| match e {
+        crate::ngql::NgqlError::Failed(_) => ApiError::InternalError(e.to_string()),
+        _ => ApiError::BadRequest(e.to_string()),
+    })?;
+
+    Ok(Json(result))
+}
+
+const DEFAULT_GROUNDED_TOLERANCE: f32 = 0.5;
+const DEFAULT_GROUNDED_LIMIT: usize = 20;
+
+/// POST /api/v1/query/grounded
+///
+/// Concepts by color and/or emotion, e.g. `{"color": "blue", "emotion": "calm"}`
+pub async fn handle_grounded_query(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Json(request): Json<GroundedQueryRequest>,
+) -> Result<Json<GroundedQueryResponse>, ApiError> {
+    use crate::bootstrap::grounded::GroundedTerm;
+    use crate::bootstrap::lexicon::Modality;
+
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let bootstrap = state.bootstrap.as_ref().ok_or_else(|| {
+        ApiError::InternalError("Graph queries are not enabled".to_string())
+    })?;
+    let library = bootstrap.read();
+
+    let tolerance = request.tolerance.unwrap_or(DEFAULT_GROUNDED_TOLERANCE);
+    let mut terms = Vec::new();
+    for (modality, value) in [(Modality::Color, &request.color), (Modality::Emotion, &request.emotion)] {
+        let values = match value {
+            None => continue,
+            Some(GroundedValue::Values(values)) => values.clone(),
+            Some(GroundedValue::Word(word)) => library.anchor_of(modality, word).ok_or_else(|| {
+                ApiError::BadRequest(format!("No {} anchor for '{}'", modality, word))
+            })?,
+        };
+        terms.push(GroundedTerm::new(modality, &values, tolerance));
+    }
+    if terms.is_empty() {
+        return Err(ApiError::BadRequest("Give a color and/or an emotion".to_string()));
+    }
+
+    let concepts = library
+        .find_grounded(&terms, request.limit.unwrap_or(DEFAULT_GROUNDED_LIMIT))
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?
+        .into_iter()
+        .map(|m| GroundedConcept { word: m.word, node_id: m.id, score: m.score, matched: m.matched })
+        .collect();
+
+    Ok(Json(GroundedQueryResponse { concepts }))
+}
+
+// ============================================================================
+// ADNA Handlers
+// ============================================================================
+
+fn adna_reader(state: &ApiState) -> Result<&crate::adna::InMemoryADNAReader, ApiError> {
+    state
+        .adna
+        .as_deref()
+        .ok_or_else(|| ApiError::InternalError("ADNA access is not enabled".to_string()))
+}
+
+/// GET /api/v1/adna
+///
+/// Current appraiser configuration
+pub async fn handle_get_adna(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<crate::adna::AppraiserConfig>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    use crate::adna::ADNAReader;
+    let config = adna_reader(&state)?
+        .get_appraiser_config()
+        .await
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    Ok(Json(config))
+}
+
+/// POST /api/v1/adna
+///
+/// Partial update, e.g. `{"curiosity": {"weight": 0.4}}`; returns the new configuration
+pub async fn handle_update_adna(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Json(patch): Json<serde_json::Value>,
+) -> Result<Json<crate::adna::AppraiserConfig>, ApiError> {
+    require_admin(&state, &headers)?;
+    reject_in_safe_mode(&state)?;
+
+    use crate::adna::ADNAReader;
+    let adna = adna_reader(&state)?;
+    let current = adna
+        .get_appraiser_config()
+        .await
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+
+    let mut value = serde_json::to_value(current)
+        .map_err(|e| ApiError::InternalError(e.to_string()))?;
+    crate::settings::merge_known_fields(&mut value, &patch, "ADNA").map_err(ApiError::BadRequest)?;
+    let updated: crate::adna::AppraiserConfig = serde_json::from_value(value)
+        .map_err(|e| ApiError::BadRequest(format!("Invalid ADNA value: {}", e)))?;
+
+    adna.update_config(updated).await;
+    Ok(Json(updated))
+}
+
+// ============================================================================
+// CDNA Handlers
+// ============================================================================
+
+fn guardian(state: &ApiState) -> Result<&parking_lot::RwLock<crate::guardian::Guardian>, ApiError> {
+    state
+        .guardian
+        .as_deref()
+        .ok_or_else(|| ApiError::InternalError("CDNA access is not enabled".to_string()))
+}
+
+/// Admin-scope check: 401 without any valid key, 403 with a non-admin key
+fn require_admin(state: &ApiState, headers: &HeaderMap) -> Result<(), ApiError> {
+    let api_key = extract_api_key(headers);
+    if state.validate_admin_key(api_key.as_deref()) {
+        Ok(())
+    } else if api_key.is_some() && state.validate_api_key(api_key.as_deref()) {
+        Err(ApiError::Forbidden)
+    } else {
+        Err(ApiError::Unauthorized)
+    }
+}
+
+/// Writes to knowledge and configuration are frozen while safe mode is engaged
+fn reject_in_safe_mode(state: &ApiState) -> Result<(), ApiError> {
+    if state.safe_mode.is_engaged() {
+        Err(ApiError::SafeMode)
+    } else {
+        Ok(())
+    }
+}
+
+/// GET /api/v1/cdna
+///
+/// Active CDNA profile (admin scope)
+pub async fn handle_get_cdna(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<CdnaResponse>, ApiError> {
+    require_admin(&state, &headers)?;
+
+    let guardian = guardian(&state)?.read();
+    Ok(Json(CdnaResponse::from_cdna(guardian.cdna())))
+}
+
+/// POST /api/v1/cdna
+///
+/// Partial update, e.g. `{"max_out_degree": 1500}` (admin scope); the patch
+/// is validated by Guardian and the new profile revision is returned
+pub async fn handle_update_cdna(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Json(patch): Json<serde_json::Value>,
+) -> Result<Json<CdnaResponse>, ApiError> {
+    require_admin(&state, &headers)?;
+    reject_in_safe_mode(&state)?;
+
+    let patch: crate::cdna::ProfilePatch = serde_json::from_value(patch)
+        .map_err(|e| ApiError::BadRequest(format!("Invalid CDNA patch: {}", e)))?;
+
+    let mut guardian = guardian(&state)?.write();
+    let cdna = guardian.patch_cdna(&patch).map_err(ApiError::BadRequest)?;
+    Ok(Json(CdnaResponse::from_cdna(cdna)))
+}
+
+// ============================================================================
+// Checkpoint Handlers
+// ============================================================================
+
+fn checkpoint_manager(state: &ApiState) -> Result<&crate::checkpoint::CheckpointManager, ApiError> {
+    state
+        .checkpoint
+        .as_deref()
+        .ok_or_else(|| ApiError::InternalError("Checkpointing is not enabled".to_string()))
+}
+
+fn checkpoint_error(e: crate::checkpoint::CheckpointError) -> ApiError {
+    use crate::checkpoint::CheckpointError;
+    match e {
+        CheckpointError::NotFound(_) | CheckpointError::Corrupt(_) | CheckpointError::UnknownSection(_) => {
+            ApiError::BadRequest(e.to_string())
+        }
+        _ => ApiError::InternalError(e.to_string()),
+    }
+}
+
+/// POST /api/v1/checkpoint
+///
+/// Write a whole-system checkpoint
+pub async fn handle_checkpoint(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Json(request): Json<CheckpointRequest>,
+) -> Result<Json<crate::checkpoint::CheckpointManifest>, ApiError> {
+    require_admin(&state, &headers)?;
+
+    let manager = checkpoint_manager(&state)?;
+    let manifest = manager
+        .checkpoint(request.label.as_deref())
+        .await
+        .map_err(checkpoint_error)?;
+    Ok(Json(manifest))
+}
+
+/// GET /api/v1/checkpoints
+///
+/// List available checkpoints
+pub async fn handle_list_checkpoints(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<CheckpointListResponse>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let manager = checkpoint_manager(&state)?;
+    let checkpoints = manager.list().map_err(checkpoint_error)?;
+    Ok(Json(CheckpointListResponse { checkpoints }))
+}
+
+/// POST /api/v1/checkpoint/restore
+///
+/// Restore a checkpoint (the newest one if no ID is given)
+pub async fn handle_restore_checkpoint(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Json(request): Json<CheckpointRestoreRequest>,
+) -> Result<Json<crate::checkpoint::CheckpointManifest>, ApiError> {
+    require_admin(&state, &headers)?;
+    reject_in_safe_mode(&state)?;
+
+    let manager = checkpoint_manager(&state)?;
+    let manifest = match request.id {
+        Some(id) => manager.restore(&id).await.map_err(checkpoint_error)?,
+        None => manager
+            .restore_latest()
+            .await
+            .map_err(checkpoint_error)?
+            .ok_or_else(|| ApiError::BadRequest("No checkpoints available".to_string()))?,
+    };
+    Ok(Json(manifest))
+}
+
+// ============================================================================
+// Statistics Handler
+// ============================================================================
+
+/// GET /api/v1/stats
+///
+/// Get detailed statistics
+pub async fn handle_stats(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<StatsResponse>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    // Get gateway stats
+    let gateway_stats = state.gateway.stats();
+
+    let gateway_stats_response = GatewayStats {
+        total_signals: gateway_stats.total_signals,
+        text_signals: gateway_stats.text_signals,
+        tick_signals: gateway_stats.tick_signals,
+        command_signals: gateway_stats.command_signals,
+        feedback_signals: gateway_stats.feedback_signals,
+        unknown_words: gateway_stats.unknown_words,
+        queue_overflows: gateway_stats.queue_overflows,
+        timeouts: gateway_stats.timeouts,
+        errors: gateway_stats.errors,
+        avg_processing_time_us: gateway_stats.avg_processing_time_us(),
+        success_rate: gateway_stats.success_rate() as f32,
+    };
+
+    // Get curiosity stats if available
+    let curiosity_stats_response = state.curiosity.as_ref().map(|c| {
+        let stats = c.stats();
+        CuriosityStats {
+            uncertainty: UncertaintyStats {
+                total_cells: stats.uncertainty.total_cells,
+                total_visits: stats.uncertainty.total_visits,
+                avg_confidence: stats.uncertainty.avg_confidence,
+                avg_visits: stats.uncertainty.avg_visits,
+            },
+            surprise: SurpriseStats {
+                current_surprise: stats.surprise.current_surprise,
+                avg_surprise: stats.surprise.avg_surprise,
+                max_recent_surprise: stats.surprise.max_recent_surprise,
+                history_size: stats.surprise.history_size,
+                total_events: stats.surprise.total_events,
+            },
+            novelty: NoveltyStats {
+                unique_states: stats.novelty.unique_states,
+                total_observations: stats.novelty.total_observations,
+                total_unique_seen: stats.novelty.total_unique_seen,
+            },
+            exploration: ExplorationStats {
+                queue_size: stats.exploration.queue_size,
+                total_added: stats.exploration.total_added,
+                total_explored: stats.exploration.total_explored,
+            },
+            autonomous_enabled: stats.autonomous_enabled,
+        }
+    });
+
+    let response = StatsResponse {
+        gateway: gateway_stats_response,
+        curiosity: curiosity_stats_response,
+        profiling: crate::profiling::PROFILER.snapshot(),
+    };
+
+    Ok(Json(response))
+}
+
+// ============================================================================
+// Profiling Handlers
+// ============================================================================
+
+/// GET /api/v1/profiling
+///
+/// Per-stage pipeline timing histograms
+pub async fn handle_profiling(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<crate::profiling::ProfilingSnapshot>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    Ok(Json(crate::profiling::PROFILER.snapshot()))
+}
+
+/// POST /api/v1/profiling
+///
+/// Replace profiling configuration (resets histograms)
+pub async fn handle_configure_profiling(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Json(config): Json<crate::profiling::ProfilingConfig>,
+) -> Result<Json<crate::profiling::ProfilingSnapshot>, ApiError> {
+    require_admin(&state, &headers)?;
+
+    crate::profiling::PROFILER
+        .configure(config)
+        .map_err(ApiError::BadRequest)?;
+
+    Ok(Json(crate::profiling::PROFILER.snapshot()))
+}
+
+/// DELETE /api/v1/profiling
+///
+/// Clear profiling histograms
+pub async fn handle_reset_profiling(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<crate::profiling::ProfilingSnapshot>, ApiError> {
+    require_admin(&state, &headers)?;
+
+    crate::profiling::PROFILER.reset();
+
+    Ok(Json(crate::profiling::PROFILER.snapshot()))
+}
+
+// ============================================================================
+// Health Check Handler
+// ============================================================================
+
+/// GET /api/v1/health
+///
+/// Health check endpoint
+pub async fn handle_health(
+    State(state): State<ApiState>,
+) -> Result<Json<HealthResponse>, ApiError> {
+    let mut checks = HashMap::new();
+
+    // Check gateway
+    checks.insert("gateway".to_string(), true);
+
+    // Check curiosity if available
+    if let Some(_curiosity) = &state.curiosity {
+        checks.insert("curiosity".to_string(), true);
+    }
+
+    let all_healthy = checks.values().all(|&v| v);
+
+    let response = HealthResponse {
+        status: if all_healthy {
+            "healthy".to_string()
+        } else {
+            "unhealthy".to_string()
+        },
+        checks,
+    };
+
+    Ok(Json(response))
+}
+
+// ============================================================================
+// Settings Handlers
+// ============================================================================
+
+fn settings_manager(state: &ApiState) -> Result<&crate::settings::SettingsManager, ApiError> {
+    state
+        .settings
+        .as_deref()
+        .ok_or_else(|| ApiError::InternalError("Runtime configuration is not enabled".to_string()))
+}
+
+fn settings_error(e: crate::settings::SettingsError) -> ApiError {
+    use crate::settings::SettingsError;
+    match e {
+        SettingsError::UnknownSection(_) | SettingsError::NotRegistered(_) => ApiError::NotFound(e.to_string()),
+        SettingsError::InvalidPatch(_) | SettingsError::Invalid(_) => ApiError::BadRequest(e.to_string()),
+        _ => ApiError::InternalError(e.to_string()),
+    }
+}
+
+/// GET /api/v1/config
+///
+/// Effective config of every registered section
+pub async fn handle_list_config(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<ConfigListResponse>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let settings = settings_manager(&state)?;
+    let sections = settings
+        .sections()
+        .into_iter()
+        .map(|section| Ok((section, settings.get(section).map_err(settings_error)?)))
+        .collect::<Result<_, ApiError>>()?;
+    Ok(Json(ConfigListResponse { sections }))
+}
+
+/// GET /api/v1/config/:section
+pub async fn handle_get_config(
+    State(state): State<ApiState>,
+    Path(section): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let settings = settings_manager(&state)?;
+    let section = section.parse().map_err(settings_error)?;
+    Ok(Json(settings.get(section).map_err(settings_error)?))
+}
+
+/// POST /api/v1/config/:section
+///
+/// Partial update, e.g. `{"boredom_threshold": 0.4}` (admin scope); applied
+/// live where the component supports it and stored as an override
+pub async fn handle_update_config(
+    State(state): State<ApiState>,
+    Path(section): Path<String>,
+    headers: HeaderMap,
+    Json(patch): Json<serde_json::Value>,
+) -> Result<Json<crate::settings::ConfigUpdate>, ApiError> {
+    require_admin(&state, &headers)?;
+    reject_in_safe_mode(&state)?;
+
+    let settings = settings_manager(&state)?;
+    let section = section.parse().map_err(settings_error)?;
+    let update = settings.update(section, patch).await.map_err(settings_error)?;
+    Ok(Json(update))
+}
+
+/// DELETE /api/v1/config/:section
+///
+/// Drop overrides and return to the startup config (admin scope)
+pub async fn handle_reset_config(
+    State(state): State<ApiState>,
+    Path(section): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<crate::settings::ConfigUpdate>, ApiError> {
+    require_admin(&state, &headers)?;
+    reject_in_safe_mode(&state)?;
+
+    let settings = settings_manager(&state)?;
+    let section = section.parse().map_err(settings_error)?;
+    let update = settings.reset(section).await.map_err(settings_error)?;
+    Ok(Json(update))
+}
+
+// ============================================================================
+// Chat History Handlers
+// ============================================================================
+
+const DEFAULT_CHAT_SEARCH_LIMIT: usize = 50;
+
+fn chat_history(state: &ApiState) -> Result<&crate::chat_history::ChatHistory, ApiError> {
+    state
+        .chat
+        .as_deref()
+        .ok_or_else(|| ApiError::InternalError("Chat history is not enabled".to_string()))
+}
+
+fn chat_error(e: crate::chat_history::ChatHistoryError) -> ApiError {
+    use crate::chat_history::ChatHistoryError;
+    match e {
+        ChatHistoryError::NotFound(_) => ApiError::NotFound(e.to_string()),
+        ChatHistoryError::ConversationFull(_) | ChatHistoryError::InvalidMessage(_) => {
+            ApiError::BadRequest(e.to_string())
+        }
+        ChatHistoryError::Config(_) | ChatHistoryError::Persistence(_) => {
+            ApiError::InternalError(e.to_string())
+        }
+    }
+}
+
+/// Record a query and its answer in the conversation named by the request context
+async fn record_exchange(
+    chat: &crate::chat_history::ChatHistory,
+    context: &HashMap<String, String>,
+    query: &str,
+    response: &QueryResponse,
+) {
+    use crate::chat_history::{ChatMessage, ChatRole};
+
+    let Some(raw) = context.get(CONVERSATION_CONTEXT_KEY) else {
+        return;
+    };
+    let Ok(id) = raw.parse::<u64>() else {
+        tracing::warn!(conversation = %raw, "Invalid conversation ID in query context");
+        return;
+    };
+
+    let mut messages = vec![ChatMessage::new(ChatRole::User, query).with_signal_id(response.signal_id)];
+    if let Some(text) = &response.response {
+        messages.push(ChatMessage::new(ChatRole::Assistant, text.clone()).with_signal_id(response.signal_id));
+    }
+    for message in messages {
+        if let Err(e) = chat.append(id, message).await {
+            // The answer is still returned; only the transcript is incomplete
+            tracing::warn!(conversation = id, error = %e, "Failed to record chat message");
+            return;
+        }
+    }
+}
+
+/// GET /api/v1/chat/conversations
+///
+/// Conversation summaries, most recently active first
+pub async fn handle_list_conversations(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<ConversationListResponse>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    Ok(Json(ConversationListResponse {
+        conversations: chat_history(&state)?.list(),
+    }))
+}
+
+/// POST /api/v1/chat/conversations
+///
+/// Start a conversation
+pub async fn handle_create_conversation(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Json(request): Json<CreateConversationRequest>,
+) -> Result<Json<crate::chat_history::ConversationSummary>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let summary = chat_history(&state)?
+        .create(request.title.as_deref())
+        .await
+        .map_err(chat_error)?;
+
+    Ok(Json(summary))
+}
+
+/// GET /api/v1/chat/conversations/:id
+///
+/// Conversation with all messages
+pub async fn handle_get_conversation(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Path(id): Path<u64>,
+) -> Result<Json<crate::chat_history::Conversation>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    chat_history(&state)?
+        .get(id)
+        .map(Json)
+        .ok_or_else(|| chat_error(crate::chat_history::ChatHistoryError::NotFound(id)))
+}
+
+/// POST /api/v1/chat/conversations/:id
+///
+/// Rename a conversation
+pub async fn handle_rename_conversation(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Path(id): Path<u64>,
+    Json(request): Json<RenameConversationRequest>,
+) -> Result<Json<crate::chat_history::ConversationSummary>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let summary = chat_history(&state)?
+        .rename(id, &request.title)
+        .await
+        .map_err(chat_error)?;
+
+    Ok(Json(summary))
+}
+
+/// DELETE /api/v1/chat/conversations/:id
+pub async fn handle_delete_conversation(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Path(id): Path<u64>,
+) -> Result<StatusCode, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    chat_history(&state)?.delete(id).await.map_err(chat_error)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /api/v1/chat/conversations/:id/messages
+///
+/// Append a message (clients that do not record through /query)
+pub async fn handle_append_message(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Path(id): Path<u64>,
+    Json(request): Json<AppendMessageRequest>,
+) -> Result<Json<crate::chat_history::ConversationSummary>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let mut message = crate::chat_history::ChatMessage::new(request.role, request.content);
+    message.signal_id = request.signal_id;
+    let summary = chat_history(&state)?.append(id, message).await.map_err(chat_error)?;
+
+    Ok(Json(summary))
+}
+
+/// GET /api/v1/chat/last
+///
+/// Most recently active conversation, for restoring the last session
+pub async fn handle_last_conversation(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<crate::chat_history::Conversation>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    chat_history(&state)?
+        .last_conversation()
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound("No conversations".to_string()))
+}
+
+/// GET /api/v1/chat/search?q=cat%20food&limit=20
+///
+/// Full-text search over messages and titles
+pub async fn handle_search_chat(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Query(query): Query<ChatSearchQuery>,
+) -> Result<Json<ChatSearchResponse>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let limit = query.limit.unwrap_or(DEFAULT_CHAT_SEARCH_LIMIT);
+
+    Ok(Json(ChatSearchResponse {
+        hits: chat_history(&state)?.search(&query.q, limit),
+    }))
+}
+
+// ============================================================================
+// Background Runtime Handlers
+// ============================================================================
+
+fn background_runtime(state: &ApiState) -> Result<&crate::background::BackgroundRuntime, ApiError> {
+    state
+        .background
+        .as_deref()
+        .ok_or_else(|| ApiError::InternalError("Background runtime is not enabled".to_string()))
+}
+
+fn background_error(e: crate::background::BackgroundError) -> ApiError {
+    use crate::background::BackgroundError;
+    match e {
+        BackgroundError::UnknownAction(_) | BackgroundError::ShuttingDown => {
+            ApiError::BadRequest(e.to_string())
+        }
+        BackgroundError::CheckpointUnavailable | BackgroundError::Checkpoint(_) => {
+            ApiError::InternalError(e.to_string())
+        }
+    }
+}
+
+/// GET /api/v1/runtime
+///
+/// Background mode status and tray menu
+pub async fn handle_runtime_status(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<crate::background::BackgroundStatus>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    Ok(Json(background_runtime(&state)?.status()))
+}
+
+/// POST /api/v1/runtime/:action
+///
+/// Execute a tray action: open, pause_learning, resume_learning,
+/// checkpoint or quit (admin scope)
+pub async fn handle_runtime_action(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Path(action): Path<String>,
+) -> Result<Json<crate::background::TrayActionOutcome>, ApiError> {
+    require_admin(&state, &headers)?;
+
+    let runtime = background_runtime(&state)?;
+    let action = action
+        .parse::<crate::background::TrayAction>()
+        .map_err(background_error)?;
+    runtime.perform(action).await.map(Json).map_err(background_error)
+}
+
+// ============================================================================
+// Safe Mode Handlers
+// ============================================================================
+
+/// GET /api/v1/safe_mode
+pub async fn handle_safe_mode_status(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<SafeModeResponse>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    Ok(Json(SafeModeResponse { status: state.safe_mode.status(), changed: false }))
+}
+
+/// POST /api/v1/safe_mode
+///
+/// Engage this instance's kill switch, e.g. `{"reason": "runaway writes"}` (admin scope)
+pub async fn handle_engage_safe_mode(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Json(request): Json<SafeModeRequest>,
+) -> Result<Json<SafeModeResponse>, ApiError> {
+    require_admin(&state, &headers)?;
+
+    let changed = state.safe_mode.engage(request.reason.as_deref());
+    Ok(Json(SafeModeResponse { status: state.safe_mode.status(), changed }))
+}
+
+/// POST /api/v1/safe_mode/resume
+///
+/// Lift the kill switch (admin scope). Apart from the console this is the
+/// only way to resume: the Gateway refuses `/resume` from other sources.
+pub async fn handle_resume_safe_mode(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<SafeModeResponse>, ApiError> {
+    require_admin(&state, &headers)?;
+
+    let changed = state.safe_mode.release();
+    Ok(Json(SafeModeResponse { status: state.safe_mode.status(), changed }))
+}
+
+// ============================================================================
+// Terminal Handlers
+// ============================================================================
+
+fn terminal(state: &ApiState) -> Result<&crate::terminal::Interpreter, ApiError> {
+    state
+        .terminal
+        .as_deref()
+        .ok_or_else(|| ApiError::InternalError("Terminal is not enabled".to_string()))
+}
+
+fn command_error(e: crate::terminal::CommandError) -> ApiError {
+    match e {
+        crate::terminal::CommandError::Unavailable(_) => ApiError::InternalError(e.to_string()),
+        _ => ApiError::BadRequest(e.to_string()),
+    }
+}
+
+/// POST /api/v1/terminal/execute
+///
+/// Run one command line, e.g. `{"line": "graph neighbors cat"}`
+pub async fn handle_terminal_execute(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Json(request): Json<TerminalRequest>,
+) -> Result<Json<crate::terminal::CommandOutput>, ApiError> {
+    require_admin(&state, &headers)?;
+
+    let output = terminal(&state)?
+        .execute(&request.line)
+        .await
+        .map_err(command_error)?;
+
+    Ok(Json(output))
+}
+
+/// GET /api/v1/terminal/complete?line=graph%20nei
+///
+/// Completion candidates for the last token of a partial line
+pub async fn handle_terminal_complete(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Query(query): Query<CompleteQuery>,
+) -> Result<Json<CompletionResponse>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    Ok(Json(CompletionResponse {
+        completions: terminal(&state)?.complete(&query.line),
+    }))
+}
+
+/// GET /api/v1/terminal/history
+///
+/// Previously executed lines, oldest first
+pub async fn handle_terminal_history(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<TerminalHistoryResponse>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    Ok(Json(TerminalHistoryResponse {
+        entries: terminal(&state)?.history(),
+    }))
+}
+
+// ============================================================================
+// Scheduler Handlers
+// ============================================================================
+
+fn scheduler(state: &ApiState) -> Result<Arc<crate::scheduler::Scheduler>, ApiError> {
+    state
+        .gateway
+        .scheduler()
+        .ok_or_else(|| ApiError::InternalError("Scheduling is not enabled".to_string()))
+}
+
+/// Run a schedule command through the Gateway (allowlist and audit apply)
+async fn schedule_command(
+    state: &ApiState,
+    command: SystemCommand,
+) -> Result<crate::action_executor::ActionResult, ApiError> {
+    let (_, receiver) = state
+        .gateway
+        .inject(InputSignal::Command { command, args: Vec::new() })
+        .await
+        .map_err(|e| match e {
+            crate::gateway::GatewayError::InvalidCommand(_) => ApiError::BadRequest(e.to_string()),
+            e => gateway_error(e),
+        })?;
+    receiver
+        .await
+        .map_err(|_| ApiError::InternalError("Response channel closed".to_string()))
+}
+
+/// GET /api/v1/schedules
+///
+/// Scheduled tasks with their next run
+pub async fn handle_list_schedules(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<ScheduleListResponse>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    Ok(Json(ScheduleListResponse {
+        schedules: scheduler(&state)?.list(),
+    }))
+}
+
+/// POST /api/v1/schedules
+///
+/// Register a recurring task, e.g. `{"schedule": "every 1h", "input": "/status"}`
+pub async fn handle_create_schedule(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Json(request): Json<ScheduleRequest>,
+) -> Result<Json<crate::scheduler::ScheduledTask>, ApiError> {
+    require_admin(&state, &headers)?;
+
+    scheduler(&state)?;
+    let recurrence = crate::scheduler::Recurrence::parse(&request.schedule)
+        .map_err(|e| ApiError::BadRequest(e.to_string()))?;
+    let command = SystemCommand::Schedule { recurrence, input: request.input };
+    let result = schedule_command(&state, command).await?;
+    if !result.success {
+        return Err(ApiError::BadRequest(result.error.unwrap_or_default()));
+    }
+    serde_json::from_value(result.output)
+        .map(Json)
+        .map_err(|e| ApiError::InternalError(e.to_string()))
+}
+
+/// DELETE /api/v1/schedules/:id
+pub async fn handle_delete_schedule(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Path(id): Path<u64>,
+) -> Result<StatusCode, ApiError> {
+    require_admin(&state, &headers)?;
+
+    if scheduler(&state)?.get(id).is_none() {
+        return Err(ApiError::NotFound(format!("Scheduled task {} not found", id)));
+    }
+    let result = schedule_command(&state, SystemCommand::Unschedule { id }).await?;
+    if !result.success {
+        return Err(ApiError::InternalError(result.error.unwrap_or_default()));
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Auth Handlers
+// ============================================================================
+
+fn auth_manager(state: &ApiState) -> Result<&crate::auth::AuthManager, ApiError> {
+    state
+        .auth
+        .as_deref()
+        .ok_or_else(|| ApiError::InternalError("Authentication is not enabled".to_string()))
+}
+
+fn auth_error(e: crate::auth::AuthError) -> ApiError {
+    use crate::auth::AuthError;
+    match e {
+        AuthError::InvalidCredentials | AuthError::InvalidToken => ApiError::Unauthorized,
+        AuthError::Locked { .. } => ApiError::TooManyRequests(e.to_string()),
+        AuthError::InvalidUsername(_) | AuthError::WeakSecret(_) | AuthError::UserExists(_) => {
+            ApiError::BadRequest(e.to_string())
+        }
+        AuthError::UserNotFound(_) => ApiError::NotFound(e.to_string()),
+        _ => ApiError::InternalError(e.to_string()),
+    }
+}
+
+/// POST /api/v1/auth/login
+///
+/// Exchange username + PIN/password for a bearer token
+pub async fn handle_login(
+    State(state): State<ApiState>,
+    Json(request): Json<LoginRequest>,
+) -> Result<Json<LoginResponse>, ApiError> {
+    let auth = auth_manager(&state)?;
+    let session = auth
+        .login(&request.username, &request.secret)
+        .await
+        .map_err(auth_error)?;
+    Ok(Json(LoginResponse {
+        token: session.token,
+        claims: session.claims,
+    }))
+}
+
+/// POST /api/v1/auth/logout
+pub async fn handle_logout(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<StatusCode, ApiError> {
+    let auth = auth_manager(&state)?;
+    let token = extract_api_key(&headers).ok_or(ApiError::Unauthorized)?;
+    if auth.logout(&token) {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(ApiError::Unauthorized)
+    }
+}
+
+/// GET /api/v1/auth/me
+///
+/// Claims of the calling session
+pub async fn handle_whoami(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<crate::auth::AuthClaims>, ApiError> {
+    let auth = auth_manager(&state)?;
+    let token = extract_api_key(&headers).ok_or(ApiError::Unauthorized)?;
+    let claims = auth.verify_token(&token).map_err(auth_error)?;
+    Ok(Json(claims))
+}
+
+/// GET /api/v1/auth/users (admin scope)
+pub async fn handle_list_users(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<UserListResponse>, ApiError> {
+    require_admin(&state, &headers)?;
+
+    let auth = auth_manager(&state)?;
+    Ok(Json(UserListResponse { users: auth.users() }))
+}
+
+/// POST /api/v1/auth/users (admin scope)
+pub async fn handle_create_user(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Json(request): Json<CreateUserRequest>,
+) -> Result<(StatusCode, Json<crate::auth::UserInfo>), ApiError> {
+    require_admin(&state, &headers)?;
+
+    let auth = auth_manager(&state)?;
+    let user = auth
+        .create_user(&request.username, &request.secret, request.kind, request.role)
+        .await
+        .map_err(auth_error)?;
+    Ok((StatusCode::CREATED, Json(user)))
+}
+
+/// DELETE /api/v1/auth/users/:username (admin scope)
+pub async fn handle_delete_user(
+    State(state): State<ApiState>,
+    Path(username): Path<String>,
+    headers: HeaderMap,
+) -> Result<StatusCode, ApiError> {
+    require_admin(&state, &headers)?;
+
+    let auth = auth_manager(&state)?;
+    auth.remove_user(&username).await.map_err(auth_error)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// ============================================================================
+// Instance Handlers
+// ============================================================================
+
+fn instance_error(e: InstanceError) -> ApiError {
+    match e {
+        InstanceError::NotFound(_) => ApiError::NotFound(e.to_string()),
+        InstanceError::InvalidId(_)
+        | InstanceError::AlreadyExists(_)
+        | InstanceError::DefaultInstance
+        | InstanceError::LimitReached(_)
+        | InstanceError::NoFactory => ApiError::BadRequest(e.to_string()),
+        _ => ApiError::InternalError(e.to_string()),
+    }
+}
+
+/// Instance management is authorized against the default instance's keys
+fn require_registry_key(registry: &InstanceRegistry, headers: &HeaderMap) -> Result<(), ApiError> {
+    let api_key = extract_api_key(headers);
+    if registry.default_instance().state().validate_api_key(api_key.as_deref()) {
+        Ok(())
+    } else {
+        Err(ApiError::Unauthorized)
+    }
+}
+
+/// GET /api/v1/instances
+pub async fn handle_list_instances(
+    State(registry): State<Arc<InstanceRegistry>>,
+    headers: HeaderMap,
+) -> Result<Json<InstanceListResponse>, ApiError> {
+    require_registry_key(&registry, &headers)?;
+
+    let instances = registry
+        .list()
+        .iter()
+        .map(|instance| InstanceInfo::from_instance(instance))
+        .collect();
+    Ok(Json(InstanceListResponse { instances }))
+}
+
+/// POST /api/v1/instances
+///
+/// Create an instance with the registry's factory (admin scope)
+pub async fn handle_create_instance(
+    State(registry): State<Arc<InstanceRegistry>>,
+    headers: HeaderMap,
+    Json(request): Json<CreateInstanceRequest>,
+) -> Result<(StatusCode, Json<InstanceInfo>), ApiError> {
+    require_admin(registry.default_instance().state(), &headers)?;
+
+    let instance = registry.create(request.id).map_err(instance_error)?;
+    Ok((StatusCode::CREATED, Json(InstanceInfo::from_instance(&instance))))
+}
+
+/// GET /api/v1/instances/:id
+pub async fn handle_get_instance(
+    State(registry): State<Arc<InstanceRegistry>>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Json<InstanceInfo>, ApiError> {
+    require_registry_key(&registry, &headers)?;
+
+    let instance = registry.resolve(Some(&id)).map_err(instance_error)?;
+    Ok(Json(InstanceInfo::from_instance(&instance)))
+}
+
+/// DELETE /api/v1/instances/:id
+///
+/// Unregister an instance (admin scope); the default instance stays
+pub async fn handle_delete_instance(
+    State(registry): State<Arc<InstanceRegistry>>,
+    Path(id): Path<String>,
+    headers: HeaderMap,
+) -> Result<StatusCode, ApiError> {
+    require_admin(registry.default_instance().state(), &headers)?;
+
+    let id = InstanceId::new(id).map_err(instance_error)?;
+    registry.remove(&id).map_err(instance_error)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// ANY /api/v1/instances/:id/*path
+///
+/// Serve the request with the instance's own `/api/v1` routes, so
+/// `/api/v1/instances/notes/query` behaves like `/api/v1/query` on "notes"
+pub async fn handle_instance_request(
+    State(registry): State<Arc<InstanceRegistry>>,
+    Path((id, path)): Path<(String, String)>,
+    mut request: Request,
+) -> Result<Response, ApiError> {
+    let instance = registry.resolve(Some(&id)).map_err(instance_error)?;
+
+    let path_and_query = match request.uri().query() {
+        Some(query) => format!("/api/v1/{}?{}", path.trim_start_matches('/'), query),
+        None => format!("/api/v1/{}", path.trim_start_matches('/')),
+    };
+    *request.uri_mut() = path_and_query
+        .parse()
+        .map_err(|e| ApiError::BadRequest(format!("Invalid path: {}", e)))?;
+
+    let response = instance.routes().call(request).await.unwrap_or_else(|e| match e {});
+    Ok(response)
+}
+
+// ============================================================================
+// Metrics Handler (v0.42.0)
+// ============================================================================
+
+/// Prometheus metrics endpoint
+///
+/// Returns metrics in Prometheus exposition format for scraping.
+/// No authentication required for metrics endpoint (standard practice).
+///
+/// # Example
+///
+/// ```bash
+/// curl http://localhost:8080/metrics
+/// ```
+pub async fn handle_metrics() -> Result<impl IntoResponse, ApiError> {
+    match crate::metrics::export_metrics() {
+        Ok(metrics_text) => {
+            // Return with proper content type for Prometheus
+            Ok((
+                StatusCode::OK,
+                [(
+                    axum::http::header::CONTENT_TYPE,
+                    "text/plain; version=0.0.4; charset=utf-8",
+                )],
+                metrics_text,
+            ))
+        }
+        Err(e) => Err(ApiError::InternalError(format!(
+            "Failed to export metrics: {}",
+            e
+        ))),
+    }
+}
+
+/// GET /api/v1/metrics/history
+///
+/// Series tracked by the metrics history with their latest sample
+pub async fn handle_metrics_history(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+) -> Result<Json<MetricsHistoryResponse>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    let history = &crate::metrics_history::METRICS_HISTORY;
+    Ok(Json(MetricsHistoryResponse {
+        interval_ms: history.config().interval_ms,
+        series: history.summaries(),
+    }))
+}
+
+/// GET /api/v1/metrics/series?name=...&points=60&rate=true
+///
+/// Downsampled window of one series, for sparklines
+pub async fn handle_metric_series(
+    State(state): State<ApiState>,
+    headers: HeaderMap,
+    Query(query): Query<MetricSeriesQuery>,
+) -> Result<Json<crate::metrics_history::Series>, ApiError> {
+    // Validate API key
+    let api_key = extract_api_key(&headers);
+    if !state.validate_api_key(api_key.as_deref()) {
+        return Err(ApiError::Unauthorized);
+    }
+
+    crate::metrics_history::METRICS_HISTORY
+        .series(&query.name, &query.series_query())
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("Metric series not found: {}", query.name)))
 }
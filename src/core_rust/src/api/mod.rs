@@ -7,15 +7,26 @@ pub mod state;
 pub mod handlers;
 pub mod router;
 pub mod websocket;
+pub mod compat;
 
 // Re-export key types
 pub use models::{
     QueryRequest, QueryResponse, QueryMetadata,
+    QueryProvenance, MatchedToken, TraversedEdge, StageTimings,
     FeedbackRequest, FeedbackResponse, FeedbackType,
     StatusResponse, StatsResponse,
     HealthResponse, ErrorResponse,
+    InstanceInfo, InstanceListResponse, CreateInstanceRequest,
+    LoginRequest, LoginResponse, CreateUserRequest, UserListResponse,
+    ConfigListResponse,
+    LogsQuery, LogsResponse,
+    MetricsHistoryResponse, MetricSeriesQuery, ModulesResponse, I18nQuery, I18nResponse,
+    IngestRequest, IngestResponse,
+    TerminalRequest, CompleteQuery, CompletionResponse, TerminalHistoryResponse,
+    CreateConversationRequest, RenameConversationRequest, AppendMessageRequest,
+    ConversationListResponse, ChatSearchQuery, ChatSearchResponse,
 };
 
 pub use state::{ApiState, ApiConfig};
-pub use router::create_router;
+pub use router::{create_router, create_instances_router};
 pub use websocket::handle_websocket;
@@ -42,6 +42,10 @@ pub struct QueryResponse {
 
     /// Processing metadata
     pub metadata: QueryMetadata,
+
+    /// How the answer was produced
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<Box<QueryProvenance>>,
 }
 
 /// Metadata about query processing
@@ -65,6 +69,91 @@ pub struct QueryMetadata {
     pub confidence: Option<f32>,
 }
 
+/// Explanation of how a query was answered
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct QueryProvenance {
+    /// Query words matched to tokens
+    #[serde(default)]
+    pub matched_tokens: Vec<MatchedToken>,
+
+    /// Words with no matching token
+    #[serde(default)]
+    pub unknown_words: Vec<String>,
+
+    /// Graph edges along the answer path
+    #[serde(default)]
+    pub traversed_edges: Vec<TraversedEdge>,
+
+    /// Pathway that produced the answer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decision_source: Option<crate::action_types::DecisionSource>,
+
+    /// Per-stage timings
+    #[serde(default)]
+    pub timings: StageTimings,
+}
+
+/// A query word matched to a token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchedToken {
+    /// Word from the query
+    pub word: String,
+
+    /// Matched token ID
+    pub token_id: u32,
+
+    /// Match confidence (0.0-1.0)
+    pub confidence: f32,
+}
+
+/// A graph edge traversed while answering
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraversedEdge {
+    /// Source node ID
+    pub from_id: u32,
+
+    /// Target node ID
+    pub to_id: u32,
+
+    /// Source concept word
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_word: Option<String>,
+
+    /// Target concept word
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_word: Option<String>,
+
+    /// Edge type name
+    pub edge_type: String,
+
+    /// Edge weight (connection strength)
+    pub weight: f32,
+}
+
+/// Processing time per stage, in microseconds
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StageTimings {
+    /// Gateway inject call, including enqueue
+    #[serde(default)]
+    pub gateway_us: u64,
+
+    /// Classification and normalization inside the Gateway
+    #[serde(default)]
+    pub normalization_us: u64,
+
+    /// Waiting in the queue for the ActionController (millisecond resolution)
+    #[serde(default)]
+    pub queue_us: u64,
+
+    /// Executor run time
+    #[serde(default)]
+    pub execution_us: u64,
+
+    /// End-to-end time
+    #[serde(default)]
+    pub total_us: u64,
+}
+
 // ============================================================================
 // Feedback API Models
 // ============================================================================
@@ -195,6 +284,9 @@ pub struct StatsResponse {
     /// Curiosity statistics (if available)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub curiosity: Option<CuriosityStats>,
+
+    /// Per-stage pipeline timings
+    pub profiling: crate::profiling::ProfilingSnapshot,
 }
 
 /// Gateway statistics
@@ -254,6 +346,624 @@ pub struct ExplorationStats {
     pub total_explored: usize,
 }
 
+// ============================================================================
+// Decision Trace Models
+// ============================================================================
+
+/// Query parameters for GET /api/v1/decisions
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DecisionsQuery {
+    /// Maximum number of traces to return (newest first)
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// Recent Arbiter decision traces
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecisionsResponse {
+    /// Traces, newest first
+    pub decisions: Vec<crate::action_types::DecisionTrace>,
+
+    /// Total traces recorded since start (including evicted)
+    pub total_recorded: u64,
+}
+
+// ============================================================================
+// Log Models
+// ============================================================================
+
+/// Query parameters for GET /api/v1/logs and /api/v1/logs/stream
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LogsQuery {
+    /// Minimum level: trace, debug, info, warn, error
+    #[serde(default)]
+    pub level: Option<crate::log_stream::LogLevel>,
+
+    /// Target prefix, e.g. `_core::gateway`
+    #[serde(default)]
+    pub target: Option<String>,
+
+    /// Case-insensitive text to search for
+    #[serde(default)]
+    pub contains: Option<String>,
+
+    /// Maximum entries returned (for /logs/stream: backlog sent first)
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+impl LogsQuery {
+    pub fn filter(&self) -> crate::log_stream::LogFilter {
+        crate::log_stream::LogFilter {
+            min_level: self.level,
+            target: self.target.clone(),
+            contains: self.contains.clone(),
+        }
+    }
+}
+
+/// Response for GET /api/v1/logs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogsResponse {
+    /// Matching entries, oldest first
+    pub entries: Vec<crate::log_stream::LogEntry>,
+}
+
+// ============================================================================
+// Metrics History Models
+// ============================================================================
+
+/// Response for GET /api/v1/metrics/history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsHistoryResponse {
+    /// Sampling interval of the history
+    pub interval_ms: u64,
+
+    /// Tracked series, sorted by name
+    pub series: Vec<crate::metrics_history::SeriesSummary>,
+}
+
+/// Query parameters for GET /api/v1/metrics/series
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricSeriesQuery {
+    /// Series name, including labels in exposition format
+    pub name: String,
+
+    /// Only samples at or after this Unix time (ms)
+    #[serde(default)]
+    pub since: Option<u64>,
+
+    /// Downsample to at most this many points
+    #[serde(default)]
+    pub points: Option<usize>,
+
+    /// Per-second rate instead of raw values
+    #[serde(default)]
+    pub rate: bool,
+}
+
+impl MetricSeriesQuery {
+    pub fn series_query(&self) -> crate::metrics_history::SeriesQuery {
+        crate::metrics_history::SeriesQuery {
+            since: self.since,
+            points: self.points,
+            rate: self.rate,
+        }
+    }
+}
+
+// ============================================================================
+// Ingestion Models
+// ============================================================================
+
+/// Request for POST /api/v1/ingest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestRequest {
+    /// File name; the extension selects the format (.txt, .md, .csv)
+    pub filename: String,
+
+    /// UTF-8 file content
+    pub content: String,
+}
+
+/// Response for POST /api/v1/ingest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IngestResponse {
+    pub document: String,
+    pub format: crate::ingestion::DocumentFormat,
+    pub report: crate::ingestion::IngestionReport,
+}
+
+// ============================================================================
+// Sync Models
+// ============================================================================
+
+/// Query parameters for GET /api/v1/sync/delta
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncDeltaQuery {
+    /// Unix timestamp to export changes from (default: everything)
+    #[serde(default)]
+    pub since: u32,
+}
+
+// ============================================================================
+// Replication Models
+// ============================================================================
+
+/// Query parameters for GET /api/v1/replication/changes
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReplicationChangesQuery {
+    /// Last sequence number the replica applied
+    #[serde(default)]
+    pub after: u64,
+    /// Feed epoch the replica follows (omit to request a snapshot)
+    #[serde(default)]
+    pub epoch: Option<String>,
+    /// Maximum changes to return (capped by the feed's `max_batch`)
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// Response for GET /api/v1/replication/status
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplicationStatusResponse {
+    /// "writer", "replica" or "standalone"
+    pub role: String,
+    /// Change feed epoch (writers)
+    pub epoch: Option<String>,
+    /// Latest change sequence number (writers)
+    pub head: Option<u64>,
+    /// Position relative to the writer (replicas)
+    pub replica: Option<crate::replica::ReplicaStatus>,
+}
+
+// ============================================================================
+// I18n Models
+// ============================================================================
+
+/// Query parameters for GET /api/v1/i18n
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct I18nQuery {
+    /// Catalog to return (default: configured locale)
+    #[serde(default)]
+    pub locale: Option<String>,
+}
+
+/// Response for GET /api/v1/i18n
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct I18nResponse {
+    pub locale: crate::i18n::Locale,
+    pub available: Vec<crate::i18n::Locale>,
+    /// Message key -> text
+    pub messages: std::collections::BTreeMap<String, String>,
+}
+
+// ============================================================================
+// Module Lifecycle Models
+// ============================================================================
+
+/// Response for GET /api/v1/modules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModulesResponse {
+    /// Core modules (enable flags, metrics)
+    pub modules: Vec<crate::module_registry::ModuleInfo>,
+
+    /// Subsystems with a start/stop lifecycle, by name
+    pub managed: Vec<crate::module_registry::ManagedModuleInfo>,
+}
+
+// ============================================================================
+// Graph Visualization Models
+// ============================================================================
+
+/// Query parameters for GET /api/v1/graph/subgraph.dot
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SubgraphDotQuery {
+    /// Center concept (word)
+    #[serde(default)]
+    pub word: Option<String>,
+
+    /// Center node ID (used if `word` is not given)
+    #[serde(default)]
+    pub node_id: Option<u32>,
+
+    /// Neighborhood radius in hops (default 1)
+    #[serde(default)]
+    pub radius: Option<usize>,
+
+    /// Show edge type names
+    #[serde(default)]
+    pub edge_labels: bool,
+}
+
+/// Query parameters for GET /api/v1/graph/subgraph
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SubgraphQuery {
+    /// Center concept (word)
+    #[serde(default)]
+    pub word: Option<String>,
+
+    /// Center node ID (used if `word` is not given)
+    #[serde(default)]
+    pub node_id: Option<u32>,
+
+    /// Neighborhood depth in hops (default 1)
+    #[serde(default)]
+    pub depth: Option<usize>,
+
+    /// "force" (default) or "projected"
+    #[serde(default)]
+    pub layout: Option<crate::graph::export::ViewLayout>,
+}
+
+/// Query parameters for GET /api/v1/graph/neighbors
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NeighborsQuery {
+    /// Concept (word)
+    pub word: String,
+
+    /// Maximum number of neighbors (default 20)
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// A neighboring concept
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Neighbor {
+    /// Node ID
+    pub node_id: u32,
+
+    /// Concept word (None for nodes without a concept)
+    pub word: Option<String>,
+
+    /// Edge weight
+    pub weight: f32,
+
+    /// Edge type name
+    pub edge_type: String,
+}
+
+/// Response for GET /api/v1/graph/neighbors
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NeighborsResponse {
+    /// Queried concept
+    pub word: String,
+
+    /// Node ID of the queried concept
+    pub node_id: u32,
+
+    /// Neighbors, strongest edge first
+    pub neighbors: Vec<Neighbor>,
+}
+
+/// Request for POST /api/v1/query/ngql
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NgqlRequest {
+    /// NGQL query, e.g. `NEIGHBORS 'cat' TYPE causal DEPTH 2`
+    pub query: String,
+}
+
+/// Anchor values, given directly or as a word to look up ("blue", "calm")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GroundedValue {
+    Values(Vec<f32>),
+    Word(String),
+}
+
+/// Request for POST /api/v1/query/grounded
+///
+/// `{"color": "blue", "emotion": "calm"}` finds concepts that feel like calm blue
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GroundedQueryRequest {
+    /// RGB (0.0-1.0) or a color word
+    #[serde(default)]
+    pub color: Option<GroundedValue>,
+
+    /// Valence, arousal, dominance (-1.0-1.0) or an emotion word
+    #[serde(default)]
+    pub emotion: Option<GroundedValue>,
+
+    /// Largest Euclidean distance per term (default 0.5)
+    #[serde(default)]
+    pub tolerance: Option<f32>,
+
+    /// Maximum number of concepts (default 20)
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// A concept matching a grounded query
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroundedConcept {
+    /// Concept word
+    pub word: String,
+
+    /// Node ID
+    pub node_id: u32,
+
+    /// Mean closeness over all terms (0.0-1.0)
+    pub score: f32,
+
+    /// Number of terms matched
+    pub matched: usize,
+}
+
+/// Response for POST /api/v1/query/grounded
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroundedQueryResponse {
+    /// Matching concepts, concepts matching every term first
+    pub concepts: Vec<GroundedConcept>,
+}
+
+// ============================================================================
+// Checkpoint Models
+// ============================================================================
+
+/// Request body for POST /api/v1/checkpoint
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CheckpointRequest {
+    /// Optional label stored in the manifest
+    #[serde(default)]
+    pub label: Option<String>,
+}
+
+/// Request body for POST /api/v1/checkpoint/restore
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CheckpointRestoreRequest {
+    /// Checkpoint ID; the newest checkpoint if omitted
+    #[serde(default)]
+    pub id: Option<String>,
+}
+
+/// Response for GET /api/v1/checkpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointListResponse {
+    /// Checkpoints, oldest first
+    pub checkpoints: Vec<crate::checkpoint::CheckpointManifest>,
+}
+
+// ============================================================================
+// CDNA Models
+// ============================================================================
+
+/// Response for GET/POST /api/v1/cdna
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CdnaResponse {
+    /// Active profile name
+    pub profile: String,
+    /// Revision counter (incremented by every patch)
+    pub revision: u32,
+    /// Last modification time (Unix seconds)
+    pub modified_at: u64,
+    /// FNV-1a checksum
+    pub checksum: u64,
+    /// Tunable parameters
+    pub parameters: crate::cdna::ProfilePatch,
+}
+
+impl CdnaResponse {
+    pub fn from_cdna(cdna: &crate::cdna::CDNA) -> Self {
+        Self {
+            profile: format!("{:?}", cdna.profile()),
+            revision: cdna.revision,
+            modified_at: cdna.modified_at,
+            checksum: cdna.checksum,
+            parameters: crate::cdna::ProfilePatch::from_cdna(cdna),
+        }
+    }
+}
+
+// ============================================================================
+// Settings Models
+// ============================================================================
+
+/// Response for GET /api/v1/config
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigListResponse {
+    /// Effective config per section
+    pub sections: std::collections::BTreeMap<crate::settings::ConfigSection, serde_json::Value>,
+}
+
+// ============================================================================
+// Chat History Models
+// ============================================================================
+
+/// `QueryRequest::context` key naming the conversation to record into
+pub const CONVERSATION_CONTEXT_KEY: &str = "conversation_id";
+
+/// Request for POST /api/v1/chat/conversations
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateConversationRequest {
+    /// Title; defaults to the first user message
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+/// Request for POST /api/v1/chat/conversations/:id
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenameConversationRequest {
+    pub title: String,
+}
+
+/// Request for POST /api/v1/chat/conversations/:id/messages
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppendMessageRequest {
+    pub role: crate::chat_history::ChatRole,
+    pub content: String,
+    #[serde(default)]
+    pub signal_id: Option<u64>,
+}
+
+/// Response for GET /api/v1/chat/conversations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversationListResponse {
+    /// Most recently active first
+    pub conversations: Vec<crate::chat_history::ConversationSummary>,
+}
+
+/// Query parameters for GET /api/v1/chat/search
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChatSearchQuery {
+    /// Words that must all appear
+    pub q: String,
+
+    /// Maximum hits (default 50)
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// Response for GET /api/v1/chat/search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatSearchResponse {
+    /// Newest first
+    pub hits: Vec<crate::chat_history::SearchHit>,
+}
+
+// ============================================================================
+// Terminal Models
+// ============================================================================
+
+/// Request for POST /api/v1/terminal/execute
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerminalRequest {
+    /// Command line, e.g. `graph neighbors cat --limit 5`
+    pub line: String,
+}
+
+/// Query parameters for GET /api/v1/terminal/complete
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompleteQuery {
+    /// Partial command line
+    #[serde(default)]
+    pub line: String,
+}
+
+/// Response for GET /api/v1/terminal/complete
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompletionResponse {
+    pub completions: Vec<crate::terminal::Completion>,
+}
+
+/// Response for GET /api/v1/terminal/history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerminalHistoryResponse {
+    /// Entered lines, oldest first
+    pub entries: Vec<String>,
+}
+
+// ============================================================================
+// Scheduler Models
+// ============================================================================
+
+/// Request for POST /api/v1/schedules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleRequest {
+    /// `every <n>(s|m|h|d)` or `at <hh:mm>`
+    pub schedule: String,
+    /// Text injected when due, e.g. `/status` or a question
+    pub input: String,
+}
+
+/// Response for GET /api/v1/schedules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleListResponse {
+    /// Ordered by ID
+    pub schedules: Vec<crate::scheduler::ScheduledTask>,
+}
+
+// ============================================================================
+// Safe Mode Models
+// ============================================================================
+
+/// Request for POST /api/v1/safe_mode
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SafeModeRequest {
+    /// Shown in the status until safe mode is lifted
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// Response for the /api/v1/safe_mode endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SafeModeResponse {
+    #[serde(flatten)]
+    pub status: crate::safe_mode::SafeModeStatus,
+    /// Whether this request engaged or lifted it (false for GET)
+    pub changed: bool,
+}
+
+// ============================================================================
+// Auth Models
+// ============================================================================
+
+/// Request for POST /api/v1/auth/login
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoginRequest {
+    pub username: String,
+    /// PIN or password
+    pub secret: String,
+}
+
+/// Response for POST /api/v1/auth/login
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoginResponse {
+    /// Bearer token for `Authorization: Bearer <token>`
+    pub token: String,
+    pub claims: crate::auth::AuthClaims,
+}
+
+/// Request for POST /api/v1/auth/users
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateUserRequest {
+    pub username: String,
+    pub secret: String,
+    pub kind: crate::auth::SecretKind,
+    pub role: crate::auth::Role,
+}
+
+/// Response for GET /api/v1/auth/users
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserListResponse {
+    pub users: Vec<crate::auth::UserInfo>,
+}
+
+// ============================================================================
+// Instance Models
+// ============================================================================
+
+/// One hosted instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstanceInfo {
+    pub id: crate::instance::InstanceId,
+    /// Creation time (Unix milliseconds)
+    pub created_at: u64,
+    /// Path prefix serving this instance
+    pub base_path: String,
+}
+
+impl InstanceInfo {
+    pub fn from_instance(instance: &crate::instance::Instance) -> Self {
+        Self {
+            id: instance.id().clone(),
+            created_at: instance.created_at(),
+            base_path: format!("/api/v1/instances/{}", instance.id()),
+        }
+    }
+}
+
+/// Response for GET /api/v1/instances
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstanceListResponse {
+    pub instances: Vec<InstanceInfo>,
+}
+
+/// Request for POST /api/v1/instances
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateInstanceRequest {
+    pub id: crate::instance::InstanceId,
+}
+
 // ============================================================================
 // Health Check Models
 // ============================================================================
@@ -334,6 +1044,24 @@ mod tests {
         assert!(json.contains("\"type\":\"correction\""));
     }
 
+    #[test]
+    fn test_query_provenance_from_controller_output() {
+        let json = serde_json::json!({
+            "matched_tokens": [{"word": "cat", "token_id": 7, "confidence": 1.0}],
+            "unknown_words": ["blorp"],
+            "decision_source": {"Reasoning": {"policy_version": 1, "reasoning_time_ms": 2}},
+            "timings": {"normalization_us": 40, "queue_us": 1000, "execution_us": 2100},
+        });
+
+        let provenance: QueryProvenance = serde_json::from_value(json).unwrap();
+        assert_eq!(provenance.matched_tokens[0].token_id, 7);
+        assert_eq!(provenance.unknown_words, vec!["blorp".to_string()]);
+        assert!(provenance.traversed_edges.is_empty());
+        assert_eq!(provenance.decision_source.unwrap().name(), "reasoning");
+        assert_eq!(provenance.timings.execution_us, 2100);
+        assert_eq!(provenance.timings.gateway_us, 0);
+    }
+
     #[test]
     fn test_error_response() {
         let err = ErrorResponse::new("bad_request", "Invalid query")
@@ -2,11 +2,17 @@
 //
 // HTTP routes and middleware configuration with distributed tracing
 
-use super::{handlers, state::ApiState};
+use super::{compat, handlers, state::ApiState};
+use crate::instance::InstanceRegistry;
 use axum::{
-    routing::{get, post},
+    extract::Request,
+    http::Method,
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{any, delete, get, post},
     Router,
 };
+use std::sync::Arc;
 use tower_http::{
     cors::{Any, CorsLayer},
     trace::TraceLayer,
@@ -14,28 +20,187 @@ use tower_http::{
 
 /// Create API router
 pub fn create_router(state: ApiState) -> Router {
-    // API v1 routes
+    let config = state.config.clone();
+    with_middleware(base_routes(state), &config)
+}
+
+/// Create API router for a multi-instance daemon
+///
+/// Unprefixed routes serve the default instance; `/api/v1/instances/{id}/...`
+/// is dispatched to instance `{id}` (see `crate::instance`).
+pub fn create_instances_router(registry: Arc<InstanceRegistry>) -> Router {
+    let default = registry.default_instance();
+    let config = default.state().config.clone();
+
+    let instances = Router::new()
+        .route(
+            "/api/v1/instances",
+            get(handlers::handle_list_instances).post(handlers::handle_create_instance),
+        )
+        .route(
+            "/api/v1/instances/:id",
+            get(handlers::handle_get_instance).delete(handlers::handle_delete_instance),
+        )
+        .route("/api/v1/instances/:id/*path", any(handlers::handle_instance_request))
+        .with_state(registry);
+
+    with_middleware(base_routes(default.state().clone()).merge(instances), &config)
+}
+
+/// `/api/v1` routes of one instance, without middleware
+pub(crate) fn api_routes(state: ApiState) -> Router {
     let api_v1 = Router::new()
         // Query endpoint
         .route("/query", post(handlers::handle_query))
+        // Graph query language
+        .route("/query/ngql", post(handlers::handle_ngql))
+        .route("/query/grounded", post(handlers::handle_grounded_query))
         // Feedback endpoint
         .route("/feedback", post(handlers::handle_feedback))
         // Status endpoint
         .route("/status", get(handlers::handle_status))
         // Statistics endpoint
         .route("/stats", get(handlers::handle_stats))
+        // Per-stage pipeline profiling
+        .route(
+            "/profiling",
+            get(handlers::handle_profiling)
+                .post(handlers::handle_configure_profiling)
+                .delete(handlers::handle_reset_profiling),
+        )
+        // Arbiter decision traces
+        .route("/decisions", get(handlers::handle_decisions))
+        // Core log entries and live stream (admin scope)
+        .route("/logs", get(handlers::handle_logs))
+        .route("/logs/stream", get(handlers::handle_log_stream))
+        // Metrics time series for dashboard sparklines
+        .route("/metrics/history", get(handlers::handle_metrics_history))
+        .route("/metrics/series", get(handlers::handle_metric_series))
+        // Document ingestion (file drop) with live progress
+        .route("/ingest", post(handlers::handle_ingest))
+        .route("/ingest/events", get(handlers::handle_ingest_events))
+        // Knowledge sync between instances (applying: admin scope)
+        .route(
+            "/sync/delta",
+            get(handlers::handle_sync_export).post(handlers::handle_sync_apply),
+        )
+        // Read replicas: writer change feed and replication role/lag
+        .route("/replication/changes", get(handlers::handle_replication_changes))
+        .route("/replication/status", get(handlers::handle_replication_status))
+        // UI message catalogs
+        .route("/i18n", get(handlers::handle_i18n))
+        // Module registry and subsystem lifecycle (admin scope)
+        .route("/modules", get(handlers::handle_modules))
+        .route("/modules/:name/:action", post(handlers::handle_module_action))
+        // Graphviz neighborhood visualization
+        .route("/graph/subgraph.dot", get(handlers::handle_subgraph_dot))
+        .route("/graph/subgraph", get(handlers::handle_subgraph))
+        .route("/graph/neighbors", get(handlers::handle_neighbors))
+        // ADNA appraiser configuration
+        .route("/adna", get(handlers::handle_get_adna).post(handlers::handle_update_adna))
+        // CDNA profile tuning (admin scope)
+        .route("/cdna", get(handlers::handle_get_cdna).post(handlers::handle_update_cdna))
+        // Runtime component configs (updates: admin scope)
+        .route("/config", get(handlers::handle_list_config))
+        .route(
+            "/config/:section",
+            get(handlers::handle_get_config)
+                .post(handlers::handle_update_config)
+                .delete(handlers::handle_reset_config),
+        )
+        // Whole-system checkpoints
+        .route("/checkpoint", post(handlers::handle_checkpoint))
+        .route("/checkpoint/restore", post(handlers::handle_restore_checkpoint))
+        .route("/checkpoints", get(handlers::handle_list_checkpoints))
+        // Persistent chat conversations
+        .route(
+            "/chat/conversations",
+            get(handlers::handle_list_conversations).post(handlers::handle_create_conversation),
+        )
+        .route(
+            "/chat/conversations/:id",
+            get(handlers::handle_get_conversation)
+                .post(handlers::handle_rename_conversation)
+                .delete(handlers::handle_delete_conversation),
+        )
+        .route("/chat/conversations/:id/messages", post(handlers::handle_append_message))
+        .route("/chat/last", get(handlers::handle_last_conversation))
+        .route("/chat/search", get(handlers::handle_search_chat))
+        // Recurring tasks
+        .route(
+            "/schedules",
+            get(handlers::handle_list_schedules).post(handlers::handle_create_schedule),
+        )
+        .route("/schedules/:id", delete(handlers::handle_delete_schedule))
+        // Background mode and tray actions
+        .route("/runtime", get(handlers::handle_runtime_status))
+        .route("/runtime/:action", post(handlers::handle_runtime_action))
+        // Operator kill switch
+        .route(
+            "/safe_mode",
+            get(handlers::handle_safe_mode_status).post(handlers::handle_engage_safe_mode),
+        )
+        .route("/safe_mode/resume", post(handlers::handle_resume_safe_mode))
+        // Command interpreter for terminals
+        .route("/terminal/execute", post(handlers::handle_terminal_execute))
+        .route("/terminal/complete", get(handlers::handle_terminal_complete))
+        .route("/terminal/history", get(handlers::handle_terminal_history))
+        // Users and sessions
+        .route("/auth/login", post(handlers::handle_login))
+        .route("/auth/logout", post(handlers::handle_logout))
+        .route("/auth/me", get(handlers::handle_whoami))
+        .route("/auth/users", get(handlers::handle_list_users).post(handlers::handle_create_user))
+        .route("/auth/users/:username", delete(handlers::handle_delete_user))
         // Health check
         .route("/health", get(handlers::handle_health));
 
-    // Root router
-    let app = Router::new()
-        .nest("/api/v1", api_v1)
+    let api_v1 = if state.is_replica() {
+        api_v1.layer(middleware::from_fn(reject_writes))
+    } else {
+        api_v1
+    };
+
+    Router::new().nest("/api/v1", api_v1).with_state(state)
+}
+
+/// POST endpoints a read replica still serves (they don't change the graph)
+const REPLICA_READ_POSTS: &[&str] = &[
+    "/query",
+    "/query/grounded",
+    "/auth/login",
+    "/auth/logout",
+    "/safe_mode",
+    "/safe_mode/resume",
+];
+
+/// Replica guard: only reads reach the handlers
+async fn reject_writes(request: Request, next: Next) -> Response {
+    let method = request.method();
+    let read = method == Method::GET
+        || method == Method::HEAD
+        || method == Method::OPTIONS
+        || (method == Method::POST && REPLICA_READ_POSTS.contains(&request.uri().path()));
+    if !read {
+        return handlers::ApiError::ReadOnlyReplica.into_response();
+    }
+    next.run(request).await
+}
+
+/// All routes of the default instance, without middleware
+fn base_routes(state: ApiState) -> Router {
+    Router::new()
         .route("/health", get(handlers::handle_health)) // Also at root
         .route("/metrics", get(handlers::handle_metrics)) // Prometheus metrics (v0.42.0)
-        .with_state(state.clone());
+        // OpenAI-compatible endpoints
+        .route("/v1/chat/completions", post(compat::handle_chat_completions))
+        .route("/v1/models", get(compat::handle_models))
+        .with_state(state.clone())
+        .merge(api_routes(state))
+}
 
+fn with_middleware(app: Router, config: &super::ApiConfig) -> Router {
     // Add CORS if enabled
-    let app = if state.config.enable_cors {
+    let app = if config.enable_cors {
         app.layer(
             CorsLayer::new()
                 .allow_origin(Any)
@@ -108,4 +273,245 @@ mod tests {
         let _router = create_router(state);
         // Just checking it compiles and creates successfully
     }
+
+    fn test_state() -> ApiState {
+        let (signal_tx, _signal_rx) = mpsc::channel(100);
+        let bootstrap = Arc::new(RwLock::new(BootstrapLibrary::new(Default::default())));
+        let gateway = Arc::new(Gateway::new(signal_tx, bootstrap.clone(), Default::default()));
+        let intuition = Arc::new(RwLock::new(IntuitionEngine::new(
+            Default::default(),
+            Arc::new(ExperienceStream::new(1000, 10)),
+            Arc::new(InMemoryADNAReader::new(Default::default())),
+            mpsc::channel(100).0,
+        )));
+        let feedback = Arc::new(FeedbackProcessor::new(
+            bootstrap,
+            Arc::new(RwLock::new(ExperienceStream::new(1000, 10))),
+            intuition,
+        ));
+        ApiState::new(gateway, feedback, Default::default())
+    }
+
+    async fn status(router: &Router, method: &str, uri: &str) -> axum::http::StatusCode {
+        use tower::Service;
+        let request = axum::http::Request::builder()
+            .method(method)
+            .uri(uri)
+            .body(axum::body::Body::empty())
+            .unwrap();
+        router.clone().call(request).await.unwrap().status()
+    }
+
+    async fn status_as(router: &Router, method: &str, uri: &str, token: &str, body: &str) -> axum::http::StatusCode {
+        use tower::Service;
+        let request = axum::http::Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("X-API-Key", token)
+            .header(axum::http::header::CONTENT_TYPE, "application/json")
+            .body(axum::body::Body::from(body.to_string()))
+            .unwrap();
+        router.clone().call(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_user_role_cannot_mutate() {
+        use crate::auth::{AuthConfig, AuthManager, Role, SecretKind};
+        use axum::http::StatusCode;
+
+        let auth = Arc::new(
+            AuthManager::new(AuthConfig { hash_memory_kib: 64, hash_iterations: 1, ..Default::default() })
+                .unwrap(),
+        );
+        auth.create_user("kiosk", "4821", SecretKind::Pin, Role::User).await.unwrap();
+        let user = auth.login("kiosk", "4821").await.unwrap().token;
+        let router = create_router(test_state().with_auth(auth));
+
+        let admin_only = [
+            ("POST", "/api/v1/ingest", r#"{"filename": "a.txt", "content": "x"}"#),
+            ("POST", "/api/v1/adna", "{}"),
+            ("POST", "/api/v1/checkpoint", "{}"),
+            ("POST", "/api/v1/checkpoint/restore", "{}"),
+            ("POST", "/api/v1/terminal/execute", r#"{"line": "checkpoint restore"}"#),
+            ("POST", "/api/v1/schedules", r#"{"schedule": "every 1h", "input": "/status"}"#),
+            ("DELETE", "/api/v1/schedules/1", ""),
+            ("POST", "/api/v1/profiling", r#"{"enabled": false, "stages": [], "bucket_bounds_us": [10]}"#),
+            ("DELETE", "/api/v1/profiling", ""),
+            ("POST", "/api/v1/safe_mode", "{}"),
+            ("POST", "/api/v1/safe_mode/resume", ""),
+        ];
+        for (method, uri, body) in admin_only {
+            assert_eq!(status_as(&router, method, uri, &user, body).await, StatusCode::FORBIDDEN, "{} {}", method, uri);
+        }
+
+        // Reads stay open to the user role
+        assert_eq!(status_as(&router, "GET", "/api/v1/profiling", &user, "").await, StatusCode::OK);
+        assert_eq!(status_as(&router, "GET", "/api/v1/safe_mode", &user, "").await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_admin_lifts_safe_mode() {
+        use crate::auth::{AuthConfig, AuthManager, Role, SecretKind};
+        use axum::http::StatusCode;
+
+        let auth = Arc::new(
+            AuthManager::new(AuthConfig { hash_memory_kib: 64, hash_iterations: 1, ..Default::default() })
+                .unwrap(),
+        );
+        auth.create_user("root", "admin-pass", SecretKind::Password, Role::Admin).await.unwrap();
+        let admin = auth.login("root", "admin-pass").await.unwrap().token;
+        let state = test_state().with_auth(auth);
+        let safe_mode = state.safe_mode.clone();
+        let router = create_router(state);
+
+        let body = r#"{"reason": "runaway writes"}"#;
+        assert_eq!(status_as(&router, "POST", "/api/v1/safe_mode", &admin, body).await, StatusCode::OK);
+        assert!(safe_mode.is_engaged());
+
+        // Knowledge and configuration writes are frozen, even for the admin
+        let writes = [
+            ("POST", "/api/v1/ingest", r#"{"filename": "a.txt", "content": "x"}"#),
+            ("POST", "/api/v1/sync/delta", r#"{"version": 1, "instance_id": "peer", "since": 0, "generated_at": 0, "concepts": [], "connections": []}"#),
+            ("POST", "/api/v1/checkpoint/restore", "{}"),
+            ("POST", "/api/v1/config/curiosity", "{}"),
+            ("DELETE", "/api/v1/config/curiosity", ""),
+            ("POST", "/api/v1/adna", "{}"),
+            ("POST", "/api/v1/cdna", "{}"),
+        ];
+        for (method, uri, body) in writes {
+            assert_eq!(
+                status_as(&router, method, uri, &admin, body).await,
+                StatusCode::SERVICE_UNAVAILABLE,
+                "{} {}",
+                method,
+                uri
+            );
+        }
+
+        assert_eq!(status_as(&router, "POST", "/api/v1/safe_mode/resume", &admin, "").await, StatusCode::OK);
+        assert!(!safe_mode.is_engaged());
+        for (method, uri, body) in writes {
+            assert_ne!(status_as(&router, method, uri, &admin, body).await, StatusCode::SERVICE_UNAVAILABLE);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_feedback_rewards_traced_edges() {
+        use crate::learner::Learner;
+        use axum::http::StatusCode;
+
+        let stream = Arc::new(ExperienceStream::new(1000, 10));
+        let learner = Arc::new(Learner::default());
+        learner.add_edge(1, 0.5);
+        learner.add_edge(2, 0.5);
+        let _stepper = learner.clone().spawn_stepper(stream.subscribe());
+
+        // Edge 1 fires, then two more experiences pass before the reward
+        let active = learner.learn(1, 1.0, 1.0).unwrap();
+        let trace = learner.trace(1).unwrap();
+        for _ in 0..2 {
+            stream.write_event(Default::default()).unwrap();
+        }
+        let decay = 0.95f32 * learner.lambda();
+        for _ in 0..100 {
+            if (learner.trace(1).unwrap() - trace * decay * decay).abs() < 1e-5 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+        }
+        assert!((learner.trace(1).unwrap() - trace * decay * decay).abs() < 1e-5);
+
+        let bootstrap = Arc::new(RwLock::new(BootstrapLibrary::new(Default::default())));
+        let gateway = Arc::new(Gateway::new(mpsc::channel(100).0, bootstrap.clone(), Default::default()));
+        let intuition = Arc::new(RwLock::new(IntuitionEngine::new(
+            Default::default(),
+            stream.clone(),
+            Arc::new(InMemoryADNAReader::new(Default::default())),
+            mpsc::channel(100).0,
+        )));
+        let feedback = Arc::new(
+            FeedbackProcessor::new(bootstrap, Arc::new(RwLock::new(ExperienceStream::new(1000, 10))), intuition)
+                .with_learner(learner.clone()),
+        );
+        let router = create_router(ApiState::new(gateway, feedback, Default::default()));
+
+        let body = r#"{"signal_id": 1, "feedback": {"type": "positive", "strength": 1.0}}"#;
+        assert_eq!(status_as(&router, "POST", "/api/v1/feedback", "", body).await, StatusCode::OK);
+
+        // The delayed reward is credited by the decayed trace; untraced edges keep their weight
+        let gain = learner.weight(1).unwrap() - active;
+        assert!((gain - 0.05 * trace * decay * decay).abs() < 1e-4, "gain {}", gain);
+        assert_eq!(learner.weight(2), Some(0.5));
+        assert_eq!(learner.stats().rewards, 1);
+    }
+
+    #[tokio::test]
+    async fn test_instance_routing() {
+        use crate::instance::InstanceId;
+        use axum::http::StatusCode;
+
+        let registry = Arc::new(InstanceRegistry::new(test_state()));
+        registry.insert(InstanceId::new("notes").unwrap(), test_state()).unwrap();
+        let router = create_instances_router(registry.clone());
+
+        assert_eq!(status(&router, "GET", "/api/v1/health").await, StatusCode::OK);
+        assert_eq!(status(&router, "GET", "/api/v1/instances").await, StatusCode::OK);
+        assert_eq!(status(&router, "GET", "/api/v1/instances/notes").await, StatusCode::OK);
+        assert_eq!(status(&router, "GET", "/api/v1/instances/notes/health").await, StatusCode::OK);
+        assert_eq!(
+            status(&router, "GET", "/api/v1/instances/notes/missing").await,
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            status(&router, "GET", "/api/v1/instances/other/health").await,
+            StatusCode::NOT_FOUND
+        );
+
+        assert_eq!(
+            status(&router, "DELETE", "/api/v1/instances/default").await,
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            status(&router, "DELETE", "/api/v1/instances/notes").await,
+            StatusCode::NO_CONTENT
+        );
+        assert_eq!(
+            status(&router, "GET", "/api/v1/instances/notes/health").await,
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sync_delta_routes() {
+        use axum::http::StatusCode;
+
+        let router = create_router(test_state());
+        assert_eq!(status(&router, "GET", "/api/v1/sync/delta").await, StatusCode::INTERNAL_SERVER_ERROR);
+
+        let node = Arc::new(crate::sync::SyncNode::new("server", Arc::new(crate::RuntimeStorage::new())));
+        let router = create_router(test_state().with_sync(node));
+        assert_eq!(status(&router, "GET", "/api/v1/sync/delta?since=0").await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_replication_routes() {
+        use axum::http::StatusCode;
+
+        let writer = Arc::new(crate::RuntimeStorage::new());
+        let feed = crate::replica::ChangeFeed::attach(&writer, Default::default()).unwrap();
+        let router = create_router(test_state().with_change_feed(feed));
+        assert_eq!(status(&router, "GET", "/api/v1/replication/changes?after=0").await, StatusCode::OK);
+        assert_eq!(status(&router, "GET", "/api/v1/replication/status").await, StatusCode::OK);
+
+        // Replicas refuse writes but keep serving reads
+        let replica = Arc::new(crate::replica::Replica::new(Arc::new(crate::RuntimeStorage::new())));
+        let router = create_router(test_state().with_replica(replica));
+        assert_eq!(status(&router, "POST", "/api/v1/feedback").await, StatusCode::FORBIDDEN);
+        assert_eq!(status(&router, "POST", "/api/v1/checkpoint").await, StatusCode::FORBIDDEN);
+        assert_eq!(status(&router, "GET", "/api/v1/replication/status").await, StatusCode::OK);
+        assert_ne!(status(&router, "POST", "/api/v1/query").await, StatusCode::FORBIDDEN);
+        // Queries cannot smuggle commands past the guard
+        let checkpoint = r#"{"query": "/checkpoint"}"#;
+        assert_eq!(status_as(&router, "POST", "/api/v1/query", "", checkpoint).await, StatusCode::FORBIDDEN);
+    }
 }
@@ -2,9 +2,24 @@
 //
 // Shared state for API handlers
 
+use crate::adna::InMemoryADNAReader;
+use crate::action_controller::DecisionTraceLog;
+use crate::auth::{AuthClaims, AuthManager, Role};
+use crate::bootstrap::BootstrapLibrary;
+use crate::background::BackgroundRuntime;
+use crate::chat_history::ChatHistory;
+use crate::ingestion::Ingestor;
+use crate::checkpoint::CheckpointManager;
 use crate::gateway::Gateway;
+use crate::guardian::Guardian;
+use crate::settings::SettingsManager;
+use crate::safe_mode::SafeMode;
+use crate::replica::{ChangeFeed, Replica};
+use crate::sync::SyncNode;
+use crate::terminal::Interpreter;
 use crate::curiosity::CuriosityDrive;
 use crate::feedback::FeedbackProcessor;
+use parking_lot::RwLock;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -23,6 +38,9 @@ pub struct ApiConfig {
     /// API key for authentication (optional)
     pub api_key: Option<String>,
 
+    /// Admin key for admin-scope endpoints such as /cdna (optional)
+    pub admin_key: Option<String>,
+
     /// Request timeout in milliseconds
     pub request_timeout_ms: u64,
 
@@ -37,6 +55,7 @@ impl Default for ApiConfig {
             port: 3000,
             enable_cors: true,
             api_key: None,
+            admin_key: None,
             request_timeout_ms: 30000,
             rate_limit_per_minute: None,
         }
@@ -58,6 +77,7 @@ impl ApiConfig {
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(true),
             api_key: std::env::var("NEUROGRAPH_API_KEY").ok(),
+            admin_key: std::env::var("NEUROGRAPH_ADMIN_KEY").ok(),
             request_timeout_ms: std::env::var("NEUROGRAPH_TIMEOUT_MS")
                 .ok()
                 .and_then(|t| t.parse().ok())
@@ -83,9 +103,54 @@ pub struct ApiState {
     /// Feedback processor
     pub feedback_processor: Arc<FeedbackProcessor>,
 
+    /// Safe mode switch of this instance (the Gateway's)
+    pub safe_mode: Arc<SafeMode>,
+
     /// Curiosity drive (optional)
     pub curiosity: Option<Arc<CuriosityDrive>>,
 
+    /// Arbiter decision traces (optional)
+    pub decision_log: Option<Arc<DecisionTraceLog>>,
+
+    /// Semantic graph for visualization (optional)
+    pub bootstrap: Option<Arc<RwLock<BootstrapLibrary>>>,
+
+    /// Checkpoint manager (optional)
+    pub checkpoint: Option<Arc<CheckpointManager>>,
+
+    /// ADNA appraiser configuration (optional)
+    pub adna: Option<Arc<InMemoryADNAReader>>,
+
+    /// Guardian holding the active CDNA (optional)
+    pub guardian: Option<Arc<RwLock<Guardian>>>,
+
+    /// User accounts and sessions (optional)
+    pub auth: Option<Arc<AuthManager>>,
+
+    /// Runtime-editable component configs (optional)
+    pub settings: Option<Arc<SettingsManager>>,
+
+    /// Terminal command interpreter (optional)
+    pub terminal: Option<Arc<Interpreter>>,
+
+    /// Persistent chat conversations (optional)
+    pub chat: Option<Arc<ChatHistory>>,
+
+    /// Background mode and tray actions (optional)
+    pub background: Option<Arc<BackgroundRuntime>>,
+
+    /// Document ingestion (optional)
+    pub ingestor: Option<Arc<Ingestor>>,
+
+    /// Knowledge sync with peer instances (optional)
+    pub sync: Option<Arc<SyncNode>>,
+
+    /// Change feed served to read replicas (optional, writer role)
+    pub change_feed: Option<Arc<ChangeFeed>>,
+
+    /// Writer batches applied here; write endpoints are rejected (optional, replica role)
+    pub replica: Option<Arc<Replica>>,
+
     /// API configuration
     pub config: Arc<ApiConfig>,
 
@@ -101,9 +166,24 @@ impl ApiState {
         config: ApiConfig,
     ) -> Self {
         Self {
+            safe_mode: gateway.safe_mode().clone(),
             gateway,
             feedback_processor,
             curiosity: None,
+            decision_log: None,
+            bootstrap: None,
+            checkpoint: None,
+            adna: None,
+            guardian: None,
+            auth: None,
+            settings: None,
+            terminal: None,
+            chat: None,
+            background: None,
+            ingestor: None,
+            sync: None,
+            change_feed: None,
+            replica: None,
             config: Arc::new(config),
             start_time: Instant::now(),
         }
@@ -117,27 +197,155 @@ impl ApiState {
         config: ApiConfig,
     ) -> Self {
         Self {
+            safe_mode: gateway.safe_mode().clone(),
             gateway,
             feedback_processor,
             curiosity: Some(curiosity),
+            decision_log: None,
+            bootstrap: None,
+            checkpoint: None,
+            adna: None,
+            guardian: None,
+            auth: None,
+            settings: None,
+            terminal: None,
+            chat: None,
+            background: None,
+            ingestor: None,
+            sync: None,
+            change_feed: None,
+            replica: None,
             config: Arc::new(config),
             start_time: Instant::now(),
         }
     }
 
+    /// Attach Arbiter decision trace log (enables /decisions)
+    pub fn with_decision_log(mut self, decision_log: Arc<DecisionTraceLog>) -> Self {
+        self.decision_log = Some(decision_log);
+        self
+    }
+
+    /// Attach bootstrap library (enables /graph/subgraph.dot)
+    pub fn with_bootstrap(mut self, bootstrap: Arc<RwLock<BootstrapLibrary>>) -> Self {
+        self.bootstrap = Some(bootstrap);
+        self
+    }
+
+    /// Attach checkpoint manager (enables /checkpoint)
+    pub fn with_checkpoint(mut self, checkpoint: Arc<CheckpointManager>) -> Self {
+        self.checkpoint = Some(checkpoint);
+        self
+    }
+
+    /// Attach ADNA reader (enables /adna)
+    pub fn with_adna(mut self, adna: Arc<InMemoryADNAReader>) -> Self {
+        self.adna = Some(adna);
+        self
+    }
+
+    /// Attach Guardian (enables /cdna)
+    pub fn with_guardian(mut self, guardian: Arc<RwLock<Guardian>>) -> Self {
+        self.guardian = Some(guardian);
+        self
+    }
+
+    /// Attach auth manager (enables /auth and session tokens)
+    pub fn with_auth(mut self, auth: Arc<AuthManager>) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Attach settings manager (enables /config)
+    pub fn with_settings(mut self, settings: Arc<SettingsManager>) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+
+    /// Attach command interpreter (enables /terminal)
+    pub fn with_terminal(mut self, terminal: Arc<Interpreter>) -> Self {
+        self.terminal = Some(terminal);
+        self
+    }
+
+    /// Attach chat history (enables /chat and conversation recording in /query)
+    pub fn with_chat(mut self, chat: Arc<ChatHistory>) -> Self {
+        self.chat = Some(chat);
+        self
+    }
+
+    /// Attach background runtime (enables /runtime)
+    pub fn with_background(mut self, background: Arc<BackgroundRuntime>) -> Self {
+        self.background = Some(background);
+        self
+    }
+
+    /// Attach document ingestor (enables /ingest)
+    pub fn with_ingestor(mut self, ingestor: Arc<Ingestor>) -> Self {
+        self.ingestor = Some(ingestor);
+        self
+    }
+
+    /// Attach knowledge sync (enables /sync/delta)
+    pub fn with_sync(mut self, sync: Arc<SyncNode>) -> Self {
+        self.sync = Some(sync);
+        self
+    }
+
+    /// Serve the change feed to read replicas (enables /replication/changes)
+    pub fn with_change_feed(mut self, change_feed: Arc<ChangeFeed>) -> Self {
+        self.change_feed = Some(change_feed);
+        self
+    }
+
+    /// Run as a read-only replica of another instance
+    ///
+    /// The Gateway goes read-only too, so /query cannot run commands.
+    pub fn with_replica(mut self, replica: Arc<Replica>) -> Self {
+        self.gateway.set_read_only(true);
+        self.replica = Some(replica);
+        self
+    }
+
+    /// Whether this instance is a read-only replica
+    pub fn is_replica(&self) -> bool {
+        self.replica.is_some()
+    }
+
     /// Get uptime in seconds
     pub fn uptime_seconds(&self) -> u64 {
         self.start_time.elapsed().as_secs()
     }
 
-    /// Check if API key is valid (if configured)
+    /// Claims of the session token, if auth is enabled and it is live
+    pub fn session(&self, provided_key: Option<&str>) -> Option<AuthClaims> {
+        let auth = self.auth.as_ref()?;
+        auth.verify_token(provided_key?).ok()
+    }
+
+    /// Check if API key or session token is valid (if configured)
     pub fn validate_api_key(&self, provided_key: Option<&str>) -> bool {
+        if self.session(provided_key).is_some() {
+            return true;
+        }
         match (&self.config.api_key, provided_key) {
             (Some(expected), Some(provided)) => expected == provided,
-            (None, _) => true, // No API key required
+            (None, _) => self.auth.is_none(), // No API key required unless auth is enabled
             (Some(_), None) => false, // API key required but not provided
         }
     }
+
+    /// Check admin scope: an admin session, the admin key if configured,
+    /// otherwise the API key
+    pub fn validate_admin_key(&self, provided_key: Option<&str>) -> bool {
+        if let Some(claims) = self.session(provided_key) {
+            return claims.role == Role::Admin;
+        }
+        match &self.config.admin_key {
+            Some(expected) => provided_key == Some(expected.as_str()),
+            None => self.validate_api_key(provided_key),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -172,7 +380,22 @@ mod tests {
                     tokio::sync::mpsc::channel(100).0,
                 ))),
             )),
+            safe_mode: Arc::default(),
             curiosity: None,
+            decision_log: None,
+            bootstrap: None,
+            checkpoint: None,
+            adna: None,
+            guardian: None,
+            auth: None,
+            settings: None,
+            terminal: None,
+            chat: None,
+            background: None,
+            ingestor: None,
+            sync: None,
+            change_feed: None,
+            replica: None,
             config: Arc::new(ApiConfig::default()),
             start_time: Instant::now(),
         };
@@ -188,7 +411,22 @@ mod tests {
         let state_with_key = ApiState {
             gateway: state.gateway.clone(),
             feedback_processor: state.feedback_processor.clone(),
+            safe_mode: state.safe_mode.clone(),
             curiosity: None,
+            decision_log: None,
+            bootstrap: None,
+            checkpoint: None,
+            adna: None,
+            guardian: None,
+            auth: None,
+            settings: None,
+            terminal: None,
+            chat: None,
+            background: None,
+            ingestor: None,
+            sync: None,
+            change_feed: None,
+            replica: None,
             config: Arc::new(config),
             start_time: Instant::now(),
         };
@@ -201,5 +439,56 @@ mod tests {
 
         // No key provided
         assert!(!state_with_key.validate_api_key(None));
+
+        // Admin scope falls back to the API key
+        assert!(state_with_key.validate_admin_key(Some("secret-key")));
+
+        let mut config = ApiConfig::default();
+        config.api_key = Some("secret-key".to_string());
+        config.admin_key = Some("admin-key".to_string());
+        let state_with_admin = ApiState { config: Arc::new(config), ..state_with_key.clone() };
+        assert!(state_with_admin.validate_admin_key(Some("admin-key")));
+        assert!(!state_with_admin.validate_admin_key(Some("secret-key")));
+        assert!(!state_with_admin.validate_admin_key(None));
+    }
+
+    #[tokio::test]
+    async fn test_session_scopes() {
+        use crate::auth::{AuthConfig, SecretKind};
+
+        let auth = Arc::new(
+            AuthManager::new(AuthConfig { hash_memory_kib: 64, hash_iterations: 1, ..Default::default() })
+                .unwrap(),
+        );
+        auth.create_user("root", "admin-pass", SecretKind::Password, Role::Admin).await.unwrap();
+        auth.create_user("kiosk", "4821", SecretKind::Pin, Role::User).await.unwrap();
+
+        let bootstrap = Arc::new(RwLock::new(BootstrapLibrary::new(Default::default())));
+        let feedback = Arc::new(FeedbackProcessor::new(
+            bootstrap.clone(),
+            Arc::new(RwLock::new(crate::experience_stream::ExperienceStream::new(1000, 10))),
+            Arc::new(RwLock::new(crate::IntuitionEngine::new(
+                Default::default(),
+                Arc::new(crate::experience_stream::ExperienceStream::new(1000, 10)),
+                Arc::new(InMemoryADNAReader::new(Default::default())),
+                tokio::sync::mpsc::channel(100).0,
+            ))),
+        ));
+        let gateway = Arc::new(Gateway::new(tokio::sync::mpsc::channel(100).0, bootstrap, Default::default()));
+        let state = ApiState::new(gateway, feedback, ApiConfig::default()).with_auth(auth.clone());
+
+        // Auth enabled: anonymous access ends even without an API key
+        assert!(!state.validate_api_key(None));
+        assert!(!state.validate_api_key(Some("guess")));
+
+        let admin = auth.login("root", "admin-pass").await.unwrap().token;
+        let user = auth.login("kiosk", "4821").await.unwrap().token;
+        assert!(state.validate_api_key(Some(&admin)));
+        assert!(state.validate_admin_key(Some(&admin)));
+        assert!(state.validate_api_key(Some(&user)));
+        assert!(!state.validate_admin_key(Some(&user)));
+
+        auth.logout(&admin);
+        assert!(!state.validate_api_key(Some(&admin)));
     }
 }
@@ -112,6 +112,7 @@ async fn websocket_connection(socket: WebSocket, state: ApiState) {
                                         decision_source: None,
                                         confidence: None,
                                     },
+                                    provenance: None,
                                 },
                             };
 
@@ -32,11 +32,12 @@ use tokio::sync::broadcast;
 use tokio::task::JoinHandle;
 
 use crate::adna::{
-    ADNAReader, ADNAError,
+    ADNAReader, ADNAError, AppraiserConfig,
     HomeostasisParams, CuriosityParams, EfficiencyParams, GoalDirectedParams,
 };
-use crate::experience_stream::{ExperienceEvent, ExperienceWriter, AppraiserType};
+use crate::experience_stream::{ExperienceEvent, ExperienceStream, ExperienceWriter, AppraiserType};
 use crate::coordinates::CoordinateExt;
+use crate::profiling::{PipelineStage, PROFILER};
 
 // ============================================================================
 // HomeostasisAppraiser
@@ -70,6 +71,7 @@ impl HomeostasisAppraiser {
         loop {
             match self.event_receiver.recv().await {
                 Ok(event) => {
+                    let _timer = PROFILER.start(PipelineStage::Appraise);
                     if let Err(e) = self.process_event(event).await {
                         eprintln!("[HomeostasisAppraiser] Error processing event: {}", e);
                     }
@@ -103,37 +105,7 @@ impl HomeostasisAppraiser {
     }
 
     fn calculate_reward(&self, event: &ExperienceEvent, params: &HomeostasisParams) -> f32 {
-        let mut total_penalty = 0.0;
-
-        // Penalty for L5 Cognitive Load deviation
-        let cognitive_load = event.l5_cognitive_load();
-        let (cl_min, cl_max) = params.cognitive_load_range;
-        if cognitive_load < cl_min {
-            total_penalty += (cl_min - cognitive_load).powi(2);
-        } else if cognitive_load > cl_max {
-            total_penalty += (cognitive_load - cl_max).powi(2);
-        }
-
-        // Penalty for L6 Certainty deviation
-        let certainty = event.l6_certainty();
-        let (cert_min, cert_max) = params.certainty_range;
-        if certainty < cert_min {
-            total_penalty += (cert_min - certainty).powi(2);
-        } else if certainty > cert_max {
-            total_penalty += (certainty - cert_max).powi(2);
-        }
-
-        // Penalty for L8 Coherence deviation
-        let coherence = event.l8_coherence();
-        let (coh_min, coh_max) = params.coherence_range;
-        if coherence < coh_min {
-            total_penalty += (coh_min - coherence).powi(2);
-        } else if coherence > coh_max {
-            total_penalty += (coherence - coh_max).powi(2);
-        }
-
-        // Return negative weighted penalty
-        -params.weight * params.penalty_multiplier * total_penalty
+        homeostasis_reward(event, params) * event.trust()
     }
 }
 
@@ -167,6 +139,7 @@ impl CuriosityAppraiser {
         loop {
             match self.event_receiver.recv().await {
                 Ok(event) => {
+                    let _timer = PROFILER.start(PipelineStage::Appraise);
                     if let Err(e) = self.process_event(event).await {
                         eprintln!("[CuriosityAppraiser] Error processing event: {}", e);
                     }
@@ -196,15 +169,7 @@ impl CuriosityAppraiser {
     }
 
     fn calculate_reward(&self, event: &ExperienceEvent, params: &CuriosityParams) -> f32 {
-        let novelty = event.l2_novelty();
-
-        // Only reward novelty above threshold
-        if novelty > params.novelty_threshold {
-            let novelty_excess = novelty - params.novelty_threshold;
-            params.weight * params.reward_multiplier * novelty_excess
-        } else {
-            0.0
-        }
+        curiosity_reward(event, params) * event.trust()
     }
 }
 
@@ -239,6 +204,7 @@ impl EfficiencyAppraiser {
         loop {
             match self.event_receiver.recv().await {
                 Ok(event) => {
+                    let _timer = PROFILER.start(PipelineStage::Appraise);
                     if let Err(e) = self.process_event(event).await {
                         eprintln!("[EfficiencyAppraiser] Error processing event: {}", e);
                     }
@@ -268,23 +234,7 @@ impl EfficiencyAppraiser {
     }
 
     fn calculate_reward(&self, event: &ExperienceEvent, params: &EfficiencyParams) -> f32 {
-        let mut total_cost = 0.0;
-
-        // Cost for motor activity (L3 velocity and acceleration)
-        let velocity = event.l3_velocity();
-        let acceleration = event.l3_acceleration();
-        total_cost += params.motor_cost_factor * (velocity.powi(2) + acceleration.powi(2));
-
-        // Cost for cognitive load
-        let cognitive_load = event.l5_cognitive_load();
-        total_cost += params.cognitive_cost_factor * cognitive_load;
-
-        // Cost for creation events (event_type is u16, not string)
-        // For MVP, we can add creation cost based on flags or skip this check
-        // Future: define event_type constants for token_created, connection_created
-
-        // Return negative weighted cost
-        -params.weight * total_cost
+        efficiency_reward(event, params) * event.trust()
     }
 }
 
@@ -319,6 +269,7 @@ impl GoalDirectedAppraiser {
         loop {
             match self.event_receiver.recv().await {
                 Ok(event) => {
+                    let _timer = PROFILER.start(PipelineStage::Appraise);
                     if let Err(e) = self.process_event(event).await {
                         eprintln!("[GoalDirectedAppraiser] Error processing event: {}", e);
                     }
@@ -348,24 +299,199 @@ impl GoalDirectedAppraiser {
     }
 
     fn calculate_reward(&self, event: &ExperienceEvent, params: &GoalDirectedParams) -> f32 {
-        // MVP: Simplified immediate rewards
-        // Full retroactive trajectory-based rewards deferred for future implementation
-
-        // For MVP, use L7 Valence as a proxy for goal achievement
-        // Positive valence indicates progress toward goals
-        let valence = event.l7_valence();
-
-        if valence > 0.5 {
-            // High positive valence → likely goal achievement
-            params.weight * valence
-        } else if valence > 0.0 {
-            // Moderate positive valence → goal progress
-            params.weight * valence * 0.5
-        } else {
-            // Negative or zero valence → no goal reward
-            0.0
+        goal_directed_reward(event, params) * event.trust()
+    }
+}
+
+// ============================================================================
+// Reward functions
+// ============================================================================
+
+/// Homeostasis reward: negative squared deviation of L5/L6/L8 from their target ranges
+pub fn homeostasis_reward(event: &ExperienceEvent, params: &HomeostasisParams) -> f32 {
+    let mut total_penalty = 0.0;
+
+    // Penalty for L5 Cognitive Load deviation
+    let cognitive_load = event.l5_cognitive_load();
+    let (cl_min, cl_max) = params.cognitive_load_range;
+    if cognitive_load < cl_min {
+        total_penalty += (cl_min - cognitive_load).powi(2);
+    } else if cognitive_load > cl_max {
+        total_penalty += (cognitive_load - cl_max).powi(2);
+    }
+
+    // Penalty for L6 Certainty deviation
+    let certainty = event.l6_certainty();
+    let (cert_min, cert_max) = params.certainty_range;
+    if certainty < cert_min {
+        total_penalty += (cert_min - certainty).powi(2);
+    } else if certainty > cert_max {
+        total_penalty += (certainty - cert_max).powi(2);
+    }
+
+    // Penalty for L8 Coherence deviation
+    let coherence = event.l8_coherence();
+    let (coh_min, coh_max) = params.coherence_range;
+    if coherence < coh_min {
+        total_penalty += (coh_min - coherence).powi(2);
+    } else if coherence > coh_max {
+        total_penalty += (coherence - coh_max).powi(2);
+    }
+
+    // Return negative weighted penalty
+    -params.weight * params.penalty_multiplier * total_penalty
+}
+
+/// Curiosity reward: L2 novelty above the threshold
+pub fn curiosity_reward(event: &ExperienceEvent, params: &CuriosityParams) -> f32 {
+    let novelty = event.l2_novelty();
+
+    // Only reward novelty above threshold
+    if novelty > params.novelty_threshold {
+        let novelty_excess = novelty - params.novelty_threshold;
+        params.weight * params.reward_multiplier * novelty_excess
+    } else {
+        0.0
+    }
+}
+
+/// Efficiency reward: negative cost of motor activity (L3) and cognitive load (L5)
+pub fn efficiency_reward(event: &ExperienceEvent, params: &EfficiencyParams) -> f32 {
+    let mut total_cost = 0.0;
+
+    // Cost for motor activity (L3 velocity and acceleration)
+    let velocity = event.l3_velocity();
+    let acceleration = event.l3_acceleration();
+    total_cost += params.motor_cost_factor * (velocity.powi(2) + acceleration.powi(2));
+
+    // Cost for cognitive load
+    let cognitive_load = event.l5_cognitive_load();
+    total_cost += params.cognitive_cost_factor * cognitive_load;
+
+    // Cost for creation events (event_type is u16, not string)
+    // For MVP, we can add creation cost based on flags or skip this check
+    // Future: define event_type constants for token_created, connection_created
+
+    // Return negative weighted cost
+    -params.weight * total_cost
+}
+
+/// Goal-directed reward (MVP): positive L7 valence as a proxy for goal progress
+pub fn goal_directed_reward(event: &ExperienceEvent, params: &GoalDirectedParams) -> f32 {
+    // MVP: Simplified immediate rewards
+    // Full retroactive trajectory-based rewards deferred for future implementation
+
+    // For MVP, use L7 Valence as a proxy for goal achievement
+    // Positive valence indicates progress toward goals
+    let valence = event.l7_valence();
+
+    if valence > 0.5 {
+        // High positive valence → likely goal achievement
+        params.weight * valence
+    } else if valence > 0.0 {
+        // Moderate positive valence → goal progress
+        params.weight * valence * 0.5
+    } else {
+        // Negative or zero valence → no goal reward
+        0.0
+    }
+}
+
+/// Partial rewards of one event from all 4 appraisers
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Appraisal {
+    pub homeostasis: f32,
+    pub curiosity: f32,
+    pub efficiency: f32,
+    pub goal_directed: f32,
+}
+
+impl Appraisal {
+    pub fn total(&self) -> f32 {
+        self.homeostasis + self.curiosity + self.efficiency + self.goal_directed
+    }
+}
+
+/// Evaluate an event with all 4 appraisers synchronously
+///
+/// Same rewards the appraiser tasks write back to the ExperienceStream,
+/// without the ADNA round-trip (replay, benchmarks, offline analysis).
+/// Every reward is scaled by the event's source trust.
+pub fn appraise_event(event: &ExperienceEvent, config: &AppraiserConfig) -> Appraisal {
+    let trust = event.trust();
+    Appraisal {
+        homeostasis: homeostasis_reward(event, &config.homeostasis) * trust,
+        curiosity: curiosity_reward(event, &config.curiosity) * trust,
+        efficiency: efficiency_reward(event, &config.efficiency) * trust,
+        goal_directed: goal_directed_reward(event, &config.goal_directed) * trust,
+    }
+}
+
+// ============================================================================
+// Re-appraisal after ADNA changes
+// ============================================================================
+
+/// Outcome of `reappraise_window`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ReappraisalReport {
+    /// Events still in the hot buffer within the window
+    pub scanned: usize,
+
+    /// Events re-scored under the new ADNA
+    pub reappraised: usize,
+
+    /// Events already scored by this generation
+    pub skipped: usize,
+
+    /// Sum of total-reward changes (new - old)
+    pub reward_delta: f64,
+}
+
+/// Recompute the rewards of the last `window` events under `config`
+///
+/// Every re-scored event is stamped with `generation`
+/// (`ExperienceStream::event_generation`), so the IntuitionEngine can tell
+/// which ADNA produced a reward. Events already scored by `generation` are
+/// left alone.
+pub fn reappraise_window(
+    stream: &ExperienceStream,
+    config: &AppraiserConfig,
+    generation: u32,
+    window: usize,
+) -> ReappraisalReport {
+    let end = stream.total_written();
+    let start = end.saturating_sub(window as u64);
+    let mut report = ReappraisalReport::default();
+
+    for seq in start..end {
+        let Some(event) = stream.get_event(seq) else {
+            continue;
+        };
+        report.scanned += 1;
+        if stream.event_generation(seq) == Some(generation) {
+            report.skipped += 1;
+            continue;
+        }
+
+        let appraisal = appraise_event(&event, config);
+        let rewards = [
+            (AppraiserType::Homeostasis, appraisal.homeostasis),
+            (AppraiserType::Curiosity, appraisal.curiosity),
+            (AppraiserType::Efficiency, appraisal.efficiency),
+            (AppraiserType::Goal, appraisal.goal_directed),
+        ];
+        if rewards.iter().any(|&(appraiser, reward)| stream.set_appraiser_reward(seq, appraiser, reward).is_err())
+            || stream.set_event_generation(seq, generation).is_err()
+        {
+            // Overwritten in the ring buffer meanwhile
+            continue;
         }
+
+        report.reappraised += 1;
+        report.reward_delta += (appraisal.total() - event.total_reward()) as f64;
     }
+
+    report
 }
 
 // ============================================================================
@@ -519,6 +645,31 @@ mod tests {
         assert!(reward < 0.0); // Should be penalized
     }
 
+    #[test]
+    fn test_appraise_event() {
+        let config = AppraiserConfig::default();
+        let mut event = ExperienceEvent::default();
+        event.state = [0.0, 0.8, 0.2, 0.0, 0.9, 0.6, 0.7, 0.8];
+
+        let appraisal = appraise_event(&event, &config);
+        assert_eq!(appraisal.homeostasis, homeostasis_reward(&event, &config.homeostasis));
+        assert!(appraisal.homeostasis < 0.0);
+        assert!(appraisal.curiosity > 0.0);
+        assert!(appraisal.efficiency < 0.0);
+        assert!(appraisal.goal_directed > 0.0);
+        assert_eq!(
+            appraisal.total(),
+            appraisal.homeostasis + appraisal.curiosity + appraisal.efficiency + appraisal.goal_directed
+        );
+
+        // Half-trusted source: half the reward
+        event.set_trust(0.5);
+        assert!((event.trust() - 0.5).abs() < 1e-6);
+        let scaled = appraise_event(&event, &config);
+        assert!((scaled.total() - appraisal.total() * 0.5).abs() < 1e-6);
+        assert!(!event.is_fully_appraised());
+    }
+
     #[test]
     fn test_curiosity_reward_calculation() {
         let params = CuriosityParams::default();
@@ -182,6 +182,16 @@ impl ExperienceToken {
         self.token_type == EXPERIENCE_TOKEN_MAGIC
     }
 
+    /// Serialize to bytes (128 bytes)
+    pub fn to_bytes(&self) -> [u8; 128] {
+        unsafe { std::mem::transmute(*self) }
+    }
+
+    /// Deserialize from bytes (128 bytes); see `validate_bytes`
+    pub fn from_bytes(bytes: &[u8; 128]) -> Self {
+        unsafe { std::mem::transmute(*bytes) }
+    }
+
     /// Check if episode is done
     pub fn is_done(&self) -> bool {
         self.has_flag(ExperienceFlags::TERMINAL) || self.has_flag(ExperienceFlags::TRUNCATED)
@@ -251,6 +261,32 @@ impl Default for ExperienceToken {
     }
 }
 
+/// Decode and validate a serialized ExperienceToken
+///
+/// Rejects input that is not exactly 128 bytes, a wrong magic number and
+/// non-finite state, action, reward or next-state values.
+pub fn validate_bytes(bytes: &[u8]) -> Result<ExperienceToken, String> {
+    let bytes: &[u8; 128] = bytes
+        .try_into()
+        .map_err(|_| format!("ExperienceToken requires 128 bytes, got {}", bytes.len()))?;
+    let token = ExperienceToken::from_bytes(bytes);
+    if !token.is_valid() {
+        let magic = token.token_type;
+        return Err(format!("Invalid magic number: 0x{:08X}", magic));
+    }
+    let (state, action, reward, next_state) = (token.state, token.action, token.reward, token.next_state);
+    if state
+        .iter()
+        .chain(&action)
+        .chain(&next_state)
+        .chain(std::iter::once(&reward))
+        .any(|v| !v.is_finite())
+    {
+        return Err("State, action, reward and next state must be finite".to_string());
+    }
+    Ok(token)
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
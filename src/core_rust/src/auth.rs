@@ -0,0 +1,756 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Auth v1.0 - user accounts, PIN/password login and sessions
+//!
+//! Secrets are hashed with Argon2id (PHC strings) and never stored or
+//! logged in plain text. User records live in memory and, when a
+//! [`PersistenceBackend`] is attached, in its configuration store:
+//!
+//! ```text
+//! component "auth", key "user:<username>" -> UserRecord JSON
+//! ```
+//!
+//! After `max_failed_attempts` wrong secrets in a row an account is locked
+//! for `lockout_duration`; the counter is persisted, so restarting the
+//! daemon does not reset it.
+//!
+//! A successful login returns an opaque bearer token mapped to
+//! [`AuthClaims`] (subject, role, expiry). Sessions are held in memory only
+//! and end with the process. The REST API accepts the token as
+//! `Authorization: Bearer <token>` or `X-API-Key`; `Role::Admin` grants the
+//! admin scope.
+
+use crate::persistence::{PersistenceBackend, PersistenceError};
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use parking_lot::RwLock;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Persistence component holding user records
+pub const AUTH_COMPONENT: &str = "auth";
+
+const USER_KEY_PREFIX: &str = "user:";
+
+/// Maximum username length
+pub const MAX_USERNAME_LEN: usize = 64;
+
+/// Role claim carried by a session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    /// Query, feedback and read endpoints
+    User,
+    /// Everything, including admin-scope endpoints and user management
+    Admin,
+}
+
+/// Kind of secret a user logs in with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SecretKind {
+    /// Digits only, for quick unlock in the desktop UI
+    Pin,
+    Password,
+}
+
+/// Auth configuration
+#[derive(Debug, Clone)]
+pub struct AuthConfig {
+    /// Failed attempts in a row before the account is locked
+    pub max_failed_attempts: u32,
+    /// How long a locked account stays locked
+    pub lockout_duration: Duration,
+    /// Session lifetime
+    pub session_ttl: Duration,
+    pub min_pin_length: usize,
+    pub min_password_length: usize,
+    /// Argon2 memory cost in KiB
+    pub hash_memory_kib: u32,
+    /// Argon2 time cost (passes)
+    pub hash_iterations: u32,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            max_failed_attempts: 5,
+            lockout_duration: Duration::from_secs(300),
+            session_ttl: Duration::from_secs(12 * 3600),
+            min_pin_length: 4,
+            min_password_length: 8,
+            // OWASP minimum for Argon2id
+            hash_memory_kib: 19 * 1024,
+            hash_iterations: 2,
+        }
+    }
+}
+
+impl AuthConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_failed_attempts == 0 {
+            return Err("max_failed_attempts must be > 0".to_string());
+        }
+        if self.session_ttl.is_zero() {
+            return Err("session_ttl must be > 0".to_string());
+        }
+        if self.min_pin_length < 4 {
+            return Err("min_pin_length must be >= 4".to_string());
+        }
+        if self.min_password_length < 8 {
+            return Err("min_password_length must be >= 8".to_string());
+        }
+        Params::new(self.hash_memory_kib, self.hash_iterations, 1, None)
+            .map_err(|e| format!("Invalid Argon2 parameters: {}", e))?;
+        Ok(())
+    }
+}
+
+/// Auth errors
+#[derive(Debug)]
+pub enum AuthError {
+    /// Unknown user or wrong secret (deliberately indistinguishable)
+    InvalidCredentials,
+    /// Too many failed attempts; locked until this Unix time (seconds)
+    Locked { until: u64 },
+    InvalidToken,
+    InvalidUsername(String),
+    WeakSecret(String),
+    UserExists(String),
+    UserNotFound(String),
+    Config(String),
+    Hash(String),
+    Persistence(PersistenceError),
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::InvalidCredentials => write!(f, "Invalid username or secret"),
+            AuthError::Locked { until } => write!(f, "Account locked until {} (Unix time)", until),
+            AuthError::InvalidToken => write!(f, "Invalid or expired session token"),
+            AuthError::InvalidUsername(name) => write!(
+                f,
+                "Invalid username '{}': use 1-{} characters from [A-Za-z0-9._-]",
+                name, MAX_USERNAME_LEN
+            ),
+            AuthError::WeakSecret(msg) => write!(f, "Weak secret: {}", msg),
+            AuthError::UserExists(name) => write!(f, "User '{}' already exists", name),
+            AuthError::UserNotFound(name) => write!(f, "User '{}' not found", name),
+            AuthError::Config(msg) => write!(f, "Invalid auth config: {}", msg),
+            AuthError::Hash(msg) => write!(f, "Hashing failed: {}", msg),
+            AuthError::Persistence(e) => write!(f, "Persistence error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for AuthError {}
+
+impl From<PersistenceError> for AuthError {
+    fn from(e: PersistenceError) -> Self {
+        AuthError::Persistence(e)
+    }
+}
+
+/// Claims of an authenticated session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthClaims {
+    /// Username
+    pub subject: String,
+    pub role: Role,
+    /// Unix seconds
+    pub issued_at: u64,
+    /// Unix seconds
+    pub expires_at: u64,
+}
+
+/// Result of a successful login
+#[derive(Debug, Clone)]
+pub struct Session {
+    /// Opaque bearer token
+    pub token: String,
+    pub claims: AuthClaims,
+}
+
+/// Public view of a user account (no hash)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserInfo {
+    pub username: String,
+    pub role: Role,
+    pub kind: SecretKind,
+    /// Unix seconds
+    pub created_at: u64,
+    pub failed_attempts: u32,
+    /// Unix seconds, if currently locked
+    pub locked_until: Option<u64>,
+}
+
+/// Stored account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct UserRecord {
+    username: String,
+    role: Role,
+    kind: SecretKind,
+    /// Argon2id PHC string
+    hash: String,
+    created_at: u64,
+    failed_attempts: u32,
+    locked_until: Option<u64>,
+}
+
+impl UserRecord {
+    fn info(&self, now: u64) -> UserInfo {
+        UserInfo {
+            username: self.username.clone(),
+            role: self.role,
+            kind: self.kind,
+            created_at: self.created_at,
+            failed_attempts: self.failed_attempts,
+            locked_until: self.locked_until.filter(|&until| until > now),
+        }
+    }
+}
+
+struct StoredUser {
+    record: UserRecord,
+    /// Active configuration row, if persisted
+    config_id: Option<i32>,
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn validate_username(username: &str) -> Result<(), AuthError> {
+    let valid = !username.is_empty()
+        && username.len() <= MAX_USERNAME_LEN
+        && username
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-'));
+    if valid {
+        Ok(())
+    } else {
+        Err(AuthError::InvalidUsername(username.to_string()))
+    }
+}
+
+/// User store, login with lockout, and session tokens
+pub struct AuthManager {
+    config: AuthConfig,
+    backend: Option<Arc<dyn PersistenceBackend>>,
+    users: RwLock<HashMap<String, StoredUser>>,
+    sessions: RwLock<HashMap<String, AuthClaims>>,
+    /// Verified against for unknown users so login time does not reveal them
+    dummy_hash: OnceLock<String>,
+}
+
+impl AuthManager {
+    pub fn new(config: AuthConfig) -> Result<Self, AuthError> {
+        config.validate().map_err(AuthError::Config)?;
+        Ok(Self {
+            config,
+            backend: None,
+            users: RwLock::new(HashMap::new()),
+            sessions: RwLock::new(HashMap::new()),
+            dummy_hash: OnceLock::new(),
+        })
+    }
+
+    /// Persist user records in `backend` (call `load` afterwards)
+    pub fn with_backend(mut self, backend: Arc<dyn PersistenceBackend>) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
+    pub fn config(&self) -> &AuthConfig {
+        &self.config
+    }
+
+    /// Load user records from the backend, replacing the in-memory ones
+    pub async fn load(&self) -> Result<usize, AuthError> {
+        let Some(backend) = &self.backend else {
+            return Ok(self.users.read().len());
+        };
+
+        let mut users = HashMap::new();
+        for config in backend.get_component_configs(AUTH_COMPONENT).await? {
+            if !config.config_key.starts_with(USER_KEY_PREFIX) {
+                continue;
+            }
+            match serde_json::from_value::<UserRecord>(config.config_value) {
+                Ok(record) => {
+                    users.insert(
+                        record.username.clone(),
+                        StoredUser { record, config_id: Some(config.config_id) },
+                    );
+                }
+                Err(e) => tracing::warn!(key = %config.config_key, error = %e, "Skipping invalid user record"),
+            }
+        }
+
+        let count = users.len();
+        *self.users.write() = users;
+        Ok(count)
+    }
+
+    /// Create a user
+    pub async fn create_user(
+        &self,
+        username: &str,
+        secret: &str,
+        kind: SecretKind,
+        role: Role,
+    ) -> Result<UserInfo, AuthError> {
+        validate_username(username)?;
+        self.check_secret(secret, kind)?;
+        if self.users.read().contains_key(username) {
+            return Err(AuthError::UserExists(username.to_string()));
+        }
+
+        let record = UserRecord {
+            username: username.to_string(),
+            role,
+            kind,
+            hash: self.hash(secret).await?,
+            created_at: now_secs(),
+            failed_attempts: 0,
+            locked_until: None,
+        };
+
+        let config_id = self.persist(&record, None).await?;
+        let mut users = self.users.write();
+        if users.contains_key(username) {
+            return Err(AuthError::UserExists(username.to_string()));
+        }
+        let info = record.info(now_secs());
+        users.insert(username.to_string(), StoredUser { record, config_id });
+        Ok(info)
+    }
+
+    /// Create an admin account if no users exist yet (first start)
+    pub async fn ensure_admin(
+        &self,
+        username: &str,
+        secret: &str,
+        kind: SecretKind,
+    ) -> Result<bool, AuthError> {
+        if !self.users.read().is_empty() {
+            return Ok(false);
+        }
+        self.create_user(username, secret, kind, Role::Admin).await?;
+        Ok(true)
+    }
+
+    /// Replace a user's secret; ends the user's sessions and clears a lockout
+    pub async fn set_secret(&self, username: &str, secret: &str, kind: SecretKind) -> Result<(), AuthError> {
+        self.check_secret(secret, kind)?;
+        let hash = self.hash(secret).await?;
+        self.update(username, |record| {
+            record.kind = kind;
+            record.hash = hash;
+            record.failed_attempts = 0;
+            record.locked_until = None;
+        })
+        .await?;
+        self.end_sessions(username);
+        Ok(())
+    }
+
+    /// Change a user's role; ends the user's sessions
+    pub async fn set_role(&self, username: &str, role: Role) -> Result<(), AuthError> {
+        self.update(username, |record| record.role = role).await?;
+        self.end_sessions(username);
+        Ok(())
+    }
+
+    /// Clear a lockout
+    pub async fn unlock(&self, username: &str) -> Result<(), AuthError> {
+        self.update(username, |record| {
+            record.failed_attempts = 0;
+            record.locked_until = None;
+        })
+        .await
+    }
+
+    /// Delete a user and end their sessions
+    pub async fn remove_user(&self, username: &str) -> Result<(), AuthError> {
+        let config_id = self
+            .users
+            .read()
+            .get(username)
+            .map(|user| user.config_id)
+            .ok_or_else(|| AuthError::UserNotFound(username.to_string()))?;
+
+        if let (Some(backend), Some(config_id)) = (&self.backend, config_id) {
+            backend.deactivate_config(config_id).await?;
+        }
+        self.users.write().remove(username);
+        self.end_sessions(username);
+        Ok(())
+    }
+
+    /// All users, sorted by name
+    pub fn users(&self) -> Vec<UserInfo> {
+        let now = now_secs();
+        let mut users: Vec<UserInfo> = self.users.read().values().map(|u| u.record.info(now)).collect();
+        users.sort_by(|a, b| a.username.cmp(&b.username));
+        users
+    }
+
+    /// Check a secret and open a session
+    pub async fn login(&self, username: &str, secret: &str) -> Result<Session, AuthError> {
+        let now = now_secs();
+        let record = self.users.read().get(username).map(|u| u.record.clone());
+
+        let Some(record) = record else {
+            self.verify_dummy(secret).await;
+            return Err(AuthError::InvalidCredentials);
+        };
+
+        if let Some(until) = record.locked_until {
+            if until > now {
+                return Err(AuthError::Locked { until });
+            }
+        }
+
+        if self.verify(secret, &record.hash).await {
+            if record.failed_attempts > 0 || record.locked_until.is_some() {
+                if let Err(e) = self
+                    .update(username, |r| {
+                        r.failed_attempts = 0;
+                        r.locked_until = None;
+                    })
+                    .await
+                {
+                    tracing::warn!(user = %username, error = %e, "Failed to reset login counter");
+                }
+            }
+            return Ok(self.open_session(&record, now));
+        }
+
+        // Failed attempt: count it against the current record (parallel
+        // guesses must all add up), lock when the limit is reached
+        let (max_attempts, lockout) = (self.config.max_failed_attempts, self.config.lockout_duration.as_secs());
+        let mut locked_until = None;
+        if let Err(e) = self
+            .update(username, |r| {
+                match r.locked_until {
+                    // Another guess locked the account meanwhile
+                    Some(until) if until > now => {
+                        locked_until = Some(until);
+                        return;
+                    }
+                    Some(_) => {
+                        r.failed_attempts = 1;
+                        r.locked_until = None;
+                    }
+                    None => r.failed_attempts = r.failed_attempts.saturating_add(1),
+                }
+                if r.failed_attempts >= max_attempts {
+                    r.locked_until = Some(now + lockout);
+                    locked_until = r.locked_until;
+                }
+            })
+            .await
+        {
+            tracing::warn!(user = %username, error = %e, "Failed to persist login failure");
+        }
+
+        match locked_until {
+            Some(until) => {
+                tracing::warn!(user = %username, until, "Account locked after failed logins");
+                Err(AuthError::Locked { until })
+            }
+            None => Err(AuthError::InvalidCredentials),
+        }
+    }
+
+    /// Claims of a live session
+    pub fn verify_token(&self, token: &str) -> Result<AuthClaims, AuthError> {
+        let claims = self.sessions.read().get(token).cloned().ok_or(AuthError::InvalidToken)?;
+        if claims.expires_at <= now_secs() {
+            self.sessions.write().remove(token);
+            return Err(AuthError::InvalidToken);
+        }
+        Ok(claims)
+    }
+
+    /// End a session; returns false if the token was unknown
+    pub fn logout(&self, token: &str) -> bool {
+        self.sessions.write().remove(token).is_some()
+    }
+
+    /// Drop expired sessions; returns how many were removed
+    pub fn purge_expired(&self) -> usize {
+        let now = now_secs();
+        let mut sessions = self.sessions.write();
+        let before = sessions.len();
+        sessions.retain(|_, claims| claims.expires_at > now);
+        before - sessions.len()
+    }
+
+    pub fn session_count(&self) -> usize {
+        self.sessions.read().len()
+    }
+
+    fn open_session(&self, record: &UserRecord, now: u64) -> Session {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let token: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+
+        let claims = AuthClaims {
+            subject: record.username.clone(),
+            role: record.role,
+            issued_at: now,
+            expires_at: now + self.config.session_ttl.as_secs(),
+        };
+        self.sessions.write().insert(token.clone(), claims.clone());
+        Session { token, claims }
+    }
+
+    fn end_sessions(&self, username: &str) {
+        self.sessions.write().retain(|_, claims| claims.subject != username);
+    }
+
+    /// Apply `change` to a user and persist the new record version
+    async fn update(&self, username: &str, change: impl FnOnce(&mut UserRecord)) -> Result<(), AuthError> {
+        // In memory first, under one lock: a backend outage must not disable
+        // the lockout, and concurrent updates must not overwrite each other
+        let (record, parent) = {
+            let mut users = self.users.write();
+            let user = users
+                .get_mut(username)
+                .ok_or_else(|| AuthError::UserNotFound(username.to_string()))?;
+            change(&mut user.record);
+            (user.record.clone(), user.config_id)
+        };
+        let config_id = self.persist(&record, parent).await?;
+        if let Some(user) = self.users.write().get_mut(username) {
+            user.config_id = config_id;
+        }
+        Ok(())
+    }
+
+    async fn persist(&self, record: &UserRecord, parent: Option<i32>) -> Result<Option<i32>, AuthError> {
+        let Some(backend) = &self.backend else {
+            return Ok(parent);
+        };
+        let value = serde_json::to_value(record)
+            .map_err(|e| AuthError::Persistence(PersistenceError::SerializationError(e.to_string())))?;
+        let key = format!("{}{}", USER_KEY_PREFIX, record.username);
+        Ok(Some(backend.save_config(AUTH_COMPONENT, &key, value, parent).await?))
+    }
+
+    fn check_secret(&self, secret: &str, kind: SecretKind) -> Result<(), AuthError> {
+        match kind {
+            SecretKind::Pin => {
+                if !secret.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(AuthError::WeakSecret("PIN must contain digits only".to_string()));
+                }
+                if secret.len() < self.config.min_pin_length {
+                    return Err(AuthError::WeakSecret(format!(
+                        "PIN must have at least {} digits",
+                        self.config.min_pin_length
+                    )));
+                }
+                if secret.bytes().all(|b| b == secret.as_bytes()[0]) {
+                    return Err(AuthError::WeakSecret("PIN must not repeat one digit".to_string()));
+                }
+            }
+            SecretKind::Password => {
+                if secret.chars().count() < self.config.min_password_length {
+                    return Err(AuthError::WeakSecret(format!(
+                        "password must have at least {} characters",
+                        self.config.min_password_length
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Hash a secret off the async workers (Argon2 is deliberately slow)
+    async fn hash(&self, secret: &str) -> Result<String, AuthError> {
+        let params = Params::new(self.config.hash_memory_kib, self.config.hash_iterations, 1, None)
+            .expect("validated in AuthManager::new");
+        let secret = secret.to_string();
+        tokio::task::spawn_blocking(move || hash_secret(params, &secret))
+            .await
+            .map_err(|e| AuthError::Hash(e.to_string()))?
+    }
+
+    /// Verify against a PHC string (its own parameters apply)
+    async fn verify(&self, secret: &str, hash: &str) -> bool {
+        let (secret, hash) = (secret.to_string(), hash.to_string());
+        tokio::task::spawn_blocking(move || verify_secret(&secret, &hash))
+            .await
+            .unwrap_or(false)
+    }
+
+    async fn verify_dummy(&self, secret: &str) {
+        let dummy = match self.dummy_hash.get() {
+            Some(dummy) => dummy.clone(),
+            None => {
+                let dummy = self.hash("dummy-secret-for-timing").await.unwrap_or_default();
+                self.dummy_hash.get_or_init(|| dummy).clone()
+            }
+        };
+        let _ = self.verify(secret, &dummy).await;
+    }
+}
+
+fn hash_secret(params: Params, secret: &str) -> Result<String, AuthError> {
+    let mut salt = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut salt);
+    let salt = SaltString::encode_b64(&salt).map_err(|e| AuthError::Hash(e.to_string()))?;
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password(secret.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| AuthError::Hash(e.to_string()))
+}
+
+fn verify_secret(secret: &str, hash: &str) -> bool {
+    match PasswordHash::new(hash) {
+        Ok(parsed) => Argon2::default().verify_password(secret.as_bytes(), &parsed).is_ok(),
+        Err(e) => {
+            tracing::warn!(error = %e, "Stored password hash is malformed");
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::testing::ConfigBackend;
+
+    /// Cheap hashing so tests stay fast in debug builds
+    fn test_config() -> AuthConfig {
+        AuthConfig {
+            max_failed_attempts: 3,
+            hash_memory_kib: 64,
+            hash_iterations: 1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_config_validate() {
+        assert!(AuthConfig::default().validate().is_ok());
+        assert!(AuthConfig { max_failed_attempts: 0, ..test_config() }.validate().is_err());
+        assert!(AuthConfig { min_pin_length: 3, ..test_config() }.validate().is_err());
+        assert!(AuthConfig { hash_memory_kib: 1, ..test_config() }.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_secret_rules() {
+        let auth = AuthManager::new(test_config()).unwrap();
+        for (secret, kind) in [("12a4", SecretKind::Pin), ("123", SecretKind::Pin), ("0000", SecretKind::Pin), ("short", SecretKind::Password)] {
+            assert!(
+                matches!(auth.create_user("alice", secret, kind, Role::User).await, Err(AuthError::WeakSecret(_))),
+                "{}",
+                secret
+            );
+        }
+        assert!(matches!(
+            auth.create_user("no spaces", "4821", SecretKind::Pin, Role::User).await,
+            Err(AuthError::InvalidUsername(_))
+        ));
+        auth.create_user("alice", "4821", SecretKind::Pin, Role::User).await.unwrap();
+        assert!(matches!(
+            auth.create_user("alice", "4821", SecretKind::Pin, Role::User).await,
+            Err(AuthError::UserExists(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_login_and_sessions() {
+        let auth = AuthManager::new(test_config()).unwrap();
+        auth.create_user("root", "correct horse", SecretKind::Password, Role::Admin).await.unwrap();
+
+        let session = auth.login("root", "correct horse").await.unwrap();
+        assert_eq!(session.token.len(), 64);
+        let claims = auth.verify_token(&session.token).unwrap();
+        assert_eq!(claims.subject, "root");
+        assert_eq!(claims.role, Role::Admin);
+
+        assert!(matches!(auth.login("nobody", "correct horse").await, Err(AuthError::InvalidCredentials)));
+        assert!(matches!(auth.verify_token("forged"), Err(AuthError::InvalidToken)));
+
+        // A role change invalidates existing sessions
+        auth.set_role("root", Role::User).await.unwrap();
+        assert!(auth.verify_token(&session.token).is_err());
+
+        let session = auth.login("root", "correct horse").await.unwrap();
+        assert_eq!(session.claims.role, Role::User);
+        assert!(auth.logout(&session.token));
+        assert!(auth.verify_token(&session.token).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_lockout_persists() {
+        let backend: Arc<dyn PersistenceBackend> = Arc::new(ConfigBackend::default());
+        let auth = AuthManager::new(test_config()).unwrap().with_backend(backend.clone());
+        auth.create_user("bob", "4821", SecretKind::Pin, Role::User).await.unwrap();
+
+        assert!(matches!(auth.login("bob", "1111").await, Err(AuthError::InvalidCredentials)));
+        assert!(matches!(auth.login("bob", "1111").await, Err(AuthError::InvalidCredentials)));
+        assert!(matches!(auth.login("bob", "1111").await, Err(AuthError::Locked { .. })));
+        // Locked even with the right PIN
+        assert!(matches!(auth.login("bob", "4821").await, Err(AuthError::Locked { .. })));
+
+        // A fresh manager sees the lockout and the hash, never the PIN
+        let reloaded = AuthManager::new(test_config()).unwrap().with_backend(backend.clone());
+        assert_eq!(reloaded.load().await.unwrap(), 1);
+        assert!(matches!(reloaded.login("bob", "4821").await, Err(AuthError::Locked { .. })));
+        let stored = backend.get_config(AUTH_COMPONENT, "user:bob").await.unwrap().unwrap();
+        assert!(!stored.config_value.to_string().contains("4821"));
+
+        reloaded.unlock("bob").await.unwrap();
+        assert!(reloaded.login("bob", "4821").await.is_ok());
+
+        reloaded.remove_user("bob").await.unwrap();
+        let fresh = AuthManager::new(test_config()).unwrap().with_backend(backend);
+        assert_eq!(fresh.load().await.unwrap(), 0);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_parallel_guesses_hit_lockout() {
+        let auth = Arc::new(AuthManager::new(test_config()).unwrap());
+        auth.create_user("eve", "4821", SecretKind::Pin, Role::User).await.unwrap();
+
+        let guesses: Vec<_> = (0..6)
+            .map(|i| {
+                let auth = auth.clone();
+                tokio::spawn(async move { auth.login("eve", &format!("11{:02}", i)).await })
+            })
+            .collect();
+        for guess in guesses {
+            assert!(guess.await.unwrap().is_err());
+        }
+
+        // Every guess counted, so even the right PIN is refused now
+        assert!(matches!(auth.login("eve", "4821").await, Err(AuthError::Locked { .. })));
+    }
+}
@@ -0,0 +1,518 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Background Runtime v1.0 - core lifetime independent of the UI window
+//!
+//! A front-end (desktop window, tray icon, web UI) attaches to a
+//! [`BackgroundRuntime`] instead of owning the core:
+//!
+//! - closing the window only detaches it (`window_closed`); with
+//!   `keep_running_on_close` the runtime keeps running (autonomous
+//!   exploration continues), otherwise it quits
+//! - tray menu actions (`TrayAction`) are executed by the core: show the
+//!   window, pause/resume learning, write a checkpoint, quit
+//! - front-ends follow [`BackgroundEvent`]s and the process owner waits on
+//!   `wait_for_quit` before shutting down
+//!
+//! Pausing learning flips the runtime's [`LearningGate`]. Hand it to the
+//! instance's `SafeMode` (`SafeMode::with_gate`): feedback is then rejected
+//! with `FeedbackError::LearningPaused` and the autonomous explorer skips
+//! its cycles until learning is resumed.
+
+use crate::checkpoint::{CheckpointError, CheckpointManager};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::{broadcast, watch};
+use tracing::{info, warn};
+
+/// Buffered events per subscriber
+const EVENT_BUFFER: usize = 64;
+
+// ============================================================================
+// Learning Gate
+// ============================================================================
+
+/// Pause switch for learning
+#[derive(Debug, Default)]
+pub struct LearningGate {
+    paused: AtomicBool,
+    /// Unix ms of the last pause (0 when running)
+    paused_at: AtomicU64,
+}
+
+impl LearningGate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `false` if learning was already paused
+    pub fn pause(&self) -> bool {
+        let changed = !self.paused.swap(true, Ordering::SeqCst);
+        if changed {
+            self.paused_at.store(now_ms(), Ordering::SeqCst);
+            info!("Learning paused");
+        }
+        changed
+    }
+
+    /// Returns `false` if learning was not paused
+    pub fn resume(&self) -> bool {
+        let changed = self.paused.swap(false, Ordering::SeqCst);
+        if changed {
+            self.paused_at.store(0, Ordering::SeqCst);
+            info!("Learning resumed");
+        }
+        changed
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    /// Unix ms since which learning is paused
+    pub fn paused_since(&self) -> Option<u64> {
+        match self.paused_at.load(Ordering::SeqCst) {
+            0 => None,
+            t => Some(t),
+        }
+    }
+}
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Background mode configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackgroundConfig {
+    /// Keep the runtime alive when the window is closed
+    pub keep_running_on_close: bool,
+
+    /// Write a checkpoint before quitting (requires a checkpoint manager)
+    pub checkpoint_on_quit: bool,
+}
+
+impl Default for BackgroundConfig {
+    fn default() -> Self {
+        Self {
+            keep_running_on_close: true,
+            checkpoint_on_quit: true,
+        }
+    }
+}
+
+// ============================================================================
+// Actions and Events
+// ============================================================================
+
+/// Tray menu action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayAction {
+    Open,
+    PauseLearning,
+    ResumeLearning,
+    Checkpoint,
+    Quit,
+}
+
+impl TrayAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TrayAction::Open => "open",
+            TrayAction::PauseLearning => "pause_learning",
+            TrayAction::ResumeLearning => "resume_learning",
+            TrayAction::Checkpoint => "checkpoint",
+            TrayAction::Quit => "quit",
+        }
+    }
+}
+
+impl fmt::Display for TrayAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TrayAction {
+    type Err = BackgroundError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('-', "_").as_str() {
+            "open" => Ok(TrayAction::Open),
+            "pause_learning" | "pause" => Ok(TrayAction::PauseLearning),
+            "resume_learning" | "resume" => Ok(TrayAction::ResumeLearning),
+            "checkpoint" => Ok(TrayAction::Checkpoint),
+            "quit" => Ok(TrayAction::Quit),
+            other => Err(BackgroundError::UnknownAction(other.to_string())),
+        }
+    }
+}
+
+/// One entry of the tray menu
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrayMenuItem {
+    pub action: TrayAction,
+    pub label: String,
+    pub enabled: bool,
+}
+
+/// Notification for attached front-ends
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum BackgroundEvent {
+    /// The window should be shown (tray "open")
+    ShowWindow,
+    /// The window was closed; runtime continues in the background
+    Backgrounded,
+    LearningPaused,
+    LearningResumed,
+    CheckpointCreated { id: String },
+    /// The runtime is shutting down
+    Quit,
+}
+
+/// Result of a tray action
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrayActionOutcome {
+    pub action: TrayAction,
+    /// Whether the action changed anything
+    pub changed: bool,
+    /// Checkpoint written by `Checkpoint` (or by `Quit` with checkpoint_on_quit)
+    pub checkpoint_id: Option<String>,
+}
+
+/// Snapshot for status displays
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackgroundStatus {
+    pub window_open: bool,
+    pub keep_running_on_close: bool,
+    pub learning_paused: bool,
+    pub learning_paused_since: Option<u64>,
+    pub quitting: bool,
+    pub last_checkpoint: Option<String>,
+    pub menu: Vec<TrayMenuItem>,
+}
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+#[derive(Debug)]
+pub enum BackgroundError {
+    UnknownAction(String),
+    /// No checkpoint manager attached
+    CheckpointUnavailable,
+    Checkpoint(CheckpointError),
+    /// Quit already requested
+    ShuttingDown,
+}
+
+impl fmt::Display for BackgroundError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackgroundError::UnknownAction(action) => write!(
+                f,
+                "Unknown tray action '{}' (expected open, pause_learning, resume_learning, checkpoint or quit)",
+                action
+            ),
+            BackgroundError::CheckpointUnavailable => write!(f, "Checkpoint manager is not attached"),
+            BackgroundError::Checkpoint(e) => write!(f, "Checkpoint failed: {}", e),
+            BackgroundError::ShuttingDown => write!(f, "Runtime is shutting down"),
+        }
+    }
+}
+
+impl std::error::Error for BackgroundError {}
+
+impl From<CheckpointError> for BackgroundError {
+    fn from(e: CheckpointError) -> Self {
+        BackgroundError::Checkpoint(e)
+    }
+}
+
+// ============================================================================
+// Background Runtime
+// ============================================================================
+
+/// Core-side state of background mode and tray actions
+pub struct BackgroundRuntime {
+    config: BackgroundConfig,
+    gate: Arc<LearningGate>,
+    checkpoint: Option<Arc<CheckpointManager>>,
+    window_open: AtomicBool,
+    last_checkpoint: RwLock<Option<String>>,
+    events: broadcast::Sender<BackgroundEvent>,
+    quit: watch::Sender<bool>,
+}
+
+impl BackgroundRuntime {
+    pub fn new(config: BackgroundConfig) -> Self {
+        Self::with_gate(config, Arc::default())
+    }
+
+    /// Runtime driving an existing learning gate
+    pub fn with_gate(config: BackgroundConfig, gate: Arc<LearningGate>) -> Self {
+        let (events, _) = broadcast::channel(EVENT_BUFFER);
+        let (quit, _) = watch::channel(false);
+        Self {
+            config,
+            gate,
+            checkpoint: None,
+            window_open: AtomicBool::new(true),
+            last_checkpoint: RwLock::new(None),
+            events,
+            quit,
+        }
+    }
+
+    /// Attach checkpoint manager (enables the Checkpoint action)
+    pub fn with_checkpoint(mut self, checkpoint: Arc<CheckpointManager>) -> Self {
+        self.checkpoint = Some(checkpoint);
+        self
+    }
+
+    pub fn config(&self) -> &BackgroundConfig {
+        &self.config
+    }
+
+    /// Learning gate flipped by PauseLearning/ResumeLearning
+    pub fn gate(&self) -> &Arc<LearningGate> {
+        &self.gate
+    }
+
+    /// Follow events (show window, quit, ...)
+    pub fn subscribe(&self) -> broadcast::Receiver<BackgroundEvent> {
+        self.events.subscribe()
+    }
+
+    /// Resolves once quit has been requested
+    pub async fn wait_for_quit(&self) {
+        let mut quit = self.quit.subscribe();
+        let _ = quit.wait_for(|quit| *quit).await;
+    }
+
+    pub fn is_quitting(&self) -> bool {
+        *self.quit.borrow()
+    }
+
+    pub fn window_opened(&self) {
+        self.window_open.store(true, Ordering::SeqCst);
+    }
+
+    /// The window was closed by the user
+    ///
+    /// Returns `true` if the runtime keeps running in the background;
+    /// otherwise quit is requested and the caller should exit.
+    pub async fn window_closed(&self) -> Result<bool, BackgroundError> {
+        self.window_open.store(false, Ordering::SeqCst);
+        if self.config.keep_running_on_close {
+            info!("Window closed, runtime continues in background");
+            self.emit(BackgroundEvent::Backgrounded);
+            Ok(true)
+        } else {
+            self.perform(TrayAction::Quit).await?;
+            Ok(false)
+        }
+    }
+
+    /// Execute a tray menu action
+    pub async fn perform(&self, action: TrayAction) -> Result<TrayActionOutcome, BackgroundError> {
+        if self.is_quitting() {
+            return Err(BackgroundError::ShuttingDown);
+        }
+
+        let mut outcome = TrayActionOutcome {
+            action,
+            changed: true,
+            checkpoint_id: None,
+        };
+        match action {
+            TrayAction::Open => {
+                self.window_opened();
+                self.emit(BackgroundEvent::ShowWindow);
+            }
+            TrayAction::PauseLearning => {
+                outcome.changed = self.gate.pause();
+                if outcome.changed {
+                    self.emit(BackgroundEvent::LearningPaused);
+                }
+            }
+            TrayAction::ResumeLearning => {
+                outcome.changed = self.gate.resume();
+                if outcome.changed {
+                    self.emit(BackgroundEvent::LearningResumed);
+                }
+            }
+            TrayAction::Checkpoint => {
+                outcome.checkpoint_id = Some(self.write_checkpoint("tray").await?);
+            }
+            TrayAction::Quit => {
+                if self.config.checkpoint_on_quit && self.checkpoint.is_some() {
+                    // A failed final checkpoint must not keep the process alive
+                    match self.write_checkpoint("quit").await {
+                        Ok(id) => outcome.checkpoint_id = Some(id),
+                        Err(e) => warn!(error = %e, "Checkpoint on quit failed"),
+                    }
+                }
+                info!("Quit requested");
+                self.quit.send_replace(true);
+                self.emit(BackgroundEvent::Quit);
+            }
+        }
+        Ok(outcome)
+    }
+
+    /// Tray menu reflecting the current state
+    pub fn menu(&self) -> Vec<TrayMenuItem> {
+        let paused = self.gate.is_paused();
+        let learning = if paused { TrayAction::ResumeLearning } else { TrayAction::PauseLearning };
+        [
+            (TrayAction::Open, true),
+            (learning, true),
+            (TrayAction::Checkpoint, self.checkpoint.is_some()),
+            (TrayAction::Quit, true),
+        ]
+        .into_iter()
+        .map(|(action, enabled)| TrayMenuItem {
+            action,
+            label: crate::i18n::tr(&format!("tray.{}", action)),
+            enabled,
+        })
+        .collect()
+    }
+
+    pub fn status(&self) -> BackgroundStatus {
+        BackgroundStatus {
+            window_open: self.window_open.load(Ordering::SeqCst),
+            keep_running_on_close: self.config.keep_running_on_close,
+            learning_paused: self.gate.is_paused(),
+            learning_paused_since: self.gate.paused_since(),
+            quitting: self.is_quitting(),
+            last_checkpoint: self.last_checkpoint.read().clone(),
+            menu: self.menu(),
+        }
+    }
+
+    async fn write_checkpoint(&self, label: &str) -> Result<String, BackgroundError> {
+        let manager = self.checkpoint.as_ref().ok_or(BackgroundError::CheckpointUnavailable)?;
+        let manifest = manager.checkpoint(Some(label)).await?;
+        *self.last_checkpoint.write() = Some(manifest.id.clone());
+        self.emit(BackgroundEvent::CheckpointCreated { id: manifest.id.clone() });
+        Ok(manifest.id)
+    }
+
+    fn emit(&self, event: BackgroundEvent) {
+        // No subscribers is fine: the tray may not be attached
+        let _ = self.events.send(event);
+    }
+}
+
+impl Default for BackgroundRuntime {
+    fn default() -> Self {
+        Self::new(BackgroundConfig::default())
+    }
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkpoint::CheckpointConfig;
+
+    fn runtime(config: BackgroundConfig) -> BackgroundRuntime {
+        BackgroundRuntime::new(config)
+    }
+
+    #[tokio::test]
+    async fn test_pause_resume_learning() {
+        let runtime = runtime(BackgroundConfig::default());
+        let mut events = runtime.subscribe();
+
+        assert_eq!(runtime.menu()[1].action, TrayAction::PauseLearning);
+        let outcome = runtime.perform("pause".parse().unwrap()).await.unwrap();
+        assert!(outcome.changed);
+        assert!(runtime.status().learning_paused);
+        assert!(runtime.status().learning_paused_since.is_some());
+        assert_eq!(runtime.menu()[1].action, TrayAction::ResumeLearning);
+        assert!(!runtime.perform(TrayAction::PauseLearning).await.unwrap().changed);
+
+        runtime.perform(TrayAction::ResumeLearning).await.unwrap();
+        assert!(!runtime.status().learning_paused);
+        assert_eq!(events.try_recv().unwrap(), BackgroundEvent::LearningPaused);
+        assert_eq!(events.try_recv().unwrap(), BackgroundEvent::LearningResumed);
+        assert!("reboot".parse::<TrayAction>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_window_close_keeps_running() {
+        let runtime = runtime(BackgroundConfig::default());
+        let mut events = runtime.subscribe();
+
+        assert!(runtime.window_closed().await.unwrap());
+        assert!(!runtime.status().window_open);
+        assert!(!runtime.is_quitting());
+
+        runtime.perform(TrayAction::Open).await.unwrap();
+        assert!(runtime.status().window_open);
+        assert_eq!(events.try_recv().unwrap(), BackgroundEvent::Backgrounded);
+        assert_eq!(events.try_recv().unwrap(), BackgroundEvent::ShowWindow);
+
+        // Checkpoint is disabled without a manager
+        assert!(!runtime.menu()[2].enabled);
+        assert!(matches!(
+            runtime.perform(TrayAction::Checkpoint).await,
+            Err(BackgroundError::CheckpointUnavailable)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_quit_with_checkpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = Arc::new(CheckpointManager::new(CheckpointConfig {
+            root: dir.path().to_path_buf(),
+            ..Default::default()
+        }));
+        let runtime = runtime(BackgroundConfig {
+            keep_running_on_close: false,
+            checkpoint_on_quit: true,
+        })
+        .with_checkpoint(manager.clone());
+
+        let outcome = runtime.perform(TrayAction::Checkpoint).await.unwrap();
+        assert_eq!(runtime.status().last_checkpoint, outcome.checkpoint_id);
+
+        assert!(!runtime.window_closed().await.unwrap());
+        runtime.wait_for_quit().await;
+        assert!(runtime.is_quitting());
+        assert_eq!(manager.list().unwrap().len(), 2);
+        assert!(matches!(
+            runtime.perform(TrayAction::Open).await,
+            Err(BackgroundError::ShuttingDown)
+        ));
+    }
+}
@@ -87,6 +87,7 @@ async fn main() {
         max_proposals_per_sec: 5,
         min_confidence_threshold: 0.6,
         strict_validation: true,
+        ..Default::default()
     };
 
     let evolution_manager = EvolutionManager::new(
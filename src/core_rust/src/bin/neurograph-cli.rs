@@ -0,0 +1,484 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! NeuroGraph CLI
+//!
+//! Command-line client for a running NeuroGraph daemon (REST API v1).
+//!
+//! ```text
+//! neurograph-cli status
+//! neurograph-cli query "what is a cat?"
+//! neurograph-cli feedback 42 positive 0.8
+//! neurograph-cli checkpoint before-upgrade
+//! neurograph-cli adna set curiosity.weight 0.4
+//! neurograph-cli graph neighbors cat --limit 5
+//! neurograph-cli ngql "ANALOGY king:queen::man:?"
+//! neurograph-cli exec "graph path cat dog"
+//! neurograph-cli ingest notes.md corpus.csv
+//! ```
+//!
+//! `exec` runs a line through the daemon's terminal interpreter (the same
+//! commands as the desktop Terminal); `complete` prints its suggestions.
+//!
+//! Server and key come from `--url`/`--api-key` or the `NEUROGRAPH_URL` /
+//! `NEUROGRAPH_API_KEY` environment variables. `--json` prints raw responses
+//! for scripting. Exit code is 0 on success, 1 on request errors, 2 on usage errors.
+
+use _core::api::models::{
+    CheckpointListResponse, CheckpointRequest, CheckpointRestoreRequest, CompletionResponse,
+    FeedbackRequest, FeedbackResponse, FeedbackType, IngestRequest, IngestResponse,
+    NeighborsResponse, NgqlRequest, QueryRequest, QueryResponse, StatusResponse, TerminalRequest,
+};
+use _core::ngql::NgqlResult;
+use _core::terminal::CommandOutput;
+use _core::checkpoint::CheckpointManifest;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::process::ExitCode;
+
+const DEFAULT_URL: &str = "http://127.0.0.1:3000";
+
+const USAGE: &str = "\
+Usage: neurograph-cli [--url URL] [--api-key KEY] [--json] <command>
+
+Commands:
+  status                              Daemon status
+  query <text> [--timeout-ms N]       Send a query
+  feedback <signal_id> positive|negative [strength]
+  feedback <signal_id> correct <value>
+  checkpoint [label]                  Create a checkpoint
+  checkpoint list                     List checkpoints
+  checkpoint restore [id]             Restore a checkpoint (newest if no id)
+  adna show                           Show appraiser configuration
+  adna set <section.field> <value>    Update one ADNA parameter
+  graph neighbors <word> [--limit N]  Direct neighbors of a concept
+  ngql <query>                        Graph query, e.g. NEIGHBORS cat TYPE causal DEPTH 2
+  exec <line>                         Run a terminal command (try 'exec help')
+  complete <line>                     Completions for a partial terminal line
+  ingest <file>...                    Ingest .txt/.md/.csv documents
+
+Environment: NEUROGRAPH_URL, NEUROGRAPH_API_KEY";
+
+// ============================================================================
+// Arguments
+// ============================================================================
+
+/// CLI error; `Usage` errors print the help text
+enum CliError {
+    Usage(String),
+    Request(String),
+}
+
+type CliResult<T> = Result<T, CliError>;
+
+struct Options {
+    url: String,
+    api_key: Option<String>,
+    json: bool,
+    command: Vec<String>,
+}
+
+impl Options {
+    fn parse(args: impl Iterator<Item = String>) -> CliResult<Self> {
+        let mut options = Options {
+            url: std::env::var("NEUROGRAPH_URL").unwrap_or_else(|_| DEFAULT_URL.to_string()),
+            api_key: std::env::var("NEUROGRAPH_API_KEY").ok(),
+            json: false,
+            command: Vec::new(),
+        };
+
+        let mut args = args.peekable();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--url" => options.url = required_value(&mut args, "--url")?,
+                "--api-key" => options.api_key = Some(required_value(&mut args, "--api-key")?),
+                "--json" => options.json = true,
+                "-h" | "--help" => return Err(CliError::Usage(String::new())),
+                _ => options.command.push(arg),
+            }
+        }
+
+        options.url = options.url.trim_end_matches('/').to_string();
+        Ok(options)
+    }
+}
+
+fn required_value(args: &mut impl Iterator<Item = String>, flag: &str) -> CliResult<String> {
+    args.next()
+        .ok_or_else(|| CliError::Usage(format!("{} requires a value", flag)))
+}
+
+/// Remove `--flag N` from `args`, parsing N
+fn take_flag<T: std::str::FromStr>(args: &mut Vec<String>, flag: &str) -> CliResult<Option<T>> {
+    let Some(pos) = args.iter().position(|a| a == flag) else {
+        return Ok(None);
+    };
+    if pos + 1 >= args.len() {
+        return Err(CliError::Usage(format!("{} requires a value", flag)));
+    }
+    let value = args.remove(pos + 1);
+    args.remove(pos);
+    value
+        .parse()
+        .map(Some)
+        .map_err(|_| CliError::Usage(format!("Invalid value for {}: '{}'", flag, value)))
+}
+
+fn parse_arg<T: std::str::FromStr>(value: &str, name: &str) -> CliResult<T> {
+    value
+        .parse()
+        .map_err(|_| CliError::Usage(format!("Invalid {}: '{}'", name, value)))
+}
+
+/// Parse an ADNA value: JSON literal if possible, otherwise a string
+fn parse_adna_value(raw: &str) -> Value {
+    serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()))
+}
+
+/// Build a nested JSON patch from a dotted path (`curiosity.weight` → `{"curiosity":{"weight":v}}`)
+fn adna_patch(path: &str, value: Value) -> CliResult<Value> {
+    if path.split('.').any(str::is_empty) {
+        return Err(CliError::Usage(format!("Invalid ADNA field '{}'", path)));
+    }
+    Ok(path
+        .rsplit('.')
+        .fold(value, |acc, key| serde_json::json!({ key: acc })))
+}
+
+// ============================================================================
+// HTTP client
+// ============================================================================
+
+struct Client {
+    http: reqwest::Client,
+    base: String,
+    api_key: Option<String>,
+}
+
+impl Client {
+    fn new(options: &Options) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base: format!("{}/api/v1", options.url),
+            api_key: options.api_key.clone(),
+        }
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str, query: &[(&str, String)]) -> CliResult<T> {
+        let request = self.http.get(format!("{}{}", self.base, path)).query(query);
+        self.send(request).await
+    }
+
+    async fn post<B: Serialize, T: DeserializeOwned>(&self, path: &str, body: &B) -> CliResult<T> {
+        let request = self.http.post(format!("{}{}", self.base, path)).json(body);
+        self.send(request).await
+    }
+
+    async fn send<T: DeserializeOwned>(&self, mut request: reqwest::RequestBuilder) -> CliResult<T> {
+        if let Some(key) = &self.api_key {
+            request = request.header("X-API-Key", key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| CliError::Request(format!("Cannot reach daemon: {}", e)))?;
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .map_err(|e| CliError::Request(e.to_string()))?;
+
+        if !status.is_success() {
+            // Error bodies are ErrorResponse {error, message}
+            let message = serde_json::from_str::<Value>(&body)
+                .ok()
+                .and_then(|v| v.get("message").and_then(Value::as_str).map(str::to_string))
+                .unwrap_or(body);
+            return Err(CliError::Request(format!("{}: {}", status, message)));
+        }
+
+        serde_json::from_str(&body)
+            .map_err(|e| CliError::Request(format!("Unexpected response: {}", e)))
+    }
+}
+
+// ============================================================================
+// Commands
+// ============================================================================
+
+fn print_json<T: Serialize>(value: &T) -> CliResult<()> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| CliError::Request(e.to_string()))?;
+    println!("{}", json);
+    Ok(())
+}
+
+fn print_manifest(manifest: &CheckpointManifest) {
+    println!(
+        "{}  {}  {} sections{}",
+        manifest.id,
+        manifest.created_at,
+        manifest.sections.len(),
+        manifest.label.as_deref().map(|l| format!("  ({})", l)).unwrap_or_default(),
+    );
+}
+
+async fn run(options: Options) -> CliResult<()> {
+    let client = Client::new(&options);
+    let mut args = options.command.clone();
+    if args.is_empty() {
+        return Err(CliError::Usage("Missing command".to_string()));
+    }
+    let command = args.remove(0);
+
+    match (command.as_str(), args.first().map(String::as_str)) {
+        ("status", _) => {
+            let status: StatusResponse = client.get("/status", &[]).await?;
+            if options.json {
+                return print_json(&status);
+            }
+            println!("version:          {}", status.version);
+            println!("uptime:           {}s", status.uptime_seconds);
+            println!("pending requests: {}", status.gateway.pending_requests);
+            println!("total signals:    {}", status.gateway.total_signals);
+            println!("unknown words:    {}", status.gateway.unknown_words);
+            println!("success rate:     {:.1}%", status.gateway.success_rate * 100.0);
+            if let Some(curiosity) = status.curiosity {
+                println!(
+                    "curiosity:        {} (queue {})",
+                    if curiosity.autonomous_enabled { "autonomous" } else { "passive" },
+                    curiosity.queue_size,
+                );
+            }
+        }
+
+        ("query", _) => {
+            let timeout_ms = take_flag(&mut args, "--timeout-ms")?;
+            if args.is_empty() {
+                return Err(CliError::Usage("query requires text".to_string()));
+            }
+            let request = QueryRequest { query: args.join(" "), context: HashMap::new(), timeout_ms };
+            let response: QueryResponse = client.post("/query", &request).await?;
+            if options.json {
+                return print_json(&response);
+            }
+            println!("{}", response.response.as_deref().unwrap_or("(no response)"));
+            eprintln!(
+                "signal {} · {} · {}µs",
+                response.signal_id, response.signal_type, response.metadata.processing_time_us
+            );
+        }
+
+        ("feedback", _) => {
+            let (signal_id, kind) = match args.as_slice() {
+                [id, kind, ..] => (parse_arg::<u64>(id, "signal_id")?, kind.as_str()),
+                _ => return Err(CliError::Usage("feedback requires <signal_id> <type>".to_string())),
+            };
+            let strength = || -> CliResult<f32> {
+                args.get(2).map_or(Ok(1.0), |s| parse_arg(s, "strength"))
+            };
+            let feedback = match kind {
+                "positive" => FeedbackType::Positive { strength: strength()? },
+                "negative" => FeedbackType::Negative { strength: strength()? },
+                "correct" | "correction" if args.len() > 2 => {
+                    FeedbackType::Correction { correct_value: args[2..].join(" ") }
+                }
+                "correct" | "correction" => {
+                    return Err(CliError::Usage("correct requires a value".to_string()))
+                }
+                other => return Err(CliError::Usage(format!("Unknown feedback type '{}'", other))),
+            };
+            let request = FeedbackRequest { signal_id, feedback, explanation: None };
+            let response: FeedbackResponse = client.post("/feedback", &request).await?;
+            if options.json {
+                return print_json(&response);
+            }
+            for change in &response.changes_made {
+                println!("{}", change);
+            }
+            for error in &response.errors {
+                eprintln!("error: {}", error);
+            }
+            if !response.success {
+                return Err(CliError::Request("Feedback was not applied".to_string()));
+            }
+        }
+
+        ("checkpoint", Some("list")) => {
+            let list: CheckpointListResponse = client.get("/checkpoints", &[]).await?;
+            if options.json {
+                return print_json(&list);
+            }
+            list.checkpoints.iter().for_each(print_manifest);
+        }
+
+        ("checkpoint", Some("restore")) => {
+            let request = CheckpointRestoreRequest { id: args.get(1).cloned() };
+            let manifest: CheckpointManifest = client.post("/checkpoint/restore", &request).await?;
+            if options.json {
+                return print_json(&manifest);
+            }
+            print!("restored ");
+            print_manifest(&manifest);
+        }
+
+        ("checkpoint", _) => {
+            let label = (!args.is_empty()).then(|| args.join(" "));
+            let manifest: CheckpointManifest =
+                client.post("/checkpoint", &CheckpointRequest { label }).await?;
+            if options.json {
+                return print_json(&manifest);
+            }
+            print_manifest(&manifest);
+        }
+
+        ("adna", Some("show")) => {
+            let config: Value = client.get("/adna", &[]).await?;
+            print_json(&config)?;
+        }
+
+        ("adna", Some("set")) => {
+            let (path, raw) = match args.as_slice() {
+                [_, path, raw] => (path, raw),
+                _ => return Err(CliError::Usage("adna set requires <section.field> <value>".to_string())),
+            };
+            let patch = adna_patch(path, parse_adna_value(raw))?;
+            let config: Value = client.post("/adna", &patch).await?;
+            if options.json {
+                return print_json(&config);
+            }
+            let new_value = path.split('.').try_fold(&config, |v, key| v.get(key));
+            println!("{} = {}", path, new_value.unwrap_or(&Value::Null));
+        }
+
+        ("graph", Some("neighbors")) => {
+            let limit: Option<usize> = take_flag(&mut args, "--limit")?;
+            let word = args
+                .get(1)
+                .ok_or_else(|| CliError::Usage("graph neighbors requires <word>".to_string()))?;
+            let mut query = vec![("word", word.clone())];
+            if let Some(limit) = limit {
+                query.push(("limit", limit.to_string()));
+            }
+            let response: NeighborsResponse = client.get("/graph/neighbors", &query).await?;
+            if options.json {
+                return print_json(&response);
+            }
+            for neighbor in &response.neighbors {
+                let label = neighbor.word.clone().unwrap_or_else(|| format!("#{}", neighbor.node_id));
+                println!("{:<24} {:>7.3}  {}", label, neighbor.weight, neighbor.edge_type);
+            }
+        }
+
+        ("ngql", _) => {
+            if args.is_empty() {
+                return Err(CliError::Usage("ngql requires a query".to_string()));
+            }
+            let request = NgqlRequest { query: args.join(" ") };
+            let result: NgqlResult = client.post("/query/ngql", &request).await?;
+            if options.json {
+                return print_json(&result);
+            }
+            if !result.is_empty() {
+                println!("{}", result);
+            }
+        }
+
+        ("exec", _) => {
+            if args.is_empty() {
+                return Err(CliError::Usage("exec requires a command line".to_string()));
+            }
+            let request = TerminalRequest { line: args.join(" ") };
+            let output: CommandOutput = client.post("/terminal/execute", &request).await?;
+            if options.json {
+                return print_json(&output.data.unwrap_or(Value::Null));
+            }
+            if !output.text.is_empty() {
+                println!("{}", output.text);
+            }
+        }
+
+        ("complete", _) => {
+            let response: CompletionResponse =
+                client.get("/terminal/complete", &[("line", args.join(" "))]).await?;
+            if options.json {
+                return print_json(&response);
+            }
+            for completion in &response.completions {
+                println!("{:<24} {}", completion.value, completion.description);
+            }
+        }
+
+        ("ingest", _) => {
+            if args.is_empty() {
+                return Err(CliError::Usage("ingest requires at least one file".to_string()));
+            }
+            for path in &args {
+                let content = std::fs::read_to_string(path)
+                    .map_err(|e| CliError::Request(format!("{}: {}", path, e)))?;
+                let filename = std::path::Path::new(path)
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| path.clone());
+                let response: IngestResponse =
+                    client.post("/ingest", &IngestRequest { filename, content }).await?;
+                if options.json {
+                    print_json(&response)?;
+                    continue;
+                }
+                let report = &response.report;
+                println!(
+                    "{}: {} sentences, {} concepts, {} new / {} reinforced connections ({} ms)",
+                    response.document,
+                    report.sentences,
+                    report.concepts,
+                    report.new_connections,
+                    report.reinforced_connections,
+                    report.duration_ms
+                );
+            }
+        }
+
+        (command, _) => return Err(CliError::Usage(format!("Unknown command '{}'", command))),
+    }
+
+    Ok(())
+}
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> ExitCode {
+    let result = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => run(options).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(CliError::Usage(message)) => {
+            if !message.is_empty() {
+                eprintln!("error: {}\n", message);
+            }
+            eprintln!("{}", USAGE);
+            ExitCode::from(2)
+        }
+        Err(CliError::Request(message)) => {
+            eprintln!("error: {}", message);
+            ExitCode::from(1)
+        }
+    }
+}
@@ -24,7 +24,8 @@
 /// - **Circular Buffer**: Fixed-size ring buffer (default: 1000 events)
 /// - **Thread-Safe**: Arc<Mutex<>> for multi-threaded access
 /// - **Automatic Dump**: Writes to disk on panic via panic hook
-/// - **JSON Format**: Human-readable event log
+/// - **JSON Format**: Human-readable event log, sealed when at-rest
+///   encryption is enabled (see `encryption`)
 ///
 /// # Event Types
 ///
@@ -49,6 +50,7 @@
 /// bb.dump_to_file("crash_dump.json").unwrap();
 /// ```
 
+use crate::encryption::{self, FileCipher};
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 use std::fs::File;
@@ -230,6 +232,8 @@ impl BlackBox {
 
     /// Dump events to JSON file
     ///
+    /// The file is sealed with the installed at-rest cipher, if any.
+    ///
     /// # Arguments
     ///
     /// * `path` - File path to write dump
@@ -248,6 +252,15 @@ impl BlackBox {
     /// bb.dump_to_file("crash_dump.json").unwrap();
     /// ```
     pub fn dump_to_file<P: AsRef<Path>>(&self, path: P) -> Result<usize, std::io::Error> {
+        self.dump_to_file_with(path, encryption::installed_cipher().as_deref())
+    }
+
+    /// [`dump_to_file`](Self::dump_to_file) with an explicit cipher
+    pub fn dump_to_file_with<P: AsRef<Path>>(
+        &self,
+        path: P,
+        cipher: Option<&FileCipher>,
+    ) -> Result<usize, std::io::Error> {
         let events = self.get_events();
         let stats = self.stats();
 
@@ -260,10 +273,11 @@ impl BlackBox {
             events,
         };
 
-        let json = serde_json::to_string_pretty(&dump)?;
+        let json = serde_json::to_vec_pretty(&dump)?;
+        let data = encryption::seal_file(cipher, json, path.as_ref()).map_err(std::io::Error::other)?;
 
         let mut file = File::create(path.as_ref())?;
-        file.write_all(json.as_bytes())?;
+        file.write_all(&data)?;
         file.sync_all()?;
 
         info!(
@@ -430,6 +444,23 @@ mod tests {
         std::fs::remove_file(temp_file).ok();
     }
 
+    #[test]
+    fn test_sealed_dump() {
+        let bb = BlackBox::new(10);
+        bb.record(Event::new(EventType::TokenCreated).with_data("id", "42"));
+
+        let cipher = FileCipher::new(&FileCipher::generate_key());
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("crash_dump.json");
+        assert_eq!(bb.dump_to_file_with(&path, Some(&cipher)).unwrap(), 1);
+
+        let sealed = std::fs::read(&path).unwrap();
+        assert!(FileCipher::is_sealed(&sealed));
+        let json = encryption::open_file(Some(&cipher), sealed, &path).unwrap();
+        let dump: BlackBoxDump = serde_json::from_slice(&json).unwrap();
+        assert_eq!(dump.events.len(), 1);
+    }
+
     #[test]
     fn test_clear() {
         let bb = BlackBox::new(10);
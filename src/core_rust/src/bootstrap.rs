@@ -28,22 +28,43 @@
 //!   * Sounds (30 volume/pitch/duration) [NEW v1.3]
 //!   * Actions (40 energy/speed/direction/impact) [NEW v1.3]
 //!   * Spatial relations (20 proximity/verticality/containment) [NEW v1.3]
+//!   * Tactile (temperature/hardness/roughness)
 //! - Semantic search via spreading activation [NEW v1.3]
 //! - Multi-query search with score combination [NEW v1.3]
 //! - Semantic analogy completion [NEW v1.3]
 //! - Connection weaving via Grid KNN
 //! - Artifact persistence (PCA model, bootstrap map)
-
-use crate::{Graph, Grid, NodeId};
+//! - Incremental concept insertion and remote embedding providers (`remote`)
+//! - ConceptNet/WordNet import of Immutable semantic relations (`relations`)
+//! - Phrase concepts from underscore-joined tokens (`phrases`)
+//! - Stop-word and frequency weights for normalization (`frequency`)
+//! - User lexicon files (TOML/CSV) for the multimodal anchors (`lexicon`)
+//! - Anchor projection into the 8D token space (`projection`)
+//! - Grounded concept queries by color, emotion and other anchors (`grounded`)
+
+use crate::{Graph, GraphConfig, Grid, NodeId};
+use crate::encryption::{self, FileCipher};
 use fasthash::murmur3::Hasher32;
 use fasthash::FastHasher;
 use ndarray::{Array1, Array2};
 use std::collections::HashMap;
 use std::hash::Hasher;
 use std::path::Path;
-use std::fs::File;
 use std::io::{Write, Read};
 
+pub mod analogy;
+pub mod frequency;
+pub mod grounded;
+pub mod lexicon;
+pub mod phrases;
+pub mod projection;
+pub mod ranking;
+pub mod relations;
+pub mod remote;
+
+/// Edge type for KNN-woven edges (kept distinct from imported Semantic relations)
+const ASSOCIATED_WITH: u8 = crate::ConnectionType::AssociatedWith as u8;
+
 // ============================================================================
 // Configuration
 // ============================================================================
@@ -71,6 +92,27 @@ pub struct BootstrapConfig {
 
     /// Seed for deterministic operations
     pub seed: u32,
+
+    /// Register underscore-joined tokens (`new_york`) as phrases
+    pub detect_phrases: bool,
+
+    /// Longest phrase registered by `detect_phrases`, in words
+    pub max_phrase_len: usize,
+
+    /// Weight concepts by their rank in the (frequency-sorted) embeddings file
+    pub rank_weights: bool,
+
+    /// Words weighted `stop_word_weight` (e.g. `frequency::ENGLISH_STOP_WORDS`)
+    pub stop_words: Vec<String>,
+
+    /// Weight of stop words
+    pub stop_word_weight: f32,
+
+    /// Lowest rank/frequency weight
+    pub min_weight: f32,
+
+    /// Multimodal anchors → 8D token coordinates (see `projection`)
+    pub projection: projection::ModalityProjection,
 }
 
 impl Default for BootstrapConfig {
@@ -83,6 +125,13 @@ impl Default for BootstrapConfig {
             knn_k: 5,
             connection_decay: 0.1,
             seed: 42,
+            detect_phrases: true,
+            max_phrase_len: 4,
+            rank_weights: false,
+            stop_words: Vec::new(),
+            stop_word_weight: 0.05,
+            min_weight: 0.1,
+            projection: projection::ModalityProjection::default(),
         }
     }
 }
@@ -112,6 +161,10 @@ pub struct SemanticConcept {
     pub sound: Option<[f32; 3]>,      // Volume, Pitch, Duration (NEW v1.3)
     pub action: Option<[f32; 4]>,     // Energy, Speed, Direction, Impact (NEW v1.3)
     pub spatial: Option<[f32; 3]>,    // Proximity, Verticality, Containment (NEW v1.3)
+    pub tactile: Option<[f32; 3]>,    // Temperature, Hardness, Roughness
+
+    /// Composition weight, 0.0-1.0 (stop words and frequent words lower)
+    pub weight: f32,
 }
 
 /// PCA model for dimensionality reduction
@@ -133,6 +186,23 @@ pub struct PCAModel {
     pub target_dim: usize,
 }
 
+impl PCAModel {
+    /// Project a single embedding to 3D: coords = (embedding - mean) @ components.T
+    pub fn project(&self, embedding: &Array1<f32>) -> [f32; 3] {
+        let centered = embedding - &self.mean;
+
+        let mut coords = [0.0f32; 3];
+        for i in 0..self.target_dim.min(3) {
+            let mut sum = 0.0f32;
+            for j in 0..self.original_dim {
+                sum += centered[j] * self.components[[i, j]];
+            }
+            coords[i] = sum;
+        }
+        coords
+    }
+}
+
 /// Main Bootstrap Library
 pub struct BootstrapLibrary {
     /// Configuration
@@ -149,6 +219,12 @@ pub struct BootstrapLibrary {
 
     /// Grid for spatial queries
     grid: Grid,
+
+    /// Multi-word phrases (word sequence → concept)
+    phrases: phrases::PhraseTable,
+
+    /// Multimodal anchor lexicons (built-in + loaded files)
+    lexicons: lexicon::LexiconSet,
 }
 
 // ============================================================================
@@ -162,8 +238,10 @@ impl BootstrapLibrary {
             config,
             concepts: HashMap::new(),
             pca_model: None,
-            graph: Graph::new(),
+            graph: Graph::with_config(GraphConfig { deduplicate_edges: true, ..Default::default() }),
             grid: Grid::new(),
+            phrases: phrases::PhraseTable::new(),
+            lexicons: lexicon::LexiconSet::builtin(),
         }
     }
 
@@ -196,6 +274,23 @@ impl BootstrapLibrary {
     pub fn concepts_iter(&self) -> impl Iterator<Item = (&String, &SemanticConcept)> {
         self.concepts.iter()
     }
+
+    /// Neighborhood of `center` labeled with concept words, for graph views
+    ///
+    /// `ViewLayout::Projected` uses the concepts' PCA coordinates.
+    pub fn subgraph_view(
+        &self,
+        center: NodeId,
+        depth: usize,
+        layout: crate::graph::export::ViewLayout,
+    ) -> Option<crate::graph::export::SubgraphView> {
+        let mut options = crate::graph::export::ViewOptions { layout, ..Default::default() };
+        for (word, concept) in &self.concepts {
+            options.labels.insert(concept.id, word.clone());
+            options.coords.insert(concept.id, concept.coords);
+        }
+        self.graph.subgraph_view(center, depth, &options)
+    }
 }
 
 // ============================================================================
@@ -242,6 +337,7 @@ impl BootstrapLibrary {
 
         let reader = BufReader::new(file);
         let mut loaded = 0;
+        let mut order = Vec::new();
 
         for (line_num, line) in reader.lines().enumerate() {
             let line = line.map_err(|e| BootstrapError::IoError(e.to_string()))?;
@@ -293,8 +389,14 @@ impl BootstrapLibrary {
                 sound: None,
                 action: None,
                 spatial: None,
+                tactile: None,
+                weight: self.initial_weight(&word),
             };
 
+            self.detect_phrase(&word);
+            if self.config.rank_weights {
+                order.push(word.clone());
+            }
             self.concepts.insert(word, concept);
             loaded += 1;
 
@@ -304,6 +406,10 @@ impl BootstrapLibrary {
             }
         }
 
+        if self.config.rank_weights {
+            self.apply_rank_weights(&order);
+        }
+
         Ok(loaded)
     }
 
@@ -400,20 +506,7 @@ impl BootstrapLibrary {
         let mut projected = 0;
 
         for concept in self.concepts.values_mut() {
-            // Center embedding
-            let centered = &concept.embedding - &pca_model.mean;
-
-            // Project: coords = centered @ components.T
-            let mut coords = [0.0f32; 3];
-            for i in 0..pca_model.target_dim {
-                let mut sum = 0.0f32;
-                for j in 0..pca_model.original_dim {
-                    sum += centered[j] * pca_model.components[[i, j]];
-                }
-                coords[i] = sum;
-            }
-
-            concept.coords = coords;
+            concept.coords = pca_model.project(&concept.embedding);
             projected += 1;
         }
 
@@ -459,13 +552,12 @@ impl BootstrapLibrary {
 
     /// Populate Grid with concept coordinates for spatial queries
     ///
-    /// Adds each concept's 3D coordinates to the Grid for KNN lookup
+    /// Adds each concept's 3D coordinates to the Grid for KNN lookup, plus
+    /// any anchors mapped by `BootstrapConfig::projection`
     ///
     /// # Returns
     /// Result with number of tokens added to grid
     pub fn populate_grid(&mut self) -> Result<usize, BootstrapError> {
-        use crate::Token;
-
         if self.concepts.is_empty() {
             return Err(BootstrapError::NoData("No concepts loaded".to_string()));
         }
@@ -477,16 +569,13 @@ impl BootstrapLibrary {
             ));
         }
 
+        self.config.projection.validate().map_err(BootstrapError::InvalidConfig)?;
+
         let mut added = 0;
 
         for concept in self.concepts.values() {
-            // Create token with concept's coordinates
-            let token = Token::from_state_f32(concept.id, &[
-                concept.coords[0],
-                concept.coords[1],
-                concept.coords[2],
-                0.0, 0.0, 0.0, 0.0, 0.0, // Fill remaining with zeros
-            ]);
+            // Create token with concept's coordinates and projected anchors
+            let token = self.concept_token(concept);
 
             if let Ok(_) = self.grid.add(token) {
                 added += 1;
@@ -513,6 +602,11 @@ impl BootstrapLibrary {
             ));
         }
 
+        Ok(self.weave_knn())
+    }
+
+    /// Create KNN edges for every concept already present in the Grid
+    fn weave_knn(&mut self) -> usize {
         let mut edges_created = 0;
         let k = self.config.knn_k;
         let decay = self.config.connection_decay;
@@ -539,23 +633,23 @@ impl BootstrapLibrary {
                 // Closer neighbors (smaller distance) get higher weight
                 let weight = 1.0 / (1.0 + distance * decay);
 
-                // Create bidirectional edge
-                let edge_id = crate::Graph::compute_edge_id(concept.id, neighbor_id, 0);
+                // Create edge; the reverse KNN edge is merged into it (bidirectional)
+                let edge_id = crate::Graph::compute_edge_id(concept.id, neighbor_id, ASSOCIATED_WITH);
 
-                if let Ok(_) = self.graph.add_edge(
+                if let Ok(true) = self.graph.add_edge(
                     edge_id,
                     concept.id,
                     neighbor_id,
-                    0, // layer
+                    ASSOCIATED_WITH,
                     weight,
-                    false, // not directed
+                    false,
                 ) {
                     edges_created += 1;
                 }
             }
         }
 
-        Ok(edges_created)
+        edges_created + self.weave_anchor_spaces()
     }
 
     /// Complete bootstrap pipeline: load → PCA → populate → weave
@@ -588,6 +682,88 @@ impl BootstrapLibrary {
     }
 }
 
+// ============================================================================
+// Incremental Concept API
+// ============================================================================
+
+impl BootstrapLibrary {
+    /// Get trained/loaded PCA model
+    pub fn pca_model(&self) -> Option<&PCAModel> {
+        self.pca_model.as_ref()
+    }
+
+    /// Add a single concept after bootstrap (e.g. an unknown word fetched remotely)
+    ///
+    /// Projects the embedding with the existing PCA model, adds the node to
+    /// Graph and Grid, and weaves KNN edges to its nearest neighbors.
+    /// Adding an already known word is a no-op returning its existing ID.
+    ///
+    /// # Returns
+    /// Result with the concept's NodeId
+    pub fn add_concept(&mut self, word: &str, embedding: Vec<f32>) -> Result<NodeId, BootstrapError> {
+        use crate::Token;
+
+        if let Some(existing) = self.concepts.get(word) {
+            return Ok(existing.id);
+        }
+
+        if embedding.len() != self.config.embedding_dim {
+            return Err(BootstrapError::DimensionMismatch {
+                expected: self.config.embedding_dim,
+                got: embedding.len(),
+            });
+        }
+
+        let pca_model = self.pca_model.as_ref()
+            .ok_or_else(|| BootstrapError::NoData("PCA model not trained".to_string()))?;
+
+        let embedding = Array1::from_vec(embedding);
+        let coords = pca_model.project(&embedding);
+        let id = Self::generate_id(word, self.config.seed);
+
+        // Graph + Grid
+        self.graph.add_node(id);
+        let token = Token::from_state_f32(id, &[
+            coords[0], coords[1], coords[2],
+            0.0, 0.0, 0.0, 0.0, 0.0,
+        ]);
+        let _ = self.grid.add(token);
+
+        // KNN edges (same weighting as weave_connections)
+        let neighbors = self.grid.find_neighbors(
+            id,
+            crate::CoordinateSpace::L1Physical,
+            100.0,
+            self.config.knn_k + 1,
+        );
+        for &(neighbor_id, distance) in &neighbors {
+            if neighbor_id == id {
+                continue;
+            }
+            let weight = 1.0 / (1.0 + distance * self.config.connection_decay);
+            let edge_id = crate::Graph::compute_edge_id(id, neighbor_id, ASSOCIATED_WITH);
+            let _ = self.graph.add_edge(edge_id, id, neighbor_id, ASSOCIATED_WITH, weight, false);
+        }
+
+        self.detect_phrase(word);
+        self.concepts.insert(word.to_string(), SemanticConcept {
+            id,
+            word: word.to_string(),
+            embedding,
+            coords,
+            color: None,
+            emotion: None,
+            sound: None,
+            action: None,
+            spatial: None,
+            tactile: None,
+            weight: self.initial_weight(word),
+        });
+
+        Ok(id)
+    }
+}
+
 // ============================================================================
 // Multimodal Anchors
 // ============================================================================
@@ -600,11 +776,10 @@ impl BootstrapLibrary {
     /// # Returns
     /// Number of concepts enriched with color
     pub fn add_color_anchors(&mut self) -> usize {
-        let color_map = Self::get_color_lexicon();
         let mut enriched = 0;
 
         for concept in self.concepts.values_mut() {
-            if let Some(&color) = color_map.get(concept.word.as_str()) {
+            if let Some(color) = self.lexicons.get_array(lexicon::Modality::Color, &concept.word) {
                 concept.color = Some(color);
                 enriched += 1;
             }
@@ -620,11 +795,10 @@ impl BootstrapLibrary {
     /// # Returns
     /// Number of concepts enriched with emotion
     pub fn add_emotion_anchors(&mut self) -> usize {
-        let emotion_map = Self::get_emotion_lexicon();
         let mut enriched = 0;
 
         for concept in self.concepts.values_mut() {
-            if let Some(&emotion) = emotion_map.get(concept.word.as_str()) {
+            if let Some(emotion) = self.lexicons.get_array(lexicon::Modality::Emotion, &concept.word) {
                 concept.emotion = Some(emotion);
                 enriched += 1;
             }
@@ -735,11 +909,10 @@ impl BootstrapLibrary {
     /// # Returns
     /// Number of concepts enriched with sound
     pub fn add_sound_anchors(&mut self) -> usize {
-        let sound_map = Self::get_sound_lexicon();
         let mut enriched = 0;
 
         for concept in self.concepts.values_mut() {
-            if let Some(&sound) = sound_map.get(concept.word.as_str()) {
+            if let Some(sound) = self.lexicons.get_array(lexicon::Modality::Sound, &concept.word) {
                 concept.sound = Some(sound);
                 enriched += 1;
             }
@@ -755,11 +928,10 @@ impl BootstrapLibrary {
     /// # Returns
     /// Number of concepts enriched with action
     pub fn add_action_anchors(&mut self) -> usize {
-        let action_map = Self::get_action_lexicon();
         let mut enriched = 0;
 
         for concept in self.concepts.values_mut() {
-            if let Some(&action) = action_map.get(concept.word.as_str()) {
+            if let Some(action) = self.lexicons.get_array(lexicon::Modality::Action, &concept.word) {
                 concept.action = Some(action);
                 enriched += 1;
             }
@@ -775,11 +947,10 @@ impl BootstrapLibrary {
     /// # Returns
     /// Number of concepts enriched with spatial relations
     pub fn add_spatial_anchors(&mut self) -> usize {
-        let spatial_map = Self::get_spatial_lexicon();
         let mut enriched = 0;
 
         for concept in self.concepts.values_mut() {
-            if let Some(&spatial) = spatial_map.get(concept.word.as_str()) {
+            if let Some(spatial) = self.lexicons.get_array(lexicon::Modality::Spatial, &concept.word) {
                 concept.spatial = Some(spatial);
                 enriched += 1;
             }
@@ -788,6 +959,25 @@ impl BootstrapLibrary {
         enriched
     }
 
+    /// Enrich concepts with tactile information
+    ///
+    /// Adds touch characteristics (temperature, hardness, roughness) to material and texture concepts
+    ///
+    /// # Returns
+    /// Number of concepts enriched with tactile anchors
+    pub fn add_tactile_anchors(&mut self) -> usize {
+        let mut enriched = 0;
+
+        for concept in self.concepts.values_mut() {
+            if let Some(tactile) = self.lexicons.get_array(lexicon::Modality::Tactile, &concept.word) {
+                concept.tactile = Some(tactile);
+                enriched += 1;
+            }
+        }
+
+        enriched
+    }
+
     /// Get sound lexicon mapping words to sound characteristics
     ///
     /// Returns HashMap of sound words to (volume, pitch, duration) values [-1.0 to 1.0]
@@ -952,6 +1142,52 @@ impl BootstrapLibrary {
         map
     }
 
+    /// Get tactile lexicon mapping words to touch characteristics
+    ///
+    /// Returns HashMap of tactile words to (temperature, hardness, roughness) values [-1.0 to 1.0]
+    /// - Temperature: cold to hot
+    /// - Hardness: soft to hard
+    /// - Roughness: smooth to rough
+    fn get_tactile_lexicon() -> HashMap<&'static str, [f32; 3]> {
+        let mut map = HashMap::new();
+
+        // Temperature
+        map.insert("hot", [0.9, 0.0, 0.0]);
+        map.insert("warm", [0.5, 0.0, 0.0]);
+        map.insert("cool", [-0.4, 0.0, 0.0]);
+        map.insert("cold", [-0.9, 0.0, 0.0]);
+        map.insert("ice", [-1.0, 0.8, -0.7]);
+        map.insert("snow", [-0.9, -0.6, 0.1]);
+        map.insert("flame", [1.0, -0.8, 0.0]);
+        map.insert("steam", [0.8, -0.9, -0.5]);
+
+        // Hardness
+        map.insert("hard", [0.0, 0.9, 0.0]);
+        map.insert("soft", [0.0, -0.9, -0.3]);
+        map.insert("stone", [-0.2, 0.9, 0.5]);
+        map.insert("rock", [-0.2, 0.9, 0.6]);
+        map.insert("steel", [-0.3, 1.0, -0.6]);
+        map.insert("metal", [-0.3, 0.9, -0.4]);
+        map.insert("wood", [0.1, 0.6, 0.4]);
+        map.insert("pillow", [0.2, -0.9, -0.5]);
+        map.insert("cotton", [0.2, -0.8, -0.3]);
+        map.insert("wool", [0.5, -0.7, 0.4]);
+
+        // Texture
+        map.insert("smooth", [0.0, 0.0, -0.9]);
+        map.insert("rough", [0.0, 0.3, 0.9]);
+        map.insert("silk", [0.0, -0.8, -1.0]);
+        map.insert("glass", [-0.2, 0.8, -1.0]);
+        map.insert("sand", [0.3, -0.2, 0.8]);
+        map.insert("bark", [0.0, 0.6, 0.9]);
+        map.insert("velvet", [0.2, -0.7, -0.6]);
+        map.insert("sticky", [0.1, -0.3, 0.5]);
+        map.insert("wet", [-0.3, -0.3, -0.6]);
+        map.insert("fur", [0.5, -0.8, 0.1]);
+
+        map
+    }
+
     /// Complete multimodal enrichment: add colors and emotions
     ///
     /// # Returns
@@ -964,17 +1200,18 @@ impl BootstrapLibrary {
 
     /// Complete extended multimodal enrichment (NEW v1.3)
     ///
-    /// Adds all 5 modalities: colors, emotions, sounds, actions, spatial relations
+    /// Adds all 6 modalities: colors, emotions, sounds, actions, spatial relations, tactile
     ///
     /// # Returns
-    /// (colors, emotions, sounds, actions, spatial)
-    pub fn enrich_extended_multimodal(&mut self) -> (usize, usize, usize, usize, usize) {
+    /// (colors, emotions, sounds, actions, spatial, tactile)
+    pub fn enrich_extended_multimodal(&mut self) -> (usize, usize, usize, usize, usize, usize) {
         let colors = self.add_color_anchors();
         let emotions = self.add_emotion_anchors();
         let sounds = self.add_sound_anchors();
         let actions = self.add_action_anchors();
         let spatial = self.add_spatial_anchors();
-        (colors, emotions, sounds, actions, spatial)
+        let tactile = self.add_tactile_anchors();
+        (colors, emotions, sounds, actions, spatial, tactile)
     }
 }
 
@@ -994,7 +1231,8 @@ impl BootstrapLibrary {
     /// * `max_depth` - Optional maximum depth for spreading (default: 3)
     ///
     /// # Returns
-    /// Vector of (word, activation_score) tuples, sorted by relevance
+    /// Vector of (word, activation_score) tuples, sorted by relevance.
+    /// See `semantic_search_with` for ranking boosted by multimodal anchors.
     ///
     /// # Example
     /// ```ignore
@@ -1007,51 +1245,26 @@ impl BootstrapLibrary {
         max_results: usize,
         max_depth: Option<usize>,
     ) -> Result<Vec<(String, f32)>, BootstrapError> {
-        // Get query concept
-        let query_concept = self.concepts.get(query)
-            .ok_or_else(|| BootstrapError::NoData(
-                format!("Unknown query word: '{}'", query)
-            ))?;
-
-        let query_id = query_concept.id;
-
-        // Create SignalConfig with custom max_depth if specified
-        let config = if let Some(depth) = max_depth {
-            Some(crate::SignalConfig {
-                max_depth: depth,
-                ..Default::default()
-            })
-        } else {
-            None  // Use default config (max_depth: 5)
-        };
-
-        // Run spreading activation from query node
-        let result = self.graph.spreading_activation(
-            query_id,
-            1.0,  // initial energy
-            config,
-        );
-
-        // Convert activated nodes to (word, score) pairs
-        let mut results: Vec<(String, f32)> = Vec::new();
-
-        for activated_node in &result.activated_nodes {
-            // Find concept matching this node ID
-            if let Some(concept) = self.concepts.values()
-                .find(|c| c.id == activated_node.node_id)
-            {
-                // Skip query word itself
-                if concept.word != query {
-                    results.push((concept.word.clone(), activated_node.energy));
-                }
-            }
-        }
-
-        // Sort by energy (descending) and limit to max_results
-        results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-        results.truncate(max_results);
+        self.semantic_search_in_layers(query, max_results, max_depth, crate::connection_v3::active_levels::ALL)
+    }
 
-        Ok(results)
+    /// Semantic search gated to L1-L8 layers
+    ///
+    /// Like `semantic_search`, but activation only spreads along edges whose
+    /// active levels intersect `layer_mask` (`connection_v3::active_levels`),
+    /// e.g. `active_levels::L4_EMOTIONAL` for emotional-only associations.
+    pub fn semantic_search_in_layers(
+        &mut self,
+        query: &str,
+        max_results: usize,
+        max_depth: Option<usize>,
+        layer_mask: u8,
+    ) -> Result<Vec<(String, f32)>, BootstrapError> {
+        self.semantic_search_with(query, max_results, &ranking::SearchConfig {
+            max_depth,
+            layer_mask,
+            ..ranking::SearchConfig::energy_only()
+        })
     }
 
     /// Multi-query semantic search (NEW v1.3)
@@ -1113,6 +1326,9 @@ impl BootstrapLibrary {
     /// Finds concepts that complete an analogy: "A is to B as C is to ?"
     /// Example: "king" is to "queen" as "man" is to "woman"
     ///
+    /// Blends the vector offset with typed relation paths from A to B
+    /// (see `analogy`); `semantic_analogy_with` selects the mode.
+    ///
     /// # Arguments
     /// * `a` - First word in analogy
     /// * `b` - Second word in analogy
@@ -1122,12 +1338,22 @@ impl BootstrapLibrary {
     /// # Returns
     /// Vector of (word, score) candidates for completing the analogy
     pub fn semantic_analogy(
-        &mut self,
+        &self,
         a: &str,
         b: &str,
         c: &str,
         max_results: usize,
     ) -> Result<Vec<(String, f32)>, BootstrapError> {
+        self.semantic_analogy_with(a, b, c, max_results, &analogy::AnalogyConfig::default())
+    }
+
+    /// Geometric analogy score of every concept except A, B and C
+    fn geometric_analogy(
+        &self,
+        a: &str,
+        b: &str,
+        c: &str,
+    ) -> Result<Vec<(&SemanticConcept, f32)>, BootstrapError> {
         // Get concepts
         let concept_a = self.concepts.get(a)
             .ok_or_else(|| BootstrapError::NoData(format!("Unknown word: '{}'", a)))?;
@@ -1151,7 +1377,7 @@ impl BootstrapLibrary {
         ];
 
         // Find concepts closest to target
-        let mut results: Vec<(String, f32)> = Vec::new();
+        let mut results: Vec<(&SemanticConcept, f32)> = Vec::new();
 
         for concept in self.concepts.values() {
             // Skip input words
@@ -1168,13 +1394,9 @@ impl BootstrapLibrary {
 
             // Convert distance to similarity score (inverse)
             let similarity = 1.0 / (1.0 + distance);
-            results.push((concept.word.clone(), similarity));
+            results.push((concept, similarity));
         }
 
-        // Sort by similarity and limit
-        results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
-        results.truncate(max_results);
-
         Ok(results)
     }
 }
@@ -1186,7 +1408,8 @@ impl BootstrapLibrary {
 impl BootstrapLibrary {
     /// Save PCA model to binary file
     ///
-    /// Saves the trained PCA model for later reuse
+    /// Saves the trained PCA model for later reuse, sealed with the
+    /// installed at-rest cipher if any
     ///
     /// # Arguments
     /// * `path` - Path to save the PCA model
@@ -1194,62 +1417,79 @@ impl BootstrapLibrary {
     /// # Returns
     /// Result with number of bytes written
     pub fn save_pca_model<P: AsRef<Path>>(&self, path: P) -> Result<usize, BootstrapError> {
+        self.save_pca_model_with(path, encryption::installed_cipher().as_deref())
+    }
+
+    /// [`save_pca_model`](Self::save_pca_model) with an explicit cipher
+    pub fn save_pca_model_with<P: AsRef<Path>>(
+        &self,
+        path: P,
+        cipher: Option<&FileCipher>,
+    ) -> Result<usize, BootstrapError> {
         let pca_model = self.pca_model.as_ref()
             .ok_or_else(|| BootstrapError::NoData("PCA model not trained".to_string()))?;
 
-        let mut file = File::create(path.as_ref())
-            .map_err(|e| BootstrapError::IoError(e.to_string()))?;
+        let mut buf = Vec::new();
 
         // Write format version (u32)
         let version: u32 = 1;
-        file.write_all(&version.to_le_bytes())
+        buf.write_all(&version.to_le_bytes())
             .map_err(|e| BootstrapError::IoError(e.to_string()))?;
 
         // Write dimensions
-        file.write_all(&(pca_model.original_dim as u32).to_le_bytes())
+        buf.write_all(&(pca_model.original_dim as u32).to_le_bytes())
             .map_err(|e| BootstrapError::IoError(e.to_string()))?;
-        file.write_all(&(pca_model.target_dim as u32).to_le_bytes())
+        buf.write_all(&(pca_model.target_dim as u32).to_le_bytes())
             .map_err(|e| BootstrapError::IoError(e.to_string()))?;
 
         // Write mean vector
         for &val in pca_model.mean.iter() {
-            file.write_all(&val.to_le_bytes())
+            buf.write_all(&val.to_le_bytes())
                 .map_err(|e| BootstrapError::IoError(e.to_string()))?;
         }
 
         // Write components matrix (row-major)
         for i in 0..pca_model.target_dim {
             for j in 0..pca_model.original_dim {
-                file.write_all(&pca_model.components[[i, j]].to_le_bytes())
+                buf.write_all(&pca_model.components[[i, j]].to_le_bytes())
                     .map_err(|e| BootstrapError::IoError(e.to_string()))?;
             }
         }
 
         // Write explained variance
         for &val in pca_model.explained_variance.iter() {
-            file.write_all(&val.to_le_bytes())
+            buf.write_all(&val.to_le_bytes())
                 .map_err(|e| BootstrapError::IoError(e.to_string()))?;
         }
 
-        let metadata = std::fs::metadata(path.as_ref())
-            .map_err(|e| BootstrapError::IoError(e.to_string()))?;
-        Ok(metadata.len() as usize)
+        write_artifact(path.as_ref(), buf, cipher)
     }
 
     /// Load PCA model from binary file
     ///
+    /// Sealed files are decrypted with the installed at-rest cipher
+    ///
     /// # Arguments
     /// * `path` - Path to the saved PCA model
     ///
     /// # Returns
     /// Result with loaded PCA model
     pub fn load_pca_model<P: AsRef<Path>>(&mut self, path: P) -> Result<(), BootstrapError> {
-        let mut file = File::open(path.as_ref())
-            .map_err(|e| BootstrapError::IoError(e.to_string()))?;
+        self.load_pca_model_with(path, encryption::installed_cipher().as_deref())
+    }
+
+    /// [`load_pca_model`](Self::load_pca_model) with an explicit cipher
+    pub fn load_pca_model_with<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        cipher: Option<&FileCipher>,
+    ) -> Result<(), BootstrapError> {
+        let data = read_artifact(path.as_ref(), cipher)?;
+        let mut input = data.as_slice();
 
         // Read version
         let mut version_bytes = [0u8; 4];
-        file.read_exact(&mut version_bytes)
+        input.read_exact(&mut version_bytes)
             .map_err(|e| BootstrapError::IoError(e.to_string()))?;
         let version = u32::from_le_bytes(version_bytes);
 
@@ -1261,11 +1501,11 @@ impl BootstrapLibrary {
 
         // Read dimensions
         let mut dim_bytes = [0u8; 4];
-        file.read_exact(&mut dim_bytes)
+        input.read_exact(&mut dim_bytes)
             .map_err(|e| BootstrapError::IoError(e.to_string()))?;
         let original_dim = u32::from_le_bytes(dim_bytes) as usize;
 
-        file.read_exact(&mut dim_bytes)
+        input.read_exact(&mut dim_bytes)
             .map_err(|e| BootstrapError::IoError(e.to_string()))?;
         let target_dim = u32::from_le_bytes(dim_bytes) as usize;
 
@@ -1273,7 +1513,7 @@ impl BootstrapLibrary {
         let mut mean = Array1::zeros(original_dim);
         for i in 0..original_dim {
             let mut val_bytes = [0u8; 4];
-            file.read_exact(&mut val_bytes)
+            input.read_exact(&mut val_bytes)
                 .map_err(|e| BootstrapError::IoError(e.to_string()))?;
             mean[i] = f32::from_le_bytes(val_bytes);
         }
@@ -1283,7 +1523,7 @@ impl BootstrapLibrary {
         for i in 0..target_dim {
             for j in 0..original_dim {
                 let mut val_bytes = [0u8; 4];
-                file.read_exact(&mut val_bytes)
+                input.read_exact(&mut val_bytes)
                     .map_err(|e| BootstrapError::IoError(e.to_string()))?;
                 components[[i, j]] = f32::from_le_bytes(val_bytes);
             }
@@ -1293,7 +1533,7 @@ impl BootstrapLibrary {
         let mut explained_variance = Array1::zeros(target_dim);
         for i in 0..target_dim {
             let mut val_bytes = [0u8; 4];
-            file.read_exact(&mut val_bytes)
+            input.read_exact(&mut val_bytes)
                 .map_err(|e| BootstrapError::IoError(e.to_string()))?;
             explained_variance[i] = f32::from_le_bytes(val_bytes);
         }
@@ -1311,7 +1551,9 @@ impl BootstrapLibrary {
 
     /// Save bootstrap map (word → concept mapping) to JSON file
     ///
-    /// Saves a lightweight mapping of words to their IDs and 3D coordinates
+    /// Saves a lightweight mapping of words to their IDs and 3D coordinates,
+    /// plus the phrases that resolve to each word, sealed with the installed
+    /// at-rest cipher if any
     ///
     /// # Arguments
     /// * `path` - Path to save the bootstrap map
@@ -1319,14 +1561,24 @@ impl BootstrapLibrary {
     /// # Returns
     /// Result with number of concepts saved
     pub fn save_bootstrap_map<P: AsRef<Path>>(&self, path: P) -> Result<usize, BootstrapError> {
+        self.save_bootstrap_map_with(path, encryption::installed_cipher().as_deref())
+    }
+
+    /// [`save_bootstrap_map`](Self::save_bootstrap_map) with an explicit cipher
+    pub fn save_bootstrap_map_with<P: AsRef<Path>>(
+        &self,
+        path: P,
+        cipher: Option<&FileCipher>,
+    ) -> Result<usize, BootstrapError> {
         if self.concepts.is_empty() {
             return Err(BootstrapError::NoData("No concepts loaded".to_string()));
         }
 
         // Create lightweight concept records (without full embedding)
+        let phrases = self.phrases_by_concept();
         let mut records = Vec::new();
         for concept in self.concepts.values() {
-            records.push(serde_json::json!({
+            let mut record = serde_json::json!({
                 "word": concept.word,
                 "id": concept.id,
                 "coords": concept.coords,
@@ -1335,21 +1587,109 @@ impl BootstrapLibrary {
                 "sound": concept.sound,
                 "action": concept.action,
                 "spatial": concept.spatial,
-            }));
+                "tactile": concept.tactile,
+                "weight": concept.weight,
+            });
+            if let Some(phrases) = phrases.get(concept.word.as_str()) {
+                record["phrases"] = serde_json::json!(phrases);
+            }
+            records.push(record);
         }
 
         let json = serde_json::to_string_pretty(&records)
             .map_err(|e| BootstrapError::IoError(e.to_string()))?;
 
-        let mut file = File::create(path.as_ref())
-            .map_err(|e| BootstrapError::IoError(e.to_string()))?;
-
-        file.write_all(json.as_bytes())
-            .map_err(|e| BootstrapError::IoError(e.to_string()))?;
+        write_artifact(path.as_ref(), json.into_bytes(), cipher)?;
 
         Ok(records.len())
     }
 
+    /// Load bootstrap map from JSON file (see `save_bootstrap_map`)
+    ///
+    /// Sealed files are decrypted with the installed at-rest cipher
+    ///
+    /// # Returns
+    /// Result with (num_concepts, num_edges)
+    pub fn load_bootstrap_map<P: AsRef<Path>>(&mut self, path: P) -> Result<(usize, usize), BootstrapError> {
+        self.load_bootstrap_map_with(path, encryption::installed_cipher().as_deref())
+    }
+
+    /// [`load_bootstrap_map`](Self::load_bootstrap_map) with an explicit cipher
+    pub fn load_bootstrap_map_with<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        cipher: Option<&FileCipher>,
+    ) -> Result<(usize, usize), BootstrapError> {
+        let json = String::from_utf8(read_artifact(path.as_ref(), cipher)?)
+            .map_err(|e| BootstrapError::ParseError(e.to_string()))?;
+        self.load_bootstrap_map_str(&json)
+    }
+
+    /// Load bootstrap map from an in-memory JSON string
+    ///
+    /// Restores concepts with their saved IDs, coordinates and anchors, then
+    /// populates Graph and Grid and weaves KNN edges. Embeddings are not part
+    /// of the map, so no PCA model is required (or produced); concepts loaded
+    /// this way cannot be re-projected. Works without filesystem access,
+    /// which makes it the entry point for the `wasm` build.
+    ///
+    /// # Returns
+    /// Result with (num_concepts, num_edges)
+    pub fn load_bootstrap_map_str(&mut self, json: &str) -> Result<(usize, usize), BootstrapError> {
+        #[derive(serde::Deserialize)]
+        struct MapRecord {
+            word: String,
+            id: NodeId,
+            coords: [f32; 3],
+            color: Option<[f32; 3]>,
+            emotion: Option<[f32; 3]>,
+            sound: Option<[f32; 3]>,
+            action: Option<[f32; 4]>,
+            spatial: Option<[f32; 3]>,
+            #[serde(default)]
+            tactile: Option<[f32; 3]>,
+            #[serde(default)]
+            phrases: Vec<String>,
+            weight: Option<f32>,
+        }
+
+        let records: Vec<MapRecord> = serde_json::from_str(json)
+            .map_err(|e| BootstrapError::ParseError(e.to_string()))?;
+        if records.is_empty() {
+            return Err(BootstrapError::NoData("Bootstrap map is empty".to_string()));
+        }
+
+        let loaded = records.len();
+        for record in records {
+            self.graph.add_node(record.id);
+
+            self.detect_phrase(&record.word);
+            for phrase in &record.phrases {
+                let words: Vec<&str> = phrase.split_whitespace().collect();
+                self.phrases.insert(&words, &record.word);
+            }
+
+            let weight = record.weight.unwrap_or_else(|| self.initial_weight(&record.word));
+            let concept = SemanticConcept {
+                id: record.id,
+                word: record.word,
+                embedding: Array1::zeros(0),
+                coords: record.coords,
+                color: record.color,
+                emotion: record.emotion,
+                sound: record.sound,
+                action: record.action,
+                spatial: record.spatial,
+                tactile: record.tactile,
+                weight,
+            };
+            let _ = self.grid.add(self.concept_token(&concept));
+            self.concepts.insert(concept.word.clone(), concept);
+        }
+
+        Ok((loaded, self.weave_knn()))
+    }
+
     /// Save all artifacts: PCA model and bootstrap map
     ///
     /// # Arguments
@@ -1376,6 +1716,19 @@ impl BootstrapLibrary {
     }
 }
 
+/// Seal `data` for `path` if a cipher is given and write it; returns the bytes written
+fn write_artifact(path: &Path, data: Vec<u8>, cipher: Option<&FileCipher>) -> Result<usize, BootstrapError> {
+    let data = encryption::seal_file(cipher, data, path).map_err(|e| BootstrapError::IoError(e.to_string()))?;
+    std::fs::write(path, &data).map_err(|e| BootstrapError::IoError(e.to_string()))?;
+    Ok(data.len())
+}
+
+/// Read an artifact written by [`write_artifact`]
+fn read_artifact(path: &Path, cipher: Option<&FileCipher>) -> Result<Vec<u8>, BootstrapError> {
+    let data = std::fs::read(path).map_err(|e| BootstrapError::IoError(e.to_string()))?;
+    encryption::open_file(cipher, data, path).map_err(|e| BootstrapError::IoError(e.to_string()))
+}
+
 // ============================================================================
 // Error Types
 // ============================================================================
@@ -1387,6 +1740,7 @@ pub enum BootstrapError {
     DimensionMismatch { expected: usize, got: usize },
     NoData(String),
     PcaError(String),
+    InvalidConfig(String),
 }
 
 impl std::fmt::Display for BootstrapError {
@@ -1399,6 +1753,7 @@ impl std::fmt::Display for BootstrapError {
             }
             Self::NoData(msg) => write!(f, "No data: {}", msg),
             Self::PcaError(msg) => write!(f, "PCA error: {}", msg),
+            Self::InvalidConfig(msg) => write!(f, "Invalid config: {}", msg),
         }
     }
 }
@@ -1846,6 +2201,71 @@ mod tests {
         std::fs::remove_file(map_path).ok();
     }
 
+    #[test]
+    fn test_sealed_artifacts() {
+        use crate::encryption::EncryptionError;
+
+        let dir = tempfile::tempdir().unwrap();
+        let embeddings = dir.path().join("embeddings.txt");
+        std::fs::write(&embeddings, "cat 0.1 0.2 0.3\ndog 0.4 0.5 0.6\nred 0.7 0.8 0.9\n").unwrap();
+        let config = BootstrapConfig { embedding_dim: 3, target_dim: 3, ..Default::default() };
+        let mut bootstrap = BootstrapLibrary::new(config.clone());
+        bootstrap.load_embeddings(&embeddings).unwrap();
+        bootstrap.run_pca_pipeline().unwrap();
+        bootstrap.add_phrase(&["kitty", "cat"], "cat").unwrap();
+
+        let cipher = FileCipher::new(&FileCipher::generate_key());
+        let pca_path = dir.path().join("pca_model.bin");
+        let map_path = dir.path().join("bootstrap_map.json");
+        bootstrap.save_pca_model_with(&pca_path, Some(&cipher)).unwrap();
+        bootstrap.save_bootstrap_map_with(&map_path, Some(&cipher)).unwrap();
+        let sealed = std::fs::read(&map_path).unwrap();
+        assert!(FileCipher::is_sealed(&sealed));
+        assert!(!sealed.windows(5).any(|w| w == b"kitty"));
+
+        let mut restored = BootstrapLibrary::new(config.clone());
+        restored.load_pca_model_with(&pca_path, Some(&cipher)).unwrap();
+        restored.load_bootstrap_map_with(&map_path, Some(&cipher)).unwrap();
+        assert!(restored.pca_model.is_some());
+        assert_eq!(restored.match_phrase(&["kitty", "cat"]).map(|(c, _)| c.word.as_str()), Some("cat"));
+
+        // Without the key, and plain files when a key is configured, are refused
+        let mut other = BootstrapLibrary::new(config);
+        let missing = EncryptionError::MissingKey.to_string();
+        assert!(matches!(other.load_bootstrap_map_with(&map_path, None), Err(BootstrapError::IoError(e)) if e == missing));
+        bootstrap.save_bootstrap_map_with(&map_path, None).unwrap();
+        let not_sealed = EncryptionError::NotSealed.to_string();
+        assert!(matches!(
+            other.load_bootstrap_map_with(&map_path, Some(&cipher)),
+            Err(BootstrapError::IoError(e)) if e == not_sealed
+        ));
+    }
+
+    #[test]
+    fn test_load_bootstrap_map_str() {
+        let json = r#"[
+            {"word": "cat", "id": 1, "coords": [0.1, 0.2, 0.3], "color": null, "emotion": null, "sound": null, "action": null, "spatial": null},
+            {"word": "dog", "id": 2, "coords": [0.15, 0.25, 0.3], "color": null, "emotion": null, "sound": null, "action": null, "spatial": null},
+            {"word": "red", "id": 3, "coords": [0.9, 0.8, 0.7], "color": [1.0, 0.0, 0.0], "emotion": null, "sound": null, "action": null, "spatial": null}
+        ]"#;
+
+        let mut bootstrap = BootstrapLibrary::new(BootstrapConfig::default());
+        let (concepts, edges) = bootstrap.load_bootstrap_map_str(json).unwrap();
+
+        assert_eq!(concepts, 3);
+        assert!(edges > 0);
+        assert_eq!(bootstrap.graph().node_count(), 3);
+        assert_eq!(bootstrap.grid().len(), 3);
+        assert_eq!(bootstrap.get_concept("red").unwrap().color, Some([1.0, 0.0, 0.0]));
+
+        let results = bootstrap.semantic_search("cat", 5, None).unwrap();
+        assert!(results.iter().any(|(word, _)| word == "dog"));
+
+        assert!(BootstrapLibrary::new(BootstrapConfig::default())
+            .load_bootstrap_map_str("[]")
+            .is_err());
+    }
+
     #[test]
     fn test_save_all_artifacts() {
         use std::io::Write;
@@ -2289,6 +2709,7 @@ mod tests {
         writeln!(file, "whisper 0.7 0.8 0.9").unwrap();  // sound
         writeln!(file, "run 0.2 0.3 0.4").unwrap();      // action
         writeln!(file, "above 0.5 0.6 0.7").unwrap();    // spatial
+        writeln!(file, "silk 0.3 0.2 0.1").unwrap();     // tactile
         writeln!(file, "table 0.1 0.1 0.1").unwrap();    // none
 
         let mut config = BootstrapConfig::default();
@@ -2297,13 +2718,14 @@ mod tests {
         let mut bootstrap = BootstrapLibrary::new(config);
         bootstrap.load_embeddings(temp_path).unwrap();
 
-        let (colors, emotions, sounds, actions, spatial) = bootstrap.enrich_extended_multimodal();
+        let (colors, emotions, sounds, actions, spatial, tactile) = bootstrap.enrich_extended_multimodal();
 
         assert_eq!(colors, 1, "Should enrich 1 color");
         assert_eq!(emotions, 1, "Should enrich 1 emotion");
         assert_eq!(sounds, 1, "Should enrich 1 sound");
         assert_eq!(actions, 1, "Should enrich 1 action");
         assert_eq!(spatial, 1, "Should enrich 1 spatial");
+        assert_eq!(tactile, 1, "Should enrich 1 tactile");
 
         // Verify each modality
         let red = bootstrap.get_concept("red").unwrap();
@@ -2329,6 +2751,10 @@ mod tests {
         let above = bootstrap.get_concept("above").unwrap();
         assert!(above.spatial.is_some());
 
+        let silk = bootstrap.get_concept("silk").unwrap();
+        assert!(silk.tactile.unwrap()[2] < 0.0, "Silk should be smooth");
+        assert!(red.tactile.is_none());
+
         let table = bootstrap.get_concept("table").unwrap();
         assert!(table.color.is_none());
         assert!(table.emotion.is_none());
@@ -2338,4 +2764,40 @@ mod tests {
 
         std::fs::remove_file(temp_path).ok();
     }
+
+    #[test]
+    fn test_add_concept_incremental() {
+        use std::io::Write;
+        use std::fs::File;
+
+        let temp_path = "/tmp/test_add_concept.txt";
+        let mut file = File::create(temp_path).unwrap();
+        for i in 0..5 {
+            let v = i as f32 * 0.1;
+            writeln!(file, "word{} {} {} {}", i, v, v * 2.0, v * 3.0).unwrap();
+        }
+
+        let mut config = BootstrapConfig::default();
+        config.embedding_dim = 3;
+        config.knn_k = 2;
+
+        let mut bootstrap = BootstrapLibrary::new(config);
+
+        // No PCA model yet
+        assert!(bootstrap.add_concept("early", vec![0.0; 3]).is_err());
+
+        bootstrap.bootstrap_from_embeddings(temp_path).unwrap();
+        let edges_before = bootstrap.graph().edge_count();
+
+        let id = bootstrap.add_concept("newcomer", vec![0.15, 0.3, 0.45]).unwrap();
+        assert_eq!(bootstrap.concept_count(), 6);
+        assert_eq!(bootstrap.get_concept("newcomer").unwrap().id, id);
+        assert!(bootstrap.graph().edge_count() > edges_before);
+
+        // Idempotent for known words, strict on dimension
+        assert_eq!(bootstrap.add_concept("newcomer", vec![9.0; 3]).unwrap(), id);
+        assert!(bootstrap.add_concept("bad", vec![0.0; 4]).is_err());
+
+        std::fs::remove_file(temp_path).ok();
+    }
 }
@@ -0,0 +1,336 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Relational analogy
+//!
+//! The geometric analogy (`c + (b - a)` in the 3D concept space) gets crude
+//! once PCA has squeezed the embeddings into three dimensions. The relational
+//! mode reads the typed graph instead:
+//!
+//! 1. collect the relation paths from `a` to `b` up to `max_hops` long, e.g.
+//!    `a —Hypernym→ b` or `a —Meronym→ x ←Meronym— b`
+//! 2. follow the same paths from `c`
+//! 3. score every reached concept by the share of `a → b` paths it completes
+//!    (weighted by path strength, the product of edge weights)
+//!
+//! [`AnalogyMode::Blended`] mixes the relational and geometric scores and
+//! falls back to the geometric one when `a` and `b` share no relation path.
+//! Associative edges (KNN weaving) are skipped by default: they mirror the
+//! geometry rather than a relation.
+
+use super::{BootstrapError, BootstrapLibrary};
+use crate::graph::Direction;
+use crate::NodeId;
+use std::collections::{HashMap, HashSet};
+
+/// How `semantic_analogy_with` scores candidates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnalogyMode {
+    /// Vector offset in concept space only
+    Geometric,
+    /// Relation paths only; concepts no path reaches are left out
+    Relational,
+    /// Weighted mix of both
+    #[default]
+    Blended,
+}
+
+/// Analogy configuration
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnalogyConfig {
+    pub mode: AnalogyMode,
+
+    /// Share of the relational score in Blended mode (0.0-1.0)
+    pub relational_weight: f32,
+
+    /// Longest relation path considered
+    pub max_hops: usize,
+
+    /// Treat Associative edges (0x50-0x5F, KNN weaving) as relations
+    pub include_associative: bool,
+}
+
+impl Default for AnalogyConfig {
+    fn default() -> Self {
+        Self {
+            mode: AnalogyMode::Blended,
+            relational_weight: 0.5,
+            max_hops: 2,
+            include_associative: false,
+        }
+    }
+}
+
+impl AnalogyConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.relational_weight) {
+            return Err(format!("relational_weight must be 0.0-1.0, got {}", self.relational_weight));
+        }
+        if self.max_hops == 0 {
+            return Err("max_hops must be > 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Direction an edge is followed in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum StepDirection {
+    /// Along a directed edge
+    Forward,
+    /// Against a directed edge
+    Backward,
+    /// Along a bidirectional edge
+    Symmetric,
+}
+
+/// One hop of a relation path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RelationStep {
+    /// Connection type (`ConnectionType as u8`)
+    pub edge_type: u8,
+    pub direction: StepDirection,
+}
+
+/// A relation path and its strength (product of edge weights)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelationPath {
+    pub steps: Vec<RelationStep>,
+    pub strength: f32,
+}
+
+impl BootstrapLibrary {
+    /// Relation paths from `from` to `to`, strongest first
+    ///
+    /// Paths do not revisit a node; of several paths with the same steps
+    /// the strongest is kept.
+    pub fn relation_paths(&self, from: NodeId, to: NodeId, config: &AnalogyConfig) -> Vec<RelationPath> {
+        let mut found: HashMap<Vec<RelationStep>, f32> = HashMap::new();
+        let mut path = Vec::new();
+        let mut visited = HashSet::from([from]);
+        self.collect_paths(from, to, 1.0, config, &mut path, &mut visited, &mut found);
+
+        let mut paths: Vec<RelationPath> = found
+            .into_iter()
+            .map(|(steps, strength)| RelationPath { steps, strength })
+            .collect();
+        paths.sort_by(|a, b| b.strength.total_cmp(&a.strength).then_with(|| a.steps.cmp(&b.steps)));
+        paths
+    }
+
+    /// Complete "a is to b as c is to ?" with the given scoring mode
+    ///
+    /// # Returns
+    /// Vector of (word, score) candidates, best first
+    pub fn semantic_analogy_with(
+        &self,
+        a: &str,
+        b: &str,
+        c: &str,
+        max_results: usize,
+        config: &AnalogyConfig,
+    ) -> Result<Vec<(String, f32)>, BootstrapError> {
+        config.validate().map_err(BootstrapError::InvalidConfig)?;
+        let geometric = self.geometric_analogy(a, b, c)?;
+        let id_of = |word: &str| self.concepts[word].id;
+        let paths = match config.mode {
+            AnalogyMode::Geometric => Vec::new(),
+            _ => self.relation_paths(id_of(a), id_of(b), config),
+        };
+
+        // Relational score: strength-weighted share of a → b paths completed from c
+        let total: f32 = paths.iter().map(|p| p.strength).sum();
+        let mut relational: HashMap<NodeId, f32> = HashMap::new();
+        for path in &paths {
+            for (node, strength) in self.follow_path(id_of(c), &path.steps, config) {
+                *relational.entry(node).or_insert(0.0) += path.strength * strength / total;
+            }
+        }
+
+        let weight = match config.mode {
+            AnalogyMode::Geometric => 0.0,
+            AnalogyMode::Relational => 1.0,
+            AnalogyMode::Blended if paths.is_empty() => 0.0,
+            AnalogyMode::Blended => config.relational_weight,
+        };
+        let mut results: Vec<(String, f32)> = geometric
+            .into_iter()
+            .filter_map(|(concept, geometric)| {
+                let relational = relational.get(&concept.id).copied();
+                if config.mode == AnalogyMode::Relational && relational.is_none() {
+                    return None;
+                }
+                let score = (1.0 - weight) * geometric + weight * relational.unwrap_or(0.0).min(1.0);
+                Some((concept.word.clone(), score))
+            })
+            .collect();
+
+        results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        results.truncate(max_results);
+        Ok(results)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn collect_paths(
+        &self,
+        node: NodeId,
+        target: NodeId,
+        strength: f32,
+        config: &AnalogyConfig,
+        path: &mut Vec<RelationStep>,
+        visited: &mut HashSet<NodeId>,
+        found: &mut HashMap<Vec<RelationStep>, f32>,
+    ) {
+        for (step, next, weight) in self.relation_steps(node, config) {
+            if !visited.insert(next) {
+                continue;
+            }
+            path.push(step);
+            if next == target {
+                let best = found.entry(path.clone()).or_insert(0.0);
+                *best = best.max(strength * weight);
+            } else if path.len() < config.max_hops {
+                self.collect_paths(next, target, strength * weight, config, path, visited, found);
+            }
+            path.pop();
+            visited.remove(&next);
+        }
+    }
+
+    /// Nodes reached from `start` along `steps`, with the strongest path to each
+    fn follow_path(&self, start: NodeId, steps: &[RelationStep], config: &AnalogyConfig) -> HashMap<NodeId, f32> {
+        let mut frontier = HashMap::from([(start, 1.0f32)]);
+        for &step in steps {
+            let mut next_frontier: HashMap<NodeId, f32> = HashMap::new();
+            for (&node, &strength) in &frontier {
+                for (taken, next, weight) in self.relation_steps(node, config) {
+                    if taken == step && next != start {
+                        let best = next_frontier.entry(next).or_insert(0.0);
+                        *best = best.max(strength * weight);
+                    }
+                }
+            }
+            frontier = next_frontier;
+        }
+        frontier
+    }
+
+    /// Typed edges leaving `node` in either direction: (step, neighbor, weight)
+    fn relation_steps(&self, node: NodeId, config: &AnalogyConfig) -> Vec<(RelationStep, NodeId, f32)> {
+        let graph = self.graph();
+        let is_relation = |edge_type: u8| config.include_associative || !(0x50..=0x5F).contains(&edge_type);
+        let outgoing = graph.get_neighbors(node, Direction::Outgoing).into_iter().map(|(n, e)| (n, e, true));
+        let incoming = graph.get_neighbors(node, Direction::Incoming).into_iter().map(|(n, e)| (n, e, false));
+
+        outgoing
+            .chain(incoming)
+            .filter(|&(neighbor, _, _)| neighbor != node)
+            .filter_map(|(neighbor, edge_id, forward)| {
+                let edge = graph.get_edge(edge_id)?;
+                if !is_relation(edge.edge_type) {
+                    return None;
+                }
+                let direction = match (edge.bidirectional, forward) {
+                    (true, _) => StepDirection::Symmetric,
+                    (false, true) => StepDirection::Forward,
+                    (false, false) => StepDirection::Backward,
+                };
+                Some((RelationStep { edge_type: edge.edge_type, direction }, neighbor, edge.weight))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootstrap::relations::RelationRecord;
+    use crate::bootstrap::BootstrapConfig;
+    use crate::ConnectionType;
+    use std::io::Write;
+
+    fn library() -> BootstrapLibrary {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("emb.txt");
+        let mut file = std::fs::File::create(&path).unwrap();
+        let words = ["car", "vehicle", "oak", "tree", "wheel", "leaf", "acorn"];
+        for (i, word) in words.iter().enumerate() {
+            let v = i as f32 * 0.1;
+            writeln!(file, "{} {} {} {}", word, v, (v * 7.0).sin(), 1.0 - v * v).unwrap();
+        }
+
+        let config = BootstrapConfig { embedding_dim: 3, knn_k: 2, ..Default::default() };
+        let mut library = BootstrapLibrary::new(config);
+        library.bootstrap_from_embeddings(&path).unwrap();
+        for (source, target, relation) in [
+            ("car", "vehicle", ConnectionType::Hypernym),
+            ("oak", "tree", ConnectionType::Hypernym),
+            ("wheel", "car", ConnectionType::Meronym),
+            ("leaf", "tree", ConnectionType::Meronym),
+        ] {
+            let record = RelationRecord {
+                source: source.to_string(),
+                target: target.to_string(),
+                relation,
+                weight: 1.0,
+            };
+            library.add_relation(&record);
+        }
+        library
+    }
+
+    #[test]
+    fn test_relation_paths() {
+        let library = library();
+        let id = |word: &str| library.get_concept(word).unwrap().id;
+        let config = AnalogyConfig::default();
+
+        let paths = library.relation_paths(id("car"), id("vehicle"), &config);
+        assert_eq!(paths.len(), 1);
+        assert_eq!(
+            paths[0].steps,
+            vec![RelationStep { edge_type: ConnectionType::Hypernym as u8, direction: StepDirection::Forward }]
+        );
+        let paths = library.relation_paths(id("wheel"), id("vehicle"), &config);
+        assert_eq!(paths[0].steps.len(), 2);
+        assert!(library.relation_paths(id("acorn"), id("car"), &config).is_empty());
+    }
+
+    #[test]
+    fn test_relational_analogy() {
+        let library = library();
+        let relational = AnalogyConfig { mode: AnalogyMode::Relational, ..Default::default() };
+        let hits = library.semantic_analogy_with("car", "vehicle", "oak", 5, &relational).unwrap();
+        assert_eq!(hits, vec![("tree".to_string(), 1.0)]);
+        let hits = library.semantic_analogy_with("wheel", "car", "leaf", 5, &relational).unwrap();
+        assert_eq!(hits[0].0, "tree");
+
+        // Blended puts the relational answer first
+        let hits = library.semantic_analogy("car", "vehicle", "oak", 3).unwrap();
+        assert_eq!(hits[0].0, "tree");
+        assert!(hits.len() > 1);
+
+        // Geometric mode and pairs without relation paths keep the plain offset
+        let geometric = AnalogyConfig { mode: AnalogyMode::Geometric, ..Default::default() };
+        assert_eq!(
+            library.semantic_analogy("acorn", "leaf", "oak", 3).unwrap(),
+            library.semantic_analogy_with("acorn", "leaf", "oak", 3, &geometric).unwrap()
+        );
+        assert!(library
+            .semantic_analogy_with("car", "vehicle", "oak", 3, &AnalogyConfig { max_hops: 0, ..Default::default() })
+            .is_err());
+    }
+}
@@ -0,0 +1,243 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Stop-word and frequency weights
+//!
+//! Every concept carries a `weight` (0.0-1.0, 1.0 = neutral) that the
+//! Normalizer multiplies into its composition weights, so frequent words like
+//! "the" stop dominating sentence states. Weights are information content
+//! normalized to the rarest word:
+//!
+//! ```text
+//! weight = ln(total / count) / ln(total / min_count)
+//! ```
+//!
+//! Counts come from either source:
+//! - the embedding file itself when `rank_weights` is set: GloVe and
+//!   word2vec vocabularies are sorted by frequency, so the word at rank `r`
+//!   gets the Zipf count `1 / (r + 1)`
+//! - a frequency list (`word count` per line) via `load_frequency_list`
+//!
+//! Words in `stop_words` always get `stop_word_weight`; weights never drop
+//! below `min_weight`, so a sentence of frequent words still composes.
+
+use super::{BootstrapError, BootstrapLibrary};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Common English function words, a starting point for `stop_words`
+pub const ENGLISH_STOP_WORDS: &[&str] = &[
+    "a", "an", "the", "and", "or", "but", "of", "to", "in", "on", "at", "by",
+    "for", "with", "from", "as", "is", "are", "was", "were", "be", "been",
+    "it", "its", "this", "that", "these", "those", "i", "you", "he", "she",
+    "we", "they", "me", "him", "her", "us", "them", "my", "your", "our",
+    "their", "do", "does", "did", "has", "have", "had", "so", "than", "then",
+];
+
+/// Normalized information content per word
+fn information_weights(counts: &[(String, f64)], min_weight: f32) -> HashMap<String, f32> {
+    let total: f64 = counts.iter().map(|(_, count)| count).sum();
+    let min_count = counts.iter().map(|(_, count)| *count).fold(f64::INFINITY, f64::min);
+    let max_information = (total / min_count).ln();
+
+    counts.iter()
+        .map(|(word, count)| {
+            let weight = if max_information > 0.0 {
+                ((total / count).ln() / max_information) as f32
+            } else {
+                1.0
+            };
+            (word.clone(), weight.clamp(min_weight, 1.0))
+        })
+        .collect()
+}
+
+impl BootstrapLibrary {
+    /// Weight of a concept (1.0 if unknown)
+    pub fn concept_weight(&self, word: &str) -> f32 {
+        self.concepts.get(word).map_or(1.0, |c| c.weight)
+    }
+
+    /// Reset every concept to its stop-word weight (or 1.0)
+    ///
+    /// # Returns
+    /// Number of stop words among the concepts
+    pub fn apply_stop_words(&mut self) -> usize {
+        let mut stop_words = 0;
+        for concept in self.concepts.values_mut() {
+            concept.weight = if self.config.stop_words.contains(&concept.word) {
+                stop_words += 1;
+                self.config.stop_word_weight
+            } else {
+                1.0
+            };
+        }
+        stop_words
+    }
+
+    /// Weight concepts by frequency rank, in load order (most frequent first)
+    ///
+    /// # Returns
+    /// Number of weighted concepts
+    pub(super) fn apply_rank_weights(&mut self, order: &[String]) -> usize {
+        let counts: Vec<(String, f64)> = order.iter()
+            .enumerate()
+            .map(|(rank, word)| (word.clone(), 1.0 / (rank + 1) as f64))
+            .collect();
+        self.apply_counts(&counts)
+    }
+
+    /// Load concept weights from a frequency list
+    ///
+    /// One `word count` per line (whitespace separated, `#` comments), as
+    /// produced by wordfreq or corpus tools. Words without a concept still
+    /// count towards the total; concepts missing from the list keep their
+    /// weight.
+    ///
+    /// # Returns
+    /// Result with number of weighted concepts
+    pub fn load_frequency_list<P: AsRef<Path>>(&mut self, path: P) -> Result<usize, BootstrapError> {
+        let file = File::open(path.as_ref())
+            .map_err(|e| BootstrapError::IoError(e.to_string()))?;
+
+        let mut counts = Vec::new();
+        for (line_num, line) in BufReader::new(file).lines().enumerate() {
+            let line = line.map_err(|e| BootstrapError::IoError(e.to_string()))?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = line.split_whitespace();
+            let (Some(word), Some(count), None) = (parts.next(), parts.next(), parts.next()) else {
+                return Err(BootstrapError::ParseError(
+                    format!("Line {}: expected 'word count'", line_num + 1)
+                ));
+            };
+            let count: f64 = count.parse()
+                .map_err(|e| BootstrapError::ParseError(format!("Line {}: {}", line_num + 1, e)))?;
+            if count <= 0.0 {
+                return Err(BootstrapError::ParseError(
+                    format!("Line {}: count must be > 0", line_num + 1)
+                ));
+            }
+            counts.push((word.to_lowercase(), count));
+        }
+
+        if counts.is_empty() {
+            return Err(BootstrapError::NoData("Frequency list is empty".to_string()));
+        }
+
+        Ok(self.apply_counts(&counts))
+    }
+
+    fn apply_counts(&mut self, counts: &[(String, f64)]) -> usize {
+        let weights = information_weights(counts, self.config.min_weight);
+        let mut weighted = 0;
+        for (word, weight) in weights {
+            if let Some(concept) = self.concepts.get_mut(&word) {
+                concept.weight = if self.config.stop_words.contains(&word) {
+                    self.config.stop_word_weight
+                } else {
+                    weight
+                };
+                weighted += 1;
+            }
+        }
+        weighted
+    }
+
+    /// Initial weight of a newly added concept
+    pub(super) fn initial_weight(&self, word: &str) -> f32 {
+        if self.config.stop_words.iter().any(|w| w == word) {
+            self.config.stop_word_weight
+        } else {
+            1.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootstrap::BootstrapConfig;
+    use std::io::Write;
+
+    fn write_embeddings(dir: &Path) -> std::path::PathBuf {
+        let path = dir.join("emb.txt");
+        let mut file = File::create(&path).unwrap();
+        // Frequency order, as in GloVe
+        for (i, word) in ["the", "of", "cat", "dog", "kitten", "axolotl"].iter().enumerate() {
+            let v = i as f32 * 0.1;
+            writeln!(file, "{} {} {} {}", word, v, v * v, 1.0 - v).unwrap();
+        }
+        path
+    }
+
+    #[test]
+    fn test_rank_weights() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_embeddings(dir.path());
+
+        let config = BootstrapConfig {
+            embedding_dim: 3,
+            rank_weights: true,
+            stop_words: vec!["of".to_string()],
+            ..Default::default()
+        };
+        let mut library = BootstrapLibrary::new(config);
+        library.load_embeddings(&path).unwrap();
+
+        assert_eq!(library.concept_weight("axolotl"), 1.0);
+        assert!(library.concept_weight("kitten") > library.concept_weight("cat"));
+        assert!(library.concept_weight("cat") > library.concept_weight("the"));
+        assert!(library.concept_weight("the") >= 0.1);
+        assert_eq!(library.concept_weight("of"), 0.05);
+
+        // Without rank weights only stop words are down-weighted
+        let config = BootstrapConfig {
+            embedding_dim: 3,
+            stop_words: vec!["of".to_string()],
+            ..Default::default()
+        };
+        let mut library = BootstrapLibrary::new(config);
+        library.load_embeddings(&path).unwrap();
+        assert_eq!(library.concept_weight("the"), 1.0);
+        assert_eq!(library.concept_weight("of"), 0.05);
+    }
+
+    #[test]
+    fn test_frequency_list() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_embeddings(dir.path());
+        let list = dir.path().join("freq.txt");
+        std::fs::write(&list, "# word count\nthe 1000\ncat 10\ndog 10\nunseen 1\n").unwrap();
+
+        let config = BootstrapConfig { embedding_dim: 3, ..Default::default() };
+        let mut library = BootstrapLibrary::new(config);
+        library.load_embeddings(&path).unwrap();
+
+        assert_eq!(library.load_frequency_list(&list).unwrap(), 3);
+        assert_eq!(library.concept_weight("cat"), library.concept_weight("dog"));
+        assert!(library.concept_weight("cat") > library.concept_weight("the"));
+        assert_eq!(library.concept_weight("kitten"), 1.0);
+
+        std::fs::write(&list, "cat ten\n").unwrap();
+        assert!(matches!(library.load_frequency_list(&list), Err(BootstrapError::ParseError(_))));
+    }
+}
@@ -0,0 +1,227 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Grounded queries by modality values
+//!
+//! Searches enriched concepts by their anchors instead of by word:
+//! `find_by_color([0.0, 0.0, 1.0], 0.3)` returns the concepts whose color
+//! lies within 0.3 (Euclidean) of pure blue, nearest first.
+//!
+//! `find_grounded` combines several terms, e.g. "calm blue" as an emotion
+//! term plus a color term. Each concept scores the mean closeness
+//! (`1 - distance / tolerance`) over all terms, 0 for terms it misses, so
+//! concepts matching every term rank first while single-term matches still
+//! show up.
+
+use super::lexicon::Modality;
+use super::{BootstrapError, BootstrapLibrary, SemanticConcept};
+use crate::NodeId;
+
+/// One modality constraint of a grounded query
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroundedTerm {
+    pub modality: Modality,
+    pub values: Vec<f32>,
+    /// Largest Euclidean distance that still matches
+    pub tolerance: f32,
+}
+
+impl GroundedTerm {
+    pub fn new(modality: Modality, values: &[f32], tolerance: f32) -> Self {
+        Self { modality, values: values.to_vec(), tolerance }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.values.len() != self.modality.dims() {
+            return Err(format!(
+                "{} needs {} values, got {}",
+                self.modality, self.modality.dims(), self.values.len()
+            ));
+        }
+        if self.values.iter().any(|v| !v.is_finite()) {
+            return Err(format!("{} values must be finite", self.modality));
+        }
+        if self.tolerance.is_nan() || self.tolerance <= 0.0 {
+            return Err(format!("tolerance must be > 0.0, got {}", self.tolerance));
+        }
+        Ok(())
+    }
+
+    /// Distance to the concept's anchor, None if it has none or is out of tolerance
+    fn distance(&self, concept: &SemanticConcept) -> Option<f32> {
+        let anchor = concept.anchor(self.modality)?;
+        let distance = anchor.iter()
+            .zip(self.values.iter())
+            .map(|(a, b)| (a - b) * (a - b))
+            .sum::<f32>()
+            .sqrt();
+        (distance <= self.tolerance).then_some(distance)
+    }
+}
+
+/// A concept found by `find_grounded`
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroundedMatch {
+    pub word: String,
+    pub id: NodeId,
+    /// Mean closeness over all terms (0.0-1.0)
+    pub score: f32,
+    /// Number of terms the concept matched
+    pub matched: usize,
+}
+
+impl BootstrapLibrary {
+    /// Concepts whose color lies within `tolerance` of `rgb`
+    ///
+    /// # Returns
+    /// (word, distance) pairs, nearest first
+    pub fn find_by_color(&self, rgb: [f32; 3], tolerance: f32) -> Vec<(String, f32)> {
+        self.find_by_modality(&GroundedTerm::new(Modality::Color, &rgb, tolerance))
+            .unwrap_or_default()
+    }
+
+    /// Concepts whose VAD emotion lies within `tolerance` of `vad`
+    ///
+    /// # Returns
+    /// (word, distance) pairs, nearest first
+    pub fn find_by_emotion(&self, vad: [f32; 3], tolerance: f32) -> Vec<(String, f32)> {
+        self.find_by_modality(&GroundedTerm::new(Modality::Emotion, &vad, tolerance))
+            .unwrap_or_default()
+    }
+
+    /// Concepts matching a single term
+    ///
+    /// # Returns
+    /// (word, distance) pairs, nearest first
+    pub fn find_by_modality(&self, term: &GroundedTerm) -> Result<Vec<(String, f32)>, BootstrapError> {
+        term.validate().map_err(BootstrapError::InvalidConfig)?;
+
+        let mut results: Vec<(String, f32)> = self.concepts.values()
+            .filter_map(|c| term.distance(c).map(|d| (c.word.clone(), d)))
+            .collect();
+        results.sort_by(|a, b| {
+            a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal).then_with(|| a.0.cmp(&b.0))
+        });
+        Ok(results)
+    }
+
+    /// Concepts matching any of `terms`, best combined score first
+    pub fn find_grounded(
+        &self,
+        terms: &[GroundedTerm],
+        max_results: usize,
+    ) -> Result<Vec<GroundedMatch>, BootstrapError> {
+        if terms.is_empty() {
+            return Err(BootstrapError::InvalidConfig("grounded query needs a term".to_string()));
+        }
+        for term in terms {
+            term.validate().map_err(BootstrapError::InvalidConfig)?;
+        }
+
+        let mut matches: Vec<GroundedMatch> = self.concepts.values()
+            .filter_map(|concept| {
+                let mut closeness = 0.0;
+                let mut matched = 0;
+                for term in terms {
+                    if let Some(distance) = term.distance(concept) {
+                        closeness += 1.0 - distance / term.tolerance;
+                        matched += 1;
+                    }
+                }
+                (matched > 0).then(|| GroundedMatch {
+                    word: concept.word.clone(),
+                    id: concept.id,
+                    score: closeness / terms.len() as f32,
+                    matched,
+                })
+            })
+            .collect();
+
+        matches.sort_by(|a, b| {
+            b.matched.cmp(&a.matched)
+                .then_with(|| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal))
+                .then_with(|| a.word.cmp(&b.word))
+        });
+        matches.truncate(max_results);
+        Ok(matches)
+    }
+
+    /// Anchor values a word stands for: the concept's own anchor, else the lexicon entry
+    ///
+    /// Lets callers phrase grounded queries as words ("blue", "calm").
+    pub fn anchor_of(&self, modality: Modality, word: &str) -> Option<Vec<f32>> {
+        let word = word.to_lowercase();
+        self.concepts.get(&word)
+            .and_then(|c| c.anchor(modality))
+            .or_else(|| self.lexicons.get(modality, &word))
+            .map(<[f32]>::to_vec)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootstrap::BootstrapConfig;
+
+    fn library() -> BootstrapLibrary {
+        let json = r#"[
+            {"word": "sky", "id": 1, "coords": [0.1, 0.0, 0.0], "color": [0.53, 0.81, 0.92], "emotion": [0.4, -0.5, 0.1]},
+            {"word": "ocean", "id": 2, "coords": [0.2, 0.0, 0.0], "color": [0.0, 0.5, 1.0]},
+            {"word": "blood", "id": 3, "coords": [0.3, 0.0, 0.0], "color": [0.72, 0.0, 0.0]},
+            {"word": "serene", "id": 4, "coords": [0.4, 0.0, 0.0], "emotion": [0.5, -0.6, 0.2]}
+        ]"#;
+        let mut library = BootstrapLibrary::new(BootstrapConfig::default());
+        library.load_bootstrap_map_str(json).unwrap();
+        library
+    }
+
+    #[test]
+    fn test_find_by_color_and_emotion() {
+        let library = library();
+
+        let blues = library.find_by_color([0.0, 0.4, 1.0], 0.7);
+        let words: Vec<&str> = blues.iter().map(|(w, _)| w.as_str()).collect();
+        assert_eq!(words, vec!["ocean", "sky"]);
+        assert!(blues[0].1 < blues[1].1);
+
+        let calm = library.find_by_emotion([0.5, -0.6, 0.2], 0.2);
+        assert_eq!(calm.len(), 2);
+        assert_eq!(calm[0], ("serene".to_string(), 0.0));
+
+        assert!(library.find_by_modality(&GroundedTerm::new(Modality::Action, &[0.0; 3], 1.0)).is_err());
+        assert!(library.find_by_color([0.0; 3], 0.0).is_empty());
+    }
+
+    #[test]
+    fn test_find_grounded_calm_blue() {
+        let library = library();
+        let calm = library.anchor_of(Modality::Emotion, "serene").unwrap();
+        let blue = library.anchor_of(Modality::Color, "blue").unwrap();
+        assert_eq!(blue, vec![0.0, 0.0, 1.0]);
+
+        let terms = [
+            GroundedTerm::new(Modality::Emotion, &calm, 0.5),
+            GroundedTerm::new(Modality::Color, &blue, 1.0),
+        ];
+        let matches = library.find_grounded(&terms, 10).unwrap();
+
+        assert_eq!(matches[0].word, "sky");
+        assert_eq!(matches[0].matched, 2);
+        assert!(matches.iter().all(|m| m.word != "blood"));
+        assert_eq!(matches.len(), 3);
+        assert!(library.find_grounded(&[], 10).is_err());
+    }
+}
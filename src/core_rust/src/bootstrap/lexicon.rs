@@ -0,0 +1,398 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Lexicon plugin files for multimodal anchors
+//!
+//! The `add_*_anchors` methods read their word → value tables from a
+//! [`LexiconSet`] that starts out as the built-in English lexicons. User
+//! files extend or replace it, so non-English or domain-specific anchors
+//! need no recompilation:
+//!
+//! ```toml
+//! # lexicon.toml: one table per modality
+//! [color]
+//! rojo = [1.0, 0.0, 0.0]
+//!
+//! [action]
+//! correr = [0.8, 0.9, 0.5, 0.4]
+//! ```
+//!
+//! ```text
+//! # color.csv: word,value,... for a single modality
+//! rojo,1.0,0.0,0.0
+//! ```
+//!
+//! Every entry is validated (dimension count, finite values, range: colors
+//! 0.0-1.0, other modalities -1.0-1.0) before anything is applied, so a bad
+//! file leaves the lexicons untouched. Loading does not touch concepts; run
+//! `enrich_extended_multimodal` (or one `add_*_anchors`) afterwards.
+
+use super::{BootstrapError, BootstrapLibrary};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Multimodal anchor kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Modality {
+    /// RGB
+    Color,
+    /// Valence, Arousal, Dominance
+    Emotion,
+    /// Volume, Pitch, Duration
+    Sound,
+    /// Energy, Speed, Direction, Impact
+    Action,
+    /// Proximity, Verticality, Containment
+    Spatial,
+    /// Temperature, Hardness, Roughness
+    Tactile,
+}
+
+impl Modality {
+    pub const ALL: [Modality; 6] = [
+        Modality::Color,
+        Modality::Emotion,
+        Modality::Sound,
+        Modality::Action,
+        Modality::Spatial,
+        Modality::Tactile,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Modality::Color => "color",
+            Modality::Emotion => "emotion",
+            Modality::Sound => "sound",
+            Modality::Action => "action",
+            Modality::Spatial => "spatial",
+            Modality::Tactile => "tactile",
+        }
+    }
+
+    pub fn from_name(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|m| m.as_str() == s)
+    }
+
+    /// Values per entry
+    pub fn dims(&self) -> usize {
+        match self {
+            Modality::Action => 4,
+            _ => 3,
+        }
+    }
+
+    /// Allowed value range
+    pub fn range(&self) -> (f32, f32) {
+        match self {
+            Modality::Color => (0.0, 1.0),
+            _ => (-1.0, 1.0),
+        }
+    }
+
+    /// Check one entry
+    pub fn validate(&self, word: &str, values: &[f32]) -> Result<(), String> {
+        if word.is_empty() || word.chars().any(char::is_whitespace) {
+            return Err(format!("{}: invalid word '{}'", self.as_str(), word));
+        }
+        if values.len() != self.dims() {
+            return Err(format!(
+                "{} '{}': expected {} values, got {}",
+                self.as_str(), word, self.dims(), values.len()
+            ));
+        }
+        let (min, max) = self.range();
+        if let Some(v) = values.iter().find(|v| !(min..=max).contains(*v)) {
+            return Err(format!(
+                "{} '{}': value {} outside {}..{}",
+                self.as_str(), word, v, min, max
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Modality {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// How loaded entries combine with the current lexicon
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LexiconMergeMode {
+    /// Add new words; existing words keep their values
+    Extend,
+    /// Add new words; existing words take the file's values
+    #[default]
+    Override,
+    /// Drop the current entries of every modality the file contains
+    Replace,
+}
+
+/// One validated lexicon entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct LexiconEntry {
+    pub modality: Modality,
+    pub word: String,
+    pub values: Vec<f32>,
+}
+
+/// Outcome of loading a lexicon file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LexiconReport {
+    /// New words
+    pub added: usize,
+    /// Existing words whose values were replaced
+    pub overridden: usize,
+    /// Existing words left alone (Extend)
+    pub kept: usize,
+    /// Entries dropped by Replace
+    pub removed: usize,
+}
+
+/// Word → anchor values, per modality
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LexiconSet {
+    tables: HashMap<Modality, HashMap<String, Vec<f32>>>,
+}
+
+impl LexiconSet {
+    /// Built-in English lexicons
+    pub fn builtin() -> Self {
+        fn table<const N: usize>(map: HashMap<&'static str, [f32; N]>) -> HashMap<String, Vec<f32>> {
+            map.into_iter().map(|(word, values)| (word.to_string(), values.to_vec())).collect()
+        }
+
+        let mut tables = HashMap::new();
+        tables.insert(Modality::Color, table(BootstrapLibrary::get_color_lexicon()));
+        tables.insert(Modality::Emotion, table(BootstrapLibrary::get_emotion_lexicon()));
+        tables.insert(Modality::Sound, table(BootstrapLibrary::get_sound_lexicon()));
+        tables.insert(Modality::Action, table(BootstrapLibrary::get_action_lexicon()));
+        tables.insert(Modality::Spatial, table(BootstrapLibrary::get_spatial_lexicon()));
+        tables.insert(Modality::Tactile, table(BootstrapLibrary::get_tactile_lexicon()));
+        Self { tables }
+    }
+
+    /// Values of `word` in `modality`
+    pub fn get(&self, modality: Modality, word: &str) -> Option<&[f32]> {
+        self.tables.get(&modality)?.get(word).map(Vec::as_slice)
+    }
+
+    /// Fixed-size values of `word` in `modality`
+    pub fn get_array<const N: usize>(&self, modality: Modality, word: &str) -> Option<[f32; N]> {
+        self.get(modality, word).and_then(|values| values.try_into().ok())
+    }
+
+    /// Number of words in `modality`
+    pub fn len(&self, modality: Modality) -> usize {
+        self.tables.get(&modality).map_or(0, HashMap::len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tables.values().all(HashMap::is_empty)
+    }
+
+    /// Apply validated entries
+    pub fn merge(&mut self, entries: Vec<LexiconEntry>, mode: LexiconMergeMode) -> LexiconReport {
+        let mut report = LexiconReport::default();
+
+        if mode == LexiconMergeMode::Replace {
+            let mut modalities: Vec<Modality> = entries.iter().map(|e| e.modality).collect();
+            modalities.sort();
+            modalities.dedup();
+            for modality in modalities {
+                if let Some(table) = self.tables.get_mut(&modality) {
+                    report.removed += table.len();
+                    table.clear();
+                }
+            }
+        }
+
+        for entry in entries {
+            let table = self.tables.entry(entry.modality).or_default();
+            match table.get_mut(&entry.word) {
+                None => {
+                    table.insert(entry.word, entry.values);
+                    report.added += 1;
+                }
+                Some(_) if mode == LexiconMergeMode::Extend => report.kept += 1,
+                Some(values) => {
+                    *values = entry.values;
+                    report.overridden += 1;
+                }
+            }
+        }
+
+        report
+    }
+
+    /// Parse a TOML lexicon: one table per modality, `word = [values]`
+    pub fn parse_toml(text: &str) -> Result<Vec<LexiconEntry>, String> {
+        let sections: HashMap<String, HashMap<String, Vec<f32>>> =
+            toml::from_str(text).map_err(|e| e.to_string())?;
+
+        let mut entries = Vec::new();
+        for (section, words) in sections {
+            let modality = Modality::from_name(&section)
+                .ok_or_else(|| format!("unknown modality '{}'", section))?;
+            for (word, values) in words {
+                let word = word.to_lowercase();
+                modality.validate(&word, &values)?;
+                entries.push(LexiconEntry { modality, word, values });
+            }
+        }
+        Self::check_duplicates(&entries)?;
+        Ok(entries)
+    }
+
+    /// Parse a CSV lexicon for one modality: `word,value,...` per line, `#` comments
+    pub fn parse_csv(text: &str, modality: Modality) -> Result<Vec<LexiconEntry>, String> {
+        let mut entries = Vec::new();
+        for (line_num, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.split(',').map(str::trim);
+            let word = fields.next().unwrap_or_default().to_lowercase();
+            let values = fields
+                .map(|v| v.parse::<f32>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("line {}: {}", line_num + 1, e))?;
+            modality.validate(&word, &values)
+                .map_err(|e| format!("line {}: {}", line_num + 1, e))?;
+            entries.push(LexiconEntry { modality, word, values });
+        }
+        Self::check_duplicates(&entries)?;
+        Ok(entries)
+    }
+
+    fn check_duplicates(entries: &[LexiconEntry]) -> Result<(), String> {
+        let mut seen = std::collections::HashSet::new();
+        for entry in entries {
+            if !seen.insert((entry.modality, entry.word.as_str())) {
+                return Err(format!("{} '{}' defined twice", entry.modality, entry.word));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl BootstrapLibrary {
+    /// Current anchor lexicons
+    pub fn lexicons(&self) -> &LexiconSet {
+        &self.lexicons
+    }
+
+    /// Load a lexicon file (`.toml` for all modalities, `.csv` for `modality`)
+    ///
+    /// CSV files need `modality`; TOML files ignore it.
+    pub fn load_lexicon_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        modality: Option<Modality>,
+        mode: LexiconMergeMode,
+    ) -> Result<LexiconReport, BootstrapError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| BootstrapError::IoError(e.to_string()))?;
+
+        let entries = match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => LexiconSet::parse_toml(&text),
+            Some("csv") => {
+                let modality = modality.ok_or_else(|| BootstrapError::InvalidConfig(
+                    "CSV lexicons need a modality".to_string()
+                ))?;
+                LexiconSet::parse_csv(&text, modality)
+            }
+            _ => {
+                return Err(BootstrapError::InvalidConfig(
+                    format!("Unsupported lexicon file: {}", path.display())
+                ));
+            }
+        }
+        .map_err(|e| BootstrapError::ParseError(format!("{}: {}", path.display(), e)))?;
+
+        Ok(self.lexicons.merge(entries, mode))
+    }
+
+    /// Restore the built-in lexicons
+    pub fn reset_lexicons(&mut self) {
+        self.lexicons = LexiconSet::builtin();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootstrap::BootstrapConfig;
+
+    #[test]
+    fn test_parse_and_validate() {
+        let entries = LexiconSet::parse_toml(
+            "[color]\nRojo = [1.0, 0.0, 0.0]\n[action]\ncorrer = [0.8, 0.9, 0.5, 0.4]\n"
+        ).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries.iter().any(|e| e.word == "rojo" && e.modality == Modality::Color));
+
+        assert!(LexiconSet::parse_toml("[taste]\nsweet = [1.0, 0.0, 0.0]").is_err());
+        assert!(LexiconSet::parse_toml("[color]\nrojo = [1.0, 0.0]").is_err());
+        assert!(LexiconSet::parse_toml("[color]\nrojo = [1.5, 0.0, 0.0]").is_err());
+
+        let entries = LexiconSet::parse_csv("# word,v,a,d\nfeliz, 0.8, 0.6, 0.5\n", Modality::Emotion).unwrap();
+        assert_eq!(entries[0].values, vec![0.8, 0.6, 0.5]);
+        let err = LexiconSet::parse_csv("feliz,0.8,0.6,0.5\nFeliz,0.1,0.1,0.1\n", Modality::Emotion);
+        assert!(err.unwrap_err().contains("twice"));
+        assert!(LexiconSet::parse_csv("triste,x,0,0\n", Modality::Emotion).unwrap_err().starts_with("line 1"));
+    }
+
+    #[test]
+    fn test_merge_modes_and_enrichment() {
+        let dir = tempfile::tempdir().unwrap();
+        let toml_path = dir.path().join("es.toml");
+        std::fs::write(&toml_path, "[color]\nrojo = [1.0, 0.0, 0.0]\nred = [0.9, 0.1, 0.1]\n").unwrap();
+
+        let json = r#"[
+            {"word": "rojo", "id": 1, "coords": [0.1, 0.0, 0.0]},
+            {"word": "red", "id": 2, "coords": [0.2, 0.0, 0.0]},
+            {"word": "blue", "id": 3, "coords": [0.3, 0.0, 0.0]}
+        ]"#;
+        let mut library = BootstrapLibrary::new(BootstrapConfig::default());
+        library.load_bootstrap_map_str(json).unwrap();
+        let builtin_colors = library.lexicons().len(Modality::Color);
+
+        let report = library.load_lexicon_file(&toml_path, None, LexiconMergeMode::Extend).unwrap();
+        assert_eq!(report, LexiconReport { added: 1, kept: 1, ..Default::default() });
+        assert_eq!(library.lexicons().get(Modality::Color, "red"), Some(&[1.0, 0.0, 0.0][..]));
+
+        library.load_lexicon_file(&toml_path, None, LexiconMergeMode::Override).unwrap();
+        assert_eq!(library.add_color_anchors(), 3);
+        assert_eq!(library.get_concept("red").unwrap().color, Some([0.9, 0.1, 0.1]));
+        assert_eq!(library.get_concept("rojo").unwrap().color, Some([1.0, 0.0, 0.0]));
+
+        let csv_path = dir.path().join("colors.csv");
+        std::fs::write(&csv_path, "azul,0.0,0.0,1.0\n").unwrap();
+        assert!(library.load_lexicon_file(&csv_path, None, LexiconMergeMode::Replace).is_err());
+        let report = library.load_lexicon_file(&csv_path, Some(Modality::Color), LexiconMergeMode::Replace).unwrap();
+        assert_eq!(report.removed, builtin_colors + 1);
+        assert_eq!(library.lexicons().len(Modality::Color), 1);
+        assert!(library.lexicons().len(Modality::Emotion) > 0);
+
+        library.reset_lexicons();
+        assert_eq!(library.lexicons().len(Modality::Color), builtin_colors);
+    }
+}
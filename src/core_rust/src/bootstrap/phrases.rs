@@ -0,0 +1,215 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Phrase (multi-word) concepts
+//!
+//! GloVe and word2vec vocabularies store phrases as underscore-joined tokens
+//! (`new_york`). While embeddings load, every such token is registered in the
+//! [`PhraseTable`] under its word sequence (`new york`), so the Normalizer can
+//! resolve "new york" to one concept instead of two unrelated nodes.
+//! Phrases can also be mapped by hand with `add_phrase`.
+//!
+//! The table is saved with the bootstrap map (`phrases` field of the concept
+//! record a phrase resolves to) and restored by `load_bootstrap_map`.
+
+use super::{BootstrapError, BootstrapLibrary, SemanticConcept};
+use std::collections::HashMap;
+
+/// Separator of phrase tokens in embedding vocabularies
+pub const PHRASE_SEPARATOR: char = '_';
+
+/// Word sequences that resolve to a single concept
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PhraseTable {
+    /// Space-joined lowercase words → concept word
+    entries: HashMap<String, String>,
+
+    /// Longest registered phrase, in words
+    max_len: usize,
+}
+
+impl PhraseTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Split an underscore-joined token into its words
+    ///
+    /// Returns None for single words, empty parts (`_x`, `a__b`) and phrases
+    /// longer than `max_len` words.
+    pub fn split(token: &str, max_len: usize) -> Option<Vec<&str>> {
+        let words: Vec<&str> = token.split(PHRASE_SEPARATOR).collect();
+        if words.len() < 2 || words.len() > max_len || words.iter().any(|w| w.is_empty()) {
+            return None;
+        }
+        Some(words)
+    }
+
+    /// Map a word sequence to a concept; false for fewer than two words
+    pub fn insert<S: AsRef<str>>(&mut self, words: &[S], concept: &str) -> bool {
+        if words.len() < 2 {
+            return false;
+        }
+        self.max_len = self.max_len.max(words.len());
+        self.entries.insert(Self::key(words), concept.to_string());
+        true
+    }
+
+    /// Concept a word sequence resolves to
+    pub fn get<S: AsRef<str>>(&self, words: &[S]) -> Option<&str> {
+        self.entries.get(&Self::key(words)).map(String::as_str)
+    }
+
+    /// Longest phrase at the start of `words`
+    ///
+    /// # Returns
+    /// (concept word, number of words the phrase spans)
+    pub fn longest_match<S: AsRef<str>>(&self, words: &[S]) -> Option<(&str, usize)> {
+        (2..=self.max_len.min(words.len()))
+            .rev()
+            .find_map(|len| self.get(&words[..len]).map(|concept| (concept, len)))
+    }
+
+    /// Iterate (space-joined phrase, concept word)
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().map(|(phrase, concept)| (phrase.as_str(), concept.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Longest registered phrase, in words
+    pub fn max_len(&self) -> usize {
+        self.max_len
+    }
+
+    fn key<S: AsRef<str>>(words: &[S]) -> String {
+        words.iter()
+            .map(|w| w.as_ref().to_lowercase())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+impl BootstrapLibrary {
+    /// Phrase table (word sequence → concept)
+    pub fn phrases(&self) -> &PhraseTable {
+        &self.phrases
+    }
+
+    /// Map a word sequence (e.g. `["big", "apple"]`) to an existing concept
+    pub fn add_phrase(&mut self, words: &[&str], concept: &str) -> Result<(), BootstrapError> {
+        if !self.concepts.contains_key(concept) {
+            return Err(BootstrapError::NoData(format!("Unknown concept: '{}'", concept)));
+        }
+        if !self.phrases.insert(words, concept) {
+            return Err(BootstrapError::InvalidConfig(
+                "a phrase needs at least two words".to_string()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Longest phrase concept at the start of `words`
+    ///
+    /// # Returns
+    /// (concept, number of words the phrase spans)
+    pub fn match_phrase<S: AsRef<str>>(&self, words: &[S]) -> Option<(&SemanticConcept, usize)> {
+        let (concept, len) = self.phrases.longest_match(words)?;
+        self.concepts.get(concept).map(|c| (c, len))
+    }
+
+    /// Register `token` if it is an underscore-joined phrase (`detect_phrases`)
+    pub(super) fn detect_phrase(&mut self, token: &str) -> bool {
+        if !self.config.detect_phrases {
+            return false;
+        }
+        match PhraseTable::split(token, self.config.max_phrase_len) {
+            Some(words) => self.phrases.insert(&words, token),
+            None => false,
+        }
+    }
+
+    /// Phrases per concept word, for the bootstrap map
+    pub(super) fn phrases_by_concept(&self) -> HashMap<&str, Vec<&str>> {
+        let mut by_concept: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (phrase, concept) in self.phrases.iter() {
+            by_concept.entry(concept).or_default().push(phrase);
+        }
+        for phrases in by_concept.values_mut() {
+            phrases.sort_unstable();
+        }
+        by_concept
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootstrap::BootstrapConfig;
+    use std::io::Write;
+
+    #[test]
+    fn test_phrase_table() {
+        assert_eq!(PhraseTable::split("new_york", 4), Some(vec!["new", "york"]));
+        assert_eq!(PhraseTable::split("york", 4), None);
+        assert_eq!(PhraseTable::split("_york", 4), None);
+        assert_eq!(PhraseTable::split("a_b_c", 2), None);
+
+        let mut table = PhraseTable::new();
+        assert!(!table.insert(&["york"], "york"));
+        assert!(table.insert(&["new", "york"], "new_york"));
+        assert!(table.insert(&["new", "york", "city"], "new_york_city"));
+
+        assert_eq!(table.longest_match(&["New", "York", "City", "hall"]), Some(("new_york_city", 3)));
+        assert_eq!(table.longest_match(&["new", "york", "state"]), Some(("new_york", 2)));
+        assert_eq!(table.longest_match(&["new", "jersey"]), None);
+        assert_eq!(table.longest_match(&["new"]), None);
+    }
+
+    #[test]
+    fn test_phrases_detected_and_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("emb.txt");
+        let mut file = std::fs::File::create(&path).unwrap();
+        for (i, word) in ["new", "york", "new_york", "city", "big", "apple"].iter().enumerate() {
+            let v = i as f32 * 0.1;
+            writeln!(file, "{} {} {} {}", word, v, v * v, 1.0 - v).unwrap();
+        }
+        drop(file);
+
+        let config = BootstrapConfig { embedding_dim: 3, knn_k: 2, ..Default::default() };
+        let mut library = BootstrapLibrary::new(config.clone());
+        library.bootstrap_from_embeddings(&path).unwrap();
+        assert_eq!(library.phrases().len(), 1);
+        library.add_phrase(&["big", "apple"], "new_york").unwrap();
+        assert!(library.add_phrase(&["big", "apple"], "boston").is_err());
+
+        let (concept, len) = library.match_phrase(&["big", "apple", "pie"]).unwrap();
+        assert_eq!((concept.word.as_str(), len), ("new_york", 2));
+
+        let map_path = dir.path().join("map.json");
+        library.save_bootstrap_map(&map_path).unwrap();
+        let mut restored = BootstrapLibrary::new(config);
+        restored.load_bootstrap_map(&map_path).unwrap();
+        assert_eq!(restored.phrases(), library.phrases());
+    }
+}
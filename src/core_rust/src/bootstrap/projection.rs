@@ -0,0 +1,303 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Modality projection into the 8D token space
+//!
+//! Concept tokens carry the PCA coordinates on the X axes of L1-L3 only. A
+//! [`ModalityProjection`] adds multimodal anchors to chosen axes of chosen
+//! coordinate spaces when the concept's token is built, so Grid neighbor
+//! search in those spaces reflects multimodal similarity:
+//!
+//! ```text
+//! token[space][axis] += weight * anchor[component]
+//! ```
+//!
+//! The standard projection puts emotion VAD on the L4 axes, sound volume and
+//! pitch on L2 Y/Z and action energy and speed on L3 Y/Z. Spaces listed in
+//! `weave_spaces` also get KNN weaving (SimilarTo edges) among the concepts
+//! that have an anchor projected there, so the similarity reaches spreading
+//! activation as well.
+//!
+//! Anchors are usually added after `populate_grid`; `project_anchors`
+//! rebuilds the tokens of anchored concepts afterwards.
+
+use super::lexicon::Modality;
+use super::{BootstrapError, BootstrapLibrary, SemanticConcept};
+use crate::{CoordinateSpace, Token};
+
+/// Edge type for KNN edges woven in anchor spaces
+const SIMILAR_TO: u8 = crate::ConnectionType::SimilarTo as u8;
+
+/// One anchor component → one token axis
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProjectionRule {
+    pub modality: Modality,
+    /// Index into the anchor (e.g. 1 = arousal for emotion)
+    pub component: usize,
+    pub space: CoordinateSpace,
+    /// 0 = X, 1 = Y, 2 = Z
+    pub axis: usize,
+    pub weight: f32,
+}
+
+impl ProjectionRule {
+    pub fn new(modality: Modality, component: usize, space: CoordinateSpace, axis: usize) -> Self {
+        Self { modality, component, space, axis, weight: 1.0 }
+    }
+
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+/// Anchor → token coordinate mapping
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModalityProjection {
+    pub rules: Vec<ProjectionRule>,
+
+    /// Spaces to weave SimilarTo KNN edges in
+    pub weave_spaces: Vec<CoordinateSpace>,
+}
+
+impl Default for ModalityProjection {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl ModalityProjection {
+    /// No projection: tokens carry PCA coordinates only
+    pub fn none() -> Self {
+        Self { rules: Vec::new(), weave_spaces: Vec::new() }
+    }
+
+    /// Emotion → L4 (V, A, D on X, Y, Z), sound → L2 Y/Z, action → L3 Y/Z
+    pub fn standard() -> Self {
+        use CoordinateSpace::*;
+        Self {
+            rules: vec![
+                ProjectionRule::new(Modality::Emotion, 0, L4Emotional, 0),
+                ProjectionRule::new(Modality::Emotion, 1, L4Emotional, 1),
+                ProjectionRule::new(Modality::Emotion, 2, L4Emotional, 2),
+                ProjectionRule::new(Modality::Sound, 0, L2Sensory, 1),
+                ProjectionRule::new(Modality::Sound, 1, L2Sensory, 2),
+                ProjectionRule::new(Modality::Action, 0, L3Motor, 1),
+                ProjectionRule::new(Modality::Action, 1, L3Motor, 2),
+            ],
+            weave_spaces: Vec::new(),
+        }
+    }
+
+    pub fn with_weave_space(mut self, space: CoordinateSpace) -> Self {
+        if !self.weave_spaces.contains(&space) {
+            self.weave_spaces.push(space);
+        }
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for rule in &self.rules {
+            if rule.component >= rule.modality.dims() {
+                return Err(format!(
+                    "{} has {} components, rule uses component {}",
+                    rule.modality, rule.modality.dims(), rule.component
+                ));
+            }
+            if rule.axis > 2 {
+                return Err(format!("axis must be 0-2, got {}", rule.axis));
+            }
+            if !rule.weight.is_finite() {
+                return Err(format!("weight must be finite, got {}", rule.weight));
+            }
+        }
+        Ok(())
+    }
+
+    /// Add the anchors of `concept` to `token`
+    ///
+    /// # Returns
+    /// Whether any rule applied
+    pub fn project(&self, concept: &SemanticConcept, token: &mut Token) -> bool {
+        let mut applied = false;
+        for rule in &self.rules {
+            let Some(value) = concept.anchor(rule.modality).and_then(|a| a.get(rule.component)) else {
+                continue;
+            };
+            let mut coords = token.get_coordinates(rule.space);
+            coords[rule.axis] += rule.weight * value;
+            token.set_coordinates(rule.space, coords[0], coords[1], coords[2]);
+            applied = true;
+        }
+        applied
+    }
+
+    /// Whether any anchor of `concept` is projected
+    pub fn applies_to(&self, concept: &SemanticConcept) -> bool {
+        self.rules.iter().any(|rule| concept.anchor(rule.modality).is_some())
+    }
+
+    /// Whether any anchor of `concept` is projected into `space`
+    pub fn projects_into(&self, concept: &SemanticConcept, space: CoordinateSpace) -> bool {
+        self.rules.iter()
+            .any(|rule| rule.space == space && concept.anchor(rule.modality).is_some())
+    }
+}
+
+impl SemanticConcept {
+    /// Anchor values of one modality
+    pub fn anchor(&self, modality: Modality) -> Option<&[f32]> {
+        match modality {
+            Modality::Color => self.color.as_ref().map(|a| &a[..]),
+            Modality::Emotion => self.emotion.as_ref().map(|a| &a[..]),
+            Modality::Sound => self.sound.as_ref().map(|a| &a[..]),
+            Modality::Action => self.action.as_ref().map(|a| &a[..]),
+            Modality::Spatial => self.spatial.as_ref().map(|a| &a[..]),
+            Modality::Tactile => self.tactile.as_ref().map(|a| &a[..]),
+        }
+    }
+}
+
+impl BootstrapLibrary {
+    /// Grid token of a concept: PCA coordinates plus projected anchors
+    pub(super) fn concept_token(&self, concept: &SemanticConcept) -> Token {
+        let mut token = Token::from_state_f32(concept.id, &[
+            concept.coords[0], concept.coords[1], concept.coords[2],
+            0.0, 0.0, 0.0, 0.0, 0.0,
+        ]);
+        self.config.projection.project(concept, &mut token);
+        token
+    }
+
+    /// Rebuild the Grid tokens of concepts with projected anchors
+    ///
+    /// Call after enrichment (`enrich_extended_multimodal`, lexicon files);
+    /// run `weave_connections` afterwards to weave `weave_spaces`.
+    ///
+    /// # Returns
+    /// Result with number of re-projected tokens
+    pub fn project_anchors(&mut self) -> Result<usize, BootstrapError> {
+        self.config.projection.validate().map_err(BootstrapError::InvalidConfig)?;
+
+        let tokens: Vec<Token> = self.concepts.values()
+            .filter(|c| self.config.projection.applies_to(c) && self.grid.get(c.id).is_some())
+            .map(|c| self.concept_token(c))
+            .collect();
+
+        let projected = tokens.len();
+        for token in tokens {
+            self.grid.remove(token.id);
+            let _ = self.grid.add(token);
+        }
+        Ok(projected)
+    }
+
+    /// KNN edges in `weave_spaces` among concepts anchored there
+    pub(super) fn weave_anchor_spaces(&mut self) -> usize {
+        let mut edges_created = 0;
+        let k = self.config.knn_k;
+        let decay = self.config.connection_decay;
+
+        for &space in &self.config.projection.weave_spaces {
+            let members: std::collections::HashSet<crate::NodeId> = self.concepts.values()
+                .filter(|c| self.config.projection.projects_into(c, space))
+                .map(|c| c.id)
+                .collect();
+
+            for &id in &members {
+                // Non-members sit at the origin of the space; over-fetch to skip them
+                let neighbors = self.grid.find_neighbors(id, space, 100.0, self.concepts.len());
+                for &(neighbor_id, distance) in neighbors.iter()
+                    .filter(|(n, _)| *n != id && members.contains(n))
+                    .take(k)
+                {
+                    let weight = 1.0 / (1.0 + distance * decay);
+                    let edge_id = crate::Graph::compute_edge_id(id, neighbor_id, SIMILAR_TO);
+                    if let Ok(true) = self.graph.add_edge(edge_id, id, neighbor_id, SIMILAR_TO, weight, false) {
+                        edges_created += 1;
+                    }
+                }
+            }
+        }
+
+        edges_created
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootstrap::BootstrapConfig;
+
+    const MAP: &str = r#"[
+        {"word": "happy", "id": 1, "coords": [0.1, 0.0, 0.0]},
+        {"word": "joy", "id": 2, "coords": [0.9, 0.0, 0.0]},
+        {"word": "sad", "id": 3, "coords": [0.2, 0.0, 0.0]},
+        {"word": "table", "id": 4, "coords": [0.15, 0.0, 0.0]}
+    ]"#;
+
+    #[test]
+    fn test_projection_moves_tokens() {
+        let mut library = BootstrapLibrary::new(BootstrapConfig::default());
+        library.load_bootstrap_map_str(MAP).unwrap();
+        assert_eq!(library.add_emotion_anchors(), 3);
+        assert_eq!(library.project_anchors().unwrap(), 3);
+
+        let happy = library.grid().get(1).unwrap().get_coordinates(CoordinateSpace::L4Emotional);
+        assert!((happy[0] - 0.8).abs() < 1e-3);
+        assert!((happy[1] - 0.6).abs() < 1e-3);
+        let table = library.grid().get(4).unwrap().get_coordinates(CoordinateSpace::L4Emotional);
+        assert_eq!(table, [0.0; 3]);
+
+        // PCA coordinates are untouched
+        let l1 = library.grid().get(1).unwrap().get_coordinates(CoordinateSpace::L1Physical);
+        assert!((l1[0] - 0.1).abs() < 1e-3);
+
+        // happy's emotional neighbor is joy, far away in L1
+        let neighbors = library.grid().find_neighbors(1, CoordinateSpace::L4Emotional, 100.0, 1);
+        assert_eq!(neighbors[0].0, 2);
+
+        let invalid = ModalityProjection {
+            rules: vec![ProjectionRule::new(Modality::Emotion, 3, CoordinateSpace::L4Emotional, 0)],
+            weave_spaces: Vec::new(),
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_weave_anchor_spaces() {
+        let config = BootstrapConfig {
+            knn_k: 1,
+            projection: ModalityProjection::standard().with_weave_space(CoordinateSpace::L4Emotional),
+            ..Default::default()
+        };
+        let mut library = BootstrapLibrary::new(config);
+        library.load_bootstrap_map_str(MAP).unwrap();
+        library.add_emotion_anchors();
+        library.project_anchors().unwrap();
+
+        let woven = library.weave_anchor_spaces();
+        assert!(woven > 0);
+        let linked = |a: u32, b: u32| {
+            let graph = library.graph();
+            graph.get_edge(crate::Graph::compute_edge_id(a, b, SIMILAR_TO)).is_some()
+                || graph.get_edge(crate::Graph::compute_edge_id(b, a, SIMILAR_TO)).is_some()
+        };
+        assert!(linked(1, 2));
+        // "table" has no emotion and takes part in no SimilarTo edge
+        assert!(!linked(1, 4) && !linked(3, 4));
+    }
+}
@@ -0,0 +1,282 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Multimodal search ranking
+//!
+//! `semantic_search` ranks purely by spreading energy. `semantic_search_with`
+//! re-ranks the activated concepts by the multimodal anchors they share with
+//! the query:
+//!
+//! ```text
+//! score = energy * (1 + color_boost   * color_match
+//!                     + emotion_boost * emotion_match
+//!                     + action_boost  * action_match)
+//! ```
+//!
+//! Every match is in 0.0-1.0 and counts only when both concepts carry the
+//! anchor (see `enrich_multimodal` / `enrich_extended_multimodal`):
+//! - color: 1.0 when both colors fall in the same family (six hue sectors
+//!   plus black/gray/white for unsaturated colors), else 0.0
+//! - emotion: `1 - distance / emotion_radius` in VAD space, clamped at 0
+//! - action: `1 - |energy_a - energy_b| / action_radius`, clamped at 0
+//!
+//! With all boosts at 0.0 the ranking is plain spreading energy.
+
+use super::{BootstrapError, BootstrapLibrary, SemanticConcept};
+use crate::connection_v3::active_levels;
+use serde::{Deserialize, Serialize};
+
+/// Search ranking configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchConfig {
+    /// Maximum spreading depth (None = SignalConfig default)
+    pub max_depth: Option<usize>,
+
+    /// Layers activation may spread along (`connection_v3::active_levels`)
+    pub layer_mask: u8,
+
+    /// Boost for results in the query's color family
+    pub color_boost: f32,
+
+    /// Boost for results with a similar VAD emotion
+    pub emotion_boost: f32,
+
+    /// Boost for results with a similar action energy
+    pub action_boost: f32,
+
+    /// VAD distance at which the emotion match drops to 0
+    pub emotion_radius: f32,
+
+    /// Action energy difference at which the action match drops to 0
+    pub action_radius: f32,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: None,
+            layer_mask: active_levels::ALL,
+            color_boost: 0.5,
+            emotion_boost: 0.5,
+            action_boost: 0.5,
+            emotion_radius: 1.0,
+            action_radius: 0.5,
+        }
+    }
+}
+
+impl SearchConfig {
+    /// Ranking by spreading energy only, as `semantic_search` does
+    pub fn energy_only() -> Self {
+        Self {
+            color_boost: 0.0,
+            emotion_boost: 0.0,
+            action_boost: 0.0,
+            ..Default::default()
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, boost) in [
+            ("color_boost", self.color_boost),
+            ("emotion_boost", self.emotion_boost),
+            ("action_boost", self.action_boost),
+        ] {
+            if boost.is_nan() || boost < 0.0 || boost.is_infinite() {
+                return Err(format!("{} must be >= 0.0, got {}", name, boost));
+            }
+        }
+        if self.emotion_radius.is_nan() || self.emotion_radius <= 0.0 {
+            return Err(format!("emotion_radius must be > 0.0, got {}", self.emotion_radius));
+        }
+        if self.action_radius.is_nan() || self.action_radius <= 0.0 {
+            return Err(format!("action_radius must be > 0.0, got {}", self.action_radius));
+        }
+        Ok(())
+    }
+
+    /// Multiplier applied to a result's energy
+    fn boost(&self, query: &SemanticConcept, result: &SemanticConcept) -> f32 {
+        let mut boost = 1.0;
+
+        if let (Some(a), Some(b)) = (query.color, result.color) {
+            if color_family(a) == color_family(b) {
+                boost += self.color_boost;
+            }
+        }
+
+        if let (Some(a), Some(b)) = (query.emotion, result.emotion) {
+            let distance = a.iter()
+                .zip(b.iter())
+                .map(|(x, y)| (x - y) * (x - y))
+                .sum::<f32>()
+                .sqrt();
+            boost += self.emotion_boost * (1.0 - distance / self.emotion_radius).max(0.0);
+        }
+
+        if let (Some(a), Some(b)) = (query.action, result.action) {
+            let difference = (a[0] - b[0]).abs();
+            boost += self.action_boost * (1.0 - difference / self.action_radius).max(0.0);
+        }
+
+        boost
+    }
+}
+
+/// Color family of an RGB color
+///
+/// Unsaturated colors map to black (0), gray (1) or white (2); the rest to
+/// one of six 60° hue sectors centered on red, yellow, green, cyan, blue and
+/// magenta (3-8).
+fn color_family(rgb: [f32; 3]) -> u8 {
+    let max = rgb[0].max(rgb[1]).max(rgb[2]);
+    let min = rgb[0].min(rgb[1]).min(rgb[2]);
+    let chroma = max - min;
+
+    if chroma < 0.15 {
+        return if max < 0.25 {
+            0
+        } else if max > 0.85 {
+            2
+        } else {
+            1
+        };
+    }
+
+    let hue = if max == rgb[0] {
+        60.0 * ((rgb[1] - rgb[2]) / chroma).rem_euclid(6.0)
+    } else if max == rgb[1] {
+        60.0 * ((rgb[2] - rgb[0]) / chroma + 2.0)
+    } else {
+        60.0 * ((rgb[0] - rgb[1]) / chroma + 4.0)
+    };
+
+    3 + (((hue + 30.0) / 60.0) as u8 % 6)
+}
+
+impl BootstrapLibrary {
+    /// Semantic search ranked by spreading energy and shared multimodal anchors
+    ///
+    /// Activation spreads from the query as in `semantic_search_in_layers`;
+    /// every activated concept is then re-ranked by the boosts in `config`
+    /// before the list is cut to `max_results`.
+    pub fn semantic_search_with(
+        &mut self,
+        query: &str,
+        max_results: usize,
+        config: &SearchConfig,
+    ) -> Result<Vec<(String, f32)>, BootstrapError> {
+        config.validate().map_err(BootstrapError::InvalidConfig)?;
+
+        let query_concept = self.concepts.get(query)
+            .ok_or_else(|| BootstrapError::NoData(
+                format!("Unknown query word: '{}'", query)
+            ))?;
+
+        // Create SignalConfig with custom max_depth / layer mask if specified
+        let signal_config = if config.max_depth.is_some() || config.layer_mask != active_levels::ALL {
+            let defaults = crate::SignalConfig::default();
+            Some(crate::SignalConfig {
+                max_depth: config.max_depth.unwrap_or(defaults.max_depth),
+                layer_mask: config.layer_mask,
+                ..defaults
+            })
+        } else {
+            None  // Use default config (max_depth: 5)
+        };
+
+        // Run spreading activation from query node
+        let result = self.graph.spreading_activation(
+            query_concept.id,
+            1.0,  // initial energy
+            signal_config,
+        );
+
+        // Convert activated nodes to (word, score) pairs
+        let mut results: Vec<(String, f32)> = Vec::new();
+
+        for activated_node in &result.activated_nodes {
+            // Find concept matching this node ID
+            if let Some(concept) = self.concepts.values()
+                .find(|c| c.id == activated_node.node_id)
+            {
+                // Skip query word itself
+                if concept.word != query {
+                    let score = activated_node.energy * config.boost(query_concept, concept);
+                    results.push((concept.word.clone(), score));
+                }
+            }
+        }
+
+        // Sort by score (descending) and limit to max_results
+        results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        results.truncate(max_results);
+
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootstrap::BootstrapConfig;
+    use std::io::Write;
+
+    #[test]
+    fn test_color_family() {
+        assert_eq!(color_family([1.0, 0.0, 0.0]), color_family([0.72, 0.0, 0.0]));
+        assert_eq!(color_family([1.0, 0.0, 0.0]), color_family([1.0, 0.27, 0.0]));
+        assert_eq!(color_family([0.0, 0.0, 1.0]), color_family([0.0, 0.2, 1.0]));
+        assert_ne!(color_family([1.0, 0.0, 0.0]), color_family([0.0, 0.0, 1.0]));
+        assert_ne!(color_family([1.0, 1.0, 1.0]), color_family([0.0, 0.0, 0.0]));
+        assert_eq!(color_family([0.5, 0.5, 0.5]), color_family([0.75, 0.75, 0.75]));
+    }
+
+    #[test]
+    fn test_search_boosts_shared_anchors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("emb.txt");
+        let mut file = std::fs::File::create(&path).unwrap();
+        // "blood" and "ocean" sit equally close to "fire"; only "blood" shares its color family
+        writeln!(file, "fire 1.0 0.0 0.0").unwrap();
+        writeln!(file, "blood 0.9 0.1 0.0").unwrap();
+        writeln!(file, "ocean 0.9 0.0 0.1").unwrap();
+        writeln!(file, "table 0.0 0.0 1.0").unwrap();
+        drop(file);
+
+        let config = BootstrapConfig { embedding_dim: 3, knn_k: 2, ..Default::default() };
+        let mut library = BootstrapLibrary::new(config);
+        library.bootstrap_from_embeddings(&path).unwrap();
+        library.enrich_multimodal();
+
+        let plain = library.semantic_search_with("fire", 10, &SearchConfig::energy_only()).unwrap();
+        assert_eq!(plain, library.semantic_search("fire", 10, None).unwrap());
+
+        let energy = |results: &[(String, f32)], word: &str| {
+            results.iter().find(|(w, _)| w == word).map(|(_, s)| *s).unwrap()
+        };
+        let boosted = library.semantic_search_with("fire", 10, &SearchConfig::default()).unwrap();
+        assert!((energy(&boosted, "blood") - energy(&plain, "blood") * 1.5).abs() < 1e-5);
+        assert!((energy(&boosted, "ocean") - energy(&plain, "ocean")).abs() < 1e-5);
+
+        let invalid = SearchConfig { emotion_radius: 0.0, ..Default::default() };
+        assert!(matches!(
+            library.semantic_search_with("fire", 10, &invalid),
+            Err(BootstrapError::InvalidConfig(_))
+        ));
+    }
+}
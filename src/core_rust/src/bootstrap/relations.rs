@@ -0,0 +1,454 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! ConceptNet / WordNet relation import
+//!
+//! KNN weaving only produces AssociatedWith edges. This module reads lexical
+//! resources and adds Immutable Semantic edges (Synonym, Hypernym, Meronym, ...)
+//! between concepts that already exist in the BootstrapLibrary.
+//!
+//! Supported formats:
+//! - ConceptNet assertions CSV (tab-separated `uri, /r/Rel, /c/en/a, /c/en/b, {json}`)
+//!   or a short comma form `Rel,a,b[,weight]`
+//! - WordNet pointer dumps: `<pointer>\t<source>\t<target>`, lemmas may be
+//!   synset names (`dog.n.01`)
+//!
+//! Inverse relations are canonicalized (Hyponym → reversed Hypernym, Holonym →
+//! reversed Meronym). Duplicates are skipped; contradictions (Synonym vs
+//! Antonym, reversed hierarchy) keep the first relation and are reported.
+
+use super::{BootstrapError, BootstrapLibrary};
+use crate::{ConnectionType, ConnectionV3, Graph, NodeId};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// Input file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelationFormat {
+    ConceptNet,
+    WordNet,
+}
+
+/// A single parsed relation `source --relation--> target`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelationRecord {
+    pub source: String,
+    pub target: String,
+    pub relation: ConnectionType,
+    pub weight: f32,
+}
+
+/// Import settings
+#[derive(Debug, Clone)]
+pub struct RelationImportConfig {
+    /// ConceptNet language code; other languages are skipped
+    pub language: String,
+
+    /// Minimum ConceptNet assertion weight
+    pub min_weight: f32,
+
+    /// Maximum number of relations to add (0 = unlimited)
+    pub max_relations: usize,
+}
+
+impl Default for RelationImportConfig {
+    fn default() -> Self {
+        Self {
+            language: "en".to_string(),
+            min_weight: 1.0,
+            max_relations: 0,
+        }
+    }
+}
+
+/// Contradiction found during import (first relation wins)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelationConflict {
+    pub source: String,
+    pub target: String,
+    pub existing: ConnectionType,
+    pub rejected: ConnectionType,
+}
+
+/// Import summary
+#[derive(Debug, Clone, Default)]
+pub struct RelationImportReport {
+    /// Non-empty lines read
+    pub lines_read: usize,
+
+    /// Lines with an unsupported relation, language or weight
+    pub skipped: usize,
+
+    /// Relations whose concepts are not in the library
+    pub missing_concepts: usize,
+
+    /// Relations already present
+    pub duplicates: usize,
+
+    /// Rejected contradictions
+    pub conflicts: Vec<RelationConflict>,
+
+    /// Immutable connections that were added to the graph
+    pub added: Vec<ConnectionV3>,
+}
+
+/// Result of adding one relation
+#[derive(Debug, Clone)]
+pub enum RelationOutcome {
+    Added(ConnectionV3),
+    Duplicate,
+    MissingConcept,
+    Conflict(ConnectionType),
+}
+
+// ============================================================================
+// Parsing
+// ============================================================================
+
+/// Map a ConceptNet relation or WordNet pointer name to a canonical relation
+///
+/// Returns `(type, reversed)`; `reversed` means source and target must be swapped.
+pub fn map_relation(name: &str) -> Option<(ConnectionType, bool)> {
+    let name = name.trim().trim_start_matches("/r/").to_lowercase();
+    let mapped = match name.as_str() {
+        "synonym" | "synonyms" => (ConnectionType::Synonym, false),
+        "antonym" | "antonyms" => (ConnectionType::Antonym, false),
+        "isa" | "hypernym" | "instance_hypernym" => (ConnectionType::Hypernym, false),
+        "hyponym" | "instance_hyponym" => (ConnectionType::Hypernym, true),
+        "partof" | "part_holonym" | "member_holonym" | "substance_holonym" => {
+            (ConnectionType::Meronym, false)
+        }
+        "hasa" | "part_meronym" | "member_meronym" | "substance_meronym" => {
+            (ConnectionType::Meronym, true)
+        }
+        "similarto" | "similar_to" | "similar" => (ConnectionType::Similar, false),
+        "mannerof" | "troponym" => (ConnectionType::Troponym, false),
+        "entails" | "entailment" => (ConnectionType::Entailment, false),
+        "derivedfrom" | "derivationally_related_form" => (ConnectionType::Derivation, false),
+        _ => return None,
+    };
+    Some(mapped)
+}
+
+/// Extract a word from `/c/en/word/n/...`, `dog.n.01` or a plain lemma
+fn normalize_term(term: &str, language: &str) -> Option<String> {
+    let term = term.trim();
+    let word = if let Some(rest) = term.strip_prefix("/c/") {
+        let mut parts = rest.split('/');
+        if parts.next()? != language {
+            return None;
+        }
+        parts.next()?
+    } else {
+        // WordNet synset name: lemma.pos.nn
+        match term.rsplitn(3, '.').collect::<Vec<_>>().as_slice() {
+            [num, pos, lemma] if num.chars().all(|c| c.is_ascii_digit()) && pos.len() == 1 => lemma,
+            _ => term,
+        }
+    };
+
+    let word = word.to_lowercase();
+    if word.is_empty() {
+        None
+    } else {
+        Some(word)
+    }
+}
+
+fn make_record(relation: &str, source: &str, target: &str, weight: f32, language: &str) -> Option<RelationRecord> {
+    let (relation, reversed) = map_relation(relation)?;
+    let source = normalize_term(source, language)?;
+    let target = normalize_term(target, language)?;
+    let (source, target) = if reversed { (target, source) } else { (source, target) };
+    Some(RelationRecord {
+        source,
+        target,
+        relation,
+        weight,
+    })
+}
+
+/// Parse a ConceptNet line (assertions CSV or `Rel,a,b[,weight]`)
+pub fn parse_conceptnet_line(line: &str, config: &RelationImportConfig) -> Option<RelationRecord> {
+    let fields: Vec<&str> = line.split('\t').collect();
+    let (relation, source, target, weight) = if fields.len() >= 4 {
+        let weight = fields
+            .get(4)
+            .and_then(|json| serde_json::from_str::<serde_json::Value>(json).ok())
+            .and_then(|v| v.get("weight").and_then(|w| w.as_f64()))
+            .unwrap_or(1.0) as f32;
+        (fields[1], fields[2], fields[3], weight)
+    } else {
+        let fields: Vec<&str> = line.split(',').collect();
+        if fields.len() < 3 {
+            return None;
+        }
+        let weight = fields.get(3).and_then(|w| w.trim().parse().ok()).unwrap_or(1.0);
+        (fields[0], fields[1], fields[2], weight)
+    };
+
+    if weight < config.min_weight {
+        return None;
+    }
+    make_record(relation, source, target, weight, &config.language)
+}
+
+/// Parse a WordNet pointer line `<pointer> <source> <target>`
+pub fn parse_wordnet_line(line: &str) -> Option<RelationRecord> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() < 3 {
+        return None;
+    }
+    make_record(fields[0], fields[1], fields[2], 1.0, "")
+}
+
+// ============================================================================
+// Import
+// ============================================================================
+
+fn is_symmetric(relation: ConnectionType) -> bool {
+    matches!(
+        relation,
+        ConnectionType::Synonym | ConnectionType::Antonym | ConnectionType::Similar | ConnectionType::Derivation
+    )
+}
+
+/// Relation types that contradict `relation` on the same (unordered) pair
+fn contradictions(relation: ConnectionType) -> &'static [ConnectionType] {
+    match relation {
+        ConnectionType::Synonym | ConnectionType::Similar => &[ConnectionType::Antonym],
+        ConnectionType::Antonym => &[ConnectionType::Synonym, ConnectionType::Similar],
+        _ => &[],
+    }
+}
+
+impl BootstrapLibrary {
+    fn has_relation(&self, from: NodeId, to: NodeId, relation: ConnectionType) -> bool {
+        let edge_type = relation as u8;
+        let exists = |a, b| {
+            self.graph
+                .get_edge(Graph::compute_edge_id(a, b, edge_type))
+                .is_some_and(|e| e.edge_type == edge_type)
+        };
+        exists(from, to) || (is_symmetric(relation) && exists(to, from))
+    }
+
+    /// Add one Immutable semantic relation between existing concepts
+    pub fn add_relation(&mut self, record: &RelationRecord) -> RelationOutcome {
+        let (Some(source), Some(target)) = (
+            self.concepts.get(&record.source).map(|c| c.id),
+            self.concepts.get(&record.target).map(|c| c.id),
+        ) else {
+            return RelationOutcome::MissingConcept;
+        };
+        if source == target {
+            return RelationOutcome::Duplicate;
+        }
+
+        let symmetric = is_symmetric(record.relation);
+        let (from, to) = if symmetric { (source.min(target), source.max(target)) } else { (source, target) };
+
+        if self.has_relation(from, to, record.relation) {
+            return RelationOutcome::Duplicate;
+        }
+        for &other in contradictions(record.relation) {
+            if self.has_relation(from, to, other) {
+                return RelationOutcome::Conflict(other);
+            }
+        }
+        // Reversed hierarchy (a IsA b vs b IsA a)
+        if !symmetric && self.has_relation(to, from, record.relation) {
+            return RelationOutcome::Conflict(record.relation);
+        }
+
+        let edge_type = record.relation as u8;
+        let edge_id = Graph::compute_edge_id(from, to, edge_type);
+        match self.graph.add_edge(edge_id, from, to, edge_type, 1.0, symmetric) {
+            Ok(true) => {}
+            // Hash collision with an edge of another type
+            _ => return RelationOutcome::Duplicate,
+        }
+
+        let mut connection = ConnectionV3::new(from, to);
+        connection.set_connection_type(record.relation);
+        RelationOutcome::Added(connection)
+    }
+
+    /// Add parsed relations, collecting statistics
+    pub fn import_relation_records<I>(&mut self, records: I, config: &RelationImportConfig) -> RelationImportReport
+    where
+        I: IntoIterator<Item = RelationRecord>,
+    {
+        let mut report = RelationImportReport::default();
+        for record in records {
+            report.lines_read += 1;
+            self.apply_relation(&record, &mut report);
+            if config.max_relations > 0 && report.added.len() >= config.max_relations {
+                break;
+            }
+        }
+        report
+    }
+
+    /// Import relations from a ConceptNet or WordNet file
+    ///
+    /// # Returns
+    /// Result with import report
+    pub fn import_relations<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        format: RelationFormat,
+        config: &RelationImportConfig,
+    ) -> Result<RelationImportReport, BootstrapError> {
+        let file = File::open(path.as_ref())
+            .map_err(|e| BootstrapError::IoError(format!("Failed to open relations file: {}", e)))?;
+        let reader = BufReader::new(file);
+
+        let mut report = RelationImportReport::default();
+        for line in reader.lines() {
+            let line = line.map_err(|e| BootstrapError::IoError(e.to_string()))?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            report.lines_read += 1;
+
+            let record = match format {
+                RelationFormat::ConceptNet => parse_conceptnet_line(line, config),
+                RelationFormat::WordNet => parse_wordnet_line(line),
+            };
+            match record {
+                Some(record) => self.apply_relation(&record, &mut report),
+                None => report.skipped += 1,
+            }
+
+            if config.max_relations > 0 && report.added.len() >= config.max_relations {
+                break;
+            }
+        }
+
+        Ok(report)
+    }
+
+    fn apply_relation(&mut self, record: &RelationRecord, report: &mut RelationImportReport) {
+        match self.add_relation(record) {
+            RelationOutcome::Added(connection) => report.added.push(connection),
+            RelationOutcome::Duplicate => report.duplicates += 1,
+            RelationOutcome::MissingConcept => report.missing_concepts += 1,
+            RelationOutcome::Conflict(existing) => report.conflicts.push(RelationConflict {
+                source: record.source.clone(),
+                target: record.target.clone(),
+                existing,
+                rejected: record.relation,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootstrap::BootstrapConfig;
+    use crate::ConnectionMutability;
+    use std::io::Write;
+
+    fn library() -> BootstrapLibrary {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("emb.txt");
+        let mut file = File::create(&path).unwrap();
+        for (i, word) in ["car", "automobile", "vehicle", "wheel", "big", "small"].iter().enumerate() {
+            let v = i as f32 * 0.1;
+            writeln!(file, "{} {} {} {}", word, v, v * v, 1.0 - v).unwrap();
+        }
+
+        let config = BootstrapConfig {
+            embedding_dim: 3,
+            knn_k: 2,
+            ..Default::default()
+        };
+        let mut library = BootstrapLibrary::new(config);
+        library.bootstrap_from_embeddings(&path).unwrap();
+        library
+    }
+
+    #[test]
+    fn test_parse_relation_lines() {
+        let config = RelationImportConfig::default();
+
+        let line = "/a/[...]\t/r/IsA\t/c/en/car/n\t/c/en/vehicle\t{\"weight\": 2.0}";
+        let record = parse_conceptnet_line(line, &config).unwrap();
+        assert_eq!(record.relation, ConnectionType::Hypernym);
+        assert_eq!((record.source.as_str(), record.target.as_str()), ("car", "vehicle"));
+        assert_eq!(record.weight, 2.0);
+
+        // Other language and low weight are skipped
+        assert!(parse_conceptnet_line("/a/x\t/r/IsA\t/c/de/auto\t/c/de/fahrzeug\t{}", &config).is_none());
+        assert!(parse_conceptnet_line("IsA,car,vehicle,0.5", &config).is_none());
+
+        // HasA is canonicalized to a reversed Meronym
+        let record = parse_conceptnet_line("HasA,car,wheel", &config).unwrap();
+        assert_eq!(record.relation, ConnectionType::Meronym);
+        assert_eq!((record.source.as_str(), record.target.as_str()), ("wheel", "car"));
+
+        let record = parse_wordnet_line("hyponym\tvehicle.n.01\tcar.n.01").unwrap();
+        assert_eq!(record.relation, ConnectionType::Hypernym);
+        assert_eq!((record.source.as_str(), record.target.as_str()), ("car", "vehicle"));
+        assert!(parse_wordnet_line("also_see\ta\tb").is_none());
+    }
+
+    #[test]
+    fn test_import_relations_file() {
+        let mut library = library();
+        let edges_before = library.graph().edge_count();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("wordnet.tsv");
+        std::fs::write(
+            &path,
+            "# pointer source target\n\
+             synonym\tcar\tautomobile\n\
+             synonym\tautomobile\tcar\n\
+             hypernym\tcar\tvehicle\n\
+             part_meronym\tcar\twheel\n\
+             antonym\tbig\tsmall\n\
+             synonym\tsmall\tbig\n\
+             hyponym\tcar\tvehicle\n\
+             hypernym\tcar\tunicorn\n\
+             see_also\tcar\tbig\n",
+        )
+        .unwrap();
+
+        let report = library
+            .import_relations(&path, RelationFormat::WordNet, &RelationImportConfig::default())
+            .unwrap();
+
+        assert_eq!(report.lines_read, 9);
+        assert_eq!(report.added.len(), 4);
+        assert_eq!(report.duplicates, 1);
+        assert_eq!(report.missing_concepts, 1);
+        assert_eq!(report.skipped, 1);
+        assert_eq!(report.conflicts.len(), 2);
+        assert_eq!(report.conflicts[0].existing, ConnectionType::Antonym);
+        assert_eq!(report.conflicts[1].rejected, ConnectionType::Hypernym);
+
+        assert!(report
+            .added
+            .iter()
+            .all(|c| c.mutability == ConnectionMutability::Immutable as u8 && !c.can_modify()));
+        assert_eq!(library.graph().edge_count(), edges_before + 4);
+    }
+}
@@ -0,0 +1,449 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Remote / on-the-fly embeddings for unknown words
+//!
+//! Static GloVe files never cover everything. This module fetches embeddings
+//! for unknown words from an [`EmbeddingProvider`], projects them with the
+//! saved PCAModel and inserts them via [`BootstrapLibrary::add_concept`].
+//!
+//! Providers:
+//! - `HttpEmbeddingProvider` (feature `remote-embeddings`): OpenAI-compatible `/embeddings`
+//! - `OnnxEmbeddingProvider` (feature `onnx`): local ONNX model + vocabulary file
+
+use super::{BootstrapError, BootstrapLibrary};
+use crate::NodeId;
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Source of word embeddings
+#[async_trait]
+pub trait EmbeddingProvider: Send + Sync {
+    /// Provider name (for logs)
+    fn name(&self) -> &str;
+
+    /// Embedding dimension (must match BootstrapConfig::embedding_dim)
+    fn dim(&self) -> usize;
+
+    /// Embed a batch of words; result has one vector per input word, in order
+    async fn embed(&self, words: &[String]) -> Result<Vec<Vec<f32>>, BootstrapError>;
+}
+
+/// RemoteEmbedder configuration
+#[derive(Debug, Clone)]
+pub struct RemoteEmbedderConfig {
+    /// Maximum words per provider request
+    pub batch_size: usize,
+
+    /// Lowercase words before lookup (GloVe vocabularies are lowercase)
+    pub lowercase: bool,
+
+    /// Remember words the provider failed on and don't retry them
+    pub remember_failures: bool,
+}
+
+impl Default for RemoteEmbedderConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 32,
+            lowercase: true,
+            remember_failures: true,
+        }
+    }
+}
+
+/// Fetches embeddings for unknown words and inserts them into BootstrapLibrary
+pub struct RemoteEmbedder {
+    provider: Arc<dyn EmbeddingProvider>,
+    config: RemoteEmbedderConfig,
+    failed: RwLock<HashSet<String>>,
+}
+
+impl RemoteEmbedder {
+    pub fn new(provider: Arc<dyn EmbeddingProvider>, config: RemoteEmbedderConfig) -> Self {
+        Self {
+            provider,
+            config,
+            failed: RwLock::new(HashSet::new()),
+        }
+    }
+
+    /// Resolve words missing from the library
+    ///
+    /// The library lock is never held across provider calls.
+    ///
+    /// # Returns
+    /// Result with (word, NodeId) for every newly added concept
+    pub async fn resolve_unknown(
+        &self,
+        bootstrap: &RwLock<BootstrapLibrary>,
+        words: &[String],
+    ) -> Result<Vec<(String, NodeId)>, BootstrapError> {
+        let missing: Vec<String> = {
+            let library = bootstrap.read();
+            let failed = self.failed.read();
+            let mut seen = HashSet::new();
+            words
+                .iter()
+                .map(|w| if self.config.lowercase { w.to_lowercase() } else { w.clone() })
+                .filter(|w| !w.is_empty())
+                .filter(|w| library.get_concept(w).is_none() && !failed.contains(w))
+                .filter(|w| seen.insert(w.clone()))
+                .collect()
+        };
+
+        let mut added = Vec::new();
+        for batch in missing.chunks(self.config.batch_size.max(1)) {
+            let embeddings = match self.provider.embed(batch).await {
+                Ok(e) if e.len() == batch.len() => e,
+                Ok(e) => {
+                    return Err(BootstrapError::ParseError(format!(
+                        "Provider '{}' returned {} embeddings for {} words",
+                        self.provider.name(),
+                        e.len(),
+                        batch.len()
+                    )));
+                }
+                Err(e) => {
+                    if self.config.remember_failures {
+                        self.failed.write().extend(batch.iter().cloned());
+                    }
+                    return Err(e);
+                }
+            };
+
+            let mut library = bootstrap.write();
+            for (word, embedding) in batch.iter().zip(embeddings) {
+                match library.add_concept(word, embedding) {
+                    Ok(id) => added.push((word.clone(), id)),
+                    Err(BootstrapError::DimensionMismatch { .. }) if self.config.remember_failures => {
+                        self.failed.write().insert(word.clone());
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        Ok(added)
+    }
+
+    /// Number of words remembered as failed
+    pub fn failed_count(&self) -> usize {
+        self.failed.read().len()
+    }
+
+    /// Forget failed words so they are retried
+    pub fn clear_failures(&self) {
+        self.failed.write().clear();
+    }
+}
+
+// ============================================================================
+// HTTP Provider (OpenAI-compatible /embeddings)
+// ============================================================================
+
+/// Configuration for HttpEmbeddingProvider
+#[cfg(feature = "remote-embeddings")]
+#[derive(Debug, Clone)]
+pub struct HttpEmbeddingConfig {
+    /// Full endpoint URL, e.g. "http://localhost:11434/v1/embeddings"
+    pub url: String,
+
+    /// Model name
+    pub model: String,
+
+    /// Bearer token (optional)
+    pub api_key: Option<String>,
+
+    /// Embedding dimension returned by the model
+    pub dim: usize,
+
+    /// Request timeout in milliseconds
+    pub timeout_ms: u64,
+}
+
+/// Embedding provider for OpenAI-compatible HTTP services
+#[cfg(feature = "remote-embeddings")]
+pub struct HttpEmbeddingProvider {
+    config: HttpEmbeddingConfig,
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "remote-embeddings")]
+impl HttpEmbeddingProvider {
+    pub fn new(config: HttpEmbeddingConfig) -> Result<Self, BootstrapError> {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_millis(config.timeout_ms))
+            .build()
+            .map_err(|e| BootstrapError::IoError(e.to_string()))?;
+        Ok(Self { config, client })
+    }
+
+    /// Parse `{"data": [{"index": 0, "embedding": [...]}, ...]}`
+    fn parse_response(body: &serde_json::Value, expected: usize) -> Result<Vec<Vec<f32>>, BootstrapError> {
+        let data = body
+            .get("data")
+            .and_then(|d| d.as_array())
+            .ok_or_else(|| BootstrapError::ParseError("Response has no 'data' array".to_string()))?;
+
+        let mut result = vec![Vec::new(); expected];
+        for (pos, item) in data.iter().enumerate() {
+            let index = item.get("index").and_then(|i| i.as_u64()).map(|i| i as usize).unwrap_or(pos);
+            let embedding: Vec<f32> = item
+                .get("embedding")
+                .and_then(|e| serde_json::from_value(e.clone()).ok())
+                .ok_or_else(|| BootstrapError::ParseError(format!("Item {} has no embedding", pos)))?;
+            if index < expected {
+                result[index] = embedding;
+            }
+        }
+
+        if result.iter().any(|e| e.is_empty()) {
+            return Err(BootstrapError::ParseError("Response is missing embeddings".to_string()));
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(feature = "remote-embeddings")]
+#[async_trait]
+impl EmbeddingProvider for HttpEmbeddingProvider {
+    fn name(&self) -> &str {
+        "http"
+    }
+
+    fn dim(&self) -> usize {
+        self.config.dim
+    }
+
+    async fn embed(&self, words: &[String]) -> Result<Vec<Vec<f32>>, BootstrapError> {
+        let mut request = self.client.post(&self.config.url).json(&serde_json::json!({
+            "model": self.config.model,
+            "input": words,
+        }));
+        if let Some(key) = &self.config.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| BootstrapError::IoError(format!("Embedding request failed: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(BootstrapError::IoError(format!(
+                "Embedding service returned {}",
+                response.status()
+            )));
+        }
+
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| BootstrapError::ParseError(e.to_string()))?;
+        Self::parse_response(&body, words.len())
+    }
+}
+
+// ============================================================================
+// ONNX Provider (local model)
+// ============================================================================
+
+/// Embedding provider running a local ONNX model
+///
+/// The model takes an int64 tensor of token IDs with shape `[N]` and returns
+/// a float tensor `[N, dim]`. Token IDs come from a vocabulary file with one
+/// word per line (line number = ID). Words outside the vocabulary fail.
+/// Requires the ONNX Runtime shared library (`ORT_DYLIB_PATH`).
+#[cfg(feature = "onnx")]
+pub struct OnnxEmbeddingProvider {
+    session: parking_lot::Mutex<ort::session::Session>,
+    vocab: std::collections::HashMap<String, i64>,
+    dim: usize,
+}
+
+#[cfg(feature = "onnx")]
+impl OnnxEmbeddingProvider {
+    pub fn new<P: AsRef<std::path::Path>>(model_path: P, vocab_path: P, dim: usize) -> Result<Self, BootstrapError> {
+        let session = ort::session::Session::builder()
+            .and_then(|b| b.commit_from_file(model_path.as_ref()))
+            .map_err(|e| BootstrapError::IoError(format!("Failed to load ONNX model: {}", e)))?;
+
+        let vocab = std::fs::read_to_string(vocab_path.as_ref())
+            .map_err(|e| BootstrapError::IoError(e.to_string()))?
+            .lines()
+            .enumerate()
+            .map(|(i, w)| (w.trim().to_string(), i as i64))
+            .collect();
+
+        Ok(Self {
+            session: parking_lot::Mutex::new(session),
+            vocab,
+            dim,
+        })
+    }
+}
+
+#[cfg(feature = "onnx")]
+#[async_trait]
+impl EmbeddingProvider for OnnxEmbeddingProvider {
+    fn name(&self) -> &str {
+        "onnx"
+    }
+
+    fn dim(&self) -> usize {
+        self.dim
+    }
+
+    async fn embed(&self, words: &[String]) -> Result<Vec<Vec<f32>>, BootstrapError> {
+        let ids: Vec<i64> = words
+            .iter()
+            .map(|w| {
+                self.vocab
+                    .get(w)
+                    .copied()
+                    .ok_or_else(|| BootstrapError::NoData(format!("'{}' not in ONNX vocabulary", w)))
+            })
+            .collect::<Result<_, _>>()?;
+
+        let input = ort::value::Tensor::from_array(([ids.len()], ids))
+            .map_err(|e| BootstrapError::PcaError(e.to_string()))?;
+
+        let mut session = self.session.lock();
+        let outputs = session
+            .run(ort::inputs![input])
+            .map_err(|e| BootstrapError::PcaError(format!("ONNX inference failed: {}", e)))?;
+        let (_shape, data) = outputs[0]
+            .try_extract_tensor::<f32>()
+            .map_err(|e| BootstrapError::PcaError(e.to_string()))?;
+
+        if data.len() != words.len() * self.dim {
+            return Err(BootstrapError::DimensionMismatch {
+                expected: words.len() * self.dim,
+                got: data.len(),
+            });
+        }
+
+        Ok(data.chunks(self.dim).map(|c| c.to_vec()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootstrap::BootstrapConfig;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Deterministic provider: embedding derived from word length
+    struct MockProvider {
+        calls: AtomicUsize,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl EmbeddingProvider for MockProvider {
+        fn name(&self) -> &str {
+            "mock"
+        }
+
+        fn dim(&self) -> usize {
+            3
+        }
+
+        async fn embed(&self, words: &[String]) -> Result<Vec<Vec<f32>>, BootstrapError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if self.fail {
+                return Err(BootstrapError::IoError("service down".to_string()));
+            }
+            Ok(words.iter().map(|w| vec![w.len() as f32 * 0.1; 3]).collect())
+        }
+    }
+
+    fn library() -> RwLock<BootstrapLibrary> {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("emb.txt");
+        let mut file = std::fs::File::create(&path).unwrap();
+        for i in 0..5 {
+            let v = i as f32 * 0.1;
+            writeln!(file, "word{} {} {} {}", i, v, v, v).unwrap();
+        }
+
+        let mut config = BootstrapConfig::default();
+        config.embedding_dim = 3;
+        config.knn_k = 2;
+        let mut library = BootstrapLibrary::new(config);
+        library.bootstrap_from_embeddings(&path).unwrap();
+        RwLock::new(library)
+    }
+
+    #[tokio::test]
+    async fn test_resolve_unknown_words() {
+        let library = library();
+        let provider = Arc::new(MockProvider { calls: AtomicUsize::new(0), fail: false });
+        let config = RemoteEmbedderConfig { batch_size: 2, ..Default::default() };
+        let embedder = RemoteEmbedder::new(provider.clone(), config);
+
+        let words: Vec<String> = ["word0", "Robot", "robot", "drone", "lidar"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let added = embedder.resolve_unknown(&library, &words).await.unwrap();
+
+        // word0 known, Robot/robot deduplicated → 3 new concepts in 2 batches
+        assert_eq!(added.len(), 3);
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 2);
+        assert!(library.read().get_concept("robot").is_some());
+
+        // Nothing left to fetch
+        assert!(embedder.resolve_unknown(&library, &words).await.unwrap().is_empty());
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_remembers_failures() {
+        let library = library();
+        let provider = Arc::new(MockProvider { calls: AtomicUsize::new(0), fail: true });
+        let embedder = RemoteEmbedder::new(provider.clone(), RemoteEmbedderConfig::default());
+
+        let words = vec!["unknown".to_string()];
+        assert!(embedder.resolve_unknown(&library, &words).await.is_err());
+        assert_eq!(embedder.failed_count(), 1);
+
+        // Failed word is not retried
+        assert!(embedder.resolve_unknown(&library, &words).await.unwrap().is_empty());
+        assert_eq!(provider.calls.load(Ordering::SeqCst), 1);
+
+        embedder.clear_failures();
+        assert_eq!(embedder.failed_count(), 0);
+    }
+
+    #[cfg(feature = "remote-embeddings")]
+    #[test]
+    fn test_http_parse_response() {
+        let body = serde_json::json!({"data": [
+            {"index": 1, "embedding": [0.4, 0.5]},
+            {"index": 0, "embedding": [0.1, 0.2]},
+        ]});
+        let parsed = HttpEmbeddingProvider::parse_response(&body, 2).unwrap();
+        assert_eq!(parsed[0], vec![0.1, 0.2]);
+        assert_eq!(parsed[1], vec![0.4, 0.5]);
+
+        assert!(HttpEmbeddingProvider::parse_response(&serde_json::json!({}), 1).is_err());
+    }
+}
@@ -0,0 +1,802 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! C ABI v1.0
+//!
+//! Stable `extern "C"` layer for embedding the core from C, C++, Go (cgo)
+//! or Swift. The header `include/neurograph_ffi.h` is generated by cbindgen
+//! from this file when building with `--features c-api`.
+//!
+//! Conventions:
+//! - Every object is an opaque handle created by `ng_*_new` and released by
+//!   the matching `ng_*_free` (passing NULL to a free function is a no-op)
+//! - Fallible calls return `NgStatus`; details via `ng_last_error()`
+//! - Strings returned by the library are owned by the caller and must be
+//!   released with `ng_string_free`
+//! - Panics never cross the boundary; they surface as `NG_STATUS_PANIC`
+//! - Handles are not thread-safe; synchronize externally when sharing one
+//!
+//! ```c
+//! NgGateway *gw = ng_gateway_new(NULL);
+//! uint64_t id;
+//! if (ng_gateway_inject_text(gw, "what is a cat?", &id) == NG_STATUS_OK) {
+//!     char *json = NULL;
+//!     while (ng_gateway_next_signal(gw, &json) == NG_STATUS_OK) {
+//!         puts(json);
+//!         ng_string_free(json);
+//!     }
+//! }
+//! ng_gateway_free(gw);
+//! ```
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use tokio::sync::mpsc;
+
+use crate::action_executor::ActionResult;
+use crate::bootstrap::{BootstrapConfig, BootstrapLibrary};
+use crate::graph::Direction;
+use crate::{
+    CoordinateSpace, Gateway, GatewayConfig, Graph, InputSignal, ProcessedSignal,
+    ResultReceiver, SignalSource, Token,
+};
+
+/// C ABI version; bumped on any breaking change to the exported surface
+pub const NG_ABI_VERSION: u32 = 1;
+
+/// Signal queue depth for embedded Gateways
+const GATEWAY_QUEUE_SIZE: usize = 1024;
+
+// ============================================================================
+// Status + errors
+// ============================================================================
+
+/// Result code of fallible calls
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NgStatus {
+    Ok = 0,
+    /// A required pointer argument was NULL
+    NullPointer = 1,
+    /// Argument out of range or not valid UTF-8/JSON
+    InvalidArgument = 2,
+    /// Requested item does not exist (or queue is empty)
+    NotFound = 3,
+    /// Result not available yet
+    Pending = 4,
+    /// Operation failed; see `ng_last_error()`
+    Error = 5,
+    /// A panic was caught at the boundary
+    Panic = 6,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: impl Into<String>) {
+    let message = CString::new(message.into().replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(message));
+}
+
+fn fail(status: NgStatus, message: impl Into<String>) -> NgStatus {
+    set_last_error(message);
+    status
+}
+
+/// Run `f`, converting a panic into `NgStatus::Panic`
+fn guard(f: impl FnOnce() -> NgStatus) -> NgStatus {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(status) => status,
+        Err(payload) => fail(NgStatus::Panic, crate::panic_handler::extract_panic_message(&payload)),
+    }
+}
+
+/// Run a handle constructor, converting a panic into NULL
+fn guard_new<T>(f: impl FnOnce() -> Option<T>) -> *mut T {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Some(value)) => Box::into_raw(Box::new(value)),
+        Ok(None) => ptr::null_mut(),
+        Err(payload) => {
+            set_last_error(crate::panic_handler::extract_panic_message(&payload));
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Borrow a C string as `&str`
+///
+/// # Safety
+/// `s` must be NULL or a valid NUL-terminated string.
+unsafe fn str_arg<'a>(s: *const c_char) -> Result<&'a str, NgStatus> {
+    if s.is_null() {
+        return Err(fail(NgStatus::NullPointer, "string argument is NULL"));
+    }
+    CStr::from_ptr(s)
+        .to_str()
+        .map_err(|_| fail(NgStatus::InvalidArgument, "string argument is not valid UTF-8"))
+}
+
+/// Hand an owned string to the caller
+///
+/// # Safety
+/// `out` must be a valid pointer.
+unsafe fn write_string(out: *mut *mut c_char, s: String) -> NgStatus {
+    match CString::new(s) {
+        Ok(s) => {
+            *out = s.into_raw();
+            NgStatus::Ok
+        }
+        Err(_) => fail(NgStatus::Error, "string contains interior NUL"),
+    }
+}
+
+fn coordinate_space(space: u8) -> Option<CoordinateSpace> {
+    Some(match space {
+        0 => CoordinateSpace::L1Physical,
+        1 => CoordinateSpace::L2Sensory,
+        2 => CoordinateSpace::L3Motor,
+        3 => CoordinateSpace::L4Emotional,
+        4 => CoordinateSpace::L5Cognitive,
+        5 => CoordinateSpace::L6Social,
+        6 => CoordinateSpace::L7Temporal,
+        7 => CoordinateSpace::L8Abstract,
+        _ => return None,
+    })
+}
+
+/// ABI version of the loaded library (compare against `NG_ABI_VERSION`)
+#[no_mangle]
+pub extern "C" fn ng_abi_version() -> u32 {
+    NG_ABI_VERSION
+}
+
+/// Last error message on the calling thread, or NULL
+///
+/// The pointer stays valid until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn ng_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| e.borrow().as_ref().map_or(ptr::null(), |s| s.as_ptr()))
+}
+
+/// Release a string returned by the library
+///
+/// # Safety
+/// `s` must be NULL or a string returned by this library, freed at most once.
+#[no_mangle]
+pub unsafe extern "C" fn ng_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+// ============================================================================
+// Token
+// ============================================================================
+
+/// Opaque token handle
+pub struct NgToken {
+    inner: Token,
+}
+
+/// Create a token with zeroed coordinates
+#[no_mangle]
+pub extern "C" fn ng_token_new(id: u32) -> *mut NgToken {
+    guard_new(|| Some(NgToken { inner: Token::new(id) }))
+}
+
+/// Create a token from an 8D state (one value per coordinate space)
+///
+/// # Safety
+/// `state` must point to 8 readable floats.
+#[no_mangle]
+pub unsafe extern "C" fn ng_token_from_state(id: u32, state: *const f32) -> *mut NgToken {
+    if state.is_null() {
+        set_last_error("state is NULL");
+        return ptr::null_mut();
+    }
+    let state: [f32; 8] = ptr::read_unaligned(state as *const [f32; 8]);
+    guard_new(|| Some(NgToken { inner: Token::from_state_f32(id, &state) }))
+}
+
+/// Release a token
+///
+/// # Safety
+/// `token` must be NULL or a handle from `ng_token_*`, freed at most once.
+#[no_mangle]
+pub unsafe extern "C" fn ng_token_free(token: *mut NgToken) {
+    if !token.is_null() {
+        drop(Box::from_raw(token));
+    }
+}
+
+/// Token ID (0 for NULL)
+///
+/// # Safety
+/// `token` must be NULL or a live token handle.
+#[no_mangle]
+pub unsafe extern "C" fn ng_token_id(token: *const NgToken) -> u32 {
+    token.as_ref().map_or(0, |t| t.inner.id)
+}
+
+/// Copy the 8D state into `out`
+///
+/// # Safety
+/// `token` must be a live handle; `out` must point to 8 writable floats.
+#[no_mangle]
+pub unsafe extern "C" fn ng_token_state(token: *const NgToken, out: *mut f32) -> NgStatus {
+    guard(|| {
+        let Some(token) = token.as_ref() else {
+            return fail(NgStatus::NullPointer, "token is NULL");
+        };
+        if out.is_null() {
+            return fail(NgStatus::NullPointer, "out is NULL");
+        }
+        ptr::write_unaligned(out as *mut [f32; 8], token.inner.to_state_f32());
+        NgStatus::Ok
+    })
+}
+
+/// Set `[x, y, z]` in one coordinate space (0-7)
+///
+/// # Safety
+/// `token` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn ng_token_set_coordinates(
+    token: *mut NgToken,
+    space: u8,
+    x: f32,
+    y: f32,
+    z: f32,
+) -> NgStatus {
+    guard(|| {
+        let Some(token) = token.as_mut() else {
+            return fail(NgStatus::NullPointer, "token is NULL");
+        };
+        let Some(space) = coordinate_space(space) else {
+            return fail(NgStatus::InvalidArgument, "space must be 0-7");
+        };
+        token.inner.set_coordinates(space, x, y, z);
+        NgStatus::Ok
+    })
+}
+
+/// Copy `[x, y, z]` of one coordinate space (0-7) into `out`
+///
+/// # Safety
+/// `token` must be a live handle; `out` must point to 3 writable floats.
+#[no_mangle]
+pub unsafe extern "C" fn ng_token_get_coordinates(
+    token: *const NgToken,
+    space: u8,
+    out: *mut f32,
+) -> NgStatus {
+    guard(|| {
+        let Some(token) = token.as_ref() else {
+            return fail(NgStatus::NullPointer, "token is NULL");
+        };
+        if out.is_null() {
+            return fail(NgStatus::NullPointer, "out is NULL");
+        }
+        let Some(space) = coordinate_space(space) else {
+            return fail(NgStatus::InvalidArgument, "space must be 0-7");
+        };
+        ptr::write_unaligned(out as *mut [f32; 3], token.inner.get_coordinates(space));
+        NgStatus::Ok
+    })
+}
+
+/// Serialize the token to its 64-byte binary form
+///
+/// # Safety
+/// `token` must be a live handle; `out` must point to 64 writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ng_token_to_bytes(token: *const NgToken, out: *mut u8) -> NgStatus {
+    guard(|| {
+        let Some(token) = token.as_ref() else {
+            return fail(NgStatus::NullPointer, "token is NULL");
+        };
+        if out.is_null() {
+            return fail(NgStatus::NullPointer, "out is NULL");
+        }
+        ptr::copy_nonoverlapping(token.inner.to_bytes().as_ptr(), out, 64);
+        NgStatus::Ok
+    })
+}
+
+// ============================================================================
+// Graph
+// ============================================================================
+
+/// Opaque graph handle
+pub struct NgGraph {
+    inner: Graph,
+}
+
+/// Create an empty graph
+#[no_mangle]
+pub extern "C" fn ng_graph_new() -> *mut NgGraph {
+    guard_new(|| Some(NgGraph { inner: Graph::new() }))
+}
+
+/// Release a graph
+///
+/// # Safety
+/// `graph` must be NULL or a handle from `ng_graph_new`, freed at most once.
+#[no_mangle]
+pub unsafe extern "C" fn ng_graph_free(graph: *mut NgGraph) {
+    if !graph.is_null() {
+        drop(Box::from_raw(graph));
+    }
+}
+
+/// Add a node; returns `NG_STATUS_OK` also when it already exists
+///
+/// # Safety
+/// `graph` must be a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn ng_graph_add_node(graph: *mut NgGraph, node_id: u32) -> NgStatus {
+    guard(|| {
+        let Some(graph) = graph.as_mut() else {
+            return fail(NgStatus::NullPointer, "graph is NULL");
+        };
+        graph.inner.add_node(node_id);
+        NgStatus::Ok
+    })
+}
+
+/// Add an edge between existing nodes, writing its ID to `out_edge_id` (may be NULL)
+///
+/// # Safety
+/// `graph` must be a live handle; `out_edge_id` must be NULL or writable.
+#[no_mangle]
+pub unsafe extern "C" fn ng_graph_add_edge(
+    graph: *mut NgGraph,
+    from_id: u32,
+    to_id: u32,
+    edge_type: u8,
+    weight: f32,
+    bidirectional: bool,
+    out_edge_id: *mut u64,
+) -> NgStatus {
+    guard(|| {
+        let Some(graph) = graph.as_mut() else {
+            return fail(NgStatus::NullPointer, "graph is NULL");
+        };
+        let edge_id = Graph::compute_edge_id(from_id, to_id, edge_type);
+        match graph.inner.add_edge(edge_id, from_id, to_id, edge_type, weight, bidirectional) {
+            Ok(_) => {
+                if !out_edge_id.is_null() {
+                    *out_edge_id = edge_id;
+                }
+                NgStatus::Ok
+            }
+            Err(e) => fail(NgStatus::NotFound, e),
+        }
+    })
+}
+
+/// Number of nodes (0 for NULL)
+///
+/// # Safety
+/// `graph` must be NULL or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn ng_graph_node_count(graph: *const NgGraph) -> usize {
+    graph.as_ref().map_or(0, |g| g.inner.node_count())
+}
+
+/// Number of edges (0 for NULL)
+///
+/// # Safety
+/// `graph` must be NULL or a live handle.
+#[no_mangle]
+pub unsafe extern "C" fn ng_graph_edge_count(graph: *const NgGraph) -> usize {
+    graph.as_ref().map_or(0, |g| g.inner.edge_count())
+}
+
+/// Copy up to `capacity` neighbor IDs (both directions) into `out`
+///
+/// Writes the total neighbor count to `out_total`, so callers can retry
+/// with a larger buffer when `*out_total > capacity`.
+///
+/// # Safety
+/// `graph` must be a live handle; `out` must hold `capacity` u32 values
+/// (may be NULL when `capacity == 0`); `out_total` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn ng_graph_neighbors(
+    graph: *const NgGraph,
+    node_id: u32,
+    out: *mut u32,
+    capacity: usize,
+    out_total: *mut usize,
+) -> NgStatus {
+    guard(|| {
+        let Some(graph) = graph.as_ref() else {
+            return fail(NgStatus::NullPointer, "graph is NULL");
+        };
+        if out_total.is_null() || (out.is_null() && capacity > 0) {
+            return fail(NgStatus::NullPointer, "output buffer is NULL");
+        }
+        if !graph.inner.contains_node(node_id) {
+            return fail(NgStatus::NotFound, format!("Node {} does not exist", node_id));
+        }
+        let neighbors = graph.inner.get_neighbors(node_id, Direction::Both);
+        for (i, (neighbor_id, _)) in neighbors.iter().take(capacity).enumerate() {
+            *out.add(i) = *neighbor_id;
+        }
+        *out_total = neighbors.len();
+        NgStatus::Ok
+    })
+}
+
+// ============================================================================
+// Gateway
+// ============================================================================
+
+/// Opaque Gateway handle
+///
+/// Owns its own single-threaded runtime and the processed-signal queue;
+/// the embedder drains that queue (acting as the ActionController) and
+/// reports results back with `ng_gateway_complete`.
+pub struct NgGateway {
+    runtime: tokio::runtime::Runtime,
+    gateway: Gateway,
+    signals: mpsc::Receiver<ProcessedSignal>,
+    results: HashMap<u64, ResultReceiver>,
+}
+
+impl NgGateway {
+    fn new(map_json: Option<&str>) -> Result<Self, String> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| e.to_string())?;
+
+        let mut bootstrap = BootstrapLibrary::new(BootstrapConfig::default());
+        if let Some(json) = map_json {
+            bootstrap.load_bootstrap_map_str(json).map_err(|e| e.to_string())?;
+        }
+
+        let (tx, rx) = mpsc::channel(GATEWAY_QUEUE_SIZE);
+        let gateway = Gateway::new(tx, Arc::new(RwLock::new(bootstrap)), GatewayConfig::default());
+
+        Ok(Self { runtime, gateway, signals: rx, results: HashMap::new() })
+    }
+
+    fn inject(&mut self, signal: InputSignal, out_signal_id: *mut u64) -> NgStatus {
+        match self.runtime.block_on(self.gateway.inject(signal)) {
+            Ok((receipt, rx)) => {
+                self.results.insert(receipt.signal_id, rx);
+                if !out_signal_id.is_null() {
+                    // SAFETY: checked non-null; caller guarantees writability
+                    unsafe { *out_signal_id = receipt.signal_id };
+                }
+                NgStatus::Ok
+            }
+            Err(e) => fail(NgStatus::Error, e.to_string()),
+        }
+    }
+}
+
+/// Create a Gateway, optionally seeded with a bootstrap map (JSON text, may be NULL)
+///
+/// Returns NULL on failure; see `ng_last_error()`.
+///
+/// # Safety
+/// `map_json` must be NULL or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn ng_gateway_new(map_json: *const c_char) -> *mut NgGateway {
+    let map_json = if map_json.is_null() {
+        None
+    } else {
+        match str_arg(map_json) {
+            Ok(s) => Some(s),
+            Err(_) => return ptr::null_mut(),
+        }
+    };
+    guard_new(|| match NgGateway::new(map_json) {
+        Ok(gateway) => Some(gateway),
+        Err(e) => {
+            set_last_error(e);
+            None
+        }
+    })
+}
+
+/// Release a Gateway
+///
+/// # Safety
+/// `gateway` must be NULL or a handle from `ng_gateway_new`, freed at most once.
+#[no_mangle]
+pub unsafe extern "C" fn ng_gateway_free(gateway: *mut NgGateway) {
+    if !gateway.is_null() {
+        drop(Box::from_raw(gateway));
+    }
+}
+
+/// Inject a text signal, writing its signal ID to `out_signal_id` (may be NULL)
+///
+/// # Safety
+/// `gateway` must be a live handle; `text` a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn ng_gateway_inject_text(
+    gateway: *mut NgGateway,
+    text: *const c_char,
+    out_signal_id: *mut u64,
+) -> NgStatus {
+    guard(|| {
+        let Some(gateway) = gateway.as_mut() else {
+            return fail(NgStatus::NullPointer, "gateway is NULL");
+        };
+        let text = match str_arg(text) {
+            Ok(text) => text,
+            Err(status) => return status,
+        };
+        gateway.inject(
+            InputSignal::Text { content: text.to_string(), source: SignalSource::Unknown, metadata: None },
+            out_signal_id,
+        )
+    })
+}
+
+/// Inject a direct 8D state signal
+///
+/// # Safety
+/// `gateway` must be a live handle; `state` must point to 8 readable floats.
+#[no_mangle]
+pub unsafe extern "C" fn ng_gateway_inject_state(
+    gateway: *mut NgGateway,
+    state: *const f32,
+    out_signal_id: *mut u64,
+) -> NgStatus {
+    guard(|| {
+        let Some(gateway) = gateway.as_mut() else {
+            return fail(NgStatus::NullPointer, "gateway is NULL");
+        };
+        if state.is_null() {
+            return fail(NgStatus::NullPointer, "state is NULL");
+        }
+        let state: [f32; 8] = ptr::read_unaligned(state as *const [f32; 8]);
+        gateway.inject(InputSignal::DirectState { state, label: None }, out_signal_id)
+    })
+}
+
+/// Pop the next processed signal as JSON
+///
+/// Returns `NG_STATUS_NOT_FOUND` when the queue is empty.
+///
+/// # Safety
+/// `gateway` must be a live handle; `out_json` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn ng_gateway_next_signal(
+    gateway: *mut NgGateway,
+    out_json: *mut *mut c_char,
+) -> NgStatus {
+    guard(|| {
+        let Some(gateway) = gateway.as_mut() else {
+            return fail(NgStatus::NullPointer, "gateway is NULL");
+        };
+        if out_json.is_null() {
+            return fail(NgStatus::NullPointer, "out_json is NULL");
+        }
+        match gateway.signals.try_recv() {
+            Ok(signal) => match serde_json::to_string(&signal) {
+                Ok(json) => write_string(out_json, json),
+                Err(e) => fail(NgStatus::Error, e.to_string()),
+            },
+            Err(_) => NgStatus::NotFound,
+        }
+    })
+}
+
+/// Report the result of a processed signal
+///
+/// `output_json` (may be NULL) becomes the result payload; when `success`
+/// is false it is used as the error message instead.
+///
+/// # Safety
+/// `gateway` must be a live handle; `output_json` NULL or a valid string.
+#[no_mangle]
+pub unsafe extern "C" fn ng_gateway_complete(
+    gateway: *mut NgGateway,
+    signal_id: u64,
+    success: bool,
+    output_json: *const c_char,
+) -> NgStatus {
+    guard(|| {
+        let Some(gateway) = gateway.as_mut() else {
+            return fail(NgStatus::NullPointer, "gateway is NULL");
+        };
+        let output = if output_json.is_null() {
+            None
+        } else {
+            match str_arg(output_json) {
+                Ok(s) => Some(s),
+                Err(status) => return status,
+            }
+        };
+        let result = if success {
+            let value = match output.map(serde_json::from_str).transpose() {
+                Ok(value) => value.unwrap_or(serde_json::Value::Null),
+                Err(e) => return fail(NgStatus::InvalidArgument, e.to_string()),
+            };
+            ActionResult::success(value, 0)
+        } else {
+            ActionResult::failure(output.unwrap_or("failed").to_string(), 0)
+        };
+        gateway.gateway.complete_request(signal_id, result);
+        NgStatus::Ok
+    })
+}
+
+/// Fetch the result of an injected signal as JSON
+///
+/// Returns `NG_STATUS_PENDING` until it is completed and `NG_STATUS_NOT_FOUND`
+/// for unknown or already fetched IDs.
+///
+/// # Safety
+/// `gateway` must be a live handle; `out_json` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn ng_gateway_poll_result(
+    gateway: *mut NgGateway,
+    signal_id: u64,
+    out_json: *mut *mut c_char,
+) -> NgStatus {
+    guard(|| {
+        let Some(gateway) = gateway.as_mut() else {
+            return fail(NgStatus::NullPointer, "gateway is NULL");
+        };
+        if out_json.is_null() {
+            return fail(NgStatus::NullPointer, "out_json is NULL");
+        }
+        let Some(rx) = gateway.results.get_mut(&signal_id) else {
+            return fail(NgStatus::NotFound, format!("Unknown signal {}", signal_id));
+        };
+        match rx.try_recv() {
+            Ok(result) => {
+                gateway.results.remove(&signal_id);
+                let json = serde_json::json!({
+                    "success": result.success,
+                    "output": result.output,
+                    "duration_ms": result.duration_ms,
+                    "error": result.error,
+                });
+                write_string(out_json, json.to_string())
+            }
+            Err(tokio::sync::oneshot::error::TryRecvError::Empty) => NgStatus::Pending,
+            Err(tokio::sync::oneshot::error::TryRecvError::Closed) => {
+                gateway.results.remove(&signal_id);
+                fail(NgStatus::Error, format!("Signal {} was dropped", signal_id))
+            }
+        }
+    })
+}
+
+/// Gateway statistics as JSON
+///
+/// # Safety
+/// `gateway` must be a live handle; `out_json` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn ng_gateway_stats(
+    gateway: *const NgGateway,
+    out_json: *mut *mut c_char,
+) -> NgStatus {
+    guard(|| {
+        let Some(gateway) = gateway.as_ref() else {
+            return fail(NgStatus::NullPointer, "gateway is NULL");
+        };
+        if out_json.is_null() {
+            return fail(NgStatus::NullPointer, "out_json is NULL");
+        }
+        match serde_json::to_string(&gateway.gateway.stats()) {
+            Ok(json) => write_string(out_json, json),
+            Err(e) => fail(NgStatus::Error, e.to_string()),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    unsafe fn take_string(s: *mut c_char) -> String {
+        let out = CStr::from_ptr(s).to_str().unwrap().to_string();
+        ng_string_free(s);
+        out
+    }
+
+    #[test]
+    fn test_token_roundtrip() {
+        unsafe {
+            let state = [0.1f32, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8];
+            let token = ng_token_from_state(42, state.as_ptr());
+            assert!(!token.is_null());
+            assert_eq!(ng_token_id(token), 42);
+
+            let mut out = [0.0f32; 8];
+            assert_eq!(ng_token_state(token, out.as_mut_ptr()), NgStatus::Ok);
+            for (a, b) in state.iter().zip(out.iter()) {
+                assert!((a - b).abs() < 0.01);
+            }
+
+            assert_eq!(ng_token_set_coordinates(token, 9, 0.0, 0.0, 0.0), NgStatus::InvalidArgument);
+            assert!(!ng_last_error().is_null());
+
+            ng_token_free(token);
+            ng_token_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_graph_neighbors() {
+        unsafe {
+            let graph = ng_graph_new();
+            for id in 1..=3 {
+                assert_eq!(ng_graph_add_node(graph, id), NgStatus::Ok);
+            }
+            let mut edge_id = 0u64;
+            assert_eq!(ng_graph_add_edge(graph, 1, 2, 0, 1.0, false, &mut edge_id), NgStatus::Ok);
+            assert_ne!(edge_id, 0);
+            assert_eq!(ng_graph_add_edge(graph, 1, 3, 0, 1.0, false, ptr::null_mut()), NgStatus::Ok);
+            assert_eq!(ng_graph_add_edge(graph, 1, 99, 0, 1.0, false, ptr::null_mut()), NgStatus::NotFound);
+            assert_eq!(ng_graph_edge_count(graph), 2);
+
+            // Undersized buffer reports the full count
+            let mut buf = [0u32; 1];
+            let mut total = 0usize;
+            assert_eq!(ng_graph_neighbors(graph, 1, buf.as_mut_ptr(), 1, &mut total), NgStatus::Ok);
+            assert_eq!(total, 2);
+
+            assert_eq!(ng_graph_neighbors(graph, 7, ptr::null_mut(), 0, &mut total), NgStatus::NotFound);
+            ng_graph_free(graph);
+        }
+    }
+
+    #[test]
+    fn test_gateway_inject_and_complete() {
+        unsafe {
+            let gateway = ng_gateway_new(ptr::null());
+            assert!(!gateway.is_null());
+
+            let text = CString::new("/status").unwrap();
+            let mut signal_id = u64::MAX;
+            assert_eq!(ng_gateway_inject_text(gateway, text.as_ptr(), &mut signal_id), NgStatus::Ok);
+
+            let mut json = ptr::null_mut();
+            assert_eq!(ng_gateway_next_signal(gateway, &mut json), NgStatus::Ok);
+            assert!(take_string(json).contains(&format!("\"signal_id\":{}", signal_id)));
+            assert_eq!(ng_gateway_next_signal(gateway, &mut json), NgStatus::NotFound);
+
+            assert_eq!(ng_gateway_poll_result(gateway, signal_id, &mut json), NgStatus::Pending);
+            let output = CString::new("{\"ok\":true}").unwrap();
+            assert_eq!(ng_gateway_complete(gateway, signal_id, true, output.as_ptr()), NgStatus::Ok);
+            assert_eq!(ng_gateway_poll_result(gateway, signal_id, &mut json), NgStatus::Ok);
+            assert!(take_string(json).contains("\"ok\":true"));
+            assert_eq!(ng_gateway_poll_result(gateway, signal_id, &mut json), NgStatus::NotFound);
+
+            assert_eq!(ng_gateway_stats(gateway, &mut json), NgStatus::Ok);
+            assert!(take_string(json).contains("total_signals"));
+
+            ng_gateway_free(gateway);
+        }
+    }
+}
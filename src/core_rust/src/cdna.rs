@@ -37,6 +37,8 @@
 /// - **Versioned**: History tracking with rollback support
 /// - **Validated**: All parameters have strict bounds
 
+use crate::decay::DecayProfiles;
+use serde::{Deserialize, Serialize};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// CDNA magic number: "CDNA" in ASCII
@@ -44,7 +46,7 @@ pub const CDNA_MAGIC: u32 = 0x434E4441; // "CDNA"
 
 /// Current CDNA version
 pub const CDNA_VERSION_MAJOR: u16 = 2;
-pub const CDNA_VERSION_MINOR: u16 = 1;
+pub const CDNA_VERSION_MINOR: u16 = 2;
 
 /// Profile IDs for predefined configurations
 #[repr(u32)]
@@ -157,7 +159,7 @@ impl CDNAFlags {
 /// 24     | 4    | profile_id
 /// 28     | 4    | profile_state
 /// 32     | 4    | flags
-/// 36     | 4    | reserved1
+/// 36     | 4    | revision
 /// 40     | 8    | checksum
 /// 48     | 16   | reserved2
 /// -------|------|------------------
@@ -190,7 +192,9 @@ impl CDNAFlags {
 /// 300    | 4    | max_rigidity
 /// 304    | 4    | default_pull_strength
 /// 308    | 4    | decay_rate
-/// 312    | 40   | reserved6
+/// 312    | 11   | category_decay[11]  (v2.2)
+/// 323    | 1    | hypothesis_decay    (v2.2)
+/// 324    | 28   | reserved6
 /// -------|------|------------------
 /// 352    | 4    | mutation_rate
 /// 356    | 4    | crossover_rate
@@ -220,8 +224,8 @@ pub struct CDNA {
     pub profile_state: u32,
     /// CDNA flags (validation, events, mutation, strict mode)
     pub flags: u32,
-    /// Reserved for future use
-    reserved1: u32,
+    /// Revision counter, incremented by every applied ProfilePatch
+    pub revision: u32,
     /// Checksum (FNV-1a hash of entire structure)
     pub checksum: u64,
     /// Reserved for alignment
@@ -286,8 +290,12 @@ pub struct CDNA {
     pub default_pull_strength: f32,
     /// Decay rate for connections
     pub decay_rate: f32,
+    /// Weight lost per hour by connection category (/255, see `decay`)
+    pub category_decay: [u8; 11],
+    /// Weight lost per hour by Hypothesis connections (/255)
+    pub hypothesis_decay: u8,
     /// Reserved
-    reserved6: [u8; 40],
+    reserved6: [u8; 28],
 
     // ==================== BLOCK 6: EVOLUTION & SUBSCRIPTION (32 bytes) ====================
     /// Mutation rate (0.0 - 1.0)
@@ -317,6 +325,8 @@ impl CDNA {
             .unwrap()
             .as_secs();
 
+        let (category_decay, hypothesis_decay) = DecayProfiles::default().to_cdna_bytes();
+
         let mut cdna = Self {
             magic: CDNA_MAGIC,
             version_major: CDNA_VERSION_MAJOR,
@@ -326,7 +336,7 @@ impl CDNA {
             profile_id: ProfileId::Default as u32,
             profile_state: ProfileState::ACTIVE | ProfileState::VALIDATED,
             flags: CDNAFlags::default().bits,
-            reserved1: 0,
+            revision: 0,
             checksum: 0,
             reserved2: [0; 16],
 
@@ -363,7 +373,9 @@ impl CDNA {
             max_rigidity: 1.0,
             default_pull_strength: 0.5,
             decay_rate: 0.01,
-            reserved6: [0; 40],
+            category_decay,
+            hypothesis_decay,
+            reserved6: [0; 28],
 
             // Evolution defaults
             mutation_rate: 0.01,
@@ -435,6 +447,16 @@ impl CDNA {
         hash
     }
 
+    /// Serialize to bytes (384 bytes)
+    pub fn to_bytes(&self) -> [u8; 384] {
+        unsafe { std::mem::transmute(*self) }
+    }
+
+    /// Deserialize from bytes (384 bytes); call `validate()` afterwards
+    pub fn from_bytes(bytes: &[u8; 384]) -> Self {
+        unsafe { std::mem::transmute(*bytes) }
+    }
+
     /// Validate CDNA structure
     pub fn validate(&self) -> Result<(), String> {
         // Check magic number
@@ -505,6 +527,206 @@ impl CDNA {
     }
 }
 
+/// Partial CDNA update for runtime profile tuning
+///
+/// Only the tunable parameters are exposed; header fields (magic, version,
+/// checksum, timestamps) are managed by `CDNA::apply_patch`. Fields left as
+/// `None` keep their current value.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfilePatch {
+    // Grid physics
+    pub dimension_scales: Option<[f32; 8]>,
+    pub bucket_sizes: Option<[f32; 8]>,
+    pub field_strength_limits: Option<[f32; 8]>,
+
+    // Graph topology
+    pub allowed_connection_types: Option<u64>,
+    pub max_out_degree: Option<u32>,
+    pub max_in_degree: Option<u32>,
+    pub max_total_degree: Option<u32>,
+    pub min_weight_threshold: Option<f32>,
+    pub max_weight_threshold: Option<f32>,
+
+    // Token properties
+    pub min_token_weight: Option<f32>,
+    pub max_token_weight: Option<f32>,
+    pub min_field_radius: Option<u16>,
+    pub max_field_radius: Option<u16>,
+    pub min_field_strength: Option<u16>,
+    pub max_field_strength: Option<u16>,
+
+    // Connection constraints
+    pub min_connection_weight: Option<f32>,
+    pub max_connection_weight: Option<f32>,
+    pub min_rigidity: Option<f32>,
+    pub max_rigidity: Option<f32>,
+    pub default_pull_strength: Option<f32>,
+    pub decay_rate: Option<f32>,
+    pub category_decay: Option<[u8; 11]>,
+    pub hypothesis_decay: Option<u8>,
+
+    // Evolution
+    pub mutation_rate: Option<f32>,
+    pub crossover_rate: Option<f32>,
+    pub selection_pressure: Option<f32>,
+    pub trace_sample_rate: Option<f32>,
+}
+
+impl ProfilePatch {
+    /// Patch that sets every tunable field to its value in `cdna`
+    pub fn from_cdna(cdna: &CDNA) -> Self {
+        Self {
+            dimension_scales: Some(cdna.dimension_scales),
+            bucket_sizes: Some(cdna.bucket_sizes),
+            field_strength_limits: Some(cdna.field_strength_limits),
+            allowed_connection_types: Some(cdna.allowed_connection_types),
+            max_out_degree: Some(cdna.max_out_degree),
+            max_in_degree: Some(cdna.max_in_degree),
+            max_total_degree: Some(cdna.max_total_degree),
+            min_weight_threshold: Some(cdna.min_weight_threshold),
+            max_weight_threshold: Some(cdna.max_weight_threshold),
+            min_token_weight: Some(cdna.min_token_weight),
+            max_token_weight: Some(cdna.max_token_weight),
+            min_field_radius: Some(cdna.min_field_radius),
+            max_field_radius: Some(cdna.max_field_radius),
+            min_field_strength: Some(cdna.min_field_strength),
+            max_field_strength: Some(cdna.max_field_strength),
+            min_connection_weight: Some(cdna.min_connection_weight),
+            max_connection_weight: Some(cdna.max_connection_weight),
+            min_rigidity: Some(cdna.min_rigidity),
+            max_rigidity: Some(cdna.max_rigidity),
+            default_pull_strength: Some(cdna.default_pull_strength),
+            decay_rate: Some(cdna.decay_rate),
+            category_decay: Some(cdna.category_decay),
+            hypothesis_decay: Some(cdna.hypothesis_decay),
+            mutation_rate: Some(cdna.mutation_rate),
+            crossover_rate: Some(cdna.crossover_rate),
+            selection_pressure: Some(cdna.selection_pressure),
+            trace_sample_rate: Some(cdna.trace_sample_rate),
+        }
+    }
+
+    /// True if the patch changes nothing
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl CDNA {
+    /// Apply a ProfilePatch
+    ///
+    /// The patched CDNA becomes a Custom profile with the next revision, a
+    /// fresh modification time and checksum. Every patched value is range
+    /// checked and the result must pass `validate()`; on error `self` is
+    /// left unchanged. Readonly profiles cannot be patched.
+    pub fn apply_patch(&mut self, patch: &ProfilePatch) -> Result<(), String> {
+        if ProfileState::new(self.profile_state).is_readonly() {
+            return Err("CDNA profile is readonly".to_string());
+        }
+        if patch.is_empty() {
+            return Err("Empty profile patch".to_string());
+        }
+
+        let mut next = *self;
+        if next.version_minor < 2 {
+            // Before v2.2 the decay profile bytes were reserved (zero)
+            (next.category_decay, next.hypothesis_decay) = DecayProfiles::default().to_cdna_bytes();
+            next.version_minor = CDNA_VERSION_MINOR;
+        }
+        macro_rules! set {
+            ($($field:ident),*) => {
+                $(if let Some(value) = patch.$field {
+                    next.$field = value;
+                })*
+            };
+        }
+        set!(
+            dimension_scales, bucket_sizes, field_strength_limits,
+            allowed_connection_types, max_out_degree, max_in_degree, max_total_degree,
+            min_weight_threshold, max_weight_threshold,
+            min_token_weight, max_token_weight, min_field_radius, max_field_radius,
+            min_field_strength, max_field_strength,
+            min_connection_weight, max_connection_weight, min_rigidity, max_rigidity,
+            default_pull_strength, decay_rate, category_decay, hypothesis_decay,
+            mutation_rate, crossover_rate, selection_pressure, trace_sample_rate
+        );
+
+        next.check_parameters()?;
+        next.profile_id = ProfileId::Custom as u32;
+        next.revision = self.revision.wrapping_add(1);
+        next.touch();
+        next.validate()?;
+
+        *self = next;
+        Ok(())
+    }
+
+    /// Bounds that `validate()` does not cover but a patch must respect
+    fn check_parameters(&self) -> Result<(), String> {
+        let per_dimension = [
+            ("dimension_scales", &self.dimension_scales),
+            ("bucket_sizes", &self.bucket_sizes),
+            ("field_strength_limits", &self.field_strength_limits),
+        ];
+        for (name, values) in per_dimension {
+            if values.iter().any(|v| !v.is_finite() || *v <= 0.0) {
+                return Err(format!("{} must be finite and > 0", name));
+            }
+        }
+
+        if self.max_out_degree == 0 || self.max_in_degree == 0 {
+            return Err("Degree limits must be > 0".to_string());
+        }
+        if self.max_total_degree < self.max_out_degree.max(self.max_in_degree) {
+            return Err("max_total_degree must be >= max_out_degree and max_in_degree".to_string());
+        }
+
+        let ranges = [
+            ("weight threshold", self.min_weight_threshold, self.max_weight_threshold),
+            ("connection weight", self.min_connection_weight, self.max_connection_weight),
+            ("rigidity", self.min_rigidity, self.max_rigidity),
+        ];
+        for (name, min, max) in ranges {
+            if !min.is_finite() || !max.is_finite() || min > max {
+                return Err(format!("Invalid {} range", name));
+            }
+        }
+        if self.min_field_radius > self.max_field_radius {
+            return Err("Invalid field radius range".to_string());
+        }
+        if self.min_field_strength > self.max_field_strength {
+            return Err("Invalid field strength range".to_string());
+        }
+
+        let rates = [
+            ("default_pull_strength", self.default_pull_strength),
+            ("decay_rate", self.decay_rate),
+            ("mutation_rate", self.mutation_rate),
+            ("crossover_rate", self.crossover_rate),
+            ("selection_pressure", self.selection_pressure),
+            ("trace_sample_rate", self.trace_sample_rate),
+        ];
+        for (name, value) in rates {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(format!("{} must be in [0.0, 1.0]", name));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Decode and validate a serialized CDNA (magic, version, checksum, ranges)
+pub fn validate_bytes(bytes: &[u8]) -> Result<CDNA, String> {
+    let bytes: &[u8; 384] = bytes
+        .try_into()
+        .map_err(|_| format!("CDNA requires 384 bytes, got {}", bytes.len()))?;
+    let cdna = CDNA::from_bytes(bytes);
+    cdna.validate()?;
+    Ok(cdna)
+}
+
 impl Default for CDNA {
     fn default() -> Self {
         Self::new()
@@ -570,6 +792,55 @@ mod tests {
         assert!(cdna.validate().is_err());
     }
 
+    #[test]
+    fn test_apply_patch() {
+        let mut cdna = CDNA::new();
+        let patch = ProfilePatch {
+            max_out_degree: Some(1500),
+            decay_rate: Some(0.05),
+            ..Default::default()
+        };
+        cdna.apply_patch(&patch).unwrap();
+        assert_eq!(cdna.max_out_degree, 1500);
+        assert_eq!(cdna.decay_rate, 0.05);
+        assert_eq!(cdna.revision, 1);
+        assert_eq!(cdna.profile(), ProfileId::Custom);
+        assert!(cdna.validate().is_ok());
+
+        // Invalid patches leave the CDNA untouched
+        let before = cdna.to_bytes();
+        let bad = ProfilePatch { max_out_degree: Some(5000), ..Default::default() };
+        assert!(cdna.apply_patch(&bad).is_err());
+        let bad = ProfilePatch { mutation_rate: Some(1.5), ..Default::default() };
+        assert!(cdna.apply_patch(&bad).is_err());
+        assert!(cdna.apply_patch(&ProfilePatch::default()).is_err());
+        assert_eq!(cdna.to_bytes(), before);
+
+        // Round trip through the full patch view
+        let mut copy = CDNA::new();
+        copy.apply_patch(&ProfilePatch::from_cdna(&cdna)).unwrap();
+        assert_eq!(copy.max_out_degree, 1500);
+
+        cdna.profile_state |= ProfileState::READONLY;
+        cdna.touch();
+        assert!(cdna.apply_patch(&patch).is_err());
+    }
+
+    #[test]
+    fn test_patch_upgrades_v2_1_decay_profiles() {
+        // v2.1 kept the decay profile bytes reserved (zero)
+        let mut cdna = CDNA::new();
+        cdna.version_minor = 1;
+        cdna.category_decay = [0; 11];
+        cdna.hypothesis_decay = 0;
+        cdna.touch();
+
+        cdna.apply_patch(&ProfilePatch { max_out_degree: Some(1500), ..Default::default() }).unwrap();
+        assert_eq!(cdna.version_minor, CDNA_VERSION_MINOR);
+        assert_eq!(cdna.category_decay, CDNA::new().category_decay);
+        assert_ne!(cdna.hypothesis_decay, 0);
+    }
+
     #[test]
     fn test_profile_state() {
         let state = ProfileState::new(ProfileState::ACTIVE | ProfileState::VALIDATED);
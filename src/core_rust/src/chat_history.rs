@@ -0,0 +1,642 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Chat History v1.0 - persistent conversations with full-text search
+//!
+//! [`ChatHistory`] keeps conversations (role, content, signal ID,
+//! timestamps) in memory and, with a [`PersistenceBackend`] attached,
+//! in the configuration store under component `"chat"`:
+//!
+//! - `conversation:<id>` - title and creation time
+//! - `message:<id>:<index>` - one message (messages are never edited, so
+//!   appending is a single write)
+//!
+//! `load` rebuilds the conversations at startup and `last_conversation`
+//! returns the most recently active one for session restore. `search`
+//! matches all query words (case-insensitive) against message contents and
+//! conversation titles.
+
+use crate::persistence::{PersistenceBackend, PersistenceError};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Persistence component holding conversations
+pub const CHAT_COMPONENT: &str = "chat";
+
+const CONVERSATION_KEY_PREFIX: &str = "conversation:";
+const MESSAGE_KEY_PREFIX: &str = "message:";
+
+/// Characters of the first user message used as the default title
+const TITLE_LENGTH: usize = 60;
+
+/// Characters of context around a search match
+const SNIPPET_CONTEXT: usize = 40;
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Who wrote a message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChatRole {
+    User,
+    Assistant,
+    System,
+}
+
+/// One chat message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub role: ChatRole,
+    pub content: String,
+    /// Gateway signal that produced (or answered) this message
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signal_id: Option<u64>,
+    /// Unix milliseconds
+    pub timestamp_ms: u64,
+}
+
+impl ChatMessage {
+    pub fn new(role: ChatRole, content: impl Into<String>) -> Self {
+        Self { role, content: content.into(), signal_id: None, timestamp_ms: now_ms() }
+    }
+
+    pub fn with_signal_id(mut self, signal_id: u64) -> Self {
+        self.signal_id = Some(signal_id);
+        self
+    }
+}
+
+/// A conversation with all its messages
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Conversation {
+    pub id: u64,
+    pub title: String,
+    /// Unix milliseconds
+    pub created_at: u64,
+    pub messages: Vec<ChatMessage>,
+}
+
+impl Conversation {
+    /// Time of the last message (creation time if empty)
+    pub fn updated_at(&self) -> u64 {
+        self.messages.last().map_or(self.created_at, |m| m.timestamp_ms)
+    }
+
+    pub fn summary(&self) -> ConversationSummary {
+        ConversationSummary {
+            id: self.id,
+            title: self.title.clone(),
+            created_at: self.created_at,
+            updated_at: self.updated_at(),
+            message_count: self.messages.len(),
+        }
+    }
+}
+
+/// Conversation without messages, for history sidebars
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConversationSummary {
+    pub id: u64,
+    pub title: String,
+    pub created_at: u64,
+    pub updated_at: u64,
+    pub message_count: usize,
+}
+
+/// A message matching a search
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub conversation_id: u64,
+    pub title: String,
+    /// Index of the message in the conversation (None = title match)
+    pub message_index: Option<usize>,
+    /// Excerpt around the first match
+    pub snippet: String,
+    pub timestamp_ms: u64,
+}
+
+/// Chat history limits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatHistoryConfig {
+    /// Conversations kept; the least recently active are deleted first
+    pub max_conversations: usize,
+    /// Messages per conversation
+    pub max_messages: usize,
+    /// Maximum message length in bytes
+    pub max_message_bytes: usize,
+}
+
+impl Default for ChatHistoryConfig {
+    fn default() -> Self {
+        Self {
+            max_conversations: 500,
+            max_messages: 5000,
+            max_message_bytes: 64 * 1024,
+        }
+    }
+}
+
+impl ChatHistoryConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_conversations == 0 {
+            return Err("max_conversations must be > 0".to_string());
+        }
+        if self.max_messages == 0 {
+            return Err("max_messages must be > 0".to_string());
+        }
+        if self.max_message_bytes == 0 {
+            return Err("max_message_bytes must be > 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Chat history errors
+#[derive(Debug)]
+pub enum ChatHistoryError {
+    NotFound(u64),
+    /// Conversation reached `max_messages`
+    ConversationFull(u64),
+    InvalidMessage(String),
+    Config(String),
+    Persistence(PersistenceError),
+}
+
+impl fmt::Display for ChatHistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChatHistoryError::NotFound(id) => write!(f, "Conversation {} not found", id),
+            ChatHistoryError::ConversationFull(id) => {
+                write!(f, "Conversation {} has reached the message limit", id)
+            }
+            ChatHistoryError::InvalidMessage(msg) => write!(f, "Invalid message: {}", msg),
+            ChatHistoryError::Config(msg) => write!(f, "Invalid chat history config: {}", msg),
+            ChatHistoryError::Persistence(e) => write!(f, "Persistence error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ChatHistoryError {}
+
+impl From<PersistenceError> for ChatHistoryError {
+    fn from(e: PersistenceError) -> Self {
+        ChatHistoryError::Persistence(e)
+    }
+}
+
+/// Stored conversation header
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ConversationRecord {
+    id: u64,
+    title: String,
+    created_at: u64,
+}
+
+#[derive(Default)]
+struct StoredConversation {
+    conversation: Option<Conversation>,
+    /// Config IDs of the header and message rows (for deletion)
+    config_ids: Vec<i32>,
+    /// Config ID of the header row (parent for title updates)
+    header_id: Option<i32>,
+}
+
+/// Conversations with optional persistence and search
+pub struct ChatHistory {
+    config: ChatHistoryConfig,
+    conversations: RwLock<BTreeMap<u64, StoredConversation>>,
+    backend: Option<Arc<dyn PersistenceBackend>>,
+}
+
+impl ChatHistory {
+    pub fn new(config: ChatHistoryConfig) -> Result<Self, ChatHistoryError> {
+        config.validate().map_err(ChatHistoryError::Config)?;
+        Ok(Self {
+            config,
+            conversations: RwLock::new(BTreeMap::new()),
+            backend: None,
+        })
+    }
+
+    /// Persist conversations in `backend` (call `load` afterwards)
+    pub fn with_backend(mut self, backend: Arc<dyn PersistenceBackend>) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
+    pub fn config(&self) -> &ChatHistoryConfig {
+        &self.config
+    }
+
+    /// Load conversations from the backend, replacing the in-memory ones
+    pub async fn load(&self) -> Result<usize, ChatHistoryError> {
+        let Some(backend) = &self.backend else {
+            return Ok(self.conversations.read().len());
+        };
+
+        let mut stored: BTreeMap<u64, StoredConversation> = BTreeMap::new();
+        let mut messages: BTreeMap<(u64, usize), ChatMessage> = BTreeMap::new();
+        for config in backend.get_component_configs(CHAT_COMPONENT).await? {
+            if config.config_key.starts_with(CONVERSATION_KEY_PREFIX) {
+                match serde_json::from_value::<ConversationRecord>(config.config_value) {
+                    Ok(record) => {
+                        let entry = stored.entry(record.id).or_default();
+                        entry.config_ids.push(config.config_id);
+                        entry.header_id = Some(config.config_id);
+                        entry.conversation = Some(Conversation {
+                            id: record.id,
+                            title: record.title,
+                            created_at: record.created_at,
+                            messages: Vec::new(),
+                        });
+                    }
+                    Err(e) => tracing::warn!(key = %config.config_key, error = %e, "Skipping invalid conversation"),
+                }
+            } else if let Some(position) = parse_message_key(&config.config_key) {
+                match serde_json::from_value::<ChatMessage>(config.config_value) {
+                    Ok(message) => {
+                        stored.entry(position.0).or_default().config_ids.push(config.config_id);
+                        messages.insert(position, message);
+                    }
+                    Err(e) => tracing::warn!(key = %config.config_key, error = %e, "Skipping invalid message"),
+                }
+            }
+        }
+
+        // Messages arrive ordered by (conversation, index)
+        for ((id, _), message) in messages {
+            if let Some(conversation) = stored.get_mut(&id).and_then(|s| s.conversation.as_mut()) {
+                conversation.messages.push(message);
+            }
+        }
+        stored.retain(|id, s| {
+            if s.conversation.is_none() {
+                tracing::warn!(conversation = id, "Skipping messages without a conversation record");
+            }
+            s.conversation.is_some()
+        });
+
+        let count = stored.len();
+        *self.conversations.write() = stored;
+        Ok(count)
+    }
+
+    /// Start a conversation; the title defaults to the first user message
+    pub async fn create(&self, title: Option<&str>) -> Result<ConversationSummary, ChatHistoryError> {
+        let conversation = {
+            let mut conversations = self.conversations.write();
+            let id = conversations.keys().next_back().map_or(1, |id| id + 1);
+            let conversation = Conversation {
+                id,
+                title: title.map(str::trim).unwrap_or_default().to_string(),
+                created_at: now_ms(),
+                messages: Vec::new(),
+            };
+            conversations.insert(
+                id,
+                StoredConversation { conversation: Some(conversation.clone()), ..Default::default() },
+            );
+            conversation
+        };
+
+        self.save_header(&conversation).await?;
+        self.evict().await?;
+        Ok(conversation.summary())
+    }
+
+    /// Append a message
+    pub async fn append(&self, id: u64, message: ChatMessage) -> Result<ConversationSummary, ChatHistoryError> {
+        if message.content.len() > self.config.max_message_bytes {
+            return Err(ChatHistoryError::InvalidMessage(format!(
+                "content exceeds {} bytes",
+                self.config.max_message_bytes
+            )));
+        }
+
+        let (index, summary, retitled) = {
+            let mut conversations = self.conversations.write();
+            let conversation = conversations
+                .get_mut(&id)
+                .and_then(|s| s.conversation.as_mut())
+                .ok_or(ChatHistoryError::NotFound(id))?;
+            if conversation.messages.len() >= self.config.max_messages {
+                return Err(ChatHistoryError::ConversationFull(id));
+            }
+            let retitled = conversation.title.is_empty() && message.role == ChatRole::User;
+            if retitled {
+                conversation.title = default_title(&message.content);
+            }
+            conversation.messages.push(message.clone());
+            let header = retitled.then(|| conversation.clone());
+            (conversation.messages.len() - 1, conversation.summary(), header)
+        };
+
+        // In memory first, like the other stores; a backend error is reported
+        if let Some(backend) = &self.backend {
+            let value = serde_json::to_value(&message)
+                .map_err(|e| PersistenceError::SerializationError(e.to_string()))?;
+            let config_id = backend.save_config(CHAT_COMPONENT, &message_key(id, index), value, None).await?;
+            if let Some(stored) = self.conversations.write().get_mut(&id) {
+                stored.config_ids.push(config_id);
+            }
+        }
+        if let Some(conversation) = retitled {
+            self.save_header(&conversation).await?;
+        }
+        Ok(summary)
+    }
+
+    pub async fn rename(&self, id: u64, title: &str) -> Result<ConversationSummary, ChatHistoryError> {
+        let conversation = {
+            let mut conversations = self.conversations.write();
+            let conversation = conversations
+                .get_mut(&id)
+                .and_then(|s| s.conversation.as_mut())
+                .ok_or(ChatHistoryError::NotFound(id))?;
+            conversation.title = title.trim().to_string();
+            conversation.clone()
+        };
+        self.save_header(&conversation).await?;
+        Ok(conversation.summary())
+    }
+
+    /// Delete a conversation and its stored messages
+    pub async fn delete(&self, id: u64) -> Result<(), ChatHistoryError> {
+        let stored = self.conversations.write().remove(&id).ok_or(ChatHistoryError::NotFound(id))?;
+        if let Some(backend) = &self.backend {
+            for config_id in stored.config_ids {
+                backend.deactivate_config(config_id).await?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, id: u64) -> Option<Conversation> {
+        self.conversations.read().get(&id).and_then(|s| s.conversation.clone())
+    }
+
+    /// All conversations, most recently active first
+    pub fn list(&self) -> Vec<ConversationSummary> {
+        let mut summaries: Vec<ConversationSummary> = self
+            .conversations
+            .read()
+            .values()
+            .filter_map(|s| s.conversation.as_ref().map(Conversation::summary))
+            .collect();
+        summaries.sort_by(|a, b| b.updated_at.cmp(&a.updated_at).then(b.id.cmp(&a.id)));
+        summaries
+    }
+
+    /// Most recently active conversation (session restore)
+    pub fn last_conversation(&self) -> Option<Conversation> {
+        let id = self.list().first()?.id;
+        self.get(id)
+    }
+
+    /// Messages and titles containing every word of `query`, newest first
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit> {
+        let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+        if terms.is_empty() {
+            return Vec::new();
+        }
+        let matches = |text: &str| {
+            let text = text.to_lowercase();
+            terms.iter().all(|term| text.contains(term.as_str()))
+        };
+
+        let mut hits = Vec::new();
+        for stored in self.conversations.read().values() {
+            let Some(conversation) = &stored.conversation else { continue };
+            if matches(&conversation.title) {
+                hits.push(SearchHit {
+                    conversation_id: conversation.id,
+                    title: conversation.title.clone(),
+                    message_index: None,
+                    snippet: conversation.title.clone(),
+                    timestamp_ms: conversation.updated_at(),
+                });
+            }
+            for (index, message) in conversation.messages.iter().enumerate() {
+                if matches(&message.content) {
+                    hits.push(SearchHit {
+                        conversation_id: conversation.id,
+                        title: conversation.title.clone(),
+                        message_index: Some(index),
+                        snippet: snippet(&message.content, &terms[0]),
+                        timestamp_ms: message.timestamp_ms,
+                    });
+                }
+            }
+        }
+        hits.sort_by_key(|hit| std::cmp::Reverse(hit.timestamp_ms));
+        hits.truncate(limit);
+        hits
+    }
+
+    pub fn len(&self) -> usize {
+        self.conversations.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    async fn save_header(&self, conversation: &Conversation) -> Result<(), ChatHistoryError> {
+        let Some(backend) = &self.backend else {
+            return Ok(());
+        };
+        let record = ConversationRecord {
+            id: conversation.id,
+            title: conversation.title.clone(),
+            created_at: conversation.created_at,
+        };
+        let value = serde_json::to_value(&record)
+            .map_err(|e| PersistenceError::SerializationError(e.to_string()))?;
+        let parent = self.conversations.read().get(&conversation.id).and_then(|s| s.header_id);
+        let key = format!("{}{}", CONVERSATION_KEY_PREFIX, conversation.id);
+        let config_id = backend.save_config(CHAT_COMPONENT, &key, value, parent).await?;
+        if let Some(stored) = self.conversations.write().get_mut(&conversation.id) {
+            stored.header_id = Some(config_id);
+            stored.config_ids.push(config_id);
+        }
+        Ok(())
+    }
+
+    /// Drop the least recently active conversations over the limit
+    async fn evict(&self) -> Result<(), ChatHistoryError> {
+        let excess = self.list().into_iter().skip(self.config.max_conversations).map(|s| s.id).collect::<Vec<_>>();
+        for id in excess {
+            self.delete(id).await?;
+        }
+        Ok(())
+    }
+}
+
+fn message_key(id: u64, index: usize) -> String {
+    format!("{}{}:{}", MESSAGE_KEY_PREFIX, id, index)
+}
+
+fn parse_message_key(key: &str) -> Option<(u64, usize)> {
+    let (id, index) = key.strip_prefix(MESSAGE_KEY_PREFIX)?.split_once(':')?;
+    Some((id.parse().ok()?, index.parse().ok()?))
+}
+
+fn default_title(content: &str) -> String {
+    let line = content.lines().next().unwrap_or_default().trim();
+    match line.char_indices().nth(TITLE_LENGTH) {
+        Some((end, _)) => format!("{}…", line[..end].trim_end()),
+        None => line.to_string(),
+    }
+}
+
+/// Excerpt of `content` around the first occurrence of `term` (lowercase)
+fn snippet(content: &str, term: &str) -> String {
+    let lower = content.to_lowercase();
+    // Lowercasing can change byte lengths; fall back to the start then
+    let at = lower.find(term).filter(|_| lower.len() == content.len()).unwrap_or(0);
+    let floor = |mut i: usize| {
+        while !content.is_char_boundary(i) {
+            i -= 1;
+        }
+        i
+    };
+    let start = floor(at.saturating_sub(SNIPPET_CONTEXT));
+    let end = floor((at + term.len() + SNIPPET_CONTEXT).min(content.len()));
+    let mut excerpt = content[start..end].trim().to_string();
+    if start > 0 {
+        excerpt.insert(0, '…');
+    }
+    if end < content.len() {
+        excerpt.push('…');
+    }
+    excerpt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::testing::ConfigBackend;
+
+    #[tokio::test]
+    async fn test_conversations_and_titles() {
+        let history = ChatHistory::new(ChatHistoryConfig::default()).unwrap();
+        let first = history.create(None).await.unwrap();
+        history.append(first.id, ChatMessage::new(ChatRole::System, "ready")).await.unwrap();
+        history
+            .append(first.id, ChatMessage::new(ChatRole::User, "What is a cat?\nAnd a dog?").with_signal_id(7))
+            .await
+            .unwrap();
+        history.append(first.id, ChatMessage::new(ChatRole::User, "second")).await.unwrap();
+
+        let conversation = history.get(first.id).unwrap();
+        assert_eq!(conversation.title, "What is a cat?");
+        assert_eq!(conversation.messages.len(), 3);
+        assert_eq!(conversation.messages[1].signal_id, Some(7));
+
+        let second = history.create(Some("Named")).await.unwrap();
+        assert_eq!(second.id, first.id + 1);
+        history.append(second.id, ChatMessage::new(ChatRole::User, "hi")).await.unwrap();
+        assert_eq!(history.get(second.id).unwrap().title, "Named");
+        assert_eq!(history.last_conversation().unwrap().id, second.id);
+
+        assert!(matches!(
+            history.append(99, ChatMessage::new(ChatRole::User, "x")).await,
+            Err(ChatHistoryError::NotFound(99))
+        ));
+        history.delete(second.id).await.unwrap();
+        assert_eq!(history.list().len(), 1);
+        assert_eq!(default_title(&"x".repeat(100)), format!("{}…", "x".repeat(TITLE_LENGTH)));
+    }
+
+    #[tokio::test]
+    async fn test_limits() {
+        let config = ChatHistoryConfig { max_conversations: 2, max_messages: 1, max_message_bytes: 8 };
+        let history = ChatHistory::new(config).unwrap();
+        let a = history.create(Some("a")).await.unwrap();
+        history.append(a.id, ChatMessage::new(ChatRole::User, "x")).await.unwrap();
+        assert!(matches!(
+            history.append(a.id, ChatMessage::new(ChatRole::User, "y")).await,
+            Err(ChatHistoryError::ConversationFull(_))
+        ));
+
+        let b = history.create(Some("b")).await.unwrap();
+        assert!(matches!(
+            history.append(b.id, ChatMessage::new(ChatRole::User, "too long!")).await,
+            Err(ChatHistoryError::InvalidMessage(_))
+        ));
+        history.create(Some("c")).await.unwrap();
+        assert_eq!(history.len(), 2);
+        assert!(history.get(a.id).is_none(), "least recently active conversation evicted");
+
+        let bad = ChatHistoryConfig { max_messages: 0, ..Default::default() };
+        assert!(ChatHistory::new(bad).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search() {
+        let history = ChatHistory::new(ChatHistoryConfig::default()).unwrap();
+        let id = history.create(Some("Pets")).await.unwrap().id;
+        let long = format!("{} the Cat sat on the mat {}", "a".repeat(60), "b".repeat(60));
+        history.append(id, ChatMessage::new(ChatRole::User, long)).await.unwrap();
+        history.append(id, ChatMessage::new(ChatRole::Assistant, "Cats purr")).await.unwrap();
+
+        let hits = history.search("cat mat", 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].message_index, Some(0));
+        assert!(hits[0].snippet.starts_with('…') && hits[0].snippet.ends_with('…'));
+        assert!(hits[0].snippet.contains("Cat sat on the mat"));
+
+        assert_eq!(history.search("CAT", 10).len(), 2);
+        assert_eq!(history.search("pets", 10)[0].message_index, None);
+        assert!(history.search("   ", 10).is_empty());
+        assert_eq!(history.search("cat", 1).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_persistence_round_trip() {
+        let backend: Arc<dyn PersistenceBackend> = Arc::new(ConfigBackend::default());
+        let history = ChatHistory::new(ChatHistoryConfig::default()).unwrap().with_backend(backend.clone());
+        let kept = history.create(None).await.unwrap().id;
+        history.append(kept, ChatMessage::new(ChatRole::User, "hello there")).await.unwrap();
+        history
+            .append(kept, ChatMessage::new(ChatRole::Assistant, "hi").with_signal_id(3))
+            .await
+            .unwrap();
+        history.rename(kept, "Greeting").await.unwrap();
+        let deleted = history.create(Some("gone")).await.unwrap().id;
+        history.append(deleted, ChatMessage::new(ChatRole::User, "bye")).await.unwrap();
+        history.delete(deleted).await.unwrap();
+
+        let restored = ChatHistory::new(ChatHistoryConfig::default()).unwrap().with_backend(backend);
+        assert_eq!(restored.load().await.unwrap(), 1);
+        let conversation = restored.last_conversation().unwrap();
+        assert_eq!(conversation, history.get(kept).unwrap());
+        assert_eq!(conversation.title, "Greeting");
+        assert_eq!(conversation.messages[1].signal_id, Some(3));
+
+        // IDs continue after the restored ones
+        assert_eq!(restored.create(None).await.unwrap().id, kept + 1);
+    }
+}
@@ -0,0 +1,1341 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Checkpoint v1.0 - Whole-system snapshot and restore
+//!
+//! Subsystems implement [`Checkpointable`] and are registered with a
+//! [`CheckpointManager`]. A checkpoint is a directory:
+//!
+//! ```text
+//! <root>/ckpt-<unix_ms>/
+//!   manifest.json      - id, label, timestamp, sections with size + CRC32
+//!   runtime.bin        - RuntimeStorage: tokens, connections, Grid, Graph, CDNA
+//!   reflexes.bin       - IntuitionEngine fast-path reflexes
+//!   adna.json          - appraiser config + action policies
+//!   curiosity.json     - uncertainty/novelty/surprise trackers, exploration queue
+//!   graph.map          - optional read-only copy of Grid/Graph for `MappedGraph`
+//! ```
+//!
+//! Sections are written into a hidden temp directory which is renamed into
+//! place after `manifest.json` is synced, so a crash never leaves a partial
+//! checkpoint visible. Restore verifies every section before touching any
+//! subsystem and rolls back already restored sections if one fails.
+//! Registered [`CheckpointHook`]s run before any section is snapshotted,
+//! e.g. to drain the persistence write-behind queue.
+//!
+//! The manifest records the struct versions the sections were written with
+//! (`formats`); packed records from older layouts are upgraded through
+//! `migration::MIGRATIONS` on restore, and checkpoints from a newer build are
+//! refused before any subsystem is touched.
+//!
+//! Learner weights are persisted in the `runtime` section as connection
+//! confidences; register `learner::LearnerSyncHook` so they are written
+//! before each checkpoint. Any new component only needs a `Checkpointable`
+//! impl to be included.
+//!
+//! [`CheckpointManager::export_redacted`] writes a shareable copy with token
+//! labels hashed and personal text removed (see `redaction`).
+//! [`CheckpointManager::runtime_snapshot`] reads the `runtime` section
+//! without restoring it, e.g. to compare two checkpoints with `snapshot_diff`.
+//!
+//! With [`CheckpointManager::with_cipher`] every section file is sealed with
+//! ChaCha20-Poly1305 under `<checkpoint id>/<file>` (see `encryption`), so
+//! sections cannot be swapped between checkpoints. The manifest stays
+//! readable and records `encrypted`, but is not authenticated: a manager
+//! with a cipher refuses plain sections whatever the manifest says. Pass the
+//! cipher from `encryption::load_at_rest_cipher`; with it installed,
+//! `MappedGraph::open_checkpoint` reads an encrypted `graph.map` into memory
+//! instead of mapping it.
+
+use crate::adna::InMemoryADNAReader;
+use crate::cdna::CDNA;
+use crate::connection_v3::ConnectionV3;
+use crate::curiosity::CuriosityDrive;
+use crate::encryption::{self, EncryptionError, FileCipher};
+use crate::graph::mapped::{self, MappedGraph};
+use crate::graph::EdgeInfo;
+use crate::intuition_engine::IntuitionEngine;
+use crate::migration::{self, BinaryFormat, FormatVersions, MigrationError, MigrationRegistry, MIGRATIONS};
+use crate::redaction::{RedactionReport, Redactor, SectionHandling};
+use crate::runtime_storage::{RuntimeSnapshot, RuntimeStorage};
+use crate::token::Token;
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Manifest format version
+pub const CHECKPOINT_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// Checkpoint errors
+#[derive(Debug)]
+pub enum CheckpointError {
+    IoError(String),
+    NotFound(String),
+    Corrupt(String),
+    UnknownSection(String),
+    DuplicateSection(String),
+    SectionFailed { section: String, message: String },
+    Migration(MigrationError),
+    Encryption(EncryptionError),
+}
+
+impl std::fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckpointError::IoError(msg) => write!(f, "IO error: {}", msg),
+            CheckpointError::NotFound(id) => write!(f, "Checkpoint '{}' not found", id),
+            CheckpointError::Corrupt(msg) => write!(f, "Corrupt checkpoint: {}", msg),
+            CheckpointError::UnknownSection(name) => write!(f, "No subsystem registered for section '{}'", name),
+            CheckpointError::DuplicateSection(name) => write!(f, "Section '{}' is already registered", name),
+            CheckpointError::SectionFailed { section, message } => {
+                write!(f, "Section '{}' failed: {}", section, message)
+            }
+            CheckpointError::Migration(e) => write!(f, "Migration failed: {}", e),
+            CheckpointError::Encryption(e) => write!(f, "Encryption failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for CheckpointError {}
+
+impl From<MigrationError> for CheckpointError {
+    fn from(e: MigrationError) -> Self {
+        CheckpointError::Migration(e)
+    }
+}
+
+impl From<EncryptionError> for CheckpointError {
+    fn from(e: EncryptionError) -> Self {
+        CheckpointError::Encryption(e)
+    }
+}
+
+impl From<std::io::Error> for CheckpointError {
+    fn from(e: std::io::Error) -> Self {
+        CheckpointError::IoError(e.to_string())
+    }
+}
+
+fn section_error(section: &str, message: impl ToString) -> CheckpointError {
+    CheckpointError::SectionFailed {
+        section: section.to_string(),
+        message: message.to_string(),
+    }
+}
+
+// ============================================================================
+// Manifest
+// ============================================================================
+
+/// One section file in a checkpoint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectionEntry {
+    /// Section name (e.g. "runtime")
+    pub name: String,
+
+    /// File name inside the checkpoint directory
+    pub file: String,
+
+    /// File size in bytes
+    pub bytes: u64,
+
+    /// CRC32 of the file contents
+    pub crc32: u32,
+}
+
+/// Checkpoint manifest (`manifest.json`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointManifest {
+    pub format_version: u32,
+
+    /// Checkpoint ID (directory name)
+    pub id: String,
+
+    /// Optional user label
+    pub label: Option<String>,
+
+    /// Creation time (Unix milliseconds)
+    pub created_at: u64,
+
+    /// Crate version that wrote the checkpoint
+    pub crate_version: String,
+
+    pub sections: Vec<SectionEntry>,
+
+    /// Binary struct versions of the section data (missing = baseline)
+    #[serde(default)]
+    pub formats: FormatVersions,
+
+    /// Section files are sealed with the manager's `FileCipher`
+    #[serde(default)]
+    pub encrypted: bool,
+}
+
+// ============================================================================
+// Checkpointable
+// ============================================================================
+
+/// Subsystem that can be captured in a checkpoint
+#[async_trait]
+pub trait Checkpointable: Send + Sync {
+    /// Unique section name
+    fn section(&self) -> &'static str;
+
+    /// File extension of the serialized section ("bin", "json")
+    fn extension(&self) -> &'static str {
+        "bin"
+    }
+
+    /// Serialize current state
+    async fn snapshot(&self) -> Result<Vec<u8>, CheckpointError>;
+
+    /// Replace current state; must leave state unchanged on error
+    async fn restore(&self, data: &[u8]) -> Result<(), CheckpointError>;
+
+    /// Restore data written with the given struct versions
+    ///
+    /// Sections holding packed structs override this to upgrade older
+    /// records; the default assumes the data is version independent.
+    async fn restore_versioned(&self, data: &[u8], versions: &FormatVersions) -> Result<(), CheckpointError> {
+        let _ = versions;
+        self.restore(data).await
+    }
+}
+
+/// Work that must finish before a checkpoint is taken, e.g. draining a
+/// write-behind queue so the durable store is not behind the checkpoint
+#[async_trait]
+pub trait CheckpointHook: Send + Sync {
+    /// Name used in errors
+    fn name(&self) -> &'static str;
+
+    /// Called before any section is snapshotted; an error aborts the checkpoint
+    async fn before_checkpoint(&self) -> Result<(), String>;
+}
+
+// ============================================================================
+// Binary encoding helpers
+// ============================================================================
+
+struct ByteWriter(Vec<u8>);
+
+impl ByteWriter {
+    fn u8(&mut self, v: u8) {
+        self.0.push(v);
+    }
+    fn u32(&mut self, v: u32) {
+        self.0.extend_from_slice(&v.to_le_bytes());
+    }
+    fn u64(&mut self, v: u64) {
+        self.0.extend_from_slice(&v.to_le_bytes());
+    }
+    fn f32(&mut self, v: f32) {
+        self.0.extend_from_slice(&v.to_le_bytes());
+    }
+    fn bytes(&mut self, v: &[u8]) {
+        self.0.extend_from_slice(v);
+    }
+    fn len(&mut self, n: usize) {
+        self.u32(n as u32);
+    }
+}
+
+struct ByteReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], CheckpointError> {
+        let end = self.pos.checked_add(n).filter(|&e| e <= self.data.len()).ok_or_else(|| {
+            CheckpointError::Corrupt(format!("unexpected end of data at byte {}", self.pos))
+        })?;
+        let slice = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], CheckpointError> {
+        let mut out = [0u8; N];
+        out.copy_from_slice(self.take(N)?);
+        Ok(out)
+    }
+
+    /// Packed struct written at the version recorded in `versions`,
+    /// upgraded to the current layout
+    fn record<const N: usize>(
+        &mut self,
+        format: BinaryFormat,
+        versions: &FormatVersions,
+        registry: &MigrationRegistry,
+    ) -> Result<[u8; N], CheckpointError> {
+        let version = migration::version_of(versions, format);
+        let bytes = self.take(registry.record_size(format, version)?)?;
+        Ok(registry.upgrade(format, version, bytes)?)
+    }
+
+    fn u8(&mut self) -> Result<u8, CheckpointError> {
+        Ok(self.take(1)?[0])
+    }
+    fn u32(&mut self) -> Result<u32, CheckpointError> {
+        Ok(u32::from_le_bytes(self.array()?))
+    }
+    fn u64(&mut self) -> Result<u64, CheckpointError> {
+        Ok(u64::from_le_bytes(self.array()?))
+    }
+    fn f32(&mut self) -> Result<f32, CheckpointError> {
+        Ok(f32::from_le_bytes(self.array()?))
+    }
+    fn len(&mut self) -> Result<usize, CheckpointError> {
+        Ok(self.u32()? as usize)
+    }
+
+    fn is_done(&self) -> bool {
+        self.pos == self.data.len()
+    }
+
+    fn finish(&self) -> Result<(), CheckpointError> {
+        if self.pos != self.data.len() {
+            return Err(CheckpointError::Corrupt(format!(
+                "{} trailing bytes",
+                self.data.len() - self.pos
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn encode_runtime(snapshot: &RuntimeSnapshot) -> Vec<u8> {
+    let mut w = ByteWriter(Vec::new());
+    w.u32(snapshot.next_token_id);
+    w.u64(snapshot.next_connection_id);
+
+    w.len(snapshot.tokens.len());
+    for token in &snapshot.tokens {
+        w.bytes(&token.to_bytes());
+    }
+    w.len(snapshot.connections.len());
+    for (id, conn) in &snapshot.connections {
+        w.u64(*id);
+        w.bytes(&conn.to_bytes());
+    }
+    w.len(snapshot.grid_tokens.len());
+    for token in &snapshot.grid_tokens {
+        w.bytes(&token.to_bytes());
+    }
+    w.len(snapshot.graph_nodes.len());
+    for node in &snapshot.graph_nodes {
+        w.u32(*node);
+    }
+    w.len(snapshot.graph_edges.len());
+    for (id, edge) in &snapshot.graph_edges {
+        w.u64(*id);
+        w.u32(edge.from_id);
+        w.u32(edge.to_id);
+        w.u8(edge.edge_type);
+        w.f32(edge.weight);
+        w.u8(edge.bidirectional as u8);
+    }
+    w.bytes(&snapshot.cdna.to_bytes());
+    w.len(snapshot.labels.len());
+    for (id, label) in &snapshot.labels {
+        w.u32(*id);
+        w.len(label.len());
+        w.bytes(label.as_bytes());
+    }
+    // Edge layer masks trail the original layout so older checkpoints still load
+    w.len(snapshot.graph_edges.len());
+    for (_, edge) in &snapshot.graph_edges {
+        w.u8(edge.active_levels);
+    }
+    w.0
+}
+
+fn decode_runtime(data: &[u8], versions: &FormatVersions) -> Result<RuntimeSnapshot, CheckpointError> {
+    let registry = MIGRATIONS.read();
+    let mut r = ByteReader::new(data);
+    let next_token_id = r.u32()?;
+    let next_connection_id = r.u64()?;
+
+    let tokens = (0..r.len()?)
+        .map(|_| Ok(Token::from_bytes(&r.record(BinaryFormat::Token, versions, &registry)?)))
+        .collect::<Result<Vec<_>, CheckpointError>>()?;
+    let connections = (0..r.len()?)
+        .map(|_| {
+            let id = r.u64()?;
+            Ok((id, ConnectionV3::from_bytes(&r.record(BinaryFormat::ConnectionV3, versions, &registry)?)))
+        })
+        .collect::<Result<Vec<_>, CheckpointError>>()?;
+    let grid_tokens = (0..r.len()?)
+        .map(|_| Ok(Token::from_bytes(&r.record(BinaryFormat::Token, versions, &registry)?)))
+        .collect::<Result<Vec<_>, CheckpointError>>()?;
+    let graph_nodes = (0..r.len()?).map(|_| r.u32()).collect::<Result<Vec<_>, _>>()?;
+    let graph_edges = (0..r.len()?)
+        .map(|_| {
+            let id = r.u64()?;
+            let edge = EdgeInfo {
+                from_id: r.u32()?,
+                to_id: r.u32()?,
+                edge_type: r.u8()?,
+                weight: r.f32()?,
+                bidirectional: r.u8()? != 0,
+                active_levels: crate::connection_v3::active_levels::ALL,
+            };
+            Ok((id, edge))
+        })
+        .collect::<Result<Vec<_>, CheckpointError>>()?;
+    let cdna = CDNA::from_bytes(&r.record(BinaryFormat::Cdna, versions, &registry)?);
+    let labels = (0..r.len()?)
+        .map(|_| {
+            let id = r.u32()?;
+            let len = r.len()?;
+            let label = String::from_utf8(r.take(len)?.to_vec())
+                .map_err(|e| CheckpointError::Corrupt(e.to_string()))?;
+            Ok((id, label))
+        })
+        .collect::<Result<Vec<_>, CheckpointError>>()?;
+    let mut graph_edges = graph_edges;
+    if !r.is_done() {
+        if r.len()? != graph_edges.len() {
+            return Err(CheckpointError::Corrupt("edge level count mismatch".to_string()));
+        }
+        for (_, edge) in graph_edges.iter_mut() {
+            edge.active_levels = r.u8()?;
+        }
+    }
+    r.finish()?;
+
+    Ok(RuntimeSnapshot {
+        tokens,
+        next_token_id,
+        connections,
+        next_connection_id,
+        grid_tokens,
+        graph_nodes,
+        graph_edges,
+        cdna,
+        labels,
+    })
+}
+
+fn encode_reflexes(reflexes: &[(u64, ConnectionV3)]) -> Vec<u8> {
+    let mut w = ByteWriter(Vec::new());
+    w.len(reflexes.len());
+    for (hash, conn) in reflexes {
+        w.u64(*hash);
+        w.bytes(&conn.to_bytes());
+    }
+    w.0
+}
+
+fn decode_reflexes(data: &[u8], versions: &FormatVersions) -> Result<Vec<(u64, ConnectionV3)>, CheckpointError> {
+    let registry = MIGRATIONS.read();
+    let mut r = ByteReader::new(data);
+    let reflexes = (0..r.len()?)
+        .map(|_| {
+            let hash = r.u64()?;
+            Ok((hash, ConnectionV3::from_bytes(&r.record(BinaryFormat::ConnectionV3, versions, &registry)?)))
+        })
+        .collect::<Result<Vec<_>, CheckpointError>>()?;
+    r.finish()?;
+    Ok(reflexes)
+}
+
+// ============================================================================
+// Subsystem implementations
+// ============================================================================
+
+#[async_trait]
+impl Checkpointable for RuntimeStorage {
+    fn section(&self) -> &'static str {
+        "runtime"
+    }
+
+    async fn snapshot(&self) -> Result<Vec<u8>, CheckpointError> {
+        Ok(encode_runtime(&self.export_snapshot()))
+    }
+
+    async fn restore(&self, data: &[u8]) -> Result<(), CheckpointError> {
+        self.restore_versioned(data, &migration::current_versions()).await
+    }
+
+    async fn restore_versioned(&self, data: &[u8], versions: &FormatVersions) -> Result<(), CheckpointError> {
+        let snapshot = decode_runtime(data, versions)?;
+        self.import_snapshot(snapshot)
+            .map_err(|e| section_error("runtime", e))
+    }
+}
+
+#[async_trait]
+impl Checkpointable for RwLock<IntuitionEngine> {
+    fn section(&self) -> &'static str {
+        "reflexes"
+    }
+
+    async fn snapshot(&self) -> Result<Vec<u8>, CheckpointError> {
+        Ok(encode_reflexes(&self.read().export_reflexes()))
+    }
+
+    async fn restore(&self, data: &[u8]) -> Result<(), CheckpointError> {
+        self.restore_versioned(data, &migration::current_versions()).await
+    }
+
+    async fn restore_versioned(&self, data: &[u8], versions: &FormatVersions) -> Result<(), CheckpointError> {
+        let reflexes = decode_reflexes(data, versions)?;
+        self.write().import_reflexes(reflexes);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Checkpointable for InMemoryADNAReader {
+    fn section(&self) -> &'static str {
+        "adna"
+    }
+
+    fn extension(&self) -> &'static str {
+        "json"
+    }
+
+    async fn snapshot(&self) -> Result<Vec<u8>, CheckpointError> {
+        self.export_state().await.map_err(|e| section_error("adna", e))
+    }
+
+    async fn restore(&self, data: &[u8]) -> Result<(), CheckpointError> {
+        self.import_state(data).await.map_err(|e| section_error("adna", e))
+    }
+}
+
+#[async_trait]
+impl Checkpointable for CuriosityDrive {
+    fn section(&self) -> &'static str {
+        "curiosity"
+    }
+
+    fn extension(&self) -> &'static str {
+        "json"
+    }
+
+    async fn snapshot(&self) -> Result<Vec<u8>, CheckpointError> {
+        self.export_state().map_err(|e| section_error("curiosity", e))
+    }
+
+    async fn restore(&self, data: &[u8]) -> Result<(), CheckpointError> {
+        self.import_state(data).map_err(|e| section_error("curiosity", e))
+    }
+}
+
+/// Writes `graph.map` from RuntimeStorage so inference-only deployments can
+/// open it directly (`MappedGraph::open_checkpoint`)
+///
+/// The file is derived from the `runtime` section; restoring only checks
+/// that it is well-formed.
+pub struct MappedGraphSection(pub Arc<RuntimeStorage>);
+
+#[async_trait]
+impl Checkpointable for MappedGraphSection {
+    fn section(&self) -> &'static str {
+        "graph"
+    }
+
+    fn extension(&self) -> &'static str {
+        "map"
+    }
+
+    async fn snapshot(&self) -> Result<Vec<u8>, CheckpointError> {
+        Ok(mapped::encode(&self.0.export_snapshot()))
+    }
+
+    async fn restore(&self, data: &[u8]) -> Result<(), CheckpointError> {
+        MappedGraph::from_bytes(data.to_vec())
+            .map(|_| ())
+            .map_err(|e| section_error("graph", e))
+    }
+}
+
+// ============================================================================
+// CheckpointManager
+// ============================================================================
+
+/// Checkpoint manager configuration
+#[derive(Debug, Clone)]
+pub struct CheckpointConfig {
+    /// Directory holding checkpoint directories
+    pub root: PathBuf,
+
+    /// Number of checkpoints to keep (0 = keep all)
+    pub keep_last: usize,
+}
+
+impl Default for CheckpointConfig {
+    fn default() -> Self {
+        Self {
+            root: PathBuf::from("checkpoints"),
+            keep_last: 5,
+        }
+    }
+}
+
+/// Writes and restores checkpoints of registered subsystems
+pub struct CheckpointManager {
+    config: CheckpointConfig,
+    sections: RwLock<Vec<Arc<dyn Checkpointable>>>,
+    hooks: RwLock<Vec<Arc<dyn CheckpointHook>>>,
+    /// Seals section files at rest
+    cipher: Option<Arc<FileCipher>>,
+    /// Serializes checkpoint/restore operations
+    op_lock: tokio::sync::Mutex<()>,
+}
+
+impl CheckpointManager {
+    pub fn new(config: CheckpointConfig) -> Self {
+        Self {
+            config,
+            sections: RwLock::new(Vec::new()),
+            hooks: RwLock::new(Vec::new()),
+            cipher: None,
+            op_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Encrypt section files of new checkpoints and decrypt encrypted ones
+    pub fn with_cipher(mut self, cipher: impl Into<Arc<FileCipher>>) -> Self {
+        self.cipher = Some(cipher.into());
+        self
+    }
+
+    /// Register a subsystem
+    pub fn register(&self, subsystem: Arc<dyn Checkpointable>) -> Result<(), CheckpointError> {
+        let mut sections = self.sections.write();
+        if sections.iter().any(|s| s.section() == subsystem.section()) {
+            return Err(CheckpointError::DuplicateSection(subsystem.section().to_string()));
+        }
+        sections.push(subsystem);
+        Ok(())
+    }
+
+    /// Run `hook` before every checkpoint
+    pub fn add_hook(&self, hook: Arc<dyn CheckpointHook>) {
+        self.hooks.write().push(hook);
+    }
+
+    /// Names of registered sections
+    pub fn section_names(&self) -> Vec<&'static str> {
+        self.sections.read().iter().map(|s| s.section()).collect()
+    }
+
+    fn registered(&self) -> Vec<Arc<dyn Checkpointable>> {
+        self.sections.read().clone()
+    }
+
+    /// Write a checkpoint of all registered subsystems
+    pub async fn checkpoint(&self, label: Option<&str>) -> Result<CheckpointManifest, CheckpointError> {
+        let _guard = self.op_lock.lock().await;
+        let hooks = self.hooks.read().clone();
+        for hook in hooks {
+            hook.before_checkpoint()
+                .await
+                .map_err(|e| section_error(hook.name(), e))?;
+        }
+        fs::create_dir_all(&self.config.root)?;
+
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        let mut id = format!("ckpt-{}", created_at);
+        let mut suffix = 1;
+        while self.config.root.join(&id).exists() {
+            id = format!("ckpt-{}-{}", created_at, suffix);
+            suffix += 1;
+        }
+
+        let tmp_dir = self.config.root.join(format!(".{}.tmp", id));
+        fs::create_dir_all(&tmp_dir)?;
+
+        let result = self.write_sections(&tmp_dir, &id, label, created_at).await;
+        let manifest = match result {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                let _ = fs::remove_dir_all(&tmp_dir);
+                return Err(e);
+            }
+        };
+
+        fs::rename(&tmp_dir, self.config.root.join(&id))?;
+        self.prune()?;
+        Ok(manifest)
+    }
+
+    async fn write_sections(
+        &self,
+        dir: &Path,
+        id: &str,
+        label: Option<&str>,
+        created_at: u64,
+    ) -> Result<CheckpointManifest, CheckpointError> {
+        let mut entries = Vec::new();
+        for subsystem in self.registered() {
+            let file = format!("{}.{}", subsystem.section(), subsystem.extension());
+            let mut data = subsystem.snapshot().await?;
+            if let Some(cipher) = &self.cipher {
+                data = cipher.seal(&data, &encryption::scoped_name(id, &file))?;
+            }
+            write_synced(&dir.join(&file), &data)?;
+            entries.push(SectionEntry {
+                name: subsystem.section().to_string(),
+                file,
+                bytes: data.len() as u64,
+                crc32: crc32fast::hash(&data),
+            });
+        }
+
+        let manifest = CheckpointManifest {
+            format_version: CHECKPOINT_FORMAT_VERSION,
+            id: id.to_string(),
+            label: label.map(|s| s.to_string()),
+            created_at,
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            sections: entries,
+            formats: migration::current_versions(),
+            encrypted: self.cipher.is_some(),
+        };
+        let json = serde_json::to_vec_pretty(&manifest)
+            .map_err(|e| CheckpointError::Corrupt(e.to_string()))?;
+        write_synced(&dir.join(MANIFEST_FILE), &json)?;
+        Ok(manifest)
+    }
+
+    /// Read a checkpoint manifest
+    pub fn manifest(&self, id: &str) -> Result<CheckpointManifest, CheckpointError> {
+        if id.is_empty() || id.starts_with('.') || id.contains(['/', '\\']) {
+            return Err(CheckpointError::NotFound(id.to_string()));
+        }
+        let path = self.config.root.join(id).join(MANIFEST_FILE);
+        let data = fs::read(&path).map_err(|_| CheckpointError::NotFound(id.to_string()))?;
+        let manifest: CheckpointManifest =
+            serde_json::from_slice(&data).map_err(|e| CheckpointError::Corrupt(e.to_string()))?;
+        if manifest.format_version > CHECKPOINT_FORMAT_VERSION {
+            return Err(CheckpointError::Corrupt(format!(
+                "unsupported format version {}",
+                manifest.format_version
+            )));
+        }
+        // Sections are sealed under the ID; it must name this directory
+        if manifest.id != id {
+            return Err(CheckpointError::Corrupt(format!("manifest of '{}' names '{}'", id, manifest.id)));
+        }
+        Ok(manifest)
+    }
+
+    /// List checkpoints, oldest first
+    pub fn list(&self) -> Result<Vec<CheckpointManifest>, CheckpointError> {
+        if !self.config.root.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut manifests: Vec<_> = fs::read_dir(&self.config.root)?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| !name.starts_with('.'))
+            .filter_map(|name| self.manifest(&name).ok())
+            .collect();
+        manifests.sort_by(|a, b| (a.created_at, &a.id).cmp(&(b.created_at, &b.id)));
+        Ok(manifests)
+    }
+
+    /// Restore all sections of a checkpoint
+    ///
+    /// Every section must have a registered subsystem. Registered subsystems
+    /// missing from the checkpoint are left unchanged.
+    pub async fn restore(&self, id: &str) -> Result<CheckpointManifest, CheckpointError> {
+        let _guard = self.op_lock.lock().await;
+        let manifest = self.manifest(id)?;
+        let dir = self.config.root.join(id);
+        let registered = self.registered();
+
+        // 1. Load and verify everything before touching any subsystem
+        MIGRATIONS.read().check_all(&manifest.formats)?;
+        let mut pending = Vec::new();
+        for entry in &manifest.sections {
+            let subsystem = registered
+                .iter()
+                .find(|s| s.section() == entry.name)
+                .cloned()
+                .ok_or_else(|| CheckpointError::UnknownSection(entry.name.clone()))?;
+            let data = self.read_section(&dir, &manifest, entry)?;
+            pending.push((subsystem, data));
+        }
+
+        // 2. Apply, keeping the previous state for rollback
+        let mut applied: Vec<(Arc<dyn Checkpointable>, Vec<u8>)> = Vec::new();
+        for (subsystem, data) in pending {
+            let backup = subsystem.snapshot().await?;
+            if let Err(e) = subsystem.restore_versioned(&data, &manifest.formats).await {
+                for (done, previous) in applied.into_iter().rev() {
+                    let _ = done.restore(&previous).await;
+                }
+                return Err(e);
+            }
+            applied.push((subsystem, backup));
+        }
+
+        Ok(manifest)
+    }
+
+    /// Restore the newest checkpoint, if any
+    pub async fn restore_latest(&self) -> Result<Option<CheckpointManifest>, CheckpointError> {
+        match self.list()?.pop() {
+            Some(latest) => self.restore(&latest.id).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Runtime data of a checkpoint, without restoring anything
+    pub async fn runtime_snapshot(&self, id: &str) -> Result<RuntimeSnapshot, CheckpointError> {
+        let _guard = self.op_lock.lock().await;
+        let manifest = self.manifest(id)?;
+        MIGRATIONS.read().check_all(&manifest.formats)?;
+        let entry = manifest.sections.iter().find(|entry| entry.name == "runtime").ok_or_else(|| {
+            CheckpointError::SectionFailed {
+                section: "runtime".to_string(),
+                message: format!("not in checkpoint '{}'", id),
+            }
+        })?;
+        let data = self.read_section(&self.config.root.join(id), &manifest, entry)?;
+        decode_runtime(&data, &manifest.formats)
+    }
+
+    /// Write a shareable copy of a checkpoint to `dest` with personal text removed
+    ///
+    /// Token labels in `runtime` are hashed, text fields are stripped from
+    /// JSON sections, `reflexes` and `graph` are numeric and carried over,
+    /// and any other section is dropped. Binary sections are re-encoded at
+    /// the current formats. The copy gets the ID `<id>-redacted` and no
+    /// label; the returned report verifies that no removed text survived.
+    /// The copy is meant for sharing and is always written unencrypted.
+    pub async fn export_redacted(
+        &self,
+        id: &str,
+        dest: &Path,
+        mut redactor: Redactor,
+    ) -> Result<RedactionReport, CheckpointError> {
+        let _guard = self.op_lock.lock().await;
+        let manifest = self.manifest(id)?;
+        let dir = self.config.root.join(id);
+        if dest.exists() {
+            return Err(CheckpointError::IoError(format!("{} already exists", dest.display())));
+        }
+        MIGRATIONS.read().check_all(&manifest.formats)?;
+
+        let mut outputs = Vec::new();
+        for entry in &manifest.sections {
+            let data = self.read_section(&dir, &manifest, entry)?;
+            let redacted = match (entry.name.as_str(), entry.file.rsplit('.').next()) {
+                ("runtime", _) => {
+                    let mut snapshot = decode_runtime(&data, &manifest.formats)?;
+                    redactor.redact_snapshot(&mut snapshot);
+                    redactor.record_section(&entry.name, SectionHandling::Redacted);
+                    encode_runtime(&snapshot)
+                }
+                ("reflexes", _) => {
+                    redactor.record_section(&entry.name, SectionHandling::Copied);
+                    encode_reflexes(&decode_reflexes(&data, &manifest.formats)?)
+                }
+                ("graph", Some("map")) => {
+                    redactor.record_section(&entry.name, SectionHandling::Copied);
+                    data
+                }
+                (_, Some("json")) => {
+                    let mut value: serde_json::Value =
+                        serde_json::from_slice(&data).map_err(|e| section_error(&entry.name, e))?;
+                    redactor.redact_value(&mut value);
+                    redactor.record_section(&entry.name, SectionHandling::Redacted);
+                    serde_json::to_vec(&value).map_err(|e| section_error(&entry.name, e))?
+                }
+                _ => {
+                    redactor.record_section(&entry.name, SectionHandling::Dropped);
+                    continue;
+                }
+            };
+            outputs.push((entry, redacted));
+        }
+
+        let redacted_manifest = CheckpointManifest {
+            format_version: CHECKPOINT_FORMAT_VERSION,
+            id: format!("{}-redacted", manifest.id),
+            label: None,
+            created_at: manifest.created_at,
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            sections: outputs
+                .iter()
+                .map(|(entry, data)| SectionEntry {
+                    name: entry.name.clone(),
+                    file: entry.file.clone(),
+                    bytes: data.len() as u64,
+                    crc32: crc32fast::hash(data),
+                })
+                .collect(),
+            formats: migration::current_versions(),
+            encrypted: false,
+        };
+        let manifest_json = serde_json::to_vec_pretty(&redacted_manifest)
+            .map_err(|e| CheckpointError::Corrupt(e.to_string()))?;
+
+        let file_name = dest
+            .file_name()
+            .ok_or_else(|| CheckpointError::IoError(format!("invalid destination {}", dest.display())))?;
+        let tmp_dir = dest.with_file_name(format!(".{}.tmp", file_name.to_string_lossy()));
+        fs::create_dir_all(&tmp_dir)?;
+        let written = outputs
+            .iter()
+            .try_for_each(|(entry, data)| write_synced(&tmp_dir.join(&entry.file), data))
+            .and_then(|_| write_synced(&tmp_dir.join(MANIFEST_FILE), &manifest_json));
+        if let Err(e) = written {
+            let _ = fs::remove_dir_all(&tmp_dir);
+            return Err(e);
+        }
+        fs::rename(&tmp_dir, dest)?;
+
+        let mut written: Vec<&[u8]> = outputs.iter().map(|(_, data)| data.as_slice()).collect();
+        written.push(&manifest_json);
+        Ok(redactor.finish(&written))
+    }
+
+    /// Read a section file, verify its checksum and decrypt it
+    ///
+    /// With a cipher every section must be sealed, whatever the manifest's
+    /// `encrypted` flag says.
+    fn read_section(
+        &self,
+        dir: &Path,
+        manifest: &CheckpointManifest,
+        entry: &SectionEntry,
+    ) -> Result<Vec<u8>, CheckpointError> {
+        let data = fs::read(dir.join(&entry.file))?;
+        if data.len() as u64 != entry.bytes || crc32fast::hash(&data) != entry.crc32 {
+            return Err(CheckpointError::Corrupt(format!("section '{}' checksum mismatch", entry.name)));
+        }
+        match &self.cipher {
+            Some(cipher) => Ok(cipher.open(&data, &encryption::scoped_name(&manifest.id, &entry.file))?),
+            None if manifest.encrypted || FileCipher::is_sealed(&data) => Err(EncryptionError::MissingKey.into()),
+            None => Ok(data),
+        }
+    }
+
+    /// Delete checkpoints beyond `keep_last`
+    fn prune(&self) -> Result<(), CheckpointError> {
+        if self.config.keep_last == 0 {
+            return Ok(());
+        }
+        let manifests = self.list()?;
+        let excess = manifests.len().saturating_sub(self.config.keep_last);
+        for manifest in manifests.into_iter().take(excess) {
+            fs::remove_dir_all(self.config.root.join(&manifest.id))?;
+        }
+        Ok(())
+    }
+}
+
+fn write_synced(path: &Path, data: &[u8]) -> Result<(), CheckpointError> {
+    let mut file = File::create(path)?;
+    file.write_all(data)?;
+    file.sync_all()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curiosity::CuriosityConfig;
+
+    fn manager(dir: &Path, keep_last: usize) -> CheckpointManager {
+        CheckpointManager::new(CheckpointConfig {
+            root: dir.to_path_buf(),
+            keep_last,
+        })
+    }
+
+    fn token(x: f32) -> Token {
+        Token::from_state_f32(0, &[x, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0])
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_restore_runtime() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(RuntimeStorage::new());
+        let a = storage.create_token(token(1.0));
+        let b = storage.create_token(token(2.0));
+        storage.create_connection(ConnectionV3::new(a, b));
+        storage.set_cdna_profile(3);
+
+        let manager = manager(dir.path(), 0);
+        manager.register(storage.clone()).unwrap();
+        let manifest = manager.checkpoint(Some("before")).await.unwrap();
+        assert_eq!(manifest.sections.len(), 1);
+        assert_eq!(manifest.label.as_deref(), Some("before"));
+
+        // Mutate, then restore
+        storage.create_token(token(3.0));
+        storage.clear_tokens();
+        storage.set_cdna_profile(0);
+        assert_eq!(storage.count_tokens(), 0);
+
+        manager.restore(&manifest.id).await.unwrap();
+        assert_eq!(storage.count_tokens(), 2);
+        assert_eq!(storage.count_connections(), 1);
+        assert_eq!(storage.get_cdna_profile(), 3);
+        assert!(storage.get_token(b).is_some());
+
+        // Counters restored: next token gets the same ID as before
+        assert_eq!(storage.create_token(token(4.0)), b + 1);
+    }
+
+    #[tokio::test]
+    async fn test_mapped_graph_section() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(RuntimeStorage::new());
+        let a = storage.create_token(token(1.0));
+        storage.create_token(token(2.0));
+
+        let manager = manager(dir.path(), 0);
+        manager.register(storage.clone()).unwrap();
+        manager.register(Arc::new(MappedGraphSection(storage.clone()))).unwrap();
+        let manifest = manager.checkpoint(None).await.unwrap();
+        assert!(manifest.sections.iter().any(|s| s.file == "graph.map"));
+
+        let graph = MappedGraph::open(dir.path().join(&manifest.id).join("graph.map")).unwrap();
+        assert_eq!(graph.token_count(), 2);
+        assert_eq!(graph.node_count(), 2);
+        assert!(graph.get_token(a).is_some());
+
+        manager.restore(&manifest.id).await.unwrap();
+    }
+
+    struct FailingHook;
+
+    #[async_trait]
+    impl CheckpointHook for FailingHook {
+        fn name(&self) -> &'static str {
+            "failing"
+        }
+
+        async fn before_checkpoint(&self) -> Result<(), String> {
+            Err("backend down".to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hook_failure_aborts_checkpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        let manager = manager(dir.path(), 0);
+        manager.register(Arc::new(RuntimeStorage::new())).unwrap();
+        manager.add_hook(Arc::new(FailingHook));
+
+        let err = manager.checkpoint(None).await.unwrap_err();
+        assert!(matches!(err, CheckpointError::SectionFailed { ref section, .. } if section == "failing"));
+        assert!(manager.list().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_curiosity_and_prune() {
+        let dir = tempfile::tempdir().unwrap();
+        let curiosity = Arc::new(CuriosityDrive::new(CuriosityConfig::default()));
+        curiosity.add_exploration_target(crate::curiosity::ExplorationTarget::new(
+            [0.5f64; 8],
+            0.9,
+            crate::curiosity::ExplorationReason::HighUncertainty,
+        ));
+
+        let manager = manager(dir.path(), 2);
+        manager.register(curiosity.clone()).unwrap();
+        assert!(manager.register(curiosity.clone()).is_err());
+
+        let first = manager.checkpoint(None).await.unwrap();
+        curiosity.get_next_target();
+        assert!(curiosity.peek_next_target().is_none());
+
+        manager.restore(&first.id).await.unwrap();
+        assert!(curiosity.peek_next_target().is_some());
+
+        manager.checkpoint(None).await.unwrap();
+        manager.checkpoint(None).await.unwrap();
+        let list = manager.list().unwrap();
+        assert_eq!(list.len(), 2);
+        assert!(list.iter().all(|m| m.id != first.id));
+    }
+
+    #[tokio::test]
+    async fn test_export_redacted() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(RuntimeStorage::new());
+        let mut snapshot = storage.export_snapshot();
+        snapshot.tokens.push(token(1.0));
+        snapshot.next_token_id = 2;
+        snapshot.labels.push((1, "alice_smith".to_string()));
+        storage.import_snapshot(snapshot).unwrap();
+
+        let manager = manager(dir.path(), 0);
+        manager.register(storage.clone()).unwrap();
+        let manifest = manager.checkpoint(Some("private")).await.unwrap();
+
+        let id = format!("{}-redacted", manifest.id);
+        let redactor = Redactor::new(Default::default()).unwrap();
+        let report = manager
+            .export_redacted(&manifest.id, &dir.path().join(&id), redactor)
+            .await
+            .unwrap();
+        assert!(report.is_clean());
+        assert_eq!(report.labels_hashed, 1);
+        assert_eq!(report.sections_redacted, vec!["runtime".to_string()]);
+
+        let redacted = manager.manifest(&id).unwrap();
+        assert_eq!(redacted.label, None);
+        manager.restore(&id).await.unwrap();
+        let labels = storage.export_snapshot().labels;
+        assert_eq!(labels.len(), 1);
+        assert!(labels[0].1.starts_with("h:"));
+        assert_eq!(storage.count_tokens(), 1);
+
+        let redactor = Redactor::new(Default::default()).unwrap();
+        assert!(manager.export_redacted(&manifest.id, &dir.path().join(&id), redactor).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_checkpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(RuntimeStorage::new());
+        let mut snapshot = storage.export_snapshot();
+        snapshot.tokens.push(token(1.0));
+        snapshot.next_token_id = 2;
+        snapshot.labels.push((1, "secret_word".to_string()));
+        storage.import_snapshot(snapshot).unwrap();
+
+        let key = FileCipher::generate_key();
+        let manager = manager(dir.path(), 0).with_cipher(FileCipher::new(&key));
+        manager.register(storage.clone()).unwrap();
+        manager.register(Arc::new(MappedGraphSection(storage.clone()))).unwrap();
+        let manifest = manager.checkpoint(None).await.unwrap();
+        assert!(manifest.encrypted);
+        let ckpt = dir.path().join(&manifest.id);
+        let graph = MappedGraph::open_checkpoint_with(&ckpt, Some(&FileCipher::new(&key))).unwrap();
+        assert!(!graph.is_mapped());
+        assert!(MappedGraph::open_with(ckpt.join("graph.map"), Some(&FileCipher::new(&key))).is_err());
+
+        let data = fs::read(dir.path().join(&manifest.id).join("runtime.bin")).unwrap();
+        assert!(FileCipher::is_sealed(&data));
+        assert!(!data.windows(11).any(|w| w == b"secret_word"));
+
+        storage.clear_tokens();
+        manager.restore(&manifest.id).await.unwrap();
+        assert_eq!(storage.count_tokens(), 1);
+
+        // Without the key (or with another one) nothing is restored
+        let plain = self::manager(dir.path(), 0);
+        plain.register(storage.clone()).unwrap();
+        plain.register(Arc::new(MappedGraphSection(storage.clone()))).unwrap();
+        assert!(matches!(
+            plain.restore(&manifest.id).await,
+            Err(CheckpointError::Encryption(EncryptionError::MissingKey))
+        ));
+        let other = self::manager(dir.path(), 0).with_cipher(FileCipher::new(&FileCipher::generate_key()));
+        other.register(storage.clone()).unwrap();
+        other.register(Arc::new(MappedGraphSection(storage.clone()))).unwrap();
+        assert!(matches!(
+            other.restore(&manifest.id).await,
+            Err(CheckpointError::Encryption(EncryptionError::Decrypt))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_checkpoint_tampering() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(RuntimeStorage::new());
+        storage.create_token(token(1.0));
+        let manager = manager(dir.path(), 0).with_cipher(FileCipher::new(&FileCipher::generate_key()));
+        manager.register(storage.clone()).unwrap();
+        let first = manager.checkpoint(None).await.unwrap();
+        storage.create_token(token(2.0));
+        let second = manager.checkpoint(None).await.unwrap();
+
+        // Rewrite a manifest so `file` holds `data` with a matching checksum
+        let replace = |id: &str, file: &str, data: &[u8], encrypted: bool| {
+            let ckpt = dir.path().join(id);
+            fs::write(ckpt.join(file), data).unwrap();
+            let mut manifest: CheckpointManifest =
+                serde_json::from_slice(&fs::read(ckpt.join(MANIFEST_FILE)).unwrap()).unwrap();
+            let entry = manifest.sections.iter_mut().find(|s| s.file == file).unwrap();
+            entry.bytes = data.len() as u64;
+            entry.crc32 = crc32fast::hash(data);
+            manifest.encrypted = encrypted;
+            fs::write(ckpt.join(MANIFEST_FILE), serde_json::to_vec(&manifest).unwrap()).unwrap();
+        };
+
+        // A section sealed for another checkpoint fails authentication
+        let sealed = fs::read(dir.path().join(&first.id).join("runtime.bin")).unwrap();
+        replace(&second.id, "runtime.bin", &sealed, true);
+        assert!(matches!(
+            manager.restore(&second.id).await,
+            Err(CheckpointError::Encryption(EncryptionError::Decrypt))
+        ));
+
+        // Downgrade: a plain section under a manifest claiming `encrypted: false`
+        replace(&first.id, "runtime.bin", &encode_runtime(&storage.export_snapshot()), false);
+        assert!(matches!(
+            manager.restore(&first.id).await,
+            Err(CheckpointError::Encryption(EncryptionError::NotSealed))
+        ));
+        assert_eq!(storage.count_tokens(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_restore_rejects_corruption() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(RuntimeStorage::new());
+        storage.create_token(token(1.0));
+
+        let manager = manager(dir.path(), 0);
+        manager.register(storage.clone()).unwrap();
+        let manifest = manager.checkpoint(None).await.unwrap();
+
+        let file = dir.path().join(&manifest.id).join("runtime.bin");
+        let mut data = fs::read(&file).unwrap();
+        data[0] ^= 0xFF;
+        fs::write(&file, data).unwrap();
+
+        storage.clear_tokens();
+        assert!(matches!(manager.restore(&manifest.id).await, Err(CheckpointError::Corrupt(_))));
+        assert_eq!(storage.count_tokens(), 0);
+
+        assert!(matches!(manager.restore("../etc").await, Err(CheckpointError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_runtime_encoding_round_trip() {
+        let storage = RuntimeStorage::new();
+        let a = storage.create_token(token(1.0));
+        let b = storage.create_token(token(-1.0));
+        storage.create_connection(ConnectionV3::new(a, b));
+
+        let snapshot = storage.export_snapshot();
+        let encoded = encode_runtime(&snapshot);
+        let decoded = decode_runtime(&encoded, &FormatVersions::new()).unwrap();
+        assert_eq!(decoded.tokens.len(), 2);
+        assert_eq!(decoded.connections.len(), 1);
+        assert_eq!(encode_runtime(&decoded), encoded);
+
+        assert!(decode_runtime(&encoded[..encoded.len() - 1], &FormatVersions::new()).is_err());
+    }
+
+    /// Hypothetical ConnectionV3 v2: the 32-byte core without the learning extension
+    fn connection_v2_to_v3(old: &[u8]) -> Result<Vec<u8>, String> {
+        let mut new = ConnectionV3::new(0, 0).to_bytes();
+        new[..32].copy_from_slice(old);
+        Ok(new.to_vec())
+    }
+
+    fn rewrite_fixture(dir: &Path, manifest: &mut CheckpointManifest, data: &[u8], connection_version: u32) {
+        let section = manifest.sections.iter_mut().find(|s| s.name == "runtime").unwrap();
+        fs::write(dir.join(&section.file), data).unwrap();
+        section.bytes = data.len() as u64;
+        section.crc32 = crc32fast::hash(data);
+        manifest
+            .formats
+            .insert(BinaryFormat::ConnectionV3.name().to_string(), connection_version);
+        fs::write(dir.join(MANIFEST_FILE), serde_json::to_vec(manifest).unwrap()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_restore_migrates_old_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(RuntimeStorage::new());
+        let mut connection = ConnectionV3::new(1, 2);
+        connection.pull_strength = 2.5;
+        let id = storage.create_connection(connection);
+
+        let manager = manager(dir.path(), 0);
+        manager.register(storage.clone()).unwrap();
+        let mut manifest = manager.checkpoint(None).await.unwrap();
+        assert_eq!(manifest.formats, migration::current_versions());
+
+        // Turn the checkpoint into a v2 fixture: no tokens, so the single
+        // connection record sits at bytes 28..92 after its u64 ID
+        let ckpt_dir = dir.path().join(&manifest.id);
+        let current = fs::read(ckpt_dir.join("runtime.bin")).unwrap();
+        let fixture = [&current[..60], &current[92..]].concat();
+        rewrite_fixture(&ckpt_dir, &mut manifest, &fixture, 2);
+
+        let _ = MIGRATIONS
+            .write()
+            .register(BinaryFormat::ConnectionV3, 2, 32, connection_v2_to_v3);
+        storage.delete_connection(id);
+        manager.restore(&manifest.id).await.unwrap();
+        let restored = storage.get_connection(id).unwrap();
+        let (pull, mutability) = (restored.pull_strength, restored.mutability);
+        assert_eq!(pull, 2.5);
+        assert_eq!(mutability, crate::connection_v3::ConnectionMutability::Learnable as u8);
+
+        // A checkpoint from a newer build is refused and nothing is touched
+        let newer = BinaryFormat::ConnectionV3.current_version() + 1;
+        rewrite_fixture(&ckpt_dir, &mut manifest, &current, newer);
+        storage.delete_connection(id);
+        let err = manager.restore(&manifest.id).await.unwrap_err();
+        assert!(matches!(
+            err,
+            CheckpointError::Migration(MigrationError::UnsupportedDowngrade { found, .. }) if found == newer
+        ));
+        assert_eq!(storage.count_connections(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_runtime_snapshot_diff() {
+        use crate::snapshot_diff::{SnapshotDiff, SnapshotDiffConfig};
+
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(RuntimeStorage::new());
+        storage.create_token(token(1.0));
+        let manager = manager(dir.path(), 0);
+        manager.register(storage.clone()).unwrap();
+        let before = manager.checkpoint(None).await.unwrap();
+        storage.create_token(token(2.0));
+        let after = manager.checkpoint(None).await.unwrap();
+
+        let diff = SnapshotDiff::compute(
+            &manager.runtime_snapshot(&before.id).await.unwrap(),
+            &manager.runtime_snapshot(&after.id).await.unwrap(),
+            &SnapshotDiffConfig::default(),
+        )
+        .unwrap();
+        assert_eq!(diff.tokens.added_ids, vec![2]);
+        assert_eq!(diff.grid.moved, 0);
+        assert_eq!(storage.count_tokens(), 2);
+    }
+}
@@ -0,0 +1,526 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Config File v1.0 - layered TOML config with hot reload
+//!
+//! Configuration is resolved in three layers, later ones winning:
+//!
+//! 1. compiled defaults (`Default` of each config type)
+//! 2. a TOML file with one table per section (`[curiosity]`, `[adna]`, ...)
+//! 3. environment variables `NEUROGRAPH__<SECTION>__<FIELD>`, e.g.
+//!    `NEUROGRAPH__CURIOSITY__BOREDOM_THRESHOLD=0.4`; deeper fields nest
+//!    further (`NEUROGRAPH__ADNA__CURIOSITY__WEIGHT=0.5`)
+//!
+//! [`LayeredConfig::build`] produces the startup config of a section.
+//! [`ConfigWatcher`] then polls the file and hands changed fields to the
+//! [`HotSection`] registered for their section: settings sections go
+//! through [`SettingsManager::update`] (appraiser weights through
+//! [`AdnaSection`]), so only their live fields take effect immediately and
+//! the rest are reported as restart-required. A file that fails to parse
+//! is logged and the previous config stays in force.
+//!
+//! Every change is emitted as a `tracing` event, so it shows up in the log
+//! subsystem (`LOG_HUB`) next to the rest of the runtime logs.
+
+use crate::adna::{ADNAReader, AppraiserConfig, InMemoryADNAReader};
+use crate::settings::{merge_into, merge_known_fields, ConfigSection, SettingsManager};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+/// Prefix of environment overrides
+pub const ENV_PREFIX: &str = "NEUROGRAPH__";
+
+/// Section name of the ADNA appraiser weights
+pub const ADNA_SECTION: &str = "adna";
+
+/// Config file errors
+#[derive(Debug)]
+pub enum ConfigFileError {
+    Io(std::io::Error),
+    Parse(String),
+    Invalid { section: String, message: String },
+}
+
+impl fmt::Display for ConfigFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigFileError::Io(e) => write!(f, "Config file I/O error: {}", e),
+            ConfigFileError::Parse(e) => write!(f, "Config file parse error: {}", e),
+            ConfigFileError::Invalid { section, message } => {
+                write!(f, "Invalid config section '{}': {}", section, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigFileError {}
+
+impl From<std::io::Error> for ConfigFileError {
+    fn from(e: std::io::Error) -> Self {
+        ConfigFileError::Io(e)
+    }
+}
+
+/// File and environment layers, merged per section
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LayeredConfig {
+    sections: Map<String, Value>,
+}
+
+impl LayeredConfig {
+    /// Read `path` (if any and present) and overlay the process environment
+    pub fn from_env(path: Option<&Path>) -> Result<Self, ConfigFileError> {
+        Self::load(path, std::env::vars())
+    }
+
+    /// Read `path` (if any and present) and overlay `env`
+    pub fn load<I>(path: Option<&Path>, env: I) -> Result<Self, ConfigFileError>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let text = match path {
+            Some(path) => read_optional(path)?,
+            None => String::new(),
+        };
+        let mut config = Self::parse(&text)?;
+        config.apply_env(env);
+        Ok(config)
+    }
+
+    /// Parse TOML text; every top-level entry must be a table
+    pub fn parse(text: &str) -> Result<Self, ConfigFileError> {
+        let value: Value = toml::from_str(text).map_err(|e| ConfigFileError::Parse(e.to_string()))?;
+        let Value::Object(sections) = value else {
+            return Err(ConfigFileError::Parse("expected a table of sections".to_string()));
+        };
+        if let Some((name, _)) = sections.iter().find(|(_, value)| !value.is_object()) {
+            return Err(ConfigFileError::Invalid {
+                section: name.clone(),
+                message: "expected a table".to_string(),
+            });
+        }
+        Ok(Self { sections })
+    }
+
+    /// Overlay `NEUROGRAPH__<SECTION>__<FIELD>` variables
+    pub fn apply_env<I>(&mut self, env: I)
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        for (key, raw) in env {
+            let Some(path) = key.strip_prefix(ENV_PREFIX) else {
+                continue;
+            };
+            let path: Vec<String> = path.split("__").map(str::to_lowercase).collect();
+            if path.len() < 2 || path.iter().any(String::is_empty) {
+                tracing::warn!(key = %key, "Ignoring malformed config environment variable");
+                continue;
+            }
+
+            let mut patch = parse_env_value(&raw);
+            for segment in path.iter().rev() {
+                let mut object = Map::new();
+                object.insert(segment.clone(), patch);
+                patch = Value::Object(object);
+            }
+            let mut sections = Value::Object(std::mem::take(&mut self.sections));
+            merge_into(&mut sections, &patch);
+            if let Value::Object(sections) = sections {
+                self.sections = sections;
+            }
+        }
+    }
+
+    /// Section names present in any layer
+    pub fn section_names(&self) -> Vec<String> {
+        self.sections.keys().cloned().collect()
+    }
+
+    /// Fields set for a section (empty table if none)
+    pub fn section(&self, name: &str) -> Value {
+        self.sections
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| Value::Object(Map::new()))
+    }
+
+    /// Startup config of a section: `defaults` with the file and env layers on top
+    pub fn build<T>(&self, section: &str, defaults: &T) -> Result<T, ConfigFileError>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let invalid = |message: String| ConfigFileError::Invalid {
+            section: section.to_string(),
+            message,
+        };
+        let mut value = serde_json::to_value(defaults).map_err(|e| invalid(e.to_string()))?;
+        merge_known_fields(&mut value, &self.section(section), section).map_err(invalid)?;
+        serde_json::from_value(value).map_err(|e| invalid(e.to_string()))
+    }
+}
+
+fn read_optional(path: &Path) -> Result<String, ConfigFileError> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => Ok(text),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Environment values are TOML scalars (`0.4`, `true`, `[1, 2]`); anything else is a string
+fn parse_env_value(raw: &str) -> Value {
+    toml::from_str::<Map<String, Value>>(&format!("value = {}", raw))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+/// A config section the watcher can update at runtime
+#[async_trait]
+pub trait HotSection: Send + Sync {
+    /// Table name in the config file
+    fn name(&self) -> &str;
+
+    /// Apply changed fields; returns the fields that need a restart
+    async fn apply(&self, patch: Value) -> Result<Vec<String>, String>;
+}
+
+/// A [`SettingsManager`] section; live fields apply, others need a restart
+pub struct SettingsSection {
+    settings: Arc<SettingsManager>,
+    section: ConfigSection,
+}
+
+impl SettingsSection {
+    pub fn new(settings: Arc<SettingsManager>, section: ConfigSection) -> Self {
+        Self { settings, section }
+    }
+}
+
+#[async_trait]
+impl HotSection for SettingsSection {
+    fn name(&self) -> &str {
+        self.section.as_str()
+    }
+
+    async fn apply(&self, patch: Value) -> Result<Vec<String>, String> {
+        let update = self.settings.update(self.section, patch).await.map_err(|e| e.to_string())?;
+        Ok(update.restart_required)
+    }
+}
+
+/// ADNA appraiser weights; every field applies immediately
+pub struct AdnaSection {
+    reader: Arc<InMemoryADNAReader>,
+}
+
+impl AdnaSection {
+    pub fn new(reader: Arc<InMemoryADNAReader>) -> Self {
+        Self { reader }
+    }
+}
+
+#[async_trait]
+impl HotSection for AdnaSection {
+    fn name(&self) -> &str {
+        ADNA_SECTION
+    }
+
+    async fn apply(&self, patch: Value) -> Result<Vec<String>, String> {
+        let current = self.reader.get_appraiser_config().await.map_err(|e| e.to_string())?;
+        let mut value = serde_json::to_value(current).map_err(|e| e.to_string())?;
+        merge_known_fields(&mut value, &patch, "ADNA")?;
+        let updated: AppraiserConfig = serde_json::from_value(value).map_err(|e| e.to_string())?;
+        self.reader.update_config(updated).await;
+        Ok(Vec::new())
+    }
+}
+
+/// What happened to a changed field
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "status", content = "reason")]
+pub enum ChangeOutcome {
+    Applied,
+    RestartRequired,
+    Rejected(String),
+}
+
+/// One changed field of a reload
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigChange {
+    pub section: String,
+    pub field: String,
+    pub outcome: ChangeOutcome,
+}
+
+type EnvSource = Arc<dyn Fn() -> Vec<(String, String)> + Send + Sync>;
+
+/// Polls the config file and applies changed fields to registered sections
+pub struct ConfigWatcher {
+    path: PathBuf,
+    env: EnvSource,
+    sections: Vec<Arc<dyn HotSection>>,
+    current: Mutex<LayeredConfig>,
+    /// Checksum of the last file contents seen by `poll`
+    checksum: Mutex<u32>,
+}
+
+impl ConfigWatcher {
+    /// `initial` is the config the components were built from
+    pub fn new(path: impl Into<PathBuf>, initial: LayeredConfig) -> Self {
+        let path = path.into();
+        let checksum = read_optional(&path).map(|text| crc32fast::hash(text.as_bytes())).unwrap_or(0);
+        Self {
+            path,
+            env: Arc::new(|| std::env::vars().collect()),
+            sections: Vec::new(),
+            current: Mutex::new(initial),
+            checksum: Mutex::new(checksum),
+        }
+    }
+
+    /// Environment layer source (the process environment by default)
+    pub fn with_env<F>(mut self, env: F) -> Self
+    where
+        F: Fn() -> Vec<(String, String)> + Send + Sync + 'static,
+    {
+        self.env = Arc::new(env);
+        self
+    }
+
+    pub fn with_section(mut self, section: Arc<dyn HotSection>) -> Self {
+        self.sections.push(section);
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Config currently in force
+    pub fn current(&self) -> LayeredConfig {
+        self.current.lock().clone()
+    }
+
+    /// Reload if the file contents changed since the last poll
+    pub async fn poll(&self) -> Result<Vec<ConfigChange>, ConfigFileError> {
+        let checksum = crc32fast::hash(read_optional(&self.path)?.as_bytes());
+        if *self.checksum.lock() == checksum {
+            return Ok(Vec::new());
+        }
+        *self.checksum.lock() = checksum;
+        self.reload().await
+    }
+
+    /// Re-read all layers and apply what changed
+    pub async fn reload(&self) -> Result<Vec<ConfigChange>, ConfigFileError> {
+        let next = LayeredConfig::load(Some(&self.path), (self.env)())?;
+        let previous = self.current();
+
+        let mut names = previous.section_names();
+        names.extend(next.section_names());
+        names.sort();
+        names.dedup();
+
+        let mut changes = Vec::new();
+        for name in names {
+            let (patch, removed) = diff_section(&previous.section(&name), &next.section(&name));
+            if patch.is_empty() && removed.is_empty() {
+                continue;
+            }
+
+            // Removed fields fall back to defaults only on the next start
+            changes.extend(removed.into_iter().map(|field| ConfigChange {
+                section: name.clone(),
+                field,
+                outcome: ChangeOutcome::RestartRequired,
+            }));
+
+            let fields: Vec<String> = patch.keys().cloned().collect();
+            let outcomes = match self.sections.iter().find(|s| s.name() == name) {
+                Some(section) => match section.apply(Value::Object(patch)).await {
+                    Ok(restart) => fields
+                        .iter()
+                        .map(|field| match restart.contains(field) {
+                            true => ChangeOutcome::RestartRequired,
+                            false => ChangeOutcome::Applied,
+                        })
+                        .collect(),
+                    Err(e) => vec![ChangeOutcome::Rejected(e); fields.len()],
+                },
+                // Only read at startup through `LayeredConfig::build`
+                None => vec![ChangeOutcome::RestartRequired; fields.len()],
+            };
+            changes.extend(fields.into_iter().zip(outcomes).map(|(field, outcome)| ConfigChange {
+                section: name.clone(),
+                field,
+                outcome,
+            }));
+        }
+
+        for change in &changes {
+            match &change.outcome {
+                ChangeOutcome::Applied => {
+                    tracing::info!(section = %change.section, field = %change.field, "Config change applied")
+                }
+                ChangeOutcome::RestartRequired => {
+                    tracing::warn!(section = %change.section, field = %change.field, "Config change requires restart")
+                }
+                ChangeOutcome::Rejected(reason) => {
+                    tracing::warn!(section = %change.section, field = %change.field, reason = %reason, "Config change rejected")
+                }
+            }
+        }
+
+        *self.current.lock() = next;
+        Ok(changes)
+    }
+
+    /// Poll the file every `interval`; errors are logged and the previous config kept
+    pub fn spawn(self: Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.poll().await {
+                    tracing::warn!(path = %self.path.display(), error = %e, "Config reload failed, keeping previous config");
+                }
+            }
+        })
+    }
+}
+
+/// Top-level fields that changed or appeared, and fields that were removed
+fn diff_section(previous: &Value, next: &Value) -> (Map<String, Value>, Vec<String>) {
+    let empty = Map::new();
+    let previous = previous.as_object().unwrap_or(&empty);
+    let next = next.as_object().unwrap_or(&empty);
+
+    let changed = next
+        .iter()
+        .filter(|(key, value)| previous.get(*key) != Some(*value))
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+    let removed = previous.keys().filter(|key| !next.contains_key(*key)).cloned().collect();
+    (changed, removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curiosity::{CuriosityConfig, CuriosityDrive};
+    use serde_json::json;
+
+    fn env(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_layers() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("neurograph.toml");
+        std::fs::write(&path, "[curiosity]\nboredom_threshold = 0.4\nnovelty_weight = 0.2\n").unwrap();
+
+        let config = LayeredConfig::load(
+            Some(&path),
+            env(&[
+                ("NEUROGRAPH__CURIOSITY__NOVELTY_WEIGHT", "0.5"),
+                ("NEUROGRAPH__ADNA__CURIOSITY__WEIGHT", "0.9"),
+                ("NEUROGRAPH__I18N__LOCALE", "ru"),
+                ("NEUROGRAPH_IGNORED", "1"),
+            ]),
+        )
+        .unwrap();
+
+        let curiosity = config.build("curiosity", &CuriosityConfig::default()).unwrap();
+        assert_eq!(curiosity.boredom_threshold, 0.4);
+        assert_eq!(curiosity.novelty_weight, 0.5);
+        assert_eq!(curiosity.exploration_interval_ms, CuriosityConfig::default().exploration_interval_ms);
+        assert_eq!(config.section("adna"), json!({"curiosity": {"weight": 0.9}}));
+        assert_eq!(config.section("i18n"), json!({"locale": "ru"}));
+
+        let bad = LayeredConfig::parse("[curiosity]\nno_such_field = 1\n").unwrap();
+        assert!(matches!(
+            bad.build("curiosity", &CuriosityConfig::default()),
+            Err(ConfigFileError::Invalid { .. })
+        ));
+        assert!(matches!(LayeredConfig::parse("curiosity = 1"), Err(ConfigFileError::Invalid { .. })));
+        assert!(matches!(LayeredConfig::parse("[curiosity"), Err(ConfigFileError::Parse(_))));
+        assert_eq!(LayeredConfig::load(Some(&dir.path().join("missing.toml")), Vec::new()).unwrap(), LayeredConfig::default());
+    }
+
+    #[tokio::test]
+    async fn test_hot_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("neurograph.toml");
+        std::fs::write(&path, "[curiosity]\nboredom_threshold = 0.4\n").unwrap();
+
+        let initial = LayeredConfig::load(Some(&path), Vec::new()).unwrap();
+        let drive = Arc::new(CuriosityDrive::new(initial.build("curiosity", &CuriosityConfig::default()).unwrap()));
+        let settings = Arc::new(SettingsManager::new());
+        let target = drive.clone();
+        settings
+            .register_live(&drive.config(), move |config: CuriosityConfig| target.update_config(config))
+            .unwrap();
+        let adna = Arc::new(InMemoryADNAReader::new(AppraiserConfig::default()));
+
+        let watcher = ConfigWatcher::new(&path, initial)
+            .with_env(Vec::new)
+            .with_section(Arc::new(SettingsSection::new(settings, ConfigSection::Curiosity)))
+            .with_section(Arc::new(AdnaSection::new(adna.clone())));
+        assert!(watcher.poll().await.unwrap().is_empty());
+
+        std::fs::write(
+            &path,
+            "[curiosity]\nboredom_threshold = 0.3\nsurprise_history_size = 10\n\n[adna.curiosity]\nweight = 0.7\n\n[gateway]\nmax_text_length = 100\n",
+        )
+        .unwrap();
+        let changes = watcher.poll().await.unwrap();
+        let outcome = |section: &str, field: &str| {
+            changes
+                .iter()
+                .find(|c| c.section == section && c.field == field)
+                .map(|c| c.outcome.clone())
+        };
+        assert_eq!(outcome("curiosity", "boredom_threshold"), Some(ChangeOutcome::Applied));
+        assert_eq!(outcome("curiosity", "surprise_history_size"), Some(ChangeOutcome::RestartRequired));
+        assert_eq!(outcome("adna", "curiosity"), Some(ChangeOutcome::Applied));
+        assert_eq!(outcome("gateway", "max_text_length"), Some(ChangeOutcome::RestartRequired));
+        assert_eq!(drive.config().boredom_threshold, 0.3);
+        assert_eq!(adna.get_appraiser_config().await.unwrap().curiosity.weight, 0.7);
+
+        // Invalid values are rejected and the running config is kept
+        std::fs::write(&path, "[curiosity]\nboredom_threshold = 7.0\nsurprise_history_size = 10\n").unwrap();
+        let changes = watcher.poll().await.unwrap();
+        let rejected = changes.iter().find(|c| c.field == "boredom_threshold").unwrap();
+        assert!(matches!(rejected.outcome, ChangeOutcome::Rejected(_)));
+        assert_eq!(drive.config().boredom_threshold, 0.3);
+
+        // A broken file leaves the previous config in force
+        std::fs::write(&path, "[curiosity").unwrap();
+        assert!(matches!(watcher.poll().await, Err(ConfigFileError::Parse(_))));
+        assert_eq!(watcher.current().section("curiosity")["boredom_threshold"], json!(7.0));
+    }
+}
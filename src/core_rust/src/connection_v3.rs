@@ -66,6 +66,18 @@ pub enum ConnectionMutability {
     Hypothesis = 2,
 }
 
+impl ConnectionMutability {
+    /// Convert raw mutability code (`ConnectionV3::mutability`)
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::Immutable),
+            1 => Some(Self::Learnable),
+            2 => Some(Self::Hypothesis),
+            _ => None,
+        }
+    }
+}
+
 /// Connection types - organized in 11 categories (176 total types)
 /// Based on Connection_V3_UNIFIED.md specification
 #[repr(u8)]
@@ -270,6 +282,43 @@ pub enum ConnectionType {
     Alternates = 0xAF,
 }
 
+impl ConnectionType {
+    /// Highest defined connection type code
+    pub const MAX: u8 = 0xAF;
+
+    /// Convert raw type code (e.g. `EdgeInfo::edge_type`) to ConnectionType
+    pub fn from_u8(value: u8) -> Option<Self> {
+        if value <= Self::MAX {
+            // SAFETY: repr(u8) enum with every code in 0x00..=0xAF defined
+            Some(unsafe { std::mem::transmute::<u8, ConnectionType>(value) })
+        } else {
+            None
+        }
+    }
+
+    /// Category name (one of the 11 groups of 16 types)
+    pub fn category(&self) -> &'static str {
+        match *self as u8 >> 4 {
+            0x0 => "semantic",
+            0x1 => "causal",
+            0x2 => "temporal",
+            0x3 => "spatial",
+            0x4 => "logical",
+            0x5 => "associative",
+            0x6 => "structural",
+            0x7 => "functional",
+            0x8 => "emotional",
+            0x9 => "rule",
+            _ => "dynamic",
+        }
+    }
+
+    /// Default mutability for this type
+    pub fn mutability(&self) -> ConnectionMutability {
+        guess_mutability(*self as u8)
+    }
+}
+
 /// Connection flags (bit field)
 pub mod connection_flags {
     pub const ACTIVE: u8 = 0x01;
@@ -292,6 +341,8 @@ pub mod active_levels {
     pub const L6_SOCIAL: u8 = 0x20;
     pub const L7_TEMPORAL: u8 = 0x40;
     pub const L8_ABSTRACT: u8 = 0x80;
+    /// All eight levels
+    pub const ALL: u8 = 0xFF;
 }
 
 /// Connection V3.0 - 64-byte learning-capable structure
@@ -394,6 +445,16 @@ impl ConnectionV3 {
         }
     }
 
+    /// Serialize to bytes (64 bytes)
+    pub fn to_bytes(&self) -> [u8; 64] {
+        unsafe { std::mem::transmute(*self) }
+    }
+
+    /// Deserialize from bytes (64 bytes)
+    pub fn from_bytes(bytes: &[u8; 64]) -> Self {
+        unsafe { std::mem::transmute(*bytes) }
+    }
+
     /// Check if connection can be modified by IntuitionEngine
     pub fn can_modify(&self) -> bool {
         self.mutability != ConnectionMutability::Immutable as u8
@@ -426,6 +487,26 @@ impl ConnectionV3 {
         self.flags |= connection_flags::MODIFIED;
     }
 
+    /// Confidence as a weight in [0.0, 1.0]
+    pub fn weight(&self) -> f32 {
+        self.confidence as f32 / 255.0
+    }
+
+    /// Store an externally learned weight in `confidence` (quantized to u8)
+    ///
+    /// Returns false for immutable connections and when the quantized value
+    /// is unchanged.
+    pub fn set_weight(&mut self, weight: f32) -> bool {
+        let confidence = (weight.clamp(0.0, 1.0) * 255.0).round() as u8;
+        if !self.can_modify() || confidence == self.confidence {
+            return false;
+        }
+        self.confidence = confidence;
+        self.last_update = current_timestamp();
+        self.flags |= connection_flags::MODIFIED;
+        true
+    }
+
     /// Apply decay for hypothesis connections (time-based)
     pub fn apply_decay(&mut self) {
         if self.mutability != ConnectionMutability::Hypothesis as u8 {
@@ -852,6 +933,27 @@ impl ConnectionV3 {
 /// Statistical analysis for automatic proposal generation based on experience.
 /// These are simplified implementations for Phase 4 - full IntuitionEngine
 /// would have more sophisticated pattern detection.
+/// Decode and validate a serialized ConnectionV3
+///
+/// Rejects input that is not exactly 64 bytes, an unknown mutability or
+/// connection type, and non-finite physics fields.
+pub fn validate_bytes(bytes: &[u8]) -> Result<ConnectionV3, String> {
+    let bytes: &[u8; 64] = bytes
+        .try_into()
+        .map_err(|_| format!("ConnectionV3 requires 64 bytes, got {}", bytes.len()))?;
+    let connection = ConnectionV3::from_bytes(bytes);
+    if connection.mutability > ConnectionMutability::Hypothesis as u8 {
+        return Err(format!("Unknown mutability {}", connection.mutability));
+    }
+    if !guardian_validation::is_connection_type_allowed(connection.connection_type) {
+        return Err(format!("Unknown connection type 0x{:02X}", connection.connection_type));
+    }
+    if !connection.pull_strength.is_finite() || !connection.preferred_distance.is_finite() {
+        return Err("Pull strength and preferred distance must be finite".to_string());
+    }
+    Ok(connection)
+}
+
 pub mod learning_stats {
     use super::*;
 
@@ -1126,7 +1228,7 @@ fn current_timestamp() -> u32 {
 }
 
 /// Guess mutability based on connection type category
-fn guess_mutability(conn_type: u8) -> ConnectionMutability {
+pub(crate) fn guess_mutability(conn_type: u8) -> ConnectionMutability {
     match conn_type {
         // Semantic - Immutable
         0x00..=0x0F => ConnectionMutability::Immutable,
@@ -1228,6 +1330,18 @@ mod tests {
         assert_eq!(conn2.token_b_id, 5);
     }
 
+    #[test]
+    fn test_connection_type_from_u8() {
+        assert_eq!(ConnectionType::from_u8(0x00), Some(ConnectionType::Synonym));
+        assert_eq!(ConnectionType::from_u8(0x50), Some(ConnectionType::AssociatedWith));
+        assert_eq!(ConnectionType::from_u8(0xAF), Some(ConnectionType::Alternates));
+        assert_eq!(ConnectionType::from_u8(0xB0), None);
+
+        assert_eq!(ConnectionType::Hypernym.category(), "semantic");
+        assert_eq!(ConnectionType::Alternates.category(), "dynamic");
+        assert_eq!(ConnectionType::Cause.mutability(), ConnectionMutability::Learnable);
+    }
+
     #[test]
     fn test_connection_type_sets_mutability() {
         let mut conn = ConnectionV3::new(1, 2);
@@ -0,0 +1,563 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Consolidation v1.0 - Offline "sleep" phase
+//!
+//! A consolidation pass runs while the system is not serving signals:
+//!
+//! 1. **Replay** - a batch of experience is sampled from the ExperienceStream,
+//!    prioritized by |reward| (`SamplingStrategy::PrioritizedByReward`).
+//! 2. **Co-activation** - a replayed event activates every stored token whose
+//!    8D state lies within `match_radius` of the event's state or action.
+//!    A connection is co-activated when both of its endpoints are.
+//! 3. **Strengthen** - Learnable connections co-activated in at least
+//!    `min_coactivations` replayed events move toward 1:
+//!    `w ← w + strengthen_rate · (1 − w)`.
+//! 4. **Prune** - Hypothesis connections with weight below
+//!    `hypothesis_prune_threshold` that were not co-activated are deleted.
+//! 5. **Compact** - the Learner's weight table drops pruned edges and
+//!    reclaims their slots.
+//!
+//! The pass is triggered by `SystemCommand::Sleep` (see `Gateway`) or by
+//! [`Consolidator::spawn`] once no activity was reported via
+//! [`Consolidator::touch`] for `idle_after_secs`. Each pass ends with a
+//! [`ConsolidationSummary`], which is also emitted as a Guardian
+//! `Consolidation` event. No pass runs while safe mode freezes learning
+//! ([`Consolidator::with_safe_mode`]).
+
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+use crate::connection_v3::ConnectionMutability;
+use crate::experience_stream::{ExperienceStream, SamplingStrategy};
+use crate::guardian::{Event as GuardianEvent, EventType as GuardianEventType, Guardian};
+use crate::learner::Learner;
+use crate::runtime_storage::RuntimeStorage;
+use crate::safe_mode::SafeMode;
+
+/// Consolidation configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConsolidationConfig {
+    /// Experience events replayed per pass
+    pub replay_batch: usize,
+
+    /// Priority exponent for reward-prioritized replay
+    pub priority_alpha: f64,
+
+    /// Max 8D distance between a token and a replayed state/action to activate it
+    pub match_radius: f32,
+
+    /// Replayed co-activations needed before a connection is strengthened
+    pub min_coactivations: usize,
+
+    /// Fraction of the remaining distance to 1.0 added per pass [0.0, 1.0]
+    pub strengthen_rate: f32,
+
+    /// Hypothesis connections below this weight are pruned [0.0, 1.0]
+    pub hypothesis_prune_threshold: f32,
+
+    /// Seconds without activity before an idle pass (0 = never)
+    pub idle_after_secs: u64,
+}
+
+impl Default for ConsolidationConfig {
+    fn default() -> Self {
+        Self {
+            replay_batch: 256,
+            priority_alpha: 1.0,
+            match_radius: 0.5,
+            min_coactivations: 3,
+            strengthen_rate: 0.1,
+            hypothesis_prune_threshold: 0.1,
+            idle_after_secs: 300,
+        }
+    }
+}
+
+impl ConsolidationConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.replay_batch == 0 {
+            return Err("replay_batch must be > 0".to_string());
+        }
+        if !self.priority_alpha.is_finite() || self.priority_alpha < 0.0 {
+            return Err("priority_alpha must be finite and >= 0".to_string());
+        }
+        if !self.match_radius.is_finite() || self.match_radius <= 0.0 {
+            return Err("match_radius must be finite and > 0".to_string());
+        }
+        if self.min_coactivations == 0 {
+            return Err("min_coactivations must be > 0".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.strengthen_rate) {
+            return Err(format!("strengthen_rate must be 0.0-1.0, got {}", self.strengthen_rate));
+        }
+        if !(0.0..=1.0).contains(&self.hypothesis_prune_threshold) {
+            return Err(format!(
+                "hypothesis_prune_threshold must be 0.0-1.0, got {}",
+                self.hypothesis_prune_threshold
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// What started a consolidation pass
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConsolidationTrigger {
+    /// No activity for `idle_after_secs`
+    Idle,
+    /// Explicit request (SystemCommand::Sleep)
+    Command,
+}
+
+impl std::fmt::Display for ConsolidationTrigger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConsolidationTrigger::Idle => write!(f, "idle"),
+            ConsolidationTrigger::Command => write!(f, "command"),
+        }
+    }
+}
+
+/// Result of one consolidation pass
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConsolidationSummary {
+    pub trigger: ConsolidationTrigger,
+    /// Experience events replayed
+    pub replayed: usize,
+    /// Connections co-activated by at least one replayed event
+    pub coactivated: usize,
+    /// Learnable connections strengthened
+    pub strengthened: usize,
+    /// Hypothesis connections deleted
+    pub pruned: usize,
+    /// Learner slots reclaimed
+    pub compacted: usize,
+    pub duration_ms: u64,
+}
+
+impl std::fmt::Display for ConsolidationSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Consolidation ({}): replayed {}, co-activated {}, strengthened {}, pruned {}, compacted {} in {}ms",
+            self.trigger,
+            self.replayed,
+            self.coactivated,
+            self.strengthened,
+            self.pruned,
+            self.compacted,
+            self.duration_ms
+        )
+    }
+}
+
+/// Consolidator statistics
+#[derive(Debug, Clone, Default)]
+pub struct ConsolidationStats {
+    pub passes: u64,
+    pub strengthened: u64,
+    pub pruned: u64,
+    pub last: Option<ConsolidationSummary>,
+}
+
+#[derive(Debug)]
+struct ActivityState {
+    last_activity: Instant,
+    /// Activity seen since the last pass
+    pending: bool,
+}
+
+/// Runs sleep-phase consolidation over RuntimeStorage connections
+pub struct Consolidator {
+    config: RwLock<ConsolidationConfig>,
+    storage: Arc<RuntimeStorage>,
+    experience: Arc<ExperienceStream>,
+    learner: Option<Arc<Learner>>,
+    guardian: Option<Arc<RwLock<Guardian>>>,
+    safe_mode: Arc<SafeMode>,
+    activity: Mutex<ActivityState>,
+    /// Serializes passes
+    running: Mutex<()>,
+    stats: RwLock<ConsolidationStats>,
+}
+
+impl Consolidator {
+    pub fn new(
+        config: ConsolidationConfig,
+        storage: Arc<RuntimeStorage>,
+        experience: Arc<ExperienceStream>,
+    ) -> Self {
+        Self {
+            config: RwLock::new(config),
+            storage,
+            experience,
+            learner: None,
+            guardian: None,
+            safe_mode: Arc::default(),
+            activity: Mutex::new(ActivityState {
+                last_activity: Instant::now(),
+                pending: false,
+            }),
+            running: Mutex::new(()),
+            stats: RwLock::new(ConsolidationStats::default()),
+        }
+    }
+
+    /// Keep a Learner in step: weights are synced before the pass and
+    /// pruned edges are dropped from its table
+    pub fn with_learner(mut self, learner: Arc<Learner>) -> Self {
+        self.learner = Some(learner);
+        self
+    }
+
+    /// Emit a Guardian event with each summary
+    pub fn with_guardian(mut self, guardian: Arc<RwLock<Guardian>>) -> Self {
+        self.guardian = Some(guardian);
+        self
+    }
+
+    /// Skip passes while this switch freezes learning
+    pub fn with_safe_mode(mut self, safe_mode: Arc<SafeMode>) -> Self {
+        self.safe_mode = safe_mode;
+        self
+    }
+
+    pub fn config(&self) -> ConsolidationConfig {
+        self.config.read().clone()
+    }
+
+    pub fn update_config(&self, config: ConsolidationConfig) -> Result<(), String> {
+        config.validate()?;
+        *self.config.write() = config;
+        Ok(())
+    }
+
+    /// Report activity; postpones the next idle pass
+    pub fn touch(&self) {
+        let mut activity = self.activity.lock();
+        activity.last_activity = Instant::now();
+        activity.pending = true;
+    }
+
+    /// Time since the last reported activity
+    pub fn idle_for(&self) -> Duration {
+        self.activity.lock().last_activity.elapsed()
+    }
+
+    /// Whether an idle pass is due: activity happened since the last pass
+    /// and none for `idle_after_secs`
+    pub fn is_idle(&self) -> bool {
+        let idle_after = self.config.read().idle_after_secs;
+        let activity = self.activity.lock();
+        idle_after > 0
+            && activity.pending
+            && activity.last_activity.elapsed() >= Duration::from_secs(idle_after)
+    }
+
+    /// Run one consolidation pass
+    ///
+    /// While learning is frozen nothing is touched and an empty summary is
+    /// returned; pending idle activity is kept for the first pass after.
+    pub fn consolidate(&self, trigger: ConsolidationTrigger) -> ConsolidationSummary {
+        let _running = self.running.lock();
+        if self.safe_mode.learning_frozen() {
+            tracing::info!(%trigger, "Consolidation skipped: learning is frozen");
+            return ConsolidationSummary {
+                trigger,
+                replayed: 0,
+                coactivated: 0,
+                strengthened: 0,
+                pruned: 0,
+                compacted: 0,
+                duration_ms: 0,
+            };
+        }
+        let start = Instant::now();
+        let config = self.config();
+        self.activity.lock().pending = false;
+
+        // Consolidate the Learner's latest weights, not the last checkpoint's
+        if let Some(learner) = &self.learner {
+            learner.sync_to_storage(&self.storage);
+        }
+
+        // 1. Replay
+        let batch = self.experience.sample_batch(
+            config.replay_batch,
+            SamplingStrategy::PrioritizedByReward { alpha: config.priority_alpha },
+        );
+
+        // 2. Co-activation counts
+        let connections = self.storage.connections();
+        let states: HashMap<u32, [f32; 8]> = connections
+            .iter()
+            .flat_map(|(_, connection)| [connection.token_a_id, connection.token_b_id])
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .filter_map(|id| self.storage.get_token(id).map(|token| (id, token.to_state_f32())))
+            .collect();
+
+        let mut coactivations: HashMap<u64, usize> = HashMap::new();
+        for event in &batch.events {
+            let active: HashSet<u32> = states
+                .iter()
+                .filter(|(_, state)| {
+                    distance(state, &event.state) <= config.match_radius
+                        || distance(state, &event.action) <= config.match_radius
+                })
+                .map(|(&id, _)| id)
+                .collect();
+            if active.len() < 2 {
+                continue;
+            }
+            for (id, connection) in &connections {
+                if active.contains(&connection.token_a_id) && active.contains(&connection.token_b_id) {
+                    *coactivations.entry(*id).or_default() += 1;
+                }
+            }
+        }
+
+        // 3. Strengthen consistently co-activated Learnable connections
+        let mut strengthened = Vec::new();
+        self.storage.modify_connections(|id, connection| {
+            let consistent = coactivations.get(&id).is_some_and(|&n| n >= config.min_coactivations);
+            if !consistent || connection.mutability != ConnectionMutability::Learnable as u8 {
+                return false;
+            }
+            let weight = connection.weight();
+            let target = weight + config.strengthen_rate * (1.0 - weight);
+            let changed = connection.set_weight(target);
+            if changed {
+                strengthened.push((id, target));
+            }
+            changed
+        });
+
+        // 4. Prune weak Hypothesis connections that replay did not support
+        let weak: Vec<u64> = connections
+            .iter()
+            .filter(|(id, connection)| {
+                connection.mutability == ConnectionMutability::Hypothesis as u8
+                    && connection.weight() < config.hypothesis_prune_threshold
+                    && !coactivations.contains_key(id)
+            })
+            .map(|(id, _)| *id)
+            .collect();
+        let pruned = weak
+            .iter()
+            .filter(|&&id| self.storage.delete_connection(id).is_some())
+            .count();
+
+        // 5. Compact
+        let mut compacted = 0;
+        if let Some(learner) = &self.learner {
+            for &(id, weight) in &strengthened {
+                if learner.weight(id).is_some() {
+                    learner.add_edge(id, weight);
+                }
+            }
+            for &id in &weak {
+                learner.remove_edge(id);
+            }
+            compacted = learner.compact();
+        }
+
+        let summary = ConsolidationSummary {
+            trigger,
+            replayed: batch.events.len(),
+            coactivated: coactivations.len(),
+            strengthened: strengthened.len(),
+            pruned,
+            compacted,
+            duration_ms: start.elapsed().as_millis() as u64,
+        };
+        self.report(&summary);
+        summary
+    }
+
+    /// Record and publish a summary
+    fn report(&self, summary: &ConsolidationSummary) {
+        {
+            let mut stats = self.stats.write();
+            stats.passes += 1;
+            stats.strengthened += summary.strengthened as u64;
+            stats.pruned += summary.pruned as u64;
+            stats.last = Some(summary.clone());
+        }
+
+        if let Some(guardian) = &self.guardian {
+            guardian.write().emit_event(
+                GuardianEvent::new(GuardianEventType::Consolidation).with_data(summary.to_string()),
+            );
+        }
+        tracing::info!(
+            trigger = %summary.trigger,
+            replayed = summary.replayed,
+            strengthened = summary.strengthened,
+            pruned = summary.pruned,
+            compacted = summary.compacted,
+            "Consolidation pass finished"
+        );
+    }
+
+    pub fn stats(&self) -> ConsolidationStats {
+        self.stats.read().clone()
+    }
+
+    /// Check for idleness every `interval` and consolidate when idle
+    pub fn spawn(self: Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                if self.is_idle() {
+                    let consolidator = self.clone();
+                    let _ = tokio::task::spawn_blocking(move || {
+                        consolidator.consolidate(ConsolidationTrigger::Idle)
+                    })
+                    .await;
+                }
+            }
+        })
+    }
+}
+
+fn distance(a: &[f32; 8], b: &[f32; 8]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum::<f32>().sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection_v3::ConnectionV3;
+    use crate::experience_stream::ExperienceEvent;
+    use crate::Token;
+
+    fn setup() -> (Arc<RuntimeStorage>, Arc<ExperienceStream>) {
+        let storage = Arc::new(RuntimeStorage::new());
+        for (id, x) in [(1, 1.0), (2, 1.2), (3, 5.0), (4, 5.1), (5, -4.0), (6, -4.1)] {
+            let mut state = [0.0; 8];
+            state[0] = x;
+            storage.create_token(Token::from_state_f32(id, &state));
+        }
+        (storage, Arc::new(ExperienceStream::new(100, 16)))
+    }
+
+    fn connection(a: u32, b: u32, mutability: ConnectionMutability, weight: f32) -> ConnectionV3 {
+        let mut connection = ConnectionV3::new(a, b);
+        connection.mutability = mutability as u8;
+        connection.set_weight(weight);
+        connection
+    }
+
+    fn replay_at(experience: &ExperienceStream, x: f32, times: usize) {
+        for _ in 0..times {
+            let mut event = ExperienceEvent::default();
+            event.state[0] = x;
+            event.reward_goal = 1.0;
+            experience.write_event(event).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_consolidate() {
+        let (storage, experience) = setup();
+        let learnable = storage.create_connection(connection(1, 2, ConnectionMutability::Learnable, 0.5));
+        let unseen = storage.create_connection(connection(3, 4, ConnectionMutability::Learnable, 0.5));
+        let weak = storage.create_connection(connection(5, 6, ConnectionMutability::Hypothesis, 0.05));
+        let supported = storage.create_connection(connection(1, 2, ConnectionMutability::Hypothesis, 0.05));
+
+        let learner = Arc::new(Learner::default());
+        learner.load_from_storage(&storage);
+        let guardian = Arc::new(RwLock::new(Guardian::new()));
+        let consolidator = Consolidator::new(ConsolidationConfig::default(), storage.clone(), experience.clone())
+            .with_learner(learner.clone())
+            .with_guardian(guardian.clone());
+
+        replay_at(&experience, 1.1, 5);
+        let summary = consolidator.consolidate(ConsolidationTrigger::Command);
+
+        assert_eq!(summary.replayed, 5);
+        assert_eq!(summary.coactivated, 2);
+        assert_eq!(summary.strengthened, 1);
+        assert_eq!(summary.pruned, 1);
+        assert_eq!(summary.compacted, 1);
+
+        let weight = storage.get_connection(learnable).unwrap().weight();
+        assert!((weight - 0.55).abs() < 0.01);
+        assert!((learner.weight(learnable).unwrap() - 0.55).abs() < 0.01);
+        assert!((storage.get_connection(unseen).unwrap().weight() - 0.5).abs() < 0.01);
+        assert!(storage.get_connection(weak).is_none());
+        assert!(learner.weight(weak).is_none());
+        assert!(storage.get_connection(supported).is_some());
+
+        assert_eq!(consolidator.stats().passes, 1);
+        let events = guardian.read().pending_events().clone();
+        assert!(events.iter().any(|e| e.event_type == GuardianEventType::Consolidation));
+    }
+
+    #[test]
+    fn test_idle_detection() {
+        let (storage, experience) = setup();
+        let config = ConsolidationConfig { idle_after_secs: 0, ..Default::default() };
+        let consolidator = Consolidator::new(config, storage, experience);
+
+        // Disabled
+        consolidator.touch();
+        assert!(!consolidator.is_idle());
+
+        consolidator.update_config(ConsolidationConfig { idle_after_secs: 1, ..Default::default() }).unwrap();
+        assert!(!consolidator.is_idle());
+        std::thread::sleep(Duration::from_millis(1050));
+        assert!(consolidator.is_idle());
+
+        // A pass clears the pending activity
+        consolidator.consolidate(ConsolidationTrigger::Idle);
+        assert!(!consolidator.is_idle());
+
+        assert!(consolidator
+            .update_config(ConsolidationConfig { strengthen_rate: 2.0, ..Default::default() })
+            .is_err());
+    }
+
+    #[test]
+    fn test_safe_mode_skips_pass() {
+        let (storage, experience) = setup();
+        let learnable = storage.create_connection(connection(1, 2, ConnectionMutability::Learnable, 0.5));
+        let weak = storage.create_connection(connection(5, 6, ConnectionMutability::Hypothesis, 0.05));
+        let safe_mode = Arc::new(SafeMode::new());
+        let consolidator = Consolidator::new(ConsolidationConfig::default(), storage.clone(), experience.clone())
+            .with_safe_mode(safe_mode.clone());
+
+        replay_at(&experience, 1.1, 5);
+        safe_mode.engage(Some("test"));
+        let summary = consolidator.consolidate(ConsolidationTrigger::Command);
+        assert_eq!((summary.replayed, summary.strengthened, summary.pruned), (0, 0, 0));
+        assert!((storage.get_connection(learnable).unwrap().weight() - 0.5).abs() < 0.01);
+        assert!(storage.get_connection(weak).is_some());
+        assert_eq!(consolidator.stats().passes, 0);
+
+        safe_mode.release();
+        assert_eq!(consolidator.consolidate(ConsolidationTrigger::Command).pruned, 1);
+    }
+}
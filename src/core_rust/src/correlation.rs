@@ -0,0 +1,120 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Correlation IDs - end-to-end tracing of one interaction
+//!
+//! The Gateway gives every injected signal a [`CorrelationId`] (or adopts
+//! one passed as `{"correlation_id": "<16 hex digits>"}` in Text metadata)
+//! and returns it in the SignalReceipt. ActionController runs the signal
+//! inside [`scope`], so everything the interaction causes can pick it up
+//! with [`current`] instead of threading it through every signature:
+//!
+//! - ProcessedSignal, ActionIntent, Proposal and OutputContext carry it
+//! - ExperienceStream records it per event (`ExperienceStream::correlation`)
+//! - SignalSystem events get it as `trace_id_hash`
+//! - log lines inside the scope belong to an `interaction` tracing span with
+//!   a `correlation_id` field
+
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use tracing::Instrument;
+
+/// Identifier shared by everything one interaction causes
+///
+/// Serialized as 16 hex digits (JSON numbers lose precision above 2^53).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
+pub struct CorrelationId(pub u64);
+
+impl CorrelationId {
+    /// Fresh random ID (never 0)
+    pub fn generate() -> Self {
+        let (high, low) = uuid::Uuid::new_v4().as_u64_pair();
+        Self((high ^ low).max(1))
+    }
+
+    /// Parse 1-16 hex digits
+    pub fn parse(text: &str) -> Option<Self> {
+        if text.is_empty() || text.len() > 16 {
+            return None;
+        }
+        u64::from_str_radix(text, 16).ok().filter(|&id| id != 0).map(Self)
+    }
+}
+
+impl std::fmt::Display for CorrelationId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+impl From<CorrelationId> for String {
+    fn from(id: CorrelationId) -> Self {
+        id.to_string()
+    }
+}
+
+impl TryFrom<String> for CorrelationId {
+    type Error = String;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        Self::parse(&text).ok_or_else(|| format!("Invalid correlation ID '{}'", text))
+    }
+}
+
+tokio::task_local! {
+    static CURRENT: CorrelationId;
+}
+
+/// Correlation ID of the interaction the current task works for
+pub fn current() -> Option<CorrelationId> {
+    CURRENT.try_with(|id| *id).ok()
+}
+
+/// Tracing span of an interaction
+pub fn span(id: CorrelationId) -> tracing::Span {
+    tracing::info_span!("interaction", correlation_id = %id)
+}
+
+/// Run `future` as part of interaction `id`
+pub async fn scope<F: Future>(id: CorrelationId, future: F) -> F::Output {
+    CURRENT.scope(id, future.instrument(span(id))).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_and_parse() {
+        let id = CorrelationId(0xab);
+        assert_eq!(id.to_string(), "00000000000000ab");
+        assert_eq!(CorrelationId::parse("00000000000000ab"), Some(id));
+        assert_eq!(CorrelationId::parse("0"), None);
+        assert_eq!(CorrelationId::parse("xyz"), None);
+        assert_eq!(serde_json::to_string(&id).unwrap(), "\"00000000000000ab\"");
+        assert_ne!(CorrelationId::generate().0, 0);
+    }
+
+    #[tokio::test]
+    async fn test_scope() {
+        assert_eq!(current(), None);
+        let id = CorrelationId::generate();
+        let seen = scope(id, async { current() }).await;
+        assert_eq!(seen, Some(id));
+        assert_eq!(current(), None);
+    }
+}
@@ -4,6 +4,7 @@
 
 use crate::curiosity::{CuriosityDrive, ExplorationTarget, ExplorationMode};
 use crate::action_controller::ActionController;
+use crate::supervisor::Supervisor;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::time;
@@ -80,7 +81,15 @@ impl AutonomousExplorer {
                         break;
                     }
 
-                    if !self.curiosity.is_autonomous_enabled() {
+                    if !self.curiosity.is_autonomous_enabled()
+                        || controller.safe_mode().learning_frozen()
+                    {
+                        continue;
+                    }
+                    if controller.budget_exceeded() {
+                        if let Some(budget) = controller.budget() {
+                            budget.record_throttled();
+                        }
                         continue;
                     }
 
@@ -177,13 +186,17 @@ impl AutonomousExplorer {
 }
 
 /// Run autonomous exploration loop (convenience function)
+///
+/// The loop runs under a [`Supervisor`] and is restarted if it panics.
 pub async fn run_autonomous_exploration(
     curiosity: Arc<CuriosityDrive>,
     controller: Arc<ActionController>,
     config: AutonomousConfig,
 ) {
-    let explorer = AutonomousExplorer::new(curiosity, config);
-    explorer.start(controller).await;
+    let explorer = Arc::new(AutonomousExplorer::new(curiosity, config));
+    let _ = Supervisor::default()
+        .supervise_explorer(explorer, controller)
+        .await;
 }
 
 #[cfg(test)]
@@ -100,6 +100,19 @@ impl Default for CuriosityConfig {
 }
 
 impl CuriosityConfig {
+    /// Fields `CuriosityDrive::update_config` applies immediately
+    pub const LIVE_FIELDS: &'static [&'static str] = &[
+        "boredom_threshold",
+        "uncertainty_weight",
+        "surprise_weight",
+        "novelty_weight",
+        "enable_autonomous",
+        "exploration_mode",
+        "max_cell_age_secs",
+        "min_cell_visits",
+        "min_curiosity_score",
+    ];
+
     /// Validate configuration values
     pub fn validate(&self) -> Result<(), String> {
         if self.boredom_threshold < 0.0 || self.boredom_threshold > 1.0 {
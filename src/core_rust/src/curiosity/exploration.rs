@@ -140,6 +140,7 @@ impl Ord for ExplorationTarget {
 }
 
 /// Queue of exploration targets
+#[derive(Serialize, Deserialize)]
 pub struct ExplorationQueue {
     /// Priority queue (BinaryHeap)
     queue: BinaryHeap<ExplorationTarget>,
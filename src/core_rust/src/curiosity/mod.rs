@@ -58,6 +58,9 @@ pub struct CuriosityContext {
     pub prediction_accuracy: Option<f32>,
 }
 
+/// Equally uncertain regions considered when suggesting exploration
+const TIE_CANDIDATES: usize = 8;
+
 /// Curiosity-driven exploration system
 pub struct CuriosityDrive {
     /// Configuration
@@ -77,6 +80,9 @@ pub struct CuriosityDrive {
 
     /// Is autonomous exploration enabled
     autonomous_enabled: Arc<RwLock<bool>>,
+
+    /// Tie-breaking between equally uncertain regions
+    rng: Arc<crate::rng::SeededRng>,
 }
 
 impl CuriosityDrive {
@@ -93,9 +99,16 @@ impl CuriosityDrive {
             novelty: Arc::new(RwLock::new(NoveltyTracker::new())),
             exploration_queue: Arc::new(RwLock::new(ExplorationQueue::new(max_targets))),
             autonomous_enabled: Arc::new(RwLock::new(autonomous_enabled)),
+            rng: Arc::new(crate::rng::SeededRng::from_entropy()),
         }
     }
 
+    /// Break exploration ties from a seeded stream (reproducible runs)
+    pub fn with_rng(mut self, rng: Arc<crate::rng::SeededRng>) -> Self {
+        self.rng = rng;
+        self
+    }
+
     /// Calculate curiosity score for a context
     pub fn calculate_curiosity(&self, context: &CuriosityContext) -> CuriosityScore {
         let config = self.config.read();
@@ -177,12 +190,17 @@ impl CuriosityDrive {
         // Check if current average confidence is below boredom threshold
         let stats = self.uncertainty.read().stats();
         if stats.avg_confidence < config.boredom_threshold {
-            // Find most uncertain region
-            let uncertain = self.find_uncertain_regions(1);
-            if let Some((state, uncertainty)) = uncertain.first() {
+            // Find most uncertain region, picking at random among equally uncertain ones
+            let uncertain = self.find_uncertain_regions(TIE_CANDIDATES);
+            let tied = uncertain
+                .iter()
+                .take_while(|(_, u)| Some(*u) == uncertain.first().map(|(_, top)| *top))
+                .count();
+            if tied > 0 {
+                let (state, uncertainty) = uncertain[self.rng.index(tied)];
                 return Some(ExplorationTarget::new(
-                    *state,
-                    *uncertainty,
+                    state,
+                    uncertainty,
                     ExplorationReason::HighUncertainty,
                 ));
             }
@@ -205,6 +223,30 @@ impl CuriosityDrive {
         self.novelty.write().cleanup_old(max_age);
     }
 
+    /// Current configuration
+    pub fn config(&self) -> CuriosityConfig {
+        self.config.read().clone()
+    }
+
+    /// Apply a new configuration at runtime
+    ///
+    /// Scoring weights, thresholds, cleanup limits and the autonomous flag
+    /// apply immediately. Queue and history sizes and the exploration
+    /// interval are fixed at construction and keep their current values.
+    pub fn update_config(&self, config: CuriosityConfig) -> Result<(), String> {
+        config.validate()?;
+        let mut current = self.config.write();
+        let fixed = current.clone();
+        *current = CuriosityConfig {
+            exploration_interval_ms: fixed.exploration_interval_ms,
+            max_exploration_targets: fixed.max_exploration_targets,
+            surprise_history_size: fixed.surprise_history_size,
+            ..config
+        };
+        *self.autonomous_enabled.write() = current.enable_autonomous;
+        Ok(())
+    }
+
     /// Enable/disable autonomous exploration
     pub fn set_autonomous(&self, enabled: bool) {
         *self.autonomous_enabled.write() = enabled;
@@ -215,6 +257,39 @@ impl CuriosityDrive {
         *self.autonomous_enabled.read()
     }
 
+    /// Serialize all trackers to JSON (used by checkpoint)
+    pub fn export_state(&self) -> serde_json::Result<Vec<u8>> {
+        let uncertainty = self.uncertainty.read();
+        let surprise = self.surprise.read();
+        let novelty = self.novelty.read();
+        let exploration = self.exploration_queue.read();
+
+        serde_json::to_vec(&serde_json::json!({
+            "uncertainty": &*uncertainty,
+            "surprise": &*surprise,
+            "novelty": &*novelty,
+            "exploration": &*exploration,
+        }))
+    }
+
+    /// Replace all trackers from `export_state` output
+    pub fn import_state(&self, data: &[u8]) -> serde_json::Result<()> {
+        #[derive(Deserialize)]
+        struct TrackerState {
+            uncertainty: UncertaintyTracker,
+            surprise: SurpriseHistory,
+            novelty: NoveltyTracker,
+            exploration: ExplorationQueue,
+        }
+
+        let state: TrackerState = serde_json::from_slice(data)?;
+        *self.uncertainty.write() = state.uncertainty;
+        *self.surprise.write() = state.surprise;
+        *self.novelty.write() = state.novelty;
+        *self.exploration_queue.write() = state.exploration;
+        Ok(())
+    }
+
     /// Get comprehensive statistics
     pub fn stats(&self) -> CuriosityStats {
         CuriosityStats {
@@ -227,6 +302,32 @@ impl CuriosityDrive {
     }
 }
 
+/// Serde helper: serialize `HashMap<CellKey, V>` as a list of pairs
+/// (struct keys are not valid JSON object keys)
+mod cell_map {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::collections::HashMap;
+    use std::hash::Hash;
+
+    pub fn serialize<K, V, S>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Serialize,
+        V: Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(map.iter())
+    }
+
+    pub fn deserialize<'de, K, V, D>(deserializer: D) -> Result<HashMap<K, V>, D::Error>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Vec::<(K, V)>::deserialize(deserializer).map(|pairs| pairs.into_iter().collect())
+    }
+}
+
 impl Default for CuriosityDrive {
     fn default() -> Self {
         Self::new(CuriosityConfig::default())
@@ -8,8 +8,10 @@ use std::time::{SystemTime, Duration};
 use serde::{Deserialize, Serialize};
 
 /// Tracks novelty of states based on recency
+#[derive(Serialize, Deserialize)]
 pub struct NoveltyTracker {
     /// Map from cell key to last seen timestamp
+    #[serde(with = "super::cell_map")]
     last_seen: HashMap<CellKey, SystemTime>,
 
     /// Total unique states seen
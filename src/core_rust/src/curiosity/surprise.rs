@@ -51,6 +51,7 @@ impl SurpriseEvent {
 }
 
 /// Tracks history of surprise events
+#[derive(Serialize, Deserialize)]
 pub struct SurpriseHistory {
     /// Ring buffer of recent surprise events
     events: VecDeque<SurpriseEvent>,
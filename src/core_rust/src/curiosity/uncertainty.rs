@@ -96,8 +96,10 @@ impl Default for CellConfidence {
 }
 
 /// Tracks uncertainty across 8D state space
+#[derive(Serialize, Deserialize)]
 pub struct UncertaintyTracker {
     /// Map from cell key to confidence
+    #[serde(with = "super::cell_map")]
     cells: HashMap<CellKey, CellConfidence>,
 
     /// Total cells tracked
@@ -184,8 +186,8 @@ impl UncertaintyTracker {
             .map(|(key, conf)| (*key, 1.0 - conf.confidence))
             .collect();
 
-        // Sort by uncertainty (descending)
-        cells.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        // Sort by uncertainty (descending); ties by cell so the order is reproducible
+        cells.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap().then_with(|| a.0.coords.cmp(&b.0.coords)));
 
         cells.into_iter().take(limit).collect()
     }
@@ -0,0 +1,288 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Decay v1.0 - Forgetting curves per connection category
+//!
+//! Each of the 11 connection categories (`ConnectionType::category`) has its
+//! own decay rate `r`: the fraction of weight lost per `DECAY_PERIOD_SECS`
+//! without an update. A connection left alone for `t` seconds keeps
+//!
+//! ```text
+//! w(t) = w₀ · (1 − r)^(t / DECAY_PERIOD_SECS)
+//! ```
+//!
+//! Hypothesis connections use the larger of their category rate and the
+//! separate hypothesis rate; Immutable connections never decay.
+//!
+//! Profiles live in CDNA (`category_decay`, `hypothesis_decay`, stored as
+//! u8 /255) and are tuned with a `ProfilePatch`. Defaults follow the
+//! mutability tiers: Immutable categories (semantic, spatial, logical,
+//! structural, rule) do not decay, Learnable categories lose
+//! `LEARNABLE_DECAY` per hour and hypotheses `HYPOTHESIS_DECAY`.
+//!
+//! [`DecayManager`] applies the profiles to RuntimeStorage connections,
+//! periodically via [`DecayManager::spawn`].
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::task::JoinHandle;
+
+use crate::cdna::CDNA;
+use crate::connection_v3::{connection_flags, guess_mutability, ConnectionMutability, ConnectionV3};
+use crate::runtime_storage::RuntimeStorage;
+
+/// Number of connection categories (high nibble of the type code)
+pub const CATEGORY_COUNT: usize = 11;
+
+/// Time unit of a decay rate
+pub const DECAY_PERIOD_SECS: u32 = 3600;
+
+/// Default hourly decay of Learnable categories (3/255)
+pub const LEARNABLE_DECAY: f32 = 3.0 / 255.0;
+
+/// Default hourly decay of Hypothesis connections (16/255, the ConnectionV3 default)
+pub const HYPOTHESIS_DECAY: f32 = 16.0 / 255.0;
+
+/// Decay rates per connection category
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DecayProfiles {
+    /// Hourly decay indexed by category (`connection_type >> 4`)
+    pub categories: [f32; CATEGORY_COUNT],
+
+    /// Hourly decay of Hypothesis connections
+    pub hypothesis: f32,
+}
+
+impl Default for DecayProfiles {
+    fn default() -> Self {
+        let mut categories = [0.0; CATEGORY_COUNT];
+        for (category, rate) in categories.iter_mut().enumerate() {
+            *rate = Self::tier_rate(guess_mutability((category as u8) << 4));
+        }
+        Self {
+            categories,
+            hypothesis: HYPOTHESIS_DECAY,
+        }
+    }
+}
+
+impl DecayProfiles {
+    /// Default hourly decay of a mutability tier
+    pub fn tier_rate(mutability: ConnectionMutability) -> f32 {
+        match mutability {
+            ConnectionMutability::Immutable => 0.0,
+            ConnectionMutability::Learnable => LEARNABLE_DECAY,
+            ConnectionMutability::Hypothesis => HYPOTHESIS_DECAY,
+        }
+    }
+
+    /// Profiles stored in a CDNA (defaults for CDNA older than v2.2)
+    pub fn from_cdna(cdna: &CDNA) -> Self {
+        if cdna.version_minor < 2 {
+            return Self::default();
+        }
+        Self {
+            categories: cdna.category_decay.map(|rate| rate as f32 / 255.0),
+            hypothesis: cdna.hypothesis_decay as f32 / 255.0,
+        }
+    }
+
+    /// CDNA encoding: `(category_decay, hypothesis_decay)`
+    pub fn to_cdna_bytes(&self) -> ([u8; CATEGORY_COUNT], u8) {
+        let encode = |rate: f32| (rate.clamp(0.0, 1.0) * 255.0).round() as u8;
+        (self.categories.map(encode), encode(self.hypothesis))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let rates = self.categories.iter().chain(std::iter::once(&self.hypothesis));
+        if rates.into_iter().any(|rate| !(0.0..=1.0).contains(rate)) {
+            return Err("decay rates must be 0.0-1.0".to_string());
+        }
+        Ok(())
+    }
+
+    /// Hourly decay that applies to `connection`
+    pub fn rate(&self, connection: &ConnectionV3) -> f32 {
+        let category = ((connection.connection_type >> 4) as usize).min(CATEGORY_COUNT - 1);
+        let rate = self.categories[category];
+        match connection.mutability {
+            m if m == ConnectionMutability::Immutable as u8 => 0.0,
+            m if m == ConnectionMutability::Hypothesis as u8 => rate.max(self.hypothesis),
+            _ => rate,
+        }
+    }
+}
+
+/// Result of one decay pass
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DecayReport {
+    /// Connections subject to decay (rate > 0)
+    pub examined: usize,
+    /// Connections whose stored weight changed
+    pub decayed: usize,
+}
+
+/// Applies per-category forgetting curves to stored connections
+pub struct DecayManager {
+    profiles: RwLock<DecayProfiles>,
+}
+
+impl DecayManager {
+    pub fn new(profiles: DecayProfiles) -> Self {
+        Self {
+            profiles: RwLock::new(profiles),
+        }
+    }
+
+    pub fn from_cdna(cdna: &CDNA) -> Self {
+        Self::new(DecayProfiles::from_cdna(cdna))
+    }
+
+    pub fn profiles(&self) -> DecayProfiles {
+        *self.profiles.read()
+    }
+
+    pub fn set_profiles(&self, profiles: DecayProfiles) -> Result<(), String> {
+        profiles.validate()?;
+        *self.profiles.write() = profiles;
+        Ok(())
+    }
+
+    /// Decay every connection as of now
+    pub fn apply(&self, storage: &RuntimeStorage) -> DecayReport {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as u32;
+        self.apply_at(storage, now)
+    }
+
+    /// Decay every connection as of `now` (Unix seconds)
+    ///
+    /// Time is measured from `last_update`, which a changed weight resets,
+    /// so repeated passes compound to the same curve. Changes below the u8
+    /// weight resolution keep accumulating until they register.
+    pub fn apply_at(&self, storage: &RuntimeStorage, now: u32) -> DecayReport {
+        let profiles = self.profiles();
+        let mut report = DecayReport::default();
+        report.decayed = storage.modify_connections(|_, connection| {
+            let rate = profiles.rate(connection);
+            if rate <= 0.0 {
+                return false;
+            }
+            report.examined += 1;
+
+            let periods = now.saturating_sub(connection.last_update) as f32 / DECAY_PERIOD_SECS as f32;
+            let weight = connection.weight() * (1.0 - rate).powf(periods);
+            let changed = connection.set_weight(weight);
+            if changed {
+                connection.flags |= connection_flags::DECAYING;
+            }
+            changed
+        });
+        report
+    }
+
+    /// Decay stored connections every `interval`, re-reading the profiles
+    /// from the storage CDNA each time
+    pub fn spawn(self: Arc<Self>, storage: Arc<RuntimeStorage>, interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                *self.profiles.write() = DecayProfiles::from_cdna(&storage.get_cdna());
+                let report = self.apply(&storage);
+                tracing::debug!(examined = report.examined, decayed = report.decayed, "Connection decay pass");
+            }
+        })
+    }
+}
+
+impl Default for DecayManager {
+    fn default() -> Self {
+        Self::new(DecayProfiles::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cdna::ProfilePatch;
+    use crate::connection_v3::ConnectionType;
+
+    fn connection(conn_type: ConnectionType, updated_at: u32) -> ConnectionV3 {
+        let mut connection = ConnectionV3::new(1, 2);
+        connection.set_connection_type(conn_type);
+        connection.confidence = 200;
+        connection.last_update = updated_at;
+        connection
+    }
+
+    #[test]
+    fn test_default_profiles_follow_tiers() {
+        let profiles = DecayProfiles::default();
+        let emotional = connection(ConnectionType::Likes, 0);
+        let structural = connection(ConnectionType::PartOf, 0);
+        assert_eq!(profiles.rate(&structural), 0.0);
+        assert_eq!(profiles.rate(&emotional), LEARNABLE_DECAY);
+
+        let mut hypothesis = emotional;
+        hypothesis.mutability = ConnectionMutability::Hypothesis as u8;
+        assert_eq!(profiles.rate(&hypothesis), HYPOTHESIS_DECAY);
+
+        // Round trip through CDNA
+        let cdna = CDNA::new();
+        let stored = DecayProfiles::from_cdna(&cdna);
+        assert!((stored.categories[8] - LEARNABLE_DECAY).abs() < 1.0 / 255.0);
+        assert!((stored.hypothesis - HYPOTHESIS_DECAY).abs() < 1.0 / 255.0);
+    }
+
+    #[test]
+    fn test_apply_per_category() {
+        let storage = RuntimeStorage::new();
+        let emotional = storage.create_connection(connection(ConnectionType::Likes, 0));
+        let causal = storage.create_connection(connection(ConnectionType::Cause, 0));
+        let structural = storage.create_connection(connection(ConnectionType::PartOf, 0));
+
+        // Emotional forgets 10x faster than causal via a CDNA patch
+        let mut cdna = CDNA::new();
+        let mut category_decay = cdna.category_decay;
+        category_decay[0x8] = 30;
+        cdna.apply_patch(&ProfilePatch { category_decay: Some(category_decay), ..Default::default() })
+            .unwrap();
+        let manager = DecayManager::from_cdna(&cdna);
+
+        let report = manager.apply_at(&storage, 10 * DECAY_PERIOD_SECS);
+        assert_eq!(report, DecayReport { examined: 2, decayed: 2 });
+
+        let weight = |id| storage.get_connection(id).unwrap().weight();
+        let start = 200.0 / 255.0;
+        assert!((weight(emotional) - start * (1.0 - 30.0 / 255.0f32).powi(10)).abs() < 0.01);
+        assert!((weight(causal) - start * (1.0 - 3.0 / 255.0f32).powi(10)).abs() < 0.01);
+        assert_eq!(weight(structural), start);
+        assert!(storage.get_connection(emotional).unwrap().flags & connection_flags::DECAYING != 0);
+
+        // last_update was reset: no time has passed since
+        let report = manager.apply_at(&storage, 10 * DECAY_PERIOD_SECS);
+        assert_eq!(report.decayed, 0);
+
+        let invalid = DecayProfiles { hypothesis: 1.5, ..Default::default() };
+        assert!(manager.set_profiles(invalid).is_err());
+    }
+}
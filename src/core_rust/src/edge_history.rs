@@ -0,0 +1,323 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Edge weight history v1.0 - time series of connection weights
+//!
+//! A debugging aid for learning: every weight change of a recorded edge is
+//! kept as a `(timestamp, weight, source)` sample in a per-edge ring buffer,
+//! so it is possible to see how a connection evolved and what moved it.
+//!
+//! Recording is opt-in (`Graph::set_weight_history`,
+//! `ProposalRouter::with_weight_history`) and can be limited to a set of
+//! edges, to every Nth change, or to changes larger than `min_delta`.
+//! `to_csv` exports samples for plotting.
+
+use parking_lot::Mutex;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::Write;
+#[cfg(not(all(target_arch = "wasm32", feature = "wasm")))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// What changed the weight
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WeightSource {
+    /// Edge was created with this weight
+    Created,
+    /// Direct API call (`Graph::set_edge_weight`)
+    Manual,
+    /// Document ingestion
+    Ingestion,
+    /// Learning proposal applied by ProposalRouter
+    Proposal,
+    /// Proposal reverted after negative reward lift
+    Revert,
+    /// Decay / forgetting
+    Decay,
+    /// Duplicate edges merged (`Graph::canonicalize`, deduplicating inserts)
+    Dedup,
+    /// Change applied from the writer's feed on a read replica
+    Replication,
+}
+
+impl WeightSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WeightSource::Created => "created",
+            WeightSource::Manual => "manual",
+            WeightSource::Ingestion => "ingestion",
+            WeightSource::Proposal => "proposal",
+            WeightSource::Revert => "revert",
+            WeightSource::Decay => "decay",
+            WeightSource::Dedup => "dedup",
+            WeightSource::Replication => "replication",
+        }
+    }
+}
+
+impl std::fmt::Display for WeightSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One recorded weight
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeightSample {
+    /// Unix milliseconds
+    pub timestamp_ms: u64,
+    pub weight: f32,
+    pub source: WeightSource,
+}
+
+/// History configuration
+#[derive(Debug, Clone)]
+pub struct EdgeHistoryConfig {
+    /// Samples kept per edge (oldest are dropped)
+    pub capacity_per_edge: usize,
+    /// Record every Nth change of an edge (1 = every change)
+    pub sample_every: u32,
+    /// Skip changes smaller than this since the last recorded sample
+    pub min_delta: f32,
+    /// Record only these edges (None = all edges)
+    pub tracked_edges: Option<HashSet<u64>>,
+    /// Stop tracking new edges beyond this many
+    pub max_edges: usize,
+}
+
+impl Default for EdgeHistoryConfig {
+    fn default() -> Self {
+        Self {
+            capacity_per_edge: 256,
+            sample_every: 1,
+            min_delta: 0.0,
+            tracked_edges: None,
+            max_edges: 10_000,
+        }
+    }
+}
+
+impl EdgeHistoryConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.capacity_per_edge == 0 {
+            return Err("capacity_per_edge must be > 0".to_string());
+        }
+        if self.sample_every == 0 {
+            return Err("sample_every must be > 0".to_string());
+        }
+        if !self.min_delta.is_finite() || self.min_delta < 0.0 {
+            return Err("min_delta must be a finite value >= 0".to_string());
+        }
+        if self.max_edges == 0 {
+            return Err("max_edges must be > 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default)]
+struct Series {
+    samples: VecDeque<WeightSample>,
+    changes: u32,
+}
+
+/// Per-edge weight history (thread-safe, shareable via `Arc`)
+#[derive(Debug)]
+pub struct EdgeHistory {
+    config: EdgeHistoryConfig,
+    series: Mutex<HashMap<u64, Series>>,
+}
+
+impl Default for EdgeHistory {
+    fn default() -> Self {
+        Self::new(EdgeHistoryConfig::default())
+    }
+}
+
+impl EdgeHistory {
+    pub fn new(config: EdgeHistoryConfig) -> Self {
+        Self {
+            config,
+            series: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn config(&self) -> &EdgeHistoryConfig {
+        &self.config
+    }
+
+    /// Record a weight change at the current time
+    pub fn record(&self, edge_id: u64, weight: f32, source: WeightSource) -> bool {
+        self.record_at(edge_id, weight, source, now_ms())
+    }
+
+    /// Record a weight change with an explicit timestamp
+    ///
+    /// Returns true if a sample was stored. Creation, reverts and the first
+    /// sample of an edge are always stored; other changes are subject to
+    /// `sample_every` and `min_delta`.
+    pub fn record_at(&self, edge_id: u64, weight: f32, source: WeightSource, timestamp_ms: u64) -> bool {
+        if let Some(tracked) = &self.config.tracked_edges {
+            if !tracked.contains(&edge_id) {
+                return false;
+            }
+        }
+
+        let mut all = self.series.lock();
+        if !all.contains_key(&edge_id) && all.len() >= self.config.max_edges {
+            return false;
+        }
+        let series = all.entry(edge_id).or_default();
+
+        let always = matches!(source, WeightSource::Created | WeightSource::Revert);
+        if !always {
+            if let Some(last) = series.samples.back() {
+                series.changes += 1;
+                if series.changes < self.config.sample_every {
+                    return false;
+                }
+                if (weight - last.weight).abs() < self.config.min_delta {
+                    return false;
+                }
+            }
+        }
+
+        series.changes = 0;
+        if series.samples.len() >= self.config.capacity_per_edge {
+            series.samples.pop_front();
+        }
+        series.samples.push_back(WeightSample { timestamp_ms, weight, source });
+        true
+    }
+
+    /// Samples of an edge within `[from_ms, to_ms]`, oldest first
+    pub fn query(&self, edge_id: u64, from_ms: Option<u64>, to_ms: Option<u64>) -> Vec<WeightSample> {
+        let from = from_ms.unwrap_or(0);
+        let to = to_ms.unwrap_or(u64::MAX);
+        self.series
+            .lock()
+            .get(&edge_id)
+            .map(|s| {
+                s.samples
+                    .iter()
+                    .filter(|sample| sample.timestamp_ms >= from && sample.timestamp_ms <= to)
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Most recent sample of an edge
+    pub fn latest(&self, edge_id: u64) -> Option<WeightSample> {
+        self.series.lock().get(&edge_id).and_then(|s| s.samples.back().copied())
+    }
+
+    /// Edges with recorded history
+    pub fn edges(&self) -> Vec<u64> {
+        let mut edges: Vec<u64> = self.series.lock().keys().copied().collect();
+        edges.sort_unstable();
+        edges
+    }
+
+    /// Drop the history of one edge
+    pub fn forget(&self, edge_id: u64) {
+        self.series.lock().remove(&edge_id);
+    }
+
+    pub fn clear(&self) {
+        self.series.lock().clear();
+    }
+
+    /// Write `edge_id,timestamp_ms,weight,source` rows for the given edges
+    /// (all edges if empty)
+    pub fn to_csv<W: Write>(&self, edge_ids: &[u64], mut writer: W) -> std::io::Result<()> {
+        let edges = if edge_ids.is_empty() { self.edges() } else { edge_ids.to_vec() };
+        writeln!(writer, "edge_id,timestamp_ms,weight,source")?;
+        for edge_id in edges {
+            for sample in self.query(edge_id, None, None) {
+                writeln!(
+                    writer,
+                    "{},{},{},{}",
+                    edge_id, sample.timestamp_ms, sample.weight, sample.source
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", feature = "wasm")))]
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Browser clock; `SystemTime` panics on wasm32
+#[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+fn now_ms() -> u64 {
+    crate::wasm::unix_time_us() / 1000
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sampling_and_ring_buffer() {
+        let history = EdgeHistory::new(EdgeHistoryConfig {
+            capacity_per_edge: 3,
+            sample_every: 2,
+            ..Default::default()
+        });
+
+        assert!(history.record_at(1, 0.1, WeightSource::Created, 0));
+        let stored: Vec<bool> = (1..=4)
+            .map(|i| history.record_at(1, 0.1 + i as f32 * 0.1, WeightSource::Proposal, i * 10))
+            .collect();
+        assert_eq!(stored, vec![false, true, false, true]);
+        assert!(history.record_at(1, 0.2, WeightSource::Revert, 50));
+
+        // Capacity 3: the creation sample fell out
+        let samples = history.query(1, None, None);
+        assert_eq!(samples.len(), 3);
+        assert_eq!(samples[0].timestamp_ms, 20);
+        assert_eq!(history.latest(1).unwrap().source, WeightSource::Revert);
+        assert_eq!(history.query(1, Some(30), Some(45)).len(), 1);
+    }
+
+    #[test]
+    fn test_tracked_edges_min_delta_and_csv() {
+        let history = EdgeHistory::new(EdgeHistoryConfig {
+            min_delta: 0.05,
+            tracked_edges: Some([7].into_iter().collect()),
+            ..Default::default()
+        });
+
+        assert!(!history.record_at(8, 0.5, WeightSource::Manual, 0));
+        assert!(history.record_at(7, 0.5, WeightSource::Manual, 0));
+        assert!(!history.record_at(7, 0.52, WeightSource::Manual, 1));
+        assert!(history.record_at(7, 0.6, WeightSource::Ingestion, 2));
+        assert_eq!(history.edges(), vec![7]);
+
+        let mut csv = Vec::new();
+        history.to_csv(&[], &mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "edge_id,timestamp_ms,weight,source\n7,0,0.5,manual\n7,2,0.6,ingestion\n"
+        );
+    }
+}
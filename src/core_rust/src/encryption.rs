@@ -0,0 +1,482 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Encryption v1.0 - At-rest encryption of checkpoints and other files
+//!
+//! A [`FileCipher`] seals files with ChaCha20-Poly1305 under a 256-bit key.
+//! Sealed files look like:
+//!
+//! ```text
+//! "NGENC1" | nonce (12 bytes) | ciphertext + tag (16 bytes)
+//! ```
+//!
+//! The file name is bound as associated data, so sections cannot be swapped
+//! between files without failing authentication. Files that belong to a
+//! larger unit bind that too ([`scoped_name`]): checkpoint sections are
+//! sealed under `<checkpoint id>/<file>`.
+//!
+//! Append-only files (replay journals) are sealed record by record with
+//! [`seal_record`]: each line is a hex-encoded sealed record bound to the
+//! file name and its line number, so lines cannot be reordered or moved
+//! between files either.
+//!
+//! The key comes from [`EncryptionConfig`] (the `[encryption]` section of
+//! the layered config), first match wins:
+//!
+//! 1. `key` - 64 hex digits, e.g. `NEUROGRAPH__ENCRYPTION__KEY=...`
+//! 2. `key_file` - a file holding 32 raw bytes or 64 hex digits
+//! 3. `keyring_service` - the OS keyring entry `(keyring_service,
+//!    keyring_user)` holding 64 hex digits (requires `--features os-keyring`)
+//!
+//! At startup [`load_at_rest_cipher`] builds the cipher from the layered
+//! config and installs it process-wide. Pass the returned cipher to
+//! `CheckpointManager::with_cipher`; the other at-rest writers pick up the
+//! installed one:
+//!
+//! - `MappedGraph::write` (`MappedGraph::open` reads sealed files into memory)
+//! - Black Box dumps (`BlackBox::dump_to_file`, also on panic)
+//! - graph exports (`Graph::export_to_file`)
+//! - bootstrap artifacts (`BootstrapLibrary::save_pca_model`,
+//!   `BootstrapLibrary::save_bootstrap_map` with its phrase table)
+//! - replay journals and state digests (`replay`)
+//!
+//! With a cipher, readers refuse plain files ([`EncryptionError::NotSealed`]):
+//! a plain copy dropped in place of a sealed file is not trusted. Without
+//! one, sealed files fail with [`EncryptionError::MissingKey`].
+
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use crate::config_file::LayeredConfig;
+use lazy_static::lazy_static;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Header of sealed files
+pub const SEALED_MAGIC: &[u8; 6] = b"NGENC1";
+
+/// Key length in bytes
+pub const KEY_LEN: usize = 32;
+
+const NONCE_LEN: usize = 12;
+
+/// Section name in the layered config
+pub const ENCRYPTION_SECTION: &str = "encryption";
+
+lazy_static! {
+    /// Cipher used by at-rest writers outside checkpoints
+    static ref AT_REST_CIPHER: RwLock<Option<Arc<FileCipher>>> = RwLock::new(None);
+}
+
+/// Encryption errors
+#[derive(Debug, Clone, PartialEq)]
+pub enum EncryptionError {
+    /// Encryption is enabled but no key source is configured
+    MissingKey,
+    InvalidKey(String),
+    Keyring(String),
+    /// Invalid `[encryption]` section
+    Config(String),
+    /// Data is not a sealed file
+    NotSealed,
+    /// Wrong key, wrong file name or tampered data
+    Decrypt,
+}
+
+impl fmt::Display for EncryptionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncryptionError::MissingKey => write!(f, "Encryption enabled but no key configured"),
+            EncryptionError::InvalidKey(msg) => write!(f, "Invalid encryption key: {}", msg),
+            EncryptionError::Keyring(msg) => write!(f, "OS keyring error: {}", msg),
+            EncryptionError::Config(msg) => write!(f, "Invalid encryption config: {}", msg),
+            EncryptionError::NotSealed => write!(f, "Data is not encrypted"),
+            EncryptionError::Decrypt => write!(f, "Decryption failed: wrong key or corrupted data"),
+        }
+    }
+}
+
+impl std::error::Error for EncryptionError {}
+
+/// Encryption configuration (`[encryption]` section)
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EncryptionConfig {
+    pub enabled: bool,
+
+    /// Key as 64 hex digits
+    pub key: Option<String>,
+
+    /// File holding the key (32 raw bytes or 64 hex digits)
+    pub key_file: Option<PathBuf>,
+
+    /// OS keyring service holding the key as 64 hex digits
+    pub keyring_service: Option<String>,
+
+    /// OS keyring user name of the entry
+    pub keyring_user: String,
+}
+
+impl Default for EncryptionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            key: None,
+            key_file: None,
+            keyring_service: None,
+            keyring_user: "checkpoint-key".to_string(),
+        }
+    }
+}
+
+impl fmt::Debug for EncryptionConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EncryptionConfig")
+            .field("enabled", &self.enabled)
+            .field("key", &self.key.as_ref().map(|_| "<redacted>"))
+            .field("key_file", &self.key_file)
+            .field("keyring_service", &self.keyring_service)
+            .field("keyring_user", &self.keyring_user)
+            .finish()
+    }
+}
+
+impl EncryptionConfig {
+    /// Startup config: defaults, then the `[encryption]` table and `NEUROGRAPH__ENCRYPTION__*`
+    pub fn from_layered(config: &LayeredConfig) -> Result<Self, EncryptionError> {
+        let built = config
+            .build(ENCRYPTION_SECTION, &Self::default())
+            .map_err(|e| EncryptionError::Config(e.to_string()))?;
+        built.validate().map_err(EncryptionError::Config)?;
+        Ok(built)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(key) = &self.key {
+            parse_hex_key(key).map_err(|e| e.to_string())?;
+        }
+        if self.enabled && self.key.is_none() && self.key_file.is_none() && self.keyring_service.is_none() {
+            return Err("enabled requires key, key_file or keyring_service".to_string());
+        }
+        if self.keyring_user.is_empty() {
+            return Err("keyring_user must not be empty".to_string());
+        }
+        Ok(())
+    }
+
+    /// Load the key from the first configured source
+    pub fn load_key(&self) -> Result<[u8; KEY_LEN], EncryptionError> {
+        if let Some(key) = &self.key {
+            return parse_hex_key(key);
+        }
+        if let Some(path) = &self.key_file {
+            let data = std::fs::read(path)
+                .map_err(|e| EncryptionError::InvalidKey(format!("{}: {}", path.display(), e)))?;
+            return match <[u8; KEY_LEN]>::try_from(data.as_slice()) {
+                Ok(key) => Ok(key),
+                Err(_) => parse_hex_key(&String::from_utf8_lossy(&data)),
+            };
+        }
+        if let Some(service) = &self.keyring_service {
+            return parse_hex_key(&keyring_get(service, &self.keyring_user)?);
+        }
+        Err(EncryptionError::MissingKey)
+    }
+
+    /// Cipher for this config, or `None` if encryption is disabled
+    pub fn cipher(&self) -> Result<Option<FileCipher>, EncryptionError> {
+        if !self.enabled {
+            return Ok(None);
+        }
+        self.load_key().map(|key| Some(FileCipher::new(&key)))
+    }
+}
+
+/// ChaCha20-Poly1305 sealing of whole files
+pub struct FileCipher {
+    cipher: ChaCha20Poly1305,
+}
+
+impl fmt::Debug for FileCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("FileCipher { .. }")
+    }
+}
+
+impl FileCipher {
+    pub fn new(key: &[u8; KEY_LEN]) -> Self {
+        Self {
+            cipher: ChaCha20Poly1305::new(Key::from_slice(key)),
+        }
+    }
+
+    /// Fresh random key
+    pub fn generate_key() -> [u8; KEY_LEN] {
+        ChaCha20Poly1305::generate_key(&mut OsRng).into()
+    }
+
+    /// Whether `data` starts with the sealed file header
+    pub fn is_sealed(data: &[u8]) -> bool {
+        data.starts_with(SEALED_MAGIC)
+    }
+
+    /// Encrypt `plaintext` stored under `file_name`
+    pub fn seal(&self, plaintext: &[u8], file_name: &str) -> Result<Vec<u8>, EncryptionError> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let aad = associated_data(file_name);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, Payload { msg: plaintext, aad: &aad })
+            .map_err(|_| EncryptionError::Decrypt)?;
+
+        let mut sealed = Vec::with_capacity(SEALED_MAGIC.len() + NONCE_LEN + ciphertext.len());
+        sealed.extend_from_slice(SEALED_MAGIC);
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+        Ok(sealed)
+    }
+
+    /// Decrypt data sealed under `file_name`
+    pub fn open(&self, sealed: &[u8], file_name: &str) -> Result<Vec<u8>, EncryptionError> {
+        if !Self::is_sealed(sealed) || sealed.len() < SEALED_MAGIC.len() + NONCE_LEN {
+            return Err(EncryptionError::NotSealed);
+        }
+        let (nonce, ciphertext) = sealed[SEALED_MAGIC.len()..].split_at(NONCE_LEN);
+        let aad = associated_data(file_name);
+        self.cipher
+            .decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad: &aad })
+            .map_err(|_| EncryptionError::Decrypt)
+    }
+}
+
+/// Build the cipher from the layered config and install it for at-rest writers
+///
+/// Returns `None` (and uninstalls any cipher) if encryption is disabled.
+pub fn load_at_rest_cipher(config: &LayeredConfig) -> Result<Option<Arc<FileCipher>>, EncryptionError> {
+    let cipher = EncryptionConfig::from_layered(config)?.cipher()?.map(Arc::new);
+    install_cipher(cipher.clone());
+    if cipher.is_some() {
+        tracing::info!("At-rest encryption enabled");
+    }
+    Ok(cipher)
+}
+
+/// Set the cipher used by at-rest writers
+pub fn install_cipher(cipher: Option<Arc<FileCipher>>) {
+    *AT_REST_CIPHER.write() = cipher;
+}
+
+/// Cipher installed by [`load_at_rest_cipher`] / [`install_cipher`]
+pub fn installed_cipher() -> Option<Arc<FileCipher>> {
+    AT_REST_CIPHER.read().clone()
+}
+
+/// Name `file_name` as part of `scope` (e.g. a checkpoint ID) for sealing
+pub fn scoped_name(scope: &str, file_name: &str) -> String {
+    format!("{}/{}", scope, file_name)
+}
+
+/// Seal `data` for `path` if a cipher is given; the file name is the associated data
+pub fn seal_file(cipher: Option<&FileCipher>, data: Vec<u8>, path: &Path) -> Result<Vec<u8>, EncryptionError> {
+    match cipher {
+        Some(cipher) => cipher.seal(&data, &file_name(path)),
+        None => Ok(data),
+    }
+}
+
+/// Open data read from `path`
+///
+/// With a cipher the data must be sealed; without one plain data passes
+/// through and sealed data fails with `MissingKey`.
+pub fn open_file(cipher: Option<&FileCipher>, data: Vec<u8>, path: &Path) -> Result<Vec<u8>, EncryptionError> {
+    match cipher {
+        Some(cipher) => cipher.open(&data, &file_name(path)),
+        None if FileCipher::is_sealed(&data) => Err(EncryptionError::MissingKey),
+        None => Ok(data),
+    }
+}
+
+/// Seal line `index` of the append-only file at `path` as one hex line (no newline)
+pub fn seal_record(cipher: &FileCipher, record: &[u8], path: &Path, index: u64) -> Result<String, EncryptionError> {
+    Ok(to_hex(&cipher.seal(record, &record_name(path, index))?))
+}
+
+/// Open line `index` written by [`seal_record`]
+pub fn open_record(cipher: &FileCipher, line: &str, path: &Path, index: u64) -> Result<Vec<u8>, EncryptionError> {
+    let line = line.trim_end();
+    if !is_sealed_record(line) || !line.len().is_multiple_of(2) {
+        return Err(EncryptionError::NotSealed);
+    }
+    let sealed = (0..line.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&line[i..i + 2], 16))
+        .collect::<Result<Vec<u8>, _>>()
+        .map_err(|_| EncryptionError::NotSealed)?;
+    cipher.open(&sealed, &record_name(path, index))
+}
+
+/// Whether `line` starts like a [`seal_record`] line
+pub fn is_sealed_record(line: &str) -> bool {
+    line.starts_with(&to_hex(SEALED_MAGIC))
+}
+
+fn record_name(path: &Path, index: u64) -> String {
+    format!("{}#{}", file_name(path), index)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default()
+}
+
+fn associated_data(file_name: &str) -> Vec<u8> {
+    [SEALED_MAGIC.as_slice(), file_name.as_bytes()].concat()
+}
+
+/// Key as 64 hex digits
+pub fn key_to_hex(key: &[u8; KEY_LEN]) -> String {
+    to_hex(key)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn parse_hex_key(text: &str) -> Result<[u8; KEY_LEN], EncryptionError> {
+    let text = text.trim();
+    if text.len() != KEY_LEN * 2 || !text.is_ascii() {
+        return Err(EncryptionError::InvalidKey(format!("expected {} hex digits", KEY_LEN * 2)));
+    }
+    let mut key = [0u8; KEY_LEN];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&text[i * 2..i * 2 + 2], 16)
+            .map_err(|_| EncryptionError::InvalidKey("not a hex string".to_string()))?;
+    }
+    Ok(key)
+}
+
+/// Store `key` in the OS keyring entry `(service, user)`
+#[cfg(feature = "os-keyring")]
+pub fn store_key_in_keyring(service: &str, user: &str, key: &[u8; KEY_LEN]) -> Result<(), EncryptionError> {
+    keyring::Entry::new(service, user)
+        .and_then(|entry| entry.set_password(&key_to_hex(key)))
+        .map_err(|e| EncryptionError::Keyring(e.to_string()))
+}
+
+#[cfg(feature = "os-keyring")]
+fn keyring_get(service: &str, user: &str) -> Result<String, EncryptionError> {
+    keyring::Entry::new(service, user)
+        .and_then(|entry| entry.get_password())
+        .map_err(|e| EncryptionError::Keyring(e.to_string()))
+}
+
+#[cfg(not(feature = "os-keyring"))]
+fn keyring_get(_service: &str, _user: &str) -> Result<String, EncryptionError> {
+    Err(EncryptionError::Keyring(
+        "built without OS keyring support (enable --features os-keyring)".to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open() {
+        let key = FileCipher::generate_key();
+        let cipher = FileCipher::new(&key);
+        let sealed = cipher.seal(b"tokens and connections", "runtime.bin").unwrap();
+        assert!(FileCipher::is_sealed(&sealed));
+        assert!(!sealed.windows(6).any(|w| w == b"tokens"));
+        assert_eq!(cipher.open(&sealed, "runtime.bin").unwrap(), b"tokens and connections");
+
+        // Wrong file name, wrong key, tampering, plain data
+        assert_eq!(cipher.open(&sealed, "reflexes.bin"), Err(EncryptionError::Decrypt));
+        let other = FileCipher::new(&FileCipher::generate_key());
+        assert_eq!(other.open(&sealed, "runtime.bin"), Err(EncryptionError::Decrypt));
+        let mut tampered = sealed.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert_eq!(cipher.open(&tampered, "runtime.bin"), Err(EncryptionError::Decrypt));
+        assert_eq!(cipher.open(b"{}", "adna.json"), Err(EncryptionError::NotSealed));
+    }
+
+    #[test]
+    fn test_key_sources() {
+        let key = FileCipher::generate_key();
+        let hex = key_to_hex(&key);
+
+        let config = EncryptionConfig { enabled: true, key: Some(hex.clone()), ..Default::default() };
+        assert!(config.validate().is_ok());
+        assert_eq!(config.load_key().unwrap(), key);
+        assert!(!format!("{:?}", config).contains(&hex));
+
+        let dir = tempfile::tempdir().unwrap();
+        let raw = dir.path().join("raw.key");
+        std::fs::write(&raw, key).unwrap();
+        let text = dir.path().join("hex.key");
+        std::fs::write(&text, format!("{}\n", hex)).unwrap();
+        for path in [raw, text] {
+            let config = EncryptionConfig { enabled: true, key_file: Some(path), ..Default::default() };
+            assert_eq!(config.load_key().unwrap(), key);
+        }
+
+        assert!(EncryptionConfig::default().cipher().unwrap().is_none());
+        assert!(EncryptionConfig { enabled: true, ..Default::default() }.validate().is_err());
+        let short = EncryptionConfig { key: Some("abcd".to_string()), ..Default::default() };
+        assert!(short.validate().is_err());
+    }
+
+    #[test]
+    fn test_config_from_layers() {
+        let key = FileCipher::generate_key();
+        let env = [
+            ("NEUROGRAPH__ENCRYPTION__ENABLED".to_string(), "true".to_string()),
+            ("NEUROGRAPH__ENCRYPTION__KEY".to_string(), key_to_hex(&key)),
+        ];
+        let layered = LayeredConfig::load(None, env).unwrap();
+        let config = EncryptionConfig::from_layered(&layered).unwrap();
+        assert_eq!(config.load_key().unwrap(), key);
+
+        let cipher = config.cipher().unwrap().unwrap();
+        let path = Path::new("dumps/crash.json");
+        let sealed = seal_file(Some(&cipher), b"{}".to_vec(), path).unwrap();
+        assert_eq!(open_file(Some(&cipher), sealed.clone(), path).unwrap(), b"{}");
+        assert_eq!(open_file(None, sealed, path), Err(EncryptionError::MissingKey));
+        assert_eq!(open_file(None, b"{}".to_vec(), path).unwrap(), b"{}");
+        // A plain file in place of a sealed one is refused
+        assert_eq!(open_file(Some(&cipher), b"{}".to_vec(), path), Err(EncryptionError::NotSealed));
+
+        let enabled_only = LayeredConfig::parse("[encryption]\nenabled = true\n").unwrap();
+        assert!(matches!(EncryptionConfig::from_layered(&enabled_only), Err(EncryptionError::Config(_))));
+    }
+
+    #[test]
+    fn test_records() {
+        let cipher = FileCipher::new(&FileCipher::generate_key());
+        let path = Path::new("replay/run.jsonl");
+        let line = seal_record(&cipher, b"{\"seq\":0}", path, 1).unwrap();
+        assert!(is_sealed_record(&line) && !line.contains('\n'));
+        assert_eq!(open_record(&cipher, &line, path, 1).unwrap(), b"{\"seq\":0}");
+
+        // Bound to the line number and the file name
+        assert_eq!(open_record(&cipher, &line, path, 2), Err(EncryptionError::Decrypt));
+        assert_eq!(open_record(&cipher, &line, Path::new("other.jsonl"), 1), Err(EncryptionError::Decrypt));
+        assert_eq!(open_record(&cipher, "{\"seq\":0}", path, 1), Err(EncryptionError::NotSealed));
+
+        assert_eq!(scoped_name("ckpt-1", "runtime.bin"), "ckpt-1/runtime.bin");
+    }
+}
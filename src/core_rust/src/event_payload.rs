@@ -0,0 +1,379 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Typed event payloads with a schema registry
+//!
+//! `ActionMetadata` has a fixed shape. Executors that need to attach other
+//! structured data to an ExperienceEvent register a [`PayloadSchema`] under a
+//! type tag and write an [`EventPayload`] next to the event:
+//!
+//! - the payload lives beside the 128-byte event (like `ActionMetadata`), the
+//!   event only gets `EventFlags::HAS_PAYLOAD`
+//! - up to 64 bytes are stored inline in a fixed buffer; larger payloads go
+//!   to a heap overflow buffer
+//! - fields are packed little-endian in schema order; strings are a u16
+//!   length followed by UTF-8 bytes
+//!
+//! Schemas evolve by appending fields: decoding ignores trailing bytes of a
+//! newer writer and returns `null` for fields an older writer did not write.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Payloads up to this size are stored inline
+pub const INLINE_PAYLOAD_SIZE: usize = 64;
+
+/// Payload type tag (0 is reserved)
+pub type PayloadTag = u16;
+
+/// Payload errors
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum PayloadError {
+    #[error("Payload tag 0 is reserved")]
+    ReservedTag,
+
+    #[error("Payload tag {0:#06x} already registered")]
+    DuplicateTag(PayloadTag),
+
+    #[error("Payload schema '{0}' already registered")]
+    DuplicateName(String),
+
+    #[error("Unknown payload tag {0:#06x}")]
+    UnknownTag(PayloadTag),
+
+    #[error("Unknown payload schema '{0}'")]
+    UnknownSchema(String),
+
+    #[error("Field '{field}': expected {expected}")]
+    InvalidField { field: String, expected: String },
+
+    #[error("Payload truncated in field '{0}'")]
+    Truncated(String),
+}
+
+/// Payload bytes: inline up to 64 bytes, overflow buffer beyond
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PayloadData {
+    Inline { len: u8, bytes: [u8; INLINE_PAYLOAD_SIZE] },
+    Overflow(Vec<u8>),
+}
+
+impl PayloadData {
+    pub fn new(data: Vec<u8>) -> Self {
+        if data.len() > INLINE_PAYLOAD_SIZE {
+            return Self::Overflow(data);
+        }
+        let mut bytes = [0u8; INLINE_PAYLOAD_SIZE];
+        bytes[..data.len()].copy_from_slice(&data);
+        Self::Inline { len: data.len() as u8, bytes }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Self::Inline { len, bytes } => &bytes[..*len as usize],
+            Self::Overflow(data) => data,
+        }
+    }
+
+    pub fn is_inline(&self) -> bool {
+        matches!(self, Self::Inline { .. })
+    }
+}
+
+/// Payload attached to an ExperienceEvent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventPayload {
+    pub tag: PayloadTag,
+    pub data: PayloadData,
+}
+
+impl EventPayload {
+    pub fn new(tag: PayloadTag, data: Vec<u8>) -> Self {
+        Self { tag, data: PayloadData::new(data) }
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        self.data.as_bytes()
+    }
+}
+
+/// Field type of a payload schema
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldKind {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    I32,
+    I64,
+    F32,
+    F64,
+    Str,
+}
+
+impl FieldKind {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Bool => "bool",
+            Self::U8 => "u8",
+            Self::U16 => "u16",
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+            Self::I32 => "i32",
+            Self::I64 => "i64",
+            Self::F32 => "f32",
+            Self::F64 => "f64",
+            Self::Str => "string",
+        }
+    }
+
+    /// Fixed encoded size (None for strings)
+    fn size(self) -> Option<usize> {
+        match self {
+            Self::Bool | Self::U8 => Some(1),
+            Self::U16 => Some(2),
+            Self::U32 | Self::I32 | Self::F32 => Some(4),
+            Self::U64 | Self::I64 | Self::F64 => Some(8),
+            Self::Str => None,
+        }
+    }
+}
+
+/// Named, typed field of a payload schema
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PayloadField {
+    pub name: String,
+    pub kind: FieldKind,
+}
+
+/// Layout of one payload type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PayloadSchema {
+    pub tag: PayloadTag,
+    pub name: String,
+    pub version: u16,
+    pub fields: Vec<PayloadField>,
+}
+
+impl PayloadSchema {
+    pub fn new(tag: PayloadTag, name: &str) -> Self {
+        Self { tag, name: name.to_string(), version: 1, fields: Vec::new() }
+    }
+
+    pub fn with_version(mut self, version: u16) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Append a field
+    pub fn field(mut self, name: &str, kind: FieldKind) -> Self {
+        self.fields.push(PayloadField { name: name.to_string(), kind });
+        self
+    }
+
+    /// Pack a JSON object; every field must be present
+    pub fn encode(&self, value: &Value) -> Result<Vec<u8>, PayloadError> {
+        let mut out = Vec::new();
+        for field in &self.fields {
+            let invalid = || PayloadError::InvalidField {
+                field: field.name.clone(),
+                expected: field.kind.name().to_string(),
+            };
+            let v = value.get(&field.name).ok_or_else(invalid)?;
+
+            match field.kind {
+                FieldKind::Bool => out.push(v.as_bool().ok_or_else(invalid)? as u8),
+                FieldKind::U8 => out.push(int(v.as_u64(), invalid)?),
+                FieldKind::U16 => out.extend(int::<u16, _>(v.as_u64(), invalid)?.to_le_bytes()),
+                FieldKind::U32 => out.extend(int::<u32, _>(v.as_u64(), invalid)?.to_le_bytes()),
+                FieldKind::U64 => out.extend(v.as_u64().ok_or_else(invalid)?.to_le_bytes()),
+                FieldKind::I32 => out.extend(int::<i32, _>(v.as_i64(), invalid)?.to_le_bytes()),
+                FieldKind::I64 => out.extend(v.as_i64().ok_or_else(invalid)?.to_le_bytes()),
+                FieldKind::F32 => out.extend((v.as_f64().ok_or_else(invalid)? as f32).to_le_bytes()),
+                FieldKind::F64 => out.extend(v.as_f64().ok_or_else(invalid)?.to_le_bytes()),
+                FieldKind::Str => {
+                    let s = v.as_str().ok_or_else(invalid)?;
+                    let len: u16 = s.len().try_into().map_err(|_| invalid())?;
+                    out.extend(len.to_le_bytes());
+                    out.extend(s.as_bytes());
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    /// Unpack into a JSON object
+    pub fn decode(&self, bytes: &[u8]) -> Result<Value, PayloadError> {
+        let mut object = Map::new();
+        let mut pos = 0;
+
+        for field in &self.fields {
+            if pos == bytes.len() {
+                // Written by an older schema version
+                object.insert(field.name.clone(), Value::Null);
+                continue;
+            }
+            let truncated = || PayloadError::Truncated(field.name.clone());
+            let size = match field.kind.size() {
+                Some(size) => size,
+                None => {
+                    let len = bytes.get(pos..pos + 2).ok_or_else(truncated)?;
+                    pos += 2;
+                    u16::from_le_bytes([len[0], len[1]]) as usize
+                }
+            };
+            let raw = bytes.get(pos..pos + size).ok_or_else(truncated)?;
+            pos += size;
+
+            let value = match field.kind {
+                FieldKind::Bool => Value::from(raw[0] != 0),
+                FieldKind::U8 => Value::from(raw[0]),
+                FieldKind::U16 => Value::from(u16::from_le_bytes(raw.try_into().unwrap())),
+                FieldKind::U32 => Value::from(u32::from_le_bytes(raw.try_into().unwrap())),
+                FieldKind::U64 => Value::from(u64::from_le_bytes(raw.try_into().unwrap())),
+                FieldKind::I32 => Value::from(i32::from_le_bytes(raw.try_into().unwrap())),
+                FieldKind::I64 => Value::from(i64::from_le_bytes(raw.try_into().unwrap())),
+                FieldKind::F32 => Value::from(f32::from_le_bytes(raw.try_into().unwrap())),
+                FieldKind::F64 => Value::from(f64::from_le_bytes(raw.try_into().unwrap())),
+                FieldKind::Str => Value::from(String::from_utf8(raw.to_vec()).map_err(|_| {
+                    PayloadError::InvalidField { field: field.name.clone(), expected: "UTF-8".to_string() }
+                })?),
+            };
+            object.insert(field.name.clone(), value);
+        }
+
+        Ok(Value::Object(object))
+    }
+}
+
+/// Narrow a JSON integer to a field type
+fn int<T: TryFrom<N>, N>(value: Option<N>, invalid: impl Fn() -> PayloadError) -> Result<T, PayloadError> {
+    value.and_then(|v| T::try_from(v).ok()).ok_or_else(invalid)
+}
+
+/// Registered payload schemas by tag and name
+#[derive(Debug, Clone, Default)]
+pub struct PayloadRegistry {
+    schemas: HashMap<PayloadTag, PayloadSchema>,
+    names: HashMap<String, PayloadTag>,
+}
+
+impl PayloadRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(&mut self, schema: PayloadSchema) -> Result<(), PayloadError> {
+        if schema.tag == 0 {
+            return Err(PayloadError::ReservedTag);
+        }
+        if self.schemas.contains_key(&schema.tag) {
+            return Err(PayloadError::DuplicateTag(schema.tag));
+        }
+        if self.names.contains_key(&schema.name) {
+            return Err(PayloadError::DuplicateName(schema.name));
+        }
+        self.names.insert(schema.name.clone(), schema.tag);
+        self.schemas.insert(schema.tag, schema);
+        Ok(())
+    }
+
+    pub fn get(&self, tag: PayloadTag) -> Option<&PayloadSchema> {
+        self.schemas.get(&tag)
+    }
+
+    pub fn by_name(&self, name: &str) -> Option<&PayloadSchema> {
+        self.names.get(name).and_then(|tag| self.schemas.get(tag))
+    }
+
+    /// Iterate registered schemas
+    pub fn schemas(&self) -> impl Iterator<Item = &PayloadSchema> {
+        self.schemas.values()
+    }
+
+    /// Encode a JSON object with the schema registered as `name`
+    pub fn encode(&self, name: &str, value: &Value) -> Result<EventPayload, PayloadError> {
+        let schema = self.by_name(name)
+            .ok_or_else(|| PayloadError::UnknownSchema(name.to_string()))?;
+        Ok(EventPayload::new(schema.tag, schema.encode(value)?))
+    }
+
+    /// Decode a payload with the schema of its tag
+    pub fn decode(&self, payload: &EventPayload) -> Result<Value, PayloadError> {
+        self.get(payload.tag)
+            .ok_or(PayloadError::UnknownTag(payload.tag))?
+            .decode(payload.bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn http_schema() -> PayloadSchema {
+        PayloadSchema::new(0x0101, "http_call")
+            .field("status", FieldKind::U16)
+            .field("latency_ms", FieldKind::F32)
+            .field("cached", FieldKind::Bool)
+            .field("url", FieldKind::Str)
+    }
+
+    #[test]
+    fn test_roundtrip_inline_and_overflow() {
+        let mut registry = PayloadRegistry::new();
+        registry.register(http_schema()).unwrap();
+
+        let value = json!({"status": 200, "latency_ms": 12.5, "cached": false, "url": "https://a.io"});
+        let payload = registry.encode("http_call", &value).unwrap();
+        assert!(payload.data.is_inline());
+        assert_eq!(registry.decode(&payload).unwrap(), value);
+
+        let long = json!({"status": 404, "latency_ms": 1.0, "cached": true, "url": "x".repeat(100)});
+        let payload = registry.encode("http_call", &long).unwrap();
+        assert!(!payload.data.is_inline());
+        assert_eq!(registry.decode(&payload).unwrap(), long);
+    }
+
+    #[test]
+    fn test_schema_errors_and_evolution() {
+        let mut registry = PayloadRegistry::new();
+        registry.register(http_schema()).unwrap();
+        assert_eq!(registry.register(PayloadSchema::new(0, "x")), Err(PayloadError::ReservedTag));
+        assert_eq!(registry.register(PayloadSchema::new(0x0101, "y")), Err(PayloadError::DuplicateTag(0x0101)));
+        assert!(matches!(registry.register(http_schema().with_version(2)), Err(PayloadError::DuplicateTag(_))));
+
+        let bad = json!({"status": 70000, "latency_ms": 1.0, "cached": true, "url": ""});
+        assert!(matches!(registry.encode("http_call", &bad), Err(PayloadError::InvalidField { .. })));
+        assert!(matches!(registry.encode("nope", &bad), Err(PayloadError::UnknownSchema(_))));
+        assert_eq!(registry.decode(&EventPayload::new(7, vec![])), Err(PayloadError::UnknownTag(7)));
+
+        // v2 appends a field: v1 payloads decode with null, v1 readers skip it
+        let v2 = http_schema().with_version(2).field("retries", FieldKind::U8);
+        let v1_bytes = http_schema()
+            .encode(&json!({"status": 200, "latency_ms": 1.0, "cached": true, "url": "u"}))
+            .unwrap();
+        assert_eq!(v2.decode(&v1_bytes).unwrap()["retries"], Value::Null);
+        let v2_bytes = v2
+            .encode(&json!({"status": 200, "latency_ms": 1.0, "cached": true, "url": "u", "retries": 3}))
+            .unwrap();
+        assert_eq!(http_schema().decode(&v2_bytes).unwrap()["status"], 200);
+        assert!(matches!(http_schema().decode(&v2_bytes[..4]), Err(PayloadError::Truncated(_))));
+    }
+}
@@ -28,13 +28,21 @@
 //! - Rollback capability (version tracking)
 
 use std::sync::Arc;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
-use parking_lot::RwLock;
+use tracing::Instrument;
+use parking_lot::{Mutex, RwLock};
 
-use crate::adna::{Proposal, ActionPolicy};
+use crate::adna::{Proposal, ActionPolicy, ADNAReader, EvolutionMetrics};
+use crate::appraisers::{reappraise_window, ReappraisalReport};
 use crate::cdna::CDNA;
-use crate::experience_stream::{ExperienceStream, ExperienceEvent, EventType as ExperienceEventType};
+use crate::experience_stream::{
+    ExperienceStream, ExperienceEvent, EventType as ExperienceEventType, SamplingStrategy,
+};
+use crate::guardian::{Event as GuardianEvent, EventType as GuardianEventType, Guardian};
+use crate::intuition_engine::quantize_state;
+use crate::safe_mode::SafeMode;
 
 /// Configuration for EvolutionManager
 #[derive(Debug, Clone)]
@@ -47,6 +55,15 @@ pub struct EvolutionConfig {
 
     /// Enable strict CDNA validation
     pub strict_validation: bool,
+
+    /// Shadow A/B evaluation before promotion
+    pub shadow: ShadowConfig,
+
+    /// When evolution attempts are triggered automatically
+    pub triggers: EvolutionTriggers,
+
+    /// Re-scoring of recent experience after ADNA changes
+    pub reappraisal: ReappraisalConfig,
 }
 
 impl Default for EvolutionConfig {
@@ -55,10 +72,378 @@ impl Default for EvolutionConfig {
             max_proposals_per_sec: 10,
             min_confidence_threshold: 0.75,
             strict_validation: true,
+            shadow: ShadowConfig::default(),
+            triggers: EvolutionTriggers::default(),
+            reappraisal: ReappraisalConfig::default(),
+        }
+    }
+}
+
+/// Re-appraisal of recent experience when a new ADNA is adopted
+///
+/// Rewards already in the stream were computed under the previous ADNA. With
+/// re-appraisal enabled, every adopted proposal bumps the ADNA generation
+/// stamped on new events and re-scores the last `window` events under the
+/// current appraiser config (see `appraisers::reappraise_window`). Requires
+/// an ADNA reader (`EvolutionManager::with_adna_reader`).
+#[derive(Debug, Clone)]
+pub struct ReappraisalConfig {
+    pub enabled: bool,
+
+    /// Number of most recent events to re-score
+    pub window: usize,
+}
+
+impl Default for ReappraisalConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window: 10_000,
+        }
+    }
+}
+
+/// Evolution cadence: what starts an evolution attempt
+///
+/// An attempt asks IntuitionEngine for an immediate analysis cycle, whose
+/// proposals then flow through EvolutionManager as usual.
+#[derive(Debug, Clone)]
+pub struct EvolutionTriggers {
+    /// Trigger after this many experience events (None = disabled)
+    pub every_n_events: Option<u64>,
+
+    /// Trigger when rewards plateau (None = disabled)
+    pub plateau: Option<PlateauConfig>,
+
+    /// Minimum time between attempts, whatever triggered them
+    pub cooldown_secs: u64,
+
+    /// How long to wait for an event to be fully appraised before using
+    /// whatever reward it has
+    pub appraisal_timeout_ms: u64,
+}
+
+impl Default for EvolutionTriggers {
+    fn default() -> Self {
+        Self {
+            every_n_events: Some(10_000),
+            plateau: Some(PlateauConfig::default()),
+            cooldown_secs: 300,
+            appraisal_timeout_ms: 2_000,
+        }
+    }
+}
+
+impl EvolutionTriggers {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.every_n_events == Some(0) {
+            return Err("every_n_events must be > 0".to_string());
+        }
+        if let Some(plateau) = &self.plateau {
+            if plateau.window < 2 {
+                return Err("plateau.window must be >= 2".to_string());
+            }
+            if !plateau.slope_threshold.is_finite() || plateau.slope_threshold < 0.0 {
+                return Err("plateau.slope_threshold must be a finite value >= 0".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reward plateau detection
+#[derive(Debug, Clone)]
+pub struct PlateauConfig {
+    /// Rewards in the rolling window
+    pub window: usize,
+
+    /// Plateau when |slope| of reward over the window falls below this
+    /// (reward units per event)
+    pub slope_threshold: f64,
+}
+
+impl Default for PlateauConfig {
+    fn default() -> Self {
+        Self {
+            window: 500,
+            slope_threshold: 1e-4,
+        }
+    }
+}
+
+/// What started an evolution attempt
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EvolutionTrigger {
+    /// `every_n_events` events observed since the last attempt
+    EventCount { events: u64 },
+    /// Rolling reward slope fell below the threshold
+    RewardPlateau { slope: f64 },
+    /// Explicit request (SystemCommand::Evolve)
+    Command,
+}
+
+impl std::fmt::Display for EvolutionTrigger {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EvolutionTrigger::EventCount { events } => write!(f, "event count ({} events)", events),
+            EvolutionTrigger::RewardPlateau { slope } => write!(f, "reward plateau (slope {:.2e})", slope),
+            EvolutionTrigger::Command => write!(f, "command"),
+        }
+    }
+}
+
+/// Scheduler statistics
+#[derive(Debug, Clone, Default)]
+pub struct SchedulerStats {
+    pub events_observed: u64,
+    pub attempts: u64,
+    /// Triggers dropped because of the cooldown
+    pub suppressed: u64,
+    pub last_trigger: Option<EvolutionTrigger>,
+}
+
+#[derive(Debug, Default)]
+struct SchedulerState {
+    events_since_attempt: u64,
+    rewards: VecDeque<f64>,
+    last_attempt: Option<Instant>,
+    stats: SchedulerStats,
+}
+
+/// Decides when to run evolution and signals IntuitionEngine
+pub struct EvolutionScheduler {
+    triggers: EvolutionTriggers,
+    state: Mutex<SchedulerState>,
+    attempt_sender: mpsc::Sender<EvolutionTrigger>,
+    guardian: Option<Arc<RwLock<Guardian>>>,
+    safe_mode: Arc<SafeMode>,
+}
+
+impl EvolutionScheduler {
+    /// Create scheduler; attempts are sent on `attempt_sender`
+    /// (see `IntuitionEngine::set_trigger_receiver`)
+    pub fn new(triggers: EvolutionTriggers, attempt_sender: mpsc::Sender<EvolutionTrigger>) -> Self {
+        Self {
+            triggers,
+            state: Mutex::new(SchedulerState::default()),
+            attempt_sender,
+            guardian: None,
+            safe_mode: Arc::default(),
+        }
+    }
+
+    /// Send no evolution attempts while this switch freezes learning
+    pub fn with_safe_mode(mut self, safe_mode: Arc<SafeMode>) -> Self {
+        self.safe_mode = safe_mode;
+        self
+    }
+
+    /// Emit a Guardian event for each evolution attempt
+    pub fn with_guardian(mut self, guardian: Arc<RwLock<Guardian>>) -> Self {
+        self.guardian = Some(guardian);
+        self
+    }
+
+    /// Observe one appraised experience event
+    ///
+    /// Returns the trigger if this event started an attempt.
+    pub fn observe(&self, event: &ExperienceEvent) -> Option<EvolutionTrigger> {
+        let trigger = {
+            let mut state = self.state.lock();
+            state.stats.events_observed += 1;
+            state.events_since_attempt += 1;
+
+            if let Some(plateau) = &self.triggers.plateau {
+                state.rewards.push_back(event.total_reward() as f64);
+                while state.rewards.len() > plateau.window {
+                    state.rewards.pop_front();
+                }
+            }
+
+            match self.triggers.every_n_events {
+                Some(n) if state.events_since_attempt >= n => Some(EvolutionTrigger::EventCount {
+                    events: state.events_since_attempt,
+                }),
+                _ => self.plateau_slope(&state).map(|slope| EvolutionTrigger::RewardPlateau { slope }),
+            }
+        }?;
+
+        self.fire(trigger).then_some(trigger)
+    }
+
+    /// Explicit evolution request; false if suppressed by the cooldown or frozen learning
+    pub fn request(&self) -> bool {
+        self.fire(EvolutionTrigger::Command)
+    }
+
+    /// Slope of rewards over a full window, if it is a plateau
+    fn plateau_slope(&self, state: &SchedulerState) -> Option<f64> {
+        let plateau = self.triggers.plateau.as_ref()?;
+        if state.rewards.len() < plateau.window {
+            return None;
+        }
+        let slope = reward_slope(&state.rewards);
+        (slope.abs() < plateau.slope_threshold).then_some(slope)
+    }
+
+    /// Start an attempt unless cooling down
+    fn fire(&self, trigger: EvolutionTrigger) -> bool {
+        {
+            let mut state = self.state.lock();
+            if self.safe_mode.learning_frozen() {
+                state.stats.suppressed += 1;
+                return false;
+            }
+            let cooldown = Duration::from_secs(self.triggers.cooldown_secs);
+            if state.last_attempt.is_some_and(|at| at.elapsed() < cooldown) {
+                state.stats.suppressed += 1;
+                return false;
+            }
+
+            state.last_attempt = Some(Instant::now());
+            state.events_since_attempt = 0;
+            // The next plateau must be observed on post-attempt rewards
+            state.rewards.clear();
+            state.stats.attempts += 1;
+            state.stats.last_trigger = Some(trigger);
+        }
+
+        if let Some(guardian) = &self.guardian {
+            guardian.write().emit_event(
+                GuardianEvent::new(GuardianEventType::EvolutionAttempt)
+                    .with_data(format!("Evolution attempt triggered by {}", trigger)),
+            );
+        }
+
+        if let Err(e) = self.attempt_sender.try_send(trigger) {
+            eprintln!("[EvolutionScheduler] Failed to signal evolution attempt: {}", e);
+        }
+        println!("[EvolutionScheduler] Evolution attempt triggered by {}", trigger);
+
+        true
+    }
+
+    /// Scheduler statistics
+    pub fn stats(&self) -> SchedulerStats {
+        self.state.lock().stats.clone()
+    }
+
+    /// Follow the ExperienceStream and feed appraised events to `observe`
+    ///
+    /// Events are taken in order; each waits for full appraisal for up to
+    /// `appraisal_timeout_ms` before its current reward is used.
+    pub async fn run(self: Arc<Self>, experience_stream: Arc<ExperienceStream>) {
+        let timeout = Duration::from_millis(self.triggers.appraisal_timeout_ms);
+        let mut next_seq = experience_stream.total_written();
+        let mut waiting_since: Option<Instant> = None;
+        let mut poll = tokio::time::interval(Duration::from_millis(100));
+
+        loop {
+            poll.tick().await;
+
+            while next_seq < experience_stream.total_written() {
+                let Some(event) = experience_stream.get_event(next_seq) else {
+                    // Overwritten in the ring buffer before we got to it
+                    next_seq += 1;
+                    waiting_since = None;
+                    continue;
+                };
+
+                if !event.is_fully_appraised() {
+                    let since = *waiting_since.get_or_insert_with(Instant::now);
+                    if since.elapsed() < timeout {
+                        break;
+                    }
+                }
+
+                self.observe(&event);
+                next_seq += 1;
+                waiting_since = None;
+            }
+        }
+    }
+}
+
+/// Least-squares slope of rewards against event index
+fn reward_slope(rewards: &VecDeque<f64>) -> f64 {
+    let n = rewards.len() as f64;
+    if n < 2.0 {
+        return 0.0;
+    }
+    let mean_x = (n - 1.0) / 2.0;
+    let mean_y = rewards.iter().sum::<f64>() / n;
+
+    let (cov, var) = rewards.iter().enumerate().fold((0.0, 0.0), |(cov, var), (i, y)| {
+        let dx = i as f64 - mean_x;
+        (cov + dx * (y - mean_y), var + dx * dx)
+    });
+
+    if var > 0.0 { cov / var } else { 0.0 }
+}
+
+/// Shadow evaluation of candidate ADNA policies
+///
+/// Before a proposal is applied, the candidate policy and the live policy are
+/// both replayed against a sample of recent experience. Each policy's value is
+/// estimated by weighting the appraised reward of every matching event with
+/// the weight the policy gives to the action that was taken.
+#[derive(Debug, Clone)]
+pub struct ShadowConfig {
+    /// Run shadow evaluation for accepted proposals
+    pub enabled: bool,
+
+    /// Number of recent events sampled per evaluation
+    pub sample_size: usize,
+
+    /// Minimum sampled events in the proposal's state bin
+    pub min_matched_events: usize,
+
+    /// Minimum reward lift (candidate - live) required for promotion
+    pub min_reward_lift: f64,
+
+    /// Bins per dimension; must match IntuitionConfig::state_bins_per_dim
+    pub state_bins_per_dim: usize,
+
+    /// Reject proposals when there is too little data to compare
+    pub reject_inconclusive: bool,
+}
+
+impl Default for ShadowConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            sample_size: 500,
+            min_matched_events: 20,
+            min_reward_lift: 0.0,
+            state_bins_per_dim: 4,
+            reject_inconclusive: false,
         }
     }
 }
 
+/// Shadow evaluation verdict
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShadowVerdict {
+    /// Candidate performed at least as well as required
+    Promote,
+    /// Candidate underperformed the live policy
+    Reject,
+    /// Not enough matching experience to compare
+    Inconclusive,
+}
+
+/// Result of a shadow A/B evaluation
+#[derive(Debug, Clone)]
+pub struct ShadowResult {
+    pub target_entity_id: String,
+    pub sampled_events: usize,
+    pub matched_events: usize,
+    pub live_reward: Option<f64>,
+    pub candidate_reward: Option<f64>,
+    pub lift: Option<f64>,
+    pub verdict: ShadowVerdict,
+}
+
 /// Validation result
 #[derive(Debug, Clone)]
 pub enum ValidationResult {
@@ -89,8 +474,15 @@ impl ADNAState {
 
     /// Apply proposal (atomic update)
     pub fn apply_proposal(&self, proposal: &Proposal) -> Result<(), String> {
-        let mut policies = self.policies.write();
+        let policy = Self::candidate_policy(proposal)?;
+        self.policies
+            .write()
+            .insert(proposal.target_entity_id.clone(), policy);
+        Ok(())
+    }
 
+    /// Policy a proposal would install, without applying it
+    pub fn candidate_policy(proposal: &Proposal) -> Result<ActionPolicy, String> {
         // Parse proposed change
         let change = &proposal.proposed_change;
 
@@ -107,8 +499,7 @@ impl ADNAState {
                     }
                 }
 
-                policies.insert(proposal.target_entity_id.clone(), policy);
-                return Ok(());
+                return Ok(policy);
             }
         }
 
@@ -128,6 +519,9 @@ pub struct EvolutionManager {
     cdna: Arc<CDNA>,
     experience_stream: Arc<ExperienceStream>,
     proposal_receiver: mpsc::Receiver<Proposal>,
+    metrics: RwLock<EvolutionMetrics>,
+    adna_reader: Option<Arc<dyn ADNAReader>>,
+    safe_mode: Arc<SafeMode>,
 }
 
 impl EvolutionManager {
@@ -145,9 +539,29 @@ impl EvolutionManager {
             cdna,
             experience_stream,
             proposal_receiver,
+            metrics: RwLock::new(EvolutionMetrics::default()),
+            adna_reader: None,
+            safe_mode: Arc::default(),
         }
     }
 
+    /// Apply no ADNA proposals while this switch freezes learning
+    pub fn with_safe_mode(mut self, safe_mode: Arc<SafeMode>) -> Self {
+        self.safe_mode = safe_mode;
+        self
+    }
+
+    /// Appraiser config source for re-appraisal
+    pub fn with_adna_reader(mut self, adna_reader: Arc<dyn ADNAReader>) -> Self {
+        self.adna_reader = Some(adna_reader);
+        self
+    }
+
+    /// Evolution metrics, including shadow evaluation results
+    pub fn metrics(&self) -> EvolutionMetrics {
+        *self.metrics.read()
+    }
+
     /// Run main proposal processing loop
     pub async fn run(mut self) {
         println!("[EvolutionManager] Starting proposal processing loop");
@@ -159,7 +573,15 @@ impl EvolutionManager {
         while let Some(proposal) = self.proposal_receiver.recv().await {
             rate_limiter.tick().await; // Rate limiting
 
-            if let Err(e) = self.process_proposal(proposal).await {
+            let span = tracing::info_span!(
+                "learning.proposal",
+                target = %proposal.target_entity_id,
+                correlation_id = tracing::field::Empty,
+            );
+            if let Some(correlation_id) = proposal.correlation_id {
+                span.record("correlation_id", tracing::field::display(correlation_id));
+            }
+            if let Err(e) = self.process_proposal(proposal).instrument(span).await {
                 eprintln!("[EvolutionManager] Error processing proposal: {}", e);
             }
         }
@@ -174,11 +596,42 @@ impl EvolutionManager {
         // 1. Validate proposal
         let validation_result = self.validate_proposal(&proposal).await;
 
-        let (accepted, reason) = match &validation_result {
+        let (mut accepted, mut reason) = match &validation_result {
             ValidationResult::Accepted { reason } => (true, reason.clone()),
             ValidationResult::Rejected { reason } => (false, reason.clone()),
         };
 
+        // 1b. Shadow A/B evaluation against recent experience
+        if accepted && self.config.shadow.enabled {
+            let shadow = self.shadow_evaluate(&proposal)?;
+            match shadow.verdict {
+                ShadowVerdict::Promote => {
+                    reason = format!(
+                        "{}; shadow lift {:+.3} over {} events",
+                        reason,
+                        shadow.lift.unwrap_or(0.0),
+                        shadow.matched_events
+                    );
+                }
+                ShadowVerdict::Reject => {
+                    accepted = false;
+                    reason = format!(
+                        "Shadow evaluation: lift {:+.3} below {:.3} ({} events)",
+                        shadow.lift.unwrap_or(0.0),
+                        self.config.shadow.min_reward_lift,
+                        shadow.matched_events
+                    );
+                }
+                ShadowVerdict::Inconclusive => {
+                    accepted = !self.config.shadow.reject_inconclusive;
+                    reason = format!(
+                        "{}; shadow evaluation inconclusive ({} matching events)",
+                        reason, shadow.matched_events
+                    );
+                }
+            }
+        }
+
         println!("[EvolutionManager] Validation: {} - {}",
             if accepted { "ACCEPTED" } else { "REJECTED" },
             reason);
@@ -188,6 +641,24 @@ impl EvolutionManager {
             match self.adna_state.apply_proposal(&proposal) {
                 Ok(_) => {
                     println!("[EvolutionManager] Successfully applied proposal to ADNA state");
+                    {
+                        let mut metrics = self.metrics.write();
+                        metrics.generation += 1;
+                        metrics.last_update = std::time::SystemTime::now()
+                            .duration_since(std::time::UNIX_EPOCH)
+                            .unwrap()
+                            .as_secs();
+                    }
+
+                    if self.config.reappraisal.enabled {
+                        match self.reappraise().await {
+                            Ok(report) => println!(
+                                "[EvolutionManager] Re-appraised {} events (reward delta {:+.3})",
+                                report.reappraised, report.reward_delta
+                            ),
+                            Err(e) => eprintln!("[EvolutionManager] Re-appraisal failed: {}", e),
+                        }
+                    }
 
                     // 3a. Log success to ExperienceStream
                     self.log_outcome(&proposal, true, "Proposal applied successfully").await;
@@ -207,8 +678,93 @@ impl EvolutionManager {
         Ok(())
     }
 
+    /// Re-score recent experience under the current ADNA generation
+    ///
+    /// Stamps the generation on new events and re-appraises the last
+    /// `reappraisal.window` events with the ADNA reader's appraiser config.
+    pub async fn reappraise(&self) -> Result<ReappraisalReport, String> {
+        let reader = self.adna_reader.as_ref()
+            .ok_or_else(|| "Re-appraisal needs an ADNA reader".to_string())?;
+        let config = reader.get_appraiser_config().await.map_err(|e| e.to_string())?;
+        // Generation 0 means "not tracked" in the stream
+        let generation = self.metrics.read().generation.max(1);
+
+        self.experience_stream.set_adna_generation(generation);
+        Ok(reappraise_window(&self.experience_stream, &config, generation, self.config.reappraisal.window))
+    }
+
+    /// Shadow-evaluate a candidate policy against the live one
+    ///
+    /// Samples recent experience, keeps events in the proposal's state bin
+    /// (`adna_state_bin_{id}`; all events if the target is not a state bin),
+    /// and compares reward-weighted estimates of both policies. The result is
+    /// recorded in EvolutionMetrics.
+    pub fn shadow_evaluate(&self, proposal: &Proposal) -> Result<ShadowResult, String> {
+        let config = &self.config.shadow;
+        let candidate = ADNAState::candidate_policy(proposal)?;
+        let live = self.adna_state.get_policy(&proposal.target_entity_id);
+
+        let target_bin = proposal
+            .target_entity_id
+            .strip_prefix("adna_state_bin_")
+            .and_then(|id| id.parse::<u64>().ok());
+
+        let batch = self
+            .experience_stream
+            .sample_batch(config.sample_size, SamplingStrategy::RecencyWeighted { decay: 0.5 });
+        let matched: Vec<&ExperienceEvent> = batch
+            .events
+            .iter()
+            .filter(|e| {
+                target_bin.is_none_or(|bin| quantize_state(&e.state, config.state_bins_per_dim) == bin)
+            })
+            .collect();
+
+        // Live policy missing → uniform over observed actions
+        let live_reward = policy_value(&matched, |action| {
+            live.as_ref().map_or(1.0, |p| p.get_weight(action))
+        });
+        let candidate_reward = policy_value(&matched, |action| candidate.get_weight(action));
+        let lift = match (live_reward, candidate_reward) {
+            (Some(live), Some(candidate)) => Some(candidate - live),
+            _ => None,
+        };
+
+        let verdict = match lift {
+            Some(_) if matched.len() < config.min_matched_events => ShadowVerdict::Inconclusive,
+            Some(lift) if lift >= config.min_reward_lift => ShadowVerdict::Promote,
+            Some(_) => ShadowVerdict::Reject,
+            None => ShadowVerdict::Inconclusive,
+        };
+
+        if verdict != ShadowVerdict::Inconclusive {
+            self.metrics.write().record_shadow_evaluation(
+                lift.unwrap_or(0.0) as f32,
+                matched.len() as u32,
+                verdict == ShadowVerdict::Promote,
+            );
+        }
+
+        Ok(ShadowResult {
+            target_entity_id: proposal.target_entity_id.clone(),
+            sampled_events: batch.events.len(),
+            matched_events: matched.len(),
+            live_reward,
+            candidate_reward,
+            lift,
+            verdict,
+        })
+    }
+
     /// Validate proposal against CDNA rules and internal constraints
     async fn validate_proposal(&self, proposal: &Proposal) -> ValidationResult {
+        // Check 0: No evolution while learning is paused or in safe mode
+        if self.safe_mode.learning_frozen() {
+            return ValidationResult::Rejected {
+                reason: "Learning is paused or frozen by safe mode".to_string(),
+            };
+        }
+
         // Check 1: Confidence threshold
         if proposal.confidence < self.config.min_confidence_threshold {
             return ValidationResult::Rejected {
@@ -322,6 +878,23 @@ impl EvolutionManager {
     }
 }
 
+/// Reward-weighted value of a policy over logged events
+///
+/// Each event's total reward counts with the weight the policy assigns to the
+/// action taken (event_type). None if the policy never takes a logged action.
+fn policy_value(events: &[&ExperienceEvent], weight: impl Fn(u16) -> f64) -> Option<f64> {
+    let (weighted, total) = events.iter().fold((0.0, 0.0), |(sum, total), event| {
+        let w = weight(event.event_type).max(0.0);
+        (sum + w * event.total_reward() as f64, total + w)
+    });
+
+    if total > 0.0 {
+        Some(weighted / total)
+    } else {
+        None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -449,4 +1022,182 @@ mod tests {
 
         assert!(!manager.validate_proposal_format(&invalid));
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_reappraise_after_adna_change() {
+        use crate::adna::{AppraiserConfig, InMemoryADNAReader};
+        use crate::appraisers::appraise_event;
+
+        let stream = Arc::new(ExperienceStream::new(100, 10));
+        let reader = Arc::new(InMemoryADNAReader::with_defaults());
+        let old = AppraiserConfig::default();
+        for i in 0..5 {
+            let mut event = ExperienceEvent::default();
+            event.state[1] = 0.5 + i as f32 * 0.1; // L2 novelty
+            let seq = stream.write_event(event).unwrap() - 1;
+            let appraisal = appraise_event(&event, &old);
+            for (appraiser, reward) in [
+                (crate::AppraiserType::Homeostasis, appraisal.homeostasis),
+                (crate::AppraiserType::Curiosity, appraisal.curiosity),
+                (crate::AppraiserType::Efficiency, appraisal.efficiency),
+                (crate::AppraiserType::Goal, appraisal.goal_directed),
+            ] {
+                stream.set_appraiser_reward(seq, appraiser, reward).unwrap();
+            }
+        }
+
+        let mut new = old;
+        new.curiosity.weight *= 2.0;
+        reader.update_config(new).await;
+
+        let config = EvolutionConfig {
+            reappraisal: ReappraisalConfig { enabled: true, window: 3 },
+            ..Default::default()
+        };
+        let (_tx, rx) = mpsc::channel(1);
+        let manager = EvolutionManager::new(config, Arc::new(ADNAState::new()), Arc::new(CDNA::default()), stream.clone(), rx)
+            .with_adna_reader(reader);
+        manager.metrics.write().generation = 1;
+
+        let report = manager.reappraise().await.unwrap();
+        assert_eq!((report.scanned, report.reappraised), (3, 3));
+        assert!(report.reward_delta > 0.0);
+
+        // Outside the window: old ADNA, unstamped
+        assert_eq!(stream.event_generation(1), Some(0));
+        let event = stream.get_event(4).unwrap();
+        assert_eq!(stream.event_generation(4), Some(1));
+        assert_eq!(event.reward_curiosity, appraise_event(&event, &new).curiosity);
+
+        // Already scored by generation 1; new events carry it from the start
+        assert_eq!(manager.reappraise().await.unwrap().skipped, 3);
+        let hashed = ExperienceEvent { adna_version_hash: 0xABCD, ..Default::default() };
+        let seq = stream.write_event(hashed).unwrap() - 1;
+        assert_eq!(stream.event_generation(seq), Some(1));
+        assert_eq!(stream.get_event(seq).unwrap().adna_version_hash, 0xABCD);
+    }
+
+    fn shadow_manager(stream: Arc<ExperienceStream>) -> EvolutionManager {
+        let config = EvolutionConfig {
+            shadow: ShadowConfig {
+                min_matched_events: 10,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let (_tx, rx) = mpsc::channel(1);
+        EvolutionManager::new(config, Arc::new(ADNAState::new()), Arc::new(CDNA::default()), stream, rx)
+    }
+
+    fn weights_proposal(target: &str, good: f64, bad: f64) -> Proposal {
+        Proposal::new(
+            target.to_string(),
+            serde_json::json!({"op": "replace", "value": {"1": good, "2": bad}}),
+            "test".to_string(),
+            1.0,
+            0.9,
+        )
+    }
+
+    #[test]
+    fn test_shadow_evaluation() {
+        let stream = Arc::new(ExperienceStream::new(1000, 100));
+        for i in 0..40 {
+            let mut event = ExperienceEvent::default();
+            event.event_type = if i % 2 == 0 { 1 } else { 2 };
+            event.reward_goal = if i % 2 == 0 { 1.0 } else { -1.0 };
+            stream.write_event(event).unwrap();
+        }
+        let manager = shadow_manager(stream);
+        let target = format!("adna_state_bin_{}", quantize_state(&[0.0; 8], 4));
+
+        // Favouring the rewarded action beats the (uniform) live policy
+        let better = manager.shadow_evaluate(&weights_proposal(&target, 0.8, 0.2)).unwrap();
+        assert_eq!(better.verdict, ShadowVerdict::Promote);
+        assert_eq!(better.matched_events, 40);
+        assert!((better.live_reward.unwrap()).abs() < 1e-9);
+        assert!((better.lift.unwrap() - 0.6).abs() < 1e-6);
+
+        let worse = manager.shadow_evaluate(&weights_proposal(&target, 0.2, 0.8)).unwrap();
+        assert_eq!(worse.verdict, ShadowVerdict::Reject);
+
+        // Other state bins have no matching experience
+        let elsewhere = manager.shadow_evaluate(&weights_proposal("adna_state_bin_1", 0.8, 0.2)).unwrap();
+        assert_eq!(elsewhere.verdict, ShadowVerdict::Inconclusive);
+
+        let metrics = manager.metrics();
+        let (evaluations, promotions, lift) =
+            (metrics.shadow_evaluations, metrics.shadow_promotions, metrics.shadow_lift);
+        assert_eq!(evaluations, 2);
+        assert_eq!(promotions, 1);
+        assert!(lift < 0.0);
+    }
+
+    fn reward_event(reward: f32) -> ExperienceEvent {
+        let mut event = ExperienceEvent::default();
+        event.reward_goal = reward;
+        event
+    }
+
+    #[test]
+    fn test_scheduler_event_count_and_cooldown() {
+        let (tx, mut rx) = mpsc::channel(8);
+        let guardian = Arc::new(RwLock::new(Guardian::new()));
+        let triggers = EvolutionTriggers {
+            every_n_events: Some(3),
+            plateau: None,
+            cooldown_secs: 3600,
+            ..Default::default()
+        };
+        let scheduler = EvolutionScheduler::new(triggers, tx).with_guardian(guardian.clone());
+
+        assert!(scheduler.observe(&reward_event(1.0)).is_none());
+        assert!(scheduler.observe(&reward_event(1.0)).is_none());
+        assert_eq!(
+            scheduler.observe(&reward_event(1.0)),
+            Some(EvolutionTrigger::EventCount { events: 3 })
+        );
+        assert_eq!(rx.try_recv().unwrap(), EvolutionTrigger::EventCount { events: 3 });
+
+        // Cooling down: neither events nor commands start an attempt
+        for _ in 0..3 {
+            assert!(scheduler.observe(&reward_event(1.0)).is_none());
+        }
+        assert!(!scheduler.request());
+        assert!(rx.try_recv().is_err());
+
+        let stats = scheduler.stats();
+        assert_eq!(stats.attempts, 1);
+        assert_eq!(stats.suppressed, 2);
+
+        let events = guardian.read().pending_events().clone();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, GuardianEventType::EvolutionAttempt);
+    }
+
+    #[test]
+    fn test_scheduler_plateau_and_command() {
+        let (tx, mut rx) = mpsc::channel(8);
+        let triggers = EvolutionTriggers {
+            every_n_events: None,
+            plateau: Some(PlateauConfig { window: 10, slope_threshold: 0.01 }),
+            cooldown_secs: 0,
+            ..Default::default()
+        };
+        let scheduler = EvolutionScheduler::new(triggers, tx);
+
+        // Rising reward: no plateau
+        for i in 0..10 {
+            assert!(scheduler.observe(&reward_event(i as f32 * 0.1)).is_none());
+        }
+
+        // Flat reward fills a fresh window, then triggers
+        let fired: Vec<_> = (0..10).filter_map(|_| scheduler.observe(&reward_event(0.9))).collect();
+        assert_eq!(fired.len(), 1);
+        assert!(matches!(fired[0], EvolutionTrigger::RewardPlateau { slope } if slope.abs() < 0.01));
+
+        assert!(scheduler.request());
+        assert!(matches!(rx.try_recv().unwrap(), EvolutionTrigger::RewardPlateau { .. }));
+        assert_eq!(rx.try_recv().unwrap(), EvolutionTrigger::Command);
+    }
+}
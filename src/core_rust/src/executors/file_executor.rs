@@ -0,0 +1,483 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! FileExecutor - sandboxed file I/O
+//!
+//! Lets action policies persist notes and read task files. All paths are
+//! resolved inside a single sandbox directory; every operation is validated
+//! by Guardian and logged to ExperienceStream.
+
+use crate::action_executor::{ActionExecutor, ActionResult, CancellationToken};
+use crate::experience_stream::{EventType, ExperienceEvent, ExperienceWriter};
+use crate::guardian::Guardian;
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// File operation kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileOperation {
+    Read,
+    Write,
+    Append,
+}
+
+impl FileOperation {
+    fn parse(s: &str) -> Result<Self, String> {
+        match s.to_lowercase().as_str() {
+            "read" => Ok(FileOperation::Read),
+            "write" => Ok(FileOperation::Write),
+            "append" => Ok(FileOperation::Append),
+            _ => Err(format!("Invalid operation: {}", s)),
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            FileOperation::Read => "read",
+            FileOperation::Write => "write",
+            FileOperation::Append => "append",
+        }
+    }
+
+    /// Encoded into the action vector of the logged experience event
+    fn code(&self) -> f32 {
+        match self {
+            FileOperation::Read => 0.0,
+            FileOperation::Write => 1.0,
+            FileOperation::Append => 2.0,
+        }
+    }
+}
+
+/// FileExecutor configuration
+#[derive(Debug, Clone)]
+pub struct FileExecutorConfig {
+    /// Sandbox root: every path is resolved relative to this directory
+    pub sandbox_root: PathBuf,
+
+    /// Maximum file size returned by a read (bytes)
+    pub max_read_bytes: u64,
+
+    /// Allow write/append operations
+    pub allow_write: bool,
+}
+
+impl Default for FileExecutorConfig {
+    fn default() -> Self {
+        Self {
+            sandbox_root: PathBuf::from("./sandbox"),
+            max_read_bytes: 1024 * 1024, // 1 MB
+            allow_write: true,
+        }
+    }
+}
+
+/// Sandboxed file I/O executor
+///
+/// # Parameters (JSON)
+///
+/// ```json
+/// {
+///   "operation": "append",      // "read", "write" or "append"
+///   "path": "notes/today.txt",  // relative to the sandbox root
+///   "content": "remember this"  // required for write/append
+/// }
+/// ```
+pub struct FileExecutor {
+    config: FileExecutorConfig,
+    guardian: Arc<Guardian>,
+    experience_writer: Arc<dyn ExperienceWriter>,
+}
+
+impl FileExecutor {
+    /// Create new FileExecutor
+    ///
+    /// The sandbox directory is created if it does not exist yet.
+    pub fn new(
+        config: FileExecutorConfig,
+        guardian: Arc<Guardian>,
+        experience_writer: Arc<dyn ExperienceWriter>,
+    ) -> std::io::Result<Self> {
+        fs::create_dir_all(&config.sandbox_root)?;
+        Ok(Self {
+            config,
+            guardian,
+            experience_writer,
+        })
+    }
+
+    /// Sandbox root directory
+    pub fn sandbox_root(&self) -> &Path {
+        &self.config.sandbox_root
+    }
+
+    /// Resolve relative path inside the sandbox
+    ///
+    /// Guardian already rejected `..` and absolute paths. Every component is
+    /// checked with `symlink_metadata` before anything is created, so a
+    /// symlink anywhere on the path (including a dangling one as the target)
+    /// is rejected instead of followed. Paths that cannot be canonicalized
+    /// are refused rather than trusted.
+    fn resolve(&self, relative: &Path, create_parents: bool) -> Result<PathBuf, String> {
+        let root = self.config.sandbox_root
+            .canonicalize()
+            .map_err(|e| format!("Sandbox unavailable: {}", e))?;
+
+        let components: Vec<_> = relative.components().collect();
+        if components.is_empty() {
+            return Err("Invalid file path".to_string());
+        }
+
+        let mut current = root.clone();
+        for (i, component) in components.iter().enumerate() {
+            let name = match component {
+                Component::Normal(name) => name,
+                _ => return Err("Invalid file path".to_string()),
+            };
+            current.push(name);
+            let is_last = i + 1 == components.len();
+
+            match fs::symlink_metadata(&current) {
+                Ok(meta) if meta.file_type().is_symlink() => {
+                    return Err("Symlinks are not allowed in the sandbox".to_string());
+                }
+                Ok(meta) if !is_last && !meta.is_dir() => {
+                    return Err("Invalid file path".to_string());
+                }
+                Ok(_) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    if !is_last {
+                        if !create_parents {
+                            return Err(format!("Failed to resolve path: {}", e));
+                        }
+                        fs::create_dir(&current)
+                            .map_err(|e| format!("Failed to create directory: {}", e))?;
+                    }
+                }
+                Err(e) => return Err(format!("Failed to resolve path: {}", e)),
+            }
+        }
+
+        // Fail closed: the parent must resolve to a directory under the root
+        let parent = current.parent().ok_or_else(|| "Invalid file path".to_string())?;
+        let resolved_parent = parent
+            .canonicalize()
+            .map_err(|e| format!("Failed to resolve path: {}", e))?;
+        if !resolved_parent.starts_with(&root) {
+            return Err("Path escapes the sandbox".to_string());
+        }
+
+        let file_name = current.file_name().ok_or_else(|| "Invalid file path".to_string())?;
+        Ok(resolved_parent.join(file_name))
+    }
+
+    fn perform(&self, op: FileOperation, relative: &Path, content: Option<&str>) -> Result<Value, String> {
+        match op {
+            FileOperation::Read => {
+                let path = self.resolve(relative, false)?;
+                let metadata = fs::metadata(&path).map_err(|e| format!("Failed to read file: {}", e))?;
+                if metadata.len() > self.config.max_read_bytes {
+                    return Err(format!(
+                        "File too large: {} bytes (max {})",
+                        metadata.len(),
+                        self.config.max_read_bytes
+                    ));
+                }
+
+                let text = fs::read_to_string(&path).map_err(|e| format!("Failed to read file: {}", e))?;
+                Ok(json!({
+                    "operation": op.as_str(),
+                    "path": relative.to_string_lossy(),
+                    "bytes": text.len(),
+                    "content": text,
+                }))
+            }
+            FileOperation::Write | FileOperation::Append => {
+                let content = content.ok_or_else(|| "Missing 'content' parameter".to_string())?;
+                let path = self.resolve(relative, true)?;
+
+                // A new file is created exclusively, so a symlink planted after
+                // resolve() makes the open fail instead of being followed
+                let exists = fs::symlink_metadata(&path).is_ok();
+                let mut file = OpenOptions::new()
+                    .create_new(!exists)
+                    .write(true)
+                    .append(op == FileOperation::Append)
+                    .truncate(op == FileOperation::Write)
+                    .open(&path)
+                    .map_err(|e| format!("Failed to open file: {}", e))?;
+                file.write_all(content.as_bytes())
+                    .map_err(|e| format!("Failed to write file: {}", e))?;
+
+                Ok(json!({
+                    "operation": op.as_str(),
+                    "path": relative.to_string_lossy(),
+                    "bytes": content.len(),
+                }))
+            }
+        }
+    }
+
+    /// Log file operation to ExperienceStream
+    ///
+    /// action[0] = operation code, action[1] = bytes (KB); state[7] = ±1 for success/failure.
+    fn log_operation(&self, op: FileOperation, bytes: usize, success: bool) {
+        let mut event = ExperienceEvent {
+            event_type: if success {
+                EventType::ActionCompleted as u16
+            } else {
+                EventType::ActionFailed as u16
+            },
+            ..Default::default()
+        };
+        event.action[0] = op.code();
+        event.action[1] = bytes as f32 / 1024.0;
+        event.state[7] = if success { 1.0 } else { -1.0 };
+
+        let _ = self.experience_writer.write_event(event);
+    }
+}
+
+#[async_trait]
+impl ActionExecutor for FileExecutor {
+    fn id(&self) -> &str {
+        "file_executor"
+    }
+
+    fn description(&self) -> &str {
+        "Sandboxed file read/write/append"
+    }
+
+    async fn execute(&self, params: Value, cancel: CancellationToken) -> ActionResult {
+        let start = Instant::now();
+
+        if cancel.is_cancelled() {
+            return ActionResult::failure(
+                "Cancelled".to_string(),
+                start.elapsed().as_millis() as u64,
+            );
+        }
+
+        if let Err(e) = self.validate_params(&params) {
+            return ActionResult::failure(e, start.elapsed().as_millis() as u64);
+        }
+
+        // validate_params guarantees both fields are present
+        let op = FileOperation::parse(params["operation"].as_str().unwrap_or_default())
+            .unwrap_or(FileOperation::Read);
+        let relative = PathBuf::from(params["path"].as_str().unwrap_or_default());
+        let content = params.get("content").and_then(|v| v.as_str());
+
+        if op != FileOperation::Read && !self.config.allow_write {
+            self.log_operation(op, 0, false);
+            return ActionResult::failure(
+                "Write operations are disabled".to_string(),
+                start.elapsed().as_millis() as u64,
+            );
+        }
+
+        // Guardian validation
+        let write_len = match op {
+            FileOperation::Read => None,
+            _ => Some(content.map(|c| c.len()).unwrap_or(0)),
+        };
+        if let Err(e) = self.guardian.validate_file_operation(&relative, write_len) {
+            self.log_operation(op, 0, false);
+            return ActionResult::failure(
+                format!("Guardian rejected file operation: {}", e),
+                start.elapsed().as_millis() as u64,
+            );
+        }
+
+        let duration_ms = || start.elapsed().as_millis() as u64;
+        match self.perform(op, &relative, content) {
+            Ok(output) => {
+                let bytes = output["bytes"].as_u64().unwrap_or(0) as usize;
+                self.log_operation(op, bytes, true);
+                ActionResult::success(output, duration_ms())
+            }
+            Err(e) => {
+                self.log_operation(op, 0, false);
+                ActionResult::failure(e, duration_ms())
+            }
+        }
+    }
+
+    fn validate_params(&self, params: &Value) -> Result<(), String> {
+        let op = match params.get("operation") {
+            Some(Value::String(s)) => FileOperation::parse(s)?,
+            _ => return Err("Missing required parameter: operation".to_string()),
+        };
+
+        match params.get("path") {
+            Some(Value::String(_)) => {}
+            _ => return Err("Missing required parameter: path".to_string()),
+        }
+
+        if op != FileOperation::Read {
+            match params.get("content") {
+                Some(Value::String(_)) => {}
+                _ => return Err("write/append require 'content' string".to_string()),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::experience_stream::ExperienceStream;
+
+    fn executor(root: &Path) -> (FileExecutor, Arc<ExperienceStream>) {
+        let stream = Arc::new(ExperienceStream::new(100, 10));
+        let config = FileExecutorConfig {
+            sandbox_root: root.to_path_buf(),
+            ..Default::default()
+        };
+        let executor = FileExecutor::new(
+            config,
+            Arc::new(Guardian::new()),
+            Arc::clone(&stream) as Arc<dyn ExperienceWriter>,
+        )
+        .unwrap();
+        (executor, stream)
+    }
+
+    #[tokio::test]
+    async fn test_file_executor_write_append_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let (executor, stream) = executor(dir.path());
+
+        let result = executor
+            .execute(json!({"operation": "write", "path": "notes/a.txt", "content": "hello"}), CancellationToken::new())
+            .await;
+        assert!(result.success, "{:?}", result.error);
+
+        let result = executor
+            .execute(json!({"operation": "append", "path": "notes/a.txt", "content": " world"}), CancellationToken::new())
+            .await;
+        assert!(result.success);
+
+        let result = executor
+            .execute(json!({"operation": "read", "path": "notes/a.txt"}), CancellationToken::new())
+            .await;
+        assert!(result.success);
+        assert_eq!(result.output["content"], "hello world");
+
+        // Every operation is logged
+        assert_eq!(stream.total_written(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_file_executor_sandbox_escape() {
+        let dir = tempfile::tempdir().unwrap();
+        let (executor, stream) = executor(dir.path());
+
+        let result = executor
+            .execute(json!({"operation": "read", "path": "../etc/passwd"}), CancellationToken::new())
+            .await;
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Guardian"));
+
+        let result = executor
+            .execute(json!({"operation": "write", "path": "/tmp/x", "content": "x"}), CancellationToken::new())
+            .await;
+        assert!(!result.success);
+
+        // Rejections are logged as failures
+        assert_eq!(stream.total_written(), 2);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_file_executor_symlinked_dir_cannot_escape() {
+        let dir = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        std::os::unix::fs::symlink(outside.path(), dir.path().join("link")).unwrap();
+        let (executor, _) = executor(dir.path());
+
+        let result = executor
+            .execute(json!({"operation": "write", "path": "link/sub/a.txt", "content": "x"}), CancellationToken::new())
+            .await;
+        assert!(!result.success);
+        // Nothing was created outside the sandbox, not even the parent directory
+        assert!(!outside.path().join("sub").exists());
+
+        let result = executor
+            .execute(json!({"operation": "write", "path": "link/a.txt", "content": "x"}), CancellationToken::new())
+            .await;
+        assert!(!result.success);
+        assert!(!outside.path().join("a.txt").exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_file_executor_dangling_symlink_cannot_escape() {
+        let dir = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        let target = outside.path().join("target.txt");
+        std::os::unix::fs::symlink(&target, dir.path().join("note.txt")).unwrap();
+        let (executor, _) = executor(dir.path());
+
+        let result = executor
+            .execute(json!({"operation": "write", "path": "note.txt", "content": "x"}), CancellationToken::new())
+            .await;
+        assert!(!result.success);
+        assert!(!target.exists());
+
+        let result = executor
+            .execute(json!({"operation": "append", "path": "note.txt", "content": "x"}), CancellationToken::new())
+            .await;
+        assert!(!result.success);
+        assert!(!target.exists());
+    }
+
+    #[tokio::test]
+    async fn test_file_executor_read_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let stream = Arc::new(ExperienceStream::new(100, 10));
+        let config = FileExecutorConfig {
+            sandbox_root: dir.path().to_path_buf(),
+            allow_write: false,
+            ..Default::default()
+        };
+        let executor = FileExecutor::new(config, Arc::new(Guardian::new()), stream).unwrap();
+
+        let result = executor
+            .execute(json!({"operation": "write", "path": "a.txt", "content": "x"}), CancellationToken::new())
+            .await;
+        assert!(!result.success);
+        assert!(!dir.path().join("a.txt").exists());
+    }
+
+    #[test]
+    fn test_file_executor_validate() {
+        let dir = tempfile::tempdir().unwrap();
+        let (executor, _) = executor(dir.path());
+
+        assert!(executor.validate_params(&json!({"operation": "read", "path": "a.txt"})).is_ok());
+        assert!(executor.validate_params(&json!({"operation": "write", "path": "a.txt"})).is_err());
+        assert!(executor.validate_params(&json!({"operation": "delete", "path": "a.txt"})).is_err());
+        assert!(executor.validate_params(&json!({"path": "a.txt"})).is_err());
+    }
+}
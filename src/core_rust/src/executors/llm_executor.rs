@@ -0,0 +1,331 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! LlmExecutor - fallback answering via an OpenAI-compatible server (feature `llm`)
+//!
+//! ActionController routes signals here when Gateway interpretation
+//! confidence is below `fallback_confidence_threshold` (see
+//! `ActionControllerConfig::fallback_executor`). Every answer is recorded
+//! in ExperienceStream as an `ExternalAnswer` event with the prompt and
+//! answer attached as metadata, so it can be learned from later.
+
+use crate::action_executor::{ActionExecutor, ActionResult, CancellationToken};
+use crate::experience_stream::{ActionMetadata, EventType, ExperienceEvent, ExperienceWriter};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// LlmExecutor configuration
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct LlmConfig {
+    /// Base URL of an OpenAI-compatible API (e.g. "http://localhost:11434/v1")
+    pub base_url: String,
+
+    /// Model name sent with every request
+    pub model: String,
+
+    /// Bearer token (optional for local servers)
+    #[serde(default)]
+    pub api_key: Option<String>,
+
+    /// System prompt prepended to every request
+    #[serde(default)]
+    pub system_prompt: Option<String>,
+
+    /// Maximum tokens in the answer
+    pub max_tokens: u32,
+
+    /// Sampling temperature
+    pub temperature: f32,
+
+    /// HTTP request timeout in milliseconds
+    pub timeout_ms: u64,
+}
+
+impl Default for LlmConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "http://localhost:11434/v1".to_string(),
+            model: "llama3".to_string(),
+            api_key: None,
+            system_prompt: None,
+            max_tokens: 256,
+            temperature: 0.7,
+            timeout_ms: 20000,
+        }
+    }
+}
+
+impl LlmConfig {
+    /// Validate configuration
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.base_url.starts_with("http://") && !self.base_url.starts_with("https://") {
+            return Err("base_url must start with http:// or https://".to_string());
+        }
+        if self.model.is_empty() {
+            return Err("model must not be empty".to_string());
+        }
+        if !(0.0..=2.0).contains(&self.temperature) {
+            return Err("temperature must be in [0.0, 2.0]".to_string());
+        }
+        Ok(())
+    }
+
+    fn completions_url(&self) -> String {
+        format!("{}/chat/completions", self.base_url.trim_end_matches('/'))
+    }
+}
+
+/// Fallback executor backed by an external LLM
+///
+/// # Parameters (JSON)
+///
+/// Either an explicit prompt, or the Intent context built by
+/// `ActionController::process_signal` (uses `metadata.original_text`):
+///
+/// ```json
+/// {
+///   "prompt": "What is a neuron?",   // optional, overrides metadata
+///   "state": [0.0, ...]              // optional 8D state, stored with the experience
+/// }
+/// ```
+pub struct LlmExecutor {
+    config: LlmConfig,
+    client: reqwest::Client,
+    experience_writer: Arc<dyn ExperienceWriter>,
+}
+
+impl LlmExecutor {
+    pub fn new(config: LlmConfig, experience_writer: Arc<dyn ExperienceWriter>) -> Result<Self, String> {
+        config.validate()?;
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .build()
+            .map_err(|e| format!("Failed to build HTTP client: {}", e))?;
+
+        Ok(Self {
+            config,
+            client,
+            experience_writer,
+        })
+    }
+
+    /// Extract prompt from explicit `prompt` or Gateway metadata
+    fn get_prompt(params: &Value) -> Option<String> {
+        params
+            .get("prompt")
+            .and_then(|v| v.as_str())
+            .or_else(|| params.pointer("/metadata/original_text").and_then(|v| v.as_str()))
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    }
+
+    fn get_state(params: &Value) -> [f32; 8] {
+        params
+            .get("state")
+            .and_then(|v| serde_json::from_value(v.clone()).ok())
+            .unwrap_or([0.0; 8])
+    }
+
+    /// Build chat completion request body
+    fn request_body(&self, prompt: &str) -> Value {
+        let mut messages = Vec::new();
+        if let Some(system) = &self.config.system_prompt {
+            messages.push(json!({ "role": "system", "content": system }));
+        }
+        messages.push(json!({ "role": "user", "content": prompt }));
+
+        json!({
+            "model": self.config.model,
+            "messages": messages,
+            "max_tokens": self.config.max_tokens,
+            "temperature": self.config.temperature,
+            "stream": false,
+        })
+    }
+
+    /// Extract answer text from chat completion response
+    fn parse_answer(response: &Value) -> Result<String, String> {
+        response
+            .pointer("/choices/0/message/content")
+            .and_then(|v| v.as_str())
+            .map(|s| s.trim().to_string())
+            .ok_or_else(|| "LLM response has no choices[0].message.content".to_string())
+    }
+
+    async fn complete(&self, prompt: &str) -> Result<String, String> {
+        let mut request = self.client
+            .post(self.config.completions_url())
+            .json(&self.request_body(prompt));
+        if let Some(key) = &self.config.api_key {
+            request = request.bearer_auth(key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("LLM request failed: {}", e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("LLM server returned {}", status));
+        }
+
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Invalid LLM response: {}", e))?;
+        Self::parse_answer(&body)
+    }
+
+    /// Record answer as an experience event for later learning
+    fn record_answer(&self, state: [f32; 8], prompt: &str, answer: &str) {
+        let event = ExperienceEvent {
+            event_id: uuid::Uuid::new_v4().as_u128(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_micros() as u64,
+            event_type: EventType::ExternalAnswer as u16,
+            state,
+            ..Default::default()
+        };
+
+        let metadata = ActionMetadata {
+            intent_type: "llm_fallback".to_string(),
+            executor_id: self.id().to_string(),
+            parameters: json!({
+                "model": self.config.model,
+                "prompt": prompt,
+                "answer": answer,
+            }),
+        };
+
+        let _ = self.experience_writer.write_event_with_metadata(event, metadata);
+    }
+}
+
+#[async_trait]
+impl ActionExecutor for LlmExecutor {
+    fn id(&self) -> &str {
+        "llm_executor"
+    }
+
+    fn description(&self) -> &str {
+        "Fallback answering via OpenAI-compatible LLM"
+    }
+
+    async fn execute(&self, params: Value, cancel: CancellationToken) -> ActionResult {
+        let start = Instant::now();
+
+        let prompt = match Self::get_prompt(&params) {
+            Some(p) => p,
+            None => {
+                return ActionResult::failure(
+                    "Missing 'prompt' parameter".to_string(),
+                    start.elapsed().as_millis() as u64,
+                );
+            }
+        };
+
+        // HTTP call is the long part: abort it on cancellation
+        let answer = tokio::select! {
+            answer = self.complete(&prompt) => answer,
+            _ = cancel.cancelled() => Err("Cancelled".to_string()),
+        };
+
+        let duration_ms = start.elapsed().as_millis() as u64;
+        match answer {
+            Ok(answer) => {
+                self.record_answer(Self::get_state(&params), &prompt, &answer);
+                ActionResult::success(
+                    json!({
+                        "response": answer,
+                        "decision_source": "llm",
+                        "model": self.config.model,
+                    }),
+                    duration_ms,
+                )
+            }
+            Err(e) => ActionResult::failure(e, duration_ms),
+        }
+    }
+
+    fn validate_params(&self, params: &Value) -> Result<(), String> {
+        if Self::get_prompt(params).is_none() {
+            return Err("Missing prompt: provide 'prompt' or 'metadata.original_text'".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::experience_stream::ExperienceStream;
+
+    fn executor() -> (LlmExecutor, Arc<ExperienceStream>) {
+        let stream = Arc::new(ExperienceStream::new(100, 10));
+        let config = LlmConfig {
+            system_prompt: Some("Be brief".to_string()),
+            ..Default::default()
+        };
+        let executor = LlmExecutor::new(config, Arc::clone(&stream) as Arc<dyn ExperienceWriter>).unwrap();
+        (executor, stream)
+    }
+
+    #[test]
+    fn test_llm_config_validate() {
+        assert!(LlmConfig::default().validate().is_ok());
+
+        let mut config = LlmConfig::default();
+        config.base_url = "localhost".to_string();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_llm_prompt_extraction() {
+        let (executor, _) = executor();
+
+        assert!(executor.validate_params(&json!({"prompt": "hi"})).is_ok());
+        assert!(executor
+            .validate_params(&json!({"metadata": {"original_text": "from gateway"}}))
+            .is_ok());
+        assert!(executor.validate_params(&json!({"metadata": {}})).is_err());
+
+        let body = executor.request_body("hi");
+        assert_eq!(body["messages"][0]["role"], "system");
+        assert_eq!(body["messages"][1]["content"], "hi");
+    }
+
+    #[test]
+    fn test_llm_parse_answer() {
+        let response = json!({"choices": [{"message": {"role": "assistant", "content": " 42 "}}]});
+        assert_eq!(LlmExecutor::parse_answer(&response).unwrap(), "42");
+        assert!(LlmExecutor::parse_answer(&json!({"choices": []})).is_err());
+    }
+
+    #[test]
+    fn test_llm_answer_recorded() {
+        let (executor, stream) = executor();
+        executor.record_answer([0.5; 8], "q", "a");
+
+        let (event, metadata) = stream.get_event_with_metadata(0).unwrap();
+        assert_eq!(event.event_type, EventType::ExternalAnswer as u16);
+        assert_eq!(metadata.unwrap().parameters["answer"], "a");
+    }
+}
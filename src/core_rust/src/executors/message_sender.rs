@@ -16,7 +16,7 @@
 
 //! MessageSenderExecutor - sends log messages
 
-use crate::action_executor::{ActionExecutor, ActionResult};
+use crate::action_executor::{ActionExecutor, ActionResult, CancellationToken};
 use async_trait::async_trait;
 use serde_json::Value;
 use std::time::Instant;
@@ -57,9 +57,16 @@ impl ActionExecutor for MessageSenderExecutor {
         "Sends log messages with configurable priority"
     }
 
-    async fn execute(&self, params: Value) -> ActionResult {
+    async fn execute(&self, params: Value, cancel: CancellationToken) -> ActionResult {
         let start = Instant::now();
 
+        if cancel.is_cancelled() {
+            return ActionResult::failure(
+                "Cancelled".to_string(),
+                start.elapsed().as_millis() as u64
+            );
+        }
+
         let message = match Self::get_message(&params) {
             Some(msg) => msg,
             None => {
@@ -127,7 +134,7 @@ mod tests {
             "priority": "info"
         });
 
-        let result = executor.execute(params).await;
+        let result = executor.execute(params, CancellationToken::new()).await;
         assert!(result.success);
         assert!(result.error.is_none());
 
@@ -144,7 +151,7 @@ mod tests {
             "priority": "info"
         });
 
-        let result = executor.execute(params).await;
+        let result = executor.execute(params, CancellationToken::new()).await;
         assert!(!result.success);
         assert!(result.error.is_some());
         assert!(result.error.unwrap().contains("Missing 'message'"));
@@ -158,7 +165,7 @@ mod tests {
             "message": "Test message"
         });
 
-        let result = executor.execute(params).await;
+        let result = executor.execute(params, CancellationToken::new()).await;
         assert!(result.success);
 
         let output = result.output.as_object().unwrap();
@@ -22,7 +22,13 @@
 mod noop;
 mod message_sender;
 mod signal_executor;
+mod file_executor;
+#[cfg(feature = "llm")]
+mod llm_executor;
 
 pub use noop::NoOpExecutor;
 pub use message_sender::MessageSenderExecutor;
-pub use signal_executor::SignalExecutor;
\ No newline at end of file
+pub use signal_executor::SignalExecutor;
+pub use file_executor::{FileExecutor, FileExecutorConfig, FileOperation};
+#[cfg(feature = "llm")]
+pub use llm_executor::{LlmExecutor, LlmConfig};
@@ -16,7 +16,7 @@
 
 //! NoOpExecutor - does nothing, useful for testing
 
-use crate::action_executor::{ActionExecutor, ActionResult};
+use crate::action_executor::{ActionExecutor, ActionResult, CancellationToken};
 use async_trait::async_trait;
 use serde_json::Value;
 use std::time::Instant;
@@ -42,11 +42,19 @@ impl ActionExecutor for NoOpExecutor {
         "No-operation executor (does nothing)"
     }
 
-    async fn execute(&self, _params: Value) -> ActionResult {
+    async fn execute(&self, _params: Value, cancel: CancellationToken) -> ActionResult {
         let start = Instant::now();
 
         // Simulate tiny work
-        tokio::time::sleep(tokio::time::Duration::from_millis(1)).await;
+        tokio::select! {
+            _ = tokio::time::sleep(tokio::time::Duration::from_millis(1)) => {}
+            _ = cancel.cancelled() => {
+                return ActionResult::failure(
+                    "Cancelled".to_string(),
+                    start.elapsed().as_millis() as u64
+                );
+            }
+        }
 
         let duration_ms = start.elapsed().as_millis() as u64;
 
@@ -76,12 +84,23 @@ mod tests {
         assert_eq!(executor.id(), "noop");
         assert!(executor.description().contains("No-operation"));
 
-        let result = executor.execute(serde_json::json!({})).await;
+        let result = executor.execute(serde_json::json!({}), CancellationToken::new()).await;
         assert!(result.success);
         assert!(result.duration_ms >= 1);
         assert!(result.error.is_none());
     }
 
+    #[tokio::test]
+    async fn test_noop_executor_cancelled() {
+        let executor = NoOpExecutor::new();
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+
+        let result = executor.execute(serde_json::json!({}), cancel).await;
+        assert!(!result.success);
+        assert_eq!(result.error, Some("Cancelled".to_string()));
+    }
+
     #[test]
     fn test_noop_validate() {
         let executor = NoOpExecutor::new();
@@ -19,8 +19,9 @@
 //! Executes neural signal propagation using spreading activation algorithm.
 //! Part of SignalSystem v1.0.
 
-use crate::action_executor::{ActionExecutor, ActionResult};
+use crate::action_executor::{ActionExecutor, ActionResult, CancellationToken};
 use crate::graph::{Graph, SignalConfig};
+use crate::profiling::{PipelineStage, PROFILER};
 use async_trait::async_trait;
 use serde_json::{json, Value};
 use std::sync::{Arc, RwLock};
@@ -73,7 +74,7 @@ impl ActionExecutor for SignalExecutor {
         "Spreading activation executor (SignalSystem v1.0)"
     }
 
-    async fn execute(&self, params: Value) -> ActionResult {
+    async fn execute(&self, params: Value, cancel: CancellationToken) -> ActionResult {
         let start = Instant::now();
 
         // Extract required parameters
@@ -97,6 +98,7 @@ impl ActionExecutor for SignalExecutor {
             || params.get("max_depth").is_some()
             || params.get("min_energy").is_some()
             || params.get("accumulation_mode").is_some()
+            || params.get("layer_mask").is_some()
         {
             let mut config = SignalConfig::default();
 
@@ -112,6 +114,18 @@ impl ActionExecutor for SignalExecutor {
                 config.min_energy = n.as_f64().unwrap_or(0.01) as f32;
             }
 
+            if let Some(Value::Number(n)) = params.get("layer_mask") {
+                match n.as_u64().and_then(|m| u8::try_from(m).ok()) {
+                    Some(mask) => config.layer_mask = mask,
+                    None => {
+                        return ActionResult::failure(
+                            "'layer_mask' must be an 8-bit L1-L8 bitmask".to_string(),
+                            start.elapsed().as_millis() as u64,
+                        );
+                    }
+                }
+            }
+
             if let Some(Value::String(s)) = params.get("accumulation_mode") {
                 match Self::parse_accumulation_mode(s) {
                     Ok(mode) => config.accumulation_mode = mode,
@@ -129,11 +143,19 @@ impl ActionExecutor for SignalExecutor {
             None
         };
 
+        // Last cooperative cancellation point before taking the graph lock
+        if cancel.is_cancelled() {
+            return ActionResult::failure(
+                "Cancelled".to_string(),
+                start.elapsed().as_millis() as u64,
+            );
+        }
+
         // Execute spreading activation
-        let result = {
+        let result = PROFILER.time(PipelineStage::Activate, || {
             let mut graph = self.graph.write().unwrap();
             graph.spreading_activation(source_id, initial_energy, custom_config)
-        };
+        });
 
         // Format output
         let activated_count = result.activated_nodes.len();
@@ -241,7 +263,7 @@ mod tests {
             "initial_energy": 1.0,
         });
 
-        let result = executor.execute(params).await;
+        let result = executor.execute(params, CancellationToken::new()).await;
 
         assert!(result.success);
         assert!(result.error.is_none());
@@ -273,7 +295,7 @@ mod tests {
             "accumulation_mode": "max",
         });
 
-        let result = executor.execute(params).await;
+        let result = executor.execute(params, CancellationToken::new()).await;
 
         assert!(result.success);
     }
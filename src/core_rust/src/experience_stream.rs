@@ -28,10 +28,13 @@
 //! - Optional cold storage for long-term persistence
 
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::collections::HashMap;
 use parking_lot::RwLock;
 use tokio::sync::broadcast;
 use serde_json::Value;
+use crate::correlation::CorrelationId;
+use crate::event_payload::EventPayload;
 
 /// ExperienceEvent - unified structure for all events (128 bytes)
 #[repr(C, align(16))]
@@ -90,6 +93,60 @@ impl ExperienceEvent {
     pub fn is_fully_appraised(&self) -> bool {
         self.flags & EventFlags::FULLY_APPRAISED != 0
     }
+
+    /// Trust of the signal source that caused the event [0.0, 1.0]
+    ///
+    /// Stored in 4 bits of `flags` (`EventFlags::TRUST_MASK`); 0 means
+    /// unset and reads as full trust.
+    pub fn trust(&self) -> f32 {
+        match (self.flags & EventFlags::TRUST_MASK) >> EventFlags::TRUST_SHIFT {
+            0 => 1.0,
+            level => (level - 1) as f32 / 14.0,
+        }
+    }
+
+    /// Store the source trust, quantized to 15 levels
+    pub fn set_trust(&mut self, trust: f32) {
+        let level = (trust.clamp(0.0, 1.0) * 14.0).round() as u16 + 1;
+        self.flags = (self.flags & !EventFlags::TRUST_MASK) | (level << EventFlags::TRUST_SHIFT);
+    }
+
+    /// Serialize to bytes (128 bytes)
+    pub fn to_bytes(&self) -> [u8; 128] {
+        unsafe { std::mem::transmute(*self) }
+    }
+
+    /// Deserialize from bytes (128 bytes); see `validate_bytes`
+    pub fn from_bytes(bytes: &[u8; 128]) -> Self {
+        unsafe { std::mem::transmute(*bytes) }
+    }
+}
+
+/// Decode and validate a serialized ExperienceEvent
+///
+/// Rejects input that is not exactly 128 bytes and non-finite state, action
+/// or reward values.
+pub fn validate_bytes(bytes: &[u8]) -> Result<ExperienceEvent, String> {
+    let bytes: &[u8; 128] = bytes
+        .try_into()
+        .map_err(|_| format!("ExperienceEvent requires 128 bytes, got {}", bytes.len()))?;
+    let event = ExperienceEvent::from_bytes(bytes);
+    let rewards = [
+        event.reward_homeostasis,
+        event.reward_curiosity,
+        event.reward_efficiency,
+        event.reward_goal,
+    ];
+    if event
+        .state
+        .iter()
+        .chain(&event.action)
+        .chain(&rewards)
+        .any(|v| !v.is_finite())
+    {
+        return Err("State, action and rewards must be finite".to_string());
+    }
+    Ok(event)
 }
 
 impl Default for ExperienceEvent {
@@ -135,6 +192,7 @@ pub enum EventType {
     ActionStarted = 0x0200,
     ActionCompleted = 0x0201,
     ActionFailed = 0x0202,
+    ExternalAnswer = 0x0210,
 
     // === Appraisal Events (0x03xx) ===
     HomeostasisReward = 0x0300,
@@ -167,6 +225,7 @@ impl From<u16> for EventType {
             0x0200 => EventType::ActionStarted,
             0x0201 => EventType::ActionCompleted,
             0x0202 => EventType::ActionFailed,
+            0x0210 => EventType::ExternalAnswer,
             0x0300 => EventType::HomeostasisReward,
             0x0301 => EventType::CuriosityReward,
             0x0302 => EventType::EfficiencyReward,
@@ -198,8 +257,15 @@ impl EventFlags {
     /// Event has been processed by all Appraisers
     pub const FULLY_APPRAISED: u16 = 0x0010;
 
+    /// Source trust level (see `ExperienceEvent::trust`)
+    pub const TRUST_MASK: u16 = 0x01E0;
+    pub const TRUST_SHIFT: u16 = 5;
+
+    /// Event has an `EventPayload` (see `ExperienceStream::get_payload`)
+    pub const HAS_PAYLOAD: u16 = 0x0200;
+
     /// Reserved flags
-    pub const _RESERVED: u16 = 0xFFE0;
+    pub const _RESERVED: u16 = 0xFC00;
 }
 
 /// Appraiser type for identifying which appraiser is updating rewards
@@ -225,18 +291,24 @@ pub struct HotBuffer {
 
     /// Total events written (never wraps)
     total_written: Arc<RwLock<u64>>,
+
+    /// ADNA generation that scored each slot (0 = not tracked), kept
+    /// outside the 128-byte events
+    generations: Box<[AtomicU32]>,
 }
 
 impl HotBuffer {
     /// Create new buffer with given capacity
     pub fn new(capacity: usize) -> Self {
         let events = vec![ExperienceEvent::default(); capacity].into_boxed_slice();
+        let generations = (0..capacity).map(|_| AtomicU32::new(0)).collect();
 
         Self {
             events,
             capacity,
             write_pos: Arc::new(RwLock::new(0)),
             total_written: Arc::new(RwLock::new(0)),
+            generations,
         }
     }
 
@@ -244,21 +316,29 @@ impl HotBuffer {
     ///
     /// Returns the global sequence number of the written event
     pub fn write(&self, event: ExperienceEvent) -> u64 {
+        self.write_replacing(event, 0).0
+    }
+
+    /// Write event scored by ADNA `generation` (0 = not tracked), returning
+    /// its sequence number and the event it overwrote once the buffer has wrapped
+    pub fn write_replacing(&self, event: ExperienceEvent, generation: u32) -> (u64, Option<ExperienceEvent>) {
         let mut write_pos = self.write_pos.write();
         let mut total = self.total_written.write();
 
         // Write to circular buffer
         let idx = *write_pos % self.capacity;
-        unsafe {
+        let replaced = unsafe {
             let ptr = self.events.as_ptr() as *mut ExperienceEvent;
-            ptr.add(idx).write(event);
-        }
+            let replaced = ptr.add(idx).replace(event);
+            (*total >= self.capacity as u64).then_some(replaced)
+        };
+        self.generations[idx].store(generation, Ordering::Relaxed);
 
         // Update counters
         *write_pos = (*write_pos + 1) % self.capacity;
         *total += 1;
 
-        *total // Return global sequence number
+        (*total, replaced) // Global sequence number
     }
 
     /// Read event by absolute sequence number
@@ -290,6 +370,11 @@ impl HotBuffer {
         std::cmp::min(total as usize, self.capacity)
     }
 
+    /// Number of events the buffer holds before overwriting
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
     /// Get total events written (including overwritten)
     pub fn total_written(&self) -> u64 {
         *self.total_written.read()
@@ -334,6 +419,34 @@ impl HotBuffer {
         Ok(())
     }
 
+    /// ADNA generation that scored an event (0 = not tracked)
+    ///
+    /// Returns None if the event has been overwritten or doesn't exist yet
+    pub fn generation(&self, seq: u64) -> Option<u32> {
+        let total = *self.total_written.read();
+        if seq + (self.capacity as u64) < total || seq >= total {
+            return None;
+        }
+        Some(self.generations[(seq as usize) % self.capacity].load(Ordering::Relaxed))
+    }
+
+    /// Record the ADNA generation that scored an event
+    pub fn set_generation(&self, seq: u64, generation: u32) -> Result<(), &'static str> {
+        let total = *self.total_written.read();
+
+        if seq + (self.capacity as u64) < total {
+            return Err("Event too old, already overwritten");
+        }
+        if seq >= total {
+            return Err("Event doesn't exist yet");
+        }
+
+        let idx = (seq as usize) % self.capacity;
+        self.generations[idx].store(generation, Ordering::Relaxed);
+
+        Ok(())
+    }
+
     /// Mark event as fully appraised (all 4 appraisers completed)
     pub fn mark_fully_appraised(&self, seq: u64) -> Result<(), &'static str> {
         let total = *self.total_written.read();
@@ -380,6 +493,20 @@ pub struct ExperienceStream {
     /// Metadata store for action events (event_id → metadata)
     /// Separate from hot buffer to maintain cache-friendly 128-byte events
     metadata: Arc<RwLock<HashMap<u128, ActionMetadata>>>,
+
+    /// Typed payloads (sequence number → payload), decoded via a PayloadRegistry
+    /// Entries are dropped when their event leaves the hot buffer
+    payloads: Arc<RwLock<HashMap<u64, EventPayload>>>,
+
+    /// Randomness for batch sampling
+    rng: Arc<crate::rng::SeededRng>,
+
+    /// ADNA generation stamped on new events (0 = not tracked)
+    adna_generation: Arc<AtomicU32>,
+
+    /// Interaction of events written inside a correlation scope (sequence number → ID)
+    /// Entries are dropped when their event leaves the hot buffer
+    correlations: Arc<RwLock<HashMap<u64, CorrelationId>>>,
 }
 
 impl ExperienceStream {
@@ -392,16 +519,55 @@ impl ExperienceStream {
         let buffer = Arc::new(HotBuffer::new(capacity));
         let (tx, _rx) = broadcast::channel(channel_size);
         let metadata = Arc::new(RwLock::new(HashMap::new()));
+        let payloads = Arc::new(RwLock::new(HashMap::new()));
+
+        let rng = Arc::new(crate::rng::SeededRng::from_entropy());
+
+        let adna_generation = Arc::new(AtomicU32::new(0));
+        let correlations = Arc::new(RwLock::new(HashMap::new()));
 
-        Self { buffer, tx, metadata }
+        Self { buffer, tx, metadata, payloads, rng, adna_generation, correlations }
+    }
+
+    /// Sample batches from a seeded stream (reproducible runs)
+    pub fn with_rng(mut self, rng: Arc<crate::rng::SeededRng>) -> Self {
+        self.rng = rng;
+        self
     }
 
     /// Write event to stream and broadcast to subscribers
     ///
     /// Returns the global sequence number of the written event
-    pub fn write_event(&self, mut event: ExperienceEvent) -> Result<u64, &'static str> {
-        // 1. Write to hot buffer
-        let seq = self.buffer.write(event);
+    pub fn write_event(&self, event: ExperienceEvent) -> Result<u64, &'static str> {
+        self.write_with_payload(event, None)
+    }
+
+    fn write_with_payload(
+        &self,
+        mut event: ExperienceEvent,
+        payload: Option<EventPayload>,
+    ) -> Result<u64, &'static str> {
+        // 1. Write to hot buffer with the ADNA generation that will appraise it.
+        //    Side tables are keyed by sequence number and filled under the
+        //    payload lock, so readers never see the event without its payload.
+        let generation = self.adna_generation.load(Ordering::Relaxed);
+        let seq = {
+            let mut payloads = self.payloads.write();
+            let (seq, _) = self.buffer.write_replacing(event, generation);
+            let index = seq - 1; // seq is 1-based
+            if let Some(expired) = index.checked_sub(self.buffer.capacity() as u64) {
+                self.forget(&mut payloads, expired);
+            }
+            if let Some(payload) = payload {
+                payloads.insert(index, payload);
+            }
+
+            // 1b. Remember which interaction caused the event
+            if let Some(correlation_id) = crate::correlation::current() {
+                self.correlations.write().insert(index, correlation_id);
+            }
+            seq
+        };
 
         // 2. Set sequence number for broadcast subscribers
         event.sequence_number = (seq - 1) as u32; // seq is 1-based, convert to 0-based u32
@@ -412,6 +578,12 @@ impl ExperienceStream {
         Ok(seq)
     }
 
+    /// Drop side-table entries of the event that left the hot buffer
+    fn forget(&self, payloads: &mut HashMap<u64, EventPayload>, seq: u64) {
+        self.correlations.write().remove(&seq);
+        payloads.remove(&seq);
+    }
+
     /// Get event by sequence number
     pub fn get_event(&self, seq: u64) -> Option<ExperienceEvent> {
         self.buffer.read(seq)
@@ -454,6 +626,32 @@ impl ExperienceStream {
         self.buffer.mark_fully_appraised(seq)
     }
 
+    /// ADNA generation that scored an event (0 = not tracked)
+    pub fn event_generation(&self, seq: u64) -> Option<u32> {
+        self.buffer.generation(seq)
+    }
+
+    /// Record the ADNA generation that scored an event
+    pub fn set_event_generation(&self, seq: u64, generation: u32) -> Result<(), &'static str> {
+        self.buffer.set_generation(seq, generation)
+    }
+
+    /// Stamp new events with an ADNA generation (see `event_generation`)
+    ///
+    /// The generation is kept next to the event; `adna_version_hash` is
+    /// left as the caller set it.
+    ///
+    /// Set by EvolutionManager when it adopts a new ADNA (with re-appraisal
+    /// enabled); 0 turns stamping off.
+    pub fn set_adna_generation(&self, generation: u32) {
+        self.adna_generation.store(generation, Ordering::Relaxed);
+    }
+
+    /// ADNA generation stamped on new events (0 = not tracked)
+    pub fn adna_generation(&self) -> u32 {
+        self.adna_generation.load(Ordering::Relaxed)
+    }
+
     /// Get reference to underlying buffer (for advanced use)
     pub fn buffer(&self) -> &Arc<HotBuffer> {
         &self.buffer
@@ -482,6 +680,42 @@ impl ExperienceStream {
         self.metadata.read().get(&event_id).cloned()
     }
 
+    /// Write event with a typed payload
+    ///
+    /// Sets `EventFlags::HAS_PAYLOAD` on the event. The payload is kept as
+    /// long as the event stays in the hot buffer.
+    pub fn write_event_with_payload(
+        &self,
+        mut event: ExperienceEvent,
+        payload: EventPayload,
+    ) -> Result<u64, &'static str> {
+        event.flags |= EventFlags::HAS_PAYLOAD;
+        self.write_with_payload(event, Some(payload))
+    }
+
+    /// Get the typed payload of the event at sequence number `seq` (as for `get_event`)
+    pub fn get_payload(&self, seq: u64) -> Option<EventPayload> {
+        self.payloads.read().get(&seq).cloned()
+    }
+
+    /// Interaction that caused the event at `seq` (events written inside a correlation scope)
+    pub fn correlation(&self, seq: u64) -> Option<CorrelationId> {
+        self.correlations.read().get(&seq).copied()
+    }
+
+    /// Sequence numbers of all live events caused by one interaction, oldest first
+    pub fn correlated_events(&self, correlation_id: CorrelationId) -> Vec<u64> {
+        let mut events: Vec<u64> = self
+            .correlations
+            .read()
+            .iter()
+            .filter(|(_, id)| **id == correlation_id)
+            .map(|(seq, _)| *seq)
+            .collect();
+        events.sort_unstable();
+        events
+    }
+
     /// Get event with its metadata by sequence number
     ///
     /// Returns (event, Option<metadata>) tuple.
@@ -514,6 +748,15 @@ pub trait ExperienceWriter: Send + Sync {
         self.write_event(event)
     }
 
+    /// Write event with a typed payload (default implementation drops the payload)
+    fn write_event_with_payload(
+        &self,
+        event: ExperienceEvent,
+        _payload: EventPayload,
+    ) -> Result<u64, &'static str> {
+        self.write_event(event)
+    }
+
     /// Write multiple events
     fn write_batch(&self, events: Vec<ExperienceEvent>) -> Result<Vec<u64>, &'static str> {
         events.into_iter().map(|e| self.write_event(e)).collect()
@@ -554,6 +797,22 @@ impl ExperienceWriter for ExperienceStream {
         self.write_event(event)
     }
 
+    fn write_event_with_metadata(
+        &self,
+        event: ExperienceEvent,
+        metadata: ActionMetadata,
+    ) -> Result<u64, &'static str> {
+        self.write_event_with_metadata(event, metadata)
+    }
+
+    fn write_event_with_payload(
+        &self,
+        event: ExperienceEvent,
+        payload: EventPayload,
+    ) -> Result<u64, &'static str> {
+        self.write_event_with_payload(event, payload)
+    }
+
     fn set_appraiser_reward(
         &self,
         seq: u64,
@@ -663,13 +922,13 @@ impl ExperienceStream {
         }
 
         let sample_size = std::cmp::min(size, all_events.len());
-        let mut rng = rand::thread_rng();
+        let mut rng = self.rng.lock();
 
         let sampled_events = match strategy {
             SamplingStrategy::Uniform => {
                 // Simple uniform random sampling
                 let mut events = all_events.clone();
-                events.shuffle(&mut rng);
+                events.shuffle(&mut *rng);
                 events.into_iter().take(sample_size).collect()
             }
 
@@ -694,7 +953,7 @@ impl ExperienceStream {
                 if total_priority == 0.0 {
                     // Fall back to uniform if all rewards are zero
                     let mut events = all_events.clone();
-                    events.shuffle(&mut rng);
+                    events.shuffle(&mut *rng);
                     events.into_iter().take(sample_size).collect()
                 } else {
                     // Sample proportional to priority
@@ -792,7 +1051,7 @@ impl ExperienceStream {
 
                 if total_weight == 0.0 {
                     let mut events = all_events.clone();
-                    events.shuffle(&mut rng);
+                    events.shuffle(&mut *rng);
                     events.into_iter().take(sample_size).collect()
                 } else {
                     let mut selected = Vec::with_capacity(sample_size);
@@ -857,6 +1116,83 @@ mod tests {
         assert_eq!(event.total_reward(), 4.0);
     }
 
+    #[test]
+    fn test_write_event_with_payload() {
+        let stream = ExperienceStream::new(10, 10);
+        let event = ExperienceEvent { event_id: 42, ..Default::default() };
+        let seq = stream.write_event_with_payload(event, EventPayload::new(3, vec![1, 2, 3])).unwrap();
+
+        let stored = stream.get_event(seq - 1).unwrap();
+        assert_ne!(stored.flags & EventFlags::HAS_PAYLOAD, 0);
+        assert_eq!(stream.get_payload(seq - 1).unwrap().bytes(), &[1, 2, 3]);
+        assert!(stream.get_payload(seq).is_none());
+    }
+
+    #[test]
+    fn test_payloads_bounded_by_buffer() {
+        let stream = ExperienceStream::new(4, 10);
+        for event_id in 1..=10 {
+            let event = ExperienceEvent { event_id, ..Default::default() };
+            stream.write_event_with_payload(event, EventPayload::new(1, vec![event_id as u8])).unwrap();
+        }
+
+        assert_eq!(stream.payloads.read().len(), 4);
+        assert!(stream.get_payload(5).is_none());
+        assert_eq!(stream.get_payload(6).unwrap().bytes(), &[7]);
+
+        // Overwriting an event with the same ID drops the old payload
+        let event = ExperienceEvent { event_id: 7, ..Default::default() };
+        let seq = stream.write_event_with_payload(event, EventPayload::new(1, vec![70])).unwrap();
+        assert!(stream.get_payload(6).is_none());
+        assert_eq!(stream.get_payload(seq - 1).unwrap().bytes(), &[70]);
+        assert_eq!(stream.payloads.read().len(), 4);
+    }
+
+    #[test]
+    fn test_payloads_of_unnamed_events() {
+        // Events without an ID (event_id 0) keep their own payloads
+        let stream = ExperienceStream::new(2, 10);
+        let write = |byte| stream.write_event_with_payload(ExperienceEvent::default(), EventPayload::new(1, vec![byte]));
+        let first = write(1).unwrap();
+        let second = write(2).unwrap();
+        assert_eq!(stream.get_payload(first - 1).unwrap().bytes(), &[1]);
+        assert_eq!(stream.get_payload(second - 1).unwrap().bytes(), &[2]);
+
+        // A plain event that overwrites one with a payload clears it
+        stream.write_event(ExperienceEvent::default()).unwrap();
+        assert!(stream.get_payload(first - 1).is_none());
+        assert_eq!(stream.payloads.read().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_write_event_records_correlation() {
+        let stream = ExperienceStream::new(10, 10);
+        let id = CorrelationId::generate();
+        let seq = crate::correlation::scope(id, async {
+            stream.write_event(ExperienceEvent::default()).unwrap();
+            stream.write_event(ExperienceEvent { event_id: 42, ..Default::default() }).unwrap()
+        }).await;
+        let outside = stream.write_event(ExperienceEvent { event_id: 43, ..Default::default() }).unwrap();
+
+        assert_eq!(stream.correlation(seq - 1), Some(id));
+        assert_eq!(stream.correlation(outside - 1), None);
+        assert_eq!(stream.correlated_events(id), vec![0, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_correlations_bounded_by_buffer() {
+        let stream = ExperienceStream::new(4, 10);
+        let id = CorrelationId::generate();
+        crate::correlation::scope(id, async {
+            for _ in 0..10 {
+                stream.write_event(ExperienceEvent::default()).unwrap();
+            }
+        }).await;
+
+        assert_eq!(stream.correlated_events(id), vec![6, 7, 8, 9]);
+        assert_eq!(stream.correlation(5), None);
+    }
+
     #[test]
     fn test_hot_buffer_write_read() {
         let buffer = HotBuffer::new(10);
@@ -1043,6 +1379,28 @@ mod tests {
         assert_eq!(batch.events.len(), 10);
     }
 
+    #[test]
+    fn test_sampling_seeded() {
+        let provider = crate::rng::RngProvider::new(7);
+        let sample = || {
+            let stream = ExperienceStream::new(1000, 100)
+                .with_rng(provider.stream(crate::rng::EXPERIENCE_STREAM));
+            for i in 0..50 {
+                let mut event = ExperienceEvent::default();
+                event.reward_homeostasis = i as f32;
+                stream.write_event(event).unwrap();
+            }
+            stream
+                .sample_batch(10, SamplingStrategy::Mixed { reward_weight: 0.5, recency_weight: 0.5 })
+                .events
+                .iter()
+                .map(|e| e.reward_homeostasis)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(sample(), sample());
+    }
+
     #[test]
     fn test_sampling_prioritized() {
         let stream = ExperienceStream::new(1000, 100);
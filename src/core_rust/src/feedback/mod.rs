@@ -10,6 +10,8 @@ use crate::{
     bootstrap::BootstrapLibrary,
     experience_stream::ExperienceStream,
     intuition_engine::IntuitionEngine,
+    learner::Learner,
+    safe_mode::SafeMode,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
@@ -105,6 +107,9 @@ pub enum FeedbackError {
 
     #[error("System error: {0}")]
     SystemError(String),
+
+    #[error("Learning is paused")]
+    LearningPaused,
 }
 
 /// Tracks corrections per signal
@@ -144,6 +149,12 @@ pub struct FeedbackProcessor {
 
     /// Track corrections per signal
     correction_tracker: Arc<RwLock<CorrectionTracker>>,
+
+    /// Learner credited with positive/negative feedback through its eligibility traces
+    learner: Option<Arc<Learner>>,
+
+    /// Feedback is rejected while this freezes learning
+    safe_mode: Arc<SafeMode>,
 }
 
 impl FeedbackProcessor {
@@ -158,15 +169,40 @@ impl FeedbackProcessor {
             experience_stream,
             intuition_engine,
             correction_tracker: Arc::new(RwLock::new(CorrectionTracker::new())),
+            learner: None,
+            safe_mode: Arc::default(),
         }
     }
 
+    /// Reject feedback while this switch freezes learning
+    pub fn with_safe_mode(mut self, safe_mode: Arc<SafeMode>) -> Self {
+        self.safe_mode = safe_mode;
+        self
+    }
+
+    /// Credit positive/negative feedback to the edges traced by `learner`
+    pub fn with_learner(mut self, learner: Arc<Learner>) -> Self {
+        self.learner = Some(learner);
+        self
+    }
+
+    /// Reward the Learner's recently traced edges; returns a change note
+    fn credit_learner(&self, reward: f32) -> Option<String> {
+        let learner = self.learner.as_ref()?;
+        let updated = learner.apply_reward(reward);
+        Some(format!("Credited reward {:+.2} to {} traced edges", reward, updated))
+    }
+
     /// Process feedback signal
     pub async fn process(&self, signal: FeedbackSignal) -> Result<FeedbackResult, FeedbackError> {
         let start = std::time::Instant::now();
         let mut changes = Vec::new();
         let mut errors = Vec::new();
 
+        if self.safe_mode.learning_frozen() {
+            return Err(FeedbackError::LearningPaused);
+        }
+
         // Validate feedback
         self.validate_feedback(&signal)?;
 
@@ -177,6 +213,7 @@ impl FeedbackProcessor {
                     Ok(change) => changes.push(change),
                     Err(e) => errors.push(e.to_string()),
                 }
+                changes.extend(self.credit_learner(*strength));
             }
 
             DetailedFeedbackType::Negative { strength } => {
@@ -184,6 +221,7 @@ impl FeedbackProcessor {
                     Ok(change) => changes.push(change),
                     Err(e) => errors.push(e.to_string()),
                 }
+                changes.extend(self.credit_learner(-*strength));
             }
 
             DetailedFeedbackType::Correction { correct_value } => {
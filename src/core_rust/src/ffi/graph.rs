@@ -36,6 +36,7 @@ impl PyGraphConfig {
             inner: GraphConfig {
                 deduplicate_edges,
                 initial_capacity,
+                ..Default::default()
             }
         }
     }
@@ -0,0 +1,250 @@
+//! Bridge between Gateway and SignalSystem
+//!
+//! Gateway and SignalSystem used to be two separate entry points: callers had
+//! to pick one. The bridge joins them in both directions:
+//!
+//! - **Gateway → SignalSystem**: every ProcessedSignal is published as a
+//!   SignalEvent (see `Gateway::set_signal_system`), so SignalSystem
+//!   subscribers see everything that enters through the Gateway.
+//! - **SignalSystem → Gateway**: `subscribe_injector` registers a subscriber
+//!   whose callback turns matching SignalEvents into InputSignals and injects
+//!   them into the Gateway.
+//!
+//! Events published by the Gateway carry `FLAG_FROM_GATEWAY`, and the injector
+//! never re-injects them, so the two directions can be wired together without
+//! feedback loops.
+
+use super::signals::{InputSignal, ProcessedSignal, SignalSource, SignalType};
+use super::Gateway;
+use crate::signal_system::{
+    EventTypeRegistry, SignalEvent, SignalSystem, SignalSystemError, Subscriber, SubscriberId,
+    SubscriptionFilter,
+};
+use std::sync::{Arc, Weak};
+
+/// Prefix for event types published by the Gateway
+pub const GATEWAY_EVENT_PREFIX: &str = "signal.gateway";
+
+/// SignalEvent flag bit marking events published by the Gateway
+pub const FLAG_FROM_GATEWAY: u8 = 0b0001_0000;
+
+/// SignalEvent flag bit 0: `trace_id_hash` holds the correlation ID
+pub const FLAG_HAS_TRACE_ID: u8 = 0b0000_0001;
+
+/// SignalEvent data_type for inline UTF-8 text
+const DATA_TYPE_TEXT: u8 = 1;
+
+/// Event type name for a processed signal type
+pub fn event_type_name(signal_type: SignalType) -> &'static str {
+    match signal_type {
+        SignalType::SemanticQuery => "signal.gateway.semantic_query",
+        SignalType::ActionRequest => "signal.gateway.action_request",
+        SignalType::FeedbackSignal => "signal.gateway.feedback",
+        SignalType::SystemSignal => "signal.gateway.system",
+        SignalType::CuriosityTrigger => "signal.gateway.curiosity",
+        SignalType::Unknown => "signal.gateway.unknown",
+    }
+}
+
+/// Convert a ProcessedSignal into a SignalEvent
+///
+/// The event type is registered on demand. The Gateway signal ID is kept in
+/// `event_id_low`, the correlation ID in `trace_id_hash`, and the original text (if any) is stored inline, truncated
+/// to 40 bytes on a character boundary; `data_size` holds the full length.
+pub fn to_signal_event(processed: &ProcessedSignal, registry: &mut EventTypeRegistry) -> SignalEvent {
+    let type_id = registry.register(event_type_name(processed.signal_type));
+    let mut event = SignalEvent::new(type_id, processed.state);
+
+    event.event_id_high = processed.received_at;
+    event.event_id_low = processed.signal_id;
+    event.timestamp_us = processed.processed_at.saturating_mul(1000);
+    event.confidence = (processed.interpretation_confidence.clamp(0.0, 1.0) * 255.0).round() as u8;
+    event.domain = match (processed.signal_type, processed.source) {
+        (SignalType::SystemSignal, _) => 2,
+        (_, SignalSource::InternalTimer | SignalSource::InternalCuriosity) => 1,
+        _ => 0,
+    };
+    event.flags |= FLAG_FROM_GATEWAY | FLAG_HAS_TRACE_ID;
+    event.trace_id_hash = processed.correlation_id.0;
+
+    if let Some(text) = &processed.metadata.original_text {
+        let mut end = text.len().min(event.inline_data.len());
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        event.inline_data[..end].copy_from_slice(&text.as_bytes()[..end]);
+        event.data_type = DATA_TYPE_TEXT;
+        event.data_location = 0;
+        event.data_size = text.len() as u32;
+    }
+
+    event
+}
+
+/// Inline text carried by an event, if any
+pub fn inline_text(event: &SignalEvent) -> Option<String> {
+    if event.data_type != DATA_TYPE_TEXT || event.data_location != 0 {
+        return None;
+    }
+    let len = (event.data_size as usize).min(event.inline_data.len());
+    let bytes = &event.inline_data[..len];
+    let valid = match std::str::from_utf8(bytes) {
+        Ok(s) => s,
+        Err(e) => std::str::from_utf8(&bytes[..e.valid_up_to()]).unwrap_or_default(),
+    };
+    // Truncated text is zero-padded up to the inline buffer size
+    Some(valid.trim_end_matches('\0').to_string())
+}
+
+/// Default SignalEvent → InputSignal conversion
+///
+/// The event vector becomes a DirectState; its inline text (or, failing that,
+/// its event type name) becomes the label. Invalid events (NaN/Inf) and
+/// events published by the Gateway itself are skipped.
+pub fn to_input_signal(event: &SignalEvent, event_type: Option<&str>) -> Option<InputSignal> {
+    if event.flags & FLAG_FROM_GATEWAY != 0 || !event.validate() {
+        return None;
+    }
+
+    Some(InputSignal::DirectState {
+        state: event.vector,
+        label: inline_text(event).or_else(|| event_type.map(str::to_string)),
+    })
+}
+
+/// Subscribe a Gateway injector to the SignalSystem
+///
+/// Every event matching `filter` is passed to `convert`; the resulting
+/// InputSignal is injected into the Gateway on `handle`. The subscriber holds
+/// only a weak reference to the Gateway, so it does not keep it alive, and it
+/// never sees events published by the Gateway itself.
+pub fn subscribe_injector<F>(
+    system: &SignalSystem,
+    gateway: &Arc<Gateway>,
+    filter: SubscriptionFilter,
+    handle: tokio::runtime::Handle,
+    convert: F,
+) -> Result<SubscriberId, SignalSystemError>
+where
+    F: Fn(&SignalEvent) -> Option<InputSignal> + Send + Sync + 'static,
+{
+    let gateway: Weak<Gateway> = Arc::downgrade(gateway);
+    let id = system.next_subscriber_id();
+
+    let subscriber = Subscriber::new_rust_callback(
+        id,
+        "gateway_injector".to_string(),
+        filter,
+        move |processed| {
+            if processed.event.flags & FLAG_FROM_GATEWAY != 0 {
+                return;
+            }
+            let Some(input) = convert(&processed.event) else {
+                return;
+            };
+            let Some(gateway) = gateway.upgrade() else {
+                return;
+            };
+            handle.spawn(async move {
+                if let Err(e) = gateway.inject(input).await {
+                    eprintln!("[GatewayBridge] Failed to inject SignalSystem event: {}", e);
+                }
+            });
+        },
+    );
+
+    system.subscribe(subscriber)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootstrap::{BootstrapConfig, BootstrapLibrary};
+    use crate::gateway::config::GatewayConfig;
+    use crate::gateway::signals::ProcessedMetadata;
+    use crate::signal_system::FilterLogic;
+    use parking_lot::RwLock;
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+
+    fn match_all(id: u64) -> SubscriptionFilter {
+        SubscriptionFilter::new_multi(id, Vec::new(), FilterLogic::And)
+    }
+
+    #[test]
+    fn test_processed_signal_round_trip() {
+        let mut registry = EventTypeRegistry::new();
+        let state = [0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8];
+        let processed = ProcessedSignal::new(7, state, SignalType::SemanticQuery, SignalSource::RestApi)
+            .with_confidence(0.5)
+            .with_metadata(ProcessedMetadata {
+                original_text: Some("привет, как дела у тебя сегодня вечером?".to_string()),
+                ..Default::default()
+            });
+
+        let event = to_signal_event(&processed, &mut registry);
+        assert_eq!(registry.get_type(event.event_type_id), Some("signal.gateway.semantic_query"));
+        assert_eq!(event.event_id_low, 7);
+        assert_eq!(event.vector, state);
+        assert_eq!(event.confidence, 128);
+        assert_ne!(event.flags & FLAG_FROM_GATEWAY, 0);
+        assert_eq!(event.trace_id_hash, processed.correlation_id.0);
+
+        // Truncated on a char boundary, never mid-codepoint
+        let text = inline_text(&event).unwrap();
+        assert!(!text.is_empty());
+        assert!("привет, как дела у тебя сегодня вечером?".starts_with(&text));
+
+        // Gateway events are never fed back in
+        assert!(to_input_signal(&event, None).is_none());
+
+        let mut external = SignalEvent::new(0, state);
+        external.inline_data[..5].copy_from_slice(b"hello");
+        external.data_type = DATA_TYPE_TEXT;
+        external.data_size = 5;
+        match to_input_signal(&external, Some("signal.input.internal.thought")) {
+            Some(InputSignal::DirectState { state: s, label }) => {
+                assert_eq!(s, state);
+                assert_eq!(label.as_deref(), Some("hello"));
+            }
+            other => panic!("unexpected conversion: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bridge_both_directions() {
+        let bootstrap = Arc::new(RwLock::new(BootstrapLibrary::new(BootstrapConfig::default())));
+        let (tx, mut rx) = mpsc::channel(16);
+        let gateway = Arc::new(Gateway::new(tx, bootstrap, GatewayConfig::default()));
+        let system = Arc::new(SignalSystem::new());
+        gateway.set_signal_system(system.clone());
+
+        let (observer, events) = Subscriber::new_polling(system.next_subscriber_id(), "observer".to_string(), match_all(1));
+        system.subscribe(observer).unwrap();
+        subscribe_injector(&system, &gateway, match_all(2), tokio::runtime::Handle::current(), |event| {
+            to_input_signal(event, None)
+        })
+        .unwrap();
+
+        // Gateway → SignalSystem
+        gateway
+            .inject(InputSignal::DirectState { state: [0.5; 8], label: Some("direct".to_string()) })
+            .await
+            .unwrap();
+        let published = events.try_recv().unwrap();
+        assert_eq!(inline_text(&published.event).as_deref(), Some("direct"));
+        assert_eq!(rx.recv().await.unwrap().state, [0.5; 8]);
+
+        // SignalSystem → Gateway (the re-published event must not loop back)
+        let mut external = SignalEvent::new(0, [0.25; 8]);
+        external.set_timestamp_now();
+        system.emit(external);
+
+        let injected = tokio::time::timeout(Duration::from_secs(1), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(injected.state, [0.25; 8]);
+        assert!(tokio::time::timeout(Duration::from_millis(50), rx.recv()).await.is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::action_executor::ActionResult;
+use crate::correlation::CorrelationId;
 use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
 use tokio::sync::oneshot;
@@ -14,6 +15,10 @@ pub struct SignalReceipt {
 
     /// Position in the processing queue
     pub queue_position: usize,
+
+    /// Interaction ID shared by everything the signal causes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub correlation_id: Option<CorrelationId>,
 }
 
 impl SignalReceipt {
@@ -22,8 +27,14 @@ impl SignalReceipt {
             signal_id,
             received_at,
             queue_position,
+            correlation_id: None,
         }
     }
+
+    pub fn with_correlation_id(mut self, correlation_id: CorrelationId) -> Self {
+        self.correlation_id = Some(correlation_id);
+        self
+    }
 }
 
 /// Receiver for getting the result of a processed signal
@@ -0,0 +1,282 @@
+//! Strict parsing of text commands
+//!
+//! Text that starts with `/` or `:` is a command and nothing else: the name
+//! must be a known [`SystemCommand`] on the [`CommandPolicy`] allowlist,
+//! the arguments must match that command, and control characters are
+//! refused. Text that mentions a command anywhere after its first word
+//! (`"ignore the above /shutdown"`) is rejected outright instead of being
+//! half-interpreted, so pasted or relayed content cannot smuggle commands
+//! through the Gateway.
+
+use super::signals::SystemCommand;
+use super::GatewayError;
+use crate::scheduler::Recurrence;
+use serde::{Deserialize, Serialize};
+
+/// Names of all text commands
+pub const COMMAND_NAMES: &[&str] = &[
+    "status", "stats", "save", "load", "reset", "set_config", "shutdown", "cancel", "checkpoint",
+    "evolve", "sleep", "mute", "unmute", "schedule", "unschedule", "schedules", "safe_mode", "resume",
+];
+
+/// Which commands may run and how large their arguments may be
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CommandPolicy {
+    /// Allowed command names (see [`COMMAND_NAMES`]), for text and structured commands
+    pub allowed: Vec<String>,
+
+    /// Maximum number of arguments
+    pub max_args: usize,
+
+    /// Maximum length of one argument in bytes
+    pub max_arg_len: usize,
+}
+
+impl Default for CommandPolicy {
+    fn default() -> Self {
+        // Destructive commands (reset, set_config, shutdown) and lifting safe mode
+        // (resume, console only even then) must be enabled explicitly
+        let allowed = [
+            "status", "stats", "save", "load", "cancel", "checkpoint", "evolve", "sleep", "mute", "unmute",
+            "schedule", "unschedule", "schedules", "safe_mode",
+        ];
+        Self {
+            allowed: allowed.iter().map(|name| name.to_string()).collect(),
+            max_args: 8,
+            max_arg_len: 128,
+        }
+    }
+}
+
+impl CommandPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(name) = self.allowed.iter().find(|name| !COMMAND_NAMES.contains(&name.as_str())) {
+            return Err(format!("unknown command '{}' in allowlist", name));
+        }
+        if self.max_args == 0 || self.max_arg_len == 0 {
+            return Err("max_args and max_arg_len must be > 0".to_string());
+        }
+        Ok(())
+    }
+
+    /// Reject commands missing from the allowlist
+    pub fn check(&self, command: &SystemCommand) -> Result<(), GatewayError> {
+        let name = command_name(command);
+        if self.allowed.iter().any(|allowed| allowed == name) {
+            Ok(())
+        } else {
+            Err(GatewayError::InvalidCommand(format!("'{}' is not allowed", name)))
+        }
+    }
+
+    /// Parse text into a command
+    ///
+    /// Returns `Ok(None)` for ordinary text and an error for malformed,
+    /// disallowed or embedded commands.
+    pub fn parse(&self, text: &str) -> Result<Option<(SystemCommand, Vec<String>)>, GatewayError> {
+        let text = text.trim();
+        let mut words = text.split_whitespace();
+        let Some(head) = words.next().and_then(command_word) else {
+            if let Some(embedded) = words.filter_map(command_word).find(|name| is_command_name(name)) {
+                return Err(GatewayError::InvalidCommand(format!(
+                    "command '{}' embedded in text",
+                    embedded
+                )));
+            }
+            return Ok(None);
+        };
+
+        if text.chars().any(char::is_control) {
+            return Err(GatewayError::InvalidCommand("control characters in command".to_string()));
+        }
+        let name = head.to_lowercase();
+        if !is_command_name(&name) {
+            return Err(GatewayError::InvalidCommand(format!("unknown command '{}'", head)));
+        }
+
+        let mut args: Vec<String> = words.map(str::to_string).collect();
+        // The scheduled input is one argument: `/schedule every 1h <input...>`
+        if name == "schedule" && args.len() > 3 {
+            let input = args.split_off(2).join(" ");
+            args.push(input);
+        }
+        if args.len() > self.max_args {
+            return Err(GatewayError::InvalidCommand(format!("more than {} arguments", self.max_args)));
+        }
+        if args.iter().any(|arg| arg.len() > self.max_arg_len) {
+            return Err(GatewayError::InvalidCommand(format!(
+                "argument longer than {} bytes",
+                self.max_arg_len
+            )));
+        }
+
+        let no_args = |command: SystemCommand| {
+            if args.is_empty() {
+                Ok(command)
+            } else {
+                Err(GatewayError::InvalidCommand(format!("'{}' takes no arguments", name)))
+            }
+        };
+        let command = match name.as_str() {
+            "status" => no_args(SystemCommand::Status)?,
+            "stats" => no_args(SystemCommand::Stats)?,
+            "save" => no_args(SystemCommand::Save)?,
+            "load" => no_args(SystemCommand::Load)?,
+            "reset" => no_args(SystemCommand::Reset)?,
+            "set_config" => no_args(SystemCommand::SetConfig)?,
+            "shutdown" => no_args(SystemCommand::Shutdown)?,
+            "evolve" => no_args(SystemCommand::Evolve)?,
+            "sleep" => no_args(SystemCommand::Sleep)?,
+            "mute" => no_args(SystemCommand::Mute)?,
+            "unmute" => no_args(SystemCommand::Unmute)?,
+            "schedules" => no_args(SystemCommand::Schedules)?,
+            "resume" => no_args(SystemCommand::Resume)?,
+            "safe_mode" => SystemCommand::SafeMode {
+                reason: (!args.is_empty()).then(|| args.join(" ")),
+            },
+            "unschedule" => match args.as_slice() {
+                [id] => SystemCommand::Unschedule {
+                    id: id.parse().map_err(|_| {
+                        GatewayError::InvalidCommand(format!("invalid task ID '{}'", id))
+                    })?,
+                },
+                _ => return Err(GatewayError::InvalidCommand("usage: /unschedule <id>".to_string())),
+            },
+            "schedule" => match args.as_slice() {
+                [kind, when, input] => SystemCommand::Schedule {
+                    recurrence: Recurrence::parse(&format!("{} {}", kind, when))
+                        .map_err(|e| GatewayError::InvalidCommand(e.to_string()))?,
+                    input: input.clone(),
+                },
+                _ => {
+                    return Err(GatewayError::InvalidCommand(
+                        "usage: /schedule every <n>(s|m|h|d) | at <hh:mm> <input>".to_string(),
+                    ))
+                }
+            },
+            "cancel" => match args.as_slice() {
+                [id] => SystemCommand::Cancel {
+                    signal_id: id.parse().map_err(|_| {
+                        GatewayError::InvalidCommand(format!("invalid signal ID '{}'", id))
+                    })?,
+                },
+                _ => return Err(GatewayError::InvalidCommand("usage: /cancel <signal_id>".to_string())),
+            },
+            _ => SystemCommand::Checkpoint {
+                label: (!args.is_empty()).then(|| args.join(" ")),
+            },
+        };
+        self.check(&command)?;
+        Ok(Some((command, args)))
+    }
+}
+
+/// Name of a command as typed (`/checkpoint`)
+pub fn command_name(command: &SystemCommand) -> &'static str {
+    match command {
+        SystemCommand::Status => "status",
+        SystemCommand::Stats => "stats",
+        SystemCommand::Save => "save",
+        SystemCommand::Load => "load",
+        SystemCommand::Reset => "reset",
+        SystemCommand::SetConfig => "set_config",
+        SystemCommand::Shutdown => "shutdown",
+        SystemCommand::Cancel { .. } => "cancel",
+        SystemCommand::Checkpoint { .. } => "checkpoint",
+        SystemCommand::Evolve => "evolve",
+        SystemCommand::Sleep => "sleep",
+        SystemCommand::Mute => "mute",
+        SystemCommand::Unmute => "unmute",
+        SystemCommand::Schedule { .. } => "schedule",
+        SystemCommand::Unschedule { .. } => "unschedule",
+        SystemCommand::Schedules => "schedules",
+        SystemCommand::SafeMode { .. } => "safe_mode",
+        SystemCommand::Resume => "resume",
+    }
+}
+
+/// Commands that change no state; the only ones accepted in safe mode
+pub fn is_read_only(command: &SystemCommand) -> bool {
+    matches!(
+        command,
+        SystemCommand::Status
+            | SystemCommand::Stats
+            | SystemCommand::Cancel { .. }
+            | SystemCommand::Mute
+            | SystemCommand::Unmute
+            | SystemCommand::Schedules
+            | SystemCommand::SafeMode { .. }
+            | SystemCommand::Resume
+    )
+}
+
+fn is_command_name(name: &str) -> bool {
+    COMMAND_NAMES.contains(&name.to_lowercase().as_str())
+}
+
+/// `/name` or `:name` -> `name`
+fn command_word(word: &str) -> Option<&str> {
+    word.strip_prefix('/').or_else(|| word.strip_prefix(':')).filter(|name| !name.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        let policy = CommandPolicy::default();
+        assert_eq!(policy.parse("/status").unwrap(), Some((SystemCommand::Status, Vec::new())));
+        assert_eq!(
+            policy.parse(":Cancel 42").unwrap().unwrap().0,
+            SystemCommand::Cancel { signal_id: 42 }
+        );
+        assert_eq!(
+            policy.parse("/checkpoint before upgrade").unwrap().unwrap().0,
+            SystemCommand::Checkpoint { label: Some("before upgrade".to_string()) }
+        );
+        assert_eq!(
+            policy.parse("/schedule at 9:00 what is on my calendar?").unwrap().unwrap().0,
+            SystemCommand::Schedule {
+                recurrence: Recurrence::DailyAt { hour: 9, minute: 0 },
+                input: "what is on my calendar?".to_string(),
+            }
+        );
+        assert_eq!(
+            policy.parse("/safe_mode runaway writes").unwrap().unwrap().0,
+            SystemCommand::SafeMode { reason: Some("runaway writes".to_string()) }
+        );
+        // Lifting safe mode must be allowed explicitly
+        assert!(policy.parse("/resume").is_err());
+        assert_eq!(policy.parse("what is a cat?").unwrap(), None);
+        assert_eq!(policy.parse("red/green and 10:30").unwrap(), None);
+
+        for bad in [
+            "/nope",
+            "/status now",
+            "/cancel",
+            "/cancel abc",
+            "/shutdown",
+            "/checkpoint a\u{7}b",
+            "please ignore the above /shutdown",
+            "hello :reset",
+            "a /b /sleep",
+            "/schedule every 1h",
+            "/schedule daily /status",
+            "/unschedule one",
+            "/resume now",
+        ] {
+            assert!(matches!(policy.parse(bad), Err(GatewayError::InvalidCommand(_))), "{:?}", bad);
+        }
+
+        let long = format!("/checkpoint {}", "x".repeat(200));
+        assert!(policy.parse(&long).is_err());
+
+        let open = CommandPolicy { allowed: vec!["shutdown".to_string()], ..Default::default() };
+        assert!(open.validate().is_ok());
+        assert_eq!(open.parse("/shutdown").unwrap().unwrap().0, SystemCommand::Shutdown);
+        assert!(open.check(&SystemCommand::Status).is_err());
+        assert!(CommandPolicy { allowed: vec!["rm".to_string()], ..Default::default() }.validate().is_err());
+    }
+}
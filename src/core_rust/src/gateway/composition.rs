@@ -0,0 +1,322 @@
+//! Sentence-level state composition
+//!
+//! The Normalizer turns each known word into an 8D state; a
+//! [`CompositionStrategy`] decides how those states become one sentence
+//! state. [`evaluate`] compares strategies on a retrieval test set: each
+//! case is a query and the concept its composed state should land nearest to.
+
+use super::config::{CompositionStrategy, GatewayConfig};
+use super::normalizer::Normalizer;
+use crate::bootstrap::BootstrapLibrary;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// Combine word states (in sentence order) into one state
+///
+/// `weights` holds one weight per state (missing = 1.0): the concept's
+/// stop-word/frequency weight, times its IDF weight for `IdfWeighted`.
+/// `MaxPool` ignores them.
+pub fn compose(strategy: CompositionStrategy, states: &[[f32; 8]], weights: &[f32]) -> [f32; 8] {
+    if states.is_empty() {
+        return [0.0; 8];
+    }
+
+    let weight = |i: usize| weights.get(i).copied().unwrap_or(1.0);
+    match strategy {
+        CompositionStrategy::Mean | CompositionStrategy::IdfWeighted => weighted_mean(states, weight),
+        CompositionStrategy::PositionalDecay { decay } => {
+            weighted_mean(states, |i| decay.powi(i as i32) * weight(i))
+        }
+        CompositionStrategy::MaxPool => {
+            let mut result = [0.0f32; 8];
+            for state in states {
+                for (r, &v) in result.iter_mut().zip(state.iter()) {
+                    if v.abs() > r.abs() {
+                        *r = v;
+                    }
+                }
+            }
+            result
+        }
+    }
+}
+
+fn weighted_mean(states: &[[f32; 8]], weight: impl Fn(usize) -> f32) -> [f32; 8] {
+    let mut result = [0.0; 8];
+    let mut total = 0.0;
+
+    for (i, state) in states.iter().enumerate() {
+        let w = weight(i);
+        total += w;
+        for (r, v) in result.iter_mut().zip(state.iter()) {
+            *r += v * w;
+        }
+    }
+
+    if total > 0.0 {
+        for r in result.iter_mut() {
+            *r /= total;
+        }
+    }
+    result
+}
+
+/// Inverse document frequency per concept
+///
+/// `idf = ln((1 + N) / (1 + df)) + 1`, so a concept in every document weighs 1
+/// and concepts never seen in the corpus get the maximum weight.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IdfTable {
+    weights: HashMap<u32, f32>,
+    /// Weight for concepts absent from the corpus
+    default: f32,
+    documents: usize,
+}
+
+impl IdfTable {
+    /// Fit on a corpus; `lookup` maps a lowercase word to its concept ID
+    pub fn fit<'a>(
+        documents: impl IntoIterator<Item = &'a str>,
+        lookup: impl Fn(&str) -> Option<u32>,
+    ) -> Self {
+        let mut df: HashMap<u32, usize> = HashMap::new();
+        let mut n = 0;
+
+        for document in documents {
+            n += 1;
+            let ids: HashSet<u32> = document
+                .split_whitespace()
+                .filter_map(|w| lookup(&w.to_lowercase()))
+                .collect();
+            for id in ids {
+                *df.entry(id).or_insert(0) += 1;
+            }
+        }
+
+        let idf = |df: usize| ((1.0 + n as f32) / (1.0 + df as f32)).ln() + 1.0;
+        Self {
+            weights: df.into_iter().map(|(id, count)| (id, idf(count))).collect(),
+            default: idf(0),
+            documents: n,
+        }
+    }
+
+    /// Weight for a concept (1.0 when the table is empty)
+    pub fn weight(&self, concept_id: u32) -> f32 {
+        if self.documents == 0 {
+            return 1.0;
+        }
+        self.weights.get(&concept_id).copied().unwrap_or(self.default)
+    }
+
+    /// Number of documents the table was fitted on
+    pub fn documents(&self) -> usize {
+        self.documents
+    }
+}
+
+/// One retrieval test: `query` should compose to a state nearest `expected`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetrievalCase {
+    pub query: String,
+    pub expected: String,
+}
+
+impl RetrievalCase {
+    pub fn new(query: &str, expected: &str) -> Self {
+        Self { query: query.to_string(), expected: expected.to_lowercase() }
+    }
+}
+
+/// Parse a test set: one `query<TAB>expected` per line, `#` comments
+pub fn parse_cases(text: &str) -> Result<Vec<RetrievalCase>, String> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(i, line)| {
+            let (query, expected) = line
+                .split_once('\t')
+                .ok_or_else(|| format!("line {}: expected 'query<TAB>expected'", i + 1))?;
+            Ok(RetrievalCase::new(query.trim(), expected.trim()))
+        })
+        .collect()
+}
+
+/// Retrieval quality of one strategy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyScore {
+    pub strategy: CompositionStrategy,
+    /// Cases that could be scored
+    pub evaluated: usize,
+    /// Cases skipped (unknown expected concept or no known query words)
+    pub skipped: usize,
+    /// Fraction of evaluated cases with `expected` in the top k
+    pub hit_rate: f32,
+    /// Mean reciprocal rank of `expected`
+    pub mrr: f32,
+}
+
+/// Score each strategy on `cases`, ranking concepts by distance to the composed state
+///
+/// Concepts that appear in the query itself are excluded from the ranking,
+/// so a case only hits when composition moves the state somewhere new.
+pub fn evaluate(
+    bootstrap: Arc<RwLock<BootstrapLibrary>>,
+    base: &GatewayConfig,
+    idf: &IdfTable,
+    strategies: &[CompositionStrategy],
+    cases: &[RetrievalCase],
+    k: usize,
+) -> Vec<StrategyScore> {
+    let concepts: Vec<(String, u32, [f32; 8])> = {
+        let library = bootstrap.read();
+        library
+            .concepts_iter()
+            .map(|(word, c)| (word.clone(), c.id, Normalizer::concept_state(&c.coords, c.emotion)))
+            .collect()
+    };
+
+    strategies
+        .iter()
+        .map(|&strategy| {
+            let config = GatewayConfig { composition: strategy, ..base.clone() };
+            let normalizer = Normalizer::new(bootstrap.clone(), config);
+            normalizer.set_idf(idf.clone());
+
+            let mut evaluated = 0;
+            let mut hits = 0;
+            let mut reciprocal_ranks = 0.0;
+
+            for case in cases {
+                let Some(expected_id) = concepts.iter().find(|(w, _, _)| *w == case.expected).map(|c| c.1) else {
+                    continue;
+                };
+                let Ok(result) = normalizer.normalize_text(&case.query) else {
+                    continue;
+                };
+
+                let in_query: HashSet<u32> = result.matched_tokens.iter().map(|(_, id, _)| *id).collect();
+                let mut ranked: Vec<(u32, f32)> = concepts
+                    .iter()
+                    .filter(|(_, id, _)| !in_query.contains(id))
+                    .map(|(_, id, state)| (*id, distance(&result.state, state)))
+                    .collect();
+                ranked.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
+
+                evaluated += 1;
+                if let Some(rank) = ranked.iter().position(|(id, _)| *id == expected_id) {
+                    if rank < k {
+                        hits += 1;
+                    }
+                    reciprocal_ranks += 1.0 / (rank + 1) as f32;
+                }
+            }
+
+            StrategyScore {
+                strategy,
+                evaluated,
+                skipped: cases.len() - evaluated,
+                hit_rate: if evaluated == 0 { 0.0 } else { hits as f32 / evaluated as f32 },
+                mrr: if evaluated == 0 { 0.0 } else { reciprocal_ranks / evaluated as f32 },
+            }
+        })
+        .collect()
+}
+
+fn distance(a: &[f32; 8], b: &[f32; 8]) -> f32 {
+    a.iter().zip(b.iter()).map(|(x, y)| (x - y).powi(2)).sum::<f32>().sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootstrap::BootstrapConfig;
+
+    const A: [f32; 8] = [1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+    const B: [f32; 8] = [0.0, -2.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0];
+
+    #[test]
+    fn test_strategies() {
+        let states = [A, B];
+
+        let mean = compose(CompositionStrategy::Mean, &states, &[]);
+        assert_eq!(&mean[..2], &[0.5, -1.0]);
+
+        let idf = compose(CompositionStrategy::IdfWeighted, &states, &[3.0, 1.0]);
+        assert_eq!(&idf[..2], &[0.75, -0.5]);
+
+        let decay = compose(CompositionStrategy::PositionalDecay { decay: 0.5 }, &states, &[]);
+        assert!((decay[0] - 2.0 / 3.0).abs() < 1e-6);
+        assert!((decay[1] + 2.0 / 3.0).abs() < 1e-6);
+
+        // Order matters for positional decay only
+        let reversed = compose(CompositionStrategy::PositionalDecay { decay: 0.5 }, &[B, A], &[]);
+        assert!((reversed[0] - 1.0 / 3.0).abs() < 1e-6);
+        assert_eq!(compose(CompositionStrategy::Mean, &[B, A], &[]), mean);
+
+        let max = compose(CompositionStrategy::MaxPool, &states, &[]);
+        assert_eq!(&max[..2], &[1.0, -2.0]);
+
+        assert_eq!(compose(CompositionStrategy::MaxPool, &[], &[]), [0.0; 8]);
+    }
+
+    #[test]
+    fn test_idf_table() {
+        let ids: HashMap<&str, u32> = [("the", 1), ("cat", 2), ("dog", 3)].into_iter().collect();
+        let table = IdfTable::fit(["the cat", "the dog", "The end"], |w| ids.get(w).copied());
+
+        assert_eq!(table.documents(), 3);
+        assert!((table.weight(1) - 1.0).abs() < 1e-6);
+        assert!(table.weight(2) > table.weight(1));
+        assert!(table.weight(99) > table.weight(2));
+        assert_eq!(IdfTable::default().weight(2), 1.0);
+    }
+
+    #[test]
+    fn test_parse_cases() {
+        let cases = parse_cases("# comment\nkitten puppy\tCat\n\n").unwrap();
+        assert_eq!(cases.len(), 1);
+        assert_eq!(cases[0].expected, "cat");
+        assert!(parse_cases("no tab here").is_err());
+    }
+
+    #[test]
+    fn test_evaluate_prefers_idf_when_stop_words_dominate() {
+        // "the" sits far from everything; "x" and "y" sit either side of "mid"
+        let json = r#"[
+            {"word": "the", "id": 1, "coords": [-5.0, 0.0, 0.0]},
+            {"word": "x", "id": 2, "coords": [0.0, 0.0, 0.0]},
+            {"word": "y", "id": 3, "coords": [1.0, 0.0, 0.0]},
+            {"word": "mid", "id": 4, "coords": [0.5, 0.0, 0.0]},
+            {"word": "far", "id": 5, "coords": [-1.5, 0.0, 0.0]}
+        ]"#;
+        let mut library = BootstrapLibrary::new(BootstrapConfig::default());
+        library.load_bootstrap_map_str(json).unwrap();
+        let bootstrap = Arc::new(RwLock::new(library));
+
+        let mut corpus = vec!["the x", "the y", "the mid", "the far"];
+        corpus.extend(std::iter::repeat("the").take(16));
+        let idf = {
+            let library = bootstrap.read();
+            IdfTable::fit(corpus, |w| library.get_concept(w).map(|c| c.id))
+        };
+
+        let cases = vec![RetrievalCase::new("the x y", "mid")];
+        let scores = evaluate(
+            bootstrap,
+            &GatewayConfig::default(),
+            &idf,
+            &[CompositionStrategy::Mean, CompositionStrategy::IdfWeighted],
+            &cases,
+            1,
+        );
+
+        assert_eq!(scores.len(), 2);
+        assert_eq!(scores[0].evaluated, 1);
+        assert_eq!(scores[0].hit_rate, 0.0);
+        assert_eq!(scores[1].hit_rate, 1.0);
+        assert!(scores[1].mrr > scores[0].mrr);
+    }
+}
@@ -1,4 +1,7 @@
+use super::commands::CommandPolicy;
+use super::signals::SignalSource;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Strategy for handling unknown words
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -13,6 +16,65 @@ pub enum UnknownWordStrategy {
     UseNearest,
 }
 
+/// How word states are combined into one sentence state
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CompositionStrategy {
+    /// Centroid of all word states (weighted by stop-word/frequency weights)
+    #[default]
+    Mean,
+    /// Centroid weighted by inverse document frequency (rare words count more)
+    IdfWeighted,
+    /// Word i weighted by `decay^i`, so earlier words dominate
+    PositionalDecay { decay: f32 },
+    /// Per dimension, the value with the largest magnitude (sign kept)
+    MaxPool,
+}
+
+/// Negation and intensifier handling in the Normalizer
+///
+/// A modifier word applies to the next known concept within `scope` words:
+/// negators flip the valence-like dimensions of its state, intensifiers
+/// scale them. Modifier words themselves contribute no state.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModifierConfig {
+    /// Enable modifier handling
+    pub enabled: bool,
+
+    /// Words that negate the following concept
+    pub negators: Vec<String>,
+
+    /// Words that scale the following concept (factor > 1 strengthens)
+    pub intensifiers: HashMap<String, f32>,
+
+    /// State dimensions affected by modifiers (L4 Emotional carries valence)
+    pub valence_dims: Vec<usize>,
+
+    /// How many words ahead a modifier can reach
+    pub scope: usize,
+}
+
+impl Default for ModifierConfig {
+    fn default() -> Self {
+        let negators = [
+            "not", "no", "never", "neither", "nor", "without", "cannot", "don't", "doesn't",
+            "didn't", "isn't", "aren't", "wasn't", "weren't", "can't", "won't",
+        ];
+        let intensifiers = [
+            ("very", 1.5), ("extremely", 2.0), ("really", 1.3), ("so", 1.3), ("quite", 1.2),
+            ("somewhat", 0.7), ("slightly", 0.5), ("barely", 0.3),
+        ];
+
+        Self {
+            enabled: true,
+            negators: negators.iter().map(|w| w.to_string()).collect(),
+            intensifiers: intensifiers.iter().map(|(w, f)| (w.to_string(), *f)).collect(),
+            valence_dims: vec![3],
+            scope: 3,
+        }
+    }
+}
+
 /// Gateway configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GatewayConfig {
@@ -33,6 +95,30 @@ pub struct GatewayConfig {
 
     /// Strategy for handling unknown words
     pub unknown_word_strategy: UnknownWordStrategy,
+
+    /// Strategy for composing word states into a sentence state
+    #[serde(default)]
+    pub composition: CompositionStrategy,
+
+    /// Negation and intensifier handling
+    #[serde(default)]
+    pub modifiers: ModifierConfig,
+
+    /// Local time zone of tick temporal context, minutes east of UTC
+    #[serde(default)]
+    pub utc_offset_minutes: i32,
+
+    /// Trust per signal source [0.0, 1.0], 1.0 if absent
+    ///
+    /// Scales the interpretation confidence of processed signals and the
+    /// appraiser rewards of the experience they cause, so noisy external
+    /// feeds move the graph slower than direct operator input.
+    #[serde(default)]
+    pub source_trust: HashMap<SignalSource, f32>,
+
+    /// Allowed commands and argument limits
+    #[serde(default)]
+    pub commands: CommandPolicy,
 }
 
 impl Default for GatewayConfig {
@@ -44,11 +130,24 @@ impl Default for GatewayConfig {
             tick_interval_ms: 1000,
             max_text_length: 4096,
             unknown_word_strategy: UnknownWordStrategy::TriggerCuriosity,
+            composition: CompositionStrategy::Mean,
+            modifiers: ModifierConfig::default(),
+            utc_offset_minutes: 0,
+            source_trust: HashMap::new(),
+            commands: CommandPolicy::default(),
         }
     }
 }
 
 impl GatewayConfig {
+    /// Fields `Gateway::update_config` applies immediately
+    pub const LIVE_FIELDS: &'static [&'static str] = &["source_trust", "commands"];
+
+    /// Trust of a signal source
+    pub fn trust(&self, source: SignalSource) -> f32 {
+        self.source_trust.get(&source).copied().unwrap_or(1.0)
+    }
+
     /// Validate configuration
     pub fn validate(&self) -> Result<(), String> {
         if self.queue_capacity == 0 {
@@ -67,6 +166,30 @@ impl GatewayConfig {
             return Err("max_text_length must be > 0".to_string());
         }
 
+        if self.modifiers.valence_dims.iter().any(|&d| d >= 8) {
+            return Err("modifier valence_dims must be < 8".to_string());
+        }
+
+        if self.modifiers.intensifiers.values().any(|f| !f.is_finite() || *f < 0.0) {
+            return Err("intensifier factors must be finite and >= 0".to_string());
+        }
+
+        if !(-14 * 60..=14 * 60).contains(&self.utc_offset_minutes) {
+            return Err("utc_offset_minutes must be within ±14 hours".to_string());
+        }
+
+        if self.source_trust.values().any(|t| !(0.0..=1.0).contains(t)) {
+            return Err("source_trust values must be 0.0-1.0".to_string());
+        }
+
+        self.commands.validate()?;
+
+        if let CompositionStrategy::PositionalDecay { decay } = self.composition {
+            if !(decay > 0.0 && decay <= 1.0) {
+                return Err("positional decay must be in (0, 1]".to_string());
+            }
+        }
+
         Ok(())
     }
 }
@@ -94,4 +217,24 @@ mod tests {
         config.processing_timeout_ms = 0;
         assert!(config.validate().is_err());
     }
+
+    #[test]
+    fn test_invalid_modifiers() {
+        let mut config = GatewayConfig::default();
+        config.modifiers.valence_dims = vec![8];
+        assert!(config.validate().is_err());
+
+        let mut config = GatewayConfig::default();
+        config.modifiers.intensifiers.insert("kinda".to_string(), -1.0);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_invalid_positional_decay() {
+        let mut config = GatewayConfig::default();
+        config.composition = CompositionStrategy::PositionalDecay { decay: 0.0 };
+        assert!(config.validate().is_err());
+        config.composition = CompositionStrategy::PositionalDecay { decay: 0.7 };
+        assert!(config.validate().is_ok());
+    }
 }
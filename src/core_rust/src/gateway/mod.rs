@@ -1,24 +1,41 @@
+pub mod bridge;
 pub mod channels;
+pub mod commands;
+pub mod composition;
 pub mod config;
 pub mod normalizer;
 pub mod signals;
 pub mod stats;
+pub mod temporal;
 
-use crate::action_executor::ActionResult;
+use crate::action_executor::{ActionResult, CancellationToken};
 use crate::bootstrap::BootstrapLibrary;
+use crate::checkpoint::CheckpointManager;
+use crate::consolidation::{ConsolidationTrigger, Consolidator};
+use crate::correlation::CorrelationId;
+use crate::evolution_manager::EvolutionScheduler;
+use crate::guardian::{Event as GuardianEvent, EventType as GuardianEventType, Guardian};
 use crate::module_id::ModuleId;
 use crate::module_registry::REGISTRY;
+use crate::profiling::{PipelineStage, PROFILER};
+use crate::replay::ReplayRecorder;
+use crate::safe_mode::SafeMode;
+use crate::scheduler::Scheduler;
+use crate::signal_system::SignalSystem;
 use channels::{create_result_channel, PendingRequests, ResultReceiver, SignalReceipt};
+use dashmap::DashMap;
 use config::GatewayConfig;
 use normalizer::{NormalizationError, Normalizer};
 use signals::{
     InputSignal, ProcessedMetadata, ProcessedSignal, SignalSource, SignalType, SystemCommand,
 };
 use stats::GatewayStats;
-use std::sync::atomic::{AtomicU64, Ordering};
+use temporal::TemporalContext;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::sync::mpsc;
+use tracing::Instrument;
 use parking_lot::RwLock;
 
 /// Gateway errors
@@ -31,6 +48,10 @@ pub enum GatewayError {
     NotImplemented(String),
     InvalidCommand(String),
     SendFailed,
+    /// Refused while safe mode is engaged
+    SafeMode(String),
+    /// Refused by a read-only replica
+    ReadOnly(String),
 }
 
 impl std::fmt::Display for GatewayError {
@@ -43,6 +64,8 @@ impl std::fmt::Display for GatewayError {
             GatewayError::NotImplemented(msg) => write!(f, "Not implemented: {}", msg),
             GatewayError::InvalidCommand(msg) => write!(f, "Invalid command: {}", msg),
             GatewayError::SendFailed => write!(f, "Failed to send signal to queue"),
+            GatewayError::SafeMode(msg) => write!(f, "Safe mode: {}", msg),
+            GatewayError::ReadOnly(msg) => write!(f, "Read-only replica: {}", msg),
         }
     }
 }
@@ -57,17 +80,50 @@ pub struct Gateway {
     /// Normalizer for converting text to states
     normalizer: Normalizer,
 
-    /// Configuration
-    config: GatewayConfig,
+    /// Configuration (`GatewayConfig::LIVE_FIELDS` can change at runtime)
+    config: RwLock<GatewayConfig>,
 
     /// Pending requests waiting for results
     pending_requests: Arc<PendingRequests>,
 
+    /// Cancellation tokens for in-flight requests (signal_id → token)
+    cancellations: Arc<DashMap<u64, CancellationToken>>,
+
     /// Statistics
     stats: Arc<RwLock<GatewayStats>>,
 
     /// Signal counter for generating IDs
     signal_counter: AtomicU64,
+
+    /// Checkpoint manager for SystemCommand::Checkpoint
+    checkpoint: RwLock<Option<Arc<CheckpointManager>>>,
+
+    /// SignalSystem that receives every processed signal as a SignalEvent
+    signal_system: RwLock<Option<Arc<SignalSystem>>>,
+
+    /// Evolution scheduler for SystemCommand::Evolve
+    evolution: RwLock<Option<Arc<EvolutionScheduler>>>,
+
+    /// Replay journal that records every injected signal
+    replay: RwLock<Option<Arc<ReplayRecorder>>>,
+
+    /// Consolidator for SystemCommand::Sleep; also told about every other signal
+    consolidator: RwLock<Option<Arc<Consolidator>>>,
+
+    /// Guardian that audits every command, run or rejected
+    guardian: RwLock<Option<Arc<RwLock<Guardian>>>>,
+
+    /// Speech mute switch for SystemCommand::Mute/Unmute, shared with TTS adapters
+    muted: Arc<AtomicBool>,
+
+    /// Task scheduler for SystemCommand::Schedule/Unschedule/Schedules
+    scheduler: RwLock<Option<Arc<Scheduler>>>,
+
+    /// Switch toggled by SystemCommand::SafeMode/Resume
+    safe_mode: Arc<SafeMode>,
+
+    /// Read replica: only queries and read-only commands are accepted
+    read_only: AtomicBool,
 }
 
 impl Gateway {
@@ -82,13 +138,205 @@ impl Gateway {
         Self {
             sender,
             normalizer,
-            config,
+            config: RwLock::new(config),
             pending_requests: Arc::new(PendingRequests::new()),
+            cancellations: Arc::new(DashMap::new()),
             stats: Arc::new(RwLock::new(GatewayStats::new())),
             signal_counter: AtomicU64::new(0),
+            checkpoint: RwLock::new(None),
+            signal_system: RwLock::new(None),
+            evolution: RwLock::new(None),
+            replay: RwLock::new(None),
+            consolidator: RwLock::new(None),
+            guardian: RwLock::new(None),
+            muted: Arc::new(AtomicBool::new(false)),
+            scheduler: RwLock::new(None),
+            safe_mode: Arc::default(),
+            read_only: AtomicBool::new(false),
+        }
+    }
+
+    /// Toggle a switch shared with the rest of the instance
+    ///
+    /// Without it the Gateway owns a fresh switch; hand `safe_mode()` to the
+    /// instance's other components instead.
+    pub fn with_safe_mode(mut self, safe_mode: Arc<SafeMode>) -> Self {
+        self.safe_mode = safe_mode;
+        self
+    }
+
+    /// Safe mode switch toggled by SystemCommand::SafeMode/Resume
+    pub fn safe_mode(&self) -> &Arc<SafeMode> {
+        &self.safe_mode
+    }
+
+    /// Serve as a read replica: commands that change state are rejected and
+    /// only queries reach the ActionController, like under safe mode
+    pub fn set_read_only(&self, read_only: bool) {
+        self.read_only.store(read_only, Ordering::Relaxed);
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only.load(Ordering::Relaxed)
+    }
+
+    /// Attach a checkpoint manager for SystemCommand::Checkpoint
+    pub fn set_checkpoint_manager(&self, manager: Arc<CheckpointManager>) {
+        *self.checkpoint.write() = Some(manager);
+    }
+
+    /// Attach an evolution scheduler for SystemCommand::Evolve
+    pub fn set_evolution_scheduler(&self, scheduler: Arc<EvolutionScheduler>) {
+        *self.evolution.write() = Some(scheduler);
+    }
+
+    /// Attach a consolidator for SystemCommand::Sleep and idle detection
+    pub fn set_consolidator(&self, consolidator: Arc<Consolidator>) {
+        *self.consolidator.write() = Some(consolidator);
+    }
+
+    /// Audit commands through a Guardian
+    pub fn set_guardian(&self, guardian: Arc<RwLock<Guardian>>) {
+        *self.guardian.write() = Some(guardian);
+    }
+
+    /// Attach a scheduler for SystemCommand::Schedule/Unschedule/Schedules
+    ///
+    /// The scheduler injects due tasks back through the Gateway; start it
+    /// with `Scheduler::spawn`.
+    pub fn set_scheduler(&self, scheduler: Arc<Scheduler>) {
+        *self.scheduler.write() = Some(scheduler);
+    }
+
+    pub fn scheduler(&self) -> Option<Arc<Scheduler>> {
+        self.scheduler.read().clone()
+    }
+
+    /// Mute switch toggled by SystemCommand::Mute/Unmute
+    ///
+    /// Speech output adapters hold a clone and stay silent while it is set.
+    pub fn mute_switch(&self) -> Arc<AtomicBool> {
+        self.muted.clone()
+    }
+
+    /// Emit a Guardian audit event for a command
+    fn audit_command(&self, event_type: GuardianEventType, data: serde_json::Value) {
+        if let Some(guardian) = self.guardian.read().as_ref() {
+            guardian.write().emit_event(GuardianEvent::new(event_type).with_data(data.to_string()));
         }
     }
 
+    /// Turn command text into a command and enforce the allowlist
+    ///
+    /// Rejections are counted and audited before the error is returned.
+    fn screen_command(&self, signal: InputSignal) -> Result<InputSignal, GatewayError> {
+        let policy = self.config.read().commands.clone();
+        let origin = match &signal {
+            InputSignal::Text { source, .. } => Some(*source),
+            _ => None,
+        };
+        let screened = match signal {
+            InputSignal::Text { content, source, metadata } => match policy.parse(&content) {
+                Ok(Some((command, args))) => Ok(InputSignal::Command { command, args }),
+                Ok(None) => return Ok(InputSignal::Text { content, source, metadata }),
+                Err(e) => {
+                    let excerpt: String = content.chars().take(80).collect();
+                    Err((e, serde_json::json!({"text": excerpt, "source": format!("{:?}", source)})))
+                }
+            },
+            InputSignal::Command { command, args } => match policy.check(&command) {
+                Ok(()) => Ok(InputSignal::Command { command, args }),
+                Err(e) => Err((e, serde_json::json!({"command": commands::command_name(&command), "args": args}))),
+            },
+            other => return Ok(other),
+        };
+        // Scheduled input must itself pass when it is injected later
+        let screened = screened.and_then(|signal| match &signal {
+            InputSignal::Command { command: SystemCommand::Schedule { input, .. }, args } => {
+                let reject = |e: GatewayError| {
+                    Err((e, serde_json::json!({"command": "schedule", "args": args, "input": input})))
+                };
+                match policy.parse(input) {
+                    Ok(Some((SystemCommand::Schedule { .. }, _))) => {
+                        reject(GatewayError::InvalidCommand("schedules cannot be scheduled".to_string()))
+                    }
+                    Ok(_) => Ok(signal),
+                    Err(e) => reject(e),
+                }
+            }
+            _ => Ok(signal),
+        });
+        // Only the operator console lifts the kill switch (REST: the admin /safe_mode/resume)
+        let screened = screened.and_then(|signal| match &signal {
+            InputSignal::Command { command: SystemCommand::Resume, args } if origin != Some(SignalSource::Console) => {
+                Err((
+                    GatewayError::InvalidCommand("'resume' is only accepted from the console".to_string()),
+                    serde_json::json!({
+                        "command": "resume",
+                        "args": args,
+                        "source": origin.map(|source| format!("{:?}", source)),
+                    }),
+                ))
+            }
+            _ => Ok(signal),
+        });
+        // Safe mode admits only commands that change nothing
+        let screened = screened.and_then(|signal| match &signal {
+            InputSignal::Command { command, args } if self.safe_mode.is_engaged() && !commands::is_read_only(command) => {
+                let name = commands::command_name(command);
+                Err((
+                    GatewayError::SafeMode(format!("'{}' is disabled", name)),
+                    serde_json::json!({"command": name, "args": args}),
+                ))
+            }
+            _ => Ok(signal),
+        });
+        // Replicas follow the writer and change nothing themselves
+        let screened = screened.and_then(|signal| match &signal {
+            InputSignal::Command { command, args } if self.is_read_only() && !commands::is_read_only(command) => {
+                let name = commands::command_name(command);
+                Err((
+                    GatewayError::ReadOnly(format!("'{}' is disabled", name)),
+                    serde_json::json!({"command": name, "args": args}),
+                ))
+            }
+            _ => Ok(signal),
+        });
+
+        match screened {
+            Ok(signal) => {
+                if let InputSignal::Command { command, args } = &signal {
+                    self.audit_command(
+                        GuardianEventType::CommandExecuted,
+                        serde_json::json!({"command": commands::command_name(command), "args": args}),
+                    );
+                }
+                Ok(signal)
+            }
+            Err((e, mut data)) => {
+                self.stats.write().rejected_commands += 1;
+                data["reason"] = serde_json::Value::String(e.to_string());
+                self.audit_command(GuardianEventType::CommandRejected, data);
+                Err(e)
+            }
+        }
+    }
+
+    /// Record every injected signal to a replay journal
+    pub fn set_replay_recorder(&self, recorder: Arc<ReplayRecorder>) {
+        *self.replay.write() = Some(recorder);
+    }
+
+    /// Stop recording injected signals
+    pub fn clear_replay_recorder(&self) {
+        *self.replay.write() = None;
+    }
+
+    /// Publish every processed signal to a SignalSystem (see `bridge`)
+    pub fn set_signal_system(&self, system: Arc<SignalSystem>) {
+        *self.signal_system.write() = Some(system);
+    }
+
     /// Generate unique signal ID
     fn generate_signal_id(&self) -> u64 {
         self.signal_counter.fetch_add(1, Ordering::SeqCst)
@@ -106,6 +354,14 @@ impl Gateway {
     pub async fn inject(
         &self,
         signal: InputSignal,
+    ) -> Result<(SignalReceipt, ResultReceiver), GatewayError> {
+        let span = tracing::info_span!("gateway.inject", correlation_id = tracing::field::Empty);
+        self.inject_signal(signal).instrument(span).await
+    }
+
+    async fn inject_signal(
+        &self,
+        signal: InputSignal,
     ) -> Result<(SignalReceipt, ResultReceiver), GatewayError> {
         // Проверяем, включен ли модуль
         if !REGISTRY.is_enabled(ModuleId::Gateway) {
@@ -115,15 +371,35 @@ impl Gateway {
 
         let start = std::time::Instant::now();
 
+        // Command text is parsed strictly; disallowed commands stop here
+        let signal = self.screen_command(signal)?;
+
+        // Any signal but Sleep itself counts as activity for idle detection
+        let is_sleep = matches!(&signal, InputSignal::Command { command: SystemCommand::Sleep, .. });
+        if !is_sleep {
+            if let Some(consolidator) = self.consolidator.read().as_ref() {
+                consolidator.touch();
+            }
+        }
+
+        if let Some(recorder) = self.replay.read().as_ref() {
+            if let Err(e) = recorder.record(&signal) {
+                eprintln!("[Gateway] Failed to record signal for replay: {}", e);
+            }
+        }
+
         // Generate signal ID
         let signal_id = self.generate_signal_id();
         let received_at = Self::now_ms();
+        let correlation_id = signal.correlation_id().unwrap_or_else(CorrelationId::generate);
+        tracing::Span::current().record("correlation_id", tracing::field::display(correlation_id));
 
         // Create result channel
         let (result_tx, result_rx) = create_result_channel();
 
         // Store pending request
         self.pending_requests.insert(signal_id, result_tx);
+        self.cancellations.insert(signal_id, CancellationToken::new());
 
         // Update stats
         {
@@ -132,7 +408,7 @@ impl Gateway {
         }
 
         // Process signal based on type
-        let processed = match signal {
+        let mut processed = match signal {
             InputSignal::Text {
                 content,
                 source,
@@ -161,6 +437,179 @@ impl Gateway {
                 ));
             }
 
+            InputSignal::Command {
+                command: SystemCommand::Cancel { signal_id: target_id },
+                args: _,
+            } => {
+                {
+                    let mut stats = self.stats.write();
+                    stats.command_signals += 1;
+                }
+
+                // Cancel is answered inline: it must not queue behind the request it aborts
+                let cancelled = self.cancel(target_id);
+                self.complete_request(
+                    signal_id,
+                    ActionResult::success(
+                        serde_json::json!({"signal_id": target_id, "cancelled": cancelled}),
+                        0,
+                    ),
+                );
+                let receipt = SignalReceipt::new(signal_id, received_at, 0).with_correlation_id(correlation_id);
+                return Ok((receipt, result_rx));
+            }
+
+            InputSignal::Command {
+                command: SystemCommand::Checkpoint { label },
+                args: _,
+            } => {
+                {
+                    let mut stats = self.stats.write();
+                    stats.command_signals += 1;
+                }
+
+                // Checkpoint runs inline so it captures state as of this command
+                let start = std::time::Instant::now();
+                let manager = self.checkpoint.read().clone();
+                let result = match manager {
+                    Some(manager) => match manager.checkpoint(label.as_deref()).await {
+                        Ok(manifest) => ActionResult::success(
+                            serde_json::to_value(&manifest).unwrap_or_default(),
+                            start.elapsed().as_millis() as u64,
+                        ),
+                        Err(e) => ActionResult::failure(e.to_string(), start.elapsed().as_millis() as u64),
+                    },
+                    None => ActionResult::failure("Checkpointing is not configured".to_string(), 0),
+                };
+                self.complete_request(signal_id, result);
+                let receipt = SignalReceipt::new(signal_id, received_at, 0).with_correlation_id(correlation_id);
+                return Ok((receipt, result_rx));
+            }
+
+            InputSignal::Command {
+                command: SystemCommand::Evolve,
+                args: _,
+            } => {
+                {
+                    let mut stats = self.stats.write();
+                    stats.command_signals += 1;
+                }
+
+                // The scheduler applies its cooldown; `triggered` is false when suppressed
+                let scheduler = self.evolution.read().clone();
+                let result = match scheduler {
+                    Some(scheduler) => ActionResult::success(
+                        serde_json::json!({"triggered": scheduler.request()}),
+                        0,
+                    ),
+                    None => ActionResult::failure("Evolution scheduling is not configured".to_string(), 0),
+                };
+                self.complete_request(signal_id, result);
+                let receipt = SignalReceipt::new(signal_id, received_at, 0).with_correlation_id(correlation_id);
+                return Ok((receipt, result_rx));
+            }
+
+            InputSignal::Command {
+                command: SystemCommand::Sleep,
+                args: _,
+            } => {
+                {
+                    let mut stats = self.stats.write();
+                    stats.command_signals += 1;
+                }
+
+                // The pass runs on a blocking thread; the summary is the result
+                let consolidator = self.consolidator.read().clone();
+                let result = match consolidator {
+                    Some(consolidator) => {
+                        match tokio::task::spawn_blocking(move || {
+                            consolidator.consolidate(ConsolidationTrigger::Command)
+                        })
+                        .await
+                        {
+                            Ok(summary) => ActionResult::success(
+                                serde_json::to_value(&summary).unwrap_or_default(),
+                                summary.duration_ms,
+                            ),
+                            Err(e) => ActionResult::failure(format!("Consolidation failed: {}", e), 0),
+                        }
+                    }
+                    None => ActionResult::failure("Consolidation is not configured".to_string(), 0),
+                };
+                self.complete_request(signal_id, result);
+                let receipt = SignalReceipt::new(signal_id, received_at, 0).with_correlation_id(correlation_id);
+                return Ok((receipt, result_rx));
+            }
+
+            InputSignal::Command {
+                command: command @ (SystemCommand::Mute | SystemCommand::Unmute),
+                args: _,
+            } => {
+                {
+                    let mut stats = self.stats.write();
+                    stats.command_signals += 1;
+                }
+
+                let muted = command == SystemCommand::Mute;
+                self.muted.store(muted, Ordering::Relaxed);
+                self.complete_request(signal_id, ActionResult::success(serde_json::json!({"muted": muted}), 0));
+                let receipt = SignalReceipt::new(signal_id, received_at, 0).with_correlation_id(correlation_id);
+                return Ok((receipt, result_rx));
+            }
+
+            InputSignal::Command {
+                command:
+                    command @ (SystemCommand::Schedule { .. } | SystemCommand::Unschedule { .. } | SystemCommand::Schedules),
+                args: _,
+            } => {
+                {
+                    let mut stats = self.stats.write();
+                    stats.command_signals += 1;
+                }
+
+                let scheduler = self.scheduler.read().clone();
+                let result = match (scheduler, command) {
+                    (None, _) => ActionResult::failure("Scheduling is not configured".to_string(), 0),
+                    (Some(scheduler), SystemCommand::Schedule { recurrence, input }) => {
+                        match scheduler.add(recurrence, &input).await {
+                            Ok(task) => ActionResult::success(serde_json::to_value(&task).unwrap_or_default(), 0),
+                            Err(e) => ActionResult::failure(e.to_string(), 0),
+                        }
+                    }
+                    (Some(scheduler), SystemCommand::Unschedule { id }) => match scheduler.remove(id).await {
+                        Ok(task) => ActionResult::success(serde_json::to_value(&task).unwrap_or_default(), 0),
+                        Err(e) => ActionResult::failure(e.to_string(), 0),
+                    },
+                    (Some(scheduler), _) => {
+                        ActionResult::success(serde_json::to_value(scheduler.list()).unwrap_or_default(), 0)
+                    }
+                };
+                self.complete_request(signal_id, result);
+                let receipt = SignalReceipt::new(signal_id, received_at, 0).with_correlation_id(correlation_id);
+                return Ok((receipt, result_rx));
+            }
+
+            InputSignal::Command {
+                command: command @ (SystemCommand::SafeMode { .. } | SystemCommand::Resume),
+                args: _,
+            } => {
+                {
+                    let mut stats = self.stats.write();
+                    stats.command_signals += 1;
+                }
+
+                // Answered inline: the queue may be stuck behind the behavior being stopped
+                let changed = match &command {
+                    SystemCommand::SafeMode { reason } => self.safe_mode.engage(reason.as_deref()),
+                    _ => self.safe_mode.release(),
+                };
+                let mut status = serde_json::to_value(self.safe_mode.status()).unwrap_or_default();
+                status["changed"] = serde_json::Value::Bool(changed);
+                self.complete_request(signal_id, ActionResult::success(status, 0));
+                let receipt = SignalReceipt::new(signal_id, received_at, 0).with_correlation_id(correlation_id);
+                return Ok((receipt, result_rx));
+            }
+
             InputSignal::Command { command, args: _ } => {
                 {
                     let mut stats = self.stats.write();
@@ -183,9 +632,37 @@ impl Gateway {
                 ));
             }
         };
+        processed.metadata.processing_time_ns = start.elapsed().as_nanos() as u64;
+        processed.correlation_id = correlation_id;
+
+        // Safe mode and replicas keep only read-only querying
+        if (self.safe_mode.is_engaged() || self.is_read_only())
+            && !matches!(processed.signal_type, SignalType::SemanticQuery | SignalType::SystemSignal)
+        {
+            self.cancellations.remove(&signal_id);
+            self.pending_requests.remove(&signal_id);
+            let reason = format!("{:?} signals are disabled, only queries are accepted", processed.signal_type);
+            return Err(if self.safe_mode.is_engaged() {
+                GatewayError::SafeMode(reason)
+            } else {
+                GatewayError::ReadOnly(reason)
+            });
+        }
+
+        // Less trusted sources yield less confident signals
+        let trust = self.config.read().trust(processed.source);
+        processed.interpretation_confidence *= trust;
+        processed.metadata.source_trust = trust;
+
+        // Mirror to SignalSystem subscribers
+        let system = self.signal_system.read().clone();
+        if let Some(system) = system {
+            let event = bridge::to_signal_event(&processed, &mut system.event_registry().write());
+            system.emit(event);
+        }
 
         // Send to queue
-        let queue_position = self.sender.max_capacity() - self.sender.capacity();
+        let queue_position = self.queue_depth();
         self.sender
             .send(processed)
             .await
@@ -199,7 +676,8 @@ impl Gateway {
         }
 
         // Create receipt
-        let receipt = SignalReceipt::new(signal_id, received_at, queue_position);
+        let receipt = SignalReceipt::new(signal_id, received_at, queue_position)
+            .with_correlation_id(correlation_id);
 
         Ok((receipt, result_rx))
     }
@@ -224,17 +702,16 @@ impl Gateway {
             return Err(GatewayError::EmptyInput);
         }
 
-        if trimmed.len() > self.config.max_text_length {
+        if trimmed.len() > self.config.read().max_text_length {
             return Err(GatewayError::InputTooLong(trimmed.len()));
         }
 
         // Classify text type
-        let signal_type = self.classify_text(trimmed);
+        let signal_type = PROFILER.time(PipelineStage::Classify, || self.classify_text(trimmed));
 
         // Normalize text to state
-        let norm_result = self
-            .normalizer
-            .normalize_text(trimmed)
+        let norm_result = PROFILER
+            .time(PipelineStage::Normalize, || self.normalizer.normalize_text(trimmed))
             .map_err(|e| match e {
                 NormalizationError::NoWords => GatewayError::EmptyInput,
                 NormalizationError::AllUnknown => {
@@ -256,7 +733,9 @@ impl Gateway {
             original_text: Some(trimmed.to_string()),
             matched_tokens: norm_result.matched_tokens.clone(),
             unknown_words: norm_result.unknown_words,
-            processing_time_ns: 0, // Updated by caller
+            processing_time_ns: 0, // Updated by inject()
+            temporal: None,
+            source_trust: 1.0, // Updated by inject()
         };
 
         // Extract token IDs
@@ -282,22 +761,24 @@ impl Gateway {
         signal_id: u64,
         received_at: u64,
         _tick_number: u64,
-        _timestamp: u64,
+        timestamp: u64,
     ) -> ProcessedSignal {
         {
             let mut stats = self.stats.write();
             stats.tick_signals += 1;
         }
 
-        // Create time-based state (simple implementation)
-        let state = [0.0; 8]; // TODO: Could use tick_number/timestamp to create meaningful state
+        // Time of day and day of week on L7 (timestamp 0 = use arrival time)
+        let now_ms = if timestamp > 0 { timestamp } else { received_at };
+        let temporal = TemporalContext::from_unix_ms(now_ms, self.config.read().utc_offset_minutes);
 
         let mut signal = ProcessedSignal::new(
             signal_id,
-            state,
+            temporal.state(),
             SignalType::CuriosityTrigger,
             SignalSource::InternalTimer,
-        );
+        )
+        .with_metadata(ProcessedMetadata { temporal: Some(temporal), ..Default::default() });
         signal.received_at = received_at;
         signal
     }
@@ -390,8 +871,37 @@ impl Gateway {
         SignalType::SemanticQuery
     }
 
+    /// Cancellation token for an in-flight request (used by ActionController)
+    pub fn cancellation_token(&self, signal_id: u64) -> Option<CancellationToken> {
+        self.cancellations.get(&signal_id).map(|t| t.clone())
+    }
+
+    /// Abort a pending request
+    ///
+    /// Signals the executor's cancellation token and answers the waiting
+    /// receiver with a failed ActionResult. Returns false if the request
+    /// already completed or never existed.
+    pub fn cancel(&self, signal_id: u64) -> bool {
+        if let Some((_, token)) = self.cancellations.remove(&signal_id) {
+            token.cancel();
+        }
+
+        match self.pending_requests.remove(&signal_id) {
+            Some((_, sender)) => {
+                let _ = sender.send(ActionResult::failure(
+                    format!("Request {} cancelled", signal_id),
+                    0,
+                ));
+                self.stats.write().cancelled += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Complete a request with a result (called by ActionController)
     pub fn complete_request(&self, signal_id: u64, result: ActionResult) {
+        self.cancellations.remove(&signal_id);
         if let Some((_, sender)) = self.pending_requests.remove(&signal_id) {
             // Send result back to waiting receiver
             let _ = sender.send(result); // Ignore error if receiver dropped
@@ -416,6 +926,9 @@ impl Gateway {
         // Remove them
         for signal_id in to_remove {
             self.pending_requests.remove(&signal_id);
+            if let Some((_, token)) = self.cancellations.remove(&signal_id) {
+                token.cancel();
+            }
 
             {
                 let mut stats = self.stats.write();
@@ -424,6 +937,22 @@ impl Gateway {
         }
     }
 
+    pub fn config(&self) -> GatewayConfig {
+        self.config.read().clone()
+    }
+
+    /// Apply a new configuration
+    ///
+    /// Only `GatewayConfig::LIVE_FIELDS` (source trust, command policy)
+    /// take effect; the Normalizer and queue settings are fixed at construction.
+    pub fn update_config(&self, config: GatewayConfig) -> Result<(), String> {
+        config.validate()?;
+        let mut current = self.config.write();
+        current.source_trust = config.source_trust;
+        current.commands = config.commands;
+        Ok(())
+    }
+
     /// Get current statistics
     pub fn stats(&self) -> GatewayStats {
         self.stats.read().clone()
@@ -433,6 +962,11 @@ impl Gateway {
     pub fn pending_count(&self) -> usize {
         self.pending_requests.len()
     }
+
+    /// Processed signals waiting in the queue
+    pub fn queue_depth(&self) -> usize {
+        self.sender.max_capacity() - self.sender.capacity()
+    }
 }
 
 #[cfg(test)]
@@ -486,4 +1020,329 @@ mod tests {
             SignalType::ActionRequest
         );
     }
+
+    #[tokio::test]
+    async fn test_cancel_pending_request() {
+        use crate::bootstrap::BootstrapConfig;
+        let bootstrap = Arc::new(RwLock::new(BootstrapLibrary::new(BootstrapConfig::default())));
+        let (tx, _rx) = mpsc::channel(100);
+        let gateway = Gateway::new(tx, bootstrap, GatewayConfig::default());
+
+        let (receipt, receiver) = gateway
+            .inject(InputSignal::DirectState { state: [0.1; 8], label: None })
+            .await
+            .unwrap();
+        let token = gateway.cancellation_token(receipt.signal_id).unwrap();
+
+        let (_, cancel_rx) = gateway
+            .inject(InputSignal::Command {
+                command: SystemCommand::Cancel { signal_id: receipt.signal_id },
+                args: Vec::new(),
+            })
+            .await
+            .unwrap();
+
+        let ack = cancel_rx.await.unwrap();
+        assert_eq!(ack.output["cancelled"], true);
+
+        let result = receiver.await.unwrap();
+        assert!(!result.success);
+        assert!(token.is_cancelled());
+        assert_eq!(gateway.pending_count(), 0);
+        assert_eq!(gateway.stats().cancelled, 1);
+
+        // Second cancel is a no-op
+        assert!(!gateway.cancel(receipt.signal_id));
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_command() {
+        use crate::bootstrap::BootstrapConfig;
+        use crate::checkpoint::CheckpointConfig;
+        use crate::runtime_storage::RuntimeStorage;
+        let bootstrap = Arc::new(RwLock::new(BootstrapLibrary::new(BootstrapConfig::default())));
+        let (tx, _rx) = mpsc::channel(100);
+        let gateway = Gateway::new(tx, bootstrap, GatewayConfig::default());
+        let command = || InputSignal::Command {
+            command: SystemCommand::Checkpoint { label: Some("manual".to_string()) },
+            args: Vec::new(),
+        };
+
+        // Not configured
+        let (_, rx) = gateway.inject(command()).await.unwrap();
+        assert!(!rx.await.unwrap().success);
+
+        let dir = tempfile::tempdir().unwrap();
+        let manager = Arc::new(CheckpointManager::new(CheckpointConfig {
+            root: dir.path().to_path_buf(),
+            keep_last: 0,
+        }));
+        manager.register(Arc::new(RuntimeStorage::new())).unwrap();
+        gateway.set_checkpoint_manager(manager.clone());
+
+        let (_, rx) = gateway.inject(command()).await.unwrap();
+        let result = rx.await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.output["label"], "manual");
+        assert_eq!(manager.list().unwrap().len(), 1);
+        assert_eq!(gateway.pending_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_sleep_command() {
+        use crate::bootstrap::BootstrapConfig;
+        use crate::consolidation::ConsolidationConfig;
+        use crate::experience_stream::ExperienceStream;
+        use crate::runtime_storage::RuntimeStorage;
+        let bootstrap = Arc::new(RwLock::new(BootstrapLibrary::new(BootstrapConfig::default())));
+        let (tx, _rx) = mpsc::channel(100);
+        let gateway = Gateway::new(tx, bootstrap, GatewayConfig::default());
+        let command = || InputSignal::Command { command: SystemCommand::Sleep, args: Vec::new() };
+
+        // Not configured
+        let (_, rx) = gateway.inject(command()).await.unwrap();
+        assert!(!rx.await.unwrap().success);
+
+        let consolidator = Arc::new(Consolidator::new(
+            ConsolidationConfig::default(),
+            Arc::new(RuntimeStorage::new()),
+            Arc::new(ExperienceStream::new(16, 16)),
+        ));
+        gateway.set_consolidator(consolidator.clone());
+
+        let (_, rx) = gateway.inject(command()).await.unwrap();
+        let result = rx.await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.output["trigger"], "Command");
+        assert_eq!(consolidator.stats().passes, 1);
+
+        // Other signals reset the idle clock
+        gateway.inject(InputSignal::DirectState { state: [0.1; 8], label: None }).await.unwrap();
+        assert!(consolidator.idle_for() < std::time::Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_tick_temporal_state() {
+        use crate::bootstrap::BootstrapConfig;
+        let bootstrap = Arc::new(RwLock::new(BootstrapLibrary::new(BootstrapConfig::default())));
+        let (tx, mut rx) = mpsc::channel(100);
+        // UTC-6: 2024-01-01 12:00 UTC is 06:00 local
+        let config = GatewayConfig { utc_offset_minutes: -360, ..Default::default() };
+        let gateway = Gateway::new(tx, bootstrap, config);
+
+        gateway
+            .inject(InputSignal::SystemTick { tick_number: 1, timestamp: 1_704_110_400_000 })
+            .await
+            .unwrap();
+        let processed = rx.recv().await.unwrap();
+        let temporal = processed.metadata.temporal.unwrap();
+        assert!((temporal.hour() - 6.0).abs() < 1e-3);
+        assert_eq!(processed.state, temporal.state());
+        assert!(processed.state[temporal::TEMPORAL_DIM] > 0.9);
+        assert_eq!(gateway.stats().tick_signals, 1);
+    }
+
+    #[tokio::test]
+    async fn test_source_trust() {
+        use crate::bootstrap::BootstrapConfig;
+        let bootstrap = Arc::new(RwLock::new(BootstrapLibrary::new(BootstrapConfig::default())));
+        let (tx, mut rx) = mpsc::channel(100);
+        let gateway = Gateway::new(tx, bootstrap, GatewayConfig::default());
+        let state = || InputSignal::DirectState { state: [0.1; 8], label: None };
+
+        gateway.inject(state()).await.unwrap();
+        let processed = rx.recv().await.unwrap();
+        assert_eq!(processed.interpretation_confidence, 1.0);
+        assert_eq!(processed.metadata.source_trust, 1.0);
+
+        // DirectState arrives as Console input
+        let mut config = gateway.config();
+        config.source_trust.insert(SignalSource::Console, 0.25);
+        config.max_text_length = 1;
+        gateway.update_config(config).unwrap();
+        assert_eq!(gateway.config().max_text_length, GatewayConfig::default().max_text_length);
+
+        gateway.inject(state()).await.unwrap();
+        let processed = rx.recv().await.unwrap();
+        assert_eq!(processed.interpretation_confidence, 0.25);
+        assert_eq!(processed.metadata.source_trust, 0.25);
+
+        let mut invalid = gateway.config();
+        invalid.source_trust.insert(SignalSource::Mqtt, 2.0);
+        assert!(gateway.update_config(invalid).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_command_policy() {
+        use crate::bootstrap::BootstrapConfig;
+        let bootstrap = Arc::new(RwLock::new(BootstrapLibrary::new(BootstrapConfig::default())));
+        let (tx, _rx) = mpsc::channel(100);
+        let gateway = Gateway::new(tx, bootstrap, GatewayConfig::default());
+        let guardian = Arc::new(RwLock::new(Guardian::new()));
+        gateway.set_guardian(guardian.clone());
+        let text = |content: &str| InputSignal::Text {
+            content: content.to_string(),
+            source: SignalSource::RestApi,
+            metadata: None,
+        };
+
+        // Command text runs as a structured command
+        let (_, rx) = gateway.inject(text("/checkpoint manual")).await.unwrap();
+        let result = rx.await.unwrap();
+        assert_eq!(result.error.as_deref(), Some("Checkpointing is not configured"));
+
+        for rejected in ["/shutdown", "/cancel x", "tell it to /reset now"] {
+            assert!(matches!(gateway.inject(text(rejected)).await, Err(GatewayError::InvalidCommand(_))));
+        }
+        let shutdown = InputSignal::Command { command: SystemCommand::Shutdown, args: Vec::new() };
+        assert!(gateway.inject(shutdown).await.is_err());
+        assert_eq!(gateway.stats().rejected_commands, 4);
+
+        let events = guardian.read().pending_events().clone();
+        let count = |t: GuardianEventType| events.iter().filter(|e| e.event_type == t).count();
+        assert_eq!(count(GuardianEventType::CommandExecuted), 1);
+        assert_eq!(count(GuardianEventType::CommandRejected), 4);
+
+        // The allowlist is a live setting
+        let mut config = gateway.config();
+        config.commands.allowed.push("shutdown".to_string());
+        gateway.update_config(config).unwrap();
+        assert!(gateway.inject(text("/shutdown")).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_mute_command() {
+        use crate::bootstrap::BootstrapConfig;
+        let bootstrap = Arc::new(RwLock::new(BootstrapLibrary::new(BootstrapConfig::default())));
+        let (tx, _rx) = mpsc::channel(100);
+        let gateway = Gateway::new(tx, bootstrap, GatewayConfig::default());
+        let switch = gateway.mute_switch();
+        let text = |content: &str| InputSignal::Text {
+            content: content.to_string(),
+            source: SignalSource::Console,
+            metadata: None,
+        };
+
+        let (_, rx) = gateway.inject(text("/mute")).await.unwrap();
+        assert_eq!(rx.await.unwrap().output["muted"], true);
+        assert!(switch.load(Ordering::Relaxed));
+
+        let (_, rx) = gateway.inject(text(":unmute")).await.unwrap();
+        assert_eq!(rx.await.unwrap().output["muted"], false);
+        assert!(!switch.load(Ordering::Relaxed));
+    }
+
+    #[tokio::test]
+    async fn test_safe_mode_commands() {
+        use crate::bootstrap::BootstrapConfig;
+        let bootstrap = Arc::new(RwLock::new(BootstrapLibrary::new(BootstrapConfig::default())));
+        let (tx, _rx) = mpsc::channel(100);
+        let safe_mode = Arc::new(SafeMode::new());
+        let mut config = GatewayConfig::default();
+        config.commands.allowed.push("resume".to_string());
+        let gateway = Gateway::new(tx.clone(), bootstrap.clone(), config).with_safe_mode(safe_mode.clone());
+        let learner = crate::learner::Learner::new(Default::default()).with_safe_mode(safe_mode.clone());
+        // Another instance has its own switch
+        let other = Gateway::new(tx, bootstrap, GatewayConfig::default());
+        learner.add_edge(1, 0.5);
+        let text = |content: &str| InputSignal::Text {
+            content: content.to_string(),
+            source: SignalSource::Console,
+            metadata: None,
+        };
+        let tick = || InputSignal::SystemTick { tick_number: 1, timestamp: 0 };
+        let query = || InputSignal::DirectState { state: [0.1; 8], label: None };
+
+        let (_, rx) = gateway.inject(text("/safe_mode runaway writes")).await.unwrap();
+        let status = rx.await.unwrap().output;
+        assert_eq!(status["engaged"], true);
+        assert_eq!(status["reason"], "runaway writes");
+        assert!(safe_mode.is_engaged());
+        assert_eq!(learner.learn(1, 1.0, 1.0), None);
+        assert!(!other.safe_mode().is_engaged());
+        assert!(other.inject(tick()).await.is_ok());
+
+        // Queries and read-only commands only
+        assert!(gateway.inject(query()).await.is_ok());
+        assert!(gateway.inject(text("/status")).await.is_ok());
+        assert!(matches!(gateway.inject(tick()).await, Err(GatewayError::SafeMode(_))));
+        assert!(matches!(gateway.inject(text("/checkpoint")).await, Err(GatewayError::SafeMode(_))));
+        assert_eq!(gateway.stats().rejected_commands, 1);
+
+        let (_, rx) = gateway.inject(text("/safe_mode")).await.unwrap();
+        assert_eq!(rx.await.unwrap().output["changed"], false);
+
+        // Even when allowlisted, only the console lifts it
+        let remote = InputSignal::Text { content: "/resume".to_string(), source: SignalSource::RestApi, metadata: None };
+        assert!(matches!(gateway.inject(remote).await, Err(GatewayError::InvalidCommand(_))));
+        let structured = InputSignal::Command { command: SystemCommand::Resume, args: Vec::new() };
+        assert!(matches!(gateway.inject(structured).await, Err(GatewayError::InvalidCommand(_))));
+        assert!(safe_mode.is_engaged());
+
+        let (_, rx) = gateway.inject(text("/resume")).await.unwrap();
+        let status = rx.await.unwrap().output;
+        assert_eq!(status["engaged"], false);
+        assert_eq!(status["changed"], true);
+        assert!(gateway.inject(tick()).await.is_ok());
+        assert!(learner.learn(1, 1.0, 1.0).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_read_only() {
+        use crate::bootstrap::BootstrapConfig;
+        let bootstrap = Arc::new(RwLock::new(BootstrapLibrary::new(BootstrapConfig::default())));
+        let (tx, _rx) = mpsc::channel(100);
+        let gateway = Gateway::new(tx, bootstrap, GatewayConfig::default());
+        gateway.set_read_only(true);
+        let text = |content: &str| InputSignal::Text {
+            content: content.to_string(),
+            source: SignalSource::RestApi,
+            metadata: None,
+        };
+
+        assert!(gateway.inject(InputSignal::DirectState { state: [0.1; 8], label: None }).await.is_ok());
+        assert!(gateway.inject(text("/status")).await.is_ok());
+        for command in ["/checkpoint", "/sleep", "/schedule every 1m /status"] {
+            assert!(matches!(gateway.inject(text(command)).await, Err(GatewayError::ReadOnly(_))), "{}", command);
+        }
+        let tick = InputSignal::SystemTick { tick_number: 1, timestamp: 0 };
+        assert!(matches!(gateway.inject(tick).await, Err(GatewayError::ReadOnly(_))));
+        assert_eq!(gateway.stats().rejected_commands, 3);
+        assert!(!gateway.safe_mode().is_engaged());
+    }
+
+    #[tokio::test]
+    async fn test_schedule_commands() {
+        use crate::bootstrap::BootstrapConfig;
+        use crate::scheduler::SchedulerConfig;
+        let bootstrap = Arc::new(RwLock::new(BootstrapLibrary::new(BootstrapConfig::default())));
+        let (tx, _rx) = mpsc::channel(100);
+        let gateway = Gateway::new(tx, bootstrap, GatewayConfig::default());
+        let text = |content: &str| InputSignal::Text {
+            content: content.to_string(),
+            source: SignalSource::Console,
+            metadata: None,
+        };
+
+        let (_, rx) = gateway.inject(text("/schedules")).await.unwrap();
+        assert_eq!(rx.await.unwrap().error.as_deref(), Some("Scheduling is not configured"));
+
+        let scheduler = Arc::new(Scheduler::new(SchedulerConfig::default()).unwrap());
+        gateway.set_scheduler(scheduler.clone());
+        let (_, rx) = gateway.inject(text("/schedule every 1h /status")).await.unwrap();
+        assert_eq!(rx.await.unwrap().output["input"], "/status");
+
+        // Input that would be rejected when due is rejected now
+        for rejected in ["/schedule every 1h /shutdown", "/schedule at 9:00 /schedule every 1m /status"] {
+            assert!(matches!(gateway.inject(text(rejected)).await, Err(GatewayError::InvalidCommand(_))));
+        }
+        let (_, rx) = gateway.inject(text("/schedule every 1s /status")).await.unwrap();
+        assert!(!rx.await.unwrap().success);
+
+        let (_, rx) = gateway.inject(text("/schedules")).await.unwrap();
+        assert_eq!(rx.await.unwrap().output.as_array().unwrap().len(), 1);
+        let (_, rx) = gateway.inject(text("/unschedule 1")).await.unwrap();
+        assert!(rx.await.unwrap().success);
+        assert!(scheduler.is_empty());
+    }
 }
@@ -1,5 +1,6 @@
 use crate::bootstrap::BootstrapLibrary;
-use crate::gateway::config::{GatewayConfig, UnknownWordStrategy};
+use crate::gateway::composition::{self, IdfTable};
+use crate::gateway::config::{CompositionStrategy, GatewayConfig, UnknownWordStrategy};
 use std::sync::Arc;
 use parking_lot::RwLock;
 
@@ -28,55 +29,113 @@ pub enum NormalizationError {
 pub struct Normalizer {
     bootstrap: Arc<RwLock<BootstrapLibrary>>,
     config: GatewayConfig,
+    idf: RwLock<IdfTable>,
 }
 
 impl Normalizer {
     pub fn new(bootstrap: Arc<RwLock<BootstrapLibrary>>, config: GatewayConfig) -> Self {
-        Self { bootstrap, config }
+        Self { bootstrap, config, idf: RwLock::new(IdfTable::default()) }
+    }
+
+    /// Replace IDF weights used by `CompositionStrategy::IdfWeighted`
+    pub fn set_idf(&self, idf: IdfTable) {
+        *self.idf.write() = idf;
+    }
+
+    /// Fit IDF weights on a corpus (one document per item)
+    pub fn fit_idf<'a>(&self, documents: impl IntoIterator<Item = &'a str>) {
+        let idf = {
+            let bootstrap = self.bootstrap.read();
+            IdfTable::fit(documents, |word| bootstrap.get_concept(word).map(|c| c.id))
+        };
+        self.set_idf(idf);
     }
 
     /// Normalize text into state vector
     pub fn normalize_text(&self, text: &str) -> Result<NormalizationResult, NormalizationError> {
-        let words: Vec<&str> = text
+        let words: Vec<String> = text
             .split_whitespace()
-            .filter(|w| !w.is_empty())
+            .map(|w| w.to_lowercase())
             .collect();
 
         if words.is_empty() {
             return Err(NormalizationError::NoWords);
         }
 
-        let word_count = words.len();
+        let mut word_count = words.len();
 
         let bootstrap = self.bootstrap.read();
 
+        let idf = self.idf.read();
+
         let mut states: Vec<[f32; 8]> = Vec::new();
+        let mut weights: Vec<f32> = Vec::new();
         let mut matched_tokens: Vec<(String, u32, f32)> = Vec::new();
         let mut unknown_words: Vec<String> = Vec::new();
 
-        for word in words {
-            let word_lower = word.to_lowercase();
+        // Pending modifier: (factor, last word index it can reach)
+        let mut modifier: Option<(f32, usize)> = None;
+
+        let mut index = 0;
+        while index < words.len() {
+            let word_lower = &words[index];
+
+            if let Some(factor) = self.modifier_factor(word_lower) {
+                let factor = match modifier {
+                    Some((pending, reach)) if index <= reach => pending * factor,
+                    _ => factor,
+                };
+                modifier = Some((factor, index + self.config.modifiers.scope));
+                word_count -= 1;
+                index += 1;
+                continue;
+            }
+
+            // Phrase tokens: the longest multi-word concept starting here wins
+            let (concept, span) = match bootstrap.match_phrase(&words[index..]) {
+                Some((concept, span)) => (Some(concept), span),
+                None => (bootstrap.get_concept(word_lower), 1),
+            };
 
-            if let Some(concept) = bootstrap.get_concept(&word_lower) {
+            if let Some(concept) = concept {
                 // Known word - convert coords to state
-                let state = self.coords_to_state(&concept.coords, concept.id);
+                let mut state = Self::concept_state(&concept.coords, concept.emotion);
+                if let Some((factor, reach)) = modifier.take() {
+                    if index <= reach {
+                        self.apply_modifier(&mut state, factor);
+                    }
+                }
                 states.push(state);
-                matched_tokens.push((word_lower.clone(), concept.id, 1.0));
+                // Stop-word/frequency weight, times IDF for IdfWeighted
+                let idf_weight = match self.config.composition {
+                    CompositionStrategy::IdfWeighted => idf.weight(concept.id),
+                    _ => 1.0,
+                };
+                weights.push(concept.weight * idf_weight);
+                matched_tokens.push((concept.word.clone(), concept.id, 1.0));
+                // A phrase counts as one word
+                word_count -= span - 1;
             } else {
                 // Unknown word - handle according to strategy
-                if let Some(state) = self.handle_unknown_word(&word_lower) {
+                if let Some(state) = self.handle_unknown_word(word_lower) {
                     states.push(state);
+                    weights.push(1.0);
                 }
-                unknown_words.push(word_lower);
+                unknown_words.push(word_lower.clone());
             }
+            index += span;
         }
 
         if states.is_empty() {
-            return Err(NormalizationError::AllUnknown);
+            return Err(if word_count == 0 {
+                NormalizationError::NoWords
+            } else {
+                NormalizationError::AllUnknown
+            });
         }
 
-        // Aggregate multiple states into one
-        let final_state = self.aggregate_states(&states);
+        // Compose multiple states into one
+        let final_state = self.aggregate_states(&states, &weights);
 
         // Calculate confidence based on known/unknown ratio
         let confidence = self.calculate_confidence(&states, word_count);
@@ -89,8 +148,32 @@ impl Normalizer {
         })
     }
 
+    /// Multiplier if `word` is a negator (-1) or intensifier
+    fn modifier_factor(&self, word: &str) -> Option<f32> {
+        let modifiers = &self.config.modifiers;
+        if !modifiers.enabled {
+            return None;
+        }
+        if modifiers.negators.iter().any(|n| n == word) {
+            return Some(-1.0);
+        }
+        modifiers.intensifiers.get(word).copied()
+    }
+
+    /// Scale valence-like dimensions, keeping them in [-1, 1]
+    fn apply_modifier(&self, state: &mut [f32; 8], factor: f32) {
+        for &dim in &self.config.modifiers.valence_dims {
+            state[dim] = (state[dim] * factor).clamp(-1.0, 1.0);
+        }
+    }
+
     /// Convert 3D coordinates to 8D state vector
     fn coords_to_state(&self, coords: &[f32; 3], _token_id: u32) -> [f32; 8] {
+        Self::concept_state(coords, None)
+    }
+
+    /// 8D state of a single concept
+    pub(crate) fn concept_state(coords: &[f32; 3], emotion: Option<[f32; 3]>) -> [f32; 8] {
         let mut state = [0.0; 8];
 
         // L1 Physical: Use coords directly
@@ -98,9 +181,8 @@ impl Normalizer {
         state[1] = coords[1];
         state[2] = coords[2];
 
-        // L4 Emotional: Could be enriched with emotion data if available
-        // For now, leave at 0.0
-        state[3] = 0.0;
+        // L4 Emotional: valence from the emotion lexicon (VAD), 0.0 if none
+        state[3] = emotion.map_or(0.0, |vad| vad[0]);
 
         // L5 Social: Not used yet
         state[4] = 0.0;
@@ -145,26 +227,9 @@ impl Normalizer {
         None
     }
 
-    /// Aggregate multiple states into single state (centroid)
-    fn aggregate_states(&self, states: &[[f32; 8]]) -> [f32; 8] {
-        if states.is_empty() {
-            return [0.0; 8];
-        }
-
-        let mut result = [0.0; 8];
-        let count = states.len() as f32;
-
-        for state in states {
-            for (i, val) in state.iter().enumerate() {
-                result[i] += val;
-            }
-        }
-
-        for val in result.iter_mut() {
-            *val /= count;
-        }
-
-        result
+    /// Aggregate multiple states into single state using the configured strategy
+    fn aggregate_states(&self, states: &[[f32; 8]], weights: &[f32]) -> [f32; 8] {
+        composition::compose(self.config.composition, states, weights)
     }
 
     /// Calculate confidence based on known/unknown ratio
@@ -253,8 +318,121 @@ mod tests {
             [0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
         ];
 
-        let result = normalizer.aggregate_states(&states);
+        let result = normalizer.aggregate_states(&states, &[]);
         assert_eq!(result[0], 0.5);
         assert_eq!(result[1], 0.5);
     }
+
+    fn emotion_normalizer(config: GatewayConfig) -> Normalizer {
+        use crate::bootstrap::BootstrapConfig;
+        let json = r#"[
+            {"word": "happy", "id": 1, "coords": [0.1, 0.2, 0.0]},
+            {"word": "sad", "id": 2, "coords": [0.1, 0.2, 0.0]},
+            {"word": "calm", "id": 3, "coords": [0.3, 0.1, 0.0]},
+            {"word": "dog", "id": 4, "coords": [0.9, 0.9, 0.0]}
+        ]"#;
+        let mut library = BootstrapLibrary::new(BootstrapConfig::default());
+        library.load_bootstrap_map_str(json).unwrap();
+        assert_eq!(library.add_emotion_anchors(), 3);
+        Normalizer::new(Arc::new(RwLock::new(library)), config)
+    }
+
+    #[test]
+    fn test_negation_flips_valence() {
+        let normalizer = emotion_normalizer(GatewayConfig::default());
+
+        let happy = normalizer.normalize_text("happy").unwrap();
+        let not_happy = normalizer.normalize_text("not happy").unwrap();
+        let sad = normalizer.normalize_text("sad").unwrap();
+
+        assert!((happy.state[3] - 0.8).abs() < 1e-6);
+        assert!((not_happy.state[3] + 0.8).abs() < 1e-6);
+        assert!(sad.state[3] < 0.0);
+        // Modifier words are neither concepts nor unknown words
+        assert_eq!(not_happy.matched_tokens.len(), 1);
+        assert!(not_happy.unknown_words.is_empty());
+        assert_eq!(not_happy.confidence, 1.0);
+
+        // Only the next concept is negated, and only within scope
+        let mixed = normalizer.normalize_text("not sad dog").unwrap();
+        assert!((mixed.state[3] - 0.35).abs() < 1e-6);
+        let out_of_scope = normalizer.normalize_text("never x y z w happy").unwrap();
+        assert!(out_of_scope.state[3] > 0.0);
+    }
+
+    #[test]
+    fn test_intensifiers_scale_valence() {
+        let normalizer = emotion_normalizer(GatewayConfig::default());
+
+        let calm = normalizer.normalize_text("calm").unwrap().state[3];
+        let very_calm = normalizer.normalize_text("very calm").unwrap().state[3];
+        let slightly_calm = normalizer.normalize_text("slightly calm").unwrap().state[3];
+        assert!((very_calm - calm * 1.5).abs() < 1e-6);
+        assert!((slightly_calm - calm * 0.5).abs() < 1e-6);
+
+        // Clamped to [-1, 1]; combined with negation
+        assert_eq!(normalizer.normalize_text("extremely happy").unwrap().state[3], 1.0);
+        assert_eq!(normalizer.normalize_text("not very happy").unwrap().state[3], -1.0);
+
+        // Non-emotion concepts carry no valence to modify
+        assert_eq!(normalizer.normalize_text("not dog").unwrap().state[3], 0.0);
+    }
+
+    #[test]
+    fn test_phrase_tokens() {
+        use crate::bootstrap::BootstrapConfig;
+        let json = r#"[
+            {"word": "new", "id": 1, "coords": [0.1, 0.0, 0.0]},
+            {"word": "york", "id": 2, "coords": [0.2, 0.0, 0.0]},
+            {"word": "new_york", "id": 3, "coords": [0.9, 0.5, 0.0]},
+            {"word": "happy", "id": 4, "coords": [0.3, 0.0, 0.0]}
+        ]"#;
+        let mut library = BootstrapLibrary::new(BootstrapConfig::default());
+        library.load_bootstrap_map_str(json).unwrap();
+        library.add_emotion_anchors();
+        let mut config = GatewayConfig::default();
+        config.unknown_word_strategy = UnknownWordStrategy::Ignore;
+        let normalizer = Normalizer::new(Arc::new(RwLock::new(library)), config);
+
+        let result = normalizer.normalize_text("not happy in New York").unwrap();
+        let words: Vec<&str> = result.matched_tokens.iter().map(|(w, _, _)| w.as_str()).collect();
+        assert_eq!(words, vec!["happy", "new_york"]);
+        assert_eq!(result.unknown_words, vec!["in".to_string()]);
+        assert!((result.confidence - 2.0 / 3.0).abs() < 1e-6);
+        assert!((result.state[0] - 0.6).abs() < 1e-6);
+
+        // Single words still resolve on their own
+        let new = normalizer.normalize_text("new").unwrap();
+        assert_eq!(new.matched_tokens[0].1, 1);
+    }
+
+    #[test]
+    fn test_stop_words_do_not_dominate() {
+        use crate::bootstrap::BootstrapConfig;
+        let json = r#"[
+            {"word": "the", "id": 1, "coords": [-5.0, 0.0, 0.0]},
+            {"word": "cat", "id": 2, "coords": [1.0, 0.0, 0.0]}
+        ]"#;
+        let config = BootstrapConfig { stop_words: vec!["the".to_string()], ..Default::default() };
+        let mut library = BootstrapLibrary::new(config);
+        library.load_bootstrap_map_str(json).unwrap();
+        let normalizer = Normalizer::new(Arc::new(RwLock::new(library)), GatewayConfig::default());
+
+        let state = normalizer.normalize_text("the cat").unwrap().state;
+        let expected = (-5.0 * 0.05 + 1.0) / 1.05;
+        assert!((state[0] - expected).abs() < 1e-6);
+        assert!(state[0] > 0.0);
+    }
+
+    #[test]
+    fn test_modifiers_disabled() {
+        let mut config = GatewayConfig::default();
+        config.modifiers.enabled = false;
+        config.unknown_word_strategy = UnknownWordStrategy::Ignore;
+        let normalizer = emotion_normalizer(config);
+
+        let result = normalizer.normalize_text("not happy").unwrap();
+        assert!((result.state[3] - 0.8).abs() < 1e-6);
+        assert_eq!(result.unknown_words, vec!["not".to_string()]);
+    }
 }
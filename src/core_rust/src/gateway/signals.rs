@@ -1,9 +1,12 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use super::temporal::TemporalContext;
+use crate::correlation::CorrelationId;
+use crate::scheduler::Recurrence;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Source of the input signal
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SignalSource {
     Console,
     RestApi,
@@ -11,6 +14,9 @@ pub enum SignalSource {
     InternalTimer,
     InternalCuriosity,
     File,
+    Mqtt,
+    /// Transcribed microphone speech
+    Voice,
     Unknown,
 }
 
@@ -33,6 +39,28 @@ pub enum SystemCommand {
     Reset,
     SetConfig,
     Shutdown,
+    /// Abort a pending request by its signal ID
+    Cancel { signal_id: u64 },
+    /// Write a whole-system checkpoint
+    Checkpoint { label: Option<String> },
+    /// Request an evolution attempt (subject to cooldown)
+    Evolve,
+    /// Run a sleep-phase consolidation pass
+    Sleep,
+    /// Silence speech output
+    Mute,
+    /// Resume speech output
+    Unmute,
+    /// Register a recurring intent
+    Schedule { recurrence: Recurrence, input: String },
+    /// Delete a scheduled task
+    Unschedule { id: u64 },
+    /// List scheduled tasks
+    Schedules,
+    /// Freeze learning and actions; only queries stay active
+    SafeMode { reason: Option<String> },
+    /// Leave safe mode
+    Resume,
 }
 
 /// Feedback type
@@ -54,6 +82,7 @@ pub enum InputSignal {
     },
     SystemTick {
         tick_number: u64,
+        /// Unix milliseconds; 0 uses the arrival time
         timestamp: u64,
     },
     DirectToken {
@@ -75,6 +104,29 @@ pub enum InputSignal {
     },
 }
 
+impl InputSignal {
+    /// Correlation ID passed in Text metadata (`{"correlation_id": "<hex>"}`)
+    pub fn correlation_id(&self) -> Option<CorrelationId> {
+        match self {
+            InputSignal::Text { metadata: Some(metadata), .. } => metadata
+                .get("correlation_id")
+                .and_then(Value::as_str)
+                .and_then(CorrelationId::parse),
+            _ => None,
+        }
+    }
+
+    /// Target instance named in Text metadata (`{"instance": "<id>"}`)
+    pub fn instance(&self) -> Option<&str> {
+        match self {
+            InputSignal::Text { metadata: Some(metadata), .. } => metadata
+                .get(crate::instance::INSTANCE_METADATA_KEY)
+                .and_then(Value::as_str),
+            _ => None,
+        }
+    }
+}
+
 /// Type of processed signal - semantic interpretation
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SignalType {
@@ -93,6 +145,16 @@ pub struct ProcessedMetadata {
     pub matched_tokens: Vec<(String, u32, f32)>, // (word, token_id, confidence)
     pub unknown_words: Vec<String>,
     pub processing_time_ns: u64,
+    /// Wall-clock context of tick signals
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temporal: Option<TemporalContext>,
+    /// Trust of the signal source (`GatewayConfig::source_trust`)
+    #[serde(default = "full_trust")]
+    pub source_trust: f32,
+}
+
+fn full_trust() -> f32 {
+    1.0
 }
 
 impl Default for ProcessedMetadata {
@@ -102,6 +164,8 @@ impl Default for ProcessedMetadata {
             matched_tokens: Vec::new(),
             unknown_words: Vec::new(),
             processing_time_ns: 0,
+            temporal: None,
+            source_trust: 1.0,
         }
     }
 }
@@ -118,6 +182,9 @@ pub struct ProcessedSignal {
     pub related_tokens: Vec<u32>,
     pub interpretation_confidence: f32,
     pub metadata: ProcessedMetadata,
+    /// Interaction this signal starts (see `correlation`)
+    #[serde(default = "CorrelationId::generate")]
+    pub correlation_id: CorrelationId,
 }
 
 impl ProcessedSignal {
@@ -142,9 +209,15 @@ impl ProcessedSignal {
             related_tokens: Vec::new(),
             interpretation_confidence: 1.0,
             metadata: ProcessedMetadata::default(),
+            correlation_id: CorrelationId::generate(),
         }
     }
 
+    pub fn with_correlation_id(mut self, correlation_id: CorrelationId) -> Self {
+        self.correlation_id = correlation_id;
+        self
+    }
+
     pub fn with_metadata(mut self, metadata: ProcessedMetadata) -> Self {
         self.metadata = metadata;
         self
@@ -36,8 +36,15 @@ pub struct GatewayStats {
     /// Timeouts (requests that didn't complete in time)
     pub timeouts: u64,
 
+    /// Requests aborted via SystemCommand::Cancel
+    pub cancelled: u64,
+
     /// Errors during processing
     pub errors: u64,
+
+    /// Commands refused by the command policy
+    #[serde(default)]
+    pub rejected_commands: u64,
 }
 
 impl GatewayStats {
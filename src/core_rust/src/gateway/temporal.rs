@@ -0,0 +1,149 @@
+//! Temporal context from the wall clock
+//!
+//! System ticks carry the time of day and the day of week into the state
+//! space, so time-dependent patterns ("asks about the weather in the
+//! morning") can be learned. Both cycles are encoded as sin/cos pairs:
+//! 23:59 lands next to 00:00 and Sunday next to Monday.
+//!
+//! Both cycles live in the L7 Temporal space. Its X, Y, Z axes hold the
+//! two circles as a torus: the time of day goes around the ring, the day
+//! of week around the tube. So all four sin/cos components are recoverable,
+//! and no other layer is touched. The 8D tick state carries L7 X, following
+//! the X-axis convention of `Token::from_state_f32`. [`TemporalContext::token`]
+//! gives the full L7 coordinates, and `ProcessedMetadata::temporal` carries
+//! the raw encoding.
+
+use crate::token::{CoordinateSpace, Token};
+use serde::{Deserialize, Serialize};
+use std::f32::consts::TAU;
+
+/// L7 Temporal dimension of the 8D state
+pub const TEMPORAL_DIM: usize = 6;
+
+/// Torus radii in L7: ring (time of day) and tube (day of week); |x|, |y|, |z| <= 1
+const DAY_RADIUS: f32 = 2.0 / 3.0;
+const WEEK_RADIUS: f32 = 1.0 / 3.0;
+
+const SECS_PER_DAY: i64 = 86_400;
+const SECS_PER_WEEK: i64 = 7 * SECS_PER_DAY;
+
+/// 1970-01-01 was a Thursday; shift so weeks start on Monday
+const EPOCH_WEEKDAY_OFFSET: i64 = 3 * SECS_PER_DAY;
+
+/// Cyclic encoding of a point in time
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TemporalContext {
+    pub day_sin: f32,
+    pub day_cos: f32,
+    pub week_sin: f32,
+    pub week_cos: f32,
+}
+
+impl TemporalContext {
+    /// Encode a Unix time in milliseconds as local time at `utc_offset_minutes`
+    pub fn from_unix_ms(timestamp_ms: u64, utc_offset_minutes: i32) -> Self {
+        let local = (timestamp_ms / 1000) as i64 + utc_offset_minutes as i64 * 60;
+        let day_phase = local.rem_euclid(SECS_PER_DAY) as f32 / SECS_PER_DAY as f32;
+        let week_phase = (local + EPOCH_WEEKDAY_OFFSET).rem_euclid(SECS_PER_WEEK) as f32 / SECS_PER_WEEK as f32;
+        Self {
+            day_sin: (TAU * day_phase).sin(),
+            day_cos: (TAU * day_phase).cos(),
+            week_sin: (TAU * week_phase).sin(),
+            week_cos: (TAU * week_phase).cos(),
+        }
+    }
+
+    /// Hours since local midnight [0, 24)
+    pub fn hour(&self) -> f32 {
+        self.day_sin.atan2(self.day_cos).rem_euclid(TAU) / TAU * 24.0
+    }
+
+    /// Days since local Monday 00:00 [0, 7)
+    pub fn weekday(&self) -> f32 {
+        self.week_sin.atan2(self.week_cos).rem_euclid(TAU) / TAU * 7.0
+    }
+
+    /// L7 Temporal (X, Y, Z): the (day, week) torus
+    pub fn coordinates(&self) -> [f32; 3] {
+        let ring = DAY_RADIUS + WEEK_RADIUS * self.week_cos;
+        [ring * self.day_sin, ring * self.day_cos, WEEK_RADIUS * self.week_sin]
+    }
+
+    /// 8D state of a tick at this time (L7 X, everything else zero)
+    pub fn state(&self) -> [f32; 8] {
+        let mut state = [0.0; 8];
+        state[TEMPORAL_DIM] = self.coordinates()[0];
+        state
+    }
+
+    /// Token of a tick at this time with all three L7 axes set
+    pub fn token(&self, id: u32) -> Token {
+        let mut token = Token::from_state_f32(id, &self.state());
+        let [x, y, z] = self.coordinates();
+        token.set_coordinates(CoordinateSpace::L7Temporal, x, y, z);
+        token
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 2024-01-01 (Monday) 06:00:00 UTC
+    const MONDAY_6AM_MS: u64 = 1_704_088_800_000;
+
+    /// Distance between two points in L7
+    fn distance(a: &TemporalContext, b: &TemporalContext) -> f32 {
+        let (a, b) = (a.coordinates(), b.coordinates());
+        a.iter().zip(&b).map(|(x, y)| (x - y).powi(2)).sum::<f32>().sqrt()
+    }
+
+    #[test]
+    fn test_cyclic_encoding() {
+        let context = TemporalContext::from_unix_ms(MONDAY_6AM_MS, 0);
+        assert!((context.hour() - 6.0).abs() < 1e-3);
+        assert!((context.weekday() - 0.25).abs() < 1e-3);
+        assert!((context.day_sin - 1.0).abs() < 1e-6);
+
+        // Only L7 is written; the state is its X axis
+        let state = context.state();
+        assert_eq!(state[TEMPORAL_DIM], context.coordinates()[0]);
+        assert!(state.iter().enumerate().all(|(i, &v)| i == TEMPORAL_DIM || v == 0.0));
+
+        // Noon and midnight differ in L7 although they share the sine
+        let noon = TemporalContext::from_unix_ms(MONDAY_6AM_MS + 6 * 3_600_000, 0);
+        let midnight = TemporalContext::from_unix_ms(MONDAY_6AM_MS - 6 * 3_600_000, 0);
+        assert!((noon.day_sin - midnight.day_sin).abs() < 1e-5);
+        assert!(distance(&noon, &midnight) > 1.0);
+
+        // Same time of day on another weekday differs on the week circle
+        let friday = TemporalContext::from_unix_ms(MONDAY_6AM_MS + 4 * 86_400_000, 0);
+        assert!((friday.hour() - context.hour()).abs() < 1e-3);
+        assert!((friday.weekday() - 4.25).abs() < 1e-3);
+        assert!(distance(&friday, &context) > 0.3);
+
+        // UTC+3: 09:00 local
+        let local = TemporalContext::from_unix_ms(MONDAY_6AM_MS, 180);
+        assert!((local.hour() - 9.0).abs() < 1e-3);
+
+        // Midnight wraps around: 23:59 is close to 00:00, and Sunday to Monday
+        let before = TemporalContext::from_unix_ms(MONDAY_6AM_MS - 6 * 3_600_000 - 60_000, 0);
+        let after = TemporalContext::from_unix_ms(MONDAY_6AM_MS - 6 * 3_600_000, 0);
+        let day_distance = (before.day_sin - after.day_sin).hypot(before.day_cos - after.day_cos);
+        let week_distance = (before.week_sin - after.week_sin).hypot(before.week_cos - after.week_cos);
+        assert!(day_distance < 0.01);
+        assert!(week_distance < 0.01);
+        assert!(distance(&before, &after) < 0.01);
+    }
+
+    #[test]
+    fn test_token_coordinates() {
+        let context = TemporalContext::from_unix_ms(MONDAY_6AM_MS + 2 * 86_400_000, 0);
+        let token = context.token(7);
+        let [x, y, z] = token.get_coordinates(CoordinateSpace::L7Temporal);
+        let [ex, ey, ez] = context.coordinates();
+        assert!((x - ex).abs() < 0.01 && (y - ey).abs() < 0.01 && (z - ez).abs() < 0.01);
+        assert_eq!(token.get_coordinates(CoordinateSpace::L5Cognitive), [0.0; 3]);
+        assert_eq!(token.get_coordinates(CoordinateSpace::L8Abstract), [0.0; 3]);
+    }
+}
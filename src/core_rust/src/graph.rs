@@ -33,6 +33,7 @@
 /// - Traversal: BFS, DFS
 /// - Pathfinding: shortest_path (BFS), dijkstra
 /// - Subgraphs: extract_subgraph, extract_neighborhood
+/// - Export: RDF Turtle, GraphML (`export`)
 ///
 /// # Memory Layout
 ///
@@ -44,6 +45,15 @@
 
 use std::collections::{HashMap, HashSet, VecDeque, BinaryHeap};
 use std::cmp::Ordering;
+use crate::connection_v3::{active_levels, guess_mutability, ConnectionMutability};
+use crate::edge_history::{EdgeHistory, WeightSource};
+use crate::guardian::{Event as GuardianEvent, EventType as GuardianEventType, Guardian};
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+pub mod export;
+pub mod mapped;
+pub mod sharded;
 
 /// Node identifier (Token.id)
 pub type NodeId = u32;
@@ -67,6 +77,7 @@ pub struct EdgeInfo {
     pub edge_type: u8,      // Connection type
     pub weight: f32,        // Connection weight (for pathfinding)
     pub bidirectional: bool, // Whether edge can be traversed both ways
+    pub active_levels: u8,  // L1-L8 bitmask (connection_v3::active_levels), ALL by default
 }
 
 /// Path through the graph
@@ -153,10 +164,18 @@ impl Subgraph {
 /// Graph configuration
 #[derive(Debug, Clone)]
 pub struct GraphConfig {
-    /// Enable edge deduplication (slower insert, less memory)
+    /// Enable edge deduplication (slower insert, less memory): an edge
+    /// between the same two nodes with the same type, in either direction,
+    /// is merged into the existing one (see `Graph::canonicalize`)
     pub deduplicate_edges: bool,
     /// Pre-allocate capacity for nodes
     pub initial_capacity: usize,
+    /// Maximum number of nodes (None = unbounded)
+    pub max_nodes: Option<usize>,
+    /// Maximum edges touching one node, incoming + outgoing (None = unbounded)
+    pub max_edges_per_node: Option<usize>,
+    /// Which edges give way when a limit is reached
+    pub eviction: EvictionPolicy,
 }
 
 impl Default for GraphConfig {
@@ -164,10 +183,88 @@ impl Default for GraphConfig {
         Self {
             deduplicate_edges: false,
             initial_capacity: 1000,
+            max_nodes: None,
+            max_edges_per_node: None,
+            eviction: EvictionPolicy::default(),
         }
     }
 }
 
+/// Which edges may be evicted to stay within GraphConfig limits
+///
+/// Immutable edges are never evicted. Candidates go lowest confidence
+/// (edge weight) first; a node is evicted only if all its edges may be.
+/// If nothing may be evicted the insert is refused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EvictionPolicy {
+    /// Never evict, refuse inserts beyond the limits
+    Refuse,
+    /// Evict Hypothesis edges only
+    #[default]
+    Hypothesis,
+    /// Evict Hypothesis edges first, then Learnable ones
+    HypothesisThenLearnable,
+}
+
+impl EvictionPolicy {
+    /// Eviction order of an edge (lower goes first), None if it must be kept
+    fn rank(self, mutability: ConnectionMutability) -> Option<u8> {
+        match (self, mutability) {
+            (EvictionPolicy::Refuse, _) | (_, ConnectionMutability::Immutable) => None,
+            (_, ConnectionMutability::Hypothesis) => Some(0),
+            (EvictionPolicy::HypothesisThenLearnable, ConnectionMutability::Learnable) => Some(1),
+            (EvictionPolicy::Hypothesis, ConnectionMutability::Learnable) => None,
+        }
+    }
+}
+
+/// Duplicate edges folded into one
+#[derive(Debug, Clone, PartialEq)]
+pub struct EdgeMerge {
+    /// Surviving edge (common direction, lower node ID first if bidirectional)
+    pub kept: EdgeId,
+    /// Duplicates folded into it
+    pub removed: Vec<EdgeId>,
+    pub from_id: NodeId,
+    pub to_id: NodeId,
+    pub edge_type: u8,
+    /// Highest confidence (weight) of the merged edges
+    pub weight: f32,
+    /// Summed evidence of the merged edges
+    pub evidence: u32,
+    /// Duplicates pointed both ways, so the survivor is bidirectional
+    pub bidirectional: bool,
+}
+
+/// Duplicate edge audit (`Graph::audit_duplicates`) or pass (`Graph::canonicalize`)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DedupReport {
+    pub edges_before: usize,
+    pub edges_after: usize,
+    pub merges: Vec<EdgeMerge>,
+}
+
+impl DedupReport {
+    /// Number of duplicate edges
+    pub fn duplicates(&self) -> usize {
+        self.merges.iter().map(|m| m.removed.len()).sum()
+    }
+
+    /// No two edges share endpoints and type
+    pub fn is_canonical(&self) -> bool {
+        self.merges.is_empty()
+    }
+}
+
+/// Limit enforcement counters (also exported as `neurograph_graph_*` metrics)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphLimitStats {
+    pub edges_evicted: u64,
+    pub nodes_evicted: u64,
+    pub nodes_refused: u64,
+    pub edges_refused: u64,
+}
+
 /// State for Dijkstra's algorithm priority queue
 #[derive(Debug, Clone)]
 struct DijkstraState {
@@ -256,12 +353,19 @@ impl NodeActivation {
     }
 
     /// Get current timestamp in microseconds
+    #[cfg(not(all(target_arch = "wasm32", feature = "wasm")))]
     fn current_timestamp_us() -> u64 {
         std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
             .as_micros() as u64
     }
+
+    /// Get current timestamp in microseconds (browser clock)
+    #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+    fn current_timestamp_us() -> u64 {
+        crate::wasm::unix_time_us()
+    }
 }
 
 /// Configuration for spreading activation algorithm
@@ -277,6 +381,9 @@ pub struct SignalConfig {
     pub activation_threshold: f32,
     /// How to accumulate energy when node receives multiple signals
     pub accumulation_mode: AccumulationMode,
+    /// L1-L8 gating mask: energy only flows along edges whose active_levels
+    /// intersect it (default: active_levels::ALL, no gating)
+    pub layer_mask: u8,
 }
 
 impl Default for SignalConfig {
@@ -287,6 +394,7 @@ impl Default for SignalConfig {
             max_depth: 5,
             activation_threshold: 0.1,
             accumulation_mode: AccumulationMode::Sum,
+            layer_mask: active_levels::ALL,
         }
     }
 }
@@ -306,6 +414,9 @@ impl SignalConfig {
         if self.activation_threshold < 0.0 {
             return Err(format!("activation_threshold must be >= 0.0, got {}", self.activation_threshold));
         }
+        if self.layer_mask == 0 {
+            return Err("layer_mask must enable at least one level".to_string());
+        }
         Ok(())
     }
 }
@@ -387,6 +498,16 @@ pub struct Graph {
     activations: HashMap<NodeId, NodeActivation>,
     /// Spreading activation configuration (SignalSystem v1.0)
     signal_config: SignalConfig,
+    /// Optional per-edge weight history (debugging)
+    weight_history: Option<Arc<EdgeHistory>>,
+    /// Mutability set explicitly; other edges derive it from their type
+    edge_mutability: HashMap<EdgeId, ConnectionMutability>,
+    /// Evidence of edges that absorbed duplicates; others count 1
+    edge_evidence: HashMap<EdgeId, u32>,
+    /// Eviction and refusal counters
+    limit_stats: GraphLimitStats,
+    /// Receives GraphLimitRefused events (optional)
+    guardian: Option<Arc<RwLock<Guardian>>>,
 }
 
 impl Graph {
@@ -405,6 +526,11 @@ impl Graph {
             edge_map: HashMap::new(),
             activations: HashMap::new(),
             signal_config: SignalConfig::default(),
+            weight_history: None,
+            edge_mutability: HashMap::new(),
+            edge_evidence: HashMap::new(),
+            limit_stats: GraphLimitStats::default(),
+            guardian: None,
         }
     }
 
@@ -445,12 +571,23 @@ impl Graph {
     }
 
     /// Add node to graph
-    /// Returns true if node was added, false if already exists
+    /// Returns true if node was added, false if already exists or
+    /// `max_nodes` is reached and no node may be evicted
     pub fn add_node(&mut self, node_id: NodeId) -> bool {
         if self.adjacency_out.contains_key(&node_id) {
             return false;
         }
 
+        if let Some(max_nodes) = self.config.max_nodes {
+            while self.node_count() >= max_nodes {
+                if !self.evict_node() {
+                    self.limit_stats.nodes_refused += 1;
+                    self.refuse("max_nodes", max_nodes, Some(node_id), None);
+                    return false;
+                }
+            }
+        }
+
         self.adjacency_out.insert(node_id, Vec::new());
         self.adjacency_in.insert(node_id, Vec::new());
         true
@@ -499,7 +636,8 @@ impl Graph {
 
     /// Add edge to graph
     /// Both nodes must already exist
-    /// Returns true if edge was added
+    /// Returns true if edge was added; errors if `max_edges_per_node` is
+    /// reached and no edge may be evicted
     pub fn add_edge(
         &mut self,
         edge_id: EdgeId,
@@ -522,6 +660,17 @@ impl Graph {
             return Ok(false);
         }
 
+        if self.config.deduplicate_edges {
+            if let Some(existing) = self.find_duplicate(from_id, to_id, edge_type) {
+                self.absorb_duplicate(existing, from_id, weight, bidirectional);
+                return Ok(false);
+            }
+        }
+
+        if let Some(max_edges) = self.config.max_edges_per_node {
+            self.make_room(from_id, to_id, max_edges)?;
+        }
+
         // Add edge metadata
         let edge_info = EdgeInfo {
             from_id,
@@ -529,21 +678,30 @@ impl Graph {
             edge_type,
             weight,
             bidirectional,
+            active_levels: active_levels::ALL,
         };
-        self.edge_map.insert(edge_id, edge_info);
+        self.insert_edge(edge_id, edge_info);
+        if let Some(history) = &self.weight_history {
+            history.record(edge_id, weight, WeightSource::Created);
+        }
 
+        Ok(true)
+    }
+
+    /// Store an edge whose nodes exist
+    fn insert_edge(&mut self, edge_id: EdgeId, edge_info: EdgeInfo) {
         // Add to adjacency lists
         self.adjacency_out
-            .get_mut(&from_id)
+            .get_mut(&edge_info.from_id)
             .unwrap()
             .push(edge_id);
 
         self.adjacency_in
-            .get_mut(&to_id)
+            .get_mut(&edge_info.to_id)
             .unwrap()
             .push(edge_id);
 
-        Ok(true)
+        self.edge_map.insert(edge_id, edge_info);
     }
 
     /// Remove edge from graph
@@ -558,6 +716,8 @@ impl Graph {
             if let Some(in_edges) = self.adjacency_in.get_mut(&edge_info.to_id) {
                 in_edges.retain(|&e| e != edge_id);
             }
+            self.edge_mutability.remove(&edge_id);
+            self.edge_evidence.remove(&edge_id);
 
             true
         } else {
@@ -575,6 +735,361 @@ impl Graph {
         self.edge_map.get(&edge_id)
     }
 
+    /// Set edge weight
+    /// Returns true if the edge exists
+    pub fn set_edge_weight(&mut self, edge_id: EdgeId, weight: f32) -> bool {
+        self.set_edge_weight_from(edge_id, weight, WeightSource::Manual)
+    }
+
+    /// Set edge weight, recording `source` in the weight history (if enabled)
+    /// Returns true if the edge exists
+    pub fn set_edge_weight_from(&mut self, edge_id: EdgeId, weight: f32, source: WeightSource) -> bool {
+        match self.edge_map.get_mut(&edge_id) {
+            Some(edge) => {
+                edge.weight = weight;
+                if let Some(history) = &self.weight_history {
+                    history.record(edge_id, weight, source);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Record edge weight changes into `history`
+    pub fn set_weight_history(&mut self, history: Arc<EdgeHistory>) {
+        self.weight_history = Some(history);
+    }
+
+    /// Weight history, if recording is enabled
+    pub fn weight_history(&self) -> Option<&Arc<EdgeHistory>> {
+        self.weight_history.as_ref()
+    }
+
+    /// Set the L1-L8 levels an edge is active on (see `SignalConfig::layer_mask`)
+    /// Returns true if the edge exists
+    pub fn set_edge_levels(&mut self, edge_id: EdgeId, levels: u8) -> bool {
+        match self.edge_map.get_mut(&edge_id) {
+            Some(edge) => {
+                edge.active_levels = levels;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Edge mutability: set explicitly, otherwise derived from the edge type
+    pub fn edge_mutability(&self, edge_id: EdgeId) -> Option<ConnectionMutability> {
+        let edge = self.edge_map.get(&edge_id)?;
+        Some(
+            self.edge_mutability
+                .get(&edge_id)
+                .copied()
+                .unwrap_or_else(|| guess_mutability(edge.edge_type)),
+        )
+    }
+
+    /// Set edge mutability (e.g. Hypothesis for learned connections)
+    /// Returns true if the edge exists
+    pub fn set_edge_mutability(&mut self, edge_id: EdgeId, mutability: ConnectionMutability) -> bool {
+        if !self.edge_map.contains_key(&edge_id) {
+            return false;
+        }
+        self.edge_mutability.insert(edge_id, mutability);
+        true
+    }
+
+    /// Observations behind an edge: 1, plus duplicates merged into it
+    pub fn edge_evidence(&self, edge_id: EdgeId) -> Option<u32> {
+        self.edge_map
+            .contains_key(&edge_id)
+            .then(|| self.edge_evidence.get(&edge_id).copied().unwrap_or(1))
+    }
+
+    /// Find edges that share endpoints (in either direction) and type
+    pub fn audit_duplicates(&self) -> DedupReport {
+        let merges: Vec<EdgeMerge> = self
+            .duplicate_groups()
+            .into_iter()
+            .map(|group| self.plan_merge(&group))
+            .collect();
+        let duplicates: usize = merges.iter().map(|m| m.removed.len()).sum();
+        DedupReport {
+            edges_before: self.edge_count(),
+            edges_after: self.edge_count() - duplicates,
+            merges,
+        }
+    }
+
+    /// Merge duplicate edges into one per node pair and type
+    ///
+    /// The survivor keeps the direction the duplicates share; if they
+    /// pointed both ways (or one was bidirectional) it becomes bidirectional
+    /// and goes from the lower to the higher node ID. Its ID is the matching
+    /// `compute_edge_id`. It takes the highest weight, summed evidence, all
+    /// active levels and the most protective mutability.
+    pub fn canonicalize(&mut self) -> DedupReport {
+        let report = self.audit_duplicates();
+        for merge in &report.merges {
+            let group: Vec<EdgeId> = std::iter::once(merge.kept)
+                .chain(merge.removed.iter().copied())
+                .filter(|id| self.edge_map.contains_key(id))
+                .collect();
+            let active_levels = group.iter().fold(0, |levels, id| levels | self.edge_map[id].active_levels);
+            let mutability = group
+                .iter()
+                .filter_map(|&id| self.edge_mutability(id))
+                .min_by_key(|&m| m as u8);
+            for &id in &group {
+                self.remove_edge(id);
+            }
+
+            self.insert_edge(
+                merge.kept,
+                EdgeInfo {
+                    from_id: merge.from_id,
+                    to_id: merge.to_id,
+                    edge_type: merge.edge_type,
+                    weight: merge.weight,
+                    bidirectional: merge.bidirectional,
+                    active_levels,
+                },
+            );
+            if let Some(mutability) = mutability.filter(|&m| m != guess_mutability(merge.edge_type)) {
+                self.edge_mutability.insert(merge.kept, mutability);
+            }
+            self.edge_evidence.insert(merge.kept, merge.evidence);
+            if let Some(history) = &self.weight_history {
+                history.record(merge.kept, merge.weight, WeightSource::Dedup);
+            }
+        }
+        if !report.is_canonical() {
+            tracing::info!(
+                merged = report.merges.len(),
+                removed = report.duplicates(),
+                edges = report.edges_after,
+                "Duplicate graph edges merged"
+            );
+        }
+        report
+    }
+
+    /// Groups of two or more edges per (lower node, higher node, type)
+    fn duplicate_groups(&self) -> Vec<Vec<EdgeId>> {
+        let mut groups: HashMap<(NodeId, NodeId, u8), Vec<EdgeId>> = HashMap::new();
+        for (&edge_id, edge) in &self.edge_map {
+            let key = (edge.from_id.min(edge.to_id), edge.from_id.max(edge.to_id), edge.edge_type);
+            groups.entry(key).or_default().push(edge_id);
+        }
+        let mut duplicates: Vec<(_, Vec<EdgeId>)> = groups.into_iter().filter(|(_, ids)| ids.len() > 1).collect();
+        duplicates.sort_by_key(|(key, _)| *key);
+        duplicates
+            .into_iter()
+            .map(|(_, mut ids)| {
+                ids.sort_unstable();
+                ids
+            })
+            .collect()
+    }
+
+    fn plan_merge(&self, group: &[EdgeId]) -> EdgeMerge {
+        let first = &self.edge_map[&group[0]];
+        let edges: Vec<&EdgeInfo> = group.iter().map(|id| &self.edge_map[id]).collect();
+        let bidirectional = edges.iter().any(|e| e.bidirectional)
+            || edges.iter().any(|e| (e.from_id, e.to_id) != (first.from_id, first.to_id));
+        let (from_id, to_id) = if bidirectional {
+            (first.from_id.min(first.to_id), first.from_id.max(first.to_id))
+        } else {
+            (first.from_id, first.to_id)
+        };
+        let kept = Self::compute_edge_id(from_id, to_id, first.edge_type);
+        EdgeMerge {
+            kept,
+            removed: group.iter().copied().filter(|&id| id != kept).collect(),
+            from_id,
+            to_id,
+            edge_type: first.edge_type,
+            weight: edges.iter().map(|e| e.weight).fold(f32::NEG_INFINITY, f32::max),
+            evidence: group.iter().map(|&id| self.edge_evidence(id).unwrap_or(1)).sum(),
+            bidirectional,
+        }
+    }
+
+    /// Existing edge between the two nodes (either direction) with this type
+    fn find_duplicate(&self, from_id: NodeId, to_id: NodeId, edge_type: u8) -> Option<EdgeId> {
+        let matches = |node_id: NodeId, other: NodeId| {
+            self.adjacency_out.get(&node_id).into_iter().flatten().copied().find(|edge_id| {
+                let edge = &self.edge_map[edge_id];
+                edge.to_id == other && edge.edge_type == edge_type
+            })
+        };
+        matches(from_id, to_id).or_else(|| matches(to_id, from_id))
+    }
+
+    /// Fold a duplicate insert into `existing`
+    fn absorb_duplicate(&mut self, existing: EdgeId, from_id: NodeId, weight: f32, bidirectional: bool) {
+        let evidence = self.edge_evidence(existing).unwrap_or(1) + 1;
+        self.edge_evidence.insert(existing, evidence);
+        let edge = self.edge_map.get_mut(&existing).expect("duplicate edge exists");
+        edge.bidirectional |= bidirectional || edge.from_id != from_id;
+        if weight > edge.weight {
+            edge.weight = weight;
+            if let Some(history) = &self.weight_history {
+                history.record(existing, weight, WeightSource::Dedup);
+            }
+        }
+    }
+
+    /// Emit GraphLimitRefused events to `guardian`
+    pub fn set_guardian(&mut self, guardian: Arc<RwLock<Guardian>>) {
+        self.guardian = Some(guardian);
+    }
+
+    /// Eviction and refusal counters
+    pub fn limit_stats(&self) -> &GraphLimitStats {
+        &self.limit_stats
+    }
+
+    /// Evict edges so both endpoints can take the new edge, or refuse
+    /// without evicting anything
+    fn make_room(&mut self, from_id: NodeId, to_id: NodeId, max_edges: usize) -> Result<(), String> {
+        // A self-loop is both an outgoing and an incoming edge of its node
+        let endpoints: &[NodeId] = if from_id == to_id { &[from_id] } else { &[from_id, to_id] };
+        let added = 3 - endpoints.len();
+
+        let mut victims: Vec<EdgeId> = Vec::new();
+        for &node_id in endpoints {
+            let freed = victims
+                .iter()
+                .filter(|edge_id| {
+                    let edge = &self.edge_map[*edge_id];
+                    edge.from_id == node_id || edge.to_id == node_id
+                })
+                .count();
+            let needed = (self.total_degree(node_id) + added).saturating_sub(max_edges + freed);
+            if needed == 0 {
+                continue;
+            }
+
+            let candidates: Vec<EdgeId> = self
+                .eviction_candidates(node_id)
+                .into_iter()
+                .filter(|edge_id| !victims.contains(edge_id))
+                .take(needed)
+                .collect();
+            if candidates.len() < needed {
+                self.limit_stats.edges_refused += 1;
+                self.refuse("max_edges_per_node", max_edges, Some(node_id), Some((from_id, to_id)));
+                return Err(format!(
+                    "Node {} has reached max_edges_per_node ({}) and no edge may be evicted",
+                    node_id, max_edges
+                ));
+            }
+            victims.extend(candidates);
+        }
+
+        for edge_id in victims {
+            self.remove_edge(edge_id);
+            self.limit_stats.edges_evicted += 1;
+            crate::metrics::GRAPH_EDGES_EVICTED.inc();
+        }
+        Ok(())
+    }
+
+    /// Evictable edges of a node in eviction order
+    fn eviction_candidates(&self, node_id: NodeId) -> Vec<EdgeId> {
+        let mut candidates: Vec<(u8, f32, EdgeId)> = self
+            .incident_edges(node_id)
+            .filter_map(|edge_id| {
+                let rank = self.config.eviction.rank(self.edge_mutability(edge_id)?)?;
+                Some((rank, self.edge_map[&edge_id].weight, edge_id))
+            })
+            .collect();
+        candidates.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)).then(a.2.cmp(&b.2)));
+        candidates.dedup_by_key(|c| c.2);
+        candidates.into_iter().map(|(_, _, edge_id)| edge_id).collect()
+    }
+
+    /// Evict the node whose strongest edge is weakest
+    ///
+    /// Only nodes with edges, all of them evictable, are considered: an
+    /// isolated node is usually about to be connected.
+    /// Returns false if no node may be evicted.
+    fn evict_node(&mut self) -> bool {
+        let mut weakest: Option<((u8, f32, NodeId), usize)> = None;
+        for &node_id in self.adjacency_out.keys() {
+            let mut key = (0u8, f32::NEG_INFINITY, node_id);
+            let mut edges = HashSet::new();
+            let mut evictable = true;
+            for edge_id in self.incident_edges(node_id) {
+                match self.edge_mutability(edge_id).and_then(|m| self.config.eviction.rank(m)) {
+                    Some(rank) => {
+                        key.0 = key.0.max(rank);
+                        key.1 = key.1.max(self.edge_map[&edge_id].weight);
+                        edges.insert(edge_id);
+                    }
+                    None => {
+                        evictable = false;
+                        break;
+                    }
+                }
+            }
+            if !evictable || edges.is_empty() {
+                continue;
+            }
+            let weaker = weakest.is_none_or(|(best, _)| {
+                key.0.cmp(&best.0).then(key.1.total_cmp(&best.1)).then(key.2.cmp(&best.2)) == Ordering::Less
+            });
+            if weaker {
+                weakest = Some((key, edges.len()));
+            }
+        }
+
+        let Some(((_, _, node_id), edges)) = weakest else {
+            return false;
+        };
+        self.remove_node(node_id);
+        self.limit_stats.nodes_evicted += 1;
+        self.limit_stats.edges_evicted += edges as u64;
+        crate::metrics::GRAPH_NODES_EVICTED.inc();
+        crate::metrics::GRAPH_EDGES_EVICTED.inc_by(edges as u64);
+        true
+    }
+
+    /// Outgoing and incoming edges of a node (a self-loop appears twice)
+    fn incident_edges(&self, node_id: NodeId) -> impl Iterator<Item = EdgeId> + '_ {
+        let out = self.adjacency_out.get(&node_id).into_iter().flatten();
+        let incoming = self.adjacency_in.get(&node_id).into_iter().flatten();
+        out.chain(incoming).copied()
+    }
+
+    /// Outgoing + incoming edges, regardless of direction flags
+    fn total_degree(&self, node_id: NodeId) -> usize {
+        self.adjacency_out.get(&node_id).map_or(0, |e| e.len())
+            + self.adjacency_in.get(&node_id).map_or(0, |e| e.len())
+    }
+
+    /// Report a refused insert (log, metric, Guardian event)
+    fn refuse(&self, limit: &str, value: usize, node_id: Option<NodeId>, edge: Option<(NodeId, NodeId)>) {
+        tracing::warn!(limit, value, node = ?node_id, edge = ?edge, "Graph limit reached, insert refused");
+        crate::metrics::GRAPH_LIMIT_REFUSALS.with_label_values(&[limit]).inc();
+        if let Some(guardian) = &self.guardian {
+            let data = serde_json::json!({
+                "limit": limit,
+                "value": value,
+                "eviction": format!("{:?}", self.config.eviction),
+            });
+            let mut event = GuardianEvent::new(GuardianEventType::GraphLimitRefused).with_data(data.to_string());
+            if let Some(node_id) = node_id {
+                event = event.with_token(node_id);
+            }
+            if let Some((from_id, to_id)) = edge {
+                event = event.with_connection(from_id, to_id);
+            }
+            guardian.write().emit_event(event);
+        }
+    }
+
     /// Get number of edges
     pub fn edge_count(&self) -> usize {
         self.edge_map.len()
@@ -655,11 +1170,18 @@ impl Graph {
         self.adjacency_out.keys().copied().collect()
     }
 
+    /// Iterate over all edges
+    pub fn edges(&self) -> impl Iterator<Item = (EdgeId, &EdgeInfo)> {
+        self.edge_map.iter().map(|(&id, info)| (id, info))
+    }
+
     /// Clear all nodes and edges
     pub fn clear(&mut self) {
         self.adjacency_out.clear();
         self.adjacency_in.clear();
         self.edge_map.clear();
+        self.edge_mutability.clear();
+        self.edge_evidence.clear();
     }
 
     // ==================== TRAVERSAL ALGORITHMS ====================
@@ -1068,6 +1590,7 @@ impl Graph {
     ///
     /// Uses BFS with energy decay to propagate activation through the graph.
     /// Energy decreases with each hop based on edge weights and decay rate.
+    /// Bidirectional edges carry activation both ways.
     ///
     /// # Arguments
     ///
@@ -1087,8 +1610,6 @@ impl Graph {
         initial_energy: f32,
         custom_config: Option<SignalConfig>,
     ) -> ActivationResult {
-        let start_time = std::time::Instant::now();
-
         // Use custom config or default
         let config = custom_config.unwrap_or_else(|| self.signal_config.clone());
 
@@ -1107,142 +1628,30 @@ impl Graph {
         // Clear previous activations
         self.clear_activations();
 
-        // Initialize result
-        let mut result = ActivationResult::default();
-        let mut queue = VecDeque::new();
-        let mut visited = HashSet::new();
-
-        // Activate source node
-        self.activate_node(source_id, initial_energy, None, &config);
-        queue.push_back((source_id, initial_energy, 0_usize, vec![source_id]));
-        visited.insert(source_id);
-
-        // BFS with energy decay
-        while let Some((current_id, current_energy, depth, path)) = queue.pop_front() {
-            result.nodes_visited += 1;
-            result.max_depth_reached = result.max_depth_reached.max(depth);
-
-            // Check max depth
-            if depth >= config.max_depth {
-                continue;
-            }
-
-            // Get outgoing neighbors
-            let neighbors = self.get_neighbors(current_id, Direction::Outgoing);
-
-            for (neighbor_id, edge_id) in neighbors {
-                // Skip already visited nodes
-                if visited.contains(&neighbor_id) {
-                    continue;
-                }
-
-                // Get edge info for weight
-                let edge_weight = self.edge_map
-                    .get(&edge_id)
-                    .map(|e| e.weight)
-                    .unwrap_or(1.0);
-
-                // Compute transmitted energy
-                let transmitted_energy = self.compute_transmitted_energy(
-                    current_energy,
-                    edge_weight,
-                    &config,
-                );
-
-                // Check energy threshold
-                if transmitted_energy < config.min_energy {
-                    continue;
-                }
-
-                // Activate neighbor node
-                self.activate_node(neighbor_id, transmitted_energy, Some(current_id), &config);
-
-                // Build path
-                let mut new_path = path.clone();
-                new_path.push(neighbor_id);
-
-                // Add to queue
-                queue.push_back((neighbor_id, transmitted_energy, depth + 1, new_path.clone()));
-                visited.insert(neighbor_id);
-
-                // Record activated node
-                if transmitted_energy >= config.activation_threshold {
-                    result.activated_nodes.push(ActivatedNode {
-                        node_id: neighbor_id,
-                        energy: transmitted_energy,
-                        depth: depth + 1,
-                        path_from_source: new_path,
-                    });
-                }
-            }
-        }
-
-        // Sort activated nodes by energy (descending)
-        result.activated_nodes.sort_by(|a, b| {
-            b.energy.partial_cmp(&a.energy).unwrap_or(std::cmp::Ordering::Equal)
-        });
-
-        // Find strongest path
-        if let Some(strongest) = result.activated_nodes.first() {
-            result.strongest_path = Some(Path {
-                nodes: strongest.path_from_source.clone(),
-                edges: Vec::new(), // TODO: populate edges
-                total_cost: strongest.energy,
-                length: strongest.depth,
-            });
-        }
-
-        result.execution_time_us = start_time.elapsed().as_micros() as u64;
-        result
-    }
-
-    /// Compute energy transmitted to neighbor node
-    ///
-    /// Formula: E_transmitted = E_source * edge_weight * (1 - decay_rate)
-    fn compute_transmitted_energy(
-        &self,
-        source_energy: f32,
-        edge_weight: f32,
-        config: &SignalConfig,
-    ) -> f32 {
-        source_energy * edge_weight * (1.0 - config.decay_rate)
-    }
-
-    /// Activate a node with given energy
-    ///
-    /// Handles different accumulation modes (Sum, Max, WeightedAverage)
-    fn activate_node(
-        &mut self,
-        node_id: NodeId,
-        energy: f32,
-        source_id: Option<NodeId>,
-        config: &SignalConfig,
-    ) {
-        let activation = self.activations.entry(node_id).or_insert_with(NodeActivation::default);
-
-        // Apply accumulation mode
-        match config.accumulation_mode {
-            AccumulationMode::Sum => {
-                activation.energy += energy;
-            }
-            AccumulationMode::Max => {
-                activation.energy = activation.energy.max(energy);
-            }
-            AccumulationMode::WeightedAverage => {
-                let count = activation.activation_count as f32;
-                if count > 0.0 {
-                    activation.energy = (activation.energy * count + energy) / (count + 1.0);
-                } else {
-                    activation.energy = energy;
-                }
-            }
-        }
-
-        activation.activation_count += 1;
-        activation.last_activated = NodeActivation::current_timestamp_us();
-        if source_id.is_some() {
-            activation.source_id = source_id;
-        }
+        let Graph { adjacency_out, adjacency_in, edge_map, activations, .. } = self;
+        spread(
+            source_id,
+            initial_energy,
+            &config,
+            |node_id| {
+                let outgoing = adjacency_out
+                    .get(&node_id)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|edge_id| edge_map.get(edge_id))
+                    .map(|edge| (edge.to_id, edge.weight, edge.active_levels));
+                // Bidirectional edges also spread against their direction
+                let reverse = adjacency_in
+                    .get(&node_id)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|edge_id| edge_map.get(edge_id))
+                    .filter(|edge| edge.bidirectional && edge.from_id != node_id)
+                    .map(|edge| (edge.from_id, edge.weight, edge.active_levels));
+                outgoing.chain(reverse).collect()
+            },
+            |node_id, energy, source| accumulate_activation(activations, node_id, energy, source, &config),
+        )
     }
 
     /// Clear all activation states
@@ -1388,6 +1797,152 @@ impl<'a> Iterator for DFSIterator<'a> {
     }
 }
 
+// ============================================================================
+// SignalSystem v1.0 - Spreading activation core
+// ============================================================================
+
+/// BFS with energy decay, shared by [`Graph`] and [`sharded::ShardedGraph`]
+///
+/// `out_edges(node)` lists `(neighbor, weight, active_levels)` of the node's
+/// outgoing edges; `activate(node, energy, from)` records each activation.
+pub(crate) fn spread<N, A>(
+    source_id: NodeId,
+    initial_energy: f32,
+    config: &SignalConfig,
+    mut out_edges: N,
+    mut activate: A,
+) -> ActivationResult
+where
+    N: FnMut(NodeId) -> Vec<(NodeId, f32, u8)>,
+    A: FnMut(NodeId, f32, Option<NodeId>),
+{
+    #[cfg(not(all(target_arch = "wasm32", feature = "wasm")))]
+    let start_time = std::time::Instant::now();
+    #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+    let start_us = crate::wasm::unix_time_us();
+
+    // Initialize result
+    let mut result = ActivationResult::default();
+    let mut queue = VecDeque::new();
+    let mut visited = HashSet::new();
+
+    // Activate source node
+    activate(source_id, initial_energy, None);
+    queue.push_back((source_id, initial_energy, 0_usize, vec![source_id]));
+    visited.insert(source_id);
+
+    // BFS with energy decay
+    while let Some((current_id, current_energy, depth, path)) = queue.pop_front() {
+        result.nodes_visited += 1;
+        result.max_depth_reached = result.max_depth_reached.max(depth);
+
+        // Check max depth
+        if depth >= config.max_depth {
+            continue;
+        }
+
+        for (neighbor_id, edge_weight, edge_levels) in out_edges(current_id) {
+            // Skip already visited nodes
+            if visited.contains(&neighbor_id) {
+                continue;
+            }
+
+            if edge_levels & config.layer_mask == 0 {
+                continue;
+            }
+
+            // E_transmitted = E_source * edge_weight * (1 - decay_rate)
+            let transmitted_energy = current_energy * edge_weight * (1.0 - config.decay_rate);
+
+            // Check energy threshold
+            if transmitted_energy < config.min_energy {
+                continue;
+            }
+
+            // Activate neighbor node
+            activate(neighbor_id, transmitted_energy, Some(current_id));
+
+            // Build path
+            let mut new_path = path.clone();
+            new_path.push(neighbor_id);
+
+            // Add to queue
+            queue.push_back((neighbor_id, transmitted_energy, depth + 1, new_path.clone()));
+            visited.insert(neighbor_id);
+
+            // Record activated node
+            if transmitted_energy >= config.activation_threshold {
+                result.activated_nodes.push(ActivatedNode {
+                    node_id: neighbor_id,
+                    energy: transmitted_energy,
+                    depth: depth + 1,
+                    path_from_source: new_path,
+                });
+            }
+        }
+    }
+
+    // Sort activated nodes by energy (descending)
+    result.activated_nodes.sort_by(|a, b| {
+        b.energy.partial_cmp(&a.energy).unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    // Find strongest path
+    if let Some(strongest) = result.activated_nodes.first() {
+        result.strongest_path = Some(Path {
+            nodes: strongest.path_from_source.clone(),
+            edges: Vec::new(), // TODO: populate edges
+            total_cost: strongest.energy,
+            length: strongest.depth,
+        });
+    }
+
+    #[cfg(not(all(target_arch = "wasm32", feature = "wasm")))]
+    {
+        result.execution_time_us = start_time.elapsed().as_micros() as u64;
+    }
+    #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+    {
+        result.execution_time_us = crate::wasm::unix_time_us().saturating_sub(start_us);
+    }
+    result
+}
+
+/// Apply an activation to `activations` per the accumulation mode (Sum, Max, WeightedAverage)
+pub(crate) fn accumulate_activation(
+    activations: &mut HashMap<NodeId, NodeActivation>,
+    node_id: NodeId,
+    energy: f32,
+    source_id: Option<NodeId>,
+    config: &SignalConfig,
+) {
+    let activation = activations.entry(node_id).or_default();
+
+    // Apply accumulation mode
+    match config.accumulation_mode {
+        AccumulationMode::Sum => {
+            activation.energy += energy;
+        }
+        AccumulationMode::Max => {
+            activation.energy = activation.energy.max(energy);
+        }
+        AccumulationMode::WeightedAverage => {
+            let count = activation.activation_count as f32;
+            if count > 0.0 {
+                activation.energy = (activation.energy * count + energy) / (count + 1.0);
+            } else {
+                activation.energy = energy;
+            }
+        }
+    }
+
+    activation.activation_count += 1;
+    activation.last_activated = NodeActivation::current_timestamp_us();
+    if source_id.is_some() {
+        activation.source_id = source_id;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1954,6 +2509,65 @@ mod tests {
         assert!(graph.get_activation(2).is_none(), "Node 2 should not be activated after clear");
     }
 
+    #[test]
+    fn test_spreading_activation_layer_mask() {
+        // 1 -> 2 is emotional, 1 -> 3 is abstract, 2 -> 4 is untagged (all levels)
+        let mut graph = Graph::new();
+        for i in 1..=4 {
+            graph.add_node(i);
+        }
+        let e12 = Graph::compute_edge_id(1, 2, 0);
+        let e13 = Graph::compute_edge_id(1, 3, 0);
+        graph.add_edge(e12, 1, 2, 0, 1.0, false).unwrap();
+        graph.add_edge(e13, 1, 3, 0, 1.0, false).unwrap();
+        graph.add_edge(Graph::compute_edge_id(2, 4, 0), 2, 4, 0, 1.0, false).unwrap();
+        assert!(graph.set_edge_levels(e12, active_levels::L4_EMOTIONAL));
+        assert!(graph.set_edge_levels(e13, active_levels::L8_ABSTRACT));
+
+        let activated = |graph: &mut Graph, mask: u8| -> Vec<NodeId> {
+            let config = SignalConfig { layer_mask: mask, ..Default::default() };
+            let mut ids: Vec<NodeId> = graph
+                .spreading_activation(1, 1.0, Some(config))
+                .activated_nodes
+                .iter()
+                .map(|n| n.node_id)
+                .collect();
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(activated(&mut graph, active_levels::ALL), vec![2, 3, 4]);
+        assert_eq!(activated(&mut graph, active_levels::L4_EMOTIONAL), vec![2, 4]);
+        assert_eq!(
+            activated(&mut graph, active_levels::L8_ABSTRACT | active_levels::L1_PHYSICAL),
+            vec![3]
+        );
+
+        let config = SignalConfig { layer_mask: 0, ..Default::default() };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_edge_weight_history() {
+        let mut graph = Graph::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        let history = Arc::new(EdgeHistory::default());
+        graph.set_weight_history(history.clone());
+
+        let edge_id = Graph::compute_edge_id(1, 2, 0);
+        graph.add_edge(edge_id, 1, 2, 0, 0.5, false).unwrap();
+        assert!(graph.set_edge_weight(edge_id, 0.7));
+        assert!(graph.set_edge_weight_from(edge_id, 0.4, WeightSource::Decay));
+
+        let samples = history.query(edge_id, None, None);
+        let trace: Vec<(f32, WeightSource)> = samples.iter().map(|s| (s.weight, s.source)).collect();
+        assert_eq!(
+            trace,
+            vec![(0.5, WeightSource::Created), (0.7, WeightSource::Manual), (0.4, WeightSource::Decay)]
+        );
+    }
+
     #[test]
     fn test_signal_config_validation() {
         // Valid config
@@ -1975,4 +2589,129 @@ mod tests {
         config.max_depth = 0;
         assert!(config.validate().is_err(), "max_depth = 0 should be invalid");
     }
+
+    #[test]
+    fn test_edge_limit_evicts_weakest_hypothesis() {
+        let guardian = Arc::new(RwLock::new(Guardian::new()));
+        let mut graph = Graph::with_config(GraphConfig { max_edges_per_node: Some(2), ..Default::default() });
+        graph.set_guardian(guardian.clone());
+        for id in 1..=5 {
+            graph.add_node(id);
+        }
+        let edge = |to, kind| Graph::compute_edge_id(1, to, kind);
+
+        // Synonym (Immutable) + Cause marked as Hypothesis
+        graph.add_edge(edge(2, 0x00), 1, 2, 0x00, 1.0, false).unwrap();
+        graph.add_edge(edge(3, 0x10), 1, 3, 0x10, 0.3, false).unwrap();
+        assert!(graph.set_edge_mutability(edge(3, 0x10), ConnectionMutability::Hypothesis));
+
+        assert_eq!(graph.add_edge(edge(4, 0x10), 1, 4, 0x10, 0.9, false), Ok(true));
+        assert!(!graph.contains_edge(edge(3, 0x10)));
+        assert!(graph.contains_edge(edge(2, 0x00)));
+        assert_eq!(graph.edge_mutability(edge(4, 0x10)), Some(ConnectionMutability::Learnable));
+
+        // Only Immutable and Learnable edges left: refused
+        assert!(graph.add_edge(edge(5, 0x10), 1, 5, 0x10, 1.0, false).is_err());
+        assert_eq!(graph.get_degree(1, Direction::Outgoing), 2);
+        let stats = graph.limit_stats();
+        assert_eq!((stats.edges_evicted, stats.edges_refused), (1, 1));
+
+        let events = guardian.read().pending_events().clone();
+        let refused: Vec<_> = events
+            .iter()
+            .filter(|e| e.event_type == GuardianEventType::GraphLimitRefused)
+            .collect();
+        assert_eq!(refused.len(), 1);
+        assert_eq!(refused[0].connection_info, Some((1, 5)));
+    }
+
+    #[test]
+    fn test_canonicalize_merges_duplicates() {
+        let mut graph = Graph::new();
+        for id in 1..=3 {
+            graph.add_node(id);
+        }
+        // Woven both ways, plus an unrelated directed edge
+        graph.add_edge(Graph::compute_edge_id(2, 1, 0x50), 2, 1, 0x50, 0.4, false).unwrap();
+        graph.add_edge(Graph::compute_edge_id(1, 2, 0x50), 1, 2, 0x50, 0.7, false).unwrap();
+        graph.add_edge(Graph::compute_edge_id(3, 1, 0x10), 3, 1, 0x10, 0.5, false).unwrap();
+        graph.set_edge_mutability(Graph::compute_edge_id(2, 1, 0x50), ConnectionMutability::Hypothesis);
+
+        let audit = graph.audit_duplicates();
+        assert_eq!((audit.edges_before, audit.edges_after, audit.duplicates()), (3, 2, 1));
+        assert_eq!(graph.edge_count(), 3);
+
+        let report = graph.canonicalize();
+        assert_eq!(report, audit);
+        let kept = Graph::compute_edge_id(1, 2, 0x50);
+        let edge = graph.get_edge(kept).unwrap();
+        assert_eq!((edge.from_id, edge.to_id, edge.weight, edge.bidirectional), (1, 2, 0.7, true));
+        assert_eq!(graph.edge_evidence(kept), Some(2));
+        assert_eq!(graph.edge_mutability(kept), Some(ConnectionMutability::Learnable));
+        assert_eq!(graph.get_neighbors(2, Direction::Both), vec![(1, kept)]);
+        assert!(graph.contains_edge(Graph::compute_edge_id(3, 1, 0x10)));
+        assert!(graph.canonicalize().is_canonical());
+    }
+
+    #[test]
+    fn test_canonicalize_keeps_common_direction() {
+        let mut graph = Graph::new();
+        graph.add_node(3);
+        graph.add_node(5);
+        // Two Cause edges 5→3 under different IDs
+        graph.add_edge(Graph::compute_edge_id(5, 3, 0x10), 5, 3, 0x10, 0.4, false).unwrap();
+        graph.add_edge(7, 5, 3, 0x10, 0.6, false).unwrap();
+
+        let report = graph.canonicalize();
+        assert_eq!(report.duplicates(), 1);
+        let kept = Graph::compute_edge_id(5, 3, 0x10);
+        assert_eq!(report.merges[0].kept, kept);
+        let edge = graph.get_edge(kept).unwrap();
+        assert_eq!((edge.from_id, edge.to_id, edge.weight, edge.bidirectional), (5, 3, 0.6, false));
+        assert_eq!(graph.get_neighbors(5, Direction::Outgoing), vec![(3, kept)]);
+        assert!(graph.get_neighbors(3, Direction::Outgoing).is_empty());
+    }
+
+    #[test]
+    fn test_deduplicating_add_edge() {
+        let mut graph = Graph::with_config(GraphConfig { deduplicate_edges: true, ..Default::default() });
+        graph.add_node(1);
+        graph.add_node(2);
+        let edge_id = Graph::compute_edge_id(1, 2, 0x50);
+        assert_eq!(graph.add_edge(edge_id, 1, 2, 0x50, 0.3, false), Ok(true));
+        assert_eq!(graph.add_edge(Graph::compute_edge_id(2, 1, 0x50), 2, 1, 0x50, 0.6, false), Ok(false));
+        assert_eq!(graph.add_edge(Graph::compute_edge_id(1, 2, 0x51), 1, 2, 0x51, 0.6, false), Ok(true));
+
+        assert_eq!(graph.edge_count(), 2);
+        let edge = graph.get_edge(edge_id).unwrap();
+        assert_eq!((edge.weight, edge.bidirectional), (0.6, true));
+        assert_eq!(graph.edge_evidence(edge_id), Some(2));
+        assert!(graph.audit_duplicates().is_canonical());
+    }
+
+    #[test]
+    fn test_node_limit_spares_immutable_structure() {
+        let mut graph = Graph::with_config(GraphConfig { max_nodes: Some(3), ..Default::default() });
+        for id in 1..=3 {
+            graph.add_node(id);
+        }
+        graph.add_edge(Graph::compute_edge_id(1, 2, 0x00), 1, 2, 0x00, 1.0, false).unwrap();
+        let hypothesis = Graph::compute_edge_id(1, 3, 0x50);
+        graph.add_edge(hypothesis, 1, 3, 0x50, 0.2, false).unwrap();
+        graph.set_edge_mutability(hypothesis, ConnectionMutability::Hypothesis);
+
+        // Node 3 hangs on a Hypothesis edge only
+        assert!(graph.add_node(4));
+        assert!(!graph.contains_node(3));
+        assert!(!graph.contains_edge(hypothesis));
+        assert_eq!(graph.node_count(), 3);
+
+        // 1 and 2 share an Immutable edge, 4 is isolated
+        assert!(!graph.add_node(5));
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(
+            graph.limit_stats(),
+            &GraphLimitStats { edges_evicted: 1, nodes_evicted: 1, nodes_refused: 1, edges_refused: 0 }
+        );
+    }
 }
@@ -0,0 +1,751 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Graph export to RDF Turtle, GraphML and Graphviz DOT
+//!
+//! Serializes nodes (tokens) and typed edges so the learned structure can be
+//! analyzed in standard tooling (Protégé, rdflib, Gephi, NetworkX, yEd).
+//! Subgraphs can be rendered with Graphviz via `Subgraph::to_dot`, or laid
+//! out for interactive viewers via `Graph::subgraph_view`.
+//! Edge weight is exported as the connection confidence. Output is sorted by
+//! ID, so exports of the same graph are byte-identical.
+//!
+//! `Graph::export_to_file` seals the file when at-rest encryption is enabled
+//! (see `encryption`); use `export_string` for a plain copy to share.
+
+use super::{Direction, EdgeId, EdgeInfo, Graph, NodeId, Subgraph};
+use crate::encryption::{self, FileCipher};
+use crate::ConnectionType;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Export format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// RDF Turtle (.ttl)
+    Turtle,
+    /// GraphML (.graphml)
+    GraphMl,
+}
+
+/// Export options
+#[derive(Debug, Clone)]
+pub struct GraphExportOptions {
+    /// Base IRI for Turtle output (must end with '/' or '#')
+    pub base_iri: String,
+
+    /// Human-readable node labels (e.g. bootstrap words)
+    pub labels: HashMap<NodeId, String>,
+
+    /// Turtle: also emit a reified `ng:Connection` per edge with
+    /// category, mutability and confidence
+    pub include_edge_metadata: bool,
+}
+
+impl Default for GraphExportOptions {
+    fn default() -> Self {
+        Self {
+            base_iri: "https://neurograph.os/".to_string(),
+            labels: HashMap::new(),
+            include_edge_metadata: true,
+        }
+    }
+}
+
+/// Turtle local name for an edge type (e.g. `Synonym`, `Type_0xB3`)
+pub(crate) fn edge_type_name(edge_type: u8) -> String {
+    match ConnectionType::from_u8(edge_type) {
+        Some(t) => format!("{:?}", t),
+        None => format!("Type_0x{:02X}", edge_type),
+    }
+}
+
+fn escape_turtle(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+impl Graph {
+    fn sorted_nodes(&self) -> Vec<NodeId> {
+        let mut nodes = self.get_nodes();
+        nodes.sort_unstable();
+        nodes
+    }
+
+    fn sorted_edges(&self) -> Vec<(EdgeId, &EdgeInfo)> {
+        let mut edges: Vec<_> = self.edges().collect();
+        edges.sort_unstable_by_key(|(id, _)| *id);
+        edges
+    }
+
+    /// Write graph as RDF Turtle
+    pub fn write_turtle<W: Write>(&self, writer: &mut W, options: &GraphExportOptions) -> io::Result<()> {
+        let base = &options.base_iri;
+        writeln!(writer, "@prefix ng: <{}ontology#> .", base)?;
+        writeln!(writer, "@prefix node: <{}node/> .", base)?;
+        writeln!(writer, "@prefix edge: <{}edge/> .", base)?;
+        writeln!(writer, "@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .")?;
+        writeln!(writer, "@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .")?;
+        writeln!(writer)?;
+
+        for node in self.sorted_nodes() {
+            match options.labels.get(&node) {
+                Some(label) => writeln!(
+                    writer,
+                    "node:{} a ng:Token ;\n    rdfs:label \"{}\" .",
+                    node,
+                    escape_turtle(label)
+                )?,
+                None => writeln!(writer, "node:{} a ng:Token .", node)?,
+            }
+        }
+        writeln!(writer)?;
+
+        for (edge_id, info) in self.sorted_edges() {
+            let type_name = edge_type_name(info.edge_type);
+            writeln!(writer, "node:{} ng:{} node:{} .", info.from_id, type_name, info.to_id)?;
+
+            if options.include_edge_metadata {
+                let connection_type = ConnectionType::from_u8(info.edge_type);
+                writeln!(writer, "edge:{} a ng:Connection ;", edge_id)?;
+                writeln!(writer, "    ng:source node:{} ;", info.from_id)?;
+                writeln!(writer, "    ng:target node:{} ;", info.to_id)?;
+                writeln!(writer, "    ng:connectionType ng:{} ;", type_name)?;
+                if let Some(t) = connection_type {
+                    writeln!(writer, "    ng:category \"{}\" ;", t.category())?;
+                    writeln!(writer, "    ng:mutability \"{:?}\" ;", t.mutability())?;
+                }
+                writeln!(writer, "    ng:confidence \"{}\"^^xsd:float ;", info.weight)?;
+                writeln!(writer, "    ng:bidirectional {} .", info.bidirectional)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write graph as GraphML
+    pub fn write_graphml<W: Write>(&self, writer: &mut W, options: &GraphExportOptions) -> io::Result<()> {
+        writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(writer, r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#)?;
+        writeln!(writer, r#"  <key id="label" for="node" attr.name="label" attr.type="string"/>"#)?;
+        writeln!(writer, r#"  <key id="type" for="edge" attr.name="type" attr.type="string"/>"#)?;
+        writeln!(writer, r#"  <key id="category" for="edge" attr.name="category" attr.type="string"/>"#)?;
+        writeln!(writer, r#"  <key id="confidence" for="edge" attr.name="confidence" attr.type="float"/>"#)?;
+        writeln!(writer, r#"  <key id="bidirectional" for="edge" attr.name="bidirectional" attr.type="boolean"/>"#)?;
+        writeln!(writer, r#"  <graph id="neurograph" edgedefault="directed">"#)?;
+
+        for node in self.sorted_nodes() {
+            match options.labels.get(&node) {
+                Some(label) => writeln!(
+                    writer,
+                    r#"    <node id="n{}"><data key="label">{}</data></node>"#,
+                    node,
+                    escape_xml(label)
+                )?,
+                None => writeln!(writer, r#"    <node id="n{}"/>"#, node)?,
+            }
+        }
+
+        for (edge_id, info) in self.sorted_edges() {
+            let category = ConnectionType::from_u8(info.edge_type)
+                .map(|t| t.category())
+                .unwrap_or("unknown");
+            writeln!(
+                writer,
+                r#"    <edge id="e{}" source="n{}" target="n{}">"#,
+                edge_id, info.from_id, info.to_id
+            )?;
+            writeln!(writer, r#"      <data key="type">{}</data>"#, edge_type_name(info.edge_type))?;
+            writeln!(writer, r#"      <data key="category">{}</data>"#, category)?;
+            writeln!(writer, r#"      <data key="confidence">{}</data>"#, info.weight)?;
+            writeln!(writer, r#"      <data key="bidirectional">{}</data>"#, info.bidirectional)?;
+            writeln!(writer, "    </edge>")?;
+        }
+
+        writeln!(writer, "  </graph>")?;
+        writeln!(writer, "</graphml>")
+    }
+
+    /// Serialize graph to a string in the given format
+    pub fn export_string(&self, format: ExportFormat, options: &GraphExportOptions) -> String {
+        let mut buf = Vec::new();
+        // Writing to Vec<u8> cannot fail
+        let _ = match format {
+            ExportFormat::Turtle => self.write_turtle(&mut buf, options),
+            ExportFormat::GraphMl => self.write_graphml(&mut buf, options),
+        };
+        String::from_utf8(buf).unwrap_or_default()
+    }
+
+    /// Export graph to a file, sealed with the installed at-rest cipher if any
+    ///
+    /// # Returns
+    /// Number of edges written
+    pub fn export_to_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+        format: ExportFormat,
+        options: &GraphExportOptions,
+    ) -> io::Result<usize> {
+        self.export_to_file_with(path, format, options, encryption::installed_cipher().as_deref())
+    }
+
+    /// [`export_to_file`](Self::export_to_file) with an explicit cipher
+    pub fn export_to_file_with<P: AsRef<Path>>(
+        &self,
+        path: P,
+        format: ExportFormat,
+        options: &GraphExportOptions,
+        cipher: Option<&FileCipher>,
+    ) -> io::Result<usize> {
+        let path = path.as_ref();
+        let mut writer = BufWriter::new(File::create(path)?);
+        match cipher {
+            Some(_) => {
+                let plain = self.export_string(format, options).into_bytes();
+                let sealed = encryption::seal_file(cipher, plain, path).map_err(io::Error::other)?;
+                writer.write_all(&sealed)?;
+            }
+            None => match format {
+                ExportFormat::Turtle => self.write_turtle(&mut writer, options)?,
+                ExportFormat::GraphMl => self.write_graphml(&mut writer, options)?,
+            },
+        }
+        writer.flush()?;
+        Ok(self.edge_count())
+    }
+}
+
+// ============================================================================
+// Graphviz DOT
+// ============================================================================
+
+/// Options for `Subgraph::to_dot`
+#[derive(Debug, Clone)]
+pub struct DotOptions {
+    /// Graph name in `digraph <name> { ... }`
+    pub graph_name: String,
+
+    /// Node labels; unlabeled nodes show their ID
+    pub labels: HashMap<NodeId, String>,
+
+    /// Node drawn highlighted (e.g. neighborhood center)
+    pub highlight: Option<NodeId>,
+
+    /// Edge pen width at confidence 0.0
+    pub min_penwidth: f32,
+
+    /// Edge pen width at confidence 1.0
+    pub max_penwidth: f32,
+
+    /// Show edge type names as edge labels
+    pub edge_labels: bool,
+}
+
+impl Default for DotOptions {
+    fn default() -> Self {
+        Self {
+            graph_name: "neurograph".to_string(),
+            labels: HashMap::new(),
+            highlight: None,
+            min_penwidth: 0.5,
+            max_penwidth: 4.0,
+            edge_labels: false,
+        }
+    }
+}
+
+/// Edge color by ConnectionType category
+pub fn category_color(category: &str) -> &'static str {
+    match category {
+        "semantic" => "#1f77b4",
+        "causal" => "#d62728",
+        "temporal" => "#9467bd",
+        "spatial" => "#2ca02c",
+        "logical" => "#17becf",
+        "associative" => "#7f7f7f",
+        "structural" => "#8c564b",
+        "functional" => "#ff7f0e",
+        "emotional" => "#e377c2",
+        "rule" => "#bcbd22",
+        "dynamic" => "#393b79",
+        _ => "#000000",
+    }
+}
+
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+impl Subgraph {
+    /// Render subgraph as Graphviz DOT
+    ///
+    /// Edge color follows the ConnectionType category, pen width scales
+    /// with confidence (edge weight). Bidirectional edges have no arrowhead.
+    pub fn to_dot(&self, graph: &Graph, options: &DotOptions) -> String {
+        let mut out = String::new();
+        out.push_str(&format!("digraph \"{}\" {{\n", escape_dot(&options.graph_name)));
+        out.push_str("    node [shape=ellipse, fontname=\"Helvetica\"];\n");
+        out.push_str("    edge [fontname=\"Helvetica\", fontsize=9];\n");
+
+        let mut nodes: Vec<NodeId> = self.nodes.iter().copied().collect();
+        nodes.sort_unstable();
+        for node in nodes {
+            let label = options
+                .labels
+                .get(&node)
+                .cloned()
+                .unwrap_or_else(|| node.to_string());
+            let style = if options.highlight == Some(node) {
+                ", style=filled, fillcolor=\"#ffd966\""
+            } else {
+                ""
+            };
+            out.push_str(&format!("    n{} [label=\"{}\"{}];\n", node, escape_dot(&label), style));
+        }
+
+        let mut edges: Vec<(EdgeId, &EdgeInfo)> = self
+            .edges
+            .iter()
+            .filter_map(|&id| graph.get_edge(id).map(|info| (id, info)))
+            .collect();
+        edges.sort_unstable_by_key(|(id, _)| *id);
+        for (_, info) in edges {
+            let category = ConnectionType::from_u8(info.edge_type)
+                .map(|t| t.category())
+                .unwrap_or("unknown");
+            let confidence = info.weight.clamp(0.0, 1.0);
+            let penwidth = options.min_penwidth + (options.max_penwidth - options.min_penwidth) * confidence;
+
+            let mut attrs = format!("color=\"{}\", penwidth={:.2}", category_color(category), penwidth);
+            if info.bidirectional {
+                attrs.push_str(", dir=none");
+            }
+            if options.edge_labels {
+                attrs.push_str(&format!(", label=\"{}\"", edge_type_name(info.edge_type)));
+            }
+            out.push_str(&format!("    n{} -> n{} [{}];\n", info.from_id, info.to_id, attrs));
+        }
+
+        out.push_str("}\n");
+        out
+    }
+}
+
+// ============================================================================
+// Interactive view
+// ============================================================================
+
+/// How node positions in a `SubgraphView` were computed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ViewLayout {
+    /// 2D force-directed layout (z = 0), center at the origin
+    Force,
+    /// Projected 3D concept coordinates (Grid space)
+    Projected,
+}
+
+/// Options for `Graph::subgraph_view`
+#[derive(Debug, Clone)]
+pub struct ViewOptions {
+    /// Node labels; unlabeled nodes get None
+    pub labels: HashMap<NodeId, String>,
+
+    /// Projected 3D coordinates per node
+    pub coords: HashMap<NodeId, [f32; 3]>,
+
+    /// Requested layout; `Projected` falls back to `Force` if any node has
+    /// no coordinates
+    pub layout: ViewLayout,
+
+    /// Node cap; nodes closest to the center are kept
+    pub max_nodes: usize,
+
+    /// Force layout iterations
+    pub iterations: usize,
+}
+
+impl Default for ViewOptions {
+    fn default() -> Self {
+        Self {
+            labels: HashMap::new(),
+            coords: HashMap::new(),
+            layout: ViewLayout::Force,
+            max_nodes: 500,
+            iterations: 100,
+        }
+    }
+}
+
+/// Node of a `SubgraphView`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ViewNode {
+    pub id: NodeId,
+    pub label: Option<String>,
+    /// Hops from the center
+    pub depth: usize,
+    pub position: [f32; 3],
+}
+
+/// Edge of a `SubgraphView`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ViewEdge {
+    pub id: EdgeId,
+    pub from: NodeId,
+    pub to: NodeId,
+    /// ConnectionType name, e.g. `Synonym`
+    pub edge_type: String,
+    /// ConnectionType category, e.g. `semantic`
+    pub category: String,
+    /// Category color (same palette as DOT output)
+    pub color: String,
+    pub weight: f32,
+    pub bidirectional: bool,
+}
+
+/// Neighborhood of a node with positions, ready to draw
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SubgraphView {
+    pub center: NodeId,
+    pub layout: ViewLayout,
+    /// Sorted by depth, then ID
+    pub nodes: Vec<ViewNode>,
+    /// Sorted by ID
+    pub edges: Vec<ViewEdge>,
+    /// Nodes left out because of `max_nodes`
+    pub truncated: usize,
+}
+
+impl Graph {
+    /// Neighborhood of `center` within `depth` hops, laid out for display
+    ///
+    /// Returns None if `center` is not in the graph. Output is
+    /// deterministic for the same graph and options.
+    pub fn subgraph_view(&self, center: NodeId, depth: usize, options: &ViewOptions) -> Option<SubgraphView> {
+        if !self.contains_node(center) {
+            return None;
+        }
+
+        // BFS by hops; neighbors sorted so truncation is deterministic
+        let mut depths: HashMap<NodeId, usize> = HashMap::from([(center, 0)]);
+        let mut order = vec![center];
+        let mut queue = VecDeque::from([center]);
+        let mut truncated = 0;
+        while let Some(node) = queue.pop_front() {
+            let hops = depths[&node];
+            if hops >= depth {
+                continue;
+            }
+            let mut neighbors: Vec<NodeId> = self
+                .get_neighbors(node, Direction::Both)
+                .into_iter()
+                .map(|(neighbor, _)| neighbor)
+                .collect();
+            neighbors.sort_unstable();
+            neighbors.dedup();
+            for neighbor in neighbors {
+                if depths.contains_key(&neighbor) {
+                    continue;
+                }
+                if order.len() >= options.max_nodes.max(1) {
+                    truncated += 1;
+                    continue;
+                }
+                depths.insert(neighbor, hops + 1);
+                order.push(neighbor);
+                queue.push_back(neighbor);
+            }
+        }
+        order.sort_by_key(|id| (depths[id], *id));
+        let index: HashMap<NodeId, usize> = order.iter().enumerate().map(|(i, id)| (*id, i)).collect();
+
+        let mut edge_ids: Vec<EdgeId> = order
+            .iter()
+            .flat_map(|&node| self.get_neighbors(node, Direction::Both))
+            .filter(|(neighbor, _)| index.contains_key(neighbor))
+            .map(|(_, edge_id)| edge_id)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        edge_ids.sort_unstable();
+        let edges: Vec<ViewEdge> = edge_ids
+            .into_iter()
+            .filter_map(|id| {
+                let info = self.get_edge(id)?;
+                let category = ConnectionType::from_u8(info.edge_type)
+                    .map(|t| t.category())
+                    .unwrap_or("unknown");
+                Some(ViewEdge {
+                    id,
+                    from: info.from_id,
+                    to: info.to_id,
+                    edge_type: edge_type_name(info.edge_type),
+                    category: category.to_string(),
+                    color: category_color(category).to_string(),
+                    weight: info.weight,
+                    bidirectional: info.bidirectional,
+                })
+            })
+            .collect();
+
+        let projected = options.layout == ViewLayout::Projected
+            && order.iter().all(|id| options.coords.contains_key(id));
+        let positions: Vec<[f32; 3]> = if projected {
+            order.iter().map(|id| options.coords[id]).collect()
+        } else {
+            let node_depths: Vec<usize> = order.iter().map(|id| depths[id]).collect();
+            let links: Vec<(usize, usize)> = edges.iter().map(|e| (index[&e.from], index[&e.to])).collect();
+            force_layout(&node_depths, &links, options.iterations)
+                .into_iter()
+                .map(|[x, y]| [x, y, 0.0])
+                .collect()
+        };
+
+        let nodes = order
+            .iter()
+            .zip(positions)
+            .map(|(&id, position)| ViewNode {
+                id,
+                label: options.labels.get(&id).cloned(),
+                depth: depths[&id],
+                position,
+            })
+            .collect();
+
+        Some(SubgraphView {
+            center,
+            layout: if projected { ViewLayout::Projected } else { ViewLayout::Force },
+            nodes,
+            edges,
+            truncated,
+        })
+    }
+}
+
+/// Fruchterman-Reingold layout with ideal edge length 1
+///
+/// Nodes start on concentric rings by depth and node 0 (the center) stays
+/// pinned at the origin, so results are deterministic.
+fn force_layout(depths: &[usize], links: &[(usize, usize)], iterations: usize) -> Vec<[f32; 2]> {
+    let n = depths.len();
+    let mut ring_sizes: HashMap<usize, usize> = HashMap::new();
+    for &depth in depths {
+        *ring_sizes.entry(depth).or_default() += 1;
+    }
+    let mut ring_seen: HashMap<usize, usize> = HashMap::new();
+    let mut pos: Vec<[f32; 2]> = depths
+        .iter()
+        .map(|&depth| {
+            let slot = ring_seen.entry(depth).or_default();
+            let angle = std::f32::consts::TAU * *slot as f32 / ring_sizes[&depth] as f32;
+            *slot += 1;
+            [depth as f32 * angle.cos(), depth as f32 * angle.sin()]
+        })
+        .collect();
+
+    for iteration in 0..iterations {
+        let temperature = 0.5 * (1.0 - iteration as f32 / iterations as f32);
+        let mut disp = vec![[0.0f32; 2]; n];
+
+        for i in 0..n {
+            for j in (i + 1)..n {
+                let dx = pos[i][0] - pos[j][0];
+                let dy = pos[i][1] - pos[j][1];
+                let dist = (dx * dx + dy * dy).sqrt().max(0.01);
+                let repulsion = 1.0 / dist;
+                disp[i][0] += dx / dist * repulsion;
+                disp[i][1] += dy / dist * repulsion;
+                disp[j][0] -= dx / dist * repulsion;
+                disp[j][1] -= dy / dist * repulsion;
+            }
+        }
+        for &(a, b) in links {
+            if a == b {
+                continue;
+            }
+            let dx = pos[a][0] - pos[b][0];
+            let dy = pos[a][1] - pos[b][1];
+            let dist = (dx * dx + dy * dy).sqrt().max(0.01);
+            let attraction = dist * dist;
+            disp[a][0] -= dx / dist * attraction;
+            disp[a][1] -= dy / dist * attraction;
+            disp[b][0] += dx / dist * attraction;
+            disp[b][1] += dy / dist * attraction;
+        }
+
+        for i in 1..n {
+            let len = (disp[i][0] * disp[i][0] + disp[i][1] * disp[i][1]).sqrt();
+            if len > 0.0 {
+                let step = len.min(temperature);
+                pos[i][0] += disp[i][0] / len * step;
+                pos[i][1] += disp[i][1] / len * step;
+            }
+        }
+    }
+    pos
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> Graph {
+        let mut graph = Graph::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_node(3);
+        let synonym = ConnectionType::Synonym as u8;
+        let cause = ConnectionType::Cause as u8;
+        graph.add_edge(Graph::compute_edge_id(1, 2, synonym), 1, 2, synonym, 1.0, true).unwrap();
+        graph.add_edge(Graph::compute_edge_id(2, 3, cause), 2, 3, cause, 0.25, false).unwrap();
+        graph
+    }
+
+    fn options() -> GraphExportOptions {
+        let mut options = GraphExportOptions::default();
+        options.labels.insert(1, "say \"hi\"".to_string());
+        options.labels.insert(2, "<a&b>".to_string());
+        options
+    }
+
+    #[test]
+    fn test_export_turtle() {
+        let ttl = graph().export_string(ExportFormat::Turtle, &options());
+
+        assert!(ttl.starts_with("@prefix ng: <https://neurograph.os/ontology#> ."));
+        assert!(ttl.contains("rdfs:label \"say \\\"hi\\\"\" ."));
+        assert!(ttl.contains("node:3 a ng:Token ."));
+        assert!(ttl.contains("node:1 ng:Synonym node:2 ."));
+        assert!(ttl.contains("ng:category \"causal\" ;"));
+        assert!(ttl.contains("ng:mutability \"Immutable\" ;"));
+        assert!(ttl.contains("ng:confidence \"0.25\"^^xsd:float ;"));
+
+        // Deterministic
+        assert_eq!(ttl, graph().export_string(ExportFormat::Turtle, &options()));
+    }
+
+    #[test]
+    fn test_export_graphml() {
+        let xml = graph().export_string(ExportFormat::GraphMl, &options());
+
+        assert!(xml.contains(r#"<node id="n2"><data key="label">&lt;a&amp;b&gt;</data></node>"#));
+        assert!(xml.contains(r#"<data key="type">Cause</data>"#));
+        assert!(xml.contains(r#"<data key="category">semantic</data>"#));
+        assert_eq!(xml.matches("<edge ").count(), 2);
+        assert!(xml.trim_end().ends_with("</graphml>"));
+    }
+
+    #[test]
+    fn test_export_to_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("graph.ttl");
+        let edges = graph()
+            .export_to_file(&path, ExportFormat::Turtle, &GraphExportOptions::default())
+            .unwrap();
+        assert_eq!(edges, 2);
+        assert!(std::fs::read_to_string(&path).unwrap().contains("ng:Connection"));
+
+        let cipher = FileCipher::new(&FileCipher::generate_key());
+        let options = GraphExportOptions::default();
+        graph().export_to_file_with(&path, ExportFormat::Turtle, &options, Some(&cipher)).unwrap();
+        let sealed = std::fs::read(&path).unwrap();
+        assert!(FileCipher::is_sealed(&sealed));
+        let plain = encryption::open_file(Some(&cipher), sealed, &path).unwrap();
+        assert_eq!(String::from_utf8(plain).unwrap(), graph().export_string(ExportFormat::Turtle, &options));
+    }
+
+    #[test]
+    fn test_subgraph_to_dot() {
+        let graph = graph();
+        let subgraph = graph.extract_neighborhood(2, 1);
+        let dot_options = DotOptions {
+            labels: options().labels,
+            highlight: Some(2),
+            edge_labels: true,
+            ..Default::default()
+        };
+
+        let dot = subgraph.to_dot(&graph, &dot_options);
+        assert!(dot.starts_with("digraph \"neurograph\" {"));
+        assert!(dot.contains("n1 [label=\"say \\\"hi\\\"\"];"));
+        assert!(dot.contains("n2 [label=\"<a&b>\", style=filled"));
+        assert!(dot.contains("n3 [label=\"3\"];"));
+
+        // Semantic edge: blue, full width, undirected
+        assert!(dot.contains("n1 -> n2 [color=\"#1f77b4\", penwidth=4.00, dir=none, label=\"Synonym\"];"));
+        // Causal edge: red, thin
+        assert!(dot.contains("n2 -> n3 [color=\"#d62728\", penwidth=1.38, label=\"Cause\"];"));
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn test_subgraph_view() {
+        let graph = graph();
+        let view_options = ViewOptions { labels: options().labels, ..Default::default() };
+
+        let view = graph.subgraph_view(1, 1, &view_options).unwrap();
+        assert_eq!(view.layout, ViewLayout::Force);
+        assert_eq!(view.nodes.iter().map(|n| (n.id, n.depth)).collect::<Vec<_>>(), vec![(1, 0), (2, 1)]);
+        assert_eq!(view.nodes[0].position, [0.0, 0.0, 0.0]);
+        assert_eq!(view.nodes[0].label.as_deref(), Some("say \"hi\""));
+        assert_eq!(view.edges.len(), 1);
+        assert_eq!(view.edges[0].edge_type, "Synonym");
+        assert_eq!(view.edges[0].color, "#1f77b4");
+
+        let view = graph.subgraph_view(1, 2, &view_options).unwrap();
+        assert_eq!(view.nodes.len(), 3);
+        assert_eq!(view.edges.len(), 2);
+        assert!(view.nodes.iter().all(|n| n.position.iter().all(|c| c.is_finite())));
+        assert_eq!(view, graph.subgraph_view(1, 2, &view_options).unwrap());
+
+        let capped = graph.subgraph_view(1, 2, &ViewOptions { max_nodes: 2, ..Default::default() }).unwrap();
+        assert_eq!(capped.nodes.len(), 2);
+        assert_eq!(capped.truncated, 1);
+
+        // Projected only when every node has coordinates
+        let mut projected = ViewOptions { layout: ViewLayout::Projected, ..Default::default() };
+        projected.coords.insert(1, [1.0, 2.0, 3.0]);
+        assert_eq!(graph.subgraph_view(1, 1, &projected).unwrap().layout, ViewLayout::Force);
+        projected.coords.insert(2, [4.0, 5.0, 6.0]);
+        let view = graph.subgraph_view(1, 1, &projected).unwrap();
+        assert_eq!(view.layout, ViewLayout::Projected);
+        assert_eq!(view.nodes[1].position, [4.0, 5.0, 6.0]);
+
+        assert!(graph.subgraph_view(99, 1, &view_options).is_none());
+    }
+}
@@ -0,0 +1,658 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Mapped Graph - read-only graph served straight from a memory-mapped file
+//!
+//! For inference-only deployments: the snapshot is laid out as sorted,
+//! fixed-size records so lookups are binary searches over the mapping and
+//! nothing is copied into HashMaps at startup. Pages are loaded by the OS
+//! on first access, so open time and RSS depend on what is queried, not on
+//! the graph size.
+//!
+//! File layout (little endian):
+//!
+//! ```text
+//! header        32 B   magic "NGMAP\0\0\0", version, token/node/edge counts
+//! tokens        64 B   Token::to_bytes, sorted by id
+//! edges         24 B   edge_id u64, from u32, to u32, weight f32,
+//!                      edge_type u8, bidirectional u8, active_levels u8, pad
+//!                      sorted by edge_id
+//! nodes          4 B   node id, sorted
+//! out_offsets    4 B   node_count + 1 offsets into out_index
+//! out_index      4 B   edge indices grouped by source node
+//! in_offsets     4 B   node_count + 1 offsets into in_index
+//! in_index       4 B   edge indices grouped by target node
+//! ```
+//!
+//! Files are written with [`MappedGraph::write`] or by registering
+//! `checkpoint::MappedGraphSection`, which adds `graph.map` to every
+//! checkpoint ([`MappedGraph::open_checkpoint`]).
+//!
+//! With at-rest encryption enabled (`encryption::load_at_rest_cipher`) the
+//! file is sealed. A sealed file cannot be mapped, so `open` decrypts it
+//! into memory instead, and refuses a plain one.
+
+use super::{
+    accumulate_activation, spread, ActivationResult, Direction, EdgeId, EdgeInfo, NodeActivation,
+    NodeId, SignalConfig,
+};
+use crate::encryption::{self, EncryptionError, FileCipher};
+use crate::runtime_storage::RuntimeSnapshot;
+use crate::token::Token;
+use memmap2::Mmap;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Write;
+use std::ops::Range;
+use std::path::Path;
+
+/// File name of the checkpoint section
+const CHECKPOINT_FILE: &str = "graph.map";
+
+/// File magic
+pub const MAPPED_GRAPH_MAGIC: [u8; 8] = *b"NGMAP\0\0\0";
+
+/// File format version
+pub const MAPPED_GRAPH_VERSION: u32 = 1;
+
+const HEADER_SIZE: usize = 32;
+const TOKEN_SIZE: usize = 64;
+const EDGE_SIZE: usize = 24;
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// Mapped graph errors
+#[derive(Debug)]
+pub enum MappedGraphError {
+    Io(String),
+    /// Not a mapped graph file, or truncated / inconsistent
+    Format(String),
+    Encryption(EncryptionError),
+}
+
+impl std::fmt::Display for MappedGraphError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MappedGraphError::Io(e) => write!(f, "IO error: {}", e),
+            MappedGraphError::Format(e) => write!(f, "Invalid mapped graph: {}", e),
+            MappedGraphError::Encryption(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for MappedGraphError {}
+
+impl From<EncryptionError> for MappedGraphError {
+    fn from(e: EncryptionError) -> Self {
+        MappedGraphError::Encryption(e)
+    }
+}
+
+impl From<std::io::Error> for MappedGraphError {
+    fn from(e: std::io::Error) -> Self {
+        MappedGraphError::Io(e.to_string())
+    }
+}
+
+// ============================================================================
+// Layout
+// ============================================================================
+
+/// Byte ranges of each section, derived from the header counts
+#[derive(Debug, Clone)]
+struct Layout {
+    tokens: Range<usize>,
+    edges: Range<usize>,
+    nodes: Range<usize>,
+    out_offsets: Range<usize>,
+    out_index: Range<usize>,
+    in_offsets: Range<usize>,
+    in_index: Range<usize>,
+}
+
+impl Layout {
+    fn new(token_count: usize, node_count: usize, edge_count: usize) -> Self {
+        let mut cursor = HEADER_SIZE;
+        let mut next = |len: usize| {
+            let range = cursor..cursor + len;
+            cursor += len;
+            range
+        };
+        Self {
+            tokens: next(token_count * TOKEN_SIZE),
+            edges: next(edge_count * EDGE_SIZE),
+            nodes: next(node_count * 4),
+            out_offsets: next((node_count + 1) * 4),
+            out_index: next(edge_count * 4),
+            in_offsets: next((node_count + 1) * 4),
+            in_index: next(edge_count * 4),
+        }
+    }
+
+    fn total_len(&self) -> usize {
+        self.in_index.end
+    }
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+}
+
+fn read_u64(bytes: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap())
+}
+
+/// Binary search over `count` sorted keys read by `key(i)`
+fn search<K: Ord>(count: usize, target: K, key: impl Fn(usize) -> K) -> Option<usize> {
+    let (mut lo, mut hi) = (0, count);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        match key(mid).cmp(&target) {
+            std::cmp::Ordering::Less => lo = mid + 1,
+            std::cmp::Ordering::Greater => hi = mid,
+            std::cmp::Ordering::Equal => return Some(mid),
+        }
+    }
+    None
+}
+
+// ============================================================================
+// Writer
+// ============================================================================
+
+/// Serialize tokens, nodes and edges of a snapshot into the mapped format
+///
+/// Edges whose endpoints are not graph nodes are dropped.
+pub fn encode(snapshot: &RuntimeSnapshot) -> Vec<u8> {
+    let mut tokens: Vec<&Token> = snapshot.tokens.iter().collect();
+    tokens.sort_by_key(|token| token.id);
+    tokens.dedup_by_key(|token| token.id);
+
+    let mut nodes = snapshot.graph_nodes.clone();
+    nodes.sort_unstable();
+    nodes.dedup();
+    let position = |node: NodeId| nodes.binary_search(&node).ok();
+
+    let mut edges: Vec<&(EdgeId, EdgeInfo)> = snapshot
+        .graph_edges
+        .iter()
+        .filter(|(_, info)| position(info.from_id).is_some() && position(info.to_id).is_some())
+        .collect();
+    edges.sort_by_key(|(edge_id, _)| *edge_id);
+    edges.dedup_by_key(|(edge_id, _)| *edge_id);
+
+    let layout = Layout::new(tokens.len(), nodes.len(), edges.len());
+    let mut out = Vec::with_capacity(layout.total_len());
+    out.extend_from_slice(&MAPPED_GRAPH_MAGIC);
+    out.extend_from_slice(&MAPPED_GRAPH_VERSION.to_le_bytes());
+    out.extend_from_slice(&(tokens.len() as u32).to_le_bytes());
+    out.extend_from_slice(&(nodes.len() as u32).to_le_bytes());
+    out.extend_from_slice(&(edges.len() as u32).to_le_bytes());
+    out.resize(HEADER_SIZE, 0);
+
+    for token in &tokens {
+        out.extend_from_slice(&token.to_bytes());
+    }
+    for (edge_id, info) in &edges {
+        out.extend_from_slice(&edge_id.to_le_bytes());
+        out.extend_from_slice(&info.from_id.to_le_bytes());
+        out.extend_from_slice(&info.to_id.to_le_bytes());
+        out.extend_from_slice(&info.weight.to_le_bytes());
+        out.extend_from_slice(&[info.edge_type, info.bidirectional as u8, info.active_levels, 0]);
+    }
+    for node in &nodes {
+        out.extend_from_slice(&node.to_le_bytes());
+    }
+
+    // CSR adjacency: edge indices grouped by endpoint position
+    let endpoints: [fn(&EdgeInfo) -> NodeId; 2] = [|info| info.from_id, |info| info.to_id];
+    for endpoint in endpoints {
+        let mut grouped: Vec<Vec<u32>> = vec![Vec::new(); nodes.len()];
+        for (index, (_, info)) in edges.iter().enumerate() {
+            if let Some(pos) = position(endpoint(info)) {
+                grouped[pos].push(index as u32);
+            }
+        }
+        let mut offset = 0u32;
+        out.extend_from_slice(&offset.to_le_bytes());
+        for group in &grouped {
+            offset += group.len() as u32;
+            out.extend_from_slice(&offset.to_le_bytes());
+        }
+        for index in grouped.iter().flatten() {
+            out.extend_from_slice(&index.to_le_bytes());
+        }
+    }
+
+    debug_assert_eq!(out.len(), layout.total_len());
+    out
+}
+
+// ============================================================================
+// MappedGraph
+// ============================================================================
+
+enum Backing {
+    Mapped(Mmap),
+    Owned(Vec<u8>),
+}
+
+impl Backing {
+    fn bytes(&self) -> &[u8] {
+        match self {
+            Backing::Mapped(map) => map,
+            Backing::Owned(bytes) => bytes,
+        }
+    }
+}
+
+/// Read-only graph over a mapped snapshot file
+pub struct MappedGraph {
+    data: Backing,
+    layout: Layout,
+    token_count: usize,
+    node_count: usize,
+    edge_count: usize,
+}
+
+impl MappedGraph {
+    /// Write `snapshot` to `path` in the mapped format, sealed with the
+    /// installed at-rest cipher if any
+    pub fn write(path: impl AsRef<Path>, snapshot: &RuntimeSnapshot) -> Result<(), MappedGraphError> {
+        Self::write_with(path, snapshot, encryption::installed_cipher().as_deref())
+    }
+
+    /// [`write`](Self::write) with an explicit cipher
+    pub fn write_with(
+        path: impl AsRef<Path>,
+        snapshot: &RuntimeSnapshot,
+        cipher: Option<&FileCipher>,
+    ) -> Result<(), MappedGraphError> {
+        let data = encryption::seal_file(cipher, encode(snapshot), path.as_ref())?;
+        let mut file = File::create(path)?;
+        file.write_all(&data)?;
+        file.sync_all()?;
+        Ok(())
+    }
+
+    /// Map a file written by [`MappedGraph::write`]
+    ///
+    /// Only the header is validated; the file must not be modified while
+    /// it is mapped. Sealed files are decrypted with the installed cipher.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, MappedGraphError> {
+        Self::open_with(path, encryption::installed_cipher().as_deref())
+    }
+
+    /// [`open`](Self::open) with an explicit cipher
+    pub fn open_with(path: impl AsRef<Path>, cipher: Option<&FileCipher>) -> Result<Self, MappedGraphError> {
+        let path = path.as_ref();
+        let file = File::open(path)?;
+        // SAFETY: the mapping is read-only and the file is treated as
+        // immutable for the lifetime of the graph (see above)
+        let map = unsafe { Mmap::map(&file)? };
+        if cipher.is_some() || FileCipher::is_sealed(&map) {
+            return Self::from_bytes(encryption::open_file(cipher, map.to_vec(), path)?);
+        }
+        Self::from_backing(Backing::Mapped(map))
+    }
+
+    /// Open the `graph.map` of the checkpoint directory `dir`
+    ///
+    /// Checkpoint sections are sealed under the checkpoint ID (the
+    /// directory name), so a sealed section is decrypted with that scope.
+    pub fn open_checkpoint(dir: impl AsRef<Path>) -> Result<Self, MappedGraphError> {
+        Self::open_checkpoint_with(dir, encryption::installed_cipher().as_deref())
+    }
+
+    /// [`open_checkpoint`](Self::open_checkpoint) with an explicit cipher
+    pub fn open_checkpoint_with(dir: impl AsRef<Path>, cipher: Option<&FileCipher>) -> Result<Self, MappedGraphError> {
+        let dir = dir.as_ref();
+        let path = dir.join(CHECKPOINT_FILE);
+        let Some(cipher) = cipher else {
+            return Self::open_with(path, None);
+        };
+        let id = dir.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+        let data = std::fs::read(&path)?;
+        Self::from_bytes(cipher.open(&data, &encryption::scoped_name(&id, CHECKPOINT_FILE))?)
+    }
+
+    /// Graph over an in-memory copy of the file
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, MappedGraphError> {
+        Self::from_backing(Backing::Owned(bytes))
+    }
+
+    fn from_backing(data: Backing) -> Result<Self, MappedGraphError> {
+        let bytes = data.bytes();
+        if bytes.len() < HEADER_SIZE || bytes[..8] != MAPPED_GRAPH_MAGIC {
+            return Err(MappedGraphError::Format("missing header".to_string()));
+        }
+        let version = read_u32(bytes, 8);
+        if version != MAPPED_GRAPH_VERSION {
+            return Err(MappedGraphError::Format(format!("unsupported version {}", version)));
+        }
+        let token_count = read_u32(bytes, 12) as usize;
+        let node_count = read_u32(bytes, 16) as usize;
+        let edge_count = read_u32(bytes, 20) as usize;
+
+        let layout = Layout::new(token_count, node_count, edge_count);
+        if bytes.len() != layout.total_len() {
+            return Err(MappedGraphError::Format(format!(
+                "expected {} bytes, found {}",
+                layout.total_len(),
+                bytes.len()
+            )));
+        }
+
+        Ok(Self {
+            data,
+            layout,
+            token_count,
+            node_count,
+            edge_count,
+        })
+    }
+
+    fn bytes(&self) -> &[u8] {
+        self.data.bytes()
+    }
+
+    /// Whether the data is memory-mapped (false for [`MappedGraph::from_bytes`])
+    pub fn is_mapped(&self) -> bool {
+        matches!(self.data, Backing::Mapped(_))
+    }
+
+    // ------------------------------------------------------------------------
+    // Tokens
+    // ------------------------------------------------------------------------
+
+    pub fn token_count(&self) -> usize {
+        self.token_count
+    }
+
+    fn token_at(&self, index: usize) -> Token {
+        let at = self.layout.tokens.start + index * TOKEN_SIZE;
+        Token::from_bytes(self.bytes()[at..at + TOKEN_SIZE].try_into().unwrap())
+    }
+
+    /// Get a token by ID
+    pub fn get_token(&self, token_id: u32) -> Option<Token> {
+        search(self.token_count, token_id, |i| self.token_at(i).id).map(|i| self.token_at(i))
+    }
+
+    /// Iterate tokens in ID order
+    pub fn tokens(&self) -> impl Iterator<Item = Token> + '_ {
+        (0..self.token_count).map(|i| self.token_at(i))
+    }
+
+    // ------------------------------------------------------------------------
+    // Nodes and edges
+    // ------------------------------------------------------------------------
+
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    fn node_at(&self, index: usize) -> NodeId {
+        read_u32(self.bytes(), self.layout.nodes.start + index * 4)
+    }
+
+    fn node_position(&self, node_id: NodeId) -> Option<usize> {
+        search(self.node_count, node_id, |i| self.node_at(i))
+    }
+
+    pub fn contains_node(&self, node_id: NodeId) -> bool {
+        self.node_position(node_id).is_some()
+    }
+
+    /// Node IDs in ascending order
+    pub fn get_nodes(&self) -> Vec<NodeId> {
+        (0..self.node_count).map(|i| self.node_at(i)).collect()
+    }
+
+    fn edge_id_at(&self, index: usize) -> EdgeId {
+        read_u64(self.bytes(), self.layout.edges.start + index * EDGE_SIZE)
+    }
+
+    fn edge_at(&self, index: usize) -> EdgeInfo {
+        let bytes = self.bytes();
+        let at = self.layout.edges.start + index * EDGE_SIZE;
+        EdgeInfo {
+            from_id: read_u32(bytes, at + 8),
+            to_id: read_u32(bytes, at + 12),
+            weight: f32::from_bits(read_u32(bytes, at + 16)),
+            edge_type: bytes[at + 20],
+            bidirectional: bytes[at + 21] != 0,
+            active_levels: bytes[at + 22],
+        }
+    }
+
+    pub fn contains_edge(&self, edge_id: EdgeId) -> bool {
+        search(self.edge_count, edge_id, |i| self.edge_id_at(i)).is_some()
+    }
+
+    /// Get edge metadata
+    pub fn get_edge(&self, edge_id: EdgeId) -> Option<EdgeInfo> {
+        search(self.edge_count, edge_id, |i| self.edge_id_at(i)).map(|i| self.edge_at(i))
+    }
+
+    /// Edge indices adjacent to a node in one CSR table
+    fn adjacent(&self, node_id: NodeId, offsets: &Range<usize>, index: &Range<usize>) -> Vec<usize> {
+        let Some(pos) = self.node_position(node_id) else {
+            return Vec::new();
+        };
+        let bytes = self.bytes();
+        let start = read_u32(bytes, offsets.start + pos * 4) as usize;
+        let end = (read_u32(bytes, offsets.start + (pos + 1) * 4) as usize).min(self.edge_count);
+        (start..end)
+            .map(|i| read_u32(bytes, index.start + i * 4) as usize)
+            .filter(|&edge| edge < self.edge_count)
+            .collect()
+    }
+
+    fn outgoing(&self, node_id: NodeId) -> Vec<usize> {
+        self.adjacent(node_id, &self.layout.out_offsets, &self.layout.out_index)
+    }
+
+    fn incoming(&self, node_id: NodeId) -> Vec<usize> {
+        self.adjacent(node_id, &self.layout.in_offsets, &self.layout.in_index)
+    }
+
+    /// Get neighbors of a node (see [`super::Graph::get_neighbors`])
+    pub fn get_neighbors(&self, node_id: NodeId, direction: Direction) -> Vec<(NodeId, EdgeId)> {
+        let out = |neighbors: &mut Vec<_>| {
+            for edge in self.outgoing(node_id) {
+                neighbors.push((self.edge_at(edge).to_id, self.edge_id_at(edge)));
+            }
+        };
+        let mut neighbors = Vec::new();
+        match direction {
+            Direction::Outgoing => out(&mut neighbors),
+            Direction::Incoming => {
+                for edge in self.incoming(node_id) {
+                    neighbors.push((self.edge_at(edge).from_id, self.edge_id_at(edge)));
+                }
+            }
+            Direction::Both => {
+                out(&mut neighbors);
+                for edge in self.incoming(node_id) {
+                    let info = self.edge_at(edge);
+                    if info.bidirectional {
+                        neighbors.push((info.from_id, self.edge_id_at(edge)));
+                    }
+                }
+            }
+        }
+        neighbors
+    }
+
+    /// Get node degree
+    pub fn get_degree(&self, node_id: NodeId, direction: Direction) -> usize {
+        self.get_neighbors(node_id, direction).len()
+    }
+
+    /// Spreading activation from a source node (see [`super::Graph::spreading_activation`])
+    ///
+    /// Returns the result and the activation state of every reached node.
+    pub fn spreading_activation(
+        &self,
+        source_id: NodeId,
+        initial_energy: f32,
+        config: &SignalConfig,
+    ) -> (ActivationResult, HashMap<NodeId, NodeActivation>) {
+        let mut activations = HashMap::new();
+        if config.validate().is_err() || !self.contains_node(source_id) {
+            return (ActivationResult::default(), activations);
+        }
+
+        let result = spread(
+            source_id,
+            initial_energy,
+            config,
+            |node_id| {
+                let outgoing = self.outgoing(node_id).into_iter().map(|edge| {
+                    let info = self.edge_at(edge);
+                    (info.to_id, info.weight, info.active_levels)
+                });
+                let reverse = self
+                    .incoming(node_id)
+                    .into_iter()
+                    .map(|edge| self.edge_at(edge))
+                    .filter(|info| info.bidirectional && info.from_id != node_id)
+                    .map(|info| (info.from_id, info.weight, info.active_levels));
+                outgoing.chain(reverse).collect()
+            },
+            |node_id, energy, source| accumulate_activation(&mut activations, node_id, energy, source, config),
+        );
+        (result, activations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cdna::CDNA;
+    use crate::graph::Graph;
+
+    fn snapshot() -> (RuntimeSnapshot, Graph) {
+        let mut graph = Graph::new();
+        for i in (0..10).rev() {
+            graph.add_node(i);
+        }
+        for i in 0..9 {
+            let edge_id = Graph::compute_edge_id(i, i + 1, 0);
+            graph.add_edge(edge_id, i, i + 1, 0, 0.9, i % 2 == 0).unwrap();
+        }
+        graph.add_edge(Graph::compute_edge_id(3, 7, 1), 3, 7, 1, 0.6, false).unwrap();
+        graph.set_edge_levels(Graph::compute_edge_id(3, 7, 1), 0b0000_0011);
+
+        let snapshot = RuntimeSnapshot {
+            tokens: (1..=5).rev().map(Token::new).collect(),
+            next_token_id: 6,
+            connections: Vec::new(),
+            next_connection_id: 1,
+            grid_tokens: Vec::new(),
+            graph_nodes: graph.get_nodes(),
+            graph_edges: graph.edges().map(|(id, info)| (id, info.clone())).collect(),
+            cdna: CDNA::default(),
+            labels: Vec::new(),
+        };
+        (snapshot, graph)
+    }
+
+    #[test]
+    fn test_matches_graph() {
+        let (snapshot, mut graph) = snapshot();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("graph.map");
+        MappedGraph::write(&path, &snapshot).unwrap();
+        let mapped = MappedGraph::open(&path).unwrap();
+
+        assert!(mapped.is_mapped());
+        assert_eq!(mapped.token_count(), 5);
+        assert_eq!(mapped.get_token(3).map(|t| t.id), Some(3));
+        assert!(mapped.get_token(9).is_none());
+        assert_eq!(mapped.tokens().map(|t| t.id).collect::<Vec<_>>(), vec![1, 2, 3, 4, 5]);
+
+        assert_eq!(mapped.node_count(), 10);
+        assert_eq!(mapped.edge_count(), 10);
+        assert!(!mapped.contains_node(10));
+        let edge_id = Graph::compute_edge_id(3, 7, 1);
+        assert_eq!(mapped.get_edge(edge_id).map(|e| (e.to_id, e.active_levels)), Some((7, 0b0000_0011)));
+
+        let sorted = |mut v: Vec<(NodeId, EdgeId)>| {
+            v.sort_unstable();
+            v
+        };
+        for node in [0, 3, 4, 7] {
+            for direction in [Direction::Outgoing, Direction::Incoming, Direction::Both] {
+                assert_eq!(sorted(mapped.get_neighbors(node, direction)), sorted(graph.get_neighbors(node, direction)));
+            }
+        }
+
+        let config = SignalConfig::default();
+        let expected = graph.spreading_activation(0, 1.0, None);
+        let (result, activations) = mapped.spreading_activation(0, 1.0, &config);
+        let energies = |r: &ActivationResult| {
+            let mut e: Vec<_> = r.activated_nodes.iter().map(|n| (n.node_id, n.energy.to_bits())).collect();
+            e.sort_unstable();
+            e
+        };
+        assert_eq!(energies(&result), energies(&expected));
+        assert_eq!(activations.get(&4).map(|a| a.energy), graph.get_activation(4));
+    }
+
+    #[test]
+    fn test_rejects_bad_files() {
+        let (snapshot, _) = snapshot();
+        let mut bytes = encode(&snapshot);
+        assert!(MappedGraph::from_bytes(bytes[..bytes.len() - 1].to_vec()).is_err());
+        bytes[0] = b'X';
+        assert!(matches!(MappedGraph::from_bytes(bytes), Err(MappedGraphError::Format(_))));
+        assert!(matches!(MappedGraph::open("/nonexistent/graph.map"), Err(MappedGraphError::Io(_))));
+    }
+
+    #[test]
+    fn test_sealed_file() {
+        let (snapshot, _) = snapshot();
+        let cipher = FileCipher::new(&FileCipher::generate_key());
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("graph.map");
+        MappedGraph::write_with(&path, &snapshot, Some(&cipher)).unwrap();
+        assert!(FileCipher::is_sealed(&std::fs::read(&path).unwrap()));
+
+        let graph = MappedGraph::open_with(&path, Some(&cipher)).unwrap();
+        assert!(!graph.is_mapped());
+        assert_eq!(graph.edge_count(), 10);
+        assert!(matches!(
+            MappedGraph::open_with(&path, None),
+            Err(MappedGraphError::Encryption(EncryptionError::MissingKey))
+        ));
+
+        // A plain file is not trusted when a cipher is configured
+        let plain = dir.path().join("plain.map");
+        MappedGraph::write_with(&plain, &snapshot, None).unwrap();
+        assert!(matches!(
+            MappedGraph::open_with(&plain, Some(&cipher)),
+            Err(MappedGraphError::Encryption(EncryptionError::NotSealed))
+        ));
+    }
+}
@@ -0,0 +1,501 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Sharded Graph - adjacency split by node ID for concurrent access
+//!
+//! Each shard owns the nodes with `node_id % shards == i`: their outgoing
+//! edges (with metadata) and the incoming edge list. A write locks at most
+//! the two shards of an edge's endpoints (in index order, so writers never
+//! deadlock); reads lock one shard per visited node, so queries keep running
+//! while tokens are added elsewhere in the graph.
+//!
+//! Spreading activation runs on shared references and returns its
+//! activations in the result instead of storing them in the graph.
+
+use super::{
+    accumulate_activation, spread, ActivationResult, Direction, EdgeId, EdgeInfo, Graph,
+    NodeActivation, NodeId, SignalConfig,
+};
+use crate::connection_v3::active_levels;
+use dashmap::DashMap;
+use parking_lot::{RwLock, RwLockWriteGuard};
+use std::collections::HashMap;
+
+/// Incoming edge entry (kept in the target's shard)
+#[derive(Debug, Clone, Copy)]
+struct InEdge {
+    edge_id: EdgeId,
+    from_id: NodeId,
+    bidirectional: bool,
+}
+
+#[derive(Default)]
+struct Shard {
+    /// Outgoing edges for each node
+    adjacency_out: HashMap<NodeId, Vec<EdgeId>>,
+    /// Incoming edges for each node
+    adjacency_in: HashMap<NodeId, Vec<InEdge>>,
+    /// Metadata of edges whose source is in this shard
+    edge_map: HashMap<EdgeId, EdgeInfo>,
+}
+
+/// Graph with per-node-range locking
+pub struct ShardedGraph {
+    shards: Box<[RwLock<Shard>]>,
+    /// Edge → source node, to find the owning shard
+    edge_sources: DashMap<EdgeId, NodeId>,
+    signal_config: RwLock<SignalConfig>,
+}
+
+impl ShardedGraph {
+    pub fn new() -> Self {
+        Self::with_shards(crate::grid::default_shard_count())
+    }
+
+    /// `shards` is rounded up to a power of two
+    pub fn with_shards(shards: usize) -> Self {
+        let count = shards.max(1).next_power_of_two();
+        Self {
+            shards: (0..count).map(|_| RwLock::new(Shard::default())).collect(),
+            edge_sources: DashMap::new(),
+            signal_config: RwLock::new(SignalConfig::default()),
+        }
+    }
+
+    /// Copy of an existing graph
+    pub fn from_graph(graph: &Graph) -> Self {
+        let sharded = Self::new();
+        sharded.load(graph);
+        sharded
+    }
+
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    fn index(&self, node_id: NodeId) -> usize {
+        node_id as usize & (self.shards.len() - 1)
+    }
+
+    /// Write-lock the shards of two nodes in index order
+    fn write_pair(&self, a: usize, b: usize) -> (RwLockWriteGuard<'_, Shard>, Option<RwLockWriteGuard<'_, Shard>>) {
+        if a == b {
+            (self.shards[a].write(), None)
+        } else if a < b {
+            let first = self.shards[a].write();
+            (first, Some(self.shards[b].write()))
+        } else {
+            let second = self.shards[b].write();
+            (self.shards[a].write(), Some(second))
+        }
+    }
+
+    /// Add node to graph
+    /// Returns true if node was added, false if already exists
+    pub fn add_node(&self, node_id: NodeId) -> bool {
+        let mut shard = self.shards[self.index(node_id)].write();
+        if shard.adjacency_out.contains_key(&node_id) {
+            return false;
+        }
+        shard.adjacency_out.insert(node_id, Vec::new());
+        shard.adjacency_in.insert(node_id, Vec::new());
+        true
+    }
+
+    /// Remove node and all edges connected to it
+    /// Returns true if node was removed
+    pub fn remove_node(&self, node_id: NodeId) -> bool {
+        // Detach the node first so no new edge can reference it
+        let (out_edges, in_edges) = {
+            let mut shard = self.shards[self.index(node_id)].write();
+            let Some(out_edges) = shard.adjacency_out.remove(&node_id) else {
+                return false;
+            };
+            let in_edges = shard.adjacency_in.remove(&node_id).unwrap_or_default();
+            (out_edges, in_edges)
+        };
+
+        for edge_id in out_edges {
+            self.remove_edge(edge_id);
+        }
+        for edge in in_edges {
+            self.remove_edge(edge.edge_id);
+        }
+        true
+    }
+
+    /// Check if node exists in graph
+    pub fn contains_node(&self, node_id: NodeId) -> bool {
+        self.shards[self.index(node_id)].read().adjacency_out.contains_key(&node_id)
+    }
+
+    /// Get number of nodes
+    pub fn node_count(&self) -> usize {
+        self.shards.iter().map(|shard| shard.read().adjacency_out.len()).sum()
+    }
+
+    /// Get all node IDs
+    pub fn get_nodes(&self) -> Vec<NodeId> {
+        self.shards
+            .iter()
+            .flat_map(|shard| shard.read().adjacency_out.keys().copied().collect::<Vec<_>>())
+            .collect()
+    }
+
+    /// Add edge to graph
+    /// Both nodes must already exist
+    /// Returns true if edge was added
+    pub fn add_edge(
+        &self,
+        edge_id: EdgeId,
+        from_id: NodeId,
+        to_id: NodeId,
+        edge_type: u8,
+        weight: f32,
+        bidirectional: bool,
+    ) -> Result<bool, String> {
+        let (from_index, to_index) = (self.index(from_id), self.index(to_id));
+        let (mut from_shard, mut to_shard) = self.write_pair(from_index, to_index);
+
+        if !from_shard.adjacency_out.contains_key(&from_id) {
+            return Err(format!("Node {} does not exist", from_id));
+        }
+        let target = to_shard.as_deref_mut().unwrap_or(&mut *from_shard);
+        if !target.adjacency_out.contains_key(&to_id) {
+            return Err(format!("Node {} does not exist", to_id));
+        }
+        if from_shard.edge_map.contains_key(&edge_id) || self.edge_sources.contains_key(&edge_id) {
+            return Ok(false);
+        }
+
+        from_shard.edge_map.insert(
+            edge_id,
+            EdgeInfo {
+                from_id,
+                to_id,
+                edge_type,
+                weight,
+                bidirectional,
+                active_levels: active_levels::ALL,
+            },
+        );
+        from_shard.adjacency_out.entry(from_id).or_default().push(edge_id);
+        let target = to_shard.as_deref_mut().unwrap_or(&mut *from_shard);
+        target.adjacency_in.entry(to_id).or_default().push(InEdge {
+            edge_id,
+            from_id,
+            bidirectional,
+        });
+        self.edge_sources.insert(edge_id, from_id);
+        Ok(true)
+    }
+
+    /// Remove edge from graph
+    /// Returns true if edge was removed
+    pub fn remove_edge(&self, edge_id: EdgeId) -> bool {
+        let Some((_, from_id)) = self.edge_sources.remove(&edge_id) else {
+            return false;
+        };
+
+        let to_id = {
+            let mut shard = self.shards[self.index(from_id)].write();
+            let Some(info) = shard.edge_map.remove(&edge_id) else {
+                return false;
+            };
+            if let Some(out_edges) = shard.adjacency_out.get_mut(&from_id) {
+                out_edges.retain(|&e| e != edge_id);
+            }
+            info.to_id
+        };
+
+        let mut shard = self.shards[self.index(to_id)].write();
+        if let Some(in_edges) = shard.adjacency_in.get_mut(&to_id) {
+            in_edges.retain(|e| e.edge_id != edge_id);
+        }
+        true
+    }
+
+    /// Check if edge exists
+    pub fn contains_edge(&self, edge_id: EdgeId) -> bool {
+        self.edge_sources.contains_key(&edge_id)
+    }
+
+    /// Get a copy of edge metadata
+    pub fn get_edge(&self, edge_id: EdgeId) -> Option<EdgeInfo> {
+        let from_id = *self.edge_sources.get(&edge_id)?;
+        self.shards[self.index(from_id)].read().edge_map.get(&edge_id).cloned()
+    }
+
+    /// Set edge weight
+    /// Returns true if the edge exists
+    pub fn set_edge_weight(&self, edge_id: EdgeId, weight: f32) -> bool {
+        self.update_edge(edge_id, |edge| edge.weight = weight)
+    }
+
+    /// Set the L1-L8 levels an edge is active on (see `SignalConfig::layer_mask`)
+    /// Returns true if the edge exists
+    pub fn set_edge_levels(&self, edge_id: EdgeId, levels: u8) -> bool {
+        self.update_edge(edge_id, |edge| edge.active_levels = levels)
+    }
+
+    fn update_edge(&self, edge_id: EdgeId, update: impl FnOnce(&mut EdgeInfo)) -> bool {
+        let Some(from_id) = self.edge_sources.get(&edge_id).map(|e| *e) else {
+            return false;
+        };
+        match self.shards[self.index(from_id)].write().edge_map.get_mut(&edge_id) {
+            Some(edge) => {
+                update(edge);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Get number of edges
+    pub fn edge_count(&self) -> usize {
+        self.edge_sources.len()
+    }
+
+    /// Copy of all edges
+    pub fn edges(&self) -> Vec<(EdgeId, EdgeInfo)> {
+        self.shards
+            .iter()
+            .flat_map(|shard| {
+                shard
+                    .read()
+                    .edge_map
+                    .iter()
+                    .map(|(&id, info)| (id, info.clone()))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Get neighbors of a node
+    /// Returns list of (neighbor_id, edge_id) tuples
+    pub fn get_neighbors(&self, node_id: NodeId, direction: Direction) -> Vec<(NodeId, EdgeId)> {
+        let shard = self.shards[self.index(node_id)].read();
+        let outgoing = || {
+            shard
+                .adjacency_out
+                .get(&node_id)
+                .into_iter()
+                .flatten()
+                .filter_map(|edge_id| shard.edge_map.get(edge_id).map(|edge| (edge.to_id, *edge_id)))
+        };
+        let incoming = shard.adjacency_in.get(&node_id).into_iter().flatten();
+
+        match direction {
+            Direction::Outgoing => outgoing().collect(),
+            Direction::Incoming => incoming.map(|e| (e.from_id, e.edge_id)).collect(),
+            Direction::Both => outgoing()
+                .chain(incoming.filter(|e| e.bidirectional).map(|e| (e.from_id, e.edge_id)))
+                .collect(),
+        }
+    }
+
+    /// Get degree of a node (number of edges)
+    pub fn get_degree(&self, node_id: NodeId, direction: Direction) -> usize {
+        let shard = self.shards[self.index(node_id)].read();
+        let out = shard.adjacency_out.get(&node_id).map_or(0, Vec::len);
+        let incoming = shard.adjacency_in.get(&node_id);
+        match direction {
+            Direction::Outgoing => out,
+            Direction::Incoming => incoming.map_or(0, Vec::len),
+            Direction::Both => out + incoming.map_or(0, |edges| edges.iter().filter(|e| e.bidirectional).count()),
+        }
+    }
+
+    /// Replace the default spreading activation configuration
+    pub fn set_signal_config(&self, config: SignalConfig) -> Result<(), String> {
+        config.validate()?;
+        *self.signal_config.write() = config;
+        Ok(())
+    }
+
+    pub fn signal_config(&self) -> SignalConfig {
+        self.signal_config.read().clone()
+    }
+
+    /// Spreading activation from a source node (see [`Graph::spreading_activation`])
+    ///
+    /// Returns the result and the activation state of every reached node.
+    pub fn spreading_activation(
+        &self,
+        source_id: NodeId,
+        initial_energy: f32,
+        custom_config: Option<SignalConfig>,
+    ) -> (ActivationResult, HashMap<NodeId, NodeActivation>) {
+        let config = custom_config.unwrap_or_else(|| self.signal_config());
+        let mut activations = HashMap::new();
+        if config.validate().is_err() || !self.contains_node(source_id) {
+            return (ActivationResult::default(), activations);
+        }
+
+        let result = spread(
+            source_id,
+            initial_energy,
+            &config,
+            |node_id| {
+                let (mut neighbors, reverse): (Vec<_>, Vec<InEdge>) = {
+                    let shard = self.shards[self.index(node_id)].read();
+                    let outgoing = shard
+                        .adjacency_out
+                        .get(&node_id)
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|edge_id| shard.edge_map.get(edge_id))
+                        .map(|edge| (edge.to_id, edge.weight, edge.active_levels))
+                        .collect();
+                    let reverse = shard
+                        .adjacency_in
+                        .get(&node_id)
+                        .into_iter()
+                        .flatten()
+                        .filter(|e| e.bidirectional && e.from_id != node_id)
+                        .copied()
+                        .collect();
+                    (outgoing, reverse)
+                };
+                // Bidirectional edges also spread against their direction;
+                // their metadata lives in the source's shard
+                for e in reverse {
+                    let shard = self.shards[self.index(e.from_id)].read();
+                    if let Some(edge) = shard.edge_map.get(&e.edge_id) {
+                        neighbors.push((e.from_id, edge.weight, edge.active_levels));
+                    }
+                }
+                neighbors
+            },
+            |node_id, energy, source| accumulate_activation(&mut activations, node_id, energy, source, &config),
+        );
+        (result, activations)
+    }
+
+    /// Replace all nodes and edges with those of `graph`
+    pub fn load(&self, graph: &Graph) {
+        self.clear();
+        for node_id in graph.get_nodes() {
+            self.add_node(node_id);
+        }
+        for (edge_id, info) in graph.edges() {
+            let _ = self.add_edge(edge_id, info.from_id, info.to_id, info.edge_type, info.weight, info.bidirectional);
+            self.set_edge_levels(edge_id, info.active_levels);
+        }
+    }
+
+    /// Single-lock copy for algorithms only [`Graph`] implements (paths, subgraphs)
+    pub fn to_graph(&self) -> Graph {
+        let mut graph = Graph::new();
+        for node_id in self.get_nodes() {
+            graph.add_node(node_id);
+        }
+        for (edge_id, info) in self.edges() {
+            let _ = graph.add_edge(edge_id, info.from_id, info.to_id, info.edge_type, info.weight, info.bidirectional);
+            graph.set_edge_levels(edge_id, info.active_levels);
+        }
+        graph
+    }
+
+    /// Clear all nodes and edges
+    pub fn clear(&self) {
+        let mut shards: Vec<_> = self.shards.iter().map(|shard| shard.write()).collect();
+        for shard in shards.iter_mut() {
+            **shard = Shard::default();
+        }
+        self.edge_sources.clear();
+    }
+}
+
+impl Default for ShardedGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn chain(graph: &mut Graph, sharded: &ShardedGraph, nodes: u32) {
+        for i in 0..nodes {
+            graph.add_node(i);
+            sharded.add_node(i);
+        }
+        for i in 0..nodes - 1 {
+            let edge_id = Graph::compute_edge_id(i, i + 1, 0);
+            graph.add_edge(edge_id, i, i + 1, 0, 0.9, i % 2 == 0).unwrap();
+            assert!(sharded.add_edge(edge_id, i, i + 1, 0, 0.9, i % 2 == 0).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_matches_graph() {
+        let mut graph = Graph::new();
+        let sharded = ShardedGraph::with_shards(4);
+        chain(&mut graph, &sharded, 10);
+
+        assert_eq!(sharded.node_count(), 10);
+        assert_eq!(sharded.edge_count(), 9);
+        assert!(!sharded.add_edge(Graph::compute_edge_id(0, 1, 0), 0, 1, 0, 0.5, false).unwrap());
+        assert!(sharded.add_edge(1, 0, 99, 0, 0.5, false).is_err());
+        for direction in [Direction::Outgoing, Direction::Incoming, Direction::Both] {
+            assert_eq!(sharded.get_neighbors(3, direction), graph.get_neighbors(3, direction));
+            assert_eq!(sharded.get_degree(3, direction), graph.get_degree(3, direction));
+        }
+
+        let expected = graph.spreading_activation(0, 1.0, None);
+        let (result, activations) = sharded.spreading_activation(0, 1.0, None);
+        let energies = |r: &ActivationResult| r.activated_nodes.iter().map(|n| (n.node_id, n.energy)).collect::<Vec<_>>();
+        assert_eq!(energies(&result), energies(&expected));
+        assert_eq!(activations.get(&4).map(|a| a.energy), graph.get_activation(4));
+
+        assert!(sharded.remove_node(5));
+        assert!(!sharded.contains_node(5));
+        assert_eq!(sharded.edge_count(), 7);
+        assert!(sharded.get_neighbors(4, Direction::Outgoing).is_empty());
+        assert!(sharded.get_neighbors(6, Direction::Incoming).is_empty());
+        assert_eq!(sharded.to_graph().edge_count(), 7);
+    }
+
+    #[test]
+    fn test_concurrent_writes_and_reads() {
+        let sharded = Arc::new(ShardedGraph::with_shards(8));
+        let writers: Vec<_> = (0..4u32)
+            .map(|t| {
+                let graph = sharded.clone();
+                std::thread::spawn(move || {
+                    for i in 0..250 {
+                        let node = t * 1000 + i;
+                        graph.add_node(node);
+                        if i > 0 {
+                            let edge_id = Graph::compute_edge_id(node - 1, node, 0);
+                            graph.add_edge(edge_id, node - 1, node, 0, 1.0, false).unwrap();
+                        }
+                        graph.get_neighbors(node, Direction::Both);
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        assert_eq!(sharded.node_count(), 1000);
+        assert_eq!(sharded.edge_count(), 996);
+    }
+}
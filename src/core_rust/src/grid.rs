@@ -21,10 +21,13 @@
 //! - Spatial indexing for fast neighbor search
 //! - Field calculations (density, influence)
 //! - Token storage and retrieval
+//! - [`ShardedGrid`]: the same index split into independently locked
+//!   shards, so inserts don't block queries on other shards
 //!
 //! Version: 2.0 (MVP implementation)
 
 use crate::token::{Token, CoordinateSpace};
+use parking_lot::RwLock;
 use std::collections::HashMap;
 
 /// Grid configuration
@@ -248,6 +251,11 @@ impl Grid {
         self.tokens.get(&token_id)
     }
 
+    /// Iterate over all tokens in the grid
+    pub fn tokens(&self) -> impl Iterator<Item = &Token> {
+        self.tokens.values()
+    }
+
     /// Get number of tokens in the grid
     pub fn len(&self) -> usize {
         self.tokens.len()
@@ -412,6 +420,173 @@ impl Default for Grid {
     }
 }
 
+/// Default shard count: 4 per available core, rounded up to a power of two
+pub(crate) fn default_shard_count() -> usize {
+    let cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4);
+    (cores * 4).next_power_of_two()
+}
+
+/// Grid split into independently locked shards by token ID
+///
+/// Each shard is a complete [`Grid`] over a subset of tokens, so a write
+/// only locks the shard of that token. Spatial queries read the shards one
+/// at a time and merge the results; they see every insert that completed
+/// before the query started.
+pub struct ShardedGrid {
+    config: GridConfig,
+    shards: Box<[RwLock<Grid>]>,
+}
+
+impl ShardedGrid {
+    pub fn new() -> Self {
+        Self::with_shards(GridConfig::default(), default_shard_count())
+    }
+
+    /// `shards` is rounded up to a power of two
+    pub fn with_shards(config: GridConfig, shards: usize) -> Self {
+        let count = shards.max(1).next_power_of_two();
+        Self {
+            shards: (0..count).map(|_| RwLock::new(Grid::with_config(config.clone()))).collect(),
+            config,
+        }
+    }
+
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    fn shard(&self, token_id: u32) -> &RwLock<Grid> {
+        &self.shards[token_id as usize & (self.shards.len() - 1)]
+    }
+
+    /// Add a token to the grid
+    pub fn add(&self, token: Token) -> Result<(), &'static str> {
+        self.shard(token.id).write().add(token)
+    }
+
+    /// Remove a token from the grid
+    pub fn remove(&self, token_id: u32) -> Option<Token> {
+        self.shard(token_id).write().remove(token_id)
+    }
+
+    /// Get a copy of a token by ID
+    pub fn get(&self, token_id: u32) -> Option<Token> {
+        self.shard(token_id).read().get(token_id).copied()
+    }
+
+    /// Get number of tokens in the grid
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| shard.read().len()).sum()
+    }
+
+    /// Check if grid is empty
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(|shard| shard.read().is_empty())
+    }
+
+    /// Copy of all tokens, taken with every shard read-locked at once
+    pub fn tokens(&self) -> Vec<Token> {
+        let shards: Vec<_> = self.shards.iter().map(|shard| shard.read()).collect();
+        shards.iter().flat_map(|grid| grid.tokens().copied()).collect()
+    }
+
+    /// Replace all tokens; on error the grid is left unchanged
+    pub fn replace(&self, tokens: impl IntoIterator<Item = Token>) -> Result<(), &'static str> {
+        let mut rebuilt: Vec<Grid> = (0..self.shards.len())
+            .map(|_| Grid::with_config(self.config.clone()))
+            .collect();
+        let mask = self.shards.len() - 1;
+        for token in tokens {
+            rebuilt[token.id as usize & mask].add(token)?;
+        }
+
+        let mut shards: Vec<_> = self.shards.iter().map(|shard| shard.write()).collect();
+        for (shard, grid) in shards.iter_mut().zip(rebuilt) {
+            **shard = grid;
+        }
+        Ok(())
+    }
+
+    /// Remove all tokens
+    pub fn clear(&self) {
+        for shard in self.shards.iter() {
+            *shard.write() = Grid::with_config(self.config.clone());
+        }
+    }
+
+    /// Find neighbors within radius in a specific space
+    pub fn find_neighbors(
+        &self,
+        center_token_id: u32,
+        space: CoordinateSpace,
+        radius: f32,
+        max_results: usize,
+    ) -> Vec<(u32, f32)> {
+        let Some(center) = self.get(center_token_id) else {
+            return Vec::new();
+        };
+        let [x, y, z] = center.get_coordinates(space);
+
+        let mut results = self.range_query(space, x, y, z, radius);
+        results.retain(|&(id, _)| id != center_token_id);
+        results.truncate(max_results);
+        results
+    }
+
+    /// Range query: find all tokens within radius of a point in a space
+    pub fn range_query(
+        &self,
+        space: CoordinateSpace,
+        x: f32,
+        y: f32,
+        z: f32,
+        radius: f32,
+    ) -> Vec<(u32, f32)> {
+        let mut results: Vec<(u32, f32)> = self
+            .shards
+            .iter()
+            .flat_map(|shard| shard.read().range_query(space, x, y, z, radius))
+            .collect();
+        results.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap().then(a.0.cmp(&b.0)));
+        results
+    }
+
+    /// Calculate field influence at a point in a space
+    pub fn calculate_field_influence(
+        &self,
+        space: CoordinateSpace,
+        x: f32,
+        y: f32,
+        z: f32,
+        radius: f32,
+    ) -> f32 {
+        self.shards
+            .iter()
+            .map(|shard| shard.read().calculate_field_influence(space, x, y, z, radius))
+            .sum()
+    }
+
+    /// Calculate node density at a point in a space
+    pub fn calculate_density(
+        &self,
+        space: CoordinateSpace,
+        x: f32,
+        y: f32,
+        z: f32,
+        radius: f32,
+    ) -> f32 {
+        let nearby = self.range_query(space, x, y, z, radius);
+        let volume = (4.0 / 3.0) * std::f32::consts::PI * radius.powi(3);
+        nearby.len() as f32 / volume
+    }
+}
+
+impl Default for ShardedGrid {
+    fn default() -> Self {
+        ShardedGrid::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -533,4 +708,38 @@ mod tests {
         let density = grid.calculate_density(CoordinateSpace::L1Physical, 0.20, 0.00, 0.00, 1.00);
         assert!(density > 0.0);
     }
+
+    #[test]
+    fn test_sharded_grid_matches_grid() {
+        let mut grid = Grid::new();
+        let sharded = ShardedGrid::with_shards(GridConfig::default(), 3);
+        assert_eq!(sharded.shard_count(), 4);
+
+        for i in 0..50 {
+            let mut token = Token::new(i);
+            token.set_coordinates(CoordinateSpace::L1Physical, (i % 10) as f32, (i / 10) as f32, 0.00);
+            grid.add(token).unwrap();
+            sharded.add(token).unwrap();
+        }
+        assert!(sharded.add(Token::new(7)).is_err());
+        assert_eq!(sharded.len(), 50);
+
+        let mut expected = grid.find_neighbors(22, CoordinateSpace::L1Physical, 2.00, 100);
+        expected.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap().then(a.0.cmp(&b.0)));
+        assert_eq!(sharded.find_neighbors(22, CoordinateSpace::L1Physical, 2.00, 100), expected);
+        assert_eq!(
+            sharded.range_query(CoordinateSpace::L1Physical, 5.00, 0.00, 0.00, 2.00).len(),
+            grid.range_query(CoordinateSpace::L1Physical, 5.00, 0.00, 0.00, 2.00).len()
+        );
+
+        assert!(sharded.remove(22).is_some());
+        assert!(sharded.get(22).is_none());
+        assert!(sharded.find_neighbors(22, CoordinateSpace::L1Physical, 2.00, 100).is_empty());
+
+        sharded.replace(grid.tokens().copied()).unwrap();
+        assert_eq!(sharded.len(), 50);
+        assert_eq!(sharded.tokens().len(), 50);
+        sharded.clear();
+        assert!(sharded.is_empty());
+    }
 }
@@ -38,9 +38,13 @@
 /// - **Transparency**: All actions are logged and tracked
 /// - **Immutability**: CDNA changes are versioned and reversible
 
-use crate::cdna::{CDNA, ProfileId};
+use crate::cdna::{CDNA, ProfileId, ProfilePatch};
 use crate::{Token, Connection, ConnectionV3};
 use std::collections::{HashMap, VecDeque};
+use std::path::{Component, Path};
+
+/// Maximum bytes a single sandboxed file write may carry
+pub const MAX_FILE_WRITE_BYTES: usize = 10 * 1024 * 1024;
 
 /// Event types that can be emitted by Guardian
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -63,6 +67,18 @@ pub enum EventType {
     ReflexValidated,
     /// Reflex validation failed
     ReflexValidationFailed,
+    /// An evolution attempt was triggered
+    EvolutionAttempt,
+    /// A sleep-phase consolidation pass finished
+    Consolidation,
+    /// A command passed the Gateway command policy
+    CommandExecuted,
+    /// A command was refused by the Gateway command policy
+    CommandRejected,
+    /// A monitored metric diverged abruptly from its recent behavior
+    AnomalyDetected,
+    /// A graph insert was refused because only protected edges could make room
+    GraphLimitRefused,
 }
 
 /// Event emitted by Guardian
@@ -335,6 +351,17 @@ impl Guardian {
         Ok(())
     }
 
+    /// Apply a ProfilePatch to the current CDNA
+    ///
+    /// The patched CDNA goes through `update_cdna`, so it is validated,
+    /// recorded in the history and announced with a CDNAUpdated event.
+    pub fn patch_cdna(&mut self, patch: &ProfilePatch) -> Result<&CDNA, String> {
+        let mut next = self.cdna;
+        next.apply_patch(patch)?;
+        self.update_cdna(next)?;
+        Ok(&self.cdna)
+    }
+
     /// Get CDNA history
     pub fn cdna_history(&self) -> &VecDeque<CDNA> {
         &self.cdna_history
@@ -601,6 +628,47 @@ impl Guardian {
         Ok(())
     }
 
+    /// Validate a sandboxed file operation requested by an action
+    ///
+    /// `path` must be relative to the executor's sandbox root. Resolving the
+    /// sandbox itself is the executor's job; Guardian only enforces policy.
+    ///
+    /// # Arguments
+    /// * `path` - Path relative to the sandbox root
+    /// * `write_len` - Bytes to be written (None for reads)
+    pub fn validate_file_operation(&self, path: &Path, write_len: Option<usize>) -> Result<(), &'static str> {
+        // 1. Path must be non-empty and relative
+        if path.as_os_str().is_empty() {
+            return Err("File path is empty");
+        }
+
+        if path.is_absolute() {
+            return Err("File path must be relative to the sandbox");
+        }
+
+        // 2. No parent traversal, root or prefix components, no hidden files
+        for component in path.components() {
+            match component {
+                Component::Normal(name) => {
+                    if name.to_string_lossy().starts_with('.') {
+                        return Err("Hidden files are not accessible");
+                    }
+                }
+                Component::CurDir => {}
+                _ => return Err("File path escapes the sandbox"),
+            }
+        }
+
+        // 3. Write size bound
+        if let Some(len) = write_len {
+            if len > MAX_FILE_WRITE_BYTES {
+                return Err("File write exceeds safe maximum (10 MB)");
+            }
+        }
+
+        Ok(())
+    }
+
     // ==================== EVENT SYSTEM ====================
 
     /// Subscribe module to events
@@ -936,6 +1004,26 @@ mod tests {
         assert_eq!(guardian.cdna().profile(), ProfileId::Default);
     }
 
+    #[test]
+    fn test_cdna_patch() {
+        let mut guardian = Guardian::new();
+        let patch = ProfilePatch { max_token_weight: Some(0.8), ..Default::default() };
+
+        let cdna = guardian.patch_cdna(&patch).unwrap();
+        assert_eq!(cdna.max_token_weight, 0.8);
+        assert_eq!(cdna.revision, 1);
+        assert_eq!(guardian.cdna_history().len(), 2);
+
+        // Patched limits are enforced immediately
+        let mut token = Token::new(1);
+        token.weight = 0.9;
+        assert!(guardian.validate_token(&token).is_err());
+
+        let bad = ProfilePatch { min_token_weight: Some(0.9), ..Default::default() };
+        assert!(guardian.patch_cdna(&bad).is_err());
+        assert_eq!(guardian.cdna().revision, 1);
+    }
+
     #[test]
     fn test_token_validation() {
         let mut guardian = Guardian::new();
@@ -1159,6 +1247,23 @@ mod tests {
         assert!(guardian.validate_reflex(&connection).is_ok());
     }
 
+    #[test]
+    fn test_validate_file_operation() {
+        let guardian = Guardian::new();
+
+        assert!(guardian.validate_file_operation(Path::new("notes/todo.txt"), None).is_ok());
+        assert!(guardian.validate_file_operation(Path::new("./log.txt"), Some(16)).is_ok());
+
+        assert!(guardian.validate_file_operation(Path::new(""), None).is_err());
+        assert!(guardian.validate_file_operation(Path::new("/etc/passwd"), None).is_err());
+        assert!(guardian.validate_file_operation(Path::new("../outside.txt"), None).is_err());
+        assert!(guardian.validate_file_operation(Path::new("notes/../../x"), None).is_err());
+        assert!(guardian.validate_file_operation(Path::new(".env"), None).is_err());
+        assert!(guardian
+            .validate_file_operation(Path::new("big.bin"), Some(MAX_FILE_WRITE_BYTES + 1))
+            .is_err());
+    }
+
     // ==================== RESOURCE QUOTA TESTS (v0.41.0) ====================
 
     #[test]
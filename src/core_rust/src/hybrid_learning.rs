@@ -38,7 +38,7 @@
 
 use std::sync::Arc;
 use std::collections::HashMap;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use parking_lot::RwLock;
 use thiserror::Error;
 
@@ -47,8 +47,10 @@ use crate::connection_v3::{
     ConnectionV3, ConnectionProposal, ConnectionType, ConnectionField,
     ConnectionMutability,
 };
+use crate::edge_history::{EdgeHistory, WeightSource};
 use crate::guardian::Guardian;
 use crate::intuition_engine::IdentifiedPattern;
+use crate::safe_mode::SafeMode;
 
 // ============================================================================
 // Unified Proposal Types
@@ -81,6 +83,22 @@ pub enum HybridProposal {
         exploration_weight: f32,
         causal_confidence: f32,
     },
+
+    /// Structural: fold one token into another (queued for review, see `resolve_merge`)
+    Merge(TokenMerge),
+}
+
+/// Proposed merge of two near-duplicate tokens
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TokenMerge {
+    /// Token that remains
+    pub keep: u32,
+    /// Token folded into `keep` and deleted
+    pub absorb: u32,
+    /// Synonym/Similar connection linking the two
+    pub connection_id: u64,
+    /// Cosine similarity of the two token states
+    pub similarity: f32,
 }
 
 /// Outcome of proposal application
@@ -109,6 +127,17 @@ pub enum ProposalOutcome {
     CrossSystemHint {
         adna_weights_updated: usize,
     },
+
+    /// Conflicting update parked until Guardian review (see `resolve_deferred`)
+    Deferred {
+        connection_id: u64,
+    },
+
+    /// Token merge queued for review (see `resolve_merge`)
+    MergeQueued {
+        keep: u32,
+        absorb: u32,
+    },
 }
 
 /// Errors that can occur during hybrid learning
@@ -128,6 +157,234 @@ pub enum HybridLearningError {
 
     #[error("Lock error")]
     LockError,
+
+    #[error("Conflicting update on connection {0} suppressed by policy")]
+    ConflictSuppressed(u64),
+
+    #[error("No deferred conflict for connection {0}")]
+    NoDeferredConflict(u64),
+
+    #[error("No pending merge for token {0}")]
+    NoPendingMerge(u32),
+
+    #[error("Learning is paused or frozen by safe mode")]
+    LearningFrozen,
+}
+
+// ============================================================================
+// Conflict Resolution
+// ============================================================================
+
+/// Which learning system an update came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LearningSource {
+    /// ADNA (BehavioralToCausal feedback)
+    Behavioral,
+    /// Connection learning (Causal proposals)
+    Causal,
+}
+
+/// What to do when ADNA and Connection learning pull one edge in opposite directions
+///
+/// A conflict is an update to a connection's confidence whose direction is
+/// opposite to a recent update from the other source (within the conflict window).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConflictPolicy {
+    /// The given source always wins; the other side's conflicting update is rejected
+    Priority(LearningSource),
+
+    /// Blend both deltas: `w * behavioral + (1 - w) * causal`
+    WeightedBlend { behavioral_weight: f32 },
+
+    /// Park the conflicting update until Guardian review approves or drops it
+    DeferToGuardian,
+}
+
+impl Default for ConflictPolicy {
+    fn default() -> Self {
+        ConflictPolicy::WeightedBlend {
+            behavioral_weight: 0.5,
+        }
+    }
+}
+
+/// Last confidence change applied to a connection
+#[derive(Debug, Clone, Copy)]
+struct RecentUpdate {
+    source: LearningSource,
+    delta: f32,
+    at: Instant,
+}
+
+/// Conflicting update awaiting Guardian review
+#[derive(Debug, Clone)]
+pub struct DeferredConflict {
+    pub connection_id: u64,
+    pub proposal: HybridProposal,
+    pub source: LearningSource,
+    /// Confidence delta the parked update wants
+    pub requested_delta: f32,
+    /// Opposite delta applied just before by the other source
+    pub previous_delta: f32,
+}
+
+// ============================================================================
+// Outcome Tracking (post-hoc lift)
+// ============================================================================
+
+/// Post-hoc evaluation of applied proposals
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiftConfig {
+    /// Rewards compared before and after each applied proposal
+    pub window: usize,
+
+    /// Minimum rewards on each side before lift is judged
+    pub min_samples: usize,
+
+    /// Lift at or below this (negative) value is a candidate for revert
+    pub revert_threshold: f32,
+
+    /// z-score the drop must reach to count as significant
+    pub significance_z: f32,
+
+    /// Revert significantly harmful proposals automatically
+    pub auto_revert: bool,
+
+    /// Completed evaluations kept for inspection (oldest dropped first)
+    pub history_limit: usize,
+}
+
+impl Default for LiftConfig {
+    fn default() -> Self {
+        Self {
+            window: 20,
+            min_samples: 10,
+            revert_threshold: -0.1,
+            significance_z: 2.0,
+            auto_revert: true,
+            history_limit: 1000,
+        }
+    }
+}
+
+impl LiftConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.window == 0 {
+            return Err("window must be > 0".to_string());
+        }
+        if self.min_samples == 0 || self.min_samples > self.window {
+            return Err("min_samples must be in 1..=window".to_string());
+        }
+        if !self.revert_threshold.is_finite() || self.revert_threshold > 0.0 {
+            return Err("revert_threshold must be a finite value <= 0".to_string());
+        }
+        if !self.significance_z.is_finite() || self.significance_z < 0.0 {
+            return Err("significance_z must be a finite value >= 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Evaluation state of an applied proposal
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LiftStatus {
+    /// Still collecting post-apply rewards
+    Pending,
+    /// Window complete; lift not significantly negative
+    Kept { lift: f32 },
+    /// Significantly negative lift; connection restored to its prior state
+    Reverted { lift: f32 },
+    /// Significantly negative lift, but nothing could be restored
+    /// (no connection change, or the connection changed again since)
+    Harmful { lift: f32 },
+    /// Too few rewards before the proposal to judge it
+    Insufficient,
+}
+
+/// Reward lift of one applied proposal
+#[derive(Debug, Clone)]
+pub struct ProposalEvaluation {
+    pub id: u64,
+    pub proposal: HybridProposal,
+    pub outcome: ProposalOutcome,
+    pub reward_before: Vec<f32>,
+    pub reward_after: Vec<f32>,
+    pub status: LiftStatus,
+    /// Connection touched by the proposal: (id, before, after)
+    connection: Option<(u64, ConnectionV3, ConnectionV3)>,
+}
+
+impl ProposalEvaluation {
+    /// mean(after) - mean(before), once both sides have rewards
+    pub fn lift(&self) -> Option<f32> {
+        Some(mean(&self.reward_after)? - mean(&self.reward_before)?)
+    }
+
+    /// z-score of the lift (Welch's standard error)
+    pub fn z_score(&self) -> Option<f32> {
+        let lift = self.lift()?;
+        let se = (variance(&self.reward_before)? / self.reward_before.len() as f32
+            + variance(&self.reward_after)? / self.reward_after.len() as f32)
+            .sqrt();
+        if se > f32::EPSILON {
+            Some(lift / se)
+        } else if lift < 0.0 {
+            Some(f32::NEG_INFINITY)
+        } else {
+            Some(0.0)
+        }
+    }
+}
+
+fn mean(values: &[f32]) -> Option<f32> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum::<f32>() / values.len() as f32)
+    }
+}
+
+fn variance(values: &[f32]) -> Option<f32> {
+    let m = mean(values)?;
+    if values.len() < 2 {
+        return Some(0.0);
+    }
+    Some(values.iter().map(|v| (v - m) * (v - m)).sum::<f32>() / (values.len() - 1) as f32)
+}
+
+/// Fields a proposal can change; used to detect later edits before reverting
+fn learnable_fields(c: &ConnectionV3) -> (u8, u16, f32, f32, u8, u8) {
+    (
+        c.confidence,
+        c.evidence_count,
+        c.pull_strength,
+        c.preferred_distance,
+        c.learning_rate,
+        c.decay_rate,
+    )
+}
+
+/// Connection weight as mirrored into the graph (confidence, 0.0-1.0)
+fn connection_weight(c: &ConnectionV3) -> f32 {
+    c.confidence as f32 / 255.0
+}
+
+/// Connection a proposal modifies, if any
+fn target_connection(proposal: &HybridProposal) -> Option<u64> {
+    match proposal {
+        HybridProposal::Causal(ConnectionProposal::Modify { connection_id, .. }) => Some(*connection_id),
+        HybridProposal::BehavioralToCausal {
+            target_connection_id,
+            ..
+        } => Some(*target_connection_id),
+        _ => None,
+    }
+}
+
+/// Result of running an update through the conflict policy
+enum Resolution {
+    Apply(f32),
+    Defer,
 }
 
 // ============================================================================
@@ -144,6 +401,39 @@ pub struct ProposalRouter {
 
     /// Statistics tracking
     stats: Arc<RwLock<HybridLearningStats>>,
+
+    /// Conflict resolution policy
+    conflict_policy: ConflictPolicy,
+
+    /// Updates from the other source older than this are not conflicts
+    conflict_window: Duration,
+
+    /// Last confidence change per connection
+    recent_updates: RwLock<HashMap<u64, RecentUpdate>>,
+
+    /// Conflicting updates awaiting Guardian review
+    deferred: RwLock<Vec<DeferredConflict>>,
+
+    /// Token merges awaiting review
+    pending_merges: RwLock<Vec<TokenMerge>>,
+
+    /// Post-hoc lift evaluation settings
+    lift_config: LiftConfig,
+
+    /// Most recent rewards (at most `lift_config.window`)
+    recent_rewards: RwLock<std::collections::VecDeque<f32>>,
+
+    /// Applied proposals and their lift
+    evaluations: RwLock<Vec<ProposalEvaluation>>,
+
+    /// Next evaluation ID
+    next_evaluation_id: std::sync::atomic::AtomicU64,
+
+    /// Optional per-connection weight history (debugging)
+    weight_history: Option<Arc<EdgeHistory>>,
+
+    /// Proposals are rejected while this freezes learning
+    safe_mode: Arc<SafeMode>,
 }
 
 /// Statistics for hybrid learning system
@@ -166,6 +456,39 @@ pub struct HybridLearningStats {
 
     /// Guardian rejections
     pub guardian_rejections: u64,
+
+    /// Opposing updates detected on the same connection
+    pub conflicts_detected: u64,
+
+    /// Conflicts where the incoming update won (Priority)
+    pub conflicts_overridden: u64,
+
+    /// Conflicts where the incoming update was rejected (Priority)
+    pub conflicts_suppressed: u64,
+
+    /// Conflicts resolved by blending (WeightedBlend)
+    pub conflicts_blended: u64,
+
+    /// Conflicts parked for Guardian review (DeferToGuardian)
+    pub conflicts_deferred: u64,
+
+    /// Token merges queued for review
+    pub merges_queued: u64,
+
+    /// Token merges approved
+    pub merges_approved: u64,
+
+    /// Token merges rejected
+    pub merges_rejected: u64,
+
+    /// Applied proposals whose lift window completed
+    pub proposals_evaluated: u64,
+
+    /// Proposals reverted for significantly negative lift
+    pub proposals_reverted: u64,
+
+    /// Mean lift over evaluated proposals
+    pub mean_lift: f32,
 }
 
 impl ProposalRouter {
@@ -175,9 +498,55 @@ impl ProposalRouter {
             connections: Arc::new(RwLock::new(HashMap::new())),
             guardian,
             stats: Arc::new(RwLock::new(HybridLearningStats::default())),
+            conflict_policy: ConflictPolicy::default(),
+            conflict_window: Duration::from_secs(60),
+            recent_updates: RwLock::new(HashMap::new()),
+            deferred: RwLock::new(Vec::new()),
+            pending_merges: RwLock::new(Vec::new()),
+            lift_config: LiftConfig::default(),
+            recent_rewards: RwLock::new(std::collections::VecDeque::new()),
+            evaluations: RwLock::new(Vec::new()),
+            next_evaluation_id: std::sync::atomic::AtomicU64::new(0),
+            weight_history: None,
+            safe_mode: Arc::default(),
         }
     }
 
+    /// Reject proposals while this switch freezes learning
+    pub fn with_safe_mode(mut self, safe_mode: Arc<SafeMode>) -> Self {
+        self.safe_mode = safe_mode;
+        self
+    }
+
+    /// Record connection weight changes (applied proposals and reverts)
+    pub fn with_weight_history(mut self, history: Arc<EdgeHistory>) -> Self {
+        self.weight_history = Some(history);
+        self
+    }
+
+    /// Set post-hoc lift evaluation settings
+    pub fn with_lift_config(mut self, config: LiftConfig) -> Self {
+        self.lift_config = config;
+        self
+    }
+
+    /// Set conflict resolution policy
+    pub fn with_conflict_policy(mut self, policy: ConflictPolicy) -> Self {
+        self.conflict_policy = policy;
+        self
+    }
+
+    /// Set how long an update counts as "recent" for conflict detection
+    pub fn with_conflict_window(mut self, window: Duration) -> Self {
+        self.conflict_window = window;
+        self
+    }
+
+    /// Current conflict resolution policy
+    pub fn conflict_policy(&self) -> ConflictPolicy {
+        self.conflict_policy
+    }
+
     /// Add connection to router's storage
     pub fn add_connection(&self, id: u64, connection: ConnectionV3) {
         self.connections.write().insert(id, connection);
@@ -194,7 +563,54 @@ impl ProposalRouter {
         proposal: HybridProposal,
     ) -> Result<ProposalOutcome, HybridLearningError> {
         self.stats.write().total_proposals += 1;
+        if self.safe_mode.learning_frozen() {
+            return Err(HybridLearningError::LearningFrozen);
+        }
+        self.dispatch(proposal)
+    }
 
+    /// Apply a proposal and start tracking its reward lift
+    fn dispatch(
+        &self,
+        proposal: HybridProposal,
+    ) -> Result<ProposalOutcome, HybridLearningError> {
+        let target = target_connection(&proposal);
+        let before = target.and_then(|id| self.get_connection(id));
+
+        let outcome = self.apply(proposal.clone())?;
+        if matches!(outcome, ProposalOutcome::Deferred { .. } | ProposalOutcome::MergeQueued { .. }) {
+            return Ok(outcome);
+        }
+
+        let connection = match (target, before) {
+            (Some(id), Some(before)) => self.get_connection(id).map(|after| (id, before, after)),
+            _ => None,
+        };
+        if let (Some(history), Some((id, before, after))) = (&self.weight_history, &connection) {
+            if connection_weight(after) != connection_weight(before) {
+                history.record(*id, connection_weight(after), WeightSource::Proposal);
+            }
+        }
+        let evaluation = ProposalEvaluation {
+            id: self
+                .next_evaluation_id
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed),
+            proposal,
+            outcome: outcome.clone(),
+            reward_before: self.recent_rewards.read().iter().copied().collect(),
+            reward_after: Vec::new(),
+            status: LiftStatus::Pending,
+            connection,
+        };
+        self.evaluations.write().push(evaluation);
+
+        Ok(outcome)
+    }
+
+    fn apply(
+        &self,
+        proposal: HybridProposal,
+    ) -> Result<ProposalOutcome, HybridLearningError> {
         match proposal {
             HybridProposal::Behavioral(p) => {
                 self.apply_behavioral_proposal(p)
@@ -230,9 +646,33 @@ impl ProposalRouter {
                     causal_confidence,
                 )
             }
+            HybridProposal::Merge(merge) => self.queue_merge(merge),
         }
     }
 
+    /// Queue a token merge for review
+    fn queue_merge(&self, merge: TokenMerge) -> Result<ProposalOutcome, HybridLearningError> {
+        if merge.keep == merge.absorb {
+            return Err(HybridLearningError::InvalidProposal(
+                "Cannot merge a token into itself".to_string(),
+            ));
+        }
+        let mut pending = self.pending_merges.write();
+        if pending.iter().any(|m| [m.keep, m.absorb].contains(&merge.absorb)) {
+            return Err(HybridLearningError::InvalidProposal(format!(
+                "Token {} already has a pending merge",
+                merge.absorb
+            )));
+        }
+        pending.push(merge);
+        self.stats.write().merges_queued += 1;
+
+        Ok(ProposalOutcome::MergeQueued {
+            keep: merge.keep,
+            absorb: merge.absorb,
+        })
+    }
+
     /// Apply behavioral (ADNA) proposal
     fn apply_behavioral_proposal(
         &self,
@@ -277,9 +717,24 @@ impl ProposalRouter {
                 // Apply modification
                 match field {
                     ConnectionField::Confidence => {
-                        let new_conf = (*new_value * 255.0) as u8;
-                        conn.confidence = new_conf;
+                        let current = conn.confidence as f32 / 255.0;
+                        let target = match self.resolve_conflict(
+                            *connection_id,
+                            LearningSource::Causal,
+                            current,
+                            *new_value,
+                            &HybridProposal::Causal(proposal.clone()),
+                        )? {
+                            Resolution::Apply(target) => target,
+                            Resolution::Defer => {
+                                return Ok(ProposalOutcome::Deferred {
+                                    connection_id: *connection_id,
+                                });
+                            }
+                        };
+                        conn.confidence = (target * 255.0) as u8;
                         conn.evidence_count = conn.evidence_count.saturating_add(*evidence_count);
+                        self.record_update(*connection_id, LearningSource::Causal, target - current);
                     }
                     ConnectionField::PullStrength => {
                         conn.pull_strength = *new_value;
@@ -321,7 +776,7 @@ impl ProposalRouter {
     /// Apply ADNA → Connection feedback
     fn apply_behavioral_to_causal_feedback(
         &self,
-        _pattern: &IdentifiedPattern,
+        pattern: &IdentifiedPattern,
         connection_id: u64,
         confidence_boost: f32,
         evidence_count: u16,
@@ -340,8 +795,25 @@ impl ProposalRouter {
 
         // Calculate new confidence
         let current_conf = conn.confidence as f32 / 255.0;
-        let new_conf = (current_conf + confidence_boost).min(1.0);
+        let requested = (current_conf + confidence_boost).min(1.0);
+        let proposal = HybridProposal::BehavioralToCausal {
+            adna_pattern: pattern.clone(),
+            target_connection_id: connection_id,
+            confidence_boost,
+            evidence_count,
+        };
+        let new_conf = match self.resolve_conflict(
+            connection_id,
+            LearningSource::Behavioral,
+            current_conf,
+            requested,
+            &proposal,
+        )? {
+            Resolution::Apply(target) => target,
+            Resolution::Defer => return Ok(ProposalOutcome::Deferred { connection_id }),
+        };
         let total_boost = new_conf - current_conf;
+        self.record_update(connection_id, LearningSource::Behavioral, total_boost);
 
         // Update connection
         conn.confidence = (new_conf * 255.0) as u8;
@@ -377,6 +849,256 @@ impl ProposalRouter {
         })
     }
 
+    /// Run a confidence change through the conflict policy
+    ///
+    /// Returns the confidence to apply, or `Defer` if the update was parked.
+    fn resolve_conflict(
+        &self,
+        connection_id: u64,
+        source: LearningSource,
+        current: f32,
+        requested: f32,
+        proposal: &HybridProposal,
+    ) -> Result<Resolution, HybridLearningError> {
+        let requested_delta = requested - current;
+        let previous = self.recent_updates.read().get(&connection_id).copied();
+
+        let previous = match previous {
+            Some(prev)
+                if prev.source != source
+                    && prev.at.elapsed() <= self.conflict_window
+                    && prev.delta * requested_delta < 0.0 =>
+            {
+                prev
+            }
+            _ => return Ok(Resolution::Apply(requested)),
+        };
+
+        self.stats.write().conflicts_detected += 1;
+
+        match self.conflict_policy {
+            ConflictPolicy::Priority(winner) => {
+                if winner == source {
+                    self.stats.write().conflicts_overridden += 1;
+                    Ok(Resolution::Apply(requested))
+                } else {
+                    self.stats.write().conflicts_suppressed += 1;
+                    Err(HybridLearningError::ConflictSuppressed(connection_id))
+                }
+            }
+            ConflictPolicy::WeightedBlend { behavioral_weight } => {
+                let w = behavioral_weight.clamp(0.0, 1.0);
+                let (behavioral, causal) = match source {
+                    LearningSource::Behavioral => (requested_delta, previous.delta),
+                    LearningSource::Causal => (previous.delta, requested_delta),
+                };
+                // The previous delta is already applied: blend from the state before it
+                let base = current - previous.delta;
+                let blended = w * behavioral + (1.0 - w) * causal;
+
+                self.stats.write().conflicts_blended += 1;
+                Ok(Resolution::Apply((base + blended).clamp(0.0, 1.0)))
+            }
+            ConflictPolicy::DeferToGuardian => {
+                self.deferred.write().push(DeferredConflict {
+                    connection_id,
+                    proposal: proposal.clone(),
+                    source,
+                    requested_delta,
+                    previous_delta: previous.delta,
+                });
+                self.stats.write().conflicts_deferred += 1;
+                Ok(Resolution::Defer)
+            }
+        }
+    }
+
+    /// Remember a confidence change for conflict detection
+    fn record_update(&self, connection_id: u64, source: LearningSource, delta: f32) {
+        if delta == 0.0 {
+            return;
+        }
+        self.recent_updates.write().insert(
+            connection_id,
+            RecentUpdate {
+                source,
+                delta,
+                at: Instant::now(),
+            },
+        );
+    }
+
+    /// Conflicting updates awaiting Guardian review
+    pub fn deferred_conflicts(&self) -> Vec<DeferredConflict> {
+        self.deferred.read().clone()
+    }
+
+    /// Guardian review of the oldest deferred conflict on a connection
+    ///
+    /// If approved, the parked proposal is applied without conflict checks;
+    /// otherwise it is dropped and `Ok(None)` is returned.
+    pub fn resolve_deferred(
+        &self,
+        connection_id: u64,
+        approve: bool,
+    ) -> Result<Option<ProposalOutcome>, HybridLearningError> {
+        let conflict = {
+            let mut deferred = self.deferred.write();
+            let index = deferred
+                .iter()
+                .position(|c| c.connection_id == connection_id)
+                .ok_or(HybridLearningError::NoDeferredConflict(connection_id))?;
+            deferred.remove(index)
+        };
+
+        if !approve {
+            return Ok(None);
+        }
+
+        self.recent_updates.write().remove(&connection_id);
+        self.dispatch(conflict.proposal).map(Some)
+    }
+
+    /// Token merges awaiting review
+    pub fn pending_merges(&self) -> Vec<TokenMerge> {
+        self.pending_merges.read().clone()
+    }
+
+    /// Review the pending merge of token `absorb`
+    ///
+    /// Returns the merge to apply if approved, `Ok(None)` if rejected.
+    /// Approvals are refused while learning is frozen; the merge stays queued.
+    pub fn resolve_merge(
+        &self,
+        absorb: u32,
+        approve: bool,
+    ) -> Result<Option<TokenMerge>, HybridLearningError> {
+        if approve && self.safe_mode.learning_frozen() {
+            return Err(HybridLearningError::LearningFrozen);
+        }
+        let merge = {
+            let mut pending = self.pending_merges.write();
+            let index = pending
+                .iter()
+                .position(|m| m.absorb == absorb)
+                .ok_or(HybridLearningError::NoPendingMerge(absorb))?;
+            pending.remove(index)
+        };
+
+        let mut stats = self.stats.write();
+        if approve {
+            stats.merges_approved += 1;
+            Ok(Some(merge))
+        } else {
+            stats.merges_rejected += 1;
+            Ok(None)
+        }
+    }
+
+    /// Feed an observed reward into lift tracking
+    ///
+    /// Each pending evaluation collects rewards until its window is full, then
+    /// is judged. Proposals with significantly negative lift are reverted
+    /// (if `auto_revert`). Returns evaluations completed by this reward.
+    pub fn record_reward(&self, reward: f32) -> Vec<ProposalEvaluation> {
+        let config = &self.lift_config;
+        {
+            let mut recent = self.recent_rewards.write();
+            recent.push_back(reward);
+            while recent.len() > config.window {
+                recent.pop_front();
+            }
+        }
+
+        let mut completed = Vec::new();
+        let mut evaluations = self.evaluations.write();
+        for evaluation in evaluations.iter_mut() {
+            if evaluation.status != LiftStatus::Pending {
+                continue;
+            }
+            evaluation.reward_after.push(reward);
+            if evaluation.reward_after.len() < config.window {
+                continue;
+            }
+
+            evaluation.status = self.judge(evaluation);
+            completed.push(evaluation.clone());
+        }
+
+        let finished = evaluations.iter().filter(|e| e.status != LiftStatus::Pending).count();
+        if finished > config.history_limit {
+            let mut excess = finished - config.history_limit;
+            evaluations.retain(|e| {
+                if excess > 0 && e.status != LiftStatus::Pending {
+                    excess -= 1;
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+        drop(evaluations);
+
+        if !completed.is_empty() {
+            let mut stats = self.stats.write();
+            for evaluation in &completed {
+                let lift = match evaluation.status {
+                    LiftStatus::Kept { lift } | LiftStatus::Harmful { lift } => lift,
+                    LiftStatus::Reverted { lift } => {
+                        stats.proposals_reverted += 1;
+                        lift
+                    }
+                    _ => continue,
+                };
+                stats.proposals_evaluated += 1;
+                stats.mean_lift += (lift - stats.mean_lift) / stats.proposals_evaluated as f32;
+            }
+        }
+
+        completed
+    }
+
+    /// Decide a completed evaluation, reverting if warranted
+    fn judge(&self, evaluation: &ProposalEvaluation) -> LiftStatus {
+        let config = &self.lift_config;
+        if evaluation.reward_before.len() < config.min_samples {
+            return LiftStatus::Insufficient;
+        }
+        let (Some(lift), Some(z)) = (evaluation.lift(), evaluation.z_score()) else {
+            return LiftStatus::Insufficient;
+        };
+
+        let harmful = lift <= config.revert_threshold && z <= -config.significance_z;
+        if !harmful {
+            return LiftStatus::Kept { lift };
+        }
+        if !config.auto_revert {
+            return LiftStatus::Harmful { lift };
+        }
+
+        match evaluation.connection {
+            Some((id, before, after)) => {
+                let mut connections = self.connections.write();
+                match connections.get_mut(&id) {
+                    Some(current) if learnable_fields(current) == learnable_fields(&after) => {
+                        *current = before;
+                        if let Some(history) = &self.weight_history {
+                            history.record(id, connection_weight(&before), WeightSource::Revert);
+                        }
+                        LiftStatus::Reverted { lift }
+                    }
+                    _ => LiftStatus::Harmful { lift },
+                }
+            }
+            None => LiftStatus::Harmful { lift },
+        }
+    }
+
+    /// All applied proposals with their lift status
+    pub fn evaluations(&self) -> Vec<ProposalEvaluation> {
+        self.evaluations.read().clone()
+    }
+
     /// Get current statistics
     pub fn get_stats(&self) -> HybridLearningStats {
         self.stats.read().clone()
@@ -512,4 +1234,141 @@ mod tests {
         let hint = connection_to_adna_hint(&conn, 1);
         assert!(hint.is_none());
     }
+
+    fn test_pattern() -> IdentifiedPattern {
+        IdentifiedPattern {
+            state_bin_id: 100,
+            better_action: 5,
+            worse_action: 3,
+            reward_delta: 1.5,
+            confidence: 0.85,
+            sample_count: 10,
+        }
+    }
+
+    fn decay_proposal(connection_id: u64, new_value: f32) -> HybridProposal {
+        HybridProposal::Causal(ConnectionProposal::Modify {
+            connection_id,
+            field: ConnectionField::Confidence,
+            old_value: 0.5,
+            new_value,
+            justification: "decay".to_string(),
+            evidence_count: 1,
+        })
+    }
+
+    #[test]
+    fn test_conflict_priority_suppresses_loser() {
+        let router = setup_test_router().with_conflict_policy(ConflictPolicy::Priority(LearningSource::Causal));
+        router.add_connection(1, ConnectionV3::new(100, 200));
+
+        router.route_proposal(decay_proposal(1, 0.3)).unwrap();
+        let result = router.route_proposal(adna_to_connection_feedback(&test_pattern(), 1));
+        assert!(matches!(result, Err(HybridLearningError::ConflictSuppressed(1))));
+        assert_eq!(router.get_connection(1).unwrap().confidence, 76);
+
+        // Same direction is not a conflict
+        router.route_proposal(decay_proposal(1, 0.2)).unwrap();
+
+        let stats = router.get_stats();
+        assert_eq!(stats.conflicts_detected, 1);
+        assert_eq!(stats.conflicts_suppressed, 1);
+    }
+
+    #[test]
+    fn test_conflict_weighted_blend() {
+        let router = setup_test_router();
+        router.add_connection(1, ConnectionV3::new(100, 200));
+
+        router.route_proposal(decay_proposal(1, 0.3)).unwrap();
+        router.route_proposal(adna_to_connection_feedback(&test_pattern(), 1)).unwrap();
+
+        // 0.5 + (0.5 * 0.085 + 0.5 * -0.2) ≈ 0.44
+        let confidence = router.get_connection(1).unwrap().confidence as f32 / 255.0;
+        assert!((confidence - 0.44).abs() < 0.01, "blended confidence {}", confidence);
+        assert_eq!(router.get_stats().conflicts_blended, 1);
+    }
+
+    #[test]
+    fn test_conflict_deferred_to_guardian() {
+        let router = setup_test_router().with_conflict_policy(ConflictPolicy::DeferToGuardian);
+        router.add_connection(1, ConnectionV3::new(100, 200));
+
+        router.route_proposal(adna_to_connection_feedback(&test_pattern(), 1)).unwrap();
+        let boosted = router.get_connection(1).unwrap().confidence;
+
+        let outcome = router.route_proposal(decay_proposal(1, 0.3)).unwrap();
+        assert!(matches!(outcome, ProposalOutcome::Deferred { connection_id: 1 }));
+        assert_eq!(router.get_connection(1).unwrap().confidence, boosted);
+        assert_eq!(router.deferred_conflicts().len(), 1);
+
+        let applied = router.resolve_deferred(1, true).unwrap();
+        assert!(matches!(applied, Some(ProposalOutcome::CausalApplied { new_confidence: 76, .. })));
+        assert!(router.deferred_conflicts().is_empty());
+        assert!(router.resolve_deferred(1, false).is_err());
+        assert_eq!(router.get_stats().conflicts_deferred, 1);
+    }
+
+    fn lift_router() -> ProposalRouter {
+        setup_test_router().with_lift_config(LiftConfig {
+            window: 5,
+            min_samples: 5,
+            ..LiftConfig::default()
+        })
+    }
+
+    #[test]
+    fn test_lift_config_validation() {
+        assert!(LiftConfig::default().validate().is_ok());
+        let bad = LiftConfig { min_samples: 50, ..LiftConfig::default() };
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn test_negative_lift_reverts_proposal() {
+        let router = lift_router();
+        router.add_connection(1, ConnectionV3::new(100, 200));
+
+        for r in [1.0, 0.9, 1.1, 1.0, 0.95] {
+            router.record_reward(r);
+        }
+        router.route_proposal(decay_proposal(1, 0.3)).unwrap();
+        assert_eq!(router.get_connection(1).unwrap().confidence, 76);
+
+        let mut completed = Vec::new();
+        for r in [0.1, 0.0, 0.2, 0.1, 0.05] {
+            completed.extend(router.record_reward(r));
+        }
+
+        assert_eq!(completed.len(), 1);
+        assert!(matches!(completed[0].status, LiftStatus::Reverted { lift } if lift < -0.5));
+        assert_eq!(router.get_connection(1).unwrap().confidence, 128);
+
+        let stats = router.get_stats();
+        assert_eq!(stats.proposals_evaluated, 1);
+        assert_eq!(stats.proposals_reverted, 1);
+        assert!(stats.mean_lift < 0.0);
+    }
+
+    #[test]
+    fn test_positive_lift_is_kept() {
+        let router = lift_router();
+        router.add_connection(1, ConnectionV3::new(100, 200));
+
+        router.route_proposal(decay_proposal(1, 0.3)).unwrap();
+        for _ in 0..5 {
+            router.record_reward(0.5);
+        }
+        // No rewards before the first proposal: cannot be judged
+        assert_eq!(router.evaluations()[0].status, LiftStatus::Insufficient);
+
+        router.route_proposal(decay_proposal(1, 0.2)).unwrap();
+        for r in [0.6, 0.7, 0.6, 0.8, 0.7] {
+            router.record_reward(r);
+        }
+
+        let evaluations = router.evaluations();
+        assert!(matches!(evaluations[1].status, LiftStatus::Kept { lift } if lift > 0.0));
+        assert_eq!(router.get_connection(1).unwrap().confidence, 51);
+    }
 }
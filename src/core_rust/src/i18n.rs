@@ -0,0 +1,431 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! I18n v1.0 - message catalogs for UI labels and user-facing errors
+//!
+//! A simple key catalog per [`Locale`] (English and Russian):
+//!
+//! - `tr(key)` / `tr_args(key, args)` look up a message in the current
+//!   locale, falling back to English and then to the key itself
+//! - `{name}` placeholders are filled from `args`
+//! - [`Localize`] renders gateway and feedback errors in a locale;
+//!   their `Display` output stays English for logs
+//!
+//! The current locale is process-wide and selected through the `i18n`
+//! settings section ([`I18nConfig`], live-applied with `I18nConfig::apply`).
+//! UI front-ends fetch a whole catalog with `messages(locale)`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+static CURRENT_LOCALE: AtomicU8 = AtomicU8::new(Locale::En as u8);
+
+// ============================================================================
+// Locale
+// ============================================================================
+
+/// Supported locale
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[repr(u8)]
+pub enum Locale {
+    #[default]
+    En = 0,
+    Ru = 1,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::En, Locale::Ru];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::En => "en",
+            Locale::Ru => "ru",
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Locale::Ru,
+            _ => Locale::En,
+        }
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    /// Accepts language tags such as `ru`, `ru-RU` or `en_US.UTF-8`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language = s
+            .split(['-', '_', '.'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        Locale::ALL
+            .into_iter()
+            .find(|locale| locale.as_str() == language)
+            .ok_or_else(|| format!("Unsupported locale '{}' (expected en or ru)", s))
+    }
+}
+
+/// Locale used by `tr`
+pub fn locale() -> Locale {
+    Locale::from_u8(CURRENT_LOCALE.load(Ordering::Relaxed))
+}
+
+/// Switch the process-wide locale
+pub fn set_locale(locale: Locale) {
+    CURRENT_LOCALE.store(locale as u8, Ordering::Relaxed);
+}
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// `i18n` settings section
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct I18nConfig {
+    pub locale: Locale,
+}
+
+impl I18nConfig {
+    /// Applied without a restart
+    pub const LIVE_FIELDS: &'static [&'static str] = &["locale"];
+
+    pub fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Make this the process-wide locale
+    pub fn apply(&self) {
+        set_locale(self.locale);
+    }
+}
+
+// ============================================================================
+// Catalogs
+// ============================================================================
+
+const EN: &[(&str, &str)] = &[
+    // Navigation
+    ("ui.nav.dashboard", "Dashboard"),
+    ("ui.nav.chat", "Chat"),
+    ("ui.nav.graph", "Graph"),
+    ("ui.nav.modules", "Modules"),
+    ("ui.nav.terminal", "Terminal"),
+    ("ui.nav.logs", "Logs"),
+    ("ui.nav.settings", "Settings"),
+    // Common actions
+    ("ui.action.start", "Start"),
+    ("ui.action.stop", "Stop"),
+    ("ui.action.restart", "Restart"),
+    ("ui.action.save", "Save"),
+    ("ui.action.cancel", "Cancel"),
+    ("ui.action.send", "Send"),
+    // Module lifecycle states
+    ("ui.module.stopped", "Stopped"),
+    ("ui.module.starting", "Starting"),
+    ("ui.module.running", "Running"),
+    ("ui.module.stopping", "Stopping"),
+    ("ui.module.restarting", "Restarting"),
+    ("ui.module.failed", "Failed"),
+    // Settings screen
+    ("ui.settings.language", "Language"),
+    ("ui.settings.saved", "Settings saved"),
+    ("ui.settings.restart_required", "Restart required to apply: {fields}"),
+    // Tray menu
+    ("tray.open", "Open NeuroGraph"),
+    ("tray.pause_learning", "Pause learning"),
+    ("tray.resume_learning", "Resume learning"),
+    ("tray.checkpoint", "Create checkpoint"),
+    ("tray.quit", "Quit"),
+    // Gateway errors
+    ("gateway.error", "Gateway error"),
+    ("gateway.error.empty_input", "Input is empty"),
+    ("gateway.error.input_too_long", "Input too long: {len} characters"),
+    ("gateway.error.queue_full", "Processing queue is full"),
+    ("gateway.error.normalization_failed", "Normalization failed: {reason}"),
+    ("gateway.error.not_implemented", "Not implemented: {what}"),
+    ("gateway.error.invalid_command", "Invalid command: {command}"),
+    ("gateway.error.send_failed", "Failed to send signal to queue"),
+    ("gateway.error.safe_mode", "Safe mode: {reason}"),
+    ("gateway.error.read_only", "Read-only replica: {reason}"),
+    // Feedback errors
+    ("feedback.error", "Feedback error"),
+    ("feedback.error.signal_not_found", "Reference signal ID {id} not found"),
+    ("feedback.error.too_old", "Feedback too old: {age} > {max}"),
+    ("feedback.error.too_many_corrections", "Too many corrections for signal {id}: {count} >= {max}"),
+    ("feedback.error.invalid_strength", "Invalid feedback strength: {strength} (must be 0.0 to 1.0)"),
+    ("feedback.error.parse", "Failed to parse correction: {reason}"),
+    ("feedback.error.system", "System error: {reason}"),
+    ("feedback.error.learning_paused", "Learning is paused"),
+];
+
+const RU: &[(&str, &str)] = &[
+    // Навигация
+    ("ui.nav.dashboard", "Обзор"),
+    ("ui.nav.chat", "Чат"),
+    ("ui.nav.graph", "Граф"),
+    ("ui.nav.modules", "Модули"),
+    ("ui.nav.terminal", "Терминал"),
+    ("ui.nav.logs", "Журнал"),
+    ("ui.nav.settings", "Настройки"),
+    // Общие действия
+    ("ui.action.start", "Запустить"),
+    ("ui.action.stop", "Остановить"),
+    ("ui.action.restart", "Перезапустить"),
+    ("ui.action.save", "Сохранить"),
+    ("ui.action.cancel", "Отмена"),
+    ("ui.action.send", "Отправить"),
+    // Состояния модулей
+    ("ui.module.stopped", "Остановлен"),
+    ("ui.module.starting", "Запускается"),
+    ("ui.module.running", "Работает"),
+    ("ui.module.stopping", "Останавливается"),
+    ("ui.module.restarting", "Перезапускается"),
+    ("ui.module.failed", "Сбой"),
+    // Экран настроек
+    ("ui.settings.language", "Язык"),
+    ("ui.settings.saved", "Настройки сохранены"),
+    ("ui.settings.restart_required", "Для применения нужен перезапуск: {fields}"),
+    // Меню трея
+    ("tray.open", "Открыть NeuroGraph"),
+    ("tray.pause_learning", "Приостановить обучение"),
+    ("tray.resume_learning", "Возобновить обучение"),
+    ("tray.checkpoint", "Создать контрольную точку"),
+    ("tray.quit", "Выход"),
+    // Ошибки Gateway
+    ("gateway.error", "Ошибка шлюза"),
+    ("gateway.error.empty_input", "Пустой ввод"),
+    ("gateway.error.input_too_long", "Слишком длинный ввод: {len} символов"),
+    ("gateway.error.queue_full", "Очередь обработки переполнена"),
+    ("gateway.error.normalization_failed", "Ошибка нормализации: {reason}"),
+    ("gateway.error.not_implemented", "Не реализовано: {what}"),
+    ("gateway.error.invalid_command", "Неверная команда: {command}"),
+    ("gateway.error.send_failed", "Не удалось отправить сигнал в очередь"),
+    ("gateway.error.safe_mode", "Безопасный режим: {reason}"),
+    ("gateway.error.read_only", "Реплика только для чтения: {reason}"),
+    // Ошибки обратной связи
+    ("feedback.error", "Ошибка обратной связи"),
+    ("feedback.error.signal_not_found", "Исходный сигнал {id} не найден"),
+    ("feedback.error.too_old", "Обратная связь устарела: {age} > {max}"),
+    ("feedback.error.too_many_corrections", "Слишком много исправлений для сигнала {id}: {count} >= {max}"),
+    ("feedback.error.invalid_strength", "Недопустимая сила обратной связи: {strength} (допустимо от 0.0 до 1.0)"),
+    ("feedback.error.parse", "Не удалось разобрать исправление: {reason}"),
+    ("feedback.error.system", "Системная ошибка: {reason}"),
+    ("feedback.error.learning_paused", "Обучение приостановлено"),
+];
+
+lazy_static::lazy_static! {
+    static ref CATALOGS: HashMap<Locale, HashMap<&'static str, &'static str>> = {
+        let mut catalogs = HashMap::new();
+        catalogs.insert(Locale::En, EN.iter().copied().collect());
+        catalogs.insert(Locale::Ru, RU.iter().copied().collect());
+        catalogs
+    };
+}
+
+fn lookup(locale: Locale, key: &str) -> Option<&'static str> {
+    CATALOGS
+        .get(&locale)
+        .and_then(|catalog| catalog.get(key))
+        .or_else(|| CATALOGS[&Locale::En].get(key))
+        .copied()
+}
+
+/// Message in the current locale
+pub fn tr(key: &str) -> String {
+    tr_in(locale(), key)
+}
+
+/// Message in a given locale (falls back to English, then to the key)
+pub fn tr_in(locale: Locale, key: &str) -> String {
+    lookup(locale, key).unwrap_or(key).to_string()
+}
+
+/// Message in the current locale with `{name}` placeholders filled
+pub fn tr_args(key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    tr_args_in(locale(), key, args)
+}
+
+/// Message in a given locale with `{name}` placeholders filled
+pub fn tr_args_in(locale: Locale, key: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut message = tr_in(locale, key);
+    for (name, value) in args {
+        message = message.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    message
+}
+
+/// Whole catalog of a locale, English entries filling any gaps
+pub fn messages(locale: Locale) -> BTreeMap<&'static str, &'static str> {
+    let mut messages: BTreeMap<_, _> = EN.iter().copied().collect();
+    if let Some(catalog) = CATALOGS.get(&locale) {
+        messages.extend(catalog.iter().map(|(k, v)| (*k, *v)));
+    }
+    messages
+}
+
+// ============================================================================
+// Localized Errors
+// ============================================================================
+
+/// User-facing message in a locale
+pub trait Localize {
+    fn localize(&self, locale: Locale) -> String;
+
+    /// Message in the current locale
+    fn localized(&self) -> String {
+        self.localize(locale())
+    }
+}
+
+impl Localize for crate::gateway::GatewayError {
+    fn localize(&self, locale: Locale) -> String {
+        use crate::gateway::GatewayError;
+        match self {
+            GatewayError::EmptyInput => tr_in(locale, "gateway.error.empty_input"),
+            GatewayError::InputTooLong(len) => {
+                tr_args_in(locale, "gateway.error.input_too_long", &[("len", len)])
+            }
+            GatewayError::QueueFull => tr_in(locale, "gateway.error.queue_full"),
+            GatewayError::NormalizationFailed(reason) => {
+                tr_args_in(locale, "gateway.error.normalization_failed", &[("reason", reason)])
+            }
+            GatewayError::NotImplemented(what) => {
+                tr_args_in(locale, "gateway.error.not_implemented", &[("what", what)])
+            }
+            GatewayError::InvalidCommand(command) => {
+                tr_args_in(locale, "gateway.error.invalid_command", &[("command", command)])
+            }
+            GatewayError::SendFailed => tr_in(locale, "gateway.error.send_failed"),
+            GatewayError::SafeMode(reason) => {
+                tr_args_in(locale, "gateway.error.safe_mode", &[("reason", reason)])
+            }
+            GatewayError::ReadOnly(reason) => {
+                tr_args_in(locale, "gateway.error.read_only", &[("reason", reason)])
+            }
+        }
+    }
+}
+
+impl Localize for crate::feedback::FeedbackError {
+    fn localize(&self, locale: Locale) -> String {
+        use crate::feedback::FeedbackError;
+        match self {
+            FeedbackError::SignalNotFound(id) => {
+                tr_args_in(locale, "feedback.error.signal_not_found", &[("id", id)])
+            }
+            FeedbackError::FeedbackTooOld(age, max) => tr_args_in(
+                locale,
+                "feedback.error.too_old",
+                &[("age", &format!("{:?}", age)), ("max", &format!("{:?}", max))],
+            ),
+            FeedbackError::TooManyCorrections(id, count, max) => tr_args_in(
+                locale,
+                "feedback.error.too_many_corrections",
+                &[("id", id), ("count", count), ("max", max)],
+            ),
+            FeedbackError::InvalidStrength(strength) => {
+                tr_args_in(locale, "feedback.error.invalid_strength", &[("strength", strength)])
+            }
+            FeedbackError::ParseError(reason) => {
+                tr_args_in(locale, "feedback.error.parse", &[("reason", reason)])
+            }
+            FeedbackError::SystemError(reason) => {
+                tr_args_in(locale, "feedback.error.system", &[("reason", reason)])
+            }
+            FeedbackError::LearningPaused => tr_in(locale, "feedback.error.learning_paused"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::feedback::FeedbackError;
+    use crate::gateway::GatewayError;
+    use std::collections::BTreeSet;
+
+    fn placeholders(message: &str) -> BTreeSet<&str> {
+        message
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    fn test_catalogs_complete() {
+        let en: BTreeMap<_, _> = EN.iter().copied().collect();
+        let ru: BTreeMap<_, _> = RU.iter().copied().collect();
+        assert_eq!(en.len(), EN.len(), "duplicate English key");
+        assert_eq!(ru.len(), RU.len(), "duplicate Russian key");
+        assert_eq!(en.keys().collect::<Vec<_>>(), ru.keys().collect::<Vec<_>>());
+        for (key, message) in &en {
+            assert_eq!(placeholders(message), placeholders(ru[key]), "placeholders differ for {}", key);
+        }
+    }
+
+    #[test]
+    fn test_lookup_and_args() {
+        assert_eq!(tr_in(Locale::Ru, "tray.quit"), "Выход");
+        assert_eq!(tr_in(Locale::En, "tray.quit"), "Quit");
+        assert_eq!(tr_in(Locale::Ru, "missing.key"), "missing.key");
+        assert_eq!(
+            tr_args_in(Locale::En, "ui.settings.restart_required", &[("fields", &"capacity")]),
+            "Restart required to apply: capacity"
+        );
+        assert_eq!(messages(Locale::Ru)["ui.nav.modules"], "Модули");
+
+        assert_eq!("ru-RU".parse::<Locale>().unwrap(), Locale::Ru);
+        assert_eq!("en_US.UTF-8".parse::<Locale>().unwrap(), Locale::En);
+        assert!("de".parse::<Locale>().is_err());
+    }
+
+    #[test]
+    fn test_localized_errors() {
+        // English catalog matches the Display strings used in logs
+        let errors = [
+            GatewayError::EmptyInput,
+            GatewayError::InputTooLong(10),
+            GatewayError::InvalidCommand("/x".to_string()),
+        ];
+        for error in &errors {
+            assert_eq!(error.localize(Locale::En), error.to_string());
+        }
+        let error = FeedbackError::TooManyCorrections(7, 3, 3);
+        assert_eq!(error.localize(Locale::En), error.to_string());
+        assert_eq!(
+            error.localize(Locale::Ru),
+            "Слишком много исправлений для сигнала 7: 3 >= 3"
+        );
+        assert_eq!(
+            GatewayError::InputTooLong(10).localize(Locale::Ru),
+            "Слишком длинный ввод: 10 символов"
+        );
+    }
+}
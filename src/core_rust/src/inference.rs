@@ -0,0 +1,521 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Inference v1.0 - Forward chaining over Immutable connection types
+//!
+//! Composition rules `first ∘ second → derived` are applied to chains of
+//! typed Graph edges: `a -first-> b -second-> c` yields `a -derived-> c`.
+//! The default rules cover transitivity of the semantic, logical and
+//! structural hierarchies (Hypernym, Implies, SubclassOf, PartOf, ...) and a
+//! few mixed chains (`MemberOf ∘ SubclassOf → MemberOf`,
+//! `Implies ∘ Contradicts → Contradicts`).
+//!
+//! Rules may only mention Immutable-tier types, so premises are always
+//! curated knowledge; conclusions are only likely and are added as
+//! Hypothesis connections:
+//!
+//! - a Graph edge `from -derived-> to` with weight
+//!   `w₁ · w₂ · weight_decay`
+//! - optionally a RuntimeStorage ConnectionV3 with mutability Hypothesis
+//!   (ConnectionV3 stores endpoints in canonical order; the direction is
+//!   kept by the Graph edge)
+//!
+//! Every conclusion records its provenance (rule, premise edges, depth).
+//! Base edges have depth 0 and a conclusion has depth `max(premises) + 1`;
+//! chaining stops at `max_depth`, below `min_weight`, or after
+//! `max_derivations` conclusions per run. [`InferenceEngine::run`] adds
+//! nothing while safe mode freezes learning.
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use crate::connection_v3::{ConnectionMutability, ConnectionType, ConnectionV3};
+use crate::graph::{EdgeId, Graph, NodeId};
+use crate::runtime_storage::RuntimeStorage;
+use crate::safe_mode::SafeMode;
+
+/// Inference limits
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InferenceConfig {
+    /// Maximum derivation depth (1 = only chains of two base edges)
+    pub max_depth: usize,
+
+    /// Maximum conclusions added per run
+    pub max_derivations: usize,
+
+    /// Conclusions weaker than this are dropped [0.0, 1.0]
+    pub min_weight: f32,
+
+    /// Factor applied to every derivation step (0.0, 1.0]
+    pub weight_decay: f32,
+}
+
+impl Default for InferenceConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: 3,
+            max_derivations: 10_000,
+            min_weight: 0.1,
+            weight_decay: 0.9,
+        }
+    }
+}
+
+impl InferenceConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_depth == 0 {
+            return Err("max_depth must be > 0".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.min_weight) {
+            return Err(format!("min_weight must be 0.0-1.0, got {}", self.min_weight));
+        }
+        if !(self.weight_decay > 0.0 && self.weight_decay <= 1.0) {
+            return Err(format!("weight_decay must be in (0.0, 1.0], got {}", self.weight_decay));
+        }
+        Ok(())
+    }
+}
+
+/// Composition rule: `a -first-> b -second-> c` ⟹ `a -derived-> c`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InferenceRule {
+    pub first: ConnectionType,
+    pub second: ConnectionType,
+    pub derived: ConnectionType,
+}
+
+impl InferenceRule {
+    pub fn new(first: ConnectionType, second: ConnectionType, derived: ConnectionType) -> Self {
+        Self { first, second, derived }
+    }
+
+    /// `t ∘ t → t`
+    pub fn transitive(conn_type: ConnectionType) -> Self {
+        Self::new(conn_type, conn_type, conn_type)
+    }
+
+    /// Rules may only chain curated (Immutable-tier) types
+    pub fn validate(&self) -> Result<(), String> {
+        for conn_type in [self.first, self.second, self.derived] {
+            if conn_type.mutability() != ConnectionMutability::Immutable {
+                return Err(format!("{:?} is not an Immutable connection type", conn_type));
+            }
+        }
+        Ok(())
+    }
+
+    /// Built-in rules
+    pub fn defaults() -> Vec<Self> {
+        use ConnectionType::*;
+
+        let mut rules: Vec<Self> = [
+            // Semantic
+            Synonym, Hypernym, Hyponym, Meronym, Holonym, Entailment,
+            // Logical
+            Implies, Equivalent, Necessary, Sufficient,
+            // Structural
+            PartOf, HasPart, SubclassOf, SuperclassOf, Contains, ContainedBy,
+        ]
+        .into_iter()
+        .map(Self::transitive)
+        .collect();
+
+        rules.extend([
+            Self::new(Synonym, Hypernym, Hypernym),
+            Self::new(Hypernym, Synonym, Hypernym),
+            Self::new(Equivalent, Implies, Implies),
+            Self::new(Implies, Equivalent, Implies),
+            Self::new(Implies, Contradicts, Contradicts),
+            Self::new(MemberOf, SubclassOf, MemberOf),
+            Self::new(ElementOf, SubclassOf, ElementOf),
+        ]);
+        rules
+    }
+}
+
+/// Provenance of one inferred edge
+#[derive(Debug, Clone, PartialEq)]
+pub struct Derivation {
+    pub edge_id: EdgeId,
+    pub from: NodeId,
+    pub to: NodeId,
+    pub edge_type: ConnectionType,
+    pub weight: f32,
+    /// 1 + the deepest premise (base edges have depth 0)
+    pub depth: usize,
+    pub rule: InferenceRule,
+    /// `[first, second]` premise edges
+    pub premises: [EdgeId; 2],
+    /// RuntimeStorage connection, when a storage is attached
+    pub connection_id: Option<u64>,
+}
+
+/// A usable premise: a Graph edge of a type some rule mentions
+#[derive(Debug, Clone, Copy)]
+struct Fact {
+    edge_id: EdgeId,
+    from: NodeId,
+    to: NodeId,
+    edge_type: u8,
+    weight: f32,
+    depth: usize,
+}
+
+/// Forward-chaining engine over Graph edges
+pub struct InferenceEngine {
+    config: InferenceConfig,
+    /// `(first, second) → derived`
+    rules: HashMap<(u8, u8), InferenceRule>,
+    storage: Option<Arc<RuntimeStorage>>,
+    safe_mode: Arc<SafeMode>,
+    provenance: RwLock<HashMap<EdgeId, Derivation>>,
+}
+
+impl InferenceEngine {
+    pub fn new(config: InferenceConfig) -> Self {
+        let mut engine = Self {
+            config,
+            rules: HashMap::new(),
+            storage: None,
+            safe_mode: Arc::default(),
+            provenance: RwLock::new(HashMap::new()),
+        };
+        for rule in InferenceRule::defaults() {
+            engine.rules.insert((rule.first as u8, rule.second as u8), rule);
+        }
+        engine
+    }
+
+    /// Also store conclusions as Hypothesis connections
+    pub fn with_storage(mut self, storage: Arc<RuntimeStorage>) -> Self {
+        self.storage = Some(storage);
+        self
+    }
+
+    /// Derive without adding anything while this switch freezes learning
+    pub fn with_safe_mode(mut self, safe_mode: Arc<SafeMode>) -> Self {
+        self.safe_mode = safe_mode;
+        self
+    }
+
+    /// Add or replace the rule for `(first, second)`
+    pub fn add_rule(&mut self, rule: InferenceRule) -> Result<(), String> {
+        rule.validate()?;
+        self.rules.insert((rule.first as u8, rule.second as u8), rule);
+        Ok(())
+    }
+
+    pub fn remove_rule(&mut self, first: ConnectionType, second: ConnectionType) -> bool {
+        self.rules.remove(&(first as u8, second as u8)).is_some()
+    }
+
+    pub fn rules(&self) -> Vec<InferenceRule> {
+        self.rules.values().copied().collect()
+    }
+
+    pub fn config(&self) -> &InferenceConfig {
+        &self.config
+    }
+
+    /// Provenance of an inferred edge
+    pub fn provenance(&self, edge_id: EdgeId) -> Option<Derivation> {
+        self.provenance.read().get(&edge_id).cloned()
+    }
+
+    /// Base (non-inferred) edges an inferred edge ultimately rests on
+    pub fn base_premises(&self, edge_id: EdgeId) -> Vec<EdgeId> {
+        let provenance = self.provenance.read();
+        let mut base = Vec::new();
+        let mut stack = vec![edge_id];
+        let mut seen = HashSet::new();
+        while let Some(id) = stack.pop() {
+            if !seen.insert(id) {
+                continue;
+            }
+            match provenance.get(&id) {
+                Some(derivation) => stack.extend(derivation.premises),
+                None => base.push(id),
+            }
+        }
+        base
+    }
+
+    /// Number of inferred edges recorded
+    pub fn derived_count(&self) -> usize {
+        self.provenance.read().len()
+    }
+
+    /// Derive conclusions from `graph` without changing it
+    ///
+    /// Runs rounds of semi-naive chaining (every new conclusion uses at
+    /// least one fact from the previous round) until nothing new is found
+    /// or a limit is reached.
+    pub fn infer(&self, graph: &Graph) -> Vec<Derivation> {
+        let typed: HashSet<u8> = self.rules.keys().flat_map(|&(first, second)| [first, second]).collect();
+        let provenance = self.provenance.read();
+
+        let mut facts: Vec<Fact> = graph
+            .edges()
+            .filter(|(_, edge)| typed.contains(&edge.edge_type))
+            .map(|(edge_id, edge)| Fact {
+                edge_id,
+                from: edge.from_id,
+                to: edge.to_id,
+                edge_type: edge.edge_type,
+                weight: edge.weight,
+                depth: provenance.get(&edge_id).map_or(0, |d| d.depth),
+            })
+            .collect();
+        drop(provenance);
+
+        let mut known: HashSet<(NodeId, NodeId, u8)> =
+            facts.iter().map(|fact| (fact.from, fact.to, fact.edge_type)).collect();
+        let mut outgoing: HashMap<NodeId, Vec<usize>> = HashMap::new();
+        for (i, fact) in facts.iter().enumerate() {
+            outgoing.entry(fact.from).or_default().push(i);
+        }
+
+        let mut derived = Vec::new();
+        let mut frontier: Vec<usize> = (0..facts.len()).collect();
+
+        while !frontier.is_empty() {
+            let fresh: HashSet<usize> = frontier.iter().copied().collect();
+            let mut next = Vec::new();
+
+            for first_idx in 0..facts.len() {
+                let first = facts[first_idx];
+                let Some(chain) = outgoing.get(&first.to).cloned() else {
+                    continue;
+                };
+                for second_idx in chain {
+                    if !fresh.contains(&first_idx) && !fresh.contains(&second_idx) {
+                        continue;
+                    }
+                    let second = facts[second_idx];
+                    let Some(rule) = self.rules.get(&(first.edge_type, second.edge_type)) else {
+                        continue;
+                    };
+                    let depth = first.depth.max(second.depth) + 1;
+                    let weight = first.weight * second.weight * self.config.weight_decay;
+                    let key = (first.from, second.to, rule.derived as u8);
+                    if first.from == second.to
+                        || depth > self.config.max_depth
+                        || weight < self.config.min_weight
+                        || known.contains(&key)
+                    {
+                        continue;
+                    }
+
+                    let edge_id = Graph::compute_edge_id(first.from, second.to, rule.derived as u8);
+                    known.insert(key);
+                    derived.push(Derivation {
+                        edge_id,
+                        from: first.from,
+                        to: second.to,
+                        edge_type: rule.derived,
+                        weight,
+                        depth,
+                        rule: *rule,
+                        premises: [first.edge_id, second.edge_id],
+                        connection_id: None,
+                    });
+
+                    let fact = Fact { edge_id, from: key.0, to: key.1, edge_type: key.2, weight, depth };
+                    outgoing.entry(fact.from).or_default().push(facts.len());
+                    next.push(facts.len());
+                    facts.push(fact);
+
+                    if derived.len() >= self.config.max_derivations {
+                        return derived;
+                    }
+                }
+            }
+            frontier = next;
+        }
+
+        derived
+    }
+
+    /// Derive conclusions and add them to `graph` (and the storage)
+    ///
+    /// Returns the conclusions that were added (none while learning is frozen).
+    pub fn run(&self, graph: &mut Graph) -> Vec<Derivation> {
+        let mut added = Vec::new();
+        if self.safe_mode.learning_frozen() {
+            return added;
+        }
+        for mut derivation in self.infer(graph) {
+            let inserted = graph.add_edge(
+                derivation.edge_id,
+                derivation.from,
+                derivation.to,
+                derivation.edge_type as u8,
+                derivation.weight,
+                false,
+            );
+            if !matches!(inserted, Ok(true)) {
+                continue;
+            }
+            // Derived edges are the first to go when the graph hits its limits
+            graph.set_edge_mutability(derivation.edge_id, ConnectionMutability::Hypothesis);
+
+            if let Some(storage) = &self.storage {
+                let mut connection = ConnectionV3::new(derivation.from, derivation.to);
+                connection.set_connection_type(derivation.edge_type);
+                connection.mutability = ConnectionMutability::Hypothesis as u8;
+                connection.confidence = (derivation.weight.clamp(0.0, 1.0) * 255.0).round() as u8;
+                derivation.connection_id = Some(storage.create_connection(connection));
+            }
+
+            self.provenance.write().insert(derivation.edge_id, derivation.clone());
+            added.push(derivation);
+        }
+
+        if !added.is_empty() {
+            tracing::debug!(derived = added.len(), "Inference added hypothesis edges");
+        }
+        added
+    }
+}
+
+impl Default for InferenceEngine {
+    fn default() -> Self {
+        Self::new(InferenceConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(NodeId, NodeId, ConnectionType)]) -> Graph {
+        let mut graph = Graph::new();
+        for &(from, to, conn_type) in edges {
+            graph.add_node(from);
+            graph.add_node(to);
+            let edge_id = Graph::compute_edge_id(from, to, conn_type as u8);
+            graph.add_edge(edge_id, from, to, conn_type as u8, 1.0, false).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn test_transitive_hypernym_with_provenance() {
+        use ConnectionType::*;
+        // dog → mammal → animal → organism
+        let mut graph = graph(&[(1, 2, Hypernym), (2, 3, Hypernym), (3, 4, Hypernym), (1, 5, Likes)]);
+        let storage = Arc::new(RuntimeStorage::new());
+        let engine = InferenceEngine::default().with_storage(storage.clone());
+
+        let added = engine.run(&mut graph);
+        let pairs: HashSet<(NodeId, NodeId)> = added.iter().map(|d| (d.from, d.to)).collect();
+        assert_eq!(pairs, HashSet::from([(1, 3), (2, 4), (1, 4)]));
+
+        let dog_organism = added.iter().find(|d| (d.from, d.to) == (1, 4)).unwrap();
+        assert_eq!(dog_organism.depth, 2);
+        assert!((dog_organism.weight - 0.81).abs() < 1e-5);
+        let mut base = engine.base_premises(dog_organism.edge_id);
+        base.sort();
+        let mut expected: Vec<EdgeId> = [(1, 2), (2, 3), (3, 4)]
+            .iter()
+            .map(|&(from, to)| Graph::compute_edge_id(from, to, Hypernym as u8))
+            .collect();
+        expected.sort();
+        assert_eq!(base, expected);
+
+        let connection = storage.get_connection(dog_organism.connection_id.unwrap()).unwrap();
+        assert_eq!(connection.mutability, ConnectionMutability::Hypothesis as u8);
+        assert_eq!(connection.connection_type, Hypernym as u8);
+        assert!(graph.contains_edge(dog_organism.edge_id));
+
+        // Fixed point: a second run finds nothing new
+        assert!(engine.run(&mut graph).is_empty());
+        assert_eq!(engine.derived_count(), 3);
+    }
+
+    #[test]
+    fn test_limits_and_mixed_rules() {
+        use ConnectionType::*;
+        let chain = graph(&[(1, 2, Implies), (2, 3, Implies), (3, 4, Implies), (4, 5, Contradicts)]);
+
+        let shallow = InferenceEngine::new(InferenceConfig { max_depth: 1, ..Default::default() });
+        let derived = shallow.infer(&chain);
+        assert!(derived.iter().all(|d| d.depth == 1));
+        assert!(derived.iter().any(|d| (d.from, d.to, d.edge_type) == (3, 5, Contradicts)));
+        assert!(!derived.iter().any(|d| (d.from, d.to) == (1, 4)));
+
+        let deep = InferenceEngine::default();
+        assert!(deep.infer(&chain).iter().any(|d| (d.from, d.to, d.edge_type) == (1, 5, Contradicts)));
+
+        let capped = InferenceEngine::new(InferenceConfig { max_derivations: 2, ..Default::default() });
+        assert_eq!(capped.infer(&chain).len(), 2);
+
+        // Learnable types cannot be chained
+        let mut engine = InferenceEngine::default();
+        assert!(engine.add_rule(InferenceRule::transitive(Cause)).is_err());
+        assert!(engine.remove_rule(Implies, Implies));
+        assert!(!engine.infer(&chain).iter().any(|d| d.edge_type == Implies));
+    }
+
+    #[test]
+    fn test_inferred_edges_are_evictable() {
+        use crate::graph::GraphConfig;
+        use ConnectionType::*;
+        let mut graph = Graph::with_config(GraphConfig { max_edges_per_node: Some(2), ..Default::default() });
+        for id in 1..=4 {
+            graph.add_node(id);
+        }
+        for (from, to) in [(1, 2), (2, 3)] {
+            let edge_id = Graph::compute_edge_id(from, to, Hypernym as u8);
+            graph.add_edge(edge_id, from, to, Hypernym as u8, 1.0, false).unwrap();
+        }
+
+        let added = InferenceEngine::default().run(&mut graph);
+        assert_eq!(added.len(), 1);
+        let inferred = added[0].edge_id;
+        assert_eq!(graph.edge_mutability(inferred), Some(ConnectionMutability::Hypothesis));
+
+        // Node 3 is full: the inferred edge makes room, the stated one stays
+        let cause = Graph::compute_edge_id(3, 4, Cause as u8);
+        assert_eq!(graph.add_edge(cause, 3, 4, Cause as u8, 0.5, false), Ok(true));
+        assert!(!graph.contains_edge(inferred));
+        assert!(graph.contains_edge(Graph::compute_edge_id(2, 3, Hypernym as u8)));
+        assert_eq!(graph.limit_stats().edges_evicted, 1);
+    }
+
+    #[test]
+    fn test_safe_mode_adds_nothing() {
+        use ConnectionType::*;
+        let mut graph = graph(&[(1, 2, Hypernym), (2, 3, Hypernym)]);
+        let storage = Arc::new(RuntimeStorage::new());
+        let safe_mode = Arc::new(SafeMode::new());
+        let engine = InferenceEngine::default()
+            .with_storage(storage.clone())
+            .with_safe_mode(safe_mode.clone());
+
+        safe_mode.engage(Some("test"));
+        assert_eq!(engine.infer(&graph).len(), 1);
+        assert!(engine.run(&mut graph).is_empty());
+        assert!(!graph.contains_edge(Graph::compute_edge_id(1, 3, Hypernym as u8)));
+        assert_eq!(storage.count_connections(), 0);
+
+        safe_mode.release();
+        assert_eq!(engine.run(&mut graph).len(), 1);
+    }
+}
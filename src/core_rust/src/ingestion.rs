@@ -0,0 +1,744 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Ingestion v1.0 - Grow the graph from plain-text documents
+//!
+//! ```text
+//! document → sentences → Normalizer → concepts → co-occurrence pairs
+//!                                                    │
+//!                          Hypothesis ConnectionV3 ◄─┴─► bootstrap Graph edge
+//! ```
+//!
+//! Concepts that appear close together in a sentence get a Hypothesis
+//! connection (fast learning, decays unless reinforced). Seeing the same pair
+//! again reinforces it instead of creating a duplicate. The matching edge in
+//! the bootstrap graph carries the connection's confidence as its weight, so
+//! spreading activation picks up what was read.
+//!
+//! Progress is broadcast as [`IngestionEvent`]s; subscribe before ingesting.
+//!
+//! Documents are plain text, Markdown or CSV ([`DocumentFormat`], picked by
+//! file extension). Markdown is reduced to its prose (code blocks, link
+//! targets and markup dropped, each heading and list item a sentence); each
+//! CSV row becomes one sentence of its cells.
+
+use crate::bootstrap::BootstrapLibrary;
+use crate::connection_v3::{ConnectionMutability, ConnectionType, ConnectionV3};
+use crate::gateway::config::{GatewayConfig, UnknownWordStrategy};
+use crate::gateway::normalizer::Normalizer;
+use crate::edge_history::WeightSource;
+use crate::graph::Graph;
+use crate::runtime_storage::RuntimeStorage;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::broadcast;
+
+/// Ingestion configuration
+#[derive(Debug, Clone)]
+pub struct IngestionConfig {
+    /// Connect concepts at most this many matched concepts apart (0 = whole sentence)
+    pub window: usize,
+
+    /// Sentences with fewer words are skipped
+    pub min_sentence_words: usize,
+
+    /// Sentences with more words are truncated
+    pub max_sentence_words: usize,
+
+    /// Confidence of a newly created connection (0.0-1.0)
+    pub initial_confidence: f32,
+
+    /// Confidence gained per repeated co-occurrence (0.0-1.0)
+    pub learning_rate: f32,
+
+    /// Hypothesis decay rate (0.0-1.0)
+    pub decay_rate: f32,
+
+    /// Connection type used for co-occurrence
+    pub connection_type: ConnectionType,
+
+    /// Emit a progress event every N sentences
+    pub progress_every: usize,
+}
+
+impl Default for IngestionConfig {
+    fn default() -> Self {
+        Self {
+            window: 4,
+            min_sentence_words: 2,
+            max_sentence_words: 200,
+            initial_confidence: 0.2,
+            learning_rate: 0.1,
+            decay_rate: 0.125,
+            connection_type: ConnectionType::Correlates,
+            progress_every: 10,
+        }
+    }
+}
+
+impl IngestionConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_sentence_words == 0 || self.min_sentence_words > self.max_sentence_words {
+            return Err("sentence word limits must satisfy 0 < min <= max".to_string());
+        }
+        for (name, value) in [
+            ("initial_confidence", self.initial_confidence),
+            ("learning_rate", self.learning_rate),
+            ("decay_rate", self.decay_rate),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(format!("{} must be in [0, 1]", name));
+            }
+        }
+        if self.progress_every == 0 {
+            return Err("progress_every must be > 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Ingestion errors
+#[derive(Debug)]
+pub enum IngestionError {
+    InvalidConfig(String),
+    /// Document name has no supported extension
+    UnsupportedFormat(String),
+    Io(std::io::Error),
+}
+
+impl fmt::Display for IngestionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IngestionError::InvalidConfig(msg) => write!(f, "Invalid ingestion config: {}", msg),
+            IngestionError::UnsupportedFormat(name) => write!(
+                f,
+                "Unsupported document format: {} (expected .txt, .md or .csv)",
+                name
+            ),
+            IngestionError::Io(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for IngestionError {}
+
+impl From<std::io::Error> for IngestionError {
+    fn from(e: std::io::Error) -> Self {
+        IngestionError::Io(e)
+    }
+}
+
+/// Summary of one ingested document
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IngestionReport {
+    pub sentences: usize,
+    /// Too short, or no known concepts
+    pub skipped_sentences: usize,
+    pub words: usize,
+    /// Distinct concepts matched (summed per document in combined reports)
+    pub concepts: usize,
+    pub matched_words: usize,
+    pub unknown_words: usize,
+    pub new_connections: usize,
+    pub reinforced_connections: usize,
+    pub graph_edges_added: usize,
+    pub duration_ms: u64,
+}
+
+impl IngestionReport {
+    fn merge(&mut self, other: &IngestionReport) {
+        self.sentences += other.sentences;
+        self.skipped_sentences += other.skipped_sentences;
+        self.words += other.words;
+        self.concepts += other.concepts;
+        self.matched_words += other.matched_words;
+        self.unknown_words += other.unknown_words;
+        self.new_connections += other.new_connections;
+        self.reinforced_connections += other.reinforced_connections;
+        self.graph_edges_added += other.graph_edges_added;
+        self.duration_ms += other.duration_ms;
+    }
+}
+
+/// Progress events
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum IngestionEvent {
+    Started {
+        document: String,
+        sentences: usize,
+    },
+    Progress {
+        document: String,
+        processed: usize,
+        total: usize,
+        new_connections: usize,
+        reinforced_connections: usize,
+    },
+    Finished {
+        document: String,
+        report: IngestionReport,
+    },
+}
+
+/// Document format, detected from the file extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DocumentFormat {
+    Text,
+    Markdown,
+    Csv,
+}
+
+impl DocumentFormat {
+    /// Format of a file name or path, `None` for unsupported extensions
+    pub fn from_name(name: &str) -> Option<Self> {
+        let extension = Path::new(name).extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "txt" | "text" => Some(DocumentFormat::Text),
+            "md" | "markdown" => Some(DocumentFormat::Markdown),
+            "csv" => Some(DocumentFormat::Csv),
+            _ => None,
+        }
+    }
+
+    /// Prose to ingest, paragraphs separated by blank lines
+    pub fn extract(&self, raw: &str) -> String {
+        match self {
+            DocumentFormat::Text => raw.to_string(),
+            DocumentFormat::Markdown => markdown_text(raw),
+            DocumentFormat::Csv => csv_text(raw),
+        }
+    }
+}
+
+/// Markdown reduced to prose
+fn markdown_text(raw: &str) -> String {
+    let mut out = String::new();
+    let mut in_code = false;
+    for line in raw.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code || trimmed.starts_with('|') || trimmed.chars().all(|c| matches!(c, '-' | '=' | '*' | '_')) {
+            // Blank lines, code, tables and rules end the paragraph
+            if !out.ends_with("\n\n") && !out.is_empty() {
+                out.push_str("\n\n");
+            }
+            continue;
+        }
+
+        let stripped = trimmed.trim_start_matches('>').trim_start();
+        let (content, standalone) = strip_block_marker(stripped);
+        let content = strip_inline_markup(content);
+        if standalone {
+            if !out.is_empty() && !out.ends_with("\n\n") {
+                out.push_str("\n\n");
+            }
+            out.push_str(&content);
+            out.push_str("\n\n");
+        } else {
+            out.push_str(&content);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Heading and list markers; returns the content and whether the line is
+/// its own sentence
+fn strip_block_marker(line: &str) -> (&str, bool) {
+    if line.starts_with('#') {
+        return (line.trim_start_matches('#').trim_start(), true);
+    }
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(rest) = line.strip_prefix(bullet) {
+            return (rest, true);
+        }
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 {
+        if let Some(rest) = line[digits..].strip_prefix(". ").or_else(|| line[digits..].strip_prefix(") ")) {
+            return (rest, true);
+        }
+    }
+    (line, false)
+}
+
+/// Links and images become their text; emphasis and code marks are dropped
+fn strip_inline_markup(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '!' if chars.peek() == Some(&'[') => {}
+            '[' => {}
+            ']' => {
+                if chars.peek() == Some(&'(') {
+                    // Skip the link target
+                    for c in chars.by_ref() {
+                        if c == ')' {
+                            break;
+                        }
+                    }
+                }
+            }
+            '*' | '_' | '`' | '~' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// One paragraph per CSV row, cells joined by spaces
+fn csv_text(raw: &str) -> String {
+    let mut out = String::new();
+    for row in parse_csv(raw) {
+        let cells: Vec<&str> = row.iter().map(|c| c.trim()).filter(|c| !c.is_empty()).collect();
+        if !cells.is_empty() {
+            out.push_str(&cells.join(" "));
+            out.push_str("\n\n");
+        }
+    }
+    out
+}
+
+/// Minimal RFC 4180 reader: quoted fields, `""` escapes, newlines in quotes
+fn parse_csv(raw: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = raw.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', true) => quoted = false,
+            ('"', false) if field.is_empty() => quoted = true,
+            (',', false) => row.push(std::mem::take(&mut field)),
+            ('\n', false) => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            ('\r', false) => {}
+            (c, _) => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
+
+/// Split text into sentences on `.`, `!`, `?`, `;` and blank lines
+pub fn split_sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    for paragraph in text.split("\n\n") {
+        let mut current = String::new();
+        for c in paragraph.chars() {
+            if matches!(c, '.' | '!' | '?' | ';') {
+                push_sentence(&mut sentences, &current);
+                current.clear();
+            } else {
+                current.push(if c == '\n' { ' ' } else { c });
+            }
+        }
+        push_sentence(&mut sentences, &current);
+    }
+    sentences
+}
+
+fn push_sentence(sentences: &mut Vec<String>, raw: &str) {
+    let words = tokenize(raw);
+    if !words.is_empty() {
+        sentences.push(words.join(" "));
+    }
+}
+
+/// Lowercase words with surrounding punctuation stripped
+fn tokenize(text: &str) -> Vec<String> {
+    text.split_whitespace()
+        .map(|w| {
+            w.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'' && c != '-')
+                .trim_matches(|c: char| c == '\'' || c == '-')
+                .to_lowercase()
+        })
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+/// Feeds documents into the bootstrap graph and runtime connections
+pub struct Ingestor {
+    bootstrap: Arc<RwLock<BootstrapLibrary>>,
+    storage: Arc<RuntimeStorage>,
+    normalizer: Normalizer,
+    config: IngestionConfig,
+    /// Canonical (a, b) concept pair → connection ID in storage
+    pairs: RwLock<HashMap<(u32, u32), u64>>,
+    events: broadcast::Sender<IngestionEvent>,
+}
+
+impl Ingestor {
+    pub fn new(
+        bootstrap: Arc<RwLock<BootstrapLibrary>>,
+        storage: Arc<RuntimeStorage>,
+        config: IngestionConfig,
+    ) -> Result<Self, IngestionError> {
+        config.validate().map_err(IngestionError::InvalidConfig)?;
+
+        // Unknown words carry no concept, so they must not contribute a state
+        let gateway_config = GatewayConfig {
+            unknown_word_strategy: UnknownWordStrategy::Ignore,
+            ..GatewayConfig::default()
+        };
+        let normalizer = Normalizer::new(bootstrap.clone(), gateway_config);
+        let (events, _) = broadcast::channel(256);
+
+        Ok(Self {
+            bootstrap,
+            storage,
+            normalizer,
+            config,
+            pairs: RwLock::new(HashMap::new()),
+            events,
+        })
+    }
+
+    pub fn config(&self) -> &IngestionConfig {
+        &self.config
+    }
+
+    /// Subscribe to progress events
+    pub fn subscribe(&self) -> broadcast::Receiver<IngestionEvent> {
+        self.events.subscribe()
+    }
+
+    /// Connection ID created for a concept pair, if any
+    pub fn connection_for(&self, a: u32, b: u32) -> Option<u64> {
+        self.pairs.read().get(&canonical(a, b)).copied()
+    }
+
+    /// Ingest one document
+    pub fn ingest_text(&self, document: &str, text: &str) -> IngestionReport {
+        let start = Instant::now();
+        let sentences = split_sentences(text);
+        let total = sentences.len();
+        let mut report = IngestionReport { sentences: total, ..Default::default() };
+
+        self.emit(IngestionEvent::Started { document: document.to_string(), sentences: total });
+
+        let mut concepts = HashSet::new();
+        for (i, sentence) in sentences.iter().enumerate() {
+            self.ingest_sentence(sentence, &mut report, &mut concepts);
+
+            let processed = i + 1;
+            if processed % self.config.progress_every == 0 || processed == total {
+                self.emit(IngestionEvent::Progress {
+                    document: document.to_string(),
+                    processed,
+                    total,
+                    new_connections: report.new_connections,
+                    reinforced_connections: report.reinforced_connections,
+                });
+            }
+        }
+
+        report.concepts = concepts.len();
+        report.duration_ms = start.elapsed().as_millis() as u64;
+        self.emit(IngestionEvent::Finished { document: document.to_string(), report: report.clone() });
+        report
+    }
+
+    /// Ingest a named `.txt`, `.md` or `.csv` document (e.g. a dropped file)
+    pub fn ingest_document(&self, name: &str, raw: &str) -> Result<IngestionReport, IngestionError> {
+        let format = DocumentFormat::from_name(name)
+            .ok_or_else(|| IngestionError::UnsupportedFormat(name.to_string()))?;
+        Ok(self.ingest_text(name, &format.extract(raw)))
+    }
+
+    /// Ingest a UTF-8 file; unknown extensions are read as plain text
+    pub fn ingest_file(&self, path: impl AsRef<Path>) -> Result<IngestionReport, IngestionError> {
+        let path = path.as_ref();
+        let name = path.display().to_string();
+        let raw = std::fs::read_to_string(path)?;
+        let format = DocumentFormat::from_name(&name).unwrap_or(DocumentFormat::Text);
+        Ok(self.ingest_text(&name, &format.extract(&raw)))
+    }
+
+    /// Ingest several `(name, text)` documents, returning the combined report
+    pub fn ingest_corpus<'a>(
+        &self,
+        documents: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> IngestionReport {
+        let mut total = IngestionReport::default();
+        for (name, text) in documents {
+            total.merge(&self.ingest_text(name, text));
+        }
+        total
+    }
+
+    fn ingest_sentence(&self, sentence: &str, report: &mut IngestionReport, seen_concepts: &mut HashSet<u32>) {
+        let mut word_count = sentence.split(' ').count();
+        report.words += word_count;
+
+        if word_count < self.config.min_sentence_words {
+            report.skipped_sentences += 1;
+            return;
+        }
+
+        let truncated;
+        let sentence = if word_count > self.config.max_sentence_words {
+            truncated = sentence
+                .split(' ')
+                .take(self.config.max_sentence_words)
+                .collect::<Vec<_>>()
+                .join(" ");
+            word_count = self.config.max_sentence_words;
+            truncated.as_str()
+        } else {
+            sentence
+        };
+
+        let Ok(normalized) = self.normalizer.normalize_text(sentence) else {
+            report.unknown_words += word_count;
+            report.skipped_sentences += 1;
+            return;
+        };
+        report.matched_words += normalized.matched_tokens.len();
+        report.unknown_words += normalized.unknown_words.len();
+
+        // Concepts in sentence order, consecutive repeats collapsed
+        let mut concepts: Vec<u32> = normalized.matched_tokens.iter().map(|(_, id, _)| *id).collect();
+        concepts.dedup();
+        seen_concepts.extend(concepts.iter().copied());
+
+        let mut seen = HashSet::new();
+        for i in 0..concepts.len() {
+            let end = match self.config.window {
+                0 => concepts.len(),
+                window => (i + 1 + window).min(concepts.len()),
+            };
+            for j in (i + 1)..end {
+                let pair = canonical(concepts[i], concepts[j]);
+                if pair.0 != pair.1 && seen.insert(pair) {
+                    self.observe_pair(pair, report);
+                }
+            }
+        }
+    }
+
+    /// Create or reinforce the connection for a co-occurring pair
+    fn observe_pair(&self, (a, b): (u32, u32), report: &mut IngestionReport) {
+        let existing = self.pairs.read().get(&(a, b)).copied();
+        let connection = match existing.and_then(|id| self.storage.get_connection(id).map(|c| (id, c))) {
+            Some((id, mut connection)) => {
+                connection.update_confidence(true);
+                connection.activate();
+                let _ = self.storage.update_connection(id, connection);
+                report.reinforced_connections += 1;
+                connection
+            }
+            None => {
+                let mut connection = ConnectionV3::new(a, b);
+                connection.set_connection_type(self.config.connection_type);
+                connection.mutability = ConnectionMutability::Hypothesis as u8;
+                connection.confidence = to_u8(self.config.initial_confidence);
+                connection.learning_rate = to_u8(self.config.learning_rate);
+                connection.decay_rate = to_u8(self.config.decay_rate);
+                connection.evidence_count = 1;
+                connection.activate();
+                let id = self.storage.create_connection(connection);
+                self.pairs.write().insert((a, b), id);
+                report.new_connections += 1;
+                connection
+            }
+        };
+
+        // Mirror into the bootstrap graph with confidence as weight
+        let weight = connection.confidence as f32 / 255.0;
+        let mut library = self.bootstrap.write();
+        let graph = library.graph_mut();
+        let edge_id = Graph::compute_edge_id(a, b, connection.connection_type);
+        if !graph.set_edge_weight_from(edge_id, weight, WeightSource::Ingestion) {
+            graph.add_node(a);
+            graph.add_node(b);
+            if let Ok(true) = graph.add_edge(edge_id, a, b, connection.connection_type, weight, true) {
+                if let Some(mutability) = ConnectionMutability::from_u8(connection.mutability) {
+                    graph.set_edge_mutability(edge_id, mutability);
+                }
+                if connection.active_levels != 0 {
+                    graph.set_edge_levels(edge_id, connection.active_levels);
+                }
+                report.graph_edges_added += 1;
+            }
+        }
+    }
+
+    fn emit(&self, event: IngestionEvent) {
+        // No subscribers is fine
+        let _ = self.events.send(event);
+    }
+}
+
+fn canonical(a: u32, b: u32) -> (u32, u32) {
+    if a <= b { (a, b) } else { (b, a) }
+}
+
+fn to_u8(value: f32) -> u8 {
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootstrap::BootstrapConfig;
+
+    fn library(words: &[&str]) -> Arc<RwLock<BootstrapLibrary>> {
+        let entries: Vec<_> = words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| serde_json::json!({"word": word, "id": i + 1, "coords": [i as f32, 0.0, 0.0]}))
+            .collect();
+        let mut library = BootstrapLibrary::new(BootstrapConfig::default());
+        library.load_bootstrap_map_str(&serde_json::to_string(&entries).unwrap()).unwrap();
+        Arc::new(RwLock::new(library))
+    }
+
+    fn ingestor(config: IngestionConfig) -> (Ingestor, Arc<RwLock<BootstrapLibrary>>, Arc<RuntimeStorage>) {
+        let bootstrap = library(&["cat", "dog", "bird", "tree"]);
+        let storage = Arc::new(RuntimeStorage::new());
+        let ingestor = Ingestor::new(bootstrap.clone(), storage.clone(), config).unwrap();
+        (ingestor, bootstrap, storage)
+    }
+
+    fn id(bootstrap: &Arc<RwLock<BootstrapLibrary>>, word: &str) -> u32 {
+        bootstrap.read().get_concept(word).unwrap().id
+    }
+
+    #[test]
+    fn test_split_sentences() {
+        let sentences = split_sentences("The Cat sat.  Did the dog bark?\nYes!\n\nNew paragraph");
+        assert_eq!(sentences, vec!["the cat sat", "did the dog bark", "yes", "new paragraph"]);
+        assert_eq!(tokenize("\"Hello,\" (world) don't -x-"), vec!["hello", "world", "don't", "x"]);
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(IngestionConfig::default().validate().is_ok());
+        let bad = IngestionConfig { initial_confidence: 1.5, ..Default::default() };
+        assert!(bad.validate().is_err());
+        let bad = IngestionConfig { min_sentence_words: 0, ..Default::default() };
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn test_cooccurrence_creates_and_reinforces_hypotheses() {
+        let (ingestor, bootstrap, storage) = ingestor(IngestionConfig::default());
+
+        let report = ingestor.ingest_text("doc", "The cat chased the dog. A cat and a dog!");
+        assert_eq!(report.sentences, 2);
+        assert_eq!(report.new_connections, 1);
+        assert_eq!(report.reinforced_connections, 1);
+        assert_eq!(report.graph_edges_added, 1);
+        assert!(report.unknown_words > 0);
+
+        let (cat, dog) = (id(&bootstrap, "cat"), id(&bootstrap, "dog"));
+        let conn_id = ingestor.connection_for(dog, cat).unwrap();
+        let connection = storage.get_connection(conn_id).unwrap();
+        assert_eq!(connection.mutability, ConnectionMutability::Hypothesis as u8);
+        assert_eq!(connection.connection_type, ConnectionType::Correlates as u8);
+        assert_eq!(connection.evidence_count, 2);
+        assert!(connection.confidence > to_u8(0.2));
+
+        let library = bootstrap.read();
+        let edge_id = Graph::compute_edge_id(connection.token_a_id, connection.token_b_id, connection.connection_type);
+        let edge = library.graph().get_edge(edge_id).unwrap();
+        assert!((edge.weight - connection.confidence as f32 / 255.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_window_limits_pairs() {
+        let config = IngestionConfig { window: 1, ..Default::default() };
+        let (ingestor, bootstrap, _) = ingestor(config);
+
+        let report = ingestor.ingest_text("doc", "cat dog bird tree");
+        assert_eq!(report.new_connections, 3);
+        assert!(ingestor.connection_for(id(&bootstrap, "cat"), id(&bootstrap, "bird")).is_none());
+        assert!(ingestor.connection_for(id(&bootstrap, "bird"), id(&bootstrap, "tree")).is_some());
+    }
+
+    #[test]
+    fn test_progress_events() {
+        let config = IngestionConfig { progress_every: 2, ..Default::default() };
+        let (ingestor, _, _) = ingestor(config);
+        let mut events = ingestor.subscribe();
+
+        let report = ingestor.ingest_corpus([("a", "cat dog. bird tree. zzz qqq"), ("b", "tree cat")]);
+        assert_eq!(report.sentences, 4);
+        assert_eq!(report.skipped_sentences, 1);
+
+        let mut received = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            received.push(event);
+        }
+        // a: started, progress 2/3, progress 3/3, finished; b: started, progress 1/1, finished
+        assert_eq!(received.len(), 7);
+        assert!(matches!(&received[0], IngestionEvent::Started { sentences: 3, .. }));
+        assert!(matches!(&received[2], IngestionEvent::Progress { processed: 3, total: 3, .. }));
+        assert!(matches!(&received[6], IngestionEvent::Finished { report, .. } if report.new_connections == 1));
+    }
+
+    #[test]
+    fn test_markdown_and_csv_extraction() {
+        let markdown = "# Cats\n\nThe *cat* sees a [dog](http://x.org/dog).\n\n- bird\n- tree\n\n```\nlet cat = dog;\n```\n";
+        let sentences = split_sentences(&DocumentFormat::Markdown.extract(markdown));
+        assert_eq!(sentences, vec!["cats", "the cat sees a dog", "bird", "tree"]);
+
+        let csv = "animal,place\ncat,\"tree, old\"\r\n\"dog \"\"rex\"\"\",yard\n";
+        let sentences = split_sentences(&DocumentFormat::Csv.extract(csv));
+        assert_eq!(sentences, vec!["animal place", "cat tree old", "dog rex yard"]);
+
+        assert_eq!(DocumentFormat::from_name("notes.MD"), Some(DocumentFormat::Markdown));
+        assert_eq!(DocumentFormat::from_name("/tmp/corpus.txt"), Some(DocumentFormat::Text));
+        assert_eq!(DocumentFormat::from_name("image.png"), None);
+    }
+
+    #[test]
+    fn test_ingest_document() {
+        let (ingestor, _, _) = ingestor(IngestionConfig::default());
+
+        let report = ingestor.ingest_document("pets.csv", "cat,dog\ndog,bird\n").unwrap();
+        assert_eq!(report.sentences, 2);
+        assert_eq!(report.concepts, 3);
+        assert_eq!(report.new_connections, 2);
+
+        assert!(matches!(
+            ingestor.ingest_document("pets.pdf", "cat dog"),
+            Err(IngestionError::UnsupportedFormat(_))
+        ));
+    }
+}
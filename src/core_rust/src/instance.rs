@@ -0,0 +1,411 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Instances v1.0 - multi-tenant isolation in one process
+//!
+//! A daemon can host several cognitive instances, e.g. one knowledge space
+//! per project. Each instance is an `ApiState` built from its own Gateway,
+//! bootstrap graph, ADNA, experience stream, Guardian and checkpoint
+//! directory; instances share nothing but the process.
+//!
+//! Addressing:
+//!
+//! - REST: `/api/v1/instances/{id}/...` is served by that instance's routes
+//!   (see `api::create_instances_router`); plain `/api/v1/...` keeps
+//!   addressing the default instance
+//! - Gateway: `InputSignal::Text` metadata `{"instance": "<id>"}`, routed by
+//!   `InstanceRegistry::inject`; signals without it go to the default
+//!   instance
+//!
+//! Instances are registered up front with `insert`, or created on demand
+//! (`POST /api/v1/instances`) by the factory set with `with_factory`.
+
+use crate::api::router::api_routes;
+use crate::api::ApiState;
+use crate::gateway::channels::{ResultReceiver, SignalReceipt};
+use crate::gateway::signals::InputSignal;
+use crate::gateway::GatewayError;
+use axum::Router;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Key in `InputSignal::Text` metadata naming the target instance
+pub const INSTANCE_METADATA_KEY: &str = "instance";
+
+/// Name of the instance every registry starts with
+pub const DEFAULT_INSTANCE: &str = "default";
+
+/// Maximum length of an instance ID
+pub const MAX_INSTANCE_ID_LEN: usize = 64;
+
+/// Instance errors
+#[derive(Debug)]
+pub enum InstanceError {
+    InvalidId(String),
+    NotFound(String),
+    AlreadyExists(String),
+    DefaultInstance,
+    LimitReached(usize),
+    NoFactory,
+    Factory(String),
+    Gateway(GatewayError),
+}
+
+impl fmt::Display for InstanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InstanceError::InvalidId(id) => write!(
+                f,
+                "Invalid instance ID '{}': use 1-{} characters from [A-Za-z0-9_-]",
+                id, MAX_INSTANCE_ID_LEN
+            ),
+            InstanceError::NotFound(id) => write!(f, "Instance '{}' not found", id),
+            InstanceError::AlreadyExists(id) => write!(f, "Instance '{}' already exists", id),
+            InstanceError::DefaultInstance => write!(f, "The default instance cannot be removed"),
+            InstanceError::LimitReached(max) => write!(f, "Instance limit reached ({})", max),
+            InstanceError::NoFactory => write!(f, "No instance factory configured"),
+            InstanceError::Factory(msg) => write!(f, "Instance creation failed: {}", msg),
+            InstanceError::Gateway(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for InstanceError {}
+
+impl From<GatewayError> for InstanceError {
+    fn from(e: GatewayError) -> Self {
+        InstanceError::Gateway(e)
+    }
+}
+
+/// Instance identifier: 1-64 characters from `[A-Za-z0-9_-]`, so it is
+/// safe in URL paths and directory names
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct InstanceId(String);
+
+impl InstanceId {
+    pub fn new(id: impl Into<String>) -> Result<Self, InstanceError> {
+        let id = id.into();
+        let valid = !id.is_empty()
+            && id.len() <= MAX_INSTANCE_ID_LEN
+            && id.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_');
+        if valid {
+            Ok(Self(id))
+        } else {
+            Err(InstanceError::InvalidId(id))
+        }
+    }
+
+    /// The default instance
+    pub fn default_instance() -> Self {
+        Self(DEFAULT_INSTANCE.to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.0 == DEFAULT_INSTANCE
+    }
+}
+
+impl fmt::Display for InstanceId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::str::FromStr for InstanceId {
+    type Err = InstanceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for InstanceId {
+    type Error = InstanceError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::new(s)
+    }
+}
+
+impl From<InstanceId> for String {
+    fn from(id: InstanceId) -> Self {
+        id.0
+    }
+}
+
+/// Builds the components of a new instance
+pub type InstanceFactory = Arc<dyn Fn(&InstanceId) -> Result<ApiState, String> + Send + Sync>;
+
+/// One isolated cognitive instance
+pub struct Instance {
+    id: InstanceId,
+    state: ApiState,
+    /// The instance's `/api/v1` routes, built once
+    routes: Router,
+    created_at: u64,
+}
+
+impl Instance {
+    fn new(id: InstanceId, state: ApiState) -> Self {
+        let routes = api_routes(state.clone());
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        Self { id, state, routes, created_at }
+    }
+
+    pub fn id(&self) -> &InstanceId {
+        &self.id
+    }
+
+    pub fn state(&self) -> &ApiState {
+        &self.state
+    }
+
+    /// Routes serving this instance (paths start with `/api/v1`)
+    pub fn routes(&self) -> Router {
+        self.routes.clone()
+    }
+
+    /// Creation time (Unix milliseconds)
+    pub fn created_at(&self) -> u64 {
+        self.created_at
+    }
+}
+
+/// Instances hosted by one process
+pub struct InstanceRegistry {
+    instances: RwLock<BTreeMap<InstanceId, Arc<Instance>>>,
+    factory: Option<InstanceFactory>,
+    max_instances: usize,
+}
+
+impl InstanceRegistry {
+    /// Registry holding only the default instance
+    pub fn new(default: ApiState) -> Self {
+        let id = InstanceId::default_instance();
+        let mut instances = BTreeMap::new();
+        instances.insert(id.clone(), Arc::new(Instance::new(id, default)));
+        Self {
+            instances: RwLock::new(instances),
+            factory: None,
+            max_instances: 64,
+        }
+    }
+
+    /// Allow creating instances on demand
+    pub fn with_factory(mut self, factory: InstanceFactory) -> Self {
+        self.factory = Some(factory);
+        self
+    }
+
+    /// Maximum number of instances, including the default one
+    pub fn with_max_instances(mut self, max_instances: usize) -> Self {
+        self.max_instances = max_instances.max(1);
+        self
+    }
+
+    /// Register an instance built by the caller
+    pub fn insert(&self, id: InstanceId, state: ApiState) -> Result<Arc<Instance>, InstanceError> {
+        let mut instances = self.instances.write();
+        if instances.contains_key(&id) {
+            return Err(InstanceError::AlreadyExists(id.to_string()));
+        }
+        if instances.len() >= self.max_instances {
+            return Err(InstanceError::LimitReached(self.max_instances));
+        }
+        let instance = Arc::new(Instance::new(id.clone(), state));
+        instances.insert(id, instance.clone());
+        Ok(instance)
+    }
+
+    /// Build an instance with the factory and register it
+    pub fn create(&self, id: InstanceId) -> Result<Arc<Instance>, InstanceError> {
+        let factory = self.factory.as_ref().ok_or(InstanceError::NoFactory)?;
+        if self.instances.read().contains_key(&id) {
+            return Err(InstanceError::AlreadyExists(id.to_string()));
+        }
+        let state = factory(&id).map_err(InstanceError::Factory)?;
+        self.insert(id, state)
+    }
+
+    /// Unregister an instance; it is dropped once in-flight requests finish
+    pub fn remove(&self, id: &InstanceId) -> Result<Arc<Instance>, InstanceError> {
+        if id.is_default() {
+            return Err(InstanceError::DefaultInstance);
+        }
+        self.instances
+            .write()
+            .remove(id)
+            .ok_or_else(|| InstanceError::NotFound(id.to_string()))
+    }
+
+    pub fn get(&self, id: &InstanceId) -> Option<Arc<Instance>> {
+        self.instances.read().get(id).cloned()
+    }
+
+    pub fn default_instance(&self) -> Arc<Instance> {
+        self.get(&InstanceId::default_instance())
+            .expect("default instance is never removed")
+    }
+
+    /// Look up an instance by name; None means the default instance
+    pub fn resolve(&self, id: Option<&str>) -> Result<Arc<Instance>, InstanceError> {
+        match id {
+            None => Ok(self.default_instance()),
+            Some(id) => {
+                let id = InstanceId::new(id)?;
+                self.get(&id).ok_or_else(|| InstanceError::NotFound(id.to_string()))
+            }
+        }
+    }
+
+    /// Registered instances, ordered by ID
+    pub fn list(&self) -> Vec<Arc<Instance>> {
+        self.instances.read().values().cloned().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.instances.read().len()
+    }
+
+    /// Always false: the default instance cannot be removed
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Inject a signal into the instance named in its metadata
+    pub async fn inject(
+        &self,
+        signal: InputSignal,
+    ) -> Result<(InstanceId, SignalReceipt, ResultReceiver), InstanceError> {
+        let instance = self.resolve(signal.instance())?;
+        let (receipt, receiver) = instance.state().gateway.inject(signal).await?;
+        Ok((instance.id().clone(), receipt, receiver))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::ApiConfig;
+    use crate::bootstrap::BootstrapLibrary;
+    use crate::experience_stream::ExperienceStream;
+    use crate::feedback::FeedbackProcessor;
+    use crate::gateway::signals::SignalSource;
+    use crate::gateway::Gateway;
+    use crate::IntuitionEngine;
+    use tokio::sync::mpsc;
+
+    /// Instance with its own components; returns the gateway's signal queue
+    fn test_state() -> (ApiState, mpsc::Receiver<crate::gateway::signals::ProcessedSignal>) {
+        let (signal_tx, signal_rx) = mpsc::channel(100);
+        let bootstrap = Arc::new(RwLock::new(BootstrapLibrary::new(Default::default())));
+        let gateway = Arc::new(Gateway::new(signal_tx, bootstrap.clone(), Default::default()));
+        let intuition = Arc::new(RwLock::new(IntuitionEngine::new(
+            Default::default(),
+            Arc::new(ExperienceStream::new(1000, 10)),
+            Arc::new(crate::adna::InMemoryADNAReader::new(Default::default())),
+            mpsc::channel(100).0,
+        )));
+        let feedback = Arc::new(FeedbackProcessor::new(
+            bootstrap,
+            Arc::new(RwLock::new(ExperienceStream::new(1000, 10))),
+            intuition,
+        ));
+        (ApiState::new(gateway, feedback, ApiConfig::default()), signal_rx)
+    }
+
+    fn text(content: &str, instance: Option<&str>) -> InputSignal {
+        InputSignal::Text {
+            content: content.to_string(),
+            source: SignalSource::RestApi,
+            metadata: instance.map(|id| serde_json::json!({ INSTANCE_METADATA_KEY: id })),
+        }
+    }
+
+    #[test]
+    fn test_instance_id_validation() {
+        assert!(InstanceId::new("project-a_2").is_ok());
+        for bad in ["", "a/b", "../etc", "with space", &"x".repeat(65)] {
+            assert!(InstanceId::new(bad).is_err(), "{:?}", bad);
+        }
+        let id: InstanceId = serde_json::from_str("\"notes\"").unwrap();
+        assert_eq!(id.as_str(), "notes");
+        assert!(serde_json::from_str::<InstanceId>("\"no/slash\"").is_err());
+    }
+
+    #[test]
+    fn test_registry_lifecycle() {
+        let registry = InstanceRegistry::new(test_state().0).with_max_instances(2);
+        assert!(matches!(
+            registry.create(InstanceId::new("a").unwrap()),
+            Err(InstanceError::NoFactory)
+        ));
+
+        let a = InstanceId::new("a").unwrap();
+        registry.insert(a.clone(), test_state().0).unwrap();
+        assert!(matches!(registry.insert(a.clone(), test_state().0), Err(InstanceError::AlreadyExists(_))));
+        assert!(matches!(
+            registry.insert(InstanceId::new("b").unwrap(), test_state().0),
+            Err(InstanceError::LimitReached(2))
+        ));
+
+        assert_eq!(registry.resolve(None).unwrap().id().as_str(), DEFAULT_INSTANCE);
+        assert_eq!(registry.resolve(Some("a")).unwrap().id(), &a);
+        assert!(matches!(registry.resolve(Some("zzz")), Err(InstanceError::NotFound(_))));
+
+        assert!(matches!(
+            registry.remove(&InstanceId::default_instance()),
+            Err(InstanceError::DefaultInstance)
+        ));
+        registry.remove(&a).unwrap();
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_inject_routes_by_metadata() {
+        let (default_state, mut default_rx) = test_state();
+        let (project_state, mut project_rx) = test_state();
+        let registry = InstanceRegistry::new(default_state);
+        registry.insert(InstanceId::new("project").unwrap(), project_state).unwrap();
+
+        let (id, _, _) = registry.inject(text("hello", Some("project"))).await.unwrap();
+        assert_eq!(id.as_str(), "project");
+        assert!(project_rx.try_recv().is_ok());
+        assert!(default_rx.try_recv().is_err());
+
+        let (id, _, _) = registry.inject(text("hello", None)).await.unwrap();
+        assert!(id.is_default());
+        assert!(default_rx.try_recv().is_ok());
+
+        assert!(matches!(
+            registry.inject(text("hello", Some("missing"))).await,
+            Err(InstanceError::NotFound(_))
+        ));
+    }
+}
@@ -41,8 +41,10 @@
 use std::sync::Arc;
 use std::collections::HashMap;
 use tokio::sync::mpsc;
+use tracing::Instrument;
 use crate::experience_stream::{ExperienceStream, ExperienceBatch, SamplingStrategy};
 use crate::adna::{ADNAReader, Proposal, InMemoryADNAReader, AppraiserConfig};
+use crate::evolution_manager::EvolutionTrigger;
 use crate::token::Token;
 use crate::connection_v3::{ConnectionV3, ConnectionMutability};
 use crate::reflex_layer::{
@@ -80,6 +82,10 @@ pub struct IntuitionConfig {
     /// Minimum absolute reward difference for significance
     pub min_reward_delta: f64,
 
+    /// Eligibility trace decay λ per step [0.0, 1.0] used by the Learner
+    /// to credit delayed rewards; 0 = credit only the current step
+    pub lambda: f32,
+
     // === Fast Path (Reflex Layer) v3.0 ===
     /// Enable fast path reflexes
     pub enable_fast_path: bool,
@@ -103,6 +109,7 @@ impl Default for IntuitionConfig {
             state_bins_per_dim: 4,
             min_samples: 10,
             min_reward_delta: 0.5,
+            lambda: 0.8,
 
             // Fast Path defaults (v3.0)
             enable_fast_path: true,  // Enable by default
@@ -112,6 +119,119 @@ impl Default for IntuitionConfig {
     }
 }
 
+/// Learning (slow path) settings of IntuitionConfig, as edited at runtime
+///
+/// `analysis_interval_secs` and `state_bins_per_dim` only take effect on
+/// restart: the analysis loop reads the interval once, and changing the bin
+/// count would orphan policies keyed by the old bins.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LearningConfig {
+    pub analysis_interval_secs: u64,
+    pub batch_size: usize,
+    pub min_confidence: f64,
+    pub max_proposals_per_cycle: usize,
+    pub state_bins_per_dim: usize,
+    pub min_samples: usize,
+    pub min_reward_delta: f64,
+    pub lambda: f32,
+    pub enable_fast_path: bool,
+}
+
+impl Default for LearningConfig {
+    fn default() -> Self {
+        Self::from(&IntuitionConfig::default())
+    }
+}
+
+impl From<&IntuitionConfig> for LearningConfig {
+    fn from(config: &IntuitionConfig) -> Self {
+        Self {
+            analysis_interval_secs: config.analysis_interval_secs,
+            batch_size: config.batch_size,
+            min_confidence: config.min_confidence,
+            max_proposals_per_cycle: config.max_proposals_per_cycle,
+            state_bins_per_dim: config.state_bins_per_dim,
+            min_samples: config.min_samples,
+            min_reward_delta: config.min_reward_delta,
+            lambda: config.lambda,
+            enable_fast_path: config.enable_fast_path,
+        }
+    }
+}
+
+impl LearningConfig {
+    /// Fields `IntuitionEngine::update_learning_config` applies immediately
+    pub const LIVE_FIELDS: &'static [&'static str] = &[
+        "batch_size",
+        "min_confidence",
+        "max_proposals_per_cycle",
+        "min_samples",
+        "min_reward_delta",
+        "lambda",
+        "enable_fast_path",
+    ];
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.analysis_interval_secs == 0 {
+            return Err("analysis_interval_secs must be > 0".to_string());
+        }
+        if self.batch_size == 0 {
+            return Err("batch_size must be > 0".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.min_confidence) {
+            return Err(format!("min_confidence must be 0.0-1.0, got {}", self.min_confidence));
+        }
+        if !(2..=16).contains(&self.state_bins_per_dim) {
+            // 16^8 bins still fit in u64 (quantize_state)
+            return Err(format!("state_bins_per_dim must be 2-16, got {}", self.state_bins_per_dim));
+        }
+        if !self.min_reward_delta.is_finite() || self.min_reward_delta < 0.0 {
+            return Err("min_reward_delta must be finite and >= 0".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.lambda) {
+            return Err(format!("lambda must be 0.0-1.0, got {}", self.lambda));
+        }
+        Ok(())
+    }
+
+    /// Copy all fields into an IntuitionConfig (for startup)
+    pub fn apply_to(&self, config: &mut IntuitionConfig) {
+        config.analysis_interval_secs = self.analysis_interval_secs;
+        config.state_bins_per_dim = self.state_bins_per_dim;
+        self.apply_live(config);
+    }
+
+    fn apply_live(&self, config: &mut IntuitionConfig) {
+        config.batch_size = self.batch_size;
+        config.min_confidence = self.min_confidence;
+        config.max_proposals_per_cycle = self.max_proposals_per_cycle;
+        config.min_samples = self.min_samples;
+        config.min_reward_delta = self.min_reward_delta;
+        config.lambda = self.lambda;
+        config.enable_fast_path = self.enable_fast_path;
+    }
+}
+
+/// Quantize continuous state into a discrete state bin
+///
+/// Shared by IntuitionEngine (pattern mining) and EvolutionManager (shadow
+/// evaluation) so that `adna_state_bin_{id}` targets mean the same thing.
+pub fn quantize_state(state: &[f32; 8], bins_per_dim: usize) -> u64 {
+    let mut bin_id: u64 = 0;
+    let bins_per_dim = bins_per_dim as u64;
+
+    for &value in state.iter() {
+        // Normalize value to [0, 1] assuming state values are roughly in [-1, 1]
+        let normalized = ((value + 1.0) / 2.0).clamp(0.0, 0.999);
+        let bin = (normalized * bins_per_dim as f32) as u64;
+
+        // Encode in base-N where N = bins_per_dim
+        bin_id = bin_id * bins_per_dim + bin;
+    }
+
+    bin_id
+}
+
 /// Identified pattern from batch analysis
 #[derive(Debug, Clone)]
 pub struct IdentifiedPattern {
@@ -141,6 +261,7 @@ pub struct IntuitionEngine {
     experience_stream: Arc<ExperienceStream>,
     _dna_reader: Arc<dyn ADNAReader>,
     proposal_sender: mpsc::Sender<Proposal>,
+    trigger_receiver: Option<mpsc::Receiver<EvolutionTrigger>>,
 
     // Fast Path (Reflex Layer) v3.0
     associative_memory: AssociativeMemory,
@@ -162,6 +283,7 @@ impl IntuitionEngine {
             experience_stream,
             _dna_reader: dna_reader,
             proposal_sender,
+            trigger_receiver: None,
 
             // Fast Path (v3.0)
             associative_memory: AssociativeMemory::new(),
@@ -170,6 +292,18 @@ impl IntuitionEngine {
         }
     }
 
+    /// Current configuration
+    pub fn config(&self) -> &IntuitionConfig {
+        &self.config
+    }
+
+    /// Apply the live-safe fields of a learning config (see `LearningConfig`)
+    pub fn update_learning_config(&mut self, learning: &LearningConfig) -> Result<(), String> {
+        learning.validate()?;
+        learning.apply_live(&mut self.config);
+        Ok(())
+    }
+
     /// Try fast path lookup (System 1)
     ///
     /// Returns ConnectionID if reflex is found and confident enough.
@@ -345,6 +479,31 @@ impl IntuitionEngine {
         true
     }
 
+    /// Export fast-path reflexes as (grid hash, connection) pairs
+    pub fn export_reflexes(&self) -> Vec<(u64, ConnectionV3)> {
+        let connections = self.connections.read().unwrap();
+        self.associative_memory
+            .entries()
+            .into_iter()
+            .filter_map(|(hash, id)| connections.get(&id).map(|conn| (hash, *conn)))
+            .collect()
+    }
+
+    /// Replace all fast-path reflexes (e.g. when restoring a checkpoint)
+    pub fn import_reflexes(&mut self, reflexes: Vec<(u64, ConnectionV3)>) {
+        let mut connections = self.connections.write().unwrap();
+        connections.clear();
+        self.associative_memory.clear();
+
+        for (hash, connection) in reflexes {
+            let conn_id = connection.token_a_id as u64;
+            connections.insert(conn_id, connection);
+            self.associative_memory.insert(hash, conn_id);
+        }
+
+        self.stats.write().unwrap().total_reflexes = connections.len();
+    }
+
     /// Get current stats (for monitoring/UI)
     pub fn get_stats(&self) -> ReflexStats {
         self.stats.read().unwrap().clone()
@@ -365,16 +524,39 @@ impl IntuitionEngine {
             .cloned()
     }
 
+    /// Run an extra analysis cycle whenever EvolutionScheduler triggers one
+    pub fn set_trigger_receiver(&mut self, receiver: mpsc::Receiver<EvolutionTrigger>) {
+        self.trigger_receiver = Some(receiver);
+    }
+
     /// Run main analysis loop (async background task)
-    pub async fn run(self) {
+    pub async fn run(mut self) {
         let mut interval = tokio::time::interval(
             tokio::time::Duration::from_secs(self.config.analysis_interval_secs)
         );
+        let mut triggers = self.trigger_receiver.take();
 
         loop {
-            interval.tick().await;
+            match triggers.as_mut() {
+                Some(rx) => {
+                    tokio::select! {
+                        _ = interval.tick() => {}
+                        trigger = rx.recv() => match trigger {
+                            Some(trigger) => {
+                                println!("[IntuitionEngine] Evolution triggered by {}", trigger);
+                            }
+                            // Scheduler gone: fall back to the interval only
+                            None => triggers = None,
+                        },
+                    }
+                }
+                None => {
+                    interval.tick().await;
+                }
+            }
 
-            if let Err(e) = self.run_analysis_cycle().await {
+            let span = tracing::info_span!("learning.analysis");
+            if let Err(e) = self.run_analysis_cycle().instrument(span).await {
                 eprintln!("IntuitionEngine analysis error: {}", e);
             }
         }
@@ -524,19 +706,7 @@ impl IntuitionEngine {
 
     /// Quantize continuous state into discrete bin
     fn quantize_state(&self, state: &[f32; 8]) -> u64 {
-        let mut bin_id: u64 = 0;
-        let bins_per_dim = self.config.state_bins_per_dim as u64;
-
-        for (i, &value) in state.iter().enumerate() {
-            // Normalize value to [0, 1] assuming state values are roughly in [-1, 1]
-            let normalized = ((value + 1.0) / 2.0).clamp(0.0, 0.999);
-            let bin = (normalized * bins_per_dim as f32) as u64;
-
-            // Encode in base-N where N = bins_per_dim
-            bin_id = bin_id * bins_per_dim + bin;
-        }
-
-        bin_id
+        quantize_state(state, self.config.state_bins_per_dim)
     }
 
     /// Calculate variance
@@ -0,0 +1,949 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Learner v1.0 - Hebbian/BCM edge weights in a struct-of-arrays table
+//!
+//! Per-edge learning state (weight, eligibility trace, BCM threshold) lives
+//! in one [`WeightTable`]: parallel vectors indexed by slot, plus a single
+//! `EdgeId → slot` index. Compared to one HashMap per field this keeps one
+//! hash entry per edge and lets passes over all edges run over dense arrays.
+//!
+//! Pruning only tombstones a slot (O(1) per edge, no list searches); the
+//! slots are reclaimed by [`WeightTable::compact`], which runs from
+//! [`Learner::maybe_compact`] once the dead fraction exceeds
+//! `LearnerConfig::compaction_ratio` — periodically via
+//! [`Learner::spawn_compaction`].
+//!
+//! Update rule for an edge with pre/post activity `x`, `y`:
+//!
+//! ```text
+//! θ ← θ + bcm_rate · (y² − θ)          sliding BCM threshold
+//! w ← clamp(w + η · x · y · (y − θ))   potentiation above θ, depression below
+//! e ← min(e + x · y, 1)                eligibility trace
+//! ```
+//!
+//! Rewards use TD(λ)-style credit assignment: [`Learner::step`] decays all
+//! traces by γλ once per time step, and [`Learner::apply_reward`] moves
+//! every traced edge by `η · r · e`. An edge active k steps before a reward
+//! therefore receives `(γλ)^k` of the credit of one active at reward time;
+//! with λ = 0 only the current step is credited. λ is the `lambda` of the
+//! intuition `LearningConfig` ([`Learner::update_learning_config`]).
+//! A time step is one experience event: [`Learner::spawn_stepper`] steps
+//! once per event on the ExperienceStream, and `FeedbackProcessor` credits
+//! user feedback through `apply_reward`.
+//!
+//! Neuromodulation: the effective step size is `η · adaptive_factor`.
+//! [`Learner::set_modulation`] raises the factor, and every `step` brings it
+//! back toward 1 by `decay`. ActionController feeds CuriosityDrive surprise
+//! in through [`Learner::modulate_surprise`], so surprising outcomes are
+//! learned faster for a few steps.
+//!
+//! For persisted graphs the table is keyed by RuntimeStorage connection ID
+//! and is only a working copy: weights are written into
+//! `ConnectionV3::confidence` by [`Learner::sync_to_storage`] (before every
+//! checkpoint via [`LearnerSyncHook`]) and read back at startup with
+//! [`Learner::load_from_storage`]. Confidence is a u8, so changes smaller
+//! than 1/255 are not persisted.
+//!
+//! Between checkpoints, an attached [`LearningJournal`] receives every weight
+//! commit (`learner:<edge_id>` → `[weight, threshold]`, empty = removed);
+//! `LearningJournal::recover` replays them through [`Learner::replay_weights`].
+
+use crate::checkpoint::CheckpointHook;
+use crate::experience_stream::ExperienceEvent;
+use crate::graph::EdgeId;
+use crate::intuition_engine::LearningConfig;
+use crate::learning_journal::LearningJournal;
+use crate::runtime_storage::RuntimeStorage;
+use crate::safe_mode::SafeMode;
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+/// Traces below this are treated as zero
+pub const TRACE_EPSILON: f32 = 1e-3;
+
+/// Journal key prefix of learner weight commits
+pub const JOURNAL_KEY_PREFIX: &str = "learner:";
+
+/// Learner configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LearnerConfig {
+    /// Step size η
+    pub learning_rate: f32,
+
+    /// Rate at which BCM thresholds follow y²
+    pub bcm_rate: f32,
+
+    /// Initial BCM threshold of new edges
+    pub initial_threshold: f32,
+
+    /// Reward discount γ per time step [0.0, 1.0]
+    pub gamma: f32,
+
+    /// Weights below this are pruned by `prune_dead_connections`
+    pub prune_threshold: f32,
+
+    /// Compact when this fraction of slots is dead (0.0, 1.0]
+    pub compaction_ratio: f32,
+
+    /// adaptive_factor = 1 + surprise_gain · surprise
+    pub surprise_gain: f32,
+
+    /// Per-step decay of the surprise boost [0.0, 1.0)
+    pub modulation_decay: f32,
+}
+
+impl Default for LearnerConfig {
+    fn default() -> Self {
+        Self {
+            learning_rate: 0.05,
+            bcm_rate: 0.01,
+            initial_threshold: 0.25,
+            gamma: 0.95,
+            prune_threshold: 0.01,
+            compaction_ratio: 0.25,
+            surprise_gain: 2.0,
+            modulation_decay: 0.8,
+        }
+    }
+}
+
+impl LearnerConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.learning_rate) {
+            return Err(format!("learning_rate must be 0.0-1.0, got {}", self.learning_rate));
+        }
+        if !(0.0..=1.0).contains(&self.bcm_rate) {
+            return Err(format!("bcm_rate must be 0.0-1.0, got {}", self.bcm_rate));
+        }
+        if !self.initial_threshold.is_finite() || self.initial_threshold < 0.0 {
+            return Err("initial_threshold must be finite and >= 0".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.gamma) {
+            return Err(format!("gamma must be 0.0-1.0, got {}", self.gamma));
+        }
+        if !(0.0..1.0).contains(&self.prune_threshold) {
+            return Err(format!("prune_threshold must be 0.0-1.0, got {}", self.prune_threshold));
+        }
+        if !(self.compaction_ratio > 0.0 && self.compaction_ratio <= 1.0) {
+            return Err(format!("compaction_ratio must be in (0.0, 1.0], got {}", self.compaction_ratio));
+        }
+        if !self.surprise_gain.is_finite() || self.surprise_gain < 0.0 {
+            return Err("surprise_gain must be finite and >= 0".to_string());
+        }
+        if !(0.0..1.0).contains(&self.modulation_decay) {
+            return Err(format!("modulation_decay must be 0.0-1.0, got {}", self.modulation_decay));
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// WeightTable
+// ============================================================================
+
+/// Struct-of-arrays store of per-edge learning state
+///
+/// All vectors have one entry per slot. Removed edges leave a dead slot
+/// until the next [`WeightTable::compact`].
+#[derive(Debug, Clone, Default)]
+pub struct WeightTable {
+    edge_ids: Vec<EdgeId>,
+    weights: Vec<f32>,
+    traces: Vec<f32>,
+    thresholds: Vec<f32>,
+    alive: Vec<bool>,
+    index: HashMap<EdgeId, u32>,
+    dead: usize,
+}
+
+impl WeightTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Live edges
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Allocated slots, live and dead
+    pub fn slots(&self) -> usize {
+        self.edge_ids.len()
+    }
+
+    pub fn dead_slots(&self) -> usize {
+        self.dead
+    }
+
+    /// Fraction of slots that are dead
+    pub fn dead_ratio(&self) -> f32 {
+        if self.edge_ids.is_empty() {
+            0.0
+        } else {
+            self.dead as f32 / self.edge_ids.len() as f32
+        }
+    }
+
+    pub fn contains(&self, edge_id: EdgeId) -> bool {
+        self.index.contains_key(&edge_id)
+    }
+
+    fn slot(&self, edge_id: EdgeId) -> Option<usize> {
+        self.index.get(&edge_id).map(|&slot| slot as usize)
+    }
+
+    /// Insert an edge, or overwrite the weight of an existing one
+    pub fn insert(&mut self, edge_id: EdgeId, weight: f32, threshold: f32) {
+        if let Some(slot) = self.slot(edge_id) {
+            self.weights[slot] = weight;
+            return;
+        }
+        self.index.insert(edge_id, self.edge_ids.len() as u32);
+        self.edge_ids.push(edge_id);
+        self.weights.push(weight);
+        self.traces.push(0.0);
+        self.thresholds.push(threshold);
+        self.alive.push(true);
+    }
+
+    /// Tombstone an edge; returns false if it is unknown
+    pub fn remove(&mut self, edge_id: EdgeId) -> bool {
+        match self.index.remove(&edge_id) {
+            Some(slot) => {
+                self.alive[slot as usize] = false;
+                self.dead += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn weight(&self, edge_id: EdgeId) -> Option<f32> {
+        self.slot(edge_id).map(|slot| self.weights[slot])
+    }
+
+    pub fn trace(&self, edge_id: EdgeId) -> Option<f32> {
+        self.slot(edge_id).map(|slot| self.traces[slot])
+    }
+
+    pub fn threshold(&self, edge_id: EdgeId) -> Option<f32> {
+        self.slot(edge_id).map(|slot| self.thresholds[slot])
+    }
+
+    pub fn set_weight(&mut self, edge_id: EdgeId, weight: f32) -> bool {
+        match self.slot(edge_id) {
+            Some(slot) => {
+                self.weights[slot] = weight;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Live `(edge_id, weight)` pairs in slot order
+    pub fn iter(&self) -> impl Iterator<Item = (EdgeId, f32)> + '_ {
+        self.edge_ids
+            .iter()
+            .zip(&self.weights)
+            .zip(&self.alive)
+            .filter(|(_, &alive)| alive)
+            .map(|((&edge_id, &weight), _)| (edge_id, weight))
+    }
+
+    /// Drop dead slots and rebuild the index; returns the number reclaimed
+    pub fn compact(&mut self) -> usize {
+        let reclaimed = self.dead;
+        if reclaimed == 0 {
+            return 0;
+        }
+
+        let mut write = 0;
+        for read in 0..self.edge_ids.len() {
+            if !self.alive[read] {
+                continue;
+            }
+            self.edge_ids[write] = self.edge_ids[read];
+            self.weights[write] = self.weights[read];
+            self.traces[write] = self.traces[read];
+            self.thresholds[write] = self.thresholds[read];
+            self.index.insert(self.edge_ids[write], write as u32);
+            write += 1;
+        }
+        self.edge_ids.truncate(write);
+        self.weights.truncate(write);
+        self.traces.truncate(write);
+        self.thresholds.truncate(write);
+        self.alive.clear();
+        self.alive.resize(write, true);
+        self.dead = 0;
+
+        self.edge_ids.shrink_to_fit();
+        self.weights.shrink_to_fit();
+        self.traces.shrink_to_fit();
+        self.thresholds.shrink_to_fit();
+        self.alive.shrink_to_fit();
+        self.index.shrink_to_fit();
+        reclaimed
+    }
+}
+
+// ============================================================================
+// Learner
+// ============================================================================
+
+/// Learner statistics
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LearnerStats {
+    pub edges: usize,
+    pub slots: usize,
+    pub dead_slots: usize,
+    pub updates: u64,
+    pub rewards: u64,
+    pub pruned: u64,
+    pub compactions: u64,
+    pub adaptive_factor: f32,
+}
+
+#[derive(Debug, Default)]
+struct Counters {
+    updates: u64,
+    rewards: u64,
+    pruned: u64,
+    compactions: u64,
+}
+
+/// Transient learning-rate boost
+#[derive(Debug, Clone, Copy)]
+struct Modulation {
+    factor: f32,
+    decay: f32,
+}
+
+impl Default for Modulation {
+    fn default() -> Self {
+        Self { factor: 1.0, decay: 0.0 }
+    }
+}
+
+/// Edge weight learner
+pub struct Learner {
+    config: RwLock<LearnerConfig>,
+    /// Trace decay λ, from `LearningConfig`
+    lambda: RwLock<f32>,
+    table: RwLock<WeightTable>,
+    counters: RwLock<Counters>,
+    modulation: RwLock<Modulation>,
+    journal: RwLock<Option<Arc<LearningJournal>>>,
+    safe_mode: Arc<SafeMode>,
+}
+
+impl Learner {
+    pub fn new(config: LearnerConfig) -> Self {
+        Self {
+            config: RwLock::new(config),
+            lambda: RwLock::new(LearningConfig::default().lambda),
+            table: RwLock::new(WeightTable::new()),
+            counters: RwLock::new(Counters::default()),
+            modulation: RwLock::new(Modulation::default()),
+            journal: RwLock::new(None),
+            safe_mode: Arc::default(),
+        }
+    }
+
+    /// Freeze `learn`, `learn_batch` and `apply_reward` with this switch
+    pub fn with_safe_mode(mut self, safe_mode: Arc<SafeMode>) -> Self {
+        self.safe_mode = safe_mode;
+        self
+    }
+
+    /// Attach a learning journal; weight commits are journaled under the table lock
+    ///
+    /// Replay the journal with `LearningJournal::recover` (or `replay_into`
+    /// and [`replay_weights`](Self::replay_weights)) first.
+    pub fn set_journal(&self, journal: Arc<LearningJournal>) {
+        *self.journal.write() = Some(journal);
+    }
+
+    fn journal_slots(&self, table: &WeightTable, slots: impl IntoIterator<Item = usize>) {
+        let journal = self.journal.read();
+        let Some(journal) = journal.as_ref() else {
+            return;
+        };
+        for slot in slots {
+            let edge_id = table.edge_ids[slot];
+            let key = format!("{}{}", JOURNAL_KEY_PREFIX, edge_id);
+            if let Err(e) = journal.record_weights(&key, &[table.weights[slot], table.thresholds[slot]]) {
+                tracing::error!(edge_id, error = %e, "Failed to journal learner weights");
+            }
+        }
+    }
+
+    fn journal_removed(&self, edge_ids: &[EdgeId]) {
+        let journal = self.journal.read();
+        let Some(journal) = journal.as_ref() else {
+            return;
+        };
+        for &edge_id in edge_ids {
+            let key = format!("{}{}", JOURNAL_KEY_PREFIX, edge_id);
+            if let Err(e) = journal.record_weights(&key, &[]) {
+                tracing::error!(edge_id, error = %e, "Failed to journal learner edge removal");
+            }
+        }
+    }
+
+    /// Apply a journaled weight commit (not journaled again)
+    ///
+    /// Returns false for keys or payloads that are not learner commits.
+    pub fn replay_weights(&self, key: &str, weights: &[f32]) -> bool {
+        let Some(edge_id) = key.strip_prefix(JOURNAL_KEY_PREFIX).and_then(|id| id.parse().ok()) else {
+            return false;
+        };
+        let mut table = self.table.write();
+        match *weights {
+            [] => {
+                table.remove(edge_id);
+            }
+            [weight, threshold] => {
+                table.insert(edge_id, weight, threshold);
+                if let Some(slot) = table.slot(edge_id) {
+                    table.thresholds[slot] = threshold;
+                }
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    pub fn config(&self) -> LearnerConfig {
+        self.config.read().clone()
+    }
+
+    pub fn update_config(&self, config: LearnerConfig) -> Result<(), String> {
+        config.validate()?;
+        *self.config.write() = config;
+        Ok(())
+    }
+
+    /// Trace decay λ per time step
+    pub fn lambda(&self) -> f32 {
+        *self.lambda.read()
+    }
+
+    /// Take λ from the learning settings (the other fields are IntuitionEngine's)
+    pub fn update_learning_config(&self, learning: &LearningConfig) -> Result<(), String> {
+        learning.validate()?;
+        *self.lambda.write() = learning.lambda;
+        Ok(())
+    }
+
+    /// Config with `learning_rate` scaled by the current adaptive factor
+    fn modulated_config(&self) -> LearnerConfig {
+        let mut config = self.config.read().clone();
+        config.learning_rate = (config.learning_rate * self.modulation.read().factor).min(1.0);
+        config
+    }
+
+    /// Current learning-rate multiplier (1.0 = unmodulated)
+    pub fn adaptive_factor(&self) -> f32 {
+        self.modulation.read().factor
+    }
+
+    /// Multiply the learning rate by `factor`, relaxing toward 1.0 by `decay` per step
+    ///
+    /// `factor` must be positive and `decay` in [0, 1); invalid values are ignored.
+    pub fn set_modulation(&self, factor: f32, decay: f32) {
+        if !(factor.is_finite() && factor > 0.0 && (0.0..1.0).contains(&decay)) {
+            return;
+        }
+        *self.modulation.write() = Modulation { factor, decay };
+    }
+
+    /// Boost learning after a surprising outcome (CuriosityScore::surprise)
+    ///
+    /// Never lowers a boost that is still active. Returns the adaptive factor.
+    pub fn modulate_surprise(&self, surprise: f32) -> f32 {
+        let (gain, decay) = {
+            let config = self.config.read();
+            (config.surprise_gain, config.modulation_decay)
+        };
+        let factor = 1.0 + gain * surprise.max(0.0);
+        if factor > self.adaptive_factor() {
+            self.set_modulation(factor, decay);
+        }
+        self.adaptive_factor()
+    }
+
+    /// Start tracking an edge (weight clamped to [0, 1])
+    pub fn add_edge(&self, edge_id: EdgeId, weight: f32) {
+        let threshold = self.config.read().initial_threshold;
+        let mut table = self.table.write();
+        table.insert(edge_id, weight.clamp(0.0, 1.0), threshold);
+        let slot = table.slot(edge_id);
+        self.journal_slots(&table, slot);
+    }
+
+    /// Stop tracking an edge
+    pub fn remove_edge(&self, edge_id: EdgeId) -> bool {
+        let mut table = self.table.write();
+        let removed = table.remove(edge_id);
+        if removed {
+            self.journal_removed(&[edge_id]);
+        }
+        removed
+    }
+
+    pub fn weight(&self, edge_id: EdgeId) -> Option<f32> {
+        self.table.read().weight(edge_id)
+    }
+
+    pub fn trace(&self, edge_id: EdgeId) -> Option<f32> {
+        self.table.read().trace(edge_id)
+    }
+
+    pub fn threshold(&self, edge_id: EdgeId) -> Option<f32> {
+        self.table.read().threshold(edge_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.table.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.table.read().is_empty()
+    }
+
+    /// Live `(edge_id, weight)` pairs
+    pub fn weights(&self) -> Vec<(EdgeId, f32)> {
+        self.table.read().iter().collect()
+    }
+
+    /// Apply one BCM update from pre/post activity; returns the new weight
+    ///
+    /// Nothing is learned while learning is frozen (see `SafeMode::learning_frozen`).
+    pub fn learn(&self, edge_id: EdgeId, pre: f32, post: f32) -> Option<f32> {
+        if self.safe_mode.learning_frozen() {
+            return None;
+        }
+        let config = self.modulated_config();
+        let mut table = self.table.write();
+        let slot = table.slot(edge_id)?;
+        let weight = Self::update_slot(&mut table, slot, pre, post, &config);
+        self.journal_slots(&table, [slot]);
+        drop(table);
+
+        self.counters.write().updates += 1;
+        Some(weight)
+    }
+
+    /// Apply `(edge_id, pre, post)` updates under one lock; unknown edges are skipped
+    ///
+    /// Returns the number of edges updated.
+    pub fn learn_batch(&self, updates: &[(EdgeId, f32, f32)]) -> usize {
+        if self.safe_mode.learning_frozen() {
+            return 0;
+        }
+        let config = self.modulated_config();
+        let mut table = self.table.write();
+        let mut updated = Vec::new();
+        for &(edge_id, pre, post) in updates {
+            if let Some(slot) = table.slot(edge_id) {
+                Self::update_slot(&mut table, slot, pre, post, &config);
+                updated.push(slot);
+            }
+        }
+        let applied = updated.len();
+        self.journal_slots(&table, updated);
+        drop(table);
+
+        self.counters.write().updates += applied as u64;
+        applied
+    }
+
+    fn update_slot(table: &mut WeightTable, slot: usize, pre: f32, post: f32, config: &LearnerConfig) -> f32 {
+        let threshold = &mut table.thresholds[slot];
+        *threshold += config.bcm_rate * (post * post - *threshold);
+
+        let delta = config.learning_rate * pre * post * (post - *threshold);
+        let weight = &mut table.weights[slot];
+        *weight = (*weight + delta).clamp(0.0, 1.0);
+
+        let trace = &mut table.traces[slot];
+        *trace = (*trace + pre * post).min(1.0);
+
+        table.weights[slot]
+    }
+
+    /// Advance one time step: decay every trace by γλ and relax the modulation
+    ///
+    /// Traces below `TRACE_EPSILON` are cleared. Returns the number of edges
+    /// still traced.
+    pub fn step(&self) -> usize {
+        let factor = self.config.read().gamma * self.lambda();
+        {
+            let mut modulation = self.modulation.write();
+            modulation.factor = 1.0 + (modulation.factor - 1.0) * modulation.decay;
+            if (modulation.factor - 1.0).abs() < TRACE_EPSILON {
+                *modulation = Modulation::default();
+            }
+        }
+        let mut table = self.table.write();
+        let mut traced = 0;
+        for trace in table.traces.iter_mut() {
+            *trace *= factor;
+            if *trace < TRACE_EPSILON {
+                *trace = 0.0;
+            } else {
+                traced += 1;
+            }
+        }
+        traced
+    }
+
+    /// Step once per event written to an ExperienceStream
+    ///
+    /// Events dropped by a lagging receiver still count as time steps.
+    pub fn spawn_stepper(self: Arc<Self>, mut events: broadcast::Receiver<ExperienceEvent>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(_) => {
+                        self.step();
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        for _ in 0..skipped {
+                            self.step();
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// Credit a reward (or TD error) to all traced edges: `w += η · r · e`
+    ///
+    /// Returns the number of edges updated.
+    pub fn apply_reward(&self, reward: f32) -> usize {
+        if self.safe_mode.learning_frozen() {
+            return 0;
+        }
+        let learning_rate = self.modulated_config().learning_rate;
+        let mut updated = Vec::new();
+        {
+            let mut guard = self.table.write();
+            let table = &mut *guard;
+            for (slot, ((weight, &trace), &alive)) in
+                table.weights.iter_mut().zip(&table.traces).zip(&table.alive).enumerate()
+            {
+                if alive && trace > 0.0 {
+                    *weight = (*weight + learning_rate * reward * trace).clamp(0.0, 1.0);
+                    updated.push(slot);
+                }
+            }
+            self.journal_slots(table, updated.iter().copied());
+        }
+
+        self.counters.write().rewards += 1;
+        updated.len()
+    }
+
+    /// Tombstone every edge whose weight fell below `prune_threshold`
+    ///
+    /// Returns the pruned edge IDs (callers remove them from the Graph).
+    pub fn prune_dead_connections(&self) -> Vec<EdgeId> {
+        let threshold = self.config.read().prune_threshold;
+        let mut table = self.table.write();
+        let dead: Vec<EdgeId> = (0..table.slots())
+            .filter(|&slot| table.alive[slot] && table.weights[slot] < threshold)
+            .map(|slot| table.edge_ids[slot])
+            .collect();
+        for &edge_id in &dead {
+            table.remove(edge_id);
+        }
+        self.journal_removed(&dead);
+        drop(table);
+
+        self.counters.write().pruned += dead.len() as u64;
+        dead
+    }
+
+    /// Reclaim dead slots now; returns the number reclaimed
+    pub fn compact(&self) -> usize {
+        let reclaimed = self.table.write().compact();
+        if reclaimed > 0 {
+            self.counters.write().compactions += 1;
+            tracing::debug!(reclaimed, "Learner weight table compacted");
+        }
+        reclaimed
+    }
+
+    /// Compact if the dead fraction exceeds `compaction_ratio`
+    pub fn maybe_compact(&self) -> Option<usize> {
+        let ratio = self.config.read().compaction_ratio;
+        if self.table.read().dead_ratio() < ratio {
+            return None;
+        }
+        Some(self.compact())
+    }
+
+    /// Run `maybe_compact` every `interval`
+    pub fn spawn_compaction(self: Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                self.maybe_compact();
+            }
+        })
+    }
+
+    pub fn stats(&self) -> LearnerStats {
+        let table = self.table.read();
+        let counters = self.counters.read();
+        LearnerStats {
+            edges: table.len(),
+            slots: table.slots(),
+            dead_slots: table.dead_slots(),
+            updates: counters.updates,
+            rewards: counters.rewards,
+            pruned: counters.pruned,
+            compactions: counters.compactions,
+            adaptive_factor: self.adaptive_factor(),
+        }
+    }
+}
+
+// ============================================================================
+// ConnectionV3 sync
+// ============================================================================
+
+impl Learner {
+    /// Write weights into the `confidence` of the connection with the same ID
+    ///
+    /// Immutable connections are left untouched. Returns the number of
+    /// connections changed.
+    pub fn sync_to_storage(&self, storage: &RuntimeStorage) -> usize {
+        let table = self.table.read();
+        storage.modify_connections(|id, connection| match table.weight(id) {
+            Some(weight) => connection.set_weight(weight),
+            None => false,
+        })
+    }
+
+    /// Replace all learning state with the stored connection confidences
+    ///
+    /// Traces restart at zero and thresholds at `initial_threshold`.
+    /// Returns the number of edges loaded.
+    pub fn load_from_storage(&self, storage: &RuntimeStorage) -> usize {
+        let threshold = self.config.read().initial_threshold;
+        let mut table = WeightTable::new();
+        for (id, connection) in storage.connections() {
+            table.insert(id, connection.weight(), threshold);
+        }
+        let loaded = table.len();
+        *self.table.write() = table;
+        loaded
+    }
+}
+
+/// Checkpoint hook that syncs Learner weights into RuntimeStorage first
+pub struct LearnerSyncHook {
+    learner: Arc<Learner>,
+    storage: Arc<RuntimeStorage>,
+}
+
+impl LearnerSyncHook {
+    pub fn new(learner: Arc<Learner>, storage: Arc<RuntimeStorage>) -> Self {
+        Self { learner, storage }
+    }
+}
+
+#[async_trait]
+impl CheckpointHook for LearnerSyncHook {
+    fn name(&self) -> &'static str {
+        "learner_sync"
+    }
+
+    async fn before_checkpoint(&self) -> Result<(), String> {
+        let written = self.learner.sync_to_storage(&self.storage);
+        tracing::debug!(written, "Learner weights synced to connections");
+        Ok(())
+    }
+}
+
+impl Default for Learner {
+    fn default() -> Self {
+        Self::new(LearnerConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bcm_update() {
+        let learner = Learner::default();
+        learner.add_edge(1, 0.5);
+
+        // Post activity above the threshold potentiates, below depresses
+        let up = learner.learn(1, 1.0, 1.0).unwrap();
+        assert!(up > 0.5);
+        assert!(learner.threshold(1).unwrap() > 0.25);
+        assert!(learner.trace(1).unwrap() > 0.0);
+        let down = learner.learn(1, 1.0, 0.1).unwrap();
+        assert!(down < up);
+
+        assert!(learner.learn(2, 1.0, 1.0).is_none());
+        assert_eq!(learner.learn_batch(&[(1, 1.0, 1.0), (2, 1.0, 1.0)]), 1);
+        assert_eq!(learner.stats().updates, 3);
+    }
+
+    #[test]
+    fn test_delayed_reward_credit() {
+        let learner = Learner::default();
+        for edge_id in 1..=3 {
+            learner.add_edge(edge_id, 0.5);
+        }
+
+        // Edge 1 fires 4 steps before the reward, edge 2 one step before, edge 3 never
+        learner.learn(1, 1.0, 1.0);
+        for t in 0..4 {
+            if t == 3 {
+                learner.learn(2, 1.0, 1.0);
+            }
+            learner.step();
+        }
+        let factor = 0.95f32 * 0.8;
+        assert!((learner.trace(1).unwrap() - factor.powi(4)).abs() < 1e-5);
+        assert!((learner.trace(2).unwrap() - factor).abs() < 1e-5);
+        assert_eq!(learner.trace(3), Some(0.0));
+
+        let before: Vec<f32> = (1..=3).map(|edge_id| learner.weight(edge_id).unwrap()).collect();
+        assert_eq!(learner.apply_reward(1.0), 2);
+        let gain = |edge_id: u64| learner.weight(edge_id).unwrap() - before[edge_id as usize - 1];
+        assert!(gain(2) > gain(1) && gain(1) > 0.0);
+        assert!((gain(1) / gain(2) - factor.powi(3)).abs() < 1e-3);
+        assert_eq!(gain(3), 0.0);
+        assert_eq!(learner.stats().rewards, 1);
+
+        // λ = 0: a reward one step late credits nothing
+        let greedy = Learner::default();
+        greedy
+            .update_learning_config(&LearningConfig { lambda: 0.0, ..Default::default() })
+            .unwrap();
+        greedy.add_edge(1, 0.5);
+        greedy.learn(1, 1.0, 1.0);
+        greedy.step();
+        assert_eq!(greedy.apply_reward(1.0), 0);
+    }
+
+    #[test]
+    fn test_surprise_modulation() {
+        let learner = Learner::default();
+        let calm = Learner::default();
+        for l in [&learner, &calm] {
+            l.add_edge(1, 0.5);
+        }
+
+        assert_eq!(learner.modulate_surprise(1.0), 3.0);
+        // A weaker surprise doesn't cut an active boost short
+        assert_eq!(learner.modulate_surprise(0.1), 3.0);
+        let boosted = learner.learn(1, 1.0, 1.0).unwrap() - 0.5;
+        let plain = calm.learn(1, 1.0, 1.0).unwrap() - 0.5;
+        assert!((boosted / plain - 3.0).abs() < 1e-3);
+
+        learner.step();
+        assert!((learner.adaptive_factor() - 2.6).abs() < 1e-5);
+        for _ in 0..50 {
+            learner.step();
+        }
+        assert_eq!(learner.adaptive_factor(), 1.0);
+
+        learner.set_modulation(-1.0, 0.5);
+        assert_eq!(learner.adaptive_factor(), 1.0);
+    }
+
+    #[test]
+    fn test_prune_and_compact() {
+        let learner = Learner::new(LearnerConfig {
+            compaction_ratio: 0.5,
+            ..Default::default()
+        });
+        for edge_id in 0..10 {
+            learner.add_edge(edge_id, if edge_id % 3 == 0 { 0.0 } else { 0.5 });
+        }
+
+        let mut pruned = learner.prune_dead_connections();
+        pruned.sort_unstable();
+        assert_eq!(pruned, vec![0, 3, 6, 9]);
+        assert_eq!(learner.len(), 6);
+        assert_eq!(learner.stats().dead_slots, 4);
+        assert!(learner.weight(3).is_none());
+
+        // 4/10 dead is under the ratio
+        assert_eq!(learner.maybe_compact(), None);
+        learner.remove_edge(1);
+        assert_eq!(learner.maybe_compact(), Some(5));
+
+        let stats = learner.stats();
+        assert_eq!((stats.edges, stats.slots, stats.dead_slots), (5, 5, 0));
+        assert_eq!(learner.weight(8), Some(0.5));
+        learner.add_edge(11, 0.7);
+        assert_eq!(learner.weight(11), Some(0.7));
+    }
+
+    #[test]
+    fn test_storage_sync() {
+        use crate::connection_v3::{ConnectionMutability, ConnectionV3};
+
+        let storage = RuntimeStorage::new();
+        let learnable = storage.create_connection(ConnectionV3::new(1, 2));
+        let mut fixed = ConnectionV3::new(2, 3);
+        fixed.mutability = ConnectionMutability::Immutable as u8;
+        let fixed = storage.create_connection(fixed);
+
+        let learner = Learner::default();
+        assert_eq!(learner.load_from_storage(&storage), 2);
+        let initial = learner.weight(learnable).unwrap();
+        assert_eq!(initial, storage.get_connection(learnable).unwrap().weight());
+
+        learner.add_edge(learnable, 0.9);
+        learner.add_edge(fixed, 0.9);
+        assert_eq!(learner.sync_to_storage(&storage), 1);
+        assert_eq!(storage.get_connection(learnable).unwrap().confidence, 230);
+        assert_ne!(storage.get_connection(fixed).unwrap().confidence, 230);
+        // Unchanged after quantization: nothing to write
+        assert_eq!(learner.sync_to_storage(&storage), 0);
+
+        let restarted = Learner::default();
+        restarted.load_from_storage(&storage);
+        assert!((restarted.weight(learnable).unwrap() - 0.9).abs() < 1.0 / 255.0);
+    }
+}
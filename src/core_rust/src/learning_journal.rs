@@ -0,0 +1,588 @@
+// NeuroGraph OS - Learning Journal v1.0
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// Crash recovery journal for in-flight learning updates.
+//
+// # Architecture
+//
+// Built on the WAL entry format (see `wal.rs`). Every ConnectionV3 mutation
+// in RuntimeStorage and every learner weight commit is appended BEFORE it is
+// applied. On startup `LearningJournal::recover` replays it on top of the
+// last checkpoint and attaches it to RuntimeStorage and the Learner; call
+// `truncate` after writing a checkpoint that covers its records.
+//
+// ## Records
+//
+// - ConnectionUpdated (0x03): connection_id u64 + ConnectionV3 (64 bytes);
+//   the header's format version byte records the ConnectionV3 layout, and
+//   older layouts are upgraded through `migration::MIGRATIONS` on replay
+// - ConnectionRemoved (0x05): connection_id u64
+// - WeightCommit (0x06): key_len u32 + key (UTF-8) + count u32 + f32 weights
+//
+// ## Durability
+//
+// Entries reach the OS page cache on every append, so they survive a process
+// crash; `sync_every` bounds how many may be lost on power failure. A panic
+// hook (see `panic_handler::register_flush_hook`) fsyncs the journal when the
+// process panics.
+//
+// ## Recovery
+//
+// A crash mid-append leaves a torn tail. Replay stops at the first incomplete
+// or corrupt entry and reports it; everything before it is applied.
+
+use crate::connection_v3::ConnectionV3;
+use crate::learner::{Learner, JOURNAL_KEY_PREFIX};
+use crate::migration::{BinaryFormat, MIGRATIONS};
+use crate::runtime_storage::RuntimeStorage;
+use crate::wal::{WalEntry, WalEntryType, WalError, WalReader, WalStats, WalWriter};
+use parking_lot::Mutex;
+use std::fs::OpenOptions;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Journal configuration
+#[derive(Debug, Clone)]
+pub struct JournalConfig {
+    /// Journal file path
+    pub path: PathBuf,
+
+    /// fsync after this many records (0 = only on explicit flush)
+    pub sync_every: usize,
+}
+
+impl Default for JournalConfig {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("learning.journal"),
+            sync_every: 64,
+        }
+    }
+}
+
+impl JournalConfig {
+    /// Validate configuration
+    pub fn validate(&self) -> Result<(), String> {
+        if self.path.as_os_str().is_empty() {
+            return Err("path must not be empty".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// A single journaled learning update
+#[derive(Debug, Clone)]
+pub enum JournalRecord {
+    /// Connection created or updated
+    ConnectionUpdated { id: u64, connection: ConnectionV3 },
+    /// Connection deleted
+    ConnectionRemoved { id: u64 },
+    /// Learner weights committed under a key
+    WeightCommit { key: String, weights: Vec<f32> },
+}
+
+impl JournalRecord {
+    fn to_entry(&self) -> WalEntry {
+        match self {
+            JournalRecord::ConnectionUpdated { id, connection } => {
+                let mut payload = Vec::with_capacity(8 + 64);
+                payload.extend_from_slice(&id.to_le_bytes());
+                payload.extend_from_slice(&connection.to_bytes());
+                let version = BinaryFormat::ConnectionV3.current_version() as u8;
+                WalEntry::with_format_version(WalEntryType::ConnectionUpdated, version, payload)
+            }
+            JournalRecord::ConnectionRemoved { id } => {
+                WalEntry::new(WalEntryType::ConnectionRemoved, id.to_le_bytes().to_vec())
+            }
+            JournalRecord::WeightCommit { key, weights } => {
+                let mut payload = Vec::with_capacity(8 + key.len() + weights.len() * 4);
+                payload.extend_from_slice(&(key.len() as u32).to_le_bytes());
+                payload.extend_from_slice(key.as_bytes());
+                payload.extend_from_slice(&(weights.len() as u32).to_le_bytes());
+                for w in weights {
+                    payload.extend_from_slice(&w.to_le_bytes());
+                }
+                WalEntry::new(WalEntryType::WeightCommit, payload)
+            }
+        }
+    }
+
+    /// Decode a WAL entry; `None` for entry types the journal doesn't own
+    fn from_entry(entry: &WalEntry) -> Result<Option<Self>, WalError> {
+        let p = &entry.payload;
+        let u32_at = |at: usize| -> Result<u32, WalError> {
+            p.get(at..at + 4)
+                .map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+                .ok_or(WalError::CorruptedFile)
+        };
+
+        let record = match entry.header.entry_type {
+            WalEntryType::ConnectionUpdated => {
+                let format = BinaryFormat::ConnectionV3;
+                let version = match entry.header.format_version() {
+                    0 => format.baseline_version(),
+                    v => v as u32,
+                };
+                let registry = MIGRATIONS.read();
+                if p.len() != 8 + registry.record_size(format, version)? {
+                    return Err(WalError::CorruptedFile);
+                }
+                let id = u64::from_le_bytes(p[0..8].try_into().unwrap());
+                let connection = ConnectionV3::from_bytes(&registry.upgrade(format, version, &p[8..])?);
+                JournalRecord::ConnectionUpdated { id, connection }
+            }
+            WalEntryType::ConnectionRemoved => {
+                let id = p.as_slice().try_into().map_err(|_| WalError::CorruptedFile)?;
+                JournalRecord::ConnectionRemoved { id: u64::from_le_bytes(id) }
+            }
+            WalEntryType::WeightCommit => {
+                let key_len = u32_at(0)? as usize;
+                let key = p
+                    .get(4..4 + key_len)
+                    .and_then(|b| String::from_utf8(b.to_vec()).ok())
+                    .ok_or(WalError::CorruptedFile)?;
+                let count = u32_at(4 + key_len)? as usize;
+                let start = 8 + key_len;
+                if p.len() != start + count * 4 {
+                    return Err(WalError::CorruptedFile);
+                }
+                let weights = p[start..]
+                    .chunks_exact(4)
+                    .map(|b| f32::from_le_bytes(b.try_into().unwrap()))
+                    .collect();
+                JournalRecord::WeightCommit { key, weights }
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(record))
+    }
+}
+
+/// Result of a journal replay
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplayReport {
+    /// Records applied
+    pub applied: usize,
+
+    /// Entries of other types that were skipped
+    pub skipped: usize,
+
+    /// Replay stopped at an incomplete or corrupt entry
+    pub torn_tail: bool,
+}
+
+/// Crash recovery journal for connection mutations and weight commits
+pub struct LearningJournal {
+    config: JournalConfig,
+    writer: Mutex<WalWriter>,
+    unsynced: AtomicUsize,
+}
+
+impl LearningJournal {
+    /// Open (or create) the journal for appending
+    pub fn open(config: JournalConfig) -> Result<Self, WalError> {
+        let writer = WalWriter::new(&config.path)?;
+        Ok(Self {
+            config,
+            writer: Mutex::new(writer),
+            unsynced: AtomicUsize::new(0),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.config.path
+    }
+
+    /// Append a record
+    pub fn record(&self, record: &JournalRecord) -> Result<(), WalError> {
+        let entry = record.to_entry();
+        let mut writer = self.writer.lock();
+        writer.append(&entry)?;
+
+        let unsynced = self.unsynced.fetch_add(1, Ordering::SeqCst) + 1;
+        if self.config.sync_every > 0 && unsynced >= self.config.sync_every {
+            writer.sync()?;
+            self.unsynced.store(0, Ordering::SeqCst);
+        }
+        Ok(())
+    }
+
+    /// Journal a created or updated connection
+    pub fn record_connection(&self, id: u64, connection: &ConnectionV3) -> Result<(), WalError> {
+        self.record(&JournalRecord::ConnectionUpdated { id, connection: *connection })
+    }
+
+    /// Journal a deleted connection
+    pub fn record_connection_removed(&self, id: u64) -> Result<(), WalError> {
+        self.record(&JournalRecord::ConnectionRemoved { id })
+    }
+
+    /// Journal a learner weight commit
+    pub fn record_weights(&self, key: &str, weights: &[f32]) -> Result<(), WalError> {
+        self.record(&JournalRecord::WeightCommit {
+            key: key.to_string(),
+            weights: weights.to_vec(),
+        })
+    }
+
+    /// fsync all appended records
+    pub fn flush(&self) -> Result<(), WalError> {
+        self.writer.lock().sync()?;
+        self.unsynced.store(0, Ordering::SeqCst);
+        Ok(())
+    }
+
+    /// Non-blocking flush for the panic hook
+    ///
+    /// Returns false if the writer is held (e.g. by the panicking thread).
+    pub fn try_flush(&self) -> bool {
+        match self.writer.try_lock() {
+            Some(mut writer) => writer.sync().is_ok(),
+            None => false,
+        }
+    }
+
+    /// Discard all records (call after a checkpoint covers them)
+    pub fn truncate(&self) -> Result<(), WalError> {
+        let mut writer = self.writer.lock();
+        let file = OpenOptions::new().write(true).open(&self.config.path)?;
+        file.set_len(0)?;
+        file.sync_all()?;
+        *writer = WalWriter::new(&self.config.path)?;
+        self.unsynced.store(0, Ordering::SeqCst);
+        info!("Learning journal truncated: {}", self.config.path.display());
+        Ok(())
+    }
+
+    /// Writer statistics
+    pub fn stats(&self) -> WalStats {
+        self.writer.lock().stats()
+    }
+
+    /// fsync this journal from the global panic hook
+    ///
+    /// Registered under the journal path, so reopening the same file
+    /// replaces the previous hook.
+    pub fn install_panic_flush(self: &Arc<Self>) {
+        let journal = Arc::downgrade(self);
+        crate::panic_handler::register_flush_hook(&self.hook_name(), move || {
+            if let Some(journal) = journal.upgrade() {
+                journal.try_flush();
+            }
+        });
+    }
+
+    /// Remove the panic hook installed by [`install_panic_flush`](Self::install_panic_flush)
+    pub fn remove_panic_flush(&self) {
+        crate::panic_handler::unregister_flush_hook(&self.hook_name());
+    }
+
+    fn hook_name(&self) -> String {
+        format!("learning_journal:{}", self.config.path.display())
+    }
+
+    /// Replay a journal file, calling `apply` for every record in order
+    ///
+    /// A missing file replays nothing.
+    pub fn replay<P, F>(path: P, mut apply: F) -> Result<ReplayReport, WalError>
+    where
+        P: AsRef<Path>,
+        F: FnMut(JournalRecord) -> Result<(), WalError>,
+    {
+        let mut report = ReplayReport::default();
+        if !path.as_ref().exists() {
+            return Ok(report);
+        }
+
+        let mut reader = WalReader::new(&path)?;
+        loop {
+            let entry = match reader.read_entry() {
+                Ok(Some(entry)) => entry,
+                Ok(None) => break,
+                Err(WalError::IoError(e)) if e.kind() != std::io::ErrorKind::UnexpectedEof => {
+                    return Err(WalError::IoError(e));
+                }
+                Err(e) => {
+                    warn!(error = %e, applied = report.applied, "Learning journal has a torn tail");
+                    report.torn_tail = true;
+                    break;
+                }
+            };
+
+            match JournalRecord::from_entry(&entry) {
+                Ok(Some(record)) => {
+                    apply(record)?;
+                    report.applied += 1;
+                    crate::metrics::WAL_ENTRIES_REPLAYED.inc();
+                }
+                Ok(None) => report.skipped += 1,
+                Err(e @ WalError::Migration(_)) => return Err(e),
+                Err(_) => {
+                    report.torn_tail = true;
+                    break;
+                }
+            }
+        }
+
+        info!(
+            applied = report.applied,
+            skipped = report.skipped,
+            "Learning journal replayed: {}",
+            path.as_ref().display()
+        );
+        Ok(report)
+    }
+
+    /// Replay connection records into storage; weight commits go to `on_weights`
+    ///
+    /// Run before attaching the journal to storage, otherwise replayed
+    /// mutations are journaled again.
+    pub fn replay_into<P, F>(path: P, storage: &RuntimeStorage, mut on_weights: F) -> Result<ReplayReport, WalError>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&str, &[f32]),
+    {
+        Self::replay(path, |record| {
+            match record {
+                JournalRecord::ConnectionUpdated { id, connection } => {
+                    storage.put_connection(id, connection);
+                }
+                JournalRecord::ConnectionRemoved { id } => {
+                    storage.delete_connection(id);
+                }
+                JournalRecord::WeightCommit { key, weights } => on_weights(&key, &weights),
+            }
+            Ok(())
+        })
+    }
+
+    /// Startup recovery: replay the journal, then journal everything from here on
+    ///
+    /// Call once after restoring the last checkpoint and before serving.
+    /// Replays `config.path` into `storage` and `learner`, reopens it for
+    /// appending, attaches it to both and registers the panic flush. After a
+    /// torn tail the journal is rewritten from the recovered state, so records
+    /// appended from now on stay reachable by the next replay.
+    pub fn recover(
+        config: JournalConfig,
+        storage: &RuntimeStorage,
+        learner: Option<&Learner>,
+    ) -> Result<(Arc<Self>, ReplayReport), WalError> {
+        let report = Self::replay_into(&config.path, storage, |key, weights| {
+            if let Some(learner) = learner {
+                if !learner.replay_weights(key, weights) {
+                    warn!(key, "Skipping unknown weight commit in learning journal");
+                }
+            }
+        })?;
+
+        let journal = Arc::new(Self::open(config)?);
+        if report.torn_tail {
+            journal.truncate()?;
+            for (id, connection) in storage.connections() {
+                journal.record_connection(id, &connection)?;
+            }
+            if let Some(learner) = learner {
+                for (edge_id, weight) in learner.weights() {
+                    let threshold = learner.threshold(edge_id).unwrap_or_default();
+                    journal.record_weights(&format!("{}{}", JOURNAL_KEY_PREFIX, edge_id), &[weight, threshold])?;
+                }
+            }
+            journal.flush()?;
+        }
+
+        storage.set_journal(journal.clone());
+        if let Some(learner) = learner {
+            learner.set_journal(journal.clone());
+        }
+        journal.install_panic_flush();
+        Ok((journal, report))
+    }
+}
+
+impl Drop for LearningJournal {
+    fn drop(&mut self) {
+        let _ = self.writer.get_mut().sync();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    fn config(dir: &Path) -> JournalConfig {
+        JournalConfig {
+            path: dir.join("learning.journal"),
+            sync_every: 0,
+        }
+    }
+
+    #[test]
+    fn test_storage_mutations_replay() {
+        let dir = tempdir().unwrap();
+        let config = config(dir.path());
+
+        let (kept, removed, updated) = {
+            let storage = RuntimeStorage::new();
+            storage.set_journal(Arc::new(LearningJournal::open(config.clone()).unwrap()));
+
+            let kept = storage.create_connection(ConnectionV3::new(1, 2));
+            let removed = storage.create_connection(ConnectionV3::new(2, 3));
+            let mut conn = ConnectionV3::new(3, 4);
+            let updated = storage.create_connection(conn);
+            conn.pull_strength = 2.5;
+            storage.update_connection(updated, conn).unwrap();
+            storage.delete_connection(removed);
+            storage.journal().unwrap().record_weights("policy", &[0.1, -0.2]).unwrap();
+            (kept, removed, updated)
+        };
+
+        // "Restart": fresh storage, replay, then attach
+        let storage = RuntimeStorage::new();
+        let mut weights = Vec::new();
+        let report = LearningJournal::replay_into(&config.path, &storage, |key, w| {
+            weights.push((key.to_string(), w.to_vec()))
+        })
+        .unwrap();
+
+        assert_eq!(report.applied, 6);
+        assert!(!report.torn_tail);
+        assert_eq!(storage.count_connections(), 2);
+        assert!(storage.get_connection(kept).is_some());
+        assert!(storage.get_connection(removed).is_none());
+        let pull_strength = storage.get_connection(updated).unwrap().pull_strength;
+        assert_eq!(pull_strength, 2.5);
+        assert_eq!(weights, vec![("policy".to_string(), vec![0.1, -0.2])]);
+
+        // New connections don't reuse replayed IDs
+        assert!(storage.create_connection(ConnectionV3::new(5, 6)) > updated);
+    }
+
+    #[test]
+    fn test_replay_torn_tail() {
+        let dir = tempdir().unwrap();
+        let config = config(dir.path());
+
+        {
+            let journal = LearningJournal::open(config.clone()).unwrap();
+            journal.record_connection(1, &ConnectionV3::new(1, 2)).unwrap();
+            journal.record_weights("w", &[1.0]).unwrap();
+        }
+
+        // Simulate a crash mid-append
+        let bytes = JournalRecord::ConnectionRemoved { id: 1 }.to_entry().to_bytes();
+        let mut file = OpenOptions::new().append(true).open(&config.path).unwrap();
+        file.write_all(&bytes[..bytes.len() - 3]).unwrap();
+
+        let mut records = Vec::new();
+        let report = LearningJournal::replay(&config.path, |r| {
+            records.push(r);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(report.applied, 2);
+        assert!(report.torn_tail);
+        assert!(matches!(records[1], JournalRecord::WeightCommit { .. }));
+    }
+
+    #[test]
+    fn test_recover_learner_after_crash() {
+        let dir = tempdir().unwrap();
+        let config = config(dir.path());
+
+        let edge = {
+            let storage = RuntimeStorage::new();
+            let learner = Learner::default();
+            LearningJournal::recover(config.clone(), &storage, Some(&learner)).unwrap();
+            let edge = storage.create_connection(ConnectionV3::new(1, 2));
+            learner.add_edge(edge, 0.5);
+            learner.add_edge(99, 0.5);
+            learner.learn(edge, 1.0, 1.0);
+            learner.remove_edge(99);
+            edge
+        };
+
+        // Torn final record: everything before it comes back
+        let bytes = JournalRecord::ConnectionRemoved { id: edge }.to_entry().to_bytes();
+        let mut file = OpenOptions::new().append(true).open(&config.path).unwrap();
+        file.write_all(&bytes[..bytes.len() - 3]).unwrap();
+
+        let storage = RuntimeStorage::new();
+        let learner = Learner::default();
+        let (journal, report) = LearningJournal::recover(config.clone(), &storage, Some(&learner)).unwrap();
+        assert!(report.torn_tail);
+        assert!(storage.get_connection(edge).is_some());
+        assert!(learner.weight(edge).unwrap() > 0.5);
+        assert!(learner.weight(99).is_none());
+
+        // The rewritten journal is whole again and keeps recording
+        learner.add_edge(7, 0.3);
+        drop(journal);
+        let replayed = LearningJournal::replay(&config.path, |_| Ok(())).unwrap();
+        assert!(!replayed.torn_tail);
+        let restarted = Learner::default();
+        LearningJournal::recover(config, &RuntimeStorage::new(), Some(&restarted)).unwrap();
+        assert_eq!(restarted.weight(7), Some(0.3));
+        assert_eq!(restarted.weight(edge), learner.weight(edge));
+    }
+
+    #[test]
+    fn test_replay_versioned_connections() {
+        let dir = tempdir().unwrap();
+        let config = config(dir.path());
+        let mut payload = 9u64.to_le_bytes().to_vec();
+        payload.extend_from_slice(&ConnectionV3::new(1, 2).to_bytes());
+
+        // Unversioned entries (older journals) are read at the baseline layout
+        {
+            let journal = LearningJournal::open(config.clone()).unwrap();
+            journal.writer.lock().append(&WalEntry::new(WalEntryType::ConnectionUpdated, payload.clone())).unwrap();
+            journal.record_connection(10, &ConnectionV3::new(3, 4)).unwrap();
+        }
+        let report = LearningJournal::replay(&config.path, |_| Ok(())).unwrap();
+        assert_eq!((report.applied, report.torn_tail), (2, false));
+
+        // Entries from a newer build are an explicit error, not a torn tail
+        {
+            let journal = LearningJournal::open(config.clone()).unwrap();
+            let newer = BinaryFormat::ConnectionV3.current_version() as u8 + 1;
+            let entry = WalEntry::with_format_version(WalEntryType::ConnectionUpdated, newer, payload);
+            journal.writer.lock().append(&entry).unwrap();
+        }
+        assert!(matches!(
+            LearningJournal::replay(&config.path, |_| Ok(())),
+            Err(WalError::Migration(_))
+        ));
+    }
+
+    #[test]
+    fn test_truncate_and_missing_file() {
+        let dir = tempdir().unwrap();
+        let config = config(dir.path());
+
+        let report = LearningJournal::replay(&config.path, |_| Ok(())).unwrap();
+        assert_eq!(report, ReplayReport::default());
+
+        let journal = Arc::new(LearningJournal::open(config.clone()).unwrap());
+        journal.install_panic_flush();
+        journal.record_connection_removed(7).unwrap();
+        assert!(journal.try_flush());
+        journal.truncate().unwrap();
+        journal.record_connection_removed(8).unwrap();
+        journal.remove_panic_flush();
+
+        let mut ids = Vec::new();
+        LearningJournal::replay(&config.path, |r| {
+            if let JournalRecord::ConnectionRemoved { id } = r {
+                ids.push(id);
+            }
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(ids, vec![8]);
+    }
+}
@@ -32,6 +32,8 @@
 /// - Zero core dependencies (pure Rust)
 
 pub mod token;
+pub mod token_precision;
+pub mod edge_history;
 pub mod connection_v3;
 
 // Re-export Connection v3.0 types (primary API)
@@ -71,22 +73,68 @@ pub mod feedback;         // NEW: v1.0 Feedback System (v0.37.0)
 pub mod curiosity;        // NEW: v1.0 Curiosity Drive (v0.38.0)
 pub mod api;              // NEW: v1.0 REST API (v0.39.0)
 pub mod panic_handler;       // NEW: v1.0 Panic Recovery (v0.41.0)
+pub mod supervisor;          // NEW: v1.0 Task Supervisor with restart/backoff
 pub mod wal;                 // NEW: v1.0 Write-Ahead Log (v0.41.0)
 pub mod async_wal;           // NEW: v1.0 Async WAL Writer (v0.44.2)
+pub mod learning_journal;    // NEW: v1.0 Crash recovery journal for learning updates
 pub mod metrics;             // NEW: v1.0 Prometheus Metrics (v0.42.0)
 pub mod black_box;           // NEW: v1.0 Black Box Recorder (v0.42.0)
 pub mod logging_utils;       // NEW: v1.0 Logging Utilities (v0.42.0)
-pub mod tracing_otel;        // NEW: v1.0 OpenTelemetry Distributed Tracing (v0.44.0)
+#[cfg(feature = "otel")]
+pub mod tracing_otel;        // NEW: v1.0 OpenTelemetry Distributed Tracing (v0.44.0, otel feature)
+pub mod log_stream;          // NEW: v1.0 Log capture and live subscriptions
+pub mod metrics_history;     // NEW: v1.0 Metrics time series for dashboards
+pub mod background;          // NEW: v1.0 Background mode, learning pause and tray actions
+pub mod i18n;                // NEW: v1.0 Message catalogs (en, ru) for UI labels and errors
+pub mod config_file;         // NEW: v1.0 Layered TOML config with hot reload
+pub mod rng;                 // NEW: v1.0 Seeded RNG streams for reproducible runs
+pub mod learner;             // NEW: v1.0 Hebbian/BCM edge weights (struct-of-arrays table)
+pub mod consolidation;       // NEW: v1.0 Sleep-phase replay, strengthening and pruning
+pub mod decay;               // NEW: v1.0 Forgetting curves per connection category
+pub mod inference;           // NEW: v1.0 Forward chaining over logical/structural connection types
+pub mod ngql;                // NEW: v1.0 Query language over the concept graph
+pub mod terminal;            // NEW: v1.0 Terminal command interpreter
+pub mod chat_history;        // NEW: v1.0 Persistent chat conversations
+pub mod scheduler;           // NEW: v1.0 Persistent recurring intents injected through the Gateway
+pub mod anomaly;             // NEW: v1.0 EWMA anomaly detection on key system metrics
+pub mod safe_mode;           // NEW: v1.0 Operator kill switch (frozen learning, NoOp-only executors)
+pub mod resource_budget;     // NEW: v1.0 CPU/memory/executor budgets feeding EfficiencyParams
+pub mod redaction;           // NEW: v1.0 Redacted experience/checkpoint export
+pub mod encryption;          // NEW: v1.0 At-rest encryption of checkpoints, dumps and exports
+pub mod synonym_collapse;    // NEW: v1.0 Review-gated merging of near-duplicate tokens
+pub mod snapshot_diff;       // NEW: v1.0 Regression diff of two runtime snapshots
 pub mod tracing_sampling;    // NEW: v1.0 Adaptive Tracing Sampling (v0.44.3)
 pub mod runtime_storage;     // NEW: v1.0 Runtime Storage (v0.50.0)
+pub mod checkpoint;          // NEW: v1.0 Whole-system Checkpoints
+pub mod profile_bundle;      // NEW: v1.0 Signed .ngprofile bundles (CDNA + ADNA)
+pub mod replay;              // NEW: v1.0 Deterministic replay journal and state diff
+pub mod migration;           // NEW: v1.0 Cross-version migrations for packed structs
 pub mod signal_system;       // NEW: v1.1 Signal System - Event Processing (v0.53.0)
 pub mod module_id;           // NEW: v1.0 Module ID Enum (v0.63.0)
 pub mod module_registry;     // NEW: v1.0 Module Registry (v0.63.0)
+pub mod profiling;           // NEW: v1.0 Pipeline stage profiling
+pub mod ingestion;           // NEW: v1.0 Document ingestion (text → concepts → connections)
+pub mod instance;            // NEW: v1.0 Multi-tenant instances (isolated Grid/Graph/ADNA/streams)
+pub mod auth;                // NEW: v1.0 Argon2 PIN/password auth, lockout and sessions
+pub mod settings;            // NEW: v1.0 Runtime config updates with persisted overrides
+pub mod event_payload;       // NEW: v1.0 Typed event payloads with a schema registry
+pub mod correlation;         // NEW: v1.0 Correlation IDs for end-to-end interaction tracing
+pub mod sync;                // NEW: v1.0 Knowledge sync (graph deltas) between instances
+pub mod skill_pack;          // NEW: v1.0 Signed .ngskill packs of learned connections
+pub mod replica;             // NEW: v1.0 Read replicas fed by the writer's graph change feed
 
 // Python bindings v1.0 (v0.40.0) - PyO3 FFI
 #[cfg(feature = "python-bindings")]
 pub mod python;
 
+// C ABI v1.0 - extern "C" API with opaque handles (header via cbindgen)
+#[cfg(feature = "c-api")]
+pub mod capi;
+
+// Browser bindings - wasm-bindgen exports for token/grid/graph/bootstrap map
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
 // Old FFI (deprecated, will be removed in favor of python module)
 // #[cfg(feature = "python")]
 // pub mod ffi;
@@ -99,14 +147,33 @@ pub use token::{
     SCALE_FACTORS,
 };
 
+pub use edge_history::{
+    EdgeHistory,
+    EdgeHistoryConfig,
+    WeightSample,
+    WeightSource,
+};
+
+pub use token_precision::{
+    PrecisionConfig,
+    PrecisionMode,
+    PrecisionStats,
+    PrecisionStore,
+};
+
 pub use grid::{
     Grid,
     GridConfig,
+    ShardedGrid,
 };
 
 pub use graph::{
     Graph,
     GraphConfig,
+    EvictionPolicy,
+    GraphLimitStats,
+    EdgeMerge,
+    DedupReport,
     NodeId,
     EdgeId,
     Direction,
@@ -120,10 +187,23 @@ pub use graph::{
     ActivationResult,
     ActivatedNode,
 };
+pub use graph::sharded::ShardedGraph;
+pub use graph::mapped::{MappedGraph, MappedGraphError};
+pub use graph::export::{
+    ExportFormat,
+    GraphExportOptions,
+    DotOptions,
+    SubgraphView,
+    ViewEdge,
+    ViewLayout,
+    ViewNode,
+    ViewOptions,
+};
 
 pub use cdna::{
     CDNA,
     ProfileId,
+    ProfilePatch,
     ProfileState,
     CDNAFlags,
     CDNA_MAGIC,
@@ -176,6 +256,10 @@ pub use appraisers::{
     EfficiencyAppraiser,
     GoalDirectedAppraiser,
     AppraiserSet,
+    Appraisal,
+    appraise_event,
+    reappraise_window,
+    ReappraisalReport,
 };
 
 pub use experience_stream::{
@@ -194,6 +278,35 @@ pub use experience_stream::{
     ExperienceBatch,
 };
 
+pub use correlation::CorrelationId;
+pub use sync::{
+    GraphDelta, SyncConcept, SyncConnection, SyncError, SyncNode, SyncReport, SyncRound,
+    SYNC_PROTOCOL_VERSION,
+};
+#[cfg(feature = "sync")]
+pub use sync::SyncClient;
+pub use skill_pack::{
+    SkillFilter, SkillImportReport, SkillPack, SkillPackError, SkillPackPayload,
+    SKILL_PACK_EXTENSION, SKILL_PACK_FORMAT_VERSION,
+};
+pub use replica::{
+    ChangeBatch, ChangeFeed, ChangeFeedConfig, GraphChange, Replica, ReplicaError, ReplicaReport,
+    ReplicaStatus, SequencedChange, REPLICATION_PROTOCOL_VERSION,
+};
+#[cfg(feature = "sync")]
+pub use replica::ReplicaFollower;
+
+pub use event_payload::{
+    EventPayload,
+    FieldKind,
+    PayloadData,
+    PayloadError,
+    PayloadField,
+    PayloadRegistry,
+    PayloadSchema,
+    PayloadTag,
+};
+
 pub use archive::{
     ExperienceToken,
     InfoFlags,
@@ -212,7 +325,9 @@ pub use intuition_engine::{
     IntuitionEngine,
     IntuitionEngineBuilder,
     IntuitionConfig,
+    LearningConfig,
     IdentifiedPattern,
+    quantize_state,
 };
 
 pub use hybrid_learning::{
@@ -221,6 +336,13 @@ pub use hybrid_learning::{
     ProposalRouter,
     HybridLearningStats,
     HybridLearningError,
+    ConflictPolicy,
+    LearningSource,
+    DeferredConflict,
+    TokenMerge,
+    LiftConfig,
+    LiftStatus,
+    ProposalEvaluation,
     adna_to_connection_feedback,
     connection_to_adna_hint,
 };
@@ -259,18 +381,31 @@ pub use evolution_manager::{
     EvolutionConfig,
     ADNAState,
     ValidationResult,
+    ShadowConfig,
+    ShadowResult,
+    ShadowVerdict,
+    ReappraisalConfig,
+    EvolutionScheduler,
+    EvolutionTriggers,
+    EvolutionTrigger,
+    PlateauConfig,
+    SchedulerStats,
 };
 
 pub use action_executor::{
     ActionExecutor,
     ActionResult,
     ActionError,
+    CancellationToken,
 };
 
 pub use action_types::{
     ActionIntent,
     ActionType,
     DecisionSource,
+    CandidatePathway,
+    DecisionCandidate,
+    DecisionTrace,
 };
 
 pub use action_controller::{
@@ -278,11 +413,20 @@ pub use action_controller::{
     ActionControllerConfig,
     ArbiterConfig,
     ArbiterStats,
+    DecisionTraceLog,
 };
 
 pub use executors::{
     NoOpExecutor,
     MessageSenderExecutor,
+    FileExecutor,
+    FileExecutorConfig,
+};
+
+#[cfg(feature = "llm")]
+pub use executors::{
+    LlmExecutor,
+    LlmConfig,
 };
 
 // Tracing sampling exports (v0.44.3+)
@@ -302,11 +446,28 @@ pub use persistence::{
     PersistenceBackend,
     PersistenceError,
     QueryOptions,
+    RetryPolicy,
+    TieredBackend,
+    TieredStats,
+    DropPolicy,
+    WriteBehind,
+    WriteBehindConfig,
+    WriteBehindError,
+    WriteBehindStats,
+    WriteOp,
 };
 
 #[cfg(feature = "persistence")]
 pub use persistence::{
     PostgresBackend,
+    PostgresConfig,
+    PoolStats,
+};
+
+#[cfg(feature = "redis-cache")]
+pub use persistence::{
+    RedisBackend,
+    RedisConfig,
 };
 
 // Bootstrap Library v1.2
@@ -317,6 +478,45 @@ pub use bootstrap::{
     PCAModel,
     BootstrapError,
 };
+pub use bootstrap::analogy::{
+    AnalogyConfig,
+    AnalogyMode,
+    RelationPath,
+    RelationStep,
+    StepDirection,
+};
+pub use bootstrap::ranking::SearchConfig;
+pub use bootstrap::phrases::PhraseTable;
+pub use bootstrap::frequency::ENGLISH_STOP_WORDS;
+pub use bootstrap::lexicon::{
+    LexiconEntry,
+    LexiconMergeMode,
+    LexiconReport,
+    LexiconSet,
+    Modality,
+};
+pub use bootstrap::projection::{ModalityProjection, ProjectionRule};
+pub use bootstrap::grounded::{GroundedMatch, GroundedTerm};
+pub use bootstrap::relations::{
+    RelationFormat,
+    RelationRecord,
+    RelationImportConfig,
+    RelationImportReport,
+    RelationConflict,
+    RelationOutcome,
+};
+pub use bootstrap::remote::{
+    EmbeddingProvider,
+    RemoteEmbedder,
+    RemoteEmbedderConfig,
+};
+#[cfg(feature = "remote-embeddings")]
+pub use bootstrap::remote::{
+    HttpEmbeddingProvider,
+    HttpEmbeddingConfig,
+};
+#[cfg(feature = "onnx")]
+pub use bootstrap::remote::OnnxEmbeddingProvider;
 
 // Gateway v1.0
 pub use gateway::{
@@ -340,6 +540,14 @@ pub use gateway::signals::{
     ProcessedMetadata,
 };
 
+pub use gateway::commands::CommandPolicy;
+pub use gateway::temporal::TemporalContext;
+
+pub use gateway::bridge::{
+    subscribe_injector,
+    FLAG_FROM_GATEWAY,
+};
+
 pub use gateway::channels::{
     SignalReceipt,
     ResultReceiver,
@@ -355,6 +563,14 @@ pub use adapters::{
     OutputContext,
     FormattedOutput,
     OutputError,
+    OutputPipeline,
+    OutputRoute,
+    OutputFilter,
+    OutputTransform,
+    DispatchReport,
+    Redact,
+    Truncate,
+    Template,
     SignalSource as AdapterSignalSource,
     SignalType as AdapterSignalType,
 };
@@ -365,6 +581,53 @@ pub use adapters::console::{
     ConsoleConfig,
 };
 
+pub use adapters::composer::{
+    ResponseComposer,
+    ComposerConfig,
+    ComposerTemplates,
+    LabelSource,
+};
+
+pub use adapters::pipe::{
+    PipeInputAdapter,
+    PipeOutputAdapter,
+    PipeConfig,
+    PipeStats,
+};
+
+#[cfg(feature = "mqtt")]
+pub use adapters::mqtt::{
+    MqttInputAdapter,
+    MqttOutputAdapter,
+    MqttConfig,
+};
+
+#[cfg(feature = "audio")]
+pub use adapters::speech::{
+    SpeechInputAdapter,
+    SpeechConfig,
+    VadConfig,
+    VoiceActivityDetector,
+    SpeechToText,
+    CommandStt,
+    HttpStt,
+};
+
+#[cfg(feature = "tts")]
+pub use adapters::tts::{
+    TtsOutputAdapter,
+    TtsConfig,
+    TtsEngine,
+};
+
+#[cfg(feature = "onnx")]
+pub use adapters::vision::{
+    VisionInputAdapter,
+    SensorEncoder,
+    VisionConfig,
+    SensorModality,
+};
+
 // Feedback v1.0
 pub use feedback::{
     FeedbackProcessor,
@@ -392,10 +655,20 @@ pub use panic_handler::{
     catch_panic,
     catch_panic_async,
     install_panic_hook,
+    register_flush_hook,
+    unregister_flush_hook,
     PanicError,
     PanicResult,
 };
 
+// Task Supervisor v1.0
+pub use supervisor::{
+    Supervisor,
+    SupervisorConfig,
+    TaskState,
+    TaskStatus,
+};
+
 // WAL (Write-Ahead Log) v1.0
 pub use wal::{
     WalEntry,
@@ -407,9 +680,202 @@ pub use wal::{
     WalWriter,
 };
 
+// Learning Journal v1.0
+pub use learning_journal::{
+    JournalConfig,
+    JournalRecord,
+    LearningJournal,
+    ReplayReport,
+};
+
 // Runtime Storage v1.0 (v0.50.0)
 pub use runtime_storage::{
     RuntimeStorage,
+    RuntimeSnapshot,
+    MergeSummary,
     StorageError,
     StorageResult,
 };
+
+// Checkpoints v1.0
+pub use checkpoint::{
+    Checkpointable,
+    CheckpointHook,
+    CheckpointConfig,
+    CheckpointError,
+    CheckpointManager,
+    CheckpointManifest,
+    MappedGraphSection,
+    SectionEntry,
+    CHECKPOINT_FORMAT_VERSION,
+};
+
+// Auth v1.0
+pub use auth::{
+    AuthClaims,
+    AuthConfig,
+    AuthError,
+    AuthManager,
+    Role,
+    SecretKind,
+    Session,
+    UserInfo,
+};
+
+// Settings v1.0
+pub use settings::{
+    ConfigApplier,
+    ConfigSection,
+    ConfigUpdate,
+    SettingsConfig,
+    SettingsError,
+    SettingsManager,
+};
+
+// Instances v1.0
+pub use instance::{
+    Instance,
+    InstanceError,
+    InstanceFactory,
+    InstanceId,
+    InstanceRegistry,
+    DEFAULT_INSTANCE,
+    INSTANCE_METADATA_KEY,
+};
+
+// Profile bundles v1.0
+pub use profile_bundle::{
+    ProfileBundle,
+    ProfileError,
+    ProfilePayload,
+    PROFILE_EXTENSION,
+    PROFILE_FORMAT_VERSION,
+};
+
+// Binary struct migrations v1.0
+pub use migration::{
+    BinaryFormat,
+    FormatVersions,
+    MigrationError,
+    MigrationRegistry,
+    MIGRATIONS,
+};
+
+// Deterministic replay v1.0
+pub use replay::{
+    ReplayEntry,
+    ReplayError,
+    ReplayHeader,
+    ReplayJournal,
+    ReplayOptions,
+    ReplayRecorder,
+    ReplayRunReport,
+    StateDiff,
+    StateDigest,
+    REPLAY_FORMAT_VERSION,
+};
+
+// Log stream v1.0
+pub use log_stream::{
+    LogEntry, LogExportFormat, LogFilter, LogHub, LogHubLayer, LogLevel, LogSubscription,
+    LOG_HUB,
+};
+
+// Metrics history v1.0
+pub use metrics_history::{
+    MetricsHistory, MetricsHistoryConfig, Sample, Series, SeriesKind, SeriesQuery, SeriesSummary,
+    METRICS_HISTORY,
+};
+
+// Background runtime v1.0
+pub use background::{
+    TrayActionOutcome, BackgroundConfig, BackgroundError, BackgroundEvent, BackgroundRuntime,
+    BackgroundStatus, LearningGate, TrayAction, TrayMenuItem,
+};
+
+// I18n v1.0
+pub use i18n::{I18nConfig, Locale, Localize};
+
+// Config File v1.0
+pub use config_file::{
+    AdnaSection, ChangeOutcome, ConfigChange, ConfigFileError, ConfigWatcher, HotSection,
+    LayeredConfig, SettingsSection,
+};
+
+// Seeded RNG v1.0
+pub use rng::{RngConfig, RngProvider, SeededRng};
+
+// Learner v1.0
+pub use learner::{Learner, LearnerConfig, LearnerStats, LearnerSyncHook, WeightTable};
+
+// Consolidation v1.0
+pub use consolidation::{
+    ConsolidationConfig, ConsolidationStats, ConsolidationSummary, ConsolidationTrigger, Consolidator,
+};
+
+// Decay v1.0
+pub use decay::{DecayManager, DecayProfiles, DecayReport};
+
+// Inference v1.0
+pub use inference::{Derivation, InferenceConfig, InferenceEngine, InferenceRule};
+
+// NGQL v1.0
+pub use ngql::{AnalogyHit, NeighborHit, NgqlError, NgqlQuery, NgqlResult, TypeFilter};
+
+// Terminal v1.0
+pub use terminal::{
+    ArgKind, CommandError, CommandHistory, CommandOutput, CommandRegistry, CommandSpec,
+    Completion, Interpreter, ParsedArgs, TerminalContext,
+};
+
+// Chat history v1.0
+pub use chat_history::{
+    ChatHistory, ChatHistoryConfig, ChatHistoryError, ChatMessage, ChatRole, Conversation,
+    ConversationSummary, SearchHit,
+};
+
+// Scheduler v1.0
+pub use scheduler::{Recurrence, ScheduledTask, Scheduler, SchedulerConfig, SchedulerError};
+
+// Anomaly detection v1.0
+pub use anomaly::{Anomaly, AnomalyConfig, AnomalyMonitor, Ewma, Watch, WatchMode};
+
+// Safe mode v1.0
+pub use safe_mode::{SafeMode, SafeModeStatus, SAFE_EXECUTOR};
+
+// Resource budget v1.0
+pub use resource_budget::{BudgetConfig, BudgetUsage, ProcProbe, ResourceBudget, ResourceProbe};
+
+// Redaction v1.0
+pub use redaction::{RedactionConfig, RedactionReport, Redactor, SectionHandling};
+
+// Encryption v1.0
+pub use encryption::{load_at_rest_cipher, EncryptionConfig, EncryptionError, FileCipher};
+
+// Synonym collapse v1.0
+pub use synonym_collapse::{CollapsePass, CollapseStats, MergeReport, SynonymCollapse, SynonymCollapseConfig};
+
+// Snapshot diff v1.0
+pub use snapshot_diff::{
+    ConfidenceChange, ConfidenceDiff, GridDiff, Histogram, IdSetDiff, SnapshotDiff, SnapshotDiffConfig, TokenDrift,
+};
+
+// Pipeline profiling v1.0
+pub use profiling::{
+    PipelineProfiler,
+    PipelineStage,
+    ProfilingConfig,
+    ProfilingSnapshot,
+    StageStats,
+    PROFILER,
+};
+
+// Document ingestion v1.0
+pub use ingestion::{
+    DocumentFormat,
+    IngestionConfig,
+    IngestionError,
+    IngestionEvent,
+    IngestionReport,
+    Ingestor,
+};
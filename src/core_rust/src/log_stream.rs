@@ -0,0 +1,521 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Log Stream v1.0 - in-process log capture and live subscriptions
+//!
+//! [`LogHubLayer`] is a `tracing_subscriber` layer that turns every event
+//! into a [`LogEntry`] and hands it to a [`LogHub`]:
+//!
+//! - the most recent entries are kept in a ring buffer (`recent`, `export`)
+//! - live entries are broadcast to subscribers (`subscribe(filter)`), which
+//!   first receive the matching backlog, then new entries
+//!
+//! The logging initializers in `logging_utils` and `tracing_otel` install
+//! the layer on the process-wide [`LOG_HUB`]. A slow subscriber never
+//! blocks logging: it skips what it missed and the count is reported in
+//! `LogSubscription::dropped`.
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// Entries kept by the global hub
+pub const DEFAULT_LOG_CAPACITY: usize = 5000;
+
+/// Broadcast buffer per subscriber before entries are skipped
+const SUBSCRIBER_BUFFER: usize = 1024;
+
+lazy_static::lazy_static! {
+    /// Process-wide hub fed by the logging initializers
+    pub static ref LOG_HUB: LogHub = LogHub::new(DEFAULT_LOG_CAPACITY);
+}
+
+/// Log level, ordered from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LogLevel::Trace => "trace",
+            LogLevel::Debug => "debug",
+            LogLevel::Info => "info",
+            LogLevel::Warn => "warn",
+            LogLevel::Error => "error",
+        }
+    }
+}
+
+impl From<&tracing::Level> for LogLevel {
+    fn from(level: &tracing::Level) -> Self {
+        match *level {
+            tracing::Level::TRACE => LogLevel::Trace,
+            tracing::Level::DEBUG => LogLevel::Debug,
+            tracing::Level::INFO => LogLevel::Info,
+            tracing::Level::WARN => LogLevel::Warn,
+            tracing::Level::ERROR => LogLevel::Error,
+        }
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for LogLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "trace" => Ok(LogLevel::Trace),
+            "debug" => Ok(LogLevel::Debug),
+            "info" => Ok(LogLevel::Info),
+            "warn" | "warning" => Ok(LogLevel::Warn),
+            "error" => Ok(LogLevel::Error),
+            other => Err(format!("Unknown log level '{}'", other)),
+        }
+    }
+}
+
+/// One captured log event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogEntry {
+    /// Monotonic sequence number (gaps mean entries were evicted)
+    pub seq: u64,
+    /// Unix milliseconds
+    pub timestamp_ms: u64,
+    pub level: LogLevel,
+    /// Module path, e.g. `_core::gateway`
+    pub target: String,
+    pub message: String,
+    /// Structured fields other than the message
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, String>,
+}
+
+impl fmt::Display for LogEntry {
+    /// Single-line text form used by `export` in text mode
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {:>5} {}: {}",
+            self.timestamp_ms,
+            self.level.as_str().to_uppercase(),
+            self.target,
+            self.message
+        )?;
+        for (key, value) in &self.fields {
+            write!(f, " {}={}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Which entries a subscriber or query wants
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LogFilter {
+    /// Minimum level (None = everything)
+    #[serde(default)]
+    pub min_level: Option<LogLevel>,
+    /// Target prefix, e.g. `_core::gateway`
+    #[serde(default)]
+    pub target: Option<String>,
+    /// Case-insensitive substring of message or field values
+    #[serde(default)]
+    pub contains: Option<String>,
+}
+
+impl LogFilter {
+    pub fn with_min_level(mut self, level: LogLevel) -> Self {
+        self.min_level = Some(level);
+        self
+    }
+
+    pub fn with_target(mut self, target: impl Into<String>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    pub fn with_contains(mut self, text: impl Into<String>) -> Self {
+        self.contains = Some(text.into());
+        self
+    }
+
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        if self.min_level.is_some_and(|min| entry.level < min) {
+            return false;
+        }
+        if let Some(target) = &self.target {
+            if !entry.target.starts_with(target.as_str()) {
+                return false;
+            }
+        }
+        if let Some(text) = &self.contains {
+            let text = text.to_lowercase();
+            let found = entry.message.to_lowercase().contains(&text)
+                || entry.fields.values().any(|v| v.to_lowercase().contains(&text));
+            if !found {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Export file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogExportFormat {
+    /// One `LogEntry` JSON object per line
+    JsonLines,
+    /// `LogEntry`'s Display form
+    Text,
+}
+
+/// Ring buffer of recent entries plus live fan-out
+pub struct LogHub {
+    capacity: usize,
+    buffer: Mutex<VecDeque<Arc<LogEntry>>>,
+    sender: broadcast::Sender<Arc<LogEntry>>,
+    next_seq: AtomicU64,
+}
+
+impl LogHub {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(SUBSCRIBER_BUFFER);
+        Self {
+            capacity: capacity.max(1),
+            buffer: Mutex::new(VecDeque::with_capacity(capacity.clamp(1, 1024))),
+            sender,
+            next_seq: AtomicU64::new(0),
+        }
+    }
+
+    /// Record an entry; `seq` is assigned here
+    pub fn push(&self, mut entry: LogEntry) {
+        let entry = {
+            let mut buffer = self.buffer.lock();
+            // Assigned under the lock so the buffer stays ordered
+            entry.seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
+            let entry = Arc::new(entry);
+            if buffer.len() == self.capacity {
+                buffer.pop_front();
+            }
+            buffer.push_back(entry.clone());
+            entry
+        };
+        // No receivers is fine
+        let _ = self.sender.send(entry);
+    }
+
+    /// Newest `limit` matching entries, oldest first
+    pub fn recent(&self, filter: &LogFilter, limit: usize) -> Vec<LogEntry> {
+        let buffer = self.buffer.lock();
+        let mut entries: Vec<LogEntry> = buffer
+            .iter()
+            .rev()
+            .filter(|entry| filter.matches(entry))
+            .take(limit)
+            .map(|entry| (**entry).clone())
+            .collect();
+        entries.reverse();
+        entries
+    }
+
+    /// Subscribe: matching backlog (up to `backlog` entries), then live entries
+    pub fn subscribe(&self, filter: LogFilter, backlog: usize) -> LogSubscription {
+        // Take the backlog and the receiver under the buffer lock, so no
+        // entry is missed or delivered twice
+        let buffer = self.buffer.lock();
+        let receiver = self.sender.subscribe();
+        let mut pending: VecDeque<Arc<LogEntry>> = buffer
+            .iter()
+            .rev()
+            .filter(|entry| filter.matches(entry))
+            .take(backlog)
+            .cloned()
+            .collect();
+        drop(buffer);
+        pending.make_contiguous().reverse();
+
+        LogSubscription {
+            filter,
+            pending,
+            receiver,
+            dropped: 0,
+        }
+    }
+
+    /// Write matching buffered entries to `writer`; returns how many
+    pub fn export<W: Write>(&self, filter: &LogFilter, format: LogExportFormat, writer: &mut W) -> io::Result<usize> {
+        let entries = self.recent(filter, self.capacity);
+        for entry in &entries {
+            match format {
+                LogExportFormat::JsonLines => {
+                    serde_json::to_writer(&mut *writer, entry).map_err(io::Error::other)?;
+                    writeln!(writer)?;
+                }
+                LogExportFormat::Text => writeln!(writer, "{}", entry)?,
+            }
+        }
+        writer.flush()?;
+        Ok(entries.len())
+    }
+
+    /// `export` into a file (created or truncated)
+    pub fn export_to_file<P: AsRef<Path>>(&self, filter: &LogFilter, format: LogExportFormat, path: P) -> io::Result<usize> {
+        let mut writer = io::BufWriter::new(std::fs::File::create(path)?);
+        self.export(filter, format, &mut writer)
+    }
+
+    /// Buffered entry count
+    pub fn len(&self) -> usize {
+        self.buffer.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Layer feeding this hub
+    pub fn layer(&'static self) -> LogHubLayer {
+        LogHubLayer { hub: self }
+    }
+}
+
+/// Live feed of entries matching a filter
+pub struct LogSubscription {
+    filter: LogFilter,
+    pending: VecDeque<Arc<LogEntry>>,
+    receiver: broadcast::Receiver<Arc<LogEntry>>,
+    dropped: u64,
+}
+
+impl LogSubscription {
+    /// Next matching entry; None once the hub is gone
+    pub async fn next(&mut self) -> Option<LogEntry> {
+        if let Some(entry) = self.pending.pop_front() {
+            return Some((*entry).clone());
+        }
+        loop {
+            match self.receiver.recv().await {
+                Ok(entry) if self.filter.matches(&entry) => return Some((*entry).clone()),
+                Ok(_) => continue,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => self.dropped += skipped,
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Matching entry if one is ready, without waiting
+    pub fn try_next(&mut self) -> Option<LogEntry> {
+        if let Some(entry) = self.pending.pop_front() {
+            return Some((*entry).clone());
+        }
+        loop {
+            match self.receiver.try_recv() {
+                Ok(entry) if self.filter.matches(&entry) => return Some((*entry).clone()),
+                Ok(_) => continue,
+                Err(broadcast::error::TryRecvError::Lagged(skipped)) => self.dropped += skipped,
+                Err(_) => return None,
+            }
+        }
+    }
+
+    pub fn filter(&self) -> &LogFilter {
+        &self.filter
+    }
+
+    /// Replace the filter (applies to entries not yet received)
+    pub fn set_filter(&mut self, filter: LogFilter) {
+        self.pending.retain(|entry| filter.matches(entry));
+        self.filter = filter;
+    }
+
+    /// Entries skipped because this subscriber fell behind
+    pub fn dropped(&self) -> u64 {
+        self.dropped
+    }
+}
+
+/// `tracing_subscriber` layer feeding a [`LogHub`]
+pub struct LogHubLayer {
+    hub: &'static LogHub,
+}
+
+#[derive(Default)]
+struct EntryVisitor {
+    message: String,
+    fields: BTreeMap<String, String>,
+}
+
+impl Visit for EntryVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message = value.to_string();
+        } else {
+            self.fields.insert(field.name().to_string(), value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        } else {
+            self.fields.insert(field.name().to_string(), format!("{:?}", value));
+        }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for LogHubLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = EntryVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        self.hub.push(LogEntry {
+            seq: 0,
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+            level: LogLevel::from(metadata.level()),
+            target: metadata.target().to_string(),
+            message: visitor.message,
+            fields: visitor.fields,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn entry(level: LogLevel, target: &str, message: &str) -> LogEntry {
+        LogEntry {
+            seq: 0,
+            timestamp_ms: 0,
+            level,
+            target: target.to_string(),
+            message: message.to_string(),
+            fields: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_filter() {
+        let warn = entry(LogLevel::Warn, "_core::gateway", "Queue Full");
+        assert!(LogFilter::default().matches(&warn));
+        assert!(LogFilter::default().with_min_level(LogLevel::Info).matches(&warn));
+        assert!(!LogFilter::default().with_min_level(LogLevel::Error).matches(&warn));
+        assert!(LogFilter::default().with_target("_core::gate").matches(&warn));
+        assert!(!LogFilter::default().with_target("_core::api").matches(&warn));
+        assert!(LogFilter::default().with_contains("queue full").matches(&warn));
+        assert_eq!("WARNING".parse::<LogLevel>().unwrap(), LogLevel::Warn);
+    }
+
+    #[test]
+    fn test_ring_buffer_and_export() {
+        let hub = LogHub::new(3);
+        for i in 0..5 {
+            hub.push(entry(LogLevel::Info, "t", &format!("m{}", i)));
+        }
+        let recent = hub.recent(&LogFilter::default(), 10);
+        assert_eq!(recent.iter().map(|e| e.seq).collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert_eq!(hub.recent(&LogFilter::default(), 1)[0].message, "m4");
+
+        let mut out = Vec::new();
+        assert_eq!(hub.export(&LogFilter::default(), LogExportFormat::JsonLines, &mut out).unwrap(), 3);
+        let lines: Vec<LogEntry> = String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines, recent);
+
+        let mut out = Vec::new();
+        hub.export(&LogFilter::default(), LogExportFormat::Text, &mut out).unwrap();
+        assert!(String::from_utf8(out).unwrap().starts_with("0  INFO t: m2\n"));
+    }
+
+    #[tokio::test]
+    async fn test_subscription_backlog_then_live() {
+        let hub = LogHub::new(100);
+        hub.push(entry(LogLevel::Debug, "t", "quiet"));
+        hub.push(entry(LogLevel::Warn, "t", "old warning"));
+
+        let mut subscription = hub.subscribe(LogFilter::default().with_min_level(LogLevel::Info), 10);
+        hub.push(entry(LogLevel::Info, "t", "live"));
+        hub.push(entry(LogLevel::Trace, "t", "noise"));
+
+        assert_eq!(subscription.next().await.unwrap().message, "old warning");
+        assert_eq!(subscription.next().await.unwrap().message, "live");
+        assert!(subscription.try_next().is_none());
+
+        subscription.set_filter(LogFilter::default());
+        hub.push(entry(LogLevel::Trace, "t", "now visible"));
+        assert_eq!(subscription.next().await.unwrap().message, "now visible");
+    }
+
+    #[test]
+    fn test_lagging_subscriber_counts_drops() {
+        let hub = LogHub::new(10);
+        let mut subscription = hub.subscribe(LogFilter::default(), 0);
+        for _ in 0..(SUBSCRIBER_BUFFER + 5) {
+            hub.push(entry(LogLevel::Info, "t", "x"));
+        }
+        assert!(subscription.try_next().is_some());
+        assert_eq!(subscription.dropped(), 5);
+    }
+
+    #[test]
+    fn test_layer_captures_events() {
+        lazy_static::lazy_static! {
+            static ref HUB: LogHub = LogHub::new(10);
+        }
+        let subscriber = tracing_subscriber::registry().with(HUB.layer());
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(user = "bob", attempts = 3, "Account locked");
+        });
+
+        let entries = HUB.recent(&LogFilter::default(), 10);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].level, LogLevel::Warn);
+        assert_eq!(entries[0].message, "Account locked");
+        assert_eq!(entries[0].fields["user"], "bob");
+        assert_eq!(entries[0].fields["attempts"], "3");
+        assert!(entries[0].target.contains("log_stream"));
+    }
+}
@@ -33,10 +33,16 @@
 /// ```
 
 use tracing::{info, warn, error};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::{self, EnvFilter};
 
+use crate::log_stream::LOG_HUB;
+
 /// Initialize logging with custom filter
 ///
+/// Events are also captured by the global `log_stream::LOG_HUB`.
+///
 /// # Arguments
 ///
 /// * `filter` - Log level filter (e.g., "info", "debug", "warn")
@@ -57,6 +63,8 @@ pub fn init_logging(filter: &str) {
         .with_target(true)
         .with_thread_ids(false)
         .with_line_number(true)
+        .finish()
+        .with(LOG_HUB.layer())
         .init();
 }
 
@@ -81,6 +89,8 @@ pub fn init_production_logging() {
         .with_target(true)
         .with_thread_ids(true)
         .with_line_number(true)
+        .finish()
+        .with(LOG_HUB.layer())
         .init();
 }
 
@@ -45,8 +45,9 @@
 
 use lazy_static::lazy_static;
 use prometheus::{
-    register_counter, register_gauge, register_histogram, register_int_counter, register_int_gauge,
-    Counter, Encoder, Gauge, Histogram, IntCounter, IntGauge, TextEncoder,
+    register_counter, register_gauge, register_histogram, register_int_counter,
+    register_int_counter_vec, register_int_gauge, Counter, Encoder, Gauge, Histogram, IntCounter,
+    IntCounterVec, IntGauge, TextEncoder,
 };
 
 // ==================== COUNTERS ====================
@@ -115,6 +116,22 @@ lazy_static! {
     )
     .unwrap();
 
+    /// Panics in supervised tasks, by subsystem
+    pub static ref SUBSYSTEM_PANICS: IntCounterVec = register_int_counter_vec!(
+        "neurograph_subsystem_panics_total",
+        "Total number of panics in supervised tasks",
+        &["subsystem"]
+    )
+    .unwrap();
+
+    /// Restarts of supervised tasks, by subsystem
+    pub static ref SUBSYSTEM_RESTARTS: IntCounterVec = register_int_counter_vec!(
+        "neurograph_subsystem_restarts_total",
+        "Total number of supervised task restarts",
+        &["subsystem"]
+    )
+    .unwrap();
+
     /// Total number of WAL entries written
     pub static ref WAL_ENTRIES_WRITTEN: IntCounter = register_int_counter!(
         "neurograph_wal_entries_written_total",
@@ -265,6 +282,83 @@ lazy_static! {
     .unwrap();
 }
 
+// ==================== WRITE-BEHIND PERSISTENCE METRICS ====================
+
+lazy_static! {
+    /// Operations waiting in write-behind queues
+    pub static ref WRITE_BEHIND_QUEUE_LEN: IntGauge = register_int_gauge!(
+        "neurograph_write_behind_queue_len",
+        "Operations waiting in the persistence write-behind queue"
+    )
+    .unwrap();
+
+    /// Age of the oldest queued operation in seconds
+    pub static ref WRITE_BEHIND_LAG_SECONDS: Gauge = register_gauge!(
+        "neurograph_write_behind_lag_seconds",
+        "Age of the oldest operation in the persistence write-behind queue"
+    )
+    .unwrap();
+
+    /// Operations written to the persistence backend
+    pub static ref WRITE_BEHIND_WRITTEN: IntCounter = register_int_counter!(
+        "neurograph_write_behind_written_total",
+        "Operations written by the persistence write-behind worker"
+    )
+    .unwrap();
+
+    /// Operations lost, by reason (queue_full, lag, stream_lagged, failed)
+    pub static ref WRITE_BEHIND_DROPPED: IntCounterVec = register_int_counter_vec!(
+        "neurograph_write_behind_dropped_total",
+        "Operations dropped by the persistence write-behind queue",
+        &["reason"]
+    )
+    .unwrap();
+}
+
+// ==================== METRICS HISTORY ====================
+
+lazy_static! {
+    /// Series tracked by the in-core metrics history
+    pub static ref METRICS_HISTORY_SERIES: IntGauge = register_int_gauge!(
+        "neurograph_metrics_history_series",
+        "Time series tracked by the in-core metrics history"
+    )
+    .unwrap();
+
+    /// Sampling passes of the metrics history
+    pub static ref METRICS_HISTORY_SAMPLES: IntCounter = register_int_counter!(
+        "neurograph_metrics_history_samples_total",
+        "Sampling passes of the in-core metrics history"
+    )
+    .unwrap();
+}
+
+// ==================== GRAPH LIMITS ====================
+
+lazy_static! {
+    /// Edges evicted to stay within graph limits
+    pub static ref GRAPH_EDGES_EVICTED: IntCounter = register_int_counter!(
+        "neurograph_graph_edges_evicted_total",
+        "Graph edges evicted to stay within max_nodes / max_edges_per_node"
+    )
+    .unwrap();
+
+    /// Nodes evicted to stay within graph limits
+    pub static ref GRAPH_NODES_EVICTED: IntCounter = register_int_counter!(
+        "neurograph_graph_nodes_evicted_total",
+        "Graph nodes evicted to stay within max_nodes"
+    )
+    .unwrap();
+
+    /// Inserts refused by graph limits, by limit (max_nodes, max_edges_per_node)
+    pub static ref GRAPH_LIMIT_REFUSALS: IntCounterVec = register_int_counter_vec!(
+        "neurograph_graph_limit_refusals_total",
+        "Graph inserts refused because only protected structure could make room",
+        &["limit"]
+    )
+    .unwrap();
+}
+
 // ==================== EXPORT ====================
 
 /// Export all metrics in Prometheus text format
@@ -0,0 +1,484 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Metrics History v1.0 - in-core time series for dashboards
+//!
+//! Prometheus metrics are instantaneous; dashboards need a short history.
+//! [`MetricsHistory`] keeps a ring of `(t, value)` samples per series:
+//!
+//! - the sampler periodically gathers the Prometheus registry (the same
+//!   families `metrics::export_metrics` serves) and records every counter
+//!   and gauge; histograms contribute `<name>_count` and `<name>_sum`
+//! - `series` returns a downsampled window for sparklines, optionally as a
+//!   per-second rate for counters
+//!
+//! Labelled metrics are stored as one series per label set, named in
+//! exposition format, e.g. `neurograph_subsystem_panics_total{subsystem="gateway_consumer"}`.
+//! The sampler is not started implicitly; call `spawn_sampler` on
+//! [`METRICS_HISTORY`] from the runtime that owns the process.
+
+use parking_lot::RwLock;
+use prometheus::proto::{MetricFamily, MetricType};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::task::JoinHandle;
+
+lazy_static::lazy_static! {
+    /// Process-wide history fed from the Prometheus registry
+    pub static ref METRICS_HISTORY: MetricsHistory = MetricsHistory::new(MetricsHistoryConfig::default());
+}
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// History configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsHistoryConfig {
+    /// Samples kept per series
+    pub capacity: usize,
+
+    /// Sampling interval in milliseconds
+    pub interval_ms: u64,
+
+    /// Only metric names starting with one of these prefixes are recorded
+    /// (empty: record everything)
+    pub prefixes: Vec<String>,
+
+    /// Upper bound on tracked series (protects against label explosions)
+    pub max_series: usize,
+}
+
+impl Default for MetricsHistoryConfig {
+    fn default() -> Self {
+        Self {
+            // 1 hour at 5 s resolution
+            capacity: 720,
+            interval_ms: 5_000,
+            prefixes: vec!["neurograph_".to_string()],
+            max_series: 1_000,
+        }
+    }
+}
+
+impl MetricsHistoryConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.capacity < 2 {
+            return Err("capacity must be at least 2".to_string());
+        }
+        if self.interval_ms == 0 {
+            return Err("interval_ms must be > 0".to_string());
+        }
+        if self.max_series == 0 {
+            return Err("max_series must be > 0".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn interval(&self) -> Duration {
+        Duration::from_millis(self.interval_ms)
+    }
+}
+
+// ============================================================================
+// Series
+// ============================================================================
+
+/// One sample
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Sample {
+    /// Unix time in milliseconds
+    pub t: u64,
+    pub value: f64,
+}
+
+/// How values of a series evolve
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SeriesKind {
+    /// Monotonic (rate makes sense)
+    Counter,
+    Gauge,
+}
+
+/// Query for one series
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SeriesQuery {
+    /// Only samples at or after this time (Unix ms)
+    #[serde(default)]
+    pub since: Option<u64>,
+
+    /// Downsample to at most this many points
+    #[serde(default)]
+    pub points: Option<usize>,
+
+    /// Per-second rate between consecutive samples instead of raw values
+    #[serde(default)]
+    pub rate: bool,
+}
+
+/// Downsampled series
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Series {
+    pub name: String,
+    pub kind: SeriesKind,
+    pub rate: bool,
+    pub samples: Vec<Sample>,
+}
+
+impl Series {
+    /// Values only, for sparkline widgets
+    pub fn values(&self) -> Vec<f64> {
+        self.samples.iter().map(|s| s.value).collect()
+    }
+}
+
+/// Latest value of a tracked series
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeriesSummary {
+    pub name: String,
+    pub kind: SeriesKind,
+    pub samples: usize,
+    pub last: Option<Sample>,
+}
+
+struct Ring {
+    kind: SeriesKind,
+    samples: VecDeque<Sample>,
+}
+
+// ============================================================================
+// History
+// ============================================================================
+
+/// Per-series ring buffers of samples
+pub struct MetricsHistory {
+    config: MetricsHistoryConfig,
+    series: RwLock<HashMap<String, Ring>>,
+}
+
+impl MetricsHistory {
+    pub fn new(config: MetricsHistoryConfig) -> Self {
+        Self {
+            config,
+            series: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn config(&self) -> &MetricsHistoryConfig {
+        &self.config
+    }
+
+    /// Record one sample; ignored once `max_series` distinct series exist
+    pub fn record(&self, name: &str, kind: SeriesKind, t: u64, value: f64) {
+        if !value.is_finite() {
+            return;
+        }
+        let mut series = self.series.write();
+        if !series.contains_key(name) && series.len() >= self.config.max_series {
+            return;
+        }
+        let ring = series.entry(name.to_string()).or_insert_with(|| Ring {
+            kind,
+            samples: VecDeque::with_capacity(self.config.capacity),
+        });
+        if ring.samples.len() == self.config.capacity {
+            ring.samples.pop_front();
+        }
+        ring.samples.push_back(Sample { t, value });
+    }
+
+    /// Record every counter and gauge of the given families at time `t`
+    pub fn record_families(&self, families: &[MetricFamily], t: u64) {
+        for family in families {
+            let name = family.get_name();
+            if !self.accepts(name) {
+                continue;
+            }
+            for metric in family.get_metric() {
+                let labels = format_labels(metric.get_label());
+                match family.get_field_type() {
+                    MetricType::COUNTER => {
+                        let value = metric.get_counter().get_value();
+                        self.record(&format!("{name}{labels}"), SeriesKind::Counter, t, value);
+                    }
+                    MetricType::GAUGE => {
+                        let value = metric.get_gauge().get_value();
+                        self.record(&format!("{name}{labels}"), SeriesKind::Gauge, t, value);
+                    }
+                    MetricType::UNTYPED => {
+                        let value = metric.get_untyped().get_value();
+                        self.record(&format!("{name}{labels}"), SeriesKind::Gauge, t, value);
+                    }
+                    MetricType::HISTOGRAM => {
+                        let histogram = metric.get_histogram();
+                        let count = histogram.get_sample_count() as f64;
+                        self.record(&format!("{name}_count{labels}"), SeriesKind::Counter, t, count);
+                        let sum = histogram.get_sample_sum();
+                        self.record(&format!("{name}_sum{labels}"), SeriesKind::Counter, t, sum);
+                    }
+                    MetricType::SUMMARY => {
+                        let summary = metric.get_summary();
+                        let count = summary.get_sample_count() as f64;
+                        self.record(&format!("{name}_count{labels}"), SeriesKind::Counter, t, count);
+                        let sum = summary.get_sample_sum();
+                        self.record(&format!("{name}_sum{labels}"), SeriesKind::Counter, t, sum);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sample the Prometheus default registry now
+    pub fn sample(&self) {
+        self.record_families(&prometheus::gather(), now_ms());
+        crate::metrics::METRICS_HISTORY_SERIES.set(self.len() as i64);
+        crate::metrics::METRICS_HISTORY_SAMPLES.inc();
+    }
+
+    /// Sample every `interval_ms` until the runtime shuts down
+    pub fn spawn_sampler(&'static self) -> JoinHandle<()> {
+        let interval = self.config.interval();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                self.sample();
+            }
+        })
+    }
+
+    /// Downsampled window of one series
+    pub fn series(&self, name: &str, query: &SeriesQuery) -> Option<Series> {
+        let series = self.series.read();
+        let ring = series.get(name)?;
+        let kind = ring.kind;
+        let since = query.since.unwrap_or(0);
+        let mut samples: Vec<Sample> = ring.samples.iter().copied().filter(|s| s.t >= since).collect();
+        drop(series);
+
+        if query.rate {
+            samples = rates(&samples);
+        }
+        if let Some(points) = query.points {
+            samples = downsample(&samples, points);
+        }
+
+        Some(Series {
+            name: name.to_string(),
+            kind,
+            rate: query.rate,
+            samples,
+        })
+    }
+
+    /// Tracked series with their latest value, sorted by name
+    pub fn summaries(&self) -> Vec<SeriesSummary> {
+        let series = self.series.read();
+        let mut summaries: Vec<_> = series
+            .iter()
+            .map(|(name, ring)| SeriesSummary {
+                name: name.clone(),
+                kind: ring.kind,
+                samples: ring.samples.len(),
+                last: ring.samples.back().copied(),
+            })
+            .collect();
+        summaries.sort_by(|a, b| a.name.cmp(&b.name));
+        summaries
+    }
+
+    /// Number of tracked series
+    pub fn len(&self) -> usize {
+        self.series.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop all samples
+    pub fn clear(&self) {
+        self.series.write().clear();
+    }
+
+    fn accepts(&self, name: &str) -> bool {
+        self.config.prefixes.is_empty() || self.config.prefixes.iter().any(|p| name.starts_with(p.as_str()))
+    }
+}
+
+/// `{k="v",...}` in exposition format, empty without labels
+fn format_labels(labels: &[prometheus::proto::LabelPair]) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+    let pairs: Vec<String> = labels
+        .iter()
+        .map(|l| format!("{}=\"{}\"", l.get_name(), l.get_value()))
+        .collect();
+    format!("{{{}}}", pairs.join(","))
+}
+
+/// Per-second rate between consecutive samples; counter resets count from zero
+fn rates(samples: &[Sample]) -> Vec<Sample> {
+    samples
+        .windows(2)
+        .filter(|w| w[1].t > w[0].t)
+        .map(|w| {
+            let delta = if w[1].value >= w[0].value { w[1].value - w[0].value } else { w[1].value };
+            Sample {
+                t: w[1].t,
+                value: delta * 1000.0 / (w[1].t - w[0].t) as f64,
+            }
+        })
+        .collect()
+}
+
+/// Average consecutive samples into at most `points` buckets
+///
+/// Each bucket is stamped with the time of its last sample.
+fn downsample(samples: &[Sample], points: usize) -> Vec<Sample> {
+    if points == 0 {
+        return Vec::new();
+    }
+    if samples.len() <= points {
+        return samples.to_vec();
+    }
+    let bucket = samples.len().div_ceil(points);
+    samples
+        .chunks(bucket)
+        .map(|chunk| Sample {
+            t: chunk[chunk.len() - 1].t,
+            value: chunk.iter().map(|s| s.value).sum::<f64>() / chunk.len() as f64,
+        })
+        .collect()
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history(capacity: usize) -> MetricsHistory {
+        MetricsHistory::new(MetricsHistoryConfig {
+            capacity,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_ring_and_downsample() {
+        let history = history(10);
+        for i in 0..15u64 {
+            history.record("neurograph_queue", SeriesKind::Gauge, i * 1000, i as f64);
+        }
+
+        let full = history.series("neurograph_queue", &SeriesQuery::default()).unwrap();
+        assert_eq!(full.samples.len(), 10);
+        assert_eq!(full.samples[0].value, 5.0);
+
+        let query = SeriesQuery { points: Some(5), ..Default::default() };
+        let sparkline = history.series("neurograph_queue", &query).unwrap();
+        assert_eq!(sparkline.values(), vec![5.5, 7.5, 9.5, 11.5, 13.5]);
+        assert_eq!(sparkline.samples[4].t, 14_000);
+
+        let query = SeriesQuery { since: Some(12_000), ..Default::default() };
+        assert_eq!(history.series("neurograph_queue", &query).unwrap().samples.len(), 3);
+        assert!(history.series("missing", &SeriesQuery::default()).is_none());
+    }
+
+    #[test]
+    fn test_counter_rate() {
+        let history = history(10);
+        history.record("neurograph_ops_total", SeriesKind::Counter, 0, 0.0);
+        history.record("neurograph_ops_total", SeriesKind::Counter, 2_000, 10.0);
+        history.record("neurograph_ops_total", SeriesKind::Counter, 4_000, 30.0);
+        // Reset after restart
+        history.record("neurograph_ops_total", SeriesKind::Counter, 5_000, 4.0);
+
+        let query = SeriesQuery { rate: true, ..Default::default() };
+        let series = history.series("neurograph_ops_total", &query).unwrap();
+        assert_eq!(series.kind, SeriesKind::Counter);
+        assert_eq!(series.values(), vec![5.0, 10.0, 4.0]);
+    }
+
+    #[test]
+    fn test_record_families() {
+        let registry = prometheus::Registry::new();
+        let gauge = prometheus::IntGauge::new("neurograph_test_gauge", "test").unwrap();
+        let counter = prometheus::IntCounterVec::new(
+            prometheus::Opts::new("neurograph_test_total", "test"),
+            &["kind"],
+        )
+        .unwrap();
+        let histogram = prometheus::Histogram::with_opts(
+            prometheus::HistogramOpts::new("neurograph_test_seconds", "test"),
+        )
+        .unwrap();
+        let other = prometheus::IntGauge::new("process_open_fds", "test").unwrap();
+        registry.register(Box::new(gauge.clone())).unwrap();
+        registry.register(Box::new(counter.clone())).unwrap();
+        registry.register(Box::new(histogram.clone())).unwrap();
+        registry.register(Box::new(other.clone())).unwrap();
+
+        gauge.set(7);
+        counter.with_label_values(&["a"]).inc_by(3);
+        histogram.observe(0.5);
+        other.set(1);
+
+        let history = history(10);
+        history.record_families(&registry.gather(), 1_000);
+
+        let names: Vec<_> = history.summaries().into_iter().map(|s| s.name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "neurograph_test_gauge",
+                "neurograph_test_seconds_count",
+                "neurograph_test_seconds_sum",
+                "neurograph_test_total{kind=\"a\"}",
+            ]
+        );
+        let last = history.summaries()[0].last.unwrap();
+        assert_eq!(last, Sample { t: 1_000, value: 7.0 });
+    }
+
+    #[test]
+    fn test_max_series_and_config() {
+        let history = MetricsHistory::new(MetricsHistoryConfig {
+            max_series: 2,
+            ..Default::default()
+        });
+        for name in ["a", "b", "c"] {
+            history.record(name, SeriesKind::Gauge, 0, 1.0);
+        }
+        history.record("a", SeriesKind::Gauge, 1, f64::NAN);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.summaries()[0].samples, 1);
+
+        assert!(MetricsHistoryConfig::default().validate().is_ok());
+        let config = MetricsHistoryConfig { capacity: 1, ..Default::default() };
+        assert!(config.validate().is_err());
+    }
+}
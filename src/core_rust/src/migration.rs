@@ -0,0 +1,431 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Binary struct migrations v1.0
+//!
+//! Token, ConnectionV3, CDNA, ADNA and the experience records are stored as
+//! raw packed bytes. When one of them changes layout, data written by an
+//! older build must be upgraded on load. `MigrationRegistry` maps
+//! `(format, from_version)` to an upgrade step producing `from_version + 1`;
+//! loaders read records at the size of the version they were written with
+//! and chain steps up to the current version.
+//!
+//! Writers record the versions they used (`CheckpointManifest::formats`,
+//! the learning journal's WAL header). Data without a recorded version is
+//! assumed to be at `baseline_version`, the layout in use when versioning
+//! was introduced.
+//!
+//! Data newer than this build is never downgraded: loaders fail with
+//! `MigrationError::UnsupportedDowngrade` before touching any state.
+//!
+//! ```ignore
+//! // ConnectionV3 v3 (64 bytes) -> v4 (80 bytes)
+//! MIGRATIONS.write().register(BinaryFormat::ConnectionV3, 3, 64, |old| {
+//!     let mut new = old.to_vec();
+//!     new.resize(80, 0);
+//!     Ok(new)
+//! })?;
+//! ```
+
+use crate::adna::ADNA_VERSION_MAJOR;
+use crate::cdna::CDNA_VERSION_MAJOR;
+use parking_lot::RwLock;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+
+/// Struct versions by format name (`BinaryFormat::name`)
+pub type FormatVersions = BTreeMap<String, u32>;
+
+/// Upgrade step: bytes at version N -> bytes at version N + 1
+pub type MigrationFn = fn(&[u8]) -> Result<Vec<u8>, String>;
+
+/// Hand-packed binary formats with versioned layouts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum BinaryFormat {
+    Token,
+    ConnectionV3,
+    ExperienceEvent,
+    ExperienceToken,
+    Cdna,
+    Adna,
+}
+
+impl BinaryFormat {
+    pub const ALL: [BinaryFormat; 6] = [
+        BinaryFormat::Token,
+        BinaryFormat::ConnectionV3,
+        BinaryFormat::ExperienceEvent,
+        BinaryFormat::ExperienceToken,
+        BinaryFormat::Cdna,
+        BinaryFormat::Adna,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BinaryFormat::Token => "token",
+            BinaryFormat::ConnectionV3 => "connection",
+            BinaryFormat::ExperienceEvent => "experience_event",
+            BinaryFormat::ExperienceToken => "experience_token",
+            BinaryFormat::Cdna => "cdna",
+            BinaryFormat::Adna => "adna",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|f| f.name() == name)
+    }
+
+    /// Layout version written by this build
+    pub fn current_version(&self) -> u32 {
+        match self {
+            BinaryFormat::Token => 2,
+            BinaryFormat::ConnectionV3 => 3,
+            BinaryFormat::ExperienceEvent => 1,
+            BinaryFormat::ExperienceToken => 3,
+            BinaryFormat::Cdna => CDNA_VERSION_MAJOR as u32,
+            BinaryFormat::Adna => ADNA_VERSION_MAJOR as u32,
+        }
+    }
+
+    /// Version assumed for data written before versions were recorded
+    pub fn baseline_version(&self) -> u32 {
+        match self {
+            BinaryFormat::Token => 2,
+            BinaryFormat::ConnectionV3 => 3,
+            BinaryFormat::ExperienceEvent => 1,
+            BinaryFormat::ExperienceToken => 3,
+            BinaryFormat::Cdna => 2,
+            BinaryFormat::Adna => 3,
+        }
+    }
+
+    /// Record size of the current version
+    pub fn current_size(&self) -> usize {
+        match self {
+            BinaryFormat::Token => std::mem::size_of::<crate::token::Token>(),
+            BinaryFormat::ConnectionV3 => std::mem::size_of::<crate::connection_v3::ConnectionV3>(),
+            BinaryFormat::ExperienceEvent => std::mem::size_of::<crate::experience_stream::ExperienceEvent>(),
+            BinaryFormat::ExperienceToken => std::mem::size_of::<crate::archive::ExperienceToken>(),
+            BinaryFormat::Cdna => std::mem::size_of::<crate::cdna::CDNA>(),
+            BinaryFormat::Adna => std::mem::size_of::<crate::adna::ADNA>(),
+        }
+    }
+
+    /// Version stored inside the record itself (CDNA and ADNA headers)
+    pub fn embedded_version(&self, bytes: &[u8]) -> Option<u32> {
+        match self {
+            // magic u32, then version_major u16
+            BinaryFormat::Cdna | BinaryFormat::Adna => {
+                bytes.get(4..6).map(|b| u16::from_le_bytes([b[0], b[1]]) as u32)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for BinaryFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Versions written by this build
+pub fn current_versions() -> FormatVersions {
+    BinaryFormat::ALL
+        .into_iter()
+        .map(|f| (f.name().to_string(), f.current_version()))
+        .collect()
+}
+
+/// Recorded version of `format`, or its baseline if none was recorded
+pub fn version_of(versions: &FormatVersions, format: BinaryFormat) -> u32 {
+    versions
+        .get(format.name())
+        .copied()
+        .unwrap_or_else(|| format.baseline_version())
+}
+
+/// Migration errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MigrationError {
+    /// Data is newer than this build
+    UnsupportedDowngrade { format: BinaryFormat, found: u32, current: u32 },
+    /// No upgrade registered from this version
+    MissingStep { format: BinaryFormat, from: u32 },
+    /// Record size does not match its version
+    WrongSize { format: BinaryFormat, version: u32, expected: usize, got: usize },
+    /// An upgrade step rejected the record
+    Failed { format: BinaryFormat, from: u32, message: String },
+    /// Invalid registration
+    InvalidStep(String),
+}
+
+impl std::fmt::Display for MigrationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MigrationError::UnsupportedDowngrade { format, found, current } => write!(
+                f,
+                "{} version {} is newer than supported version {}; downgrades are not supported",
+                format, found, current
+            ),
+            MigrationError::MissingStep { format, from } => {
+                write!(f, "No migration registered for {} version {}", format, from)
+            }
+            MigrationError::WrongSize { format, version, expected, got } => write!(
+                f,
+                "{} version {} record must be {} bytes, got {}",
+                format, version, expected, got
+            ),
+            MigrationError::Failed { format, from, message } => {
+                write!(f, "Migrating {} from version {} failed: {}", format, from, message)
+            }
+            MigrationError::InvalidStep(msg) => write!(f, "Invalid migration: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for MigrationError {}
+
+#[derive(Clone, Copy)]
+struct Step {
+    /// Record size at the source version
+    from_size: usize,
+    upgrade: MigrationFn,
+}
+
+/// Upgrade steps per (format, from_version)
+#[derive(Default, Clone)]
+pub struct MigrationRegistry {
+    steps: HashMap<(BinaryFormat, u32), Step>,
+}
+
+impl std::fmt::Debug for MigrationRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut steps: Vec<_> = self.steps.keys().collect();
+        steps.sort();
+        f.debug_struct("MigrationRegistry").field("steps", &steps).finish()
+    }
+}
+
+impl MigrationRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the upgrade from `from_version` (records of `from_size`
+    /// bytes) to `from_version + 1`
+    pub fn register(
+        &mut self,
+        format: BinaryFormat,
+        from_version: u32,
+        from_size: usize,
+        upgrade: MigrationFn,
+    ) -> Result<(), MigrationError> {
+        if from_version >= format.current_version() {
+            return Err(MigrationError::InvalidStep(format!(
+                "{} version {} is not older than current version {}",
+                format,
+                from_version,
+                format.current_version()
+            )));
+        }
+        if from_size == 0 {
+            return Err(MigrationError::InvalidStep("from_size must be > 0".to_string()));
+        }
+        if self.steps.contains_key(&(format, from_version)) {
+            return Err(MigrationError::InvalidStep(format!(
+                "{} version {} is already registered",
+                format, from_version
+            )));
+        }
+        self.steps.insert((format, from_version), Step { from_size, upgrade });
+        Ok(())
+    }
+
+    /// Size of a record written at `version`
+    pub fn record_size(&self, format: BinaryFormat, version: u32) -> Result<usize, MigrationError> {
+        let current = format.current_version();
+        if version == current {
+            return Ok(format.current_size());
+        }
+        if version > current {
+            return Err(MigrationError::UnsupportedDowngrade { format, found: version, current });
+        }
+        self.steps
+            .get(&(format, version))
+            .map(|step| step.from_size)
+            .ok_or(MigrationError::MissingStep { format, from: version })
+    }
+
+    /// Check that `version` can be upgraded to the current version
+    pub fn check(&self, format: BinaryFormat, version: u32) -> Result<(), MigrationError> {
+        self.record_size(format, version)?;
+        for v in version + 1..format.current_version() {
+            self.record_size(format, v)?;
+        }
+        Ok(())
+    }
+
+    /// `check` for every format in `versions` (missing = baseline)
+    pub fn check_all(&self, versions: &FormatVersions) -> Result<(), MigrationError> {
+        for name in versions.keys() {
+            if BinaryFormat::from_name(name).is_none() {
+                return Err(MigrationError::InvalidStep(format!("unknown format '{}'", name)));
+            }
+        }
+        BinaryFormat::ALL
+            .into_iter()
+            .try_for_each(|format| self.check(format, version_of(versions, format)))
+    }
+
+    /// Upgrade a record from `version` to the current version
+    pub fn migrate<'a>(
+        &self,
+        format: BinaryFormat,
+        version: u32,
+        bytes: &'a [u8],
+    ) -> Result<Cow<'a, [u8]>, MigrationError> {
+        self.check(format, version)?;
+        let expected = self.record_size(format, version)?;
+        if bytes.len() != expected {
+            return Err(MigrationError::WrongSize { format, version, expected, got: bytes.len() });
+        }
+
+        let mut data = Cow::Borrowed(bytes);
+        for from in version..format.current_version() {
+            let step = self.steps[&(format, from)];
+            if data.len() != step.from_size {
+                return Err(MigrationError::WrongSize {
+                    format,
+                    version: from,
+                    expected: step.from_size,
+                    got: data.len(),
+                });
+            }
+            let upgraded = (step.upgrade)(&data)
+                .map_err(|message| MigrationError::Failed { format, from, message })?;
+            data = Cow::Owned(upgraded);
+        }
+
+        if data.len() != format.current_size() {
+            return Err(MigrationError::WrongSize {
+                format,
+                version: format.current_version(),
+                expected: format.current_size(),
+                got: data.len(),
+            });
+        }
+        Ok(data)
+    }
+
+    /// `migrate` into a fixed-size array for `from_bytes`
+    pub fn upgrade<const N: usize>(
+        &self,
+        format: BinaryFormat,
+        version: u32,
+        bytes: &[u8],
+    ) -> Result<[u8; N], MigrationError> {
+        let data = self.migrate(format, version, bytes)?;
+        data.as_ref().try_into().map_err(|_| MigrationError::WrongSize {
+            format,
+            version: format.current_version(),
+            expected: N,
+            got: data.len(),
+        })
+    }
+}
+
+lazy_static::lazy_static! {
+    /// Process-wide registry used by checkpoint and journal loaders
+    pub static ref MIGRATIONS: RwLock<MigrationRegistry> = RwLock::new(MigrationRegistry::new());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pad_to_64(old: &[u8]) -> Result<Vec<u8>, String> {
+        let mut new = old.to_vec();
+        new.resize(64, 0);
+        Ok(new)
+    }
+
+    fn widen(old: &[u8]) -> Result<Vec<u8>, String> {
+        if old[0] == 0xFF {
+            return Err("poisoned record".to_string());
+        }
+        Ok([old, &[0u8; 8]].concat())
+    }
+
+    #[test]
+    fn test_migration_chain() {
+        let mut registry = MigrationRegistry::new();
+        // Hypothetical history: Token v0 (48 bytes) -> v1 (56) -> v2 (64)
+        registry.register(BinaryFormat::Token, 0, 48, widen).unwrap();
+        registry.register(BinaryFormat::Token, 1, 56, pad_to_64).unwrap();
+        assert!(registry.register(BinaryFormat::Token, 1, 56, pad_to_64).is_err());
+        assert!(registry.register(BinaryFormat::Token, 2, 64, pad_to_64).is_err());
+
+        let current = [7u8; 64];
+        assert!(matches!(registry.migrate(BinaryFormat::Token, 2, &current), Ok(Cow::Borrowed(_))));
+
+        let old = [1u8; 48];
+        let upgraded: [u8; 64] = registry.upgrade(BinaryFormat::Token, 0, &old).unwrap();
+        assert_eq!(&upgraded[..48], &old[..]);
+        assert!(upgraded[48..].iter().all(|b| *b == 0));
+
+        assert_eq!(
+            registry.migrate(BinaryFormat::Token, 0, &[1u8; 50]).unwrap_err(),
+            MigrationError::WrongSize { format: BinaryFormat::Token, version: 0, expected: 48, got: 50 }
+        );
+        assert!(matches!(
+            registry.migrate(BinaryFormat::Token, 0, &[0xFF; 48]),
+            Err(MigrationError::Failed { from: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_downgrades_and_missing_steps() {
+        let registry = MigrationRegistry::new();
+        let current = BinaryFormat::ConnectionV3.current_version();
+
+        assert_eq!(
+            registry.record_size(BinaryFormat::ConnectionV3, current + 1).unwrap_err(),
+            MigrationError::UnsupportedDowngrade {
+                format: BinaryFormat::ConnectionV3,
+                found: current + 1,
+                current,
+            }
+        );
+        assert_eq!(
+            registry.check(BinaryFormat::ConnectionV3, current - 1).unwrap_err(),
+            MigrationError::MissingStep { format: BinaryFormat::ConnectionV3, from: current - 1 }
+        );
+
+        // Unversioned data is read at the baseline, which this build supports
+        assert!(registry.check_all(&FormatVersions::new()).is_ok());
+        assert!(registry.check_all(&current_versions()).is_ok());
+        let mut newer = current_versions();
+        newer.insert("adna".to_string(), BinaryFormat::Adna.current_version() + 1);
+        assert!(registry.check_all(&newer).is_err());
+
+        let cdna = crate::cdna::CDNA::new().to_bytes();
+        assert_eq!(
+            BinaryFormat::Cdna.embedded_version(&cdna),
+            Some(BinaryFormat::Cdna.current_version())
+        );
+    }
+}
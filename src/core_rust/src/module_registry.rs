@@ -1,6 +1,10 @@
-use std::collections::HashMap;
-use std::sync::RwLock;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, RwLock};
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
 
 use crate::module_id::ModuleId;
 
@@ -59,6 +63,159 @@ pub struct ModuleInfo {
     pub metrics: ModuleMetrics,
 }
 
+/// Состояние жизненного цикла управляемого модуля
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LifecycleState {
+    Stopped,
+    Starting,
+    Running,
+    Stopping,
+    /// Перезапуск после сбоя
+    Restarting,
+    Failed,
+}
+
+impl LifecycleState {
+    /// Модуль запущен или находится в процессе запуска
+    pub fn is_active(&self) -> bool {
+        matches!(self, Self::Starting | Self::Running | Self::Restarting)
+    }
+}
+
+/// Уровень здоровья модуля
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthLevel {
+    Healthy,
+    Degraded,
+    Unhealthy,
+}
+
+/// Здоровье управляемого модуля
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModuleHealth {
+    pub level: HealthLevel,
+    pub message: Option<String>,
+}
+
+impl ModuleHealth {
+    pub fn healthy() -> Self {
+        Self { level: HealthLevel::Healthy, message: None }
+    }
+
+    pub fn degraded(message: impl Into<String>) -> Self {
+        Self { level: HealthLevel::Degraded, message: Some(message.into()) }
+    }
+
+    pub fn unhealthy(message: impl Into<String>) -> Self {
+        Self { level: HealthLevel::Unhealthy, message: Some(message.into()) }
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.level == HealthLevel::Healthy
+    }
+}
+
+/// Действие над управляемым модулем
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModuleAction {
+    Start,
+    Stop,
+    Restart,
+}
+
+impl FromStr for ModuleAction {
+    type Err = ModuleActionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "start" => Ok(Self::Start),
+            "stop" => Ok(Self::Stop),
+            "restart" => Ok(Self::Restart),
+            other => Err(ModuleActionError::InvalidAction(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for ModuleAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Start => write!(f, "start"),
+            Self::Stop => write!(f, "stop"),
+            Self::Restart => write!(f, "restart"),
+        }
+    }
+}
+
+/// Ошибка действия над управляемым модулем
+#[derive(Debug, Clone, PartialEq)]
+pub enum ModuleActionError {
+    NotFound(String),
+    InvalidAction(String),
+    Failed {
+        module: String,
+        action: ModuleAction,
+        message: String,
+    },
+}
+
+impl fmt::Display for ModuleActionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound(name) => write!(f, "Managed module not found: {}", name),
+            Self::InvalidAction(action) => {
+                write!(f, "Invalid module action '{}' (expected start, stop or restart)", action)
+            }
+            Self::Failed { module, action, message } => {
+                write!(f, "Failed to {} {}: {}", action, module, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ModuleActionError {}
+
+/// Хэндл жизненного цикла подсистемы (Gateway consumer, explorer, API server, ...)
+///
+/// Подсистема регистрирует хэндл через `ModuleRegistry::register_managed`,
+/// после чего ей можно управлять через `ModuleRegistry::module_action`.
+#[async_trait]
+pub trait ManagedModule: Send + Sync {
+    /// Описание для экрана модулей
+    fn description(&self) -> String {
+        String::new()
+    }
+
+    /// Текущее состояние
+    fn state(&self) -> LifecycleState;
+
+    /// Текущее здоровье
+    fn health(&self) -> ModuleHealth;
+
+    async fn start(&self) -> Result<(), String>;
+
+    async fn stop(&self) -> Result<(), String>;
+
+    /// По умолчанию: stop (если запущен) + start
+    async fn restart(&self) -> Result<(), String> {
+        if self.state().is_active() {
+            self.stop().await?;
+        }
+        self.start().await
+    }
+}
+
+/// Снимок управляемого модуля
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManagedModuleInfo {
+    pub name: String,
+    pub description: String,
+    pub state: LifecycleState,
+    pub health: ModuleHealth,
+}
+
 /// Реестр модулей
 pub struct ModuleRegistry {
     /// Флаги включения модулей
@@ -72,6 +229,9 @@ pub struct ModuleRegistry {
 
     /// Статусы модулей
     statuses: RwLock<HashMap<ModuleId, ModuleStatus>>,
+
+    /// Управляемые модули с жизненным циклом (по имени)
+    managed: RwLock<BTreeMap<String, Arc<dyn ManagedModule>>>,
 }
 
 impl ModuleRegistry {
@@ -91,6 +251,7 @@ impl ModuleRegistry {
             configs: RwLock::new(HashMap::new()),
             metrics: RwLock::new(HashMap::new()),
             statuses: RwLock::new(statuses),
+            managed: RwLock::new(BTreeMap::new()),
         }
     }
 
@@ -213,6 +374,83 @@ impl ModuleRegistry {
             },
         );
     }
+
+    /// Зарегистрировать хэндл жизненного цикла (заменяет прежний с тем же именем)
+    pub fn register_managed(&self, name: &str, module: Arc<dyn ManagedModule>) {
+        self.managed.write().unwrap().insert(name.to_string(), module);
+    }
+
+    /// Удалить хэндл жизненного цикла
+    pub fn unregister_managed(&self, name: &str) -> bool {
+        self.managed.write().unwrap().remove(name).is_some()
+    }
+
+    /// Снимок одного управляемого модуля
+    pub fn managed_module(&self, name: &str) -> Option<ManagedModuleInfo> {
+        let module = self.managed.read().unwrap().get(name).cloned()?;
+        Some(managed_info(name, module.as_ref()))
+    }
+
+    /// Снимки всех управляемых модулей (по имени)
+    pub fn managed_modules(&self) -> Vec<ManagedModuleInfo> {
+        let modules: Vec<_> = self
+            .managed
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(name, module)| (name.clone(), module.clone()))
+            .collect();
+        modules
+            .iter()
+            .map(|(name, module)| managed_info(name, module.as_ref()))
+            .collect()
+    }
+
+    /// Выполнить start/stop/restart и вернуть новое состояние модуля
+    pub async fn module_action(
+        &self,
+        name: &str,
+        action: ModuleAction,
+    ) -> Result<ManagedModuleInfo, ModuleActionError> {
+        // Хэндл клонируется, чтобы не держать блокировку через await
+        let module = self
+            .managed
+            .read()
+            .unwrap()
+            .get(name)
+            .cloned()
+            .ok_or_else(|| ModuleActionError::NotFound(name.to_string()))?;
+
+        let result = match action {
+            ModuleAction::Start => module.start().await,
+            ModuleAction::Stop => module.stop().await,
+            ModuleAction::Restart => module.restart().await,
+        };
+
+        match result {
+            Ok(()) => {
+                info!(module = %name, %action, "Module action applied");
+                Ok(managed_info(name, module.as_ref()))
+            }
+            Err(message) => {
+                warn!(module = %name, %action, error = %message, "Module action failed");
+                Err(ModuleActionError::Failed {
+                    module: name.to_string(),
+                    action,
+                    message,
+                })
+            }
+        }
+    }
+}
+
+fn managed_info(name: &str, module: &dyn ManagedModule) -> ManagedModuleInfo {
+    ManagedModuleInfo {
+        name: name.to_string(),
+        description: module.description(),
+        state: module.state(),
+        health: module.health(),
+    }
 }
 
 impl Default for ModuleRegistry {
@@ -240,3 +478,64 @@ macro_rules! check_module_enabled {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    struct TestModule {
+        state: Mutex<LifecycleState>,
+    }
+
+    #[async_trait]
+    impl ManagedModule for TestModule {
+        fn state(&self) -> LifecycleState {
+            *self.state.lock().unwrap()
+        }
+
+        fn health(&self) -> ModuleHealth {
+            ModuleHealth::healthy()
+        }
+
+        async fn start(&self) -> Result<(), String> {
+            let mut state = self.state.lock().unwrap();
+            if state.is_active() {
+                return Err("already running".to_string());
+            }
+            *state = LifecycleState::Running;
+            Ok(())
+        }
+
+        async fn stop(&self) -> Result<(), String> {
+            *self.state.lock().unwrap() = LifecycleState::Stopped;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_module_action() {
+        let registry = ModuleRegistry::new();
+        registry.register_managed(
+            "api_server",
+            Arc::new(TestModule { state: Mutex::new(LifecycleState::Running) }),
+        );
+        assert_eq!(registry.managed_modules().len(), 1);
+
+        let info = registry.module_action("api_server", ModuleAction::Stop).await.unwrap();
+        assert_eq!(info.state, LifecycleState::Stopped);
+
+        let info = registry.module_action("api_server", "RESTART".parse().unwrap()).await.unwrap();
+        assert_eq!(info.state, LifecycleState::Running);
+
+        let err = registry.module_action("api_server", ModuleAction::Start).await.unwrap_err();
+        assert!(matches!(err, ModuleActionError::Failed { .. }));
+
+        let err = registry.module_action("archiver", ModuleAction::Start).await.unwrap_err();
+        assert_eq!(err, ModuleActionError::NotFound("archiver".to_string()));
+        assert!("pause".parse::<ModuleAction>().is_err());
+
+        assert!(registry.unregister_managed("api_server"));
+        assert!(registry.managed_module("api_server").is_none());
+    }
+}
@@ -0,0 +1,465 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! NGQL v1.0 - Tiny query language over the concept graph
+//!
+//! ```text
+//! NEIGHBORS 'cat' TYPE Causal MINCONF 0.6 DEPTH 2 LIMIT 10
+//! ANALOGY king:queen::man:? LIMIT 5
+//! ```
+//!
+//! Keywords are case-insensitive, words may be quoted with `'` or `"`.
+//!
+//! - `NEIGHBORS <word>` walks Graph edges in both directions up to `DEPTH`
+//!   hops (default 1, at most [`MAX_DEPTH`]). `TYPE` keeps edges of one
+//!   category (`causal`, `semantic`, ...) or one connection type (`Cause`),
+//!   `MINCONF` drops edges lighter than the threshold. A reached concept
+//!   scores the product of the edge weights along its path.
+//! - `ANALOGY a:b::c:?` completes the analogy with
+//!   [`BootstrapLibrary::semantic_analogy`].
+//!
+//! [`parse`] turns a query string into an [`NgqlQuery`], [`execute`] runs it
+//! against a BootstrapLibrary. The Terminal (`ngql ...`), the REST API
+//! (`POST /api/v1/query/ngql`) and `neurograph-cli ngql` share this module.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+
+use crate::bootstrap::BootstrapLibrary;
+use crate::connection_v3::ConnectionType;
+use crate::graph::{Direction, NodeId};
+
+/// Maximum NEIGHBORS depth
+pub const MAX_DEPTH: usize = 5;
+
+/// Results returned when the query has no LIMIT
+pub const DEFAULT_LIMIT: usize = 20;
+
+/// Query parsing or execution error
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum NgqlError {
+    #[error("Syntax error: {0}")]
+    Syntax(String),
+
+    #[error("Unknown concept '{0}'")]
+    UnknownConcept(String),
+
+    #[error("Query failed: {0}")]
+    Failed(String),
+}
+
+/// Edge filter of a NEIGHBORS query
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TypeFilter {
+    /// Category code (high nibble of the type code)
+    Category(u8),
+    /// One connection type
+    Type(ConnectionType),
+}
+
+impl TypeFilter {
+    /// Category name (`causal`) or connection type name (`Cause`), case-insensitive
+    pub fn parse(name: &str) -> Option<Self> {
+        let types = || (0..=ConnectionType::MAX).filter_map(ConnectionType::from_u8);
+        if let Some(t) = types().find(|t| t.category().eq_ignore_ascii_case(name)) {
+            return Some(TypeFilter::Category(t as u8 >> 4));
+        }
+        types()
+            .find(|t| format!("{:?}", t).eq_ignore_ascii_case(name))
+            .map(TypeFilter::Type)
+    }
+
+    pub fn matches(&self, edge_type: u8) -> bool {
+        match self {
+            TypeFilter::Category(category) => edge_type >> 4 == *category,
+            TypeFilter::Type(t) => edge_type == *t as u8,
+        }
+    }
+}
+
+/// Parsed query
+#[derive(Debug, Clone, PartialEq)]
+pub enum NgqlQuery {
+    Neighbors {
+        word: String,
+        type_filter: Option<TypeFilter>,
+        min_conf: f32,
+        depth: usize,
+        limit: usize,
+    },
+    Analogy {
+        a: String,
+        b: String,
+        c: String,
+        limit: usize,
+    },
+}
+
+/// Concept reached by a NEIGHBORS query
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NeighborHit {
+    pub node_id: NodeId,
+    /// None for graph nodes without a concept word
+    pub word: Option<String>,
+    /// Hops from the start concept
+    pub depth: usize,
+    /// Product of edge weights along the path
+    pub confidence: f32,
+    /// Type of the last edge on the path
+    pub edge_type: String,
+}
+
+/// Candidate completing an ANALOGY query
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnalogyHit {
+    pub word: String,
+    pub score: f32,
+}
+
+/// Query result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum NgqlResult {
+    Neighbors { word: String, hits: Vec<NeighborHit> },
+    Analogy { a: String, b: String, c: String, hits: Vec<AnalogyHit> },
+}
+
+impl NgqlResult {
+    pub fn len(&self) -> usize {
+        match self {
+            NgqlResult::Neighbors { hits, .. } => hits.len(),
+            NgqlResult::Analogy { hits, .. } => hits.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl fmt::Display for NgqlResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines: Vec<String> = match self {
+            NgqlResult::Neighbors { hits, .. } => hits
+                .iter()
+                .map(|hit| {
+                    let label = hit.word.clone().unwrap_or_else(|| format!("#{}", hit.node_id));
+                    format!("{:<24} {:>7.3}  d{}  {}", label, hit.confidence, hit.depth, hit.edge_type)
+                })
+                .collect(),
+            NgqlResult::Analogy { hits, .. } => hits
+                .iter()
+                .map(|hit| format!("{:<24} {:>7.3}", hit.word, hit.score))
+                .collect(),
+        };
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+// ============================================================================
+// Parser
+// ============================================================================
+
+/// Split a query into words; quotes group words and are removed
+fn tokenize(query: &str) -> Result<Vec<String>, NgqlError> {
+    let mut tokens = Vec::new();
+    let mut current: Option<String> = None;
+    let mut quote: Option<char> = None;
+    for c in query.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => current.get_or_insert_with(String::new).push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                current.get_or_insert_with(String::new);
+            }
+            (None, c) if c.is_whitespace() => tokens.extend(current.take()),
+            (None, c) => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quote.is_some() {
+        return Err(NgqlError::Syntax("unterminated quote".to_string()));
+    }
+    tokens.extend(current);
+    Ok(tokens)
+}
+
+fn value<T: std::str::FromStr>(keyword: &str, token: Option<String>) -> Result<T, NgqlError> {
+    let token = token.ok_or_else(|| NgqlError::Syntax(format!("{} requires a value", keyword)))?;
+    token
+        .parse()
+        .map_err(|_| NgqlError::Syntax(format!("invalid {} value '{}'", keyword, token)))
+}
+
+/// `a:b::c:?` -> (a, b, c)
+fn parse_analogy(pattern: &str) -> Result<(String, String, String), NgqlError> {
+    let invalid = || NgqlError::Syntax(format!("expected a:b::c:?, got '{}'", pattern));
+    let (left, right) = pattern.split_once("::").ok_or_else(invalid)?;
+    let (a, b) = left.split_once(':').ok_or_else(invalid)?;
+    let (c, unknown) = right.split_once(':').ok_or_else(invalid)?;
+    if unknown != "?" || [a, b, c].iter().any(|w| w.is_empty()) {
+        return Err(invalid());
+    }
+    Ok((a.to_lowercase(), b.to_lowercase(), c.to_lowercase()))
+}
+
+/// Parse a query string
+pub fn parse(query: &str) -> Result<NgqlQuery, NgqlError> {
+    let mut tokens = tokenize(query)?.into_iter();
+    let verb = tokens.next().ok_or_else(|| NgqlError::Syntax("empty query".to_string()))?;
+
+    let mut query = match verb.to_uppercase().as_str() {
+        "NEIGHBORS" => {
+            let word = tokens
+                .next()
+                .ok_or_else(|| NgqlError::Syntax("NEIGHBORS requires a word".to_string()))?;
+            NgqlQuery::Neighbors {
+                word: word.to_lowercase(),
+                type_filter: None,
+                min_conf: 0.0,
+                depth: 1,
+                limit: DEFAULT_LIMIT,
+            }
+        }
+        "ANALOGY" => {
+            // Tolerate spaces inside the pattern: `king : queen :: man : ?`
+            let rest: Vec<String> = tokens.by_ref().collect();
+            let split = rest.iter().position(|t| t.eq_ignore_ascii_case("LIMIT")).unwrap_or(rest.len());
+            let (a, b, c) = parse_analogy(&rest[..split].concat())?;
+            let limit = if split < rest.len() {
+                value("LIMIT", rest.get(split + 1).cloned())?
+            } else {
+                DEFAULT_LIMIT
+            };
+            if let Some(extra) = rest.get(split + 2) {
+                return Err(NgqlError::Syntax(format!("unexpected '{}'", extra)));
+            }
+            NgqlQuery::Analogy { a, b, c, limit }
+        }
+        other => return Err(NgqlError::Syntax(format!("unknown query '{}' (NEIGHBORS or ANALOGY)", other))),
+    };
+
+    if let NgqlQuery::Neighbors { type_filter, min_conf, depth, limit, .. } = &mut query {
+        while let Some(keyword) = tokens.next() {
+            match keyword.to_uppercase().as_str() {
+                "TYPE" => {
+                    let name = tokens
+                        .next()
+                        .ok_or_else(|| NgqlError::Syntax("TYPE requires a value".to_string()))?;
+                    *type_filter = Some(
+                        TypeFilter::parse(&name)
+                            .ok_or_else(|| NgqlError::Syntax(format!("unknown connection type '{}'", name)))?,
+                    );
+                }
+                "MINCONF" => {
+                    *min_conf = value("MINCONF", tokens.next())?;
+                    if !(0.0..=1.0).contains(min_conf) {
+                        return Err(NgqlError::Syntax("MINCONF must be 0.0-1.0".to_string()));
+                    }
+                }
+                "DEPTH" => {
+                    *depth = value("DEPTH", tokens.next())?;
+                    if !(1..=MAX_DEPTH).contains(depth) {
+                        return Err(NgqlError::Syntax(format!("DEPTH must be 1-{}", MAX_DEPTH)));
+                    }
+                }
+                "LIMIT" => *limit = value("LIMIT", tokens.next())?,
+                other => return Err(NgqlError::Syntax(format!("unexpected '{}'", other))),
+            }
+        }
+    }
+    if let Some(extra) = tokens.next() {
+        return Err(NgqlError::Syntax(format!("unexpected '{}'", extra)));
+    }
+    Ok(query)
+}
+
+// ============================================================================
+// Execution
+// ============================================================================
+
+/// Run a parsed query against the concept graph
+pub fn execute(library: &BootstrapLibrary, query: &NgqlQuery) -> Result<NgqlResult, NgqlError> {
+    match query {
+        NgqlQuery::Neighbors { word, type_filter, min_conf, depth, limit } => {
+            let start = library
+                .get_concept(word)
+                .map(|c| c.id)
+                .ok_or_else(|| NgqlError::UnknownConcept(word.clone()))?;
+            let graph = library.graph();
+            let words: HashMap<NodeId, &String> = library.concepts_iter().map(|(w, c)| (c.id, w)).collect();
+
+            // Breadth-first: the first path to reach a node is one of its shortest
+            let mut visited = HashSet::from([start]);
+            let mut queue = VecDeque::from([(start, 0usize, 1.0f32)]);
+            let mut hits = Vec::new();
+            while let Some((node_id, hops, confidence)) = queue.pop_front() {
+                if hops == *depth {
+                    continue;
+                }
+                for (neighbor_id, edge_id) in graph.get_neighbors(node_id, Direction::Both) {
+                    let Some(edge) = graph.get_edge(edge_id) else { continue };
+                    if edge.weight < *min_conf || type_filter.is_some_and(|f| !f.matches(edge.edge_type)) {
+                        continue;
+                    }
+                    if !visited.insert(neighbor_id) {
+                        continue;
+                    }
+                    let confidence = confidence * edge.weight;
+                    hits.push(NeighborHit {
+                        node_id: neighbor_id,
+                        word: words.get(&neighbor_id).map(|w| (*w).clone()),
+                        depth: hops + 1,
+                        confidence,
+                        edge_type: crate::graph::export::edge_type_name(edge.edge_type),
+                    });
+                    queue.push_back((neighbor_id, hops + 1, confidence));
+                }
+            }
+            hits.sort_by(|a, b| {
+                a.depth
+                    .cmp(&b.depth)
+                    .then(b.confidence.partial_cmp(&a.confidence).unwrap_or(std::cmp::Ordering::Equal))
+            });
+            hits.truncate(*limit);
+            Ok(NgqlResult::Neighbors { word: word.clone(), hits })
+        }
+        NgqlQuery::Analogy { a, b, c, limit } => {
+            for word in [a, b, c] {
+                if library.get_concept(word).is_none() {
+                    return Err(NgqlError::UnknownConcept(word.clone()));
+                }
+            }
+            let hits = library
+                .semantic_analogy(a, b, c, *limit)
+                .map_err(|e| NgqlError::Failed(e.to_string()))?
+                .into_iter()
+                .map(|(word, score)| AnalogyHit { word, score })
+                .collect();
+            Ok(NgqlResult::Analogy { a: a.clone(), b: b.clone(), c: c.clone(), hits })
+        }
+    }
+}
+
+/// Parse and run a query string
+pub fn query(library: &BootstrapLibrary, query: &str) -> Result<NgqlResult, NgqlError> {
+    execute(library, &parse(query)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Graph;
+
+    fn library() -> BootstrapLibrary {
+        let json = r#"[
+            {"word": "king", "id": 1, "coords": [1.0, 1.0, 0.0], "color": null, "emotion": null, "sound": null, "action": null, "spatial": null},
+            {"word": "queen", "id": 2, "coords": [1.0, 2.0, 0.0], "color": null, "emotion": null, "sound": null, "action": null, "spatial": null},
+            {"word": "man", "id": 3, "coords": [3.0, 1.0, 0.0], "color": null, "emotion": null, "sound": null, "action": null, "spatial": null},
+            {"word": "woman", "id": 4, "coords": [3.0, 2.0, 0.0], "color": null, "emotion": null, "sound": null, "action": null, "spatial": null},
+            {"word": "rain", "id": 5, "coords": [9.0, 9.0, 9.0], "color": null, "emotion": null, "sound": null, "action": null, "spatial": null},
+            {"word": "wet", "id": 6, "coords": [9.5, 9.0, 9.0], "color": null, "emotion": null, "sound": null, "action": null, "spatial": null},
+            {"word": "slippery", "id": 7, "coords": [9.0, 9.5, 9.0], "color": null, "emotion": null, "sound": null, "action": null, "spatial": null}
+        ]"#;
+        let mut library = BootstrapLibrary::new(Default::default());
+        library.load_bootstrap_map_str(json).unwrap();
+
+        let id = |library: &BootstrapLibrary, word: &str| library.get_concept(word).unwrap().id;
+        let (rain, wet, slippery) = (id(&library, "rain"), id(&library, "wet"), id(&library, "slippery"));
+        let graph = library.graph_mut();
+        let cause = ConnectionType::Cause as u8;
+        for (from, to, weight) in [(rain, wet, 0.9), (wet, slippery, 0.7)] {
+            graph.add_edge(Graph::compute_edge_id(from, to, cause), from, to, cause, weight, false).unwrap();
+        }
+        library
+    }
+
+    #[test]
+    fn test_parse() {
+        let parsed = parse("neighbors 'cat' TYPE Causal MINCONF 0.6 DEPTH 2").unwrap();
+        assert_eq!(
+            parsed,
+            NgqlQuery::Neighbors {
+                word: "cat".to_string(),
+                type_filter: Some(TypeFilter::Category(0x1)),
+                min_conf: 0.6,
+                depth: 2,
+                limit: DEFAULT_LIMIT,
+            }
+        );
+        assert!(matches!(
+            parse("NEIGHBORS cat TYPE Cause").unwrap(),
+            NgqlQuery::Neighbors { type_filter: Some(TypeFilter::Type(ConnectionType::Cause)), .. }
+        ));
+        assert_eq!(
+            parse("ANALOGY king:queen::man:? LIMIT 3").unwrap(),
+            NgqlQuery::Analogy { a: "king".into(), b: "queen".into(), c: "man".into(), limit: 3 }
+        );
+        assert!(matches!(parse("ANALOGY King : queen :: man : ?").unwrap(), NgqlQuery::Analogy { .. }));
+
+        for bad in [
+            "",
+            "FIND cat",
+            "NEIGHBORS",
+            "NEIGHBORS 'cat",
+            "NEIGHBORS cat TYPE Nope",
+            "NEIGHBORS cat MINCONF 2",
+            "NEIGHBORS cat DEPTH 9",
+            "NEIGHBORS cat DEPTH",
+            "NEIGHBORS cat extra",
+            "ANALOGY king:queen::man",
+            "ANALOGY king:queen::man:woman",
+        ] {
+            assert!(matches!(parse(bad), Err(NgqlError::Syntax(_))), "{:?} should not parse", bad);
+        }
+    }
+
+    #[test]
+    fn test_execute() {
+        let library = library();
+
+        // Depth 1 only reaches direct causal neighbors
+        let result = query(&library, "NEIGHBORS rain TYPE Causal").unwrap();
+        let NgqlResult::Neighbors { hits, .. } = &result else { panic!("expected neighbors") };
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].word.as_deref(), Some("wet"));
+        assert_eq!(hits[0].edge_type, "Cause");
+
+        // Depth 2 follows the chain, confidence multiplies
+        let result = query(&library, "NEIGHBORS rain TYPE Causal DEPTH 2").unwrap();
+        let NgqlResult::Neighbors { hits, .. } = &result else { panic!("expected neighbors") };
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[1].word.as_deref(), Some("slippery"));
+        assert_eq!(hits[1].depth, 2);
+        assert!((hits[1].confidence - 0.63).abs() < 1e-5);
+        assert!(result.to_string().contains("slippery"));
+
+        // MINCONF cuts the weaker second edge
+        let result = query(&library, "NEIGHBORS rain TYPE Causal MINCONF 0.8 DEPTH 2").unwrap();
+        assert_eq!(result.len(), 1);
+
+        let result = query(&library, "ANALOGY king:queen::man:? LIMIT 1").unwrap();
+        let NgqlResult::Analogy { hits, .. } = &result else { panic!("expected analogy") };
+        assert_eq!(hits[0].word, "woman");
+
+        assert_eq!(
+            query(&library, "NEIGHBORS unicorn"),
+            Err(NgqlError::UnknownConcept("unicorn".to_string()))
+        );
+        assert!(matches!(query(&library, "ANALOGY king:queen::unicorn:?"), Err(NgqlError::UnknownConcept(_))));
+    }
+}
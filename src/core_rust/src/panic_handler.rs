@@ -3,9 +3,17 @@
 //
 // Provides panic recovery infrastructure for production resilience.
 
+use parking_lot::Mutex;
 use std::panic::{self, AssertUnwindSafe};
 use tracing::{error, warn};
 
+type FlushHook = Box<dyn Fn() + Send + Sync>;
+
+lazy_static::lazy_static! {
+    /// Hooks run by the panic hook before the process unwinds (name, hook)
+    static ref FLUSH_HOOKS: Mutex<Vec<(String, FlushHook)>> = Mutex::new(Vec::new());
+}
+
 /// Result type for panic-recoverable operations
 pub type PanicResult<T> = Result<T, PanicError>;
 
@@ -135,7 +143,7 @@ where
 }
 
 /// Extract human-readable message from panic payload
-fn extract_panic_message(payload: &Box<dyn std::any::Any + Send>) -> String {
+pub(crate) fn extract_panic_message(payload: &Box<dyn std::any::Any + Send>) -> String {
     if let Some(s) = payload.downcast_ref::<&str>() {
         s.to_string()
     } else if let Some(s) = payload.downcast_ref::<String>() {
@@ -145,6 +153,43 @@ fn extract_panic_message(payload: &Box<dyn std::any::Any + Send>) -> String {
     }
 }
 
+/// Register a hook to flush durable state when a panic occurs
+///
+/// Hooks run inside the panic hook, so they must not block: use `try_lock`
+/// on anything the panicking thread may hold. Registering a hook under an
+/// existing name replaces it.
+pub fn register_flush_hook<F>(name: &str, hook: F)
+where
+    F: Fn() + Send + Sync + 'static,
+{
+    let mut hooks = FLUSH_HOOKS.lock();
+    hooks.retain(|(existing, _)| existing != name);
+    hooks.push((name.to_string(), Box::new(hook)));
+}
+
+/// Remove a flush hook by name
+pub fn unregister_flush_hook(name: &str) {
+    FLUSH_HOOKS.lock().retain(|(existing, _)| existing != name);
+}
+
+/// Run all registered flush hooks
+///
+/// Called by the panic hook installed with [`install_panic_hook`]. Hooks must
+/// not panic: a second panic inside the panic hook aborts the process.
+/// Returns the number of hooks run.
+pub fn run_flush_hooks() -> usize {
+    // Skip if a hook is being registered on the panicking thread
+    let Some(hooks) = FLUSH_HOOKS.try_lock() else {
+        warn!("Flush hooks busy, skipping flush on panic");
+        return 0;
+    };
+
+    for (_, hook) in hooks.iter() {
+        hook();
+    }
+    hooks.len()
+}
+
 /// Install global panic hook for production
 ///
 /// This should be called once at application startup.
@@ -182,6 +227,12 @@ pub fn install_panic_hook() {
             "PANIC OCCURRED"
         );
 
+        // Flush journals before anything else can fail
+        let flushed = run_flush_hooks();
+        if flushed > 0 {
+            warn!(hooks = flushed, "Durable state flushed after panic");
+        }
+
         // Record panic in Black Box (v0.42.0)
         crate::black_box::record_event(
             crate::black_box::Event::new(crate::black_box::EventType::PanicRecovered)
@@ -286,6 +337,25 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_flush_hooks() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        register_flush_hook("test_flush_hooks", move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        run_flush_hooks();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        unregister_flush_hook("test_flush_hooks");
+        run_flush_hooks();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_catch_panic_async_success() {
         let result = catch_panic_async("async_test", || async { 42 }).await;
@@ -39,6 +39,9 @@ pub enum PersistenceError {
 
     #[error("Invalid configuration: {0}")]
     ConfigError(String),
+
+    #[error("Operation not supported by this backend: {0}")]
+    Unsupported(String),
 }
 
 /// Query options for retrieving events
@@ -86,6 +89,111 @@ impl Default for QueryOptions {
     }
 }
 
+impl QueryOptions {
+    /// Whether an event passes the filters (archive state is not checked)
+    ///
+    /// For backends that filter in memory instead of in the query.
+    pub fn matches(&self, event: &ExperienceEvent) -> bool {
+        let timestamp = event.timestamp as i64;
+        self.event_type.is_none_or(|t| event.event_type == t)
+            && self.episode_id.is_none_or(|e| event.episode_id == e)
+            && self.timestamp_start.is_none_or(|t| timestamp >= t)
+            && self.timestamp_end.is_none_or(|t| timestamp <= t)
+            && self.min_reward.is_none_or(|r| event.total_reward() >= r)
+    }
+
+    /// Filter, order and paginate events in memory
+    pub fn apply(&self, events: impl IntoIterator<Item = ExperienceEvent>) -> Vec<ExperienceEvent> {
+        let mut events: Vec<ExperienceEvent> = events.into_iter().filter(|e| self.matches(e)).collect();
+        if self.order_asc {
+            events.sort_by_key(|e| e.timestamp);
+        } else {
+            events.sort_by_key(|e| std::cmp::Reverse(e.timestamp));
+        }
+        events
+            .into_iter()
+            .skip(self.offset.unwrap_or(0) as usize)
+            .take(self.limit.map_or(usize::MAX, |l| l as usize))
+            .collect()
+    }
+}
+
+/// Retry policy for transient backend errors (exponential backoff)
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt (0 = fail immediately)
+    pub max_retries: u32,
+
+    /// Delay before the first retry
+    pub initial_backoff_ms: u64,
+
+    /// Upper bound for a single delay
+    pub max_backoff_ms: u64,
+
+    /// Delay growth per retry
+    pub multiplier: f64,
+
+    /// Random extra delay as a fraction of the backoff (0.0 - 1.0), so
+    /// clients that failed together don't retry together
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 5,
+            initial_backoff_ms: 100,
+            max_backoff_ms: 10_000,
+            multiplier: 2.0,
+            jitter: 0.1,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Never retry
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Default::default()
+        }
+    }
+
+    /// Delay before retry number `attempt` (0-based), without jitter
+    pub fn backoff(&self, attempt: u32) -> std::time::Duration {
+        let delay = self.initial_backoff_ms as f64 * self.multiplier.powi(attempt.min(64) as i32);
+        std::time::Duration::from_millis(delay.min(self.max_backoff_ms as f64) as u64)
+    }
+
+    /// `backoff` plus random jitter
+    pub fn backoff_with_jitter(&self, attempt: u32) -> std::time::Duration {
+        let base = self.backoff(attempt);
+        base + base.mul_f64(self.jitter * rand::random::<f64>())
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.multiplier.is_finite() || self.multiplier < 1.0 {
+            return Err("multiplier must be >= 1.0".to_string());
+        }
+        if self.max_backoff_ms < self.initial_backoff_ms {
+            return Err("max_backoff_ms must be >= initial_backoff_ms".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.jitter) {
+            return Err("jitter must be in [0.0, 1.0]".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Parse an environment variable, falling back to `default` if unset or invalid
+#[cfg(any(feature = "persistence", feature = "redis-cache"))]
+pub(crate) fn env_or<T: std::str::FromStr>(name: &str, default: T) -> T {
+    std::env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
 /// Main trait for persistence backends
 #[async_trait]
 pub trait PersistenceBackend: Send + Sync {
@@ -215,4 +323,64 @@ pub struct Configuration {
     pub config_value: serde_json::Value,
     pub version: i32,
     pub parent_config_id: Option<i32>,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_retry_backoff() {
+        let policy = RetryPolicy {
+            initial_backoff_ms: 100,
+            max_backoff_ms: 1_000,
+            jitter: 0.5,
+            ..Default::default()
+        };
+        assert!(policy.validate().is_ok());
+
+        let delays: Vec<u64> = (0..6).map(|a| policy.backoff(a).as_millis() as u64).collect();
+        assert_eq!(delays, vec![100, 200, 400, 800, 1_000, 1_000]);
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_millis(1_000));
+
+        let jittered = policy.backoff_with_jitter(1);
+        assert!(jittered >= Duration::from_millis(200) && jittered <= Duration::from_millis(300));
+
+        assert!(RetryPolicy { multiplier: 0.5, ..Default::default() }.validate().is_err());
+        assert!(RetryPolicy { max_backoff_ms: 10, ..Default::default() }.validate().is_err());
+        assert_eq!(RetryPolicy::none().max_retries, 0);
+    }
+
+    #[test]
+    fn test_query_options_apply() {
+        let events: Vec<ExperienceEvent> = (0..10u64)
+            .map(|i| ExperienceEvent {
+                timestamp: i * 100,
+                episode_id: i % 2,
+                reward_goal: i as f32,
+                ..Default::default()
+            })
+            .collect();
+
+        let options = QueryOptions {
+            episode_id: Some(0),
+            min_reward: Some(2.0),
+            limit: Some(2),
+            ..Default::default()
+        };
+        let timestamps: Vec<u64> = options.apply(events.clone()).iter().map(|e| e.timestamp).collect();
+        assert_eq!(timestamps, vec![800, 600]);
+
+        let options = QueryOptions {
+            timestamp_start: Some(300),
+            timestamp_end: Some(500),
+            order_asc: true,
+            offset: Some(1),
+            limit: None,
+            ..Default::default()
+        };
+        let timestamps: Vec<u64> = options.apply(events).iter().map(|e| e.timestamp).collect();
+        assert_eq!(timestamps, vec![400, 500]);
+    }
+}
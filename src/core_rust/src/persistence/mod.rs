@@ -21,13 +21,31 @@
 //! - ADNA policies and state
 //! - Configuration store
 //! - Learning metrics
+//!
+//! and an optional Redis backend for hot state (recent events, reflexes,
+//! pending requests), combined with Postgres through `TieredBackend`.
+//! `WriteBehind` moves backend writes off the hot path.
 
 pub mod backend;
+pub mod tiered;
+pub mod write_behind;
+
+#[cfg(test)]
+pub(crate) mod testing;
 
 #[cfg(feature = "persistence")]
 pub mod postgres;
 
-pub use backend::{PersistenceBackend, PersistenceError, QueryOptions, ADNAPolicy, Configuration};
+#[cfg(feature = "redis-cache")]
+pub mod redis;
+
+pub use backend::{PersistenceBackend, PersistenceError, QueryOptions, RetryPolicy, ADNAPolicy, Configuration};
 
 #[cfg(feature = "persistence")]
-pub use postgres::{PostgresBackend, PostgresConfig};
\ No newline at end of file
+pub use postgres::{PoolStats, PostgresBackend, PostgresConfig};
+
+#[cfg(feature = "redis-cache")]
+pub use self::redis::{RedisBackend, RedisConfig};
+
+pub use tiered::{TieredBackend, TieredStats};
+pub use write_behind::{DropPolicy, WriteBehind, WriteBehindConfig, WriteBehindError, WriteBehindStats, WriteOp};
@@ -0,0 +1,474 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Redis backend for hot state caching
+//!
+//! Low-latency storage for state that is read often and can be rebuilt:
+//! recent ExperienceEvents, IntuitionEngine reflex mappings and pending
+//! Gateway requests. Postgres stays the system of record; pair the two with
+//! `TieredBackend`.
+//!
+//! Only the event part of `PersistenceBackend` is implemented. Events are
+//! stored in their 128-byte binary form with a TTL and indexed by a capped
+//! list of recent ids, so queries only see the last `recent_events` events
+//! and filter in memory. Policy and configuration calls return
+//! `PersistenceError::Unsupported`.
+//!
+//! Key layout (`{prefix}` = `RedisConfig::key_prefix`):
+//!
+//! ```text
+//! {prefix}:event:{id}     - ExperienceEvent bytes
+//! {prefix}:meta:{id}      - ActionMetadata JSON
+//! {prefix}:events:recent  - list of event ids, newest first
+//! {prefix}:reflexes       - hash: shift hash -> ConnectionV3 bytes
+//! {prefix}:pending        - hash: signal id -> SignalReceipt JSON
+//! ```
+
+use super::backend::{
+    env_or, ADNAPolicy, Configuration, PersistenceBackend, PersistenceError, QueryOptions, RetryPolicy,
+};
+use crate::connection_v3::{self, ConnectionV3};
+use crate::experience_stream::{self, ActionMetadata, ExperienceBatch, ExperienceEvent};
+use crate::gateway::channels::SignalReceipt;
+use async_trait::async_trait;
+use redis::aio::{ConnectionManager, ConnectionManagerConfig};
+use redis::{AsyncCommands, Pipeline};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Redis backend configuration
+#[derive(Debug, Clone)]
+pub struct RedisConfig {
+    /// Redis connection URL
+    pub url: String,
+
+    /// Prefix for all keys, so several instances can share a database
+    pub key_prefix: String,
+
+    /// Events kept in the recent-events index
+    pub recent_events: usize,
+
+    /// Event and metadata TTL in seconds (0 = no expiry)
+    pub event_ttl: u64,
+
+    /// Connection timeout in seconds
+    pub connect_timeout: u64,
+
+    /// Response timeout in seconds
+    pub response_timeout: u64,
+
+    /// Reconnect policy
+    pub retry: RetryPolicy,
+}
+
+impl Default for RedisConfig {
+    fn default() -> Self {
+        Self {
+            url: std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379".to_string()),
+            key_prefix: "neurograph".to_string(),
+            recent_events: 10_000,
+            event_ttl: 3600,
+            connect_timeout: 5,
+            response_timeout: 2,
+            retry: RetryPolicy::default(),
+        }
+    }
+}
+
+impl RedisConfig {
+    /// Load configuration from environment variables
+    pub fn from_env() -> Result<Self, PersistenceError> {
+        let defaults = Self::default();
+        let config = Self {
+            key_prefix: std::env::var("REDIS_KEY_PREFIX").unwrap_or(defaults.key_prefix.clone()),
+            recent_events: env_or("REDIS_RECENT_EVENTS", defaults.recent_events),
+            event_ttl: env_or("REDIS_EVENT_TTL", defaults.event_ttl),
+            connect_timeout: env_or("REDIS_CONNECT_TIMEOUT", defaults.connect_timeout),
+            response_timeout: env_or("REDIS_RESPONSE_TIMEOUT", defaults.response_timeout),
+            ..defaults
+        };
+        config.validate().map_err(PersistenceError::ConfigError)?;
+        Ok(config)
+    }
+
+    /// Validate configuration
+    pub fn validate(&self) -> Result<(), String> {
+        if self.key_prefix.is_empty() {
+            return Err("key_prefix must not be empty".to_string());
+        }
+        if self.recent_events == 0 {
+            return Err("recent_events must be > 0".to_string());
+        }
+        if self.connect_timeout == 0 || self.response_timeout == 0 {
+            return Err("timeouts must be > 0".to_string());
+        }
+        self.retry.validate()
+    }
+}
+
+fn query_error(e: redis::RedisError) -> PersistenceError {
+    PersistenceError::QueryError(e.to_string())
+}
+
+fn unsupported(operation: &str) -> PersistenceError {
+    PersistenceError::Unsupported(format!("{} (Redis backend stores hot state only)", operation))
+}
+
+/// Redis backend implementation
+///
+/// Cheap to clone: clones share one multiplexed connection, which reconnects
+/// in the background with the configured backoff.
+#[derive(Clone)]
+pub struct RedisBackend {
+    conn: ConnectionManager,
+    config: RedisConfig,
+}
+
+impl RedisBackend {
+    /// Connect to Redis
+    pub async fn new(config: RedisConfig) -> Result<Self, PersistenceError> {
+        config.validate().map_err(PersistenceError::ConfigError)?;
+
+        let client = redis::Client::open(config.url.as_str())
+            .map_err(|e| PersistenceError::ConfigError(e.to_string()))?;
+        let retry = &config.retry;
+        let manager_config = ConnectionManagerConfig::new()
+            .set_number_of_retries(retry.max_retries as usize)
+            .set_factor(retry.initial_backoff_ms)
+            .set_exponent_base(retry.multiplier.round().max(1.0) as u64)
+            .set_max_delay(retry.max_backoff_ms)
+            .set_connection_timeout(Duration::from_secs(config.connect_timeout))
+            .set_response_timeout(Duration::from_secs(config.response_timeout));
+        let conn = ConnectionManager::new_with_config(client, manager_config)
+            .await
+            .map_err(|e| PersistenceError::ConnectionError(e.to_string()))?;
+
+        Ok(Self { conn, config })
+    }
+
+    /// Create from environment variables
+    pub async fn from_env() -> Result<Self, PersistenceError> {
+        Self::new(RedisConfig::from_env()?).await
+    }
+
+    pub fn config(&self) -> &RedisConfig {
+        &self.config
+    }
+
+    fn key(&self, suffix: &str) -> String {
+        format!("{}:{}", self.config.key_prefix, suffix)
+    }
+
+    fn event_key(&self, event_id: u128) -> String {
+        self.key(&format!("event:{:032x}", event_id))
+    }
+
+    fn meta_key(&self, event_id: u128) -> String {
+        self.key(&format!("meta:{:032x}", event_id))
+    }
+
+    /// Queue SET (with TTL if configured) on a pipeline
+    fn set_value(&self, pipe: &mut Pipeline, key: String, value: Vec<u8>) {
+        if self.config.event_ttl > 0 {
+            pipe.set_ex(key, value, self.config.event_ttl).ignore();
+        } else {
+            pipe.set(key, value).ignore();
+        }
+    }
+
+    /// Queue an event write and its index entry on a pipeline
+    fn push_event(&self, pipe: &mut Pipeline, event: &ExperienceEvent) {
+        self.set_value(pipe, self.event_key(event.event_id), event.to_bytes().to_vec());
+        pipe.lpush(self.key("events:recent"), format!("{:032x}", event.event_id)).ignore();
+    }
+
+    fn trim_recent(&self, pipe: &mut Pipeline) {
+        pipe.ltrim(self.key("events:recent"), 0, self.config.recent_events as isize - 1).ignore();
+    }
+
+    /// Ids in the recent-events index, newest first
+    async fn recent_ids(&self) -> Result<Vec<u128>, PersistenceError> {
+        let ids: Vec<String> = self
+            .conn
+            .clone()
+            .lrange(self.key("events:recent"), 0, -1)
+            .await
+            .map_err(query_error)?;
+        Ok(ids.iter().filter_map(|id| u128::from_str_radix(id, 16).ok()).collect())
+    }
+
+    /// Recent events that have not expired
+    async fn recent_events(&self) -> Result<Vec<ExperienceEvent>, PersistenceError> {
+        let ids = self.recent_ids().await?;
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let keys: Vec<String> = ids.iter().map(|id| self.event_key(*id)).collect();
+        let values: Vec<Option<Vec<u8>>> = self.conn.clone().mget(keys).await.map_err(query_error)?;
+        values
+            .into_iter()
+            .flatten()
+            .map(|bytes| experience_stream::validate_bytes(&bytes).map_err(PersistenceError::SerializationError))
+            .collect()
+    }
+
+    async fn read_metadata(&self, event_id: u128) -> Result<Option<ActionMetadata>, PersistenceError> {
+        let json: Option<String> = self.conn.clone().get(self.meta_key(event_id)).await.map_err(query_error)?;
+        json.map(|json| {
+            serde_json::from_str(&json).map_err(|e| PersistenceError::SerializationError(e.to_string()))
+        })
+        .transpose()
+    }
+
+    // ==================== Reflex Mappings ====================
+
+    /// Replace all stored reflexes (`IntuitionEngine::export_reflexes`)
+    pub async fn save_reflexes(&self, reflexes: &[(u64, ConnectionV3)]) -> Result<(), PersistenceError> {
+        let key = self.key("reflexes");
+        let mut pipe = redis::pipe();
+        pipe.atomic().del(&key).ignore();
+        for (hash, connection) in reflexes {
+            pipe.hset(&key, *hash, connection.to_bytes().to_vec()).ignore();
+        }
+        pipe.query_async(&mut self.conn.clone()).await.map_err(query_error)
+    }
+
+    /// Store or replace one reflex
+    pub async fn save_reflex(&self, hash: u64, connection: &ConnectionV3) -> Result<(), PersistenceError> {
+        self.conn
+            .clone()
+            .hset(self.key("reflexes"), hash, connection.to_bytes().to_vec())
+            .await
+            .map_err(query_error)
+    }
+
+    /// Remove one reflex; returns false if it was not stored
+    pub async fn remove_reflex(&self, hash: u64) -> Result<bool, PersistenceError> {
+        let removed: u64 = self.conn.clone().hdel(self.key("reflexes"), hash).await.map_err(query_error)?;
+        Ok(removed > 0)
+    }
+
+    /// All stored reflexes (`IntuitionEngine::import_reflexes`)
+    pub async fn load_reflexes(&self) -> Result<Vec<(u64, ConnectionV3)>, PersistenceError> {
+        let stored: HashMap<u64, Vec<u8>> = self.conn.clone().hgetall(self.key("reflexes")).await.map_err(query_error)?;
+        let mut reflexes = stored
+            .into_iter()
+            .map(|(hash, bytes)| {
+                connection_v3::validate_bytes(&bytes)
+                    .map(|connection| (hash, connection))
+                    .map_err(|e| PersistenceError::SerializationError(format!("reflex {:016x}: {}", hash, e)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        reflexes.sort_unstable_by_key(|(hash, _)| *hash);
+        Ok(reflexes)
+    }
+
+    // ==================== Pending Requests ====================
+
+    /// Record a request that is waiting for its result
+    pub async fn put_pending(&self, receipt: &SignalReceipt) -> Result<(), PersistenceError> {
+        let json = serde_json::to_string(receipt)
+            .map_err(|e| PersistenceError::SerializationError(e.to_string()))?;
+        self.conn
+            .clone()
+            .hset(self.key("pending"), receipt.signal_id, json)
+            .await
+            .map_err(query_error)
+    }
+
+    /// Forget a completed or cancelled request; returns false if unknown
+    pub async fn remove_pending(&self, signal_id: u64) -> Result<bool, PersistenceError> {
+        let removed: u64 = self.conn.clone().hdel(self.key("pending"), signal_id).await.map_err(query_error)?;
+        Ok(removed > 0)
+    }
+
+    /// All pending requests, oldest first
+    pub async fn pending(&self) -> Result<Vec<SignalReceipt>, PersistenceError> {
+        let stored: HashMap<u64, String> = self.conn.clone().hgetall(self.key("pending")).await.map_err(query_error)?;
+        let mut receipts = stored
+            .into_values()
+            .map(|json| serde_json::from_str(&json).map_err(|e| PersistenceError::SerializationError(e.to_string())))
+            .collect::<Result<Vec<SignalReceipt>, _>>()?;
+        receipts.sort_unstable_by_key(|r| (r.received_at, r.signal_id));
+        Ok(receipts)
+    }
+
+    /// Drop requests received more than `max_age_ms` before `now_ms`;
+    /// returns the dropped signal ids
+    pub async fn expire_pending(&self, max_age_ms: u64, now_ms: u64) -> Result<Vec<u64>, PersistenceError> {
+        let cutoff = now_ms.saturating_sub(max_age_ms);
+        let stale: Vec<u64> = self
+            .pending()
+            .await?
+            .into_iter()
+            .filter(|r| r.received_at < cutoff)
+            .map(|r| r.signal_id)
+            .collect();
+        if !stale.is_empty() {
+            let _: u64 = self.conn.clone().hdel(self.key("pending"), &stale).await.map_err(query_error)?;
+        }
+        Ok(stale)
+    }
+}
+
+#[async_trait]
+impl PersistenceBackend for RedisBackend {
+    async fn write_event(&self, event: &ExperienceEvent) -> Result<(), PersistenceError> {
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        self.push_event(&mut pipe, event);
+        self.trim_recent(&mut pipe);
+        pipe.query_async(&mut self.conn.clone()).await.map_err(query_error)
+    }
+
+    async fn write_event_with_metadata(
+        &self,
+        event: &ExperienceEvent,
+        metadata: &ActionMetadata,
+    ) -> Result<(), PersistenceError> {
+        let json = serde_json::to_vec(metadata)
+            .map_err(|e| PersistenceError::SerializationError(e.to_string()))?;
+
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        self.push_event(&mut pipe, event);
+        self.set_value(&mut pipe, self.meta_key(event.event_id), json);
+        self.trim_recent(&mut pipe);
+        pipe.query_async(&mut self.conn.clone()).await.map_err(query_error)
+    }
+
+    async fn write_batch(&self, batch: &ExperienceBatch) -> Result<(), PersistenceError> {
+        if batch.events.is_empty() {
+            return Ok(());
+        }
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        for event in &batch.events {
+            self.push_event(&mut pipe, event);
+        }
+        self.trim_recent(&mut pipe);
+        pipe.query_async(&mut self.conn.clone()).await.map_err(query_error)
+    }
+
+    async fn read_event(&self, event_id: u128) -> Result<ExperienceEvent, PersistenceError> {
+        let bytes: Option<Vec<u8>> = self.conn.clone().get(self.event_key(event_id)).await.map_err(query_error)?;
+        let bytes = bytes.ok_or_else(|| PersistenceError::NotFound(format!("Event {} not found", event_id)))?;
+        experience_stream::validate_bytes(&bytes).map_err(PersistenceError::SerializationError)
+    }
+
+    async fn read_event_with_metadata(
+        &self,
+        event_id: u128,
+    ) -> Result<(ExperienceEvent, Option<ActionMetadata>), PersistenceError> {
+        let event = self.read_event(event_id).await?;
+        Ok((event, self.read_metadata(event_id).await?))
+    }
+
+    async fn query_events(&self, options: QueryOptions) -> Result<Vec<ExperienceEvent>, PersistenceError> {
+        Ok(options.apply(self.recent_events().await?))
+    }
+
+    async fn query_events_with_metadata(
+        &self,
+        options: QueryOptions,
+    ) -> Result<Vec<(ExperienceEvent, Option<ActionMetadata>)>, PersistenceError> {
+        let mut results = Vec::new();
+        for event in self.query_events(options).await? {
+            results.push((event, self.read_metadata(event.event_id).await?));
+        }
+        Ok(results)
+    }
+
+    /// Redis has no archive: events expire after `event_ttl` instead
+    async fn archive_old_events(&self, _days_threshold: i32) -> Result<u64, PersistenceError> {
+        Ok(0)
+    }
+
+    async fn count_events(&self, options: QueryOptions) -> Result<u64, PersistenceError> {
+        let options = QueryOptions { limit: None, offset: None, ..options };
+        Ok(self.query_events(options).await?.len() as u64)
+    }
+
+    async fn health_check(&self) -> Result<(), PersistenceError> {
+        let _: String = redis::cmd("PING")
+            .query_async(&mut self.conn.clone())
+            .await
+            .map_err(|e| PersistenceError::ConnectionError(e.to_string()))?;
+        Ok(())
+    }
+
+    // ==================== ADNA Policy Management ====================
+
+    async fn save_policy(
+        &self,
+        _state_bin_id: &str,
+        _rule_id: &str,
+        _action_weights: &HashMap<u16, f64>,
+        _metadata: Option<serde_json::Value>,
+        _parent_policy_id: Option<i32>,
+    ) -> Result<i32, PersistenceError> {
+        Err(unsupported("save_policy"))
+    }
+
+    async fn get_active_policy(&self, _state_bin_id: &str) -> Result<Option<ADNAPolicy>, PersistenceError> {
+        Err(unsupported("get_active_policy"))
+    }
+
+    async fn get_all_active_policies(&self) -> Result<Vec<ADNAPolicy>, PersistenceError> {
+        Err(unsupported("get_all_active_policies"))
+    }
+
+    async fn deactivate_policy(&self, _policy_id: i32) -> Result<(), PersistenceError> {
+        Err(unsupported("deactivate_policy"))
+    }
+
+    async fn update_policy_metrics(
+        &self,
+        _policy_id: i32,
+        _total_executions: i64,
+        _avg_reward: f32,
+    ) -> Result<(), PersistenceError> {
+        Err(unsupported("update_policy_metrics"))
+    }
+
+    // ==================== Configuration Management ====================
+
+    async fn save_config(
+        &self,
+        _component_name: &str,
+        _config_key: &str,
+        _config_value: serde_json::Value,
+        _parent_config_id: Option<i32>,
+    ) -> Result<i32, PersistenceError> {
+        Err(unsupported("save_config"))
+    }
+
+    async fn get_config(
+        &self,
+        _component_name: &str,
+        _config_key: &str,
+    ) -> Result<Option<Configuration>, PersistenceError> {
+        Err(unsupported("get_config"))
+    }
+
+    async fn get_component_configs(&self, _component_name: &str) -> Result<Vec<Configuration>, PersistenceError> {
+        Err(unsupported("get_component_configs"))
+    }
+
+    async fn deactivate_config(&self, _config_id: i32) -> Result<(), PersistenceError> {
+        Err(unsupported("deactivate_config"))
+    }
+}
@@ -0,0 +1,123 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Test doubles for `PersistenceBackend`
+
+use super::{ADNAPolicy, Configuration, PersistenceBackend, PersistenceError, QueryOptions};
+use crate::experience_stream::{ActionMetadata, ExperienceBatch, ExperienceEvent};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+
+/// Configuration store only; event and policy calls are unsupported
+#[derive(Default)]
+pub(crate) struct ConfigBackend {
+    configs: Mutex<Vec<(Configuration, bool)>>,
+}
+
+fn unsupported<T>() -> Result<T, PersistenceError> {
+    Err(PersistenceError::Unsupported("test backend".to_string()))
+}
+
+#[async_trait]
+impl PersistenceBackend for ConfigBackend {
+    async fn write_event(&self, _: &ExperienceEvent) -> Result<(), PersistenceError> {
+        unsupported()
+    }
+    async fn write_event_with_metadata(&self, _: &ExperienceEvent, _: &ActionMetadata) -> Result<(), PersistenceError> {
+        unsupported()
+    }
+    async fn write_batch(&self, _: &ExperienceBatch) -> Result<(), PersistenceError> {
+        unsupported()
+    }
+    async fn read_event(&self, _: u128) -> Result<ExperienceEvent, PersistenceError> {
+        unsupported()
+    }
+    async fn read_event_with_metadata(&self, _: u128) -> Result<(ExperienceEvent, Option<ActionMetadata>), PersistenceError> {
+        unsupported()
+    }
+    async fn query_events(&self, _: QueryOptions) -> Result<Vec<ExperienceEvent>, PersistenceError> {
+        unsupported()
+    }
+    async fn query_events_with_metadata(&self, _: QueryOptions) -> Result<Vec<(ExperienceEvent, Option<ActionMetadata>)>, PersistenceError> {
+        unsupported()
+    }
+    async fn archive_old_events(&self, _: i32) -> Result<u64, PersistenceError> {
+        unsupported()
+    }
+    async fn count_events(&self, _: QueryOptions) -> Result<u64, PersistenceError> {
+        unsupported()
+    }
+    async fn health_check(&self) -> Result<(), PersistenceError> {
+        Ok(())
+    }
+    async fn save_policy(&self, _: &str, _: &str, _: &HashMap<u16, f64>, _: Option<serde_json::Value>, _: Option<i32>) -> Result<i32, PersistenceError> {
+        unsupported()
+    }
+    async fn get_active_policy(&self, _: &str) -> Result<Option<ADNAPolicy>, PersistenceError> {
+        unsupported()
+    }
+    async fn get_all_active_policies(&self) -> Result<Vec<ADNAPolicy>, PersistenceError> {
+        unsupported()
+    }
+    async fn deactivate_policy(&self, _: i32) -> Result<(), PersistenceError> {
+        unsupported()
+    }
+    async fn update_policy_metrics(&self, _: i32, _: i64, _: f32) -> Result<(), PersistenceError> {
+        unsupported()
+    }
+    async fn save_config(&self, component: &str, key: &str, value: serde_json::Value, parent: Option<i32>) -> Result<i32, PersistenceError> {
+        let mut configs = self.configs.lock();
+        for (config, active) in configs.iter_mut() {
+            if config.component_name == component && config.config_key == key {
+                *active = false;
+            }
+        }
+        let config_id = configs.len() as i32 + 1;
+        configs.push((
+            Configuration {
+                config_id,
+                component_name: component.to_string(),
+                config_key: key.to_string(),
+                config_value: value,
+                version: 1,
+                parent_config_id: parent,
+            },
+            true,
+        ));
+        Ok(config_id)
+    }
+    async fn get_config(&self, component: &str, key: &str) -> Result<Option<Configuration>, PersistenceError> {
+        Ok(self.get_component_configs(component).await?.into_iter().find(|c| c.config_key == key))
+    }
+    async fn get_component_configs(&self, component: &str) -> Result<Vec<Configuration>, PersistenceError> {
+        Ok(self
+            .configs
+            .lock()
+            .iter()
+            .filter(|(c, active)| *active && c.component_name == component)
+            .map(|(c, _)| c.clone())
+            .collect())
+    }
+    async fn deactivate_config(&self, config_id: i32) -> Result<(), PersistenceError> {
+        for (config, active) in self.configs.lock().iter_mut() {
+            if config.config_id == config_id {
+                *active = false;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,403 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Tiered persistence: a hot cache in front of a durable store
+//!
+//! `TieredBackend` pairs a durable backend (Postgres) with an optional hot
+//! one (Redis):
+//!
+//! - event writes go to the durable tier first, then to the hot tier
+//! - single-event reads try the hot tier and fall back to the durable tier
+//! - queries, counts, archiving, policies and configuration use the durable
+//!   tier only, since the hot tier holds a subset of recent events
+//!
+//! Hot tier failures are logged and never fail the call: the cache can be
+//! lost at any time without losing data.
+
+use super::backend::{ADNAPolicy, Configuration, PersistenceBackend, PersistenceError, QueryOptions};
+use crate::experience_stream::{ActionMetadata, ExperienceBatch, ExperienceEvent};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tracing::warn;
+
+/// Hot tier statistics
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TieredStats {
+    /// Reads served by the hot tier
+    pub hot_hits: u64,
+    /// Reads that fell back to the durable tier
+    pub hot_misses: u64,
+    /// Hot tier errors (logged and ignored)
+    pub hot_errors: u64,
+}
+
+/// Durable backend with an optional hot cache
+pub struct TieredBackend {
+    durable: Arc<dyn PersistenceBackend>,
+    hot: Option<Arc<dyn PersistenceBackend>>,
+    hot_hits: AtomicU64,
+    hot_misses: AtomicU64,
+    hot_errors: AtomicU64,
+}
+
+impl TieredBackend {
+    /// Durable tier only; add a cache with `with_hot`
+    pub fn new(durable: Arc<dyn PersistenceBackend>) -> Self {
+        Self {
+            durable,
+            hot: None,
+            hot_hits: AtomicU64::new(0),
+            hot_misses: AtomicU64::new(0),
+            hot_errors: AtomicU64::new(0),
+        }
+    }
+
+    /// Put a hot tier in front of the durable one
+    pub fn with_hot(mut self, hot: Arc<dyn PersistenceBackend>) -> Self {
+        self.hot = Some(hot);
+        self
+    }
+
+    pub fn durable(&self) -> &Arc<dyn PersistenceBackend> {
+        &self.durable
+    }
+
+    pub fn hot(&self) -> Option<&Arc<dyn PersistenceBackend>> {
+        self.hot.as_ref()
+    }
+
+    pub fn stats(&self) -> TieredStats {
+        TieredStats {
+            hot_hits: self.hot_hits.load(Ordering::Relaxed),
+            hot_misses: self.hot_misses.load(Ordering::Relaxed),
+            hot_errors: self.hot_errors.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Log a hot tier error and carry on
+    fn hot_failed(&self, operation: &str, error: &PersistenceError) {
+        self.hot_errors.fetch_add(1, Ordering::Relaxed);
+        warn!(error = %error, "Hot tier {} failed", operation);
+    }
+
+    fn hot_result<T>(&self, operation: &str, result: Result<T, PersistenceError>) -> Option<T> {
+        match result {
+            Ok(value) => {
+                self.hot_hits.fetch_add(1, Ordering::Relaxed);
+                Some(value)
+            }
+            Err(PersistenceError::NotFound(_)) => {
+                self.hot_misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+            Err(e) => {
+                self.hot_misses.fetch_add(1, Ordering::Relaxed);
+                self.hot_failed(operation, &e);
+                None
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl PersistenceBackend for TieredBackend {
+    async fn write_event(&self, event: &ExperienceEvent) -> Result<(), PersistenceError> {
+        self.durable.write_event(event).await?;
+        if let Some(hot) = &self.hot {
+            if let Err(e) = hot.write_event(event).await {
+                self.hot_failed("write_event", &e);
+            }
+        }
+        Ok(())
+    }
+
+    async fn write_event_with_metadata(
+        &self,
+        event: &ExperienceEvent,
+        metadata: &ActionMetadata,
+    ) -> Result<(), PersistenceError> {
+        self.durable.write_event_with_metadata(event, metadata).await?;
+        if let Some(hot) = &self.hot {
+            if let Err(e) = hot.write_event_with_metadata(event, metadata).await {
+                self.hot_failed("write_event_with_metadata", &e);
+            }
+        }
+        Ok(())
+    }
+
+    async fn write_batch(&self, batch: &ExperienceBatch) -> Result<(), PersistenceError> {
+        self.durable.write_batch(batch).await?;
+        if let Some(hot) = &self.hot {
+            if let Err(e) = hot.write_batch(batch).await {
+                self.hot_failed("write_batch", &e);
+            }
+        }
+        Ok(())
+    }
+
+    async fn read_event(&self, event_id: u128) -> Result<ExperienceEvent, PersistenceError> {
+        if let Some(hot) = &self.hot {
+            if let Some(event) = self.hot_result("read_event", hot.read_event(event_id).await) {
+                return Ok(event);
+            }
+        }
+        self.durable.read_event(event_id).await
+    }
+
+    async fn read_event_with_metadata(
+        &self,
+        event_id: u128,
+    ) -> Result<(ExperienceEvent, Option<ActionMetadata>), PersistenceError> {
+        if let Some(hot) = &self.hot {
+            let result = hot.read_event_with_metadata(event_id).await;
+            if let Some(found) = self.hot_result("read_event_with_metadata", result) {
+                return Ok(found);
+            }
+        }
+        self.durable.read_event_with_metadata(event_id).await
+    }
+
+    async fn query_events(&self, options: QueryOptions) -> Result<Vec<ExperienceEvent>, PersistenceError> {
+        self.durable.query_events(options).await
+    }
+
+    async fn query_events_with_metadata(
+        &self,
+        options: QueryOptions,
+    ) -> Result<Vec<(ExperienceEvent, Option<ActionMetadata>)>, PersistenceError> {
+        self.durable.query_events_with_metadata(options).await
+    }
+
+    async fn archive_old_events(&self, days_threshold: i32) -> Result<u64, PersistenceError> {
+        self.durable.archive_old_events(days_threshold).await
+    }
+
+    async fn count_events(&self, options: QueryOptions) -> Result<u64, PersistenceError> {
+        self.durable.count_events(options).await
+    }
+
+    /// Fails only if the durable tier is unhealthy
+    async fn health_check(&self) -> Result<(), PersistenceError> {
+        if let Some(hot) = &self.hot {
+            if let Err(e) = hot.health_check().await {
+                self.hot_failed("health_check", &e);
+            }
+        }
+        self.durable.health_check().await
+    }
+
+    // ==================== ADNA Policy Management ====================
+
+    async fn save_policy(
+        &self,
+        state_bin_id: &str,
+        rule_id: &str,
+        action_weights: &HashMap<u16, f64>,
+        metadata: Option<serde_json::Value>,
+        parent_policy_id: Option<i32>,
+    ) -> Result<i32, PersistenceError> {
+        self.durable
+            .save_policy(state_bin_id, rule_id, action_weights, metadata, parent_policy_id)
+            .await
+    }
+
+    async fn get_active_policy(&self, state_bin_id: &str) -> Result<Option<ADNAPolicy>, PersistenceError> {
+        self.durable.get_active_policy(state_bin_id).await
+    }
+
+    async fn get_all_active_policies(&self) -> Result<Vec<ADNAPolicy>, PersistenceError> {
+        self.durable.get_all_active_policies().await
+    }
+
+    async fn deactivate_policy(&self, policy_id: i32) -> Result<(), PersistenceError> {
+        self.durable.deactivate_policy(policy_id).await
+    }
+
+    async fn update_policy_metrics(
+        &self,
+        policy_id: i32,
+        total_executions: i64,
+        avg_reward: f32,
+    ) -> Result<(), PersistenceError> {
+        self.durable
+            .update_policy_metrics(policy_id, total_executions, avg_reward)
+            .await
+    }
+
+    // ==================== Configuration Management ====================
+
+    async fn save_config(
+        &self,
+        component_name: &str,
+        config_key: &str,
+        config_value: serde_json::Value,
+        parent_config_id: Option<i32>,
+    ) -> Result<i32, PersistenceError> {
+        self.durable
+            .save_config(component_name, config_key, config_value, parent_config_id)
+            .await
+    }
+
+    async fn get_config(
+        &self,
+        component_name: &str,
+        config_key: &str,
+    ) -> Result<Option<Configuration>, PersistenceError> {
+        self.durable.get_config(component_name, config_key).await
+    }
+
+    async fn get_component_configs(&self, component_name: &str) -> Result<Vec<Configuration>, PersistenceError> {
+        self.durable.get_component_configs(component_name).await
+    }
+
+    async fn deactivate_config(&self, config_id: i32) -> Result<(), PersistenceError> {
+        self.durable.deactivate_config(config_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+
+    /// In-memory event store; `fail` makes every call error
+    #[derive(Default)]
+    struct MemoryBackend {
+        events: Mutex<HashMap<u128, ExperienceEvent>>,
+        fail: bool,
+    }
+
+    impl MemoryBackend {
+        fn check(&self) -> Result<(), PersistenceError> {
+            if self.fail {
+                Err(PersistenceError::ConnectionError("down".to_string()))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[async_trait]
+    impl PersistenceBackend for MemoryBackend {
+        async fn write_event(&self, event: &ExperienceEvent) -> Result<(), PersistenceError> {
+            self.check()?;
+            self.events.lock().insert(event.event_id, *event);
+            Ok(())
+        }
+        async fn write_event_with_metadata(&self, event: &ExperienceEvent, _: &ActionMetadata) -> Result<(), PersistenceError> {
+            self.write_event(event).await
+        }
+        async fn write_batch(&self, batch: &ExperienceBatch) -> Result<(), PersistenceError> {
+            for event in &batch.events {
+                self.write_event(event).await?;
+            }
+            Ok(())
+        }
+        async fn read_event(&self, event_id: u128) -> Result<ExperienceEvent, PersistenceError> {
+            self.check()?;
+            self.events.lock().get(&event_id).copied().ok_or_else(|| PersistenceError::NotFound(event_id.to_string()))
+        }
+        async fn read_event_with_metadata(&self, event_id: u128) -> Result<(ExperienceEvent, Option<ActionMetadata>), PersistenceError> {
+            Ok((self.read_event(event_id).await?, None))
+        }
+        async fn query_events(&self, options: QueryOptions) -> Result<Vec<ExperienceEvent>, PersistenceError> {
+            self.check()?;
+            Ok(options.apply(self.events.lock().values().copied()))
+        }
+        async fn query_events_with_metadata(&self, options: QueryOptions) -> Result<Vec<(ExperienceEvent, Option<ActionMetadata>)>, PersistenceError> {
+            Ok(self.query_events(options).await?.into_iter().map(|e| (e, None)).collect())
+        }
+        async fn archive_old_events(&self, _: i32) -> Result<u64, PersistenceError> {
+            Ok(0)
+        }
+        async fn count_events(&self, options: QueryOptions) -> Result<u64, PersistenceError> {
+            Ok(self.query_events(options).await?.len() as u64)
+        }
+        async fn health_check(&self) -> Result<(), PersistenceError> {
+            self.check()
+        }
+        async fn save_policy(&self, _: &str, _: &str, _: &HashMap<u16, f64>, _: Option<serde_json::Value>, _: Option<i32>) -> Result<i32, PersistenceError> {
+            Ok(1)
+        }
+        async fn get_active_policy(&self, _: &str) -> Result<Option<ADNAPolicy>, PersistenceError> {
+            Ok(None)
+        }
+        async fn get_all_active_policies(&self) -> Result<Vec<ADNAPolicy>, PersistenceError> {
+            Ok(Vec::new())
+        }
+        async fn deactivate_policy(&self, _: i32) -> Result<(), PersistenceError> {
+            Ok(())
+        }
+        async fn update_policy_metrics(&self, _: i32, _: i64, _: f32) -> Result<(), PersistenceError> {
+            Ok(())
+        }
+        async fn save_config(&self, _: &str, _: &str, _: serde_json::Value, _: Option<i32>) -> Result<i32, PersistenceError> {
+            Ok(1)
+        }
+        async fn get_config(&self, _: &str, _: &str) -> Result<Option<Configuration>, PersistenceError> {
+            Ok(None)
+        }
+        async fn get_component_configs(&self, _: &str) -> Result<Vec<Configuration>, PersistenceError> {
+            Ok(Vec::new())
+        }
+        async fn deactivate_config(&self, _: i32) -> Result<(), PersistenceError> {
+            Ok(())
+        }
+    }
+
+    fn event(id: u128) -> ExperienceEvent {
+        ExperienceEvent { event_id: id, ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn test_reads_hit_hot_then_durable() {
+        let durable = Arc::new(MemoryBackend::default());
+        let hot = Arc::new(MemoryBackend::default());
+        let tiered = TieredBackend::new(durable.clone()).with_hot(hot.clone());
+
+        tiered.write_event(&event(1)).await.unwrap();
+        assert!(hot.events.lock().contains_key(&1));
+        assert!(durable.events.lock().contains_key(&1));
+
+        // Evicted from the cache: served by the durable tier
+        hot.events.lock().clear();
+        assert_eq!(tiered.read_event(1).await.unwrap().event_id, 1);
+        tiered.write_event(&event(2)).await.unwrap();
+        assert_eq!(tiered.read_event(2).await.unwrap().event_id, 2);
+
+        assert_eq!(tiered.stats(), TieredStats { hot_hits: 1, hot_misses: 1, hot_errors: 0 });
+        assert!(matches!(tiered.read_event(3).await, Err(PersistenceError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_hot_failures_are_ignored() {
+        let durable = Arc::new(MemoryBackend::default());
+        let hot = Arc::new(MemoryBackend { fail: true, ..Default::default() });
+        let tiered = TieredBackend::new(durable.clone()).with_hot(hot);
+
+        tiered.write_event(&event(1)).await.unwrap();
+        assert_eq!(tiered.read_event(1).await.unwrap().event_id, 1);
+        assert!(tiered.health_check().await.is_ok());
+        assert_eq!(tiered.count_events(QueryOptions::default()).await.unwrap(), 1);
+        assert_eq!(tiered.stats().hot_errors, 3);
+
+        // Durable failures are not hidden
+        let tiered = TieredBackend::new(Arc::new(MemoryBackend { fail: true, ..Default::default() }));
+        assert!(tiered.write_event(&event(1)).await.is_err());
+    }
+}
@@ -0,0 +1,899 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Write-behind persistence worker
+//!
+//! Keeps `PersistenceBackend` latency out of the hot path: producers push
+//! `WriteOp`s into a bounded in-memory queue and return immediately, and a
+//! background task writes them in batches (consecutive events become one
+//! `write_batch` call).
+//!
+//! ```text
+//! ExperienceStream ──┐
+//! learning / ADNA  ──┼─> WriteBehind queue ──> worker ──> PersistenceBackend
+//! config changes   ──┘   (capacity, max_lag)   (batch_size, flush_interval)
+//! ```
+//!
+//! The queue is bounded in size (`capacity`) and in lag (`max_lag`, the age
+//! of the oldest queued operation). When a bound is hit, `DropPolicy`
+//! decides: block producers, reject new operations, or drop the oldest
+//! ones. Graph-level state without a table of its own (ADNA updates,
+//! serialized subgraphs) goes through the versioned config store via
+//! `WriteOp::Config`.
+//!
+//! `flush` waits until everything queued before the call is written;
+//! `WriteBehind` is also a `CheckpointHook`, so registering it with the
+//! `CheckpointManager` flushes the queue before every checkpoint. Lag and
+//! drop counters are in `stats()` and the `neurograph_write_behind_*`
+//! Prometheus metrics.
+
+use super::backend::{PersistenceBackend, PersistenceError, RetryPolicy};
+use crate::checkpoint::CheckpointHook;
+use crate::experience_stream::{ActionMetadata, ExperienceBatch, ExperienceEvent, ExperienceStream};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+use thiserror::Error;
+use tokio::sync::{broadcast, watch, Notify};
+use tokio::task::JoinHandle;
+use tracing::{error, warn};
+
+/// Operation to persist
+#[derive(Debug, Clone)]
+pub enum WriteOp {
+    Event(ExperienceEvent),
+    EventWithMetadata(ExperienceEvent, ActionMetadata),
+    PolicyMetrics {
+        policy_id: i32,
+        total_executions: i64,
+        avg_reward: f32,
+    },
+    Config {
+        component: String,
+        key: String,
+        value: serde_json::Value,
+    },
+}
+
+/// What to do when the queue is full or lagging more than `max_lag`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DropPolicy {
+    /// `enqueue` waits for room; `try_enqueue` fails
+    Block,
+    /// Reject the new operation
+    DropNewest,
+    /// Drop the oldest queued operations to make room
+    DropOldest,
+}
+
+/// Write-behind configuration
+#[derive(Debug, Clone)]
+pub struct WriteBehindConfig {
+    /// Maximum queued operations
+    pub capacity: usize,
+
+    /// Maximum operations per backend call
+    pub batch_size: usize,
+
+    /// Write a partial batch once its oldest operation is this old
+    pub flush_interval: Duration,
+
+    /// Maximum age of the oldest queued operation
+    pub max_lag: Duration,
+
+    pub drop_policy: DropPolicy,
+
+    /// Retries for failed backend calls before the operations are dropped
+    pub retry: RetryPolicy,
+}
+
+impl Default for WriteBehindConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 10_000,
+            batch_size: 500,
+            flush_interval: Duration::from_millis(50),
+            max_lag: Duration::from_secs(5),
+            drop_policy: DropPolicy::DropOldest,
+            retry: RetryPolicy::default(),
+        }
+    }
+}
+
+impl WriteBehindConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.capacity == 0 {
+            return Err("capacity must be > 0".to_string());
+        }
+        if self.batch_size == 0 || self.batch_size > self.capacity {
+            return Err("batch_size must be in 1..=capacity".to_string());
+        }
+        if self.max_lag < self.flush_interval {
+            return Err("max_lag must be >= flush_interval".to_string());
+        }
+        self.retry.validate()
+    }
+}
+
+/// Write-behind errors
+#[derive(Debug, Error)]
+pub enum WriteBehindError {
+    #[error("Write-behind queue is full ({0} operations)")]
+    QueueFull(usize),
+
+    #[error("Write-behind lag {0:?} exceeds the limit")]
+    LagExceeded(Duration),
+
+    #[error("Write-behind worker is stopped")]
+    Closed,
+
+    #[error("{0} operations could not be persisted")]
+    Failed(u64),
+
+    #[error("Invalid configuration: {0}")]
+    Config(String),
+}
+
+/// Queue and throughput statistics
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WriteBehindStats {
+    /// Operations waiting in the queue
+    pub queued: usize,
+    /// Age of the oldest queued operation
+    pub lag: Duration,
+    /// Highest lag seen by the worker
+    pub max_lag: Duration,
+    pub enqueued: u64,
+    pub written: u64,
+    pub batches: u64,
+    /// Dropped because the queue was full
+    pub dropped_full: u64,
+    /// Dropped because they exceeded `max_lag`
+    pub dropped_lag: u64,
+    /// Missed by `attach_stream` because the broadcast channel overflowed
+    pub dropped_stream: u64,
+    /// Given up after retries
+    pub failed: u64,
+}
+
+struct Queued {
+    seq: u64,
+    at: Instant,
+    op: WriteOp,
+}
+
+struct Queue {
+    ops: VecDeque<Queued>,
+    next_seq: u64,
+    /// First sequence number of the batch being written
+    in_flight: Option<u64>,
+    flush_requested: bool,
+}
+
+impl Queue {
+    /// Every operation up to this sequence number is written, failed or dropped
+    fn completed(&self) -> u64 {
+        self.in_flight
+            .or(self.ops.front().map(|q| q.seq))
+            .unwrap_or(self.next_seq)
+            - 1
+    }
+
+    fn lag(&self) -> Duration {
+        self.ops.front().map_or(Duration::ZERO, |q| q.at.elapsed())
+    }
+}
+
+#[derive(Default)]
+struct Counters {
+    enqueued: AtomicU64,
+    written: AtomicU64,
+    batches: AtomicU64,
+    dropped_full: AtomicU64,
+    dropped_lag: AtomicU64,
+    dropped_stream: AtomicU64,
+    failed: AtomicU64,
+    max_lag_us: AtomicU64,
+}
+
+struct Shared {
+    config: WriteBehindConfig,
+    queue: Mutex<Queue>,
+    /// Latest `Queue::completed`
+    done: watch::Sender<u64>,
+    /// Wakes the worker
+    wake: Notify,
+    /// Wakes producers blocked on a full queue
+    space: Notify,
+    closed: AtomicBool,
+    counters: Counters,
+}
+
+impl Shared {
+    fn publish_completed(&self, queue: &Queue) {
+        self.done.send_if_modified(|done| {
+            let completed = queue.completed();
+            let changed = completed > *done;
+            *done = (*done).max(completed);
+            changed
+        });
+        crate::metrics::WRITE_BEHIND_QUEUE_LEN.set(queue.ops.len() as i64);
+        crate::metrics::WRITE_BEHIND_LAG_SECONDS.set(queue.lag().as_secs_f64());
+    }
+
+    /// Drop operations older than `max_lag` (DropOldest only)
+    fn expire(&self, queue: &mut Queue) {
+        if self.config.drop_policy != DropPolicy::DropOldest {
+            return;
+        }
+        let mut expired = 0;
+        while queue.ops.front().is_some_and(|q| q.at.elapsed() > self.config.max_lag) {
+            queue.ops.pop_front();
+            expired += 1;
+        }
+        if expired > 0 {
+            self.counters.dropped_lag.fetch_add(expired, Ordering::Relaxed);
+            crate::metrics::WRITE_BEHIND_DROPPED.with_label_values(&["lag"]).inc_by(expired);
+        }
+    }
+
+    /// Queue `op`, or hand it back (boxed, it is large) with the reason
+    fn push(&self, op: WriteOp) -> Result<(), (WriteBehindError, Box<WriteOp>)> {
+        if self.closed.load(Ordering::Acquire) {
+            return Err((WriteBehindError::Closed, Box::new(op)));
+        }
+
+        let mut queue = self.queue.lock();
+        self.expire(&mut queue);
+
+        let lag = queue.lag();
+        if self.config.drop_policy != DropPolicy::DropOldest && lag > self.config.max_lag {
+            self.reject("lag");
+            return Err((WriteBehindError::LagExceeded(lag), Box::new(op)));
+        }
+        if queue.ops.len() >= self.config.capacity {
+            if self.config.drop_policy != DropPolicy::DropOldest {
+                self.reject("queue_full");
+                return Err((WriteBehindError::QueueFull(queue.ops.len()), Box::new(op)));
+            }
+            queue.ops.pop_front();
+            self.counters.dropped_full.fetch_add(1, Ordering::Relaxed);
+            crate::metrics::WRITE_BEHIND_DROPPED.with_label_values(&["queue_full"]).inc();
+        }
+
+        let seq = queue.next_seq;
+        queue.next_seq += 1;
+        queue.ops.push_back(Queued { seq, at: Instant::now(), op });
+        self.counters.enqueued.fetch_add(1, Ordering::Relaxed);
+        let wake = queue.ops.len() >= self.config.batch_size || queue.ops.len() == 1;
+        self.publish_completed(&queue);
+        drop(queue);
+
+        if wake {
+            self.wake.notify_one();
+        }
+        Ok(())
+    }
+
+    /// Count a rejected operation (Block rejections are retried, not lost)
+    fn reject(&self, reason: &str) {
+        if self.config.drop_policy == DropPolicy::DropNewest {
+            match reason {
+                "lag" => self.counters.dropped_lag.fetch_add(1, Ordering::Relaxed),
+                _ => self.counters.dropped_full.fetch_add(1, Ordering::Relaxed),
+            };
+            crate::metrics::WRITE_BEHIND_DROPPED.with_label_values(&[reason]).inc();
+        }
+    }
+}
+
+/// Write-behind queue handle (cheap to clone)
+#[derive(Clone)]
+pub struct WriteBehind {
+    shared: Arc<Shared>,
+    worker: Arc<Mutex<Option<JoinHandle<()>>>>,
+}
+
+impl WriteBehind {
+    /// Start the background worker (requires a tokio runtime)
+    pub fn spawn(backend: Arc<dyn PersistenceBackend>, config: WriteBehindConfig) -> Result<Self, WriteBehindError> {
+        config.validate().map_err(WriteBehindError::Config)?;
+
+        let shared = Arc::new(Shared {
+            config,
+            queue: Mutex::new(Queue {
+                ops: VecDeque::new(),
+                next_seq: 1,
+                in_flight: None,
+                flush_requested: false,
+            }),
+            done: watch::channel(0).0,
+            wake: Notify::new(),
+            space: Notify::new(),
+            closed: AtomicBool::new(false),
+            counters: Counters::default(),
+        });
+        let worker = tokio::spawn(Worker { shared: shared.clone(), backend }.run());
+
+        Ok(Self {
+            shared,
+            worker: Arc::new(Mutex::new(Some(worker))),
+        })
+    }
+
+    pub fn config(&self) -> &WriteBehindConfig {
+        &self.shared.config
+    }
+
+    /// Queue an operation without waiting
+    pub fn try_enqueue(&self, op: WriteOp) -> Result<(), WriteBehindError> {
+        self.shared.push(op).map_err(|(e, _)| e)
+    }
+
+    /// Queue an operation; with `DropPolicy::Block` waits for room
+    pub async fn enqueue(&self, mut op: WriteOp) -> Result<(), WriteBehindError> {
+        loop {
+            let space = self.shared.space.notified();
+            tokio::pin!(space);
+            space.as_mut().enable();
+
+            match self.shared.push(op) {
+                Err((WriteBehindError::QueueFull(_) | WriteBehindError::LagExceeded(_), rejected))
+                    if self.shared.config.drop_policy == DropPolicy::Block =>
+                {
+                    op = *rejected;
+                    space.await;
+                }
+                result => return result.map_err(|(e, _)| e),
+            }
+        }
+    }
+
+    /// Queue an ExperienceEvent without waiting
+    pub fn write_event(&self, event: ExperienceEvent) -> Result<(), WriteBehindError> {
+        self.try_enqueue(WriteOp::Event(event))
+    }
+
+    /// Persist every event written to `stream`
+    ///
+    /// Stops when the stream is dropped or the worker is shut down. Events
+    /// missed because the broadcast channel overflowed are counted in
+    /// `dropped_stream`.
+    pub fn attach_stream(&self, stream: &ExperienceStream) -> JoinHandle<()> {
+        let mut receiver = stream.subscribe();
+        let writer = self.clone();
+        tokio::spawn(async move {
+            loop {
+                match receiver.recv().await {
+                    // Other rejections are counted by the queue
+                    Ok(event) => {
+                        if let Err(WriteBehindError::Closed) = writer.enqueue(WriteOp::Event(event)).await {
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        writer.shared.counters.dropped_stream.fetch_add(missed, Ordering::Relaxed);
+                        crate::metrics::WRITE_BEHIND_DROPPED
+                            .with_label_values(&["stream_lagged"])
+                            .inc_by(missed);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// Wait until every operation queued before this call is processed
+    ///
+    /// Fails if some of them could not be persisted.
+    pub async fn flush(&self) -> Result<(), WriteBehindError> {
+        let failed_before = self.shared.counters.failed.load(Ordering::Relaxed);
+        let target = {
+            let mut queue = self.shared.queue.lock();
+            queue.flush_requested = true;
+            queue.next_seq - 1
+        };
+        self.shared.wake.notify_one();
+
+        let mut done = self.shared.done.subscribe();
+        if *done.borrow() < target && self.worker.lock().as_ref().is_none_or(|w| w.is_finished()) {
+            return Err(WriteBehindError::Closed);
+        }
+        done.wait_for(|completed| *completed >= target)
+            .await
+            .map_err(|_| WriteBehindError::Closed)?;
+
+        match self.shared.counters.failed.load(Ordering::Relaxed) - failed_before {
+            0 => Ok(()),
+            failed => Err(WriteBehindError::Failed(failed)),
+        }
+    }
+
+    /// Stop accepting operations, write what is queued and stop the worker
+    pub async fn shutdown(&self) {
+        self.shared.closed.store(true, Ordering::Release);
+        self.shared.wake.notify_one();
+        self.shared.space.notify_waiters();
+
+        let worker = self.worker.lock().take();
+        if let Some(worker) = worker {
+            if let Err(e) = worker.await {
+                error!("Write-behind worker panicked: {}", e);
+            }
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.shared.closed.load(Ordering::Acquire)
+    }
+
+    pub fn stats(&self) -> WriteBehindStats {
+        let (queued, lag) = {
+            let queue = self.shared.queue.lock();
+            (queue.ops.len(), queue.lag())
+        };
+        let c = &self.shared.counters;
+        WriteBehindStats {
+            queued,
+            lag,
+            max_lag: Duration::from_micros(c.max_lag_us.load(Ordering::Relaxed)),
+            enqueued: c.enqueued.load(Ordering::Relaxed),
+            written: c.written.load(Ordering::Relaxed),
+            batches: c.batches.load(Ordering::Relaxed),
+            dropped_full: c.dropped_full.load(Ordering::Relaxed),
+            dropped_lag: c.dropped_lag.load(Ordering::Relaxed),
+            dropped_stream: c.dropped_stream.load(Ordering::Relaxed),
+            failed: c.failed.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[async_trait]
+impl CheckpointHook for WriteBehind {
+    fn name(&self) -> &'static str {
+        "write_behind"
+    }
+
+    async fn before_checkpoint(&self) -> Result<(), String> {
+        self.flush().await.map_err(|e| e.to_string())
+    }
+}
+
+/// Background writer task
+struct Worker {
+    shared: Arc<Shared>,
+    backend: Arc<dyn PersistenceBackend>,
+}
+
+impl Worker {
+    async fn run(self) {
+        while let Some(batch) = self.next_batch().await {
+            self.write(batch).await;
+
+            let mut queue = self.shared.queue.lock();
+            queue.in_flight = None;
+            self.shared.publish_completed(&queue);
+        }
+    }
+
+    /// Wait for a batch to be due; None once closed and drained
+    async fn next_batch(&self) -> Option<Vec<WriteOp>> {
+        let config = &self.shared.config;
+        loop {
+            let wait = {
+                let mut queue = self.shared.queue.lock();
+                self.shared.expire(&mut queue);
+
+                let closed = self.shared.closed.load(Ordering::Acquire);
+                if queue.ops.is_empty() {
+                    queue.flush_requested = false;
+                    self.shared.publish_completed(&queue);
+                    if closed {
+                        return None;
+                    }
+                    None
+                } else {
+                    let lag = queue.lag();
+                    let due = queue.ops.len() >= config.batch_size
+                        || lag >= config.flush_interval
+                        || queue.flush_requested
+                        || closed;
+                    if due {
+                        let take = queue.ops.len().min(config.batch_size);
+                        let batch: Vec<Queued> = queue.ops.drain(..take).collect();
+                        queue.in_flight = Some(batch[0].seq);
+                        self.shared.counters.max_lag_us.fetch_max(lag.as_micros() as u64, Ordering::Relaxed);
+                        self.shared.publish_completed(&queue);
+                        drop(queue);
+                        self.shared.space.notify_waiters();
+                        return Some(batch.into_iter().map(|q| q.op).collect());
+                    }
+                    Some(config.flush_interval - lag)
+                }
+            };
+
+            match wait {
+                Some(delay) => {
+                    let _ = tokio::time::timeout(delay, self.shared.wake.notified()).await;
+                }
+                None => self.shared.wake.notified().await,
+            }
+        }
+    }
+
+    /// Write a batch: runs of events as one `write_batch`, the rest one by one
+    async fn write(&self, batch: Vec<WriteOp>) {
+        let mut events = Vec::new();
+        for op in batch {
+            match op {
+                WriteOp::Event(event) => events.push(event),
+                op => {
+                    self.write_events(&mut events).await;
+                    self.write_op(&op).await;
+                }
+            }
+        }
+        self.write_events(&mut events).await;
+        self.shared.counters.batches.fetch_add(1, Ordering::Relaxed);
+    }
+
+    async fn write_events(&self, events: &mut Vec<ExperienceEvent>) {
+        if events.is_empty() {
+            return;
+        }
+        let batch = ExperienceBatch {
+            events: std::mem::take(events),
+            sampled_at: SystemTime::now(),
+        };
+        let count = batch.events.len() as u64;
+        let result = self.with_retry("write_batch", || self.backend.write_batch(&batch)).await;
+        self.record(result, count);
+    }
+
+    async fn write_op(&self, op: &WriteOp) {
+        let result = match op {
+            WriteOp::Event(event) => self.with_retry("write_event", || self.backend.write_event(event)).await,
+            WriteOp::EventWithMetadata(event, metadata) => {
+                self.with_retry("write_event_with_metadata", || {
+                    self.backend.write_event_with_metadata(event, metadata)
+                })
+                .await
+            }
+            WriteOp::PolicyMetrics { policy_id, total_executions, avg_reward } => {
+                self.with_retry("update_policy_metrics", || {
+                    self.backend.update_policy_metrics(*policy_id, *total_executions, *avg_reward)
+                })
+                .await
+            }
+            WriteOp::Config { component, key, value } => {
+                self.with_retry("save_config", || self.backend.save_config(component, key, value.clone(), None))
+                    .await
+                    .map(|_| ())
+            }
+        };
+        self.record(result, 1);
+    }
+
+    fn record(&self, result: Result<(), PersistenceError>, count: u64) {
+        let counters = &self.shared.counters;
+        match result {
+            Ok(()) => {
+                counters.written.fetch_add(count, Ordering::Relaxed);
+                crate::metrics::WRITE_BEHIND_WRITTEN.inc_by(count);
+            }
+            Err(e) => {
+                error!(error = %e, count, "Write-behind gave up on operations");
+                counters.failed.fetch_add(count, Ordering::Relaxed);
+                crate::metrics::WRITE_BEHIND_DROPPED.with_label_values(&["failed"]).inc_by(count);
+            }
+        }
+    }
+
+    /// Retry connection and query errors; other errors are permanent
+    async fn with_retry<T, F, Fut>(&self, what: &str, mut op: F) -> Result<T, PersistenceError>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T, PersistenceError>>,
+    {
+        let policy = &self.shared.config.retry;
+        let mut attempt = 0;
+        loop {
+            match op().await {
+                Err(e @ (PersistenceError::ConnectionError(_) | PersistenceError::QueryError(_)))
+                    if attempt < policy.max_retries =>
+                {
+                    let delay = policy.backoff_with_jitter(attempt);
+                    warn!(error = %e, attempt = attempt + 1, "Write-behind {} failed, retrying", what);
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::backend::{ADNAPolicy, Configuration, QueryOptions};
+    use std::collections::HashMap;
+
+    /// Records written events; fails the first `failures` calls and
+    /// blocks while `gate` has no permits
+    struct RecordingBackend {
+        events: Mutex<Vec<u128>>,
+        calls: AtomicU64,
+        failures: AtomicU64,
+        gate: tokio::sync::Semaphore,
+    }
+
+    impl RecordingBackend {
+        fn closed() -> Self {
+            Self {
+                events: Mutex::new(Vec::new()),
+                calls: AtomicU64::new(0),
+                failures: AtomicU64::new(0),
+                gate: tokio::sync::Semaphore::new(0),
+            }
+        }
+
+        fn open() -> Self {
+            let backend = Self::closed();
+            backend.gate.add_permits(tokio::sync::Semaphore::MAX_PERMITS);
+            backend
+        }
+
+        async fn call(&self) -> Result<(), PersistenceError> {
+            let _permit = self.gate.acquire().await.unwrap();
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            if self.failures.load(Ordering::Relaxed) > 0 {
+                self.failures.fetch_sub(1, Ordering::Relaxed);
+                return Err(PersistenceError::ConnectionError("down".to_string()));
+            }
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl PersistenceBackend for RecordingBackend {
+        async fn write_event(&self, event: &ExperienceEvent) -> Result<(), PersistenceError> {
+            self.call().await?;
+            self.events.lock().push(event.event_id);
+            Ok(())
+        }
+        async fn write_event_with_metadata(&self, event: &ExperienceEvent, _: &ActionMetadata) -> Result<(), PersistenceError> {
+            self.write_event(event).await
+        }
+        async fn write_batch(&self, batch: &ExperienceBatch) -> Result<(), PersistenceError> {
+            self.call().await?;
+            self.events.lock().extend(batch.events.iter().map(|e| e.event_id));
+            Ok(())
+        }
+        async fn read_event(&self, id: u128) -> Result<ExperienceEvent, PersistenceError> {
+            Err(PersistenceError::NotFound(id.to_string()))
+        }
+        async fn read_event_with_metadata(&self, id: u128) -> Result<(ExperienceEvent, Option<ActionMetadata>), PersistenceError> {
+            Err(PersistenceError::NotFound(id.to_string()))
+        }
+        async fn query_events(&self, _: QueryOptions) -> Result<Vec<ExperienceEvent>, PersistenceError> {
+            Ok(Vec::new())
+        }
+        async fn query_events_with_metadata(&self, _: QueryOptions) -> Result<Vec<(ExperienceEvent, Option<ActionMetadata>)>, PersistenceError> {
+            Ok(Vec::new())
+        }
+        async fn archive_old_events(&self, _: i32) -> Result<u64, PersistenceError> {
+            Ok(0)
+        }
+        async fn count_events(&self, _: QueryOptions) -> Result<u64, PersistenceError> {
+            Ok(self.events.lock().len() as u64)
+        }
+        async fn health_check(&self) -> Result<(), PersistenceError> {
+            Ok(())
+        }
+        async fn save_policy(&self, _: &str, _: &str, _: &HashMap<u16, f64>, _: Option<serde_json::Value>, _: Option<i32>) -> Result<i32, PersistenceError> {
+            Ok(1)
+        }
+        async fn get_active_policy(&self, _: &str) -> Result<Option<ADNAPolicy>, PersistenceError> {
+            Ok(None)
+        }
+        async fn get_all_active_policies(&self) -> Result<Vec<ADNAPolicy>, PersistenceError> {
+            Ok(Vec::new())
+        }
+        async fn deactivate_policy(&self, _: i32) -> Result<(), PersistenceError> {
+            Ok(())
+        }
+        async fn update_policy_metrics(&self, _: i32, _: i64, _: f32) -> Result<(), PersistenceError> {
+            self.call().await
+        }
+        async fn save_config(&self, _: &str, _: &str, _: serde_json::Value, _: Option<i32>) -> Result<i32, PersistenceError> {
+            self.call().await.map(|_| 1)
+        }
+        async fn get_config(&self, _: &str, _: &str) -> Result<Option<Configuration>, PersistenceError> {
+            Ok(None)
+        }
+        async fn get_component_configs(&self, _: &str) -> Result<Vec<Configuration>, PersistenceError> {
+            Ok(Vec::new())
+        }
+        async fn deactivate_config(&self, _: i32) -> Result<(), PersistenceError> {
+            Ok(())
+        }
+    }
+
+    fn event(id: u128) -> ExperienceEvent {
+        ExperienceEvent { event_id: id, ..Default::default() }
+    }
+
+    fn fast_retry() -> RetryPolicy {
+        RetryPolicy { initial_backoff_ms: 1, max_backoff_ms: 1, ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn test_batches_and_flush() {
+        let backend = Arc::new(RecordingBackend::open());
+        let writer = WriteBehind::spawn(
+            backend.clone(),
+            WriteBehindConfig {
+                batch_size: 4,
+                flush_interval: Duration::from_secs(60),
+                max_lag: Duration::from_secs(60),
+                retry: fast_retry(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        for id in 1..=5 {
+            writer.write_event(event(id)).unwrap();
+        }
+        writer
+            .try_enqueue(WriteOp::Config {
+                component: "graph".to_string(),
+                key: "k".to_string(),
+                value: serde_json::json!(1),
+            })
+            .unwrap();
+        writer.write_event(event(6)).unwrap();
+
+        // Partial batch is written on flush, long before flush_interval
+        backend.failures.store(1, Ordering::Relaxed);
+        writer.flush().await.unwrap();
+        assert_eq!(*backend.events.lock(), vec![1, 2, 3, 4, 5, 6]);
+
+        let stats = writer.stats();
+        assert_eq!((stats.enqueued, stats.written, stats.failed, stats.queued), (7, 7, 0, 0));
+        assert_eq!(stats.batches, 2);
+
+        writer.shutdown().await;
+        assert!(matches!(writer.write_event(event(7)), Err(WriteBehindError::Closed)));
+    }
+
+    #[tokio::test]
+    async fn test_drop_policies() {
+        let backend = Arc::new(RecordingBackend::closed());
+        let config = WriteBehindConfig {
+            capacity: 3,
+            batch_size: 1,
+            flush_interval: Duration::from_secs(60),
+            max_lag: Duration::from_secs(60),
+            ..Default::default()
+        };
+
+        let writer = WriteBehind::spawn(backend.clone(), config.clone()).unwrap();
+        for id in 1..=6 {
+            writer.write_event(event(id)).unwrap();
+            tokio::task::yield_now().await;
+        }
+        // Worker holds one op in flight; the queue keeps the newest three
+        let stats = writer.stats();
+        assert_eq!(stats.queued, 3);
+        assert_eq!(stats.dropped_full, 2);
+
+        let newest = WriteBehind::spawn(
+            backend.clone(),
+            WriteBehindConfig { drop_policy: DropPolicy::DropNewest, ..config.clone() },
+        )
+        .unwrap();
+        let results: Vec<bool> = (1..=6).map(|id| newest.write_event(event(id)).is_ok()).collect();
+        assert_eq!(results.iter().filter(|ok| !**ok).count(), 3);
+        assert_eq!(newest.stats().dropped_full, 3);
+
+        let blocking = WriteBehind::spawn(backend.clone(), WriteBehindConfig { drop_policy: DropPolicy::Block, ..config }).unwrap();
+        blocking.write_event(event(1)).unwrap();
+        while blocking.stats().queued > 0 {
+            tokio::task::yield_now().await;
+        }
+        for id in 2..=4 {
+            blocking.write_event(event(id)).unwrap();
+        }
+        assert!(matches!(blocking.write_event(event(5)), Err(WriteBehindError::QueueFull(3))));
+        let pending = tokio::spawn({
+            let blocking = blocking.clone();
+            async move { blocking.enqueue(WriteOp::Event(event(5))).await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!pending.is_finished());
+
+        // Open the backend: everything drains, nothing blocked is lost
+        backend.gate.add_permits(tokio::sync::Semaphore::MAX_PERMITS);
+        pending.await.unwrap().unwrap();
+        blocking.flush().await.unwrap();
+        assert_eq!(blocking.stats().dropped_full, 0);
+    }
+
+    #[tokio::test]
+    async fn test_lag_bound_and_failures() {
+        let backend = Arc::new(RecordingBackend::closed());
+        let writer = WriteBehind::spawn(
+            backend.clone(),
+            WriteBehindConfig {
+                batch_size: 1,
+                flush_interval: Duration::from_millis(1),
+                max_lag: Duration::from_millis(5),
+                retry: RetryPolicy::none(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        for id in 1..=3 {
+            writer.write_event(event(id)).unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        writer.write_event(event(4)).unwrap();
+        // Events 2 and 3 waited longer than max_lag behind the stalled write
+        assert_eq!(writer.stats().dropped_lag, 2);
+
+        backend.failures.store(1, Ordering::Relaxed);
+        backend.gate.add_permits(tokio::sync::Semaphore::MAX_PERMITS);
+        assert!(matches!(writer.flush().await, Err(WriteBehindError::Failed(1))));
+        assert_eq!(*backend.events.lock(), vec![4]);
+    }
+
+    #[tokio::test]
+    async fn test_stream_and_checkpoint_hook() {
+        let backend = Arc::new(RecordingBackend::open());
+        let writer = WriteBehind::spawn(
+            backend.clone(),
+            WriteBehindConfig {
+                flush_interval: Duration::from_secs(60),
+                max_lag: Duration::from_secs(60),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let stream = ExperienceStream::new(100, 100);
+        let forwarder = writer.attach_stream(&stream);
+        for id in 1..=10 {
+            stream.write_event(event(id)).unwrap();
+        }
+        while writer.stats().enqueued < 10 {
+            tokio::task::yield_now().await;
+        }
+
+        let hook: &dyn CheckpointHook = &writer;
+        hook.before_checkpoint().await.unwrap();
+        assert_eq!(backend.events.lock().len(), 10);
+
+        writer.shutdown().await;
+        drop(stream);
+        forwarder.await.unwrap();
+    }
+}
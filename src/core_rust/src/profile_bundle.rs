@@ -0,0 +1,365 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Profile bundles v1.0 - shareable `.ngprofile` files
+//!
+//! A bundle packs a tuned "personality" into one signed file: the active
+//! CDNA, the ADNA action policies and the appraiser configuration. It can be
+//! exported on one instance and imported on another.
+//!
+//! ```text
+//! {
+//!   "payload": {
+//!     "format_version": 1,
+//!     "name", "description", "created_at",
+//!     "cdna_version": [major, minor], "adna_version": [major, minor],
+//!     "cdna": "<384 bytes, hex>",
+//!     "adna": { "config": {...}, "policies": {...} }
+//!   },
+//!   "signature": "<HMAC-SHA256 of the canonical payload, hex>"
+//! }
+//! ```
+//!
+//! The signature covers the canonical JSON encoding of `payload` (sorted
+//! keys), so whitespace changes do not break it but any edit to the content
+//! does. Import checks the signature first, then compatibility: the major
+//! versions must match and the bundle's minor version must not be newer
+//! than this build's.
+
+use crate::adna::{InMemoryADNAReader, ADNA_VERSION_MAJOR, ADNA_VERSION_MINOR};
+use crate::cdna::{CDNA, CDNA_VERSION_MAJOR, CDNA_VERSION_MINOR};
+use crate::guardian::Guardian;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Bundle format version
+pub const PROFILE_FORMAT_VERSION: u32 = 1;
+
+/// File extension for profile bundles
+pub const PROFILE_EXTENSION: &str = "ngprofile";
+
+type HmacSha256 = Hmac<Sha256>;
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// Profile bundle errors
+#[derive(Debug)]
+pub enum ProfileError {
+    IoError(String),
+    Malformed(String),
+    InvalidSignature,
+    Incompatible(String),
+    Rejected(String),
+}
+
+impl std::fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProfileError::IoError(msg) => write!(f, "IO error: {}", msg),
+            ProfileError::Malformed(msg) => write!(f, "Malformed profile: {}", msg),
+            ProfileError::InvalidSignature => write!(f, "Profile signature does not match"),
+            ProfileError::Incompatible(msg) => write!(f, "Incompatible profile: {}", msg),
+            ProfileError::Rejected(msg) => write!(f, "Profile rejected: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ProfileError {}
+
+impl From<std::io::Error> for ProfileError {
+    fn from(e: std::io::Error) -> Self {
+        ProfileError::IoError(e.to_string())
+    }
+}
+
+// ============================================================================
+// Bundle
+// ============================================================================
+
+/// Signed contents of a `.ngprofile` file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfilePayload {
+    pub format_version: u32,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Unix seconds
+    pub created_at: u64,
+    pub cdna_version: (u16, u16),
+    pub adna_version: (u16, u16),
+    /// Raw 384-byte CDNA, hex encoded
+    pub cdna: String,
+    /// ADNA appraiser config + action policies (`InMemoryADNAReader::export_state`)
+    pub adna: serde_json::Value,
+}
+
+/// A `.ngprofile` bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileBundle {
+    pub payload: ProfilePayload,
+    pub signature: String,
+}
+
+impl ProfileBundle {
+    /// Capture the active CDNA and ADNA state and sign it with `key`
+    pub async fn export(
+        name: &str,
+        description: &str,
+        guardian: &Guardian,
+        adna: &InMemoryADNAReader,
+        key: &[u8],
+    ) -> Result<Self, ProfileError> {
+        let adna_state = adna
+            .export_state()
+            .await
+            .map_err(|e| ProfileError::Malformed(e.to_string()))?;
+        let adna_state: serde_json::Value =
+            serde_json::from_slice(&adna_state).map_err(|e| ProfileError::Malformed(e.to_string()))?;
+
+        let payload = ProfilePayload {
+            format_version: PROFILE_FORMAT_VERSION,
+            name: name.to_string(),
+            description: description.to_string(),
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            cdna_version: (CDNA_VERSION_MAJOR, CDNA_VERSION_MINOR),
+            adna_version: (ADNA_VERSION_MAJOR, ADNA_VERSION_MINOR),
+            cdna: to_hex(&guardian.cdna().to_bytes()),
+            adna: adna_state,
+        };
+        let signature = sign(&payload, key)?;
+        Ok(Self { payload, signature })
+    }
+
+    /// Parse a bundle and verify its signature
+    pub fn from_bytes(data: &[u8], key: &[u8]) -> Result<Self, ProfileError> {
+        let bundle: ProfileBundle =
+            serde_json::from_slice(data).map_err(|e| ProfileError::Malformed(e.to_string()))?;
+        bundle.verify(key)?;
+        Ok(bundle)
+    }
+
+    /// Serialize to the on-disk format
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ProfileError> {
+        serde_json::to_vec_pretty(self).map_err(|e| ProfileError::Malformed(e.to_string()))
+    }
+
+    /// Read and verify a bundle from a file
+    pub fn load(path: &Path, key: &[u8]) -> Result<Self, ProfileError> {
+        Self::from_bytes(&std::fs::read(path)?, key)
+    }
+
+    /// Write the bundle to a file
+    pub fn save(&self, path: &Path) -> Result<(), ProfileError> {
+        std::fs::write(path, self.to_bytes()?)?;
+        Ok(())
+    }
+
+    /// Check the signature against `key`
+    pub fn verify(&self, key: &[u8]) -> Result<(), ProfileError> {
+        if verify_json(&self.payload, &self.signature, key) {
+            Ok(())
+        } else {
+            Err(ProfileError::InvalidSignature)
+        }
+    }
+
+    /// Check format and CDNA/ADNA versions against this build
+    pub fn check_compatibility(&self) -> Result<(), ProfileError> {
+        let payload = &self.payload;
+        if payload.format_version > PROFILE_FORMAT_VERSION {
+            return Err(ProfileError::Incompatible(format!(
+                "format version {} is newer than supported {}",
+                payload.format_version, PROFILE_FORMAT_VERSION
+            )));
+        }
+        check_version("CDNA", payload.cdna_version, (CDNA_VERSION_MAJOR, CDNA_VERSION_MINOR))?;
+        check_version("ADNA", payload.adna_version, (ADNA_VERSION_MAJOR, ADNA_VERSION_MINOR))?;
+        Ok(())
+    }
+
+    /// Decode and validate the bundled CDNA
+    pub fn cdna(&self) -> Result<CDNA, ProfileError> {
+        let bytes = from_hex(&self.payload.cdna)
+            .ok_or_else(|| ProfileError::Malformed("CDNA is not valid hex".to_string()))?;
+        let bytes: [u8; 384] = bytes
+            .try_into()
+            .map_err(|_| ProfileError::Malformed("CDNA must be 384 bytes".to_string()))?;
+        let cdna = CDNA::from_bytes(&bytes);
+        cdna.validate().map_err(ProfileError::Rejected)?;
+        Ok(cdna)
+    }
+
+    /// Apply the bundle to a running instance
+    ///
+    /// Everything is validated before anything changes: the CDNA goes to
+    /// Guardian (versioned, so `rollback_cdna` undoes it) and the ADNA state
+    /// replaces the reader's appraiser config and policies.
+    pub async fn import(&self, guardian: &mut Guardian, adna: &InMemoryADNAReader) -> Result<(), ProfileError> {
+        self.check_compatibility()?;
+        let cdna = self.cdna()?;
+        let adna_state =
+            serde_json::to_vec(&self.payload.adna).map_err(|e| ProfileError::Malformed(e.to_string()))?;
+
+        // Parse ADNA before touching Guardian so a bad section changes nothing
+        let scratch = InMemoryADNAReader::with_defaults();
+        scratch
+            .import_state(&adna_state)
+            .await
+            .map_err(|e| ProfileError::Malformed(format!("ADNA: {}", e)))?;
+
+        guardian.update_cdna(cdna).map_err(ProfileError::Rejected)?;
+        adna.import_state(&adna_state)
+            .await
+            .map_err(|e| ProfileError::Malformed(format!("ADNA: {}", e)))?;
+        Ok(())
+    }
+}
+
+/// Major must match; a newer minor may carry fields this build ignores
+fn check_version(what: &str, bundle: (u16, u16), current: (u16, u16)) -> Result<(), ProfileError> {
+    if bundle.0 != current.0 || bundle.1 > current.1 {
+        return Err(ProfileError::Incompatible(format!(
+            "{} v{}.{} (this build supports v{}.x up to v{}.{})",
+            what, bundle.0, bundle.1, current.0, current.0, current.1
+        )));
+    }
+    Ok(())
+}
+
+/// Value as JSON with sorted keys
+fn canonical_json<T: Serialize>(value: &T) -> Result<Vec<u8>, String> {
+    let value = serde_json::to_value(value).map_err(|e| e.to_string())?;
+    serde_json::to_vec(&value).map_err(|e| e.to_string())
+}
+
+/// HMAC-SHA256 of the canonical JSON of `value`, hex (shared with skill packs)
+pub(crate) fn sign_json<T: Serialize>(value: &T, key: &[u8]) -> Result<String, String> {
+    let mut mac = HmacSha256::new_from_slice(key).map_err(|e| e.to_string())?;
+    mac.update(&canonical_json(value)?);
+    Ok(to_hex(&mac.finalize().into_bytes()))
+}
+
+/// Check a [`sign_json`] signature
+pub(crate) fn verify_json<T: Serialize>(value: &T, signature: &str, key: &[u8]) -> bool {
+    let (Some(expected), Ok(mut mac), Ok(canonical)) = (
+        from_hex(signature),
+        HmacSha256::new_from_slice(key),
+        canonical_json(value),
+    ) else {
+        return false;
+    };
+    mac.update(&canonical);
+    mac.verify_slice(&expected).is_ok()
+}
+
+fn sign(payload: &ProfilePayload, key: &[u8]) -> Result<String, ProfileError> {
+    sign_json(payload, key).map_err(ProfileError::Malformed)
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub(crate) fn from_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adna::{ActionPolicy, ADNAReader};
+    use crate::cdna::ProfilePatch;
+
+    const KEY: &[u8] = b"shared-secret";
+
+    async fn tuned_instance() -> (Guardian, InMemoryADNAReader) {
+        let mut guardian = Guardian::new();
+        guardian
+            .patch_cdna(&ProfilePatch { max_out_degree: Some(750), ..Default::default() })
+            .unwrap();
+
+        let adna = InMemoryADNAReader::with_defaults();
+        let mut config = adna.get_appraiser_config().await.unwrap();
+        config.curiosity.weight = 0.9;
+        adna.update_config(config).await;
+        let mut policy = ActionPolicy::new("explore");
+        policy.set_weight(3, 0.7);
+        adna.set_action_policy("bin_1".to_string(), policy).await;
+
+        (guardian, adna)
+    }
+
+    #[tokio::test]
+    async fn test_export_import_round_trip() {
+        let (guardian, adna) = tuned_instance().await;
+        let bundle = ProfileBundle::export("curious", "likes exploring", &guardian, &adna, KEY)
+            .await
+            .unwrap();
+        let bytes = bundle.to_bytes().unwrap();
+
+        let loaded = ProfileBundle::from_bytes(&bytes, KEY).unwrap();
+        let mut target_guardian = Guardian::new();
+        let target_adna = InMemoryADNAReader::with_defaults();
+        loaded.import(&mut target_guardian, &target_adna).await.unwrap();
+
+        assert_eq!(target_guardian.cdna().max_out_degree, 750);
+        assert_eq!(target_guardian.cdna().revision, 1);
+        assert_eq!(target_adna.get_appraiser_config().await.unwrap().curiosity.weight, 0.9);
+        let policy = target_adna.get_policy_by_bin("bin_1").await.unwrap();
+        assert_eq!(policy.get_weight(3), 0.7);
+    }
+
+    #[tokio::test]
+    async fn test_signature_and_compatibility() {
+        let (guardian, adna) = tuned_instance().await;
+        let bundle = ProfileBundle::export("curious", "", &guardian, &adna, KEY).await.unwrap();
+        let bytes = bundle.to_bytes().unwrap();
+
+        assert!(matches!(
+            ProfileBundle::from_bytes(&bytes, b"other-key"),
+            Err(ProfileError::InvalidSignature)
+        ));
+
+        // Any edit to the payload breaks the signature
+        let mut tampered = bundle.clone();
+        tampered.payload.name = "renamed".to_string();
+        assert!(matches!(tampered.verify(KEY), Err(ProfileError::InvalidSignature)));
+
+        // Re-signed bundle from a newer minor version is rejected on import
+        let mut newer = bundle.clone();
+        newer.payload.cdna_version.1 = CDNA_VERSION_MINOR + 1;
+        newer.signature = sign(&newer.payload, KEY).unwrap();
+        assert!(newer.verify(KEY).is_ok());
+        let mut target = Guardian::new();
+        let result = newer.import(&mut target, &InMemoryADNAReader::with_defaults()).await;
+        assert!(matches!(result, Err(ProfileError::Incompatible(_))));
+        assert_eq!(target.cdna().revision, 0);
+    }
+}
@@ -0,0 +1,434 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Profiling v1.0 - Per-stage timing of the signal pipeline
+//!
+//! Every signal passes through the same stages:
+//!
+//! ```text
+//! normalize → classify → activate → appraise → decide → execute
+//! ```
+//!
+//! Each stage records its duration into a fixed-bucket histogram held by
+//! [`PipelineProfiler`]. Recording is lock-free on the hot path (atomics
+//! behind a read lock), so the global [`PROFILER`] stays enabled in
+//! production and a regression in one stage shows up in `/stats` and
+//! `GET /api/v1/profiling` without attaching a profiler.
+//!
+//! ```rust
+//! use _core::profiling::{self, PipelineStage};
+//!
+//! let _timer = profiling::start(PipelineStage::Decide);
+//! // ... stage work, recorded when `_timer` drops
+//! ```
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// Stage of the signal pipeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PipelineStage {
+    /// Gateway: text → 8D state
+    Normalize,
+    /// Gateway: signal type classification
+    Classify,
+    /// Spreading activation over the graph
+    Activate,
+    /// Appraisers computing rewards
+    Appraise,
+    /// ADNA policy / Arbiter decision
+    Decide,
+    /// ActionExecutor run
+    Execute,
+}
+
+impl PipelineStage {
+    /// All stages in pipeline order
+    pub const ALL: [PipelineStage; 6] = [
+        PipelineStage::Normalize,
+        PipelineStage::Classify,
+        PipelineStage::Activate,
+        PipelineStage::Appraise,
+        PipelineStage::Decide,
+        PipelineStage::Execute,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PipelineStage::Normalize => "normalize",
+            PipelineStage::Classify => "classify",
+            PipelineStage::Activate => "activate",
+            PipelineStage::Appraise => "appraise",
+            PipelineStage::Decide => "decide",
+            PipelineStage::Execute => "execute",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// Profiler configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfilingConfig {
+    /// Record anything at all
+    pub enabled: bool,
+
+    /// Stages to record (others are ignored)
+    pub stages: Vec<PipelineStage>,
+
+    /// Histogram bucket upper bounds in microseconds, strictly increasing.
+    /// Samples above the last bound land in an overflow bucket.
+    pub bucket_bounds_us: Vec<u64>,
+}
+
+impl Default for ProfilingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            stages: PipelineStage::ALL.to_vec(),
+            bucket_bounds_us: vec![
+                10, 50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000, 25_000, 50_000, 100_000,
+                250_000, 1_000_000,
+            ],
+        }
+    }
+}
+
+impl ProfilingConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.bucket_bounds_us.is_empty() {
+            return Err("bucket_bounds_us must not be empty".to_string());
+        }
+        if self.bucket_bounds_us.windows(2).any(|w| w[0] >= w[1]) {
+            return Err("bucket_bounds_us must be strictly increasing".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Histogram for one stage
+struct StageHistogram {
+    bounds_us: Vec<u64>,
+    /// One counter per bound plus the overflow bucket
+    buckets: Vec<AtomicU64>,
+    count: AtomicU64,
+    sum_us: AtomicU64,
+    max_us: AtomicU64,
+}
+
+impl StageHistogram {
+    fn new(bounds_us: &[u64]) -> Self {
+        Self {
+            bounds_us: bounds_us.to_vec(),
+            buckets: (0..=bounds_us.len()).map(|_| AtomicU64::new(0)).collect(),
+            count: AtomicU64::new(0),
+            sum_us: AtomicU64::new(0),
+            max_us: AtomicU64::new(0),
+        }
+    }
+
+    fn record(&self, us: u64) {
+        let bucket = self.bounds_us.partition_point(|&bound| bound < us);
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_us.fetch_add(us, Ordering::Relaxed);
+        self.max_us.fetch_max(us, Ordering::Relaxed);
+    }
+
+    fn stats(&self, stage: PipelineStage) -> StageStats {
+        let counts: Vec<u64> = self.buckets.iter().map(|b| b.load(Ordering::Relaxed)).collect();
+        let count = self.count.load(Ordering::Relaxed);
+        let total_us = self.sum_us.load(Ordering::Relaxed);
+        let max_us = self.max_us.load(Ordering::Relaxed);
+
+        // Percentile = upper bound of the bucket holding the q-th sample,
+        // capped at the observed maximum
+        let percentile = |q: f64| -> u64 {
+            if count == 0 {
+                return 0;
+            }
+            let rank = ((q * count as f64).ceil() as u64).max(1);
+            let mut seen = 0;
+            for (i, c) in counts.iter().enumerate() {
+                seen += c;
+                if seen >= rank {
+                    return self.bounds_us.get(i).map_or(max_us, |&b| b.min(max_us));
+                }
+            }
+            max_us
+        };
+
+        StageStats {
+            stage,
+            count,
+            total_us,
+            mean_us: if count == 0 { 0.0 } else { total_us as f64 / count as f64 },
+            max_us,
+            p50_us: percentile(0.50),
+            p95_us: percentile(0.95),
+            p99_us: percentile(0.99),
+            buckets: counts
+                .iter()
+                .enumerate()
+                .map(|(i, &count)| HistogramBucket { le_us: self.bounds_us.get(i).copied(), count })
+                .collect(),
+        }
+    }
+}
+
+/// One histogram bucket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistogramBucket {
+    /// Upper bound in microseconds (None = overflow bucket)
+    pub le_us: Option<u64>,
+    /// Samples in this bucket (not cumulative)
+    pub count: u64,
+}
+
+/// Aggregated timings for one stage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StageStats {
+    pub stage: PipelineStage,
+    pub count: u64,
+    pub total_us: u64,
+    pub mean_us: f64,
+    pub max_us: u64,
+    /// Percentiles are bucket upper bounds, so they are estimates
+    pub p50_us: u64,
+    pub p95_us: u64,
+    pub p99_us: u64,
+    pub buckets: Vec<HistogramBucket>,
+}
+
+/// Point-in-time view of all stage histograms
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfilingSnapshot {
+    pub enabled: bool,
+    /// Stages in pipeline order (only recorded stages)
+    pub stages: Vec<StageStats>,
+}
+
+/// Per-stage histograms for the signal pipeline
+pub struct PipelineProfiler {
+    config: RwLock<ProfilingConfig>,
+    enabled: AtomicBool,
+    stage_enabled: [AtomicBool; 6],
+    histograms: RwLock<Vec<StageHistogram>>,
+}
+
+impl PipelineProfiler {
+    pub fn new(config: ProfilingConfig) -> Result<Self, String> {
+        config.validate()?;
+        let profiler = Self {
+            config: RwLock::new(ProfilingConfig::default()),
+            enabled: AtomicBool::new(false),
+            stage_enabled: Default::default(),
+            histograms: RwLock::new(Vec::new()),
+        };
+        profiler.apply(config);
+        Ok(profiler)
+    }
+
+    /// Replace configuration; resets all histograms
+    pub fn configure(&self, config: ProfilingConfig) -> Result<(), String> {
+        config.validate()?;
+        self.apply(config);
+        Ok(())
+    }
+
+    fn apply(&self, config: ProfilingConfig) {
+        for stage in PipelineStage::ALL {
+            self.stage_enabled[stage.index()].store(config.stages.contains(&stage), Ordering::Relaxed);
+        }
+        self.enabled.store(config.enabled, Ordering::Relaxed);
+        *self.histograms.write() = PipelineStage::ALL
+            .iter()
+            .map(|_| StageHistogram::new(&config.bucket_bounds_us))
+            .collect();
+        *self.config.write() = config;
+    }
+
+    pub fn config(&self) -> ProfilingConfig {
+        self.config.read().clone()
+    }
+
+    /// Check if a stage is currently being recorded
+    pub fn is_recording(&self, stage: PipelineStage) -> bool {
+        self.enabled.load(Ordering::Relaxed) && self.stage_enabled[stage.index()].load(Ordering::Relaxed)
+    }
+
+    /// Record one sample for a stage
+    pub fn record(&self, stage: PipelineStage, elapsed: Duration) {
+        if !self.is_recording(stage) {
+            return;
+        }
+        self.histograms.read()[stage.index()].record(elapsed.as_micros() as u64);
+    }
+
+    /// Run `f` and record its duration
+    pub fn time<T>(&self, stage: PipelineStage, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = f();
+        self.record(stage, start.elapsed());
+        result
+    }
+
+    /// Start a timer that records when dropped (works across `.await`)
+    pub fn start(&self, stage: PipelineStage) -> StageTimer<'_> {
+        StageTimer { profiler: self, stage, start: Instant::now() }
+    }
+
+    /// Current histograms
+    pub fn snapshot(&self) -> ProfilingSnapshot {
+        let config = self.config.read();
+        let histograms = self.histograms.read();
+        ProfilingSnapshot {
+            enabled: config.enabled,
+            stages: PipelineStage::ALL
+                .iter()
+                .filter(|stage| config.stages.contains(stage))
+                .map(|&stage| histograms[stage.index()].stats(stage))
+                .collect(),
+        }
+    }
+
+    /// Clear all histograms, keeping configuration
+    pub fn reset(&self) {
+        let config = self.config.read();
+        *self.histograms.write() = PipelineStage::ALL
+            .iter()
+            .map(|_| StageHistogram::new(&config.bucket_bounds_us))
+            .collect();
+    }
+}
+
+impl Default for PipelineProfiler {
+    fn default() -> Self {
+        Self::new(ProfilingConfig::default()).expect("default profiling config is valid")
+    }
+}
+
+/// Records elapsed time for a stage on drop
+pub struct StageTimer<'a> {
+    profiler: &'a PipelineProfiler,
+    stage: PipelineStage,
+    start: Instant,
+}
+
+impl Drop for StageTimer<'_> {
+    fn drop(&mut self) {
+        self.profiler.record(self.stage, self.start.elapsed());
+    }
+}
+
+lazy_static::lazy_static! {
+    /// Process-wide profiler used by the pipeline
+    pub static ref PROFILER: PipelineProfiler = PipelineProfiler::default();
+}
+
+/// Record a sample on the global profiler
+pub fn record(stage: PipelineStage, elapsed: Duration) {
+    PROFILER.record(stage, elapsed);
+}
+
+/// Start a drop-timer on the global profiler
+pub fn start(stage: PipelineStage) -> StageTimer<'static> {
+    PROFILER.start(stage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(bounds: Vec<u64>) -> ProfilingConfig {
+        ProfilingConfig { bucket_bounds_us: bounds, ..Default::default() }
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(ProfilingConfig::default().validate().is_ok());
+        assert!(config(vec![]).validate().is_err());
+        assert!(config(vec![10, 10]).validate().is_err());
+        assert!(PipelineProfiler::new(config(vec![5, 1])).is_err());
+    }
+
+    #[test]
+    fn test_record_and_percentiles() {
+        let profiler = PipelineProfiler::new(config(vec![10, 100, 1_000])).unwrap();
+        for _ in 0..90 {
+            profiler.record(PipelineStage::Normalize, Duration::from_micros(5));
+        }
+        for _ in 0..10 {
+            profiler.record(PipelineStage::Normalize, Duration::from_micros(500));
+        }
+        profiler.record(PipelineStage::Execute, Duration::from_millis(5));
+
+        let snapshot = profiler.snapshot();
+        assert_eq!(snapshot.stages.len(), 6);
+
+        let normalize = &snapshot.stages[0];
+        assert_eq!(normalize.stage, PipelineStage::Normalize);
+        assert_eq!(normalize.count, 100);
+        assert_eq!(normalize.total_us, 90 * 5 + 10 * 500);
+        assert_eq!(normalize.p50_us, 10);
+        assert_eq!(normalize.p95_us, 500);
+        assert_eq!(normalize.max_us, 500);
+        assert_eq!(normalize.buckets[0].count, 90);
+        assert_eq!(normalize.buckets[2].count, 10);
+
+        let execute = &snapshot.stages[5];
+        assert_eq!(execute.count, 1);
+        assert_eq!(execute.buckets[3].le_us, None);
+        assert_eq!(execute.buckets[3].count, 1);
+        assert_eq!(execute.p99_us, 5_000);
+    }
+
+    #[test]
+    fn test_disabled_stages_are_skipped() {
+        let profiler = PipelineProfiler::new(ProfilingConfig {
+            stages: vec![PipelineStage::Decide],
+            ..Default::default()
+        })
+        .unwrap();
+
+        profiler.record(PipelineStage::Normalize, Duration::from_micros(50));
+        {
+            let _timer = profiler.start(PipelineStage::Decide);
+        }
+        assert_eq!(profiler.time(PipelineStage::Decide, || 7), 7);
+
+        let snapshot = profiler.snapshot();
+        assert_eq!(snapshot.stages.len(), 1);
+        assert_eq!(snapshot.stages[0].count, 2);
+
+        profiler.configure(ProfilingConfig { enabled: false, ..Default::default() }).unwrap();
+        profiler.record(PipelineStage::Decide, Duration::from_micros(50));
+        assert!(profiler.snapshot().stages.iter().all(|s| s.count == 0));
+    }
+
+    #[test]
+    fn test_reset() {
+        let profiler = PipelineProfiler::default();
+        profiler.record(PipelineStage::Activate, Duration::from_micros(30));
+        profiler.reset();
+        assert!(profiler.snapshot().stages.iter().all(|s| s.count == 0));
+    }
+}
@@ -0,0 +1,509 @@
+// Python bindings for ExperienceStream and Learner
+
+use pyo3::prelude::*;
+use pyo3::exceptions::{PyIndexError, PyKeyError, PyValueError};
+use pyo3::types::PyDict;
+use numpy::{IntoPyArray, PyArray2};
+use crate::connection_v3::ConnectionV3;
+use crate::experience_stream::{ExperienceEvent, ExperienceStream, SamplingStrategy};
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+fn event_to_dict<'py>(py: Python<'py>, event: &ExperienceEvent) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new_bound(py);
+    dict.set_item("sequence_number", event.sequence_number)?;
+    dict.set_item("event_id", event.event_id)?;
+    dict.set_item("timestamp", event.timestamp)?;
+    dict.set_item("episode_id", event.episode_id)?;
+    dict.set_item("step_number", event.step_number)?;
+    dict.set_item("event_type", event.event_type)?;
+    dict.set_item("flags", event.flags)?;
+    dict.set_item("state", event.state.to_vec())?;
+    dict.set_item("action", event.action.to_vec())?;
+    dict.set_item("reward_homeostasis", event.reward_homeostasis)?;
+    dict.set_item("reward_curiosity", event.reward_curiosity)?;
+    dict.set_item("reward_efficiency", event.reward_efficiency)?;
+    dict.set_item("reward_goal", event.reward_goal)?;
+    dict.set_item("total_reward", event.total_reward())?;
+    Ok(dict)
+}
+
+/// Column-oriented NumPy view of a list of events
+fn events_to_numpy<'py>(py: Python<'py>, events: &[ExperienceEvent]) -> PyResult<Bound<'py, PyDict>> {
+    let states: Vec<Vec<f32>> = events.iter().map(|e| e.state.to_vec()).collect();
+    let actions: Vec<Vec<f32>> = events.iter().map(|e| e.action.to_vec()).collect();
+
+    let dict = PyDict::new_bound(py);
+    dict.set_item(
+        "sequence_number",
+        events.iter().map(|e| e.sequence_number).collect::<Vec<_>>().into_pyarray_bound(py),
+    )?;
+    dict.set_item(
+        "episode_id",
+        events.iter().map(|e| e.episode_id).collect::<Vec<_>>().into_pyarray_bound(py),
+    )?;
+    dict.set_item(
+        "event_type",
+        events.iter().map(|e| e.event_type).collect::<Vec<_>>().into_pyarray_bound(py),
+    )?;
+    dict.set_item("state", PyArray2::from_vec2_bound(py, &states)?)?;
+    dict.set_item("action", PyArray2::from_vec2_bound(py, &actions)?)?;
+    dict.set_item(
+        "reward",
+        events.iter().map(|e| e.total_reward()).collect::<Vec<_>>().into_pyarray_bound(py),
+    )?;
+    Ok(dict)
+}
+
+fn parse_strategy(name: &str, alpha: f64, decay: f64) -> PyResult<SamplingStrategy> {
+    match name {
+        "uniform" => Ok(SamplingStrategy::Uniform),
+        "reward" => Ok(SamplingStrategy::PrioritizedByReward { alpha }),
+        "recency" => Ok(SamplingStrategy::RecencyWeighted { decay }),
+        "mixed" => Ok(SamplingStrategy::Mixed {
+            reward_weight: 0.5,
+            recency_weight: 0.5,
+        }),
+        other => Err(PyValueError::new_err(format!(
+            "Unknown strategy '{}' (expected uniform, reward, recency, mixed)",
+            other
+        ))),
+    }
+}
+
+fn to_8d(name: &str, values: Option<Vec<f32>>) -> PyResult<[f32; 8]> {
+    match values {
+        None => Ok([0.0; 8]),
+        Some(v) => v
+            .try_into()
+            .map_err(|v: Vec<f32>| PyValueError::new_err(format!("{} must have 8 values, got {}", name, v.len()))),
+    }
+}
+
+/// Python wrapper for ExperienceStream
+///
+/// Ring buffer of 128-byte experience events (state, action, rewards).
+///
+/// # Example
+///
+/// ```python
+/// stream = ExperienceStream(capacity=10_000)
+/// seq = stream.append(state=[0.1] * 8, action=[0.0] * 8, rewards=(0.5, 0.0, 0.0, 0.0))
+///
+/// for event in stream:
+///     print(event["total_reward"])
+///
+/// batch = stream.sample_numpy(256, strategy="reward")
+/// batch["state"].shape  # (256, 8)
+/// ```
+#[pyclass(name = "ExperienceStream")]
+pub struct PyExperienceStream {
+    pub(crate) inner: Arc<ExperienceStream>,
+}
+
+impl PyExperienceStream {
+    /// [start, end) of events still in the buffer (0-based sequence numbers)
+    fn available_range(&self) -> (u64, u64) {
+        let total = self.inner.total_written();
+        (total - self.inner.size() as u64, total)
+    }
+
+    fn range(&self, start: Option<u64>, end: Option<u64>) -> Vec<ExperienceEvent> {
+        let (first, total) = self.available_range();
+        let start = start.unwrap_or(first).max(first);
+        let end = end.unwrap_or(total).min(total);
+        self.inner.query_range(start, end)
+    }
+}
+
+#[pymethods]
+impl PyExperienceStream {
+    /// Create new ExperienceStream
+    ///
+    /// Args:
+    ///     capacity: Ring buffer capacity in events (default: 100000)
+    ///     channel_size: Broadcast channel size (default: 1000)
+    #[new]
+    #[pyo3(signature = (capacity=100_000, channel_size=1000))]
+    pub fn new(capacity: usize, channel_size: usize) -> PyResult<Self> {
+        if capacity == 0 || channel_size == 0 {
+            return Err(PyValueError::new_err("capacity and channel_size must be > 0"));
+        }
+        Ok(PyExperienceStream {
+            inner: Arc::new(ExperienceStream::new(capacity, channel_size)),
+        })
+    }
+
+    /// Append an event
+    ///
+    /// Args:
+    ///     state: 8 floats
+    ///     action: 8 floats (default: zeros)
+    ///     event_type: Event type code (default: 0)
+    ///     episode_id: Episode ID (default: 0)
+    ///     step_number: Step within episode (default: 0)
+    ///     rewards: (homeostasis, curiosity, efficiency, goal) (default: zeros)
+    ///
+    /// Returns:
+    ///     int: Sequence number of the event
+    #[pyo3(signature = (state, action=None, event_type=0, episode_id=0, step_number=0, rewards=None))]
+    pub fn append(
+        &self,
+        state: Vec<f32>,
+        action: Option<Vec<f32>>,
+        event_type: u16,
+        episode_id: u64,
+        step_number: u32,
+        rewards: Option<(f32, f32, f32, f32)>,
+    ) -> PyResult<u64> {
+        let (homeostasis, curiosity, efficiency, goal) = rewards.unwrap_or_default();
+        let event = ExperienceEvent {
+            event_id: uuid::Uuid::new_v4().as_u128(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_micros() as u64,
+            episode_id,
+            step_number,
+            event_type,
+            state: to_8d("state", Some(state))?,
+            action: to_8d("action", action)?,
+            reward_homeostasis: homeostasis,
+            reward_curiosity: curiosity,
+            reward_efficiency: efficiency,
+            reward_goal: goal,
+            ..Default::default()
+        };
+
+        let seq = self
+            .inner
+            .write_event(event)
+            .map_err(PyValueError::new_err)?;
+        Ok(seq - 1)
+    }
+
+    /// Get event by sequence number
+    ///
+    /// Returns:
+    ///     dict | None: Event, or None if overwritten / not written yet
+    pub fn get(&self, py: Python, seq: u64) -> PyResult<Option<PyObject>> {
+        self.inner
+            .get_event(seq)
+            .map(|mut e| {
+                e.sequence_number = seq as u32;
+                event_to_dict(py, &e).map(|d| d.into_any().unbind())
+            })
+            .transpose()
+    }
+
+    /// Events in [start, end) as a list of dicts (default: whole buffer)
+    #[pyo3(signature = (start=None, end=None))]
+    pub fn events(&self, py: Python, start: Option<u64>, end: Option<u64>) -> PyResult<Vec<PyObject>> {
+        let first = start.unwrap_or(self.available_range().0);
+        self.range(start, end)
+            .iter()
+            .enumerate()
+            .map(|(i, e)| {
+                let mut e = *e;
+                e.sequence_number = (first + i as u64) as u32;
+                event_to_dict(py, &e).map(|d| d.into_any().unbind())
+            })
+            .collect()
+    }
+
+    /// Events in [start, end) as NumPy columns
+    ///
+    /// Returns:
+    ///     dict: state (N, 8), action (N, 8), reward (N,), episode_id (N,),
+    ///           event_type (N,), sequence_number (N,)
+    #[pyo3(signature = (start=None, end=None))]
+    pub fn to_numpy(&self, py: Python, start: Option<u64>, end: Option<u64>) -> PyResult<PyObject> {
+        let first = start.unwrap_or(self.available_range().0);
+        let mut events = self.range(start, end);
+        for (i, e) in events.iter_mut().enumerate() {
+            e.sequence_number = (first + i as u64) as u32;
+        }
+        Ok(events_to_numpy(py, &events)?.into_any().unbind())
+    }
+
+    /// Sample a batch as a list of dicts
+    ///
+    /// Args:
+    ///     size: Batch size
+    ///     strategy: "uniform", "reward", "recency" or "mixed"
+    ///     alpha: Priority exponent for "reward"
+    ///     decay: Decay factor for "recency"
+    #[pyo3(signature = (size, strategy="uniform", alpha=1.0, decay=0.9))]
+    pub fn sample(&self, py: Python, size: usize, strategy: &str, alpha: f64, decay: f64) -> PyResult<Vec<PyObject>> {
+        let strategy = parse_strategy(strategy, alpha, decay)?;
+        let batch = py.allow_threads(|| self.inner.sample_batch(size, strategy));
+        batch
+            .events
+            .iter()
+            .map(|e| event_to_dict(py, e).map(|d| d.into_any().unbind()))
+            .collect()
+    }
+
+    /// Sample a batch as NumPy columns (see `to_numpy`)
+    #[pyo3(signature = (size, strategy="uniform", alpha=1.0, decay=0.9))]
+    pub fn sample_numpy(&self, py: Python, size: usize, strategy: &str, alpha: f64, decay: f64) -> PyResult<PyObject> {
+        let strategy = parse_strategy(strategy, alpha, decay)?;
+        let batch = py.allow_threads(|| self.inner.sample_batch(size, strategy));
+        Ok(events_to_numpy(py, &batch.events)?.into_any().unbind())
+    }
+
+    /// Total events written (including overwritten)
+    #[getter]
+    pub fn total_written(&self) -> u64 {
+        self.inner.total_written()
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.size()
+    }
+
+    /// Iterate over a snapshot of buffered events as dicts
+    fn __iter__(&self) -> PyExperienceIterator {
+        let (first, _) = self.available_range();
+        PyExperienceIterator {
+            events: self.range(None, None).into_iter(),
+            next_seq: first,
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ExperienceStream(size={}, total_written={})",
+            self.inner.size(),
+            self.inner.total_written()
+        )
+    }
+}
+
+/// Iterator over an ExperienceStream snapshot
+#[pyclass]
+pub struct PyExperienceIterator {
+    events: std::vec::IntoIter<ExperienceEvent>,
+    next_seq: u64,
+}
+
+#[pymethods]
+impl PyExperienceIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(mut slf: PyRefMut<'_, Self>, py: Python) -> PyResult<Option<PyObject>> {
+        let seq = slf.next_seq;
+        match slf.events.next() {
+            Some(mut event) => {
+                slf.next_seq += 1;
+                event.sequence_number = seq as u32;
+                Ok(Some(event_to_dict(py, &event)?.into_any().unbind()))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+/// Learner metrics
+#[derive(Debug, Clone, Default)]
+struct LearnerMetrics {
+    updates: u64,
+    successes: u64,
+    failures: u64,
+    overrides: u64,
+}
+
+/// Python wrapper for connection learning
+///
+/// Holds ConnectionV3 weights (confidence in [0, 1]) and applies the
+/// built-in rule (`ConnectionV3::update_confidence`). `set_weight` lets
+/// Python code plug in an alternative rule against the same connections.
+///
+/// # Example
+///
+/// ```python
+/// learner = Learner()
+/// cid = learner.add_connection(1, 2)
+///
+/// for event in stream.sample(64, strategy="reward"):
+///     learner.learn(cid, event["total_reward"] > 0)
+///
+/// print(learner.get_weight(cid), learner.metrics())
+/// ```
+#[pyclass(name = "Learner")]
+pub struct PyLearner {
+    connections: RwLock<HashMap<u64, ConnectionV3>>,
+    next_id: RwLock<u64>,
+    metrics: RwLock<LearnerMetrics>,
+}
+
+impl PyLearner {
+    fn with_connection<T>(&self, id: u64, f: impl FnOnce(&mut ConnectionV3) -> T) -> PyResult<T> {
+        let mut connections = self.connections.write();
+        let connection = connections
+            .get_mut(&id)
+            .ok_or_else(|| PyKeyError::new_err(format!("Unknown connection {}", id)))?;
+        Ok(f(connection))
+    }
+}
+
+#[pymethods]
+impl PyLearner {
+    #[new]
+    pub fn new() -> Self {
+        PyLearner {
+            connections: RwLock::new(HashMap::new()),
+            next_id: RwLock::new(1),
+            metrics: RwLock::new(LearnerMetrics::default()),
+        }
+    }
+
+    /// Add a learnable connection
+    ///
+    /// Args:
+    ///     token_a, token_b: Token IDs
+    ///     weight: Initial confidence in [0, 1] (default: 0.5)
+    ///     learning_rate: Step size in [0, 1] (default: 0.125)
+    ///
+    /// Returns:
+    ///     int: Connection ID
+    #[pyo3(signature = (token_a, token_b, weight=None, learning_rate=None))]
+    pub fn add_connection(
+        &self,
+        token_a: u32,
+        token_b: u32,
+        weight: Option<f32>,
+        learning_rate: Option<f32>,
+    ) -> PyResult<u64> {
+        let mut connection = ConnectionV3::new(token_a, token_b);
+        if let Some(w) = weight {
+            if !(0.0..=1.0).contains(&w) {
+                return Err(PyValueError::new_err("weight must be in [0, 1]"));
+            }
+            connection.confidence = (w * 255.0) as u8;
+        }
+        if let Some(lr) = learning_rate {
+            if !(0.0..=1.0).contains(&lr) {
+                return Err(PyValueError::new_err("learning_rate must be in [0, 1]"));
+            }
+            connection.learning_rate = (lr * 255.0) as u8;
+        }
+
+        let mut next_id = self.next_id.write();
+        let id = *next_id;
+        *next_id += 1;
+        self.connections.write().insert(id, connection);
+        Ok(id)
+    }
+
+    /// Current weight (confidence in [0, 1])
+    pub fn get_weight(&self, id: u64) -> PyResult<f32> {
+        self.with_connection(id, |c| c.confidence as f32 / 255.0)
+    }
+
+    /// Overwrite a weight (for learning rules implemented in Python)
+    pub fn set_weight(&self, id: u64, weight: f32) -> PyResult<()> {
+        if !(0.0..=1.0).contains(&weight) {
+            return Err(PyValueError::new_err("weight must be in [0, 1]"));
+        }
+        let modifiable = self.with_connection(id, |c| {
+            if c.can_modify() {
+                c.confidence = (weight * 255.0) as u8;
+            }
+            c.can_modify()
+        })?;
+        if !modifiable {
+            return Err(PyValueError::new_err(format!("Connection {} is immutable", id)));
+        }
+        self.metrics.write().overrides += 1;
+        Ok(())
+    }
+
+    /// Apply one observation with the built-in rule
+    ///
+    /// Returns:
+    ///     float: New weight
+    pub fn learn(&self, id: u64, success: bool) -> PyResult<f32> {
+        let weight = self.with_connection(id, |c| {
+            c.update_confidence(success);
+            c.confidence as f32 / 255.0
+        })?;
+
+        let mut metrics = self.metrics.write();
+        metrics.updates += 1;
+        if success {
+            metrics.successes += 1;
+        } else {
+            metrics.failures += 1;
+        }
+        Ok(weight)
+    }
+
+    /// Apply a batch: positive reward counts as success
+    ///
+    /// Returns:
+    ///     list[float]: New weights, one per update
+    pub fn learn_batch(&self, ids: Vec<u64>, rewards: Vec<f32>) -> PyResult<Vec<f32>> {
+        if ids.len() != rewards.len() {
+            return Err(PyIndexError::new_err("ids and rewards must have the same length"));
+        }
+        ids.iter()
+            .zip(rewards)
+            .map(|(&id, reward)| self.learn(id, reward > 0.0))
+            .collect()
+    }
+
+    /// All weights as {connection_id: weight}
+    pub fn weights(&self) -> HashMap<u64, f32> {
+        self.connections
+            .read()
+            .iter()
+            .map(|(&id, c)| (id, c.confidence as f32 / 255.0))
+            .collect()
+    }
+
+    /// Learning metrics
+    ///
+    /// Returns:
+    ///     dict: connections, updates, successes, failures, overrides, mean_weight
+    pub fn metrics(&self, py: Python) -> PyResult<PyObject> {
+        let connections = self.connections.read();
+        let metrics = self.metrics.read().clone();
+        let mean_weight = if connections.is_empty() {
+            0.0
+        } else {
+            connections.values().map(|c| c.confidence as f32 / 255.0).sum::<f32>() / connections.len() as f32
+        };
+
+        let dict = PyDict::new_bound(py);
+        dict.set_item("connections", connections.len())?;
+        dict.set_item("updates", metrics.updates)?;
+        dict.set_item("successes", metrics.successes)?;
+        dict.set_item("failures", metrics.failures)?;
+        dict.set_item("overrides", metrics.overrides)?;
+        dict.set_item("mean_weight", mean_weight)?;
+        Ok(dict.into_any().unbind())
+    }
+
+    fn __len__(&self) -> usize {
+        self.connections.read().len()
+    }
+
+    fn __repr__(&self) -> String {
+        let metrics = self.metrics.read();
+        format!(
+            "Learner(connections={}, updates={})",
+            self.connections.read().len(),
+            metrics.updates
+        )
+    }
+}
+
+impl Default for PyLearner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
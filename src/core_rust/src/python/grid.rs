@@ -0,0 +1,115 @@
+// Python bindings for Grid
+//
+// Spatial index over tokens with bulk NumPy-friendly state export.
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
+use crate::grid::Grid;
+use crate::token::CoordinateSpace;
+use super::state_array::PyStateArray;
+use super::token::PyToken;
+
+fn coordinate_space(space: u8) -> PyResult<CoordinateSpace> {
+    Ok(match space {
+        0 => CoordinateSpace::L1Physical,
+        1 => CoordinateSpace::L2Sensory,
+        2 => CoordinateSpace::L3Motor,
+        3 => CoordinateSpace::L4Emotional,
+        4 => CoordinateSpace::L5Cognitive,
+        5 => CoordinateSpace::L6Social,
+        6 => CoordinateSpace::L7Temporal,
+        7 => CoordinateSpace::L8Abstract,
+        _ => return Err(PyValueError::new_err("Invalid space index (0-7)")),
+    })
+}
+
+/// Python wrapper for Grid
+///
+/// # Example
+///
+/// ```python
+/// import numpy as np
+///
+/// grid = neurograph.Grid()
+/// for token in tokens:
+///     grid.add(token)
+///
+/// arr = grid.states()
+/// states = np.asarray(arr)  # (N, 8) float32, no copy
+/// ```
+#[pyclass(name = "Grid")]
+pub struct PyGrid {
+    inner: Grid,
+}
+
+#[pymethods]
+impl PyGrid {
+    /// Create an empty Grid
+    #[new]
+    pub fn new() -> Self {
+        PyGrid { inner: Grid::new() }
+    }
+
+    /// Add a token
+    pub fn add(&mut self, token: &PyToken) -> PyResult<()> {
+        self.inner.add(token.inner).map_err(PyValueError::new_err)
+    }
+
+    /// Add many tokens (GIL released)
+    pub fn add_batch(&mut self, py: Python, tokens: Vec<PyRef<'_, PyToken>>) -> PyResult<()> {
+        let tokens: Vec<_> = tokens.iter().map(|t| t.inner).collect();
+        let grid = &mut self.inner;
+        py.allow_threads(|| {
+            for token in tokens {
+                grid.add(token)?;
+            }
+            Ok::<(), String>(())
+        })
+        .map_err(PyValueError::new_err)
+    }
+
+    /// Remove a token by ID
+    pub fn remove(&mut self, token_id: u32) -> Option<PyToken> {
+        self.inner.remove(token_id).map(|inner| PyToken { inner })
+    }
+
+    /// Get a token by ID
+    pub fn get(&self, token_id: u32) -> Option<PyToken> {
+        self.inner.get(token_id).map(|t| PyToken { inner: *t })
+    }
+
+    /// Find neighbors of a token within radius in one space (0-7)
+    #[pyo3(signature = (token_id, space, radius, max_results=10))]
+    pub fn find_neighbors(
+        &self,
+        token_id: u32,
+        space: u8,
+        radius: f32,
+        max_results: usize,
+    ) -> PyResult<Vec<(u32, f32)>> {
+        let space = coordinate_space(space)?;
+        Ok(self.inner.find_neighbors(token_id, space, radius, max_results))
+    }
+
+    /// Export all token states as one (N, 8) float32 array
+    ///
+    /// Row order matches `StateArray.ids`.
+    pub fn states(&self, py: Python) -> PyStateArray {
+        let grid = &self.inner;
+        py.allow_threads(|| PyStateArray::from_tokens(grid.tokens()))
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Grid(tokens={})", self.inner.len())
+    }
+}
+
+impl Default for PyGrid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
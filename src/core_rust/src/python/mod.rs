@@ -6,15 +6,21 @@
 use pyo3::prelude::*;
 
 mod token;
+mod state_array;
+mod grid;
 mod intuition;
 mod runtime;
 mod signal_system;
+mod experience;
 pub mod modules;
 
 use token::PyToken;
+use state_array::PyStateArray;
+use grid::PyGrid;
 use intuition::{PyIntuitionEngine, PyIntuitionConfig};
 use runtime::PyRuntime;
 use signal_system::PySignalSystem;
+use experience::{PyExperienceIterator, PyExperienceStream, PyLearner};
 
 /// NeuroGraph OS Python Module (_core)
 ///
@@ -31,6 +37,8 @@ fn _core(m: &Bound<'_, PyModule>) -> PyResult<()> {
 
     // Core types
     m.add_class::<PyToken>()?;
+    m.add_class::<PyGrid>()?;
+    m.add_class::<PyStateArray>()?;
 
     // Intuition Engine
     m.add_class::<PyIntuitionEngine>()?;
@@ -39,6 +47,11 @@ fn _core(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Signal System (new in v0.53.0)
     m.add_class::<PySignalSystem>()?;
 
+    // Experience Stream + Learner
+    m.add_class::<PyExperienceStream>()?;
+    m.add_class::<PyExperienceIterator>()?;
+    m.add_class::<PyLearner>()?;
+
     // Module Registry (new in v0.63.0)
     modules::register_module(m.py(), m)?;
 
@@ -0,0 +1,133 @@
+// Python bindings for bulk token state export
+//
+// Exposes a contiguous (N, 8) float32 block through the buffer protocol, so
+// `numpy.asarray(arr)` and `memoryview(arr)` wrap Rust memory without a copy.
+
+use pyo3::prelude::*;
+use pyo3::exceptions::PyBufferError;
+use pyo3::ffi;
+use std::os::raw::{c_int, c_void};
+use crate::Token;
+
+/// Width of a state row (one value per coordinate space)
+const STATE_DIM: usize = 8;
+
+/// Read-only (N, 8) float32 array of token states
+///
+/// Row `i` is the 8D state of token `ids[i]` (X axis of L1..L8).
+///
+/// # Example
+///
+/// ```python
+/// import numpy as np
+///
+/// arr = grid.states()
+/// states = np.asarray(arr)   # shape (N, 8), dtype float32, no copy
+/// ids = arr.ids              # token IDs, same order as rows
+/// ```
+#[pyclass(name = "StateArray")]
+pub struct PyStateArray {
+    ids: Vec<u32>,
+    data: Vec<f32>,
+    /// Buffer shape/strides; must outlive every exported view
+    shape: [ffi::Py_ssize_t; 2],
+    strides: [ffi::Py_ssize_t; 2],
+}
+
+impl PyStateArray {
+    /// Decode token states into one contiguous block
+    pub(crate) fn from_tokens<'a>(tokens: impl Iterator<Item = &'a Token>) -> Self {
+        let mut ids = Vec::new();
+        let mut data = Vec::new();
+        for token in tokens {
+            ids.push(token.id);
+            data.extend_from_slice(&token.to_state_f32());
+        }
+        Self::new(ids, data)
+    }
+
+    fn new(ids: Vec<u32>, data: Vec<f32>) -> Self {
+        let rows = ids.len() as ffi::Py_ssize_t;
+        let item = std::mem::size_of::<f32>() as ffi::Py_ssize_t;
+        Self {
+            ids,
+            data,
+            shape: [rows, STATE_DIM as ffi::Py_ssize_t],
+            strides: [item * STATE_DIM as ffi::Py_ssize_t, item],
+        }
+    }
+}
+
+#[pymethods]
+impl PyStateArray {
+    /// Token IDs, one per row
+    #[getter]
+    pub fn ids(&self) -> Vec<u32> {
+        self.ids.clone()
+    }
+
+    /// Array shape (N, 8)
+    #[getter]
+    pub fn shape(&self) -> (usize, usize) {
+        (self.ids.len(), STATE_DIM)
+    }
+
+    /// Row as a list of 8 floats
+    pub fn row(&self, index: usize) -> Option<Vec<f32>> {
+        self.data
+            .get(index * STATE_DIM..(index + 1) * STATE_DIM)
+            .map(|row| row.to_vec())
+    }
+
+    fn __len__(&self) -> usize {
+        self.ids.len()
+    }
+
+    unsafe fn __getbuffer__(
+        slf: PyRef<'_, Self>,
+        view: *mut ffi::Py_buffer,
+        flags: c_int,
+    ) -> PyResult<()> {
+        if view.is_null() {
+            return Err(PyBufferError::new_err("View is null"));
+        }
+        if (flags & ffi::PyBUF_WRITABLE) == ffi::PyBUF_WRITABLE {
+            return Err(PyBufferError::new_err("StateArray is read-only"));
+        }
+
+        ffi::Py_INCREF(slf.as_ptr());
+        (*view).obj = slf.as_ptr();
+        (*view).buf = slf.data.as_ptr() as *mut c_void;
+        (*view).len = (slf.data.len() * std::mem::size_of::<f32>()) as ffi::Py_ssize_t;
+        (*view).readonly = 1;
+        (*view).itemsize = std::mem::size_of::<f32>() as ffi::Py_ssize_t;
+        (*view).format = if (flags & ffi::PyBUF_FORMAT) == ffi::PyBUF_FORMAT {
+            c"f".as_ptr() as *mut _
+        } else {
+            std::ptr::null_mut()
+        };
+        (*view).ndim = 2;
+        (*view).shape = if (flags & ffi::PyBUF_ND) == ffi::PyBUF_ND {
+            slf.shape.as_ptr() as *mut _
+        } else {
+            std::ptr::null_mut()
+        };
+        (*view).strides = if (flags & ffi::PyBUF_STRIDES) == ffi::PyBUF_STRIDES {
+            slf.strides.as_ptr() as *mut _
+        } else {
+            std::ptr::null_mut()
+        };
+        (*view).suboffsets = std::ptr::null_mut();
+        (*view).internal = std::ptr::null_mut();
+
+        Ok(())
+    }
+
+    unsafe fn __releasebuffer__(&self, _view: *mut ffi::Py_buffer) {
+        // Data is owned by self and lives as long as the view holds a reference
+    }
+
+    fn __repr__(&self) -> String {
+        format!("StateArray(shape=({}, {}), dtype=float32)", self.ids.len(), STATE_DIM)
+    }
+}
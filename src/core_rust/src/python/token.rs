@@ -4,6 +4,7 @@
 
 use pyo3::prelude::*;
 use crate::Token;
+use super::state_array::PyStateArray;
 
 /// Python wrapper for Token
 ///
@@ -111,6 +112,32 @@ impl PyToken {
             .collect()
     }
 
+    /// Get 8D state (X axis of each coordinate space)
+    ///
+    /// Returns:
+    ///     list[float]: 8 values
+    #[getter]
+    pub fn state(&self) -> Vec<f32> {
+        self.inner.to_state_f32().to_vec()
+    }
+
+    /// Export states of many tokens as one (N, 8) float32 array
+    ///
+    /// Wrap with `numpy.asarray()` - no per-element Python objects,
+    /// no second copy.
+    ///
+    /// # Example
+    ///
+    /// ```python
+    /// tokens = Token.create_batch(100_000)
+    /// states = np.asarray(Token.states(tokens))  # (100000, 8) float32
+    /// ```
+    #[staticmethod]
+    pub fn states(py: Python, tokens: Vec<PyRef<'_, PyToken>>) -> PyStateArray {
+        let inner: Vec<Token> = tokens.iter().map(|t| t.inner).collect();
+        py.allow_threads(|| PyStateArray::from_tokens(inner.iter()))
+    }
+
     /// String representation
     fn __repr__(&self) -> String {
         let id = self.inner.id; // Copy to avoid packed field reference
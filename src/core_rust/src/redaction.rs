@@ -0,0 +1,344 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Redaction v1.0 - Shareable exports without personal text
+//!
+//! A [`Redactor`] turns experience and checkpoints into data that can be
+//! handed to someone else:
+//!
+//! - JSON fields that carry user text (`original_text`, `content`,
+//!   `prompt`, ... see [`RedactionConfig::text_keys`]) are removed, at any
+//!   depth (action metadata, ADNA and curiosity sections)
+//! - token labels are replaced by salted SHA-256 digests, so equal labels
+//!   stay equal without being readable
+//! - numeric states, actions, rewards, connections and graph structure are
+//!   kept as they are
+//!
+//! Every removed string is remembered; [`Redactor::finish`] scans the
+//! written output for them and returns a [`RedactionReport`] of what was
+//! removed and whether anything survived (`leaks`, reported as digests).
+//!
+//! Entry points: [`Redactor::export_experience`] writes experience events as
+//! JSON lines, `CheckpointManager::export_redacted` writes a redacted copy of
+//! a checkpoint.
+
+use crate::experience_stream::ExperienceStream;
+use crate::runtime_storage::RuntimeSnapshot;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::io::{self, Write};
+
+/// What to redact
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RedactionConfig {
+    /// Mixed into label digests; keep it secret to prevent dictionary lookups
+    pub salt: String,
+
+    /// JSON keys whose values are removed wherever they occur
+    pub text_keys: Vec<String>,
+
+    /// Removed strings shorter than this are not searched for in the output
+    pub min_leak_len: usize,
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        let text_keys = [
+            "original_text",
+            "text",
+            "content",
+            "message",
+            "prompt",
+            "input",
+            "label",
+            "matched_tokens",
+            "unknown_words",
+        ];
+        Self {
+            salt: String::new(),
+            text_keys: text_keys.iter().map(|key| key.to_string()).collect(),
+            min_leak_len: 4,
+        }
+    }
+}
+
+impl RedactionConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.text_keys.iter().any(|key| key.is_empty()) {
+            return Err("text_keys must not contain empty keys".to_string());
+        }
+        if self.min_leak_len == 0 {
+            return Err("min_leak_len must be > 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// What a redacted export removed, and whether it verified clean
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RedactionReport {
+    /// Token labels replaced by digests
+    pub labels_hashed: usize,
+
+    /// Removed JSON fields per key
+    pub fields_removed: BTreeMap<String, usize>,
+
+    /// Experience events written
+    pub events_exported: usize,
+
+    /// Events whose action metadata was redacted
+    pub metadata_redacted: usize,
+
+    /// Sections or files written after redaction
+    pub sections_redacted: Vec<String>,
+
+    /// Sections copied unchanged (numeric only)
+    pub sections_copied: Vec<String>,
+
+    /// Sections left out because their content is unknown
+    pub sections_dropped: Vec<String>,
+
+    /// Distinct strings removed
+    pub strings_removed: usize,
+
+    /// Digests of removed strings still found in the output
+    pub leaks: Vec<String>,
+}
+
+impl RedactionReport {
+    /// No removed string appears in the output
+    pub fn is_clean(&self) -> bool {
+        self.leaks.is_empty()
+    }
+}
+
+/// One redaction pass; collects what it removed for verification
+pub struct Redactor {
+    config: RedactionConfig,
+    removed: HashSet<String>,
+    report: RedactionReport,
+}
+
+impl Redactor {
+    pub fn new(config: RedactionConfig) -> Result<Self, String> {
+        config.validate()?;
+        Ok(Self {
+            config,
+            removed: HashSet::new(),
+            report: RedactionReport::default(),
+        })
+    }
+
+    /// Salted digest standing in for a label (`h:` + 16 hex digits)
+    pub fn hash_label(&self, label: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.config.salt.as_bytes());
+        hasher.update([0u8]);
+        hasher.update(label.as_bytes());
+        let digest = hasher.finalize();
+        let hex: String = digest[..8].iter().map(|b| format!("{:02x}", b)).collect();
+        format!("h:{}", hex)
+    }
+
+    /// Remember a removed string for verification
+    pub fn forget(&mut self, text: &str) {
+        if !text.is_empty() {
+            self.removed.insert(text.to_string());
+        }
+    }
+
+    /// Remove text fields from a JSON value, at any depth
+    pub fn redact_value(&mut self, value: &mut Value) {
+        match value {
+            Value::Object(fields) => {
+                let keys: Vec<String> = fields
+                    .keys()
+                    .filter(|key| self.config.text_keys.contains(key))
+                    .cloned()
+                    .collect();
+                for key in keys {
+                    if let Some(removed) = fields.remove(&key) {
+                        self.forget_strings(&removed);
+                        *self.report.fields_removed.entry(key).or_insert(0) += 1;
+                    }
+                }
+                fields.values_mut().for_each(|field| self.redact_value(field));
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.redact_value(item)),
+            _ => {}
+        }
+    }
+
+    /// Hash token labels in a runtime snapshot
+    pub fn redact_snapshot(&mut self, snapshot: &mut RuntimeSnapshot) {
+        for (_, label) in snapshot.labels.iter_mut() {
+            let hashed = self.hash_label(label);
+            self.forget(&std::mem::replace(label, hashed));
+            self.report.labels_hashed += 1;
+        }
+    }
+
+    /// Write events `[start, end)` as JSON lines: numeric fields plus
+    /// redacted action metadata
+    ///
+    /// Returns the number of events written.
+    pub fn export_experience<W: Write>(
+        &mut self,
+        stream: &ExperienceStream,
+        start: u64,
+        end: u64,
+        writer: &mut W,
+    ) -> io::Result<usize> {
+        let events = stream.query_range(start, end);
+        for event in &events {
+            let metadata = stream.get_metadata(event.event_id).map(|metadata| {
+                let mut parameters = metadata.parameters;
+                self.redact_value(&mut parameters);
+                self.report.metadata_redacted += 1;
+                json!({
+                    "intent_type": metadata.intent_type,
+                    "executor_id": metadata.executor_id,
+                    "parameters": parameters,
+                })
+            });
+            let line = json!({
+                "event_id": event.event_id.to_string(),
+                "timestamp": event.timestamp,
+                "episode_id": event.episode_id,
+                "step_number": event.step_number,
+                "event_type": event.event_type,
+                "flags": event.flags,
+                "state": event.state,
+                "action": event.action,
+                "rewards": [
+                    event.reward_homeostasis,
+                    event.reward_curiosity,
+                    event.reward_efficiency,
+                    event.reward_goal,
+                ],
+                "metadata": metadata,
+            });
+            serde_json::to_writer(&mut *writer, &line)?;
+            writer.write_all(b"\n")?;
+        }
+        self.report.events_exported += events.len();
+        Ok(events.len())
+    }
+
+    /// Note how a section was handled
+    pub fn record_section(&mut self, name: &str, handling: SectionHandling) {
+        let list = match handling {
+            SectionHandling::Redacted => &mut self.report.sections_redacted,
+            SectionHandling::Copied => &mut self.report.sections_copied,
+            SectionHandling::Dropped => &mut self.report.sections_dropped,
+        };
+        list.push(name.to_string());
+    }
+
+    /// Verify the written output and return the report
+    pub fn finish(mut self, outputs: &[&[u8]]) -> RedactionReport {
+        let mut leaks: Vec<String> = self
+            .removed
+            .iter()
+            .filter(|text| text.len() >= self.config.min_leak_len)
+            .filter(|text| outputs.iter().any(|output| contains(output, text.as_bytes())))
+            .map(|text| self.hash_label(text))
+            .collect();
+        leaks.sort();
+        self.report.leaks = leaks;
+        self.report.strings_removed = self.removed.len();
+        self.report
+    }
+
+    fn forget_strings(&mut self, value: &Value) {
+        match value {
+            Value::String(s) => self.forget(s),
+            Value::Array(items) => items.iter().for_each(|item| self.forget_strings(item)),
+            Value::Object(fields) => fields.values().for_each(|field| self.forget_strings(field)),
+            _ => {}
+        }
+    }
+}
+
+/// How an export treated one section
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SectionHandling {
+    Redacted,
+    Copied,
+    Dropped,
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    !needle.is_empty() && haystack.windows(needle.len()).any(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::experience_stream::{ActionMetadata, ExperienceEvent};
+
+    #[test]
+    fn test_redact_value_and_labels() {
+        let mut redactor = Redactor::new(RedactionConfig::default()).unwrap();
+        let mut value = json!({
+            "signal_type": "SemanticQuery",
+            "metadata": {"original_text": "call Alice at home", "matched_tokens": [["alice", 7, 0.9]]},
+            "state": [0.5, 0.1],
+        });
+        redactor.redact_value(&mut value);
+        assert_eq!(value, json!({"signal_type": "SemanticQuery", "metadata": {}, "state": [0.5, 0.1]}));
+
+        let a = redactor.hash_label("alice");
+        assert_eq!(a, redactor.hash_label("alice"));
+        assert_ne!(a, redactor.hash_label("bob"));
+        let salted = Redactor::new(RedactionConfig { salt: "s".to_string(), ..Default::default() }).unwrap();
+        assert_ne!(a, salted.hash_label("alice"));
+
+        let report = redactor.finish(&[b"{\"note\": \"call Alice at home\"}"]);
+        assert_eq!(report.fields_removed["original_text"], 1);
+        assert_eq!(report.leaks.len(), 1);
+        assert!(!report.is_clean());
+    }
+
+    #[test]
+    fn test_export_experience() {
+        let stream = ExperienceStream::new(100, 10);
+        let event = ExperienceEvent { state: [0.25; 8], reward_goal: 1.0, ..Default::default() };
+        let metadata = ActionMetadata {
+            intent_type: "SemanticQuery".to_string(),
+            executor_id: "noop".to_string(),
+            parameters: json!({"metadata": {"original_text": "my address is 1 Main St"}}),
+        };
+        stream.write_event_with_metadata(event, metadata).unwrap();
+        stream.write_event(ExperienceEvent { event_id: 2, ..Default::default() }).unwrap();
+
+        let mut redactor = Redactor::new(RedactionConfig::default()).unwrap();
+        let mut out = Vec::new();
+        assert_eq!(redactor.export_experience(&stream, 0, 10, &mut out).unwrap(), 2);
+        let report = redactor.finish(&[&out]);
+        assert!(report.is_clean());
+        assert_eq!(report.metadata_redacted, 1);
+        assert_eq!(report.events_exported, 2);
+
+        let first: Value = serde_json::from_str(std::str::from_utf8(&out).unwrap().lines().next().unwrap()).unwrap();
+        assert_eq!(first["state"][0], 0.25);
+        assert_eq!(first["rewards"][3], 1.0);
+        assert_eq!(first["metadata"]["parameters"], json!({"metadata": {}}));
+    }
+}
@@ -516,6 +516,22 @@ impl AssociativeMemory {
         self.memory.is_empty()
     }
 
+    /// All (hash, connection_id) pairs (for checkpointing)
+    pub fn entries(&self) -> Vec<(u64, u64)> {
+        self.memory
+            .iter()
+            .flat_map(|entry| {
+                let hash = *entry.key();
+                entry.value().iter().map(move |&id| (hash, id)).collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Remove all entries
+    pub fn clear(&self) {
+        self.memory.clear();
+    }
+
     /// TODO v0.32.0: Implement LRU eviction
     ///
     /// This method will track last access time for each entry and
@@ -0,0 +1,803 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Deterministic replay v1.0 - record and re-run the cognitive loop
+//!
+//! A `ReplayRecorder` attached to the Gateway (`Gateway::set_replay_recorder`)
+//! writes every injected `InputSignal` to a JSON-lines journal, together with
+//! its offset from the start of the recording and a per-signal RNG seed
+//! derived from the journal seed. The header names the checkpoint the
+//! recording started from.
+//!
+//! `ReplayJournal::replay` injects the entries again in order (optionally
+//! paced by the recorded offsets); `replay_from_checkpoint` restores the
+//! checkpoint first. The `on_entry` hook receives each entry before it is
+//! injected so callers can reseed their RNGs with `entry.seed`.
+//!
+//! `StateDigest` captures graph edges and ADNA state after a run; `diff`
+//! compares two digests to find where runs diverged.
+//!
+//! With at-rest encryption enabled (`encryption::load_at_rest_cipher`)
+//! journal files are sealed line by line (`encryption::seal_record`, line 0
+//! is the header), so a recording still survives a crash after each signal;
+//! digests are sealed as whole files.
+//!
+//! Journal format:
+//! ```text
+//! {"format_version":1,"seed":42,"started_at":...,"checkpoint":"cp-...","crate_version":"..."}
+//! {"seq":0,"offset_us":0,"seed":...,"signal":{"Text":{...}}}
+//! {"seq":1,"offset_us":1530,"seed":...,"signal":{...}}
+//! ```
+
+use crate::action_executor::ActionResult;
+use crate::adna::InMemoryADNAReader;
+use crate::checkpoint::{CheckpointError, CheckpointManager};
+use crate::encryption::{self, EncryptionError, FileCipher};
+use crate::gateway::signals::InputSignal;
+use crate::gateway::Gateway;
+use crate::graph::Graph;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Current journal format version
+pub const REPLAY_FORMAT_VERSION: u32 = 1;
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// Replay errors
+#[derive(Debug)]
+pub enum ReplayError {
+    IoError(String),
+    Format(String),
+    UnsupportedVersion(u32),
+    NoCheckpoint,
+    Checkpoint(CheckpointError),
+    Encryption(EncryptionError),
+}
+
+impl std::fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayError::IoError(msg) => write!(f, "IO error: {}", msg),
+            ReplayError::Format(msg) => write!(f, "Invalid journal: {}", msg),
+            ReplayError::UnsupportedVersion(v) => write!(f, "Unsupported journal format version {}", v),
+            ReplayError::NoCheckpoint => write!(f, "Journal does not reference a checkpoint"),
+            ReplayError::Checkpoint(e) => write!(f, "Checkpoint restore failed: {}", e),
+            ReplayError::Encryption(e) => write!(f, "Journal encryption failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl From<std::io::Error> for ReplayError {
+    fn from(e: std::io::Error) -> Self {
+        ReplayError::IoError(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for ReplayError {
+    fn from(e: serde_json::Error) -> Self {
+        ReplayError::Format(e.to_string())
+    }
+}
+
+impl From<EncryptionError> for ReplayError {
+    fn from(e: EncryptionError) -> Self {
+        ReplayError::Encryption(e)
+    }
+}
+
+// ============================================================================
+// Journal
+// ============================================================================
+
+/// First line of a journal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayHeader {
+    pub format_version: u32,
+    /// Base seed; per-signal seeds are derived from it
+    pub seed: u64,
+    /// Recording start (Unix milliseconds)
+    pub started_at: u64,
+    /// Checkpoint the recording started from
+    pub checkpoint: Option<String>,
+    pub crate_version: String,
+}
+
+/// One recorded signal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayEntry {
+    pub seq: u64,
+    /// Microseconds since the start of the recording
+    pub offset_us: u64,
+    /// RNG seed for processing this signal
+    pub seed: u64,
+    pub signal: InputSignal,
+}
+
+/// Per-signal seed (splitmix64 of base seed and sequence number)
+pub fn derive_seed(base: u64, seq: u64) -> u64 {
+    let mut z = base.wrapping_add(seq.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Cipher and journal path that lines are sealed for
+struct Sealing {
+    cipher: Arc<FileCipher>,
+    path: PathBuf,
+}
+
+enum Sink {
+    Writer {
+        writer: Box<dyn Write + Send>,
+        seal: Option<Sealing>,
+    },
+    Memory(Vec<ReplayEntry>),
+}
+
+/// Write `value` as line `index` of a journal, sealed if `seal` is given
+fn write_line<W: Write + ?Sized>(
+    writer: &mut W,
+    value: &impl Serialize,
+    seal: Option<&Sealing>,
+    index: u64,
+) -> Result<(), ReplayError> {
+    let json = serde_json::to_vec(value)?;
+    match seal {
+        Some(seal) => writer.write_all(encryption::seal_record(&seal.cipher, &json, &seal.path, index)?.as_bytes())?,
+        None => writer.write_all(&json)?,
+    }
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+struct RecorderState {
+    next_seq: u64,
+    sink: Sink,
+}
+
+/// Records injected signals to a journal (thread-safe, shareable via `Arc`)
+pub struct ReplayRecorder {
+    header: ReplayHeader,
+    start: Instant,
+    state: Mutex<RecorderState>,
+}
+
+impl ReplayRecorder {
+    /// Record to a journal file (truncated if it exists), sealed with the
+    /// installed at-rest cipher if any
+    pub fn create(path: impl AsRef<Path>, seed: u64, checkpoint: Option<String>) -> Result<Self, ReplayError> {
+        Self::create_with(path, seed, checkpoint, encryption::installed_cipher())
+    }
+
+    /// [`create`](Self::create) with an explicit cipher
+    pub fn create_with(
+        path: impl AsRef<Path>,
+        seed: u64,
+        checkpoint: Option<String>,
+        cipher: Option<Arc<FileCipher>>,
+    ) -> Result<Self, ReplayError> {
+        let path = path.as_ref();
+        let writer = BufWriter::new(File::create(path)?);
+        let seal = cipher.map(|cipher| Sealing { cipher, path: path.to_path_buf() });
+        Self::start(Box::new(writer), seal, seed, checkpoint)
+    }
+
+    /// Record to any writer; the header is written immediately
+    pub fn to_writer<W: Write + Send + 'static>(
+        writer: W,
+        seed: u64,
+        checkpoint: Option<String>,
+    ) -> Result<Self, ReplayError> {
+        Self::start(Box::new(writer), None, seed, checkpoint)
+    }
+
+    fn start(
+        mut writer: Box<dyn Write + Send>,
+        seal: Option<Sealing>,
+        seed: u64,
+        checkpoint: Option<String>,
+    ) -> Result<Self, ReplayError> {
+        let header = new_header(seed, checkpoint);
+        write_line(&mut writer, &header, seal.as_ref(), 0)?;
+        writer.flush()?;
+        Ok(Self::with_sink(header, Sink::Writer { writer, seal }))
+    }
+
+    /// Keep entries in memory (see `journal`)
+    pub fn in_memory(seed: u64, checkpoint: Option<String>) -> Self {
+        Self::with_sink(new_header(seed, checkpoint), Sink::Memory(Vec::new()))
+    }
+
+    fn with_sink(header: ReplayHeader, sink: Sink) -> Self {
+        Self {
+            header,
+            start: Instant::now(),
+            state: Mutex::new(RecorderState { next_seq: 0, sink }),
+        }
+    }
+
+    pub fn header(&self) -> &ReplayHeader {
+        &self.header
+    }
+
+    /// Number of recorded signals
+    pub fn len(&self) -> u64 {
+        self.state.lock().next_seq
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Append a signal; returns its seed
+    ///
+    /// Each entry is flushed, so the journal survives a crash right after
+    /// the signal that caused it.
+    pub fn record(&self, signal: &InputSignal) -> Result<u64, ReplayError> {
+        let mut state = self.state.lock();
+        let seq = state.next_seq;
+        let entry = ReplayEntry {
+            seq,
+            offset_us: self.start.elapsed().as_micros() as u64,
+            seed: derive_seed(self.header.seed, seq),
+            signal: signal.clone(),
+        };
+        let seed = entry.seed;
+
+        match &mut state.sink {
+            Sink::Writer { writer, seal } => {
+                write_line(writer, &entry, seal.as_ref(), seq + 1)?;
+                writer.flush()?;
+            }
+            Sink::Memory(entries) => entries.push(entry),
+        }
+        state.next_seq += 1;
+        Ok(seed)
+    }
+
+    /// Recorded journal (in-memory recorders only)
+    pub fn journal(&self) -> Option<ReplayJournal> {
+        match &self.state.lock().sink {
+            Sink::Memory(entries) => Some(ReplayJournal {
+                header: self.header.clone(),
+                entries: entries.clone(),
+            }),
+            Sink::Writer { .. } => None,
+        }
+    }
+}
+
+fn new_header(seed: u64, checkpoint: Option<String>) -> ReplayHeader {
+    ReplayHeader {
+        format_version: REPLAY_FORMAT_VERSION,
+        seed,
+        started_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0),
+        checkpoint,
+        crate_version: env!("CARGO_PKG_VERSION").to_string(),
+    }
+}
+
+/// Replay options
+#[derive(Debug, Clone)]
+pub struct ReplayOptions {
+    /// Wait between signals as recorded (false = inject back to back)
+    pub pace: bool,
+    /// Pacing speed multiplier (2.0 = twice as fast)
+    pub speed: f64,
+    /// Wait up to this long for each signal's ActionResult
+    pub result_timeout: Option<Duration>,
+}
+
+impl Default for ReplayOptions {
+    fn default() -> Self {
+        Self {
+            pace: false,
+            speed: 1.0,
+            result_timeout: None,
+        }
+    }
+}
+
+impl ReplayOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.speed.is_finite() || self.speed <= 0.0 {
+            return Err("speed must be a finite value > 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Outcome of a replay run
+#[derive(Debug, Default)]
+pub struct ReplayRunReport {
+    /// Entries accepted by the Gateway
+    pub replayed: usize,
+    /// Entries the Gateway rejected: (seq, error)
+    pub rejected: Vec<(u64, String)>,
+    /// Results received within `result_timeout`: (seq, result)
+    pub results: Vec<(u64, ActionResult)>,
+}
+
+/// A loaded journal
+#[derive(Debug, Clone)]
+pub struct ReplayJournal {
+    pub header: ReplayHeader,
+    pub entries: Vec<ReplayEntry>,
+}
+
+impl ReplayJournal {
+    /// Load a journal file; sealed journals are opened with the installed
+    /// at-rest cipher
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+        Self::load_with(path, encryption::installed_cipher().as_deref())
+    }
+
+    /// [`load`](Self::load) with an explicit cipher
+    ///
+    /// With a cipher every line must be sealed for this file and position;
+    /// only a torn last line is ignored.
+    pub fn load_with(path: impl AsRef<Path>, cipher: Option<&FileCipher>) -> Result<Self, ReplayError> {
+        let path = path.as_ref();
+        let lines: Vec<String> = BufReader::new(File::open(path)?).lines().collect::<Result<_, _>>()?;
+        let Some(cipher) = cipher else {
+            if lines.first().is_some_and(|line| encryption::is_sealed_record(line)) {
+                return Err(EncryptionError::MissingKey.into());
+            }
+            return Self::from_lines(lines);
+        };
+
+        let last = lines.len().saturating_sub(1);
+        let mut opened = Vec::with_capacity(lines.len());
+        for (i, line) in lines.iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match encryption::open_record(cipher, line, path, i as u64) {
+                Ok(json) => opened.push(String::from_utf8(json).map_err(|e| ReplayError::Format(e.to_string()))?),
+                Err(_) if i == last && i > 0 => break,
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Self::from_lines(opened)
+    }
+
+    /// Parse a journal; a truncated last line (crash mid-write) is ignored
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, ReplayError> {
+        Self::from_lines(reader.lines().collect::<Result<_, _>>()?)
+    }
+
+    fn from_lines(lines: Vec<String>) -> Result<Self, ReplayError> {
+        let mut lines = lines.into_iter();
+        let header: ReplayHeader = match lines.next() {
+            Some(line) => serde_json::from_str(&line)?,
+            None => return Err(ReplayError::Format("missing header".to_string())),
+        };
+        if header.format_version > REPLAY_FORMAT_VERSION {
+            return Err(ReplayError::UnsupportedVersion(header.format_version));
+        }
+
+        let lines: Vec<String> = lines.collect();
+        let last = lines.len().saturating_sub(1);
+        let mut entries = Vec::with_capacity(lines.len());
+        for (i, line) in lines.iter().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<ReplayEntry>(line) {
+                Ok(entry) => entries.push(entry),
+                Err(_) if i == last => break,
+                Err(e) => return Err(ReplayError::Format(format!("line {}: {}", i + 2, e))),
+            }
+        }
+        Ok(Self { header, entries })
+    }
+
+    /// Write the journal, sealed with the installed at-rest cipher if any
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ReplayError> {
+        self.save_with(path, encryption::installed_cipher())
+    }
+
+    /// [`save`](Self::save) with an explicit cipher
+    pub fn save_with(&self, path: impl AsRef<Path>, cipher: Option<Arc<FileCipher>>) -> Result<(), ReplayError> {
+        let path = path.as_ref();
+        let seal = cipher.map(|cipher| Sealing { cipher, path: path.to_path_buf() });
+        let mut writer = BufWriter::new(File::create(path)?);
+        write_line(&mut writer, &self.header, seal.as_ref(), 0)?;
+        for (i, entry) in self.entries.iter().enumerate() {
+            write_line(&mut writer, entry, seal.as_ref(), i as u64 + 1)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Inject every entry into the Gateway in recorded order
+    ///
+    /// The Gateway should be in the state the recording started from (see
+    /// `replay_from_checkpoint`). A recorder attached to it records the
+    /// replay as a new journal.
+    pub async fn replay<F>(&self, gateway: &Gateway, options: &ReplayOptions, mut on_entry: F) -> ReplayRunReport
+    where
+        F: FnMut(&ReplayEntry),
+    {
+        let start = Instant::now();
+        let mut report = ReplayRunReport::default();
+
+        for entry in &self.entries {
+            if options.pace {
+                let due = Duration::from_micros((entry.offset_us as f64 / options.speed) as u64);
+                if let Some(wait) = due.checked_sub(start.elapsed()) {
+                    tokio::time::sleep(wait).await;
+                }
+            }
+
+            on_entry(entry);
+            match gateway.inject(entry.signal.clone()).await {
+                Ok((_, result_rx)) => {
+                    report.replayed += 1;
+                    if let Some(timeout) = options.result_timeout {
+                        if let Ok(Ok(result)) = tokio::time::timeout(timeout, result_rx).await {
+                            report.results.push((entry.seq, result));
+                        }
+                    }
+                }
+                Err(e) => report.rejected.push((entry.seq, e.to_string())),
+            }
+        }
+        report
+    }
+
+    /// Restore the journal's checkpoint, then replay
+    pub async fn replay_from_checkpoint<F>(
+        &self,
+        manager: &CheckpointManager,
+        gateway: &Gateway,
+        options: &ReplayOptions,
+        on_entry: F,
+    ) -> Result<ReplayRunReport, ReplayError>
+    where
+        F: FnMut(&ReplayEntry),
+    {
+        let id = self.header.checkpoint.as_deref().ok_or(ReplayError::NoCheckpoint)?;
+        manager.restore(id).await.map_err(ReplayError::Checkpoint)?;
+        Ok(self.replay(gateway, options, on_entry).await)
+    }
+}
+
+// ============================================================================
+// State comparison
+// ============================================================================
+
+/// Edge state in a digest
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EdgeDigest {
+    pub from_id: u32,
+    pub to_id: u32,
+    pub weight: f32,
+    pub active_levels: u8,
+}
+
+/// Comparable snapshot of graph and ADNA state after a run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StateDigest {
+    pub edges: BTreeMap<u64, EdgeDigest>,
+    /// ADNA export (`InMemoryADNAReader::export_state`), Null if not captured
+    pub adna: Value,
+}
+
+impl StateDigest {
+    /// Graph edges only
+    pub fn from_graph(graph: &Graph) -> Self {
+        let edges = graph
+            .edges()
+            .map(|(id, info)| {
+                (
+                    id,
+                    EdgeDigest {
+                        from_id: info.from_id,
+                        to_id: info.to_id,
+                        weight: info.weight,
+                        active_levels: info.active_levels,
+                    },
+                )
+            })
+            .collect();
+        Self { edges, adna: Value::Null }
+    }
+
+    /// Graph edges and ADNA state
+    pub async fn capture(graph: &Graph, adna: &InMemoryADNAReader) -> Result<Self, ReplayError> {
+        let mut digest = Self::from_graph(graph);
+        digest.adna = serde_json::from_slice(&adna.export_state().await?)?;
+        Ok(digest)
+    }
+
+    /// Load a digest; sealed files are opened with the installed at-rest cipher
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ReplayError> {
+        let path = path.as_ref();
+        let data = encryption::open_file(encryption::installed_cipher().as_deref(), std::fs::read(path)?, path)?;
+        Ok(serde_json::from_slice(&data)?)
+    }
+
+    /// Write the digest, sealed with the installed at-rest cipher if any
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ReplayError> {
+        let path = path.as_ref();
+        let data = encryption::seal_file(encryption::installed_cipher().as_deref(), serde_json::to_vec(self)?, path)?;
+        std::fs::write(path, data)?;
+        Ok(())
+    }
+
+    /// Differences from `self` to `other`; weights within `weight_tolerance`
+    /// count as equal
+    pub fn diff(&self, other: &StateDigest, weight_tolerance: f32) -> StateDiff {
+        let mut diff = StateDiff::default();
+
+        for (id, before) in &self.edges {
+            match other.edges.get(id) {
+                None => diff.edges_removed.push(*id),
+                Some(after) => {
+                    if (before.from_id, before.to_id) != (after.from_id, after.to_id) {
+                        diff.edges_rewired.push(*id);
+                    }
+                    if (before.weight - after.weight).abs() > weight_tolerance {
+                        diff.weights_changed.push((*id, before.weight, after.weight));
+                    }
+                    if before.active_levels != after.active_levels {
+                        diff.levels_changed.push(*id);
+                    }
+                }
+            }
+        }
+        diff.edges_added = other
+            .edges
+            .keys()
+            .filter(|id| !self.edges.contains_key(id))
+            .copied()
+            .collect();
+
+        let mut before = BTreeMap::new();
+        let mut after = BTreeMap::new();
+        flatten_json("", &self.adna, &mut before);
+        flatten_json("", &other.adna, &mut after);
+        for (path, value) in &before {
+            if after.get(path) != Some(value) {
+                diff.adna_changed.push(path.clone());
+            }
+        }
+        for path in after.keys() {
+            if !before.contains_key(path) {
+                diff.adna_changed.push(path.clone());
+            }
+        }
+        diff.adna_changed.sort();
+        diff
+    }
+}
+
+/// Leaf values by JSON path (`/config/weights/0`)
+fn flatten_json<'a>(prefix: &str, value: &'a Value, out: &mut BTreeMap<String, &'a Value>) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                flatten_json(&format!("{}/{}", prefix, key), child, out);
+            }
+        }
+        Value::Array(items) => {
+            for (i, child) in items.iter().enumerate() {
+                flatten_json(&format!("{}/{}", prefix, i), child, out);
+            }
+        }
+        leaf => {
+            out.insert(prefix.to_string(), leaf);
+        }
+    }
+}
+
+/// Result of `StateDigest::diff`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StateDiff {
+    pub edges_added: Vec<u64>,
+    pub edges_removed: Vec<u64>,
+    /// Same edge ID, different endpoints
+    pub edges_rewired: Vec<u64>,
+    /// (edge_id, before, after)
+    pub weights_changed: Vec<(u64, f32, f32)>,
+    pub levels_changed: Vec<u64>,
+    /// JSON paths of ADNA values that differ
+    pub adna_changed: Vec<String>,
+}
+
+impl StateDiff {
+    pub fn is_identical(&self) -> bool {
+        self.edges_added.is_empty()
+            && self.edges_removed.is_empty()
+            && self.edges_rewired.is_empty()
+            && self.weights_changed.is_empty()
+            && self.levels_changed.is_empty()
+            && self.adna_changed.is_empty()
+    }
+}
+
+impl std::fmt::Display for StateDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_identical() {
+            return write!(f, "identical");
+        }
+        writeln!(
+            f,
+            "edges: +{} -{} rewired {} weights {} levels {}; adna: {} values",
+            self.edges_added.len(),
+            self.edges_removed.len(),
+            self.edges_rewired.len(),
+            self.weights_changed.len(),
+            self.levels_changed.len(),
+            self.adna_changed.len()
+        )?;
+        for (id, before, after) in &self.weights_changed {
+            writeln!(f, "  edge {}: {} -> {}", id, before, after)?;
+        }
+        for path in &self.adna_changed {
+            writeln!(f, "  adna {}", path)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adna::AppraiserConfig;
+    use crate::bootstrap::{BootstrapConfig, BootstrapLibrary};
+    use crate::gateway::config::GatewayConfig;
+    use std::sync::Arc;
+    use tokio::sync::mpsc;
+
+    fn direct(value: f32, label: &str) -> InputSignal {
+        InputSignal::DirectState {
+            state: [value; 8],
+            label: Some(label.to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let bootstrap = Arc::new(parking_lot::RwLock::new(BootstrapLibrary::new(BootstrapConfig::default())));
+        let (tx, mut rx) = mpsc::channel(16);
+        let gateway = Gateway::new(tx, bootstrap, GatewayConfig::default());
+
+        let recorder = Arc::new(ReplayRecorder::in_memory(42, Some("cp-1".to_string())));
+        gateway.set_replay_recorder(recorder.clone());
+        for (i, label) in ["a", "b", "c"].iter().enumerate() {
+            gateway.inject(direct(i as f32 * 0.1, label)).await.unwrap();
+            rx.recv().await.unwrap();
+        }
+        gateway.clear_replay_recorder();
+
+        let journal = recorder.journal().unwrap();
+        assert_eq!(journal.entries.len(), 3);
+        assert_eq!(journal.entries[1].seed, derive_seed(42, 1));
+        assert_ne!(journal.entries[0].seed, journal.entries[1].seed);
+
+        // Round trip through the JSON-lines format, with a torn last line
+        let mut bytes = serde_json::to_vec(&journal.header).unwrap();
+        bytes.push(b'\n');
+        for entry in &journal.entries {
+            bytes.extend(serde_json::to_vec(entry).unwrap());
+            bytes.push(b'\n');
+        }
+        bytes.extend_from_slice(b"{\"seq\":3,\"off");
+        let loaded = ReplayJournal::from_reader(&bytes[..]).unwrap();
+        assert_eq!(loaded.header, journal.header);
+        assert_eq!(loaded.entries.len(), 3);
+
+        let mut seeds = Vec::new();
+        let report = loaded
+            .replay(&gateway, &ReplayOptions::default(), |entry| seeds.push(entry.seed))
+            .await;
+        assert_eq!(report.replayed, 3);
+        assert!(report.rejected.is_empty());
+        assert_eq!(seeds, journal.entries.iter().map(|e| e.seed).collect::<Vec<_>>());
+        for i in 0..3 {
+            assert_eq!(rx.recv().await.unwrap().state, [i as f32 * 0.1; 8]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_state_diff() {
+        let mut graph = Graph::new();
+        graph.add_node(1);
+        graph.add_node(2);
+        graph.add_node(3);
+        graph.add_edge(10, 1, 2, 0, 0.5, false).unwrap();
+        graph.add_edge(11, 2, 3, 0, 0.5, false).unwrap();
+
+        let adna = InMemoryADNAReader::new(AppraiserConfig::default());
+        let before = StateDigest::capture(&graph, &adna).await.unwrap();
+        assert!(before.diff(&before, 0.0).is_identical());
+
+        graph.set_edge_weight(10, 0.9);
+        graph.remove_edge(11);
+        graph.add_edge(12, 1, 3, 0, 0.1, false).unwrap();
+        let mut after = StateDigest::capture(&graph, &adna).await.unwrap();
+        after.adna["config"]["changed"] = Value::Bool(true);
+
+        let diff = before.diff(&after, 1e-6);
+        assert_eq!(diff.edges_added, vec![12]);
+        assert_eq!(diff.edges_removed, vec![11]);
+        assert_eq!(diff.weights_changed, vec![(10, 0.5, 0.9)]);
+        assert_eq!(diff.adna_changed, vec!["/config/changed".to_string()]);
+        assert!(before.diff(&after, 1.0).weights_changed.is_empty());
+        assert!(diff.to_string().starts_with("edges: +1 -1"));
+    }
+
+    #[test]
+    fn test_sealed_journal() {
+        let cipher = Arc::new(FileCipher::new(&FileCipher::generate_key()));
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run.jsonl");
+        let recorder = ReplayRecorder::create_with(&path, 7, None, Some(cipher.clone())).unwrap();
+        for label in ["first_secret", "second_secret", "third_secret"] {
+            recorder.record(&direct(0.5, label)).unwrap();
+        }
+        drop(recorder);
+
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(!text.contains("secret"));
+        let journal = ReplayJournal::load_with(&path, Some(&cipher)).unwrap();
+        assert_eq!(journal.header.seed, 7);
+        assert_eq!(journal.entries.len(), 3);
+        assert!(matches!(
+            ReplayJournal::load_with(&path, None),
+            Err(ReplayError::Encryption(EncryptionError::MissingKey))
+        ));
+
+        // A torn last line is ignored; reordered lines fail authentication
+        let lines: Vec<&str> = text.lines().collect();
+        let torn = format!("{}\n{}", lines[..3].join("\n"), &lines[3][..20]);
+        std::fs::write(&path, torn).unwrap();
+        assert_eq!(ReplayJournal::load_with(&path, Some(&cipher)).unwrap().entries.len(), 2);
+        let swapped = [lines[0], lines[2], lines[1], lines[3]].join("\n");
+        std::fs::write(&path, swapped).unwrap();
+        assert!(matches!(
+            ReplayJournal::load_with(&path, Some(&cipher)),
+            Err(ReplayError::Encryption(EncryptionError::Decrypt))
+        ));
+
+        // A plain journal is refused when a key is configured
+        journal.save_with(&path, None).unwrap();
+        assert!(matches!(
+            ReplayJournal::load_with(&path, Some(&cipher)),
+            Err(ReplayError::Encryption(EncryptionError::NotSealed))
+        ));
+        journal.save_with(&path, Some(cipher.clone())).unwrap();
+        assert_eq!(ReplayJournal::load_with(&path, Some(&cipher)).unwrap().entries.len(), 3);
+    }
+}
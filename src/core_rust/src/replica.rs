@@ -0,0 +1,600 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Read replicas v1.0 - scale `/query` horizontally off one learning writer
+//!
+//! One writer instance runs the learning loop; any number of read-only
+//! replicas serve queries from a copy of its graph:
+//!
+//! - the writer attaches a [`ChangeFeed`] to its `RuntimeStorage`
+//!   (`RuntimeStorage::set_change_feed`); every connection mutation gets a
+//!   sequence number, and `GET /api/v1/replication/changes` serves them
+//! - a replica applies the batches through a [`Replica`], mirroring them into
+//!   its bootstrap graph, and [`ReplicaFollower`] (feature `sync`) polls the
+//!   writer
+//!
+//! Unlike knowledge sync (`crate::sync`) there is no conflict resolution:
+//! replicas never learn, so they take the writer's connections verbatim,
+//! under the writer's IDs. A replica API (`ApiState::with_replica`) rejects
+//! write endpoints; the host should not start IntuitionEngine or
+//! EvolutionManager for it.
+//!
+//! The feed keeps the last `capacity` changes in memory. A replica that
+//! starts fresh, falls behind the retained window, or sees the writer restart
+//! (new feed epoch) gets a full snapshot instead. Changes are whole-record
+//! upserts/removals, so re-applying one is harmless.
+
+use crate::bootstrap::BootstrapLibrary;
+use crate::connection_v3::{ConnectionMutability, ConnectionV3};
+use crate::edge_history::WeightSource;
+use crate::graph::Graph;
+use crate::migration::BinaryFormat;
+use crate::profile_bundle::{from_hex, to_hex};
+use crate::runtime_storage::RuntimeStorage;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashSet, VecDeque};
+use std::sync::{Arc, Weak};
+
+/// Batch format version; bumped on incompatible changes
+pub const REPLICATION_PROTOCOL_VERSION: u32 = 1;
+
+/// Replication errors
+#[derive(Debug, thiserror::Error)]
+pub enum ReplicaError {
+    #[error("Unsupported replication protocol version {0} (expected {REPLICATION_PROTOCOL_VERSION})")]
+    UnsupportedVersion(u32),
+
+    #[error("Unsupported connection format version {0}")]
+    UnsupportedFormat(u32),
+
+    #[error("Malformed change {seq}: {reason}")]
+    Malformed { seq: u64, reason: String },
+
+    #[error("Batch continues from {after}, replica is at {applied}")]
+    OutOfOrder { applied: u64, after: u64 },
+
+    #[error("Replication transport error: {0}")]
+    Transport(String),
+}
+
+/// Change feed configuration
+#[derive(Debug, Clone)]
+pub struct ChangeFeedConfig {
+    /// Changes retained in memory; older replicas resync from a snapshot
+    pub capacity: usize,
+
+    /// Upper bound on changes per batch
+    pub max_batch: usize,
+}
+
+impl Default for ChangeFeedConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 100_000,
+            max_batch: 1_000,
+        }
+    }
+}
+
+impl ChangeFeedConfig {
+    /// Validate configuration
+    pub fn validate(&self) -> Result<(), String> {
+        if self.capacity == 0 {
+            return Err("capacity must be > 0".to_string());
+        }
+        if self.max_batch == 0 {
+            return Err("max_batch must be > 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// One graph mutation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum GraphChange {
+    /// Connection created or updated; `connection` is the hex-encoded
+    /// `ConnectionV3::to_bytes` record
+    ConnectionUpdated { id: u64, connection: String },
+    /// Connection deleted
+    ConnectionRemoved { id: u64 },
+}
+
+impl GraphChange {
+    fn updated(id: u64, connection: &ConnectionV3) -> Self {
+        GraphChange::ConnectionUpdated { id, connection: to_hex(&connection.to_bytes()) }
+    }
+}
+
+/// A change with its position in the feed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SequencedChange {
+    pub seq: u64,
+    #[serde(flatten)]
+    pub change: GraphChange,
+}
+
+/// Changes served to a replica
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChangeBatch {
+    pub version: u32,
+    /// `ConnectionV3` binary format version of the encoded records
+    pub connection_format: u32,
+    /// Feed instance; changes when the writer restarts
+    pub epoch: String,
+    /// Sequence number the batch continues from
+    pub after: u64,
+    /// Latest sequence number on the writer
+    pub head: u64,
+    /// `changes` is every current connection; anything else is stale
+    pub snapshot: bool,
+    pub changes: Vec<SequencedChange>,
+}
+
+impl ChangeBatch {
+    /// Sequence number a replica is at after applying this batch
+    pub fn last_seq(&self) -> u64 {
+        if self.snapshot {
+            return self.head;
+        }
+        self.changes.last().map_or(self.after, |c| c.seq)
+    }
+}
+
+struct FeedLog {
+    head: u64,
+    changes: VecDeque<SequencedChange>,
+}
+
+/// Writer side: sequence-numbered connection mutations
+pub struct ChangeFeed {
+    config: ChangeFeedConfig,
+    epoch: String,
+    storage: Weak<RuntimeStorage>,
+    log: Mutex<FeedLog>,
+}
+
+impl ChangeFeed {
+    /// Create a feed and attach it to `storage`
+    pub fn attach(storage: &Arc<RuntimeStorage>, config: ChangeFeedConfig) -> Result<Arc<Self>, String> {
+        config.validate()?;
+        let feed = Arc::new(Self {
+            config,
+            epoch: uuid::Uuid::new_v4().simple().to_string(),
+            storage: Arc::downgrade(storage),
+            log: Mutex::new(FeedLog { head: 0, changes: VecDeque::new() }),
+        });
+        storage.set_change_feed(feed.clone());
+        Ok(feed)
+    }
+
+    pub fn epoch(&self) -> &str {
+        &self.epoch
+    }
+
+    /// Latest sequence number (0 = nothing recorded yet)
+    pub fn head(&self) -> u64 {
+        self.log.lock().head
+    }
+
+    /// Changes currently retained
+    pub fn len(&self) -> usize {
+        self.log.lock().changes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn push(&self, change: GraphChange) {
+        let mut log = self.log.lock();
+        log.head += 1;
+        let seq = log.head;
+        log.changes.push_back(SequencedChange { seq, change });
+        if log.changes.len() > self.config.capacity {
+            log.changes.pop_front();
+        }
+    }
+
+    pub(crate) fn record_connection(&self, id: u64, connection: &ConnectionV3) {
+        self.push(GraphChange::updated(id, connection));
+    }
+
+    pub(crate) fn record_connection_removed(&self, id: u64) {
+        self.push(GraphChange::ConnectionRemoved { id });
+    }
+
+    /// Changes after `after` for a replica at `epoch` (`None` = fresh replica)
+    ///
+    /// Falls back to a snapshot when the replica is fresh, from another
+    /// epoch, or behind the retained window.
+    pub fn changes_since(&self, epoch: Option<&str>, after: u64, limit: Option<usize>) -> ChangeBatch {
+        let limit = limit.unwrap_or(self.config.max_batch).clamp(1, self.config.max_batch);
+        let log = self.log.lock();
+        let oldest = log.changes.front().map_or(log.head + 1, |c| c.seq);
+        let in_window = epoch == Some(self.epoch.as_str()) && after <= log.head && after + 1 >= oldest;
+
+        if !in_window {
+            drop(log);
+            return self.snapshot();
+        }
+
+        let changes = log
+            .changes
+            .iter()
+            .skip_while(|c| c.seq <= after)
+            .take(limit)
+            .cloned()
+            .collect();
+        self.batch(after, log.head, false, changes)
+    }
+
+    /// Every current connection, tagged with the head as of reading them
+    fn snapshot(&self) -> ChangeBatch {
+        let Some(storage) = self.storage.upgrade() else {
+            return self.batch(0, self.head(), true, Vec::new());
+        };
+        let (mut connections, head) = storage.connections_with(|| self.head());
+        connections.sort_by_key(|(id, _)| *id);
+        let changes = connections
+            .iter()
+            .map(|(id, connection)| SequencedChange { seq: head, change: GraphChange::updated(*id, connection) })
+            .collect();
+        self.batch(0, head, true, changes)
+    }
+
+    fn batch(&self, after: u64, head: u64, snapshot: bool, changes: Vec<SequencedChange>) -> ChangeBatch {
+        ChangeBatch {
+            version: REPLICATION_PROTOCOL_VERSION,
+            connection_format: BinaryFormat::ConnectionV3.current_version(),
+            epoch: self.epoch.clone(),
+            after,
+            head,
+            snapshot,
+            changes,
+        }
+    }
+}
+
+/// Result of applying one batch
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplicaReport {
+    pub updated: usize,
+    pub removed: usize,
+    /// Local connections dropped because a snapshot no longer has them
+    pub dropped: usize,
+    /// Bootstrap graph edges added or reweighted
+    pub mirrored: usize,
+}
+
+/// Where a replica is relative to its writer
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplicaStatus {
+    pub epoch: Option<String>,
+    pub applied_seq: u64,
+    pub writer_head: u64,
+    /// Changes the replica has not applied yet (as of the last batch)
+    pub lag: u64,
+    pub snapshots: u64,
+    pub changes_applied: u64,
+}
+
+/// Replica side: applies writer batches to local storage
+pub struct Replica {
+    storage: Arc<RuntimeStorage>,
+    bootstrap: Option<Arc<RwLock<BootstrapLibrary>>>,
+    status: Mutex<ReplicaStatus>,
+}
+
+impl Replica {
+    pub fn new(storage: Arc<RuntimeStorage>) -> Self {
+        Self { storage, bootstrap: None, status: Mutex::new(ReplicaStatus::default()) }
+    }
+
+    /// Mirror replicated connections into the graph `/query` reads
+    ///
+    /// Only connections between existing concepts become edges, with
+    /// confidence as weight (as `Ingestor` does on the writer).
+    pub fn with_bootstrap(mut self, bootstrap: Arc<RwLock<BootstrapLibrary>>) -> Self {
+        self.bootstrap = Some(bootstrap);
+        self
+    }
+
+    pub fn storage(&self) -> &Arc<RuntimeStorage> {
+        &self.storage
+    }
+
+    pub fn status(&self) -> ReplicaStatus {
+        self.status.lock().clone()
+    }
+
+    /// (epoch, sequence) to request the next batch from
+    pub fn position(&self) -> (Option<String>, u64) {
+        let status = self.status.lock();
+        (status.epoch.clone(), status.applied_seq)
+    }
+
+    /// Apply a batch from the writer
+    ///
+    /// Incremental batches must continue exactly where the replica is;
+    /// snapshots replace all local connections.
+    pub fn apply(&self, batch: &ChangeBatch) -> Result<ReplicaReport, ReplicaError> {
+        if batch.version != REPLICATION_PROTOCOL_VERSION {
+            return Err(ReplicaError::UnsupportedVersion(batch.version));
+        }
+        if batch.connection_format != BinaryFormat::ConnectionV3.current_version() {
+            return Err(ReplicaError::UnsupportedFormat(batch.connection_format));
+        }
+
+        let mut status = self.status.lock();
+        if !batch.snapshot
+            && (status.epoch.as_deref() != Some(batch.epoch.as_str()) || status.applied_seq != batch.after)
+        {
+            return Err(ReplicaError::OutOfOrder { applied: status.applied_seq, after: batch.after });
+        }
+
+        // Decode everything first so a bad record leaves the replica untouched
+        let mut changes = Vec::with_capacity(batch.changes.len());
+        for change in &batch.changes {
+            changes.push(match &change.change {
+                GraphChange::ConnectionUpdated { id, connection } => (*id, Some(decode(change.seq, connection)?)),
+                GraphChange::ConnectionRemoved { id } => (*id, None),
+            });
+        }
+
+        let mut report = ReplicaReport::default();
+        if batch.snapshot {
+            let keep: HashSet<u64> = changes.iter().map(|(id, _)| *id).collect();
+            for (id, _) in self.storage.connections() {
+                if !keep.contains(&id) {
+                    self.remove(id, &mut report);
+                    report.dropped += 1;
+                }
+            }
+        }
+
+        for (id, connection) in changes {
+            match connection {
+                Some(connection) => {
+                    self.storage.put_connection(id, connection);
+                    if self.mirror(&connection) {
+                        report.mirrored += 1;
+                    }
+                    report.updated += 1;
+                }
+                None => {
+                    if self.remove(id, &mut report) {
+                        report.removed += 1;
+                    }
+                }
+            }
+        }
+
+        status.epoch = Some(batch.epoch.clone());
+        status.applied_seq = batch.last_seq();
+        status.writer_head = batch.head;
+        status.lag = batch.head.saturating_sub(status.applied_seq);
+        status.changes_applied += (report.updated + report.removed) as u64;
+        if batch.snapshot {
+            status.snapshots += 1;
+        }
+
+        Ok(report)
+    }
+
+    fn remove(&self, id: u64, report: &mut ReplicaReport) -> bool {
+        let Some(connection) = self.storage.delete_connection(id) else {
+            return false;
+        };
+        if let Some(bootstrap) = &self.bootstrap {
+            let edge_id = Graph::compute_edge_id(
+                connection.token_a_id,
+                connection.token_b_id,
+                connection.connection_type,
+            );
+            if bootstrap.write().graph_mut().remove_edge(edge_id) {
+                report.mirrored += 1;
+            }
+        }
+        true
+    }
+
+    fn mirror(&self, connection: &ConnectionV3) -> bool {
+        let Some(bootstrap) = &self.bootstrap else {
+            return false;
+        };
+        let (a, b) = (connection.token_a_id, connection.token_b_id);
+        let weight = connection.confidence as f32 / 255.0;
+        let mut library = bootstrap.write();
+        let graph = library.graph_mut();
+        let edge_id = Graph::compute_edge_id(a, b, connection.connection_type);
+        if graph.set_edge_weight_from(edge_id, weight, WeightSource::Replication) {
+            return true;
+        }
+        if !graph.contains_node(a) || !graph.contains_node(b) {
+            return false;
+        }
+        if let Ok(true) = graph.add_edge(edge_id, a, b, connection.connection_type, weight, true) {
+            if let Some(mutability) = ConnectionMutability::from_u8(connection.mutability) {
+                graph.set_edge_mutability(edge_id, mutability);
+            }
+            if connection.active_levels != 0 {
+                graph.set_edge_levels(edge_id, connection.active_levels);
+            }
+            return true;
+        }
+        false
+    }
+}
+
+fn decode(seq: u64, hex: &str) -> Result<ConnectionV3, ReplicaError> {
+    let bytes: [u8; 64] = from_hex(hex)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| ReplicaError::Malformed { seq, reason: "expected 64 hex-encoded bytes".to_string() })?;
+    Ok(ConnectionV3::from_bytes(&bytes))
+}
+
+/// Keeps a [`Replica`] up to date with its writer over HTTP
+#[cfg(feature = "sync")]
+pub struct ReplicaFollower {
+    replica: Arc<Replica>,
+    /// Writer base URL, e.g. "http://writer:3000"
+    base_url: String,
+    api_key: Option<String>,
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "sync")]
+impl ReplicaFollower {
+    pub fn new(replica: Arc<Replica>, base_url: impl Into<String>, timeout_ms: u64) -> Result<Self, ReplicaError> {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_millis(timeout_ms))
+            .build()
+            .map_err(|e| ReplicaError::Transport(e.to_string()))?;
+        Ok(Self {
+            replica,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            api_key: None,
+            client,
+        })
+    }
+
+    /// Key sent as `X-API-Key`
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    async fn fetch(&self) -> Result<ChangeBatch, ReplicaError> {
+        let (epoch, after) = self.replica.position();
+        let mut request = self
+            .client
+            .get(format!("{}/api/v1/replication/changes", self.base_url))
+            .query(&[("after", after)]);
+        if let Some(epoch) = epoch {
+            request = request.query(&[("epoch", epoch)]);
+        }
+        if let Some(key) = &self.api_key {
+            request = request.header("X-API-Key", key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| ReplicaError::Transport(format!("Replication request failed: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(ReplicaError::Transport(format!("Writer returned {}", response.status())));
+        }
+        response.json().await.map_err(|e| ReplicaError::Transport(e.to_string()))
+    }
+
+    /// Fetch and apply batches until caught up with the writer
+    pub async fn poll(&self) -> Result<ReplicaReport, ReplicaError> {
+        let mut total = ReplicaReport::default();
+        loop {
+            let batch = self.fetch().await?;
+            let replica = self.replica.clone();
+            let report = tokio::task::spawn_blocking(move || replica.apply(&batch))
+                .await
+                .map_err(|e| ReplicaError::Transport(format!("Apply task failed: {}", e)))??;
+            total.updated += report.updated;
+            total.removed += report.removed;
+            total.dropped += report.dropped;
+            total.mirrored += report.mirrored;
+            if self.replica.status().lag == 0 {
+                return Ok(total);
+            }
+        }
+    }
+
+    /// Poll every `interval` until the task is dropped
+    pub async fn run(self, interval: std::time::Duration) {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.poll().await {
+                tracing::warn!(error = %e, "Replica poll failed");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connection(a: u32, b: u32, confidence: u8) -> ConnectionV3 {
+        let mut connection = ConnectionV3::new(a, b);
+        connection.confidence = confidence;
+        connection
+    }
+
+    #[test]
+    fn test_replica_follows_feed() {
+        let writer = Arc::new(RuntimeStorage::new());
+        let first = writer.create_connection(connection(1, 2, 100));
+        let feed = ChangeFeed::attach(&writer, ChangeFeedConfig::default()).unwrap();
+        let replica = Replica::new(Arc::new(RuntimeStorage::new()));
+
+        // Fresh replica: snapshot includes connections made before the feed existed
+        let batch = feed.changes_since(None, 0, None);
+        assert!(batch.snapshot);
+        replica.apply(&batch).unwrap();
+        assert_eq!(replica.storage().get_connection(first).unwrap().confidence, 100);
+
+        let second = writer.create_connection(connection(2, 3, 50));
+        writer.update_connection(first, connection(1, 2, 200)).unwrap();
+        writer.delete_connection(second);
+
+        let (epoch, after) = replica.position();
+        let batch = feed.changes_since(epoch.as_deref(), after, None);
+        assert!(!batch.snapshot);
+        assert_eq!(batch.changes.len(), 3);
+        let report = replica.apply(&batch).unwrap();
+        assert_eq!((report.updated, report.removed), (2, 1));
+        assert_eq!(replica.storage().get_connection(first).unwrap().confidence, 200);
+        assert!(replica.storage().get_connection(second).is_none());
+        assert_eq!(replica.status().lag, 0);
+
+        // The same batch again no longer lines up
+        assert!(matches!(replica.apply(&batch), Err(ReplicaError::OutOfOrder { .. })));
+    }
+
+    #[test]
+    fn test_snapshot_when_behind_window() {
+        let writer = Arc::new(RuntimeStorage::new());
+        let config = ChangeFeedConfig { capacity: 2, max_batch: 10 };
+        let feed = ChangeFeed::attach(&writer, config).unwrap();
+        let replica = Replica::new(Arc::new(RuntimeStorage::new()));
+        replica.apply(&feed.changes_since(None, 0, None)).unwrap();
+
+        let stale = 99;
+        replica.storage().put_connection(stale, connection(7, 8, 1));
+        for i in 0..4 {
+            writer.create_connection(connection(i, i + 1, 10));
+        }
+
+        let (epoch, after) = replica.position();
+        let batch = feed.changes_since(epoch.as_deref(), after, None);
+        assert!(batch.snapshot);
+        let report = replica.apply(&batch).unwrap();
+        assert_eq!((report.updated, report.dropped), (4, 1));
+        assert!(replica.storage().get_connection(stale).is_none());
+        assert_eq!(replica.status().applied_seq, feed.head());
+        assert_eq!(replica.status().snapshots, 2);
+    }
+}
@@ -0,0 +1,388 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Resource Budget v1.0 - Measured costs for resource-aware behavior
+//!
+//! [`ResourceBudget`] tracks what the system actually spends per time
+//! window: process CPU time, resident memory and executor calls. Each
+//! budgeted resource yields a usage ratio; the largest is the pressure:
+//!
+//! ```text
+//! pressure = max(cpu_ms / max_cpu_ms, memory_mb / max_memory_mb, calls / max_executor_calls)
+//! ```
+//!
+//! The pressure feeds the EfficiencyAppraiser: [`ResourceBudget::efficiency_params`]
+//! scales the ADNA cost factors by `1 + cost_scale · pressure`, so actions
+//! become more expensive as the budget is used up. At pressure ≥ 1 the
+//! budget is exceeded and the ActionController stops curiosity-driven
+//! actions (autonomous exploration, curiosity pre-emption) until the
+//! window rolls over.
+//!
+//! CPU time and memory come from a [`ResourceProbe`]; [`ProcProbe`] reads
+//! `/proc/self` on Linux and reports nothing elsewhere (those budgets are
+//! then never exceeded).
+
+use crate::adna::{EfficiencyParams, InMemoryADNAReader};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+/// Kernel clock ticks per second for `/proc/self/stat` times (USER_HZ)
+const CLOCK_TICKS_PER_SEC: u64 = 100;
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Budgets per window; `None` leaves a resource unbudgeted
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BudgetConfig {
+    /// Window length in seconds
+    pub window_secs: u64,
+
+    /// Process CPU time per window in milliseconds
+    pub max_cpu_ms: Option<u64>,
+
+    /// Resident memory in megabytes
+    pub max_memory_mb: Option<u64>,
+
+    /// Executor calls per window
+    pub max_executor_calls: Option<u64>,
+
+    /// How strongly pressure raises the efficiency cost factors
+    pub cost_scale: f32,
+
+    /// Interval between ADNA updates in `spawn`, in milliseconds
+    pub update_interval_ms: u64,
+}
+
+impl Default for BudgetConfig {
+    fn default() -> Self {
+        Self {
+            window_secs: 60,
+            // Half a core
+            max_cpu_ms: Some(30_000),
+            max_memory_mb: None,
+            max_executor_calls: Some(600),
+            cost_scale: 1.0,
+            update_interval_ms: 5_000,
+        }
+    }
+}
+
+impl BudgetConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.window_secs == 0 {
+            return Err("window_secs must be > 0".to_string());
+        }
+        if [self.max_cpu_ms, self.max_memory_mb, self.max_executor_calls].contains(&Some(0)) {
+            return Err("budgets must be > 0 (use None for no budget)".to_string());
+        }
+        if !(0.0..=10.0).contains(&self.cost_scale) {
+            return Err("cost_scale must be in [0, 10]".to_string());
+        }
+        if self.update_interval_ms == 0 {
+            return Err("update_interval_ms must be > 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Probes
+// ============================================================================
+
+/// Source of process resource measurements
+pub trait ResourceProbe: Send + Sync {
+    /// Total CPU time (user + system) consumed by the process, in milliseconds
+    fn cpu_time_ms(&self) -> Option<u64>;
+
+    /// Resident memory in bytes
+    fn memory_bytes(&self) -> Option<u64>;
+}
+
+/// Reads `/proc/self/stat` and `/proc/self/status` (Linux only)
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ProcProbe;
+
+impl ResourceProbe for ProcProbe {
+    fn cpu_time_ms(&self) -> Option<u64> {
+        let stat = std::fs::read_to_string("/proc/self/stat").ok()?;
+        // The command name may contain spaces; fields resume after its ')'
+        let mut fields = stat.rsplit_once(')')?.1.split_whitespace();
+        // utime and stime are fields 14 and 15, i.e. 12 and 13 after the name
+        let utime: u64 = fields.nth(11)?.parse().ok()?;
+        let stime: u64 = fields.next()?.parse().ok()?;
+        Some((utime + stime) * 1000 / CLOCK_TICKS_PER_SEC)
+    }
+
+    fn memory_bytes(&self) -> Option<u64> {
+        let status = std::fs::read_to_string("/proc/self/status").ok()?;
+        let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+        let kb: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+        Some(kb * 1024)
+    }
+}
+
+// ============================================================================
+// Budget
+// ============================================================================
+
+/// Usage in the current window
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BudgetUsage {
+    pub window_secs: u64,
+    /// Time since the window started
+    pub elapsed_ms: u64,
+    pub cpu_ms: Option<u64>,
+    pub memory_mb: Option<u64>,
+    pub executor_calls: u64,
+    /// Calls per executor ID
+    pub calls_by_executor: HashMap<String, u64>,
+    /// Curiosity-driven actions skipped this window
+    pub throttled: u64,
+    /// Largest usage ratio of a budgeted resource
+    pub pressure: f32,
+    pub exceeded: bool,
+}
+
+#[derive(Debug)]
+struct Window {
+    started: Instant,
+    cpu_at_start: Option<u64>,
+    calls: HashMap<String, u64>,
+    throttled: u64,
+}
+
+/// Per-window tracker of CPU time, memory and executor calls
+pub struct ResourceBudget {
+    config: BudgetConfig,
+    probe: Arc<dyn ResourceProbe>,
+    window: Mutex<Window>,
+}
+
+impl ResourceBudget {
+    pub fn new(config: BudgetConfig) -> Result<Self, String> {
+        config.validate()?;
+        let probe: Arc<dyn ResourceProbe> = Arc::new(ProcProbe);
+        Ok(Self {
+            window: Mutex::new(Window {
+                started: Instant::now(),
+                cpu_at_start: probe.cpu_time_ms(),
+                calls: HashMap::new(),
+                throttled: 0,
+            }),
+            config,
+            probe,
+        })
+    }
+
+    /// Measure with another probe (restarts the current window)
+    pub fn with_probe(mut self, probe: Arc<dyn ResourceProbe>) -> Self {
+        {
+            let window = self.window.get_mut();
+            window.started = Instant::now();
+            window.cpu_at_start = probe.cpu_time_ms();
+        }
+        self.probe = probe;
+        self
+    }
+
+    pub fn config(&self) -> &BudgetConfig {
+        &self.config
+    }
+
+    /// Count one executor call
+    pub fn record_call(&self, executor_id: &str) {
+        let mut window = self.current_window(Instant::now());
+        *window.calls.entry(executor_id.to_string()).or_insert(0) += 1;
+    }
+
+    /// Count one curiosity-driven action that was skipped
+    pub fn record_throttled(&self) {
+        self.current_window(Instant::now()).throttled += 1;
+    }
+
+    /// Usage of the current window
+    pub fn usage(&self) -> BudgetUsage {
+        self.usage_at(Instant::now())
+    }
+
+    /// Largest usage ratio of a budgeted resource
+    pub fn pressure(&self) -> f32 {
+        self.usage().pressure
+    }
+
+    /// Whether some budget is used up
+    pub fn is_exceeded(&self) -> bool {
+        self.usage().exceeded
+    }
+
+    /// Efficiency parameters with cost factors raised by the current pressure
+    pub fn efficiency_params(&self, base: &EfficiencyParams) -> EfficiencyParams {
+        let factor = 1.0 + self.config.cost_scale * self.pressure();
+        EfficiencyParams {
+            motor_cost_factor: base.motor_cost_factor * factor,
+            cognitive_cost_factor: base.cognitive_cost_factor * factor,
+            creation_cost_factor: base.creation_cost_factor * factor,
+            ..*base
+        }
+    }
+
+    /// Write pressure-scaled efficiency parameters to the ADNA every
+    /// `update_interval_ms`; `base` are the parameters at zero pressure
+    pub fn spawn(self: Arc<Self>, adna: Arc<InMemoryADNAReader>, base: EfficiencyParams) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(Duration::from_millis(self.config.update_interval_ms));
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                adna.update_efficiency(self.efficiency_params(&base)).await;
+            }
+        })
+    }
+
+    /// The window at `now`, starting a new one when the old has run out
+    fn current_window(&self, now: Instant) -> parking_lot::MutexGuard<'_, Window> {
+        let mut window = self.window.lock();
+        if now.saturating_duration_since(window.started) >= Duration::from_secs(self.config.window_secs) {
+            *window = Window {
+                started: now,
+                cpu_at_start: self.probe.cpu_time_ms(),
+                calls: HashMap::new(),
+                throttled: 0,
+            };
+        }
+        window
+    }
+
+    fn usage_at(&self, now: Instant) -> BudgetUsage {
+        let window = self.current_window(now);
+        let cpu_ms = self
+            .probe
+            .cpu_time_ms()
+            .zip(window.cpu_at_start)
+            .map(|(total, start)| total.saturating_sub(start));
+        let memory_mb = self.probe.memory_bytes().map(|bytes| bytes / (1024 * 1024));
+        let executor_calls = window.calls.values().sum();
+
+        let ratio = |used: Option<u64>, budget: Option<u64>| match (used, budget) {
+            (Some(used), Some(budget)) => used as f32 / budget as f32,
+            _ => 0.0,
+        };
+        let pressure = ratio(cpu_ms, self.config.max_cpu_ms)
+            .max(ratio(memory_mb, self.config.max_memory_mb))
+            .max(ratio(Some(executor_calls), self.config.max_executor_calls));
+
+        BudgetUsage {
+            window_secs: self.config.window_secs,
+            elapsed_ms: now.saturating_duration_since(window.started).as_millis() as u64,
+            cpu_ms,
+            memory_mb,
+            executor_calls,
+            calls_by_executor: window.calls.clone(),
+            throttled: window.throttled,
+            pressure,
+            exceeded: pressure >= 1.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    /// CPU time and memory set by the test
+    #[derive(Default)]
+    struct FixedProbe {
+        cpu_ms: AtomicU64,
+        memory_bytes: AtomicU64,
+    }
+
+    impl ResourceProbe for FixedProbe {
+        fn cpu_time_ms(&self) -> Option<u64> {
+            Some(self.cpu_ms.load(Ordering::Relaxed))
+        }
+
+        fn memory_bytes(&self) -> Option<u64> {
+            Some(self.memory_bytes.load(Ordering::Relaxed))
+        }
+    }
+
+    #[test]
+    fn test_budget_config_validate() {
+        assert!(BudgetConfig::default().validate().is_ok());
+        assert!(BudgetConfig { window_secs: 0, ..Default::default() }.validate().is_err());
+        assert!(BudgetConfig { max_cpu_ms: Some(0), ..Default::default() }.validate().is_err());
+        assert!(BudgetConfig { cost_scale: -1.0, ..Default::default() }.validate().is_err());
+    }
+
+    #[test]
+    fn test_pressure_and_window_rollover() {
+        let probe = Arc::new(FixedProbe::default());
+        probe.cpu_ms.store(5_000, Ordering::Relaxed);
+        let config = BudgetConfig {
+            window_secs: 60,
+            max_cpu_ms: Some(1_000),
+            max_memory_mb: Some(100),
+            max_executor_calls: Some(4),
+            ..Default::default()
+        };
+        let budget = ResourceBudget::new(config).unwrap().with_probe(probe.clone());
+
+        // CPU is counted from the start of the window
+        probe.cpu_ms.store(5_250, Ordering::Relaxed);
+        probe.memory_bytes.store(50 * 1024 * 1024, Ordering::Relaxed);
+        budget.record_call("noop");
+        let usage = budget.usage();
+        assert_eq!(usage.cpu_ms, Some(250));
+        assert_eq!(usage.memory_mb, Some(50));
+        assert!((usage.pressure - 0.5).abs() < 1e-6);
+        assert!(!usage.exceeded);
+
+        for _ in 0..3 {
+            budget.record_call("message_sender");
+        }
+        let usage = budget.usage();
+        assert_eq!(usage.calls_by_executor["message_sender"], 3);
+        assert!(usage.exceeded);
+
+        let base = EfficiencyParams::default();
+        let scaled = budget.efficiency_params(&base);
+        assert!((scaled.motor_cost_factor - base.motor_cost_factor * 2.0).abs() < 1e-6);
+        assert_eq!(scaled.weight, base.weight);
+
+        // A new window starts from zero
+        let later = Instant::now() + Duration::from_secs(61);
+        let usage = budget.usage_at(later);
+        assert_eq!(usage.executor_calls, 0);
+        assert_eq!(usage.cpu_ms, Some(0));
+        assert!(!usage.exceeded);
+    }
+
+    #[test]
+    fn test_proc_probe() {
+        if cfg!(target_os = "linux") {
+            assert!(ProcProbe.memory_bytes().unwrap() > 0);
+            assert!(ProcProbe.cpu_time_ms().is_some());
+        }
+    }
+}
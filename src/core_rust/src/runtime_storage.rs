@@ -25,13 +25,17 @@
 
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::Arc;
 use parking_lot::RwLock;
 
 use crate::token::Token;
 use crate::connection_v3::ConnectionV3;
-use crate::grid::Grid;
-use crate::graph::Graph;
+use crate::grid::ShardedGrid;
+use crate::graph::sharded::ShardedGraph;
+use crate::graph::{Direction, EdgeId, EdgeInfo, Graph};
 use crate::cdna::CDNA;
+use crate::learning_journal::LearningJournal;
+use crate::replica::ChangeFeed;
 
 // ============================================================================
 // Error Types
@@ -46,6 +50,7 @@ pub enum StorageError {
     InvalidTokenId(u32),
     InvalidConnectionId(u64),
     GridError(String),
+    GraphError(String),
     CDNAError(String),
 }
 
@@ -59,6 +64,7 @@ impl std::fmt::Display for StorageError {
             StorageError::InvalidTokenId(id) => write!(f, "Invalid token ID: {}", id),
             StorageError::InvalidConnectionId(id) => write!(f, "Invalid connection ID: {}", id),
             StorageError::GridError(msg) => write!(f, "Grid error: {}", msg),
+            StorageError::GraphError(msg) => write!(f, "Graph error: {}", msg),
             StorageError::CDNAError(msg) => write!(f, "CDNA error: {}", msg),
         }
     }
@@ -96,12 +102,12 @@ pub struct RuntimeStorage {
     next_connection_id: AtomicU64,
 
     // === Spatial Index ===
-    /// Grid for spatial queries on tokens
-    grid: RwLock<Grid>,
+    /// Grid for spatial queries on tokens (sharded: inserts don't block queries)
+    grid: ShardedGrid,
 
     // === Graph Topology ===
-    /// Graph structure (nodes and edges)
-    graph: RwLock<Graph>,
+    /// Graph structure (nodes and edges, sharded by node ID)
+    graph: ShardedGraph,
 
     // === Constitution ===
     /// CDNA configuration
@@ -112,6 +118,14 @@ pub struct RuntimeStorage {
     label_to_id: RwLock<HashMap<String, u32>>,
     /// ID to label mapping
     id_to_label: RwLock<HashMap<u32, String>>,
+
+    // === Crash Recovery ===
+    /// Journal for connection mutations (optional)
+    journal: RwLock<Option<Arc<LearningJournal>>>,
+
+    // === Replication ===
+    /// Change feed served to read replicas (optional)
+    change_feed: RwLock<Option<Arc<ChangeFeed>>>,
 }
 
 impl RuntimeStorage {
@@ -122,11 +136,58 @@ impl RuntimeStorage {
             next_token_id: AtomicU32::new(1),
             connections: RwLock::new(HashMap::new()),
             next_connection_id: AtomicU64::new(1),
-            grid: RwLock::new(Grid::new()),
-            graph: RwLock::new(Graph::new()),
+            grid: ShardedGrid::new(),
+            graph: ShardedGraph::new(),
             cdna: RwLock::new(CDNA::new()),
             label_to_id: RwLock::new(HashMap::new()),
             id_to_label: RwLock::new(HashMap::new()),
+            journal: RwLock::new(None),
+            change_feed: RwLock::new(None),
+        }
+    }
+
+    /// Attach a learning journal; connection mutations are journaled before they apply
+    ///
+    /// Replay the journal with `LearningJournal::replay_into` first.
+    pub fn set_journal(&self, journal: Arc<LearningJournal>) {
+        *self.journal.write() = Some(journal);
+    }
+
+    /// Currently attached learning journal
+    pub fn journal(&self) -> Option<Arc<LearningJournal>> {
+        self.journal.read().clone()
+    }
+
+    /// Publish connection mutations to `feed` (writer side of read replicas)
+    ///
+    /// Like journaling, publishing happens under the connections lock.
+    pub fn set_change_feed(&self, feed: Arc<ChangeFeed>) {
+        *self.change_feed.write() = Some(feed);
+    }
+
+    /// Currently attached change feed
+    pub fn change_feed(&self) -> Option<Arc<ChangeFeed>> {
+        self.change_feed.read().clone()
+    }
+
+    fn journal_connection(&self, id: u64, connection: Option<&ConnectionV3>) {
+        if let Some(feed) = self.change_feed.read().as_ref() {
+            match connection {
+                Some(connection) => feed.record_connection(id, connection),
+                None => feed.record_connection_removed(id),
+            }
+        }
+
+        let journal = self.journal.read();
+        let Some(journal) = journal.as_ref() else {
+            return;
+        };
+        let result = match connection {
+            Some(connection) => journal.record_connection(id, connection),
+            None => journal.record_connection_removed(id),
+        };
+        if let Err(e) = result {
+            tracing::error!(connection_id = id, error = %e, "Failed to journal connection mutation");
         }
     }
 
@@ -152,14 +213,10 @@ impl RuntimeStorage {
         drop(tokens);
 
         // Add to grid (if has coordinates)
-        let mut grid = self.grid.write();
-        let _ = grid.add(token.clone());
-        drop(grid);
+        let _ = self.grid.add(token.clone());
 
         // Add node to graph
-        let mut graph = self.graph.write();
-        graph.add_node(id);
-        drop(graph);
+        self.graph.add_node(id);
 
         id
     }
@@ -198,9 +255,8 @@ impl RuntimeStorage {
 
         // Update in grid (remove old, add new)
         if let Some(old) = old_token {
-            let mut grid = self.grid.write();
-            grid.remove(old.id);
-            let _ = grid.add(token);
+            self.grid.remove(old.id);
+            let _ = self.grid.add(token);
         }
 
         Ok(())
@@ -219,14 +275,10 @@ impl RuntimeStorage {
         drop(tokens);
 
         // Remove from grid
-        let mut grid = self.grid.write();
-        grid.remove(id);
-        drop(grid);
+        self.grid.remove(id);
 
         // Remove node from graph
-        let mut graph = self.graph.write();
-        graph.remove_node(id);
-        drop(graph);
+        self.graph.remove_node(id);
 
         Some(token)
     }
@@ -269,17 +321,50 @@ impl RuntimeStorage {
         drop(tokens);
 
         // Clear grid
-        let mut grid = self.grid.write();
-        *grid = Grid::new();
-        drop(grid);
+        self.grid.clear();
 
         // Clear graph
-        let mut graph = self.graph.write();
-        *graph = Graph::new();
+        self.graph.clear();
 
         count
     }
 
+    // ========================================================================
+    // Label API
+    // ========================================================================
+
+    /// Name a token; a label already naming another token moves to `id`
+    pub fn set_label(&self, id: u32, label: &str) {
+        let mut label_to_id = self.label_to_id.write();
+        let mut id_to_label = self.id_to_label.write();
+
+        if let Some(previous_id) = label_to_id.insert(label.to_string(), id) {
+            if previous_id != id {
+                id_to_label.remove(&previous_id);
+            }
+        }
+        if let Some(previous_label) = id_to_label.insert(id, label.to_string()) {
+            if previous_label != label {
+                label_to_id.remove(&previous_label);
+            }
+        }
+    }
+
+    /// Label of a token
+    pub fn label(&self, id: u32) -> Option<String> {
+        self.id_to_label.read().get(&id).cloned()
+    }
+
+    /// Token named `label`
+    pub fn token_by_label(&self, label: &str) -> Option<u32> {
+        self.label_to_id.read().get(label).copied()
+    }
+
+    /// All (token ID, label) pairs
+    pub fn labels(&self) -> Vec<(u32, String)> {
+        self.id_to_label.read().iter().map(|(&id, label)| (id, label.clone())).collect()
+    }
+
     // ========================================================================
     // Connection API
     // ========================================================================
@@ -297,11 +382,23 @@ impl RuntimeStorage {
         let id = self.next_connection_id.fetch_add(1, Ordering::SeqCst);
 
         let mut connections = self.connections.write();
+        self.journal_connection(id, Some(&connection));
         connections.insert(id, connection);
 
         id
     }
 
+    /// Insert or replace a connection under a known ID
+    ///
+    /// Used by journal replay; advances the ID counter past `id`.
+    pub fn put_connection(&self, id: u64, connection: ConnectionV3) {
+        self.next_connection_id.fetch_max(id + 1, Ordering::SeqCst);
+
+        let mut connections = self.connections.write();
+        self.journal_connection(id, Some(&connection));
+        connections.insert(id, connection);
+    }
+
     /// Get a connection by ID
     pub fn get_connection(&self, id: u64) -> Option<ConnectionV3> {
         let connections = self.connections.read();
@@ -316,6 +413,7 @@ impl RuntimeStorage {
             return Err(StorageError::ConnectionNotFound(id));
         }
 
+        self.journal_connection(id, Some(&connection));
         connections.insert(id, connection);
         Ok(())
     }
@@ -323,7 +421,11 @@ impl RuntimeStorage {
     /// Delete a connection
     pub fn delete_connection(&self, id: u64) -> Option<ConnectionV3> {
         let mut connections = self.connections.write();
-        connections.remove(&id)
+        let removed = connections.remove(&id);
+        if removed.is_some() {
+            self.journal_connection(id, None);
+        }
+        removed
     }
 
     /// List connections with pagination
@@ -343,6 +445,134 @@ impl RuntimeStorage {
         connections.len()
     }
 
+    /// All connections with their IDs
+    pub fn connections(&self) -> Vec<(u64, ConnectionV3)> {
+        let connections = self.connections.read();
+        connections.iter().map(|(&id, conn)| (id, *conn)).collect()
+    }
+
+    /// All connections plus `f`'s result, with no mutation in between
+    ///
+    /// Mutations are journaled under the connections lock, so `f` observes
+    /// the journal/change feed exactly as of the returned connections.
+    pub(crate) fn connections_with<R>(&self, f: impl FnOnce() -> R) -> (Vec<(u64, ConnectionV3)>, R) {
+        let connections = self.connections.read();
+        let result = f();
+        (connections.iter().map(|(&id, conn)| (id, *conn)).collect(), result)
+    }
+
+    /// Fold token `absorb` into `keep`
+    ///
+    /// Connections of `absorb` are moved to `keep`; a moved connection that
+    /// duplicates an existing one (same endpoints and type) is folded into it
+    /// (evidence summed, max confidence), and connections between the two
+    /// tokens are dropped. Graph edges are moved the same way, the absorbed
+    /// label resolves to `keep` (and becomes its label if it had none), and
+    /// `absorb` is deleted.
+    pub fn merge_tokens(&self, keep: u32, absorb: u32) -> StorageResult<MergeSummary> {
+        if keep == absorb {
+            return Err(StorageError::InvalidTokenId(absorb));
+        }
+        {
+            let tokens = self.tokens.read();
+            if let Some(missing) = [keep, absorb].into_iter().find(|id| !tokens.contains_key(id)) {
+                return Err(StorageError::TokenNotFound(missing));
+            }
+        }
+
+        let mut summary = MergeSummary::default();
+        {
+            let mut connections = self.connections.write();
+            let key = |c: &ConnectionV3| (c.token_a_id, c.token_b_id, c.connection_type);
+            let touches = |c: &ConnectionV3| c.token_a_id == absorb || c.token_b_id == absorb;
+            let mut existing: HashMap<(u32, u32, u8), u64> = connections
+                .iter()
+                .filter(|(_, c)| !touches(c))
+                .map(|(&id, c)| (key(c), id))
+                .collect();
+            let mut moved: Vec<u64> = connections
+                .iter()
+                .filter(|(_, c)| touches(c))
+                .map(|(&id, _)| id)
+                .collect();
+            moved.sort_unstable();
+
+            for id in moved {
+                let mut conn = connections[&id];
+                let a = if conn.token_a_id == absorb { keep } else { conn.token_a_id };
+                let b = if conn.token_b_id == absorb { keep } else { conn.token_b_id };
+                if a == b {
+                    connections.remove(&id);
+                    self.journal_connection(id, None);
+                    summary.dropped += 1;
+                    continue;
+                }
+                (conn.token_a_id, conn.token_b_id) = (a.min(b), a.max(b));
+
+                match existing.get(&key(&conn)).copied() {
+                    Some(target) => {
+                        if let Some(into) = connections.get_mut(&target) {
+                            into.evidence_count = into.evidence_count.saturating_add(conn.evidence_count);
+                            into.confidence = into.confidence.max(conn.confidence);
+                            self.journal_connection(target, Some(into));
+                        }
+                        connections.remove(&id);
+                        self.journal_connection(id, None);
+                        summary.folded += 1;
+                    }
+                    None => {
+                        existing.insert(key(&conn), id);
+                        self.journal_connection(id, Some(&conn));
+                        connections.insert(id, conn);
+                        summary.rewired += 1;
+                    }
+                }
+            }
+        }
+
+        for (_, edge_id) in self.graph.get_neighbors(absorb, Direction::Both) {
+            let Some(edge) = self.graph.get_edge(edge_id) else {
+                continue;
+            };
+            let from = if edge.from_id == absorb { keep } else { edge.from_id };
+            let to = if edge.to_id == absorb { keep } else { edge.to_id };
+            if from == to {
+                continue;
+            }
+            let moved_id = Graph::compute_edge_id(from, to, edge.edge_type);
+            if let Ok(true) = self.graph.add_edge(moved_id, from, to, edge.edge_type, edge.weight, edge.bidirectional) {
+                self.graph.set_edge_levels(moved_id, edge.active_levels);
+            }
+        }
+
+        {
+            let mut label_to_id = self.label_to_id.write();
+            let mut id_to_label = self.id_to_label.write();
+            if let Some(label) = id_to_label.remove(&absorb) {
+                label_to_id.insert(label.clone(), keep);
+                id_to_label.entry(keep).or_insert(label);
+            }
+        }
+        self.delete_token(absorb);
+        Ok(summary)
+    }
+
+    /// Modify connections in place under one lock
+    ///
+    /// `f` returns whether it changed the connection; changed connections
+    /// are journaled. Returns the number changed.
+    pub fn modify_connections(&self, mut f: impl FnMut(u64, &mut ConnectionV3) -> bool) -> usize {
+        let mut connections = self.connections.write();
+        let mut changed = 0;
+        for (&id, connection) in connections.iter_mut() {
+            if f(id, connection) {
+                self.journal_connection(id, Some(connection));
+                changed += 1;
+            }
+        }
+        changed
+    }
+
     // ========================================================================
     // Grid API
     // ========================================================================
@@ -352,10 +582,9 @@ impl RuntimeStorage {
     /// # Returns
     /// A tuple of (total_tokens_in_grid, grid_bounds)
     pub fn grid_info(&self) -> (usize, [f32; 6]) {
-        let grid = self.grid.read();
         // Return count and bounds [min_x, max_x, min_y, max_y, min_z, max_z]
         // For now, return defaults - Grid struct needs to expose this info
-        (grid.len(), [0.0, 100.0, 0.0, 100.0, 0.0, 100.0])
+        (self.grid.len(), [0.0, 100.0, 0.0, 100.0, 0.0, 100.0])
     }
 
     /// Add a token to the grid by ID
@@ -372,8 +601,7 @@ impl RuntimeStorage {
             .clone();
         drop(tokens);
 
-        let mut grid = self.grid.write();
-        grid.add(token)
+        self.grid.add(token)
             .map_err(|e| StorageError::GridError(e.to_string()))?;
         Ok(())
     }
@@ -383,8 +611,7 @@ impl RuntimeStorage {
     /// # Arguments
     /// * `token_id` - ID of token to remove from grid
     pub fn remove_from_grid(&self, token_id: u32) {
-        let mut grid = self.grid.write();
-        grid.remove(token_id);
+        self.grid.remove(token_id);
     }
 
     /// Find neighbors of a token within a radius
@@ -406,8 +633,7 @@ impl RuntimeStorage {
         drop(tokens);
 
         // Use Grid's find_neighbors method
-        let grid = self.grid.read();
-        let neighbors = grid.find_neighbors(
+        let neighbors = self.grid.find_neighbors(
             token_id,
             CoordinateSpace::L1Physical,
             radius,
@@ -428,8 +654,7 @@ impl RuntimeStorage {
     pub fn range_query(&self, center: [f32; 3], radius: f32) -> Vec<(u32, f32)> {
         use crate::token::CoordinateSpace;
 
-        let grid = self.grid.read();
-        grid.range_query(
+        self.grid.range_query(
             CoordinateSpace::L1Physical,
             center[0],
             center[1],
@@ -528,6 +753,104 @@ impl RuntimeStorage {
     }
 }
 
+// ============================================================================
+// Snapshot API (used by checkpoint)
+// ============================================================================
+
+/// Complete RuntimeStorage contents
+#[derive(Clone)]
+pub struct RuntimeSnapshot {
+    pub tokens: Vec<Token>,
+    pub next_token_id: u32,
+    pub connections: Vec<(u64, ConnectionV3)>,
+    pub next_connection_id: u64,
+    pub grid_tokens: Vec<Token>,
+    pub graph_nodes: Vec<u32>,
+    pub graph_edges: Vec<(EdgeId, EdgeInfo)>,
+    pub cdna: CDNA,
+    pub labels: Vec<(u32, String)>,
+}
+
+/// Connections affected by [`RuntimeStorage::merge_tokens`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MergeSummary {
+    /// Moved from the absorbed token to the kept one
+    pub rewired: usize,
+    /// Folded into an existing connection of the kept token
+    pub folded: usize,
+    /// Between the two tokens, removed
+    pub dropped: usize,
+}
+
+impl RuntimeStorage {
+    /// Capture all runtime data
+    ///
+    /// Token, connection, CDNA and label locks are held together; Grid and
+    /// Graph are each copied with all of their shards locked at once.
+    pub fn export_snapshot(&self) -> RuntimeSnapshot {
+        let tokens = self.tokens.read();
+        let connections = self.connections.read();
+        let cdna = self.cdna.read();
+        let id_to_label = self.id_to_label.read();
+
+        // Field setters don't maintain the checksum; seal the copy so it validates on import
+        let mut cdna_copy = *cdna;
+        cdna_copy.checksum = cdna_copy.compute_checksum();
+
+        RuntimeSnapshot {
+            tokens: tokens.values().copied().collect(),
+            next_token_id: self.next_token_id.load(Ordering::SeqCst),
+            connections: connections.iter().map(|(&id, conn)| (id, *conn)).collect(),
+            next_connection_id: self.next_connection_id.load(Ordering::SeqCst),
+            grid_tokens: self.grid.tokens(),
+            graph_nodes: self.graph.get_nodes(),
+            graph_edges: self.graph.edges(),
+            cdna: cdna_copy,
+            labels: id_to_label.iter().map(|(&id, label)| (id, label.clone())).collect(),
+        }
+    }
+
+    /// Replace all runtime data with a snapshot
+    ///
+    /// Graph is rebuilt and Grid replaced before anything else changes; on
+    /// error the current state is left untouched.
+    pub fn import_snapshot(&self, snapshot: RuntimeSnapshot) -> StorageResult<()> {
+        snapshot.cdna.validate().map_err(StorageError::CDNAError)?;
+
+        let mut graph = Graph::new();
+        for node in snapshot.graph_nodes {
+            graph.add_node(node);
+        }
+        for (edge_id, info) in snapshot.graph_edges {
+            graph
+                .add_edge(edge_id, info.from_id, info.to_id, info.edge_type, info.weight, info.bidirectional)
+                .map_err(StorageError::GraphError)?;
+            graph.set_edge_levels(edge_id, info.active_levels);
+        }
+
+        self.grid
+            .replace(snapshot.grid_tokens)
+            .map_err(|e| StorageError::GridError(e.to_string()))?;
+
+        let mut tokens = self.tokens.write();
+        let mut connections = self.connections.write();
+        let mut cdna = self.cdna.write();
+        let mut label_to_id = self.label_to_id.write();
+        let mut id_to_label = self.id_to_label.write();
+
+        *tokens = snapshot.tokens.into_iter().map(|t| (t.id, t)).collect();
+        *connections = snapshot.connections.into_iter().collect();
+        self.graph.load(&graph);
+        *cdna = snapshot.cdna;
+        *label_to_id = snapshot.labels.iter().map(|(id, label)| (label.clone(), *id)).collect();
+        *id_to_label = snapshot.labels.into_iter().collect();
+        self.next_token_id.store(snapshot.next_token_id, Ordering::SeqCst);
+        self.next_connection_id.store(snapshot.next_connection_id, Ordering::SeqCst);
+
+        Ok(())
+    }
+}
+
 impl Default for RuntimeStorage {
     fn default() -> Self {
         Self::new()
@@ -780,4 +1103,60 @@ mod tests {
         storage.reset_cdna();
         assert!(storage.validate_cdna());
     }
+
+    #[test]
+    fn test_merge_tokens() {
+        let storage = RuntimeStorage::new();
+        let mut snapshot = storage.export_snapshot();
+        snapshot.labels.push((2, "sofa".to_string()));
+        storage.import_snapshot(snapshot).unwrap();
+        let keep = storage.create_token(Token::new(0));
+        let absorb = storage.create_token(Token::new(0));
+        let other = storage.create_token(Token::new(0));
+
+        let mut existing = ConnectionV3::new(keep, other);
+        existing.evidence_count = 3;
+        existing.confidence = 100;
+        let existing_id = storage.create_connection(existing);
+        let mut duplicate = ConnectionV3::new(absorb, other);
+        duplicate.evidence_count = 2;
+        duplicate.confidence = 200;
+        storage.create_connection(duplicate);
+        storage.create_connection(ConnectionV3::new(keep, absorb));
+        let mut typed = ConnectionV3::new(other, absorb);
+        typed.connection_type = crate::connection_v3::ConnectionType::Cause as u8;
+        let typed_id = storage.create_connection(typed);
+
+        let summary = storage.merge_tokens(keep, absorb).unwrap();
+        assert_eq!(summary, MergeSummary { rewired: 1, folded: 1, dropped: 1 });
+        assert_eq!(storage.count_tokens(), 2);
+        assert_eq!(storage.count_connections(), 2);
+
+        let folded = storage.get_connection(existing_id).unwrap();
+        let (evidence, confidence) = (folded.evidence_count, folded.confidence);
+        assert_eq!((evidence, confidence), (5, 200));
+        let moved = storage.get_connection(typed_id).unwrap();
+        let (a, b) = (moved.token_a_id, moved.token_b_id);
+        assert_eq!((a, b), (keep, other));
+
+        assert_eq!(storage.export_snapshot().labels, vec![(keep, "sofa".to_string())]);
+        assert!(matches!(storage.merge_tokens(keep, absorb), Err(StorageError::TokenNotFound(_))));
+        assert!(storage.merge_tokens(keep, keep).is_err());
+    }
+
+    #[test]
+    fn test_labels() {
+        let storage = RuntimeStorage::new();
+        let cat = storage.create_token(Token::new(0));
+        let dog = storage.create_token(Token::new(0));
+
+        storage.set_label(cat, "cat");
+        assert_eq!(storage.token_by_label("cat"), Some(cat));
+        storage.set_label(cat, "kitten");
+        assert_eq!(storage.token_by_label("cat"), None);
+        storage.set_label(dog, "kitten");
+        assert_eq!(storage.token_by_label("kitten"), Some(dog));
+        assert_eq!(storage.label(cat), None);
+        assert_eq!(storage.labels(), vec![(dog, "kitten".to_string())]);
+    }
 }
@@ -0,0 +1,179 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Safe Mode v1.0 - Operator kill switch
+//!
+//! `/safe_mode [reason]` engages the instance's [`SafeMode`] and `/resume`
+//! releases it. While engaged:
+//!
+//! - learning is frozen ([`SafeMode::learning_frozen`]): Learner updates,
+//!   hybrid learning proposals, evolution attempts and ADNA proposals,
+//!   feedback and autonomous exploration are all skipped or rejected
+//! - the ActionController runs no executor but [`SAFE_EXECUTOR`]
+//! - the Gateway admits only queries and read-only commands
+//! - writes to knowledge and configuration are refused: REST ingest,
+//!   sync deltas, checkpoint restore and `/config`, `/adna`, `/cdna`
+//!   updates (503), consolidation and inference passes, synonym merges
+//!   and skill-pack imports
+//!
+//! There is no process-wide switch: instances share nothing, so each one
+//! owns its switch (`Gateway::safe_mode`, also held by `ApiState`) and
+//! hands it to its Learner, ProposalRouter, EvolutionScheduler,
+//! EvolutionManager, FeedbackProcessor, Consolidator and InferenceEngine
+//! (`with_safe_mode`), its ActionController (`set_safe_mode`) and
+//! `SkillPack::import`.
+//!
+//! Safe mode is independent of the tray's learning pause ([`LearningGate`],
+//! shared through [`SafeMode::with_gate`]): resuming learning from the tray
+//! does not lift it.
+
+use crate::background::LearningGate;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::warn;
+
+/// The only executor allowed to run in safe mode
+pub const SAFE_EXECUTOR: &str = "noop";
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Safe mode state as reported by `/safe_mode` and `/resume`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SafeModeStatus {
+    pub engaged: bool,
+    /// Unix ms since which safe mode is engaged
+    pub since: Option<u64>,
+    pub reason: Option<String>,
+}
+
+/// Kill switch for learning, actions and writes
+#[derive(Debug, Default)]
+pub struct SafeMode {
+    engaged: AtomicBool,
+    /// Unix ms of engagement (0 when released)
+    engaged_at: AtomicU64,
+    reason: RwLock<Option<String>>,
+    /// Tray/REST learning pause of the same instance
+    gate: Arc<LearningGate>,
+}
+
+impl SafeMode {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also freeze learning while `gate` is paused (see `BackgroundRuntime::gate`)
+    pub fn with_gate(gate: Arc<LearningGate>) -> Self {
+        Self { gate, ..Self::default() }
+    }
+
+    /// Returns `false` if safe mode was already engaged (the reason is kept)
+    pub fn engage(&self, reason: Option<&str>) -> bool {
+        let changed = !self.engaged.swap(true, Ordering::SeqCst);
+        if changed {
+            self.engaged_at.store(now_ms(), Ordering::SeqCst);
+            *self.reason.write() = reason.map(str::to_string);
+            warn!(reason = reason.unwrap_or(""), "Safe mode engaged: learning frozen, executors disabled");
+        }
+        changed
+    }
+
+    /// Returns `false` if safe mode was not engaged
+    pub fn release(&self) -> bool {
+        let changed = self.engaged.swap(false, Ordering::SeqCst);
+        if changed {
+            self.engaged_at.store(0, Ordering::SeqCst);
+            *self.reason.write() = None;
+            warn!("Safe mode released");
+        }
+        changed
+    }
+
+    pub fn is_engaged(&self) -> bool {
+        self.engaged.load(Ordering::SeqCst)
+    }
+
+    /// Learning is off: paused (tray, REST) or frozen by this switch
+    pub fn learning_frozen(&self) -> bool {
+        self.gate.is_paused() || self.is_engaged()
+    }
+
+    /// Whether an executor may run
+    pub fn allows_executor(&self, executor_id: &str) -> bool {
+        !self.is_engaged() || executor_id == SAFE_EXECUTOR
+    }
+
+    pub fn status(&self) -> SafeModeStatus {
+        SafeModeStatus {
+            engaged: self.is_engaged(),
+            since: match self.engaged_at.load(Ordering::SeqCst) {
+                0 => None,
+                t => Some(t),
+            },
+            reason: self.reason.read().clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_engage_and_release() {
+        let safe_mode = SafeMode::new();
+        assert!(safe_mode.allows_executor("file"));
+        assert_eq!(safe_mode.status(), SafeModeStatus { engaged: false, since: None, reason: None });
+
+        assert!(safe_mode.engage(Some("runaway writes")));
+        assert!(!safe_mode.engage(Some("again")));
+        let status = safe_mode.status();
+        assert!(status.engaged && status.since.is_some());
+        assert_eq!(status.reason.as_deref(), Some("runaway writes"));
+        assert!(safe_mode.allows_executor(SAFE_EXECUTOR));
+        assert!(!safe_mode.allows_executor("file"));
+
+        assert!(safe_mode.release());
+        assert!(!safe_mode.release());
+        assert!(safe_mode.allows_executor("file"));
+        assert_eq!(safe_mode.status().reason, None);
+    }
+
+    #[test]
+    fn test_switches_are_independent() {
+        let gate = Arc::new(LearningGate::new());
+        let a = SafeMode::with_gate(gate.clone());
+        let b = SafeMode::new();
+
+        a.engage(None);
+        assert!(a.learning_frozen());
+        assert!(!b.learning_frozen());
+
+        a.release();
+        gate.pause();
+        assert!(a.learning_frozen());
+        assert!(!a.is_engaged());
+        assert!(!b.learning_frozen());
+    }
+}
@@ -0,0 +1,529 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Scheduler v1.0 - recurring user-defined intents
+//!
+//! A [`ScheduledTask`] pairs a [`Recurrence`] with the text to inject into
+//! the Gateway when it is due:
+//!
+//! ```text
+//! every 1h   /status
+//! at 9:00    what is on my calendar today?
+//! ```
+//!
+//! Due tasks are injected as `InputSignal::Text` from
+//! `SignalSource::InternalTimer`, so commands go through the same strict
+//! parsing, allowlist and audit as typed ones. Tasks are managed with the
+//! `/schedule`, `/unschedule` and `/schedules` commands or the REST
+//! `/schedules` endpoint.
+//!
+//! With a [`PersistenceBackend`] attached, task definitions are kept in
+//! the configuration store under component `"scheduler"` (`task:<id>`).
+//! Run times are not persisted: after a restart every task is next due at
+//! its first occurrence from now, missed runs are skipped.
+
+use crate::gateway::Gateway;
+use crate::persistence::{PersistenceBackend, PersistenceError};
+use crate::{InputSignal, SignalSource};
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::task::JoinHandle;
+
+/// Persistence component holding task definitions
+pub const SCHEDULER_COMPONENT: &str = "scheduler";
+
+const TASK_KEY_PREFIX: &str = "task:";
+
+const SECS_PER_DAY: i64 = 86_400;
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// When a task repeats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Recurrence {
+    /// Fixed interval, first run one interval after creation
+    Every { interval_secs: u64 },
+    /// Once a day at a local wall-clock time
+    DailyAt { hour: u8, minute: u8 },
+}
+
+impl Recurrence {
+    /// Parse `every <n><s|m|h|d>` or `at <hh:mm>`
+    pub fn parse(text: &str) -> Result<Self, SchedulerError> {
+        let invalid = || SchedulerError::InvalidSchedule(format!("'{}' (expected 'every 30m' or 'at 9:00')", text));
+        let words: Vec<&str> = text.split_whitespace().collect();
+        match words.as_slice() {
+            [every, amount] if every.eq_ignore_ascii_case("every") => {
+                let unit_at = amount.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+                let (count, unit) = amount.split_at(unit_at);
+                let count: u64 = count.parse().map_err(|_| invalid())?;
+                let scale = match unit.to_ascii_lowercase().as_str() {
+                    "s" => 1,
+                    "m" => 60,
+                    "h" => 3_600,
+                    "d" => SECS_PER_DAY as u64,
+                    _ => return Err(invalid()),
+                };
+                match count.checked_mul(scale) {
+                    Some(interval_secs) if interval_secs > 0 => Ok(Recurrence::Every { interval_secs }),
+                    _ => Err(invalid()),
+                }
+            }
+            [at, time] if at.eq_ignore_ascii_case("at") => {
+                let (hour, minute) = time.split_once(':').ok_or_else(invalid)?;
+                let hour: u8 = hour.parse().map_err(|_| invalid())?;
+                let minute: u8 = minute.parse().map_err(|_| invalid())?;
+                if hour > 23 || minute > 59 {
+                    return Err(invalid());
+                }
+                Ok(Recurrence::DailyAt { hour, minute })
+            }
+            _ => Err(invalid()),
+        }
+    }
+
+    /// First occurrence strictly after `after_ms` (Unix ms)
+    pub fn next_after(&self, after_ms: u64, utc_offset_minutes: i32) -> u64 {
+        match *self {
+            Recurrence::Every { interval_secs } => after_ms + interval_secs * 1000,
+            Recurrence::DailyAt { hour, minute } => {
+                let offset = utc_offset_minutes as i64 * 60;
+                let local = (after_ms / 1000) as i64 + offset;
+                let mut target = local - local.rem_euclid(SECS_PER_DAY) + hour as i64 * 3_600 + minute as i64 * 60;
+                if target <= local {
+                    target += SECS_PER_DAY;
+                }
+                (target - offset).max(0) as u64 * 1000
+            }
+        }
+    }
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Recurrence::Every { interval_secs } => {
+                let (count, unit) = [(SECS_PER_DAY as u64, "d"), (3_600, "h"), (60, "m")]
+                    .into_iter()
+                    .find(|(scale, _)| interval_secs % scale == 0)
+                    .map_or((interval_secs, "s"), |(scale, unit)| (interval_secs / scale, unit));
+                write!(f, "every {}{}", count, unit)
+            }
+            Recurrence::DailyAt { hour, minute } => write!(f, "at {:02}:{:02}", hour, minute),
+        }
+    }
+}
+
+/// A recurring intent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduledTask {
+    pub id: u64,
+    pub recurrence: Recurrence,
+    /// Text injected when due (a command or a question)
+    pub input: String,
+    pub created_at: u64,
+    /// Next run (Unix ms)
+    pub next_run: u64,
+    #[serde(default)]
+    pub last_run: Option<u64>,
+    /// Signal ID of the last injection
+    #[serde(default)]
+    pub last_signal_id: Option<u64>,
+    #[serde(default)]
+    pub runs: u64,
+}
+
+/// Scheduler limits
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SchedulerConfig {
+    pub max_tasks: usize,
+    /// Shortest allowed `every` interval
+    pub min_interval_secs: u64,
+    /// Maximum input length in bytes
+    pub max_input_bytes: usize,
+    /// Local time zone of `at` schedules
+    pub utc_offset_minutes: i32,
+    /// How often the run loop looks for due tasks
+    pub poll_interval_ms: u64,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            max_tasks: 100,
+            min_interval_secs: 60,
+            max_input_bytes: 1024,
+            utc_offset_minutes: 0,
+            poll_interval_ms: 1000,
+        }
+    }
+}
+
+impl SchedulerConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_tasks == 0 {
+            return Err("max_tasks must be > 0".to_string());
+        }
+        if self.min_interval_secs == 0 {
+            return Err("min_interval_secs must be > 0".to_string());
+        }
+        if self.max_input_bytes == 0 {
+            return Err("max_input_bytes must be > 0".to_string());
+        }
+        if self.utc_offset_minutes.abs() > 14 * 60 {
+            return Err("utc_offset_minutes must be within ±14h".to_string());
+        }
+        if self.poll_interval_ms == 0 {
+            return Err("poll_interval_ms must be > 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Scheduler errors
+#[derive(Debug)]
+pub enum SchedulerError {
+    NotFound(u64),
+    /// `max_tasks` reached
+    Full(usize),
+    InvalidSchedule(String),
+    InvalidInput(String),
+    Config(String),
+    Persistence(PersistenceError),
+}
+
+impl fmt::Display for SchedulerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchedulerError::NotFound(id) => write!(f, "Scheduled task {} not found", id),
+            SchedulerError::Full(max) => write!(f, "At most {} tasks can be scheduled", max),
+            SchedulerError::InvalidSchedule(msg) => write!(f, "Invalid schedule: {}", msg),
+            SchedulerError::InvalidInput(msg) => write!(f, "Invalid scheduled input: {}", msg),
+            SchedulerError::Config(msg) => write!(f, "Invalid scheduler config: {}", msg),
+            SchedulerError::Persistence(e) => write!(f, "Persistence error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SchedulerError {}
+
+impl From<PersistenceError> for SchedulerError {
+    fn from(e: PersistenceError) -> Self {
+        SchedulerError::Persistence(e)
+    }
+}
+
+/// Stored task definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TaskRecord {
+    id: u64,
+    recurrence: Recurrence,
+    input: String,
+    created_at: u64,
+}
+
+struct StoredTask {
+    task: ScheduledTask,
+    /// Config ID of the stored definition (for deletion)
+    config_id: Option<i32>,
+}
+
+/// Recurring tasks with optional persistence
+pub struct Scheduler {
+    config: SchedulerConfig,
+    tasks: RwLock<BTreeMap<u64, StoredTask>>,
+    backend: Option<Arc<dyn PersistenceBackend>>,
+}
+
+impl Scheduler {
+    pub fn new(config: SchedulerConfig) -> Result<Self, SchedulerError> {
+        config.validate().map_err(SchedulerError::Config)?;
+        Ok(Self {
+            config,
+            tasks: RwLock::new(BTreeMap::new()),
+            backend: None,
+        })
+    }
+
+    /// Persist task definitions in `backend` (call `load` afterwards)
+    pub fn with_backend(mut self, backend: Arc<dyn PersistenceBackend>) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
+    pub fn config(&self) -> &SchedulerConfig {
+        &self.config
+    }
+
+    /// Load tasks from the backend, replacing the in-memory ones
+    pub async fn load(&self) -> Result<usize, SchedulerError> {
+        let Some(backend) = &self.backend else {
+            return Ok(self.len());
+        };
+
+        let now = now_ms();
+        let mut tasks = BTreeMap::new();
+        for config in backend.get_component_configs(SCHEDULER_COMPONENT).await? {
+            if !config.config_key.starts_with(TASK_KEY_PREFIX) {
+                continue;
+            }
+            match serde_json::from_value::<TaskRecord>(config.config_value) {
+                Ok(record) => {
+                    let task = ScheduledTask {
+                        id: record.id,
+                        recurrence: record.recurrence,
+                        next_run: record.recurrence.next_after(now, self.config.utc_offset_minutes),
+                        input: record.input,
+                        created_at: record.created_at,
+                        last_run: None,
+                        last_signal_id: None,
+                        runs: 0,
+                    };
+                    tasks.insert(task.id, StoredTask { task, config_id: Some(config.config_id) });
+                }
+                Err(e) => tracing::warn!(key = %config.config_key, error = %e, "Skipping invalid scheduled task"),
+            }
+        }
+
+        let count = tasks.len();
+        *self.tasks.write() = tasks;
+        Ok(count)
+    }
+
+    /// Register a recurring task
+    pub async fn add(&self, recurrence: Recurrence, input: &str) -> Result<ScheduledTask, SchedulerError> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(SchedulerError::InvalidInput("input is empty".to_string()));
+        }
+        if input.len() > self.config.max_input_bytes {
+            return Err(SchedulerError::InvalidInput(format!(
+                "input exceeds {} bytes",
+                self.config.max_input_bytes
+            )));
+        }
+        if let Recurrence::Every { interval_secs } = recurrence {
+            if interval_secs < self.config.min_interval_secs {
+                return Err(SchedulerError::InvalidSchedule(format!(
+                    "interval must be at least {}s",
+                    self.config.min_interval_secs
+                )));
+            }
+        }
+
+        let task = {
+            let mut tasks = self.tasks.write();
+            if tasks.len() >= self.config.max_tasks {
+                return Err(SchedulerError::Full(self.config.max_tasks));
+            }
+            let now = now_ms();
+            let id = tasks.keys().next_back().map_or(1, |id| id + 1);
+            let task = ScheduledTask {
+                id,
+                recurrence,
+                input: input.to_string(),
+                created_at: now,
+                next_run: recurrence.next_after(now, self.config.utc_offset_minutes),
+                last_run: None,
+                last_signal_id: None,
+                runs: 0,
+            };
+            tasks.insert(id, StoredTask { task: task.clone(), config_id: None });
+            task
+        };
+
+        // In memory first, like the other stores; a backend error is reported
+        if let Some(backend) = &self.backend {
+            let record = TaskRecord {
+                id: task.id,
+                recurrence: task.recurrence,
+                input: task.input.clone(),
+                created_at: task.created_at,
+            };
+            let value = serde_json::to_value(&record)
+                .map_err(|e| PersistenceError::SerializationError(e.to_string()))?;
+            let key = format!("{}{}", TASK_KEY_PREFIX, task.id);
+            let config_id = backend.save_config(SCHEDULER_COMPONENT, &key, value, None).await?;
+            if let Some(stored) = self.tasks.write().get_mut(&task.id) {
+                stored.config_id = Some(config_id);
+            }
+        }
+        Ok(task)
+    }
+
+    /// Delete a task
+    pub async fn remove(&self, id: u64) -> Result<ScheduledTask, SchedulerError> {
+        let stored = self.tasks.write().remove(&id).ok_or(SchedulerError::NotFound(id))?;
+        if let (Some(backend), Some(config_id)) = (&self.backend, stored.config_id) {
+            backend.deactivate_config(config_id).await?;
+        }
+        Ok(stored.task)
+    }
+
+    pub fn get(&self, id: u64) -> Option<ScheduledTask> {
+        self.tasks.read().get(&id).map(|s| s.task.clone())
+    }
+
+    /// All tasks by ID
+    pub fn list(&self) -> Vec<ScheduledTask> {
+        self.tasks.read().values().map(|s| s.task.clone()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tasks.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Claim the tasks due at `now_ms` and advance their next run
+    pub fn take_due(&self, now_ms: u64) -> Vec<ScheduledTask> {
+        let mut due = Vec::new();
+        for stored in self.tasks.write().values_mut() {
+            let task = &mut stored.task;
+            if task.next_run <= now_ms {
+                task.last_run = Some(now_ms);
+                task.runs += 1;
+                task.next_run = task.recurrence.next_after(now_ms, self.config.utc_offset_minutes);
+                due.push(task.clone());
+            }
+        }
+        due
+    }
+
+    /// Inject every task due at `now_ms`; returns (task ID, signal ID or error)
+    pub async fn run_due(&self, gateway: &Gateway, now_ms: u64) -> Vec<(u64, Result<u64, String>)> {
+        let mut outcomes = Vec::new();
+        for task in self.take_due(now_ms) {
+            let signal = InputSignal::Text {
+                content: task.input.clone(),
+                source: SignalSource::InternalTimer,
+                metadata: Some(serde_json::json!({ "schedule_id": task.id })),
+            };
+            let outcome = match gateway.inject(signal).await {
+                Ok((receipt, _receiver)) => {
+                    if let Some(stored) = self.tasks.write().get_mut(&task.id) {
+                        stored.task.last_signal_id = Some(receipt.signal_id);
+                    }
+                    Ok(receipt.signal_id)
+                }
+                Err(e) => {
+                    tracing::warn!(task = task.id, error = %e, "Scheduled task was not accepted");
+                    Err(e.to_string())
+                }
+            };
+            outcomes.push((task.id, outcome));
+        }
+        outcomes
+    }
+
+    /// Inject due tasks every `poll_interval_ms`
+    pub fn spawn(self: Arc<Self>, gateway: Arc<Gateway>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(Duration::from_millis(self.config.poll_interval_ms));
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                self.run_due(&gateway, now_ms()).await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootstrap::{BootstrapConfig, BootstrapLibrary};
+    use crate::gateway::config::GatewayConfig;
+    use crate::persistence::testing::ConfigBackend;
+    use tokio::sync::mpsc;
+
+    // 2024-01-01 (Monday) 06:00:00 UTC
+    const MONDAY_6AM_MS: u64 = 1_704_088_800_000;
+
+    #[test]
+    fn test_parse_recurrence() {
+        assert_eq!(Recurrence::parse("every 1h").unwrap(), Recurrence::Every { interval_secs: 3600 });
+        assert_eq!(Recurrence::parse("Every 90s").unwrap(), Recurrence::Every { interval_secs: 90 });
+        assert_eq!(Recurrence::parse("at 9:05").unwrap(), Recurrence::DailyAt { hour: 9, minute: 5 });
+        for bad in ["every", "every 0m", "every 5x", "every h", "at 24:00", "at 9", "daily"] {
+            assert!(Recurrence::parse(bad).is_err(), "{:?}", bad);
+        }
+        assert_eq!(Recurrence::Every { interval_secs: 7200 }.to_string(), "every 2h");
+        assert_eq!(Recurrence::Every { interval_secs: 90 }.to_string(), "every 90s");
+        assert_eq!(Recurrence::DailyAt { hour: 9, minute: 0 }.to_string(), "at 09:00");
+
+        // 09:00 local is later today, 05:00 tomorrow; at UTC+2 it is 08:00 local
+        let at_nine = Recurrence::DailyAt { hour: 9, minute: 0 };
+        assert_eq!(at_nine.next_after(MONDAY_6AM_MS, 0), MONDAY_6AM_MS + 3 * 3_600_000);
+        assert_eq!(at_nine.next_after(MONDAY_6AM_MS, 120), MONDAY_6AM_MS + 3_600_000);
+        let at_five = Recurrence::DailyAt { hour: 5, minute: 0 };
+        assert_eq!(at_five.next_after(MONDAY_6AM_MS, 0), MONDAY_6AM_MS + 23 * 3_600_000);
+    }
+
+    #[tokio::test]
+    async fn test_tasks_run_and_persist() {
+        let backend: Arc<dyn PersistenceBackend> = Arc::new(ConfigBackend::default());
+        let scheduler = Scheduler::new(SchedulerConfig::default()).unwrap().with_backend(backend.clone());
+
+        assert!(matches!(
+            scheduler.add(Recurrence::Every { interval_secs: 5 }, "/status").await,
+            Err(SchedulerError::InvalidSchedule(_))
+        ));
+        assert!(scheduler.add(Recurrence::Every { interval_secs: 60 }, "  ").await.is_err());
+        let hourly = scheduler.add(Recurrence::parse("every 1h").unwrap(), "/status").await.unwrap();
+        let daily = scheduler.add(Recurrence::parse("at 9:00").unwrap(), "what is on my calendar?").await.unwrap();
+
+        let bootstrap = Arc::new(RwLock::new(BootstrapLibrary::new(BootstrapConfig::default())));
+        let (tx, mut rx) = mpsc::channel(10);
+        let gateway = Gateway::new(tx, bootstrap, GatewayConfig::default());
+
+        assert!(scheduler.run_due(&gateway, hourly.created_at).await.is_empty());
+        let outcomes = scheduler.run_due(&gateway, hourly.next_run).await;
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!(outcomes[0].0, hourly.id);
+        assert!(outcomes[0].1.is_ok());
+        let task = scheduler.get(hourly.id).unwrap();
+        assert_eq!(task.runs, 1);
+        assert_eq!(task.next_run, hourly.next_run + 3_600_000);
+
+        // The question reaches the pipeline as a timer signal
+        rx.recv().await.unwrap();
+        scheduler.remove(hourly.id).await.unwrap();
+        assert!(matches!(scheduler.remove(hourly.id).await, Err(SchedulerError::NotFound(_))));
+        scheduler.run_due(&gateway, daily.next_run).await;
+        assert_eq!(rx.recv().await.unwrap().source, SignalSource::InternalTimer);
+
+        // Definitions survive a restart; removed tasks stay removed
+        let restored = Scheduler::new(SchedulerConfig::default()).unwrap().with_backend(backend);
+        assert_eq!(restored.load().await.unwrap(), 1);
+        let task = restored.get(daily.id).unwrap();
+        assert_eq!(task.input, "what is on my calendar?");
+        assert_eq!(task.runs, 0);
+    }
+}
@@ -0,0 +1,584 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Settings v1.0 - runtime-editable component configs
+//!
+//! [`SettingsManager`] holds the Gateway, Curiosity, Learning, Arbiter and
+//! I18n configs a process was started with, plus user overrides. An update is a
+//! partial JSON patch (`{"boredom_threshold": 0.4}`):
+//!
+//! 1. merged into the effective config; unknown fields are rejected
+//! 2. deserialized into the typed config and validated
+//! 3. applied live through the section's applier, if one is registered;
+//!    fields the component cannot change while running are reported in
+//!    `ConfigUpdate::restart_required`
+//! 4. stored as an override (only the patched fields), in the attached
+//!    [`PersistenceBackend`] under component `"settings"`
+//!
+//! At startup, `load` reads the stored overrides and `effective::<T>()`
+//! returns the config to build each component with.
+
+use crate::action_controller::ArbiterConfig;
+use crate::curiosity::CuriosityConfig;
+use crate::gateway::config::GatewayConfig;
+use crate::i18n::I18nConfig;
+use crate::intuition_engine::LearningConfig;
+use crate::persistence::{PersistenceBackend, PersistenceError};
+use parking_lot::RwLock;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Persistence component holding overrides
+pub const SETTINGS_COMPONENT: &str = "settings";
+
+/// Editable config sections
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConfigSection {
+    Gateway,
+    Curiosity,
+    Learning,
+    Arbiter,
+    I18n,
+}
+
+impl ConfigSection {
+    pub const ALL: [ConfigSection; 5] = [
+        ConfigSection::Gateway,
+        ConfigSection::Curiosity,
+        ConfigSection::Learning,
+        ConfigSection::Arbiter,
+        ConfigSection::I18n,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConfigSection::Gateway => "gateway",
+            ConfigSection::Curiosity => "curiosity",
+            ConfigSection::Learning => "learning",
+            ConfigSection::Arbiter => "arbiter",
+            ConfigSection::I18n => "i18n",
+        }
+    }
+}
+
+impl fmt::Display for ConfigSection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ConfigSection {
+    type Err = SettingsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|section| section.as_str() == s)
+            .ok_or_else(|| SettingsError::UnknownSection(s.to_string()))
+    }
+}
+
+/// A config type editable through [`SettingsManager`]
+pub trait SettingsConfig: Serialize + DeserializeOwned + Send + Sync + 'static {
+    const SECTION: ConfigSection;
+
+    /// Top-level fields the registered applier changes without a restart
+    const LIVE_FIELDS: &'static [&'static str];
+
+    fn check(&self) -> Result<(), String>;
+}
+
+impl SettingsConfig for GatewayConfig {
+    const SECTION: ConfigSection = ConfigSection::Gateway;
+    const LIVE_FIELDS: &'static [&'static str] = GatewayConfig::LIVE_FIELDS;
+
+    fn check(&self) -> Result<(), String> {
+        self.validate()
+    }
+}
+
+impl SettingsConfig for CuriosityConfig {
+    const SECTION: ConfigSection = ConfigSection::Curiosity;
+    const LIVE_FIELDS: &'static [&'static str] = CuriosityConfig::LIVE_FIELDS;
+
+    fn check(&self) -> Result<(), String> {
+        self.validate()
+    }
+}
+
+impl SettingsConfig for LearningConfig {
+    const SECTION: ConfigSection = ConfigSection::Learning;
+    const LIVE_FIELDS: &'static [&'static str] = LearningConfig::LIVE_FIELDS;
+
+    fn check(&self) -> Result<(), String> {
+        self.validate()
+    }
+}
+
+impl SettingsConfig for ArbiterConfig {
+    const SECTION: ConfigSection = ConfigSection::Arbiter;
+    // Read on the decision hot path without a lock
+    const LIVE_FIELDS: &'static [&'static str] = &[];
+
+    fn check(&self) -> Result<(), String> {
+        self.validate()
+    }
+}
+
+impl SettingsConfig for I18nConfig {
+    const SECTION: ConfigSection = ConfigSection::I18n;
+    const LIVE_FIELDS: &'static [&'static str] = I18nConfig::LIVE_FIELDS;
+
+    fn check(&self) -> Result<(), String> {
+        self.validate()
+    }
+}
+
+/// Settings errors
+#[derive(Debug)]
+pub enum SettingsError {
+    UnknownSection(String),
+    /// Section exists but no config was registered for it
+    NotRegistered(ConfigSection),
+    /// Patch does not fit the config shape (unknown field, wrong type)
+    InvalidPatch(String),
+    /// Patched config failed validation
+    Invalid(String),
+    /// The live applier rejected the config; nothing was stored
+    Apply(String),
+    Persistence(PersistenceError),
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SettingsError::UnknownSection(name) => write!(f, "Unknown config section '{}'", name),
+            SettingsError::NotRegistered(section) => write!(f, "Config section '{}' is not available", section),
+            SettingsError::InvalidPatch(msg) => write!(f, "Invalid config patch: {}", msg),
+            SettingsError::Invalid(msg) => write!(f, "Invalid config: {}", msg),
+            SettingsError::Apply(msg) => write!(f, "Failed to apply config: {}", msg),
+            SettingsError::Persistence(e) => write!(f, "Persistence error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SettingsError {}
+
+impl From<PersistenceError> for SettingsError {
+    fn from(e: PersistenceError) -> Self {
+        SettingsError::Persistence(e)
+    }
+}
+
+/// Result of `SettingsManager::update`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigUpdate {
+    pub section: ConfigSection,
+    /// Effective config after the update
+    pub config: Value,
+    /// Top-level fields whose value changed
+    pub changed: Vec<String>,
+    /// True if the running component received the new config
+    pub applied: bool,
+    /// Changed fields that only take effect after a restart
+    pub restart_required: Vec<String>,
+}
+
+/// Merge `patch` into `target`, rejecting keys that `target` does not have
+///
+/// `what` names the config in messages (e.g. "ADNA").
+pub fn merge_known_fields(target: &mut Value, patch: &Value, what: &str) -> Result<(), String> {
+    merge_at(target, patch, what, "")
+}
+
+fn merge_at(target: &mut Value, patch: &Value, what: &str, path: &str) -> Result<(), String> {
+    match (target, patch) {
+        (Value::Object(target), Value::Object(patch)) => {
+            for (key, value) in patch {
+                let field_path = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                let field = target
+                    .get_mut(key)
+                    .ok_or_else(|| format!("Unknown {} field '{}'", what, field_path))?;
+                merge_at(field, value, what, &field_path)?;
+            }
+            Ok(())
+        }
+        (Value::Object(_), _) if path.is_empty() => Err(format!("{} update must be a JSON object", what)),
+        (Value::Object(_), _) => Err(format!("{} field '{}' is a section, not a value", what, path)),
+        (target, value) => {
+            *target = value.clone();
+            Ok(())
+        }
+    }
+}
+
+/// Merge without shape checks (accumulating overrides)
+pub(crate) fn merge_into(target: &mut Value, patch: &Value) {
+    match (target, patch) {
+        (Value::Object(target), Value::Object(patch)) => {
+            for (key, value) in patch {
+                merge_into(target.entry(key.clone()).or_insert(Value::Null), value);
+            }
+        }
+        (target, value) => *target = value.clone(),
+    }
+}
+
+type Checker = fn(Value) -> Result<Value, String>;
+
+/// Applies a validated config to the running component
+pub type ConfigApplier = Arc<dyn Fn(&Value) -> Result<(), String> + Send + Sync>;
+
+fn check<T: SettingsConfig>(value: Value) -> Result<Value, String> {
+    let config: T = serde_json::from_value(value).map_err(|e| e.to_string())?;
+    config.check()?;
+    serde_json::to_value(config).map_err(|e| e.to_string())
+}
+
+struct Section {
+    base: Value,
+    overrides: Value,
+    check: Checker,
+    live_fields: &'static [&'static str],
+    applier: Option<ConfigApplier>,
+    config_id: Option<i32>,
+}
+
+impl Section {
+    fn effective(&self) -> Value {
+        let mut value = self.base.clone();
+        merge_into(&mut value, &self.overrides);
+        // Overrides were valid when stored, but a newer build may reject them
+        (self.check)(value).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Ignoring stale config overrides");
+            self.base.clone()
+        })
+    }
+}
+
+/// Component configs with validated, persisted runtime overrides
+pub struct SettingsManager {
+    sections: RwLock<BTreeMap<ConfigSection, Section>>,
+    backend: Option<Arc<dyn PersistenceBackend>>,
+}
+
+impl Default for SettingsManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SettingsManager {
+    pub fn new() -> Self {
+        Self {
+            sections: RwLock::new(BTreeMap::new()),
+            backend: None,
+        }
+    }
+
+    /// Store overrides in `backend` (call `load` afterwards)
+    pub fn with_backend(mut self, backend: Arc<dyn PersistenceBackend>) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
+    /// Register the startup config of a section (restart-only)
+    pub fn register<T: SettingsConfig>(&self, base: &T) -> Result<(), SettingsError> {
+        self.insert::<T>(base, None)
+    }
+
+    /// Register a section whose running component can take updates
+    pub fn register_live<T, F>(&self, base: &T, apply: F) -> Result<(), SettingsError>
+    where
+        T: SettingsConfig,
+        F: Fn(T) -> Result<(), String> + Send + Sync + 'static,
+    {
+        let applier: ConfigApplier = Arc::new(move |value: &Value| {
+            let config: T = serde_json::from_value(value.clone()).map_err(|e| e.to_string())?;
+            apply(config)
+        });
+        self.insert::<T>(base, Some(applier))
+    }
+
+    fn insert<T: SettingsConfig>(&self, base: &T, applier: Option<ConfigApplier>) -> Result<(), SettingsError> {
+        let base = serde_json::to_value(base).map_err(|e| SettingsError::Invalid(e.to_string()))?;
+        let mut sections = self.sections.write();
+        let previous = sections.remove(&T::SECTION);
+        sections.insert(
+            T::SECTION,
+            Section {
+                base,
+                overrides: previous.as_ref().map(|s| s.overrides.clone()).unwrap_or_else(|| Value::Object(Default::default())),
+                check: check::<T>,
+                live_fields: T::LIVE_FIELDS,
+                applier,
+                config_id: previous.and_then(|s| s.config_id),
+            },
+        );
+        Ok(())
+    }
+
+    /// Read stored overrides; returns how many sections have overrides
+    pub async fn load(&self) -> Result<usize, SettingsError> {
+        let Some(backend) = &self.backend else {
+            return Ok(0);
+        };
+
+        let mut loaded = 0;
+        for config in backend.get_component_configs(SETTINGS_COMPONENT).await? {
+            let Ok(section) = config.config_key.parse::<ConfigSection>() else {
+                tracing::warn!(key = %config.config_key, "Skipping unknown settings section");
+                continue;
+            };
+            let mut sections = self.sections.write();
+            let Some(entry) = sections.get_mut(&section) else {
+                continue;
+            };
+            entry.overrides = config.config_value;
+            entry.config_id = Some(config.config_id);
+            loaded += 1;
+        }
+        Ok(loaded)
+    }
+
+    /// Registered sections
+    pub fn sections(&self) -> Vec<ConfigSection> {
+        self.sections.read().keys().copied().collect()
+    }
+
+    /// Effective config of a section as JSON
+    pub fn get(&self, section: ConfigSection) -> Result<Value, SettingsError> {
+        self.sections
+            .read()
+            .get(&section)
+            .map(Section::effective)
+            .ok_or(SettingsError::NotRegistered(section))
+    }
+
+    /// Effective typed config (startup config plus overrides)
+    pub fn effective<T: SettingsConfig>(&self) -> Result<T, SettingsError> {
+        let value = self.get(T::SECTION)?;
+        serde_json::from_value(value).map_err(|e| SettingsError::Invalid(e.to_string()))
+    }
+
+    /// Overridden fields of a section
+    pub fn overrides(&self, section: ConfigSection) -> Result<Value, SettingsError> {
+        self.sections
+            .read()
+            .get(&section)
+            .map(|s| s.overrides.clone())
+            .ok_or(SettingsError::NotRegistered(section))
+    }
+
+    /// Validate, apply and store a partial update
+    pub async fn update(&self, section: ConfigSection, patch: Value) -> Result<ConfigUpdate, SettingsError> {
+        let (current, mut overrides, check, live_fields, applier, parent) = {
+            let sections = self.sections.read();
+            let entry = sections.get(&section).ok_or(SettingsError::NotRegistered(section))?;
+            (
+                entry.effective(),
+                entry.overrides.clone(),
+                entry.check,
+                entry.live_fields,
+                entry.applier.clone(),
+                entry.config_id,
+            )
+        };
+
+        let mut updated = current.clone();
+        merge_known_fields(&mut updated, &patch, section.as_str()).map_err(SettingsError::InvalidPatch)?;
+        let updated = check(updated).map_err(SettingsError::Invalid)?;
+        merge_into(&mut overrides, &patch);
+
+        let changed = changed_fields(&current, &updated);
+        let applied = match &applier {
+            Some(apply) if !changed.is_empty() => {
+                apply(&updated).map_err(SettingsError::Apply)?;
+                true
+            }
+            _ => false,
+        };
+        let restart_required = changed
+            .iter()
+            .filter(|field| !applied || !live_fields.contains(&field.as_str()))
+            .cloned()
+            .collect();
+
+        let config_id = self.persist(section, &overrides, parent).await?;
+        if let Some(entry) = self.sections.write().get_mut(&section) {
+            entry.overrides = overrides;
+            entry.config_id = config_id;
+        }
+
+        tracing::info!(section = %section, ?changed, applied, "Config updated");
+        Ok(ConfigUpdate {
+            section,
+            config: updated,
+            changed,
+            applied,
+            restart_required,
+        })
+    }
+
+    /// Drop all overrides of a section and return to its startup config
+    pub async fn reset(&self, section: ConfigSection) -> Result<ConfigUpdate, SettingsError> {
+        let (current, base, live_fields, applier, config_id) = {
+            let sections = self.sections.read();
+            let entry = sections.get(&section).ok_or(SettingsError::NotRegistered(section))?;
+            (entry.effective(), entry.base.clone(), entry.live_fields, entry.applier.clone(), entry.config_id)
+        };
+
+        let changed = changed_fields(&current, &base);
+        let applied = match &applier {
+            Some(apply) if !changed.is_empty() => {
+                apply(&base).map_err(SettingsError::Apply)?;
+                true
+            }
+            _ => false,
+        };
+        let restart_required = changed
+            .iter()
+            .filter(|field| !applied || !live_fields.contains(&field.as_str()))
+            .cloned()
+            .collect();
+
+        if let (Some(backend), Some(config_id)) = (&self.backend, config_id) {
+            backend.deactivate_config(config_id).await?;
+        }
+        if let Some(entry) = self.sections.write().get_mut(&section) {
+            entry.overrides = Value::Object(Default::default());
+            entry.config_id = None;
+        }
+
+        Ok(ConfigUpdate {
+            section,
+            config: base,
+            changed,
+            applied,
+            restart_required,
+        })
+    }
+
+    async fn persist(&self, section: ConfigSection, overrides: &Value, parent: Option<i32>) -> Result<Option<i32>, SettingsError> {
+        let Some(backend) = &self.backend else {
+            return Ok(parent);
+        };
+        let config_id = backend
+            .save_config(SETTINGS_COMPONENT, section.as_str(), overrides.clone(), parent)
+            .await?;
+        Ok(Some(config_id))
+    }
+}
+
+/// Top-level fields that differ, sorted
+fn changed_fields(before: &Value, after: &Value) -> Vec<String> {
+    match (before, after) {
+        (Value::Object(before), Value::Object(after)) => after
+            .iter()
+            .filter(|(key, value)| before.get(*key) != Some(*value))
+            .map(|(key, _)| key.clone())
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curiosity::CuriosityDrive;
+    use serde_json::json;
+
+    #[test]
+    fn test_merge_known_fields() {
+        let mut value = json!({"a": 1, "nested": {"b": 2}});
+        merge_known_fields(&mut value, &json!({"nested": {"b": 3}}), "test").unwrap();
+        assert_eq!(value, json!({"a": 1, "nested": {"b": 3}}));
+
+        let err = merge_known_fields(&mut value, &json!({"nested": {"c": 1}}), "test").unwrap_err();
+        assert_eq!(err, "Unknown test field 'nested.c'");
+        assert!(merge_known_fields(&mut value, &json!({"nested": 1}), "test").is_err());
+        assert!(merge_known_fields(&mut value, &json!(5), "test").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_update_validates_and_applies_live() {
+        let drive = Arc::new(CuriosityDrive::new(CuriosityConfig::default()));
+        let settings = SettingsManager::new();
+        let target = drive.clone();
+        settings
+            .register_live(&drive.config(), move |config: CuriosityConfig| target.update_config(config))
+            .unwrap();
+
+        let update = settings
+            .update(ConfigSection::Curiosity, json!({"boredom_threshold": 0.3, "surprise_history_size": 10}))
+            .await
+            .unwrap();
+        assert!(update.applied);
+        assert_eq!(update.changed, vec!["boredom_threshold", "surprise_history_size"]);
+        assert_eq!(update.restart_required, vec!["surprise_history_size"]);
+        assert_eq!(drive.config().boredom_threshold, 0.3);
+        // Fixed at construction
+        assert_eq!(drive.config().surprise_history_size, 50);
+
+        assert!(matches!(
+            settings.update(ConfigSection::Curiosity, json!({"boredom_threshold": 7.0})).await,
+            Err(SettingsError::Invalid(_))
+        ));
+        assert!(matches!(
+            settings.update(ConfigSection::Curiosity, json!({"no_such_field": 1})).await,
+            Err(SettingsError::InvalidPatch(_))
+        ));
+        assert!(matches!(
+            settings.update(ConfigSection::Arbiter, json!({})).await,
+            Err(SettingsError::NotRegistered(ConfigSection::Arbiter))
+        ));
+        assert_eq!(drive.config().boredom_threshold, 0.3);
+
+        let reset = settings.reset(ConfigSection::Curiosity).await.unwrap();
+        assert!(reset.applied);
+        assert_eq!(drive.config().boredom_threshold, 0.6);
+        assert_eq!(settings.overrides(ConfigSection::Curiosity).unwrap(), json!({}));
+    }
+
+    #[tokio::test]
+    async fn test_restart_only_sections() {
+        let settings = SettingsManager::new();
+        settings.register(&ArbiterConfig::default()).unwrap();
+        settings.register(&LearningConfig::default()).unwrap();
+
+        let update = settings
+            .update(ConfigSection::Arbiter, json!({"reflex_confidence_threshold": 180}))
+            .await
+            .unwrap();
+        assert!(!update.applied);
+        assert_eq!(update.restart_required, vec!["reflex_confidence_threshold"]);
+        assert_eq!(settings.effective::<ArbiterConfig>().unwrap().reflex_confidence_threshold, 180);
+
+        assert!(matches!(
+            settings.update(ConfigSection::Learning, json!({"state_bins_per_dim": 1})).await,
+            Err(SettingsError::Invalid(_))
+        ));
+        assert_eq!("learning".parse::<ConfigSection>().unwrap(), ConfigSection::Learning);
+        assert!("grid".parse::<ConfigSection>().is_err());
+    }
+}
@@ -39,6 +39,9 @@ pub enum FilterCondition {
     /// Проверка hash (sensor_id, etc.)
     Hash(HashCondition),
 
+    /// Семантический регион: вектор события в пределах 8D радиуса
+    Region(RegionCondition),
+
     /// Комбинированное условие (AND/OR/NOT)
     Combined(Box<CombinedCondition>),
 }
@@ -298,6 +301,63 @@ impl HashMode {
     }
 }
 
+// ═══════════════════════════════════════════════════════════════════════════════
+// REGION CONDITION
+// ═══════════════════════════════════════════════════════════════════════════════
+
+/// Семантический регион — шар в 8D пространстве
+///
+/// Совпадает, если SemanticCore события (вектор) лежит в пределах `radius`
+/// (евклидово расстояние) от `center`. Позволяет подписаться на
+/// "всё рядом с регионом опасности".
+#[derive(Debug, Clone)]
+pub struct RegionCondition {
+    /// Центр региона (опорное состояние)
+    pub center: [f32; 8],
+
+    /// Радиус региона
+    pub radius: f32,
+
+    /// radius² — предвычислен, чтобы не брать sqrt на каждое событие
+    radius_sq: f32,
+}
+
+impl RegionCondition {
+    pub fn new(center: [f32; 8], radius: f32) -> Result<Self, FilterError> {
+        if !radius.is_finite() || radius < 0.0 {
+            return Err(FilterError::InvalidFormat(
+                "region radius must be a finite non-negative number".to_string(),
+            ));
+        }
+        if center.iter().any(|v| !v.is_finite()) {
+            return Err(FilterError::InvalidFormat(
+                "region center must contain finite numbers".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            center,
+            radius,
+            radius_sq: radius * radius,
+        })
+    }
+
+    /// Квадрат расстояния от центра до вектора
+    #[inline]
+    pub fn distance_sq(&self, vector: &[f32; 8]) -> f32 {
+        self.center
+            .iter()
+            .zip(vector.iter())
+            .map(|(c, v)| (v - c) * (v - c))
+            .sum()
+    }
+
+    #[inline]
+    pub fn matches(&self, event: &SignalEvent) -> bool {
+        self.distance_sq(&event.vector) <= self.radius_sq
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════════
 // COMBINED CONDITION
 // ═══════════════════════════════════════════════════════════════════════════════
@@ -338,6 +398,7 @@ impl SubscriptionFilter {
     /// {"event_type": "signal.input.external.text.chat"}
     /// {"event_type": {"$wildcard": "signal.input.external.*"}}
     /// {"priority": {"$gte": 200}}
+    /// {"region": {"center": [0.9, 0.0, 0.0, -0.8, 0.0, 0.0, 0.0, 0.0], "radius": 0.5}}
     /// {"$and": [{"event_type": "..."}, {"priority": {"$gt": 100}}]}
     /// ```
     pub fn compile(
@@ -390,6 +451,7 @@ impl SubscriptionFilter {
         for (key, value) in obj {
             let condition = match key.as_str() {
                 "event_type" => Self::compile_event_type(value, registry)?,
+                "region" => Self::compile_region(value)?,
                 field if NumericField::from_str(field).is_some() => {
                     Self::compile_numeric(field, value)?
                 }
@@ -433,6 +495,40 @@ impl SubscriptionFilter {
         )))
     }
 
+    /// {"region": {"center": [8 чисел], "radius": 0.5}}
+    fn compile_region(value: &JsonValue) -> Result<FilterCondition, FilterError> {
+        let obj = value
+            .as_object()
+            .ok_or(FilterError::InvalidFormat("region must be object".to_string()))?;
+
+        let center_json = obj
+            .get("center")
+            .and_then(|c| c.as_array())
+            .ok_or(FilterError::InvalidFormat("region.center must be array".to_string()))?;
+        if center_json.len() != 8 {
+            return Err(FilterError::InvalidFormat(format!(
+                "region.center must have 8 values, got {}",
+                center_json.len()
+            )));
+        }
+
+        let mut center = [0.0f32; 8];
+        for (slot, v) in center.iter_mut().zip(center_json) {
+            *slot = v
+                .as_f64()
+                .ok_or(FilterError::InvalidFormat("region.center values must be numbers".to_string()))?
+                as f32;
+        }
+
+        let radius = obj
+            .get("radius")
+            .and_then(|r| r.as_f64())
+            .ok_or(FilterError::InvalidFormat("region.radius must be number".to_string()))?
+            as f32;
+
+        Ok(FilterCondition::Region(RegionCondition::new(center, radius)?))
+    }
+
     fn compile_numeric(field: &str, value: &JsonValue) -> Result<FilterCondition, FilterError> {
         let numeric_field = NumericField::from_str(field)
             .ok_or_else(|| FilterError::UnknownField(field.to_string()))?;
@@ -509,6 +605,8 @@ impl FilterCondition {
                 c.mode.check(hash, &c.hashes)
             }
 
+            FilterCondition::Region(c) => c.matches(event),
+
             FilterCondition::Combined(c) => {
                 match c.logic {
                     FilterLogic::And => c.conditions.iter().all(|cond| cond.matches(event, registry)),
@@ -718,4 +816,27 @@ mod tests {
         event.priority = 100;
         assert!(!filter.matches(&event, &registry));
     }
+
+    #[test]
+    fn test_filter_region() {
+        let mut registry = EventTypeRegistry::new();
+        let json = serde_json::json!({
+            "region": {"center": [1.0, 0.0, 0.0, -1.0, 0.0, 0.0, 0.0, 0.0], "radius": 0.5}
+        });
+
+        let filter = SubscriptionFilter::compile(1, &json, &mut registry).unwrap();
+
+        let mut event = SignalEvent::default();
+        event.vector = [0.8, 0.1, 0.0, -0.9, 0.0, 0.0, 0.0, 0.0];
+        assert!(filter.matches(&event, &registry));
+
+        event.vector = [0.0; 8];
+        assert!(!filter.matches(&event, &registry));
+
+        // Некорректные регионы отвергаются при компиляции
+        let bad_dims = serde_json::json!({"region": {"center": [1.0, 0.0], "radius": 0.5}});
+        assert!(SubscriptionFilter::compile(2, &bad_dims, &mut registry).is_err());
+        let bad_radius = serde_json::json!({"region": {"center": [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0], "radius": -1.0}});
+        assert!(SubscriptionFilter::compile(3, &bad_radius, &mut registry).is_err());
+    }
 }
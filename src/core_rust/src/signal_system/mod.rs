@@ -21,9 +21,9 @@ pub mod py_bindings;
 
 // Re-exports
 pub use event::{SignalEvent, SignalSource, SemanticCore, EnergyProfile, TemporalBinding, RoutingInfo};
-pub use registry::EventTypeRegistry;
+pub use registry::{EventTypeRegistry, RegistryError, RegistrySnapshot, TypeMigration};
 pub use result::{ProcessingResult, NeighborInfo};
-pub use filter::{SubscriptionFilter, FilterCondition, FilterLogic, FilterError};
+pub use filter::{SubscriptionFilter, FilterCondition, FilterLogic, FilterError, RegionCondition};
 pub use subscriber::{Subscriber, SubscriberId, CallbackType, ProcessedEvent, DeliveryMeta, SubscriberError};
 pub use system::{SignalSystem, SignalSystemConfig, SignalSystemStats, SignalSystemError};
 
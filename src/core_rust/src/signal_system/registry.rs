@@ -1,4 +1,14 @@
-use std::collections::HashMap;
+use crate::persistence::{PersistenceBackend, PersistenceError};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use thiserror::Error;
+
+/// Компонент и ключ в config store PersistenceBackend
+pub const REGISTRY_CONFIG_COMPONENT: &str = "signal_system";
+pub const REGISTRY_CONFIG_KEY: &str = "event_type_registry";
+
+/// Текущая версия формата снимка реестра
+pub const REGISTRY_SNAPSHOT_VERSION: u32 = 1;
 
 /// Реестр типов событий — строки маппятся в u32 ID
 ///
@@ -15,16 +25,74 @@ pub struct EventTypeRegistry {
 
     /// Предкомпилированные wildcard паттерны для быстрого matching
     wildcard_cache: HashMap<String, Vec<u32>>,
+
+    /// Старое имя → новое имя (после переименований)
+    aliases: HashMap<String, String>,
+}
+
+/// Ошибки реестра типов событий
+#[derive(Debug, Error)]
+pub enum RegistryError {
+    #[error("Invalid event type name: {0}")]
+    InvalidName(String),
+
+    #[error("Event type not registered: {0}")]
+    NotRegistered(String),
+
+    #[error("Event type already registered: {0}")]
+    AlreadyRegistered(String),
+
+    #[error("Unsupported registry snapshot version: {0}")]
+    UnsupportedVersion(u32),
+}
+
+/// Снимок реестра для персистентности
+///
+/// `types[i]` — тип с ID `i`, поэтому ID совпадают между запусками.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegistrySnapshot {
+    pub version: u32,
+    pub types: Vec<String>,
+    #[serde(default)]
+    pub aliases: HashMap<String, String>,
+}
+
+/// Миграция переименованного типа: `from` → `to` (ID сохраняется)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypeMigration {
+    pub from: String,
+    pub to: String,
+}
+
+impl TypeMigration {
+    pub fn new(from: &str, to: &str) -> Self {
+        Self {
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
 }
 
 impl EventTypeRegistry {
     /// Инициализация с базовыми типами из таксономии
     pub fn new() -> Self {
-        let mut registry = Self {
+        let mut registry = Self::empty();
+        registry.register_base_types();
+        registry
+    }
+
+    fn empty() -> Self {
+        Self {
             type_to_id: HashMap::new(),
             id_to_type: Vec::new(),
             wildcard_cache: HashMap::new(),
-        };
+            aliases: HashMap::new(),
+        }
+    }
+
+    /// Регистрирует базовую таксономию (уже известные типы не меняются)
+    fn register_base_types(&mut self) {
+        let registry = self;
 
         // Регистрируем базовые типы из таксономии Signal Gateway v2.0
 
@@ -83,14 +151,14 @@ impl EventTypeRegistry {
         registry.register("signal.meta.subscription.removed");
         registry.register("signal.meta.sensor.registered");
         registry.register("signal.meta.sensor.unregistered");
-
-        registry
     }
 
     /// Регистрирует новый тип события, возвращает ID
+    ///
+    /// Старое (переименованное) имя разрешается в ID нового.
     pub fn register(&mut self, event_type: &str) -> u32 {
         // Если уже зарегистрирован - возвращаем существующий ID
-        if let Some(&id) = self.type_to_id.get(event_type) {
+        if let Some(id) = self.get_id(event_type) {
             return id;
         }
 
@@ -98,13 +166,154 @@ impl EventTypeRegistry {
         let id = self.id_to_type.len() as u32;
         self.type_to_id.insert(event_type.to_string(), id);
         self.id_to_type.push(event_type.to_string());
+        // Новый тип может попадать под уже скомпилированные паттерны
+        self.wildcard_cache.clear();
 
         id
     }
 
-    /// Получает ID по строке
+    /// Регистрирует тип в пространстве имён: ("user", "query") → "user.query"
+    pub fn register_namespaced(&mut self, namespace: &str, name: &str) -> Result<u32, RegistryError> {
+        let event_type = format!("{}.{}", namespace, name);
+        Self::validate_name(&event_type)?;
+        Ok(self.register(&event_type))
+    }
+
+    /// Проверяет имя типа: непустые сегменты из [a-z0-9_], разделённые точками
+    pub fn validate_name(event_type: &str) -> Result<(), RegistryError> {
+        let valid = event_type.split('.').all(|segment| {
+            !segment.is_empty()
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        });
+
+        if valid {
+            Ok(())
+        } else {
+            Err(RegistryError::InvalidName(event_type.to_string()))
+        }
+    }
+
+    /// Пространство имён типа — первый сегмент ("user.query" → "user")
+    pub fn namespace_of(event_type: &str) -> &str {
+        event_type.split('.').next().unwrap_or(event_type)
+    }
+
+    /// Все пространства имён (отсортированы)
+    pub fn namespaces(&self) -> Vec<String> {
+        self.id_to_type
+            .iter()
+            .map(|t| Self::namespace_of(t).to_string())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect()
+    }
+
+    /// Типы в пространстве имён с их ID
+    pub fn list_namespace(&self, namespace: &str) -> Vec<(u32, String)> {
+        self.id_to_type
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| Self::namespace_of(t) == namespace)
+            .map(|(id, t)| (id as u32, t.clone()))
+            .collect()
+    }
+
+    /// Получает ID по строке (с учётом переименований)
     pub fn get_id(&self, event_type: &str) -> Option<u32> {
-        self.type_to_id.get(event_type).copied()
+        if let Some(&id) = self.type_to_id.get(event_type) {
+            return Some(id);
+        }
+
+        // Следуем цепочке переименований (a → b → c)
+        let mut current = event_type;
+        for _ in 0..self.aliases.len() {
+            current = self.aliases.get(current)?;
+            if let Some(&id) = self.type_to_id.get(current) {
+                return Some(id);
+            }
+        }
+        None
+    }
+
+    /// Переименовывает тип, сохраняя его ID
+    ///
+    /// Старое имя остаётся алиасом, так что архивные данные и код,
+    /// использующий старое имя, продолжают работать.
+    pub fn rename(&mut self, old: &str, new: &str) -> Result<u32, RegistryError> {
+        Self::validate_name(new)?;
+
+        let id = *self
+            .type_to_id
+            .get(old)
+            .ok_or_else(|| RegistryError::NotRegistered(old.to_string()))?;
+        if self.get_id(new).is_some() {
+            return Err(RegistryError::AlreadyRegistered(new.to_string()));
+        }
+
+        self.type_to_id.remove(old);
+        self.type_to_id.insert(new.to_string(), id);
+        self.id_to_type[id as usize] = new.to_string();
+        self.aliases.insert(old.to_string(), new.to_string());
+        self.wildcard_cache.clear();
+
+        Ok(id)
+    }
+
+    /// Применяет миграции переименований
+    ///
+    /// Идемпотентно: уже применённые миграции (старого имени нет, новое
+    /// есть) пропускаются. Возвращает ID переименованных типов.
+    pub fn apply_migrations(&mut self, migrations: &[TypeMigration]) -> Result<Vec<u32>, RegistryError> {
+        let mut renamed = Vec::new();
+
+        for migration in migrations {
+            if !self.type_to_id.contains_key(&migration.from) {
+                if self.get_id(&migration.to).is_some() {
+                    continue;
+                }
+                return Err(RegistryError::NotRegistered(migration.from.clone()));
+            }
+            renamed.push(self.rename(&migration.from, &migration.to)?);
+        }
+
+        Ok(renamed)
+    }
+
+    /// Алиасы (старое имя → новое)
+    pub fn aliases(&self) -> &HashMap<String, String> {
+        &self.aliases
+    }
+
+    /// Снимок для персистентности
+    pub fn snapshot(&self) -> RegistrySnapshot {
+        RegistrySnapshot {
+            version: REGISTRY_SNAPSHOT_VERSION,
+            types: self.id_to_type.clone(),
+            aliases: self.aliases.clone(),
+        }
+    }
+
+    /// Восстанавливает реестр из снимка, сохраняя ID
+    ///
+    /// Базовые типы, появившиеся после создания снимка, добавляются в конец.
+    pub fn from_snapshot(snapshot: &RegistrySnapshot) -> Result<Self, RegistryError> {
+        if snapshot.version != REGISTRY_SNAPSHOT_VERSION {
+            return Err(RegistryError::UnsupportedVersion(snapshot.version));
+        }
+
+        let mut registry = Self::empty();
+        for (id, event_type) in snapshot.types.iter().enumerate() {
+            if registry.type_to_id.insert(event_type.clone(), id as u32).is_some() {
+                return Err(RegistryError::AlreadyRegistered(event_type.clone()));
+            }
+            registry.id_to_type.push(event_type.clone());
+        }
+        registry.aliases = snapshot.aliases.clone();
+        registry.register_base_types();
+
+        Ok(registry)
     }
 
     /// Получает строку по ID
@@ -192,6 +401,35 @@ impl EventTypeRegistry {
     }
 }
 
+impl RegistrySnapshot {
+    /// Сохраняет снимок в config store (новая версия конфигурации)
+    pub async fn save(&self, backend: &dyn PersistenceBackend) -> Result<i32, PersistenceError> {
+        let value = serde_json::to_value(self)
+            .map_err(|e| PersistenceError::SerializationError(e.to_string()))?;
+        let parent = backend
+            .get_config(REGISTRY_CONFIG_COMPONENT, REGISTRY_CONFIG_KEY)
+            .await?
+            .map(|c| c.config_id);
+
+        backend
+            .save_config(REGISTRY_CONFIG_COMPONENT, REGISTRY_CONFIG_KEY, value, parent)
+            .await
+    }
+
+    /// Загружает последний сохранённый снимок, если он есть
+    pub async fn load(backend: &dyn PersistenceBackend) -> Result<Option<Self>, PersistenceError> {
+        match backend
+            .get_config(REGISTRY_CONFIG_COMPONENT, REGISTRY_CONFIG_KEY)
+            .await?
+        {
+            Some(config) => serde_json::from_value(config.config_value)
+                .map(Some)
+                .map_err(|e| PersistenceError::SerializationError(e.to_string())),
+            None => Ok(None),
+        }
+    }
+}
+
 impl Default for EventTypeRegistry {
     fn default() -> Self {
         Self::new()
@@ -293,4 +531,64 @@ mod tests {
         assert!(all_types.contains(&"signal.activation.resonance".to_string()));
         assert!(all_types.contains(&"signal.anomaly.novelty".to_string()));
     }
+
+    #[test]
+    fn test_namespaced_types() {
+        let mut registry = EventTypeRegistry::new();
+
+        let query = registry.register_namespaced("user", "query").unwrap();
+        let tick = registry.register_namespaced("system", "tick").unwrap();
+
+        assert_eq!(registry.get_type(query), Some("user.query"));
+        assert_eq!(EventTypeRegistry::namespace_of("system.tick"), "system");
+        assert_eq!(registry.list_namespace("system"), vec![(tick, "system.tick".to_string())]);
+        assert!(registry.namespaces().contains(&"user".to_string()));
+
+        assert!(registry.register_namespaced("User", "query").is_err());
+        assert!(registry.register_namespaced("user", "").is_err());
+    }
+
+    #[test]
+    fn test_snapshot_round_trip_keeps_ids() {
+        let mut registry = EventTypeRegistry::new();
+        let custom = registry.register("user.query");
+        let json = serde_json::to_value(registry.snapshot()).unwrap();
+
+        let snapshot: RegistrySnapshot = serde_json::from_value(json).unwrap();
+        let restored = EventTypeRegistry::from_snapshot(&snapshot).unwrap();
+
+        assert_eq!(restored.count(), registry.count());
+        assert_eq!(restored.get_id("user.query"), Some(custom));
+        assert_eq!(restored.list_all(), registry.list_all());
+
+        // Тип, зарегистрированный в другом порядке, получил бы другой ID
+        let mut fresh = EventTypeRegistry::new();
+        fresh.register("user.other");
+        assert_ne!(fresh.register("user.query"), custom);
+    }
+
+    #[test]
+    fn test_rename_migration() {
+        let mut registry = EventTypeRegistry::new();
+        let id = registry.register("user.ask");
+        let pattern_ids = registry.compile_wildcard("user.*");
+        assert_eq!(pattern_ids, vec![id]);
+
+        let migrations = vec![TypeMigration::new("user.ask", "user.query")];
+        assert_eq!(registry.apply_migrations(&migrations).unwrap(), vec![id]);
+
+        assert_eq!(registry.get_type(id), Some("user.query"));
+        assert_eq!(registry.get_id("user.ask"), Some(id));
+        assert_eq!(registry.register("user.ask"), id);
+
+        // Повторное применение — no-op, алиас переживает снимок
+        assert!(registry.apply_migrations(&migrations).unwrap().is_empty());
+        let restored = EventTypeRegistry::from_snapshot(&registry.snapshot()).unwrap();
+        assert_eq!(restored.get_id("user.ask"), Some(id));
+
+        assert!(registry.rename("user.missing", "user.other").is_err());
+        let other = registry.register("user.other");
+        assert!(registry.rename("user.query", "user.other").is_err());
+        assert_ne!(other, id);
+    }
 }
@@ -2,6 +2,8 @@ use crate::signal_system::{
     EventTypeRegistry, SignalEvent, ProcessingResult, Subscriber, SubscriberId,
     ProcessedEvent, DeliveryMeta,
 };
+use crate::signal_system::registry::{RegistrySnapshot, TypeMigration};
+use crate::persistence::{PersistenceBackend, PersistenceError};
 use crate::module_id::ModuleId;
 use crate::module_registry::REGISTRY;
 use parking_lot::RwLock;
@@ -101,6 +103,38 @@ impl SignalSystem {
         &self.event_registry
     }
 
+    /// Сохранить реестр типов через PersistenceBackend
+    pub async fn persist_event_types(
+        &self,
+        backend: &dyn PersistenceBackend,
+    ) -> Result<i32, PersistenceError> {
+        let snapshot = self.event_registry.read().snapshot();
+        snapshot.save(backend).await
+    }
+
+    /// Восстановить реестр типов и применить миграции переименований
+    ///
+    /// Вызывать до создания подписок: фильтры хранят предкомпилированные ID.
+    /// Возвращает false, если сохранённого реестра нет.
+    pub async fn restore_event_types(
+        &self,
+        backend: &dyn PersistenceBackend,
+        migrations: &[TypeMigration],
+    ) -> Result<bool, PersistenceError> {
+        let Some(snapshot) = RegistrySnapshot::load(backend).await? else {
+            return Ok(false);
+        };
+
+        let mut registry = EventTypeRegistry::from_snapshot(&snapshot)
+            .map_err(|e| PersistenceError::SerializationError(e.to_string()))?;
+        registry
+            .apply_migrations(migrations)
+            .map_err(|e| PersistenceError::SerializationError(e.to_string()))?;
+        *self.event_registry.write() = registry;
+
+        Ok(true)
+    }
+
     // ═══════════════════════════════════════════════════════════════════════════════
     // SUBSCRIPTION API
     // ═══════════════════════════════════════════════════════════════════════════════
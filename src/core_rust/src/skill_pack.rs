@@ -0,0 +1,428 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Skill packs v1.0 - shareable `.ngskill` files of learned connections
+//!
+//! A skill pack carries a filtered piece of what an instance learned, e.g.
+//! "all Learnable connections around `cooking` above confidence 0.7":
+//!
+//! - [`SkillFilter`] picks the topic cluster (labeled concepts within
+//!   `hops` connections of the seed labels, or the whole graph) and the
+//!   connections inside it that pass the mutability/confidence filter
+//! - [`SkillPack::export`] packs them, addressed by label like sync deltas
+//!   (see `sync`), and signs the payload with HMAC-SHA256 the same way
+//!   `.ngprofile` bundles are signed
+//! - [`SkillPack::load`] / [`SkillPack::from_bytes`] check the signature,
+//!   [`SkillPack::import`] merges a loaded pack into another instance
+//!   (refused while that instance is in safe mode)
+//!
+//! Imported connections become Hypothesis edges with no local evidence: they
+//! keep the exported confidence but decay unless this instance's experience
+//! confirms them. Connections the importing instance already has are left
+//! alone, missing concepts are created.
+
+use crate::connection_v3::{ConnectionMutability, ConnectionV3};
+use crate::profile_bundle::{sign_json, verify_json};
+use crate::runtime_storage::RuntimeStorage;
+use crate::safe_mode::SafeMode;
+use crate::sync::{SyncConcept, SyncConnection};
+use crate::token::Token;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+
+/// Skill pack format version
+pub const SKILL_PACK_FORMAT_VERSION: u32 = 1;
+
+/// File extension for skill packs
+pub const SKILL_PACK_EXTENSION: &str = "ngskill";
+
+/// Skill pack errors
+#[derive(Debug, thiserror::Error)]
+pub enum SkillPackError {
+    #[error("IO error: {0}")]
+    IoError(String),
+
+    #[error("Malformed skill pack: {0}")]
+    Malformed(String),
+
+    #[error("Skill pack signature does not match")]
+    InvalidSignature,
+
+    #[error("Skill pack format version {0} is newer than supported {SKILL_PACK_FORMAT_VERSION}")]
+    Incompatible(u32),
+
+    #[error("Invalid skill filter: {0}")]
+    InvalidFilter(String),
+
+    #[error("Safe mode is engaged; skill packs cannot be imported")]
+    SafeMode,
+}
+
+impl From<std::io::Error> for SkillPackError {
+    fn from(e: std::io::Error) -> Self {
+        SkillPackError::IoError(e.to_string())
+    }
+}
+
+/// Which learned connections go into a pack
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkillFilter {
+    /// Seed labels of the topic cluster (empty = whole graph)
+    pub topic: Vec<String>,
+
+    /// Cluster radius in connections around the seeds
+    pub hops: usize,
+
+    /// Only connections of this mutability (None = any)
+    pub mutability: Option<ConnectionMutability>,
+
+    /// Minimum confidence (0.0-1.0)
+    pub min_confidence: f32,
+
+    /// Upper bound on exported connections (most confident first)
+    pub max_connections: usize,
+}
+
+impl Default for SkillFilter {
+    fn default() -> Self {
+        Self {
+            topic: Vec::new(),
+            hops: 2,
+            mutability: Some(ConnectionMutability::Learnable),
+            min_confidence: 0.7,
+            max_connections: 10_000,
+        }
+    }
+}
+
+impl SkillFilter {
+    /// Cluster around `labels`
+    pub fn topic(labels: &[&str]) -> Self {
+        Self { topic: labels.iter().map(|l| l.to_string()).collect(), ..Default::default() }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.min_confidence) {
+            return Err(format!("min_confidence must be 0.0-1.0, got {}", self.min_confidence));
+        }
+        if self.max_connections == 0 {
+            return Err("max_connections must be > 0".to_string());
+        }
+        Ok(())
+    }
+
+    fn accepts(&self, connection: &ConnectionV3) -> bool {
+        let mutability_ok = self
+            .mutability
+            .is_none_or(|m| connection.mutability == m as u8);
+        mutability_ok && connection.confidence as f32 / 255.0 >= self.min_confidence
+    }
+}
+
+/// Signed contents of a `.ngskill` file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillPackPayload {
+    pub format_version: u32,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Unix seconds
+    pub created_at: u32,
+    /// Seed labels the pack was cut around (empty = whole graph)
+    #[serde(default)]
+    pub topic: Vec<String>,
+    pub concepts: Vec<SyncConcept>,
+    pub connections: Vec<SyncConnection>,
+}
+
+/// A `.ngskill` skill pack
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillPack {
+    pub payload: SkillPackPayload,
+    pub signature: String,
+}
+
+/// Outcome of [`SkillPack::import`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillImportReport {
+    pub concepts_added: usize,
+    /// New Hypothesis connections
+    pub connections_added: usize,
+    /// Connections this instance already had, left unchanged
+    pub already_known: usize,
+}
+
+impl SkillPack {
+    /// Cut a pack out of `storage` and sign it with `key`
+    pub fn export(
+        storage: &RuntimeStorage,
+        name: &str,
+        description: &str,
+        filter: &SkillFilter,
+        key: &[u8],
+    ) -> Result<Self, SkillPackError> {
+        filter.validate().map_err(SkillPackError::InvalidFilter)?;
+
+        let labels: HashMap<u32, String> = storage.labels().into_iter().collect();
+        let all_connections = storage.connections();
+        let cluster = Self::cluster(storage, &all_connections, filter);
+
+        let mut selected: Vec<(ConnectionV3, &String, &String)> = all_connections
+            .iter()
+            .filter(|(_, c)| filter.accepts(c))
+            .filter_map(|(_, c)| {
+                let (a, b) = (c.token_a_id, c.token_b_id);
+                if !cluster.as_ref().is_none_or(|ids| ids.contains(&a) && ids.contains(&b)) {
+                    return None;
+                }
+                Some((*c, labels.get(&a)?, labels.get(&b)?))
+            })
+            .collect();
+        selected.sort_by(|x, y| {
+            y.0.confidence.cmp(&x.0.confidence).then_with(|| (x.1, x.2).cmp(&(y.1, y.2)))
+        });
+        selected.truncate(filter.max_connections);
+
+        let mut endpoints = HashSet::new();
+        let connections: Vec<SyncConnection> = selected
+            .into_iter()
+            .map(|(c, from, to)| {
+                endpoints.insert(c.token_a_id);
+                endpoints.insert(c.token_b_id);
+                SyncConnection::from_connection(&c, from.clone(), to.clone())
+            })
+            .collect();
+        let mut concepts: Vec<SyncConcept> = endpoints
+            .into_iter()
+            .filter_map(|id| Some(SyncConcept::from_token(&storage.get_token(id)?, labels.get(&id)?.clone())))
+            .collect();
+        concepts.sort_by(|a, b| a.label.cmp(&b.label));
+
+        let payload = SkillPackPayload {
+            format_version: SKILL_PACK_FORMAT_VERSION,
+            name: name.to_string(),
+            description: description.to_string(),
+            created_at: Token::current_timestamp(),
+            topic: filter.topic.clone(),
+            concepts,
+            connections,
+        };
+        let signature = sign_json(&payload, key).map_err(SkillPackError::Malformed)?;
+        Ok(Self { payload, signature })
+    }
+
+    /// Token IDs of the topic cluster, None for the whole graph
+    fn cluster(
+        storage: &RuntimeStorage,
+        connections: &[(u64, ConnectionV3)],
+        filter: &SkillFilter,
+    ) -> Option<HashSet<u32>> {
+        if filter.topic.is_empty() {
+            return None;
+        }
+
+        let mut adjacency: HashMap<u32, Vec<u32>> = HashMap::new();
+        for (_, c) in connections {
+            adjacency.entry(c.token_a_id).or_default().push(c.token_b_id);
+            adjacency.entry(c.token_b_id).or_default().push(c.token_a_id);
+        }
+
+        let mut cluster = HashSet::new();
+        let mut queue: VecDeque<(u32, usize)> = filter
+            .topic
+            .iter()
+            .filter_map(|label| storage.token_by_label(label))
+            .map(|id| (id, 0))
+            .collect();
+        while let Some((id, depth)) = queue.pop_front() {
+            if !cluster.insert(id) || depth == filter.hops {
+                continue;
+            }
+            for &neighbor in adjacency.get(&id).into_iter().flatten() {
+                queue.push_back((neighbor, depth + 1));
+            }
+        }
+        Some(cluster)
+    }
+
+    /// Parse a pack and verify its signature
+    pub fn from_bytes(data: &[u8], key: &[u8]) -> Result<Self, SkillPackError> {
+        let pack: SkillPack =
+            serde_json::from_slice(data).map_err(|e| SkillPackError::Malformed(e.to_string()))?;
+        pack.verify(key)?;
+        Ok(pack)
+    }
+
+    /// Serialize to the on-disk format
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SkillPackError> {
+        serde_json::to_vec_pretty(self).map_err(|e| SkillPackError::Malformed(e.to_string()))
+    }
+
+    /// Read and verify a pack from a file
+    pub fn load(path: &Path, key: &[u8]) -> Result<Self, SkillPackError> {
+        Self::from_bytes(&std::fs::read(path)?, key)
+    }
+
+    /// Write the pack to a file
+    pub fn save(&self, path: &Path) -> Result<(), SkillPackError> {
+        std::fs::write(path, self.to_bytes()?)?;
+        Ok(())
+    }
+
+    /// Check the signature against `key`
+    pub fn verify(&self, key: &[u8]) -> Result<(), SkillPackError> {
+        if verify_json(&self.payload, &self.signature, key) {
+            Ok(())
+        } else {
+            Err(SkillPackError::InvalidSignature)
+        }
+    }
+
+    /// Add the pack's knowledge to `storage` as Hypothesis connections
+    ///
+    /// `safe_mode` is the importing instance's switch.
+    pub fn import(&self, storage: &RuntimeStorage, safe_mode: &SafeMode) -> Result<SkillImportReport, SkillPackError> {
+        if safe_mode.is_engaged() {
+            return Err(SkillPackError::SafeMode);
+        }
+        if self.payload.format_version > SKILL_PACK_FORMAT_VERSION {
+            return Err(SkillPackError::Incompatible(self.payload.format_version));
+        }
+
+        let mut report = SkillImportReport::default();
+        for concept in &self.payload.concepts {
+            if storage.token_by_label(&concept.label).is_none() {
+                let id = storage.create_token(concept.to_token());
+                storage.set_label(id, &concept.label);
+                report.concepts_added += 1;
+            }
+        }
+
+        let mut known: HashSet<(u32, u32, u8)> = storage
+            .connections()
+            .into_iter()
+            .map(|(_, c)| (c.token_a_id, c.token_b_id, c.connection_type))
+            .collect();
+        for remote in &self.payload.connections {
+            let (Some(a), Some(b)) = (storage.token_by_label(&remote.from), storage.token_by_label(&remote.to)) else {
+                return Err(SkillPackError::Malformed(format!(
+                    "connection {} - {} names a concept the pack does not contain",
+                    remote.from, remote.to
+                )));
+            };
+            let mut connection = ConnectionV3::new(a, b);
+            connection.connection_type = remote.connection_type;
+            if !known.insert((connection.token_a_id, connection.token_b_id, connection.connection_type)) {
+                report.already_known += 1;
+                continue;
+            }
+
+            connection.mutability = ConnectionMutability::Hypothesis as u8;
+            connection.confidence = remote.confidence;
+            connection.active_levels = remote.active_levels;
+            connection.pull_strength = remote.pull_strength;
+            connection.preferred_distance = remote.preferred_distance;
+            connection.learning_rate = 128; // Fast learning for hypothesis
+            connection.decay_rate = 32; // Moderate decay
+            storage.create_connection(connection);
+            report.connections_added += 1;
+        }
+
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &[u8] = b"shared-secret";
+
+    fn learned(storage: &RuntimeStorage, a: &str, b: &str, confidence: u8, mutability: ConnectionMutability) {
+        let id_of = |label: &str| {
+            storage.token_by_label(label).unwrap_or_else(|| {
+                let id = storage.create_token(Token::new(0));
+                storage.set_label(id, label);
+                id
+            })
+        };
+        let mut connection = ConnectionV3::new(id_of(a), id_of(b));
+        connection.confidence = confidence;
+        connection.evidence_count = 12;
+        connection.mutability = mutability as u8;
+        storage.create_connection(connection);
+    }
+
+    fn cooking_instance() -> RuntimeStorage {
+        let storage = RuntimeStorage::new();
+        learned(&storage, "cooking", "pan", 230, ConnectionMutability::Learnable);
+        learned(&storage, "pan", "oil", 200, ConnectionMutability::Learnable);
+        learned(&storage, "oil", "engine", 220, ConnectionMutability::Learnable);
+        learned(&storage, "cooking", "salt", 100, ConnectionMutability::Learnable);
+        learned(&storage, "cooking", "food", 255, ConnectionMutability::Immutable);
+        storage
+    }
+
+    #[test]
+    fn test_export_filters_topic_cluster() {
+        let storage = cooking_instance();
+        let pack = SkillPack::export(&storage, "cooking", "", &SkillFilter::topic(&["cooking"]), KEY).unwrap();
+
+        // engine is 3 hops away, salt below 0.7, food Immutable
+        let pairs: Vec<(&str, &str)> = pack.payload.connections.iter()
+            .map(|c| (c.from.as_str(), c.to.as_str()))
+            .collect();
+        assert_eq!(pairs, vec![("cooking", "pan"), ("pan", "oil")]);
+        let labels: Vec<&str> = pack.payload.concepts.iter().map(|c| c.label.as_str()).collect();
+        assert_eq!(labels, vec!["cooking", "oil", "pan"]);
+
+        let everything = SkillFilter { mutability: None, min_confidence: 0.0, ..Default::default() };
+        assert_eq!(SkillPack::export(&storage, "all", "", &everything, KEY).unwrap().payload.connections.len(), 5);
+        let invalid = SkillFilter { min_confidence: 2.0, ..Default::default() };
+        assert!(SkillPack::export(&storage, "x", "", &invalid, KEY).is_err());
+    }
+
+    #[test]
+    fn test_import_as_hypotheses() {
+        let pack = SkillPack::export(&cooking_instance(), "cooking", "", &SkillFilter::topic(&["cooking"]), KEY)
+            .unwrap();
+        let bytes = pack.to_bytes().unwrap();
+        assert!(matches!(SkillPack::from_bytes(&bytes, b"other-key"), Err(SkillPackError::InvalidSignature)));
+        let mut tampered = pack.clone();
+        tampered.payload.connections[0].confidence = 255;
+        assert!(tampered.verify(KEY).is_err());
+
+        let target = RuntimeStorage::new();
+        learned(&target, "pan", "oil", 50, ConnectionMutability::Learnable);
+        let safe_mode = SafeMode::new();
+        let loaded = SkillPack::from_bytes(&bytes, KEY).unwrap();
+        safe_mode.engage(Some("test"));
+        assert!(matches!(loaded.import(&target, &safe_mode), Err(SkillPackError::SafeMode)));
+        assert!(target.token_by_label("cooking").is_none());
+        safe_mode.release();
+        let report = loaded.import(&target, &safe_mode).unwrap();
+        assert_eq!(report, SkillImportReport { concepts_added: 1, connections_added: 1, already_known: 1 });
+
+        let cooking = target.token_by_label("cooking").unwrap();
+        let (_, imported) = target.connections().into_iter()
+            .find(|(_, c)| c.token_a_id == cooking || c.token_b_id == cooking)
+            .unwrap();
+        let (mutability, confidence, evidence) = (imported.mutability, imported.confidence, imported.evidence_count);
+        assert_eq!(mutability, ConnectionMutability::Hypothesis as u8);
+        assert_eq!((confidence, evidence), (230, 0));
+    }
+}
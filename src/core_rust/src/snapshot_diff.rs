@@ -0,0 +1,438 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Snapshot Diff v1.0 - Compare two runtime snapshots
+//!
+//! Regression check for refactors and new learning rules: run the same
+//! workload before and after the change, capture both states
+//! (`RuntimeStorage::export_snapshot` or `CheckpointManager::runtime_snapshot`)
+//! and compare them with [`SnapshotDiff::compute`]:
+//!
+//! - **Tokens / graph nodes** - IDs added and removed
+//! - **Grid** - coordinate drift of tokens present in both, as a histogram
+//!   plus the largest movers
+//! - **Graph edges / connections** - added, removed and confidence deltas
+//!   (edge weight, connection confidence scaled to 0.0-1.0)
+//!
+//! The diff serializes to JSON; [`SnapshotDiff::is_unchanged`] is the
+//! one-line assertion for tests.
+
+use crate::connection_v3::active_levels;
+use crate::runtime_storage::RuntimeSnapshot;
+use crate::token::{CoordinateSpace, Token};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Snapshot diff configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SnapshotDiffConfig {
+    /// Coordinate spaces compared for drift (`connection_v3::active_levels` bits);
+    /// a token's drift is its largest move in any of them
+    pub levels: u8,
+
+    /// Upper bounds of the drift histogram buckets (one more open bucket follows)
+    pub drift_buckets: Vec<f32>,
+
+    /// Upper bounds of the confidence delta histogram buckets
+    pub delta_buckets: Vec<f32>,
+
+    /// Drift up to this does not count as a move
+    pub drift_tolerance: f32,
+
+    /// Confidence changes up to this do not count as a change
+    pub confidence_tolerance: f32,
+
+    /// IDs and changes listed individually per category (largest first)
+    pub max_listed: usize,
+}
+
+impl Default for SnapshotDiffConfig {
+    fn default() -> Self {
+        Self {
+            levels: active_levels::ALL,
+            drift_buckets: vec![0.01, 0.1, 0.5, 1.0, 5.0],
+            delta_buckets: vec![-0.5, -0.1, -0.01, 0.01, 0.1, 0.5],
+            drift_tolerance: 0.0,
+            confidence_tolerance: 0.0,
+            max_listed: 20,
+        }
+    }
+}
+
+impl SnapshotDiffConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.levels == 0 {
+            return Err("levels must select at least one coordinate space".to_string());
+        }
+        for (name, bounds) in [("drift_buckets", &self.drift_buckets), ("delta_buckets", &self.delta_buckets)] {
+            if bounds.iter().any(|b| !b.is_finite()) || bounds.windows(2).any(|w| w[0] >= w[1]) {
+                return Err(format!("{} must be finite and strictly ascending", name));
+            }
+        }
+        if !(self.drift_tolerance >= 0.0 && self.confidence_tolerance >= 0.0) {
+            return Err("tolerances must be >= 0.0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Bucketed counts; `counts[i]` holds values up to `bounds[i]`, the last
+/// count everything above the last bound
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Histogram {
+    pub bounds: Vec<f32>,
+    pub counts: Vec<u64>,
+}
+
+impl Histogram {
+    fn new(bounds: &[f32]) -> Self {
+        Self {
+            bounds: bounds.to_vec(),
+            counts: vec![0; bounds.len() + 1],
+        }
+    }
+
+    fn record(&mut self, value: f32) {
+        let bucket = self.bounds.iter().position(|&b| value <= b).unwrap_or(self.bounds.len());
+        self.counts[bucket] += 1;
+    }
+}
+
+/// IDs present in only one snapshot
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IdSetDiff {
+    pub before: usize,
+    pub after: usize,
+    pub added: usize,
+    pub removed: usize,
+    /// Lowest added IDs, up to `max_listed`
+    pub added_ids: Vec<u64>,
+    /// Lowest removed IDs, up to `max_listed`
+    pub removed_ids: Vec<u64>,
+}
+
+impl IdSetDiff {
+    fn compute(before: &BTreeSet<u64>, after: &BTreeSet<u64>, max_listed: usize) -> Self {
+        let added: Vec<u64> = after.difference(before).copied().collect();
+        let removed: Vec<u64> = before.difference(after).copied().collect();
+        Self {
+            before: before.len(),
+            after: after.len(),
+            added: added.len(),
+            removed: removed.len(),
+            added_ids: added.into_iter().take(max_listed).collect(),
+            removed_ids: removed.into_iter().take(max_listed).collect(),
+        }
+    }
+
+    pub fn is_unchanged(&self) -> bool {
+        self.added == 0 && self.removed == 0
+    }
+}
+
+/// A token that moved
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TokenDrift {
+    pub token_id: u32,
+    /// Largest move in the compared spaces
+    pub distance: f32,
+    pub space: u8,
+}
+
+/// Grid token changes
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GridDiff {
+    pub tokens: IdSetDiff,
+    /// Tokens present in both snapshots
+    pub compared: usize,
+    /// Compared tokens that drifted more than `drift_tolerance`
+    pub moved: usize,
+    pub max_drift: f32,
+    pub mean_drift: f32,
+    /// Drift of all compared tokens
+    pub drift: Histogram,
+    /// Largest drifts, up to `max_listed`
+    pub largest: Vec<TokenDrift>,
+}
+
+/// A confidence that changed
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ConfidenceChange {
+    pub id: u64,
+    pub from_id: u32,
+    pub to_id: u32,
+    pub before: f32,
+    pub after: f32,
+}
+
+impl ConfidenceChange {
+    pub fn delta(&self) -> f32 {
+        self.after - self.before
+    }
+}
+
+/// Edge or connection changes
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConfidenceDiff {
+    pub ids: IdSetDiff,
+    /// Present in both snapshots
+    pub compared: usize,
+    /// Compared entries whose confidence moved more than `confidence_tolerance`
+    pub changed: usize,
+    pub mean_delta: f32,
+    pub max_abs_delta: f32,
+    /// Signed confidence deltas of all compared entries
+    pub deltas: Histogram,
+    /// Largest absolute changes, up to `max_listed`
+    pub largest: Vec<ConfidenceChange>,
+}
+
+impl ConfidenceDiff {
+    /// `before` / `after`: id → (from, to, confidence)
+    fn compute(
+        before: &BTreeMap<u64, (u32, u32, f32)>,
+        after: &BTreeMap<u64, (u32, u32, f32)>,
+        config: &SnapshotDiffConfig,
+    ) -> Self {
+        let ids = |m: &BTreeMap<u64, (u32, u32, f32)>| m.keys().copied().collect::<BTreeSet<u64>>();
+        let mut diff = Self {
+            ids: IdSetDiff::compute(&ids(before), &ids(after), config.max_listed),
+            deltas: Histogram::new(&config.delta_buckets),
+            ..Default::default()
+        };
+
+        let mut changes = Vec::new();
+        let mut total = 0.0f64;
+        for (&id, &(from_id, to_id, old)) in before {
+            let Some(&(_, _, new)) = after.get(&id) else {
+                continue;
+            };
+            let change = ConfidenceChange { id, from_id, to_id, before: old, after: new };
+            let delta = change.delta();
+            diff.compared += 1;
+            diff.deltas.record(delta);
+            total += delta as f64;
+            diff.max_abs_delta = diff.max_abs_delta.max(delta.abs());
+            if delta.abs() > config.confidence_tolerance {
+                diff.changed += 1;
+                changes.push(change);
+            }
+        }
+        if diff.compared > 0 {
+            diff.mean_delta = (total / diff.compared as f64) as f32;
+        }
+        changes.sort_by(|a, b| b.delta().abs().total_cmp(&a.delta().abs()).then(a.id.cmp(&b.id)));
+        changes.truncate(config.max_listed);
+        diff.largest = changes;
+        diff
+    }
+
+    pub fn is_unchanged(&self) -> bool {
+        self.ids.is_unchanged() && self.changed == 0
+    }
+}
+
+/// Machine-readable difference between two runtime snapshots
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotDiff {
+    /// Runtime tokens
+    pub tokens: IdSetDiff,
+    pub grid: GridDiff,
+    pub graph_nodes: IdSetDiff,
+    /// Graph edges, weight as confidence
+    pub graph_edges: ConfidenceDiff,
+    /// Runtime connections, confidence / 255
+    pub connections: ConfidenceDiff,
+}
+
+impl SnapshotDiff {
+    /// Compare `after` against `before`
+    pub fn compute(
+        before: &RuntimeSnapshot,
+        after: &RuntimeSnapshot,
+        config: &SnapshotDiffConfig,
+    ) -> Result<Self, String> {
+        config.validate()?;
+        let token_ids = |tokens: &[Token]| tokens.iter().map(|t| t.id as u64).collect::<BTreeSet<u64>>();
+        let node_ids = |nodes: &[u32]| nodes.iter().map(|&id| id as u64).collect::<BTreeSet<u64>>();
+        let edges = |s: &RuntimeSnapshot| {
+            s.graph_edges
+                .iter()
+                .map(|(id, e)| (*id, (e.from_id, e.to_id, e.weight)))
+                .collect::<BTreeMap<_, _>>()
+        };
+        let connections = |s: &RuntimeSnapshot| {
+            s.connections
+                .iter()
+                .map(|(id, c)| (*id, (c.token_a_id, c.token_b_id, c.confidence as f32 / 255.0)))
+                .collect::<BTreeMap<_, _>>()
+        };
+
+        Ok(Self {
+            tokens: IdSetDiff::compute(&token_ids(&before.tokens), &token_ids(&after.tokens), config.max_listed),
+            grid: grid_diff(&before.grid_tokens, &after.grid_tokens, config),
+            graph_nodes: IdSetDiff::compute(
+                &node_ids(&before.graph_nodes),
+                &node_ids(&after.graph_nodes),
+                config.max_listed,
+            ),
+            graph_edges: ConfidenceDiff::compute(&edges(before), &edges(after), config),
+            connections: ConfidenceDiff::compute(&connections(before), &connections(after), config),
+        })
+    }
+
+    /// Nothing added, removed, moved or changed beyond the tolerances
+    pub fn is_unchanged(&self) -> bool {
+        self.tokens.is_unchanged()
+            && self.grid.tokens.is_unchanged()
+            && self.grid.moved == 0
+            && self.graph_nodes.is_unchanged()
+            && self.graph_edges.is_unchanged()
+            && self.connections.is_unchanged()
+    }
+}
+
+fn grid_diff(before: &[Token], after: &[Token], config: &SnapshotDiffConfig) -> GridDiff {
+    let by_id = |tokens: &[Token]| tokens.iter().map(|t| (t.id, *t)).collect::<BTreeMap<u32, Token>>();
+    let (before, after) = (by_id(before), by_id(after));
+    let ids = |m: &BTreeMap<u32, Token>| m.keys().map(|&id| id as u64).collect::<BTreeSet<u64>>();
+    let spaces: Vec<CoordinateSpace> = CoordinateSpace::ALL
+        .into_iter()
+        .filter(|&space| config.levels & (1 << space as u8) != 0)
+        .collect();
+
+    let mut diff = GridDiff {
+        tokens: IdSetDiff::compute(&ids(&before), &ids(&after), config.max_listed),
+        drift: Histogram::new(&config.drift_buckets),
+        ..Default::default()
+    };
+    let mut drifts = Vec::new();
+    let mut total = 0.0f64;
+    for (&token_id, old) in &before {
+        let Some(new) = after.get(&token_id) else {
+            continue;
+        };
+        let drift = spaces
+            .iter()
+            .map(|&space| TokenDrift {
+                token_id,
+                distance: distance(old.get_coordinates(space), new.get_coordinates(space)),
+                space: space as u8,
+            })
+            .max_by(|a, b| a.distance.total_cmp(&b.distance))
+            .expect("at least one space");
+        diff.compared += 1;
+        diff.drift.record(drift.distance);
+        total += drift.distance as f64;
+        diff.max_drift = diff.max_drift.max(drift.distance);
+        if drift.distance > config.drift_tolerance {
+            diff.moved += 1;
+            drifts.push(drift);
+        }
+    }
+    if diff.compared > 0 {
+        diff.mean_drift = (total / diff.compared as f64) as f32;
+    }
+    drifts.sort_by(|a, b| b.distance.total_cmp(&a.distance).then(a.token_id.cmp(&b.token_id)));
+    drifts.truncate(config.max_listed);
+    diff.largest = drifts;
+    diff
+}
+
+fn distance(a: [f32; 3], b: [f32; 3]) -> f32 {
+    a.iter().zip(&b).map(|(x, y)| (x - y) * (x - y)).sum::<f32>().sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection_v3::ConnectionV3;
+    use crate::graph::{EdgeInfo, Graph};
+    use crate::runtime_storage::RuntimeStorage;
+
+    fn token(id: u32, x: f32) -> Token {
+        let mut token = Token::new(id);
+        token.set_coordinates(CoordinateSpace::L1Physical, x, 0.0, 0.0);
+        token
+    }
+
+    fn storage() -> RuntimeStorage {
+        let storage = RuntimeStorage::new();
+        for x in [1.0, 2.0, 3.0] {
+            storage.create_token(token(0, x));
+        }
+        let mut connection = ConnectionV3::new(1, 2);
+        connection.confidence = 102;
+        storage.create_connection(connection);
+        storage
+    }
+
+    #[test]
+    fn test_identical_snapshots() {
+        let snapshot = storage().export_snapshot();
+        let diff = SnapshotDiff::compute(&snapshot, &snapshot, &SnapshotDiffConfig::default()).unwrap();
+        assert!(diff.is_unchanged());
+        assert_eq!(diff.grid.compared, 3);
+        assert_eq!(diff.grid.drift.counts[0], 3);
+        assert_eq!(diff.connections.compared, 1);
+    }
+
+    #[test]
+    fn test_changes_are_reported() {
+        let storage = storage();
+        let before = storage.export_snapshot();
+
+        storage.update_token(2, token(2, 2.5)).unwrap();
+        storage.create_token(token(0, 9.0));
+        storage.delete_token(3);
+        let (id, mut connection) = storage.connections().into_iter().next().unwrap();
+        connection.confidence = 204;
+        storage.update_connection(id, connection).unwrap();
+        let mut after = storage.export_snapshot();
+        let edge = EdgeInfo {
+            from_id: 1,
+            to_id: 2,
+            edge_type: 0x50,
+            weight: 0.4,
+            bidirectional: true,
+            active_levels: active_levels::ALL,
+        };
+        after.graph_edges.push((Graph::compute_edge_id(1, 2, 0x50), edge));
+
+        let diff = SnapshotDiff::compute(&before, &after, &SnapshotDiffConfig::default()).unwrap();
+        assert!(!diff.is_unchanged());
+        assert_eq!((diff.tokens.added_ids.clone(), diff.tokens.removed_ids.clone()), (vec![4], vec![3]));
+        assert_eq!(diff.grid.moved, 1);
+        assert_eq!(diff.grid.largest[0].token_id, 2);
+        assert!((diff.grid.largest[0].distance - 0.5).abs() < 0.01);
+        assert_eq!(diff.connections.changed, 1);
+        assert!((diff.connections.largest[0].delta() - 0.4).abs() < 1e-6);
+        assert_eq!(diff.connections.deltas.counts[5], 1);
+        assert_eq!((diff.graph_edges.ids.added, diff.graph_edges.compared), (1, 0));
+        assert_eq!(diff.graph_nodes.removed_ids, vec![3]);
+
+        let json = serde_json::to_value(&diff).unwrap();
+        assert_eq!(json["grid"]["moved"], 1);
+
+        let relaxed = SnapshotDiffConfig { drift_tolerance: 1.0, confidence_tolerance: 0.5, ..Default::default() };
+        let diff = SnapshotDiff::compute(&before, &after, &relaxed).unwrap();
+        assert_eq!((diff.grid.moved, diff.connections.changed), (0, 0));
+
+        let bad = SnapshotDiffConfig { drift_buckets: vec![1.0, 0.5], ..Default::default() };
+        assert!(SnapshotDiff::compute(&before, &after, &bad).is_err());
+    }
+}
@@ -0,0 +1,665 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Task Supervisor v1.0 - Automatic restart of long-running subsystems
+//!
+//! `panic_handler` turns a panic into an error; the supervisor goes further
+//! for background tasks (Gateway consumer, autonomous explorer):
+//!
+//! - records panic context in tracing and the Black Box
+//! - increments `neurograph_subsystem_panics_total{subsystem=...}`
+//! - restarts the task with exponential backoff
+//! - opens a circuit breaker after too many panics within a window
+//!
+//! A task that returns normally is not restarted. Individual tasks can be
+//! stopped and started again by name, which backs the managed-module
+//! lifecycle in `module_registry`.
+
+use crate::action_controller::ActionController;
+use crate::curiosity::AutonomousExplorer;
+use crate::gateway::signals::ProcessedSignal;
+use crate::module_registry::{LifecycleState, ManagedModule, ModuleHealth};
+use async_trait::async_trait;
+use dashmap::DashMap;
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::{AbortHandle, JoinHandle};
+use tracing::{error, info, warn};
+
+/// Subsystem name of the Gateway consumer task
+pub const GATEWAY_CONSUMER: &str = "gateway_consumer";
+
+/// Subsystem name of the autonomous explorer task
+pub const AUTONOMOUS_EXPLORER: &str = "autonomous_explorer";
+
+// ============================================================================
+// Configuration
+// ============================================================================
+
+/// Restart policy
+#[derive(Debug, Clone)]
+pub struct SupervisorConfig {
+    /// Delay before the first restart
+    pub initial_backoff: Duration,
+
+    /// Upper bound for the restart delay
+    pub max_backoff: Duration,
+
+    /// Delay multiplier per consecutive panic
+    pub backoff_multiplier: f64,
+
+    /// Panics within `window` that open the circuit breaker
+    pub max_panics: usize,
+
+    /// Sliding window for the circuit breaker
+    pub window: Duration,
+
+    /// Runtime after which the backoff resets
+    pub stable_after: Duration,
+}
+
+impl Default for SupervisorConfig {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(30),
+            backoff_multiplier: 2.0,
+            max_panics: 5,
+            window: Duration::from_secs(60),
+            stable_after: Duration::from_secs(30),
+        }
+    }
+}
+
+impl SupervisorConfig {
+    /// Validate configuration
+    pub fn validate(&self) -> Result<(), String> {
+        if self.backoff_multiplier < 1.0 {
+            return Err("backoff_multiplier must be >= 1.0".to_string());
+        }
+        if self.initial_backoff > self.max_backoff {
+            return Err("initial_backoff must not exceed max_backoff".to_string());
+        }
+        if self.max_panics == 0 {
+            return Err("max_panics must be > 0".to_string());
+        }
+        Ok(())
+    }
+
+    /// Restart delay after `consecutive` panics in a row (1-based)
+    pub fn backoff(&self, consecutive: u32) -> Duration {
+        let factor = self.backoff_multiplier.powi(consecutive.saturating_sub(1) as i32);
+        self.initial_backoff.mul_f64(factor).min(self.max_backoff)
+    }
+}
+
+// ============================================================================
+// Task Status
+// ============================================================================
+
+/// Lifecycle state of a supervised task
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TaskState {
+    Running,
+    /// Waiting to restart after a panic
+    Backoff,
+    /// Returned normally or supervisor shut down
+    Stopped,
+    /// Too many panics; not restarted
+    CircuitOpen,
+}
+
+/// Snapshot of a supervised task
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskStatus {
+    pub name: String,
+    pub state: TaskState,
+
+    /// Total panics since supervision started
+    pub panics: u64,
+
+    /// Total restarts
+    pub restarts: u64,
+
+    /// Message of the most recent panic
+    pub last_panic: Option<String>,
+}
+
+impl TaskStatus {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            state: TaskState::Running,
+            panics: 0,
+            restarts: 0,
+            last_panic: None,
+        }
+    }
+}
+
+// ============================================================================
+// Supervisor
+// ============================================================================
+
+/// Builds a fresh instance of a supervised task
+type TaskFactory = Arc<dyn Fn() -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// Restarts panicking background tasks
+pub struct Supervisor {
+    config: SupervisorConfig,
+    tasks: Arc<DashMap<String, TaskStatus>>,
+    /// Factories kept for `start` after `stop`
+    factories: DashMap<String, TaskFactory>,
+    /// Abort handles of the supervision loops
+    loops: DashMap<String, AbortHandle>,
+    /// Abort handles of the currently running task instances
+    running: Arc<DashMap<String, AbortHandle>>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl Supervisor {
+    pub fn new(config: SupervisorConfig) -> Self {
+        Self {
+            config,
+            tasks: Arc::new(DashMap::new()),
+            factories: DashMap::new(),
+            loops: DashMap::new(),
+            running: Arc::new(DashMap::new()),
+            shutdown: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Supervise a task built by `factory`
+    ///
+    /// `factory` is called again for every restart, so it must rebuild the
+    /// task from shared state. The returned handle completes when the task
+    /// stops for good (normal return, circuit open or shutdown).
+    pub fn supervise<F, Fut>(&self, name: &str, factory: F) -> JoinHandle<TaskState>
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let factory: TaskFactory = Arc::new(move || Box::pin(factory()));
+        self.stop(name);
+        self.factories.insert(name.to_string(), factory.clone());
+        self.tasks.insert(name.to_string(), TaskStatus::new(name));
+        self.spawn_loop(name, factory)
+    }
+
+    /// Start a previously stopped task again
+    ///
+    /// Returns `None` if the task is unknown, still running or the supervisor
+    /// has been shut down. Panic and restart counters are kept.
+    pub fn start(&self, name: &str) -> Option<JoinHandle<TaskState>> {
+        if self.shutdown.load(Ordering::SeqCst) || self.is_active(name) {
+            return None;
+        }
+        let factory = self.factories.get(name)?.clone();
+        set_state(&self.tasks, name, TaskState::Running);
+        Some(self.spawn_loop(name, factory))
+    }
+
+    /// Stop one task without restarting it
+    ///
+    /// The handle returned by `supervise`/`start` for this run is cancelled.
+    /// Returns `false` if the task was not running.
+    pub fn stop(&self, name: &str) -> bool {
+        let Some((_, supervision)) = self.loops.remove(name) else {
+            return false;
+        };
+        let was_active = !supervision.is_finished();
+        supervision.abort();
+        if let Some((_, task)) = self.running.remove(name) {
+            task.abort();
+        }
+        if was_active {
+            set_state(&self.tasks, name, TaskState::Stopped);
+            info!(subsystem = %name, "Supervised task stopped");
+        }
+        was_active
+    }
+
+    /// Whether the supervision loop of a task is still alive
+    pub fn is_active(&self, name: &str) -> bool {
+        self.loops.get(name).is_some_and(|h| !h.is_finished())
+    }
+
+    fn spawn_loop(&self, name: &str, factory: TaskFactory) -> JoinHandle<TaskState> {
+        let name = name.to_string();
+        let config = self.config.clone();
+        let tasks = self.tasks.clone();
+        let running = self.running.clone();
+        let shutdown = self.shutdown.clone();
+        let loop_name = name.clone();
+
+        let handle = tokio::spawn(async move {
+            let mut recent_panics: VecDeque<Instant> = VecDeque::new();
+            let mut consecutive = 0u32;
+
+            let final_state = loop {
+                if shutdown.load(Ordering::SeqCst) {
+                    break TaskState::Stopped;
+                }
+
+                set_state(&tasks, &name, TaskState::Running);
+                let started = Instant::now();
+                let handle = tokio::spawn(factory());
+                running.insert(name.clone(), handle.abort_handle());
+                if shutdown.load(Ordering::SeqCst) {
+                    handle.abort();
+                }
+                let result = handle.await;
+                running.remove(&name);
+
+                let payload = match result {
+                    Ok(()) => break TaskState::Stopped,
+                    Err(e) if e.is_cancelled() => break TaskState::Stopped,
+                    Err(e) => e.into_panic(),
+                };
+                let message = crate::panic_handler::extract_panic_message(&payload);
+
+                // Telemetry
+                let panics = record_panic(&tasks, &name, &message);
+                error!(
+                    subsystem = %name,
+                    panic_message = %message,
+                    panics,
+                    uptime_ms = started.elapsed().as_millis() as u64,
+                    "Supervised task panicked"
+                );
+                crate::metrics::SUBSYSTEM_PANICS.with_label_values(&[name.as_str()]).inc();
+                crate::metrics::PANICS_RECOVERED.inc();
+                crate::black_box::record_event(
+                    crate::black_box::Event::new(crate::black_box::EventType::PanicRecovered)
+                        .with_data("subsystem", name.clone())
+                        .with_data("message", message.clone()),
+                );
+
+                // Circuit breaker
+                let now = Instant::now();
+                recent_panics.push_back(now);
+                while recent_panics
+                    .front()
+                    .is_some_and(|t| now.duration_since(*t) > config.window)
+                {
+                    recent_panics.pop_front();
+                }
+                if recent_panics.len() >= config.max_panics {
+                    error!(
+                        subsystem = %name,
+                        panics_in_window = recent_panics.len(),
+                        "Circuit breaker open, task will not be restarted"
+                    );
+                    break TaskState::CircuitOpen;
+                }
+
+                // Backoff
+                consecutive = if started.elapsed() >= config.stable_after { 1 } else { consecutive + 1 };
+                let delay = config.backoff(consecutive);
+                set_state(&tasks, &name, TaskState::Backoff);
+                warn!(subsystem = %name, delay_ms = delay.as_millis() as u64, "Restarting supervised task");
+                tokio::time::sleep(delay).await;
+
+                if let Some(mut status) = tasks.get_mut(&name) {
+                    status.restarts += 1;
+                }
+                crate::metrics::SUBSYSTEM_RESTARTS.with_label_values(&[name.as_str()]).inc();
+            };
+
+            set_state(&tasks, &name, final_state);
+            info!(subsystem = %name, state = ?final_state, "Supervised task finished");
+            final_state
+        });
+        self.loops.insert(loop_name, handle.abort_handle());
+        handle
+    }
+
+    /// Supervise a loop that drains `receiver` through `handler`
+    ///
+    /// The receiver is shared across restarts, so messages queued while the
+    /// consumer restarts are not lost. The message being handled when the
+    /// panic happened is dropped. The task ends when all senders are gone.
+    pub fn supervise_consumer<T, F, Fut>(
+        &self,
+        name: &str,
+        receiver: mpsc::Receiver<T>,
+        handler: F,
+    ) -> JoinHandle<TaskState>
+    where
+        T: Send + 'static,
+        F: Fn(T) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let receiver = Arc::new(Mutex::new(receiver));
+        let handler = Arc::new(handler);
+        self.supervise(name, move || {
+            let receiver = receiver.clone();
+            let handler = handler.clone();
+            async move {
+                loop {
+                    let message = receiver.lock().await.recv().await;
+                    match message {
+                        Some(message) => handler(message).await,
+                        None => break,
+                    }
+                }
+            }
+        })
+    }
+
+    /// Supervise the Gateway → ActionController consumer loop
+    ///
+    /// See [`supervise_consumer`](Self::supervise_consumer) for what survives a restart.
+    pub fn supervise_gateway_consumer(
+        &self,
+        receiver: mpsc::Receiver<ProcessedSignal>,
+        controller: Arc<ActionController>,
+    ) -> JoinHandle<TaskState> {
+        self.supervise_consumer(GATEWAY_CONSUMER, receiver, move |signal| {
+            let controller = controller.clone();
+            async move { controller.process_signal(signal).await }
+        })
+    }
+
+    /// Supervise the autonomous exploration loop
+    pub fn supervise_explorer(
+        &self,
+        explorer: Arc<AutonomousExplorer>,
+        controller: Arc<ActionController>,
+    ) -> JoinHandle<TaskState> {
+        self.supervise(AUTONOMOUS_EXPLORER, move || {
+            let explorer = explorer.clone();
+            let controller = controller.clone();
+            async move { explorer.start(controller).await }
+        })
+    }
+
+    /// Status of one task
+    pub fn status(&self, name: &str) -> Option<TaskStatus> {
+        self.tasks.get(name).map(|s| s.clone())
+    }
+
+    /// Status of all tasks
+    pub fn statuses(&self) -> Vec<TaskStatus> {
+        let mut statuses: Vec<_> = self.tasks.iter().map(|s| s.clone()).collect();
+        statuses.sort_by(|a, b| a.name.cmp(&b.name));
+        statuses
+    }
+
+    /// Stop restarting and abort all running tasks
+    pub fn shutdown(&self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        for entry in self.running.iter() {
+            entry.value().abort();
+        }
+    }
+}
+
+impl Default for Supervisor {
+    fn default() -> Self {
+        Self::new(SupervisorConfig::default())
+    }
+}
+
+// ============================================================================
+// Managed Module
+// ============================================================================
+
+/// Lifecycle handle of a supervised task for the module registry
+///
+/// ```ignore
+/// let supervisor = Arc::new(Supervisor::default());
+/// supervisor.supervise_explorer(explorer, controller);
+/// REGISTRY.register_managed(
+///     AUTONOMOUS_EXPLORER,
+///     Arc::new(SupervisedModule::new(supervisor.clone(), AUTONOMOUS_EXPLORER)),
+/// );
+/// ```
+pub struct SupervisedModule {
+    supervisor: Arc<Supervisor>,
+    name: String,
+    description: String,
+}
+
+impl SupervisedModule {
+    pub fn new(supervisor: Arc<Supervisor>, name: &str) -> Self {
+        let description = match name {
+            GATEWAY_CONSUMER => "Gateway → ActionController signal consumer",
+            AUTONOMOUS_EXPLORER => "Autonomous curiosity-driven exploration loop",
+            _ => "Supervised background task",
+        };
+        Self {
+            supervisor,
+            name: name.to_string(),
+            description: description.to_string(),
+        }
+    }
+
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+}
+
+#[async_trait]
+impl ManagedModule for SupervisedModule {
+    fn description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn state(&self) -> LifecycleState {
+        match self.supervisor.status(&self.name).map(|s| s.state) {
+            Some(TaskState::Running) => LifecycleState::Running,
+            Some(TaskState::Backoff) => LifecycleState::Restarting,
+            Some(TaskState::CircuitOpen) => LifecycleState::Failed,
+            Some(TaskState::Stopped) | None => LifecycleState::Stopped,
+        }
+    }
+
+    fn health(&self) -> ModuleHealth {
+        let Some(status) = self.supervisor.status(&self.name) else {
+            return ModuleHealth::unhealthy("not supervised");
+        };
+        match status.state {
+            TaskState::Running | TaskState::Stopped => ModuleHealth::healthy(),
+            TaskState::Backoff => ModuleHealth::degraded(format!(
+                "restarting after panic: {}",
+                status.last_panic.unwrap_or_default()
+            )),
+            TaskState::CircuitOpen => ModuleHealth::unhealthy(format!(
+                "circuit breaker open after {} panics: {}",
+                status.panics,
+                status.last_panic.unwrap_or_default()
+            )),
+        }
+    }
+
+    async fn start(&self) -> Result<(), String> {
+        if self.supervisor.is_active(&self.name) {
+            return Err(format!("{} is already running", self.name));
+        }
+        self.supervisor
+            .start(&self.name)
+            .map(|_| ())
+            .ok_or_else(|| format!("{} cannot be started", self.name))
+    }
+
+    async fn stop(&self) -> Result<(), String> {
+        if self.supervisor.stop(&self.name) {
+            Ok(())
+        } else {
+            Err(format!("{} is not running", self.name))
+        }
+    }
+}
+
+fn set_state(tasks: &DashMap<String, TaskStatus>, name: &str, state: TaskState) {
+    if let Some(mut status) = tasks.get_mut(name) {
+        status.state = state;
+    }
+}
+
+fn record_panic(tasks: &DashMap<String, TaskStatus>, name: &str, message: &str) -> u64 {
+    match tasks.get_mut(name) {
+        Some(mut status) => {
+            status.panics += 1;
+            status.last_panic = Some(message.to_string());
+            status.panics
+        }
+        None => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    fn fast_config() -> SupervisorConfig {
+        SupervisorConfig {
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(5),
+            max_panics: 3,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_backoff() {
+        let config = SupervisorConfig::default();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.backoff(1), Duration::from_millis(100));
+        assert_eq!(config.backoff(3), Duration::from_millis(400));
+        assert_eq!(config.backoff(30), Duration::from_secs(30));
+    }
+
+    #[tokio::test]
+    async fn test_restart_until_success() {
+        let supervisor = Supervisor::new(fast_config());
+        let attempts = Arc::new(AtomicUsize::new(0));
+
+        let counter = attempts.clone();
+        let handle = supervisor.supervise("flaky", move || {
+            let counter = counter.clone();
+            async move {
+                if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                    panic!("flaky failure");
+                }
+            }
+        });
+
+        assert_eq!(handle.await.unwrap(), TaskState::Stopped);
+        let status = supervisor.status("flaky").unwrap();
+        assert_eq!(status.panics, 2);
+        assert_eq!(status.restarts, 2);
+        assert_eq!(status.last_panic.as_deref(), Some("flaky failure"));
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker() {
+        let supervisor = Supervisor::new(fast_config());
+        let handle = supervisor.supervise("broken", || async { panic!("always") });
+
+        assert_eq!(handle.await.unwrap(), TaskState::CircuitOpen);
+        let status = supervisor.status("broken").unwrap();
+        assert_eq!(status.state, TaskState::CircuitOpen);
+        assert_eq!(status.panics, 3);
+        assert_eq!(status.restarts, 2);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown() {
+        let supervisor = Supervisor::new(fast_config());
+        let handle = supervisor.supervise("forever", || std::future::pending());
+        tokio::task::yield_now().await;
+
+        supervisor.shutdown();
+        assert_eq!(handle.await.unwrap(), TaskState::Stopped);
+        assert_eq!(supervisor.statuses().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_stop_and_start() {
+        let supervisor = Arc::new(Supervisor::new(fast_config()));
+        let starts = Arc::new(AtomicUsize::new(0));
+
+        let counter = starts.clone();
+        let handle = supervisor.supervise("worker", move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            std::future::pending()
+        });
+        tokio::task::yield_now().await;
+
+        let module = SupervisedModule::new(supervisor.clone(), "worker");
+        assert_eq!(module.state(), LifecycleState::Running);
+        assert!(module.start().await.is_err());
+
+        module.stop().await.unwrap();
+        assert!(handle.await.unwrap_err().is_cancelled());
+        assert_eq!(module.state(), LifecycleState::Stopped);
+        assert!(!supervisor.is_active("worker"));
+        assert!(module.stop().await.is_err());
+
+        module.start().await.unwrap();
+        tokio::task::yield_now().await;
+        assert_eq!(module.state(), LifecycleState::Running);
+        assert_eq!(starts.load(Ordering::SeqCst), 2);
+
+        module.restart().await.unwrap();
+        tokio::task::yield_now().await;
+        assert_eq!(starts.load(Ordering::SeqCst), 3);
+        assert!(module.health().is_healthy());
+
+        assert!(supervisor.start("unknown").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_consumer_restarts_after_panic() {
+        let supervisor = Supervisor::new(fast_config());
+        let (tx, rx) = mpsc::channel(16);
+        let handled = Arc::new(AtomicUsize::new(0));
+
+        let counter = handled.clone();
+        let handle = supervisor.supervise_consumer("consumer", rx, move |n: usize| {
+            let counter = counter.clone();
+            async move {
+                if n == 1 {
+                    panic!("bad message");
+                }
+                counter.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+
+        // Messages queued behind the panicking one are handled after the restart
+        for n in 0..4 {
+            tx.send(n).await.unwrap();
+        }
+        drop(tx);
+
+        assert_eq!(handle.await.unwrap(), TaskState::Stopped);
+        assert_eq!(handled.load(Ordering::SeqCst), 3);
+        let status = supervisor.status("consumer").unwrap();
+        assert_eq!((status.panics, status.restarts), (1, 1));
+        assert_eq!(status.last_panic.as_deref(), Some("bad message"));
+    }
+}
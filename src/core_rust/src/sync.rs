@@ -0,0 +1,527 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Knowledge sync v1.0 - exchange graph deltas between instances
+//!
+//! Lets e.g. a desktop instance and a home server share what they learned.
+//! A [`SyncNode`] wraps an instance's `RuntimeStorage`:
+//!
+//! - [`SyncNode::export_delta`] collects concepts and connections changed
+//!   since a Unix timestamp into a [`GraphDelta`]
+//! - [`SyncNode::apply_delta`] merges a peer's delta
+//!
+//! Token and connection IDs are local, so deltas address concepts by label;
+//! unlabeled tokens and their connections are not synced. Connections are
+//! matched by (endpoints, connection type) and conflicts resolved per
+//! connection ([`resolve`]):
+//!
+//! 1. a local Immutable connection is never overwritten
+//! 2. otherwise the side with the higher `evidence_count` wins
+//! 3. ties go to the higher confidence, then the later `last_update`;
+//!    identical records keep the local copy
+//!
+//! A peer cannot make a connection Immutable here: a remote Immutable
+//! connection is stored as Learnable, and connections with an unknown
+//! mutability code are ignored.
+//!
+//! Both sides apply the same rules, so two instances that exchange deltas
+//! converge on every connection neither holds as Immutable. Transport is HTTP: `GET /api/v1/sync/delta?since=` serves a
+//! delta and `POST /api/v1/sync/delta` applies one; [`SyncClient`]
+//! (feature `sync`) drives a pull/push round against a peer.
+
+use crate::connection_v3::{ConnectionMutability, ConnectionV3};
+use crate::runtime_storage::RuntimeStorage;
+use crate::token::Token;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Delta format version; bumped on incompatible changes
+pub const SYNC_PROTOCOL_VERSION: u32 = 1;
+
+/// Sync errors
+#[derive(Debug, thiserror::Error)]
+pub enum SyncError {
+    #[error("Unsupported sync protocol version {0} (expected {SYNC_PROTOCOL_VERSION})")]
+    UnsupportedVersion(u32),
+
+    #[error("Delta was exported by this instance ('{0}')")]
+    OwnDelta(String),
+
+    #[error("Sync transport error: {0}")]
+    Transport(String),
+}
+
+/// A labeled token
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncConcept {
+    pub label: String,
+    /// Raw fixed-point coordinates, 8 spaces × (X, Y, Z)
+    pub coordinates: [[i16; 3]; 8],
+    pub weight: f32,
+    pub timestamp: u32,
+}
+
+impl SyncConcept {
+    pub(crate) fn from_token(token: &Token, label: String) -> Self {
+        Self {
+            label,
+            coordinates: token.coordinates,
+            weight: token.weight,
+            timestamp: token.timestamp,
+        }
+    }
+
+    /// New token carrying the concept's coordinates (ID assigned on insert)
+    pub(crate) fn to_token(&self) -> Token {
+        let mut token = Token::new(0);
+        token.coordinates = self.coordinates;
+        token.weight = self.weight;
+        token.timestamp = self.timestamp;
+        token
+    }
+}
+
+/// A connection between two labeled tokens
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SyncConnection {
+    pub from: String,
+    pub to: String,
+    pub connection_type: u8,
+    /// `ConnectionMutability` code
+    pub mutability: u8,
+    pub confidence: u8,
+    pub evidence_count: u16,
+    pub active_levels: u8,
+    pub pull_strength: f32,
+    pub preferred_distance: f32,
+    pub last_update: u32,
+}
+
+impl SyncConnection {
+    pub(crate) fn from_connection(connection: &ConnectionV3, from: String, to: String) -> Self {
+        Self {
+            from,
+            to,
+            connection_type: connection.connection_type,
+            mutability: connection.mutability,
+            confidence: connection.confidence,
+            evidence_count: connection.evidence_count,
+            active_levels: connection.active_levels,
+            pull_strength: connection.pull_strength,
+            preferred_distance: connection.preferred_distance,
+            last_update: connection.last_update,
+        }
+    }
+
+    /// Mutability to store locally, None for an unknown code
+    ///
+    /// Immutable is only ever set locally, so a peer's Immutable
+    /// connection arrives as Learnable.
+    pub fn received_mutability(&self) -> Option<ConnectionMutability> {
+        match ConnectionMutability::from_u8(self.mutability)? {
+            ConnectionMutability::Immutable => Some(ConnectionMutability::Learnable),
+            mutability => Some(mutability),
+        }
+    }
+
+    /// Copy the synced fields onto a local connection
+    fn apply_to(&self, connection: &mut ConnectionV3, mutability: ConnectionMutability) {
+        connection.mutability = mutability as u8;
+        connection.confidence = self.confidence;
+        connection.evidence_count = self.evidence_count;
+        connection.active_levels = self.active_levels;
+        connection.pull_strength = self.pull_strength;
+        connection.preferred_distance = self.preferred_distance;
+        connection.last_update = self.last_update;
+    }
+}
+
+/// Changes of one instance since a point in time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphDelta {
+    pub version: u32,
+    /// Exporting instance
+    pub instance_id: String,
+    /// Changes from this Unix timestamp on (0 = everything)
+    pub since: u32,
+    /// Export time; pass as `since` next round
+    pub generated_at: u32,
+    pub concepts: Vec<SyncConcept>,
+    pub connections: Vec<SyncConnection>,
+}
+
+/// Outcome of [`SyncNode::apply_delta`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncReport {
+    pub concepts_added: usize,
+    pub connections_added: usize,
+    /// Local connections replaced by the remote version
+    pub connections_updated: usize,
+    /// Remote connections that lost conflict resolution
+    pub kept_local: usize,
+    /// Remote changes to local Immutable connections, ignored
+    pub immutable_protected: usize,
+    /// Connections naming a concept neither side sent
+    pub unresolved: usize,
+    /// Connections with an unknown mutability code, ignored
+    pub invalid: usize,
+}
+
+/// How a remote connection is merged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// No local counterpart
+    Insert,
+    TakeRemote,
+    KeepLocal,
+    /// Local connection is Immutable
+    Protected,
+}
+
+/// Conflict resolution for one connection (see module docs)
+pub fn resolve(local: Option<&ConnectionV3>, remote: &SyncConnection) -> Resolution {
+    let Some(local) = local else {
+        return Resolution::Insert;
+    };
+    if ConnectionMutability::from_u8(local.mutability) == Some(ConnectionMutability::Immutable) {
+        return Resolution::Protected;
+    }
+
+    let local_rank = (local.evidence_count, local.confidence, local.last_update);
+    let remote_rank = (remote.evidence_count, remote.confidence, remote.last_update);
+    if remote_rank > local_rank {
+        Resolution::TakeRemote
+    } else {
+        Resolution::KeepLocal
+    }
+}
+
+/// Canonical local key of a connection (endpoints in ID order, type)
+fn connection_key(a: u32, b: u32, connection_type: u8) -> (u32, u32, u8) {
+    (a.min(b), a.max(b), connection_type)
+}
+
+/// Sync endpoint of one instance
+pub struct SyncNode {
+    instance_id: String,
+    storage: Arc<RuntimeStorage>,
+}
+
+impl SyncNode {
+    pub fn new(instance_id: impl Into<String>, storage: Arc<RuntimeStorage>) -> Self {
+        Self { instance_id: instance_id.into(), storage }
+    }
+
+    pub fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    /// Concepts and connections changed at or after `since`
+    ///
+    /// Concepts are the labeled tokens created since then plus every
+    /// endpoint of an exported connection, so the peer can resolve them.
+    pub fn export_delta(&self, since: u32) -> GraphDelta {
+        let generated_at = Token::current_timestamp();
+        let labels: HashMap<u32, String> = self.storage.labels().into_iter().collect();
+
+        let mut connections = Vec::new();
+        let mut endpoints = std::collections::HashSet::new();
+        for (_, connection) in self.storage.connections() {
+            if connection.last_update < since {
+                continue;
+            }
+            let (a, b) = (connection.token_a_id, connection.token_b_id);
+            let (Some(from), Some(to)) = (labels.get(&a), labels.get(&b)) else {
+                continue;
+            };
+            endpoints.insert(a);
+            endpoints.insert(b);
+            connections.push(SyncConnection::from_connection(&connection, from.clone(), to.clone()));
+        }
+
+        let mut concepts: Vec<SyncConcept> = labels
+            .iter()
+            .filter_map(|(&id, label)| {
+                let token = self.storage.get_token(id)?;
+                let timestamp = token.timestamp;
+                (timestamp >= since || endpoints.contains(&id))
+                    .then(|| SyncConcept::from_token(&token, label.clone()))
+            })
+            .collect();
+        concepts.sort_by(|a, b| a.label.cmp(&b.label));
+
+        GraphDelta {
+            version: SYNC_PROTOCOL_VERSION,
+            instance_id: self.instance_id.clone(),
+            since,
+            generated_at,
+            concepts,
+            connections,
+        }
+    }
+
+    /// Merge a peer's delta into local storage
+    pub fn apply_delta(&self, delta: &GraphDelta) -> Result<SyncReport, SyncError> {
+        if delta.version != SYNC_PROTOCOL_VERSION {
+            return Err(SyncError::UnsupportedVersion(delta.version));
+        }
+        if delta.instance_id == self.instance_id {
+            return Err(SyncError::OwnDelta(delta.instance_id.clone()));
+        }
+
+        let mut report = SyncReport::default();
+
+        // 1. Concepts: known labels keep their local token
+        for concept in &delta.concepts {
+            if concept.label.is_empty() || self.storage.token_by_label(&concept.label).is_some() {
+                continue;
+            }
+            let id = self.storage.create_token(concept.to_token());
+            self.storage.set_label(id, &concept.label);
+            report.concepts_added += 1;
+        }
+
+        // 2. Connections
+        let mut local: HashMap<(u32, u32, u8), (u64, ConnectionV3)> = self.storage
+            .connections()
+            .into_iter()
+            .map(|(id, c)| (connection_key(c.token_a_id, c.token_b_id, c.connection_type), (id, c)))
+            .collect();
+
+        for remote in &delta.connections {
+            let Some(mutability) = remote.received_mutability() else {
+                report.invalid += 1;
+                continue;
+            };
+            let (Some(a), Some(b)) = (
+                self.storage.token_by_label(&remote.from),
+                self.storage.token_by_label(&remote.to),
+            ) else {
+                report.unresolved += 1;
+                continue;
+            };
+            let key = connection_key(a, b, remote.connection_type);
+
+            match resolve(local.get(&key).map(|(_, c)| c), remote) {
+                Resolution::Insert => {
+                    let mut connection = ConnectionV3::new(a, b);
+                    connection.connection_type = remote.connection_type;
+                    remote.apply_to(&mut connection, mutability);
+                    let id = self.storage.create_connection(connection);
+                    local.insert(key, (id, connection));
+                    report.connections_added += 1;
+                }
+                Resolution::TakeRemote => {
+                    let (id, connection) = local.get_mut(&key).expect("resolved against a local connection");
+                    remote.apply_to(connection, mutability);
+                    if self.storage.update_connection(*id, *connection).is_ok() {
+                        report.connections_updated += 1;
+                    }
+                }
+                Resolution::KeepLocal => report.kept_local += 1,
+                Resolution::Protected => report.immutable_protected += 1,
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Result of one [`SyncClient::sync`] round
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncRound {
+    /// Peer changes merged locally
+    pub pulled: SyncReport,
+    /// Local changes merged by the peer
+    pub pushed: SyncReport,
+}
+
+/// HTTP sync with one peer (e.g. the home server)
+///
+/// Remembers how far each direction got, so repeated rounds only move new
+/// changes.
+#[cfg(feature = "sync")]
+pub struct SyncClient {
+    node: Arc<SyncNode>,
+    /// Peer base URL, e.g. "http://home-server:3000"
+    base_url: String,
+    api_key: Option<String>,
+    client: reqwest::Client,
+    last_pull: u32,
+    last_push: u32,
+}
+
+#[cfg(feature = "sync")]
+impl SyncClient {
+    pub fn new(node: Arc<SyncNode>, base_url: impl Into<String>, timeout_ms: u64) -> Result<Self, SyncError> {
+        let client = reqwest::Client::builder()
+            .timeout(std::time::Duration::from_millis(timeout_ms))
+            .build()
+            .map_err(|e| SyncError::Transport(e.to_string()))?;
+        Ok(Self {
+            node,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            api_key: None,
+            client,
+            last_pull: 0,
+            last_push: 0,
+        })
+    }
+
+    /// Key sent as `X-API-Key` (the peer's admin scope for pushes)
+    pub fn with_api_key(mut self, api_key: impl Into<String>) -> Self {
+        self.api_key = Some(api_key.into());
+        self
+    }
+
+    fn delta_url(&self) -> String {
+        format!("{}/api/v1/sync/delta", self.base_url)
+    }
+
+    fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match &self.api_key {
+            Some(key) => request.header("X-API-Key", key),
+            None => request,
+        }
+    }
+
+    async fn send<T: serde::de::DeserializeOwned>(request: reqwest::RequestBuilder) -> Result<T, SyncError> {
+        let response = request
+            .send()
+            .await
+            .map_err(|e| SyncError::Transport(format!("Sync request failed: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(SyncError::Transport(format!("Peer returned {}", response.status())));
+        }
+        response.json().await.map_err(|e| SyncError::Transport(e.to_string()))
+    }
+
+    /// Fetch the peer's changes since the last pull and merge them
+    pub async fn pull(&mut self) -> Result<SyncReport, SyncError> {
+        let request = self.client.get(self.delta_url()).query(&[("since", self.last_pull)]);
+        let delta: GraphDelta = Self::send(self.authorize(request)).await?;
+        let report = self.node.apply_delta(&delta)?;
+        self.last_pull = delta.generated_at;
+        Ok(report)
+    }
+
+    /// Send local changes since the last push to the peer
+    pub async fn push(&mut self) -> Result<SyncReport, SyncError> {
+        let delta = self.node.export_delta(self.last_push);
+        let request = self.client.post(self.delta_url()).json(&delta);
+        let report = Self::send(self.authorize(request)).await?;
+        self.last_push = delta.generated_at;
+        Ok(report)
+    }
+
+    /// Pull, then push
+    pub async fn sync(&mut self) -> Result<SyncRound, SyncError> {
+        let pulled = self.pull().await?;
+        let pushed = self.push().await?;
+        Ok(SyncRound { pulled, pushed })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn concept(storage: &RuntimeStorage, label: &str) -> u32 {
+        let id = storage.create_token(Token::new(0));
+        storage.set_label(id, label);
+        id
+    }
+
+    fn connect(storage: &RuntimeStorage, a: u32, b: u32, evidence: u16, mutability: ConnectionMutability) -> u64 {
+        let mut connection = ConnectionV3::new(a, b);
+        connection.evidence_count = evidence;
+        connection.mutability = mutability as u8;
+        storage.create_connection(connection)
+    }
+
+    #[test]
+    fn test_desktop_and_server_converge() {
+        let desktop = Arc::new(RuntimeStorage::new());
+        let (cat, dog, fish) = (concept(&desktop, "cat"), concept(&desktop, "dog"), concept(&desktop, "fish"));
+        connect(&desktop, cat, dog, 5, ConnectionMutability::Learnable);
+        connect(&desktop, cat, fish, 1, ConnectionMutability::Learnable);
+
+        let server = Arc::new(RuntimeStorage::new());
+        let bird = concept(&server, "bird");
+        let (s_dog, s_cat) = (concept(&server, "dog"), concept(&server, "cat"));
+        connect(&server, s_cat, s_dog, 2, ConnectionMutability::Learnable);
+        let s_fish = concept(&server, "fish");
+        let protected = connect(&server, s_cat, s_fish, 0, ConnectionMutability::Immutable);
+        connect(&server, bird, s_dog, 3, ConnectionMutability::Hypothesis);
+
+        let desktop_node = SyncNode::new("desktop", desktop.clone());
+        let server_node = SyncNode::new("server", server.clone());
+
+        let pushed = server_node.apply_delta(&desktop_node.export_delta(0)).unwrap();
+        assert_eq!(pushed.concepts_added, 0);
+        assert_eq!(pushed.connections_updated, 1); // cat-dog: 5 beats 2
+        assert_eq!(pushed.immutable_protected, 1); // cat-fish stays Immutable
+        assert_eq!(server.get_connection(protected).unwrap().evidence_count, 0);
+
+        let pulled = desktop_node.apply_delta(&server_node.export_delta(0)).unwrap();
+        assert_eq!(pulled.concepts_added, 1);
+        assert_eq!(pulled.connections_added, 1); // bird-dog
+        assert_eq!(pulled.connections_updated, 0);
+        assert_eq!(pulled.kept_local, 2); // cat-dog equal, cat-fish: 1 beats 0
+
+        // Second round changes nothing
+        let again = server_node.apply_delta(&desktop_node.export_delta(0)).unwrap();
+        assert_eq!(again.connections_added + again.connections_updated, 0);
+        assert_eq!(desktop.count_connections(), server.count_connections());
+    }
+
+    #[test]
+    fn test_remote_immutable_is_not_trusted() {
+        let desktop = Arc::new(RuntimeStorage::new());
+        let (cat, dog) = (concept(&desktop, "cat"), concept(&desktop, "dog"));
+        connect(&desktop, cat, dog, 1, ConnectionMutability::Immutable);
+
+        let server = Arc::new(RuntimeStorage::new());
+        let report = SyncNode::new("server", server.clone())
+            .apply_delta(&SyncNode::new("desktop", desktop).export_delta(0))
+            .unwrap();
+        assert_eq!(report.connections_added, 1);
+        let (_, connection) = server.connections().into_iter().next().unwrap();
+        assert_eq!(connection.mutability, ConnectionMutability::Learnable as u8);
+    }
+
+    #[test]
+    fn test_resolve_and_rejects() {
+        let remote = SyncConnection::from_connection(&ConnectionV3::new(1, 2), "a".into(), "b".into());
+        assert_eq!(resolve(None, &remote), Resolution::Insert);
+        let mut local = ConnectionV3::new(1, 2);
+        local.evidence_count = 1;
+        assert_eq!(resolve(Some(&local), &remote), Resolution::KeepLocal);
+        local.evidence_count = 0;
+        local.confidence = 0;
+        assert_eq!(resolve(Some(&local), &remote), Resolution::TakeRemote);
+
+        let node = SyncNode::new("a", Arc::new(RuntimeStorage::new()));
+        let mut delta = node.export_delta(0);
+        assert!(matches!(node.apply_delta(&delta), Err(SyncError::OwnDelta(_))));
+        delta.instance_id = "b".into();
+        delta.connections.push(SyncConnection { mutability: 7, ..remote.clone() });
+        assert_eq!(node.apply_delta(&delta).unwrap().invalid, 1);
+        delta.version = 99;
+        assert!(matches!(node.apply_delta(&delta), Err(SyncError::UnsupportedVersion(99))));
+    }
+}
@@ -0,0 +1,391 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Synonym Collapse v1.0 - Merge near-duplicate tokens
+//!
+//! A maintenance pass over RuntimeStorage:
+//!
+//! 1. **Candidates** - token pairs linked by a `Synonym` or `Similar`
+//!    connection whose 8D states have cosine similarity of at least
+//!    `similarity_threshold`. The older token (lower ID) is kept.
+//! 2. **Propose** - each candidate is routed as `HybridProposal::Merge`;
+//!    the ProposalRouter queues it for review instead of applying it.
+//! 3. **Review** - [`SynonymCollapse::review`] resolves the queued merge;
+//!    an approved merge folds the absorbed token into the kept one
+//!    (`RuntimeStorage::merge_tokens`) and reports the node count before
+//!    and after.
+//!
+//! A token takes part in at most one proposal per pass, tokens with a merge
+//! already pending are skipped, and rejected pairs are not proposed again.
+//! [`SynonymCollapse::spawn`] runs the propose step periodically; nothing is
+//! merged without review, and nothing is proposed or merged while the
+//! router's safe mode freezes learning.
+
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+
+use crate::connection_v3::ConnectionType;
+use crate::hybrid_learning::{HybridLearningError, HybridProposal, ProposalRouter, TokenMerge};
+use crate::runtime_storage::{MergeSummary, RuntimeStorage};
+
+/// Synonym collapse configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SynonymCollapseConfig {
+    /// Minimum cosine similarity of two token states to propose a merge
+    pub similarity_threshold: f32,
+
+    /// Merges proposed per pass at most (most similar first)
+    pub max_proposals: usize,
+}
+
+impl Default for SynonymCollapseConfig {
+    fn default() -> Self {
+        Self {
+            similarity_threshold: 0.98,
+            max_proposals: 64,
+        }
+    }
+}
+
+impl SynonymCollapseConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(-1.0..=1.0).contains(&self.similarity_threshold) {
+            return Err(format!(
+                "similarity_threshold must be -1.0-1.0, got {}",
+                self.similarity_threshold
+            ));
+        }
+        if self.max_proposals == 0 {
+            return Err("max_proposals must be > 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Result of one propose pass
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CollapsePass {
+    /// Synonym/Similar connections above the threshold
+    pub candidates: usize,
+    /// Merges queued with the router
+    pub proposed: usize,
+    /// Merges the router refused
+    pub refused: usize,
+}
+
+/// An applied merge
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MergeReport {
+    pub merge: TokenMerge,
+    pub nodes_before: usize,
+    pub nodes_after: usize,
+    pub connections: MergeSummary,
+}
+
+impl MergeReport {
+    /// Tokens removed by the merge
+    pub fn node_reduction(&self) -> usize {
+        self.nodes_before.saturating_sub(self.nodes_after)
+    }
+}
+
+/// Synonym collapse statistics
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CollapseStats {
+    pub passes: u64,
+    pub proposed: u64,
+    pub approved: u64,
+    pub rejected: u64,
+    /// Tokens removed by approved merges
+    pub nodes_removed: u64,
+    pub last_merge: Option<MergeReport>,
+}
+
+/// Finds near-duplicate tokens and merges them after review
+pub struct SynonymCollapse {
+    config: RwLock<SynonymCollapseConfig>,
+    storage: Arc<RuntimeStorage>,
+    router: Arc<ProposalRouter>,
+    /// Pairs (keep, absorb) rejected in review
+    rejected: RwLock<HashSet<(u32, u32)>>,
+    /// Serializes passes and merges
+    running: Mutex<()>,
+    stats: RwLock<CollapseStats>,
+}
+
+impl SynonymCollapse {
+    pub fn new(
+        config: SynonymCollapseConfig,
+        storage: Arc<RuntimeStorage>,
+        router: Arc<ProposalRouter>,
+    ) -> Self {
+        Self {
+            config: RwLock::new(config),
+            storage,
+            router,
+            rejected: RwLock::new(HashSet::new()),
+            running: Mutex::new(()),
+            stats: RwLock::new(CollapseStats::default()),
+        }
+    }
+
+    pub fn config(&self) -> SynonymCollapseConfig {
+        self.config.read().clone()
+    }
+
+    pub fn update_config(&self, config: SynonymCollapseConfig) -> Result<(), String> {
+        config.validate()?;
+        *self.config.write() = config;
+        Ok(())
+    }
+
+    /// Merge candidates, most similar first
+    pub fn candidates(&self) -> Vec<TokenMerge> {
+        let threshold = self.config.read().similarity_threshold;
+        let pending: HashSet<u32> = self
+            .router
+            .pending_merges()
+            .iter()
+            .flat_map(|m| [m.keep, m.absorb])
+            .collect();
+        let rejected = self.rejected.read();
+
+        let mut candidates: Vec<TokenMerge> = self
+            .storage
+            .connections()
+            .into_iter()
+            .filter(|(_, conn)| {
+                conn.connection_type == ConnectionType::Synonym as u8
+                    || conn.connection_type == ConnectionType::Similar as u8
+            })
+            .filter_map(|(connection_id, conn)| {
+                let (a, b) = (conn.token_a_id, conn.token_b_id);
+                let pair = (a.min(b), a.max(b));
+                if a == b || pending.contains(&a) || pending.contains(&b) || rejected.contains(&pair) {
+                    return None;
+                }
+                let similarity = cosine(
+                    &self.storage.get_token(a)?.to_state_f32(),
+                    &self.storage.get_token(b)?.to_state_f32(),
+                )?;
+                (similarity >= threshold).then_some(TokenMerge {
+                    keep: a.min(b),
+                    absorb: a.max(b),
+                    connection_id,
+                    similarity,
+                })
+            })
+            .collect();
+        candidates.sort_by(|x, y| {
+            y.similarity
+                .total_cmp(&x.similarity)
+                .then(x.connection_id.cmp(&y.connection_id))
+        });
+        candidates
+    }
+
+    /// Queue merges for the best candidates with the router
+    pub fn propose(&self) -> CollapsePass {
+        let _running = self.running.lock();
+        let max_proposals = self.config.read().max_proposals;
+        let candidates = self.candidates();
+        let mut pass = CollapsePass {
+            candidates: candidates.len(),
+            ..Default::default()
+        };
+
+        let mut involved = HashSet::new();
+        for merge in candidates {
+            if pass.proposed >= max_proposals {
+                break;
+            }
+            if involved.contains(&merge.keep) || involved.contains(&merge.absorb) {
+                continue;
+            }
+            match self.router.route_proposal(HybridProposal::Merge(merge)) {
+                Ok(_) => {
+                    involved.extend([merge.keep, merge.absorb]);
+                    pass.proposed += 1;
+                }
+                Err(e) => {
+                    tracing::debug!(keep = merge.keep, absorb = merge.absorb, error = %e, "Merge not queued");
+                    pass.refused += 1;
+                }
+            }
+        }
+
+        let mut stats = self.stats.write();
+        stats.passes += 1;
+        stats.proposed += pass.proposed as u64;
+        pass
+    }
+
+    /// Review the pending merge of token `absorb`; approved merges are applied
+    ///
+    /// Returns the applied merge, or `Ok(None)` if rejected.
+    pub fn review(&self, absorb: u32, approve: bool) -> Result<Option<MergeReport>, HybridLearningError> {
+        let _running = self.running.lock();
+        let pending = self.router.pending_merges().into_iter().find(|m| m.absorb == absorb);
+        let Some(merge) = self.router.resolve_merge(absorb, approve)? else {
+            if let Some(merge) = pending {
+                self.rejected.write().insert((merge.keep, merge.absorb));
+            }
+            self.stats.write().rejected += 1;
+            return Ok(None);
+        };
+
+        let nodes_before = self.storage.count_tokens();
+        let connections = self
+            .storage
+            .merge_tokens(merge.keep, merge.absorb)
+            .map_err(|e| HybridLearningError::InvalidProposal(e.to_string()))?;
+        let report = MergeReport {
+            merge,
+            nodes_before,
+            nodes_after: self.storage.count_tokens(),
+            connections,
+        };
+        tracing::info!(
+            keep = merge.keep,
+            absorb = merge.absorb,
+            similarity = merge.similarity,
+            nodes_before = report.nodes_before,
+            nodes_after = report.nodes_after,
+            "Synonym tokens merged"
+        );
+
+        let mut stats = self.stats.write();
+        stats.approved += 1;
+        stats.nodes_removed += report.node_reduction() as u64;
+        stats.last_merge = Some(report);
+        Ok(Some(report))
+    }
+
+    pub fn stats(&self) -> CollapseStats {
+        self.stats.read().clone()
+    }
+
+    /// Propose merges every `interval`
+    pub fn spawn(self: Arc<Self>, interval: Duration) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                let collapse = self.clone();
+                let _ = tokio::task::spawn_blocking(move || collapse.propose()).await;
+            }
+        })
+    }
+}
+
+/// Cosine similarity, `None` for zero vectors
+fn cosine(a: &[f32; 8], b: &[f32; 8]) -> Option<f32> {
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = |v: &[f32; 8]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let denominator = norm(a) * norm(b);
+    (denominator > 0.0).then(|| dot / denominator)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection_v3::ConnectionV3;
+    use crate::guardian::Guardian;
+    use crate::safe_mode::SafeMode;
+    use crate::Token;
+
+    fn setup() -> (Arc<RuntimeStorage>, SynonymCollapse) {
+        setup_with(Arc::default())
+    }
+
+    fn setup_with(safe_mode: Arc<SafeMode>) -> (Arc<RuntimeStorage>, SynonymCollapse) {
+        let storage = Arc::new(RuntimeStorage::new());
+        for x in [1.0, 1.01, 0.0, 5.0] {
+            storage.create_token(Token::from_state_f32(0, &[x, 1.0, 0.5, 0.0, 0.0, 0.0, 0.0, 0.0]));
+        }
+        let link = |a, b, kind: ConnectionType| {
+            let mut conn = ConnectionV3::new(a, b);
+            conn.connection_type = kind as u8;
+            storage.create_connection(conn)
+        };
+        link(1, 2, ConnectionType::Synonym);
+        link(1, 3, ConnectionType::Similar);
+        link(2, 3, ConnectionType::Cause);
+        link(1, 4, ConnectionType::Synonym);
+
+        let router = Arc::new(ProposalRouter::new(Arc::new(Guardian::new())).with_safe_mode(safe_mode));
+        let collapse = SynonymCollapse::new(SynonymCollapseConfig::default(), storage.clone(), router);
+        (storage, collapse)
+    }
+
+    #[test]
+    fn test_propose_and_approve() {
+        let (storage, collapse) = setup();
+        let candidates = collapse.candidates();
+        assert_eq!(candidates.len(), 1);
+        assert_eq!((candidates[0].keep, candidates[0].absorb), (1, 2));
+
+        let pass = collapse.propose();
+        assert_eq!(pass.proposed, 1);
+        assert!(collapse.candidates().is_empty());
+        assert_eq!(collapse.propose().proposed, 0);
+        assert_eq!(storage.count_tokens(), 4);
+
+        let report = collapse.review(2, true).unwrap().unwrap();
+        assert_eq!((report.nodes_before, report.nodes_after), (4, 3));
+        assert_eq!(report.node_reduction(), 1);
+        assert_eq!(report.connections.dropped, 1);
+        assert!(storage.get_token(2).is_none());
+        assert_eq!(collapse.stats().nodes_removed, 1);
+        assert!(matches!(collapse.review(2, true), Err(HybridLearningError::NoPendingMerge(2))));
+    }
+
+    #[test]
+    fn test_rejected_merge_keeps_tokens() {
+        let (storage, collapse) = setup();
+        collapse.propose();
+        assert!(collapse.review(2, false).unwrap().is_none());
+        assert_eq!(storage.count_tokens(), 4);
+        assert_eq!(collapse.stats().rejected, 1);
+        assert_eq!(collapse.propose().proposed, 0);
+
+        assert!(SynonymCollapseConfig { max_proposals: 0, ..Default::default() }.validate().is_err());
+    }
+
+    #[test]
+    fn test_safe_mode_freezes_merges() {
+        let safe_mode = Arc::new(SafeMode::new());
+        let (storage, collapse) = setup_with(safe_mode.clone());
+        collapse.propose();
+
+        safe_mode.engage(Some("test"));
+        let pass = collapse.propose();
+        assert_eq!(pass.proposed, 0);
+        assert!(matches!(collapse.review(2, true), Err(HybridLearningError::LearningFrozen)));
+        assert_eq!(storage.count_tokens(), 4);
+
+        // The merge stayed queued and goes through once safe mode is lifted
+        safe_mode.release();
+        assert!(collapse.review(2, true).unwrap().is_some());
+        assert_eq!(storage.count_tokens(), 3);
+    }
+}
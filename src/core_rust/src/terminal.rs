@@ -0,0 +1,1264 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Terminal v1.0 - command interpreter for interactive terminals
+//!
+//! [`Interpreter`] turns a command line into a call on core components:
+//!
+//! ```text
+//! graph neighbors cat --limit 5
+//! ngql NEIGHBORS cat TYPE causal DEPTH 2
+//! adna set curiosity.weight 0.4
+//! checkpoint create "before upgrade"
+//! help graph
+//! ```
+//!
+//! Commands are declared with a [`CommandSpec`] (path, arguments, flags) in
+//! a [`CommandRegistry`], which parses lines (quotes and `--flag=value`
+//! supported), renders help and suggests completions for a partial line.
+//! Concept words and checkpoint IDs are completed from the live components.
+//! Every executed line is kept in a bounded [`CommandHistory`].
+//!
+//! The REST API exposes the interpreter under `/api/v1/terminal`, so
+//! desktop terminals and `neurograph-cli exec` share the same commands.
+
+use crate::adna::{ADNAReader, AppraiserConfig, InMemoryADNAReader};
+use crate::bootstrap::BootstrapLibrary;
+use crate::checkpoint::{CheckpointManager, CheckpointManifest};
+use futures::future::BoxFuture;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+use std::sync::Arc;
+
+/// Lines kept by the interpreter history
+pub const DEFAULT_HISTORY_CAPACITY: usize = 500;
+
+/// Maximum completions returned for one request
+const MAX_COMPLETIONS: usize = 50;
+
+const DEFAULT_NEIGHBORS_LIMIT: usize = 20;
+
+// ============================================================================
+// Errors and output
+// ============================================================================
+
+/// Command parsing or execution error
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommandError {
+    /// Blank line
+    Empty,
+    /// No command with this path
+    UnknownCommand(String),
+    UnknownFlag { command: String, flag: String },
+    MissingArgument { command: String, argument: String },
+    InvalidArgument { argument: String, value: String, expected: String },
+    UnexpectedArgument { command: String, value: String },
+    UnterminatedQuote,
+    /// Registration of an already registered path
+    DuplicateCommand(String),
+    /// Malformed `CommandSpec`
+    InvalidSpec(String),
+    /// Component the command needs is not attached
+    Unavailable(String),
+    /// The command ran and failed
+    Failed(String),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::Empty => write!(f, "Empty command"),
+            CommandError::UnknownCommand(name) => {
+                write!(f, "Unknown command '{}' (try 'help')", name)
+            }
+            CommandError::UnknownFlag { command, flag } => {
+                write!(f, "'{}' has no flag '{}'", command, flag)
+            }
+            CommandError::MissingArgument { command, argument } => {
+                write!(f, "'{}' requires <{}>", command, argument)
+            }
+            CommandError::InvalidArgument { argument, value, expected } => {
+                write!(f, "Invalid {}: '{}' (expected {})", argument, value, expected)
+            }
+            CommandError::UnexpectedArgument { command, value } => {
+                write!(f, "Unexpected argument '{}' for '{}'", value, command)
+            }
+            CommandError::UnterminatedQuote => write!(f, "Unterminated quote"),
+            CommandError::DuplicateCommand(path) => {
+                write!(f, "Command '{}' is already registered", path)
+            }
+            CommandError::InvalidSpec(msg) => write!(f, "Invalid command spec: {}", msg),
+            CommandError::Unavailable(what) => write!(f, "{} is not enabled", what),
+            CommandError::Failed(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+/// Result of a command: text for terminals, optional JSON for scripts
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CommandOutput {
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl CommandOutput {
+    pub fn text(text: impl Into<String>) -> Self {
+        Self { text: text.into(), data: None }
+    }
+
+    pub fn with_data<T: Serialize>(mut self, data: &T) -> Self {
+        self.data = serde_json::to_value(data).ok();
+        self
+    }
+}
+
+pub type CommandResult = Result<CommandOutput, CommandError>;
+
+/// Command implementation
+pub type CommandHandler =
+    Arc<dyn Fn(Arc<TerminalContext>, ParsedArgs) -> BoxFuture<'static, CommandResult> + Send + Sync>;
+
+// ============================================================================
+// Command specs
+// ============================================================================
+
+/// Argument or flag value type (drives validation and completion)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgKind {
+    /// Any single token
+    Word,
+    Integer,
+    Number,
+    /// Rest of the line, joined with spaces (last argument only)
+    Text,
+    /// One of a fixed set
+    Choice(&'static [&'static str]),
+    /// Concept word from the bootstrap library
+    Concept,
+    /// Checkpoint ID
+    Checkpoint,
+    /// Registered command path (rest of the line, last argument only)
+    Command,
+}
+
+impl ArgKind {
+    /// Consumes the remaining positional tokens
+    fn takes_rest(&self) -> bool {
+        matches!(self, ArgKind::Text | ArgKind::Command)
+    }
+
+    fn expected(&self) -> String {
+        match self {
+            ArgKind::Integer => "an integer".to_string(),
+            ArgKind::Number => "a number".to_string(),
+            ArgKind::Choice(choices) => format!("one of {}", choices.join(", ")),
+            _ => "a value".to_string(),
+        }
+    }
+
+    fn check(&self, argument: &str, value: &str) -> Result<(), CommandError> {
+        let valid = match self {
+            ArgKind::Integer => value.parse::<i64>().is_ok(),
+            ArgKind::Number => value.parse::<f64>().is_ok(),
+            ArgKind::Choice(choices) => choices.contains(&value),
+            _ => true,
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(CommandError::InvalidArgument {
+                argument: argument.to_string(),
+                value: value.to_string(),
+                expected: self.expected(),
+            })
+        }
+    }
+}
+
+/// Positional argument
+#[derive(Debug, Clone)]
+pub struct ArgSpec {
+    pub name: &'static str,
+    pub kind: ArgKind,
+    pub required: bool,
+    pub help: &'static str,
+}
+
+/// `--name <value>` option
+#[derive(Debug, Clone)]
+pub struct FlagSpec {
+    pub name: &'static str,
+    pub kind: ArgKind,
+    pub help: &'static str,
+}
+
+/// Declaration of one command
+#[derive(Debug, Clone)]
+pub struct CommandSpec {
+    /// Space-separated words, e.g. `graph neighbors`
+    pub path: &'static str,
+    pub summary: &'static str,
+    pub args: Vec<ArgSpec>,
+    pub flags: Vec<FlagSpec>,
+}
+
+impl CommandSpec {
+    pub fn new(path: &'static str, summary: &'static str) -> Self {
+        Self { path, summary, args: Vec::new(), flags: Vec::new() }
+    }
+
+    pub fn arg(mut self, name: &'static str, kind: ArgKind, help: &'static str) -> Self {
+        self.args.push(ArgSpec { name, kind, required: true, help });
+        self
+    }
+
+    pub fn optional_arg(mut self, name: &'static str, kind: ArgKind, help: &'static str) -> Self {
+        self.args.push(ArgSpec { name, kind, required: false, help });
+        self
+    }
+
+    pub fn flag(mut self, name: &'static str, kind: ArgKind, help: &'static str) -> Self {
+        self.flags.push(FlagSpec { name, kind, help });
+        self
+    }
+
+    /// One-line synopsis, e.g. `graph neighbors <word> [--limit N]`
+    pub fn usage(&self) -> String {
+        let mut usage = self.path.to_string();
+        for arg in &self.args {
+            let name = if arg.kind.takes_rest() { format!("{}...", arg.name) } else { arg.name.to_string() };
+            if arg.required {
+                usage.push_str(&format!(" <{}>", name));
+            } else {
+                usage.push_str(&format!(" [{}]", name));
+            }
+        }
+        for flag in &self.flags {
+            usage.push_str(&format!(" [--{} {}]", flag.name, flag.name.to_uppercase()));
+        }
+        usage
+    }
+
+    fn words(&self) -> impl Iterator<Item = &'static str> {
+        self.path.split_whitespace()
+    }
+
+    fn validate(&self) -> Result<(), CommandError> {
+        if self.words().next().is_none() {
+            return Err(CommandError::InvalidSpec("empty path".to_string()));
+        }
+        let mut seen_optional = false;
+        for (i, arg) in self.args.iter().enumerate() {
+            if arg.kind.takes_rest() && i + 1 != self.args.len() {
+                return Err(CommandError::InvalidSpec(format!(
+                    "'{}': text argument <{}> must be last",
+                    self.path, arg.name
+                )));
+            }
+            if arg.required && seen_optional {
+                return Err(CommandError::InvalidSpec(format!(
+                    "'{}': required <{}> follows an optional argument",
+                    self.path, arg.name
+                )));
+            }
+            seen_optional |= !arg.required;
+        }
+        if let Some(flag) = self.flags.iter().find(|f| f.kind.takes_rest()) {
+            return Err(CommandError::InvalidSpec(format!(
+                "'{}': flag --{} cannot take text",
+                self.path, flag.name
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Arguments and flag values of a parsed line, by name
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParsedArgs {
+    values: HashMap<&'static str, String>,
+}
+
+impl ParsedArgs {
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    /// Typed value; kinds were checked while parsing
+    pub fn parse<T: std::str::FromStr>(&self, name: &str) -> Result<Option<T>, CommandError> {
+        self.get(name)
+            .map(|value| {
+                value.parse().map_err(|_| CommandError::InvalidArgument {
+                    argument: name.to_string(),
+                    value: value.to_string(),
+                    expected: std::any::type_name::<T>().to_string(),
+                })
+            })
+            .transpose()
+    }
+
+    pub fn require(&self, name: &str) -> Result<&str, CommandError> {
+        self.get(name).ok_or_else(|| CommandError::Failed(format!("Missing <{}>", name)))
+    }
+}
+
+// ============================================================================
+// Tokenizer
+// ============================================================================
+
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    text: String,
+    /// Byte offset of the token in the line
+    start: usize,
+}
+
+/// Split a line into tokens; `"..."` and `'...'` group words, `\` escapes
+/// inside double quotes. Returns whether the line ends inside a quote.
+fn tokenize(line: &str) -> (Vec<Token>, bool) {
+    let mut tokens = Vec::new();
+    let mut current: Option<Token> = None;
+    let mut quote: Option<char> = None;
+    let mut chars = line.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match (quote, c) {
+            (Some('"'), '\\') => {
+                if let Some((_, next)) = chars.next() {
+                    current.get_or_insert(Token { text: String::new(), start: i }).text.push(next);
+                }
+            }
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => current.get_or_insert(Token { text: String::new(), start: i }).text.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(c);
+                current.get_or_insert(Token { text: String::new(), start: i });
+            }
+            (None, c) if c.is_whitespace() => tokens.extend(current.take()),
+            (None, c) => current.get_or_insert(Token { text: String::new(), start: i }).text.push(c),
+        }
+    }
+    tokens.extend(current);
+    (tokens, quote.is_some())
+}
+
+/// Quote a completion value if it would not survive tokenizing
+fn quote_value(value: &str) -> String {
+    if value.chars().any(|c| c.is_whitespace() || c == '"' || c == '\'') {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// ============================================================================
+// Registry
+// ============================================================================
+
+/// A completion candidate: replace `line[start..]` with `value`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Completion {
+    pub value: String,
+    /// Short description (command summary, argument help)
+    pub description: String,
+    pub start: usize,
+}
+
+#[derive(Clone)]
+enum Handler {
+    Help,
+    History,
+    Custom(CommandHandler),
+}
+
+struct Command {
+    spec: CommandSpec,
+    handler: Handler,
+}
+
+/// Registered commands with parsing, help and completion
+#[derive(Default)]
+pub struct CommandRegistry {
+    commands: Vec<Command>,
+}
+
+impl CommandRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a command
+    pub fn register<F>(&mut self, spec: CommandSpec, handler: F) -> Result<(), CommandError>
+    where
+        F: Fn(Arc<TerminalContext>, ParsedArgs) -> BoxFuture<'static, CommandResult> + Send + Sync + 'static,
+    {
+        self.insert(spec, Handler::Custom(Arc::new(handler)))
+    }
+
+    fn insert(&mut self, spec: CommandSpec, handler: Handler) -> Result<(), CommandError> {
+        spec.validate()?;
+        if self.spec(spec.path).is_some() {
+            return Err(CommandError::DuplicateCommand(spec.path.to_string()));
+        }
+        self.commands.push(Command { spec, handler });
+        self.commands.sort_by(|a, b| a.spec.path.cmp(b.spec.path));
+        Ok(())
+    }
+
+    /// Registered commands, sorted by path
+    pub fn specs(&self) -> impl Iterator<Item = &CommandSpec> {
+        self.commands.iter().map(|c| &c.spec)
+    }
+
+    pub fn spec(&self, path: &str) -> Option<&CommandSpec> {
+        let words: Vec<&str> = path.split_whitespace().collect();
+        self.specs().find(|spec| spec.words().eq(words.iter().copied()))
+    }
+
+    /// Command with the longest path matching the leading tokens
+    fn resolve(&self, tokens: &[Token]) -> Option<(&Command, usize)> {
+        self.commands
+            .iter()
+            .filter_map(|command| {
+                let len = command.spec.words().count();
+                let matches = len <= tokens.len()
+                    && command.spec.words().zip(tokens).all(|(word, token)| word == token.text);
+                matches.then_some((command, len))
+            })
+            .max_by_key(|(_, len)| *len)
+    }
+
+    /// Parse a line into its command spec and arguments
+    pub fn parse(&self, line: &str) -> Result<(&CommandSpec, ParsedArgs), CommandError> {
+        self.parse_command(line).map(|(command, args)| (&command.spec, args))
+    }
+
+    fn parse_command(&self, line: &str) -> Result<(&Command, ParsedArgs), CommandError> {
+        let (tokens, open_quote) = tokenize(line);
+        if open_quote {
+            return Err(CommandError::UnterminatedQuote);
+        }
+        if tokens.is_empty() {
+            return Err(CommandError::Empty);
+        }
+
+        let (command, consumed) = self.resolve(&tokens).ok_or_else(|| {
+            // Name as much of the path as the user typed ("graph foo")
+            let typed = tokens.iter().take(2).map(|t| t.text.as_str()).collect::<Vec<_>>();
+            let is_group = self.specs().any(|s| s.words().next() == Some(typed[0]));
+            let name = if is_group { typed.join(" ") } else { typed[0].to_string() };
+            CommandError::UnknownCommand(name)
+        })?;
+        let spec = &command.spec;
+
+        let mut args = ParsedArgs::default();
+        let mut positional = Vec::new();
+        let mut rest = tokens[consumed..].iter().map(|t| t.text.clone());
+        while let Some(token) = rest.next() {
+            let Some(flag) = token.strip_prefix("--").filter(|f| !f.is_empty()) else {
+                positional.push(token);
+                continue;
+            };
+            let (name, inline) = match flag.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (flag, None),
+            };
+            let flag_spec = spec.flags.iter().find(|f| f.name == name).ok_or_else(|| {
+                CommandError::UnknownFlag { command: spec.path.to_string(), flag: format!("--{}", name) }
+            })?;
+            let value = inline.or_else(|| rest.next()).ok_or_else(|| CommandError::MissingArgument {
+                command: spec.path.to_string(),
+                argument: format!("--{} value", flag_spec.name),
+            })?;
+            flag_spec.kind.check(flag_spec.name, &value)?;
+            args.values.insert(flag_spec.name, value);
+        }
+
+        let mut positional = positional.into_iter();
+        for arg in &spec.args {
+            let value = if arg.kind.takes_rest() {
+                let text: Vec<String> = positional.by_ref().collect();
+                (!text.is_empty()).then(|| text.join(" "))
+            } else {
+                positional.next()
+            };
+            match value {
+                Some(value) => {
+                    arg.kind.check(arg.name, &value)?;
+                    args.values.insert(arg.name, value);
+                }
+                None if arg.required => {
+                    return Err(CommandError::MissingArgument {
+                        command: spec.path.to_string(),
+                        argument: arg.name.to_string(),
+                    })
+                }
+                None => {}
+            }
+        }
+        if let Some(extra) = positional.next() {
+            return Err(CommandError::UnexpectedArgument { command: spec.path.to_string(), value: extra });
+        }
+
+        Ok((command, args))
+    }
+
+    /// Command overview, or details of the commands under `topic`
+    pub fn help(&self, topic: Option<&str>) -> Result<String, CommandError> {
+        let topic_words: Vec<&str> = topic.map(|t| t.split_whitespace().collect()).unwrap_or_default();
+        let matching: Vec<&CommandSpec> = self
+            .specs()
+            .filter(|spec| spec.words().take(topic_words.len()).eq(topic_words.iter().copied()))
+            .collect();
+        if matching.is_empty() {
+            return Err(CommandError::UnknownCommand(topic_words.join(" ")));
+        }
+
+        let mut out = String::new();
+        if let [spec] = matching.as_slice() {
+            out.push_str(&format!("{}\n\n  {}\n", spec.summary, spec.usage()));
+            for arg in &spec.args {
+                out.push_str(&format!("\n  {:<16} {}", arg.name, arg.help));
+            }
+            for flag in &spec.flags {
+                out.push_str(&format!("\n  --{:<14} {}", flag.name, flag.help));
+            }
+            return Ok(out.trim_end().to_string());
+        }
+
+        let width = matching.iter().map(|s| s.usage().len()).max().unwrap_or(0);
+        for spec in matching {
+            out.push_str(&format!("  {:<width$}  {}\n", spec.usage(), spec.summary, width = width));
+        }
+        Ok(out.trim_end().to_string())
+    }
+
+    /// Next path words after the `typed` words, with the command summary
+    fn path_candidates(&self, typed: &[&str]) -> Vec<(String, String)> {
+        self.specs()
+            .filter_map(|spec| {
+                let words: Vec<&str> = spec.words().collect();
+                let prefix_matches = words.len() > typed.len() && words.iter().zip(typed).all(|(w, t)| w == t);
+                prefix_matches.then(|| (words[typed.len()].to_string(), spec.summary.to_string()))
+            })
+            .collect()
+    }
+
+    /// Completions for the last (partial) token of `line`; dynamic kinds
+    /// (concepts, checkpoints) are looked up through `values`
+    pub fn complete(&self, line: &str, values: &dyn Fn(ArgKind) -> Vec<String>) -> Vec<Completion> {
+        let (mut tokens, open_quote) = tokenize(line);
+        let ends_token = !open_quote && line.ends_with(|c: char| c.is_whitespace());
+        let partial = if line.is_empty() || ends_token {
+            Token { text: String::new(), start: line.len() }
+        } else {
+            match tokens.pop() {
+                Some(token) => token,
+                None => Token { text: String::new(), start: line.len() },
+            }
+        };
+
+        let resolved = self
+            .resolve(&tokens)
+            .filter(|(command, consumed)| command.spec.words().count() == *consumed);
+
+        // Still typing the command path (including subcommands of a command
+        // that is itself complete, e.g. "checkpoint " → "list")
+        let typed: Vec<&str> = tokens.iter().map(|t| t.text.as_str()).collect();
+        let mut candidates = self.path_candidates(&typed);
+
+        if let Some((command, consumed)) = resolved {
+            let spec = &command.spec;
+            let after: Vec<&str> = tokens[consumed..].iter().map(|t| t.text.as_str()).collect();
+            let pending_flag = after
+                .last()
+                .and_then(|t| t.strip_prefix("--"))
+                .and_then(|name| spec.flags.iter().find(|f| f.name == name));
+
+            if let Some(flag) = pending_flag {
+                candidates = kind_values(flag.kind, values)
+                    .into_iter()
+                    .map(|v| (v, flag.help.to_string()))
+                    .collect();
+            } else if partial.text.starts_with('-') {
+                candidates = spec
+                    .flags
+                    .iter()
+                    .filter(|f| !after.contains(&format!("--{}", f.name).as_str()))
+                    .map(|f| (format!("--{}", f.name), f.help.to_string()))
+                    .collect();
+            } else {
+                // Index of the positional argument being typed
+                let mut index = 0;
+                let mut skip_value = false;
+                for token in &after {
+                    if skip_value {
+                        skip_value = false;
+                    } else if token.starts_with("--") && !token.contains('=') {
+                        skip_value = true;
+                    } else if !token.starts_with("--") {
+                        index += 1;
+                    }
+                }
+                let arg = spec
+                    .args
+                    .get(index)
+                    .or_else(|| spec.args.last().filter(|a| a.kind.takes_rest()));
+                if arg.is_some_and(|a| a.kind == ArgKind::Command) {
+                    let typed: Vec<&str> = after.iter().copied().filter(|t| !t.starts_with("--")).collect();
+                    candidates.extend(self.path_candidates(&typed));
+                } else if let Some(arg) = arg {
+                    candidates.extend(
+                        kind_values(arg.kind, values).into_iter().map(|v| (v, arg.help.to_string())),
+                    );
+                }
+            }
+        }
+
+        let mut completions: Vec<Completion> = candidates
+            .into_iter()
+            .filter(|(value, _)| value.starts_with(&partial.text))
+            .map(|(value, description)| Completion {
+                value: quote_value(&value),
+                description,
+                start: partial.start,
+            })
+            .collect();
+        completions.sort_by(|a, b| a.value.cmp(&b.value));
+        completions.dedup_by(|a, b| a.value == b.value);
+        completions.truncate(MAX_COMPLETIONS);
+        completions
+    }
+}
+
+fn kind_values(kind: ArgKind, values: &dyn Fn(ArgKind) -> Vec<String>) -> Vec<String> {
+    match kind {
+        ArgKind::Choice(choices) => choices.iter().map(|c| c.to_string()).collect(),
+        ArgKind::Concept | ArgKind::Checkpoint => values(kind),
+        _ => Vec::new(),
+    }
+}
+
+// ============================================================================
+// History
+// ============================================================================
+
+/// Bounded command history with shell-style navigation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandHistory {
+    entries: VecDeque<String>,
+    capacity: usize,
+    /// Navigation position (index into `entries`); None = not navigating
+    #[serde(skip)]
+    cursor: Option<usize>,
+}
+
+impl Default for CommandHistory {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_CAPACITY)
+    }
+}
+
+impl CommandHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self { entries: VecDeque::new(), capacity: capacity.max(1), cursor: None }
+    }
+
+    /// Record a line; blank lines and repeats of the last line are skipped
+    pub fn push(&mut self, line: &str) {
+        self.cursor = None;
+        let line = line.trim();
+        if line.is_empty() || self.entries.back().map(String::as_str) == Some(line) {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(line.to_string());
+    }
+
+    /// Step to an older entry (Up arrow); stays on the oldest
+    pub fn older(&mut self) -> Option<&str> {
+        let index = match self.cursor {
+            None => self.entries.len().checked_sub(1)?,
+            Some(i) => i.saturating_sub(1),
+        };
+        self.cursor = Some(index);
+        self.entries.get(index).map(String::as_str)
+    }
+
+    /// Step to a newer entry (Down arrow); None past the newest
+    pub fn newer(&mut self) -> Option<&str> {
+        let index = self.cursor? + 1;
+        if index >= self.entries.len() {
+            self.cursor = None;
+            return None;
+        }
+        self.cursor = Some(index);
+        self.entries.get(index).map(String::as_str)
+    }
+
+    /// Entries containing `text`, newest first
+    pub fn search(&self, text: &str) -> Vec<&str> {
+        self.entries.iter().rev().filter(|e| e.contains(text)).map(String::as_str).collect()
+    }
+
+    /// All entries, oldest first
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &str> {
+        self.entries.iter().map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.cursor = None;
+    }
+}
+
+// ============================================================================
+// Interpreter
+// ============================================================================
+
+/// Components the built-in commands operate on
+#[derive(Default, Clone)]
+pub struct TerminalContext {
+    pub bootstrap: Option<Arc<RwLock<BootstrapLibrary>>>,
+    pub adna: Option<Arc<InMemoryADNAReader>>,
+    pub checkpoint: Option<Arc<CheckpointManager>>,
+}
+
+impl TerminalContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_bootstrap(mut self, bootstrap: Arc<RwLock<BootstrapLibrary>>) -> Self {
+        self.bootstrap = Some(bootstrap);
+        self
+    }
+
+    pub fn with_adna(mut self, adna: Arc<InMemoryADNAReader>) -> Self {
+        self.adna = Some(adna);
+        self
+    }
+
+    pub fn with_checkpoint(mut self, checkpoint: Arc<CheckpointManager>) -> Self {
+        self.checkpoint = Some(checkpoint);
+        self
+    }
+
+    fn bootstrap(&self) -> Result<&RwLock<BootstrapLibrary>, CommandError> {
+        self.bootstrap.as_deref().ok_or_else(|| CommandError::Unavailable("Graph access".to_string()))
+    }
+
+    fn adna(&self) -> Result<&InMemoryADNAReader, CommandError> {
+        self.adna.as_deref().ok_or_else(|| CommandError::Unavailable("ADNA access".to_string()))
+    }
+
+    fn checkpoint(&self) -> Result<&CheckpointManager, CommandError> {
+        self.checkpoint.as_deref().ok_or_else(|| CommandError::Unavailable("Checkpointing".to_string()))
+    }
+
+    /// Completion values for dynamic argument kinds
+    fn values(&self, kind: ArgKind) -> Vec<String> {
+        match kind {
+            ArgKind::Concept => self
+                .bootstrap
+                .as_ref()
+                .map(|b| b.read().concepts_iter().map(|(word, _)| word.clone()).collect())
+                .unwrap_or_default(),
+            ArgKind::Checkpoint => self
+                .checkpoint
+                .as_ref()
+                .and_then(|c| c.list().ok())
+                .map(|list| list.into_iter().map(|m| m.id).collect())
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Command registry with built-in commands, bound to core components
+pub struct Interpreter {
+    registry: CommandRegistry,
+    context: Arc<TerminalContext>,
+    history: Mutex<CommandHistory>,
+}
+
+impl Interpreter {
+    pub fn new(context: TerminalContext) -> Self {
+        let mut registry = CommandRegistry::new();
+        register_builtins(&mut registry).expect("built-in command specs are valid");
+        Self {
+            registry,
+            context: Arc::new(context),
+            history: Mutex::new(CommandHistory::default()),
+        }
+    }
+
+    pub fn with_history_capacity(mut self, capacity: usize) -> Self {
+        self.history = Mutex::new(CommandHistory::new(capacity));
+        self
+    }
+
+    /// Add a command next to the built-ins
+    pub fn register<F>(&mut self, spec: CommandSpec, handler: F) -> Result<(), CommandError>
+    where
+        F: Fn(Arc<TerminalContext>, ParsedArgs) -> BoxFuture<'static, CommandResult> + Send + Sync + 'static,
+    {
+        self.registry.register(spec, handler)
+    }
+
+    pub fn registry(&self) -> &CommandRegistry {
+        &self.registry
+    }
+
+    /// Parse and run a line; the line is recorded in history even if it fails
+    pub async fn execute(&self, line: &str) -> CommandResult {
+        self.history.lock().push(line);
+        let (command, args) = self.registry.parse_command(line)?;
+        match &command.handler {
+            Handler::Help => self.registry.help(args.get("command")).map(CommandOutput::text),
+            Handler::History => {
+                let limit: Option<usize> = args.parse("limit")?;
+                let history = self.history.lock();
+                let mut entries: Vec<&str> = match args.get("filter") {
+                    Some(text) => history.search(text),
+                    None => history.entries().rev().collect(),
+                };
+                entries.truncate(limit.unwrap_or(usize::MAX));
+                entries.reverse();
+                Ok(CommandOutput::text(entries.join("\n")).with_data(&entries))
+            }
+            Handler::Custom(handler) => handler(self.context.clone(), args).await,
+        }
+    }
+
+    /// Completions for a partial line
+    pub fn complete(&self, line: &str) -> Vec<Completion> {
+        self.registry.complete(line, &|kind| self.context.values(kind))
+    }
+
+    pub fn help(&self, topic: Option<&str>) -> Result<String, CommandError> {
+        self.registry.help(topic)
+    }
+
+    /// History entries, oldest first
+    pub fn history(&self) -> Vec<String> {
+        self.history.lock().entries().map(str::to_string).collect()
+    }
+}
+
+// ============================================================================
+// Built-in commands
+// ============================================================================
+
+fn register_builtins(registry: &mut CommandRegistry) -> Result<(), CommandError> {
+    registry.insert(
+        CommandSpec::new("help", "List commands or describe one")
+            .optional_arg("command", ArgKind::Command, "Command or group, e.g. 'graph'"),
+        Handler::Help,
+    )?;
+    registry.insert(
+        CommandSpec::new("history", "Previously entered commands")
+            .optional_arg("filter", ArgKind::Word, "Only lines containing this text")
+            .flag("limit", ArgKind::Integer, "Number of lines to show"),
+        Handler::History,
+    )?;
+
+    registry.register(
+        CommandSpec::new("graph neighbors", "Direct neighbors of a concept, strongest first")
+            .arg("word", ArgKind::Concept, "Concept word")
+            .flag("limit", ArgKind::Integer, "Maximum neighbors (default 20)"),
+        |ctx, args| Box::pin(async move { graph_neighbors(&ctx, &args) }),
+    )?;
+    registry.register(
+        CommandSpec::new("graph path", "Shortest path between two concepts")
+            .arg("from", ArgKind::Concept, "Start concept")
+            .arg("to", ArgKind::Concept, "Target concept"),
+        |ctx, args| Box::pin(async move { graph_path(&ctx, &args) }),
+    )?;
+    registry.register(
+        CommandSpec::new("graph stats", "Concept, node and edge counts"),
+        |ctx, _| Box::pin(async move { graph_stats(&ctx) }),
+    )?;
+
+    registry.register(
+        CommandSpec::new("ngql", "Run a graph query, e.g. NEIGHBORS cat TYPE causal DEPTH 2")
+            .arg("query", ArgKind::Text, "NEIGHBORS <word> [TYPE t] [MINCONF x] [DEPTH n] [LIMIT n] or ANALOGY a:b::c:?"),
+        |ctx, args| {
+            Box::pin(async move {
+                let library = ctx.bootstrap()?.read();
+                let result = crate::ngql::query(&library, args.require("query")?)
+                    .map_err(|e| CommandError::Failed(e.to_string()))?;
+                Ok(CommandOutput::text(result.to_string()).with_data(&result))
+            })
+        },
+    )?;
+
+    registry.register(
+        CommandSpec::new("adna show", "Appraiser configuration")
+            .optional_arg("section", ArgKind::Word, "Only this section, e.g. 'curiosity'"),
+        |ctx, args| Box::pin(async move { adna_show(&ctx, &args).await }),
+    )?;
+    registry.register(
+        CommandSpec::new("adna set", "Update one appraiser parameter")
+            .arg("field", ArgKind::Word, "Dotted path, e.g. 'curiosity.weight'")
+            .arg("value", ArgKind::Text, "New value (JSON literal or string)"),
+        |ctx, args| Box::pin(async move { adna_set(&ctx, &args).await }),
+    )?;
+
+    registry.register(
+        CommandSpec::new("checkpoint list", "Stored checkpoints, oldest first"),
+        |ctx, _| Box::pin(async move { checkpoint_list(&ctx) }),
+    )?;
+    registry.register(
+        CommandSpec::new("checkpoint create", "Write a whole-system checkpoint")
+            .optional_arg("label", ArgKind::Text, "Label stored in the manifest"),
+        |ctx, args| {
+            Box::pin(async move {
+                let manifest = ctx
+                    .checkpoint()?
+                    .checkpoint(args.get("label"))
+                    .await
+                    .map_err(|e| CommandError::Failed(e.to_string()))?;
+                Ok(CommandOutput::text(manifest_line(&manifest)).with_data(&manifest))
+            })
+        },
+    )?;
+    registry.register(
+        CommandSpec::new("checkpoint restore", "Restore a checkpoint (newest if no ID)")
+            .optional_arg("id", ArgKind::Checkpoint, "Checkpoint ID"),
+        |ctx, args| {
+            Box::pin(async move {
+                let manager = ctx.checkpoint()?;
+                let manifest = match args.get("id") {
+                    Some(id) => manager.restore(id).await.map(Some),
+                    None => manager.restore_latest().await,
+                }
+                .map_err(|e| CommandError::Failed(e.to_string()))?
+                .ok_or_else(|| CommandError::Failed("No checkpoints to restore".to_string()))?;
+                Ok(CommandOutput::text(format!("restored {}", manifest_line(&manifest))).with_data(&manifest))
+            })
+        },
+    )?;
+
+    Ok(())
+}
+
+fn concept_id(library: &BootstrapLibrary, word: &str) -> Result<u32, CommandError> {
+    library
+        .get_concept(&word.to_lowercase())
+        .map(|c| c.id)
+        .ok_or_else(|| CommandError::Failed(format!("Unknown concept '{}'", word)))
+}
+
+fn graph_neighbors(ctx: &TerminalContext, args: &ParsedArgs) -> CommandResult {
+    let limit = args.parse::<usize>("limit")?.unwrap_or(DEFAULT_NEIGHBORS_LIMIT);
+    let library = ctx.bootstrap()?.read();
+    let node_id = concept_id(&library, args.require("word")?)?;
+    let graph = library.graph();
+    let words: HashMap<u32, &String> = library.concepts_iter().map(|(w, c)| (c.id, w)).collect();
+
+    let mut neighbors: Vec<(String, f32, String)> = graph
+        .get_neighbors(node_id, crate::graph::Direction::Both)
+        .into_iter()
+        .filter_map(|(neighbor_id, edge_id)| {
+            let edge = graph.get_edge(edge_id)?;
+            let label = words.get(&neighbor_id).map(|w| (*w).clone()).unwrap_or_else(|| format!("#{}", neighbor_id));
+            Some((label, edge.weight, crate::graph::export::edge_type_name(edge.edge_type)))
+        })
+        .collect();
+    neighbors.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    neighbors.truncate(limit);
+
+    let text = neighbors
+        .iter()
+        .map(|(label, weight, edge_type)| format!("{:<24} {:>7.3}  {}", label, weight, edge_type))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let data: Vec<Value> = neighbors
+        .iter()
+        .map(|(word, weight, edge_type)| serde_json::json!({"word": word, "weight": weight, "edge_type": edge_type}))
+        .collect();
+    Ok(CommandOutput::text(text).with_data(&data))
+}
+
+fn graph_path(ctx: &TerminalContext, args: &ParsedArgs) -> CommandResult {
+    let library = ctx.bootstrap()?.read();
+    let from = concept_id(&library, args.require("from")?)?;
+    let to = concept_id(&library, args.require("to")?)?;
+    let path = library
+        .graph()
+        .find_path(from, to)
+        .ok_or_else(|| CommandError::Failed("No path between these concepts".to_string()))?;
+
+    let words: HashMap<u32, &String> = library.concepts_iter().map(|(w, c)| (c.id, w)).collect();
+    let labels: Vec<String> = path
+        .nodes
+        .iter()
+        .map(|id| words.get(id).map(|w| (*w).clone()).unwrap_or_else(|| format!("#{}", id)))
+        .collect();
+    Ok(CommandOutput::text(format!("{} ({} hops)", labels.join(" -> "), path.length)).with_data(&labels))
+}
+
+fn graph_stats(ctx: &TerminalContext) -> CommandResult {
+    let library = ctx.bootstrap()?.read();
+    let stats = serde_json::json!({
+        "concepts": library.concepts_iter().count(),
+        "nodes": library.graph().node_count(),
+        "edges": library.graph().edge_count(),
+    });
+    Ok(CommandOutput {
+        text: format!("concepts: {}\nnodes:    {}\nedges:    {}", stats["concepts"], stats["nodes"], stats["edges"]),
+        data: Some(stats),
+    })
+}
+
+async fn adna_config(ctx: &TerminalContext) -> Result<AppraiserConfig, CommandError> {
+    ctx.adna()?
+        .get_appraiser_config()
+        .await
+        .map_err(|e| CommandError::Failed(e.to_string()))
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+async fn adna_show(ctx: &TerminalContext, args: &ParsedArgs) -> CommandResult {
+    let config = serde_json::to_value(adna_config(ctx).await?).map_err(|e| CommandError::Failed(e.to_string()))?;
+    let value = match args.get("section") {
+        Some(section) => config
+            .get(section)
+            .cloned()
+            .ok_or_else(|| CommandError::Failed(format!("Unknown ADNA section '{}'", section)))?,
+        None => config,
+    };
+    Ok(CommandOutput { text: pretty(&value), data: Some(value) })
+}
+
+async fn adna_set(ctx: &TerminalContext, args: &ParsedArgs) -> CommandResult {
+    let path = args.require("field")?;
+    let raw = args.require("value")?;
+    if path.split('.').any(str::is_empty) {
+        return Err(CommandError::InvalidArgument {
+            argument: "field".to_string(),
+            value: path.to_string(),
+            expected: "a dotted path".to_string(),
+        });
+    }
+    // JSON literal if possible, otherwise a string
+    let new_value = serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()));
+    let patch = path.rsplit('.').fold(new_value, |acc, key| serde_json::json!({ key: acc }));
+
+    let mut config = serde_json::to_value(adna_config(ctx).await?).map_err(|e| CommandError::Failed(e.to_string()))?;
+    crate::settings::merge_known_fields(&mut config, &patch, "ADNA").map_err(CommandError::Failed)?;
+    let updated: AppraiserConfig = serde_json::from_value(config)
+        .map_err(|e| CommandError::Failed(format!("Invalid ADNA value: {}", e)))?;
+    ctx.adna()?.update_config(updated).await;
+
+    let updated = serde_json::to_value(updated).map_err(|e| CommandError::Failed(e.to_string()))?;
+    let current = match path.split('.').try_fold(&updated, |v, key| v.get(key)) {
+        // Parameters are f32; print them without f64 widening noise
+        Some(Value::Number(n)) if n.is_f64() => format!("{}", n.as_f64().unwrap_or_default() as f32),
+        Some(value) => value.to_string(),
+        None => Value::Null.to_string(),
+    };
+    Ok(CommandOutput { text: format!("{} = {}", path, current), data: Some(updated) })
+}
+
+fn manifest_line(manifest: &CheckpointManifest) -> String {
+    format!(
+        "{}  {}  {} sections{}",
+        manifest.id,
+        manifest.created_at,
+        manifest.sections.len(),
+        manifest.label.as_deref().map(|l| format!("  ({})", l)).unwrap_or_default(),
+    )
+}
+
+fn checkpoint_list(ctx: &TerminalContext) -> CommandResult {
+    let list = ctx.checkpoint()?.list().map_err(|e| CommandError::Failed(e.to_string()))?;
+    let text = list.iter().map(manifest_line).collect::<Vec<_>>().join("\n");
+    Ok(CommandOutput::text(text).with_data(&list))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interpreter() -> Interpreter {
+        let json = r#"[
+            {"word": "cat", "id": 1, "coords": [0.1, 0.2, 0.3], "color": null, "emotion": null, "sound": null, "action": null, "spatial": null},
+            {"word": "car", "id": 2, "coords": [0.15, 0.25, 0.3], "color": null, "emotion": null, "sound": null, "action": null, "spatial": null},
+            {"word": "dog", "id": 3, "coords": [0.12, 0.22, 0.31], "color": null, "emotion": null, "sound": null, "action": null, "spatial": null}
+        ]"#;
+        let mut library = BootstrapLibrary::new(Default::default());
+        library.load_bootstrap_map_str(json).unwrap();
+        Interpreter::new(
+            TerminalContext::new()
+                .with_bootstrap(Arc::new(RwLock::new(library)))
+                .with_adna(Arc::new(InMemoryADNAReader::new(Default::default()))),
+        )
+    }
+
+    fn values(completions: Vec<Completion>) -> Vec<String> {
+        completions.into_iter().map(|c| c.value).collect()
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let (tokens, open) = tokenize(r#"checkpoint create "before \"big\" upgrade" x"#);
+        assert!(!open);
+        let texts: Vec<&str> = tokens.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(texts, vec!["checkpoint", "create", "before \"big\" upgrade", "x"]);
+        assert_eq!(tokens[2].start, 18);
+        assert!(tokenize("adna set 'x").1);
+    }
+
+    #[test]
+    fn test_parse_arguments_and_flags() {
+        let interpreter = interpreter();
+        let registry = interpreter.registry();
+
+        let (spec, args) = registry.parse("graph neighbors cat --limit=3").unwrap();
+        assert_eq!(spec.path, "graph neighbors");
+        assert_eq!(args.get("word"), Some("cat"));
+        assert_eq!(args.parse::<usize>("limit").unwrap(), Some(3));
+
+        let (_, args) = registry.parse("checkpoint create before the upgrade").unwrap();
+        assert_eq!(args.get("label"), Some("before the upgrade"));
+
+        assert!(matches!(registry.parse("   "), Err(CommandError::Empty)));
+        assert!(matches!(registry.parse("graph fly"), Err(CommandError::UnknownCommand(n)) if n == "graph fly"));
+        assert!(matches!(registry.parse("graph neighbors"), Err(CommandError::MissingArgument { .. })));
+        assert!(matches!(registry.parse("graph neighbors cat --limit x"), Err(CommandError::InvalidArgument { .. })));
+        assert!(matches!(registry.parse("graph neighbors cat --depth 2"), Err(CommandError::UnknownFlag { .. })));
+        assert!(matches!(registry.parse("graph stats now"), Err(CommandError::UnexpectedArgument { .. })));
+
+        let mut registry = CommandRegistry::new();
+        let bad = CommandSpec::new("x", "").arg("text", ArgKind::Text, "").arg("y", ArgKind::Word, "");
+        assert!(matches!(
+            registry.register(bad, |_, _| Box::pin(async { Ok(CommandOutput::default()) })),
+            Err(CommandError::InvalidSpec(_))
+        ));
+    }
+
+    #[test]
+    fn test_help() {
+        let interpreter = interpreter();
+        let overview = interpreter.help(None).unwrap();
+        assert!(overview.contains("graph neighbors <word> [--limit LIMIT]"));
+        assert!(overview.contains("checkpoint restore [id]"));
+
+        let group = interpreter.help(Some("adna")).unwrap();
+        assert!(group.contains("adna set") && !group.contains("graph"));
+
+        let single = interpreter.help(Some("adna set")).unwrap();
+        assert!(single.starts_with("Update one appraiser parameter"));
+        assert!(single.contains("Dotted path"));
+
+        assert!(interpreter.help(Some("fly")).is_err());
+    }
+
+    #[test]
+    fn test_completion() {
+        let interpreter = interpreter();
+
+        assert_eq!(values(interpreter.complete("gr")), vec!["graph"]);
+        assert_eq!(values(interpreter.complete("graph ")), vec!["neighbors", "path", "stats"]);
+        assert_eq!(values(interpreter.complete("checkpoint re")), vec!["restore"]);
+
+        let completions = interpreter.complete("graph neighbors ca");
+        assert_eq!(values(completions.clone()), vec!["car", "cat"]);
+        assert_eq!(completions[0].start, 16);
+
+        assert_eq!(values(interpreter.complete("graph neighbors cat --")), vec!["--limit"]);
+        assert!(interpreter.complete("graph neighbors cat --limit ").is_empty());
+        assert_eq!(values(interpreter.complete("graph path cat d")), vec!["dog"]);
+        assert_eq!(values(interpreter.complete("help adna s")), vec!["set", "show"]);
+        assert_eq!(interpreter.registry().parse("help adna set").unwrap().1.get("command"), Some("adna set"));
+    }
+
+    #[test]
+    fn test_history_navigation() {
+        let mut history = CommandHistory::new(3);
+        for line in ["a", "b", "b", "  ", "c", "d"] {
+            history.push(line);
+        }
+        assert_eq!(history.entries().collect::<Vec<_>>(), vec!["b", "c", "d"]);
+
+        assert_eq!(history.older(), Some("d"));
+        assert_eq!(history.older(), Some("c"));
+        assert_eq!(history.older(), Some("b"));
+        assert_eq!(history.older(), Some("b"));
+        assert_eq!(history.newer(), Some("c"));
+        assert_eq!(history.newer(), Some("d"));
+        assert_eq!(history.newer(), None);
+        assert_eq!(history.search("c"), vec!["c"]);
+    }
+
+    #[tokio::test]
+    async fn test_execute_builtins() {
+        let interpreter = interpreter();
+
+        let output = interpreter.execute("graph neighbors cat").await.unwrap();
+        assert!(output.text.contains("dog") || output.text.contains("car"));
+        assert!(output.data.unwrap().as_array().is_some());
+
+        let output = interpreter.execute("graph path cat cat").await.unwrap();
+        assert_eq!(output.text, "cat (0 hops)");
+
+        let output = interpreter.execute("adna set curiosity.weight 0.4").await.unwrap();
+        assert_eq!(output.text, "curiosity.weight = 0.4");
+        let output = interpreter.execute("adna show curiosity").await.unwrap();
+        assert_eq!(output.data.unwrap()["weight"].as_f64().unwrap() as f32, 0.4);
+        assert!(matches!(
+            interpreter.execute("adna set curiosity.nope 1").await,
+            Err(CommandError::Failed(_))
+        ));
+
+        assert!(matches!(
+            interpreter.execute("checkpoint list").await,
+            Err(CommandError::Unavailable(_))
+        ));
+
+        let output = interpreter.execute("history --limit 2").await.unwrap();
+        assert_eq!(output.text, "checkpoint list\nhistory --limit 2");
+        assert_eq!(interpreter.history().len(), 7);
+
+        let output = interpreter.execute("ngql NEIGHBORS 'cat' DEPTH 2").await.unwrap();
+        assert_eq!(output.data.unwrap()["kind"], "neighbors");
+        assert!(matches!(
+            interpreter.execute("ngql NEIGHBORS cat DEPTH 0").await,
+            Err(CommandError::Failed(_))
+        ));
+    }
+}
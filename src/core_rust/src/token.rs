@@ -28,6 +28,7 @@
 /// - field_strength: 1 byte (u8)
 /// - timestamp: 4 bytes (u32)
 
+#[cfg(not(all(target_arch = "wasm32", feature = "wasm")))]
 use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Coordinate space identifiers
@@ -44,6 +45,20 @@ pub enum CoordinateSpace {
     L8Abstract = 7,    // Abstract semantics
 }
 
+impl CoordinateSpace {
+    /// All spaces in index order
+    pub const ALL: [CoordinateSpace; 8] = [
+        CoordinateSpace::L1Physical,
+        CoordinateSpace::L2Sensory,
+        CoordinateSpace::L3Motor,
+        CoordinateSpace::L4Emotional,
+        CoordinateSpace::L5Cognitive,
+        CoordinateSpace::L6Social,
+        CoordinateSpace::L7Temporal,
+        CoordinateSpace::L8Abstract,
+    ];
+}
+
 /// Entity types (stored in flags, bits 8-11)
 #[repr(u16)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -225,6 +240,7 @@ impl Token {
     }
 
     /// Get current Unix timestamp
+    #[cfg(not(all(target_arch = "wasm32", feature = "wasm")))]
     pub fn current_timestamp() -> u32 {
         SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -232,6 +248,12 @@ impl Token {
             .as_secs() as u32
     }
 
+    /// Get current Unix timestamp (browser clock; `SystemTime` panics on wasm32)
+    #[cfg(all(target_arch = "wasm32", feature = "wasm"))]
+    pub fn current_timestamp() -> u32 {
+        (crate::wasm::unix_time_us() / 1_000_000) as u32
+    }
+
     /// Encode a float coordinate to i16 with scaling
     pub fn encode_coordinate(value: f32, space: CoordinateSpace) -> i16 {
         let scale = SCALE_FACTORS[space as usize];
@@ -380,6 +402,23 @@ impl Token {
     }
 }
 
+/// Decode and validate a serialized Token
+///
+/// Rejects input that is not exactly 64 bytes, a non-finite weight, and
+/// anything `Token::validate` rejects.
+pub fn validate_bytes(bytes: &[u8]) -> Result<Token, String> {
+    let bytes: &[u8; 64] = bytes
+        .try_into()
+        .map_err(|_| format!("Token requires 64 bytes, got {}", bytes.len()))?;
+    let token = Token::from_bytes(bytes);
+    let weight = token.weight;
+    if !weight.is_finite() {
+        return Err("Weight is not finite".to_string());
+    }
+    token.validate()?;
+    Ok(token)
+}
+
 impl std::fmt::Debug for Token {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         // Copy packed fields to avoid unaligned references
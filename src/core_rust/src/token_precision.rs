@@ -0,0 +1,289 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+//
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+//
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! High-precision shadow coordinates for Token V2
+//!
+//! Token coordinates are fixed-point i16 scaled by `SCALE_FACTORS`, so a
+//! learning step smaller than one quantum (0.01 in L1, 0.0001 in L2) is lost
+//! every time it is written back. `PrecisionStore` keeps a shadow copy of
+//! token positions in f32 or f64 for the spaces that need it; learning
+//! updates accumulate there and are quantized back into the 64-byte token
+//! every `quantize_every` updates (or on `flush`).
+//!
+//! Spaces left in `PrecisionMode::Fixed` are updated directly on the token,
+//! exactly as before.
+
+use crate::token::{flags, CoordinateSpace, Token};
+use std::collections::HashMap;
+
+/// Coordinate precision for one space
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PrecisionMode {
+    /// Fixed-point i16 only (no shadow)
+    #[default]
+    Fixed,
+    /// f32 shadow
+    F32,
+    /// f64 shadow
+    F64,
+}
+
+/// Precision configuration per CoordinateSpace
+#[derive(Debug, Clone)]
+pub struct PrecisionConfig {
+    /// Mode per space, indexed by `CoordinateSpace as usize`
+    pub modes: [PrecisionMode; 8],
+    /// Quantize a token back after this many shadow updates
+    pub quantize_every: u32,
+}
+
+impl Default for PrecisionConfig {
+    fn default() -> Self {
+        Self {
+            modes: [PrecisionMode::Fixed; 8],
+            quantize_every: 32,
+        }
+    }
+}
+
+impl PrecisionConfig {
+    /// Same mode for every space
+    pub fn uniform(mode: PrecisionMode) -> Self {
+        Self {
+            modes: [mode; 8],
+            ..Default::default()
+        }
+    }
+
+    /// Set the mode of one space
+    pub fn with_mode(mut self, space: CoordinateSpace, mode: PrecisionMode) -> Self {
+        self.modes[space as usize] = mode;
+        self
+    }
+
+    pub fn mode(&self, space: CoordinateSpace) -> PrecisionMode {
+        self.modes[space as usize]
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.quantize_every == 0 {
+            return Err("quantize_every must be > 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Shadow position of one token
+#[derive(Debug, Clone)]
+struct Shadow {
+    coords: [[f64; 3]; 8],
+    pending: u32,
+}
+
+/// Statistics
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrecisionStats {
+    pub tracked_tokens: usize,
+    pub shadow_updates: u64,
+    pub fixed_updates: u64,
+    pub quantizations: u64,
+}
+
+/// High-precision shadow store for token coordinates
+#[derive(Debug, Clone, Default)]
+pub struct PrecisionStore {
+    config: PrecisionConfig,
+    shadows: HashMap<u32, Shadow>,
+    stats: PrecisionStats,
+}
+
+impl PrecisionStore {
+    pub fn new(config: PrecisionConfig) -> Self {
+        Self {
+            config,
+            shadows: HashMap::new(),
+            stats: PrecisionStats::default(),
+        }
+    }
+
+    pub fn config(&self) -> &PrecisionConfig {
+        &self.config
+    }
+
+    /// True if any space keeps a shadow
+    pub fn is_enabled(&self) -> bool {
+        self.config.modes.iter().any(|m| *m != PrecisionMode::Fixed)
+    }
+
+    /// Current position in `space`: the shadow if tracked, else the token's
+    pub fn position(&self, token: &Token, space: CoordinateSpace) -> [f64; 3] {
+        let id = token.id;
+        match self.shadows.get(&id) {
+            Some(shadow) if self.config.mode(space) != PrecisionMode::Fixed => shadow.coords[space as usize],
+            _ => token.get_coordinates(space).map(f64::from),
+        }
+    }
+
+    /// Move a token by `delta` in `space`
+    ///
+    /// Returns true if the token's fixed-point coordinates were rewritten
+    /// (always for Fixed spaces, every `quantize_every` updates otherwise).
+    pub fn apply_delta(&mut self, token: &mut Token, space: CoordinateSpace, delta: [f64; 3]) -> bool {
+        let current = self.position(token, space);
+        let target = [current[0] + delta[0], current[1] + delta[1], current[2] + delta[2]];
+        self.set_position(token, space, target)
+    }
+
+    /// Set a token's position in `space`; same write-back rules as `apply_delta`
+    pub fn set_position(&mut self, token: &mut Token, space: CoordinateSpace, position: [f64; 3]) -> bool {
+        let mode = self.config.mode(space);
+        if mode == PrecisionMode::Fixed {
+            self.stats.fixed_updates += 1;
+            write_space(token, space, position);
+            return true;
+        }
+
+        let shadow = self.shadows.entry(token.id).or_insert_with(|| Shadow {
+            coords: std::array::from_fn(|i| token.get_coordinates(CoordinateSpace::ALL[i]).map(f64::from)),
+            pending: 0,
+        });
+        shadow.coords[space as usize] = match mode {
+            PrecisionMode::F32 => position.map(|v| v as f32 as f64),
+            _ => position,
+        };
+        shadow.pending += 1;
+        self.stats.shadow_updates += 1;
+
+        if shadow.pending >= self.config.quantize_every {
+            self.quantize(token);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Write the shadow position back into the token's fixed-point coordinates
+    pub fn quantize(&mut self, token: &mut Token) {
+        let id = token.id;
+        let Some(shadow) = self.shadows.get_mut(&id) else {
+            return;
+        };
+        if shadow.pending == 0 {
+            return;
+        }
+        for space in CoordinateSpace::ALL {
+            if self.config.mode(space) != PrecisionMode::Fixed {
+                write_space(token, space, shadow.coords[space as usize]);
+            }
+        }
+        shadow.pending = 0;
+        self.stats.quantizations += 1;
+    }
+
+    /// Quantize every token with pending shadow updates
+    pub fn flush<'a>(&mut self, tokens: impl IntoIterator<Item = &'a mut Token>) {
+        for token in tokens {
+            self.quantize(token);
+        }
+    }
+
+    /// IDs of tokens with shadow updates not yet written back
+    pub fn pending(&self) -> Vec<u32> {
+        self.shadows
+            .iter()
+            .filter(|(_, s)| s.pending > 0)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// Drop the shadow of a token (e.g. after it is deleted)
+    pub fn forget(&mut self, token_id: u32) {
+        self.shadows.remove(&token_id);
+    }
+
+    pub fn stats(&self) -> PrecisionStats {
+        PrecisionStats {
+            tracked_tokens: self.shadows.len(),
+            ..self.stats
+        }
+    }
+}
+
+fn write_space(token: &mut Token, space: CoordinateSpace, position: [f64; 3]) {
+    let before = token.coordinates[space as usize];
+    token.set_coordinates(space, position[0] as f32, position[1] as f32, position[2] as f32);
+    let after = token.coordinates[space as usize];
+    if after != before {
+        token.set_flag(flags::DIRTY);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_updates_survive_in_shadow() {
+        let mut fixed_token = Token::new(1);
+        let mut shadow_token = Token::new(2);
+
+        let mut fixed = PrecisionStore::new(PrecisionConfig::default());
+        let mut shadow = PrecisionStore::new(
+            PrecisionConfig::default().with_mode(CoordinateSpace::L1Physical, PrecisionMode::F64),
+        );
+
+        // 0.001 is a tenth of an L1 quantum
+        for _ in 0..1000 {
+            fixed.apply_delta(&mut fixed_token, CoordinateSpace::L1Physical, [0.001, 0.0, 0.0]);
+            shadow.apply_delta(&mut shadow_token, CoordinateSpace::L1Physical, [0.001, 0.0, 0.0]);
+        }
+
+        assert_eq!(fixed_token.get_coordinates(CoordinateSpace::L1Physical)[0], 0.0);
+        let precise = shadow.position(&shadow_token, CoordinateSpace::L1Physical)[0];
+        assert!((precise - 1.0).abs() < 1e-9);
+
+        shadow.flush([&mut shadow_token]);
+        let quantized = shadow_token.get_coordinates(CoordinateSpace::L1Physical)[0];
+        assert!((quantized - 1.0).abs() <= 0.01);
+        assert!(shadow_token.has_flag(flags::DIRTY));
+        assert!(shadow.pending().is_empty());
+    }
+
+    #[test]
+    fn test_periodic_quantization_per_space() {
+        let config = PrecisionConfig {
+            quantize_every: 4,
+            ..PrecisionConfig::default().with_mode(CoordinateSpace::L4Emotional, PrecisionMode::F32)
+        };
+        assert!(config.validate().is_ok());
+        let mut store = PrecisionStore::new(config);
+        let mut token = Token::new(7);
+
+        let written: Vec<bool> = (0..4)
+            .map(|_| store.apply_delta(&mut token, CoordinateSpace::L4Emotional, [0.05, 0.0, 0.0]))
+            .collect();
+        assert_eq!(written, vec![false, false, false, true]);
+        assert!((token.get_coordinates(CoordinateSpace::L4Emotional)[0] - 0.2).abs() < 1e-3);
+
+        // Fixed spaces bypass the shadow
+        assert!(store.apply_delta(&mut token, CoordinateSpace::L2Sensory, [0.5, 0.0, 0.0]));
+        let stats = store.stats();
+        assert_eq!((stats.tracked_tokens, stats.shadow_updates, stats.fixed_updates), (1, 4, 1));
+
+        store.forget(7);
+        assert_eq!(store.stats().tracked_tokens, 0);
+    }
+}
@@ -9,6 +9,24 @@
 //! - Context propagation via headers
 //! - Integration with existing tracing infrastructure
 //!
+//! Only built with the `otel` feature; without it spans go to the log
+//! subscribers only.
+//!
+//! # Pipeline spans
+//!
+//! | Span | Where |
+//! |------|-------|
+//! | `gateway.inject` | Gateway: screening, normalization, queueing |
+//! | `interaction` | ActionController handling one signal (root of the action trace) |
+//! | `action.arbitrate` | Fast/slow path decision |
+//! | `action.execute` | Executor run with timeout and experience logging |
+//! | `learning.analysis` | IntuitionEngine analysis cycle |
+//! | `learning.proposal` | EvolutionManager validating and applying a proposal |
+//!
+//! Gateway and ActionController run on different tasks, so one interaction
+//! shows up as two traces; both carry the same `correlation_id` attribute
+//! (see `correlation`), which is what to search for in Jaeger/Tempo.
+//!
 //! # Usage
 //!
 //! ```rust
@@ -28,6 +46,87 @@ use opentelemetry_sdk::Resource;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::Registry;
 
+/// Exporter settings for [`init_otel`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct OtelConfig {
+    /// Name of the service (e.g., "neurograph-api")
+    pub service_name: String,
+    /// Jaeger agent endpoint (Tempo accepts the Jaeger protocol as well)
+    pub endpoint: String,
+    /// Fraction of traces exported (0.0-1.0)
+    pub sample_ratio: f64,
+    /// Logging level filter for the formatting layer (e.g., "info")
+    pub log_level: String,
+}
+
+impl Default for OtelConfig {
+    fn default() -> Self {
+        Self {
+            service_name: "neurograph".to_string(),
+            endpoint: "localhost:6831".to_string(),
+            sample_ratio: 0.01, // v0.44.3: 1% sampling
+            log_level: "info".to_string(),
+        }
+    }
+}
+
+impl OtelConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.service_name.is_empty() {
+            return Err("service_name must not be empty".to_string());
+        }
+        if !(0.0..=1.0).contains(&self.sample_ratio) {
+            return Err(format!("sample_ratio must be 0.0-1.0, got {}", self.sample_ratio));
+        }
+        Ok(())
+    }
+}
+
+/// Jaeger pipeline shared by all initializers
+fn install_tracer(
+    service_name: &str,
+    endpoint: &str,
+    sample_ratio: f64,
+) -> Result<opentelemetry_sdk::trace::Tracer, String> {
+    opentelemetry_jaeger::new_agent_pipeline()
+        .with_endpoint(endpoint)
+        .with_service_name(service_name)
+        .with_auto_split_batch(true)
+        .with_max_packet_size(65_000)
+        .with_trace_config(
+            trace::config()
+                .with_sampler(Sampler::TraceIdRatioBased(sample_ratio))
+                .with_id_generator(RandomIdGenerator::default())
+                .with_resource(Resource::new(vec![
+                    KeyValue::new("service.name", service_name.to_string()),
+                    KeyValue::new("service.version", crate::VERSION),
+                ])),
+        )
+        .install_batch(opentelemetry_sdk::runtime::Tokio)
+        .map_err(|e| format!("Failed to install Jaeger tracer: {}", e))
+}
+
+/// Initialize structured logging plus span export from an [`OtelConfig`]
+///
+/// Installs the same layers as [`init_tracing_with_jaeger`], with the
+/// sampling ratio taken from the config.
+pub fn init_otel(config: &OtelConfig) -> Result<(), String> {
+    use tracing_subscriber::fmt;
+    use tracing_subscriber::EnvFilter;
+
+    config.validate()?;
+    let tracer = install_tracer(&config.service_name, &config.endpoint, config.sample_ratio)?;
+
+    let subscriber = Registry::default()
+        .with(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(&config.log_level)))
+        .with(fmt::layer().with_target(true).with_line_number(true).with_thread_ids(false))
+        .with(tracing_opentelemetry::layer().with_tracer(tracer))
+        .with(crate::log_stream::LOG_HUB.layer());
+
+    tracing::subscriber::set_global_default(subscriber)
+        .map_err(|e| format!("Failed to set global subscriber: {}", e))
+}
+
 /// Initialize OpenTelemetry tracer with Jaeger backend
 ///
 /// # Arguments
@@ -43,23 +142,8 @@ use tracing_subscriber::Registry;
 /// init_tracer("neurograph-api", "http://jaeger:14268/api/traces")?;
 /// ```
 pub fn init_tracer(service_name: &str, jaeger_endpoint: &str) -> Result<(), String> {
-    // Create Jaeger exporter
-    let tracer = opentelemetry_jaeger::new_agent_pipeline()
-        .with_endpoint(jaeger_endpoint)
-        .with_service_name(service_name)
-        .with_auto_split_batch(true)
-        .with_max_packet_size(65_000)
-        .with_trace_config(
-            trace::config()
-                .with_sampler(Sampler::TraceIdRatioBased(0.01))  // v0.44.3: 1% sampling
-                .with_id_generator(RandomIdGenerator::default())
-                .with_resource(Resource::new(vec![
-                    KeyValue::new("service.name", service_name.to_string()),
-                    KeyValue::new("service.version", "v0.45.0"),  // Updated version
-                ])),
-        )
-        .install_batch(opentelemetry_sdk::runtime::Tokio)
-        .map_err(|e| format!("Failed to install Jaeger tracer: {}", e))?;
+    // Create Jaeger exporter (v0.44.3: 1% sampling)
+    let tracer = install_tracer(service_name, jaeger_endpoint, 0.01)?;
 
     // Create OpenTelemetry tracing layer
     let telemetry = tracing_opentelemetry::layer().with_tracer(tracer);
@@ -100,23 +184,8 @@ pub fn init_tracing_with_jaeger(
     use tracing_subscriber::fmt;
     use tracing_subscriber::EnvFilter;
 
-    // Create Jaeger exporter
-    let tracer = opentelemetry_jaeger::new_agent_pipeline()
-        .with_endpoint(jaeger_endpoint)
-        .with_service_name(service_name)
-        .with_auto_split_batch(true)
-        .with_max_packet_size(65_000)
-        .with_trace_config(
-            trace::config()
-                .with_sampler(Sampler::TraceIdRatioBased(0.01))  // v0.44.3: 1% sampling
-                .with_id_generator(RandomIdGenerator::default())
-                .with_resource(Resource::new(vec![
-                    KeyValue::new("service.name", service_name.to_string()),
-                    KeyValue::new("service.version", "v0.45.0"),  // Updated version
-                ])),
-        )
-        .install_batch(opentelemetry_sdk::runtime::Tokio)
-        .map_err(|e| format!("Failed to install Jaeger tracer: {}", e))?;
+    // Create Jaeger exporter (v0.44.3: 1% sampling)
+    let tracer = install_tracer(service_name, jaeger_endpoint, 0.01)?;
 
     // Create OpenTelemetry layer
     let telemetry = tracing_opentelemetry::layer().with_tracer(tracer);
@@ -135,7 +204,8 @@ pub fn init_tracing_with_jaeger(
     let subscriber = Registry::default()
         .with(filter_layer)
         .with(fmt_layer)
-        .with(telemetry);
+        .with(telemetry)
+        .with(crate::log_stream::LOG_HUB.layer());
 
     // Set as global default
     tracing::subscriber::set_global_default(subscriber)
@@ -264,6 +334,13 @@ mod tests {
         let _ = result;
     }
 
+    #[test]
+    fn test_otel_config_validation() {
+        assert!(OtelConfig::default().validate().is_ok());
+        let config = OtelConfig { sample_ratio: 1.5, ..Default::default() };
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_trace_context_extraction() {
         let mut headers = axum::http::HeaderMap::new();
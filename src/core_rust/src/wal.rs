@@ -26,6 +26,8 @@
 // - 0x02: ExperienceAdded
 // - 0x03: ConnectionUpdated
 // - 0x04: Snapshot (full state dump)
+// - 0x05: ConnectionRemoved
+// - 0x06: WeightCommit (learner weight vector)
 //
 // ## Recovery Process
 //
@@ -49,6 +51,8 @@ pub enum WalEntryType {
     ExperienceAdded = 0x02,
     ConnectionUpdated = 0x03,
     Snapshot = 0x04,
+    ConnectionRemoved = 0x05,
+    WeightCommit = 0x06,
 }
 
 impl TryFrom<u8> for WalEntryType {
@@ -60,6 +64,8 @@ impl TryFrom<u8> for WalEntryType {
             0x02 => Ok(WalEntryType::ExperienceAdded),
             0x03 => Ok(WalEntryType::ConnectionUpdated),
             0x04 => Ok(WalEntryType::Snapshot),
+            0x05 => Ok(WalEntryType::ConnectionRemoved),
+            0x06 => Ok(WalEntryType::WeightCommit),
             _ => Err(WalError::InvalidEntryType(value)),
         }
     }
@@ -104,6 +110,11 @@ impl WalEntryHeader {
         bytes
     }
 
+    /// Layout version of the payload struct (0 = not recorded)
+    pub fn format_version(&self) -> u8 {
+        self.reserved[0]
+    }
+
     pub fn from_bytes(bytes: &[u8; Self::SIZE]) -> Result<Self, WalError> {
         let timestamp = u64::from_le_bytes(bytes[0..8].try_into().unwrap());
         let entry_type = WalEntryType::try_from(bytes[8])?;
@@ -140,6 +151,19 @@ impl WalEntry {
         }
     }
 
+    /// Create an entry whose payload holds a packed struct of `format_version`
+    pub fn with_format_version(entry_type: WalEntryType, format_version: u8, payload: Vec<u8>) -> Self {
+        let mut header = WalEntryHeader::new(entry_type, payload.len() as u32);
+        header.reserved[0] = format_version;
+        let checksum = Self::calculate_checksum(&header, &payload);
+
+        Self {
+            header,
+            payload,
+            checksum,
+        }
+    }
+
     /// Calculate CRC32 checksum
     fn calculate_checksum(header: &WalEntryHeader, payload: &[u8]) -> u32 {
         let mut hasher = crc32fast::Hasher::new();
@@ -348,6 +372,9 @@ pub enum WalError {
 
     #[error("WAL writer closed")]
     WriterClosed,
+
+    #[error("Migration failed: {0}")]
+    Migration(#[from] crate::migration::MigrationError),
 }
 
 #[cfg(test)]
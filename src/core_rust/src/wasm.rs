@@ -0,0 +1,384 @@
+// NeuroGraph - Высокопроизводительная система пространственных вычислений на основе токенов.
+// Copyright (C) 2024-2025 Chernov Denys
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Browser bindings (wasm-bindgen)
+//!
+//! Exposes the synchronous, filesystem-free core to JavaScript:
+//! - `Token` - 8D state encoding
+//! - `Grid` - spatial index with bulk `Float32Array` state export
+//! - `Graph` - nodes, edges, spreading activation
+//! - `Explorer` - bootstrap map loading + semantic search
+//!
+//! Only these modules are touched from here; everything async/server-side
+//! (Gateway, API, persistence, telemetry) stays out of the browser bundle.
+//!
+//! Structured results are returned as JSON strings (`JSON.parse` on the JS side)
+//! to keep the binding dependency-free beyond wasm-bindgen.
+//!
+//! # Build
+//!
+//! ```text
+//! wasm-pack build --target web -- --features wasm
+//! ```
+//!
+//! # Example
+//!
+//! ```text
+//! import init, { Explorer } from "./pkg/_core.js";
+//!
+//! await init();
+//! const explorer = new Explorer();
+//! explorer.loadMap(await (await fetch("bootstrap_map.json")).text());
+//! const hits = JSON.parse(explorer.semanticSearch("cat", 10));
+//! ```
+
+use wasm_bindgen::prelude::*;
+
+use crate::bootstrap::{BootstrapConfig, BootstrapLibrary};
+use crate::graph::Direction;
+use crate::{CoordinateSpace, Graph, Grid, NodeId, Token};
+
+/// Current Unix time in microseconds from the browser clock
+///
+/// `std::time::SystemTime`/`Instant` panic on wasm32-unknown-unknown.
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn unix_time_us() -> u64 {
+    (js_sys::Date::now() * 1000.0) as u64
+}
+
+fn coordinate_space(space: u8) -> Result<CoordinateSpace, JsError> {
+    Ok(match space {
+        0 => CoordinateSpace::L1Physical,
+        1 => CoordinateSpace::L2Sensory,
+        2 => CoordinateSpace::L3Motor,
+        3 => CoordinateSpace::L4Emotional,
+        4 => CoordinateSpace::L5Cognitive,
+        5 => CoordinateSpace::L6Social,
+        6 => CoordinateSpace::L7Temporal,
+        7 => CoordinateSpace::L8Abstract,
+        _ => return Err(JsError::new("Invalid space index (0-7)")),
+    })
+}
+
+fn state_from_slice(state: &[f32]) -> Result<[f32; 8], JsError> {
+    state
+        .try_into()
+        .map_err(|_| JsError::new(&format!("State must have 8 values, got {}", state.len())))
+}
+
+fn to_json(value: &serde_json::Value) -> String {
+    value.to_string()
+}
+
+// ============================================================================
+// Token
+// ============================================================================
+
+/// 8D token
+#[wasm_bindgen(js_name = Token)]
+pub struct WasmToken {
+    inner: Token,
+}
+
+#[wasm_bindgen(js_class = Token)]
+impl WasmToken {
+    /// Create a token with zeroed coordinates
+    #[wasm_bindgen(constructor)]
+    pub fn new(id: u32) -> WasmToken {
+        WasmToken { inner: Token::new(id) }
+    }
+
+    /// Create a token from an 8D state (one value per space)
+    #[wasm_bindgen(js_name = fromState)]
+    pub fn from_state(id: u32, state: &[f32]) -> Result<WasmToken, JsError> {
+        let state = state_from_slice(state)?;
+        Ok(WasmToken { inner: Token::from_state_f32(id, &state) })
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn id(&self) -> u32 {
+        self.inner.id
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn weight(&self) -> f32 {
+        self.inner.weight
+    }
+
+    /// 8D state as `Float32Array`
+    pub fn state(&self) -> Vec<f32> {
+        self.inner.to_state_f32().to_vec()
+    }
+
+    /// Coordinates `[x, y, z]` in one space (0-7)
+    pub fn coordinates(&self, space: u8) -> Result<Vec<f32>, JsError> {
+        Ok(self.inner.get_coordinates(coordinate_space(space)?).to_vec())
+    }
+
+    /// Set coordinates in one space (0-7)
+    #[wasm_bindgen(js_name = setCoordinates)]
+    pub fn set_coordinates(&mut self, space: u8, x: f32, y: f32, z: f32) -> Result<(), JsError> {
+        self.inner.set_coordinates(coordinate_space(space)?, x, y, z);
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Grid
+// ============================================================================
+
+/// Spatial index over tokens
+#[wasm_bindgen(js_name = Grid)]
+pub struct WasmGrid {
+    inner: Grid,
+}
+
+#[wasm_bindgen(js_class = Grid)]
+impl WasmGrid {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> WasmGrid {
+        WasmGrid { inner: Grid::new() }
+    }
+
+    pub fn add(&mut self, token: &WasmToken) -> Result<(), JsError> {
+        self.inner.add(token.inner).map_err(|e| JsError::new(&e))
+    }
+
+    pub fn remove(&mut self, token_id: u32) -> bool {
+        self.inner.remove(token_id).is_some()
+    }
+
+    pub fn get(&self, token_id: u32) -> Option<WasmToken> {
+        self.inner.get(token_id).map(|t| WasmToken { inner: *t })
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn length(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// Neighbors within radius in one space, as JSON `[[id, distance], ...]`
+    #[wasm_bindgen(js_name = findNeighbors)]
+    pub fn find_neighbors(
+        &self,
+        token_id: u32,
+        space: u8,
+        radius: f32,
+        max_results: usize,
+    ) -> Result<String, JsError> {
+        let space = coordinate_space(space)?;
+        let neighbors = self.inner.find_neighbors(token_id, space, radius, max_results);
+        Ok(to_json(&serde_json::json!(neighbors)))
+    }
+
+    /// Token IDs as `Uint32Array`, same order as `states()` rows
+    pub fn ids(&self) -> Vec<u32> {
+        self.inner.tokens().map(|t| t.id).collect()
+    }
+
+    /// All token states as a flat (N * 8) `Float32Array`
+    pub fn states(&self) -> Vec<f32> {
+        self.inner.tokens().flat_map(|t| t.to_state_f32()).collect()
+    }
+}
+
+impl Default for WasmGrid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// Graph
+// ============================================================================
+
+/// Node/edge topology with spreading activation
+#[wasm_bindgen(js_name = Graph)]
+pub struct WasmGraph {
+    inner: Graph,
+}
+
+#[wasm_bindgen(js_class = Graph)]
+impl WasmGraph {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> WasmGraph {
+        WasmGraph { inner: Graph::new() }
+    }
+
+    #[wasm_bindgen(js_name = addNode)]
+    pub fn add_node(&mut self, node_id: NodeId) -> bool {
+        self.inner.add_node(node_id)
+    }
+
+    /// Add an edge, returning its ID
+    #[wasm_bindgen(js_name = addEdge)]
+    pub fn add_edge(
+        &mut self,
+        from_id: NodeId,
+        to_id: NodeId,
+        edge_type: u8,
+        weight: f32,
+        bidirectional: bool,
+    ) -> Result<u64, JsError> {
+        let edge_id = Graph::compute_edge_id(from_id, to_id, edge_type);
+        self.inner
+            .add_edge(edge_id, from_id, to_id, edge_type, weight, bidirectional)
+            .map_err(|e| JsError::new(&e))?;
+        Ok(edge_id)
+    }
+
+    #[wasm_bindgen(getter, js_name = nodeCount)]
+    pub fn node_count(&self) -> usize {
+        self.inner.node_count()
+    }
+
+    #[wasm_bindgen(getter, js_name = edgeCount)]
+    pub fn edge_count(&self) -> usize {
+        self.inner.edge_count()
+    }
+
+    /// Neighbor node IDs (both directions) as `Uint32Array`
+    pub fn neighbors(&self, node_id: NodeId) -> Vec<NodeId> {
+        self.inner
+            .get_neighbors(node_id, Direction::Both)
+            .into_iter()
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    /// Spreading activation, as JSON `[{node_id, energy, depth}, ...]`
+    #[wasm_bindgen(js_name = spreadingActivation)]
+    pub fn spreading_activation(&mut self, source_id: NodeId, energy: f32) -> String {
+        let result = self.inner.spreading_activation(source_id, energy, None);
+        let nodes: Vec<_> = result
+            .activated_nodes
+            .iter()
+            .map(|n| serde_json::json!({
+                "node_id": n.node_id,
+                "energy": n.energy,
+                "depth": n.depth,
+            }))
+            .collect();
+        to_json(&serde_json::json!(nodes))
+    }
+}
+
+impl Default for WasmGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// Explorer (bootstrap map + semantic search)
+// ============================================================================
+
+/// Semantic graph explorer over a pre-computed bootstrap map
+///
+/// The map is the `bootstrap_map.json` produced by `save_bootstrap_map`;
+/// embeddings and PCA stay on the server.
+#[wasm_bindgen(js_name = Explorer)]
+pub struct WasmExplorer {
+    inner: BootstrapLibrary,
+}
+
+#[wasm_bindgen(js_class = Explorer)]
+impl WasmExplorer {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> WasmExplorer {
+        WasmExplorer { inner: BootstrapLibrary::new(BootstrapConfig::default()) }
+    }
+
+    /// Load a bootstrap map (JSON text), returning the number of concepts
+    #[wasm_bindgen(js_name = loadMap)]
+    pub fn load_map(&mut self, json: &str) -> Result<usize, JsError> {
+        let (concepts, _edges) = self
+            .inner
+            .load_bootstrap_map_str(json)
+            .map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(concepts)
+    }
+
+    #[wasm_bindgen(getter, js_name = conceptCount)]
+    pub fn concept_count(&self) -> usize {
+        self.inner.concept_count()
+    }
+
+    #[wasm_bindgen(getter, js_name = edgeCount)]
+    pub fn edge_count(&self) -> usize {
+        self.inner.graph().edge_count()
+    }
+
+    /// Semantic search, as JSON `[[word, score], ...]`
+    #[wasm_bindgen(js_name = semanticSearch)]
+    pub fn semantic_search(&mut self, query: &str, max_results: usize) -> Result<String, JsError> {
+        let results = self
+            .inner
+            .semantic_search(query, max_results, None)
+            .map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(to_json(&serde_json::json!(results)))
+    }
+
+    /// Concept ID, or `undefined` for unknown words
+    #[wasm_bindgen(js_name = conceptId)]
+    pub fn concept_id(&self, word: &str) -> Option<NodeId> {
+        self.inner.get_concept(word).map(|c| c.id)
+    }
+
+    /// 3D map coordinates of a concept
+    pub fn coords(&self, word: &str) -> Option<Vec<f32>> {
+        self.inner.get_concept(word).map(|c| c.coords.to_vec())
+    }
+
+    /// Words and edge weights adjacent to a concept, as JSON `[[word, weight], ...]`
+    pub fn neighbors(&self, word: &str) -> Result<String, JsError> {
+        let concept = self
+            .inner
+            .get_concept(word)
+            .ok_or_else(|| JsError::new(&format!("Unknown word: '{}'", word)))?;
+
+        let graph = self.inner.graph();
+        let mut neighbors: Vec<(String, f32)> = graph
+            .get_neighbors(concept.id, Direction::Both)
+            .into_iter()
+            .filter_map(|(node_id, edge_id)| {
+                let weight = graph.get_edge(edge_id)?.weight;
+                self.inner
+                    .concepts_iter()
+                    .find(|(_, c)| c.id == node_id)
+                    .map(|(w, _)| (w.clone(), weight))
+            })
+            .collect();
+        neighbors.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        Ok(to_json(&serde_json::json!(neighbors)))
+    }
+
+    /// Concept IDs as `Uint32Array`, same order as `states()` rows
+    pub fn ids(&self) -> Vec<u32> {
+        self.inner.grid().tokens().map(|t| t.id).collect()
+    }
+
+    /// All concept states as a flat (N * 8) `Float32Array` for rendering
+    pub fn states(&self) -> Vec<f32> {
+        self.inner.grid().tokens().flat_map(|t| t.to_state_f32()).collect()
+    }
+}
+
+impl Default for WasmExplorer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
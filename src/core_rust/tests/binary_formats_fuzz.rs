@@ -0,0 +1,226 @@
+// SPDX-License-Identifier: AGPL-3.0-only
+// Copyright (C) 2024-2025 Chernov Denys
+
+//! Property-based fuzz suite for the hand-packed binary formats
+//!
+//! For Token, ConnectionV3, ExperienceEvent, ExperienceToken, CDNA and ADNA:
+//!
+//! - random valid values round-trip: `validate_bytes(to_bytes(x))` succeeds
+//!   and re-serializes to the same bytes
+//! - truncated and over-long input is rejected
+//! - random garbage never panics, and anything accepted round-trips
+//!
+//! Inputs come from a seeded RNG so failures are reproducible. Set
+//! `NEUROGRAPH_FUZZ_ITERS` for longer runs and `NEUROGRAPH_FUZZ_SEED` to
+//! explore other inputs:
+//!
+//! ```bash
+//! NEUROGRAPH_FUZZ_ITERS=200000 cargo test --release --test binary_formats_fuzz
+//! ```
+
+use _core::adna::{self, PolicyType, ADNA};
+use _core::archive::experience_token::{self, ExperienceToken};
+use _core::cdna::{self, CDNA};
+use _core::connection_v3::{self, ConnectionV3};
+use _core::experience_stream::{self, ExperienceEvent};
+use _core::token::{self, Token};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+fn env_u64(name: &str, default: u64) -> u64 {
+    std::env::var(name).ok().and_then(|v| v.parse().ok()).unwrap_or(default)
+}
+
+fn iterations() -> u64 {
+    env_u64("NEUROGRAPH_FUZZ_ITERS", 2_000)
+}
+
+fn rng(salt: u64) -> StdRng {
+    StdRng::seed_from_u64(env_u64("NEUROGRAPH_FUZZ_SEED", 0x4E47_5A5A) ^ salt)
+}
+
+fn random_bytes<const N: usize>(rng: &mut StdRng) -> [u8; N] {
+    let mut bytes = [0u8; N];
+    rng.fill(&mut bytes[..]);
+    bytes
+}
+
+fn finite(rng: &mut StdRng) -> f32 {
+    rng.gen_range(-1000.0..1000.0)
+}
+
+/// Run the round-trip, length and garbage properties for one format
+fn check_format<T>(
+    name: &str,
+    size: usize,
+    salt: u64,
+    mut valid: impl FnMut(&mut StdRng) -> Vec<u8>,
+    validate: impl Fn(&[u8]) -> Result<T, String>,
+    to_bytes: impl Fn(&T) -> Vec<u8>,
+) {
+    let mut rng = rng(salt);
+
+    for i in 0..iterations() {
+        // Round trip
+        let bytes = valid(&mut rng);
+        assert_eq!(bytes.len(), size, "{}: generator produced wrong size", name);
+        let decoded = validate(&bytes)
+            .unwrap_or_else(|e| panic!("{} #{}: valid value rejected: {}", name, i, e));
+        assert_eq!(to_bytes(&decoded), bytes, "{} #{}: round trip changed bytes", name, i);
+
+        // Wrong lengths
+        let cut = rng.gen_range(0..size);
+        assert!(validate(&bytes[..cut]).is_err(), "{} #{}: accepted {} bytes", name, i, cut);
+        let mut longer = bytes.clone();
+        longer.push(rng.gen());
+        assert!(validate(&longer).is_err(), "{} #{}: accepted {} bytes", name, i, size + 1);
+
+        // Garbage: must not panic; whatever is accepted must round-trip
+        let mut garbage = bytes;
+        if rng.gen_bool(0.5) {
+            rng.fill(&mut garbage[..]);
+        } else {
+            let flips = rng.gen_range(1..8);
+            for _ in 0..flips {
+                let bit = rng.gen_range(0..size * 8);
+                garbage[bit / 8] ^= 1 << (bit % 8);
+            }
+        }
+        if let Ok(decoded) = validate(&garbage) {
+            assert_eq!(to_bytes(&decoded), garbage, "{} #{}: garbage did not round-trip", name, i);
+        }
+    }
+}
+
+#[test]
+fn fuzz_token() {
+    check_format(
+        "Token",
+        64,
+        1,
+        |rng| {
+            let mut token = Token::from_bytes(&random_bytes(rng));
+            token.id = rng.gen_range(1..=u32::MAX);
+            token.weight = rng.gen_range(0.0..=100.0);
+            token.timestamp = rng.gen_range(0..=Token::current_timestamp());
+            token.to_bytes().to_vec()
+        },
+        token::validate_bytes,
+        |t| t.to_bytes().to_vec(),
+    );
+}
+
+#[test]
+fn fuzz_connection_v3() {
+    check_format(
+        "ConnectionV3",
+        64,
+        2,
+        |rng| {
+            let mut connection = ConnectionV3::from_bytes(&random_bytes(rng));
+            connection.mutability = rng.gen_range(0..=2);
+            connection.connection_type = rng.gen_range(0..=0xAF);
+            connection.pull_strength = finite(rng);
+            connection.preferred_distance = finite(rng);
+            connection.to_bytes().to_vec()
+        },
+        connection_v3::validate_bytes,
+        |c| c.to_bytes().to_vec(),
+    );
+}
+
+#[test]
+fn fuzz_experience_event() {
+    check_format(
+        "ExperienceEvent",
+        128,
+        3,
+        |rng| {
+            let mut event = ExperienceEvent::from_bytes(&random_bytes(rng));
+            event.state = std::array::from_fn(|_| finite(rng));
+            event.action = std::array::from_fn(|_| finite(rng));
+            event.reward_homeostasis = finite(rng);
+            event.reward_curiosity = finite(rng);
+            event.reward_efficiency = finite(rng);
+            event.reward_goal = finite(rng);
+            event.to_bytes().to_vec()
+        },
+        experience_stream::validate_bytes,
+        |e| e.to_bytes().to_vec(),
+    );
+}
+
+#[test]
+fn fuzz_experience_token() {
+    check_format(
+        "ExperienceToken",
+        128,
+        4,
+        |rng| {
+            let mut token = ExperienceToken::from_bytes(&random_bytes(rng));
+            token.token_type = experience_token::EXPERIENCE_TOKEN_MAGIC;
+            token.state = std::array::from_fn(|_| finite(rng));
+            token.action = std::array::from_fn(|_| finite(rng));
+            token.reward = finite(rng);
+            token.next_state = std::array::from_fn(|_| finite(rng));
+            token.to_bytes().to_vec()
+        },
+        experience_token::validate_bytes,
+        |t| t.to_bytes().to_vec(),
+    );
+}
+
+#[test]
+fn fuzz_cdna() {
+    check_format(
+        "CDNA",
+        384,
+        5,
+        |rng| {
+            let mut cdna = CDNA::new();
+            cdna.dimension_scales = std::array::from_fn(|_| rng.gen_range(0.1..10.0));
+            cdna.revision = rng.gen();
+            cdna.touch();
+            cdna.to_bytes().to_vec()
+        },
+        cdna::validate_bytes,
+        |c| c.to_bytes().to_vec(),
+    );
+
+    // The checksum catches any single-bit corruption
+    let mut rng = rng(6);
+    let bytes = CDNA::new().to_bytes();
+    for _ in 0..iterations() {
+        let mut corrupt = bytes;
+        let bit = rng.gen_range(0..384 * 8);
+        corrupt[bit / 8] ^= 1 << (bit % 8);
+        assert!(cdna::validate_bytes(&corrupt).is_err(), "bit {} flip accepted", bit);
+    }
+}
+
+#[test]
+fn fuzz_adna() {
+    check_format(
+        "ADNA",
+        256,
+        7,
+        |rng| {
+            let mut adna = ADNA::from_bytes(&random_bytes(rng));
+            let fresh = ADNA::new(PolicyType::from(rng.gen_range(0..=4u16)));
+            adna.header.magic = fresh.header.magic;
+            adna.header.version_major = fresh.header.version_major;
+            adna.header.policy_type = fresh.header.policy_type;
+            adna.evolution.fitness_score = rng.gen();
+            adna.evolution.confidence = rng.gen();
+            adna.evolution.exploration_rate = rng.gen();
+            adna.evolution.learning_rate = rng.gen();
+            adna.evolution.success_rate = rng.gen();
+            adna.evolution.shadow_lift = finite(rng);
+            adna.state_mapping.state_normalization = std::array::from_fn(|_| finite(rng));
+            adna.state_mapping.action_bounds = std::array::from_fn(|_| finite(rng));
+            adna.to_bytes().to_vec()
+        },
+        adna::validate_bytes,
+        |a| a.to_bytes().to_vec(),
+    );
+}